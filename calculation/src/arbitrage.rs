@@ -0,0 +1,343 @@
+//! 프리미엄 맵 무차익 검증
+//!
+//! `update_premium_map` 결과를 저장하기 전에 풋-콜 패리티, 행사가 단조성,
+//! 캘린더 스프레드 조건을 확인합니다. 단조성/캘린더 위반은 경계값으로
+//! 보정(clamp)하고, 패리티를 벗어난 행사가는 API에서 제외(withhold)합니다.
+
+use crate::models::OptionPremium;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// 위반 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// C - P ≠ S - K·e^(-rT)
+    PutCallParity,
+    /// 행사가가 높을수록 콜은 싸지고 풋은 비싸져야 함
+    StrikeMonotonicity,
+    /// 같은 행사가에서 만기가 길수록 비싸야 함
+    CalendarSpread,
+}
+
+/// 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationAction {
+    Clamped,
+    Withheld,
+}
+
+/// 발견된 위반
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArbitrageViolation {
+    pub expiry: String,
+    pub strike: f64,
+    pub is_call: bool,
+    pub kind: ViolationKind,
+    pub action: ViolationAction,
+    /// 원래 프리미엄
+    pub premium: f64,
+    /// 조건상 경계값
+    pub bound: f64,
+}
+
+/// 검증 대상 만기 하나
+#[derive(Debug, Clone)]
+pub struct ExpirySlice {
+    pub expiry: String,
+    pub time_to_expiry: f64,
+    pub premiums: Vec<OptionPremium>,
+}
+
+/// 검증 결과
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub checked_quotes: usize,
+    pub violations: Vec<ArbitrageViolation>,
+}
+
+impl ValidationReport {
+    pub fn count(&self, kind: ViolationKind) -> usize {
+        self.violations.iter().filter(|v| v.kind == kind).count()
+    }
+
+    pub fn withheld(&self) -> usize {
+        self.violations
+            .iter()
+            .filter(|v| v.action == ViolationAction::Withheld)
+            .count()
+    }
+}
+
+/// 누적 위반 지표
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArbitrageMetrics {
+    pub validations: u64,
+    pub put_call_parity: u64,
+    pub strike_monotonicity: u64,
+    pub calendar_spread: u64,
+    pub withheld: u64,
+    /// 마지막 검증 결과
+    pub last_report: ValidationReport,
+}
+
+impl ArbitrageMetrics {
+    pub fn record(&mut self, report: ValidationReport) {
+        self.validations += 1;
+        self.put_call_parity += report.count(ViolationKind::PutCallParity) as u64;
+        self.strike_monotonicity += report.count(ViolationKind::StrikeMonotonicity) as u64;
+        self.calendar_spread += report.count(ViolationKind::CalendarSpread) as u64;
+        self.withheld += report.withheld() as u64;
+        self.last_report = report;
+    }
+}
+
+/// 무차익 검증기
+#[derive(Debug, Clone)]
+pub struct ArbitrageValidator {
+    /// 패리티 허용 오차 (현물 대비 비율)
+    pub parity_tolerance: f64,
+    /// 단조성/캘린더 비교 시 허용 오차 (USD)
+    pub price_epsilon: f64,
+}
+
+impl Default for ArbitrageValidator {
+    fn default() -> Self {
+        Self {
+            parity_tolerance: 0.01,
+            price_epsilon: 1e-6,
+        }
+    }
+}
+
+impl ArbitrageValidator {
+    /// 위반을 보정/제외하고 결과 보고 (slices는 만기 순서와 무관)
+    pub fn validate(&self, spot: f64, rate: f64, slices: &mut [ExpirySlice]) -> ValidationReport {
+        let mut report = ValidationReport {
+            checked_quotes: slices.iter().map(|s| s.premiums.len() * 2).sum(),
+            violations: Vec::new(),
+        };
+
+        for slice in slices.iter_mut() {
+            slice
+                .premiums
+                .sort_by(|a, b| a.strike.total_cmp(&b.strike));
+            self.clamp_monotonicity(slice, &mut report);
+        }
+
+        slices.sort_by(|a, b| a.time_to_expiry.total_cmp(&b.time_to_expiry));
+        for i in 1..slices.len() {
+            let (shorter, longer) = slices.split_at_mut(i);
+            self.clamp_calendar(&shorter[i - 1], &mut longer[0], rate, &mut report);
+        }
+
+        for slice in slices.iter_mut() {
+            self.withhold_parity(slice, spot, rate, &mut report);
+        }
+
+        for violation in &report.violations {
+            warn!(
+                "⚠️ Arbitrage {:?} at {} K={} {}: premium {:.2}, bound {:.2} → {:?}",
+                violation.kind,
+                violation.expiry,
+                violation.strike,
+                if violation.is_call { "call" } else { "put" },
+                violation.premium,
+                violation.bound,
+                violation.action
+            );
+        }
+        report
+    }
+
+    fn clamp_monotonicity(&self, slice: &mut ExpirySlice, report: &mut ValidationReport) {
+        for i in 1..slice.premiums.len() {
+            let (lower, higher) = (slice.premiums[i - 1].clone(), &mut slice.premiums[i]);
+            if higher.call_premium > lower.call_premium + self.price_epsilon {
+                report.violations.push(violation(
+                    higher,
+                    true,
+                    ViolationKind::StrikeMonotonicity,
+                    ViolationAction::Clamped,
+                    lower.call_premium,
+                ));
+                higher.call_premium = lower.call_premium;
+            }
+            if higher.put_premium + self.price_epsilon < lower.put_premium {
+                report.violations.push(violation(
+                    higher,
+                    false,
+                    ViolationKind::StrikeMonotonicity,
+                    ViolationAction::Clamped,
+                    lower.put_premium,
+                ));
+                higher.put_premium = lower.put_premium;
+            }
+        }
+    }
+
+    /// 콜: C(T2) ≥ C(T1), 풋: P(T2) ≥ P(T1) - K·(e^(-rT1) - e^(-rT2))
+    fn clamp_calendar(
+        &self,
+        shorter: &ExpirySlice,
+        longer: &mut ExpirySlice,
+        rate: f64,
+        report: &mut ValidationReport,
+    ) {
+        let discount_gap = (-rate * shorter.time_to_expiry).exp() - (-rate * longer.time_to_expiry).exp();
+        for premium in longer.premiums.iter_mut() {
+            let Some(near) = shorter
+                .premiums
+                .iter()
+                .find(|p| (p.strike - premium.strike).abs() < f64::EPSILON)
+            else {
+                continue;
+            };
+
+            if premium.call_premium + self.price_epsilon < near.call_premium {
+                report.violations.push(violation(
+                    premium,
+                    true,
+                    ViolationKind::CalendarSpread,
+                    ViolationAction::Clamped,
+                    near.call_premium,
+                ));
+                premium.call_premium = near.call_premium;
+            }
+            let put_floor = near.put_premium - premium.strike * discount_gap;
+            if premium.put_premium + self.price_epsilon < put_floor {
+                report.violations.push(violation(
+                    premium,
+                    false,
+                    ViolationKind::CalendarSpread,
+                    ViolationAction::Clamped,
+                    put_floor,
+                ));
+                premium.put_premium = put_floor;
+            }
+        }
+    }
+
+    fn withhold_parity(
+        &self,
+        slice: &mut ExpirySlice,
+        spot: f64,
+        rate: f64,
+        report: &mut ValidationReport,
+    ) {
+        let tolerance = self.parity_tolerance * spot;
+        let time_to_expiry = slice.time_to_expiry;
+        slice.premiums.retain(|premium| {
+            let forward_diff = spot - premium.strike * (-rate * time_to_expiry).exp();
+            let diff = premium.call_premium - premium.put_premium;
+            if (diff - forward_diff).abs() <= tolerance {
+                return true;
+            }
+            report.violations.push(violation(
+                premium,
+                true,
+                ViolationKind::PutCallParity,
+                ViolationAction::Withheld,
+                premium.put_premium + forward_diff,
+            ));
+            false
+        });
+    }
+}
+
+fn violation(
+    premium: &OptionPremium,
+    is_call: bool,
+    kind: ViolationKind,
+    action: ViolationAction,
+    bound: f64,
+) -> ArbitrageViolation {
+    ArbitrageViolation {
+        expiry: premium.expiry.clone(),
+        strike: premium.strike,
+        is_call,
+        kind,
+        action,
+        premium: if is_call {
+            premium.call_premium
+        } else {
+            premium.put_premium
+        },
+        bound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OptionParameters;
+    use crate::pricing::{BlackScholesPricing, PricingEngine};
+
+    const SPOT: f64 = 70_000.0;
+    const RATE: f64 = 0.05;
+
+    fn slice(expiry: &str, time_to_expiry: f64, vol: f64) -> ExpirySlice {
+        let engine = BlackScholesPricing::new();
+        let premiums = [60_000.0, 70_000.0, 80_000.0]
+            .iter()
+            .map(|&strike| {
+                let params = |is_call| OptionParameters {
+                    spot: SPOT,
+                    strike,
+                    time_to_expiry,
+                    volatility: vol,
+                    risk_free_rate: RATE,
+                    is_call,
+                };
+                OptionPremium {
+                    strike,
+                    expiry: expiry.to_string(),
+                    call_premium: engine.calculate_option_price(&params(true)),
+                    put_premium: engine.calculate_option_price(&params(false)),
+                    implied_volatility: vol,
+                }
+            })
+            .collect();
+        ExpirySlice {
+            expiry: expiry.to_string(),
+            time_to_expiry,
+            premiums,
+        }
+    }
+
+    #[test]
+    fn test_black_scholes_map_is_arbitrage_free() {
+        let mut slices = vec![slice("far", 0.5, 0.6), slice("near", 0.1, 0.6)];
+        let report = ArbitrageValidator::default().validate(SPOT, RATE, &mut slices);
+        assert!(report.violations.is_empty(), "{:?}", report.violations);
+        assert_eq!(report.checked_quotes, 12);
+        assert_eq!(slices[0].expiry, "near");
+    }
+
+    #[test]
+    fn test_violations_are_clamped_or_withheld() {
+        let mut near = slice("near", 0.1, 0.6);
+        let mut far = slice("far", 0.5, 0.6);
+        // 행사가 80k 콜이 70k 콜보다 비쌈 (단조성)
+        near.premiums[2].call_premium = near.premiums[1].call_premium + 100.0;
+        // 먼 만기 60k 콜이 가까운 만기보다 쌈 (캘린더)
+        far.premiums[0].call_premium = near.premiums[0].call_premium - 500.0;
+        // 먼 만기 70k 풋을 크게 올려 패리티 위반
+        far.premiums[1].put_premium += 2_000.0;
+
+        let mut slices = vec![near, far];
+        let report = ArbitrageValidator::default().validate(SPOT, RATE, &mut slices);
+
+        // 낮춘 먼 만기 60k 콜 때문에 70k 콜도 단조성 위반
+        assert_eq!(report.count(ViolationKind::StrikeMonotonicity), 2);
+        assert_eq!(report.count(ViolationKind::CalendarSpread), 1);
+        assert_eq!(report.count(ViolationKind::PutCallParity), 3);
+        assert_eq!(report.withheld(), 3);
+
+        // 보정된 값은 경계에 맞춰지고, 패리티를 깬 행사가는 제외됨
+        let strikes = |s: &ExpirySlice| s.premiums.iter().map(|p| p.strike).collect::<Vec<_>>();
+        assert_eq!(strikes(&slices[0]), vec![60_000.0, 70_000.0]);
+        assert_eq!(strikes(&slices[1]), vec![80_000.0]);
+    }
+}
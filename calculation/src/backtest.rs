@@ -0,0 +1,398 @@
+//! 풀 전략 백테스트
+//!
+//! 과거 가격 시계열을 SimpleContractManager와 ThetaTargetingEngine에 그대로
+//! 재생하여 풀 손익, 최대 낙폭, 이용률, LP APY를 계산합니다. 배포 전에
+//! target theta 파라미터를 오프라인으로 조정하는 용도입니다.
+
+use crate::theta_targeting::ThetaTargetingEngine;
+use btcfi_contracts::block_time::BlockClock;
+use btcfi_contracts::{OptionType, SimpleContractManager};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const SATS_PER_BTC: f64 = 100_000_000.0;
+
+/// 과거 가격 데이터 포인트
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub timestamp: u64, // Unix timestamp (초)
+    pub price: f64,     // USD
+}
+
+/// 옵션 매수 주문
+#[derive(Debug, Clone)]
+pub struct OptionOrder {
+    pub is_call: bool,
+    pub strike: f64, // USD
+    pub quantity_btc: f64,
+    pub days_to_expiry: f64,
+}
+
+/// 옵션 수요 모델
+pub trait DemandModel {
+    /// 해당 시점에 들어오는 매수 주문 목록
+    fn orders(&mut self, step: usize, point: &PricePoint) -> Vec<OptionOrder>;
+}
+
+/// 고정 수요 모델: 매 N 스텝마다 Call/Put을 같은 moneyness로 한 건씩 발생
+#[derive(Debug, Clone)]
+pub struct ConstantDemand {
+    /// 주문 발생 간격 (스텝)
+    pub every_n_steps: usize,
+    /// 행사가 / 현재가 (Call 기준, Put은 역수)
+    pub moneyness: f64,
+    pub quantity_btc: f64,
+    pub days_to_expiry: f64,
+    pub include_calls: bool,
+    pub include_puts: bool,
+}
+
+impl Default for ConstantDemand {
+    fn default() -> Self {
+        Self {
+            every_n_steps: 1,
+            moneyness: 1.05,
+            quantity_btc: 0.1,
+            days_to_expiry: 7.0,
+            include_calls: true,
+            include_puts: true,
+        }
+    }
+}
+
+impl DemandModel for ConstantDemand {
+    fn orders(&mut self, step: usize, point: &PricePoint) -> Vec<OptionOrder> {
+        if self.every_n_steps == 0 || !step.is_multiple_of(self.every_n_steps) {
+            return vec![];
+        }
+
+        let mut orders = Vec::new();
+        if self.include_calls {
+            orders.push(OptionOrder {
+                is_call: true,
+                strike: point.price * self.moneyness,
+                quantity_btc: self.quantity_btc,
+                days_to_expiry: self.days_to_expiry,
+            });
+        }
+        if self.include_puts {
+            orders.push(OptionOrder {
+                is_call: false,
+                strike: point.price / self.moneyness,
+                quantity_btc: self.quantity_btc,
+                days_to_expiry: self.days_to_expiry,
+            });
+        }
+        orders
+    }
+}
+
+/// 백테스트 설정
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    pub initial_liquidity_sats: u64,
+    pub target_theta: f64,
+    pub risk_free_rate: f64,
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            initial_liquidity_sats: 1_000_000_000, // 10 BTC
+            target_theta: -0.02,
+            risk_free_rate: 0.05,
+        }
+    }
+}
+
+/// 백테스트 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub pool_pnl_sats: i64,
+    pub total_premium_sats: u64,
+    pub total_payout_sats: u64,
+    /// 최대 낙폭 (%)
+    pub max_drawdown_pct: f64,
+    /// 평균 이용률 (%)
+    pub avg_utilization_pct: f64,
+    /// 최대 이용률 (%)
+    pub peak_utilization_pct: f64,
+    /// 연환산 LP 수익률 (%)
+    pub lp_apy_pct: f64,
+    pub options_written: u32,
+    pub options_rejected: u32,
+    /// 스텝별 풀 총 유동성 (satoshis)
+    pub equity_curve: Vec<u64>,
+}
+
+/// 가격 시계열 재생기
+pub struct Backtester {
+    config: BacktestConfig,
+    engine: ThetaTargetingEngine,
+    /// 재생 기간의 실제 블록 헤더 (만기 타임스탬프 → 블록 높이)
+    block_clock: BlockClock,
+}
+
+impl Backtester {
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            config,
+            engine: ThetaTargetingEngine::new(),
+            block_clock: BlockClock::new(),
+        }
+    }
+
+    /// 재생 기간의 블록 헤더로 만기 높이 환산 (없으면 첫 가격 시각을 높이 0으로 두고 평균 간격 적용)
+    pub fn with_block_clock(mut self, block_clock: BlockClock) -> Self {
+        self.block_clock = block_clock;
+        self
+    }
+
+    /// 가격 시계열 전체를 재생하고 결과 반환
+    pub fn run(
+        &self,
+        prices: &[PricePoint],
+        demand: &mut dyn DemandModel,
+    ) -> Result<BacktestReport, String> {
+        if prices.is_empty() {
+            return Err("Empty price series".to_string());
+        }
+
+        let mut manager = SimpleContractManager::new();
+        manager
+            .add_liquidity(self.config.initial_liquidity_sats)
+            .map_err(|e| e.to_string())?;
+
+        let mut block_clock = self.block_clock.clone();
+        if block_clock.is_empty() {
+            block_clock.observe(0, prices[0].timestamp);
+        }
+
+        let mut expiries: HashMap<String, u64> = HashMap::new();
+        let mut written = 0u32;
+        let mut rejected = 0u32;
+        let mut equity_curve = Vec::with_capacity(prices.len());
+        let mut utilization_sum = 0.0;
+        let mut peak_utilization: f64 = 0.0;
+
+        for (step, point) in prices.iter().enumerate() {
+            let spot_cents = (point.price * 100.0).round() as u64;
+
+            // 1. 만기 도래 옵션 정산
+            let due: Vec<String> = expiries
+                .iter()
+                .filter(|(_, &expiry)| expiry <= point.timestamp)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for option_id in due {
+                manager
+                    .settle_option(&option_id, spot_cents)
+                    .map_err(|e| e.to_string())?;
+                expiries.remove(&option_id);
+            }
+
+            // 2. 신규 주문 처리
+            for order in demand.orders(step, point) {
+                let premium = self.engine.calculate_premium_with_target_theta(
+                    point.price,
+                    point.price,
+                    point.price,
+                    order.strike,
+                    order.days_to_expiry,
+                    self.config.risk_free_rate,
+                    order.is_call,
+                    self.config.target_theta,
+                    order.quantity_btc,
+                );
+                let premium_sats = match premium {
+                    Ok(result) => (result.premium_btc * SATS_PER_BTC).round() as u64,
+                    Err(_) => {
+                        rejected += 1;
+                        continue;
+                    }
+                };
+
+                let expiry_ts =
+                    point.timestamp + (order.days_to_expiry * 86_400.0).round() as u64;
+                let option_id = format!("BT-{}", written + rejected);
+                let created = manager.create_option(
+                    option_id.clone(),
+                    if order.is_call {
+                        OptionType::Call
+                    } else {
+                        OptionType::Put
+                    },
+                    (order.strike * 100.0).round() as u64,
+                    (order.quantity_btc * SATS_PER_BTC).round() as u64,
+                    premium_sats,
+                    block_clock.height_at(expiry_ts).unwrap_or_default(),
+                    "backtest".to_string(),
+                );
+
+                match created {
+                    Ok(()) => {
+                        expiries.insert(option_id, expiry_ts);
+                        written += 1;
+                    }
+                    Err(_) => rejected += 1,
+                }
+            }
+
+            let utilization = manager.pool_state.utilization_rate();
+            utilization_sum += utilization;
+            peak_utilization = peak_utilization.max(utilization);
+            equity_curve.push(manager.pool_state.total_liquidity);
+        }
+
+        // 남은 옵션은 마지막 가격으로 정산
+        let last = prices[prices.len() - 1];
+        let last_cents = (last.price * 100.0).round() as u64;
+        for option_id in expiries.keys() {
+            manager
+                .settle_option(option_id, last_cents)
+                .map_err(|e| e.to_string())?;
+        }
+        if let Some(final_equity) = equity_curve.last_mut() {
+            *final_equity = manager.pool_state.total_liquidity;
+        }
+
+        let initial = self.config.initial_liquidity_sats;
+        let final_liquidity = manager.pool_state.total_liquidity;
+        let period_secs = last.timestamp.saturating_sub(prices[0].timestamp);
+
+        Ok(BacktestReport {
+            pool_pnl_sats: final_liquidity as i64 - initial as i64,
+            total_premium_sats: manager.pool_state.total_premium_collected,
+            total_payout_sats: manager.pool_state.total_payout,
+            max_drawdown_pct: max_drawdown_pct(&equity_curve),
+            avg_utilization_pct: utilization_sum / prices.len() as f64,
+            peak_utilization_pct: peak_utilization,
+            lp_apy_pct: annualized_return_pct(initial, final_liquidity, period_secs),
+            options_written: written,
+            options_rejected: rejected,
+            equity_curve,
+        })
+    }
+
+    /// target theta 후보별로 백테스트를 반복 실행
+    pub fn sweep_target_theta<D, F>(
+        config: &BacktestConfig,
+        prices: &[PricePoint],
+        target_thetas: &[f64],
+        mut make_demand: F,
+    ) -> Vec<(f64, Result<BacktestReport, String>)>
+    where
+        D: DemandModel,
+        F: FnMut() -> D,
+    {
+        target_thetas
+            .iter()
+            .map(|&target_theta| {
+                let backtester = Backtester::new(BacktestConfig {
+                    target_theta,
+                    ..config.clone()
+                });
+                let mut demand = make_demand();
+                (target_theta, backtester.run(prices, &mut demand))
+            })
+            .collect()
+    }
+}
+
+/// 고점 대비 최대 낙폭 (%)
+fn max_drawdown_pct(equity_curve: &[u64]) -> f64 {
+    let mut peak = 0u64;
+    let mut max_drawdown: f64 = 0.0;
+
+    for &equity in equity_curve {
+        peak = peak.max(equity);
+        if peak > 0 {
+            let drawdown = (peak - equity) as f64 / peak as f64 * 100.0;
+            max_drawdown = max_drawdown.max(drawdown);
+        }
+    }
+
+    max_drawdown
+}
+
+/// 기간 수익률의 연환산 (%)
+fn annualized_return_pct(initial: u64, final_value: u64, period_secs: u64) -> f64 {
+    if initial == 0 || period_secs == 0 {
+        return 0.0;
+    }
+
+    let growth = final_value as f64 / initial as f64;
+    let years = period_secs as f64 / (365.0 * 86_400.0);
+    (growth.powf(1.0 / years) - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily_series(prices: &[f64]) -> Vec<PricePoint> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| PricePoint {
+                timestamp: 1_700_000_000 + i as u64 * 86_400,
+                price,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_market_earns_premium() {
+        let prices = daily_series(&[70_000.0; 30]);
+        let mut demand = ConstantDemand::default();
+
+        let report = Backtester::new(BacktestConfig::default())
+            .run(&prices, &mut demand)
+            .unwrap();
+
+        assert!(report.options_written > 0);
+        assert_eq!(report.total_payout_sats, 0);
+        assert!(report.pool_pnl_sats > 0);
+        assert_eq!(report.max_drawdown_pct, 0.0);
+        assert!(report.avg_utilization_pct > 0.0);
+    }
+
+    #[test]
+    fn test_crash_triggers_put_payouts() {
+        let mut series = vec![70_000.0; 5];
+        series.extend(vec![50_000.0; 10]);
+        let prices = daily_series(&series);
+        let mut demand = ConstantDemand {
+            every_n_steps: 5,
+            include_calls: false,
+            ..ConstantDemand::default()
+        };
+
+        let report = Backtester::new(BacktestConfig::default())
+            .run(&prices, &mut demand)
+            .unwrap();
+
+        assert!(report.total_payout_sats > 0);
+        assert!(report.max_drawdown_pct > 0.0);
+    }
+
+    #[test]
+    fn test_max_drawdown() {
+        assert_eq!(max_drawdown_pct(&[100, 120, 90, 130]), 25.0);
+        assert_eq!(max_drawdown_pct(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_sweep_target_theta() {
+        let prices = daily_series(&[70_000.0; 10]);
+        let results = Backtester::sweep_target_theta(
+            &BacktestConfig::default(),
+            &prices,
+            &[-0.01, -0.02],
+            ConstantDemand::default,
+        );
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, report)| report.is_ok()));
+    }
+}
@@ -1,11 +1,34 @@
+pub mod arbitrage;
+pub mod backtest;
+pub mod market_data;
 pub mod models;
+pub mod premium_snapshot;
 pub mod pricing;
+pub mod products;
+pub mod regime;
 pub mod repositories;
+pub mod rfq;
+pub mod risk;
 pub mod services;
+pub mod skew;
 pub mod theta_targeting;
+pub mod vol_feed;
 
+pub use arbitrage::{ArbitrageMetrics, ArbitrageValidator, ArbitrageViolation, ValidationReport, ViolationKind};
+pub use backtest::{Backtester, BacktestConfig, BacktestReport, ConstantDemand, DemandModel, PricePoint};
+pub use market_data::{Candle, CandleInterval, CandleSeries, MarketDataStore, TradeRecord};
 pub use models::*;
+pub use premium_snapshot::{PremiumDelta, PremiumSnapshot, PremiumSnapshots};
 pub use pricing::{BlackScholesPricing, PricingEngine};
+pub use products::{ProductQuote, ProductQuoteRequest, ProductRegistry, ProductTemplate, StrikeRule};
+pub use regime::{RegimeConfig, RegimeDetector, RegimeState, VolRegime};
 pub use repositories::*;
+pub use rfq::QuoteService;
+pub use risk::{RiskEngine, StressReport, StressScenario, StressTestService};
 pub use services::*;
-pub use theta_targeting::{ThetaTargetingEngine, PremiumResult, DeltaNeutralManager, OptionPosition};
\ No newline at end of file
+pub use skew::{InventorySkew, SkewedPremium};
+pub use theta_targeting::{
+    ThetaTargetingEngine, PremiumResult, DeltaNeutralManager, OptionPosition, PoolCapacity,
+    UtilizationCurve,
+};
+pub use vol_feed::VolSurfaceFeed;
\ No newline at end of file
@@ -1,35 +1,222 @@
-use axum::{extract::Query, http::StatusCode, response::Json, routing::get, Router};
+use axum::{
+    body::Bytes,
+    extract::Query,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post, put},
+    Router,
+};
 use std::sync::Arc;
 use tokio::net::TcpListener;
-use tracing::info;
+use tracing::{info, warn};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
 
+mod arbitrage;
+mod market_data;
 mod models;
+mod premium_snapshot;
 mod pricing;
+mod products;
+mod regime;
 mod repositories;
+mod rfq;
+mod risk;
 mod services;
+mod skew;
+mod theta_targeting;
+mod vol_feed;
 
-use models::{DeltaInfo, MarketState, OptionPremium, PremiumQuery};
+use arbitrage::{ArbitrageMetrics, ArbitrageValidator};
+use btcfi_contracts::admin_api::OperatorAuth;
+use btcfi_contracts::tracing_context::with_correlation;
+use btcfi_contracts::webhooks::{verify_signature, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use btcfi_contracts::{PriceFeedService, WebhookEvent};
+use market_data::{Candle, CandleQuery, MarketDataStore, TradeRecord};
+use models::{DeltaInfo, MarketState, OptionPremium, PremiumQuery, VolSurface};
+use premium_snapshot::PremiumDelta;
 use pricing::BlackScholesPricing;
-use repositories::{InMemoryMarketRepo, InMemoryPoolRepo, InMemoryPremiumRepo};
+use products::{ProductQuote, ProductQuoteRequest, ProductRegistry, ProductTemplate};
+use repositories::{
+    InMemoryMarketRepo, InMemoryOpenInterestRepo, InMemoryPoolRepo, InMemoryPositionRepo,
+    InMemoryPremiumRepo, InMemoryVolSurfaceRepo, VolSurfaceRepository,
+};
+use oracle_vm_common::crypto::{generate_keypair, SecretKey};
+use oracle_vm_common::{
+    ContractSpec, ErrorClass, ExercisePolicy, Expiry, ExpiryCalendar, FeedStatus, GreeksLimits,
+    OpenInterestCapacity, OpenInterestCaps, OpenInterestEntry,
+    BuyBackQuote, BuyBackRequest, OptionQuote, QuoteRequest, Shutdown, ShutdownSignal,
+};
+use rfq::QuoteService;
+use regime::{RegimeDetector, RegimeState};
+use risk::{RiskEngine, StressReport, StressTestService};
+use theta_targeting::{OptionPosition, UtilizationCurve};
+use skew::InventorySkew;
 use services::{DeltaManagementService, MarketDataService, PremiumCalculationService};
+use vol_feed::VolSurfaceFeed;
+
+/// `/openapi.json` 명세 (핸들러의 `#[utoipa::path]`에서 생성)
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "BTCFi Calculation API"),
+    paths(
+        get_premium_map,
+        get_premium_delta,
+        get_arbitrage_metrics,
+        get_pool_delta,
+        get_current_delta,
+        get_market_state,
+        get_vol_surface,
+        get_stress_report,
+        open_position,
+        request_quote,
+        request_buy_back_quote,
+        get_quote_public_key,
+        get_quote_curve,
+        get_quote_skew,
+        get_exercise_policy,
+        get_greeks_limits,
+        get_open_interest_capacity,
+        sync_open_interest,
+        get_expiries,
+        list_products,
+        get_product,
+        quote_product,
+        get_candles,
+        get_trades,
+        receive_trade_webhook,
+    )
+)]
+struct ApiDoc;
 
 /// 애플리케이션 상태
 struct AppState {
     premium_service: Arc<PremiumCalculationService<BlackScholesPricing>>,
     delta_service: Arc<DeltaManagementService>,
     market_service: Arc<MarketDataService>,
+    stress_service: Arc<StressTestService>,
+    risk_engine: Arc<RiskEngine>,
+    vol_repo: Arc<dyn VolSurfaceRepository>,
+    quote_service: Arc<QuoteService<BlackScholesPricing>>,
+    calendar: ExpiryCalendar,
+    /// 상품 템플릿 (PRODUCT_TEMPLATES)
+    products: ProductRegistry,
+    market_data: Arc<MarketDataStore>,
+    /// 합의 가격 점프 기반 변동성 국면
+    regime: Arc<RegimeDetector>,
+    /// contracts 웹훅 서명 비밀값 (없으면 웹훅 전체 거부)
+    trade_webhook_secret: Option<String>,
+    /// 운영자/contracts 전용 경로 토큰 (OPERATOR_TOKEN_HASH, 없으면 해당 경로 전체 거부)
+    operator_auth: OperatorAuth,
+}
+
+/// 운영자 토큰 확인 (`Authorization: Bearer <token>`)
+fn authorize_operator(state: &AppState, headers: &HeaderMap) -> Result<(), (StatusCode, String)> {
+    if !state.operator_auth.is_configured() {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            "operator API disabled: OPERATOR_TOKEN_HASH not set".to_string(),
+        ));
+    }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !state.operator_auth.authorize(token) {
+        return Err((StatusCode::UNAUTHORIZED, "missing or invalid operator token".to_string()));
+    }
+    Ok(())
 }
 
+/// 프리미엄 맵 (ETag/Last-Modified, 바뀌지 않았으면 304)
+#[utoipa::path(
+    get,
+    path = "/api/premium",
+    tag = "premium",
+    params(PremiumQuery),
+    responses(
+        (status = 200, description = "프리미엄 맵", body = [OptionPremium]),
+        (status = 304, description = "If-None-Match/If-Modified-Since 이후 변경 없음"),
+        (status = 404, description = "해당 만기 없음")
+    )
+)]
 async fn get_premium_map(
     Query(params): Query<PremiumQuery>,
     axum::extract::State(state): axum::extract::State<Arc<AppState>>,
-) -> Result<Json<Vec<OptionPremium>>, StatusCode> {
-    match state.premium_service.get_premiums_by_expiry(params.expiry).await {
-        Ok(premiums) => Ok(Json(premiums)),
-        Err(_) => Err(StatusCode::NOT_FOUND),
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let snapshot = state
+        .premium_service
+        .snapshots()
+        .snapshot(params.expiry.as_deref());
+    if params.expiry.is_some() && snapshot.premiums.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
     }
+
+    let etag = state.premium_service.snapshots().etag();
+    let last_modified = httpdate::fmt_http_date(
+        std::time::UNIX_EPOCH + std::time::Duration::from_secs(snapshot.updated_at),
+    );
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::LAST_MODIFIED, last_modified),
+        (header::CACHE_CONTROL, "no-cache".to_string()),
+    ];
+
+    // If-None-Match가 있으면 If-Modified-Since는 무시 (RFC 9110)
+    let request_header = |name| headers.get(name).and_then(|v: &axum::http::HeaderValue| v.to_str().ok());
+    let not_modified = match request_header(header::IF_NONE_MATCH) {
+        Some(tags) => tags
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == "*" || tag == etag),
+        None => request_header(header::IF_MODIFIED_SINCE)
+            .and_then(|since| httpdate::parse_http_date(since).ok())
+            .and_then(|since| since.duration_since(std::time::UNIX_EPOCH).ok())
+            .is_some_and(|since| snapshot.updated_at <= since.as_secs()),
+    };
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+    Ok((cache_headers, Json(snapshot.premiums)).into_response())
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+struct PremiumDeltaQuery {
+    /// 클라이언트가 가진 맵 버전 (ETag의 숫자, 없으면 전체)
+    #[serde(default)]
+    since: u64,
 }
 
+/// `since` 버전 이후 바뀐 프리미엄만 조회
+#[utoipa::path(
+    get,
+    path = "/api/premium/delta",
+    tag = "premium",
+    params(PremiumDeltaQuery),
+    responses((status = 200, body = PremiumDelta))
+)]
+async fn get_premium_delta(
+    Query(query): Query<PremiumDeltaQuery>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<PremiumDelta> {
+    Json(state.premium_service.snapshots().delta(query.since))
+}
+
+/// 프리미엄 맵 무차익 검증 지표
+#[utoipa::path(
+    get,
+    path = "/api/premium/arbitrage",
+    tag = "premium",
+    responses((status = 200, body = Object))
+)]
+async fn get_arbitrage_metrics(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<ArbitrageMetrics> {
+    Json(state.premium_service.arbitrage_metrics())
+}
+
+#[utoipa::path(get, path = "/api/pool/delta", tag = "pool", responses((status = 200, body = DeltaInfo)))]
 async fn get_pool_delta(
     axum::extract::State(state): axum::extract::State<Arc<AppState>>,
 ) -> Result<Json<DeltaInfo>, StatusCode> {
@@ -39,6 +226,7 @@ async fn get_pool_delta(
     }
 }
 
+#[utoipa::path(get, path = "/api/delta/current", tag = "pool", responses((status = 200, body = f64)))]
 async fn get_current_delta(
     axum::extract::State(state): axum::extract::State<Arc<AppState>>,
 ) -> Result<Json<f64>, StatusCode> {
@@ -48,50 +236,773 @@ async fn get_current_delta(
     }
 }
 
+/// 시장 상태와 변동성 국면
+#[derive(serde::Serialize, ToSchema)]
+struct MarketView {
+    #[serde(flatten)]
+    market: MarketState,
+    #[schema(value_type = Object)]
+    regime: RegimeState,
+}
+
+#[utoipa::path(get, path = "/api/market", tag = "market", responses((status = 200, body = MarketView)))]
 async fn get_market_state(
     axum::extract::State(state): axum::extract::State<Arc<AppState>>,
-) -> Result<Json<MarketState>, StatusCode> {
+) -> Result<Json<MarketView>, StatusCode> {
     match state.market_service.get_market_state().await {
-        Ok(market_state) => Ok(Json(market_state)),
+        Ok(market) => Ok(Json(MarketView {
+            market,
+            regime: state.regime.state(),
+        })),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/market/vol-surface",
+    tag = "market",
+    responses(
+        (status = 200, body = VolSurface),
+        (status = 404, description = "아직 받은 IV 곡면 없음")
+    )
+)]
+async fn get_vol_surface(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Result<Json<VolSurface>, StatusCode> {
+    match state.vol_repo.get_surface().await {
+        Ok(Some(surface)) => Ok(Json(surface)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Aggregator의 IV 곡면을 주기적으로 가져와 프리미엄 맵 갱신
+async fn run_vol_feed(
+    aggregator_url: String,
+    vol_repo: Arc<dyn VolSurfaceRepository>,
+    premium_service: Arc<PremiumCalculationService<BlackScholesPricing>>,
+    market_service: Arc<MarketDataService>,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), String> {
+    let mut feed = VolSurfaceFeed::new(&aggregator_url, vol_repo).await?;
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(VOL_FEED_INTERVAL_SECS));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.recv() => return Ok(()),
+        }
+        let Some(_work) = shutdown.begin_work() else {
+            return Ok(());
+        };
+
+        match feed.refresh().await {
+            Ok(surface) => {
+                info!(
+                    "IV surface updated from {} ({} points)",
+                    surface.source,
+                    surface.points.len()
+                );
+                let spot = market_service.get_market_state().await?.current_price;
+                premium_service.update_premium_map(spot).await?;
+            }
+            Err(e) => warn!("Failed to refresh IV surface: {}", e),
+        }
+    }
+}
+
+/// Aggregator 합의 가격을 공유 피드로 받아 캔들과 국면 감지기에 기록
+async fn run_price_recorder(
+    aggregator_url: String,
+    market_data: Arc<MarketDataStore>,
+    regime: Arc<RegimeDetector>,
+    mut shutdown: ShutdownSignal,
+) {
+    let service = PriceFeedService::lazy(&aggregator_url, PRICE_RECORD_INTERVAL_SECS);
+    let mut updates = service.feed().subscribe();
+    tokio::spawn(service.run_until(shutdown.clone()));
+
+    loop {
+        tokio::select! {
+            changed = updates.changed() => if changed.is_err() { return },
+            _ = shutdown.recv() => return,
+        }
+
+        // stale 전환도 알림으로 오지만 기록은 새 가격일 때만
+        let snapshot = updates.borrow_and_update().clone();
+        let (FeedStatus::Fresh, Some(price)) = (snapshot.status, snapshot.value) else {
+            continue;
+        };
+        let spot = price.average_price as f64 / 100.0;
+        market_data.record_price(snapshot.observed_at, spot);
+        let before = regime.state().regime;
+        let state = regime.observe(snapshot.observed_at, spot);
+        if state.regime != before {
+            warn!("Volatility regime changed to {:?} (z = {:?})", state.regime, state.last_z);
+        }
+    }
+}
+
+/// OHLC 캔들 (strike/option_type이 없으면 현물 가격)
+#[utoipa::path(
+    get,
+    path = "/api/candles",
+    tag = "market",
+    params(CandleQuery),
+    responses(
+        (status = 200, body = [Candle]),
+        (status = 400, description = "strike/option_type 중 하나만 지정", body = String)
+    )
+)]
+async fn get_candles(
+    Query(query): Query<CandleQuery>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Result<Json<Vec<Candle>>, (StatusCode, String)> {
+    let series = query
+        .series()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    Ok(Json(state.market_data.candles(
+        series,
+        query.interval,
+        query.from,
+        query.to,
+        query.limit,
+    )))
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+struct TradesQuery {
+    limit: Option<usize>,
+}
+
+/// 최근 옵션 체결
+#[utoipa::path(get, path = "/api/trades", tag = "market", params(TradesQuery), responses((status = 200, body = [TradeRecord])))]
+async fn get_trades(
+    Query(query): Query<TradesQuery>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<Vec<TradeRecord>> {
+    Json(state.market_data.recent_trades(query.limit.unwrap_or(100)))
+}
+
+/// 웹훅 서명 확인 (비밀값이 설정되지 않았으면 서명 없는 체결로 시장 데이터를 오염시키지 않도록 전부 거부)
+fn authorize_trade_webhook(
+    secret: Option<&str>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(), (StatusCode, String)> {
+    let Some(secret) = secret else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            "trade webhook disabled: TRADE_WEBHOOK_SECRET not set".to_string(),
+        ));
+    };
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let timestamp = header(TIMESTAMP_HEADER).and_then(|v| v.parse().ok());
+    match (timestamp, header(SIGNATURE_HEADER)) {
+        (Some(timestamp), Some(signature)) if verify_signature(secret, timestamp, body, signature) => Ok(()),
+        _ => Err((
+            StatusCode::UNAUTHORIZED,
+            "invalid webhook signature".to_string(),
+        )),
+    }
+}
+
+/// contracts 서비스 웹훅 수신 (옵션 생성/프리미엄 지급)
+#[utoipa::path(
+    post,
+    path = "/api/trades/webhook",
+    tag = "market",
+    request_body = Object,
+    responses(
+        (status = 204),
+        (status = 400, body = String),
+        (status = 401, description = "서명 불일치 또는 비밀값 미설정", body = String),
+        (status = 422, body = String)
+    )
+)]
+async fn receive_trade_webhook(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, (StatusCode, String)> {
+    authorize_trade_webhook(state.trade_webhook_secret.as_deref(), &headers, &body)?;
+
+    let event: WebhookEvent =
+        serde_json::from_slice(&body).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    state
+        .market_data
+        .ingest_webhook(&event)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(get, path = "/api/risk/stress", tag = "risk", responses((status = 200, body = Object)))]
+async fn get_stress_report(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Result<Json<StressReport>, StatusCode> {
+    match state.stress_service.run().await {
+        Ok(report) => Ok(Json(report)),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/risk/positions",
+    tag = "risk",
+    request_body = Object,
+    responses(
+        (status = 201),
+        (status = 401, body = String),
+        (status = 422, description = "리스크 한도 초과", body = String)
+    )
+)]
+async fn open_position(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(position): Json<OptionPosition>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    authorize_operator(&state, &headers)?;
+    match state.risk_engine.open_position(position).await {
+        Ok(()) => Ok(StatusCode::CREATED),
+        Err(reason) => Err((StatusCode::UNPROCESSABLE_ENTITY, reason)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/rfq",
+    tag = "rfq",
+    request_body = Object,
+    responses(
+        (status = 200, description = "서명된 확정 호가", body = Object),
+        (status = 422, body = String),
+        (status = 503, description = "재시도 가능한 오류", body = String)
+    )
+)]
+async fn request_quote(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    Json(request): Json<QuoteRequest>,
+) -> Result<Json<OptionQuote>, (StatusCode, String)> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match state.quote_service.request_quote(&request, now).await {
+        Ok(quote) => Ok(Json(quote)),
+        Err(e) => {
+            let status = if e.is_retryable() {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::UNPROCESSABLE_ENTITY
+            };
+            Err((status, format!("{}: {}", e.code(), e)))
+        }
+    }
+}
+
+/// 만기 전 되사기 호가 (이론가 - 스프레드, Contracts `buy_back_option`에 제출)
+#[utoipa::path(
+    post,
+    path = "/api/rfq/buy-back",
+    tag = "rfq",
+    request_body = Object,
+    responses(
+        (status = 200, body = Object),
+        (status = 422, body = String),
+        (status = 503, body = String)
+    )
+)]
+async fn request_buy_back_quote(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    Json(request): Json<BuyBackRequest>,
+) -> Result<Json<BuyBackQuote>, (StatusCode, String)> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match state.quote_service.quote_buy_back(&request, now).await {
+        Ok(quote) => Ok(Json(quote)),
+        Err(e) => {
+            let status = if e.is_retryable() {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::UNPROCESSABLE_ENTITY
+            };
+            Err((status, format!("{}: {}", e.code(), e)))
+        }
+    }
+}
+
+#[utoipa::path(get, path = "/api/rfq/pubkey", tag = "rfq", responses((status = 200, body = String)))]
+async fn get_quote_public_key(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<String> {
+    Json(state.quote_service.public_key().to_string())
+}
+
+#[utoipa::path(get, path = "/api/rfq/curve", tag = "rfq", responses((status = 200, body = Object)))]
+async fn get_quote_curve(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<UtilizationCurve> {
+    Json(state.quote_service.curve().clone())
+}
+
+/// 재고 스큐 설정 공시 (호가 프리미엄은 이론가에 가산 배율과 스큐를 적용한 ask)
+#[utoipa::path(get, path = "/api/rfq/skew", tag = "rfq", responses((status = 200, body = Object)))]
+async fn get_quote_skew(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<Option<InventorySkew>> {
+    Json(state.quote_service.inventory_skew().copied())
+}
+
+/// 거래당 델타/베가 한도 공시
+#[utoipa::path(get, path = "/api/rfq/greeks-limits", tag = "rfq", responses((status = 200, body = Object)))]
+async fn get_greeks_limits(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<GreeksLimits> {
+    Json(*state.quote_service.greeks_limits())
+}
+
+#[derive(serde::Deserialize, IntoParams)]
+struct CapacityQuery {
+    strike_price: u64,
+    expiry: String,
+    #[serde(default)]
+    otc: bool,
+}
+
+/// 행사가/만기별 남은 미결제약정 용량 공시
+#[utoipa::path(
+    get,
+    path = "/api/rfq/capacity",
+    tag = "rfq",
+    params(CapacityQuery),
+    responses((status = 200, body = Object), (status = 422, body = String))
+)]
+async fn get_open_interest_capacity(
+    Query(query): Query<CapacityQuery>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Result<Json<OpenInterestCapacity>, (StatusCode, String)> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    state
+        .quote_service
+        .open_interest_capacity(query.strike_price, &query.expiry, query.otc, now)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, format!("{}: {}", e.code(), e)))
+}
+
+/// contracts가 보고한 활성 옵션의 행사가/만기별 잠긴 담보로 미결제약정 교체 (운영자 토큰)
+///
+/// 표를 통째로 바꾸므로 종료/행사/만기된 옵션의 담보는 다음 보고에서 빠집니다.
+#[utoipa::path(
+    put,
+    path = "/api/rfq/open-interest",
+    tag = "rfq",
+    request_body = [Object],
+    responses((status = 204), (status = 401, body = String), (status = 503, body = String))
+)]
+async fn sync_open_interest(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(entries): Json<Vec<OpenInterestEntry>>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    authorize_operator(&state, &headers)?;
+    state
+        .quote_service
+        .sync_open_interest(&entries)
+        .await
+        .map(|()| StatusCode::NO_CONTENT)
+        .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e.to_string()))
+}
+
+/// 자동 행사/dust 지급 정책 공시
+#[utoipa::path(get, path = "/api/rfq/exercise-policy", tag = "rfq", responses((status = 200, body = Object)))]
+async fn get_exercise_policy(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<ExercisePolicy> {
+    Json(*state.quote_service.exercise_policy())
+}
+
+/// 상장 만기 목록 (일간 08:00 UTC, 주간 금요일, 월간 마지막 금요일)
+#[utoipa::path(get, path = "/api/expiries", tag = "products", responses((status = 200, body = [Object])))]
+async fn get_expiries(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<Vec<Expiry>> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    Json(state.calendar.expiries(now))
+}
+
+/// 상품 템플릿 목록
+#[utoipa::path(get, path = "/api/products", tag = "products", responses((status = 200, body = [Object])))]
+async fn list_products(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Json<Vec<ProductTemplate>> {
+    Json(state.products.templates().to_vec())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/products/{id}",
+    tag = "products",
+    params(("id" = String, Path, description = "상품 템플릿 ID")),
+    responses((status = 200, body = Object), (status = 404))
+)]
+async fn get_product(
+    axum::extract::Path(id): axum::extract::Path<String>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+) -> Result<Json<ProductTemplate>, StatusCode> {
+    state
+        .products
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// 템플릿 ID와 수량으로 확정 호가 (레그별 서명 호가)
+#[utoipa::path(
+    post,
+    path = "/api/products/{id}/quote",
+    tag = "products",
+    params(("id" = String, Path, description = "상품 템플릿 ID")),
+    request_body = Object,
+    responses(
+        (status = 200, body = Object),
+        (status = 404, body = String),
+        (status = 422, body = String),
+        (status = 503, body = String)
+    )
+)]
+async fn quote_product(
+    axum::extract::Path(id): axum::extract::Path<String>,
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    Json(request): Json<ProductQuoteRequest>,
+) -> Result<Json<ProductQuote>, (StatusCode, String)> {
+    let Some(template) = state.products.get(&id) else {
+        return Err((StatusCode::NOT_FOUND, format!("Unknown product template {}", id)));
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match state.quote_service.quote_product(template, &request, now).await {
+        Ok(quote) => Ok(Json(quote)),
+        Err(e) => {
+            let status = if e.is_retryable() {
+                StatusCode::SERVICE_UNAVAILABLE
+            } else {
+                StatusCode::UNPROCESSABLE_ENTITY
+            };
+            Err((status, format!("{}: {}", e.code(), e)))
+        }
+    }
+}
+
+/// 상품 템플릿 (PRODUCT_TEMPLATES JSON 파일, 없거나 잘못되면 기본 템플릿)
+fn load_product_templates() -> ProductRegistry {
+    let Ok(path) = std::env::var("PRODUCT_TEMPLATES") else {
+        return ProductRegistry::default();
+    };
+
+    match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|body| serde_json::from_str(&body).map_err(|e| e.to_string()))
+        .and_then(|templates| ProductRegistry::new(templates).map_err(|e| e.to_string()))
+    {
+        Ok(registry) => registry,
+        Err(e) => {
+            warn!("Failed to load product templates from {}: {}, using defaults", path, e);
+            ProductRegistry::default()
+        }
+    }
+}
+
+/// 사용률 가산 곡선 (PREMIUM_CURVE_CONFIG JSON 파일, 없으면 기본값)
+fn load_utilization_curve() -> UtilizationCurve {
+    let Ok(path) = std::env::var("PREMIUM_CURVE_CONFIG") else {
+        return UtilizationCurve::default();
+    };
+
+    match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|body| serde_json::from_str(&body).map_err(|e| e.to_string()))
+    {
+        Ok(curve) => curve,
+        Err(e) => {
+            warn!("Failed to load premium curve from {}: {}, using defaults", path, e);
+            UtilizationCurve::default()
+        }
+    }
+}
+
+/// 재고 스큐 (INVENTORY_SKEW_CONFIG JSON 파일, 없으면 기본값)
+fn load_inventory_skew() -> InventorySkew {
+    let Ok(path) = std::env::var("INVENTORY_SKEW_CONFIG") else {
+        return InventorySkew::default();
+    };
+
+    match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|body| serde_json::from_str(&body).map_err(|e| e.to_string()))
+    {
+        Ok(skew) => skew,
+        Err(e) => {
+            warn!("Failed to load inventory skew from {}: {}, using defaults", path, e);
+            InventorySkew::default()
+        }
+    }
+}
+
+/// 무차익 검증기 (ARBITRAGE_PARITY_TOLERANCE: 현물 대비 패리티 허용 오차, 기본 1%)
+fn load_arbitrage_validator() -> ArbitrageValidator {
+    let mut validator = ArbitrageValidator::default();
+    if let Ok(value) = std::env::var("ARBITRAGE_PARITY_TOLERANCE") {
+        match value.parse::<f64>() {
+            Ok(tolerance) if tolerance > 0.0 => validator.parity_tolerance = tolerance,
+            _ => warn!("Invalid ARBITRAGE_PARITY_TOLERANCE {}, using default", value),
+        }
+    }
+    validator
+}
+
+/// 거래당 그릭 한도 (MAX_TRADE_DELTA: BTC, MAX_TRADE_VEGA: USD/vol point, 미설정 시 무제한)
+///
+/// Contracts의 `BuyerOnlyOptionManager`도 같은 값을 써야 호가가 체결됩니다.
+fn load_greeks_limits() -> GreeksLimits {
+    let load = |name: &str| {
+        let value = std::env::var(name).ok()?;
+        match value.parse::<f64>() {
+            Ok(limit) if limit > 0.0 => Some(limit),
+            _ => {
+                warn!("Invalid {} {}, limit disabled", name, value);
+                None
+            }
+        }
+    };
+    GreeksLimits {
+        max_delta: load("MAX_TRADE_DELTA"),
+        max_vega: load("MAX_TRADE_VEGA"),
+    }
+}
+
+/// 행사가/만기별 미결제약정 한도 (OI_MAX_STRIKE_PCT, OI_MAX_EXPIRY_PCT: 풀 유동성 대비 %,
+/// OI_TAPER_SECS: 만기 전 축소 시작, OI_MIN_FRACTION: 만기 시점에 남는 비율, 미설정 시 무제한)
+///
+/// Contracts의 `SimpleContractManager::set_open_interest_caps`도 같은 값을 써야 호가가 체결됩니다.
+fn load_open_interest_caps() -> OpenInterestCaps {
+    fn load<T: std::str::FromStr + PartialOrd + Default>(name: &str) -> Option<T> {
+        let value = std::env::var(name).ok()?;
+        match value.parse::<T>() {
+            Ok(parsed) if parsed > T::default() => Some(parsed),
+            _ => {
+                warn!("Invalid {} {}, ignored", name, value);
+                None
+            }
+        }
+    }
+    OpenInterestCaps {
+        max_strike_pct: load("OI_MAX_STRIKE_PCT"),
+        max_expiry_pct: load("OI_MAX_EXPIRY_PCT"),
+        taper_secs: load("OI_TAPER_SECS").unwrap_or(0),
+        min_fraction: load("OI_MIN_FRACTION").unwrap_or(0.0),
+    }
+}
+
+/// 운영자 토큰 해시 (OPERATOR_TOKEN_HASH: SHA256 hex, 쉼표로 여러 개, contracts `--admin-token-hash`와 같은 형식)
+fn load_operator_auth() -> OperatorAuth {
+    let hashes = std::env::var("OPERATOR_TOKEN_HASH")
+        .map(|value| value.split(',').map(|hash| hash.trim().to_string()).filter(|hash| !hash.is_empty()).collect())
+        .unwrap_or_default();
+    OperatorAuth::new(hashes).expect("Invalid OPERATOR_TOKEN_HASH")
+}
+
+/// 자동 행사 정책 (DUST_THRESHOLD_SATS, DUST_HANDLING=pool_revenue|accumulate)
+///
+/// Contracts 서비스와 같은 값을 써야 호가가 체결됩니다.
+fn load_exercise_policy() -> ExercisePolicy {
+    let mut policy = ExercisePolicy::default();
+    if let Ok(value) = std::env::var("DUST_THRESHOLD_SATS") {
+        match value.parse::<u64>() {
+            Ok(threshold) => policy.dust_threshold_sats = threshold,
+            Err(_) => warn!("Invalid DUST_THRESHOLD_SATS {}, dust policy disabled", value),
+        }
+    }
+    if let Ok(value) = std::env::var("DUST_HANDLING") {
+        match serde_json::from_value(serde_json::Value::String(value.clone())) {
+            Ok(handling) => policy.dust_handling = handling,
+            Err(_) => warn!("Invalid DUST_HANDLING {}, using pool_revenue", value),
+        }
+    }
+    policy
+}
+
+/// 호가 서명키 (QUOTE_SIGNING_KEY 미설정 시 임시 키 생성)
+fn load_quote_signing_key() -> SecretKey {
+    match std::env::var("QUOTE_SIGNING_KEY") {
+        Ok(hex) => hex.parse().expect("Invalid QUOTE_SIGNING_KEY"),
+        Err(_) => {
+            warn!("QUOTE_SIGNING_KEY not set, using ephemeral quote signing key");
+            generate_keypair().0
+        }
+    }
+}
+
+/// IV 곡면 갱신 간격 (초)
+const VOL_FEED_INTERVAL_SECS: u64 = 300;
+
+/// 캔들용 합의 가격 기록 간격 (초)
+const PRICE_RECORD_INTERVAL_SECS: u64 = 10;
+
+/// 종료 시 진행 중인 IV 곡면 갱신을 기다리는 최대 시간 (초)
+const DRAIN_TIMEOUT_SECS: u64 = 30;
+
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    let _tracing = oracle_vm_common::init_tracing("calculation");
+
+    // Ctrl-C / SIGTERM: 새 요청을 받지 않고 진행 중인 요청/갱신을 마친 뒤 종료
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals();
 
     // 저장소 초기화
     let premium_repo = Arc::new(InMemoryPremiumRepo::new());
     let pool_repo = Arc::new(InMemoryPoolRepo::new());
     let market_repo = Arc::new(InMemoryMarketRepo::new());
+    let position_repo = Arc::new(InMemoryPositionRepo::new());
+    let vol_repo: Arc<dyn VolSurfaceRepository> = Arc::new(InMemoryVolSurfaceRepo::new());
 
     // 서비스 초기화
     let pricing_engine = BlackScholesPricing::new();
-    let premium_service = Arc::new(PremiumCalculationService::new(
-        pricing_engine,
-        premium_repo.clone(),
-        market_repo.clone(),
-    ));
+    let regime = Arc::new(RegimeDetector::default());
+    let premium_service = Arc::new(
+        PremiumCalculationService::new(pricing_engine, premium_repo.clone(), market_repo.clone())
+            .with_vol_surface(vol_repo.clone())
+            .with_arbitrage_validator(load_arbitrage_validator())
+            .with_regime_detector(regime.clone()),
+    );
     let delta_service = Arc::new(DeltaManagementService::new(pool_repo.clone()));
     let market_service = Arc::new(MarketDataService::new(market_repo.clone()));
+    let stress_service = Arc::new(StressTestService::new(
+        position_repo.clone(),
+        pool_repo.clone(),
+        market_repo.clone(),
+    ));
+    let utilization_curve = load_utilization_curve();
+    let risk_engine = Arc::new(
+        RiskEngine::new(stress_service.clone(), position_repo.clone())
+            .with_max_trade_quantity(utilization_curve.max_order_btc)
+            .with_regime_detector(regime.clone()),
+    );
+    let calendar = ExpiryCalendar::default();
+    let quote_service = Arc::new(
+        QuoteService::new(
+            BlackScholesPricing::new(),
+            market_repo.clone(),
+            load_quote_signing_key(),
+        )
+        .with_vol_surface(vol_repo.clone())
+        .with_pool_curve(pool_repo.clone(), utilization_curve)
+        .with_inventory_skew(load_inventory_skew())
+        .with_calendar(calendar.clone())
+        .with_contract_spec(ContractSpec::default())
+        .with_exercise_policy(load_exercise_policy())
+        .with_greeks_limits(load_greeks_limits())
+        .with_open_interest_caps(Arc::new(InMemoryOpenInterestRepo::new()), load_open_interest_caps()),
+    );
+    info!("Quote signing key: {}", quote_service.public_key());
+
+    let market_data = Arc::new(MarketDataStore::new());
+    let trade_webhook_secret = std::env::var("TRADE_WEBHOOK_SECRET").ok();
+    if trade_webhook_secret.is_none() {
+        warn!("TRADE_WEBHOOK_SECRET not set, POST /api/trades/webhook rejects every webhook");
+    }
+    let operator_auth = load_operator_auth();
+    if !operator_auth.is_configured() {
+        warn!("OPERATOR_TOKEN_HASH not set, POST /api/risk/positions and PUT /api/rfq/open-interest are disabled");
+    }
 
     // 초기 데이터 설정
     premium_service.update_premium_map(70000.0).await.unwrap();
 
+    // 외부 IV 곡면 피드 (AGGREGATOR_URL 설정 시)
+    if let Ok(aggregator_url) = std::env::var("AGGREGATOR_URL") {
+        info!("IV surface feed enabled: {}", aggregator_url);
+        let feed = run_vol_feed(
+            aggregator_url.clone(),
+            vol_repo.clone(),
+            premium_service.clone(),
+            market_service.clone(),
+            shutdown.signal(),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = feed.await {
+                warn!("IV surface feed stopped: {}", e);
+            }
+        });
+
+        tokio::spawn(run_price_recorder(
+            aggregator_url,
+            market_data.clone(),
+            regime.clone(),
+            shutdown.signal(),
+        ));
+    }
+
     // 애플리케이션 상태
     let app_state = Arc::new(AppState {
         premium_service,
         delta_service,
         market_service,
+        stress_service,
+        risk_engine,
+        vol_repo,
+        quote_service,
+        calendar,
+        products: load_product_templates(),
+        market_data,
+        regime,
+        trade_webhook_secret,
+        operator_auth,
     });
 
     let app = Router::new()
         .route("/api/premium", get(get_premium_map))
+        .route("/api/premium/delta", get(get_premium_delta))
+        .route("/api/premium/arbitrage", get(get_arbitrage_metrics))
         .route("/api/pool/delta", get(get_pool_delta))
         .route("/api/delta/current", get(get_current_delta))
         .route("/api/market", get(get_market_state))
-        .with_state(app_state);
+        .route("/api/market/vol-surface", get(get_vol_surface))
+        .route("/api/risk/stress", get(get_stress_report))
+        .route("/api/risk/positions", post(open_position))
+        .route("/api/rfq", post(request_quote))
+        .route("/api/rfq/buy-back", post(request_buy_back_quote))
+        .route("/api/rfq/pubkey", get(get_quote_public_key))
+        .route("/api/rfq/curve", get(get_quote_curve))
+        .route("/api/rfq/skew", get(get_quote_skew))
+        .route("/api/rfq/exercise-policy", get(get_exercise_policy))
+        .route("/api/rfq/greeks-limits", get(get_greeks_limits))
+        .route("/api/rfq/capacity", get(get_open_interest_capacity))
+        .route("/api/rfq/open-interest", put(sync_open_interest))
+        .route("/api/expiries", get(get_expiries))
+        .route("/api/products", get(list_products))
+        .route("/api/products/:id", get(get_product))
+        .route("/api/products/:id/quote", post(quote_product))
+        .route("/api/candles", get(get_candles))
+        .route("/api/trades", get(get_trades))
+        .route("/api/trades/webhook", post(receive_trade_webhook))
+        .with_state(app_state)
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()));
+    let app = with_correlation(app);
 
     let listener = TcpListener::bind("127.0.0.1:3000")
         .await
@@ -100,13 +1011,36 @@ async fn main() {
     info!("Calculation API server starting on http://127.0.0.1:3000");
     info!("Available endpoints:");
     info!("  GET /api/premium - 프리미엄 맵");
+    info!("  GET /api/premium/arbitrage - 프리미엄 맵 무차익 검증 지표");
     info!("  GET /api/pool/delta - 풀 델타 정보");
     info!("  GET /api/delta/current - 현재 델타값");
-    info!("  GET /api/market - 시장 상태");
+    info!("  GET /api/market - 시장 상태 (변동성 국면 포함)");
+    info!("  GET /api/market/vol-surface - 외부 IV 곡면");
+    info!("  GET /api/risk/stress - 풀 스트레스 테스트");
+    info!("  POST /api/risk/positions - 리스크 검사 후 포지션 등록 (운영자 토큰)");
+    info!("  POST /api/rfq - 확정 호가 요청");
+    info!("  GET /api/rfq/pubkey - 호가 서명 공개키");
+    info!("  GET /api/rfq/curve - 사용률 프리미엄 가산 곡선");
+    info!("  PUT /api/rfq/open-interest - contracts 미결제약정 보고 (운영자 토큰)");
+    info!("  GET /api/expiries - 상장 만기 캘린더");
+    info!("  GET /api/products - 상품 템플릿, POST /api/products/{{id}}/quote - 템플릿 호가");
+    info!("  GET /api/candles - 현물/프리미엄 OHLC 캔들 (1m/5m/1h)");
+    info!("  GET /api/trades - 최근 옵션 체결");
+    info!("  POST /api/trades/webhook - contracts 체결 웹훅 수신");
+    info!("  GET /openapi.json - OpenAPI 명세, GET /docs - Swagger UI");
 
+    let mut signal = shutdown.signal();
     axum::serve(listener, app)
+        .with_graceful_shutdown(async move { signal.recv().await })
         .await
         .expect("Failed to start server");
+
+    info!("Shutting down, waiting for in-flight IV surface refresh...");
+    let timeout = std::time::Duration::from_secs(DRAIN_TIMEOUT_SECS);
+    if !shutdown.drain(timeout).await {
+        warn!("{} refreshes still in flight after {:?}", shutdown.in_flight(), timeout);
+    }
+    info!("Calculation API server stopped");
 }
 
 #[cfg(test)]
@@ -163,4 +1097,35 @@ mod tests {
         assert!(price < 70000.0);
         assert!(delta > 0.4 && delta < 0.6);
     }
+
+    #[test]
+    fn test_trade_webhook_requires_secret_and_signature() {
+        use btcfi_contracts::webhooks::sign_payload;
+
+        let body = br#"{"event":"option_created"}"#;
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMESTAMP_HEADER, "1700000000".parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, sign_payload("secret", 1_700_000_000, body).parse().unwrap());
+
+        // 비밀값이 없으면 서명이 있어도 없어도 거부
+        assert_eq!(authorize_trade_webhook(None, &HeaderMap::new(), body).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(authorize_trade_webhook(None, &headers, body).unwrap_err().0, StatusCode::UNAUTHORIZED);
+
+        assert!(authorize_trade_webhook(Some("secret"), &headers, body).is_ok());
+        assert_eq!(authorize_trade_webhook(Some("other"), &headers, body).unwrap_err().0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            authorize_trade_webhook(Some("secret"), &HeaderMap::new(), body).unwrap_err().0,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[test]
+    fn test_openapi_documents_every_route() {
+        let spec = ApiDoc::openapi();
+        assert_eq!(spec.paths.paths.len(), 25);
+        let quote = &spec.paths.paths["/api/products/{id}/quote"];
+        assert!(quote.post.is_some());
+        let premium = spec.paths.paths["/api/premium"].get.as_ref().unwrap();
+        assert!(premium.responses.responses.contains_key("304"));
+    }
 }
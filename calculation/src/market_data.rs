@@ -0,0 +1,371 @@
+//! 체결 내역과 OHLC 캔들
+//!
+//! contracts 서비스의 옵션 체결(웹훅)과 aggregator 합의 가격을 기록하고,
+//! 현물과 행사가별 프리미엄을 1분/5분/1시간 캔들로 집계합니다. 프리미엄 캔들
+//! 가격은 명목 1 BTC당 프리미엄(BTC), 거래량은 명목 수량(BTC)입니다.
+
+use btcfi_contracts::{WebhookEvent, WebhookEventKind};
+use oracle_vm_common::types::OptionType;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use utoipa::{IntoParams, ToSchema};
+use std::sync::Mutex;
+
+/// 시리즈/간격별 최대 보관 캔들 수
+pub const MAX_CANDLES: usize = 2_000;
+/// 최대 보관 체결 수
+pub const MAX_TRADES: usize = 10_000;
+
+/// 캔들 간격
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 3] = [Self::OneMinute, Self::FiveMinutes, Self::OneHour];
+
+    pub fn secs(&self) -> u64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 300,
+            Self::OneHour => 3_600,
+        }
+    }
+
+    fn bucket(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.secs()
+    }
+}
+
+/// 캔들 시리즈
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CandleSeries {
+    /// 합의 현물 가격 (USD)
+    Spot,
+    /// 행사가(USD cents)/종류별 프리미엄
+    Premium { strike_cents: u64, is_call: bool },
+}
+
+/// OHLC 캔들
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Candle {
+    /// 구간 시작 (Unix 초)
+    pub start: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub trades: u64,
+}
+
+impl Candle {
+    fn new(start: u64, price: f64, volume: f64) -> Self {
+        Self {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+            trades: 1,
+        }
+    }
+
+    fn update(&mut self, price: f64, volume: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += volume;
+        self.trades += 1;
+    }
+}
+
+/// 옵션 체결 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TradeRecord {
+    pub option_id: String,
+    #[schema(value_type = String)]
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub quantity: u64,     // satoshis
+    pub premium: u64,      // satoshis
+    pub timestamp: u64,
+}
+
+impl TradeRecord {
+    /// 명목 1 BTC당 프리미엄 (BTC)
+    pub fn unit_premium(&self) -> f64 {
+        self.premium as f64 / self.quantity as f64
+    }
+}
+
+/// `GET /api/candles` 쿼리
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct CandleQuery {
+    #[param(inline)]
+    pub interval: CandleInterval,
+    /// 행사가 (USD cents, 없으면 현물)
+    pub strike: Option<u64>,
+    #[param(value_type = Option<String>)]
+    pub option_type: Option<OptionType>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl CandleQuery {
+    pub fn series(&self) -> Result<CandleSeries, String> {
+        match (self.strike, self.option_type) {
+            (None, None) => Ok(CandleSeries::Spot),
+            (Some(strike_cents), Some(option_type)) => Ok(CandleSeries::Premium {
+                strike_cents,
+                is_call: option_type == OptionType::Call,
+            }),
+            _ => Err("strike and option_type must be given together".to_string()),
+        }
+    }
+}
+
+/// 옵션 생성 웹훅 (프리미엄 웹훅 도착 전까지 보관)
+#[derive(Debug, Clone)]
+struct PendingTrade {
+    option_type: OptionType,
+    strike_price: u64,
+    quantity: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    candles: HashMap<(CandleSeries, CandleInterval), BTreeMap<u64, Candle>>,
+    trades: VecDeque<TradeRecord>,
+    pending: HashMap<String, PendingTrade>,
+}
+
+impl Inner {
+    fn record(&mut self, series: CandleSeries, timestamp: u64, price: f64, volume: f64) {
+        for interval in CandleInterval::ALL {
+            let candles = self.candles.entry((series, interval)).or_default();
+            let start = interval.bucket(timestamp);
+            candles
+                .entry(start)
+                .and_modify(|candle| candle.update(price, volume))
+                .or_insert_with(|| Candle::new(start, price, volume));
+            while candles.len() > MAX_CANDLES {
+                candles.pop_first();
+            }
+        }
+    }
+}
+
+/// 체결/가격 기록 및 캔들 집계
+#[derive(Default)]
+pub struct MarketDataStore {
+    inner: Mutex<Inner>,
+}
+
+impl MarketDataStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 합의 가격 기록
+    pub fn record_price(&self, timestamp: u64, price: f64) {
+        self.inner
+            .lock()
+            .unwrap()
+            .record(CandleSeries::Spot, timestamp, price, 0.0);
+    }
+
+    /// 옵션 체결 기록
+    pub fn record_trade(&self, trade: TradeRecord) {
+        if trade.quantity == 0 {
+            return;
+        }
+        let series = CandleSeries::Premium {
+            strike_cents: trade.strike_price,
+            is_call: trade.option_type == OptionType::Call,
+        };
+        let mut inner = self.inner.lock().unwrap();
+        inner.record(
+            series,
+            trade.timestamp,
+            trade.unit_premium(),
+            trade.quantity as f64 / 1e8,
+        );
+        inner.trades.push_back(trade);
+        while inner.trades.len() > MAX_TRADES {
+            inner.trades.pop_front();
+        }
+    }
+
+    /// contracts 웹훅 수신 (OptionCreated + PremiumPaid를 한 체결로 묶음)
+    ///
+    /// 체결이 기록되면 true
+    pub fn ingest_webhook(&self, event: &WebhookEvent) -> Result<bool, String> {
+        let field = |name: &str| {
+            event
+                .data
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("{} missing `{}`", event.event_id, name))
+        };
+        let option_id: String =
+            serde_json::from_value(field("option_id")?).map_err(|e| e.to_string())?;
+
+        match event.kind {
+            WebhookEventKind::OptionCreated => {
+                let pending = PendingTrade {
+                    option_type: serde_json::from_value(field("option_type")?)
+                        .map_err(|e| e.to_string())?,
+                    strike_price: serde_json::from_value(field("strike_price")?)
+                        .map_err(|e| e.to_string())?,
+                    quantity: serde_json::from_value(field("quantity")?)
+                        .map_err(|e| e.to_string())?,
+                };
+                self.inner.lock().unwrap().pending.insert(option_id, pending);
+                Ok(false)
+            }
+            WebhookEventKind::PremiumPaid => {
+                let premium: u64 =
+                    serde_json::from_value(field("premium")?).map_err(|e| e.to_string())?;
+                let Some(pending) = self.inner.lock().unwrap().pending.remove(&option_id) else {
+                    return Err(format!("premium for unknown option {}", option_id));
+                };
+                self.record_trade(TradeRecord {
+                    option_id,
+                    option_type: pending.option_type,
+                    strike_price: pending.strike_price,
+                    quantity: pending.quantity,
+                    premium,
+                    timestamp: event.timestamp,
+                });
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// 캔들 조회 (시간순, limit이면 최근 것부터 자름)
+    pub fn candles(
+        &self,
+        series: CandleSeries,
+        interval: CandleInterval,
+        from: Option<u64>,
+        to: Option<u64>,
+        limit: Option<usize>,
+    ) -> Vec<Candle> {
+        let inner = self.inner.lock().unwrap();
+        let Some(candles) = inner.candles.get(&(series, interval)) else {
+            return Vec::new();
+        };
+        let range = candles.range(from.unwrap_or(0)..=to.unwrap_or(u64::MAX));
+        let mut selected: Vec<Candle> = range.map(|(_, candle)| candle.clone()).collect();
+        if let Some(limit) = limit {
+            selected.drain(..selected.len().saturating_sub(limit));
+        }
+        selected
+    }
+
+    /// 최근 체결 (최신순)
+    pub fn recent_trades(&self, limit: usize) -> Vec<TradeRecord> {
+        let inner = self.inner.lock().unwrap();
+        inner.trades.iter().rev().take(limit).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spot_candles_across_intervals() {
+        let store = MarketDataStore::new();
+        // 12:00:10 ~ 12:06:00
+        for (timestamp, price) in [(43_210, 70_000.0), (43_230, 70_500.0), (43_250, 69_800.0), (43_560, 70_100.0)] {
+            store.record_price(timestamp, price);
+        }
+
+        let minute = store.candles(CandleSeries::Spot, CandleInterval::OneMinute, None, None, None);
+        assert_eq!(minute.len(), 2);
+        assert_eq!(
+            minute[0],
+            Candle {
+                start: 43_200,
+                open: 70_000.0,
+                high: 70_500.0,
+                low: 69_800.0,
+                close: 69_800.0,
+                volume: 0.0,
+                trades: 3,
+            }
+        );
+
+        let five = store.candles(CandleSeries::Spot, CandleInterval::FiveMinutes, None, None, None);
+        assert_eq!(five.iter().map(|c| c.start).collect::<Vec<_>>(), vec![43_200, 43_500]);
+        let hour = store.candles(CandleSeries::Spot, CandleInterval::OneHour, None, None, Some(1));
+        assert_eq!((hour[0].start, hour[0].close, hour[0].trades), (43_200, 70_100.0, 4));
+        assert!(store
+            .candles(CandleSeries::Spot, CandleInterval::OneMinute, Some(43_300), None, None)
+            .iter()
+            .all(|c| c.start >= 43_300));
+    }
+
+    #[test]
+    fn test_webhook_trades_build_premium_candles() {
+        let store = MarketDataStore::new();
+        let event = |id: &str, kind, data| WebhookEvent {
+            event_id: id.to_string(),
+            kind,
+            timestamp: 43_210,
+            data,
+        };
+
+        let created = event(
+            "pool-1-created",
+            WebhookEventKind::OptionCreated,
+            serde_json::json!({
+                "option_id": "OPT-1",
+                "option_type": "Call",
+                "strike_price": 7_500_000u64,
+                "quantity": 50_000_000u64,
+                "collateral": 50_000_000u64,
+                "user_id": "alice",
+            }),
+        );
+        let premium = event(
+            "pool-1-premium",
+            WebhookEventKind::PremiumPaid,
+            serde_json::json!({ "option_id": "OPT-1", "premium": 1_000_000u64, "user_id": "alice" }),
+        );
+        assert_eq!(store.ingest_webhook(&created), Ok(false));
+        assert_eq!(store.ingest_webhook(&premium), Ok(true));
+        assert!(store.ingest_webhook(&premium).is_err());
+
+        let series = CandleSeries::Premium {
+            strike_cents: 7_500_000,
+            is_call: true,
+        };
+        let candles = store.candles(series, CandleInterval::OneMinute, None, None, None);
+        assert_eq!(candles.len(), 1);
+        assert!((candles[0].close - 0.02).abs() < 1e-12);
+        assert!((candles[0].volume - 0.5).abs() < 1e-12);
+        assert_eq!(store.recent_trades(10)[0].option_id, "OPT-1");
+
+        let query = CandleQuery {
+            interval: CandleInterval::OneMinute,
+            strike: Some(7_500_000),
+            option_type: None,
+            from: None,
+            to: None,
+            limit: None,
+        };
+        assert!(query.series().is_err());
+    }
+}
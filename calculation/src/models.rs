@@ -1,7 +1,9 @@
+use crate::theta_targeting::PoolCapacity;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 /// 옵션 프리미엄 정보
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct OptionPremium {
     pub strike: f64,
     pub expiry: String,
@@ -11,12 +13,14 @@ pub struct OptionPremium {
 }
 
 /// 델타 정보
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct DeltaInfo {
     pub total_call_delta: f64,
     pub total_put_delta: f64,
     pub net_delta: f64,
     pub available_liquidity: f64,
+    #[serde(default)]
+    pub locked_collateral: f64,
 }
 
 impl DeltaInfo {
@@ -26,6 +30,16 @@ impl DeltaInfo {
             total_put_delta: 0.0,
             net_delta: 0.0,
             available_liquidity,
+            locked_collateral: 0.0,
+        }
+    }
+
+    /// 풀 용량 (사용률 가산 계산용)
+    pub fn pool_capacity(&self) -> PoolCapacity {
+        PoolCapacity {
+            total_liquidity: self.available_liquidity + self.locked_collateral,
+            locked_collateral: self.locked_collateral,
+            net_delta: self.net_delta,
         }
     }
 
@@ -40,7 +54,7 @@ impl DeltaInfo {
 }
 
 /// 현재 시장 상태
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MarketState {
     pub current_price: f64,
     pub timestamp: u64,
@@ -59,6 +73,83 @@ impl MarketState {
     }
 }
 
+/// IV 곡면 포인트
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VolSurfacePoint {
+    pub time_to_expiry: f64, // 연 단위
+    pub strike: f64,
+    pub implied_vol: f64,
+}
+
+/// 외부 시장 IV 곡면 (Deribit 등)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VolSurface {
+    pub source: String,
+    pub timestamp: u64,
+    pub underlying_price: f64,
+    pub points: Vec<VolSurfacePoint>,
+}
+
+impl VolSurface {
+    /// 행사가/만기에 대한 IV 보간
+    ///
+    /// 만기별 스마일은 행사가 기준 선형 보간, 만기 사이는 총분산(σ²t) 선형 보간.
+    /// 곡면 범위 밖은 가장 가까운 값을 그대로 사용합니다.
+    pub fn implied_vol(&self, strike: f64, time_to_expiry: f64) -> Option<f64> {
+        let mut expiries: Vec<f64> = self.points.iter().map(|p| p.time_to_expiry).collect();
+        expiries.sort_by(|a, b| a.total_cmp(b));
+        expiries.dedup();
+
+        let lower = expiries.iter().rev().find(|&&t| t <= time_to_expiry).copied();
+        let upper = expiries.iter().find(|&&t| t >= time_to_expiry).copied();
+
+        match (lower, upper) {
+            (Some(t_lo), Some(t_hi)) if t_hi > t_lo => {
+                let var_lo = self.smile_vol(t_lo, strike)?.powi(2) * t_lo;
+                let var_hi = self.smile_vol(t_hi, strike)?.powi(2) * t_hi;
+                let weight = (time_to_expiry - t_lo) / (t_hi - t_lo);
+                let variance = var_lo + (var_hi - var_lo) * weight;
+                Some((variance / time_to_expiry).sqrt())
+            }
+            (Some(t), _) | (None, Some(t)) => self.smile_vol(t, strike),
+            (None, None) => None,
+        }
+    }
+
+    /// 단일 만기 스마일에서 행사가 보간 (같은 행사가의 Call/Put IV는 평균)
+    fn smile_vol(&self, time_to_expiry: f64, strike: f64) -> Option<f64> {
+        let mut smile: Vec<(f64, f64, u32)> = Vec::new();
+        for point in self.points.iter().filter(|p| p.time_to_expiry == time_to_expiry) {
+            match smile.iter_mut().find(|(k, _, _)| *k == point.strike) {
+                Some((_, iv_sum, count)) => {
+                    *iv_sum += point.implied_vol;
+                    *count += 1;
+                }
+                None => smile.push((point.strike, point.implied_vol, 1)),
+            }
+        }
+        let mut smile: Vec<(f64, f64)> = smile
+            .into_iter()
+            .map(|(k, iv_sum, count)| (k, iv_sum / count as f64))
+            .collect();
+        smile.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let (first, last) = (smile.first()?, smile.last()?);
+        if strike <= first.0 {
+            return Some(first.1);
+        }
+        if strike >= last.0 {
+            return Some(last.1);
+        }
+
+        smile.windows(2).find_map(|pair| {
+            let ((k_lo, iv_lo), (k_hi, iv_hi)) = (pair[0], pair[1]);
+            (strike >= k_lo && strike <= k_hi)
+                .then(|| iv_lo + (iv_hi - iv_lo) * (strike - k_lo) / (k_hi - k_lo))
+        })
+    }
+}
+
 /// 옵션 파라미터
 #[derive(Debug, Clone)]
 pub struct OptionParameters {
@@ -71,7 +162,59 @@ pub struct OptionParameters {
 }
 
 /// API 쿼리 파라미터
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
 pub struct PremiumQuery {
     pub expiry: Option<String>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(time_to_expiry: f64, strike: f64, implied_vol: f64) -> VolSurfacePoint {
+        VolSurfacePoint {
+            time_to_expiry,
+            strike,
+            implied_vol,
+        }
+    }
+
+    fn surface() -> VolSurface {
+        VolSurface {
+            source: "deribit".to_string(),
+            timestamp: 0,
+            underlying_price: 70000.0,
+            points: vec![
+                point(0.1, 60000.0, 0.70),
+                point(0.1, 70000.0, 0.50),
+                point(0.1, 80000.0, 0.60),
+                point(0.4, 70000.0, 0.60),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_smile_interpolation() {
+        let surface = surface();
+        assert!((surface.implied_vol(65000.0, 0.1).unwrap() - 0.60).abs() < 1e-9);
+        // 범위 밖은 끝값 사용
+        assert!((surface.implied_vol(50000.0, 0.1).unwrap() - 0.70).abs() < 1e-9);
+        assert!((surface.implied_vol(70000.0, 0.01).unwrap() - 0.50).abs() < 1e-9);
+        assert!((surface.implied_vol(70000.0, 1.0).unwrap() - 0.60).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_term_structure_interpolation() {
+        let surface = surface();
+        // 총분산 보간: (0.25*0.1 + 0.36*0.4) / 2 / 0.25
+        let iv = surface.implied_vol(70000.0, 0.25).unwrap();
+        let expected = ((0.025 + (0.144 - 0.025) * 0.5) / 0.25_f64).sqrt();
+        assert!((iv - expected).abs() < 1e-9);
+
+        let empty = VolSurface {
+            points: vec![],
+            ..surface
+        };
+        assert!(empty.implied_vol(70000.0, 0.25).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! 버전이 붙은 프리미엄 맵 스냅샷
+//!
+//! 프론트엔드는 `/api/premium`을 계속 폴링하는데 맵 전체가 매번 내려갑니다.
+//! 프리미엄 맵을 갱신할 때마다 값이 바뀐 항목에만 새 버전을 매기고, 맵 버전을
+//! ETag로, 마지막 변경 시각을 Last-Modified로 공시합니다. 클라이언트는 조건부
+//! 요청으로 304를 받거나, 가진 버전 이후 바뀐 항목만 받아 갑니다.
+
+use crate::models::OptionPremium;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+
+/// 삭제 기록 보관 수 (넘치면 오래된 버전부터는 전체 맵으로 재동기화)
+const MAX_TOMBSTONES: usize = 1_000;
+
+/// 만기 + 행사가 (행사가는 비트 패턴으로 정렬 키 사용)
+type EntryKey = (String, u64);
+
+fn entry_key(premium: &OptionPremium) -> EntryKey {
+    (premium.expiry.clone(), premium.strike.to_bits())
+}
+
+#[derive(Debug, Clone)]
+struct VersionedPremium {
+    premium: OptionPremium,
+    version: u64,
+}
+
+#[derive(Debug, Default)]
+struct SnapshotState {
+    version: u64,
+    /// 마지막으로 내용이 바뀐 시각 (Unix 초)
+    updated_at: u64,
+    entries: BTreeMap<EntryKey, VersionedPremium>,
+    /// 삭제된 항목 (삭제된 버전 순)
+    tombstones: Vec<(u64, OptionPremium)>,
+    /// 이 버전 이전부터의 변경분은 재구성할 수 없음
+    floor: u64,
+}
+
+/// 전체 프리미엄 맵 (만기 필터 적용 가능)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PremiumSnapshot {
+    pub version: u64,
+    pub updated_at: u64,
+    pub premiums: Vec<OptionPremium>,
+}
+
+/// `since` 버전 이후 바뀐 항목 (클라이언트는 `removed`를 지운 뒤 `changed`를 덮어씀)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PremiumDelta {
+    pub since: u64,
+    pub version: u64,
+    pub updated_at: u64,
+    /// true면 `since`가 너무 오래되어 `changed`가 전체 맵 (로컬 맵을 교체)
+    pub reset: bool,
+    pub changed: Vec<OptionPremium>,
+    pub removed: Vec<OptionPremium>,
+}
+
+/// 프리미엄 맵 버전 관리
+#[derive(Debug, Default)]
+pub struct PremiumSnapshots {
+    state: RwLock<SnapshotState>,
+}
+
+impl PremiumSnapshots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 새 프리미엄 맵 반영, 바뀐 것이 있으면 버전 증가 후 현재 버전 반환
+    pub fn publish(&self, premiums: Vec<OptionPremium>, now: u64) -> u64 {
+        let mut state = self.state.write().unwrap();
+        let next = state.version + 1;
+        let mut changed = false;
+
+        let incoming: BTreeMap<EntryKey, OptionPremium> =
+            premiums.into_iter().map(|premium| (entry_key(&premium), premium)).collect();
+        let removed: Vec<EntryKey> = state
+            .entries
+            .keys()
+            .filter(|key| !incoming.contains_key(*key))
+            .cloned()
+            .collect();
+        for key in removed {
+            if let Some(entry) = state.entries.remove(&key) {
+                state.tombstones.push((next, entry.premium));
+                changed = true;
+            }
+        }
+        for (key, premium) in incoming {
+            if state.entries.get(&key).is_some_and(|entry| entry.premium == premium) {
+                continue;
+            }
+            // 다시 생긴 항목은 삭제 기록에서 지움
+            state.tombstones.retain(|(_, removed)| entry_key(removed) != key);
+            state.entries.insert(key, VersionedPremium { premium, version: next });
+            changed = true;
+        }
+
+        if changed {
+            state.version = next;
+            state.updated_at = now;
+            if state.tombstones.len() > MAX_TOMBSTONES {
+                let excess = state.tombstones.len() - MAX_TOMBSTONES;
+                let dropped: Vec<_> = state.tombstones.drain(..excess).collect();
+                state.floor = dropped.last().map_or(state.floor, |(version, _)| *version);
+            }
+        }
+        state.version
+    }
+
+    pub fn version(&self) -> u64 {
+        self.state.read().unwrap().version
+    }
+
+    /// 현재 버전의 강한 ETag
+    pub fn etag(&self) -> String {
+        format!("\"premium-v{}\"", self.version())
+    }
+
+    /// 현재 맵 (만기가 주어지면 그 만기만)
+    pub fn snapshot(&self, expiry: Option<&str>) -> PremiumSnapshot {
+        let state = self.state.read().unwrap();
+        PremiumSnapshot {
+            version: state.version,
+            updated_at: state.updated_at,
+            premiums: state
+                .entries
+                .values()
+                .filter(|entry| expiry.is_none_or(|expiry| entry.premium.expiry == expiry))
+                .map(|entry| entry.premium.clone())
+                .collect(),
+        }
+    }
+
+    /// `since` 버전 이후 바뀌거나 삭제된 항목
+    pub fn delta(&self, since: u64) -> PremiumDelta {
+        let state = self.state.read().unwrap();
+        let reset = since < state.floor || since > state.version;
+        let changed = state
+            .entries
+            .values()
+            .filter(|entry| reset || entry.version > since)
+            .map(|entry| entry.premium.clone())
+            .collect();
+        let removed = if reset {
+            Vec::new()
+        } else {
+            state
+                .tombstones
+                .iter()
+                .filter(|(version, _)| *version > since)
+                .map(|(_, premium)| premium.clone())
+                .collect()
+        };
+        PremiumDelta {
+            since,
+            version: state.version,
+            updated_at: state.updated_at,
+            reset,
+            changed,
+            removed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn premium(expiry: &str, strike: f64, call: f64) -> OptionPremium {
+        OptionPremium {
+            strike,
+            expiry: expiry.to_string(),
+            call_premium: call,
+            put_premium: 100.0,
+            implied_volatility: 0.6,
+        }
+    }
+
+    #[test]
+    fn test_versions_track_changed_entries() {
+        let snapshots = PremiumSnapshots::new();
+        let map = vec![
+            premium("2024-02-01", 65000.0, 6000.0),
+            premium("2024-02-01", 70000.0, 2500.0),
+            premium("2024-03-01", 70000.0, 3500.0),
+        ];
+        assert_eq!(snapshots.publish(map.clone(), 100), 1);
+        assert_eq!(snapshots.etag(), "\"premium-v1\"");
+
+        // 같은 맵을 다시 계산하면 버전과 시각 유지
+        assert_eq!(snapshots.publish(map.clone(), 110), 1);
+        assert_eq!(snapshots.snapshot(None).updated_at, 100);
+
+        // 한 항목 변경 + 한 항목 삭제
+        let next = vec![map[0].clone(), premium("2024-02-01", 70000.0, 2600.0)];
+        assert_eq!(snapshots.publish(next, 120), 2);
+        let delta = snapshots.delta(1);
+        assert!(!delta.reset);
+        assert_eq!(delta.changed, vec![premium("2024-02-01", 70000.0, 2600.0)]);
+        assert_eq!(delta.removed, vec![map[2].clone()]);
+        assert!(snapshots.delta(2).changed.is_empty());
+
+        // 모르는 버전은 전체 맵으로 재동기화
+        let stale = snapshots.delta(9);
+        assert!(stale.reset);
+        assert_eq!(stale.changed.len(), 2);
+        assert_eq!(snapshots.snapshot(Some("2024-03-01")).premiums.len(), 0);
+    }
+}
@@ -1,4 +1,9 @@
 use crate::models::OptionParameters;
+use oracle_vm_common::Barrier;
+use pricing_core::{
+    barrier_price, binomial_price, BarrierSpec, BlackScholesInputs, Greeks, MonteCarloConfig,
+    DEFAULT_BINOMIAL_STEPS,
+};
 
 /// Black-Scholes 가격 계산 인터페이스
 pub trait PricingEngine {
@@ -10,7 +15,7 @@ pub trait PricingEngine {
     fn calculate_rho(&self, params: &OptionParameters) -> f64;
 }
 
-/// Black-Scholes 가격 계산 엔진
+/// Black-Scholes 가격 계산 엔진 (`pricing_core` 위임)
 pub struct BlackScholesPricing;
 
 impl BlackScholesPricing {
@@ -18,26 +23,20 @@ impl BlackScholesPricing {
         Self
     }
 
-    /// 표준정규분포 누적밀도함수
-    fn normal_cdf(&self, x: f64) -> f64 {
-        (1.0 + libm::erf(x / 2.0f64.sqrt())) / 2.0
-    }
-
-    /// 표준정규분포 확률밀도함수
-    fn normal_pdf(&self, x: f64) -> f64 {
-        (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
-    }
-
-    /// d1 계산
-    fn calculate_d1(&self, params: &OptionParameters) -> f64 {
-        ((params.spot / params.strike).ln()
-            + (params.risk_free_rate + params.volatility.powi(2) / 2.0) * params.time_to_expiry)
-            / (params.volatility * params.time_to_expiry.sqrt())
+    fn inputs(params: &OptionParameters) -> BlackScholesInputs {
+        BlackScholesInputs {
+            spot: params.spot,
+            strike: params.strike,
+            time_to_expiry: params.time_to_expiry,
+            volatility: params.volatility,
+            risk_free_rate: params.risk_free_rate,
+            is_call: params.is_call,
+        }
     }
 
-    /// d2 계산
-    fn calculate_d2(&self, d1: f64, params: &OptionParameters) -> f64 {
-        d1 - params.volatility * params.time_to_expiry.sqrt()
+    /// 전체 그릭 (베가/로는 1%p, 세타는 1일 기준)
+    pub fn greeks(&self, params: &OptionParameters) -> Greeks {
+        Self::inputs(params).greeks()
     }
 }
 
@@ -49,119 +48,50 @@ impl Default for BlackScholesPricing {
 
 impl PricingEngine for BlackScholesPricing {
     fn calculate_option_price(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return if params.is_call {
-                (params.spot - params.strike).max(0.0)
-            } else {
-                (params.strike - params.spot).max(0.0)
-            };
-        }
-
-        let d1 = self.calculate_d1(params);
-        let d2 = self.calculate_d2(d1, params);
-
-        let n_d1 = self.normal_cdf(d1);
-        let n_d2 = self.normal_cdf(d2);
-        let n_neg_d1 = self.normal_cdf(-d1);
-        let n_neg_d2 = self.normal_cdf(-d2);
-
-        let discount_factor = (-params.risk_free_rate * params.time_to_expiry).exp();
-
-        if params.is_call {
-            params.spot * n_d1 - params.strike * discount_factor * n_d2
-        } else {
-            params.strike * discount_factor * n_neg_d2 - params.spot * n_neg_d1
-        }
+        Self::inputs(params).price()
     }
 
     fn calculate_delta(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return if params.is_call {
-                if params.spot > params.strike {
-                    1.0
-                } else {
-                    0.0
-                }
-            } else if params.spot < params.strike {
-                -1.0
-            } else {
-                0.0
-            };
-        }
-
-        let d1 = self.calculate_d1(params);
-
-        if params.is_call {
-            self.normal_cdf(d1)
-        } else {
-            self.normal_cdf(d1) - 1.0
-        }
+        self.greeks(params).delta
     }
 
     fn calculate_gamma(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return 0.0;
-        }
-
-        let d1 = self.calculate_d1(params);
-        let n_prime_d1 = self.normal_pdf(d1);
-
-        n_prime_d1 / (params.spot * params.volatility * params.time_to_expiry.sqrt())
+        self.greeks(params).gamma
     }
 
     fn calculate_vega(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return 0.0;
-        }
-
-        let d1 = self.calculate_d1(params);
-        let n_prime_d1 = self.normal_pdf(d1);
-
-        params.spot * n_prime_d1 * params.time_to_expiry.sqrt() / 100.0
+        self.greeks(params).vega
     }
 
     fn calculate_theta(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return 0.0;
-        }
-
-        let d1 = self.calculate_d1(params);
-        let d2 = self.calculate_d2(d1, params);
-        let n_prime_d1 = self.normal_pdf(d1);
-        
-        let discount_factor = (-params.risk_free_rate * params.time_to_expiry).exp();
-
-        if params.is_call {
-            let n_d2 = self.normal_cdf(d2);
-            (-(params.spot * n_prime_d1 * params.volatility) / (2.0 * params.time_to_expiry.sqrt())
-                - params.risk_free_rate * params.strike * discount_factor * n_d2) / 365.0
-        } else {
-            let n_neg_d2 = self.normal_cdf(-d2);
-            (-(params.spot * n_prime_d1 * params.volatility) / (2.0 * params.time_to_expiry.sqrt())
-                + params.risk_free_rate * params.strike * discount_factor * n_neg_d2) / 365.0
-        }
+        self.greeks(params).theta
     }
 
     fn calculate_rho(&self, params: &OptionParameters) -> f64 {
-        if params.time_to_expiry <= 0.0 {
-            return 0.0;
-        }
-
-        let d1 = self.calculate_d1(params);
-        let d2 = self.calculate_d2(d1, params);
-        
-        let discount_factor = (-params.risk_free_rate * params.time_to_expiry).exp();
-
-        if params.is_call {
-            let n_d2 = self.normal_cdf(d2);
-            params.strike * params.time_to_expiry * discount_factor * n_d2 / 100.0
-        } else {
-            let n_neg_d2 = self.normal_cdf(-d2);
-            -params.strike * params.time_to_expiry * discount_factor * n_neg_d2 / 100.0
-        }
+        self.greeks(params).rho
     }
 }
 
+/// 미국식 옵션 가격 (`pricing_core` 이항 트리, 만기 전 조기 행사 가치 포함)
+pub fn calculate_american_price(params: &OptionParameters) -> f64 {
+    binomial_price(&BlackScholesPricing::inputs(params), DEFAULT_BINOMIAL_STEPS, true)
+}
+
+/// 배리어 옵션 가격 (`pricing_core` 몬테카를로, 연속 관측 보정 포함)
+pub fn calculate_barrier_price(params: &OptionParameters, barrier: &Barrier) -> f64 {
+    let spec = BarrierSpec {
+        level: barrier.level as f64 / 100.0,
+        up: barrier.is_up(),
+        knock_in: barrier.is_knock_in(),
+    };
+    barrier_price(&BlackScholesPricing::inputs(params), &spec, &MonteCarloConfig::default())
+}
+
+/// 바이너리(cash-or-nothing) 옵션 가격: 지급액 1당 e^(-rT) N(±d2)
+pub fn calculate_binary_price(params: &OptionParameters) -> f64 {
+    BlackScholesPricing::inputs(params).digital_price()
+}
+
 /// 만기일까지 시간 계산 유틸리티
 pub fn calculate_time_to_expiry(expiry: &str) -> f64 {
     // 실제 구현에서는 chrono 등을 사용하여 정확한 날짜 계산
@@ -216,5 +146,8 @@ mod tests {
 
         let vega = pricing.calculate_vega(&params);
         assert!(vega > 0.0);
+
+        let rho = pricing.calculate_rho(&params);
+        assert!(rho > 0.0);
     }
 }
\ No newline at end of file
@@ -0,0 +1,255 @@
+//! 상품 템플릿과 파라미터 프리셋
+//!
+//! "주간 10-delta 콜", "월간 ATM 스트래들"처럼 자주 쓰는 조합을 템플릿으로
+//! 등록해 두면 클라이언트는 템플릿 ID와 수량만으로 호가를 받습니다. 템플릿은
+//! 행사가 선택 규칙, 만기 시리즈, 수량 한도, 수수료율을 담고, 실제 호가는
+//! 레그마다 `QuoteService::request_quote`로 발행되어 각각 서명됩니다.
+
+use btcfi_contracts::fees::FeeSchedule;
+use oracle_vm_common::{Expiry, ExpiryCalendar, ExpiryKind, OptionQuote, OptionType, PricingError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 행사가 선택 규칙
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum StrikeRule {
+    /// 현물가에 가장 가까운 행사가
+    Atm,
+    /// 절대 델타가 목표에 가장 가까운 행사가 (0.10 = 10-delta)
+    Delta { delta: f64 },
+    /// 현물 대비 비율 (1.05 = 현물보다 5% 위)
+    Moneyness { ratio: f64 },
+}
+
+/// 템플릿의 옵션 한 개
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLeg {
+    pub option_type: OptionType,
+    pub strike: StrikeRule,
+}
+
+/// 상품 템플릿
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProductTemplate {
+    pub id: String,
+    pub name: String,
+    pub legs: Vec<TemplateLeg>,
+    /// 이 시리즈 이상에 속한 가장 가까운 상장 만기 (Weekly면 월간 만기도 포함)
+    pub expiry: ExpiryKind,
+    /// 이보다 가까운 만기는 건너뜀 (초)
+    #[serde(default)]
+    pub min_time_to_expiry_secs: u64,
+    /// 행사가 단위 (USD cents)
+    pub strike_step: u64,
+    /// 레그당 최소 수량 (satoshis)
+    pub min_quantity: u64,
+    /// 레그당 최대 수량 (satoshis)
+    pub max_quantity: u64,
+    /// 공시 수수료율 (Contracts 풀 설정과 같아야 함)
+    #[serde(default)]
+    pub fees: FeeSchedule,
+}
+
+impl ProductTemplate {
+    pub fn validate(&self) -> Result<(), PricingError> {
+        let invalid = |reason: &str| {
+            Err(PricingError::InvalidInput(format!("Template {}: {}", self.id, reason)))
+        };
+        if self.id.is_empty() {
+            return invalid("empty id");
+        }
+        if self.legs.is_empty() {
+            return invalid("no legs");
+        }
+        for leg in &self.legs {
+            match leg.strike {
+                StrikeRule::Delta { delta } if delta.is_nan() || delta <= 0.0 || delta >= 1.0 => {
+                    return invalid("delta must be in (0, 1)")
+                }
+                StrikeRule::Moneyness { ratio } if !ratio.is_finite() || ratio <= 0.0 => {
+                    return invalid("moneyness ratio must be positive")
+                }
+                _ => {}
+            }
+        }
+        if self.strike_step == 0 {
+            return invalid("strike step must be positive");
+        }
+        if self.min_quantity == 0 || self.min_quantity > self.max_quantity {
+            return invalid("quantity range is empty");
+        }
+        self.fees
+            .validate()
+            .map_err(|e| PricingError::InvalidInput(format!("Template {}: {}", self.id, e)))
+    }
+
+    /// 레그당 수량 한도 확인
+    pub fn check_quantity(&self, quantity: u64) -> Result<(), PricingError> {
+        if quantity > self.max_quantity {
+            return Err(PricingError::OrderTooLarge {
+                size: quantity as f64 / 100_000_000.0,
+                max: self.max_quantity as f64 / 100_000_000.0,
+            });
+        }
+        if quantity < self.min_quantity {
+            return Err(PricingError::InvalidInput(format!(
+                "Quantity {} sats is below the {} minimum of {} sats",
+                quantity, self.id, self.min_quantity
+            )));
+        }
+        Ok(())
+    }
+
+    /// 템플릿 시리즈의 가장 가까운 상장 만기
+    pub fn select_expiry(&self, calendar: &ExpiryCalendar, now: u64) -> Result<Expiry, PricingError> {
+        calendar
+            .expiries(now)
+            .into_iter()
+            .find(|expiry| {
+                expiry.kind >= self.expiry && expiry.timestamp >= now + self.min_time_to_expiry_secs
+            })
+            .ok_or_else(|| {
+                PricingError::InvalidInput(format!("No listed {:?} expiry for template {}", self.expiry, self.id))
+            })
+    }
+
+    /// 행사가를 가장 가까운 단위로 반올림 (USD → cents, 최소 한 단위)
+    pub fn round_strike(&self, strike: f64) -> u64 {
+        let steps = (strike * 100.0 / self.strike_step as f64).round().max(1.0);
+        steps as u64 * self.strike_step
+    }
+}
+
+/// 등록된 상품 템플릿
+#[derive(Debug, Clone)]
+pub struct ProductRegistry {
+    templates: Vec<ProductTemplate>,
+}
+
+impl Default for ProductRegistry {
+    fn default() -> Self {
+        let template = |id: &str, name: &str, legs: Vec<TemplateLeg>, expiry| ProductTemplate {
+            id: id.to_string(),
+            name: name.to_string(),
+            legs,
+            expiry,
+            min_time_to_expiry_secs: 86_400,
+            strike_step: 100_000, // $1,000
+            min_quantity: 1_000_000,
+            max_quantity: 100_000_000,
+            fees: FeeSchedule::default(),
+        };
+        let leg = |option_type, strike| TemplateLeg { option_type, strike };
+        Self {
+            templates: vec![
+                template(
+                    "weekly-10d-call",
+                    "Weekly 10-delta call",
+                    vec![leg(OptionType::Call, StrikeRule::Delta { delta: 0.10 })],
+                    ExpiryKind::Weekly,
+                ),
+                template(
+                    "weekly-25d-put",
+                    "Weekly 25-delta put",
+                    vec![leg(OptionType::Put, StrikeRule::Delta { delta: 0.25 })],
+                    ExpiryKind::Weekly,
+                ),
+                template(
+                    "monthly-atm-straddle",
+                    "Monthly ATM straddle",
+                    vec![leg(OptionType::Call, StrikeRule::Atm), leg(OptionType::Put, StrikeRule::Atm)],
+                    ExpiryKind::Monthly,
+                ),
+            ],
+        }
+    }
+}
+
+impl ProductRegistry {
+    /// 템플릿 검증 후 등록 (ID 중복 거부)
+    pub fn new(templates: Vec<ProductTemplate>) -> Result<Self, PricingError> {
+        let mut ids = HashSet::new();
+        for template in &templates {
+            template.validate()?;
+            if !ids.insert(template.id.as_str()) {
+                return Err(PricingError::InvalidInput(format!("Duplicate template id {}", template.id)));
+            }
+        }
+        Ok(Self { templates })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ProductTemplate> {
+        self.templates.iter().find(|template| template.id == id)
+    }
+
+    pub fn templates(&self) -> &[ProductTemplate] {
+        &self.templates
+    }
+}
+
+/// 템플릿 호가 요청 (행사가와 만기는 템플릿이 정함)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductQuoteRequest {
+    /// 레그당 수량 (satoshis)
+    pub quantity: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+}
+
+/// 템플릿 호가 (레그별 서명 호가와 합계)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductQuote {
+    pub template_id: String,
+    pub expiry: Expiry,
+    pub legs: Vec<OptionQuote>,
+    /// 레그 프리미엄 합계 (satoshis)
+    pub total_premium: u64,
+    pub fees: FeeSchedule,
+    /// 합계 프리미엄 기준 프로토콜 수수료 (satoshis)
+    pub protocol_fee: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_templates_are_valid_and_select_series_expiry() {
+        let registry = ProductRegistry::new(ProductRegistry::default().templates().to_vec()).unwrap();
+        let weekly = registry.get("weekly-10d-call").unwrap();
+        let monthly = registry.get("monthly-atm-straddle").unwrap();
+        assert!(registry.get("unknown").is_none());
+
+        // 2024-01-10 12:00 UTC (수요일): 주간 = 1/12 금요일, 월간 = 1/26 마지막 금요일
+        let now = 1_704_888_000;
+        let calendar = ExpiryCalendar::default();
+        assert_eq!(weekly.select_expiry(&calendar, now).unwrap().date, "2024-01-12");
+        assert_eq!(monthly.select_expiry(&calendar, now).unwrap().date, "2024-01-26");
+
+        // 최소 만기 시간 안쪽의 금요일은 건너뜀 (목요일 12:00 → 다음 주 금요일)
+        assert_eq!(weekly.select_expiry(&calendar, now + 86_400).unwrap().date, "2024-01-19");
+
+        assert_eq!(weekly.round_strike(70_420.0), 7_000_000);
+        assert!(weekly.check_quantity(1_000_000).is_ok());
+        assert!(weekly.check_quantity(999_999).is_err());
+        assert!(matches!(
+            weekly.check_quantity(100_000_001),
+            Err(PricingError::OrderTooLarge { .. })
+        ));
+
+        let mut duplicate = registry.templates().to_vec();
+        duplicate.push(weekly.clone());
+        assert!(ProductRegistry::new(duplicate).is_err());
+        let invalid = ProductTemplate {
+            legs: vec![TemplateLeg {
+                option_type: OptionType::Call,
+                strike: StrikeRule::Delta { delta: 1.5 },
+            }],
+            ..weekly.clone()
+        };
+        assert!(ProductRegistry::new(vec![invalid]).is_err());
+    }
+}
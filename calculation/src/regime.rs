@@ -0,0 +1,224 @@
+//! 가격 점프 감지와 변동성 국면 전환
+//!
+//! 합의 가격의 로그 수익률을 최근 구간 평균/표준편차로 표준화(Z-score)해서
+//! 임계값을 넘는 점프가 나오면 고변동성 국면으로 전환합니다. 고변동성 국면에서는
+//! 프리미엄 맵의 IV를 넓히고 RiskEngine의 거래당 수량 한도를 줄이며, 점프 없는
+//! 표본이 일정 수 이상 이어지면 평상 국면으로 돌아갑니다.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// 국면 감지 설정
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegimeConfig {
+    /// Z-score 계산에 쓰는 최근 수익률 수
+    pub window: usize,
+    /// 이 값 이상의 |Z|를 점프로 판단
+    pub z_threshold: f64,
+    /// 고변동성 국면 해제까지 필요한 연속 평상 표본 수
+    pub calm_samples: usize,
+    /// 고변동성 국면의 IV 배율
+    pub iv_multiplier: f64,
+    /// 고변동성 국면의 거래당 수량 한도 배율
+    pub size_multiplier: f64,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            window: 60,
+            z_threshold: 4.0,
+            calm_samples: 30,
+            iv_multiplier: 1.25,
+            size_multiplier: 0.5,
+        }
+    }
+}
+
+/// 변동성 국면
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolRegime {
+    Normal,
+    HighVol,
+}
+
+/// 현재 국면 (시장 상태 API에 노출)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RegimeState {
+    pub regime: VolRegime,
+    /// 마지막 수익률의 Z-score (표본이 부족하면 None)
+    pub last_z: Option<f64>,
+    /// 고변동성 국면으로 전환된 시각
+    pub triggered_at: Option<u64>,
+    /// 고변동성 국면에서 이어진 평상 표본 수
+    pub calm_streak: usize,
+    pub iv_multiplier: f64,
+    pub size_multiplier: f64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    last_price: Option<(u64, f64)>,
+    returns: VecDeque<f64>,
+    state: RegimeState,
+}
+
+/// 수익률 Z-score 기반 국면 감지기 (서비스 간 공유)
+#[derive(Debug)]
+pub struct RegimeDetector {
+    config: RegimeConfig,
+    inner: Mutex<Inner>,
+}
+
+impl RegimeDetector {
+    pub fn new(config: RegimeConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                last_price: None,
+                returns: VecDeque::new(),
+                state: RegimeState {
+                    regime: VolRegime::Normal,
+                    last_z: None,
+                    triggered_at: None,
+                    calm_streak: 0,
+                    iv_multiplier: 1.0,
+                    size_multiplier: 1.0,
+                },
+            }),
+        }
+    }
+
+    pub fn state(&self) -> RegimeState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// 프리미엄 계산에 곱할 IV 배율
+    pub fn iv_multiplier(&self) -> f64 {
+        self.state().iv_multiplier
+    }
+
+    /// 거래당 수량 한도에 곱할 배율
+    pub fn size_multiplier(&self) -> f64 {
+        self.state().size_multiplier
+    }
+
+    /// 합의 가격 표본 반영 (이전 표본보다 오래되었거나 가격이 0이면 무시)
+    pub fn observe(&self, timestamp: u64, price: f64) -> RegimeState {
+        let mut inner = self.inner.lock().unwrap();
+        let previous = inner.last_price;
+        if price <= 0.0 || previous.is_some_and(|(last, _)| timestamp <= last) {
+            return inner.state;
+        }
+        inner.last_price = Some((timestamp, price));
+        let Some((_, previous_price)) = previous else {
+            return inner.state;
+        };
+
+        let ret = (price / previous_price).ln();
+        let z = z_score(&inner.returns, ret);
+        inner.returns.push_back(ret);
+        while inner.returns.len() > self.config.window {
+            inner.returns.pop_front();
+        }
+
+        let jump = z.is_some_and(|z| z.abs() >= self.config.z_threshold);
+        let state = &mut inner.state;
+        state.last_z = z;
+        match (state.regime, jump) {
+            (_, true) => {
+                if state.regime == VolRegime::Normal {
+                    state.triggered_at = Some(timestamp);
+                }
+                state.regime = VolRegime::HighVol;
+                state.calm_streak = 0;
+            }
+            (VolRegime::HighVol, false) => {
+                state.calm_streak += 1;
+                if state.calm_streak >= self.config.calm_samples {
+                    state.regime = VolRegime::Normal;
+                    state.triggered_at = None;
+                    state.calm_streak = 0;
+                }
+            }
+            (VolRegime::Normal, false) => {}
+        }
+        (state.iv_multiplier, state.size_multiplier) = match state.regime {
+            VolRegime::Normal => (1.0, 1.0),
+            VolRegime::HighVol => (self.config.iv_multiplier, self.config.size_multiplier),
+        };
+        *state
+    }
+}
+
+impl Default for RegimeDetector {
+    fn default() -> Self {
+        Self::new(RegimeConfig::default())
+    }
+}
+
+/// 이전 수익률 분포 기준 Z-score (표본 10개 미만이거나 분산이 0이면 None)
+fn z_score(returns: &VecDeque<f64>, ret: f64) -> Option<f64> {
+    if returns.len() < 10 {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (variance > 0.0).then(|| (ret - mean) / variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ±0.1% 교대로 움직이는 평상 구간
+    fn feed_calm(detector: &RegimeDetector, start: u64, count: u64, price: &mut f64) {
+        for i in 0..count {
+            *price *= if i % 2 == 0 { 1.001 } else { 0.999 };
+            detector.observe(start + i * 60, *price);
+        }
+    }
+
+    #[test]
+    fn test_jump_switches_regime_and_calm_restores() {
+        let detector = RegimeDetector::new(RegimeConfig {
+            calm_samples: 5,
+            ..RegimeConfig::default()
+        });
+        let mut price = 70_000.0;
+        detector.observe(0, price);
+        feed_calm(&detector, 60, 30, &mut price);
+        assert_eq!(detector.state().regime, VolRegime::Normal);
+        assert_eq!(detector.iv_multiplier(), 1.0);
+
+        // 5% 급락
+        price *= 0.95;
+        let state = detector.observe(10_000, price);
+        assert_eq!(state.regime, VolRegime::HighVol);
+        assert!(state.last_z.unwrap() < -4.0);
+        assert_eq!(state.triggered_at, Some(10_000));
+        assert_eq!(detector.iv_multiplier(), 1.25);
+        assert_eq!(detector.size_multiplier(), 0.5);
+
+        feed_calm(&detector, 10_060, 4, &mut price);
+        assert_eq!(detector.state().regime, VolRegime::HighVol);
+        feed_calm(&detector, 20_000, 1, &mut price);
+        assert_eq!(detector.state().regime, VolRegime::Normal);
+        assert_eq!(detector.size_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_ignores_stale_and_sparse_samples() {
+        let detector = RegimeDetector::default();
+        detector.observe(100, 70_000.0);
+        // 표본이 부족하면 큰 움직임도 Z-score 없음
+        let state = detector.observe(160, 60_000.0);
+        assert_eq!(state.last_z, None);
+        assert_eq!(state.regime, VolRegime::Normal);
+        assert_eq!(detector.observe(100, 1.0), state);
+    }
+}
@@ -1,5 +1,7 @@
-use crate::models::{DeltaInfo, MarketState, OptionPremium};
+use crate::models::{DeltaInfo, MarketState, OptionPremium, VolSurface};
+use crate::theta_targeting::OptionPosition;
 use async_trait::async_trait;
+use oracle_vm_common::{OpenInterest, OpenInterestEntry};
 use std::collections::HashMap;
 use std::sync::RwLock;
 
@@ -26,6 +28,29 @@ pub trait MarketDataRepository: Send + Sync {
     async fn update_state(&self, state: MarketState) -> Result<(), String>;
 }
 
+/// 풀 옵션 포지션 저장소 인터페이스
+#[async_trait]
+pub trait PositionRepository: Send + Sync {
+    async fn get_positions(&self) -> Result<Vec<OptionPosition>, String>;
+    async fn add_position(&self, position: OptionPosition) -> Result<(), String>;
+}
+
+/// IV 곡면 저장소 인터페이스
+#[async_trait]
+pub trait VolSurfaceRepository: Send + Sync {
+    async fn get_surface(&self) -> Result<Option<VolSurface>, String>;
+    async fn update_surface(&self, surface: VolSurface) -> Result<(), String>;
+}
+
+/// 행사가/만기별 미결제약정 저장소 인터페이스 (잠긴 담보 satoshis)
+#[async_trait]
+pub trait OpenInterestRepository: Send + Sync {
+    /// `strike_price` 행사가(같은 만기)와 `expiry` 만기 전체의 미결제약정
+    async fn get_open_interest(&self, strike_price: u64, expiry: &str) -> Result<OpenInterest, String>;
+    /// contracts가 보고한 활성 옵션 기준 표로 전체 교체 (종료/행사/만기분이 빠짐)
+    async fn replace_open_interest(&self, entries: &[OpenInterestEntry]) -> Result<(), String>;
+}
+
 /// 인메모리 프리미엄 저장소 구현
 pub struct InMemoryPremiumRepo {
     data: RwLock<HashMap<String, Vec<OptionPremium>>>,
@@ -138,6 +163,118 @@ impl MarketDataRepository for InMemoryMarketRepo {
     }
 }
 
+/// 인메모리 포지션 저장소 구현
+pub struct InMemoryPositionRepo {
+    positions: RwLock<Vec<OptionPosition>>,
+}
+
+impl InMemoryPositionRepo {
+    pub fn new() -> Self {
+        Self {
+            positions: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Default for InMemoryPositionRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PositionRepository for InMemoryPositionRepo {
+    async fn get_positions(&self) -> Result<Vec<OptionPosition>, String> {
+        let positions = self.positions.read().map_err(|_| "Lock error")?;
+        Ok(positions.clone())
+    }
+
+    async fn add_position(&self, position: OptionPosition) -> Result<(), String> {
+        let mut positions = self.positions.write().map_err(|_| "Lock error")?;
+        positions.push(position);
+        Ok(())
+    }
+}
+
+/// 인메모리 IV 곡면 저장소 구현
+pub struct InMemoryVolSurfaceRepo {
+    surface: RwLock<Option<VolSurface>>,
+}
+
+impl InMemoryVolSurfaceRepo {
+    pub fn new() -> Self {
+        Self {
+            surface: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for InMemoryVolSurfaceRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VolSurfaceRepository for InMemoryVolSurfaceRepo {
+    async fn get_surface(&self) -> Result<Option<VolSurface>, String> {
+        let surface = self.surface.read().map_err(|_| "Lock error")?;
+        Ok(surface.clone())
+    }
+
+    async fn update_surface(&self, surface: VolSurface) -> Result<(), String> {
+        let mut current = self.surface.write().map_err(|_| "Lock error")?;
+        *current = Some(surface);
+        Ok(())
+    }
+}
+
+/// 인메모리 미결제약정 저장소 구현
+pub struct InMemoryOpenInterestRepo {
+    /// (만기, 행사가) → 담보
+    collateral: RwLock<HashMap<(String, u64), u64>>,
+}
+
+impl InMemoryOpenInterestRepo {
+    pub fn new() -> Self {
+        Self {
+            collateral: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryOpenInterestRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OpenInterestRepository for InMemoryOpenInterestRepo {
+    async fn get_open_interest(&self, strike_price: u64, expiry: &str) -> Result<OpenInterest, String> {
+        let collateral = self.collateral.read().map_err(|_| "Lock error")?;
+        Ok(collateral
+            .iter()
+            .filter(|((bucket, _), _)| bucket == expiry)
+            .fold(OpenInterest::default(), |mut open, ((_, strike), amount)| {
+                open.expiry += amount;
+                if *strike == strike_price {
+                    open.strike += amount;
+                }
+                open
+            }))
+    }
+
+    async fn replace_open_interest(&self, entries: &[OpenInterestEntry]) -> Result<(), String> {
+        let mut buckets = self.collateral.write().map_err(|_| "Lock error")?;
+        buckets.clear();
+        for entry in entries {
+            *buckets.entry((entry.expiry.clone(), entry.strike_price)).or_default() += entry.collateral;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,1126 @@
+//! 옵션 호가 요청 (RFQ)
+//!
+//! 클라이언트의 (유형, 행사가, 만기, 수량) 요청에 대해 짧은 유효시간을 가진
+//! 확정 호가를 발행하고 서명합니다. Contracts는 서명이 유효하고 만료되지 않은
+//! 호가로만 옵션을 생성하므로, 오래된 가격으로 옵션을 사는 것을 막습니다.
+
+use crate::models::OptionParameters;
+use crate::pricing::{
+    calculate_american_price, calculate_barrier_price, calculate_binary_price, calculate_time_to_expiry,
+    PricingEngine,
+};
+use crate::products::{ProductQuote, ProductQuoteRequest, ProductTemplate, StrikeRule};
+use crate::repositories::{
+    MarketDataRepository, OpenInterestRepository, PoolStateRepository, VolSurfaceRepository,
+};
+use crate::skew::InventorySkew;
+use crate::theta_targeting::UtilizationCurve;
+use oracle_vm_common::crypto::{public_key_from_secret, PublicKey, SecretKey};
+use oracle_vm_common::settlement_currency::sats_to_cents;
+use oracle_vm_common::{
+    Barrier, BuyBackQuote, BuyBackRequest, ContractSpec, ExercisePolicy, ExerciseStyle, ExpiryCalendar,
+    GreeksLimits, OpenInterest, OpenInterestCapacity, OpenInterestCaps, OpenInterestEntry, OptionQuote, OptionType, Payoff,
+    PricingError, QuoteRequest,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// 기본 호가 유효시간 (초)
+pub const DEFAULT_QUOTE_TTL_SECS: u64 = 30;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// 확정 호가 발행 서비스
+pub struct QuoteService<P> {
+    pricing_engine: P,
+    market_repo: Arc<dyn MarketDataRepository>,
+    vol_repo: Option<Arc<dyn VolSurfaceRepository>>,
+    pool_repo: Option<Arc<dyn PoolStateRepository>>,
+    curve: UtilizationCurve,
+    skew: Option<InventorySkew>,
+    calendar: Option<ExpiryCalendar>,
+    contract_spec: Option<ContractSpec>,
+    exercise_policy: ExercisePolicy,
+    greeks_limits: GreeksLimits,
+    open_interest_repo: Option<Arc<dyn OpenInterestRepository>>,
+    open_interest_caps: OpenInterestCaps,
+    signing_key: SecretKey,
+    public_key: PublicKey,
+    ttl_secs: u64,
+    sequence: AtomicU64,
+}
+
+impl<P> QuoteService<P>
+where
+    P: PricingEngine,
+{
+    pub fn new(
+        pricing_engine: P,
+        market_repo: Arc<dyn MarketDataRepository>,
+        signing_key: SecretKey,
+    ) -> Self {
+        let public_key = public_key_from_secret(&signing_key);
+        Self {
+            pricing_engine,
+            market_repo,
+            vol_repo: None,
+            pool_repo: None,
+            curve: UtilizationCurve::default(),
+            skew: None,
+            calendar: None,
+            contract_spec: None,
+            exercise_policy: ExercisePolicy::default(),
+            greeks_limits: GreeksLimits::default(),
+            open_interest_repo: None,
+            open_interest_caps: OpenInterestCaps::default(),
+            signing_key,
+            public_key,
+            ttl_secs: DEFAULT_QUOTE_TTL_SECS,
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    /// 외부 IV 곡면 사용 (없는 구간은 volatility_24h로 대체)
+    pub fn with_vol_surface(mut self, vol_repo: Arc<dyn VolSurfaceRepository>) -> Self {
+        self.vol_repo = Some(vol_repo);
+        self
+    }
+
+    /// 풀 사용률/델타 기반 프리미엄 가산 및 주문 한도 적용
+    pub fn with_pool_curve(
+        mut self,
+        pool_repo: Arc<dyn PoolStateRepository>,
+        curve: UtilizationCurve,
+    ) -> Self {
+        self.pool_repo = Some(pool_repo);
+        self.curve = curve;
+        self
+    }
+
+    /// 풀 재고 기반 스큐 적용: 호가 프리미엄은 재고만큼 이동한 ask
+    /// (재고는 `with_pool_curve`의 풀 상태에서 읽음)
+    pub fn with_inventory_skew(mut self, skew: InventorySkew) -> Self {
+        self.skew = Some(skew);
+        self
+    }
+
+    /// 만기 캘린더 적용: 캘린더 만기만 호가 (OTC 요청 제외)
+    pub fn with_calendar(mut self, calendar: ExpiryCalendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// 표준 계약 단위 적용: 수량은 계약 단위의 배수, 프리미엄은 틱 단위로 올림
+    pub fn with_contract_spec(mut self, spec: ContractSpec) -> Self {
+        self.contract_spec = Some(spec);
+        self
+    }
+
+    /// 자동 행사/dust 정책 공시 (호가에 포함되어 서명됨, Contracts 설정과 같아야 함)
+    pub fn with_exercise_policy(mut self, policy: ExercisePolicy) -> Self {
+        self.exercise_policy = policy;
+        self
+    }
+
+    /// 거래당 델타/베가 한도 적용: 초과 요청은 `allow_partial`이면 줄인 수량으로
+    /// 호가하고, 아니면 최대 수량과 분할 일정을 담아 거부
+    pub fn with_greeks_limits(mut self, limits: GreeksLimits) -> Self {
+        self.greeks_limits = limits;
+        self
+    }
+
+    pub fn greeks_limits(&self) -> &GreeksLimits {
+        &self.greeks_limits
+    }
+
+    /// 행사가/만기별 미결제약정 한도 적용 (풀 유동성은 `with_pool_curve`의 풀 상태):
+    /// 남은 용량을 넘는 요청은 `allow_partial`이면 줄인 수량으로 호가하고, 아니면 거부
+    pub fn with_open_interest_caps(
+        mut self,
+        open_interest_repo: Arc<dyn OpenInterestRepository>,
+        caps: OpenInterestCaps,
+    ) -> Self {
+        self.open_interest_repo = Some(open_interest_repo);
+        self.open_interest_caps = caps;
+        self
+    }
+
+    /// contracts가 보고한 행사가/만기별 잠긴 담보로 미결제약정 교체
+    pub async fn sync_open_interest(&self, entries: &[OpenInterestEntry]) -> Result<(), PricingError> {
+        if let Some(repo) = &self.open_interest_repo {
+            repo.replace_open_interest(entries).await?;
+        }
+        Ok(())
+    }
+
+    /// 행사가/만기에 새로 잠글 수 있는 담보 (한도나 풀 상태가 없으면 무제한)
+    pub async fn open_interest_capacity(
+        &self,
+        strike_price: u64,
+        expiry: &str,
+        otc: bool,
+        now: u64,
+    ) -> Result<OpenInterestCapacity, PricingError> {
+        Ok(match self.open_interest_inputs(strike_price, expiry, otc, now).await? {
+            Some((pool_liquidity, open, secs_to_expiry)) => {
+                self.open_interest_caps.capacity(pool_liquidity, open, secs_to_expiry)
+            }
+            None => OpenInterestCapacity::default(),
+        })
+    }
+
+    /// (풀 유동성 sats, 미결제약정, 만기까지 남은 초)
+    async fn open_interest_inputs(
+        &self,
+        strike_price: u64,
+        expiry: &str,
+        otc: bool,
+        now: u64,
+    ) -> Result<Option<(u64, OpenInterest, u64)>, PricingError> {
+        let (Some(open_interest_repo), Some(pool_repo)) = (&self.open_interest_repo, &self.pool_repo) else {
+            return Ok(None);
+        };
+        if self.open_interest_caps.is_unlimited() {
+            return Ok(None);
+        }
+        let total_liquidity = pool_repo.get_delta_info().await?.pool_capacity().total_liquidity;
+        let secs_to_expiry = match &self.calendar {
+            Some(calendar) => calendar.resolve(expiry, now, otc)?.saturating_sub(now),
+            None => (calculate_time_to_expiry(expiry) * SECONDS_PER_YEAR) as u64,
+        };
+        Ok(Some((
+            (total_liquidity * 100_000_000.0) as u64,
+            open_interest_repo.get_open_interest(strike_price, expiry).await?,
+            secs_to_expiry,
+        )))
+    }
+
+    pub fn exercise_policy(&self) -> &ExercisePolicy {
+        &self.exercise_policy
+    }
+
+    pub fn curve(&self) -> &UtilizationCurve {
+        &self.curve
+    }
+
+    pub fn inventory_skew(&self) -> Option<&InventorySkew> {
+        self.skew.as_ref()
+    }
+
+    /// 호가 서명 검증용 공개키 (Contracts에 등록)
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    /// BTC 1개당 이론가 (USD): 유럽식은 가격 엔진, 미국식은 이항 트리,
+    /// 배리어는 몬테카를로 (미국식 배리어는 지원하지 않음)
+    ///
+    /// 바이너리는 지급액 1 BTC당 가치(현물 × e^(-rT) N(±d2))로 환산해 같은 sats
+    /// 변환을 타며, 유럽식 배리어 없는 옵션만 지원합니다.
+    fn theoretical_price(
+        &self,
+        params: &OptionParameters,
+        style: ExerciseStyle,
+        barrier: Option<&Barrier>,
+        payoff: Payoff,
+    ) -> Result<f64, PricingError> {
+        match (style, barrier, payoff) {
+            (ExerciseStyle::European, None, Payoff::Vanilla) => {
+                Ok(self.pricing_engine.calculate_option_price(params))
+            }
+            (ExerciseStyle::American, None, Payoff::Vanilla) => Ok(calculate_american_price(params)),
+            (ExerciseStyle::European, Some(barrier), Payoff::Vanilla) => {
+                Ok(calculate_barrier_price(params, barrier))
+            }
+            (ExerciseStyle::European, None, Payoff::Binary) => Ok(calculate_binary_price(params) * params.spot),
+            (ExerciseStyle::American, Some(_), _) => Err(PricingError::InvalidInput(
+                "Barrier options are European-style only".to_string(),
+            )),
+            (_, _, Payoff::Binary) => Err(PricingError::InvalidInput(
+                "Binary options are European-style without a barrier".to_string(),
+            )),
+        }
+    }
+
+    /// 확정 호가 발행
+    pub async fn request_quote(
+        &self,
+        request: &QuoteRequest,
+        now: u64,
+    ) -> Result<OptionQuote, PricingError> {
+        if request.quantity == 0 || request.strike_price == 0 {
+            return Err(PricingError::InvalidInput(
+                "Strike and quantity must be positive".to_string(),
+            ));
+        }
+        if let Some(spec) = &self.contract_spec {
+            spec.contracts(request.quantity)?;
+        }
+
+        let market_state = self.market_repo.get_current_state().await?;
+        let spot = market_state.current_price;
+        if spot <= 0.0 {
+            return Err(PricingError::NoMarketData("No spot price available".to_string()));
+        }
+
+        let strike = request.strike_price as f64 / 100.0;
+        let time_to_expiry = match &self.calendar {
+            Some(calendar) => {
+                let expiry_at = calendar.resolve(&request.expiry, now, request.otc)?;
+                (expiry_at - now) as f64 / SECONDS_PER_YEAR
+            }
+            None => calculate_time_to_expiry(&request.expiry),
+        };
+        let surface = match &self.vol_repo {
+            Some(repo) => repo.get_surface().await?,
+            None => None,
+        };
+        let volatility = surface
+            .as_ref()
+            .and_then(|surface| surface.implied_vol(strike, time_to_expiry))
+            .unwrap_or(market_state.volatility_24h);
+
+        let params = OptionParameters {
+            spot,
+            strike,
+            time_to_expiry,
+            volatility,
+            risk_free_rate: 0.05,
+            is_call: request.option_type == OptionType::Call,
+        };
+
+        // 거래당 그릭 한도 (그릭은 수량에 비례)
+        let lot = self.contract_spec.map_or(1, |spec| spec.contract_size);
+        let mut quantity = request.quantity;
+        let requested_btc = quantity as f64 / 100_000_000.0;
+        if let Err(e) = self.greeks_limits.check(
+            quantity,
+            self.pricing_engine.calculate_delta(&params) * requested_btc,
+            self.pricing_engine.calculate_vega(&params) * requested_btc,
+            lot,
+        ) {
+            match e {
+                PricingError::GreeksLimitExceeded { max_quantity, .. }
+                    if request.allow_partial && max_quantity > 0 =>
+                {
+                    quantity = max_quantity;
+                }
+                e => return Err(e),
+            }
+        }
+
+        // 행사가/만기 미결제약정 한도 (담보 기준, 만기가 가까울수록 축소)
+        if let Some((pool_liquidity, open, secs_to_expiry)) = self
+            .open_interest_inputs(request.strike_price, &request.expiry, request.otc, now)
+            .await?
+        {
+            // 수량 1 sat당 담보 (풋은 행사가 기준 BTC 환산)
+            let collateral_per_sat = match (request.payoff, request.option_type) {
+                (Payoff::Binary, _) | (_, OptionType::Call) => 1.0,
+                (_, OptionType::Put) => strike / spot,
+            };
+            let collateral = (quantity as f64 * collateral_per_sat).ceil() as u64;
+            if let Err(e) = self
+                .open_interest_caps
+                .check(collateral, pool_liquidity, open, secs_to_expiry)
+            {
+                let remaining = self
+                    .open_interest_caps
+                    .capacity(pool_liquidity, open, secs_to_expiry)
+                    .remaining
+                    .unwrap_or(0);
+                let fits = (remaining as f64 / collateral_per_sat).floor() as u64 / lot.max(1) * lot.max(1);
+                if !request.allow_partial || fits == 0 {
+                    return Err(e);
+                }
+                quantity = quantity.min(fits);
+            }
+        }
+
+        // 풀 상태 기준 가산 배율과 재고 비율 (풀은 옵션 매도자)
+        let notional_btc = quantity as f64 / 100_000_000.0;
+        let (multiplier, inventory_ratio) = match &self.pool_repo {
+            Some(repo) => {
+                let delta_info = repo.get_delta_info().await?;
+                // 바이너리는 지급액(수량)만 담보로 잠금
+                let collateral = match (request.payoff, request.option_type) {
+                    (Payoff::Binary, _) | (_, OptionType::Call) => notional_btc,
+                    (_, OptionType::Put) => strike / spot * notional_btc,
+                };
+                let order_delta = -self.pricing_engine.calculate_delta(&params) * notional_btc;
+                let multiplier = self.curve.adjustment(
+                    &delta_info.pool_capacity(),
+                    notional_btc,
+                    collateral,
+                    order_delta,
+                )?;
+                (multiplier, InventorySkew::inventory_ratio(&delta_info, request.option_type))
+            }
+            None => (1.0, 0.0),
+        };
+
+        // BTC 1개당 USD 프리미엄 → 수량 기준 satoshis (호가는 스큐 적용 ask)
+        let theoretical_usd =
+            self.theoretical_price(&params, request.style, request.barrier.as_ref(), request.payoff)?;
+        let premium_usd = match &self.skew {
+            Some(skew) => skew.quote(theoretical_usd * multiplier, inventory_ratio).ask,
+            None => theoretical_usd * multiplier,
+        };
+        let to_sats = |usd: f64| (usd / spot * quantity as f64).round() as u64;
+        let mut premium = to_sats(premium_usd);
+        if let Some(spec) = &self.contract_spec {
+            premium = spec.round_premium(premium);
+        }
+
+        // 호가 시점 합의 가격이 USD 환산 환율 (서명 대상)
+        let spot_price = (spot * 100.0).round() as u64;
+
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut quote = OptionQuote {
+            quote_id: format!("Q-{}-{}", now, sequence),
+            option_type: request.option_type,
+            strike_price: request.strike_price,
+            expiry: request.expiry.clone(),
+            quantity,
+            premium,
+            spot_price,
+            issued_at: now,
+            valid_until: now + self.ttl_secs,
+            premium_usd: sats_to_cents(premium, spot_price),
+            otc: request.otc,
+            exercise: self.exercise_policy,
+            referral_code: request.referral_code.clone(),
+            tenant_id: request.tenant_id.clone(),
+            theoretical_premium: Some(to_sats(theoretical_usd)),
+            style: request.style,
+            barrier: request.barrier,
+            payoff: request.payoff,
+            signature: String::new(),
+        };
+        quote
+            .sign(&self.signing_key)
+            .map_err(|e| PricingError::Signing(e.to_string()))?;
+
+        Ok(quote)
+    }
+
+    /// 만기 전 되사기 호가: 현재 이론가에서 풀 스프레드를 뺀 bid를 서명해 발행
+    ///
+    /// 재고 스큐가 설정되지 않았으면 기본 스큐의 bid를 씁니다. 풀 사용률 가산은
+    /// 매도 호가에만 붙으므로 되사기 가격에는 적용하지 않습니다.
+    pub async fn quote_buy_back(
+        &self,
+        request: &BuyBackRequest,
+        now: u64,
+    ) -> Result<BuyBackQuote, PricingError> {
+        if request.quantity == 0 || request.strike_price == 0 {
+            return Err(PricingError::InvalidInput(
+                "Strike and quantity must be positive".to_string(),
+            ));
+        }
+
+        let market_state = self.market_repo.get_current_state().await?;
+        let spot = market_state.current_price;
+        if spot <= 0.0 {
+            return Err(PricingError::NoMarketData("No spot price available".to_string()));
+        }
+
+        // 이미 열린 옵션이므로 캘린더 밖(OTC) 만기도 허용
+        let strike = request.strike_price as f64 / 100.0;
+        let time_to_expiry = match &self.calendar {
+            Some(calendar) => {
+                let expiry_at = calendar.resolve(&request.expiry, now, true)?;
+                (expiry_at - now) as f64 / SECONDS_PER_YEAR
+            }
+            None => calculate_time_to_expiry(&request.expiry),
+        };
+        let surface = match &self.vol_repo {
+            Some(repo) => repo.get_surface().await?,
+            None => None,
+        };
+        let volatility = surface
+            .as_ref()
+            .and_then(|surface| surface.implied_vol(strike, time_to_expiry))
+            .unwrap_or(market_state.volatility_24h);
+        let params = OptionParameters {
+            spot,
+            strike,
+            time_to_expiry,
+            volatility,
+            risk_free_rate: 0.05,
+            is_call: request.option_type == OptionType::Call,
+        };
+
+        let inventory_ratio = match &self.pool_repo {
+            Some(repo) => {
+                InventorySkew::inventory_ratio(&repo.get_delta_info().await?, request.option_type)
+            }
+            None => 0.0,
+        };
+        let theoretical_usd =
+            self.theoretical_price(&params, request.style, request.barrier.as_ref(), request.payoff)?;
+        let bid_usd = self
+            .skew
+            .unwrap_or_default()
+            .quote(theoretical_usd, inventory_ratio)
+            .bid;
+        // 풀이 지급하는 금액이므로 내림
+        let to_sats = |usd: f64| (usd / spot * request.quantity as f64).floor() as u64;
+
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut quote = BuyBackQuote {
+            quote_id: format!("B-{}-{}", now, sequence),
+            option_id: request.option_id.clone(),
+            option_type: request.option_type,
+            strike_price: request.strike_price,
+            expiry: request.expiry.clone(),
+            quantity: request.quantity,
+            value: to_sats(bid_usd),
+            theoretical_value: to_sats(theoretical_usd),
+            spot_price: (spot * 100.0).round() as u64,
+            issued_at: now,
+            valid_until: now + self.ttl_secs,
+            tenant_id: request.tenant_id.clone(),
+            signature: String::new(),
+        };
+        quote
+            .sign(&self.signing_key)
+            .map_err(|e| PricingError::Signing(e.to_string()))?;
+
+        Ok(quote)
+    }
+
+    /// 상품 템플릿 호가: 템플릿 규칙으로 만기와 레그별 행사가를 정해 레그마다 확정 호가 발행
+    ///
+    /// 레그 수량이 서로 달라지지 않도록 그릭 한도 초과 시 부분 호가 없이 거부합니다.
+    pub async fn quote_product(
+        &self,
+        template: &ProductTemplate,
+        request: &ProductQuoteRequest,
+        now: u64,
+    ) -> Result<ProductQuote, PricingError> {
+        template.check_quantity(request.quantity)?;
+        let expiry = template.select_expiry(&self.calendar.clone().unwrap_or_default(), now)?;
+
+        let market_state = self.market_repo.get_current_state().await?;
+        let spot = market_state.current_price;
+        if spot <= 0.0 {
+            return Err(PricingError::NoMarketData("No spot price available".to_string()));
+        }
+        let time_to_expiry = (expiry.timestamp - now) as f64 / SECONDS_PER_YEAR;
+        let surface = match &self.vol_repo {
+            Some(repo) => repo.get_surface().await?,
+            None => None,
+        };
+        let volatility = |strike: f64| {
+            surface
+                .as_ref()
+                .and_then(|surface| surface.implied_vol(strike, time_to_expiry))
+                .unwrap_or(market_state.volatility_24h)
+        };
+
+        let mut legs = Vec::with_capacity(template.legs.len());
+        for leg in &template.legs {
+            let is_call = leg.option_type == OptionType::Call;
+            let strike = match leg.strike {
+                StrikeRule::Atm => spot,
+                StrikeRule::Moneyness { ratio } => spot * ratio,
+                StrikeRule::Delta { delta } => {
+                    self.strike_for_delta(is_call, delta, spot, time_to_expiry, volatility)
+                }
+            };
+            let leg_request = QuoteRequest {
+                option_type: leg.option_type,
+                strike_price: template.round_strike(strike),
+                expiry: expiry.date.clone(),
+                quantity: request.quantity,
+                otc: false,
+                referral_code: request.referral_code.clone(),
+                tenant_id: request.tenant_id.clone(),
+                allow_partial: false,
+                style: ExerciseStyle::European,
+                barrier: None,
+                payoff: Payoff::Vanilla,
+            };
+            legs.push(self.request_quote(&leg_request, now).await?);
+        }
+
+        let total_premium = legs.iter().map(|quote| quote.premium).sum();
+        Ok(ProductQuote {
+            template_id: template.id.clone(),
+            expiry,
+            legs,
+            total_premium,
+            fees: template.fees,
+            protocol_fee: template.fees.protocol_fee(total_premium),
+        })
+    }
+
+    /// 절대 델타가 `target`인 행사가 (USD, 행사가에 대해 단조이므로 로그 구간 이분 탐색)
+    fn strike_for_delta(
+        &self,
+        is_call: bool,
+        target: f64,
+        spot: f64,
+        time_to_expiry: f64,
+        volatility: impl Fn(f64) -> f64,
+    ) -> f64 {
+        let (mut low, mut high) = ((spot * 0.1).ln(), (spot * 10.0).ln());
+        for _ in 0..60 {
+            let strike = ((low + high) / 2.0).exp();
+            let delta = self
+                .pricing_engine
+                .calculate_delta(&OptionParameters {
+                    spot,
+                    strike,
+                    time_to_expiry,
+                    volatility: volatility(strike),
+                    risk_free_rate: 0.05,
+                    is_call,
+                })
+                .abs();
+            // 콜 델타는 행사가가 오를수록 줄고 풋 델타(절댓값)는 늘어남
+            if (delta > target) == is_call {
+                low = strike.ln();
+            } else {
+                high = strike.ln();
+            }
+        }
+        ((low + high) / 2.0).exp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::BlackScholesPricing;
+    use crate::models::DeltaInfo;
+    use crate::repositories::{InMemoryMarketRepo, InMemoryPoolRepo};
+    use oracle_vm_common::crypto::generate_keypair;
+
+    fn service() -> QuoteService<BlackScholesPricing> {
+        let (secret_key, _) = generate_keypair();
+        QuoteService::new(
+            BlackScholesPricing::new(),
+            Arc::new(InMemoryMarketRepo::new()),
+            secret_key,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_quote_is_signed_and_time_limited() {
+        let service = service();
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 10_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+
+        let quote = service.request_quote(&request, 1_000).await.unwrap();
+        assert!(quote.premium > 0 && quote.premium < request.quantity);
+        assert_eq!(quote.valid_until, 1_000 + DEFAULT_QUOTE_TTL_SECS);
+        // USD 프리미엄은 호가 시점 현물가로 환산
+        assert_eq!(quote.premium_usd, sats_to_cents(quote.premium, quote.spot_price));
+        assert!(quote.premium_usd > 0);
+        assert!(quote.verify(&service.public_key()).is_ok());
+
+        let next = service.request_quote(&request, 1_000).await.unwrap();
+        assert_ne!(quote.quote_id, next.quote_id);
+
+        // 자동 행사 정책은 호가에 공시되고 서명에 포함됨
+        let policy = ExercisePolicy::new(1_000, oracle_vm_common::DustHandling::Accumulate);
+        let service = service.with_exercise_policy(policy);
+        let mut disclosed = service.request_quote(&request, 1_000).await.unwrap();
+        assert_eq!(disclosed.exercise, policy);
+        disclosed.exercise = ExercisePolicy::default();
+        assert!(disclosed.verify(&service.public_key()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pool_utilization_raises_premium() {
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let base = service().request_quote(&request, 1_000).await.unwrap();
+
+        let pool_repo = Arc::new(InMemoryPoolRepo::new());
+        let mut busy = DeltaInfo::new(10.0);
+        busy.locked_collateral = 80.0;
+        pool_repo.update_delta_info(busy).await.unwrap();
+
+        let service = service().with_pool_curve(pool_repo, UtilizationCurve::default());
+        let quote = service.request_quote(&request, 1_000).await.unwrap();
+        assert!(quote.premium > base.premium);
+
+        // 주문 한도 초과
+        let oversized = QuoteRequest {
+            quantity: 600_000_000,
+            ..request
+        };
+        assert!(matches!(
+            service.request_quote(&oversized, 1_000).await,
+            Err(PricingError::OrderTooLarge { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_inventory_skew_quotes_over_theoretical() {
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let flat_curve = UtilizationCurve {
+            base_slope: 0.0,
+            kink_slope: 0.0,
+            delta_slope: 0.0,
+            ..UtilizationCurve::default()
+        };
+        let pool_repo = Arc::new(InMemoryPoolRepo::new());
+        pool_repo.update_delta_info(DeltaInfo::new(100.0)).await.unwrap();
+        let service = service()
+            .with_pool_curve(pool_repo.clone(), flat_curve)
+            .with_inventory_skew(InventorySkew::default());
+
+        let flat = service.request_quote(&request, 1_000).await.unwrap();
+        let theoretical = flat.theoretical_premium.unwrap();
+        assert!(flat.premium > theoretical);
+        assert!(flat.verify(&service.public_key()).is_ok());
+
+        // 풀이 콜을 팔아 둔 만큼 콜 호가가 오르고 풋은 그대로
+        let mut short_calls = DeltaInfo::new(100.0);
+        short_calls.add_delta(-20.0, true);
+        pool_repo.update_delta_info(short_calls).await.unwrap();
+        let skewed = service.request_quote(&request, 1_000).await.unwrap();
+        assert_eq!(skewed.theoretical_premium, Some(theoretical));
+        assert!(skewed.premium > flat.premium);
+
+        let put = QuoteRequest {
+            option_type: OptionType::Put,
+            ..request
+        };
+        let put_quote = service.request_quote(&put, 1_000).await.unwrap();
+        let expected = (put_quote.theoretical_premium.unwrap() as f64 * 1.02).round() as u64;
+        assert!(put_quote.premium.abs_diff(expected) <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_american_quote_prices_early_exercise() {
+        let service = service();
+        let european = QuoteRequest {
+            option_type: OptionType::Put,
+            strike_price: 14_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let american = QuoteRequest { style: ExerciseStyle::American, ..european.clone() };
+
+        // 깊은 ITM 풋: 미국식은 즉시 행사 가치(내재가치) 이상, 유럽식은 할인만큼 낮음
+        let european_quote = service.request_quote(&european, 1_000).await.unwrap();
+        let american_quote = service.request_quote(&american, 1_000).await.unwrap();
+        assert!(american_quote.premium > european_quote.premium);
+        assert!(american_quote.theoretical_premium.unwrap() >= 100_000_000);
+        assert_eq!(american_quote.style, ExerciseStyle::American);
+
+        // 스타일은 서명에 포함됨
+        let mut relabeled = american_quote.clone();
+        relabeled.style = ExerciseStyle::European;
+        assert!(american_quote.verify(&service.public_key()).is_ok());
+        assert!(relabeled.verify(&service.public_key()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_barrier_quote_is_cheaper_than_vanilla() {
+        let service = service();
+        let vanilla = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let barrier = Barrier {
+            kind: oracle_vm_common::BarrierKind::UpAndOut,
+            level: 8_000_000,
+        };
+        let knock_out = QuoteRequest { barrier: Some(barrier), ..vanilla.clone() };
+
+        let vanilla_quote = service.request_quote(&vanilla, 1_000).await.unwrap();
+        let barrier_quote = service.request_quote(&knock_out, 1_000).await.unwrap();
+        assert!(barrier_quote.premium < vanilla_quote.premium);
+        assert_eq!(barrier_quote.barrier, Some(barrier));
+        let mut stripped = barrier_quote.clone();
+        stripped.barrier = None;
+        assert!(stripped.verify(&service.public_key()).is_err());
+
+        let american = QuoteRequest { style: ExerciseStyle::American, ..knock_out };
+        assert!(matches!(
+            service.request_quote(&american, 1_000).await,
+            Err(PricingError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_binary_quote_prices_fixed_payout() {
+        let service = service();
+        let call = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 1_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Binary,
+        };
+        let put = QuoteRequest { option_type: OptionType::Put, ..call.clone() };
+
+        // 프리미엄은 지급액(수량) 미만이고, 콜 + 풋은 할인된 지급액과 같음
+        let call_quote = service.request_quote(&call, 1_000).await.unwrap();
+        let put_quote = service.request_quote(&put, 1_000).await.unwrap();
+        assert!(call_quote.premium > 0 && call_quote.premium < call.quantity);
+        let discount = (-0.05 * calculate_time_to_expiry(&call.expiry)).exp();
+        let total = (call_quote.premium + put_quote.premium) as f64;
+        assert!((total - discount * call.quantity as f64).abs() <= 2.0);
+
+        // 페이오프는 서명에 포함됨
+        assert_eq!(call_quote.payoff, Payoff::Binary);
+        let mut relabeled = call_quote.clone();
+        relabeled.payoff = Payoff::Vanilla;
+        assert!(relabeled.verify(&service.public_key()).is_err());
+
+        let american = QuoteRequest { style: ExerciseStyle::American, ..call };
+        assert!(matches!(
+            service.request_quote(&american, 1_000).await,
+            Err(PricingError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_buy_back_quote_is_below_theoretical() {
+        let pool_repo = Arc::new(InMemoryPoolRepo::new());
+        pool_repo.update_delta_info(DeltaInfo::new(100.0)).await.unwrap();
+        let service = service().with_pool_curve(pool_repo.clone(), UtilizationCurve::default());
+        let request = BuyBackRequest {
+            option_id: "OPT-1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            tenant_id: None,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+
+        let quote = service.quote_buy_back(&request, 1_000).await.unwrap();
+        assert!(quote.value < quote.theoretical_value);
+        assert_eq!(quote.option_id, "OPT-1");
+        assert_eq!(quote.valid_until, 1_000 + DEFAULT_QUOTE_TTL_SECS);
+        assert!(quote.verify(&service.public_key()).is_ok());
+
+        // 풀이 콜을 많이 팔아 둔 상태면 되사기 가격도 올라감
+        let mut short_calls = DeltaInfo::new(100.0);
+        short_calls.add_delta(-20.0, true);
+        pool_repo.update_delta_info(short_calls).await.unwrap();
+        let skewed = service.quote_buy_back(&request, 1_000).await.unwrap();
+        assert!(skewed.value > quote.value);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_zero_quantity() {
+        let request = QuoteRequest {
+            option_type: OptionType::Put,
+            strike_price: 6_500_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 0,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        assert!(service().request_quote(&request, 1_000).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calendar_rejects_off_calendar_expiry_unless_otc() {
+        // 2024-01-10 12:00 UTC
+        let now = 1_704_888_000;
+        let service = service().with_calendar(ExpiryCalendar::default());
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-01-26".to_string(),
+            quantity: 10_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let quote = service.request_quote(&request, now).await.unwrap();
+        assert!(!quote.otc);
+
+        let off_calendar = QuoteRequest {
+            expiry: "2024-02-01".to_string(),
+            ..request
+        };
+        assert_eq!(
+            service.request_quote(&off_calendar, now).await.unwrap_err(),
+            PricingError::NonStandardExpiry("2024-02-01".to_string())
+        );
+
+        let otc = QuoteRequest { otc: true, ..off_calendar };
+        let quote = service.request_quote(&otc, now).await.unwrap();
+        assert!(quote.otc && quote.verify(&service.public_key()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_contract_spec_sizes_and_ticks() {
+        let service = service().with_contract_spec(ContractSpec::default());
+        let request = QuoteRequest {
+            option_type: OptionType::Put,
+            strike_price: 6_500_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 3_000_000, // 3 contracts
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let quote = service.request_quote(&request, 1_000).await.unwrap();
+        assert_eq!(quote.premium % ContractSpec::default().premium_tick, 0);
+
+        let fractional = QuoteRequest {
+            quantity: 3_500_000,
+            ..request
+        };
+        assert!(matches!(
+            service.request_quote(&fractional, 1_000).await,
+            Err(PricingError::NonConformingSize { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_greeks_limits_reduce_or_split() {
+        let service = service()
+            .with_contract_spec(ContractSpec::default())
+            .with_greeks_limits(GreeksLimits {
+                max_delta: Some(0.3),
+                max_vega: None,
+            });
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 100_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        let Err(PricingError::GreeksLimitExceeded {
+            max_quantity,
+            schedule,
+            ..
+        }) = service.request_quote(&request, 1_000).await
+        else {
+            panic!("expected greeks limit error");
+        };
+        assert!(max_quantity > 0 && max_quantity < request.quantity);
+        assert_eq!(schedule.iter().sum::<u64>(), request.quantity);
+
+        // 부분 체결 허용 시 줄인 수량으로 서명된 호가
+        let partial = QuoteRequest {
+            allow_partial: true,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+            ..request
+        };
+        let quote = service.request_quote(&partial, 1_000).await.unwrap();
+        assert_eq!(quote.quantity, max_quantity);
+        assert!(quote.verify(&service.public_key()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_open_interest_caps_limit_quotes() {
+        use crate::repositories::{InMemoryOpenInterestRepo, OpenInterestRepository};
+
+        // 풀 10 BTC, 행사가당 10%, 만기 전체 25%
+        let pool_repo = Arc::new(InMemoryPoolRepo::new());
+        pool_repo.update_delta_info(DeltaInfo::new(10.0)).await.unwrap();
+        let open_interest = Arc::new(InMemoryOpenInterestRepo::new());
+        let service = service()
+            .with_contract_spec(ContractSpec::default())
+            .with_pool_curve(pool_repo, UtilizationCurve::default())
+            .with_open_interest_caps(
+                open_interest.clone(),
+                OpenInterestCaps {
+                    max_strike_pct: Some(10.0),
+                    max_expiry_pct: Some(25.0),
+                    ..OpenInterestCaps::default()
+                },
+            );
+        let entry = |strike_price, collateral| OpenInterestEntry {
+            strike_price,
+            expiry: "2024-02-01".to_string(),
+            collateral,
+        };
+        open_interest
+            .replace_open_interest(&[entry(7_000_000, 80_000_000), entry(8_000_000, 100_000_000)])
+            .await
+            .unwrap();
+
+        let capacity = service.open_interest_capacity(7_000_000, "2024-02-01", false, 1_000).await.unwrap();
+        assert_eq!(capacity.strike_cap, Some(100_000_000));
+        assert_eq!(capacity.remaining, Some(20_000_000));
+
+        let request = QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-02-01".to_string(),
+            quantity: 30_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: false,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+        };
+        assert!(matches!(
+            service.request_quote(&request, 1_000).await,
+            Err(PricingError::OpenInterestCapExceeded { remaining: 20_000_000, .. })
+        ));
+        let partial = QuoteRequest {
+            allow_partial: true,
+            ..request.clone()
+        };
+        assert_eq!(service.request_quote(&partial, 1_000).await.unwrap().quantity, 20_000_000);
+
+        // 다른 만기는 별도 용량
+        let other_expiry = QuoteRequest {
+            expiry: "2024-03-01".to_string(),
+            ..request
+        };
+        assert_eq!(service.request_quote(&other_expiry, 1_000).await.unwrap().quantity, 30_000_000);
+
+        // contracts 보고로 교체: 8M 행사가가 닫혀 만기 전체 여유가 생기고 7M 행사가만 남음
+        service.sync_open_interest(&[entry(7_000_000, 90_000_000)]).await.unwrap();
+        let capacity = service.open_interest_capacity(7_000_000, "2024-02-01", false, 1_000).await.unwrap();
+        assert_eq!(capacity.remaining, Some(10_000_000));
+        let capacity = service.open_interest_capacity(8_000_000, "2024-02-01", false, 1_000).await.unwrap();
+        assert_eq!(capacity.remaining, Some(100_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_product_template_quotes() {
+        use crate::products::ProductRegistry;
+
+        // 2024-01-10 12:00 UTC, 현물 $70,000
+        let now = 1_704_888_000;
+        let service = service()
+            .with_calendar(ExpiryCalendar::default())
+            .with_contract_spec(ContractSpec::default());
+        let registry = ProductRegistry::default();
+        let request = ProductQuoteRequest {
+            quantity: 10_000_000,
+            referral_code: None,
+            tenant_id: None,
+        };
+
+        let call = service
+            .quote_product(registry.get("weekly-10d-call").unwrap(), &request, now)
+            .await
+            .unwrap();
+        assert_eq!(call.expiry.date, "2024-01-12");
+        let leg = &call.legs[0];
+        assert!(leg.strike_price > 7_000_000 && leg.strike_price % 100_000 == 0);
+        assert!(leg.verify(&service.public_key()).is_ok());
+        let otm_put = service
+            .quote_product(registry.get("weekly-25d-put").unwrap(), &request, now)
+            .await
+            .unwrap();
+        assert!(otm_put.legs[0].strike_price < 7_000_000);
+
+        let straddle = service
+            .quote_product(registry.get("monthly-atm-straddle").unwrap(), &request, now)
+            .await
+            .unwrap();
+        assert_eq!(straddle.expiry.date, "2024-01-26");
+        assert_eq!(straddle.legs.len(), 2);
+        assert_eq!(straddle.legs[0].strike_price, 7_000_000);
+        assert_eq!(straddle.legs[0].strike_price, straddle.legs[1].strike_price);
+        assert_eq!(straddle.legs[0].option_type, OptionType::Call);
+        assert_eq!(straddle.legs[1].option_type, OptionType::Put);
+        assert_eq!(
+            straddle.total_premium,
+            straddle.legs[0].premium + straddle.legs[1].premium
+        );
+
+        let oversized = ProductQuoteRequest {
+            quantity: 200_000_000,
+            ..request
+        };
+        assert!(matches!(
+            service
+                .quote_product(registry.get("monthly-atm-straddle").unwrap(), &oversized, now)
+                .await,
+            Err(PricingError::OrderTooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,388 @@
+use crate::models::OptionParameters;
+use crate::pricing::{BlackScholesPricing, PricingEngine};
+use crate::regime::RegimeDetector;
+use crate::repositories::{MarketDataRepository, PoolStateRepository, PositionRepository};
+use crate::theta_targeting::OptionPosition;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const RISK_FREE_RATE: f64 = 0.05;
+
+/// 스트레스 시나리오
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressScenario {
+    pub name: String,
+    /// 현물 가격 충격 (0.1 = +10%)
+    pub spot_shock: f64,
+    /// 변동성 충격 (0.2 = +20 vol points)
+    pub vol_shift: f64,
+    /// 즉시 만기 (내재가치로만 평가)
+    pub instant_expiry: bool,
+}
+
+impl StressScenario {
+    fn new(name: &str, spot_shock: f64, vol_shift: f64, instant_expiry: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            spot_shock,
+            vol_shift,
+            instant_expiry,
+        }
+    }
+
+    /// 기본 시나리오: spot ±10/20/30%, vol ±20pt, 즉시 만기
+    pub fn standard_set() -> Vec<Self> {
+        let mut scenarios = Vec::new();
+        for shock in [0.1, 0.2, 0.3] {
+            let pct = (shock * 100.0) as u32;
+            scenarios.push(Self::new(&format!("spot_up_{}", pct), shock, 0.0, false));
+            scenarios.push(Self::new(&format!("spot_down_{}", pct), -shock, 0.0, false));
+        }
+        scenarios.push(Self::new("vol_up_20", 0.0, 0.2, false));
+        scenarios.push(Self::new("vol_down_20", 0.0, -0.2, false));
+        scenarios.push(Self::new("instant_expiry", 0.0, 0.0, true));
+        scenarios
+    }
+}
+
+/// 시나리오별 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub scenario: StressScenario,
+    pub spot: f64,
+    /// 풀 손익 (USD, 양수 = 이익)
+    pub pnl: f64,
+    /// 충격 후 풀 델타 (BTC)
+    pub delta: f64,
+    /// 충격 후 숏 포지션 청산에 필요한 담보 (USD)
+    pub required_collateral: f64,
+    /// 풀 자본 초과 여부
+    pub breached: bool,
+}
+
+/// 스트레스 테스트 리포트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressReport {
+    pub spot: f64,
+    pub capital: f64,
+    pub position_count: usize,
+    pub scenarios: Vec<ScenarioResult>,
+    pub breached: bool,
+}
+
+/// 풀 포지션 스트레스 테스트 서비스
+pub struct StressTestService {
+    pricing_engine: BlackScholesPricing,
+    position_repo: Arc<dyn PositionRepository>,
+    pool_repo: Arc<dyn PoolStateRepository>,
+    market_repo: Arc<dyn MarketDataRepository>,
+    scenarios: Vec<StressScenario>,
+}
+
+impl StressTestService {
+    pub fn new(
+        position_repo: Arc<dyn PositionRepository>,
+        pool_repo: Arc<dyn PoolStateRepository>,
+        market_repo: Arc<dyn MarketDataRepository>,
+    ) -> Self {
+        Self {
+            pricing_engine: BlackScholesPricing::new(),
+            position_repo,
+            pool_repo,
+            market_repo,
+            scenarios: StressScenario::standard_set(),
+        }
+    }
+
+    /// 현재 풀 포지션에 대한 스트레스 테스트
+    pub async fn run(&self) -> Result<StressReport, String> {
+        let positions = self.position_repo.get_positions().await?;
+        self.run_with_positions(&positions).await
+    }
+
+    /// 주어진 포지션 집합에 대한 스트레스 테스트
+    pub async fn run_with_positions(
+        &self,
+        positions: &[OptionPosition],
+    ) -> Result<StressReport, String> {
+        let spot = self.market_repo.get_current_state().await?.current_price;
+        let capital = self.pool_repo.get_delta_info().await?.available_liquidity;
+        Ok(self.evaluate(positions, spot, capital))
+    }
+
+    fn evaluate(&self, positions: &[OptionPosition], spot: f64, capital: f64) -> StressReport {
+        let base_value: f64 = positions
+            .iter()
+            .map(|pos| self.position_value(pos, spot, 0.0, false))
+            .sum();
+
+        let scenarios: Vec<ScenarioResult> = self
+            .scenarios
+            .iter()
+            .map(|scenario| {
+                let shocked_spot = spot * (1.0 + scenario.spot_shock);
+                let shocked_value: f64 = positions
+                    .iter()
+                    .map(|pos| {
+                        self.position_value(
+                            pos,
+                            shocked_spot,
+                            scenario.vol_shift,
+                            scenario.instant_expiry,
+                        )
+                    })
+                    .sum();
+                let delta: f64 = positions
+                    .iter()
+                    .map(|pos| {
+                        self.position_delta(
+                            pos,
+                            shocked_spot,
+                            scenario.vol_shift,
+                            scenario.instant_expiry,
+                        )
+                    })
+                    .sum();
+                let required_collateral: f64 = positions
+                    .iter()
+                    .filter(|pos| !pos.is_long)
+                    .map(|pos| {
+                        -self.position_value(
+                            pos,
+                            shocked_spot,
+                            scenario.vol_shift,
+                            scenario.instant_expiry,
+                        )
+                    })
+                    .sum();
+
+                let pnl = shocked_value - base_value;
+                ScenarioResult {
+                    scenario: scenario.clone(),
+                    spot: shocked_spot,
+                    pnl,
+                    delta,
+                    required_collateral,
+                    breached: -pnl > capital || required_collateral > capital,
+                }
+            })
+            .collect();
+
+        StressReport {
+            spot,
+            capital,
+            position_count: positions.len(),
+            breached: scenarios.iter().any(|result| result.breached),
+            scenarios,
+        }
+    }
+
+    fn shocked_params(
+        &self,
+        pos: &OptionPosition,
+        spot: f64,
+        vol_shift: f64,
+        instant_expiry: bool,
+    ) -> OptionParameters {
+        OptionParameters {
+            spot,
+            strike: pos.strike,
+            time_to_expiry: if instant_expiry {
+                0.0
+            } else {
+                pos.days_to_expiry / 365.0
+            },
+            volatility: (pos.implied_vol + vol_shift).max(0.01),
+            risk_free_rate: RISK_FREE_RATE,
+            is_call: pos.is_call,
+        }
+    }
+
+    /// 풀 입장의 포지션 가치 (숏이면 음수)
+    fn position_value(
+        &self,
+        pos: &OptionPosition,
+        spot: f64,
+        vol_shift: f64,
+        instant_expiry: bool,
+    ) -> f64 {
+        let params = self.shocked_params(pos, spot, vol_shift, instant_expiry);
+        let sign = if pos.is_long { 1.0 } else { -1.0 };
+        sign * self.pricing_engine.calculate_option_price(&params) * pos.quantity
+    }
+
+    fn position_delta(
+        &self,
+        pos: &OptionPosition,
+        spot: f64,
+        vol_shift: f64,
+        instant_expiry: bool,
+    ) -> f64 {
+        let params = self.shocked_params(pos, spot, vol_shift, instant_expiry);
+        let sign = if pos.is_long { 1.0 } else { -1.0 };
+        sign * self.pricing_engine.calculate_delta(&params) * pos.quantity
+    }
+}
+
+/// 신규 옵션 리스크 검사
+pub struct RiskEngine {
+    stress_service: Arc<StressTestService>,
+    position_repo: Arc<dyn PositionRepository>,
+    /// 거래당 최대 수량 (BTC, None = 무제한)
+    max_trade_quantity: Option<f64>,
+    regime: Option<Arc<RegimeDetector>>,
+}
+
+impl RiskEngine {
+    pub fn new(
+        stress_service: Arc<StressTestService>,
+        position_repo: Arc<dyn PositionRepository>,
+    ) -> Self {
+        Self {
+            stress_service,
+            position_repo,
+            max_trade_quantity: None,
+            regime: None,
+        }
+    }
+
+    /// 거래당 최대 수량 (BTC)
+    pub fn with_max_trade_quantity(mut self, max_trade_quantity: f64) -> Self {
+        self.max_trade_quantity = Some(max_trade_quantity);
+        self
+    }
+
+    /// 변동성 국면 감지기 사용 (고변동성 국면에서 거래당 수량 한도를 줄임)
+    pub fn with_regime_detector(mut self, regime: Arc<RegimeDetector>) -> Self {
+        self.regime = Some(regime);
+        self
+    }
+
+    /// 현재 국면을 반영한 거래당 최대 수량 (BTC)
+    pub fn trade_size_limit(&self) -> Option<f64> {
+        let size_multiplier = self.regime.as_ref().map_or(1.0, |regime| regime.size_multiplier());
+        self.max_trade_quantity.map(|max| max * size_multiplier)
+    }
+
+    /// 신규 옵션을 추가해도 모든 시나리오가 자본 내인지 검사
+    pub async fn check_new_option(&self, candidate: &OptionPosition) -> Result<(), String> {
+        if let Some(limit) = self.trade_size_limit() {
+            if candidate.quantity > limit {
+                return Err(format!(
+                    "Trade size {:.4} BTC exceeds per-trade limit {:.4} BTC",
+                    candidate.quantity, limit
+                ));
+            }
+        }
+
+        let mut positions = self.position_repo.get_positions().await?;
+        positions.push(candidate.clone());
+
+        let report = self.stress_service.run_with_positions(&positions).await?;
+        if let Some(breach) = report.scenarios.iter().find(|result| result.breached) {
+            return Err(format!(
+                "Stress scenario '{}' breaches pool capital (pnl {:.2}, required collateral {:.2}, capital {:.2})",
+                breach.scenario.name, breach.pnl, breach.required_collateral, report.capital
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 리스크 검사를 통과한 경우에만 포지션 등록
+    pub async fn open_position(&self, candidate: OptionPosition) -> Result<(), String> {
+        self.check_new_option(&candidate).await?;
+        self.position_repo.add_position(candidate).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{InMemoryMarketRepo, InMemoryPoolRepo, InMemoryPositionRepo};
+
+    fn short_call(strike: f64, quantity: f64) -> OptionPosition {
+        OptionPosition {
+            strike,
+            days_to_expiry: 7.0,
+            implied_vol: 0.6,
+            is_call: true,
+            is_long: false,
+            quantity,
+        }
+    }
+
+    fn setup() -> (Arc<InMemoryPositionRepo>, Arc<StressTestService>) {
+        let position_repo = Arc::new(InMemoryPositionRepo::new());
+        let service = Arc::new(StressTestService::new(
+            position_repo.clone(),
+            Arc::new(InMemoryPoolRepo::new()),
+            Arc::new(InMemoryMarketRepo::new()),
+        ));
+        (position_repo, service)
+    }
+
+    #[tokio::test]
+    async fn test_short_call_loses_on_spot_rally() {
+        let (position_repo, service) = setup();
+        position_repo.add_position(short_call(75000.0, 1.0)).await.unwrap();
+
+        let report = service.run().await.unwrap();
+        let up = report
+            .scenarios
+            .iter()
+            .find(|r| r.scenario.name == "spot_up_30")
+            .unwrap();
+        let down = report
+            .scenarios
+            .iter()
+            .find(|r| r.scenario.name == "spot_down_30")
+            .unwrap();
+
+        assert!(up.pnl < 0.0);
+        assert!(down.pnl > 0.0);
+        assert!(up.delta < 0.0);
+        assert!(!report.breached);
+    }
+
+    #[tokio::test]
+    async fn test_risk_engine_blocks_oversized_option() {
+        let (position_repo, service) = setup();
+        let engine = RiskEngine::new(service, position_repo.clone());
+
+        // 기본 자본 1,000,000 USD - 100 BTC 콜은 30% 상승 시 자본 초과
+        let result = engine.open_position(short_call(70000.0, 100.0)).await;
+        assert!(result.is_err());
+        assert!(position_repo.get_positions().await.unwrap().is_empty());
+
+        engine.open_position(short_call(75000.0, 0.5)).await.unwrap();
+        assert_eq!(position_repo.get_positions().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_high_vol_regime_tightens_trade_size() {
+        use crate::regime::{RegimeConfig, RegimeDetector, VolRegime};
+
+        let (position_repo, service) = setup();
+        let regime = Arc::new(RegimeDetector::new(RegimeConfig::default()));
+        let engine = RiskEngine::new(service, position_repo.clone())
+            .with_max_trade_quantity(1.0)
+            .with_regime_detector(regime.clone());
+        assert_eq!(engine.trade_size_limit(), Some(1.0));
+        engine.check_new_option(&short_call(75000.0, 0.8)).await.unwrap();
+
+        let mut price = 70_000.0;
+        regime.observe(0, price);
+        for i in 1..=20u64 {
+            price *= if i % 2 == 0 { 1.001 } else { 0.999 };
+            regime.observe(i * 60, price);
+        }
+        regime.observe(1_500, price * 0.9);
+        assert_eq!(regime.state().regime, VolRegime::HighVol);
+
+        // 고변동성 국면: 한도 절반
+        assert_eq!(engine.trade_size_limit(), Some(0.5));
+        assert!(engine.check_new_option(&short_call(75000.0, 0.8)).await.is_err());
+        engine.open_position(short_call(75000.0, 0.5)).await.unwrap();
+    }
+}
@@ -1,13 +1,24 @@
+use crate::arbitrage::{ArbitrageMetrics, ArbitrageValidator, ExpirySlice};
 use crate::models::{DeltaInfo, MarketState, OptionParameters, OptionPremium};
+use crate::premium_snapshot::PremiumSnapshots;
 use crate::pricing::{calculate_time_to_expiry, PricingEngine};
-use crate::repositories::{MarketDataRepository, PoolStateRepository, PremiumRepository};
-use std::sync::Arc;
+use crate::regime::RegimeDetector;
+use crate::repositories::{
+    MarketDataRepository, PoolStateRepository, PremiumRepository, VolSurfaceRepository,
+};
+use std::sync::{Arc, RwLock};
 
 /// 프리미엄 계산 서비스
 pub struct PremiumCalculationService<P> {
     pricing_engine: P,
     premium_repo: Arc<dyn PremiumRepository>,
     market_repo: Arc<dyn MarketDataRepository>,
+    vol_repo: Option<Arc<dyn VolSurfaceRepository>>,
+    arbitrage_validator: ArbitrageValidator,
+    arbitrage_metrics: RwLock<ArbitrageMetrics>,
+    regime: Option<Arc<RegimeDetector>>,
+    /// 갱신마다 바뀐 항목에 버전을 매긴 맵 (조건부 요청/변경분 조회용)
+    snapshots: PremiumSnapshots,
 }
 
 impl<P> PremiumCalculationService<P>
@@ -23,9 +34,42 @@ where
             pricing_engine,
             premium_repo,
             market_repo,
+            vol_repo: None,
+            arbitrage_validator: ArbitrageValidator::default(),
+            arbitrage_metrics: RwLock::new(ArbitrageMetrics::default()),
+            regime: None,
+            snapshots: PremiumSnapshots::new(),
         }
     }
 
+    /// 외부 IV 곡면 사용 (없는 구간은 volatility_24h로 대체)
+    pub fn with_vol_surface(mut self, vol_repo: Arc<dyn VolSurfaceRepository>) -> Self {
+        self.vol_repo = Some(vol_repo);
+        self
+    }
+
+    /// 무차익 검증 허용 오차 변경
+    pub fn with_arbitrage_validator(mut self, validator: ArbitrageValidator) -> Self {
+        self.arbitrage_validator = validator;
+        self
+    }
+
+    /// 변동성 국면 감지기 사용 (고변동성 국면에서 IV를 넓힘)
+    pub fn with_regime_detector(mut self, regime: Arc<RegimeDetector>) -> Self {
+        self.regime = Some(regime);
+        self
+    }
+
+    /// 버전이 붙은 프리미엄 맵
+    pub fn snapshots(&self) -> &PremiumSnapshots {
+        &self.snapshots
+    }
+
+    /// 프리미엄 맵 무차익 검증 지표
+    pub fn arbitrage_metrics(&self) -> ArbitrageMetrics {
+        self.arbitrage_metrics.read().unwrap().clone()
+    }
+
     /// 프리미엄 맵 업데이트
     pub async fn update_premium_map(&self, current_price: f64) -> Result<(), String> {
         let strikes = vec![60000.0, 65000.0, 70000.0, 75000.0, 80000.0];
@@ -33,17 +77,29 @@ where
         let risk_free_rate = 0.05;
 
         let market_state = self.market_repo.get_current_state().await?;
+        let surface = match &self.vol_repo {
+            Some(repo) => repo.get_surface().await?,
+            None => None,
+        };
+        let iv_multiplier = self.regime.as_ref().map_or(1.0, |regime| regime.iv_multiplier());
 
+        let mut slices = Vec::new();
         for expiry in &expiries {
             let mut options = Vec::new();
             let time_to_expiry = calculate_time_to_expiry(expiry);
 
             for &strike in &strikes {
+                let volatility = surface
+                    .as_ref()
+                    .and_then(|surface| surface.implied_vol(strike, time_to_expiry))
+                    .unwrap_or(market_state.volatility_24h)
+                    * iv_multiplier;
+
                 let call_params = OptionParameters {
                     spot: current_price,
                     strike,
                     time_to_expiry,
-                    volatility: market_state.volatility_24h,
+                    volatility,
                     risk_free_rate,
                     is_call: true,
                 };
@@ -52,7 +108,7 @@ where
                     spot: current_price,
                     strike,
                     time_to_expiry,
-                    volatility: market_state.volatility_24h,
+                    volatility,
                     risk_free_rate,
                     is_call: false,
                 };
@@ -65,15 +121,36 @@ where
                     expiry: expiry.to_string(),
                     call_premium,
                     put_premium,
-                    implied_volatility: market_state.volatility_24h,
+                    implied_volatility: volatility,
                 });
             }
 
+            slices.push(ExpirySlice {
+                expiry: expiry.to_string(),
+                time_to_expiry,
+                premiums: options,
+            });
+        }
+
+        // 차익거래가 가능한 호가는 보정하거나 제외한 뒤 저장
+        let report = self
+            .arbitrage_validator
+            .validate(current_price, risk_free_rate, &mut slices);
+        self.arbitrage_metrics.write().unwrap().record(report);
+
+        for slice in slices {
             self.premium_repo
-                .save_premiums(expiry.to_string(), options)
+                .save_premiums(slice.expiry, slice.premiums)
                 .await?;
         }
 
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.snapshots
+            .publish(self.premium_repo.get_all_premiums().await?, now);
+
         Ok(())
     }
 
@@ -150,7 +227,10 @@ impl MarketDataService {
 mod tests {
     use super::*;
     use crate::pricing::BlackScholesPricing;
-    use crate::repositories::{InMemoryMarketRepo, InMemoryPoolRepo, InMemoryPremiumRepo};
+    use crate::models::{VolSurface, VolSurfacePoint};
+    use crate::repositories::{
+        InMemoryMarketRepo, InMemoryPoolRepo, InMemoryPremiumRepo, InMemoryVolSurfaceRepo,
+    };
 
     #[tokio::test]
     async fn test_premium_calculation_service() {
@@ -172,6 +252,74 @@ mod tests {
             .unwrap();
 
         assert!(!premiums.is_empty());
+
+        let metrics = service.arbitrage_metrics();
+        assert_eq!(metrics.validations, 1);
+        assert_eq!(metrics.withheld, 0);
+    }
+
+    #[tokio::test]
+    async fn test_premium_uses_vol_surface() {
+        let premium_repo = Arc::new(InMemoryPremiumRepo::new());
+        let vol_repo = Arc::new(InMemoryVolSurfaceRepo::new());
+        vol_repo
+            .update_surface(VolSurface {
+                source: "deribit".to_string(),
+                timestamp: 0,
+                underlying_price: 70000.0,
+                points: vec![VolSurfacePoint {
+                    time_to_expiry: 30.0 / 365.0,
+                    strike: 70000.0,
+                    implied_vol: 0.45,
+                }],
+            })
+            .await
+            .unwrap();
+
+        let service = PremiumCalculationService::new(
+            BlackScholesPricing::new(),
+            premium_repo.clone(),
+            Arc::new(InMemoryMarketRepo::new()),
+        )
+        .with_vol_surface(vol_repo);
+
+        service.update_premium_map(70000.0).await.unwrap();
+
+        let premiums = service
+            .get_premiums_by_expiry(Some("2024-02-01".to_string()))
+            .await
+            .unwrap();
+        assert!(premiums.iter().all(|p| (p.implied_volatility - 0.45).abs() < 1e-9));
+    }
+
+    #[tokio::test]
+    async fn test_high_vol_regime_widens_quoted_iv() {
+        use crate::regime::{RegimeConfig, RegimeDetector};
+
+        let regime = Arc::new(RegimeDetector::new(RegimeConfig::default()));
+        let service = PremiumCalculationService::new(
+            BlackScholesPricing::new(),
+            Arc::new(InMemoryPremiumRepo::new()),
+            Arc::new(InMemoryMarketRepo::new()),
+        )
+        .with_regime_detector(regime.clone());
+        service.update_premium_map(70000.0).await.unwrap();
+        let calm = service.get_premiums_by_expiry(Some("2024-02-01".to_string())).await.unwrap();
+
+        let mut price = 70_000.0;
+        regime.observe(0, price);
+        for i in 1..=20u64 {
+            price *= if i % 2 == 0 { 1.001 } else { 0.999 };
+            regime.observe(i * 60, price);
+        }
+        regime.observe(1_500, price * 1.08);
+
+        service.update_premium_map(70000.0).await.unwrap();
+        let wide = service.get_premiums_by_expiry(Some("2024-02-01".to_string())).await.unwrap();
+        for (calm, wide) in calm.iter().zip(&wide) {
+            assert!((wide.implied_volatility - calm.implied_volatility * 1.25).abs() < 1e-9);
+            assert!(wide.call_premium > calm.call_premium);
+        }
     }
 
     #[tokio::test]
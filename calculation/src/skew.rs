@@ -0,0 +1,104 @@
+//! 풀 재고 기반 호가 스큐 (이론가 주변 bid/ask 스프레드)
+//!
+//! 풀은 옵션 매도자이므로 같은 유형을 많이 팔아 둔 상태일수록 추가 매도를
+//! 억제하고 되사기는 쉽게 해야 합니다. 유형별 숏 델타를 풀 유동성으로 나눈
+//! 재고 비율만큼 bid/ask 중심을 이론가 위로 옮기고, 그 양쪽에 고정 반 스프레드를
+//! 둡니다. 풀이 숏 콜이면 콜 ask와 bid가 함께 오르고, 롱이면 내려갑니다.
+
+use crate::models::DeltaInfo;
+use oracle_vm_common::OptionType;
+use serde::{Deserialize, Serialize};
+
+/// 재고 스큐 설정 (비율은 이론가 대비)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InventorySkew {
+    /// 중심에서 bid/ask까지의 비율
+    pub half_spread: f64,
+    /// 재고 비율 1.0당 중심 이동
+    pub inventory_slope: f64,
+    /// 중심 이동 상한 (양방향)
+    pub max_skew: f64,
+}
+
+impl Default for InventorySkew {
+    fn default() -> Self {
+        Self {
+            half_spread: 0.02,
+            inventory_slope: 0.5,
+            max_skew: 0.25,
+        }
+    }
+}
+
+/// 이론가와 스큐 적용 호가 (같은 단위)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkewedPremium {
+    pub theoretical: f64,
+    /// 풀이 되살 때 지급하는 가격
+    pub bid: f64,
+    /// 풀이 팔 때 받는 가격
+    pub ask: f64,
+}
+
+impl InventorySkew {
+    /// 유형별 재고 비율: 풀 숏 델타 / 풀 유동성 (숏이면 양수, 롱이면 음수)
+    ///
+    /// DeltaInfo의 델타는 풀 관점이므로 숏 콜은 음의 콜 델타, 숏 풋은 양의 풋
+    /// 델타로 쌓입니다.
+    pub fn inventory_ratio(delta_info: &DeltaInfo, option_type: OptionType) -> f64 {
+        let total_liquidity = delta_info.pool_capacity().total_liquidity;
+        if total_liquidity <= 0.0 {
+            return 0.0;
+        }
+        let short_delta = match option_type {
+            OptionType::Call => -delta_info.total_call_delta,
+            OptionType::Put => delta_info.total_put_delta,
+        };
+        short_delta / total_liquidity
+    }
+
+    /// 이론가 대비 중심 이동 비율 (±max_skew로 제한)
+    pub fn skew(&self, inventory_ratio: f64) -> f64 {
+        (self.inventory_slope * inventory_ratio).clamp(-self.max_skew, self.max_skew)
+    }
+
+    /// 이론가 주변 bid/ask (bid는 0 미만으로 내려가지 않음)
+    pub fn quote(&self, theoretical: f64, inventory_ratio: f64) -> SkewedPremium {
+        let center = 1.0 + self.skew(inventory_ratio);
+        SkewedPremium {
+            theoretical,
+            bid: (theoretical * (center - self.half_spread)).max(0.0),
+            ask: theoretical * (center + self.half_spread),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_inventory_raises_both_sides() {
+        let skew = InventorySkew::default();
+        let mut info = DeltaInfo::new(10.0);
+
+        let flat = skew.quote(100.0, InventorySkew::inventory_ratio(&info, OptionType::Call));
+        assert!((flat.ask - 102.0).abs() < 1e-9);
+        assert!((flat.bid - 98.0).abs() < 1e-9);
+
+        // 콜 2 BTC 델타 매도 → 콜 재고 0.2, 중심 +10%; 풋은 그대로
+        info.add_delta(-2.0, true);
+        let call_ratio = InventorySkew::inventory_ratio(&info, OptionType::Call);
+        assert!((call_ratio - 0.2).abs() < 1e-9);
+        let call = skew.quote(100.0, call_ratio);
+        assert!((call.ask - 112.0).abs() < 1e-9);
+        assert!((call.bid - 108.0).abs() < 1e-9);
+        assert_eq!(InventorySkew::inventory_ratio(&info, OptionType::Put), 0.0);
+
+        // 롱 재고는 가격을 내리고, 이동폭은 상한에서 멈춤
+        assert!(skew.quote(100.0, -0.2).ask < flat.ask);
+        assert!((skew.quote(100.0, 5.0).ask - 127.0).abs() < 1e-9);
+        assert_eq!(InventorySkew::inventory_ratio(&DeltaInfo::new(0.0), OptionType::Call), 0.0);
+    }
+}
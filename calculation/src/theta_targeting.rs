@@ -1,18 +1,150 @@
 use crate::models::OptionParameters;
 use crate::pricing::{BlackScholesPricing, PricingEngine};
+use oracle_vm_common::{ContractSpec, ExpiryCalendar, GreeksLimits, PricingError};
+use pricing_core::{volatility_for_theta, BlackScholesInputs};
+use serde::{Deserialize, Serialize};
+
+/// 풀 사용률/델타 기반 프리미엄 가산 곡선
+///
+/// 주문 체결 후 사용률이 kink 이하에서는 완만하게, 이상에서는 가파르게
+/// 프리미엄을 올리고, 풀 순델타가 커질수록 추가로 가산합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UtilizationCurve {
+    /// 기울기가 바뀌는 사용률 (0.0 ~ 1.0)
+    pub kink_utilization: f64,
+    /// kink 이하 사용률 1.0당 가산율
+    pub base_slope: f64,
+    /// kink 초과 사용률 1.0당 가산율
+    pub kink_slope: f64,
+    /// |순델타| / 풀 유동성 1.0당 가산율
+    pub delta_slope: f64,
+    /// 최대 배율
+    pub max_multiplier: f64,
+    /// 주문당 최대 수량 (BTC)
+    pub max_order_btc: f64,
+}
+
+impl Default for UtilizationCurve {
+    fn default() -> Self {
+        Self {
+            kink_utilization: 0.8,
+            base_slope: 0.25,
+            kink_slope: 2.0,
+            delta_slope: 0.5,
+            max_multiplier: 3.0,
+            max_order_btc: 5.0,
+        }
+    }
+}
+
+/// 풀 용량 (BTC 단위)
+#[derive(Debug, Clone, Copy)]
+pub struct PoolCapacity {
+    pub total_liquidity: f64,
+    pub locked_collateral: f64,
+    pub net_delta: f64,
+}
+
+impl UtilizationCurve {
+    /// 사용률/델타 비율에 대한 프리미엄 배율
+    pub fn multiplier(&self, utilization: f64, delta_ratio: f64) -> f64 {
+        let below = utilization.min(self.kink_utilization).max(0.0);
+        let above = (utilization - self.kink_utilization).max(0.0);
+        let multiplier = 1.0
+            + self.base_slope * below
+            + self.kink_slope * above
+            + self.delta_slope * delta_ratio.abs();
+        multiplier.min(self.max_multiplier)
+    }
+
+    /// 주문 체결 후 풀 상태 기준 배율 (주문 크기/유동성 검사 포함)
+    ///
+    /// `collateral`은 주문이 잠그는 담보, `order_delta`는 풀이 떠안는 델타 (BTC).
+    pub fn adjustment(
+        &self,
+        pool: &PoolCapacity,
+        notional_btc: f64,
+        collateral: f64,
+        order_delta: f64,
+    ) -> Result<f64, PricingError> {
+        if notional_btc > self.max_order_btc {
+            return Err(PricingError::OrderTooLarge {
+                size: notional_btc,
+                max: self.max_order_btc,
+            });
+        }
+        if pool.total_liquidity <= 0.0 {
+            return Err(PricingError::InsufficientLiquidity);
+        }
+
+        let utilization = (pool.locked_collateral + collateral) / pool.total_liquidity;
+        if utilization > 1.0 {
+            return Err(PricingError::InsufficientLiquidity);
+        }
+
+        let delta_ratio = (pool.net_delta + order_delta) / pool.total_liquidity;
+        Ok(self.multiplier(utilization, delta_ratio))
+    }
+}
 
 /// Target Theta 기반 옵션 프리미엄 계산
 pub struct ThetaTargetingEngine {
     pricing_engine: BlackScholesPricing,
+    curve: UtilizationCurve,
+    calendar: ExpiryCalendar,
+    greeks_limits: GreeksLimits,
 }
 
 impl ThetaTargetingEngine {
     pub fn new() -> Self {
         Self {
             pricing_engine: BlackScholesPricing::new(),
+            curve: UtilizationCurve::default(),
+            calendar: ExpiryCalendar::default(),
+            greeks_limits: GreeksLimits::default(),
         }
     }
 
+    /// 거래당 델타/베가 한도 지정 (초과 시 최대 수량과 분할 일정을 담아 거부)
+    pub fn with_greeks_limits(mut self, limits: GreeksLimits) -> Self {
+        self.greeks_limits = limits;
+        self
+    }
+
+    /// 만기일까지 남은 일수 (캘린더 만기만 허용, OTC는 임의의 미래 날짜 허용)
+    ///
+    /// 결과는 `calculate_premium_with_target_theta`의 `time_to_expiry_days`로 사용합니다.
+    pub fn days_to_expiry(&self, expiry: &str, now: u64, otc: bool) -> Result<f64, PricingError> {
+        let expiry_at = self.calendar.resolve(expiry, now, otc)?;
+        Ok((expiry_at - now) as f64 / 86_400.0)
+    }
+
+    /// 풀 사용률/델타에 따라 프리미엄 가산 (풀은 옵션 매도자)
+    pub fn apply_pool_slippage(
+        &self,
+        result: &PremiumResult,
+        pool: &PoolCapacity,
+        notional_btc: f64,
+        is_call: bool,
+    ) -> Result<PremiumResult, PricingError> {
+        let collateral = if is_call {
+            notional_btc
+        } else {
+            result.strike_price / result.spot_price * notional_btc
+        };
+        let multiplier = self
+            .curve
+            .adjustment(pool, notional_btc, collateral, -result.delta)?;
+
+        Ok(PremiumResult {
+            premium_usd: result.premium_usd * multiplier,
+            premium_btc: result.premium_btc * multiplier,
+            slippage_multiplier: multiplier,
+            ..result.clone()
+        })
+    }
+
     /// Target theta를 달성하기 위한 implied volatility 찾기
     pub fn find_iv_for_target_theta(
         &self,
@@ -22,45 +154,17 @@ impl ThetaTargetingEngine {
         risk_free_rate: f64,
         is_call: bool,
         target_theta: f64, // 일일 theta (음수)
-    ) -> Result<f64, String> {
-        // Newton-Raphson method로 IV 찾기
-        let mut iv = 0.5; // 초기 추정값 50%
-        let tolerance = 0.0001;
-        let max_iterations = 100;
-        
-        for _ in 0..max_iterations {
-            let params = OptionParameters {
-                spot,
-                strike,
-                volatility: iv,
-                risk_free_rate,
-                time_to_expiry,
-                is_call,
-            };
-            
-            let current_theta = self.pricing_engine.calculate_theta(&params);
-            let daily_theta = current_theta / 365.0; // 연간 theta를 일일 theta로 변환
-            
-            let diff = daily_theta - target_theta;
-            
-            if diff.abs() < tolerance {
-                return Ok(iv);
-            }
-            
-            // Vega를 사용해 다음 IV 추정
-            let vega = self.pricing_engine.calculate_vega(&params);
-            if vega.abs() < 1e-10 {
-                return Err("Vega too small for convergence".to_string());
-            }
-            
-            // Theta는 IV에 대해 증가함수이므로
-            iv = iv - diff / (vega * 0.01); // 적절한 스케일링
-            
-            // IV 범위 제한
-            iv = iv.max(0.01).min(5.0); // 1% ~ 500%
-        }
-        
-        Err("Failed to converge to target theta".to_string())
+    ) -> Result<f64, PricingError> {
+        let inputs = BlackScholesInputs {
+            spot,
+            strike,
+            time_to_expiry,
+            volatility: 0.0,
+            risk_free_rate,
+            is_call,
+        };
+        volatility_for_theta(&inputs, target_theta)
+            .map_err(|e| PricingError::NotConverged(e.to_string()))
     }
 
     /// 3개 거래소 평균 가격을 사용한 프리미엄 계산
@@ -75,7 +179,7 @@ impl ThetaTargetingEngine {
         is_call: bool,
         target_theta: f64,
         notional_btc: f64, // BTC 단위 수량
-    ) -> Result<PremiumResult, String> {
+    ) -> Result<PremiumResult, PricingError> {
         // 3개 거래소 평균 가격
         let spot = (binance_price + coinbase_price + kraken_price) / 3.0;
         
@@ -103,11 +207,14 @@ impl ThetaTargetingEngine {
         };
         
         let option_price = self.pricing_engine.calculate_option_price(&params);
-        let delta = self.pricing_engine.calculate_delta(&params);
-        let gamma = self.pricing_engine.calculate_gamma(&params);
-        let vega = self.pricing_engine.calculate_vega(&params);
-        let theta = self.pricing_engine.calculate_theta(&params);
-        let rho = self.pricing_engine.calculate_rho(&params);
+        let greeks = self.pricing_engine.greeks(&params).scaled(notional_btc);
+        // 표준 계약 단위로 최대 수량/분할 일정 제시
+        self.greeks_limits.check(
+            (notional_btc * 100_000_000.0).round() as u64,
+            greeks.delta,
+            greeks.vega,
+            ContractSpec::default().contract_size,
+        )?;
         
         // BTC 단위로 프리미엄 계산
         let premium_btc = (option_price / spot) * notional_btc;
@@ -118,12 +225,13 @@ impl ThetaTargetingEngine {
             implied_volatility: implied_vol,
             premium_usd: option_price * notional_btc,
             premium_btc,
-            delta: delta * notional_btc,
-            gamma: gamma * notional_btc,
-            vega: vega * notional_btc,
-            theta: theta * notional_btc,
-            daily_theta: (theta / 365.0) * notional_btc,
-            rho: rho * notional_btc,
+            delta: greeks.delta,
+            gamma: greeks.gamma,
+            vega: greeks.vega,
+            theta: greeks.theta * 365.0,
+            daily_theta: greeks.theta,
+            rho: greeks.rho,
+            slippage_multiplier: 1.0,
         })
     }
 }
@@ -139,9 +247,12 @@ pub struct PremiumResult {
     pub delta: f64,
     pub gamma: f64,
     pub vega: f64,
+    /// 연간 theta
     pub theta: f64,
     pub daily_theta: f64,
     pub rho: f64,
+    /// 풀 사용률 가산 배율 (1.0 = 가산 없음)
+    pub slippage_multiplier: f64,
 }
 
 /// Delta-neutral 포트폴리오 관리
@@ -201,16 +312,16 @@ impl DeltaNeutralManager {
                     is_call: pos.is_call,
                 };
                 
-                let theta = self.engine.pricing_engine.calculate_theta(&params);
-                let daily_theta = theta / 365.0;
-                daily_theta * pos.quantity * if pos.is_long { -1.0 } else { 1.0 }
+                // theta는 보유자 기준 음수이므로 매도 포지션이 시간가치를 받음
+                let daily_theta = self.engine.pricing_engine.calculate_theta(&params);
+                daily_theta * pos.quantity * if pos.is_long { 1.0 } else { -1.0 }
             })
             .sum()
     }
 }
 
 /// 옵션 포지션 정보
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionPosition {
     pub strike: f64,
     pub days_to_expiry: f64,
@@ -266,6 +377,89 @@ mod tests {
         assert!(premium.daily_theta < 0.0);
     }
 
+    #[test]
+    fn test_utilization_curve_kink() {
+        let curve = UtilizationCurve::default();
+        assert_eq!(curve.multiplier(0.0, 0.0), 1.0);
+        assert!((curve.multiplier(0.4, 0.0) - 1.1).abs() < 1e-9);
+        // kink 이후 기울기 증가: 0.8 → 1.2, 0.9 → 1.4
+        assert!((curve.multiplier(0.9, 0.0) - 1.4).abs() < 1e-9);
+        assert_eq!(curve.multiplier(1.0, 10.0), curve.max_multiplier);
+    }
+
+    #[test]
+    fn test_pool_slippage_scales_premium_and_caps_size() {
+        let engine = ThetaTargetingEngine::new();
+        let base = engine
+            .calculate_premium_with_target_theta(
+                70000.0, 70000.0, 70000.0, 75000.0, 7.0, 0.05, true, -0.02, 1.0,
+            )
+            .unwrap();
+
+        let idle = PoolCapacity {
+            total_liquidity: 100.0,
+            locked_collateral: 0.0,
+            net_delta: 0.0,
+        };
+        let busy = PoolCapacity {
+            locked_collateral: 85.0,
+            net_delta: -20.0,
+            ..idle
+        };
+
+        let cheap = engine.apply_pool_slippage(&base, &idle, 1.0, true).unwrap();
+        let expensive = engine.apply_pool_slippage(&base, &busy, 1.0, true).unwrap();
+        assert!(cheap.premium_btc >= base.premium_btc);
+        assert!(expensive.premium_btc > cheap.premium_btc);
+        assert!(expensive.slippage_multiplier > cheap.slippage_multiplier);
+
+        // 주문 한도 초과, 유동성 부족
+        assert!(matches!(
+            engine.apply_pool_slippage(&base, &idle, 6.0, true),
+            Err(PricingError::OrderTooLarge { .. })
+        ));
+        assert!(engine.apply_pool_slippage(&base, &busy, 1.0, true).is_ok());
+        let full = PoolCapacity {
+            locked_collateral: 99.5,
+            ..idle
+        };
+        assert_eq!(
+            engine.apply_pool_slippage(&base, &full, 1.0, true).unwrap_err(),
+            PricingError::InsufficientLiquidity
+        );
+    }
+
+    #[test]
+    fn test_greeks_limits_reject_oversized_trade() {
+        let engine = ThetaTargetingEngine::new().with_greeks_limits(GreeksLimits {
+            max_delta: Some(0.5),
+            max_vega: None,
+        });
+        let quote = |notional: f64| {
+            engine.calculate_premium_with_target_theta(
+                70000.0, 70000.0, 70000.0, 70000.0, 7.0, 0.05, true, -100.0, notional,
+            )
+        };
+        assert!(quote(0.5).is_ok());
+
+        // ATM 콜 델타 ~0.5/BTC: 2 BTC면 한도의 두 배 가까이
+        match quote(2.0) {
+            Err(PricingError::GreeksLimitExceeded {
+                greek,
+                max_quantity,
+                schedule,
+                ..
+            }) => {
+                assert_eq!(greek, "delta");
+                assert!(max_quantity > 0 && max_quantity < 200_000_000);
+                assert_eq!(max_quantity % 1_000_000, 0);
+                assert_eq!(schedule.iter().sum::<u64>(), 200_000_000);
+                assert!(quote(max_quantity as f64 / 100_000_000.0).is_ok());
+            }
+            other => panic!("expected greeks limit error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_delta_neutral_portfolio() {
         let manager = DeltaNeutralManager::new();
@@ -299,4 +493,15 @@ mod tests {
         let theta_revenue = manager.calculate_portfolio_theta_revenue(&positions, 70000.0);
         assert!(theta_revenue > 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_days_to_calendar_expiry() {
+        let engine = ThetaTargetingEngine::new();
+        // 2024-01-10 12:00 UTC → 2024-01-12 08:00 UTC (주간 만기)
+        let days = engine.days_to_expiry("2024-01-12", 1_704_888_000, false).unwrap();
+        assert!((days - 44.0 / 24.0).abs() < 1e-9);
+        assert!(engine.days_to_expiry("2024-01-13", 1_704_888_000, false).is_ok()); // 일간 만기
+        assert!(engine.days_to_expiry("2024-05-01", 1_704_888_000, false).is_err());
+        assert!(engine.days_to_expiry("2024-05-01", 1_704_888_000, true).is_ok());
+    }
+}
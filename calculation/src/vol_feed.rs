@@ -0,0 +1,107 @@
+//! 외부 IV 곡면 피드
+//!
+//! Oracle Node가 Deribit에서 수집해 Aggregator에 올린 IV 곡면을 가져와
+//! VolSurfaceRepository에 저장합니다. PremiumCalculationService는 이 곡면을
+//! 우선 사용하여 호가가 외부 시장과 크게 벗어나지 않도록 합니다.
+
+use crate::models::{VolSurface, VolSurfacePoint};
+use crate::repositories::VolSurfaceRepository;
+use btcfi_contracts::price_feed_client::oracle::GetVolSurfaceResponse;
+use btcfi_contracts::PriceFeedClient;
+use std::sync::Arc;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// 가격 산정에 쓰는 IV 곡면 소스 (다른 소스 곡면은 받지 않음)
+const VOL_SURFACE_SOURCE: &str = "deribit";
+
+/// Aggregator IV 곡면 폴링 클라이언트
+pub struct VolSurfaceFeed {
+    client: PriceFeedClient,
+    vol_repo: Arc<dyn VolSurfaceRepository>,
+}
+
+impl VolSurfaceFeed {
+    pub async fn new(
+        aggregator_url: &str,
+        vol_repo: Arc<dyn VolSurfaceRepository>,
+    ) -> Result<Self, String> {
+        let client = PriceFeedClient::new(aggregator_url)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self { client, vol_repo })
+    }
+
+    /// 최신 곡면을 가져와 저장
+    pub async fn refresh(&mut self) -> Result<VolSurface, String> {
+        let response = self
+            .client
+            .get_vol_surface(VOL_SURFACE_SOURCE)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let surface = surface_from_response(&response);
+        if surface.points.is_empty() {
+            return Err("IV surface has no unexpired points".to_string());
+        }
+
+        self.vol_repo.update_surface(surface.clone()).await?;
+        Ok(surface)
+    }
+}
+
+/// gRPC 응답을 만기까지 남은 기간(연) 기준 곡면으로 변환
+pub fn surface_from_response(response: &GetVolSurfaceResponse) -> VolSurface {
+    let points = response
+        .points
+        .iter()
+        .filter(|point| point.expiry > response.timestamp)
+        .map(|point| VolSurfacePoint {
+            time_to_expiry: (point.expiry - response.timestamp) as f64 / SECONDS_PER_YEAR,
+            strike: point.strike,
+            implied_vol: point.mark_iv,
+        })
+        .collect();
+
+    VolSurface {
+        source: response.source.clone(),
+        timestamp: response.timestamp,
+        underlying_price: response.underlying_price,
+        points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use btcfi_contracts::price_feed_client::oracle::VolPoint;
+
+    #[test]
+    fn test_surface_from_response() {
+        let response = GetVolSurfaceResponse {
+            success: true,
+            source: "deribit".to_string(),
+            timestamp: 1_700_000_000,
+            underlying_price: 70000.0,
+            points: vec![
+                VolPoint {
+                    expiry: 1_700_000_000 + 73 * 86_400,
+                    strike: 70000.0,
+                    is_call: true,
+                    mark_iv: 0.55,
+                },
+                VolPoint {
+                    expiry: 1_600_000_000, // 만기 지남
+                    strike: 70000.0,
+                    is_call: false,
+                    mark_iv: 0.50,
+                },
+            ],
+        };
+
+        let surface = surface_from_response(&response);
+        assert_eq!(surface.points.len(), 1);
+        assert!((surface.points[0].time_to_expiry - 0.2).abs() < 1e-9);
+        assert_eq!(surface.source, "deribit");
+    }
+}
@@ -5,6 +5,7 @@
 //! 3. 자동 정산 실행
 
 use anyhow::Result;
+use oracle_vm_common::NetworkProfile;
 use btcfi_contracts::{
     bitvmx_proof_generator::OptionSettlementProofGenerator,
     bitvmx_presign::PreSignedSettlementBuilder,
@@ -20,7 +21,8 @@ fn main() -> Result<()> {
     println!("=== BitVMX Option Settlement Full Flow ===\n");
     
     // 1. 초기 설정
-    let network = Network::Testnet;
+    let profile = NetworkProfile::TESTNET;
+    let network = profile.network;
     let secp = Secp256k1::new();
     
     // 테스트 키 (실제로는 안전하게 생성/관리)
@@ -50,7 +52,7 @@ fn main() -> Result<()> {
     let option_value = Amount::from_sat(100_000_000); // 1 BTC locked
     
     // Pre-signed transaction 생성
-    let presign_builder = PreSignedSettlementBuilder::new(network);
+    let presign_builder = PreSignedSettlementBuilder::new(profile);
     
     // 간단한 정산 스크립트 (실제로는 BitVMX 검증 스크립트)
     let settlement_script = create_settlement_verification_script();
@@ -33,9 +33,9 @@ async fn main() -> Result<()> {
             error!("❌ Failed to connect to Aggregator: {}", e);
             error!("💡 Make sure to run:");
             error!("   1. cargo run -p aggregator");
-            error!("   2. cargo run -p oracle-node -- --exchange binance");
-            error!("   3. cargo run -p oracle-node -- --exchange coinbase");
-            error!("   4. cargo run -p oracle-node -- --exchange kraken");
+            error!("   2. cargo run -p oracle-node -- --dev --exchange binance");
+            error!("   3. cargo run -p oracle-node -- --dev --exchange coinbase");
+            error!("   4. cargo run -p oracle-node -- --dev --exchange kraken");
             return Err(e);
         }
     };
@@ -3,6 +3,7 @@ use btcfi_contracts::testnet_deployer::TestnetDeployer;
 use bitcoin::secp256k1::{Secp256k1, SecretKey, PublicKey};
 use bitcoin::{Network, Transaction, Address};
 use anyhow::Result;
+use oracle_vm_common::NetworkProfile;
 use std::str::FromStr;
 
 /// Bitcoin Testnet에서 실제 옵션 데모
@@ -14,7 +15,7 @@ async fn main() -> Result<()> {
     println!("================================\n");
     
     let secp = Secp256k1::new();
-    let deployer = TestnetDeployer::new();
+    let deployer = TestnetDeployer::new(NetworkProfile::TESTNET);
     
     // 테스트용 키 (실제로는 generate-keys로 생성한 키 사용)
     let buyer_secret = SecretKey::from_str("d8a1e1224e63135765bde9dc8a2c8e403eee8be73d3589d58c5ddbf9dce3fdf4")?;
@@ -0,0 +1,13 @@
+//! BitVMX 에뮬레이터 출력 파서 퍼징
+//!
+//! 실행: `cd contracts/fuzz && cargo +nightly fuzz run bitvmx_output corpus/bitvmx_output`
+
+#![no_main]
+
+use btcfi_contracts::bitvmx_bridge::parse_settlement_output;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // 오류는 허용, 패닉만 실패로 간주
+    let _ = parse_settlement_output(data);
+});
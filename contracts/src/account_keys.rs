@@ -0,0 +1,262 @@
+//! 계정 키 (보유자/LP 요청 서명)
+//!
+//! user_id, provider_id 같은 계정 ID는 요청 본문의 문자열일 뿐이므로 청구 잔고
+//! 출금, 조기 행사, 되사기/롤, 수익자 등록, LP 지분 출금, 출금 청구권 상환/이전처럼
+//! 돈의 행선지를 정하는 요청은 그 계정에 묶인 공개키의 서명을 요구합니다. 키는
+//! 계정이 처음 생길 때(옵션 생성, 청구권 양수) 한 번 묶이고 바꿀 수 없으며, 이미
+//! 상태가 있는 계정은 운영자만 묶을 수 있습니다. 같은 서명의 재전송을 막기 위해
+//! 계정마다 nonce가 커져야 합니다.
+
+use oracle_vm_common::crypto::{verify_signature, PublicKey, Signature};
+use oracle_vm_common::{AccountKeyError, CanonicalEncoder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// 요청 서명 (계정별로 커지는 nonce + DER hex 서명)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AccountSignature {
+    pub nonce: u64,
+    pub signature: String,
+}
+
+/// 청구 잔고 출금 서명 대상
+pub fn withdraw_payload(user_id: &str, address: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/withdraw/v1");
+    encoder.str(user_id).str(address).u64(nonce);
+    encoder.finish()
+}
+
+/// 미국식 조기 행사 서명 대상
+pub fn exercise_payload(option_id: &str, user_id: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/exercise/v1");
+    encoder.str(option_id).str(user_id).u64(nonce);
+    encoder.finish()
+}
+
+/// 되사기 서명 대상 (호가 ID까지 묶어 다른 호가로 바꿔 제출할 수 없음)
+pub fn buy_back_payload(option_id: &str, quote_id: &str, user_id: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/buy-back/v1");
+    encoder.str(option_id).str(quote_id).str(user_id).u64(nonce);
+    encoder.finish()
+}
+
+/// 롤 서명 대상 (되사기 호가와 새 옵션 호가를 함께 묶음)
+pub fn roll_payload(
+    option_id: &str,
+    buy_back_quote_id: &str,
+    quote_id: &str,
+    new_option_id: &str,
+    user_id: &str,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/roll/v1");
+    encoder
+        .str(option_id)
+        .str(buy_back_quote_id)
+        .str(quote_id)
+        .str(new_option_id)
+        .str(user_id)
+        .u64(nonce);
+    encoder.finish()
+}
+
+/// 수익자 등록 서명 대상 (지급 주소와 이후 변경에 쓸 소유자 키를 묶음)
+pub fn beneficiary_payload(option_id: &str, user_id: &str, address: &str, owner_key: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/beneficiary/v1");
+    encoder.str(option_id).str(user_id).str(address).str(owner_key).u64(nonce);
+    encoder.finish()
+}
+
+/// LP 지분 출금 서명 대상
+pub fn lp_exit_payload(provider_id: &str, shares: u64, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/lp-exit/v1");
+    encoder.str(provider_id).u64(shares).u64(nonce);
+    encoder.finish()
+}
+
+/// 출금 청구권 상환 서명 대상
+pub fn redeem_payload(claim_id: u64, holder: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/exit-claim-redeem/v1");
+    encoder.u64(claim_id).str(holder).u64(nonce);
+    encoder.finish()
+}
+
+/// 출금 청구권 이전 서명 대상 (양수인과 양수인 키까지 묶음)
+pub fn transfer_payload(claim_id: u64, from: &str, to: &str, to_key: &str, nonce: u64) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("account/exit-claim-transfer/v1");
+    encoder.u64(claim_id).str(from).str(to).str(to_key).u64(nonce);
+    encoder.finish()
+}
+
+/// 계정별 공개키와 마지막으로 쓴 nonce
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountKeys {
+    keys: BTreeMap<String, PublicKey>,
+    nonces: BTreeMap<String, u64>,
+}
+
+impl AccountKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn key(&self, account: &str) -> Option<&PublicKey> {
+        self.keys.get(account)
+    }
+
+    /// 공개키 hex 파싱
+    pub fn parse_key(public_key: &str) -> Result<PublicKey, AccountKeyError> {
+        PublicKey::from_str(public_key).map_err(|e| AccountKeyError::Malformed(e.to_string()))
+    }
+
+    /// 키를 묶을 수 있는지 확인 (이미 같은 키면 통과, 다른 키면 거부)
+    pub fn check_bind(&self, account: &str, key: &PublicKey) -> Result<(), AccountKeyError> {
+        match self.keys.get(account) {
+            Some(bound) if bound != key => Err(AccountKeyError::KeyMismatch(account.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// 키 묶기 (새로 묶였으면 true)
+    pub fn bind(&mut self, account: &str, key: PublicKey) -> Result<bool, AccountKeyError> {
+        self.check_bind(account, &key)?;
+        Ok(self.keys.insert(account.to_string(), key).is_none())
+    }
+
+    /// 서명과 nonce 확인 (상태는 바꾸지 않음)
+    pub fn verify(
+        &self,
+        account: &str,
+        payload: &[u8],
+        auth: &AccountSignature,
+    ) -> Result<(), AccountKeyError> {
+        let key = self
+            .keys
+            .get(account)
+            .ok_or_else(|| AccountKeyError::NoKey(account.to_string()))?;
+        let last = self.nonces.get(account).copied().unwrap_or(0);
+        if auth.nonce <= last {
+            return Err(AccountKeyError::StaleNonce {
+                account: account.to_string(),
+                nonce: auth.nonce,
+                last,
+            });
+        }
+        let signature =
+            Signature::from_str(&auth.signature).map_err(|e| AccountKeyError::Malformed(e.to_string()))?;
+        if !verify_signature(payload, &signature, key).unwrap_or(false) {
+            return Err(AccountKeyError::Unauthorized(account.to_string()));
+        }
+        Ok(())
+    }
+
+    /// 확인한 서명의 nonce 사용 처리
+    pub fn consume(&mut self, account: &str, nonce: u64) {
+        self.nonces.insert(account.to_string(), nonce);
+    }
+
+    /// 서명 확인 후 nonce 사용 처리
+    pub fn authorize(
+        &mut self,
+        account: &str,
+        payload: &[u8],
+        auth: &AccountSignature,
+    ) -> Result<(), AccountKeyError> {
+        self.verify(account, payload, auth)?;
+        self.consume(account, auth.nonce);
+        Ok(())
+    }
+}
+
+/// 테스트/클라이언트용: 계정 비밀키로 요청 서명
+pub fn sign_request(
+    payload: &[u8],
+    nonce: u64,
+    secret_key: &oracle_vm_common::crypto::SecretKey,
+) -> AccountSignature {
+    let signature = oracle_vm_common::crypto::sign_data(payload, secret_key)
+        .expect("sha256 digest is a valid message");
+    AccountSignature {
+        nonce,
+        signature: signature.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::crypto::generate_keypair;
+
+    #[test]
+    fn test_bind_once_and_verify_with_increasing_nonce() {
+        let (secret_key, public_key) = generate_keypair();
+        let (other_secret, other_key) = generate_keypair();
+        let mut keys = AccountKeys::new();
+        assert!(keys.bind("alice", public_key).unwrap());
+        assert!(!keys.bind("alice", public_key).unwrap());
+        assert_eq!(
+            keys.bind("alice", other_key),
+            Err(AccountKeyError::KeyMismatch("alice".to_string()))
+        );
+
+        let payload = withdraw_payload("alice", "tb1qaddr", 1);
+        keys.authorize("alice", &payload, &sign_request(&payload, 1, &secret_key))
+            .unwrap();
+        // 같은 서명 재전송, 다른 키 서명, 다른 주소로 바꾼 요청은 거부
+        assert!(matches!(
+            keys.authorize("alice", &payload, &sign_request(&payload, 1, &secret_key)),
+            Err(AccountKeyError::StaleNonce { last: 1, .. })
+        ));
+        let next = withdraw_payload("alice", "tb1qaddr", 2);
+        assert_eq!(
+            keys.verify("alice", &next, &sign_request(&next, 2, &other_secret)),
+            Err(AccountKeyError::Unauthorized("alice".to_string()))
+        );
+        let signed = sign_request(&next, 2, &secret_key);
+        let redirected = withdraw_payload("alice", "tb1qthief", 2);
+        assert!(keys.verify("alice", &redirected, &signed).is_err());
+        assert!(keys.verify("alice", &next, &signed).is_ok());
+        assert_eq!(
+            keys.verify("bob", &next, &signed),
+            Err(AccountKeyError::NoKey("bob".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_signature_does_not_carry_over_to_other_requests() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut keys = AccountKeys::new();
+        keys.bind("alice", public_key).unwrap();
+
+        // 같은 nonce라도 도메인이 다른 요청이나 다른 호가/양수인에는 쓸 수 없음
+        let exercise = exercise_payload("CALL-1", "alice", 1);
+        let signed = sign_request(&exercise, 1, &secret_key);
+        for payload in [
+            buy_back_payload("CALL-1", "Q-1", "alice", 1),
+            roll_payload("CALL-1", "Q-1", "Q-2", "CALL-2", "alice", 1),
+            beneficiary_payload("CALL-1", "alice", "tb1qaddr", "02ab", 1),
+            exercise_payload("CALL-2", "alice", 1),
+        ] {
+            assert_eq!(
+                keys.verify("alice", &payload, &signed),
+                Err(AccountKeyError::Unauthorized("alice".to_string()))
+            );
+        }
+        let transfer = transfer_payload(7, "alice", "bob", "02ab", 1);
+        let signed = sign_request(&transfer, 1, &secret_key);
+        assert!(keys
+            .verify("alice", &transfer_payload(7, "alice", "mallory", "02ab", 1), &signed)
+            .is_err());
+        assert!(keys
+            .verify("alice", &transfer_payload(7, "alice", "bob", "03cd", 1), &signed)
+            .is_err());
+        keys.authorize("alice", &transfer, &signed).unwrap();
+        assert_ne!(lp_exit_payload("alice", 7, 2), redeem_payload(7, "alice", 2));
+    }
+}
@@ -0,0 +1,265 @@
+//! 손실 분담과 자동 디레버리징 (ADL)
+//!
+//! 옵션 지급은 그 옵션의 담보에서 나가고, 담보를 넘는 몫은 풀의 사용 가능한
+//! 유동성을 추가로 잠가 충당합니다. 만기가 지난 옵션들에 줄 지급액 합계가 풀 자산
+//! (사용 가능한 유동성 + 지급 대기 옵션의 담보)을 넘으면 부족분을 지급 대기 중인
+//! 보유자들이 나눠 부담합니다. 규칙은 결정적입니다.
+//!
+//! 1. 포지션을 수익성(지급액 / 낸 프리미엄) 내림차순으로, 같으면 지급액 내림차순,
+//!    옵션 ID 오름차순으로 정렬합니다.
+//! 2. 부족분을 먼저 각 포지션의 이익(지급액 - 프리미엄)에 비례해 깎습니다.
+//! 3. 이익을 모두 깎아도 남는 부족분은 남은 지급액에 비례해 깎습니다.
+//!
+//! 비례 배분의 내림 나머지는 정렬 순서대로 1 sat씩 얹으므로, 같은 입력이면 항상
+//! 같은 결과가 나옵니다. 적용된 삭감은 `PayoutHaircut` 이벤트로 남고 정산 시
+//! 지급액에서 빠집니다.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// 지급 대기 중인 포지션
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingPayout {
+    pub option_id: String,
+    pub user_id: String,
+    pub owed: u64,    // satoshis
+    pub premium: u64, // satoshis
+}
+
+impl PendingPayout {
+    fn profit(&self) -> u64 {
+        self.owed.saturating_sub(self.premium)
+    }
+
+    /// 수익성 내림차순 (지급액/프리미엄을 교차 곱으로 비교, 프리미엄 0이 가장 앞)
+    fn rank_cmp(&self, other: &Self) -> Ordering {
+        let lhs = self.owed as u128 * other.premium as u128;
+        let rhs = other.owed as u128 * self.premium as u128;
+        rhs.cmp(&lhs)
+            .then_with(|| other.owed.cmp(&self.owed))
+            .then_with(|| self.option_id.cmp(&other.option_id))
+    }
+}
+
+/// 포지션 하나에 적용된 지급 삭감
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Haircut {
+    pub option_id: String,
+    pub user_id: String,
+    /// 삭감 전 지급액 (satoshis)
+    pub owed: u64,
+    pub haircut: u64, // satoshis
+    /// 수익성 순위 (1 = 가장 먼저 삭감)
+    pub rank: u32,
+}
+
+impl Haircut {
+    /// 삭감 후 지급액
+    pub fn paid(&self) -> u64 {
+        self.owed - self.haircut
+    }
+}
+
+/// 손실 분담 계산 결과
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaircutPlan {
+    /// 지급에 쓸 수 있는 풀 자산 (satoshis)
+    pub assets: u64,
+    /// 지급 대기 합계 (satoshis)
+    pub liabilities: u64,
+    /// 부족분 (satoshis, 삭감 합계와 같음)
+    pub shortfall: u64,
+    /// 삭감된 포지션 (수익성 순)
+    pub haircuts: Vec<Haircut>,
+}
+
+impl HaircutPlan {
+    pub fn is_empty(&self) -> bool {
+        self.haircuts.is_empty()
+    }
+}
+
+/// `amount`를 `weights`에 비례해 내림 배분하고 나머지는 앞에서부터 1씩 (각 몫은 weight 이하)
+fn distribute(weights: &[u64], amount: u64) -> Vec<u64> {
+    let total: u128 = weights.iter().map(|&weight| weight as u128).sum();
+    if total == 0 {
+        return vec![0; weights.len()];
+    }
+    let amount = (amount as u128).min(total);
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|&weight| (weight as u128 * amount / total) as u64)
+        .collect();
+    let mut remainder = amount as u64 - shares.iter().sum::<u64>();
+    for (share, &weight) in shares.iter_mut().zip(weights) {
+        if remainder == 0 {
+            break;
+        }
+        if *share < weight {
+            *share += 1;
+            remainder -= 1;
+        }
+    }
+    shares
+}
+
+/// 지급 대기 포지션과 풀 자산으로 삭감 계획 계산 (부족분이 없으면 빈 계획)
+pub fn plan_haircuts(mut payouts: Vec<PendingPayout>, assets: u64) -> HaircutPlan {
+    let liabilities: u64 = payouts.iter().map(|payout| payout.owed).sum();
+    let shortfall = liabilities.saturating_sub(assets);
+    if shortfall == 0 {
+        return HaircutPlan {
+            assets,
+            liabilities,
+            ..HaircutPlan::default()
+        };
+    }
+
+    payouts.sort_by(PendingPayout::rank_cmp);
+    let profits: Vec<u64> = payouts.iter().map(PendingPayout::profit).collect();
+    let from_profit = distribute(&profits, shortfall);
+    let remaining: Vec<u64> = payouts
+        .iter()
+        .zip(&from_profit)
+        .map(|(payout, cut)| payout.owed - cut)
+        .collect();
+    let from_principal = distribute(&remaining, shortfall - from_profit.iter().sum::<u64>());
+
+    let haircuts = payouts
+        .into_iter()
+        .enumerate()
+        .map(|(index, payout)| Haircut {
+            haircut: from_profit[index] + from_principal[index],
+            option_id: payout.option_id,
+            user_id: payout.user_id,
+            owed: payout.owed,
+            rank: index as u32 + 1,
+        })
+        .filter(|haircut| haircut.haircut > 0)
+        .collect();
+    HaircutPlan {
+        assets,
+        liabilities,
+        shortfall,
+        haircuts,
+    }
+}
+
+/// `/adl/*` 삭감 조회와 `/admin/adl/deleverage` API
+pub mod api {
+    use crate::admin_api::{error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct DeleverageRequest {
+        pub spot_price: u64, // USD cents
+        pub current_height: u32,
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/adl/haircuts",
+        tag = "adl",
+        responses((status = 200, body = Object))
+    )]
+    async fn list_haircuts(State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let haircuts: Vec<_> = manager.haircuts().collect();
+        Json(json!({ "haircuts": haircuts })).into_response()
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/adl/haircuts/{option_id}",
+        tag = "adl",
+        params(("option_id" = String, Path, description = "옵션 ID")),
+        responses((status = 200, body = Object),
+            (status = 404))
+    )]
+    async fn get_haircut(Path(option_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.haircut(&option_id) {
+            Some(haircut) => Json(haircut).into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/adl/deleverage",
+        tag = "adl",
+        request_body = DeleverageRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn deleverage(State(manager): State<SharedManager>, Json(request): Json<DeleverageRequest>) -> Response {
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.deleverage(request.spot_price, request.current_height) {
+            Ok(plan) => Json(plan).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// ADL 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/adl/haircuts", get(list_haircuts))
+            .route("/adl/haircuts/:option_id", get(get_haircut))
+            .route("/admin/adl/deleverage", post(deleverage))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payout(option_id: &str, owed: u64, premium: u64) -> PendingPayout {
+        PendingPayout {
+            option_id: option_id.to_string(),
+            user_id: format!("user-{}", option_id),
+            owed,
+            premium,
+        }
+    }
+
+    #[test]
+    fn test_haircuts_take_profit_first_in_rank_order() {
+        // 부족분 없음
+        assert!(plan_haircuts(vec![payout("A", 100, 10)], 100).is_empty());
+
+        // 부족분 30: 이익(90, 30, 0)에 비례 → 22.5 / 7.5 → 내림 22 / 7, 나머지 1은 1위(A)에
+        let plan = plan_haircuts(vec![payout("C", 50, 50), payout("B", 60, 30), payout("A", 100, 10)], 180);
+        assert_eq!((plan.liabilities, plan.shortfall), (210, 30));
+        let cuts: Vec<_> = plan.haircuts.iter().map(|h| (h.option_id.as_str(), h.haircut, h.rank)).collect();
+        assert_eq!(cuts, [("A", 23, 1), ("B", 7, 2)]);
+        assert_eq!(plan.haircuts.iter().map(|h| h.haircut).sum::<u64>(), plan.shortfall);
+
+        // 이익보다 큰 부족분: 이익 전액 + 남은 지급액 비례
+        let plan = plan_haircuts(vec![payout("A", 100, 10), payout("B", 60, 30), payout("C", 50, 50)], 70);
+        let paid: Vec<_> = plan.haircuts.iter().map(|h| (h.option_id.as_str(), h.paid())).collect();
+        assert_eq!(paid, [("A", 7), ("B", 24), ("C", 39)]);
+        assert_eq!(plan.haircuts.iter().map(|h| h.haircut).sum::<u64>(), 140);
+
+        // 입력 순서와 무관하게 같은 결과
+        let reordered = plan_haircuts(vec![payout("C", 50, 50), payout("A", 100, 10), payout("B", 60, 30)], 70);
+        assert_eq!(reordered, plan);
+    }
+}
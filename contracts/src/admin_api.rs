@@ -0,0 +1,707 @@
+//! 운영자용 관리 HTTP API (`/admin/*`)
+//!
+//! btcfi-admin CLI가 호출하는 엔드포인트입니다. 실행 중인 관리자 상태를 직접
+//! 조회/변경하므로 운영자가 저장소를 손으로 고칠 필요가 없습니다.
+//! 같은 라우터에서 `/reports/{kind}`도 관리자의 이벤트 저장소로 제공합니다.
+//!
+//! `/admin/*`, `/reports/*`, `/webhooks*`는 (테넌트 풀 아래 포함) 설정 파일의 운영자 토큰
+//! (`Authorization: Bearer`)이 있어야 하며, 토큰이 설정되지 않으면 모두 거부합니다.
+
+use crate::account_keys::AccountKeys;
+use crate::reporting::api::{render_report, ReportQuery};
+use crate::simple_contract::{OptionStatus, SimpleContractManager, SimpleOption};
+use crate::tenant::hash_api_key;
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use oracle_vm_common::crypto::{constant_time_eq, PublicKey};
+use oracle_vm_common::{ErrorClass, SystemEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use utoipa::{IntoParams, ToSchema};
+
+/// 관리 API 공유 상태
+pub type SharedManager = Arc<RwLock<SimpleContractManager>>;
+
+/// 오류 응답 본문 (`ErrorClass` 코드 포함)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AdminError {
+    pub code: String,
+    pub message: String,
+    pub retryable: bool,
+}
+
+pub(crate) fn error_response<E: ErrorClass + std::fmt::Display>(err: E) -> Response {
+    let code = err.code();
+    let status = if code.ends_with("NOT_FOUND") {
+        StatusCode::NOT_FOUND
+    } else if code.ends_with("UNAUTHORIZED") {
+        StatusCode::UNAUTHORIZED
+    } else if err.is_retryable() {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::CONFLICT
+    };
+    let body = AdminError {
+        code: code.to_string(),
+        message: err.to_string(),
+        retryable: err.is_retryable(),
+    };
+    (status, Json(body)).into_response()
+}
+
+pub(crate) fn bad_request(message: impl Into<String>) -> Response {
+    let body = AdminError {
+        code: "ADMIN_BAD_REQUEST".to_string(),
+        message: message.into(),
+        retryable: false,
+    };
+    (StatusCode::BAD_REQUEST, Json(body)).into_response()
+}
+
+fn unauthorized(message: &str) -> Response {
+    let body = AdminError {
+        code: "ADMIN_UNAUTHORIZED".to_string(),
+        message: message.to_string(),
+        retryable: false,
+    };
+    (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+}
+
+/// 운영자 토큰 검증 (설정 파일의 SHA256 hex 해시와 비교)
+#[derive(Debug, Clone, Default)]
+pub struct OperatorAuth {
+    token_hashes: Vec<String>,
+}
+
+impl OperatorAuth {
+    /// 토큰 해시 목록으로 생성 (64자리 hex가 아니면 거부)
+    pub fn new(token_hashes: Vec<String>) -> Result<Self, String> {
+        let token_hashes = token_hashes
+            .into_iter()
+            .map(|hash| {
+                let valid = hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit());
+                valid
+                    .then(|| hash.to_ascii_lowercase())
+                    .ok_or_else(|| format!("operator token hash must be 64 hex chars: {}", hash))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { token_hashes })
+    }
+
+    /// 토큰이 하나라도 설정되어 있는지 (없으면 운영자 경로 전체 거부)
+    pub fn is_configured(&self) -> bool {
+        !self.token_hashes.is_empty()
+    }
+
+    /// 토큰 검증 (해시를 상수 시간으로 비교)
+    pub fn authorize(&self, token: Option<&str>) -> bool {
+        let Some(hash) = token.map(hash_api_key) else {
+            return false;
+        };
+        self.token_hashes
+            .iter()
+            .fold(false, |found, allowed| found | constant_time_eq(allowed.as_bytes(), hash.as_bytes()))
+    }
+}
+
+/// 운영자 토큰이 필요한 경로 (`/admin/*`, `/reports/*`, `/webhooks*`, 테넌트 풀 아래 같은 경로)
+pub fn is_operator_path(path: &str) -> bool {
+    let path = match path.strip_prefix("/tenants/") {
+        Some(rest) => rest.find('/').map_or("", |slash| &rest[slash..]),
+        None => path,
+    };
+    path.starts_with("/admin/")
+        || path.starts_with("/reports/")
+        || path == "/webhooks"
+        || path.starts_with("/webhooks/")
+}
+
+async fn require_operator(State(auth): State<Arc<OperatorAuth>>, request: Request, next: Next) -> Response {
+    if !is_operator_path(request.uri().path()) {
+        return next.run(request).await;
+    }
+    if !auth.is_configured() {
+        return unauthorized("operator API disabled: no operator token configured");
+    }
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !auth.authorize(token) {
+        return unauthorized("missing or invalid operator token");
+    }
+    next.run(request).await
+}
+
+/// 앱 전체에 운영자 인증 적용 (운영자 경로만 검사, 나머지는 그대로 통과)
+pub fn with_operator_auth(app: Router, auth: OperatorAuth) -> Router {
+    app.layer(middleware::from_fn_with_state(Arc::new(auth), require_operator))
+}
+
+fn lock_poisoned() -> Response {
+    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+}
+
+/// 옵션 목록 필터
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct OptionFilter {
+    /// active, expired, settled
+    pub status: Option<String>,
+}
+
+fn parse_status(status: &str) -> Option<OptionStatus> {
+    match status.to_lowercase().as_str() {
+        "active" => Some(OptionStatus::Active),
+        "expired" => Some(OptionStatus::Expired),
+        "settled" => Some(OptionStatus::Settled),
+        "cancelled" => Some(OptionStatus::Cancelled),
+        _ => None,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/options",
+    tag = "admin",
+    params(OptionFilter),
+    responses(
+        (status = 200, body = [Object]),
+        (status = 400, body = AdminError)
+    )
+)]
+async fn list_options(
+    Query(filter): Query<OptionFilter>,
+    State(manager): State<SharedManager>,
+) -> Response {
+    let status = match filter.status.as_deref().map(parse_status) {
+        Some(None) => return bad_request("status must be active, expired or settled"),
+        Some(status) => status,
+        None => None,
+    };
+    let Ok(manager) = manager.read() else {
+        return lock_poisoned();
+    };
+
+    let mut options: Vec<&SimpleOption> = manager
+        .options
+        .values()
+        .filter(|option| status.is_none_or(|status| option.status == status))
+        .collect();
+    options.sort_by(|a, b| a.option_id.cmp(&b.option_id));
+    Json(options).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/options/{id}",
+    tag = "admin",
+    params(("id" = String, Path, description = "옵션 ID")),
+    responses(
+        (status = 200, body = Object),
+        (status = 404, body = AdminError)
+    )
+)]
+async fn get_option(Path(option_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+    let Ok(manager) = manager.read() else {
+        return lock_poisoned();
+    };
+    match manager.options.get(&option_id) {
+        Some(option) => Json(option).into_response(),
+        None => error_response(oracle_vm_common::SettlementError::OptionNotFound(option_id)),
+    }
+}
+
+/// 강제 만료 요청
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExpireRequest {
+    pub reason: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/options/{id}/expire",
+    tag = "admin",
+    params(("id" = String, Path, description = "옵션 ID")),
+    request_body = ExpireRequest,
+    responses(
+        (status = 200, body = Object),
+        (status = 404, body = AdminError),
+        (status = 409, body = AdminError)
+    )
+)]
+async fn expire_option(
+    Path(option_id): Path<String>,
+    State(manager): State<SharedManager>,
+    Json(request): Json<ExpireRequest>,
+) -> Response {
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    match manager.force_expire(&option_id, &request.reason) {
+        Ok(()) => Json(&manager.options[&option_id]).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// 정산 요청
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SettleRequest {
+    pub spot_price: u64, // USD cents
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/options/{id}/settle",
+    tag = "admin",
+    params(("id" = String, Path, description = "옵션 ID")),
+    request_body = SettleRequest,
+    responses(
+        (status = 200, body = Object),
+        (status = 404, body = AdminError),
+        (status = 409, body = AdminError)
+    )
+)]
+async fn settle_option(
+    Path(option_id): Path<String>,
+    State(manager): State<SharedManager>,
+    Json(request): Json<SettleRequest>,
+) -> Response {
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    match manager.settle_option(&option_id, request.spot_price) {
+        Ok(payout) => Json(json!({
+            "option_id": option_id,
+            "payout": payout,
+            "settlement": manager.settlement(&option_id),
+        }))
+        .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// 거래 중단 요청
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PauseRequest {
+    pub reason: String,
+    pub resume_at: Option<u64>, // 자동 재개 시각 (Unix timestamp)
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/trading/pause",
+    tag = "admin",
+    request_body = PauseRequest,
+    responses((status = 200, body = Object))
+)]
+async fn pause_trading(
+    State(manager): State<SharedManager>,
+    Json(request): Json<PauseRequest>,
+) -> Response {
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    manager.apply_system_event(&SystemEvent::TradingHalted {
+        reason: request.reason,
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        resume_at: request.resume_at,
+    });
+    Json(manager.trading_halt().cloned()).into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/trading/resume",
+    tag = "admin",
+    responses((status = 204))
+)]
+async fn resume_trading(State(manager): State<SharedManager>) -> Response {
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    manager.apply_system_event(&SystemEvent::TradingResumed {
+        timestamp: chrono::Utc::now().timestamp() as u64,
+        manual: true,
+    });
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// 계정 키 등록 요청 (이미 상태가 있는 계정을 운영자가 본인 확인 후 묶을 때)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BindAccountKeyRequest {
+    /// 공개키 hex
+    pub public_key: String,
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/accounts/{account}/key",
+    tag = "admin",
+    params(("account" = String, Path, description = "user_id 또는 provider_id")),
+    request_body = BindAccountKeyRequest,
+    responses(
+        (status = 204),
+        (status = 400, body = AdminError),
+        (status = 409, description = "다른 키가 이미 묶인 계정", body = AdminError)
+    )
+)]
+async fn bind_account_key(
+    Path(account): Path<String>,
+    State(manager): State<SharedManager>,
+    Json(request): Json<BindAccountKeyRequest>,
+) -> Response {
+    let key = match AccountKeys::parse_key(&request.public_key) {
+        Ok(key) => key,
+        Err(e) => return bad_request(e.to_string()),
+    };
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    match manager.bind_account_key(&account, key) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// 호가 서명키 교체 요청
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RotateKeyRequest {
+    pub public_key: String, // 압축 공개키 hex
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/keys/quote",
+    tag = "admin",
+    request_body = RotateKeyRequest,
+    responses(
+        (status = 204),
+        (status = 400, body = AdminError)
+    )
+)]
+async fn rotate_quote_key(
+    State(manager): State<SharedManager>,
+    Json(request): Json<RotateKeyRequest>,
+) -> Response {
+    let public_key = match PublicKey::from_str(&request.public_key) {
+        Ok(key) => key,
+        Err(e) => return bad_request(format!("Invalid public key: {}", e)),
+    };
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    match manager.rotate_quote_key(public_key) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/anchors/requeue",
+    tag = "admin",
+    responses((status = 200, description = "다시 조회 대상이 된 앵커의 옵션 ID", body = Object))
+)]
+async fn requeue_anchors(State(manager): State<SharedManager>) -> Response {
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    Json(json!({ "requeued": manager.requeue_anchors() })).into_response()
+}
+
+/// 경쟁 지출 감시 대상 등록 요청
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WatchUtxoRequest {
+    pub option_id: String,
+    pub outpoint: String, // txid:vout
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/mempool/watch",
+    tag = "admin",
+    request_body = WatchUtxoRequest,
+    responses(
+        (status = 204),
+        (status = 400, body = AdminError)
+    )
+)]
+async fn watch_utxo(State(manager): State<SharedManager>, Json(request): Json<WatchUtxoRequest>) -> Response {
+    let outpoint = match bitcoin::OutPoint::from_str(&request.outpoint) {
+        Ok(outpoint) => outpoint,
+        Err(e) => return bad_request(format!("Invalid outpoint: {}", e)),
+    };
+    let Ok(mut manager) = manager.write() else {
+        return lock_poisoned();
+    };
+    if !manager.options.contains_key(&request.option_id) {
+        return error_response(oracle_vm_common::SettlementError::OptionNotFound(request.option_id));
+    }
+    match manager.watch_utxo(&request.option_id, outpoint) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/mempool",
+    tag = "admin",
+    responses((status = 200, description = "감시 중인 UTXO와 무장한 챌린지", body = Object))
+)]
+async fn mempool_watch(State(manager): State<SharedManager>) -> Response {
+    let Ok(manager) = manager.read() else {
+        return lock_poisoned();
+    };
+    Json(manager.mempool_watch().records()).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/pool",
+    tag = "admin",
+    responses((status = 200, body = Object))
+)]
+async fn pool_metrics(State(manager): State<SharedManager>) -> Response {
+    let Ok(manager) = manager.read() else {
+        return lock_poisoned();
+    };
+    Json(manager.get_system_status()).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/{kind}",
+    tag = "reports",
+    params(
+        ("kind" = String, Path, description = "settlements, liquidity, premiums, payouts, fees, referrals, funding"),
+        ReportQuery
+    ),
+    responses(
+        (status = 200, description = "format에 따른 리포트", content(
+            (String = "text/csv"),
+            (Object = "application/json"),
+            (Vec<u8> = "application/vnd.apache.parquet")
+        )),
+        (status = 400, description = "알 수 없는 format"),
+        (status = 404, description = "알 수 없는 리포트 종류"),
+        (status = 501, description = "빌드에 포함되지 않은 format")
+    )
+)]
+async fn get_report(
+    Path(kind): Path<String>,
+    Query(params): Query<ReportQuery>,
+    State(manager): State<SharedManager>,
+) -> Result<Response, StatusCode> {
+    let manager = manager
+        .read()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    render_report(manager.event_store(), &kind, &params)
+}
+
+/// `/admin/*` 및 `/reports/{kind}` 라우터 생성
+pub fn router(manager: SharedManager) -> Router {
+    Router::new()
+        .route("/admin/options", get(list_options))
+        .route("/admin/options/:id", get(get_option))
+        .route("/admin/options/:id/expire", post(expire_option))
+        .route("/admin/options/:id/settle", post(settle_option))
+        .route("/admin/trading/pause", post(pause_trading))
+        .route("/admin/trading/resume", post(resume_trading))
+        .route("/admin/pool", get(pool_metrics))
+        .route("/admin/accounts/:account/key", put(bind_account_key))
+        .route("/admin/keys/quote", post(rotate_quote_key))
+        .route("/admin/anchors/requeue", post(requeue_anchors))
+        .route("/admin/mempool", get(mempool_watch))
+        .route("/admin/mempool/watch", post(watch_utxo))
+        .route("/reports/:kind", get(get_report))
+        .with_state(manager)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use oracle_vm_common::types::OptionType;
+    use tower::ServiceExt;
+
+    fn shared_manager() -> SharedManager {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        for id in ["OPT-1", "OPT-2"] {
+            manager
+                .create_option(
+                    id.to_string(),
+                    OptionType::Call,
+                    7_000_000,
+                    1_000_000,
+                    25_000,
+                    800_000,
+                    "user".to_string(),
+                )
+                .unwrap();
+        }
+        Arc::new(RwLock::new(manager))
+    }
+
+    async fn call(manager: &SharedManager, method: &str, uri: &str, body: serde_json::Value) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router(manager.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let value = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+        (status, value)
+    }
+
+    #[tokio::test]
+    async fn test_expire_settle_and_list() {
+        let manager = shared_manager();
+
+        let (status, body) = call(&manager, "POST", "/admin/options/OPT-1/expire", json!({ "reason": "stale oracle" })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "Expired");
+
+        let (status, body) = call(&manager, "POST", "/admin/options/OPT-2/settle", json!({ "spot_price": 7_200_000 })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payout"], 2_000);
+
+        let (status, body) = call(&manager, "POST", "/admin/options/OPT-2/settle", json!({ "spot_price": 7_200_000 })).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "SETTLEMENT_OPTION_NOT_ACTIVE");
+
+        let (status, body) = call(&manager, "GET", "/admin/options?status=active", json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!([]));
+
+        let (_, body) = call(&manager, "GET", "/admin/pool", json!(null)).await;
+        assert_eq!(body["pool_state"]["locked_collateral"], 0);
+        assert!(manager.read().unwrap().event_store().events().iter().any(|event| matches!(
+            event.kind,
+            crate::event_store::PoolEventKind::OptionExpired { .. }
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_pause_blocks_settlement_until_resume() {
+        let manager = shared_manager();
+
+        let (status, _) = call(&manager, "POST", "/admin/trading/pause", json!({ "reason": "maintenance" })).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = call(&manager, "POST", "/admin/options/OPT-1/settle", json!({ "spot_price": 7_200_000 })).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["code"], "SETTLEMENT_POSTPONED");
+
+        let (status, _) = call(&manager, "POST", "/admin/trading/resume", json!(null)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = call(&manager, "POST", "/admin/options/OPT-1/settle", json!({ "spot_price": 7_200_000 })).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = call(&manager, "POST", "/admin/keys/quote", json!({ "public_key": "zz" })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&manager, "GET", "/admin/options/NOPE", json!(null)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_quote_key_and_requeue_anchors() {
+        use crate::anchor_tracker::{AnchorStatus, AnchorTracker, TrackedAnchor, MAX_RECOVERY_ATTEMPTS};
+        use oracle_vm_common::crypto::generate_keypair;
+        use oracle_vm_common::NetworkProfile;
+
+        let manager = shared_manager();
+        let (_, public_key) = generate_keypair();
+        let (status, _) = call(&manager, "POST", "/admin/keys/quote", json!({ "public_key": public_key.to_string() })).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        // 교체한 키는 스냅샷에 남아 재시작 후에도 유지
+        let snapshot = manager.read().unwrap().snapshot(0, Vec::new());
+        assert_eq!(snapshot.quote_key, Some(public_key));
+
+        let (status, body) = call(&manager, "POST", "/admin/anchors/requeue", json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["requeued"], json!([]));
+
+        let mut tracker = AnchorTracker::new(NetworkProfile::TESTNET);
+        tracker.restore(vec![TrackedAnchor {
+            option_id: "OPT-1".to_string(),
+            txid: "tx1".to_string(),
+            payload: b"proof".to_vec(),
+            raw_tx: None,
+            status: AnchorStatus::Reorged { txid: "tx1".to_string() },
+            block_hash: None,
+            replaced: Vec::new(),
+            failures: MAX_RECOVERY_ATTEMPTS,
+        }]);
+        manager.write().unwrap().enable_anchor_tracking(tracker);
+        let (_, body) = call(&manager, "POST", "/admin/anchors/requeue", json!(null)).await;
+        assert_eq!(body["requeued"], json!(["OPT-1"]));
+    }
+
+    #[tokio::test]
+    async fn test_watch_utxo_is_listed_and_persisted() {
+        let manager = shared_manager();
+        let outpoint = format!("{}:1", "ab".repeat(32));
+        let request = |option_id: &str, outpoint: &str| json!({ "option_id": option_id, "outpoint": outpoint });
+
+        let (status, _) = call(&manager, "POST", "/admin/mempool/watch", request("OPT-1", "nope")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&manager, "POST", "/admin/mempool/watch", request("OPT-9", &outpoint)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = call(&manager, "POST", "/admin/mempool/watch", request("OPT-1", &outpoint)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, body) = call(&manager, "GET", "/admin/mempool", json!(null)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["watched"][0][0], json!(outpoint));
+        assert_eq!(body["watched"][0][1]["option_id"], "OPT-1");
+        let snapshot = manager.read().unwrap().snapshot(0, Vec::new());
+        assert_eq!(snapshot.mempool_watch.unwrap().watched.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_operator_token_required() {
+        let manager = shared_manager();
+        let send = |auth: OperatorAuth, uri: &str, token: Option<&str>| {
+            let mut request = Request::builder().uri(uri);
+            if let Some(token) = token {
+                request = request.header("authorization", format!("Bearer {}", token));
+            }
+            let app = with_operator_auth(router(manager.clone()), auth);
+            async move { app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status() }
+        };
+
+        let auth = OperatorAuth::new(vec![hash_api_key("ops-token")]).unwrap();
+        assert_eq!(send(auth.clone(), "/admin/pool", None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(send(auth.clone(), "/admin/pool", Some("guess")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(send(auth.clone(), "/admin/pool", Some("ops-token")).await, StatusCode::OK);
+        assert_eq!(send(auth, "/reports/settlements", None).await, StatusCode::UNAUTHORIZED);
+        // 토큰이 설정되지 않으면 올바른 요청도 거부
+        assert_eq!(
+            send(OperatorAuth::default(), "/admin/pool", Some("ops-token")).await,
+            StatusCode::UNAUTHORIZED
+        );
+
+        assert!(is_operator_path("/tenants/acme/admin/options"));
+        assert!(!is_operator_path("/tenants/acme/options"));
+        assert!(!is_operator_path("/options/admin/x"));
+        assert!(is_operator_path("/webhooks"));
+        assert!(is_operator_path("/webhooks/deliveries"));
+        assert!(!is_operator_path("/webhooksx"));
+        assert!(OperatorAuth::new(vec!["zz".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,749 @@
+//! 운영 경보 규칙과 알림 채널
+//!
+//! 주기마다 수집한 지표(`AlertInputs`)를 규칙(`AlertRule`)으로 평가해 Slack,
+//! PagerDuty, 이메일로 알립니다. 경보는 규칙 이름과 대상(옵션 ID 등)으로 만든
+//! 키로 구분해, 조건이 이어지는 동안에는 한 번만 발송하고(중복 제거) 조건이
+//! 풀리면 해제 알림을 보냅니다. 규칙의 `for_secs`는 조건이 그 시간 동안 계속
+//! 성립해야 발송하는 유예 시간입니다 (예: 만기 후 1시간 넘게 미정산).
+//!
+//! 설정 파일 (`contracts serve --alerting <path>`):
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     { "name": "consensus_failures", "condition": { "consensus_failures": { "threshold": 3 } }, "severity": "critical" },
+//!     { "name": "settlement_pending", "condition": "settlement_pending", "severity": "critical", "for_secs": 3600 }
+//!   ],
+//!   "sinks": [
+//!     { "slack": { "webhook_url": "https://hooks.slack.com/services/..." } },
+//!     { "pagerduty": { "routing_key": "..." } },
+//!     { "email": { "from": "alerts@btcfi.example", "to": ["ops@btcfi.example"] } }
+//!   ]
+//! }
+//! ```
+//!
+//! `rules`를 생략하면 `AlertRule::defaults()`를 씁니다.
+
+use crate::anchor_tracker::AnchorAlert;
+use crate::webhooks::{HttpTransport, WebhookRequest, WebhookTransport};
+use async_trait::async_trait;
+use oracle_vm_common::AlertingError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// PagerDuty Events API v2 주소
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// 경보 심각도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+/// 규칙 조건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// 합의 가격 수집이 `threshold`번 이상 연속 실패
+    ConsensusFailures { threshold: u64 },
+    /// 앵커 재전송/재앵커링 실패 (옵션마다 하나)
+    AnchorBroadcastFailed,
+    /// 풀 담보 사용률(%)이 `max_percent` 초과 (풀마다 하나)
+    HighUtilization { max_percent: f64 },
+    /// 만기가 지났지만 정산되지 않은 옵션 (옵션마다 하나, 보통 `for_secs`와 함께)
+    SettlementPending,
+}
+
+/// 경보 규칙
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub name: String,
+    pub condition: AlertCondition,
+    pub severity: AlertSeverity,
+    /// 조건이 이 시간(초) 동안 계속 성립해야 발송
+    #[serde(default)]
+    pub for_secs: u64,
+}
+
+impl AlertRule {
+    /// 기본 규칙: 합의 3회 연속 실패, 앵커 전송 실패, 사용률 90% 초과, 1시간 넘은 미정산
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                name: "consensus_failures".to_string(),
+                condition: AlertCondition::ConsensusFailures { threshold: 3 },
+                severity: AlertSeverity::Critical,
+                for_secs: 0,
+            },
+            Self {
+                name: "anchor_broadcast_failed".to_string(),
+                condition: AlertCondition::AnchorBroadcastFailed,
+                severity: AlertSeverity::Warning,
+                for_secs: 0,
+            },
+            Self {
+                name: "high_utilization".to_string(),
+                condition: AlertCondition::HighUtilization { max_percent: 90.0 },
+                severity: AlertSeverity::Warning,
+                for_secs: 0,
+            },
+            Self {
+                name: "settlement_pending".to_string(),
+                condition: AlertCondition::SettlementPending,
+                severity: AlertSeverity::Critical,
+                for_secs: 3_600,
+            },
+        ]
+    }
+
+    /// 성립한 조건마다 (대상, 요약)
+    fn matches(&self, inputs: &AlertInputs) -> Vec<(String, String)> {
+        match &self.condition {
+            AlertCondition::ConsensusFailures { threshold } => {
+                if inputs.consecutive_consensus_failures >= *threshold {
+                    vec![(
+                        "consensus".to_string(),
+                        format!(
+                            "Consensus price unavailable for {} consecutive rounds",
+                            inputs.consecutive_consensus_failures
+                        ),
+                    )]
+                } else {
+                    Vec::new()
+                }
+            }
+            AlertCondition::AnchorBroadcastFailed => inputs
+                .anchor_failures
+                .iter()
+                .map(|failure| {
+                    (
+                        failure.option_id.clone(),
+                        format!(
+                            "Anchor {} for {} failed to broadcast: {}",
+                            failure.txid, failure.option_id, failure.error
+                        ),
+                    )
+                })
+                .collect(),
+            AlertCondition::HighUtilization { max_percent } => inputs
+                .utilization
+                .iter()
+                .filter(|(_, percent)| *percent > *max_percent)
+                .map(|(pool, percent)| {
+                    (
+                        pool.clone(),
+                        format!("Pool {} utilization {:.1}% above {:.1}%", pool, percent, max_percent),
+                    )
+                })
+                .collect(),
+            AlertCondition::SettlementPending => inputs
+                .pending_settlements
+                .iter()
+                .map(|option_id| (option_id.clone(), format!("Option {} expired but is not settled", option_id)))
+                .collect(),
+        }
+    }
+}
+
+/// 실패한 앵커 전송
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorFailure {
+    pub option_id: String,
+    pub txid: String,
+    pub error: String,
+}
+
+/// 한 번의 평가에 쓰는 지표
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertInputs {
+    /// 합의 가격 수집 연속 실패 횟수
+    pub consecutive_consensus_failures: u64,
+    /// 마지막 앵커 조회에서 실패한 전송
+    pub anchor_failures: Vec<AnchorFailure>,
+    /// 풀 이름 → 담보 사용률 (%)
+    pub utilization: Vec<(String, f64)>,
+    /// 만기가 지났지만 정산되지 않은 옵션 ID
+    pub pending_settlements: Vec<String>,
+}
+
+impl AlertInputs {
+    /// `AnchorTracker::poll` 결과에서 실패한 전송 수집
+    pub fn observe_anchor_alerts(&mut self, alerts: &[AnchorAlert]) {
+        self.anchor_failures.extend(alerts.iter().filter_map(|alert| match alert {
+            AnchorAlert::Failed { option_id, txid, error } => Some(AnchorFailure {
+                option_id: option_id.clone(),
+                txid: txid.clone(),
+                error: error.clone(),
+            }),
+            _ => None,
+        }));
+    }
+}
+
+/// 발송 중인 경보
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    /// 중복 제거 키 (`{rule}:{subject}`)
+    pub key: String,
+    pub rule: String,
+    pub severity: AlertSeverity,
+    pub summary: String,
+    /// 조건이 처음 성립한 시각
+    pub since: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// 채널로 보내는 알림
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertNotification {
+    pub status: AlertStatus,
+    pub alert: Alert,
+    pub timestamp: u64,
+}
+
+impl AlertNotification {
+    /// 사람이 읽는 한 줄 요약
+    pub fn headline(&self) -> String {
+        match self.status {
+            AlertStatus::Firing => format!("[{}] {}", self.alert.severity.as_str().to_uppercase(), self.alert.summary),
+            AlertStatus::Resolved => format!("[RESOLVED] {}", self.alert.summary),
+        }
+    }
+}
+
+/// 규칙 평가기 (발송 상태를 기억해 중복 제거/해제 알림)
+#[derive(Debug, Clone)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    /// 조건 성립 중인 키 → 처음 성립한 시각 (`for_secs` 유예 포함)
+    active_since: BTreeMap<String, u64>,
+    /// 발송한 경보
+    firing: BTreeMap<String, Alert>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            active_since: BTreeMap::new(),
+            firing: BTreeMap::new(),
+        }
+    }
+
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    /// 발송 중인 경보 (키 순)
+    pub fn firing(&self) -> impl Iterator<Item = &Alert> {
+        self.firing.values()
+    }
+
+    /// 지표를 평가해 새로 발송할 경보와 해제된 경보를 반환
+    pub fn evaluate(&mut self, inputs: &AlertInputs, now: u64) -> Vec<AlertNotification> {
+        let mut matched = BTreeMap::new();
+        for rule in &self.rules {
+            for (subject, summary) in rule.matches(inputs) {
+                matched.insert(format!("{}:{}", rule.name, subject), (rule, summary));
+            }
+        }
+
+        let mut notifications = Vec::new();
+        let resolved: Vec<String> = self
+            .firing
+            .keys()
+            .filter(|key| !matched.contains_key(*key))
+            .cloned()
+            .collect();
+        for key in resolved {
+            let alert = self.firing.remove(&key).expect("firing alert");
+            notifications.push(AlertNotification {
+                status: AlertStatus::Resolved,
+                alert,
+                timestamp: now,
+            });
+        }
+        self.active_since.retain(|key, _| matched.contains_key(key));
+
+        for (key, (rule, summary)) in matched {
+            let since = *self.active_since.entry(key.clone()).or_insert(now);
+            if self.firing.contains_key(&key) || now.saturating_sub(since) < rule.for_secs {
+                continue;
+            }
+            let alert = Alert {
+                key: key.clone(),
+                rule: rule.name.clone(),
+                severity: rule.severity,
+                summary,
+                since,
+            };
+            self.firing.insert(key, alert.clone());
+            notifications.push(AlertNotification {
+                status: AlertStatus::Firing,
+                alert,
+                timestamp: now,
+            });
+        }
+        notifications
+    }
+}
+
+/// 알림 채널
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), String>;
+}
+
+/// 2xx가 아니면 오류
+async fn post_json(
+    transport: &dyn WebhookTransport,
+    url: &str,
+    headers: Vec<(&'static str, String)>,
+    body: serde_json::Value,
+) -> Result<(), String> {
+    let request = WebhookRequest {
+        url: url.to_string(),
+        headers,
+        body: body.to_string().into_bytes(),
+    };
+    match transport.post(&request).await? {
+        status if (200..300).contains(&status) => Ok(()),
+        status => Err(format!("HTTP {}", status)),
+    }
+}
+
+/// Slack incoming webhook
+pub struct SlackSink {
+    webhook_url: String,
+    transport: Arc<dyn WebhookTransport>,
+}
+
+impl SlackSink {
+    pub fn new(webhook_url: impl Into<String>, transport: Arc<dyn WebhookTransport>) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            transport,
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for SlackSink {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), String> {
+        let icon = match (notification.status, notification.alert.severity) {
+            (AlertStatus::Resolved, _) => ":white_check_mark:",
+            (AlertStatus::Firing, AlertSeverity::Critical) => ":rotating_light:",
+            (AlertStatus::Firing, AlertSeverity::Warning) => ":warning:",
+        };
+        let body = json!({ "text": format!("{} {}", icon, notification.headline()) });
+        post_json(self.transport.as_ref(), &self.webhook_url, Vec::new(), body).await
+    }
+}
+
+/// PagerDuty Events API v2 (경보 키를 dedup_key로 써 발송/해제를 같은 인시던트에 묶음)
+pub struct PagerDutySink {
+    routing_key: String,
+    url: String,
+    transport: Arc<dyn WebhookTransport>,
+}
+
+impl PagerDutySink {
+    pub fn new(routing_key: impl Into<String>, transport: Arc<dyn WebhookTransport>) -> Self {
+        Self {
+            routing_key: routing_key.into(),
+            url: PAGERDUTY_EVENTS_URL.to_string(),
+            transport,
+        }
+    }
+
+    /// Events API 주소 교체 (프록시, 테스트)
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+}
+
+#[async_trait]
+impl AlertSink for PagerDutySink {
+    fn name(&self) -> &'static str {
+        "pagerduty"
+    }
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), String> {
+        let alert = &notification.alert;
+        let body = match notification.status {
+            AlertStatus::Firing => json!({
+                "routing_key": self.routing_key,
+                "event_action": "trigger",
+                "dedup_key": alert.key,
+                "payload": {
+                    "summary": alert.summary,
+                    "severity": alert.severity.as_str(),
+                    "source": "btcfi-contracts",
+                    "component": alert.rule,
+                },
+            }),
+            AlertStatus::Resolved => json!({
+                "routing_key": self.routing_key,
+                "event_action": "resolve",
+                "dedup_key": alert.key,
+            }),
+        };
+        post_json(self.transport.as_ref(), &self.url, Vec::new(), body).await
+    }
+}
+
+/// 메일 발송 방식 (테스트에서는 mock 사용)
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, from: &str, to: &[String], subject: &str, body: &str) -> Result<(), String>;
+}
+
+/// 로컬 MTA의 `sendmail -t`로 발송
+pub struct SendmailMailer {
+    program: String,
+}
+
+impl Default for SendmailMailer {
+    fn default() -> Self {
+        Self {
+            program: "/usr/sbin/sendmail".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SendmailMailer {
+    async fn send(&self, from: &str, to: &[String], subject: &str, body: &str) -> Result<(), String> {
+        use tokio::io::AsyncWriteExt;
+
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+            from,
+            to.join(", "),
+            subject,
+            body
+        );
+        let mut child = tokio::process::Command::new(&self.program)
+            .arg("-t")
+            .stdin(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("{}: {}", self.program, e))?;
+        let mut stdin = child.stdin.take().ok_or("sendmail stdin unavailable")?;
+        stdin.write_all(message.as_bytes()).await.map_err(|e| e.to_string())?;
+        drop(stdin);
+        let status = child.wait().await.map_err(|e| e.to_string())?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{} exited with {}", self.program, status))
+        }
+    }
+}
+
+/// 이메일 알림
+pub struct EmailSink {
+    from: String,
+    to: Vec<String>,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl EmailSink {
+    pub fn new(from: impl Into<String>, to: Vec<String>, mailer: Arc<dyn Mailer>) -> Self {
+        Self {
+            from: from.into(),
+            to,
+            mailer,
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for EmailSink {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, notification: &AlertNotification) -> Result<(), String> {
+        let alert = &notification.alert;
+        // 제목은 한 줄이어야 하므로 요약의 줄바꿈 제거
+        let subject = notification.headline().replace(['\r', '\n'], " ");
+        let body = format!(
+            "{}\n\nrule: {}\nkey: {}\nseverity: {}\nsince: {}\nat: {}\n",
+            alert.summary,
+            alert.rule,
+            alert.key,
+            alert.severity.as_str(),
+            alert.since,
+            notification.timestamp
+        );
+        self.mailer.send(&self.from, &self.to, &subject, &body).await
+    }
+}
+
+/// 알림 채널 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSinkConfig {
+    Slack { webhook_url: String },
+    #[serde(rename = "pagerduty")]
+    PagerDuty { routing_key: String },
+    Email { from: String, to: Vec<String> },
+}
+
+/// 경보 설정 파일
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertingConfig {
+    #[serde(default = "AlertRule::defaults")]
+    pub rules: Vec<AlertRule>,
+    #[serde(default)]
+    pub sinks: Vec<AlertSinkConfig>,
+}
+
+impl AlertingConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AlertingError> {
+        let path = path.as_ref();
+        let body = std::fs::read_to_string(path)
+            .map_err(|e| AlertingError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&body)
+            .map_err(|e| AlertingError::InvalidConfig(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 설정한 채널 생성 (HTTP 채널은 하나의 전송을 공유)
+    pub fn build_sinks(&self) -> Vec<Box<dyn AlertSink>> {
+        let transport: Arc<dyn WebhookTransport> = Arc::new(HttpTransport::new(Duration::from_secs(10)));
+        let mailer: Arc<dyn Mailer> = Arc::new(SendmailMailer::default());
+        self.sinks
+            .iter()
+            .map(|sink| -> Box<dyn AlertSink> {
+                match sink {
+                    AlertSinkConfig::Slack { webhook_url } => Box::new(SlackSink::new(webhook_url, transport.clone())),
+                    AlertSinkConfig::PagerDuty { routing_key } => {
+                        Box::new(PagerDutySink::new(routing_key, transport.clone()))
+                    }
+                    AlertSinkConfig::Email { from, to } => Box::new(EmailSink::new(from, to.clone(), mailer.clone())),
+                }
+            })
+            .collect()
+    }
+}
+
+/// 규칙 평가기 + 채널 묶음
+pub struct AlertManager {
+    engine: AlertEngine,
+    sinks: Vec<Box<dyn AlertSink>>,
+}
+
+impl AlertManager {
+    pub fn new(engine: AlertEngine, sinks: Vec<Box<dyn AlertSink>>) -> Self {
+        Self { engine, sinks }
+    }
+
+    pub fn from_config(config: &AlertingConfig) -> Self {
+        Self::new(AlertEngine::new(config.rules.clone()), config.build_sinks())
+    }
+
+    pub fn engine(&self) -> &AlertEngine {
+        &self.engine
+    }
+
+    /// 평가 후 모든 채널로 발송 (채널 실패는 기록만 하고 다른 채널은 계속)
+    ///
+    /// 발송/해제한 알림을 반환합니다. 실패한 발송은 재시도하지 않으며, 발송
+    /// 중인 경보는 조건이 풀릴 때 해제 알림으로 닫힙니다.
+    pub async fn evaluate(&mut self, inputs: &AlertInputs, now: u64) -> Vec<AlertNotification> {
+        let notifications = self.engine.evaluate(inputs, now);
+        for notification in &notifications {
+            info!("Alert {}: {}", notification.alert.key, notification.headline());
+            for sink in &self.sinks {
+                if let Err(e) = sink.send(notification).await {
+                    warn!("Alert {} not delivered to {}: {}", notification.alert.key, sink.name(), e);
+                }
+            }
+        }
+        notifications
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        sent: Mutex<Vec<WebhookRequest>>,
+    }
+
+    #[async_trait]
+    impl WebhookTransport for RecordingTransport {
+        async fn post(&self, request: &WebhookRequest) -> Result<u16, String> {
+            self.sent.lock().unwrap().push(request.clone());
+            Ok(202)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingMailer {
+        sent: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl Mailer for RecordingMailer {
+        async fn send(&self, _from: &str, to: &[String], subject: &str, _body: &str) -> Result<(), String> {
+            self.sent.lock().unwrap().push((to.join(","), subject.to_string()));
+            Ok(())
+        }
+    }
+
+    fn body(request: &WebhookRequest) -> serde_json::Value {
+        serde_json::from_slice(&request.body).unwrap()
+    }
+
+    #[test]
+    fn test_rules_deduplicate_and_resolve() {
+        let mut engine = AlertEngine::new(AlertRule::defaults());
+        let mut inputs = AlertInputs {
+            consecutive_consensus_failures: 3,
+            utilization: vec![("default".to_string(), 95.0)],
+            pending_settlements: vec!["CALL-1".to_string()],
+            ..AlertInputs::default()
+        };
+        inputs.observe_anchor_alerts(&[
+            AnchorAlert::Failed {
+                option_id: "CALL-2".to_string(),
+                txid: "ab".to_string(),
+                error: "mempool full".to_string(),
+            },
+            AnchorAlert::Rebroadcast {
+                option_id: "CALL-3".to_string(),
+                txid: "cd".to_string(),
+            },
+        ]);
+
+        // 미정산은 1시간 유예, 나머지는 바로 발송
+        let fired = engine.evaluate(&inputs, 1_000);
+        let keys: Vec<_> = fired.iter().map(|n| n.alert.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["anchor_broadcast_failed:CALL-2", "consensus_failures:consensus", "high_utilization:default"]
+        );
+        assert!(fired.iter().all(|n| n.status == AlertStatus::Firing));
+
+        // 조건이 이어지는 동안은 다시 보내지 않음
+        assert!(engine.evaluate(&inputs, 1_060).is_empty());
+        let late = engine.evaluate(&inputs, 4_600);
+        assert_eq!(late.len(), 1);
+        assert_eq!(late[0].alert.key, "settlement_pending:CALL-1");
+        assert_eq!(late[0].alert.since, 1_000);
+
+        // 합의 회복과 정산 완료는 해제 알림
+        inputs.consecutive_consensus_failures = 0;
+        inputs.pending_settlements.clear();
+        let resolved = engine.evaluate(&inputs, 4_660);
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved.iter().all(|n| n.status == AlertStatus::Resolved));
+        assert_eq!(engine.firing().count(), 2);
+    }
+
+    #[test]
+    fn test_condition_cleared_during_grace_period_never_fires() {
+        let mut engine = AlertEngine::new(AlertRule::defaults());
+        let pending = AlertInputs {
+            pending_settlements: vec!["CALL-1".to_string()],
+            ..AlertInputs::default()
+        };
+        assert!(engine.evaluate(&pending, 0).is_empty());
+        assert!(engine.evaluate(&AlertInputs::default(), 1_800).is_empty());
+        // 다시 성립하면 유예 시간을 처음부터 셈
+        assert!(engine.evaluate(&pending, 3_000).is_empty());
+        assert!(engine.evaluate(&pending, 6_000).is_empty());
+        assert_eq!(engine.evaluate(&pending, 6_600).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sinks_receive_trigger_and_resolve() {
+        let transport = Arc::new(RecordingTransport::default());
+        let mailer = Arc::new(RecordingMailer::default());
+        let rules = vec![AlertRule {
+            name: "high_utilization".to_string(),
+            condition: AlertCondition::HighUtilization { max_percent: 90.0 },
+            severity: AlertSeverity::Warning,
+            for_secs: 0,
+        }];
+        let mut manager = AlertManager::new(
+            AlertEngine::new(rules),
+            vec![
+                Box::new(SlackSink::new("https://hooks.slack.test/T1", transport.clone())),
+                Box::new(PagerDutySink::new("rk-1", transport.clone()).with_url("https://pd.test/enqueue")),
+                Box::new(EmailSink::new("alerts@btcfi.test", vec!["ops@btcfi.test".to_string()], mailer.clone())),
+            ],
+        );
+
+        let busy = AlertInputs {
+            utilization: vec![("default".to_string(), 92.5)],
+            ..AlertInputs::default()
+        };
+        assert_eq!(manager.evaluate(&busy, 10).await.len(), 1);
+        assert_eq!(manager.evaluate(&AlertInputs::default(), 20).await.len(), 1);
+
+        let sent = transport.sent.lock().unwrap();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[0].url, "https://hooks.slack.test/T1");
+        assert_eq!(body(&sent[0])["text"], ":warning: [WARNING] Pool default utilization 92.5% above 90.0%");
+        assert_eq!(body(&sent[1])["event_action"], "trigger");
+        assert_eq!(body(&sent[1])["dedup_key"], "high_utilization:default");
+        assert_eq!(body(&sent[1])["payload"]["severity"], "warning");
+        assert_eq!(body(&sent[3])["event_action"], "resolve");
+        assert_eq!(body(&sent[3])["dedup_key"], "high_utilization:default");
+
+        let mails = mailer.sent.lock().unwrap();
+        assert_eq!(mails.len(), 2);
+        assert_eq!(mails[1].0, "ops@btcfi.test");
+        assert!(mails[1].1.starts_with("[RESOLVED]"));
+    }
+
+    #[test]
+    fn test_config_defaults_rules() {
+        let config: AlertingConfig = serde_json::from_str(
+            r#"{"sinks": [{"slack": {"webhook_url": "https://hooks.slack.test/T1"}}, {"pagerduty": {"routing_key": "rk"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.rules, AlertRule::defaults());
+        assert_eq!(config.build_sinks().len(), 2);
+
+        let rule: AlertRule = serde_json::from_str(
+            r#"{"name": "stuck", "condition": "settlement_pending", "severity": "critical", "for_secs": 600}"#,
+        )
+        .unwrap();
+        assert_eq!(rule.condition, AlertCondition::SettlementPending);
+    }
+}
@@ -0,0 +1,518 @@
+//! 앵커링 백엔드 선택 (Bitcoin / Liquid)
+//!
+//! 모든 앵커 페이로드는 같은 스키마(3바이트 태그 + 본문)를 쓰므로 태그로 앵커
+//! 종류를 구분해 체인을 고를 수 있습니다. 빈번한 CREATE/BUY 앵커는 수수료가
+//! 싼 Liquid(Elements RPC)로 보내고, 정산 앵커는 항상 Bitcoin에 남깁니다.
+//! `RoutedAnchorer`는 `AnchorBroadcaster`를 구현하므로 기존 앵커링 코드에
+//! 그대로 끼울 수 있습니다. Bitcoin 쪽은 `BitcoinAnchorer`가 bitcoind 지갑으로
+//! 수수료를 채워 앵커/정산 트랜잭션을 보냅니다.
+
+use crate::anchor_tracker::AnchorBroadcaster;
+use crate::beneficiary::BUY_ANCHOR_TAG;
+use crate::binary::BINARY_ANCHOR_TAG;
+use crate::bootstrap::{call_blocking, BitcoindRpc};
+use crate::buy_back::{CANCEL_ANCHOR_TAG, ROLL_ANCHOR_TAG};
+use crate::early_exercise::EXERCISE_ANCHOR_TAG;
+use crate::price_commitment::PRICE_ANCHOR_TAG;
+use crate::program_binding::ProgramBinding;
+use oracle_vm_common::option_id::{OptionId, SHORT_ID_LEN};
+use oracle_vm_common::AnchorError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 옵션 생성 앵커 태그
+pub const CREATE_ANCHOR_TAG: &[u8; 3] = b"CRT";
+/// 정산 앵커 태그
+pub const SETTLE_ANCHOR_TAG: &[u8; 3] = b"STL";
+
+/// 옵션 생성 앵커 페이로드: "CRT" || 옵션 단축 ID(6) || 프로그램 바인딩 커밋먼트(32)
+pub fn create_anchor_payload(option_id: &OptionId, binding: &ProgramBinding) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(CREATE_ANCHOR_TAG.len() + SHORT_ID_LEN + 32);
+    payload.extend_from_slice(CREATE_ANCHOR_TAG);
+    payload.extend_from_slice(&option_id.short_id());
+    payload.extend_from_slice(&binding.commitment());
+    payload
+}
+
+/// 생성 앵커 페이로드에서 프로그램 바인딩 커밋먼트 추출
+pub fn create_anchor_binding(payload: &[u8]) -> Option<[u8; 32]> {
+    if AnchorKind::of_payload(payload) != AnchorKind::Create {
+        return None;
+    }
+    payload.get(3 + SHORT_ID_LEN..)?.try_into().ok()
+}
+
+/// 생성 앵커 페이로드에서 옵션 단축 ID 추출
+pub fn create_anchor_short_id(payload: &[u8]) -> Option<[u8; SHORT_ID_LEN]> {
+    if AnchorKind::of_payload(payload) != AnchorKind::Create {
+        return None;
+    }
+    payload.get(3..3 + SHORT_ID_LEN)?.try_into().ok()
+}
+
+/// Liquid OP_RETURN 페이로드 최대 크기 (Bitcoin과 같은 기본 relay 한도)
+pub const MAX_LIQUID_PAYLOAD: usize = 80;
+
+/// 앵커 종류 (페이로드 태그 기준)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorKind {
+    Create,
+    Buy,
+    Settle,
+    /// 바이너리 옵션 정산 (고정 지급액 포함)
+    BinarySettle,
+    /// 미국식 조기 행사
+    Exercise,
+    /// 만기 전 되사기
+    Cancel,
+    /// 롤 (이전 옵션 종료 + 새 옵션 생성)
+    Roll,
+    PriceCommitment,
+    /// 알 수 없는 태그
+    Other,
+}
+
+impl AnchorKind {
+    pub fn of_payload(payload: &[u8]) -> Self {
+        match payload.get(..3) {
+            Some(tag) if tag == CREATE_ANCHOR_TAG => Self::Create,
+            Some(tag) if tag == BUY_ANCHOR_TAG => Self::Buy,
+            Some(tag) if tag == SETTLE_ANCHOR_TAG => Self::Settle,
+            Some(tag) if tag == BINARY_ANCHOR_TAG => Self::BinarySettle,
+            Some(tag) if tag == EXERCISE_ANCHOR_TAG => Self::Exercise,
+            Some(tag) if tag == CANCEL_ANCHOR_TAG => Self::Cancel,
+            Some(tag) if tag == ROLL_ANCHOR_TAG => Self::Roll,
+            Some(tag) if tag == PRICE_ANCHOR_TAG => Self::PriceCommitment,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// 앵커를 남길 체인
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorChain {
+    #[default]
+    Bitcoin,
+    Liquid,
+}
+
+/// 앵커 종류별 체인 설정 (없는 종류는 Bitcoin)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorRouting {
+    #[serde(default)]
+    pub routes: HashMap<AnchorKind, AnchorChain>,
+}
+
+impl AnchorRouting {
+    /// CREATE/BUY는 Liquid, 나머지는 Bitcoin
+    pub fn liquid_for_trades() -> Self {
+        Self {
+            routes: HashMap::from([
+                (AnchorKind::Create, AnchorChain::Liquid),
+                (AnchorKind::Buy, AnchorChain::Liquid),
+            ]),
+        }
+    }
+
+    pub fn chain_for(&self, kind: AnchorKind) -> AnchorChain {
+        self.routes.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// 정산 앵커는 Bitcoin에만 둘 수 있음
+    pub fn validate(&self) -> Result<(), AnchorError> {
+        if [AnchorKind::Settle, AnchorKind::BinarySettle]
+            .into_iter()
+            .any(|kind| self.chain_for(kind) != AnchorChain::Bitcoin)
+        {
+            return Err(AnchorError::InvalidRoute(
+                "settlement anchors must stay on Bitcoin".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 특정 체인에 앵커를 남기는 백엔드
+pub trait AnchorBackend: AnchorBroadcaster {
+    fn chain(&self) -> AnchorChain;
+}
+
+/// RPC 응답 객체의 hex 문자열 필드
+fn hex_field(value: &Value, field: &str, method: &str) -> Result<String, AnchorError> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| AnchorError::Rpc(format!("{} returned no `{}`", method, field)))
+}
+
+/// 문자열 RPC 응답 (txid, hex)
+fn as_string(value: Value, method: &str) -> Result<String, AnchorError> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AnchorError::Rpc(format!("{} returned {}", method, value)))
+}
+
+/// Bitcoin(bitcoind 지갑) 앵커링 백엔드
+///
+/// 데이터 출력만 있는 트랜잭션을 `wallet`으로 채우고 서명해 보냅니다.
+/// 앵커링 인터페이스는 동기이므로 `call_blocking`으로 호출합니다.
+pub struct BitcoinAnchorer {
+    rpc: Arc<dyn BitcoindRpc>,
+    wallet: String,
+}
+
+impl BitcoinAnchorer {
+    pub fn new(rpc: Arc<dyn BitcoindRpc>, wallet: impl Into<String>) -> Self {
+        Self {
+            rpc,
+            wallet: wallet.into(),
+        }
+    }
+
+    fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> Result<Value, AnchorError> {
+        call_blocking(self.rpc.as_ref(), wallet, method, params)
+    }
+}
+
+impl AnchorBroadcaster for BitcoinAnchorer {
+    /// 이미 멤풀/블록에 있는 트랜잭션은 성공으로 봄
+    fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+        match self.call(None, "sendrawtransaction", vec![json!(hex::encode(raw_tx))]) {
+            Ok(txid) => as_string(txid, "sendrawtransaction"),
+            Err(AnchorError::Rpc(message)) if message.contains("already") => {
+                let tx: bitcoin::Transaction = bitcoin::consensus::deserialize(raw_tx)
+                    .map_err(|e| AnchorError::InvalidPayload(e.to_string()))?;
+                Ok(tx.compute_txid().to_string())
+            }
+            Err(AnchorError::Rpc(message)) => Err(AnchorError::BroadcastRejected(message)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+        let wallet = Some(self.wallet.as_str());
+        let raw = self.call(
+            None,
+            "createrawtransaction",
+            vec![json!([]), json!([{ "data": hex::encode(payload) }])],
+        )?;
+        let raw = as_string(raw, "createrawtransaction")?;
+        let funded = self.call(wallet, "fundrawtransaction", vec![json!(raw)])?;
+        let funded = hex_field(&funded, "hex", "fundrawtransaction")?;
+        let signed = self.call(wallet, "signrawtransactionwithwallet", vec![json!(funded)])?;
+        if signed.get("complete").and_then(Value::as_bool) != Some(true) {
+            return Err(AnchorError::Rpc("signrawtransactionwithwallet incomplete".to_string()));
+        }
+        let signed = hex_field(&signed, "hex", "signrawtransactionwithwallet")?;
+        let txid = self.call(None, "sendrawtransaction", vec![json!(signed)])?;
+        as_string(txid, "sendrawtransaction")
+    }
+}
+
+impl AnchorBackend for BitcoinAnchorer {
+    fn chain(&self) -> AnchorChain {
+        AnchorChain::Bitcoin
+    }
+}
+
+/// Elements JSON-RPC 호출 인터페이스
+pub trait ElementsRpc {
+    fn call(&self, method: &str, params: Vec<Value>) -> Result<Value, AnchorError>;
+}
+
+/// Liquid(Elements) 앵커링 백엔드
+///
+/// 데이터 출력만 있는 트랜잭션을 만들어 지갑으로 수수료를 채우고, 거스름 출력을
+/// blinding한 뒤 서명/전송합니다.
+pub struct LiquidAnchorer<R: ElementsRpc> {
+    rpc: R,
+}
+
+impl<R: ElementsRpc> LiquidAnchorer<R> {
+    pub fn new(rpc: R) -> Self {
+        Self { rpc }
+    }
+
+}
+
+impl<R: ElementsRpc> AnchorBroadcaster for LiquidAnchorer<R> {
+    fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+        let txid = self.rpc.call("sendrawtransaction", vec![json!(hex::encode(raw_tx))])?;
+        as_string(txid, "sendrawtransaction")
+    }
+
+    fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+        if payload.len() > MAX_LIQUID_PAYLOAD {
+            return Err(AnchorError::InvalidPayload(format!(
+                "{} bytes exceeds Liquid OP_RETURN limit of {}",
+                payload.len(),
+                MAX_LIQUID_PAYLOAD
+            )));
+        }
+
+        let raw = self.rpc.call(
+            "createrawtransaction",
+            vec![json!([]), json!([{ "data": hex::encode(payload) }])],
+        )?;
+        let raw = as_string(raw, "createrawtransaction")?;
+        let funded = self.rpc.call("fundrawtransaction", vec![json!(raw)])?;
+        let funded = hex_field(&funded, "hex", "fundrawtransaction")?;
+        let blinded = self.rpc.call("blindrawtransaction", vec![json!(funded)])?;
+        let blinded = as_string(blinded, "blindrawtransaction")?;
+        let signed = self.rpc.call("signrawtransactionwithwallet", vec![json!(blinded)])?;
+        if signed.get("complete").and_then(Value::as_bool) != Some(true) {
+            return Err(AnchorError::Rpc("signrawtransactionwithwallet incomplete".to_string()));
+        }
+        let signed = hex_field(&signed, "hex", "signrawtransactionwithwallet")?;
+
+        let txid = self.rpc.call("sendrawtransaction", vec![json!(signed)])?;
+        as_string(txid, "sendrawtransaction")
+    }
+}
+
+impl<R: ElementsRpc> AnchorBackend for LiquidAnchorer<R> {
+    fn chain(&self) -> AnchorChain {
+        AnchorChain::Liquid
+    }
+}
+
+/// 앵커 종류에 따라 Bitcoin/Liquid 백엔드로 분기
+pub struct RoutedAnchorer {
+    routing: AnchorRouting,
+    bitcoin: Box<dyn AnchorBackend + Send + Sync>,
+    liquid: Option<Box<dyn AnchorBackend + Send + Sync>>,
+}
+
+impl RoutedAnchorer {
+    pub fn new(
+        routing: AnchorRouting,
+        bitcoin: Box<dyn AnchorBackend + Send + Sync>,
+        liquid: Option<Box<dyn AnchorBackend + Send + Sync>>,
+    ) -> Result<Self, AnchorError> {
+        routing.validate()?;
+        if liquid.is_none() && routing.routes.values().any(|chain| *chain == AnchorChain::Liquid) {
+            return Err(AnchorError::InvalidRoute(
+                "Liquid route configured without a Liquid backend".to_string(),
+            ));
+        }
+        Ok(Self {
+            routing,
+            bitcoin,
+            liquid,
+        })
+    }
+
+    fn backend(&self, chain: AnchorChain) -> &dyn AnchorBackend {
+        match (chain, &self.liquid) {
+            (AnchorChain::Liquid, Some(liquid)) => liquid.as_ref(),
+            _ => self.bitcoin.as_ref(),
+        }
+    }
+
+    /// 페이로드가 앵커될 체인
+    pub fn chain_for(&self, payload: &[u8]) -> AnchorChain {
+        self.routing.chain_for(AnchorKind::of_payload(payload))
+    }
+
+    /// 앵커링 후 (체인, txid) 반환
+    pub fn anchor_routed(&self, payload: &[u8]) -> Result<(AnchorChain, String), AnchorError> {
+        let chain = self.chain_for(payload);
+        let txid = self.backend(chain).anchor(payload)?;
+        Ok((chain, txid))
+    }
+}
+
+impl AnchorBroadcaster for RoutedAnchorer {
+    /// 원본 트랜잭션이 어느 체인 것인지 모르므로 Bitcoin, Liquid 순으로 시도
+    fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+        match (self.bitcoin.rebroadcast(raw_tx), &self.liquid) {
+            (Err(_), Some(liquid)) => liquid.rebroadcast(raw_tx),
+            (result, _) => result,
+        }
+    }
+
+    fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+        self.anchor_routed(payload).map(|(_, txid)| txid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 호출을 기록하고 정해진 응답을 돌려주는 Elements RPC
+    #[derive(Default)]
+    struct MockElements {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl ElementsRpc for MockElements {
+        fn call(&self, method: &str, _params: Vec<Value>) -> Result<Value, AnchorError> {
+            self.calls.lock().unwrap().push(method.to_string());
+            Ok(match method {
+                "createrawtransaction" => json!("00"),
+                "fundrawtransaction" => json!({ "hex": "01", "fee": 0.0000025 }),
+                "blindrawtransaction" => json!("02"),
+                "signrawtransactionwithwallet" => json!({ "hex": "03", "complete": true }),
+                "sendrawtransaction" => json!("liquid-tx"),
+                _ => return Err(AnchorError::Rpc(format!("unexpected {}", method))),
+            })
+        }
+    }
+
+    struct MockBitcoin;
+
+    impl AnchorBroadcaster for MockBitcoin {
+        fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+            Err(AnchorError::BroadcastRejected("unknown tx".to_string()))
+        }
+
+        fn anchor(&self, _payload: &[u8]) -> Result<String, AnchorError> {
+            Ok("bitcoin-tx".to_string())
+        }
+    }
+
+    impl AnchorBackend for MockBitcoin {
+        fn chain(&self) -> AnchorChain {
+            AnchorChain::Bitcoin
+        }
+    }
+
+    #[test]
+    fn test_routes_trades_to_liquid_and_settlements_to_bitcoin() {
+        let liquid = LiquidAnchorer::new(MockElements::default());
+        let anchorer = RoutedAnchorer::new(
+            AnchorRouting::liquid_for_trades(),
+            Box::new(MockBitcoin),
+            Some(Box::new(liquid)),
+        )
+        .unwrap();
+
+        let mut buy = BUY_ANCHOR_TAG.to_vec();
+        buy.extend_from_slice(&[0u8; 64]);
+        assert_eq!(
+            anchorer.anchor_routed(&buy).unwrap(),
+            (AnchorChain::Liquid, "liquid-tx".to_string())
+        );
+        assert_eq!(
+            anchorer.anchor_routed(b"STLsettled").unwrap(),
+            (AnchorChain::Bitcoin, "bitcoin-tx".to_string())
+        );
+        assert_eq!(anchorer.chain_for(b"PRC"), AnchorChain::Bitcoin);
+        assert_eq!(anchorer.rebroadcast(&[0xde, 0xad]).unwrap(), "liquid-tx");
+
+        // 정산을 Liquid로 보내거나 Liquid 백엔드 없이 Liquid 경로를 쓰면 거부
+        let mut routing = AnchorRouting::liquid_for_trades();
+        routing.routes.insert(AnchorKind::Settle, AnchorChain::Liquid);
+        assert!(matches!(routing.validate(), Err(AnchorError::InvalidRoute(_))));
+        assert!(RoutedAnchorer::new(AnchorRouting::liquid_for_trades(), Box::new(MockBitcoin), None).is_err());
+
+        let parsed: AnchorRouting =
+            serde_json::from_str(r#"{ "routes": { "create": "liquid", "buy": "liquid" } }"#).unwrap();
+        assert_eq!(parsed, AnchorRouting::liquid_for_trades());
+    }
+
+    /// 지갑 경로와 함께 호출을 기록하는 bitcoind
+    #[derive(Default)]
+    struct MockBitcoind {
+        calls: Mutex<Vec<(Option<String>, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl BitcoindRpc for MockBitcoind {
+        async fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> anyhow::Result<Value> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((wallet.map(str::to_string), method.to_string()));
+            Ok(match method {
+                "createrawtransaction" => json!("00"),
+                "fundrawtransaction" => json!({ "hex": "01", "fee": 0.0000025 }),
+                "signrawtransactionwithwallet" => json!({ "hex": "02", "complete": true }),
+                "sendrawtransaction" if params[0] == json!("dead") => anyhow::bail!("bad-txns-inputs-missingorspent"),
+                "sendrawtransaction" if params[0] != json!("02") => anyhow::bail!("Transaction already in block chain"),
+                "sendrawtransaction" => json!("bitcoin-tx"),
+                _ => anyhow::bail!("unexpected {}", method),
+            })
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bitcoin_anchor_funds_from_wallet() {
+        let rpc = Arc::new(MockBitcoind::default());
+        let anchorer = BitcoinAnchorer::new(rpc.clone(), "btcfi-anchor");
+        assert_eq!(anchorer.anchor(b"STLsettled").unwrap(), "bitcoin-tx");
+        let wallet = Some("btcfi-anchor".to_string());
+        assert_eq!(
+            *rpc.calls.lock().unwrap(),
+            vec![
+                (None, "createrawtransaction".to_string()),
+                (wallet.clone(), "fundrawtransaction".to_string()),
+                (wallet, "signrawtransactionwithwallet".to_string()),
+                (None, "sendrawtransaction".to_string()),
+            ]
+        );
+
+        // 이미 체인에 있는 트랜잭션의 재전송은 성공, 그 외 거부는 BroadcastRejected
+        let tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::ZERO,
+            input: Vec::new(),
+            output: Vec::new(),
+        };
+        let raw = bitcoin::consensus::serialize(&tx);
+        assert_eq!(anchorer.rebroadcast(&raw).unwrap(), tx.compute_txid().to_string());
+        assert!(matches!(
+            anchorer.rebroadcast(&[0xde, 0xad]),
+            Err(AnchorError::BroadcastRejected(_))
+        ));
+    }
+
+    #[test]
+    fn test_liquid_anchor_rpc_sequence() {
+        let liquid = LiquidAnchorer::new(MockElements::default());
+        assert_eq!(liquid.anchor(b"CRTterms").unwrap(), "liquid-tx");
+        assert_eq!(
+            *liquid.rpc.calls.lock().unwrap(),
+            vec![
+                "createrawtransaction",
+                "fundrawtransaction",
+                "blindrawtransaction",
+                "signrawtransactionwithwallet",
+                "sendrawtransaction",
+            ]
+        );
+        assert!(matches!(
+            liquid.anchor(&[0u8; MAX_LIQUID_PAYLOAD + 1]),
+            Err(AnchorError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_anchor_links_to_option_id() {
+        let terms = oracle_vm_common::OptionTerms {
+            option_type: oracle_vm_common::types::OptionType::Call,
+            strike_price: 7_000_000,
+            quantity: 1_000_000,
+            expiry: 800_000,
+        };
+        let option_id = OptionId::derive(&[2u8; 33], &terms, 0);
+        let binding = ProgramBinding::new([9u8; 32], [1u8; 12], vec![]);
+        let payload = create_anchor_payload(&option_id, &binding);
+
+        assert_eq!(payload.len(), 41);
+        assert_eq!(create_anchor_binding(&payload), Some(binding.commitment()));
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::Create);
+        assert!(payload.len() <= MAX_LIQUID_PAYLOAD);
+        assert!(option_id.matches_short(&create_anchor_short_id(&payload).unwrap()));
+        assert_eq!(create_anchor_short_id(b"STL123456"), None);
+        assert_eq!(create_anchor_short_id(b"CRT12"), None);
+    }
+}
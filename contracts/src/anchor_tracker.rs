@@ -0,0 +1,604 @@
+//! 앵커 트랜잭션 확인 추적
+//!
+//! 앵커링 후 txid만 받고 끝나던 흐름을 보완합니다. 추적 중인 앵커마다 체인
+//! 상태를 주기적으로 조회해 확인 수를 갱신하고, 블록에서 빠지거나(reorg)
+//! 멤풀에서 축출된 앵커는 원본 트랜잭션을 재전송하거나 같은 페이로드로 다시
+//! 앵커링합니다. 상태 변화는 알림으로 반환하고 옵션 기록에도 반영합니다.
+//! 복구가 `MAX_RECOVERY_ATTEMPTS`번 연달아 실패한 앵커는 더 조회하지 않고
+//! 운영자가 `requeue`로 다시 넣을 때까지 보류합니다.
+//!
+//! `contracts serve`는 `BitcoindChain`으로 1분마다 풀별 추적기를 조회합니다.
+
+use crate::bootstrap::{call_blocking, BitcoindRpc};
+use crate::simple_contract::SimpleContractManager;
+use crate::snapshot::AnchorRecord;
+use oracle_vm_common::{AnchorError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+/// 체인에서 본 트랜잭션 상태
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    Confirmed { block_height: u32, block_hash: String },
+    InMempool,
+    NotFound,
+}
+
+/// 앵커 상태 조회용 체인 인터페이스
+pub trait ChainSource {
+    fn tip_height(&self) -> Result<u32, AnchorError>;
+    fn tx_status(&self, txid: &str) -> Result<TxStatus, AnchorError>;
+}
+
+/// bitcoind 체인 조회 (지갑이 있으면 지갑 트랜잭션부터 확인)
+///
+/// 지갑 밖 트랜잭션은 `getrawtransaction`으로 찾으므로 확인된 트랜잭션까지 보려면
+/// bitcoind에 `-txindex`가 필요합니다.
+pub struct BitcoindChain {
+    rpc: Arc<dyn BitcoindRpc>,
+    wallet: Option<String>,
+}
+
+impl BitcoindChain {
+    pub fn new(rpc: Arc<dyn BitcoindRpc>, wallet: Option<String>) -> Self {
+        Self { rpc, wallet }
+    }
+
+    fn confirmed(block_hash: &str, height: &Value) -> Result<TxStatus, AnchorError> {
+        let block_height = height
+            .as_u64()
+            .ok_or_else(|| AnchorError::Rpc(format!("no height for block {}", block_hash)))?;
+        Ok(TxStatus::Confirmed {
+            block_height: block_height as u32,
+            block_hash: block_hash.to_string(),
+        })
+    }
+}
+
+impl ChainSource for BitcoindChain {
+    fn tip_height(&self) -> Result<u32, AnchorError> {
+        let height = call_blocking(self.rpc.as_ref(), None, "getblockcount", Vec::new())?;
+        height
+            .as_u64()
+            .map(|height| height as u32)
+            .ok_or_else(|| AnchorError::Rpc(format!("getblockcount returned {}", height)))
+    }
+
+    fn tx_status(&self, txid: &str) -> Result<TxStatus, AnchorError> {
+        if let Some(wallet) = &self.wallet {
+            if let Ok(tx) = call_blocking(self.rpc.as_ref(), Some(wallet), "gettransaction", vec![json!(txid)]) {
+                match (tx["confirmations"].as_i64().unwrap_or(0), tx["blockhash"].as_str()) {
+                    (confirmations, Some(hash)) if confirmations > 0 => return Self::confirmed(hash, &tx["blockheight"]),
+                    // 충돌하는 트랜잭션이 확인돼 밀려남
+                    (confirmations, _) if confirmations < 0 => return Ok(TxStatus::NotFound),
+                    // 미확인: 아직 멤풀에 있는지는 아래에서 확인
+                    _ => {}
+                }
+            }
+        }
+        match call_blocking(self.rpc.as_ref(), None, "getrawtransaction", vec![json!(txid), json!(true)]) {
+            Ok(tx) => match tx["blockhash"].as_str() {
+                Some(hash) => {
+                    let header = call_blocking(self.rpc.as_ref(), None, "getblockheader", vec![json!(hash)])?;
+                    Self::confirmed(hash, &header["height"])
+                }
+                None => Ok(TxStatus::InMempool),
+            },
+            // RPC_INVALID_ADDRESS_OR_KEY: 멤풀에도 (인덱스된) 블록에도 없음
+            Err(AnchorError::Rpc(message)) if message.contains("\"code\":-5") => Ok(TxStatus::NotFound),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 재전송/재앵커링 인터페이스
+pub trait AnchorBroadcaster {
+    /// 서명된 원본 트랜잭션 재전송
+    fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError>;
+    /// 같은 페이로드로 새 앵커 트랜잭션 생성/전송
+    fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError>;
+}
+
+/// 옵션 기록에 남기는 앵커 상태
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum AnchorStatus {
+    Pending { txid: String },
+    Confirmed { txid: String, block_height: u32, confirmations: u32 },
+    /// 블록/멤풀에서 빠져 재전송 대기 중
+    Reorged { txid: String },
+}
+
+/// 보류 전까지 연달아 허용하는 재전송/재앵커링 실패 횟수
+pub const MAX_RECOVERY_ATTEMPTS: u32 = 5;
+
+/// 추적 중인 앵커
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedAnchor {
+    pub option_id: String,
+    pub txid: String,
+    pub payload: Vec<u8>,
+    /// 재전송용 서명된 원본 트랜잭션 (없으면 재앵커링)
+    pub raw_tx: Option<Vec<u8>>,
+    pub status: AnchorStatus,
+    pub block_hash: Option<String>,
+    /// 이전에 사용했다가 버려진 txid
+    pub replaced: Vec<String>,
+    /// 연달아 실패한 복구 횟수 (성공하면 0으로 돌아감)
+    #[serde(default)]
+    pub failures: u32,
+}
+
+impl TrackedAnchor {
+    fn is_final(&self, profile: &NetworkProfile) -> bool {
+        matches!(self.status, AnchorStatus::Confirmed { confirmations, .. } if profile.is_final(confirmations))
+    }
+
+    /// 복구 실패가 한도에 닿아 운영자 재등록을 기다리는 중
+    pub fn is_parked(&self) -> bool {
+        self.failures >= MAX_RECOVERY_ATTEMPTS
+    }
+}
+
+/// 추적 결과 알림
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnchorAlert {
+    /// 필요한 확인 수 도달
+    Confirmed { option_id: String, txid: String, confirmations: u32 },
+    /// 확인됐던 앵커가 블록에서 빠지거나 다른 블록으로 옮겨짐
+    Reorged { option_id: String, txid: String },
+    /// 원본 트랜잭션 재전송
+    Rebroadcast { option_id: String, txid: String },
+    /// 새 트랜잭션으로 재앵커링
+    Reanchored { option_id: String, old_txid: String, new_txid: String },
+    /// 재전송/재앵커링 실패 (한도 전까지 다음 조회에서 재시도)
+    Failed { option_id: String, txid: String, error: String },
+}
+
+/// 앵커 확인 추적기
+pub struct AnchorTracker {
+    profile: NetworkProfile,
+    anchors: Vec<TrackedAnchor>,
+}
+
+impl AnchorTracker {
+    pub fn new(profile: NetworkProfile) -> Self {
+        Self {
+            profile,
+            anchors: Vec::new(),
+        }
+    }
+
+    /// 방금 전송한 앵커 추적 시작
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, txid = %txid))]
+    pub fn track(&mut self, option_id: &str, txid: &str, payload: Vec<u8>, raw_tx: Option<Vec<u8>>) {
+        self.anchors.push(TrackedAnchor {
+            option_id: option_id.to_string(),
+            txid: txid.to_string(),
+            payload,
+            raw_tx,
+            status: AnchorStatus::Pending {
+                txid: txid.to_string(),
+            },
+            block_hash: None,
+            replaced: Vec::new(),
+            failures: 0,
+        });
+    }
+
+    /// 스냅샷에서 복원한 앵커로 추적 재개
+    pub fn restore(&mut self, anchors: Vec<TrackedAnchor>) {
+        self.anchors = anchors;
+    }
+
+    pub fn anchors(&self) -> &[TrackedAnchor] {
+        &self.anchors
+    }
+
+    /// 복구 실패로 보류된 앵커를 다시 조회 대상으로 (다시 넣은 옵션 ID 반환)
+    #[instrument(level = "info", skip_all)]
+    pub fn requeue(&mut self) -> Vec<String> {
+        self.anchors
+            .iter_mut()
+            .filter(|anchor| anchor.is_parked())
+            .map(|anchor| {
+                info!("Requeued anchor {} for {}", anchor.txid, anchor.option_id);
+                anchor.failures = 0;
+                anchor.option_id.clone()
+            })
+            .collect()
+    }
+
+    /// 아직 최종 확인 전인 앵커
+    pub fn unsettled(&self) -> impl Iterator<Item = &TrackedAnchor> {
+        self.anchors.iter().filter(|anchor| !anchor.is_final(&self.profile))
+    }
+
+    /// 체인 상태를 조회해 앵커 상태 갱신
+    ///
+    /// 최종 확인된 앵커와 보류된 앵커는 조회하지 않습니다.
+    #[instrument(level = "info", skip_all, fields(anchors = self.anchors.len()))]
+    pub fn poll(
+        &mut self,
+        chain: &dyn ChainSource,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<Vec<AnchorAlert>, AnchorError> {
+        let tip = chain.tip_height()?;
+        let profile = self.profile;
+        let mut alerts = Vec::new();
+
+        for anchor in self
+            .anchors
+            .iter_mut()
+            .filter(|anchor| !anchor.is_final(&profile) && !anchor.is_parked())
+        {
+            let was_confirmed = matches!(anchor.status, AnchorStatus::Confirmed { .. });
+
+            match chain.tx_status(&anchor.txid)? {
+                TxStatus::Confirmed {
+                    block_height,
+                    block_hash,
+                } => {
+                    if anchor.block_hash.as_ref().is_some_and(|hash| *hash != block_hash) {
+                        warn!("Anchor {} for {} moved to block {}", anchor.txid, anchor.option_id, block_hash);
+                        alerts.push(AnchorAlert::Reorged {
+                            option_id: anchor.option_id.clone(),
+                            txid: anchor.txid.clone(),
+                        });
+                    }
+                    let confirmations = tip.saturating_sub(block_height) + 1;
+                    anchor.block_hash = Some(block_hash);
+                    anchor.status = AnchorStatus::Confirmed {
+                        txid: anchor.txid.clone(),
+                        block_height,
+                        confirmations,
+                    };
+                    if profile.is_final(confirmations) {
+                        info!("Anchor {} for {} final ({} confirmations)", anchor.txid, anchor.option_id, confirmations);
+                        alerts.push(AnchorAlert::Confirmed {
+                            option_id: anchor.option_id.clone(),
+                            txid: anchor.txid.clone(),
+                            confirmations,
+                        });
+                    }
+                }
+                TxStatus::InMempool => {
+                    if was_confirmed {
+                        warn!("Anchor {} for {} dropped back to mempool", anchor.txid, anchor.option_id);
+                        alerts.push(AnchorAlert::Reorged {
+                            option_id: anchor.option_id.clone(),
+                            txid: anchor.txid.clone(),
+                        });
+                    }
+                    anchor.block_hash = None;
+                    anchor.status = AnchorStatus::Pending {
+                        txid: anchor.txid.clone(),
+                    };
+                }
+                TxStatus::NotFound => {
+                    warn!("Anchor {} for {} evicted", anchor.txid, anchor.option_id);
+                    if was_confirmed {
+                        alerts.push(AnchorAlert::Reorged {
+                            option_id: anchor.option_id.clone(),
+                            txid: anchor.txid.clone(),
+                        });
+                    }
+                    anchor.block_hash = None;
+                    anchor.status = AnchorStatus::Reorged {
+                        txid: anchor.txid.clone(),
+                    };
+                    alerts.push(Self::recover(anchor, broadcaster));
+                }
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// 축출된 앵커 복구: 원본 재전송, 실패하면 재앵커링
+    fn recover(anchor: &mut TrackedAnchor, broadcaster: &dyn AnchorBroadcaster) -> AnchorAlert {
+        if let Some(raw_tx) = &anchor.raw_tx {
+            if broadcaster.rebroadcast(raw_tx).is_ok() {
+                anchor.failures = 0;
+                anchor.status = AnchorStatus::Pending {
+                    txid: anchor.txid.clone(),
+                };
+                return AnchorAlert::Rebroadcast {
+                    option_id: anchor.option_id.clone(),
+                    txid: anchor.txid.clone(),
+                };
+            }
+        }
+
+        match broadcaster.anchor(&anchor.payload) {
+            Ok(new_txid) => {
+                let old_txid = std::mem::replace(&mut anchor.txid, new_txid.clone());
+                anchor.replaced.push(old_txid.clone());
+                // 새 트랜잭션은 다른 입력을 쓰므로 원본은 더 이상 재전송하지 않음
+                anchor.raw_tx = None;
+                anchor.failures = 0;
+                anchor.status = AnchorStatus::Pending {
+                    txid: new_txid.clone(),
+                };
+                AnchorAlert::Reanchored {
+                    option_id: anchor.option_id.clone(),
+                    old_txid,
+                    new_txid,
+                }
+            }
+            Err(e) => {
+                anchor.failures += 1;
+                if anchor.is_parked() {
+                    warn!("Anchor {} for {} parked after {} failed recoveries", anchor.txid, anchor.option_id, anchor.failures);
+                }
+                AnchorAlert::Failed {
+                    option_id: anchor.option_id.clone(),
+                    txid: anchor.txid.clone(),
+                    error: e.to_string(),
+                }
+            }
+        }
+    }
+
+    /// 옵션 기록에 앵커 상태 반영
+    pub fn sync(&self, manager: &mut SimpleContractManager) {
+        for anchor in &self.anchors {
+            manager.set_anchor_status(&anchor.option_id, anchor.status.clone());
+        }
+    }
+
+    /// 최종 확인된 앵커 (스냅샷용)
+    pub fn confirmed_records(&self) -> Vec<AnchorRecord> {
+        self.anchors
+            .iter()
+            .filter(|anchor| anchor.is_final(&self.profile))
+            .map(|anchor| AnchorRecord {
+                txid: anchor.txid.clone(),
+                reference: anchor.option_id.clone(),
+                payload: hex::encode(&anchor.payload),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MockChain {
+        tip: RefCell<u32>,
+        txs: RefCell<HashMap<String, TxStatus>>,
+    }
+
+    impl ChainSource for MockChain {
+        fn tip_height(&self) -> Result<u32, AnchorError> {
+            Ok(*self.tip.borrow())
+        }
+
+        fn tx_status(&self, txid: &str) -> Result<TxStatus, AnchorError> {
+            Ok(self.txs.borrow().get(txid).cloned().unwrap_or(TxStatus::NotFound))
+        }
+    }
+
+    /// 재전송은 항상 거부, 재앵커링은 새 txid 발급
+    #[derive(Default)]
+    struct MockBroadcaster {
+        anchored: RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl AnchorBroadcaster for MockBroadcaster {
+        fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+            Err(AnchorError::BroadcastRejected("inputs spent".to_string()))
+        }
+
+        fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+            self.anchored.borrow_mut().push(payload.to_vec());
+            Ok(format!("re-{}", self.anchored.borrow().len()))
+        }
+    }
+
+    fn confirmed(height: u32, hash: &str) -> TxStatus {
+        TxStatus::Confirmed {
+            block_height: height,
+            block_hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_confirmations_until_final() {
+        let chain = MockChain::default();
+        let broadcaster = MockBroadcaster::default();
+        let mut tracker = AnchorTracker::new(NetworkProfile::TESTNET);
+        tracker.track("CALL-1", "tx1", b"proof".to_vec(), None);
+
+        chain.txs.borrow_mut().insert("tx1".to_string(), TxStatus::InMempool);
+        *chain.tip.borrow_mut() = 100;
+        assert!(tracker.poll(&chain, &broadcaster).unwrap().is_empty());
+
+        chain.txs.borrow_mut().insert("tx1".to_string(), confirmed(101, "b101"));
+        *chain.tip.borrow_mut() = 102;
+        assert!(tracker.poll(&chain, &broadcaster).unwrap().is_empty());
+        assert_eq!(
+            tracker.anchors()[0].status,
+            AnchorStatus::Confirmed {
+                txid: "tx1".to_string(),
+                block_height: 101,
+                confirmations: 2
+            }
+        );
+
+        *chain.tip.borrow_mut() = 103;
+        let alerts = tracker.poll(&chain, &broadcaster).unwrap();
+        assert_eq!(
+            alerts,
+            vec![AnchorAlert::Confirmed {
+                option_id: "CALL-1".to_string(),
+                txid: "tx1".to_string(),
+                confirmations: 3
+            }]
+        );
+        assert_eq!(tracker.unsettled().count(), 0);
+        assert_eq!(tracker.confirmed_records()[0].payload, hex::encode(b"proof"));
+
+        let mut manager = SimpleContractManager::new();
+        tracker.sync(&mut manager);
+        assert!(matches!(
+            manager.anchor_status("CALL-1"),
+            Some(AnchorStatus::Confirmed { confirmations: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_reorg_eviction_reanchors() {
+        let chain = MockChain::default();
+        let broadcaster = MockBroadcaster::default();
+        let mut tracker = AnchorTracker::new(NetworkProfile::TESTNET);
+        tracker.track("PUT-1", "tx1", b"proof".to_vec(), Some(vec![0xde, 0xad]));
+
+        chain.txs.borrow_mut().insert("tx1".to_string(), confirmed(101, "b101"));
+        *chain.tip.borrow_mut() = 101;
+        tracker.poll(&chain, &broadcaster).unwrap();
+
+        // reorg로 블록이 사라지고 입력 충돌로 원본도 축출됨
+        chain.txs.borrow_mut().remove("tx1");
+        let alerts = tracker.poll(&chain, &broadcaster).unwrap();
+        assert_eq!(
+            alerts,
+            vec![
+                AnchorAlert::Reorged {
+                    option_id: "PUT-1".to_string(),
+                    txid: "tx1".to_string()
+                },
+                AnchorAlert::Reanchored {
+                    option_id: "PUT-1".to_string(),
+                    old_txid: "tx1".to_string(),
+                    new_txid: "re-1".to_string()
+                },
+            ]
+        );
+        assert_eq!(broadcaster.anchored.borrow()[0], b"proof");
+
+        let anchor = &tracker.anchors()[0];
+        assert_eq!(anchor.replaced, vec!["tx1".to_string()]);
+        assert_eq!(anchor.status, AnchorStatus::Pending { txid: "re-1".to_string() });
+    }
+
+    #[test]
+    fn test_failed_recovery_parks_until_requeued() {
+        struct Offline;
+
+        impl AnchorBroadcaster for Offline {
+            fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+                Err(AnchorError::BroadcastRejected("no peers".to_string()))
+            }
+
+            fn anchor(&self, _payload: &[u8]) -> Result<String, AnchorError> {
+                Err(AnchorError::BroadcastRejected("no peers".to_string()))
+            }
+        }
+
+        let chain = MockChain::default();
+        let mut tracker = AnchorTracker::new(NetworkProfile::TESTNET);
+        tracker.track("CALL-1", "tx1", b"proof".to_vec(), None);
+        for _ in 0..MAX_RECOVERY_ATTEMPTS {
+            let alerts = tracker.poll(&chain, &Offline).unwrap();
+            assert!(matches!(alerts[..], [AnchorAlert::Failed { .. }]));
+        }
+        // 한도에 닿으면 더 조회하지 않음
+        assert!(tracker.anchors()[0].is_parked());
+        assert!(tracker.poll(&chain, &Offline).unwrap().is_empty());
+
+        assert_eq!(tracker.requeue(), vec!["CALL-1".to_string()]);
+        assert!(tracker.requeue().is_empty());
+        let broadcaster = MockBroadcaster::default();
+        let alerts = tracker.poll(&chain, &broadcaster).unwrap();
+        assert!(matches!(alerts[..], [AnchorAlert::Reanchored { .. }]));
+        assert_eq!(tracker.anchors()[0].failures, 0);
+    }
+
+    #[test]
+    fn test_manager_poll_records_anchor_events() {
+        use crate::event_store::PoolEventKind;
+
+        let chain = MockChain::default();
+        let broadcaster = MockBroadcaster::default();
+        let mut manager = SimpleContractManager::new();
+        // 추적이 꺼져 있으면 무시
+        manager.track_anchor("CALL-1", "tx0", b"proof".to_vec(), None).unwrap();
+        assert!(manager.event_store().events().is_empty());
+
+        manager.enable_anchor_tracking(AnchorTracker::new(NetworkProfile::TESTNET));
+        manager.track_anchor("CALL-1", "tx1", b"proof".to_vec(), None).unwrap();
+        manager.track_anchor("PUT-1", "tx2", b"other".to_vec(), None).unwrap();
+        chain.txs.borrow_mut().insert("tx1".to_string(), confirmed(101, "b101"));
+        *chain.tip.borrow_mut() = 103;
+        let alerts = manager.poll_anchors(&chain, &broadcaster).unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(manager.anchor_alerts(), &alerts[..]);
+        assert!(matches!(
+            manager.anchor_status("CALL-1"),
+            Some(AnchorStatus::Confirmed { confirmations: 3, .. })
+        ));
+        assert_eq!(
+            manager.anchor_status("PUT-1"),
+            Some(&AnchorStatus::Pending { txid: "re-1".to_string() })
+        );
+
+        let kinds: Vec<_> = manager
+            .event_store()
+            .events()
+            .iter()
+            .filter_map(|event| match &event.kind {
+                PoolEventKind::AnchorBroadcast { txid, .. } => Some(format!("broadcast {}", txid)),
+                PoolEventKind::AnchorConfirmed { txid, .. } => Some(format!("confirmed {}", txid)),
+                PoolEventKind::AnchorReplaced { new_txid, .. } => Some(format!("replaced {}", new_txid)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kinds, vec!["broadcast tx1", "broadcast tx2", "confirmed tx1", "replaced re-1"]);
+    }
+
+    /// 지갑은 tx1만 알고, tx2는 멤풀, tx3는 블록 300에 있음
+    struct MockBitcoind;
+
+    #[async_trait::async_trait]
+    impl BitcoindRpc for MockBitcoind {
+        async fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> anyhow::Result<Value> {
+            let txid = params.first().and_then(Value::as_str).unwrap_or_default();
+            Ok(match (wallet, method, txid) {
+                (None, "getblockcount", _) => json!(305),
+                (Some("anchor"), "gettransaction", "tx1") => {
+                    json!({ "confirmations": 2, "blockhash": "b304", "blockheight": 304 })
+                }
+                (Some("anchor"), "gettransaction", _) => anyhow::bail!(
+                    "gettransaction failed: {}",
+                    json!({ "code": -5, "message": "Invalid or non-wallet transaction id" })
+                ),
+                (None, "getrawtransaction", "tx2") => json!({ "txid": "tx2" }),
+                (None, "getrawtransaction", "tx3") => json!({ "txid": "tx3", "blockhash": "b300" }),
+                (None, "getblockheader", "b300") => json!({ "height": 300 }),
+                (None, "getrawtransaction", _) => anyhow::bail!(
+                    "getrawtransaction failed: {}",
+                    json!({ "code": -5, "message": "No such mempool or blockchain transaction" })
+                ),
+                _ => anyhow::bail!("unexpected {}", method),
+            })
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bitcoind_chain_reads_wallet_then_node() {
+        let chain = BitcoindChain::new(Arc::new(MockBitcoind), Some("anchor".to_string()));
+        assert_eq!(chain.tip_height().unwrap(), 305);
+        assert_eq!(chain.tx_status("tx1").unwrap(), confirmed(304, "b304"));
+        assert_eq!(chain.tx_status("tx2").unwrap(), TxStatus::InMempool);
+        assert_eq!(chain.tx_status("tx3").unwrap(), confirmed(300, "b300"));
+        assert_eq!(chain.tx_status("tx4").unwrap(), TxStatus::NotFound);
+    }
+}
@@ -0,0 +1,519 @@
+//! 옵션별 감사 기록 (해시 체인)
+//!
+//! 옵션 상태를 바꾸는 모든 변경(생성, 프리미엄 수취, 앵커, 배리어 접촉, 정산 증명, 지급)을
+//! 옵션마다 append-only로 남깁니다. 각 기록의 해시는 직전 기록의 해시를
+//! 포함하므로 중간 기록을 고치거나 빼면 이후 해시가 모두 달라집니다.
+//! 정산(STL), 조기 행사(EXR), 되사기/협의 해지(CNL), 롤(RLL) 앵커에 마지막 감사 해시를
+//! 넣어 온체인에서 변조 여부를 확인할 수 있게 합니다.
+
+use crate::anchor_backend::SETTLE_ANCHOR_TAG;
+use oracle_vm_common::crypto::sha256;
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{BarrierKind, CanonicalEncode, CanonicalEncoder};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 첫 기록의 직전 해시
+pub const GENESIS_HASH: [u8; 32] = [0u8; 32];
+
+/// 감사 대상 변경
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    Created {
+        option_type: OptionType,
+        strike_price: u64, // USD cents
+        quantity: u64,     // satoshis
+        collateral: u64,   // satoshis
+        user_id: String,
+    },
+    PremiumReceived {
+        amount: u64,
+    },
+    Anchored {
+        txid: String,
+    },
+    SettlementProof {
+        proof_hash: String, // hex
+    },
+    Payout {
+        spot_price: u64, // USD cents
+        amount: u64,     // satoshis
+        dust: u64,       // satoshis
+    },
+    /// 운영자 강제 만료
+    Expired {
+        reason: String,
+    },
+    /// 만기 전 되사기
+    BoughtBack {
+        spot_price: u64, // USD cents
+        amount: u64,     // satoshis
+    },
+    /// 배리어 첫 접촉
+    BarrierTouched {
+        kind: BarrierKind,
+        level: u64,      // USD cents
+        spot_price: u64, // USD cents
+        observed_at: u64,
+    },
+    /// 풀 부족분 분담으로 지급 삭감 (지급은 뒤이은 Payout)
+    Haircut {
+        owed: u64,    // satoshis
+        haircut: u64, // satoshis
+        rank: u32,
+    },
+    /// 미국식 조기 행사 (지급은 뒤이은 Payout)
+    Exercised {
+        spot_price: u64, // USD cents
+        user_id: String,
+    },
+    /// 롤로 생성 (이전 옵션 종료와 새 옵션 생성을 함께 기록)
+    Rolled {
+        from_option_id: String,
+        buy_back_amount: u64, // satoshis
+        option_type: OptionType,
+        strike_price: u64, // USD cents
+        quantity: u64,     // satoshis
+        premium: u64,      // satoshis
+        collateral: u64,   // satoshis
+        user_id: String,
+    },
+    /// 만기 전 협의 해지 (양측 서명 해지 트랜잭션)
+    CooperativelyClosed {
+        spot_price: u64, // USD cents
+        amount: u64,     // satoshis, 보유자 몫
+        txid: String,
+    },
+}
+
+/// 감사 기록
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub option_id: String,
+    /// 옵션 안에서의 순번 (0부터)
+    pub sequence: u64,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub action: AuditAction,
+    pub prev_hash: String, // hex
+    pub hash: String,      // hex
+}
+
+impl CanonicalEncode for AuditAction {
+    const DOMAIN: &'static str = "audit_action/v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        match self {
+            Self::Created { option_type, strike_price, quantity, collateral, user_id } => {
+                encoder
+                    .u8(0)
+                    .option_type(*option_type)
+                    .u64(*strike_price)
+                    .u64(*quantity)
+                    .u64(*collateral)
+                    .str(user_id);
+            }
+            Self::PremiumReceived { amount } => {
+                encoder.u8(1).u64(*amount);
+            }
+            Self::Anchored { txid } => {
+                encoder.u8(2).str(txid);
+            }
+            Self::SettlementProof { proof_hash } => {
+                encoder.u8(3).str(proof_hash);
+            }
+            Self::Payout { spot_price, amount, dust } => {
+                encoder.u8(4).u64(*spot_price).u64(*amount).u64(*dust);
+            }
+            Self::Expired { reason } => {
+                encoder.u8(5).str(reason);
+            }
+            Self::BoughtBack { spot_price, amount } => {
+                encoder.u8(6).u64(*spot_price).u64(*amount);
+            }
+            Self::BarrierTouched { kind, level, spot_price, observed_at } => {
+                encoder
+                    .u8(7)
+                    .str(&kind.to_string())
+                    .u64(*level)
+                    .u64(*spot_price)
+                    .u64(*observed_at);
+            }
+            Self::Haircut { owed, haircut, rank } => {
+                encoder.u8(8).u64(*owed).u64(*haircut).u32(*rank);
+            }
+            Self::Exercised { spot_price, user_id } => {
+                encoder.u8(9).u64(*spot_price).str(user_id);
+            }
+            Self::Rolled {
+                from_option_id,
+                buy_back_amount,
+                option_type,
+                strike_price,
+                quantity,
+                premium,
+                collateral,
+                user_id,
+            } => {
+                encoder
+                    .u8(10)
+                    .str(from_option_id)
+                    .u64(*buy_back_amount)
+                    .option_type(*option_type)
+                    .u64(*strike_price)
+                    .u64(*quantity)
+                    .u64(*premium)
+                    .u64(*collateral)
+                    .str(user_id);
+            }
+            Self::CooperativelyClosed { spot_price, amount, txid } => {
+                encoder.u8(11).u64(*spot_price).u64(*amount).str(txid);
+            }
+        }
+    }
+}
+
+impl AuditRecord {
+    /// 기록 해시: 옵션, 순번, 시각, 직전 해시, 변경의 정규 인코딩에 대한 SHA256
+    pub fn compute_hash(
+        option_id: &str,
+        sequence: u64,
+        timestamp: u64,
+        action: &AuditAction,
+        prev_hash: &str,
+    ) -> [u8; 32] {
+        let mut encoder = CanonicalEncoder::new("audit/v2");
+        encoder
+            .str(option_id)
+            .u64(sequence)
+            .u64(timestamp)
+            .str(prev_hash)
+            .value(action);
+        encoder.hash()
+    }
+
+    /// 정규 인코딩 도입 전 해시: SHA256("audit|옵션|순번|시각|직전 해시|변경 JSON")
+    ///
+    /// 이전에 기록(및 앵커)된 감사 기록을 계속 검증하기 위해서만 사용합니다.
+    fn compute_legacy_hash(
+        option_id: &str,
+        sequence: u64,
+        timestamp: u64,
+        action: &AuditAction,
+        prev_hash: &str,
+    ) -> [u8; 32] {
+        let action = serde_json::to_string(action).expect("AuditAction serializes");
+        sha256(
+            format!(
+                "audit|{}|{}|{}|{}|{}",
+                option_id, sequence, timestamp, prev_hash, action
+            )
+            .as_bytes(),
+        )
+    }
+
+    /// 저장된 해시가 현재 또는 이전 인코딩의 해시와 일치하는지
+    fn hash_matches(&self, prev_hash: &str) -> bool {
+        [Self::compute_hash, Self::compute_legacy_hash].iter().any(|compute| {
+            self.hash
+                == hex::encode(compute(&self.option_id, self.sequence, self.timestamp, &self.action, prev_hash))
+        })
+    }
+}
+
+/// 체인 검증, 처음 어긋난 기록의 순번 반환
+pub fn verify_trail(records: &[AuditRecord]) -> Result<(), u64> {
+    let mut prev_hash = hex::encode(GENESIS_HASH);
+    for (index, record) in records.iter().enumerate() {
+        if record.sequence != index as u64
+            || record.prev_hash != prev_hash
+            || !record.hash_matches(&prev_hash)
+        {
+            return Err(index as u64);
+        }
+        prev_hash = record.hash.clone();
+    }
+    Ok(())
+}
+
+/// STL 앵커 페이로드: "STL" || SHA256(옵션 ID) || 마지막 감사 해시 (67 bytes)
+pub fn settle_anchor_payload(option_id: &str, audit_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(67);
+    payload.extend_from_slice(SETTLE_ANCHOR_TAG);
+    payload.extend_from_slice(&sha256(option_id.as_bytes()));
+    payload.extend_from_slice(audit_hash);
+    payload
+}
+
+/// 옵션별 감사 기록
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditLog {
+    trails: BTreeMap<String, Vec<AuditRecord>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 기록 목록에서 복원 (옵션마다 체인 검증)
+    pub fn from_records(records: Vec<AuditRecord>) -> Result<Self, String> {
+        let mut trails: BTreeMap<String, Vec<AuditRecord>> = BTreeMap::new();
+        for record in records {
+            trails.entry(record.option_id.clone()).or_default().push(record);
+        }
+        for (option_id, trail) in &trails {
+            verify_trail(trail).map_err(|sequence| {
+                format!("audit trail of {} broken at record {}", option_id, sequence)
+            })?;
+        }
+        Ok(Self { trails })
+    }
+
+    /// 기록 추가 후 반환
+    pub fn append(&mut self, option_id: &str, timestamp: u64, action: AuditAction) -> &AuditRecord {
+        let trail = self.trails.entry(option_id.to_string()).or_default();
+        let sequence = trail.len() as u64;
+        let prev_hash = trail
+            .last()
+            .map(|record| record.hash.clone())
+            .unwrap_or_else(|| hex::encode(GENESIS_HASH));
+        let hash = AuditRecord::compute_hash(option_id, sequence, timestamp, &action, &prev_hash);
+        trail.push(AuditRecord {
+            option_id: option_id.to_string(),
+            sequence,
+            timestamp,
+            action,
+            prev_hash,
+            hash: hex::encode(hash),
+        });
+        trail.last().expect("pushed above")
+    }
+
+    /// 옵션의 감사 기록 (순번 순)
+    pub fn trail(&self, option_id: &str) -> &[AuditRecord] {
+        self.trails.get(option_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 옵션의 마지막 감사 해시 (기록이 없으면 None)
+    pub fn head_hash(&self, option_id: &str) -> Option<[u8; 32]> {
+        let hash = hex::decode(&self.trails.get(option_id)?.last()?.hash).ok()?;
+        hash.try_into().ok()
+    }
+
+    /// 마지막으로 기록된 앵커 txid
+    pub fn last_anchor(&self, option_id: &str) -> Option<&str> {
+        self.trail(option_id).iter().rev().find_map(|record| match &record.action {
+            AuditAction::Anchored { txid } => Some(txid.as_str()),
+            _ => None,
+        })
+    }
+
+    /// 전체 기록 (옵션 ID, 순번 순)
+    pub fn records(&self) -> impl Iterator<Item = &AuditRecord> {
+        self.trails.values().flatten()
+    }
+}
+
+/// `/options/{id}/audit` 감사 기록 조회 API
+pub mod api {
+    use crate::admin_api::{error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    };
+    use oracle_vm_common::SettlementError;
+    use serde_json::json;
+
+    #[utoipa::path(
+        get,
+        path = "/options/{id}/audit",
+        tag = "options",
+        params(("id" = String, Path, description = "옵션 ID")),
+        responses(
+            (status = 200, body = Object),
+            (status = 404, body = AdminError)
+        )
+    )]
+    async fn get_audit(Path(option_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        if !manager.options.contains_key(&option_id) {
+            return error_response(SettlementError::OptionNotFound(option_id));
+        }
+        let trail = manager.audit().trail(&option_id);
+        Json(json!({
+            "option_id": option_id,
+            "head_hash": manager.audit().head_hash(&option_id).map(hex::encode),
+            "verified": super::verify_trail(trail).is_ok(),
+            "records": trail,
+        }))
+        .into_response()
+    }
+
+    /// `/options/{id}/audit` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/options/:id/audit", get(get_audit))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anchor_backend::AnchorKind;
+    use crate::anchor_tracker::AnchorStatus;
+    use crate::simple_contract::SimpleContractManager;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use std::collections::HashMap;
+    use std::sync::{Arc, RwLock};
+    use tower::ServiceExt;
+
+    fn manager() -> SimpleContractManager {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option(
+                "OPT-1".to_string(),
+                OptionType::Call,
+                7_000_000,
+                1_000_000,
+                25_000,
+                800_000,
+                "user".to_string(),
+            )
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_tampering_breaks_chain() {
+        let mut log = AuditLog::new();
+        log.append("OPT-1", 100, AuditAction::PremiumReceived { amount: 25_000 });
+        log.append("OPT-1", 200, AuditAction::Anchored { txid: "aa".repeat(32) });
+        log.append("OPT-2", 150, AuditAction::PremiumReceived { amount: 1 });
+        log.append("OPT-1", 300, AuditAction::Payout { spot_price: 7_200_000, amount: 2_777, dust: 0 });
+        assert!(verify_trail(log.trail("OPT-1")).is_ok());
+        assert_eq!(log.trail("OPT-2")[0].prev_hash, hex::encode(GENESIS_HASH));
+
+        let records: Vec<AuditRecord> = log.records().cloned().collect();
+        assert_eq!(AuditLog::from_records(records.clone()).unwrap(), log);
+
+        // 지급액 변조
+        let mut tampered = log.trail("OPT-1").to_vec();
+        tampered[2].action = AuditAction::Payout { spot_price: 7_200_000, amount: 9_999, dust: 0 };
+        assert_eq!(verify_trail(&tampered), Err(2));
+
+        // 중간 기록 삭제
+        let mut removed = log.trail("OPT-1").to_vec();
+        removed.remove(1);
+        assert_eq!(verify_trail(&removed), Err(1));
+        let mut broken = records;
+        broken.retain(|record| !(record.option_id == "OPT-1" && record.sequence == 1));
+        assert!(AuditLog::from_records(broken).unwrap_err().contains("OPT-1"));
+    }
+
+    #[test]
+    fn test_hash_golden_vector_and_legacy_trails() {
+        let action = AuditAction::PremiumReceived { amount: 25_000 };
+        let genesis = hex::encode(GENESIS_HASH);
+        assert_eq!(
+            hex::encode(AuditRecord::compute_hash("OPT-1", 0, 100, &action, &genesis)),
+            "2ad223860923a83c7e0931d5949e57245c8c916862ad8de857211b34176cfa97"
+        );
+
+        // 정규 인코딩 이전에 기록된 체인도 계속 검증되고, 이어서 기록 가능
+        let legacy = AuditRecord {
+            option_id: "OPT-1".to_string(),
+            sequence: 0,
+            timestamp: 100,
+            hash: hex::encode(AuditRecord::compute_legacy_hash("OPT-1", 0, 100, &action, &genesis)),
+            action,
+            prev_hash: genesis,
+        };
+        let mut log = AuditLog::from_records(vec![legacy]).unwrap();
+        log.append("OPT-1", 200, AuditAction::Anchored { txid: "aa".repeat(32) });
+        assert!(verify_trail(log.trail("OPT-1")).is_ok());
+
+        let mut tampered = log.trail("OPT-1").to_vec();
+        tampered[0].action = AuditAction::PremiumReceived { amount: 1 };
+        assert_eq!(verify_trail(&tampered), Err(0));
+    }
+
+    #[test]
+    fn test_manager_records_lifecycle_and_settle_anchor() {
+        let mut manager = manager();
+        let txid = "cd".repeat(32);
+        manager.set_anchor_status("OPT-1", AnchorStatus::Pending { txid: txid.clone() });
+        // 같은 앵커의 확인 수 갱신은 새 기록을 남기지 않음
+        manager.set_anchor_status(
+            "OPT-1",
+            AnchorStatus::Confirmed { txid: txid.clone(), block_height: 10, confirmations: 3 },
+        );
+        manager.record_settlement_proof("OPT-1", &[9u8; 32]).unwrap();
+        assert!(manager.settle_anchor_payload("OPT-1").is_none());
+        manager.settle_option("OPT-1", 7_200_000).unwrap();
+
+        let trail = manager.audit().trail("OPT-1");
+        let actions: Vec<&str> = trail
+            .iter()
+            .map(|record| match record.action {
+                AuditAction::Created { .. } => "created",
+                AuditAction::PremiumReceived { .. } => "premium",
+                AuditAction::Anchored { .. } => "anchored",
+                AuditAction::SettlementProof { .. } => "proof",
+                AuditAction::Payout { .. } => "payout",
+                AuditAction::Expired { .. } => "expired",
+                AuditAction::BoughtBack { .. } => "bought_back",
+                AuditAction::Rolled { .. } => "rolled",
+                AuditAction::Exercised { .. } => "exercised",
+                AuditAction::CooperativelyClosed { .. } => "cooperatively_closed",
+                AuditAction::BarrierTouched { .. } => "barrier_touched",
+                AuditAction::Haircut { .. } => "haircut",
+            })
+            .collect();
+        assert_eq!(actions, ["created", "premium", "anchored", "proof", "payout"]);
+        assert!(verify_trail(trail).is_ok());
+        assert!(manager.record_settlement_proof("OPT-9", &[0u8; 32]).is_err());
+
+        let payload = manager.settle_anchor_payload("OPT-1").unwrap();
+        assert_eq!(payload.len(), 67);
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::Settle);
+        assert_eq!(payload[35..], manager.audit().head_hash("OPT-1").unwrap());
+
+        // 감사 기록은 스냅샷과 함께 복원
+        let chain: HashMap<String, Vec<u8>> = HashMap::new();
+        let snapshot = manager.snapshot(900_000, vec![]);
+        let restored = SimpleContractManager::restore(snapshot.clone(), &chain).unwrap();
+        assert_eq!(restored.audit(), manager.audit());
+
+        let mut tampered = snapshot;
+        tampered.audit[4].timestamp += 1;
+        assert!(SimpleContractManager::restore(tampered, &chain).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_audit_endpoint() {
+        let shared = Arc::new(RwLock::new(manager()));
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = api::router(shared.clone()).oneshot(get("/options/OPT-1/audit")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body["verified"], true);
+        assert_eq!(body["records"][0]["action"], "created");
+        assert_eq!(body["records"][1]["amount"], 25_000);
+        assert_eq!(body["head_hash"], body["records"][1]["hash"]);
+
+        let missing = api::router(shared).oneshot(get("/options/OPT-9/audit")).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,33 @@
+//! 배리어 옵션 상태 (knock-in / knock-out)
+//!
+//! 배리어 호가로 연 옵션은 옵션 조건에 배리어와 접촉 여부를 함께 보관합니다.
+//! 가격 흐름의 배리어 감시 단계가 매분 합의 가격으로 `monitor_barriers`를
+//! 호출하면, 처음 배리어에 닿은 옵션은 접촉 시각/가격을 남기고 상태가 한 번만
+//! 바뀝니다. 정산은 이 상태를 따라 knock-out된 옵션과 knock-in되지 않은 옵션에
+//! 지급하지 않습니다.
+
+use oracle_vm_common::Barrier;
+use serde::{Deserialize, Serialize};
+
+/// 배리어 접촉 기록
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BarrierTouch {
+    /// 합의 가격 관측 시각 (Unix 초)
+    pub observed_at: u64,
+    pub spot_price: u64, // USD cents
+}
+
+/// 옵션 하나의 배리어와 접촉 여부
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BarrierState {
+    pub barrier: Barrier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub touched: Option<BarrierTouch>,
+}
+
+impl BarrierState {
+    /// 정산 시 지급 대상인지 (knock-in은 접촉 후, knock-out은 접촉 전)
+    pub fn is_live(&self) -> bool {
+        self.barrier.is_live(self.touched.is_some())
+    }
+}
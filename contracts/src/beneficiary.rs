@@ -0,0 +1,546 @@
+//! 정산 지급 주소(수익자) 관리
+//!
+//! 옵션 구매 시 지급 주소와 소유자 공개키를 등록하고, 만기 전 cut-off 높이까지는
+//! 소유자 서명이 있는 요청으로 지급 주소를 바꿀 수 있습니다. 주소는 운영
+//! 네트워크 기준으로 검증하며, 수익자 해시(지급 scriptPubKey의 SHA256)를 BUY
+//! 앵커 데이터에 포함해 온체인에서 지급 대상이 바뀌지 않았음을 확인할 수 있게
+//! 합니다.
+//!
+//! 등록부는 풀 관리자에 붙어 스냅샷에 함께 저장되고, 변경 마감은 헤더 동기화가
+//! 갱신하는 관리자의 tip 높이로 판단합니다. 수익자가 있는 옵션의 청구 잔고 적립은
+//! 보유자 출금에 묶이지 않고 수익자 주소로 따로 지급됩니다.
+
+use crate::simple_contract::{OptionStatus, SimpleOption};
+use bitcoin::Address;
+use oracle_vm_common::crypto::{sha256, verify_signature, PublicKey, Signature};
+use oracle_vm_common::{BeneficiaryError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// 만기 몇 블록 전부터 지급 주소 변경을 막는지 (약 6시간)
+pub const DEFAULT_CUTOFF_BLOCKS: u32 = 36;
+
+/// BUY 앵커 태그
+pub const BUY_ANCHOR_TAG: &[u8; 3] = b"BUY";
+
+/// 운영 네트워크 기준 주소 검증
+pub fn validate_address(address: &str, profile: &NetworkProfile) -> Result<Address, BeneficiaryError> {
+    profile
+        .validate_address(address)
+        .map_err(|e| BeneficiaryError::InvalidAddress {
+            address: address.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// 수익자 해시: 지급 scriptPubKey의 SHA256
+pub fn beneficiary_hash(address: &Address) -> [u8; 32] {
+    sha256(address.script_pubkey().as_bytes())
+}
+
+/// 옵션 조건 해시 (BUY 앵커용)
+pub fn option_terms_hash(option: &SimpleOption) -> [u8; 32] {
+    let terms = format!(
+        "{}|{:?}|{}|{}|{}|{}",
+        option.option_id,
+        option.option_type,
+        option.strike_price,
+        option.quantity,
+        option.premium_paid,
+        option.expiry_height
+    );
+    sha256(terms.as_bytes())
+}
+
+/// BUY 앵커 페이로드: "BUY" || 옵션 조건 해시 || 수익자 해시 (67 bytes)
+pub fn buy_anchor_payload(option: &SimpleOption, beneficiary_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(67);
+    payload.extend_from_slice(BUY_ANCHOR_TAG);
+    payload.extend_from_slice(&option_terms_hash(option));
+    payload.extend_from_slice(beneficiary_hash);
+    payload
+}
+
+/// 지급 주소 변경 요청에 소유자가 서명하는 바이트
+pub fn update_payload(option_id: &str, address: &str, nonce: u64) -> Vec<u8> {
+    format!("beneficiary|{}|{}|{}", option_id, address, nonce).into_bytes()
+}
+
+/// 등록된 수익자
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Beneficiary {
+    pub option_id: String,
+    pub address: String,
+    /// 변경 요청 서명 검증용 소유자 공개키
+    pub owner_key: PublicKey,
+    /// 마지막으로 적용된 변경 nonce (등록 시 0)
+    pub nonce: u64,
+    pub updated_at: u64,
+    /// 구매 시 BUY 앵커에 들어간 수익자 해시 (hex)
+    pub anchored_hash: String,
+}
+
+impl Beneficiary {
+    /// 현재 지급 주소의 수익자 해시 (hex)
+    pub fn current_hash(&self) -> String {
+        let address = Address::from_str(&self.address)
+            .expect("stored addresses are validated")
+            .assume_checked();
+        hex::encode(beneficiary_hash(&address))
+    }
+}
+
+/// 서명된 지급 주소 변경 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeneficiaryUpdate {
+    pub address: String,
+    pub nonce: u64,
+    pub signature: String, // DER hex
+}
+
+/// 수익자 등록부
+pub struct BeneficiaryRegistry {
+    profile: NetworkProfile,
+    cutoff_blocks: u32,
+    tip_height: Option<u32>,
+    entries: HashMap<String, Beneficiary>,
+}
+
+impl BeneficiaryRegistry {
+    pub fn new(profile: NetworkProfile) -> Self {
+        Self {
+            profile,
+            cutoff_blocks: DEFAULT_CUTOFF_BLOCKS,
+            tip_height: None,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn with_cutoff_blocks(mut self, cutoff_blocks: u32) -> Self {
+        self.cutoff_blocks = cutoff_blocks;
+        self
+    }
+
+    pub fn profile(&self) -> &NetworkProfile {
+        &self.profile
+    }
+
+    /// 체인 tip 높이 갱신 (높이는 되돌아가지 않음)
+    pub fn observe_height(&mut self, height: u32) {
+        self.tip_height = Some(self.tip_height.map_or(height, |tip| tip.max(height)));
+    }
+
+    /// 변경 마감 높이
+    pub fn cutoff_height(&self, option: &SimpleOption) -> u32 {
+        option.expiry_height.saturating_sub(self.cutoff_blocks)
+    }
+
+    /// 등록할 수 있는지 확인 후 수익자 해시 반환 (상태는 바꾸지 않음)
+    pub fn check_register(&self, option: &SimpleOption, address: &str) -> Result<[u8; 32], BeneficiaryError> {
+        if self.entries.contains_key(&option.option_id) {
+            return Err(BeneficiaryError::AlreadyRegistered(option.option_id.clone()));
+        }
+        let parsed = validate_address(address, &self.profile)?;
+        Ok(beneficiary_hash(&parsed))
+    }
+
+    /// 구매 시 수익자 등록, BUY 앵커 페이로드 반환
+    pub fn register(
+        &mut self,
+        option: &SimpleOption,
+        address: &str,
+        owner_key: PublicKey,
+        now: u64,
+    ) -> Result<Vec<u8>, BeneficiaryError> {
+        let hash = self.check_register(option, address)?;
+
+        self.entries.insert(
+            option.option_id.clone(),
+            Beneficiary {
+                option_id: option.option_id.clone(),
+                address: address.to_string(),
+                owner_key,
+                nonce: 0,
+                updated_at: now,
+                anchored_hash: hex::encode(hash),
+            },
+        );
+        Ok(buy_anchor_payload(option, &hash))
+    }
+
+    /// 변경 요청 확인: 마감 전, 다음 nonce, 소유자 서명, 주소 (상태는 바꾸지 않음)
+    pub fn check_change(&self, option: &SimpleOption, update: &BeneficiaryUpdate) -> Result<(), BeneficiaryError> {
+        let option_id = &option.option_id;
+        if option.status != OptionStatus::Active {
+            return Err(BeneficiaryError::OptionNotActive(option_id.clone()));
+        }
+        let tip = self.tip_height.ok_or(BeneficiaryError::TipUnknown)?;
+        let cutoff_height = self.cutoff_height(option);
+        if tip >= cutoff_height {
+            return Err(BeneficiaryError::CutoffPassed {
+                option_id: option_id.clone(),
+                cutoff_height,
+            });
+        }
+
+        let entry = self
+            .entries
+            .get(option_id)
+            .ok_or_else(|| BeneficiaryError::NotFound(option_id.clone()))?;
+        if update.nonce != entry.nonce + 1 {
+            return Err(BeneficiaryError::StaleNonce {
+                expected: entry.nonce + 1,
+                got: update.nonce,
+            });
+        }
+        let signature = Signature::from_str(&update.signature)
+            .map_err(|_| BeneficiaryError::InvalidSignature(option_id.clone()))?;
+        let payload = update_payload(option_id, &update.address, update.nonce);
+        if !verify_signature(&payload, &signature, &entry.owner_key).unwrap_or(false) {
+            return Err(BeneficiaryError::InvalidSignature(option_id.clone()));
+        }
+        validate_address(&update.address, &self.profile)?;
+        Ok(())
+    }
+
+    /// 소유자 서명 확인 후 지급 주소 변경
+    pub fn change(
+        &mut self,
+        option: &SimpleOption,
+        update: &BeneficiaryUpdate,
+        now: u64,
+    ) -> Result<&Beneficiary, BeneficiaryError> {
+        self.check_change(option, update)?;
+        let entry = self
+            .entries
+            .get_mut(&option.option_id)
+            .expect("checked above");
+        entry.address = update.address.clone();
+        entry.nonce = update.nonce;
+        entry.updated_at = now;
+        Ok(entry)
+    }
+
+    pub fn get(&self, option_id: &str) -> Option<&Beneficiary> {
+        self.entries.get(option_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 스냅샷용 수익자 목록 (옵션 ID 순)
+    pub fn entries(&self) -> Vec<Beneficiary> {
+        let mut entries: Vec<Beneficiary> = self.entries.values().cloned().collect();
+        entries.sort_by(|a, b| a.option_id.cmp(&b.option_id));
+        entries
+    }
+
+    /// 스냅샷의 수익자 목록 복원
+    pub fn restore(&mut self, entries: Vec<Beneficiary>) {
+        self.entries = entries
+            .into_iter()
+            .map(|entry| (entry.option_id.clone(), entry))
+            .collect();
+    }
+
+    /// 정산 시 지급 주소
+    pub fn payout_address(&self, option_id: &str) -> Result<Address, BeneficiaryError> {
+        let entry = self
+            .entries
+            .get(option_id)
+            .ok_or_else(|| BeneficiaryError::NotFound(option_id.to_string()))?;
+        validate_address(&entry.address, &self.profile)
+    }
+}
+
+/// 수익자 관리 HTTP API
+///
+/// - `POST /beneficiaries/{option_id}` 구매 시 등록 (`{address, owner_key, nonce, signature}`, 보유자 계정 키 서명)
+/// - `GET /beneficiaries/{option_id}` 현재 수익자 조회
+/// - `PUT /beneficiaries/{option_id}` 서명된 변경 (`{address, nonce, signature}`)
+pub mod api {
+    use super::BeneficiaryUpdate;
+    use crate::account_keys::{beneficiary_payload, AccountSignature};
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    };
+    use oracle_vm_common::crypto::PublicKey;
+    use oracle_vm_common::{BeneficiaryError, SettlementError};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use std::str::FromStr;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct RegisterRequest {
+        pub address: String,
+        pub owner_key: String, // compressed public key hex
+        /// 보유자 키로 `account/beneficiary/v1` (option_id, user_id, address, owner_key, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/beneficiaries/{option_id}",
+        tag = "beneficiaries",
+        params(("option_id" = String, Path, description = "옵션 ID")),
+        request_body = RegisterRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn register(
+        Path(option_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<RegisterRequest>,
+    ) -> Response {
+        let Ok(owner_key) = PublicKey::from_str(&request.owner_key) else {
+            return bad_request("owner_key must be a hex public key");
+        };
+        let Ok(mut manager) = manager.write() else {
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let Some(user_id) = manager.options.get(&option_id).map(|option| option.user_id.clone()) else {
+            return error_response(SettlementError::OptionNotFound(option_id));
+        };
+        let payload = beneficiary_payload(
+            &option_id,
+            &user_id,
+            &request.address,
+            &request.owner_key,
+            request.auth.nonce,
+        );
+        if let Err(e) = manager.authorize_account(&user_id, &payload, &request.auth) {
+            return error_response(e);
+        }
+        match manager.register_beneficiary(&option_id, &request.address, owner_key) {
+            Ok(payload) => Json(json!({
+                "beneficiary": manager.beneficiary(&option_id),
+                "buy_anchor": hex::encode(payload),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/beneficiaries/{option_id}",
+        tag = "beneficiaries",
+        params(("option_id" = String, Path, description = "옵션 ID")),
+        responses(
+            (status = 200, body = Object),
+            (status = 404, body = AdminError)
+        )
+    )]
+    async fn get_beneficiary(Path(option_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.beneficiary(&option_id) {
+            Some(entry) => Json(json!({
+                "beneficiary": entry,
+                "current_hash": entry.current_hash(),
+            }))
+            .into_response(),
+            None => error_response(BeneficiaryError::NotFound(option_id)),
+        }
+    }
+
+    #[utoipa::path(
+        put,
+        path = "/beneficiaries/{option_id}",
+        tag = "beneficiaries",
+        params(("option_id" = String, Path, description = "옵션 ID")),
+        request_body = Object,
+        responses(
+            (status = 200, body = Object),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn change(
+        Path(option_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(update): Json<BeneficiaryUpdate>,
+    ) -> Response {
+        let Ok(mut manager) = manager.write() else {
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        if !manager.options.contains_key(&option_id) {
+            return error_response(SettlementError::OptionNotFound(option_id));
+        }
+        match manager.change_beneficiary(&option_id, &update) {
+            Ok(entry) => Json(entry).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/beneficiaries` 라우터 생성 (관리자에 수익자 등록부가 활성화되어 있어야 함)
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route(
+                "/beneficiaries/:option_id",
+                get(get_beneficiary).post(register).put(change),
+            )
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_contract::OptionTerms;
+    use oracle_vm_common::crypto::{generate_keypair, sign_data};
+    use oracle_vm_common::types::OptionType;
+
+    const BUYER: &str = "tb1qerq9kwplk0we7ql3agkapdt39d0ahmtvsptj3e";
+    const NEW_PAYOUT: &str = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+
+    fn option() -> SimpleOption {
+        SimpleOption {
+            option_id: "CALL-1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            quantity: 10_000_000,
+            premium_paid: 250_000,
+            expiry_height: 1_000,
+            status: OptionStatus::Active,
+            user_id: "user".to_string(),
+            terms: OptionTerms::default(),
+        }
+    }
+
+    fn signed(secret: &oracle_vm_common::crypto::SecretKey, address: &str, nonce: u64) -> BeneficiaryUpdate {
+        let signature = sign_data(&update_payload("CALL-1", address, nonce), secret).unwrap();
+        BeneficiaryUpdate {
+            address: address.to_string(),
+            nonce,
+            signature: signature.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_register_validates_network_and_anchors_hash() {
+        let (_, owner) = generate_keypair();
+        let mut registry = BeneficiaryRegistry::new(NetworkProfile::TESTNET);
+
+        let mainnet = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        assert!(matches!(
+            registry.register(&option(), mainnet, owner, 0),
+            Err(BeneficiaryError::InvalidAddress { .. })
+        ));
+
+        let payload = registry.register(&option(), BUYER, owner, 0).unwrap();
+        assert_eq!(payload.len(), 67);
+        assert_eq!(&payload[..3], BUY_ANCHOR_TAG);
+        let entry = registry.get("CALL-1").unwrap();
+        assert_eq!(hex::encode(&payload[35..]), entry.anchored_hash);
+        assert_eq!(entry.current_hash(), entry.anchored_hash);
+    }
+
+    #[test]
+    fn test_signed_change_before_cutoff() {
+        let (secret, owner) = generate_keypair();
+        let (other_secret, _) = generate_keypair();
+        let mut registry = BeneficiaryRegistry::new(NetworkProfile::TESTNET).with_cutoff_blocks(10);
+        registry.register(&option(), BUYER, owner, 0).unwrap();
+
+        // tip을 모르면 변경 불가
+        assert_eq!(
+            registry.change(&option(), &signed(&secret, NEW_PAYOUT, 1), 1),
+            Err(BeneficiaryError::TipUnknown)
+        );
+        registry.observe_height(980);
+
+        assert_eq!(
+            registry.change(&option(), &signed(&other_secret, NEW_PAYOUT, 1), 1),
+            Err(BeneficiaryError::InvalidSignature("CALL-1".to_string()))
+        );
+        assert!(matches!(
+            registry.change(&option(), &signed(&secret, NEW_PAYOUT, 2), 1),
+            Err(BeneficiaryError::StaleNonce { expected: 1, got: 2 })
+        ));
+
+        let entry = registry.change(&option(), &signed(&secret, NEW_PAYOUT, 1), 1).unwrap();
+        assert_eq!(entry.address, NEW_PAYOUT);
+        assert_ne!(entry.current_hash(), entry.anchored_hash);
+        assert_eq!(registry.payout_address("CALL-1").unwrap().to_string(), NEW_PAYOUT);
+
+        // 만기 10블록 전부터 변경 마감
+        registry.observe_height(990);
+        assert_eq!(
+            registry.change(&option(), &signed(&secret, BUYER, 2), 2),
+            Err(BeneficiaryError::CutoffPassed {
+                option_id: "CALL-1".to_string(),
+                cutoff_height: 990
+            })
+        );
+    }
+
+    #[test]
+    fn test_manager_pays_beneficiary_and_restores_registry() {
+        use crate::account_keys::{sign_request, withdraw_payload};
+        use crate::claimable::ClaimableLedger;
+        use crate::simple_contract::SimpleContractManager;
+
+        let (pool_secret, _) = generate_keypair();
+        let (secret, owner) = generate_keypair();
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager.enable_claimable_balances(ClaimableLedger::new(NetworkProfile::TESTNET, pool_secret, 10_000));
+        manager.enable_beneficiaries(BeneficiaryRegistry::new(NetworkProfile::TESTNET).with_cutoff_blocks(10));
+        for id in ["CALL-1", "CALL-2"] {
+            manager
+                .create_option(id.to_string(), OptionType::Call, 7_000_000, 10_000_000, 250_000, 1_000, "user".to_string())
+                .unwrap();
+        }
+        manager.register_beneficiary("CALL-1", BUYER, owner).unwrap();
+
+        // 헤더 동기화가 tip을 넣기 전에는 변경 마감을 판단할 수 없음
+        assert_eq!(
+            manager.change_beneficiary("CALL-1", &signed(&secret, NEW_PAYOUT, 1)).err(),
+            Some(BeneficiaryError::TipUnknown)
+        );
+        manager.observe_height(980);
+        manager.change_beneficiary("CALL-1", &signed(&secret, NEW_PAYOUT, 1)).unwrap();
+
+        let designated_payout = manager.settle_option("CALL-1", 7_500_000).unwrap();
+        let holder_payout = manager.settle_option("CALL-2", 7_500_000).unwrap();
+
+        // 수익자 옵션의 적립은 변경된 지급 주소로 따로 출금
+        let withdrawals = manager.withdraw_to_beneficiaries().unwrap();
+        assert_eq!(withdrawals.len(), 1);
+        assert_eq!(withdrawals[0].address, NEW_PAYOUT);
+        assert_eq!(withdrawals[0].amount, designated_payout);
+        assert!(manager.withdraw_to_beneficiaries().unwrap().is_empty());
+
+        // 보유자 출금에는 수익자 옵션의 적립이 묶이지 않음
+        let (holder_secret, holder_key) = generate_keypair();
+        manager.bind_account_key("user", holder_key).unwrap();
+        let payload = withdraw_payload("user", BUYER, 1);
+        let withdrawal = manager
+            .withdraw_claims("user", BUYER, &sign_request(&payload, 1, &holder_secret))
+            .unwrap();
+        assert_eq!(withdrawal.amount, holder_payout);
+
+        // 등록부는 스냅샷으로 복원
+        let snapshot = manager.snapshot(980, Vec::new());
+        let mut restored = SimpleContractManager::restore(snapshot, &HashMap::new()).unwrap();
+        restored.enable_beneficiaries(BeneficiaryRegistry::new(NetworkProfile::TESTNET));
+        let entry = restored.beneficiary("CALL-1").unwrap();
+        assert_eq!(entry.address, NEW_PAYOUT);
+        assert_eq!(entry.nonce, 1);
+    }
+}
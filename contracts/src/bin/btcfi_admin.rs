@@ -0,0 +1,309 @@
+//! btcfi-admin: 운영자 CLI
+//!
+//! 실행 중인 contracts 서버(`contracts serve`)의 `/admin/*` API를 호출합니다.
+//! `init`은 서버 없이 bitcoind에 직접 붙어 새 환경을 한 번에 준비합니다.
+
+use anyhow::{bail, Context, Result};
+use btcfi_contracts::admin_api::{
+    AdminError, ExpireRequest, PauseRequest, RotateKeyRequest, SettleRequest,
+};
+use btcfi_contracts::bootstrap::{
+    bootstrap, write_generated_keys, BootstrapOptions, HttpBitcoindRpc, PoolKeys,
+};
+use btcfi_contracts::emergency::EmergencyConfig;
+use btcfi_contracts::tenant::api::API_KEY_HEADER;
+use clap::{Parser, Subcommand};
+use oracle_vm_common::NetworkProfile;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Parser)]
+#[command(name = "btcfi-admin")]
+#[command(about = "BTCFi operator tools")]
+struct Args {
+    /// contracts 서버 주소
+    #[arg(long, default_value = "http://127.0.0.1:3100")]
+    url: String,
+
+    /// 운영자 토큰 (생략 시 `BTCFI_ADMIN_TOKEN` 환경 변수)
+    #[arg(long)]
+    token: Option<String>,
+
+    /// 풀 API 키 (`X-Api-Key`, 생략 시 `BTCFI_API_KEY` 환경 변수)
+    #[arg(long)]
+    api_key: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 옵션 목록 (상태 필터: active, expired, settled)
+    List {
+        #[arg(long)]
+        status: Option<String>,
+    },
+
+    /// 옵션 상세 조회
+    Inspect { option_id: String },
+
+    /// 옵션 강제 만료 (지급 없이 담보 반환)
+    Expire {
+        option_id: String,
+
+        #[arg(long)]
+        reason: String,
+    },
+
+    /// 옵션 정산 실행
+    Settle {
+        option_id: String,
+
+        /// 정산 가격 (USD cents)
+        #[arg(long)]
+        spot_price: u64,
+    },
+
+    /// 거래 중단
+    Pause {
+        #[arg(long)]
+        reason: String,
+
+        /// 자동 재개 시각 (Unix timestamp, 초)
+        #[arg(long)]
+        resume_at: Option<u64>,
+    },
+
+    /// 거래 재개
+    Resume,
+
+    /// 호가 서명 공개키 교체 (스냅샷에 남아 재시작 후에도 유지)
+    RotateQuoteKey {
+        /// 압축 공개키 (hex)
+        public_key: String,
+    },
+
+    /// 복구 실패로 보류된 앵커를 다시 조회 대상으로
+    RequeueAnchors,
+
+    /// 풀 지표 출력
+    Pool,
+
+    /// 새 환경 부트스트랩: 풀 키, bitcoind 지갑, 풀 주소, (regtest) 초기 자금, 통합 설정
+    Init(Box<InitArgs>),
+}
+
+/// `init` 인수
+#[derive(clap::Args)]
+struct InitArgs {
+    /// 네트워크 프로필 (mainnet, testnet, signet, regtest)
+    #[arg(long, default_value = "regtest")]
+    network: NetworkProfile,
+
+    /// bitcoind RPC 주소 (기본: 네트워크 기본 포트)
+    #[arg(long)]
+    rpc_url: Option<String>,
+
+    /// bitcoind `.cookie` 파일 (rpc-user/rpc-password 대신)
+    #[arg(long)]
+    rpc_cookie: Option<PathBuf>,
+
+    #[arg(long, default_value = "btcfi")]
+    rpc_user: String,
+
+    #[arg(long, default_value = "btcfi")]
+    rpc_password: String,
+
+    /// 풀 운영자 키 수 (N)
+    #[arg(long, default_value_t = 3)]
+    managers: usize,
+
+    /// 풀 출력 사용에 필요한 서명 수 (M)
+    #[arg(long, default_value_t = 2)]
+    threshold: usize,
+
+    /// 가져올 운영자 공개키 (hex, 여러 번 지정 가능, 모자란 키는 생성)
+    #[arg(long)]
+    manager_pubkey: Vec<String>,
+
+    /// 가져올 복구 공개키 (hex, 생략 시 생성)
+    #[arg(long)]
+    recovery_pubkey: Option<String>,
+
+    /// regtest 풀 초기 자금 (BTC)
+    #[arg(long, default_value_t = 10.0)]
+    fund_btc: f64,
+
+    /// 통합 설정 출력 경로
+    #[arg(long, default_value = "config/btcfi.toml")]
+    config_out: PathBuf,
+
+    /// 새로 만든 비밀키 출력 경로 (0600, 기존 파일은 덮어쓰지 않음)
+    #[arg(long, default_value = "data/bootstrap-keys.json")]
+    keys_out: PathBuf,
+
+    #[arg(long, default_value = "http://127.0.0.1:50051")]
+    aggregator_url: String,
+
+    #[arg(long, default_value = "http://127.0.0.1:3000")]
+    calculation_url: String,
+}
+
+struct AdminClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+    api_key: Option<String>,
+}
+
+impl AdminClient {
+    fn new(base_url: &str, token: Option<String>, api_key: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            api_key,
+        }
+    }
+
+    fn authorized(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(api_key) = &self.api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        request
+    }
+
+    async fn get(&self, path: &str) -> Result<serde_json::Value> {
+        let response = self
+            .authorized(self.http.get(format!("{}{}", self.base_url, path)))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.base_url))?;
+        Self::read(response).await
+    }
+
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<serde_json::Value> {
+        let response = self
+            .authorized(self.http.post(format!("{}{}", self.base_url, path)))
+            .json(body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.base_url))?;
+        Self::read(response).await
+    }
+
+    async fn read(response: reqwest::Response) -> Result<serde_json::Value> {
+        let status = response.status();
+        let bytes = response.bytes().await?;
+        if !status.is_success() {
+            match serde_json::from_slice::<AdminError>(&bytes) {
+                Ok(err) => bail!("{} ({}): {}", status, err.code, err.message),
+                Err(_) => bail!("{}: {}", status, String::from_utf8_lossy(&bytes)),
+            }
+        }
+        if bytes.is_empty() {
+            return Ok(serde_json::Value::Null);
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+fn parse_pubkey(hex: &str) -> Result<bitcoin::PublicKey> {
+    bitcoin::PublicKey::from_str(hex).with_context(|| format!("Invalid public key {}", hex))
+}
+
+/// `init`: 키와 지갑을 준비하고 설정/키 파일을 쓴 뒤 리포트 반환
+async fn init(args: InitArgs, contracts_url: String) -> Result<serde_json::Value> {
+    let network = args.network;
+    let rpc_url = args.rpc_url.unwrap_or_else(|| network.default_rpc_url());
+    let rpc = match &args.rpc_cookie {
+        Some(path) => HttpBitcoindRpc::from_cookie(&rpc_url, path)?,
+        None => HttpBitcoindRpc::new(&rpc_url, &args.rpc_user, &args.rpc_password),
+    };
+    if args.config_out.exists() {
+        bail!("{} already exists, remove it to re-initialize", args.config_out.display());
+    }
+
+    let imported = args
+        .manager_pubkey
+        .iter()
+        .map(|hex| parse_pubkey(hex))
+        .collect::<Result<Vec<_>>>()?;
+    let recovery = args.recovery_pubkey.as_deref().map(parse_pubkey).transpose()?;
+    let keys = PoolKeys::provision(network.network, imported, args.managers, recovery)?;
+    if !keys.generated.is_empty() && args.keys_out.exists() {
+        bail!("{} already exists, move it before generating new keys", args.keys_out.display());
+    }
+    let options = BootstrapOptions {
+        profile: network,
+        rpc_url,
+        threshold: args.threshold,
+        emergency: EmergencyConfig::default(),
+        fund_amount: bitcoin::Amount::from_btc(args.fund_btc)?,
+        contracts_url,
+        aggregator_url: args.aggregator_url,
+        calculation_url: args.calculation_url,
+    };
+
+    let (config, mut report) = bootstrap(&rpc, &options, &keys).await?;
+    if !keys.generated.is_empty() {
+        write_generated_keys(&args.keys_out, &keys.generated)?;
+        report.generated_keys_path = Some(args.keys_out.display().to_string());
+    }
+    config.save(&args.config_out)?;
+    report.config_path = Some(args.config_out.display().to_string());
+    Ok(serde_json::to_value(report)?)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let token = args.token.or_else(|| std::env::var("BTCFI_ADMIN_TOKEN").ok());
+    let api_key = args.api_key.or_else(|| std::env::var("BTCFI_API_KEY").ok());
+    let client = AdminClient::new(&args.url, token, api_key);
+
+    let result = match args.command {
+        Command::List { status } => match status {
+            Some(status) => client.get(&format!("/admin/options?status={}", status)).await?,
+            None => client.get("/admin/options").await?,
+        },
+        Command::Inspect { option_id } => client.get(&format!("/admin/options/{}", option_id)).await?,
+        Command::Expire { option_id, reason } => {
+            client
+                .post(&format!("/admin/options/{}/expire", option_id), &ExpireRequest { reason })
+                .await?
+        }
+        Command::Settle {
+            option_id,
+            spot_price,
+        } => {
+            client
+                .post(&format!("/admin/options/{}/settle", option_id), &SettleRequest { spot_price })
+                .await?
+        }
+        Command::Pause { reason, resume_at } => {
+            client
+                .post("/admin/trading/pause", &PauseRequest { reason, resume_at })
+                .await?
+        }
+        Command::Resume => client.post("/admin/trading/resume", &()).await?,
+        Command::RotateQuoteKey { public_key } => {
+            client
+                .post("/admin/keys/quote", &RotateKeyRequest { public_key })
+                .await?
+        }
+        Command::RequeueAnchors => client.post("/admin/anchors/requeue", &()).await?,
+        Command::Pool => client.get("/admin/pool").await?,
+        Command::Init(init_args) => init(*init_args, args.url.clone()).await?,
+    };
+
+    if !result.is_null() {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! 바이너리(cash-or-nothing) 옵션
+//!
+//! 바이너리 호가(`payoff: binary`)로 연 옵션은 정산 가격이 행사가를 넘으면
+//! (콜은 위, 풋은 아래) 수량만큼의 고정 지급액을, 아니면 아무것도 지급하지
+//! 않습니다. 최대 지급액이 수량이므로 콜/풋 모두 수량만큼만 담보로 잠급니다.
+//! 정산은 STL 대신 고정 지급액을 담은 BIN 앵커로 남겨 검증자가 지급 조건을
+//! 옵션 조건 없이 확인할 수 있게 합니다.
+
+use oracle_vm_common::crypto::sha256;
+
+/// 바이너리 정산 앵커 태그
+pub const BINARY_ANCHOR_TAG: &[u8; 3] = b"BIN";
+
+/// BIN 앵커 페이로드: "BIN" || SHA256(옵션 ID) || 고정 지급액 (u64 BE) || 마지막 감사 해시 (75 bytes)
+pub fn binary_settle_anchor_payload(option_id: &str, payout: u64, audit_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(75);
+    payload.extend_from_slice(BINARY_ANCHOR_TAG);
+    payload.extend_from_slice(&sha256(option_id.as_bytes()));
+    payload.extend_from_slice(&payout.to_be_bytes());
+    payload.extend_from_slice(audit_hash);
+    payload
+}
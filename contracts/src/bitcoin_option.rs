@@ -1,10 +1,10 @@
 use bitcoin::blockdata::opcodes::all::*;
 use bitcoin::blockdata::script::{Builder, ScriptBuf};
-use bitcoin::taproot::{TaprootBuilder, TaprootSpendInfo};
-use bitcoin::secp256k1::{Secp256k1};
-use bitcoin::XOnlyPublicKey;
+use bitcoin::taproot::TaprootSpendInfo;
 use anyhow::Result;
+use crate::taproot_address::{OptionTaproot, TaprootAddressBuilder};
 use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{NetworkProfile, OptionId, OptionTerms};
 
 /// Bitcoin L1 단방향 옵션 컨트랙트
 /// BitVMX를 사용하여 오프체인 계산과 온체인 검증을 결합
@@ -29,42 +29,36 @@ pub struct BitcoinOption {
 
 
 impl BitcoinOption {
-    /// 옵션 컨트랙트의 Taproot 스크립트 생성
-    pub fn create_taproot_script(&self) -> Result<(ScriptBuf, TaprootSpendInfo)> {
-        let secp = Secp256k1::new();
-        
-        // 1. Key-path spending: 정상적인 협력 정산
-        let internal_key = self.create_musig_internal_key()?;
-        
-        // 2. Script-path spending: BitVMX 증명 기반 정산
-        let settlement_script = self.create_settlement_script();
-        let refund_script = self.create_refund_script();
-        
-        // Taproot tree 구성
-        let taproot_builder = TaprootBuilder::new()
-            .add_leaf(1, settlement_script.clone())?
-            .add_leaf(1, refund_script.clone())?;
-            
-        let internal_xonly = XOnlyPublicKey::from(internal_key);
-        let taproot_spend_info = taproot_builder.finalize(&secp, internal_xonly)
-            .map_err(|_| anyhow::anyhow!("Failed to finalize taproot"))?;
-        
-        // Taproot output script
-        let taproot_output = Builder::new()
-            .push_opcode(OP_PUSHNUM_1)
-            .push_slice(&internal_xonly.serialize())
-            .into_script();
-        
-        Ok((taproot_output, taproot_spend_info))
+    /// 구매자 공개키와 옵션 조건으로 파생한 정식 옵션 ID
+    pub fn option_id(&self, nonce: u64) -> OptionId {
+        let terms = OptionTerms {
+            option_type: self.option_type,
+            strike_price: self.strike_price,
+            quantity: self.collateral,
+            expiry: self.expiry_block as u64,
+        };
+        OptionId::derive(&self.buyer_pubkey.serialize(), &terms, nonce)
     }
-    
-    /// MuSig 내부 키 생성 (구매자 + 판매자 협력)
-    fn create_musig_internal_key(&self) -> Result<bitcoin::secp256k1::PublicKey> {
-        // 실제 구현에서는 MuSig2 프로토콜 사용
-        // 여기서는 단순화를 위해 구매자 키 반환
-        Ok(self.buyer_pubkey)
+
+    /// 옵션 담보 Taproot 출력 (NUMS 내부 키 + 정산/협의 해지/회수 경로)
+    ///
+    /// 정산 경로는 BitVMX 증명 검증 스크립트, 협의 해지와 회수는 판매자(풀) 키를 씁니다.
+    pub fn taproot(&self, profile: NetworkProfile, option_id: &str) -> Result<OptionTaproot> {
+        TaprootAddressBuilder::new(profile, self.seller_pubkey, self.verifier_pubkey).build_with_settlement(
+            option_id,
+            &self.buyer_pubkey,
+            self.expiry_block,
+            self.create_settlement_script(),
+        )
     }
-    
+
+    /// 옵션 컨트랙트의 Taproot 출력 스크립트와 spend info
+    pub fn create_taproot_script(&self) -> Result<(ScriptBuf, TaprootSpendInfo)> {
+        // 출력 스크립트는 네트워크와 무관 (주소 문자열만 달라짐)
+        let taproot = self.taproot(NetworkProfile::MAINNET, &self.option_id(0).to_string())?;
+        Ok((taproot.record.script_pubkey, taproot.spend_info))
+    }
+
     /// 정산 스크립트: BitVMX 증명 검증 후 자동 정산
     fn create_settlement_script(&self) -> ScriptBuf {
         Builder::new()
@@ -102,20 +96,6 @@ impl BitcoinOption {
             .into_script()
     }
     
-    /// 환불 스크립트: 만기 후 일정 시간 지나면 판매자가 회수
-    fn create_refund_script(&self) -> ScriptBuf {
-        Builder::new()
-            // 만기 + 1일 후
-            .push_int((self.expiry_block + 144) as i64)
-            .push_opcode(OP_CLTV)
-            .push_opcode(OP_DROP)
-            
-            // 판매자 서명
-            .push_slice(&self.seller_pubkey.serialize())
-            .push_opcode(OP_CHECKSIG)
-            .into_script()
-    }
-    
     /// 옵션 구매 트랜잭션 생성
     pub fn create_purchase_transaction(&self) -> Result<bitcoin::Transaction> {
         // TODO: 실제 트랜잭션 구성
@@ -183,7 +163,8 @@ impl BitcoinOption {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bitcoin::secp256k1::{SecretKey, PublicKey, rand::thread_rng};
+    use bitcoin::secp256k1::{Secp256k1, SecretKey, PublicKey, rand::thread_rng};
+    use crate::taproot_address::LeafKind;
     
     #[test]
     fn test_create_option_script() {
@@ -205,8 +186,19 @@ mod tests {
             collateral: 10_000_000, // 0.1 BTC
         };
         
-        let (script, _spend_info) = option.create_taproot_script().unwrap();
+        let (script, spend_info) = option.create_taproot_script().unwrap();
         assert!(script.is_p2tr());
+        assert_eq!(script, bitcoin::ScriptBuf::new_p2tr_tweaked(spend_info.output_key()));
+
+        // 출력 키에 커밋된 세 경로, 회수는 만기 하루 뒤
+        let record = option.taproot(NetworkProfile::TESTNET, "OPT-1").unwrap().record;
+        assert_eq!(record.script_pubkey, script);
+        for leaf in &record.leaves {
+            let control_block = leaf.control_block().unwrap();
+            assert!(control_block.verify_taproot_commitment(&secp, spend_info.output_key().to_x_only_public_key(), &leaf.script));
+        }
+        let timeout = &record.leaf(LeafKind::Timeout).unwrap().script;
+        assert!(timeout.as_bytes().starts_with(&Builder::new().push_int(800_144).into_script().as_bytes()));
     }
     
     #[test]
@@ -0,0 +1,268 @@
+//! BitVMX 실행 백엔드
+//!
+//! 정산 프로그램 실행을 트레이트 뒤로 분리합니다. 실제 배포는 BitVMX-CPU
+//! 에뮬레이터 바이너리와 ELF 파일을 사용하고, 테스트는 파일시스템이나
+//! 하위 프로세스 없이 같은 출력 형식을 내는 결정적 mock을 사용합니다.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::warn;
+
+/// `cargo xtask build-programs`가 쓰는 정산 프로그램 lock 파일
+pub const PROGRAM_LOCK_PATH: &str = "../programs/programs.lock.json";
+
+/// 빌드 파이프라인이 고정한 정산 프로그램 버전과 ELF 해시
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramPin {
+    pub name: String,
+    pub version: String,
+    /// ELF 파일 SHA-256 (hex)
+    pub sha256: String,
+}
+
+/// 정산 프로그램 lock 파일 (`programs/programs.lock.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramLock {
+    pub programs: Vec<ProgramPin>,
+}
+
+impl ProgramLock {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read program lock {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Invalid program lock {}", path.display()))
+    }
+
+    pub fn pin(&self, name: &str) -> Option<&ProgramPin> {
+        self.programs.iter().find(|pin| pin.name == name)
+    }
+}
+
+/// 정산 프로그램 실행기
+pub trait BitVmxBackend: Send + Sync {
+    /// 16바이트 정산 입력을 실행하고 에뮬레이터 표준 출력을 반환
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>>;
+
+    /// 실행하는 정산 프로그램의 해시 (CREATE 앵커 바인딩에 고정)
+    fn program_hash(&self) -> Result<[u8; 32]>;
+
+    fn name(&self) -> &str;
+}
+
+/// BitVMX-CPU 에뮬레이터 하위 프로세스 실행
+pub struct EmulatorProcessBackend {
+    /// BitVMX 바이너리 경로
+    bitvmx_path: String,
+    /// 옵션 정산 프로그램 경로
+    settlement_program: String,
+    /// 실행 전에 확인할 프로그램 버전/해시 (없으면 검사하지 않음)
+    pin: Option<ProgramPin>,
+}
+
+impl EmulatorProcessBackend {
+    pub fn new(bitvmx_path: impl Into<String>, settlement_program: impl Into<String>) -> Self {
+        Self {
+            bitvmx_path: bitvmx_path.into(),
+            settlement_program: settlement_program.into(),
+            pin: None,
+        }
+    }
+
+    /// 정산 프로그램 ELF가 고정된 해시와 같을 때만 실행
+    pub fn with_pin(mut self, pin: ProgramPin) -> Self {
+        self.pin = Some(pin);
+        self
+    }
+
+    fn elf_hash(&self) -> Result<[u8; 32]> {
+        let elf = std::fs::read(&self.settlement_program)
+            .with_context(|| format!("Failed to read {}", self.settlement_program))?;
+        Ok(Sha256::digest(&elf).into())
+    }
+
+    /// 고정된 해시와 ELF 파일 비교
+    fn verify_program(&self) -> Result<()> {
+        let Some(pin) = &self.pin else {
+            return Ok(());
+        };
+        let actual = hex::encode(self.elf_hash()?);
+        if actual != pin.sha256 {
+            bail!(
+                "Settlement program {} does not match pinned {} v{} (expected {}, found {})",
+                self.settlement_program,
+                pin.name,
+                pin.version,
+                pin.sha256,
+                actual
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Default for EmulatorProcessBackend {
+    /// lock 파일에 option_settlement가 고정되어 있으면 그 해시로 검사
+    fn default() -> Self {
+        let backend = Self::new(
+            "../bitvmx_protocol/BitVMX-CPU/target/release/emulator",
+            "../bitvmx_protocol/execution_files/option_settlement.elf",
+        );
+        match ProgramLock::load(PROGRAM_LOCK_PATH) {
+            Ok(lock) => match lock.pin("option_settlement") {
+                Some(pin) => backend.with_pin(pin.clone()),
+                None => {
+                    warn!("⚠️ option_settlement is not pinned in {}", PROGRAM_LOCK_PATH);
+                    backend
+                }
+            },
+            Err(e) => {
+                warn!("⚠️ Running unpinned settlement program: {}", e);
+                backend
+            }
+        }
+    }
+}
+
+impl BitVmxBackend for EmulatorProcessBackend {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>> {
+        self.verify_program()?;
+        let output = Command::new(&self.bitvmx_path)
+            .arg("execute")
+            .arg("--elf")
+            .arg(&self.settlement_program)
+            .arg("--input")
+            .arg(hex::encode(input))
+            .arg("--trace")
+            .output()
+            .with_context(|| format!("Failed to run {}", self.bitvmx_path))?;
+
+        if !output.status.success() {
+            bail!("BitVMX execution failed");
+        }
+        Ok(output.stdout)
+    }
+
+    fn program_hash(&self) -> Result<[u8; 32]> {
+        self.verify_program()?;
+        self.elf_hash()
+    }
+
+    fn name(&self) -> &str {
+        "emulator"
+    }
+}
+
+/// 결정적 인메모리 백엔드 (테스트용)
+///
+/// 정산 프로그램과 같은 규칙으로 내재가치를 계산해 에뮬레이터와 같은 형식으로 출력합니다.
+#[derive(Debug, Default)]
+pub struct MockBitVmxBackend {
+    executions: AtomicUsize,
+}
+
+impl MockBitVmxBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 지금까지 실행된 횟수
+    pub fn executions(&self) -> usize {
+        self.executions.load(Ordering::SeqCst)
+    }
+}
+
+impl BitVmxBackend for MockBitVmxBackend {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>> {
+        self.executions.fetch_add(1, Ordering::SeqCst);
+
+        if input.len() != 16 {
+            bail!("Expected 16-byte settlement input, got {}", input.len());
+        }
+        let word = |index: usize| {
+            let bytes: [u8; 4] = input[index * 4..index * 4 + 4].try_into().unwrap();
+            u32::from_le_bytes(bytes) as u64
+        };
+        let (option_type, strike, spot, quantity) = (word(0), word(1), word(2), word(3));
+
+        let intrinsic = match option_type {
+            0 => spot.saturating_sub(strike),
+            1 => strike.saturating_sub(spot),
+            other => bail!("Invalid option type {}", other),
+        };
+        // quantity는 소수점 2자리 고정소수 (100 = 1.00)
+        let settlement_cents = intrinsic * quantity / 100;
+
+        let mut stdout = String::from("Executing option_settlement.elf\n");
+        if settlement_cents > 0 {
+            stdout.push_str(&format!("Settlement amount: {} cents\n", settlement_cents));
+        }
+        stdout.push_str("Halt: 0\n");
+        Ok(stdout.into_bytes())
+    }
+
+    /// 실제 ELF가 없으므로 고정된 가짜 해시
+    fn program_hash(&self) -> Result<[u8; 32]> {
+        Ok(Sha256::digest(b"mock option_settlement.elf").into())
+    }
+
+    fn name(&self) -> &str {
+        "mock"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitvmx_bridge::parse_settlement_output;
+
+    fn input(option_type: u32, strike: u32, spot: u32) -> Vec<u8> {
+        [option_type, strike, spot, 100]
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_mock_matches_emulator_output_format() {
+        let backend = MockBitVmxBackend::new();
+
+        let itm = backend.execute(&input(0, 50_000, 52_000)).unwrap();
+        assert_eq!(parse_settlement_output(&itm).unwrap(), 2_000_000);
+
+        let otm = backend.execute(&input(1, 50_000, 52_000)).unwrap();
+        assert_eq!(parse_settlement_output(&otm).unwrap(), 0);
+
+        assert!(backend.execute(&input(7, 1, 1)).is_err());
+        assert!(backend.execute(&[0u8; 3]).is_err());
+        assert_eq!(backend.executions(), 4);
+    }
+
+    #[test]
+    fn test_emulator_refuses_unpinned_elf() {
+        let path = std::env::temp_dir().join(format!("option_settlement-{}.elf", std::process::id()));
+        std::fs::write(&path, b"\x7fELF pinned").unwrap();
+        let pin = ProgramPin {
+            name: "option_settlement".to_string(),
+            version: "0.1.0".to_string(),
+            sha256: hex::encode(Sha256::digest(b"\x7fELF pinned")),
+        };
+        let lock = ProgramLock {
+            programs: vec![pin.clone()],
+        };
+        assert_eq!(lock.pin("option_settlement"), Some(&pin));
+
+        let backend = EmulatorProcessBackend::new("/nonexistent/emulator", path.to_string_lossy())
+            .with_pin(pin);
+        backend.verify_program().unwrap();
+
+        std::fs::write(&path, b"\x7fELF swapped").unwrap();
+        let err = backend.execute(&input(0, 1, 2)).unwrap_err();
+        assert!(err.to_string().contains("does not match pinned"), "{}", err);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,89 +1,158 @@
 use crate::bitcoin_option::BitcoinOption;
+use crate::anchor_backend::{create_anchor_binding, create_anchor_short_id};
+use crate::bitvmx_backend::{BitVmxBackend, EmulatorProcessBackend};
+use crate::program_binding::{ProgramBinding, OPTION_INPUT_LEN};
+use bitcoin::secp256k1::PublicKey;
+use oracle_vm_common::{ConsensusProof, SharedClock, SystemClock};
 use oracle_vm_common::types::OptionType;
-use anyhow::Result;
+use oracle_vm_common::option_id::{OptionId, SHORT_ID_LEN};
+use anyhow::{anyhow, bail, Result};
+use tracing::instrument;
 use bitcoin::hashes::{sha256, Hash};
-use std::process::Command;
+
+/// BitVMX 출력에서 정산 금액 파싱 (신뢰할 수 없는 입력, 패닉 없이 오류 반환)
+///
+/// 출력 형식: "Settlement amount: XXXX cents". 해당 줄이 없으면 OTM으로 보고 0을 반환합니다.
+pub fn parse_settlement_output(output: &[u8]) -> Result<u64> {
+    let output = String::from_utf8_lossy(output);
+    for line in output.lines() {
+        if line.contains("Settlement amount:") {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 3 {
+                let cents: u64 = parts[2].parse()?;
+                // cents to satoshis (assuming 1 BTC = $100,000)
+                return cents
+                    .checked_mul(1_000)
+                    .ok_or_else(|| anyhow::anyhow!("Settlement amount {} out of range", cents));
+            }
+        }
+    }
+
+    // ITM이 아니면 0 반환
+    Ok(0)
+}
 
 /// BitVMX와 Bitcoin 옵션을 연결하는 브릿지
 /// 오프체인에서 가격을 받아 BitVMX로 증명을 생성하고
 /// 온체인에서 검증 가능한 형태로 변환
 pub struct BitVmxBridge {
-    /// BitVMX 바이너리 경로
-    bitvmx_path: String,
-    /// 옵션 정산 프로그램 경로
-    settlement_program: String,
+    /// 정산 프로그램 실행 백엔드
+    backend: Box<dyn BitVmxBackend>,
+    /// 증명 데이터 타임스탬프용 시계
+    clock: SharedClock,
 }
 
 impl BitVmxBridge {
+    /// 로컬 BitVMX-CPU 에뮬레이터를 사용하는 브릿지
     pub fn new() -> Self {
+        Self::with_backend(Box::new(EmulatorProcessBackend::default()))
+    }
+
+    /// 지정한 실행 백엔드 사용 (테스트에서는 MockBitVmxBackend)
+    pub fn with_backend(backend: Box<dyn BitVmxBackend>) -> Self {
         Self {
-            bitvmx_path: "../bitvmx_protocol/BitVMX-CPU/target/release/emulator".to_string(),
-            settlement_program: "../bitvmx_protocol/execution_files/option_settlement.elf".to_string(),
+            backend,
+            clock: SystemClock::shared(),
         }
     }
+
+    /// 시계 교체 (테스트에서 증명 데이터를 결정적으로 만들 때)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
     
+    /// 현물가를 뺀 정산 입력: option_type, strike(cents), quantity (CREATE 앵커 바인딩용)
+    pub fn option_input(&self, option: &BitcoinOption) -> [u8; OPTION_INPUT_LEN] {
+        let option_type = match option.option_type {
+            OptionType::Call => 0u32,
+            OptionType::Put => 1u32,
+        };
+        let strike_cents = (option.strike_price / 1_000) as u32; // satoshis to cents
+        // Quantity - simplified to 1 unit
+        let quantity = 100u32; // 1.00 in fixed point
+
+        let mut input = [0u8; OPTION_INPUT_LEN];
+        input[0..4].copy_from_slice(&option_type.to_le_bytes());
+        input[4..8].copy_from_slice(&strike_cents.to_le_bytes());
+        input[8..12].copy_from_slice(&quantity.to_le_bytes());
+        input
+    }
+
     /// Oracle 가격 데이터를 BitVMX 입력 형식으로 변환
+    ///
+    /// option_type(4) || strike(4) || spot(4) || quantity(4), 모두 u32 LE
     pub fn prepare_settlement_input(
         &self,
         option: &BitcoinOption,
         spot_price: u64,
     ) -> Vec<u8> {
+        let terms = self.option_input(option);
         let mut input = Vec::with_capacity(16);
-        
-        // Option type (4 bytes)
-        let option_type_bytes = match option.option_type {
-            OptionType::Call => 0u32,
-            OptionType::Put => 1u32,
-        };
-        input.extend_from_slice(&option_type_bytes.to_le_bytes());
-        
-        // Strike price in cents (4 bytes)
-        let strike_cents = (option.strike_price / 1_000) as u32; // satoshis to cents
-        input.extend_from_slice(&strike_cents.to_le_bytes());
-        
+        input.extend_from_slice(&terms[0..8]);
+
         // Spot price in cents (4 bytes)
         let spot_cents = (spot_price / 1_000) as u32;
         input.extend_from_slice(&spot_cents.to_le_bytes());
-        
-        // Quantity (4 bytes) - simplified to 1 unit
-        let quantity = 100u32; // 1.00 in fixed point
-        input.extend_from_slice(&quantity.to_le_bytes());
-        
+
+        input.extend_from_slice(&terms[8..12]);
         input
     }
-    
+
+    /// 옵션 생성 시 CREATE 앵커에 넣을 프로그램 바인딩
+    pub fn program_binding(&self, option: &BitcoinOption, oracle_keys: Vec<PublicKey>) -> Result<ProgramBinding> {
+        Ok(ProgramBinding::new(
+            self.backend.program_hash()?,
+            self.option_input(option),
+            oracle_keys,
+        ))
+    }
+
     /// BitVMX를 실행하여 정산 증명 생성
     pub async fn generate_settlement_proof(
         &self,
         option: &BitcoinOption,
         spot_price: u64,
+    ) -> Result<SettlementProof> {
+        self.generate_proof(option, spot_price, None)
+    }
+
+    /// 옵션 ID(6바이트 단축 ID)를 증명 데이터에 묶어 정산 증명 생성
+    ///
+    /// 정산 프로그램 입력은 16바이트 고정이라 ID는 증명 데이터 쪽에 들어가며,
+    /// 앵커의 단축 ID와 같은 값이므로 증명과 앵커를 서로 연결할 수 있습니다.
+    pub async fn generate_settlement_proof_for(
+        &self,
+        option_id: &OptionId,
+        option: &BitcoinOption,
+        spot_price: u64,
+    ) -> Result<SettlementProof> {
+        self.generate_proof(option, spot_price, Some(option_id))
+    }
+
+    #[instrument(level = "info", skip_all, fields(option_id = option_id.map(tracing::field::display), spot_price = spot_price))]
+    fn generate_proof(
+        &self,
+        option: &BitcoinOption,
+        spot_price: u64,
+        option_id: Option<&OptionId>,
     ) -> Result<SettlementProof> {
         let input = self.prepare_settlement_input(option, spot_price);
-        let input_hex = hex::encode(&input);
-        
-        // BitVMX 에뮬레이터 실행
-        let output = Command::new(&self.bitvmx_path)
-            .arg("execute")
-            .arg("--elf")
-            .arg(&self.settlement_program)
-            .arg("--input")
-            .arg(&input_hex)
-            .arg("--trace")
-            .output()?;
-            
-        if !output.status.success() {
-            anyhow::bail!("BitVMX execution failed");
-        }
+        let program_hash = self.backend.program_hash()?;
+        
+        // BitVMX 정산 프로그램 실행
+        let output = self.backend.execute(&input)?;
         
         // 실행 결과 파싱
-        let stdout = String::from_utf8(output.stdout)?;
-        let settlement_amount = self.parse_settlement_amount(&stdout)?;
+        let settlement_amount = parse_settlement_output(&output)?;
+        let stdout = String::from_utf8_lossy(&output).into_owned();
         
         // 증명 데이터 구성
         let proof_data = self.create_proof_data(
             option,
             spot_price,
             settlement_amount,
+            option_id,
         );
         
         // 증명 해시 계산
@@ -94,33 +163,18 @@ impl BitVmxBridge {
             proof_hash: proof_hash.to_byte_array(),
             settlement_amount,
             execution_trace: stdout,
+            settlement_input: input,
+            program_hash,
         })
     }
     
-    /// BitVMX 출력에서 정산 금액 파싱
-    fn parse_settlement_amount(&self, output: &str) -> Result<u64> {
-        // BitVMX 출력 형식: "Settlement amount: XXXX cents"
-        for line in output.lines() {
-            if line.contains("Settlement amount:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 3 {
-                    let cents: u64 = parts[2].parse()?;
-                    // cents to satoshis (assuming 1 BTC = $100,000)
-                    return Ok(cents * 1_000);
-                }
-            }
-        }
-        
-        // ITM이 아니면 0 반환
-        Ok(0)
-    }
-    
     /// 온체인 검증을 위한 증명 데이터 생성
     fn create_proof_data(
         &self,
         option: &BitcoinOption,
         spot_price: u64,
         settlement_amount: u64,
+        option_id: Option<&OptionId>,
     ) -> Vec<u8> {
         let mut data = Vec::new();
         
@@ -134,12 +188,14 @@ impl BitVmxBridge {
         data.extend_from_slice(&settlement_amount.to_le_bytes());
         
         // 타임스탬프 추가
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let timestamp = self.clock.now();
         data.extend_from_slice(&timestamp.to_le_bytes());
         
+        // 옵션 단축 ID (있을 때만)
+        if let Some(option_id) = option_id {
+            data.extend_from_slice(&option_id.short_id());
+        }
+        
         data
     }
     
@@ -152,6 +208,55 @@ impl BitVmxBridge {
         let computed_hash = sha256::Hash::hash(&proof.proof_data);
         &computed_hash.to_byte_array() == expected_hash
     }
+
+    /// 정산 증명을 CREATE 앵커의 프로그램 바인딩과 합의 증명에 대조
+    ///
+    /// 앵커의 커밋먼트가 `binding`과 같고, 증명이 같은 옵션 ID와 프로그램으로 같은
+    /// 조건을 실행했으며, 입력 현물가가 바인딩된 Aggregator 키로 서명된 합의
+    /// 증명의 중앙값일 때만 통과합니다.
+    pub fn validate_proof(
+        &self,
+        proof: &SettlementProof,
+        create_anchor: &[u8],
+        binding: &ProgramBinding,
+        consensus: &ConsensusProof,
+    ) -> Result<()> {
+        if !self.verify_proof(proof, &proof.proof_hash) {
+            bail!("Proof hash does not match proof data");
+        }
+        if create_anchor_binding(create_anchor) != Some(binding.commitment()) {
+            bail!("CREATE anchor does not commit to this program binding");
+        }
+        match (create_anchor_short_id(create_anchor), proof.option_short_id()) {
+            (Some(anchored), Some(proven)) if anchored == proven => {}
+            _ => bail!("Proof is not bound to the anchored option ID"),
+        }
+        if proof.program_hash != binding.program_hash {
+            bail!(
+                "Proof was produced by program {}, anchor binds {}",
+                hex::encode(proof.program_hash),
+                hex::encode(binding.program_hash)
+            );
+        }
+        let input = &proof.settlement_input;
+        if input.len() != 16 || input[0..8] != binding.option_input[0..8] || input[12..16] != binding.option_input[8..12] {
+            bail!("Settlement input does not match the anchored option terms");
+        }
+
+        consensus.verify().map_err(|e| anyhow!("Invalid consensus proof: {}", e))?;
+        if !binding.contains_key(&consensus.aggregator_pubkey) {
+            bail!("Consensus proof signed by a key outside the anchored oracle set");
+        }
+        let spot_cents = u32::from_le_bytes(input[8..12].try_into().unwrap()) as u64;
+        if spot_cents != consensus.settlement_price() {
+            bail!(
+                "Settlement spot {} cents differs from consensus median {} cents",
+                spot_cents,
+                consensus.settlement_price()
+            );
+        }
+        Ok(())
+    }
 }
 
 /// 정산 증명 구조체
@@ -165,11 +270,30 @@ pub struct SettlementProof {
     pub settlement_amount: u64,
     /// BitVMX 실행 트레이스
     pub execution_trace: String,
+    /// 정산 프로그램에 넣은 16바이트 입력
+    pub settlement_input: Vec<u8>,
+    /// 실행한 정산 프로그램 해시
+    pub program_hash: [u8; 32],
+}
+
+impl SettlementProof {
+    /// ID 없는 증명 데이터 길이 (타입 + 행사가 + 현물가 + 정산 금액 + 타임스탬프)
+    const BASE_PROOF_LEN: usize = 1 + 8 * 4;
+
+    /// 증명 데이터에 묶인 옵션 단축 ID
+    pub fn option_short_id(&self) -> Option<[u8; SHORT_ID_LEN]> {
+        if self.proof_data.len() != Self::BASE_PROOF_LEN + SHORT_ID_LEN {
+            return None;
+        }
+        self.proof_data[Self::BASE_PROOF_LEN..].try_into().ok()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bitvmx_backend::MockBitVmxBackend;
+    use oracle_vm_common::ManualClock;
     use bitcoin::secp256k1::{Secp256k1, SecretKey, PublicKey};
     use bitcoin::secp256k1::rand::thread_rng;
     
@@ -202,6 +326,9 @@ mod tests {
         let strike_bytes = &input[4..8];
         let strike = u32::from_le_bytes(strike_bytes.try_into().unwrap());
         assert_eq!(strike, 50_000_000);
+
+        // 골든 벡터: Call, strike 50,000,000, spot 52,000,000, quantity 100 (u32 LE)
+        assert_eq!(hex::encode(&input), "0000000080f0fa020075190364000000");
     }
     
     #[test]
@@ -216,6 +343,8 @@ mod tests {
             proof_hash,
             settlement_amount: 1_000_000,
             execution_trace: "test trace".to_string(),
+            settlement_input: vec![0; 16],
+            program_hash: [0; 32],
         };
         
         // Should verify with correct hash
@@ -225,4 +354,138 @@ mod tests {
         let wrong_hash = [0u8; 32];
         assert!(!bridge.verify_proof(&proof, &wrong_hash));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_parse_settlement_output_malformed() {
+        assert_eq!(parse_settlement_output(b"Settlement amount: 2000 cents").unwrap(), 2_000_000);
+        assert_eq!(parse_settlement_output(b"no settlement").unwrap(), 0);
+        assert!(parse_settlement_output(b"Settlement amount: abc cents").is_err());
+        // 곱셈 오버플로와 비 UTF-8 바이트
+        assert!(parse_settlement_output(b"Settlement amount: 18446744073709551615 cents").is_err());
+        assert_eq!(parse_settlement_output(&[0xff, 0xfe, b'\n']).unwrap(), 0);
+    }
+    
+    #[tokio::test]
+    async fn test_generate_proof_with_mock_backend() {
+        let bridge = BitVmxBridge::with_backend(Box::new(MockBitVmxBackend::new()));
+        let secp = Secp256k1::new();
+        let key = |byte: u8| PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[byte; 32]).unwrap());
+        
+        let option = BitcoinOption {
+            option_type: OptionType::Call,
+            strike_price: 50_000_000, // $50k → 50,000 cents 입력
+            expiry_block: 800_000,
+            buyer_pubkey: key(1),
+            seller_pubkey: key(2),
+            verifier_pubkey: key(3),
+            premium: 1_000_000,
+            collateral: 10_000_000,
+        };
+        
+        let proof = bridge.generate_settlement_proof(&option, 52_000_000).await.unwrap();
+        assert_eq!(proof.settlement_amount, 2_000_000);
+        assert!(bridge.verify_proof(&proof, &proof.proof_hash));
+        
+        let otm = bridge.generate_settlement_proof(&option, 48_000_000).await.unwrap();
+        assert_eq!(otm.settlement_amount, 0);
+        assert_eq!(otm.option_short_id(), None);
+        
+        // 단축 ID가 증명에 묶이고 해시에도 반영됨
+        let option_id = option.option_id(0);
+        let bound = bridge.generate_settlement_proof_for(&option_id, &option, 52_000_000).await.unwrap();
+        assert_eq!(bound.settlement_amount, 2_000_000);
+        assert!(bridge.verify_proof(&bound, &bound.proof_hash));
+        assert!(option_id.matches_short(&bound.option_short_id().unwrap()));
+        assert!(!option.option_id(1).matches_short(&bound.option_short_id().unwrap()));
+
+        // 고정된 시계에서는 같은 입력이 같은 증명 해시를 만듦
+        let clock = ManualClock::new(1_700_000_000);
+        let bridge = BitVmxBridge::with_backend(Box::new(MockBitVmxBackend::new())).with_clock(clock.shared());
+        let first = bridge.generate_settlement_proof(&option, 52_000_000).await.unwrap();
+        let second = bridge.generate_settlement_proof(&option, 52_000_000).await.unwrap();
+        assert_eq!(first.proof_hash, second.proof_hash);
+        clock.advance(1);
+        let later = bridge.generate_settlement_proof(&option, 52_000_000).await.unwrap();
+        assert_ne!(later.proof_hash, first.proof_hash);
+    }
+
+    #[tokio::test]
+    async fn test_validate_proof_against_anchor_binding() {
+        use crate::anchor_backend::create_anchor_payload;
+        use oracle_vm_common::crypto::{generate_keypair, price_submission_payload, sign_data};
+        use oracle_vm_common::{OptionTerms, SignedSubmission};
+
+        let bridge = BitVmxBridge::with_backend(Box::new(MockBitVmxBackend::new()));
+        let secp = Secp256k1::new();
+        let key = |byte: u8| PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[byte; 32]).unwrap());
+        let option = BitcoinOption {
+            option_type: OptionType::Call,
+            strike_price: 50_000_000,
+            expiry_block: 800_000,
+            buyer_pubkey: key(1),
+            seller_pubkey: key(2),
+            verifier_pubkey: key(3),
+            premium: 1_000_000,
+            collateral: 10_000_000,
+        };
+        let option_id = OptionId::derive(
+            &[2u8; 33],
+            &OptionTerms {
+                option_type: OptionType::Call,
+                strike_price: 50_000,
+                quantity: 100,
+                expiry: 800_000,
+            },
+            0,
+        );
+
+        // 합의 증명: 중앙값 52,000 cents
+        let (aggregator_key, aggregator_pubkey) = generate_keypair();
+        let (node_key, node_pubkey) = generate_keypair();
+        let submission = |exchange: &str, price_cents: u64| SignedSubmission {
+            node_id: "node-1".to_string(),
+            exchange: exchange.to_string(),
+            node_pubkey,
+            price_cents,
+            timestamp: 100,
+            nonce: 1,
+            degraded: false,
+            backfilled: false,
+            signature: sign_data(&price_submission_payload("node-1", exchange, price_cents, 100, 1, false), &node_key)
+                .unwrap(),
+        };
+        let consensus = ConsensusProof::build(
+            "BTC/USD",
+            100,
+            vec![submission("binance", 51_990), submission("kraken", 52_000), submission("coinbase", 52_010)],
+            &aggregator_key,
+        )
+        .unwrap();
+
+        let binding = bridge.program_binding(&option, vec![aggregator_pubkey]).unwrap();
+        let anchor = create_anchor_payload(&option_id, &binding);
+        let proof = bridge
+            .generate_settlement_proof_for(&option_id, &option, 52_000_000)
+            .await
+            .unwrap();
+        bridge.validate_proof(&proof, &anchor, &binding, &consensus).unwrap();
+
+        // 다른 현물가로 만든 증명
+        let off_consensus = bridge
+            .generate_settlement_proof_for(&option_id, &option, 53_000_000)
+            .await
+            .unwrap();
+        assert!(bridge.validate_proof(&off_consensus, &anchor, &binding, &consensus).is_err());
+
+        // 앵커에 없는 Aggregator 키
+        let outsider = bridge.program_binding(&option, vec![key(9)]).unwrap();
+        let outsider_anchor = create_anchor_payload(&option_id, &outsider);
+        assert!(bridge.validate_proof(&proof, &outsider_anchor, &outsider, &consensus).is_err());
+
+        // 다른 프로그램 해시로 바인딩한 앵커
+        let other_program = ProgramBinding::new([0; 32], binding.option_input, vec![aggregator_pubkey]);
+        assert!(bridge.validate_proof(&proof, &anchor, &other_program, &consensus).is_err());
+        let other_anchor = create_anchor_payload(&option_id, &other_program);
+        assert!(bridge.validate_proof(&proof, &other_anchor, &other_program, &consensus).is_err());
+    }
+}
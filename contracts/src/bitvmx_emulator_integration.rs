@@ -128,7 +128,8 @@ impl OptionSettlementExecutor {
         // BTC 환산 (1 BTC = $50,000)
         let btc_price = 50_000_00;
         let settlement_sats = if is_itm {
-            ((intrinsic_value as u64 * quantity as u64 * 100_000_000) / btc_price as u64) as u32
+            // quantity는 0.01 BTC 단위 (100 = 1 BTC)
+            ((intrinsic_value as u64 * quantity as u64 * 1_000_000) / btc_price as u64) as u32
         } else {
             0
         };
@@ -4,44 +4,83 @@ use anyhow::Result;
 use bitcoin::{
     Transaction, TxOut, TxIn, OutPoint, Witness,
     ScriptBuf, Address, Network,
-    secp256k1::{Secp256k1, SecretKey, PublicKey},
+    secp256k1::{Secp256k1, SecretKey},
     Amount, locktime::absolute::LockTime, Sequence,
 };
+use bitcoin::hashes::Hash;
+use bitcoin::sighash::{EcdsaSighashType, SighashCache};
 use crate::bitvmx_proof_generator::SettlementResult;
+use oracle_vm_common::crypto::KeyStore;
+use oracle_vm_common::NetworkProfile;
+use std::sync::Arc;
+
+/// 정산 트랜잭션 서명 키 이름
+pub const SETTLEMENT_KEY: &str = "settlement-operator";
+
+/// 정산 트랜잭션 수수료 (satoshis)
+const SETTLEMENT_FEE_SATS: u64 = 1000;
 
 /// Pre-signed 옵션 정산 트랜잭션 생성기
 pub struct PreSignedSettlementBuilder {
     secp: Secp256k1<bitcoin::secp256k1::All>,
     network: Network,
+    /// 운영자 서명 키 저장소 (없으면 더미 서명)
+    signer: Option<Arc<dyn KeyStore>>,
 }
 
 impl PreSignedSettlementBuilder {
     /// 새로운 빌더 생성
-    pub fn new(network: Network) -> Self {
+    pub fn new(profile: NetworkProfile) -> Self {
         Self {
             secp: Secp256k1::new(),
-            network,
+            network: profile.network,
+            signer: None,
         }
     }
+
+    /// 정산 트랜잭션을 키 저장소의 `settlement-operator` 키로 서명
+    pub fn with_signer(mut self, keys: Arc<dyn KeyStore>) -> Self {
+        self.signer = Some(keys);
+        self
+    }
     
-    /// 옵션 정산을 위한 pre-signed transaction 생성
+    /// 옵션 정산을 위한 pre-signed transaction 생성 (매수자 키 주소로 지급)
     pub fn create_settlement_transaction(
         &self,
         option_utxo: OutPoint,
         option_value: Amount,
         buyer_key: &SecretKey,
-        _operator_key: &SecretKey,
+        operator_key: &SecretKey,
         settlement_script: ScriptBuf,
         expiry_height: u32,
     ) -> Result<(Transaction, Vec<Vec<u8>>)> {
         // 매수자 주소 생성
-        let buyer_pubkey = PublicKey::from_secret_key(&self.secp, buyer_key);
         let compressed_pubkey = bitcoin::key::CompressedPublicKey::from_private_key(
             &self.secp,
             &bitcoin::key::PrivateKey::new(*buyer_key, self.network)
         ).unwrap();
         let buyer_address = Address::p2wpkh(&compressed_pubkey, self.network);
-        
+
+        self.create_settlement_transaction_to(
+            option_utxo,
+            option_value,
+            &buyer_address,
+            operator_key,
+            settlement_script,
+            expiry_height,
+        )
+    }
+
+    /// 등록된 수익자 주소로 지급하는 정산 트랜잭션 생성
+    pub fn create_settlement_transaction_to(
+        &self,
+        option_utxo: OutPoint,
+        option_value: Amount,
+        payout_address: &Address,
+        _operator_key: &SecretKey,
+        settlement_script: ScriptBuf,
+        expiry_height: u32,
+    ) -> Result<(Transaction, Vec<Vec<u8>>)> {
         // 정산 트랜잭션 생성
         let tx = Transaction {
             version: bitcoin::transaction::Version::TWO,
@@ -53,8 +92,8 @@ impl PreSignedSettlementBuilder {
                 witness: Witness::new(),
             }],
             output: vec![TxOut {
-                value: option_value - Amount::from_sat(1000), // 수수료 제외
-                script_pubkey: buyer_address.script_pubkey(),
+                value: option_value - Amount::from_sat(SETTLEMENT_FEE_SATS), // 수수료 제외
+                script_pubkey: payout_address.script_pubkey(),
             }],
         };
         
@@ -93,24 +132,39 @@ impl PreSignedSettlementBuilder {
         // Witness에 증명 추가
         witness_template[1] = proof_data;
         
-        // 더미 서명 추가 (실제로는 적절한 서명 필요)
-        witness_template[0] = vec![0; 64];
+        // 운영자 서명 (키 저장소가 없으면 더미 서명)
+        witness_template[0] = match &self.signer {
+            Some(keys) => Self::operator_signature(&tx, keys.as_ref(), &witness_template[2])?,
+            None => vec![0; 64],
+        };
         
         // 트랜잭션에 witness 설정
         tx.input[0].witness = Witness::from(witness_template);
         
         Ok(tx)
     }
+
+    /// 정산 스크립트 경로 sighash에 대한 운영자 서명 (옵션 UTXO 금액 = 지급액 + 수수료)
+    fn operator_signature(tx: &Transaction, keys: &dyn KeyStore, settlement_script: &[u8]) -> Result<Vec<u8>> {
+        let script = ScriptBuf::from_bytes(settlement_script.to_vec());
+        let option_value = tx.output[0].value + Amount::from_sat(SETTLEMENT_FEE_SATS);
+        let sighash = SighashCache::new(tx).p2wsh_signature_hash(0, &script, option_value, EcdsaSighashType::All)?;
+        let signature = bitcoin::ecdsa::Signature {
+            signature: keys.sign_digest(SETTLEMENT_KEY, &sighash.to_byte_array())?,
+            sighash_type: EcdsaSighashType::All,
+        };
+        Ok(signature.to_vec())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bitcoin::hashes::Hash;
+    use oracle_vm_common::crypto::MemoryKeyStore;
     
     #[test]
     fn test_presigned_settlement() {
-        let builder = PreSignedSettlementBuilder::new(Network::Testnet);
+        let builder = PreSignedSettlementBuilder::new(NetworkProfile::TESTNET);
         
         // 테스트 키
         let buyer_key = SecretKey::from_slice(&[1u8; 32]).unwrap();
@@ -141,4 +195,33 @@ mod tests {
         assert_eq!(tx.output.len(), 1);
         assert_eq!(witness.len(), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_settlement_signed_with_key_store() {
+        let operator_key = SecretKey::from_slice(&[2u8; 32]).unwrap();
+        let keys = Arc::new(MemoryKeyStore::with_key(SETTLEMENT_KEY, operator_key));
+        let builder = PreSignedSettlementBuilder::new(NetworkProfile::TESTNET).with_signer(keys.clone());
+        let settlement_script = ScriptBuf::from(vec![bitcoin::opcodes::all::OP_PUSHNUM_1.to_u8()]);
+        let (tx, witness) = builder
+            .create_settlement_transaction(
+                OutPoint { txid: bitcoin::Txid::all_zeros(), vout: 0 },
+                Amount::from_sat(100_000),
+                &SecretKey::from_slice(&[1u8; 32]).unwrap(),
+                &operator_key,
+                settlement_script.clone(),
+                800_000,
+            )
+            .unwrap();
+        let result = SettlementResult { is_itm: true, intrinsic_value: 500_000, settlement_amount: 99_000 };
+        let tx = builder.complete_with_proof(tx, witness, Vec::new(), &result).unwrap();
+
+        let witness: Vec<&[u8]> = tx.input[0].witness.iter().collect();
+        let signature = bitcoin::ecdsa::Signature::from_slice(witness[0]).unwrap();
+        let sighash = SighashCache::new(&tx)
+            .p2wsh_signature_hash(0, &settlement_script, Amount::from_sat(100_000), EcdsaSighashType::All)
+            .unwrap();
+        let message = bitcoin::secp256k1::Message::from_digest(sighash.to_byte_array());
+        let operator_pubkey = keys.public_key(SETTLEMENT_KEY).unwrap();
+        assert!(Secp256k1::new().verify_ecdsa(&message, &signature.signature, &operator_pubkey).is_ok());
+    }
+}
@@ -0,0 +1,235 @@
+//! 블록 높이 ↔ 시각 환산
+//!
+//! 만기는 블록 높이로 정하지만 담보 사용료, 만기 전 한도 감축 같은 계산에는 초 단위
+//! 잔존 기간이 필요합니다. `높이 × 600초`로 어림하면 난이도 조정 주기마다 달라지는
+//! 실제 블록 간격과 어긋나 만기 시각이 몇 시간씩 틀어지므로, 노드에서 받은 실제
+//! 블록 헤더 시각을 기준점으로 삼습니다. 알려진 헤더 사이는 보간하고, 팁 이후는
+//! 최근 블록들의 평균 간격으로 외삽합니다.
+
+use crate::bootstrap::BitcoindRpc;
+use crate::simple_contract::AVG_BLOCK_SECS;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// 평균 블록 간격을 잴 최근 블록 수 (약 하루)
+pub const HEADER_WINDOW: u32 = 144;
+/// 동기화 때마다 다시 받는 최근 블록 수 (reorg로 바뀐 헤더 반영)
+const REORG_DEPTH: u32 = 6;
+/// 보관하는 최대 헤더 수 (난이도 조정 주기 하나)
+const MAX_HEADERS: usize = 2_016;
+
+/// 블록 헤더 조회 인터페이스
+#[async_trait]
+pub trait HeaderSource: Send + Sync {
+    async fn tip_height(&self) -> Result<u32>;
+    /// 블록 헤더의 `time` (Unix seconds)
+    async fn header_time(&self, height: u32) -> Result<u64>;
+}
+
+/// bitcoind `getblockcount` / `getblockhash` / `getblockheader`
+#[async_trait]
+impl<R: BitcoindRpc> HeaderSource for R {
+    async fn tip_height(&self) -> Result<u32> {
+        let count = self.call(None, "getblockcount", vec![]).await?;
+        count
+            .as_u64()
+            .map(|height| height as u32)
+            .ok_or_else(|| anyhow!("getblockcount returned {}", count))
+    }
+
+    async fn header_time(&self, height: u32) -> Result<u64> {
+        let hash = self.call(None, "getblockhash", vec![json!(height)]).await?;
+        let header = self.call(None, "getblockheader", vec![hash]).await?;
+        header["time"]
+            .as_u64()
+            .ok_or_else(|| anyhow!("getblockheader {} has no time", height))
+    }
+}
+
+/// 실제 블록 헤더 시각 기반 높이 ↔ 시각 환산
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockClock {
+    /// 높이 → 헤더 시각 (Unix seconds)
+    headers: BTreeMap<u32, u64>,
+}
+
+impl BlockClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 헤더 시각 기록 (오래된 헤더부터 `MAX_HEADERS`개만 유지)
+    pub fn observe(&mut self, height: u32, time: u64) {
+        self.headers.insert(height, time);
+        while self.headers.len() > MAX_HEADERS {
+            self.headers.pop_first();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    /// 가장 높은 헤더 (높이, 시각)
+    pub fn tip(&self) -> Option<(u32, u64)> {
+        self.headers.last_key_value().map(|(&height, &time)| (height, time))
+    }
+
+    /// 팁까지 최근 `HEADER_WINDOW` 블록의 평균 간격 (초, 헤더가 부족하면 `AVG_BLOCK_SECS`)
+    pub fn block_interval(&self) -> f64 {
+        let Some((tip, tip_time)) = self.tip() else {
+            return AVG_BLOCK_SECS as f64;
+        };
+        match self.headers.range(tip.saturating_sub(HEADER_WINDOW)..tip).next() {
+            Some((&base, &base_time)) if tip_time > base_time => {
+                (tip_time - base_time) as f64 / (tip - base) as f64
+            }
+            _ => AVG_BLOCK_SECS as f64,
+        }
+    }
+
+    /// `height` 블록의 (예상) 시각 (헤더가 하나도 없으면 None)
+    pub fn timestamp_at(&self, height: u32) -> Option<u64> {
+        if let Some(&time) = self.headers.get(&height) {
+            return Some(time);
+        }
+        let before = self.headers.range(..height).next_back();
+        let after = self.headers.range(height..).next();
+        match (before, after) {
+            (Some((&h0, &t0)), Some((&h1, &t1))) => {
+                Some(t0 + t1.saturating_sub(t0) * (height - h0) as u64 / (h1 - h0) as u64)
+            }
+            (Some((&h0, &t0)), None) => {
+                Some(t0 + ((height - h0) as f64 * self.block_interval()).round() as u64)
+            }
+            (None, Some((&h1, &t1))) => {
+                Some(t1.saturating_sub(((h1 - height) as f64 * self.block_interval()).round() as u64))
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// `timestamp` 이후 처음 나올 (것으로 예상되는) 블록 높이
+    pub fn height_at(&self, timestamp: u64) -> Option<u32> {
+        let (tip, tip_time) = self.tip()?;
+        if timestamp > tip_time {
+            let blocks = ((timestamp - tip_time) as f64 / self.block_interval()).ceil() as u32;
+            return Some(tip + blocks);
+        }
+        let mut previous: Option<(u32, u64)> = None;
+        for (&height, &time) in &self.headers {
+            if time >= timestamp {
+                return Some(match previous {
+                    Some((h0, t0)) if time > t0 => {
+                        let span = (height - h0) as u64;
+                        h0 + ((timestamp - t0) * span).div_ceil(time - t0) as u32
+                    }
+                    Some(_) => height,
+                    None => {
+                        let blocks = ((time - timestamp) as f64 / self.block_interval()).floor() as u32;
+                        height.saturating_sub(blocks)
+                    }
+                });
+            }
+            previous = Some((height, time));
+        }
+        Some(tip)
+    }
+
+    /// `now`부터 `height` 블록까지 남은 예상 시간 (초)
+    pub fn secs_until(&self, height: u32, now: u64) -> Option<u64> {
+        self.timestamp_at(height).map(|time| time.saturating_sub(now))
+    }
+
+    /// 노드의 최근 `HEADER_WINDOW` 블록 헤더 동기화 (없는 헤더 + 최근 `REORG_DEPTH`개), 팁 높이 반환
+    pub async fn sync(&mut self, source: &(impl HeaderSource + ?Sized)) -> Result<u32> {
+        let tip = source.tip_height().await?;
+        for height in tip.saturating_sub(HEADER_WINDOW)..=tip {
+            if height + REORG_DEPTH > tip || !self.headers.contains_key(&height) {
+                let time = source.header_time(height).await?;
+                self.observe(height, time);
+            }
+        }
+        // reorg로 팁이 낮아졌으면 그 위의 헤더는 버림
+        self.headers.retain(|&height, _| height <= tip);
+        Ok(tip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// 585초 간격 체인 (높이 840,000 = 1,713,571,767)
+    const TIP: u32 = 840_000;
+    const TIP_TIME: u64 = 1_713_571_767;
+    const INTERVAL: u64 = 585;
+
+    struct MockNode;
+
+    #[async_trait]
+    impl BitcoindRpc for MockNode {
+        async fn call(&self, _wallet: Option<&str>, method: &str, params: Vec<Value>) -> Result<Value> {
+            Ok(match method {
+                "getblockcount" => json!(TIP),
+                "getblockhash" => json!(format!("hash-{}", params[0])),
+                "getblockheader" => {
+                    let height: u64 = params[0].as_str().unwrap()["hash-".len()..].parse().unwrap();
+                    json!({ "height": height, "time": TIP_TIME - (TIP as u64 - height) * INTERVAL })
+                }
+                other => return Err(anyhow!("unexpected {}", other)),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_converts_with_actual_header_interval() {
+        let mut clock = BlockClock::new();
+        assert_eq!(clock.sync(&MockNode).await.unwrap(), TIP);
+        assert_eq!(clock.block_interval(), INTERVAL as f64);
+
+        // 알려진 헤더는 그대로, 팁 이후는 최근 평균 간격으로 외삽
+        assert_eq!(clock.timestamp_at(TIP - 10), Some(TIP_TIME - 10 * INTERVAL));
+        assert_eq!(clock.timestamp_at(TIP + 144), Some(TIP_TIME + 144 * INTERVAL));
+        assert_eq!(clock.height_at(TIP_TIME + 144 * INTERVAL), Some(TIP + 144));
+        assert_eq!(clock.height_at(TIP_TIME + 144 * INTERVAL - 1), Some(TIP + 144));
+        assert_eq!(clock.height_at(TIP_TIME - 10 * INTERVAL), Some(TIP - 10));
+    }
+
+    #[test]
+    fn test_expiry_is_not_height_times_600() {
+        // 회귀: `now + expiry_height * 600`은 30일 만기를 16년 뒤로 보냄
+        let mut clock = BlockClock::new();
+        for height in TIP - HEADER_WINDOW..=TIP {
+            clock.observe(height, TIP_TIME - (TIP - height) as u64 * INTERVAL);
+        }
+        let expiry_height = TIP + 4_320;
+        let expiry = clock.timestamp_at(expiry_height).unwrap();
+        assert_eq!(expiry, TIP_TIME + 4_320 * INTERVAL);
+        assert_eq!(clock.secs_until(expiry_height, TIP_TIME), Some(4_320 * INTERVAL));
+        assert!(TIP_TIME + expiry_height as u64 * AVG_BLOCK_SECS - expiry > 15 * 365 * 86_400);
+    }
+
+    #[test]
+    fn test_interpolates_between_headers_and_falls_back_without_window() {
+        let mut clock = BlockClock::new();
+        assert_eq!(clock.timestamp_at(100), None);
+        assert_eq!(clock.height_at(1_000), None);
+
+        // 헤더 하나: 평균 간격 대신 600초
+        clock.observe(100, 60_000);
+        assert_eq!(clock.block_interval(), AVG_BLOCK_SECS as f64);
+        assert_eq!(clock.timestamp_at(110), Some(66_000));
+        assert_eq!(clock.timestamp_at(90), Some(54_000));
+
+        // 떨어진 두 헤더 사이는 선형 보간
+        clock.observe(110, 64_000);
+        assert_eq!(clock.timestamp_at(105), Some(62_000));
+        assert_eq!(clock.height_at(62_000), Some(105));
+        assert_eq!(clock.height_at(62_001), Some(106));
+    }
+}
@@ -0,0 +1,510 @@
+//! 신규 환경 부트스트랩 (`btcfi-admin init`)
+//!
+//! 풀 운영자 키(M-of-N)와 복구 키를 가져오거나 새로 만들고, `EmergencyVault`와
+//! 같은 스크립트로 풀 P2WSH 주소를 계산합니다. bitcoind에는 풀 주소를 감시하는
+//! 지갑과 faucet 지갑을 만들고, regtest에서는 faucet에 블록 보상을 채굴해 풀
+//! 주소로 초기 자금을 보냅니다. 결과는 통합 설정 파일(TOML)과 부트스트랩
+//! 리포트로 남기며, 새로 만든 비밀키는 별도 파일(0600)에만 기록합니다.
+
+use crate::emergency::{EmergencyConfig, EmergencyVault};
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use bitcoin::secp256k1::{rand::thread_rng, Secp256k1, SecretKey};
+use bitcoin::{Amount, Network, PublicKey};
+use oracle_vm_common::{AnchorError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// 풀 주소 감시 지갑 (비밀키 없음)
+pub const POOL_WALLET: &str = "btcfi-pool";
+/// regtest 초기 자금용 지갑
+pub const FAUCET_WALLET: &str = "btcfi-faucet";
+/// 코인베이스 성숙에 필요한 블록 수 + 1
+pub const COINBASE_MATURITY_BLOCKS: u64 = 101;
+
+/// bitcoind JSON-RPC 호출 인터페이스 (`wallet`이 있으면 `/wallet/<name>` 경로)
+#[async_trait]
+pub trait BitcoindRpc: Send + Sync {
+    async fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> Result<Value>;
+}
+
+/// HTTP bitcoind RPC (사용자/비밀번호 또는 `.cookie` 인증)
+pub struct HttpBitcoindRpc {
+    http: reqwest::Client,
+    url: String,
+    user: String,
+    password: String,
+}
+
+impl HttpBitcoindRpc {
+    pub fn new(url: &str, user: &str, password: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+            user: user.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    /// bitcoind 데이터 디렉터리의 `.cookie` 파일로 인증
+    pub fn from_cookie(url: &str, cookie_path: &Path) -> Result<Self> {
+        let cookie = std::fs::read_to_string(cookie_path)
+            .with_context(|| format!("Failed to read {}", cookie_path.display()))?;
+        let (user, password) = cookie
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Malformed cookie file {}", cookie_path.display()))?;
+        Ok(Self::new(url, user, password))
+    }
+}
+
+#[async_trait]
+impl BitcoindRpc for HttpBitcoindRpc {
+    async fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> Result<Value> {
+        let url = match wallet {
+            Some(wallet) => format!("{}/wallet/{}", self.url, wallet),
+            None => self.url.clone(),
+        };
+        let response: Value = self
+            .http
+            .post(&url)
+            .basic_auth(&self.user, Some(&self.password))
+            .json(&json!({"jsonrpc": "1.0", "id": "btcfi-init", "method": method, "params": params}))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach bitcoind at {}", self.url))?
+            .json()
+            .await
+            .with_context(|| format!("{} returned a non-JSON response", method))?;
+        match response.get("error") {
+            Some(error) if !error.is_null() => bail!("{} failed: {}", method, error),
+            _ => Ok(response.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+}
+
+/// 동기 인터페이스(앵커링, 체인 조회)에서 쓰는 RPC 호출
+///
+/// 멀티스레드 런타임 안에서 블로킹으로 기다리므로 current-thread 런타임에서는
+/// 쓸 수 없습니다.
+pub fn call_blocking(
+    rpc: &dyn BitcoindRpc,
+    wallet: Option<&str>,
+    method: &str,
+    params: Vec<Value>,
+) -> std::result::Result<Value, AnchorError> {
+    tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(rpc.call(wallet, method, params)))
+        .map_err(|e| AnchorError::Rpc(format!("{:#}", e)))
+}
+
+/// 이번 부트스트랩에서 새로 만든 키 (운영자에게 전달 후 파일 삭제)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedKey {
+    /// "manager-<n>" 또는 "recovery"
+    pub role: String,
+    pub public_key: String,
+    /// WIF
+    pub secret_key: String,
+}
+
+/// 풀 멀티시그 키 집합
+#[derive(Debug, Clone)]
+pub struct PoolKeys {
+    pub managers: Vec<PublicKey>,
+    pub recovery: PublicKey,
+    pub generated: Vec<GeneratedKey>,
+}
+
+impl PoolKeys {
+    /// 가져온 공개키를 쓰고 모자란 운영자 키와 (없으면) 복구 키를 생성
+    pub fn provision(
+        network: Network,
+        imported: Vec<PublicKey>,
+        managers: usize,
+        recovery: Option<PublicKey>,
+    ) -> Result<Self> {
+        if imported.len() > managers {
+            bail!("{} manager keys imported but only {} managers configured", imported.len(), managers);
+        }
+        let secp = Secp256k1::new();
+        let mut generated = Vec::new();
+        let mut generate = |role: String| {
+            let secret = bitcoin::PrivateKey::new(SecretKey::new(&mut thread_rng()), network);
+            let public = secret.public_key(&secp);
+            generated.push(GeneratedKey {
+                role,
+                public_key: public.to_string(),
+                secret_key: secret.to_wif(),
+            });
+            public
+        };
+
+        let mut keys = imported;
+        for index in keys.len()..managers {
+            keys.push(generate(format!("manager-{}", index + 1)));
+        }
+        let recovery = recovery.unwrap_or_else(|| generate("recovery".to_string()));
+        Ok(Self {
+            managers: keys,
+            recovery,
+            generated,
+        })
+    }
+}
+
+/// 부트스트랩 설정
+#[derive(Debug, Clone)]
+pub struct BootstrapOptions {
+    pub profile: NetworkProfile,
+    pub rpc_url: String,
+    /// 풀 출력 사용에 필요한 운영자 서명 수
+    pub threshold: usize,
+    pub emergency: EmergencyConfig,
+    /// regtest에서 풀 주소로 보낼 초기 자금
+    pub fund_amount: Amount,
+    pub contracts_url: String,
+    pub aggregator_url: String,
+    pub calculation_url: String,
+}
+
+/// 통합 설정 파일의 bitcoind 구간
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitcoindSection {
+    pub rpc_url: String,
+    pub pool_wallet: String,
+    pub faucet_wallet: Option<String>,
+}
+
+/// 통합 설정 파일의 풀 구간
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSection {
+    pub address: String,
+    /// witness 스크립트 (hex)
+    pub script: String,
+    pub threshold: usize,
+    pub managers: Vec<String>,
+    pub recovery_key: String,
+    pub emergency: EmergencyConfig,
+}
+
+/// 서비스 주소
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServicesSection {
+    pub contracts_url: String,
+    pub aggregator_url: String,
+    pub calculation_url: String,
+}
+
+/// 통합 설정 (`config/btcfi.toml`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnifiedConfig {
+    pub network: String,
+    pub bitcoind: BitcoindSection,
+    pub pool: PoolSection,
+    pub services: ServicesSection,
+}
+
+impl UnifiedConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let body = std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&body).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// 부트스트랩 결과 리포트
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapReport {
+    pub network: String,
+    pub pool_address: String,
+    pub threshold: usize,
+    pub managers: Vec<String>,
+    pub recovery_key: String,
+    pub wallets: Vec<String>,
+    pub faucet_address: Option<String>,
+    pub funding_txid: Option<String>,
+    pub funded_sats: u64,
+    pub block_height: u64,
+    pub config_path: Option<String>,
+    /// 새로 만든 비밀키 파일 (가져온 키만 썼으면 None)
+    pub generated_keys_path: Option<String>,
+    /// 수행한 단계
+    pub steps: Vec<String>,
+}
+
+/// 지갑이 없으면 만들고, 있지만 로드되지 않았으면 로드
+async fn ensure_wallet(rpc: &dyn BitcoindRpc, name: &str, watch_only: bool) -> Result<&'static str> {
+    let loaded = rpc.call(None, "listwallets", vec![]).await?;
+    if loaded
+        .as_array()
+        .is_some_and(|wallets| wallets.iter().any(|wallet| wallet.as_str() == Some(name)))
+    {
+        return Ok("already loaded");
+    }
+    if rpc.call(None, "loadwallet", vec![json!(name)]).await.is_ok() {
+        return Ok("loaded");
+    }
+    // createwallet name disable_private_keys blank
+    rpc.call(None, "createwallet", vec![json!(name), json!(watch_only), json!(watch_only)])
+        .await?;
+    Ok("created")
+}
+
+fn string_result(value: Value, method: &str) -> Result<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("{} returned {}", method, value))
+}
+
+/// 키, 지갑, 풀 주소, (regtest) 초기 자금을 준비하고 통합 설정을 만듦
+pub async fn bootstrap(
+    rpc: &dyn BitcoindRpc,
+    options: &BootstrapOptions,
+    keys: &PoolKeys,
+) -> Result<(UnifiedConfig, BootstrapReport)> {
+    let network = options.profile.network;
+    let vault = EmergencyVault::new(
+        options.profile,
+        options.emergency,
+        keys.managers.clone(),
+        options.threshold,
+        keys.recovery,
+    )?;
+    let pool_address = vault.pool_address();
+    let mut steps = vec![format!(
+        "pool address {} ({}-of-{}, recovery after {} blocks)",
+        pool_address,
+        options.threshold,
+        keys.managers.len(),
+        options.emergency.timeout_blocks
+    )];
+
+    let chain = rpc.call(None, "getblockchaininfo", vec![]).await?;
+    let chain_name = chain.get("chain").and_then(Value::as_str).unwrap_or_default();
+    let expected = match network {
+        Network::Bitcoin => "main",
+        Network::Testnet => "test",
+        Network::Signet => "signet",
+        Network::Regtest => "regtest",
+        _ => "",
+    };
+    if chain_name != expected {
+        bail!("bitcoind at {} is on {}, expected {}", options.rpc_url, chain_name, expected);
+    }
+
+    // 풀 주소 감시 지갑
+    let status = ensure_wallet(rpc, POOL_WALLET, true).await?;
+    steps.push(format!("wallet {} {}", POOL_WALLET, status));
+    let descriptor = rpc
+        .call(None, "getdescriptorinfo", vec![json!(format!("addr({})", pool_address))])
+        .await?;
+    let descriptor = descriptor
+        .get("descriptor")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("getdescriptorinfo returned no descriptor"))?
+        .to_string();
+    rpc.call(
+        Some(POOL_WALLET),
+        "importdescriptors",
+        vec![json!([{"desc": descriptor, "timestamp": "now", "label": "btcfi-pool"}])],
+    )
+    .await?;
+    steps.push(format!("watching {} in {}", descriptor, POOL_WALLET));
+
+    let mut wallets = vec![POOL_WALLET.to_string()];
+    let mut faucet_address = None;
+    let mut funding_txid = None;
+    let mut funded_sats = 0;
+    if network == Network::Regtest {
+        let status = ensure_wallet(rpc, FAUCET_WALLET, false).await?;
+        steps.push(format!("wallet {} {}", FAUCET_WALLET, status));
+        wallets.push(FAUCET_WALLET.to_string());
+
+        let address = string_result(
+            rpc.call(Some(FAUCET_WALLET), "getnewaddress", vec![json!("faucet")]).await?,
+            "getnewaddress",
+        )?;
+        rpc.call(None, "generatetoaddress", vec![json!(COINBASE_MATURITY_BLOCKS), json!(address)])
+            .await?;
+        steps.push(format!("mined {} blocks to faucet {}", COINBASE_MATURITY_BLOCKS, address));
+
+        if options.fund_amount > Amount::ZERO {
+            let txid = string_result(
+                rpc.call(
+                    Some(FAUCET_WALLET),
+                    "sendtoaddress",
+                    vec![json!(pool_address.to_string()), json!(options.fund_amount.to_btc())],
+                )
+                .await?,
+                "sendtoaddress",
+            )?;
+            rpc.call(None, "generatetoaddress", vec![json!(1), json!(address)]).await?;
+            steps.push(format!("funded pool with {} in {}", options.fund_amount, txid));
+            funding_txid = Some(txid);
+            funded_sats = options.fund_amount.to_sat();
+        }
+        faucet_address = Some(address);
+    } else {
+        steps.push(format!("{} has no faucet, fund {} manually", options.profile, pool_address));
+    }
+    let block_height = rpc.call(None, "getblockcount", vec![]).await?.as_u64().unwrap_or(0);
+
+    let managers: Vec<String> = keys.managers.iter().map(|key| key.to_string()).collect();
+    let config = UnifiedConfig {
+        network: options.profile.to_string(),
+        bitcoind: BitcoindSection {
+            rpc_url: options.rpc_url.clone(),
+            pool_wallet: POOL_WALLET.to_string(),
+            faucet_wallet: faucet_address.as_ref().map(|_| FAUCET_WALLET.to_string()),
+        },
+        pool: PoolSection {
+            address: pool_address.to_string(),
+            script: hex::encode(vault.pool_script().as_bytes()),
+            threshold: options.threshold,
+            managers: managers.clone(),
+            recovery_key: keys.recovery.to_string(),
+            emergency: options.emergency,
+        },
+        services: ServicesSection {
+            contracts_url: options.contracts_url.clone(),
+            aggregator_url: options.aggregator_url.clone(),
+            calculation_url: options.calculation_url.clone(),
+        },
+    };
+    let report = BootstrapReport {
+        network: options.profile.to_string(),
+        pool_address: pool_address.to_string(),
+        threshold: options.threshold,
+        managers,
+        recovery_key: keys.recovery.to_string(),
+        wallets,
+        faucet_address,
+        funding_txid,
+        funded_sats,
+        block_height,
+        config_path: None,
+        generated_keys_path: None,
+        steps,
+    };
+    Ok((config, report))
+}
+
+/// 새로 만든 비밀키를 소유자만 읽을 수 있는 파일로 저장 (이미 있으면 덮어쓰지 않음)
+pub fn write_generated_keys(path: &Path, keys: &[GeneratedKey]) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Refusing to overwrite {}", path.display()))?;
+    file.write_all(serde_json::to_string_pretty(keys)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 호출을 기록하는 regtest bitcoind
+    struct MockBitcoind {
+        calls: Mutex<Vec<(Option<String>, String)>>,
+    }
+
+    #[async_trait]
+    impl BitcoindRpc for MockBitcoind {
+        async fn call(&self, wallet: Option<&str>, method: &str, params: Vec<Value>) -> Result<Value> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((wallet.map(str::to_string), method.to_string()));
+            Ok(match method {
+                "getblockchaininfo" => json!({"chain": "regtest"}),
+                "listwallets" => json!([]),
+                "loadwallet" => bail!("Wallet file not found"),
+                "getdescriptorinfo" => json!({"descriptor": format!("{}#checksum", params[0].as_str().unwrap())}),
+                "getnewaddress" => json!("bcrt1qfaucet"),
+                "sendtoaddress" => json!("ab".repeat(32)),
+                "getblockcount" => json!(102),
+                _ => Value::Null,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_regtest_bootstrap_provisions_wallets_and_funds_pool() {
+        let secp = Secp256k1::new();
+        let imported = PublicKey::new(SecretKey::from_slice(&[1; 32]).unwrap().public_key(&secp));
+        let keys = PoolKeys::provision(Network::Regtest, vec![imported], 3, None).unwrap();
+        assert_eq!(keys.managers[0], imported);
+        assert_eq!(keys.generated.len(), 3); // manager-2, manager-3, recovery
+        assert!(PoolKeys::provision(Network::Regtest, vec![imported; 2], 1, None).is_err());
+
+        let rpc = MockBitcoind {
+            calls: Mutex::new(Vec::new()),
+        };
+        let options = BootstrapOptions {
+            profile: NetworkProfile::REGTEST,
+            rpc_url: "http://127.0.0.1:18443".to_string(),
+            threshold: 2,
+            emergency: EmergencyConfig::default(),
+            fund_amount: Amount::from_btc(10.0).unwrap(),
+            contracts_url: "http://127.0.0.1:3100".to_string(),
+            aggregator_url: "http://127.0.0.1:50051".to_string(),
+            calculation_url: "http://127.0.0.1:3000".to_string(),
+        };
+        let (config, report) = bootstrap(&rpc, &options, &keys).await.unwrap();
+
+        let vault = EmergencyVault::new(
+            NetworkProfile::REGTEST,
+            EmergencyConfig::default(),
+            keys.managers.clone(),
+            2,
+            keys.recovery,
+        )
+        .unwrap();
+        assert_eq!(report.pool_address, vault.pool_address().to_string());
+        assert_eq!(config.pool.address, report.pool_address);
+        assert_eq!(report.wallets, vec![POOL_WALLET, FAUCET_WALLET]);
+        assert_eq!(report.funded_sats, 1_000_000_000);
+        assert!(report.funding_txid.is_some());
+
+        let calls = rpc.calls.lock().unwrap().clone();
+        let count = |method: &str| calls.iter().filter(|(_, m)| m == method).count();
+        assert_eq!(count("createwallet"), 2);
+        assert_eq!(count("generatetoaddress"), 2);
+        assert!(calls.contains(&(Some(POOL_WALLET.to_string()), "importdescriptors".to_string())));
+        assert!(calls.contains(&(Some(FAUCET_WALLET.to_string()), "sendtoaddress".to_string())));
+
+        // 통합 설정은 TOML로 왕복
+        let path = std::env::temp_dir().join(format!("btcfi-init-{}.toml", std::process::id()));
+        config.save(&path).unwrap();
+        assert_eq!(UnifiedConfig::load(&path).unwrap(), config);
+        std::fs::remove_file(&path).unwrap();
+
+        // 다른 네트워크의 bitcoind는 거부
+        let testnet = BootstrapOptions {
+            profile: NetworkProfile::TESTNET,
+            ..options
+        };
+        assert!(bootstrap(&rpc, &testnet, &keys).await.is_err());
+    }
+}
@@ -0,0 +1,218 @@
+//! 만기 전 되사기 (포지션 조기 종료)
+//!
+//! 보유자는 Calculation `/api/rfq/buy-back`에서 서명된 되사기 호가(현재 이론가 -
+//! 풀 스프레드)를 받아 `POST /options/{id}/buy-back`으로 제출합니다. 관리자는
+//! 호가 조건을 저장된 옵션과 대조해 옵션을 Cancelled로 닫고, 호가 금액을 지급한
+//! 뒤 나머지 담보를 풀로 돌립니다. 조기 종료는 CNL 앵커로 온체인에 남깁니다.
+//!
+//! 롤(`POST /options/{id}/roll`)은 되사기 호가와 더 늦은 만기의 확정 호가를 함께
+//! 제출해 기존 옵션을 닫고 새 옵션을 엽니다. 프리미엄과 담보는 차액만 움직이고,
+//! 앵커는 두 옵션을 잇는 RLL 한 건만 남깁니다.
+
+use oracle_vm_common::crypto::sha256;
+use serde::{Deserialize, Serialize};
+
+/// 되사기(조기 종료) 앵커 태그
+pub const CANCEL_ANCHOR_TAG: &[u8; 3] = b"CNL";
+
+/// CNL 앵커 페이로드: "CNL" || SHA256(옵션 ID) || 마지막 감사 해시 (STL과 같은 67 bytes)
+pub fn cancel_anchor_payload(option_id: &str, audit_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(67);
+    payload.extend_from_slice(CANCEL_ANCHOR_TAG);
+    payload.extend_from_slice(&sha256(option_id.as_bytes()));
+    payload.extend_from_slice(audit_hash);
+    payload
+}
+
+/// 롤 앵커 태그
+pub const ROLL_ANCHOR_TAG: &[u8; 3] = b"RLL";
+
+/// RLL 앵커 페이로드: "RLL" || SHA256(이전 옵션 ID) || 새 옵션의 마지막 감사 해시 (67 bytes)
+///
+/// 새 옵션의 감사 기록은 이전 옵션 ID를 담은 `Rolled`로 시작하므로 두 옵션이
+/// 하나의 앵커로 연결됩니다.
+pub fn roll_anchor_payload(from_option_id: &str, audit_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(67);
+    payload.extend_from_slice(ROLL_ANCHOR_TAG);
+    payload.extend_from_slice(&sha256(from_option_id.as_bytes()));
+    payload.extend_from_slice(audit_hash);
+    payload
+}
+
+/// 롤 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollOutcome {
+    pub from_option_id: String,
+    pub option_id: String,
+    /// 이전 옵션 되사기 금액 (satoshis)
+    pub buy_back_amount: u64,
+    /// 새 옵션 프리미엄 (satoshis)
+    pub premium: u64,
+    /// 보유자 순지급액: 프리미엄 - 되사기 금액 (음수면 보유자가 받음)
+    pub net_premium: i64,
+    /// 풀 담보 변화: 새 담보 - 이전 담보
+    pub collateral_delta: i64,
+}
+
+/// `/options/{id}/buy-back`, `/options/{id}/roll` API
+pub mod api {
+    use crate::account_keys::{buy_back_payload, roll_payload, AccountSignature};
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use crate::eligibility::{screen_pool, EligibilityRequest};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::post,
+        Json, Router,
+    };
+    use oracle_vm_common::{BuyBackQuote, OptionQuote};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct BuyBackSubmission {
+        #[schema(value_type = Object)]
+        pub quote: BuyBackQuote,
+        pub user_id: String,
+        /// 보유자 키로 `account/buy-back/v1` (option_id, quote_id, user_id, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct RollSubmission {
+        #[schema(value_type = Object)]
+        pub buy_back: BuyBackQuote,
+        #[schema(value_type = Object)]
+        pub quote: OptionQuote,
+        pub new_option_id: String,
+        /// 새 옵션 만기 블록 높이 (생략하면 호가 만기로 계산, 보내면 계산한 높이와 같아야 함)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub expiry_height: Option<u32>,
+        pub user_id: String,
+        /// 보유자 키로 `account/roll/v1` (option_id, 두 호가 ID, new_option_id, user_id, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/options/{id}/buy-back",
+        tag = "options",
+        params(("id" = String, Path, description = "옵션 ID")),
+        request_body = BuyBackSubmission,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn buy_back(
+        Path(option_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<BuyBackSubmission>,
+    ) -> Response {
+        if request.quote.option_id != option_id {
+            return bad_request("quote is for a different option");
+        }
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = buy_back_payload(&option_id, &request.quote.quote_id, &request.user_id, request.auth.nonce);
+        if let Err(e) = manager.authorize_account(&request.user_id, &payload, &request.auth) {
+            return error_response(e);
+        }
+        match manager.buy_back_option(&request.quote, &request.user_id) {
+            Ok(amount) => Json(json!({
+                "option_id": option_id,
+                "amount": amount,
+                "cancel_anchor": manager.cancel_anchor_payload(&option_id).map(hex::encode),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/options/{id}/roll",
+        tag = "options",
+        params(("id" = String, Path, description = "옵션 ID")),
+        request_body = RollSubmission,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn roll(
+        Path(option_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<RollSubmission>,
+    ) -> Response {
+        if request.buy_back.option_id != option_id {
+            return bad_request("buy-back quote is for a different option");
+        }
+        // 롤은 새 옵션을 여는 구매이므로 생성과 같이 자격 확인
+        let eligibility = EligibilityRequest {
+            user_id: request.user_id.clone(),
+            address: None,
+            option_id: request.new_option_id.clone(),
+        };
+        let decision_hash = match screen_pool(&manager, &eligibility).await {
+            Ok(hash) => hash,
+            Err(e) => return error_response(e),
+        };
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = roll_payload(
+            &option_id,
+            &request.buy_back.quote_id,
+            &request.quote.quote_id,
+            &request.new_option_id,
+            &request.user_id,
+            request.auth.nonce,
+        );
+        if let Err(e) = manager.authorize_account(&request.user_id, &payload, &request.auth) {
+            return error_response(e);
+        }
+        let expiry_height = match request.expiry_height {
+            Some(height) => height,
+            None => match manager.quote_expiry_height(&request.quote) {
+                Ok(height) => height,
+                Err(e) => return error_response(e),
+            },
+        };
+        match manager.roll_option(
+            &request.buy_back,
+            &request.quote,
+            request.new_option_id,
+            expiry_height,
+            &request.user_id,
+        ) {
+            Ok(outcome) => {
+                if let Some(hash) = decision_hash {
+                    manager.record_eligibility_hash(&outcome.option_id, hash);
+                }
+                let roll_anchor = manager.roll_anchor_payload(&outcome.option_id).map(hex::encode);
+                Json(json!({ "roll": outcome, "roll_anchor": roll_anchor })).into_response()
+            }
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/options/{id}/buy-back`, `/options/{id}/roll` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/options/:id/buy-back", post(buy_back))
+            .route("/options/:id/roll", post(roll))
+            .with_state(manager)
+    }
+}
@@ -1,7 +1,22 @@
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::eligibility::{screen, EligibilityProvider, EligibilityRequest};
+use crate::hedge_executor::{HedgeFill, RebalanceRecord, RebalanceRequest};
+use crate::theta_policy::{ThetaBand, ThetaPolicyConfig, ThetaPolicyEngine};
 use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{
+    ContractError, ContractSpec, GreeksLimits, HedgeError, OptionId, OptionTerms, PricingError,
+    SettlementError, SharedClock, SystemClock,
+};
+use pricing_core::{BlackScholesInputs, Greeks};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::warn;
+
+/// 리밸런싱 기본 임계값 (순 델타 BTC)
+pub const DEFAULT_REBALANCE_THRESHOLD: f64 = 0.1;
+
+/// 그릭 계산용 무위험 이자율 (calculation 서비스와 동일)
+const RISK_FREE_RATE: f64 = 0.05;
 
 /// 단방향 옵션 (Buyer-only Option)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +32,9 @@ pub struct BuyerOnlyOption {
     pub buyer_address: String,   // Bitcoin address
     pub pre_sign_tx: Vec<u8>,   // BitVMX pre-signed transaction
     pub status: OptionStatus,
+    /// 구매 시 자격 확인 결과 해시 (확인 없이 구매했으면 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eligibility_hash: Option<String>,
 }
 
 /// 옵션 상태
@@ -61,6 +79,9 @@ pub struct HedgePositions {
     pub bybit_position: f64,      // BTC position on Bybit
     pub total_hedge: f64,         // Total hedge position
     pub last_rebalance: u64,      // Last rebalance timestamp
+    /// 리밸런싱 요청/체결 이력
+    #[serde(default)]
+    pub rebalance_history: Vec<RebalanceRecord>,
 }
 
 /// 가격 데이터 (3개 거래소 평균)
@@ -77,10 +98,26 @@ pub struct AggregatedPrice {
 pub struct BuyerOnlyOptionManager {
     pool: DeltaNeutralPool,
     price_cache: Option<AggregatedPrice>,
+    rebalance_threshold: f64,
+    rebalance_queue: Option<UnboundedSender<RebalanceRequest>>,
+    next_rebalance_id: u64,
+    /// 옵션 ID 파생용 논스 (정산 후에도 재사용하지 않음)
+    next_option_nonce: u64,
+    /// 거래당 델타/베가 한도
+    greeks_limits: GreeksLimits,
+    /// 설정 시 풀 성과로 정한 theta 밴드 밖의 요청 거부
+    theta_policy: Option<ThetaPolicyEngine>,
+    /// 만기 시각과 그릭 잔존 기간 계산용 시계
+    clock: SharedClock,
 }
 
 impl BuyerOnlyOptionManager {
     pub fn new(initial_liquidity: u64) -> Self {
+        Self::with_clock(initial_liquidity, SystemClock::shared())
+    }
+
+    /// 지정한 시계를 사용하는 관리자 생성 (테스트에서 시간 이동)
+    pub fn with_clock(initial_liquidity: u64, clock: SharedClock) -> Self {
         Self {
             pool: DeltaNeutralPool {
                 total_liquidity: initial_liquidity,
@@ -98,16 +135,75 @@ impl BuyerOnlyOptionManager {
                     bybit_position: 0.0,
                     total_hedge: 0.0,
                     last_rebalance: 0,
+                    rebalance_history: Vec::new(),
                 },
                 active_options: HashMap::new(),
             },
             price_cache: None,
+            rebalance_threshold: DEFAULT_REBALANCE_THRESHOLD,
+            rebalance_queue: None,
+            next_rebalance_id: 1,
+            next_option_nonce: 0,
+            greeks_limits: GreeksLimits::default(),
+            theta_policy: None,
+            clock,
         }
     }
 
+    /// 거래당 델타/베가 한도 설정 (calculation 호가 서비스와 같은 값이어야 함)
+    pub fn set_greeks_limits(&mut self, limits: GreeksLimits) {
+        self.greeks_limits = limits;
+    }
+
+    /// target theta 정책 활성화, 이후 가격 업데이트와 정산마다 밴드 재계산
+    pub fn enable_theta_policy(&mut self, config: ThetaPolicyConfig) {
+        self.theta_policy = Some(ThetaPolicyEngine::new(config));
+        self.refresh_theta_band();
+    }
+
+    /// 현재 허용 theta 밴드 (정책 미설정 시 None)
+    pub fn theta_band(&self) -> Option<&ThetaBand> {
+        self.theta_policy.as_ref().map(ThetaPolicyEngine::band)
+    }
+
+    /// 풀 성과(내재 변동성, 지급률, 사용률)로 theta 밴드 재계산
+    fn refresh_theta_band(&mut self) {
+        let Some(policy) = self.theta_policy.as_mut() else {
+            return;
+        };
+        let active: Vec<f64> = self
+            .pool
+            .active_options
+            .values()
+            .map(|option| option.implied_volatility)
+            .collect();
+        let implied_vol = (!active.is_empty()).then(|| active.iter().sum::<f64>() / active.len() as f64);
+        let payout_ratio = match self.pool.total_premium_collected {
+            0 => 0.0,
+            premiums => self.pool.total_payouts as f64 / premiums as f64,
+        };
+        let utilization = match self.pool.total_liquidity {
+            0 => 0.0,
+            total => self.pool.locked_for_payouts as f64 / total as f64,
+        };
+        policy.update(implied_vol, payout_ratio, utilization, self.clock.now());
+    }
+
+    /// 자동 리밸런싱 활성화, 반환된 큐는 `run_hedge_worker`가 소비
+    pub fn enable_auto_rebalance(&mut self, threshold: f64) -> UnboundedReceiver<RebalanceRequest> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.rebalance_threshold = threshold;
+        self.rebalance_queue = Some(sender);
+        receiver
+    }
+
     /// 3개 거래소 가격 업데이트
     pub fn update_price(&mut self, aggregated_price: AggregatedPrice) {
+        if let Some(policy) = self.theta_policy.as_mut() {
+            policy.observe_price(aggregated_price.timestamp, aggregated_price.average_price as f64);
+        }
         self.price_cache = Some(aggregated_price);
+        self.refresh_theta_band();
     }
 
     /// Target theta에 맞는 프리미엄 계산
@@ -118,9 +214,9 @@ impl BuyerOnlyOptionManager {
         quantity: u64,
         target_theta: f64,
         days_to_expiry: f64,
-    ) -> Result<(u64, f64)> { // Returns (premium, implied_volatility)
+    ) -> Result<(u64, f64), PricingError> { // Returns (premium, implied_volatility)
         let spot = self.price_cache.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No price data available"))?
+            .ok_or_else(|| PricingError::NoMarketData("no aggregated price".to_string()))?
             .average_price;
         
         // Simplified calculation - in production, use proper Black-Scholes
@@ -154,7 +250,11 @@ impl BuyerOnlyOptionManager {
         target_theta: f64,
         days_to_expiry: f64,
         buyer_address: String,
-    ) -> Result<BuyerOnlyOption> {
+    ) -> Result<BuyerOnlyOption, ContractError> {
+        if let Some(policy) = &self.theta_policy {
+            policy.check(target_theta)?;
+        }
+
         // 1. Calculate premium based on target theta
         let (premium, implied_vol) = self.calculate_premium_for_target_theta(
             option_type,
@@ -166,7 +266,7 @@ impl BuyerOnlyOptionManager {
         
         // 2. Check available liquidity
         let spot_price = self.price_cache.as_ref()
-            .ok_or_else(|| anyhow::anyhow!("No price data available"))?
+            .ok_or_else(|| PricingError::NoMarketData("no aggregated price".to_string()))?
             .average_price;
             
         let max_payout = match option_type {
@@ -174,19 +274,44 @@ impl BuyerOnlyOptionManager {
             OptionType::Put => (strike_price * quantity) / spot_price, // Limited to strike
         };
         
+        // 한 거래가 풀 순델타/베가를 한도 이상 움직이지 못하게
+        let greeks = BlackScholesInputs {
+            spot: spot_price as f64 / 100.0,
+            strike: strike_price as f64 / 100.0,
+            time_to_expiry: days_to_expiry / 365.0,
+            volatility: implied_vol,
+            risk_free_rate: RISK_FREE_RATE,
+            is_call: option_type == OptionType::Call,
+        }
+        .greeks()
+        .scaled(quantity as f64 / 1e8);
+        self.greeks_limits.check(
+            quantity,
+            greeks.delta,
+            greeks.vega,
+            ContractSpec::default().contract_size,
+        )?;
+
         if self.pool.available_liquidity < max_payout {
-            anyhow::bail!("Insufficient liquidity in pool");
+            return Err(ContractError::InsufficientLiquidity {
+                required: max_payout,
+                available: self.pool.available_liquidity,
+            });
         }
         
         // 3. Create option
-        let option_id = format!("OPT-{}-{}", 
-            chrono::Utc::now().timestamp_millis(), 
-            buyer_address.chars().take(8).collect::<String>()
-        );
-        
-        let expiry_timestamp = chrono::Utc::now().timestamp() as u64 
+        let expiry_timestamp = self.clock.now() 
             + (days_to_expiry * 86400.0) as u64;
         
+        let terms = OptionTerms {
+            option_type,
+            strike_price,
+            quantity,
+            expiry: expiry_timestamp,
+        };
+        let option_id = OptionId::derive(buyer_address.as_bytes(), &terms, self.next_option_nonce).to_string();
+        self.next_option_nonce += 1;
+        
         let option = BuyerOnlyOption {
             option_id: option_id.clone(),
             option_type,
@@ -199,6 +324,7 @@ impl BuyerOnlyOptionManager {
             buyer_address: buyer_address.clone(),
             pre_sign_tx: vec![], // Would be generated by BitVMX
             status: OptionStatus::Active,
+            eligibility_hash: None,
         };
         
         // 4. Update pool state
@@ -212,42 +338,165 @@ impl BuyerOnlyOptionManager {
         
         // 6. Store option
         self.pool.active_options.insert(option_id.clone(), option.clone());
+        self.refresh_theta_band();
         
         Ok(option)
     }
 
+    /// 자격 확인 후 옵션 구매, 확인 결과 해시를 옵션에 남김
+    ///
+    /// 옵션 ID는 구매 전에 알 수 없으므로 확인 요청에는 구매자 주소만 담습니다.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn buy_option_screened(
+        &mut self,
+        provider: &dyn EligibilityProvider,
+        user_id: &str,
+        option_type: OptionType,
+        strike_price: u64,
+        quantity: u64,
+        target_theta: f64,
+        days_to_expiry: f64,
+        buyer_address: String,
+    ) -> Result<BuyerOnlyOption, ContractError> {
+        let request = EligibilityRequest {
+            user_id: user_id.to_string(),
+            address: Some(buyer_address.clone()),
+            option_id: String::new(),
+        };
+        let decision = screen(provider, &request).await?;
+        let mut option = self.buy_option(
+            option_type,
+            strike_price,
+            quantity,
+            target_theta,
+            days_to_expiry,
+            buyer_address,
+        )?;
+        let hash = decision.hash(&request);
+        option.eligibility_hash = Some(hash.clone());
+        if let Some(stored) = self.pool.active_options.get_mut(&option.option_id) {
+            stored.eligibility_hash = Some(hash);
+        }
+        Ok(option)
+    }
+
+    /// 옵션 포지션의 Black-Scholes 그릭 (수량 BTC 기준, 감마/베가는 USD 가격 단위)
+    fn option_greeks(option: &BuyerOnlyOption, spot_cents: u64, now: u64) -> Greeks {
+        let inputs = BlackScholesInputs {
+            spot: spot_cents as f64 / 100.0,
+            strike: option.strike_price as f64 / 100.0,
+            time_to_expiry: option.expiry_timestamp.saturating_sub(now) as f64 / 86400.0 / 365.0,
+            volatility: option.implied_volatility,
+            risk_free_rate: RISK_FREE_RATE,
+            is_call: option.option_type == OptionType::Call,
+        };
+        inputs.greeks().scaled(option.quantity as f64 / 1e8)
+    }
+
     /// Update pool Greeks after new option
     fn update_pool_greeks(&mut self, option: &BuyerOnlyOption) {
-        // Simplified Greeks calculation
-        let spot = self.price_cache.as_ref().unwrap().average_price as f64;
-        let strike = option.strike_price as f64;
-        let time_to_expiry = (option.expiry_timestamp - chrono::Utc::now().timestamp() as u64) as f64 / 86400.0 / 365.0;
-        
-        // Delta calculation (simplified)
-        let moneyness = spot / strike;
-        let delta = match option.option_type {
-            OptionType::Call => 0.5 + 0.5 * moneyness.ln(),
-            OptionType::Put => -0.5 + 0.5 * moneyness.ln(),
-        }.max(-1.0).min(1.0);
-        
+        let spot = self.price_cache.as_ref().unwrap().average_price;
+        let greeks = Self::option_greeks(option, spot, self.clock.now());
+
         // Update pool Greeks
-        self.pool.net_delta += delta * (option.quantity as f64 / 1e8);
+        self.pool.net_delta += greeks.delta;
+        self.pool.net_gamma += greeks.gamma;
+        self.pool.net_vega += greeks.vega;
         self.pool.net_theta += option.target_theta;
-        
-        // Trigger rebalance if delta exceeds threshold
-        if self.pool.net_delta.abs() > 0.1 {
-            // In production, this would trigger external hedge rebalancing
-            println!("Delta rebalance needed: {}", self.pool.net_delta);
+
+        self.check_rebalance();
+    }
+
+    /// 헷지 후 잔여 델타가 임계값을 넘으면 리밸런싱 요청 (처리 중인 요청이 있으면 대기)
+    fn check_rebalance(&mut self) {
+        let hedges = &mut self.pool.hedge_positions;
+        let residual = self.pool.net_delta + hedges.total_hedge;
+        if residual.abs() <= self.rebalance_threshold
+            || hedges.rebalance_history.iter().any(RebalanceRecord::is_pending)
+        {
+            return;
+        }
+        let Some(queue) = &self.rebalance_queue else {
+            warn!("Delta rebalance needed but no hedge queue: {:.4}", residual);
+            return;
+        };
+
+        let request = RebalanceRequest {
+            request_id: self.next_rebalance_id,
+            net_delta: self.pool.net_delta,
+            size: -residual,
+            requested_at: self.clock.now(),
+        };
+        if queue.send(request.clone()).is_err() {
+            warn!("Hedge worker stopped, rebalance of {:.4} BTC not queued", request.size);
+            return;
         }
+        self.next_rebalance_id += 1;
+        hedges.rebalance_history.push(RebalanceRecord {
+            request_id: request.request_id,
+            net_delta: request.net_delta,
+            intended_size: request.size,
+            executed_size: None,
+            venue: None,
+            requested_at: request.requested_at,
+            executed_at: None,
+            error: None,
+        });
+    }
+
+    /// 헷지 체결 반영 (부분 체결로 잔여 델타가 남으면 다시 요청)
+    pub fn record_hedge_fill(&mut self, fill: &HedgeFill) -> Result<(), HedgeError> {
+        let hedges = &mut self.pool.hedge_positions;
+        let record = hedges
+            .rebalance_history
+            .iter_mut()
+            .find(|record| record.request_id == fill.request_id)
+            .ok_or(HedgeError::UnknownRequest(fill.request_id))?;
+        record.executed_size = Some(fill.executed_size);
+        record.venue = Some(fill.venue.clone());
+        record.executed_at = Some(fill.executed_at);
+
+        match fill.venue.as_str() {
+            "binance" => hedges.binance_position += fill.executed_size,
+            "bybit" => hedges.bybit_position += fill.executed_size,
+            _ => {}
+        }
+        hedges.total_hedge += fill.executed_size;
+        hedges.last_rebalance = fill.executed_at;
+
+        self.check_rebalance();
+        Ok(())
+    }
+
+    /// 헷지 실패 기록 (다음 그릭 갱신 때 다시 요청)
+    pub fn record_hedge_failure(&mut self, request_id: u64, error: &HedgeError) -> Result<(), HedgeError> {
+        let record = self
+            .pool
+            .hedge_positions
+            .rebalance_history
+            .iter_mut()
+            .find(|record| record.request_id == request_id)
+            .ok_or(HedgeError::UnknownRequest(request_id))?;
+        record.error = Some(error.to_string());
+        Ok(())
+    }
+
+    /// 리밸런싱 이력
+    pub fn rebalance_history(&self) -> &[RebalanceRecord] {
+        &self.pool.hedge_positions.rebalance_history
     }
 
     /// Settle expired option
-    pub fn settle_option(&mut self, option_id: &str, settlement_price: u64) -> Result<u64> {
+    pub fn settle_option(
+        &mut self,
+        option_id: &str,
+        settlement_price: u64,
+    ) -> Result<u64, SettlementError> {
         let option = self.pool.active_options.get_mut(option_id)
-            .ok_or_else(|| anyhow::anyhow!("Option not found"))?;
+            .ok_or_else(|| SettlementError::OptionNotFound(option_id.to_string()))?;
         
         if option.status != OptionStatus::Active {
-            anyhow::bail!("Option already settled");
+            return Err(SettlementError::OptionNotActive(option_id.to_string()));
         }
         
         let payout = match option.option_type {
@@ -290,6 +539,7 @@ impl BuyerOnlyOptionManager {
         
         // Recalculate Greeks after removing option
         self.recalculate_pool_greeks();
+        self.refresh_theta_band();
         
         Ok(payout)
     }
@@ -302,26 +552,21 @@ impl BuyerOnlyOptionManager {
         self.pool.net_theta = 0.0;
         
         if let Some(price_data) = &self.price_cache {
-            let spot = price_data.average_price as f64;
-            
+            let spot = price_data.average_price;
+            let now = self.clock.now();
+
             for option in self.pool.active_options.values() {
                 if option.status == OptionStatus::Active {
-                    // Simplified Greeks calculation
-                    let strike = option.strike_price as f64;
-                    let time_to_expiry = (option.expiry_timestamp - chrono::Utc::now().timestamp() as u64) as f64 / 86400.0 / 365.0;
-                    
-                    // Delta calculation (simplified)
-                    let moneyness = spot / strike;
-                    let delta = match option.option_type {
-                        OptionType::Call => 0.5 + 0.5 * moneyness.ln(),
-                        OptionType::Put => -0.5 + 0.5 * moneyness.ln(),
-                    }.max(-1.0).min(1.0);
-                    
-                    self.pool.net_delta += delta * (option.quantity as f64 / 1e8);
+                    let greeks = Self::option_greeks(option, spot, now);
+                    self.pool.net_delta += greeks.delta;
+                    self.pool.net_gamma += greeks.gamma;
+                    self.pool.net_vega += greeks.vega;
                     self.pool.net_theta += option.target_theta;
                 }
             }
         }
+
+        self.check_rebalance();
     }
     
     /// Get pool statistics
@@ -333,6 +578,7 @@ impl BuyerOnlyOptionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oracle_vm_common::{Clock, ManualClock};
 
     #[test]
     fn test_buy_call_option() {
@@ -362,6 +608,75 @@ mod tests {
         assert!(option.premium_paid > 0);
         assert_eq!(option.strike_price, 7500000);
         assert_eq!(manager.pool.active_options.len(), 1);
+
+        // 같은 조건으로 다시 사도 ID는 겹치지 않음
+        let again = manager
+            .buy_option(OptionType::Call, 7500000, 1_000_000, -0.02, 7.0, "bc1qtest".to_string())
+            .unwrap();
+        assert!(option.option_id.parse::<OptionId>().is_ok());
+        assert_ne!(again.option_id, option.option_id);
+        assert_eq!(manager.pool.active_options.len(), 2);
+    }
+
+    #[test]
+    fn test_theta_policy_rejects_out_of_band_requests() {
+        let mut manager = BuyerOnlyOptionManager::new(1_000_000_000);
+        manager.update_price(AggregatedPrice {
+            binance_price: 7000000,
+            coinbase_price: 7000000,
+            kraken_price: 7000000,
+            average_price: 7000000,
+            timestamp: 1234567890,
+        });
+        assert!(manager.theta_band().is_none());
+        manager.enable_theta_policy(ThetaPolicyConfig::default());
+
+        // 아무 지급도 없는 풀은 기준 밴드보다 낮은 감가 허용
+        let band = *manager.theta_band().unwrap();
+        assert!(band.scale < 1.0);
+        assert!(matches!(
+            manager.buy_option(OptionType::Call, 7500000, 1_000_000, -0.2, 7.0, "bc1qtest".to_string()),
+            Err(ContractError::ThetaOutOfBand { .. })
+        ));
+        assert!(manager.pool.active_options.is_empty());
+
+        let option = manager
+            .buy_option(OptionType::Call, 7000000, 1_000_000, band.max_theta, 1.0, "bc1qtest".to_string())
+            .unwrap();
+        // ITM 지급 후 지급률이 오르면 밴드가 더 큰 감가 쪽으로
+        manager.settle_option(&option.option_id, 9000000).unwrap();
+        let after = manager.theta_band().unwrap();
+        assert!(after.inputs.payout_ratio > 0.6);
+        assert!(after.min_theta < band.min_theta);
+    }
+
+    #[test]
+    fn test_greeks_limits_cap_single_trade() {
+        let mut manager = BuyerOnlyOptionManager::new(1_000_000_000);
+        manager.update_price(AggregatedPrice {
+            binance_price: 7000000,
+            coinbase_price: 7000000,
+            kraken_price: 7000000,
+            average_price: 7000000,
+            timestamp: 1234567890,
+        });
+        manager.set_greeks_limits(GreeksLimits {
+            max_delta: Some(0.2),
+            max_vega: None,
+        });
+
+        // ATM 콜 1 BTC는 델타 ~0.5로 한도 초과, 풀은 그대로
+        let oversized = manager.buy_option(OptionType::Call, 7000000, 100_000_000, -0.02, 7.0, "bc1qtest".to_string());
+        let Err(ContractError::Pricing(PricingError::GreeksLimitExceeded { max_quantity, schedule, .. })) = oversized else {
+            panic!("expected greeks limit error");
+        };
+        assert_eq!(manager.pool.available_liquidity, 1_000_000_000);
+        assert_eq!(schedule.iter().sum::<u64>(), 100_000_000);
+
+        manager
+            .buy_option(OptionType::Call, 7000000, max_quantity, -0.02, 7.0, "bc1qtest".to_string())
+            .unwrap();
+        assert!(manager.pool.net_delta <= 0.2);
     }
 
     #[test]
@@ -392,4 +707,105 @@ mod tests {
         // Check pool updated
         assert_eq!(manager.pool.total_payouts, payout);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_delta_breach_queues_rebalance() {
+        use crate::hedge_executor::{run_hedge_worker, PaperHedgeExecutor};
+        use std::sync::{Arc, Mutex};
+
+        let mut manager = BuyerOnlyOptionManager::new(10_000_000_000);
+        manager.update_price(AggregatedPrice {
+            binance_price: 7000000,
+            coinbase_price: 7000000,
+            kraken_price: 7000000,
+            average_price: 7000000,
+            timestamp: 1234567890,
+        });
+        let mut queue = manager.enable_auto_rebalance(0.1);
+
+        // 0.01 BTC 콜: 델타 0.01 이하, 임계값 이하
+        manager.buy_option(OptionType::Call, 7000000, 1_000_000, -0.02, 7.0, "bc1qtest".to_string()).unwrap();
+        assert!(queue.try_recv().is_err());
+
+        // 1 BTC 콜 추가 → 요청 1건, 처리 전에는 중복 요청 없음
+        manager.buy_option(OptionType::Call, 7000000, 100_000_000, -0.02, 7.0, "bc1qtest".to_string()).unwrap();
+        let delta_at_request = manager.get_pool_stats().net_delta;
+        manager.buy_option(OptionType::Call, 7000000, 100_000_000, -0.02, 7.0, "bc1qtest".to_string()).unwrap();
+        let request = queue.try_recv().unwrap();
+        assert!(queue.try_recv().is_err());
+        assert!((request.size + delta_at_request).abs() < 1e-9);
+        assert!(manager.rebalance_history()[0].is_pending());
+
+        // 워커가 체결하면 이력과 헷지 포지션에 반영되고 남은 델타로 다시 요청
+        let manager = Arc::new(Mutex::new(manager));
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        sender.send(request).unwrap();
+        drop(sender);
+        run_hedge_worker(receiver, Arc::new(PaperHedgeExecutor::new("binance")), manager.clone()).await;
+
+        let manager = manager.lock().unwrap();
+        let stats = manager.get_pool_stats();
+        assert!((stats.hedge_positions.binance_position + delta_at_request).abs() < 1e-9);
+        assert_eq!(stats.hedge_positions.rebalance_history[0].executed_size, Some(-delta_at_request));
+        let follow_up = queue.try_recv().unwrap();
+        assert!((follow_up.size + stats.net_delta - delta_at_request).abs() < 1e-9);
+        assert_eq!(manager.rebalance_history().len(), 2);
+    }
+
+    #[test]
+    fn test_pool_greeks_follow_black_scholes() {
+        let mut manager = BuyerOnlyOptionManager::new(10_000_000_000);
+        manager.update_price(AggregatedPrice {
+            binance_price: 7000000,
+            coinbase_price: 7000000,
+            kraken_price: 7000000,
+            average_price: 7000000,
+            timestamp: 1234567890,
+        });
+
+        let call = manager.buy_option(OptionType::Call, 7000000, 100_000_000, -0.0001, 30.0, "bc1qtest".to_string()).unwrap();
+        // 옵션 ID가 밀리초+주소 앞 8자라 주소를 달리함
+        let put = manager.buy_option(OptionType::Put, 6500000, 100_000_000, -0.0001, 30.0, "tb1qputs".to_string()).unwrap();
+
+        let stats = manager.get_pool_stats().clone();
+        // ATM 콜 델타는 0.5 근처, OTM 풋은 음수
+        assert!(stats.net_delta > 0.0 && stats.net_delta < 1.0);
+        assert!(stats.net_gamma > 0.0);
+        assert!(stats.net_vega > 0.0);
+
+        manager.settle_option(&put.option_id, 7000000).unwrap();
+        let after_put = manager.get_pool_stats().net_vega;
+        assert!(after_put > 0.0 && after_put < stats.net_vega);
+
+        manager.settle_option(&call.option_id, 7000000).unwrap();
+        let stats = manager.get_pool_stats();
+        assert_eq!((stats.net_delta, stats.net_gamma, stats.net_vega), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_greeks_decay_over_simulated_time() {
+        let clock = ManualClock::new(1_700_000_000);
+        let mut manager = BuyerOnlyOptionManager::with_clock(10_000_000_000, clock.shared());
+        manager.update_price(AggregatedPrice {
+            binance_price: 7000000,
+            coinbase_price: 7000000,
+            kraken_price: 7000000,
+            average_price: 7000000,
+            timestamp: clock.now(),
+        });
+
+        let call = manager.buy_option(OptionType::Call, 7000000, 100_000_000, -0.0001, 30.0, "bc1qtest".to_string()).unwrap();
+        let put = manager.buy_option(OptionType::Put, 6500000, 100_000_000, -0.0001, 30.0, "tb1qputs".to_string()).unwrap();
+        assert_eq!(call.expiry_timestamp, clock.now() + 30 * 86400);
+        let fresh = BuyerOnlyOptionManager::option_greeks(&call, 7000000, clock.now());
+
+        // 29일 뒤 풋 정산으로 재계산하면 남은 콜은 만기 하루 전 그릭
+        clock.advance(29 * 86400);
+        manager.settle_option(&put.option_id, 7000000).unwrap();
+        let stats = manager.get_pool_stats();
+        let aged = BuyerOnlyOptionManager::option_greeks(&call, 7000000, clock.now());
+        assert_eq!(stats.net_vega, aged.vega);
+        assert!(aged.vega < fresh.vega / 3.0);
+        assert!(aged.gamma > fresh.gamma);
+    }
+}
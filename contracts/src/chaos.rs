@@ -0,0 +1,589 @@
+//! 장애 주입(chaos) 모드 (`chaos` feature)
+//!
+//! 외부 연동 지점을 감싸 시드에 따라 무작위로 지연, 오류, 응답 손상을
+//! 일으킵니다. 같은 시드와 같은 호출 순서면 같은 장애가 재현됩니다.
+//!
+//! - 오라클/계산/계약 연동: 흐름 단계(`Step`)를 감싸는 [`ChaosStep`]
+//! - BitVMX 실행: [`ChaosBitVmx`]
+//! - 앵커 전송: [`ChaosBroadcaster`]
+//! - 웹훅 알림: [`ChaosTransport`]
+//!
+//! ```ignore
+//! let chaos = Arc::new(ChaosInjector::new(ChaosConfig::new(7).with_errors(0.3)));
+//! let flow = Flow::new("price_commitment.record", metrics)
+//!     .then(ChaosStep::new(FetchConsensusPrice::new(url), chaos.clone()))
+//!     .then(RecordPrice::new(log));
+//! ```
+
+use crate::anchor_tracker::AnchorBroadcaster;
+use crate::bitvmx_backend::BitVmxBackend;
+use crate::flow::{Step, StepPolicy};
+use crate::webhooks::{WebhookRequest, WebhookTransport};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use oracle_vm_common::AnchorError;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+/// 장애 주입 설정 (확률은 호출당 0.0~1.0)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    pub seed: u64,
+    pub delay_rate: f64,
+    pub max_delay: Duration,
+    pub error_rate: f64,
+    pub corrupt_rate: f64,
+}
+
+impl ChaosConfig {
+    /// 장애 없는 설정
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            delay_rate: 0.0,
+            max_delay: Duration::ZERO,
+            error_rate: 0.0,
+            corrupt_rate: 0.0,
+        }
+    }
+
+    /// `rate` 확률로 최대 `max_delay`까지 지연
+    pub fn with_delays(mut self, rate: f64, max_delay: Duration) -> Self {
+        self.delay_rate = rate.clamp(0.0, 1.0);
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_errors(mut self, rate: f64) -> Self {
+        self.error_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_corruption(mut self, rate: f64) -> Self {
+        self.corrupt_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// 한 호출에 주입할 장애
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// 지연 후 정상 실행
+    Delay(Duration),
+    /// 실행하지 않고 실패
+    Error,
+    /// 실행 후 응답 손상
+    Corrupt,
+}
+
+/// 주입한 장애 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ChaosStats {
+    pub calls: u64,
+    pub delays: u64,
+    pub errors: u64,
+    pub corruptions: u64,
+}
+
+/// 시드 기반 장애 결정기 (여러 연동 지점이 공유)
+#[derive(Debug)]
+pub struct ChaosInjector {
+    config: ChaosConfig,
+    state: Mutex<u64>,
+    calls: AtomicU64,
+    delays: AtomicU64,
+    errors: AtomicU64,
+    corruptions: AtomicU64,
+}
+
+impl ChaosInjector {
+    pub fn new(config: ChaosConfig) -> Self {
+        Self {
+            state: Mutex::new(config.seed),
+            config,
+            calls: AtomicU64::new(0),
+            delays: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            corruptions: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    /// [0, 1) 균등 난수 (splitmix64)
+    fn next_unit(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 이번 호출의 장애 결정 (오류 → 손상 → 지연 순으로 판정)
+    pub fn roll(&self, target: &str) -> Option<Fault> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let draw = self.next_unit();
+        let config = &self.config;
+        let fault = if draw < config.error_rate {
+            self.errors.fetch_add(1, Ordering::SeqCst);
+            Fault::Error
+        } else if draw < config.error_rate + config.corrupt_rate {
+            self.corruptions.fetch_add(1, Ordering::SeqCst);
+            Fault::Corrupt
+        } else if draw < config.error_rate + config.corrupt_rate + config.delay_rate {
+            self.delays.fetch_add(1, Ordering::SeqCst);
+            Fault::Delay(config.max_delay.mul_f64(self.next_unit()))
+        } else {
+            return None;
+        };
+        debug!("Chaos: injecting {:?} into {}", fault, target);
+        Some(fault)
+    }
+
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            calls: self.calls.load(Ordering::SeqCst),
+            delays: self.delays.load(Ordering::SeqCst),
+            errors: self.errors.load(Ordering::SeqCst),
+            corruptions: self.corruptions.load(Ordering::SeqCst),
+        }
+    }
+}
+
+type Corruptor<T> = Box<dyn Fn(&mut T) + Send + Sync>;
+
+/// 흐름 단계 장애 주입
+///
+/// 손상은 `with_corruption`으로 준 함수가 출력을 바꾸는 방식이며, 함수가
+/// 없으면 손상 판정은 정상 실행으로 처리합니다. 재시도/타임아웃 정책은
+/// 감싼 단계의 것을 그대로 씁니다.
+pub struct ChaosStep<S: Step> {
+    inner: S,
+    chaos: Arc<ChaosInjector>,
+    corrupt: Option<Corruptor<S::Output>>,
+}
+
+impl<S: Step> ChaosStep<S> {
+    pub fn new(inner: S, chaos: Arc<ChaosInjector>) -> Self {
+        Self {
+            inner,
+            chaos,
+            corrupt: None,
+        }
+    }
+
+    pub fn with_corruption(mut self, corrupt: impl Fn(&mut S::Output) + Send + Sync + 'static) -> Self {
+        self.corrupt = Some(Box::new(corrupt));
+        self
+    }
+}
+
+#[async_trait]
+impl<S: Step> Step for ChaosStep<S> {
+    type Input = S::Input;
+    type Output = S::Output;
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn policy(&self) -> StepPolicy {
+        self.inner.policy()
+    }
+
+    async fn run(&self, input: &S::Input) -> Result<S::Output, String> {
+        match self.chaos.roll(self.inner.name()) {
+            Some(Fault::Error) => Err(format!("chaos: injected failure in {}", self.inner.name())),
+            Some(Fault::Delay(delay)) => {
+                tokio::time::sleep(delay).await;
+                self.inner.run(input).await
+            }
+            Some(Fault::Corrupt) => {
+                let mut output = self.inner.run(input).await?;
+                if let Some(corrupt) = &self.corrupt {
+                    corrupt(&mut output);
+                }
+                Ok(output)
+            }
+            None => self.inner.run(input).await,
+        }
+    }
+}
+
+/// BitVMX 실행 장애 주입
+///
+/// 손상은 출력의 숫자를 모두 깨뜨립니다 (정산 금액 줄이 읽히지 않는 응답).
+pub struct ChaosBitVmx<B> {
+    inner: B,
+    chaos: Arc<ChaosInjector>,
+}
+
+impl<B: BitVmxBackend> ChaosBitVmx<B> {
+    pub fn new(inner: B, chaos: Arc<ChaosInjector>) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+impl<B: BitVmxBackend> BitVmxBackend for ChaosBitVmx<B> {
+    fn execute(&self, input: &[u8]) -> Result<Vec<u8>> {
+        match self.chaos.roll("bitvmx") {
+            Some(Fault::Error) => bail!("chaos: BitVMX execution failed"),
+            Some(Fault::Delay(delay)) => {
+                std::thread::sleep(delay);
+                self.inner.execute(input)
+            }
+            Some(Fault::Corrupt) => {
+                let mut output = self.inner.execute(input)?;
+                for byte in output.iter_mut().filter(|byte| byte.is_ascii_digit()) {
+                    *byte = b'#';
+                }
+                Ok(output)
+            }
+            None => self.inner.execute(input),
+        }
+    }
+
+    fn program_hash(&self) -> Result<[u8; 32]> {
+        self.inner.program_hash()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+/// 앵커 전송 장애 주입 (손상은 노드가 거부한 전송으로 처리)
+pub struct ChaosBroadcaster<B> {
+    inner: B,
+    chaos: Arc<ChaosInjector>,
+}
+
+impl<B: AnchorBroadcaster> ChaosBroadcaster<B> {
+    pub fn new(inner: B, chaos: Arc<ChaosInjector>) -> Self {
+        Self { inner, chaos }
+    }
+
+    fn inject(&self, send: impl FnOnce() -> Result<String, AnchorError>) -> Result<String, AnchorError> {
+        match self.chaos.roll("anchor") {
+            Some(Fault::Error) => Err(AnchorError::Rpc("chaos: connection reset".to_string())),
+            Some(Fault::Corrupt) => Err(AnchorError::BroadcastRejected(
+                "chaos: malformed transaction".to_string(),
+            )),
+            Some(Fault::Delay(delay)) => {
+                std::thread::sleep(delay);
+                send()
+            }
+            None => send(),
+        }
+    }
+}
+
+impl<B: AnchorBroadcaster> AnchorBroadcaster for ChaosBroadcaster<B> {
+    fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+        self.inject(|| self.inner.rebroadcast(raw_tx))
+    }
+
+    fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+        self.inject(|| self.inner.anchor(payload))
+    }
+}
+
+/// 웹훅 전송 장애 주입 (손상은 502 응답으로 처리)
+pub struct ChaosTransport<T> {
+    inner: T,
+    chaos: Arc<ChaosInjector>,
+}
+
+impl<T: WebhookTransport> ChaosTransport<T> {
+    pub fn new(inner: T, chaos: Arc<ChaosInjector>) -> Self {
+        Self { inner, chaos }
+    }
+}
+
+#[async_trait]
+impl<T: WebhookTransport> WebhookTransport for ChaosTransport<T> {
+    async fn post(&self, request: &WebhookRequest) -> Result<u16, String> {
+        match self.chaos.roll("webhook") {
+            Some(Fault::Error) => Err("chaos: connection reset".to_string()),
+            Some(Fault::Corrupt) => Ok(502),
+            Some(Fault::Delay(delay)) => {
+                tokio::time::sleep(delay).await;
+                self.inner.post(request).await
+            }
+            None => self.inner.post(request).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin_option::BitcoinOption;
+    use crate::OptionType;
+    use crate::bitvmx_backend::MockBitVmxBackend;
+    use crate::bitvmx_bridge::BitVmxBridge;
+    use crate::flow::{Flow, FlowMetrics};
+    use crate::price_commitment::PriceCommitmentLog;
+    use crate::webhooks::{DeliveryStatus, RetryPolicy, WebhookDispatcher, WebhookEvent, WebhookEventKind};
+    use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use std::collections::BTreeMap;
+
+    const MINUTE: u64 = 60;
+    const DAY_START: u64 = 1_700_006_400; // 2023-11-15 00:00 UTC
+
+    fn injector(config: ChaosConfig) -> Arc<ChaosInjector> {
+        Arc::new(ChaosInjector::new(config))
+    }
+
+    /// 분마다 정해진 가격을 돌려주는 오라클
+    struct FixedOracle;
+
+    fn true_price(minute: u64) -> u64 {
+        6_500_000 + (minute / MINUTE % 100) * 25
+    }
+
+    #[async_trait]
+    impl Step for FixedOracle {
+        type Input = u64;
+        type Output = (u64, u64);
+
+        fn name(&self) -> &'static str {
+            "fetch_price"
+        }
+
+        fn policy(&self) -> StepPolicy {
+            StepPolicy::retry(5, Duration::from_millis(1)).with_timeout(Duration::from_millis(50))
+        }
+
+        async fn run(&self, now: &u64) -> Result<(u64, u64), String> {
+            Ok((*now, true_price(*now)))
+        }
+    }
+
+    /// 범위 밖 가격 거부
+    struct ValidatePrice;
+
+    #[async_trait]
+    impl Step for ValidatePrice {
+        type Input = (u64, u64);
+        type Output = (u64, u64);
+
+        fn name(&self) -> &'static str {
+            "validate_price"
+        }
+
+        async fn run(&self, &(timestamp, price): &(u64, u64)) -> Result<(u64, u64), String> {
+            if !(100_000..=100_000_000).contains(&price) {
+                return Err(format!("price {} out of range", price));
+            }
+            Ok((timestamp, price))
+        }
+    }
+
+    struct Record {
+        log: Arc<Mutex<PriceCommitmentLog>>,
+    }
+
+    #[async_trait]
+    impl Step for Record {
+        type Input = (u64, u64);
+        type Output = ();
+
+        fn name(&self) -> &'static str {
+            "record_price"
+        }
+
+        async fn run(&self, &(timestamp, price): &(u64, u64)) -> Result<(), String> {
+            self.log.lock().unwrap().record(timestamp, price);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingBroadcaster {
+        anchored: Mutex<Vec<Vec<u8>>>,
+    }
+
+    impl AnchorBroadcaster for &CountingBroadcaster {
+        fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+            unreachable!("price commitments are never rebroadcast")
+        }
+
+        fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+            let mut anchored = self.anchored.lock().unwrap();
+            anchored.push(payload.to_vec());
+            Ok(format!("{:064x}", anchored.len()))
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        received: Mutex<Vec<Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl WebhookTransport for &RecordingTransport {
+        async fn post(&self, request: &WebhookRequest) -> Result<u16, String> {
+            self.received.lock().unwrap().push(request.body.clone());
+            Ok(200)
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_faults() {
+        let config = ChaosConfig::new(7)
+            .with_errors(0.2)
+            .with_corruption(0.1)
+            .with_delays(0.3, Duration::from_millis(10));
+        let a = ChaosInjector::new(config);
+        let b = ChaosInjector::new(config);
+        let rolls_a: Vec<_> = (0..200).map(|_| a.roll("a")).collect();
+        let rolls_b: Vec<_> = (0..200).map(|_| b.roll("b")).collect();
+        assert_eq!(rolls_a, rolls_b);
+
+        let stats = a.stats();
+        assert_eq!(stats.calls, 200);
+        assert!(stats.errors > 20 && stats.errors < 60, "{:?}", stats);
+        assert!(stats.corruptions > 5 && stats.delays > 30, "{:?}", stats);
+
+        let other = ChaosInjector::new(ChaosConfig { seed: 8, ..config });
+        assert_ne!(rolls_a, (0..200).map(|_| other.roll("c")).collect::<Vec<_>>());
+
+        let calm = ChaosInjector::new(ChaosConfig::new(7));
+        assert!((0..50).all(|_| calm.roll("calm").is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_price_flow_recovers_without_corrupting_commitments() {
+        let chaos = injector(
+            ChaosConfig::new(42)
+                .with_errors(0.3)
+                .with_corruption(0.1)
+                .with_delays(0.2, Duration::from_millis(80)),
+        );
+        let metrics = Arc::new(FlowMetrics::new());
+        let log = Arc::new(Mutex::new(PriceCommitmentLog::new()));
+        let flow = Flow::new("price_commitment.record", metrics.clone())
+            .then(ChaosStep::new(FixedOracle, chaos.clone()).with_corruption(|(_, price)| *price = 0))
+            .then(ValidatePrice)
+            .then(Record { log: log.clone() });
+
+        let mut recorded = BTreeMap::new();
+        for minute in 0..120 {
+            let now = DAY_START + minute * MINUTE;
+            if flow.run(now).await.is_ok() {
+                recorded.insert(now, true_price(now));
+            }
+        }
+
+        // 오류/지연(타임아웃)은 재시도로 대부분 복구, 손상은 검증에서 걸러짐
+        let stats = chaos.stats();
+        let fetch = metrics.step("price_commitment.record", "fetch_price").unwrap();
+        assert!(stats.errors > 0 && stats.corruptions > 0 && fetch.timeouts > 0);
+        assert!(fetch.failures > 0 && fetch.successes <= 120, "{:?}", fetch);
+        let validate = metrics.step("price_commitment.record", "validate_price").unwrap();
+        assert!(validate.failures > 0);
+        assert_eq!(recorded.len() as u64, fetch.successes - validate.failures);
+        assert!(recorded.len() >= 100);
+
+        // 기록된 가격은 모두 오라클 원본과 같음
+        let mut log = log.lock().unwrap();
+        let date = chrono::DateTime::from_timestamp(DAY_START as i64, 0).unwrap().date_naive();
+        let commitment = log.seal_day(date).unwrap().clone();
+        assert_eq!(commitment.prices as usize, recorded.len());
+        for (timestamp, price) in &recorded {
+            let proof = log.prove_price(*timestamp).unwrap();
+            assert_eq!(proof.price, *price);
+            assert!(proof.verify());
+        }
+
+        // 앵커링 실패는 txid 없이 남고 다음 시도에서 앵커링됨
+        let inner = CountingBroadcaster::default();
+        let broadcaster = ChaosBroadcaster::new(&inner, injector(ChaosConfig::new(3).with_errors(0.7)));
+        let mut attempts = 0;
+        while log.commitments().any(|c| c.txid.is_none()) {
+            attempts += 1;
+            log.anchor_pending(&broadcaster);
+            assert!(attempts < 50);
+        }
+        assert_eq!(inner.anchored.lock().unwrap().as_slice(), &[commitment.payload()]);
+    }
+
+    #[tokio::test]
+    async fn test_settlement_proofs_never_use_corrupted_output() {
+        let chaos = injector(ChaosConfig::new(11).with_errors(0.25).with_corruption(0.25));
+        let bridge = BitVmxBridge::with_backend(Box::new(ChaosBitVmx::new(
+            MockBitVmxBackend::new(),
+            chaos.clone(),
+        )));
+        let secp = Secp256k1::new();
+        let key = |byte: u8| PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[byte; 32]).unwrap());
+        let option = BitcoinOption {
+            option_type: OptionType::Call,
+            strike_price: 50_000_000,
+            expiry_block: 800_000,
+            buyer_pubkey: key(1),
+            seller_pubkey: key(2),
+            verifier_pubkey: key(3),
+            premium: 1_000_000,
+            collateral: 10_000_000,
+        };
+
+        let mut settled = 0;
+        for _ in 0..40 {
+            match bridge.generate_settlement_proof(&option, 52_000_000).await {
+                Ok(proof) => {
+                    assert_eq!(proof.settlement_amount, 2_000_000);
+                    settled += 1;
+                }
+                Err(e) => assert!(e.to_string().contains("chaos") || e.to_string().contains("invalid digit")),
+            }
+        }
+        let stats = chaos.stats();
+        assert_eq!(settled, 40 - stats.errors - stats.corruptions);
+        assert!(settled > 0 && stats.errors > 0 && stats.corruptions > 0);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_alerts_delivered_exactly_once_despite_faults() {
+        let chaos = injector(
+            ChaosConfig::new(5)
+                .with_errors(0.3)
+                .with_corruption(0.2)
+                .with_delays(0.2, Duration::from_millis(2)),
+        );
+        let inner = RecordingTransport::default();
+        let transport = ChaosTransport::new(&inner, chaos.clone());
+        let mut dispatcher = WebhookDispatcher::new(RetryPolicy {
+            max_attempts: 20,
+            base_delay_secs: 1,
+            max_delay_secs: 4,
+        });
+        dispatcher.register("https://ops.example".into(), "secret".into(), vec![WebhookEventKind::AnchorConfirmed]);
+        let events: Vec<WebhookEvent> = (0..10)
+            .map(|i| WebhookEvent::anchor_confirmed(&format!("{:064x}", i), "opt", 1, DAY_START))
+            .collect();
+        for event in &events {
+            dispatcher.enqueue(event, DAY_START);
+        }
+
+        for tick in 0..200 {
+            dispatcher.deliver_due(&transport, DAY_START + tick).await;
+        }
+
+        let deliveries = dispatcher.deliveries(None);
+        assert!(deliveries.iter().all(|d| d.status == DeliveryStatus::Delivered));
+        assert!(deliveries.iter().any(|d| d.attempts > 1));
+        // 실패한 시도는 수신 측에 도달하지 않았으므로 이벤트마다 정확히 한 번 수신
+        let received = inner.received.lock().unwrap();
+        assert_eq!(received.len(), events.len());
+        assert!(chaos.stats().errors + chaos.stats().corruptions > 0);
+    }
+}
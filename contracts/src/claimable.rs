@@ -0,0 +1,581 @@
+//! 사용자별 청구 가능 잔고 (모아서 출금)
+//!
+//! 정산 지급을 건별로 온체인 전송하지 않고 사용자 잔고에 적립합니다. 적립마다
+//! 풀 키로 서명한 영수증을 발행하고, 사용자가 출금을 요청하면 쌓인 잔고 전체를
+//! 한 번의 온체인 지급으로 묶습니다. 최소 출금액 미만이면 거부합니다.
+//! 출금은 지급 지갑(bitcoind)에서 `pay_withdrawal`로 보내고 txid를 기록합니다.
+
+use crate::bootstrap::BitcoindRpc;
+use anyhow::anyhow;
+use bitcoin::Amount;
+use oracle_vm_common::crypto::{
+    public_key_from_secret, sign_data, verify_signature, PublicKey, SecretKey, Signature,
+};
+use oracle_vm_common::{CanonicalEncoder, ClaimError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// 기본 최소 출금액 (satoshis)
+pub const DEFAULT_MIN_WITHDRAWAL_SATS: u64 = 10_000;
+
+/// 이미 보낸 지급을 찾을 때 확인하는 최근 지갑 트랜잭션 수
+const PAYOUT_LOOKBACK: u64 = 1_000;
+
+/// 적립 영수증 서명 대상: credit_id, user_id, option_id, amount, credited_at의 정규 인코딩
+pub fn credit_receipt_payload(
+    credit_id: u64,
+    user_id: &str,
+    option_id: &str,
+    amount: u64,
+    credited_at: u64,
+) -> Vec<u8> {
+    let mut encoder = CanonicalEncoder::new("claim/v2");
+    encoder
+        .u64(credit_id)
+        .str(user_id)
+        .str(option_id)
+        .u64(amount)
+        .u64(credited_at);
+    encoder.finish()
+}
+
+/// 정규 인코딩 도입 전 영수증 서명 대상 (이전 영수증 검증용)
+fn legacy_credit_receipt_payload(
+    credit_id: u64,
+    user_id: &str,
+    option_id: &str,
+    amount: u64,
+    credited_at: u64,
+) -> Vec<u8> {
+    format!(
+        "claim|{}|{}|{}|{}|{}",
+        credit_id, user_id, option_id, amount, credited_at
+    )
+    .into_bytes()
+}
+
+/// 잔고 적립 한 건 (서명된 영수증 포함)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimCredit {
+    pub credit_id: u64,
+    pub user_id: String,
+    pub option_id: String,
+    pub amount: u64, // satoshis
+    pub credited_at: u64,
+    /// 풀 키 서명 (DER hex)
+    pub receipt: String,
+    /// 이 적립을 지급한 출금 (출금 전이면 None)
+    pub withdrawal_id: Option<u64>,
+}
+
+impl ClaimCredit {
+    pub fn payload(&self) -> Vec<u8> {
+        credit_receipt_payload(
+            self.credit_id,
+            &self.user_id,
+            &self.option_id,
+            self.amount,
+            self.credited_at,
+        )
+    }
+
+    /// 영수증 서명 검증 (정규 인코딩 이전에 발행된 영수증도 허용)
+    pub fn verify(&self, pool_key: &PublicKey) -> bool {
+        let Ok(signature) = Signature::from_str(&self.receipt) else {
+            return false;
+        };
+        let legacy = legacy_credit_receipt_payload(
+            self.credit_id,
+            &self.user_id,
+            &self.option_id,
+            self.amount,
+            self.credited_at,
+        );
+        [self.payload(), legacy]
+            .iter()
+            .any(|payload| verify_signature(payload, &signature, pool_key).unwrap_or(false))
+    }
+}
+
+/// 모아서 지급하는 출금 한 건
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Withdrawal {
+    pub withdrawal_id: u64,
+    pub user_id: String,
+    pub address: String,
+    pub amount: u64, // satoshis
+    pub credit_ids: Vec<u64>,
+    pub requested_at: u64,
+    /// 지급 트랜잭션 (전송 전이면 None)
+    pub txid: Option<String>,
+}
+
+/// 스냅샷에 싣는 적립/출금 기록 (서명키는 설정에서 다시 받음)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimRecords {
+    pub credits: Vec<ClaimCredit>,
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+/// 청구 가능 잔고 원장
+pub struct ClaimableLedger {
+    profile: NetworkProfile,
+    signing_key: SecretKey,
+    min_withdrawal_sats: u64,
+    credits: Vec<ClaimCredit>,
+    withdrawals: Vec<Withdrawal>,
+}
+
+impl ClaimableLedger {
+    pub fn new(profile: NetworkProfile, signing_key: SecretKey, min_withdrawal_sats: u64) -> Self {
+        Self {
+            profile,
+            signing_key,
+            min_withdrawal_sats,
+            credits: Vec::new(),
+            withdrawals: Vec::new(),
+        }
+    }
+
+    /// 영수증 검증용 풀 공개키
+    pub fn public_key(&self) -> PublicKey {
+        public_key_from_secret(&self.signing_key)
+    }
+
+    pub fn min_withdrawal_sats(&self) -> u64 {
+        self.min_withdrawal_sats
+    }
+
+    /// 스냅샷용 적립/출금 기록
+    pub fn records(&self) -> ClaimRecords {
+        ClaimRecords {
+            credits: self.credits.clone(),
+            withdrawals: self.withdrawals.clone(),
+        }
+    }
+
+    /// 스냅샷의 적립/출금 기록 복원
+    pub fn restore_records(&mut self, records: ClaimRecords) {
+        self.credits = records.credits;
+        self.withdrawals = records.withdrawals;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.credits.is_empty() && self.withdrawals.is_empty()
+    }
+
+    /// 서명된 적립 생성 (원장에는 아직 반영하지 않음)
+    pub fn prepare_credit(
+        &self,
+        user_id: &str,
+        option_id: &str,
+        amount: u64,
+        now: u64,
+    ) -> Result<ClaimCredit, ClaimError> {
+        let credit_id = self.credits.len() as u64 + 1;
+        let payload = credit_receipt_payload(credit_id, user_id, option_id, amount, now);
+        let receipt = sign_data(&payload, &self.signing_key)
+            .map_err(|e| ClaimError::Signing(e.to_string()))?;
+        Ok(ClaimCredit {
+            credit_id,
+            user_id: user_id.to_string(),
+            option_id: option_id.to_string(),
+            amount,
+            credited_at: now,
+            receipt: receipt.to_string(),
+            withdrawal_id: None,
+        })
+    }
+
+    /// 준비한 적립 반영
+    pub fn apply_credit(&mut self, credit: ClaimCredit) {
+        self.credits.push(credit);
+    }
+
+    /// 아직 출금하지 않은 잔고 (satoshis)
+    pub fn balance(&self, user_id: &str) -> u64 {
+        self.open_credits(user_id).map(|credit| credit.amount).sum()
+    }
+
+    /// 사용자의 적립 내역
+    pub fn credits(&self, user_id: &str) -> Vec<&ClaimCredit> {
+        self.credits
+            .iter()
+            .filter(|credit| credit.user_id == user_id)
+            .collect()
+    }
+
+    /// 사용자의 출금 내역
+    pub fn withdrawals(&self, user_id: &str) -> Vec<&Withdrawal> {
+        self.withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.user_id == user_id)
+            .collect()
+    }
+
+    /// 지급 트랜잭션을 기다리는 출금
+    pub fn pending_withdrawals(&self) -> impl Iterator<Item = &Withdrawal> {
+        self.withdrawals.iter().filter(|withdrawal| withdrawal.txid.is_none())
+    }
+
+    /// 모든 사용자의 미출금 적립
+    pub fn unwithdrawn(&self) -> impl Iterator<Item = &ClaimCredit> {
+        self.credits.iter().filter(|credit| credit.withdrawal_id.is_none())
+    }
+
+    fn open_credits<'a>(&'a self, user_id: &'a str) -> impl Iterator<Item = &'a ClaimCredit> {
+        self.credits
+            .iter()
+            .filter(move |credit| credit.user_id == user_id && credit.withdrawal_id.is_none())
+    }
+
+    /// 잔고 전체를 한 건의 출금으로 묶음
+    pub fn withdraw(&mut self, user_id: &str, address: &str, now: u64) -> Result<Withdrawal, ClaimError> {
+        let withdrawal = self.prepare_withdrawal(user_id, address, now)?;
+        self.apply_withdrawal(withdrawal.clone());
+        Ok(withdrawal)
+    }
+
+    /// 출금 검사 후 출금 생성 (원장에는 아직 반영하지 않음)
+    pub fn prepare_withdrawal(&self, user_id: &str, address: &str, now: u64) -> Result<Withdrawal, ClaimError> {
+        self.prepare_withdrawal_where(user_id, address, now, self.min_withdrawal_sats, |_| true)
+    }
+
+    /// 조건에 맞는 미출금 적립만 묶은 출금 생성 (수익자 지급처럼 따로 보내는 적립용)
+    pub fn prepare_withdrawal_where(
+        &self,
+        user_id: &str,
+        address: &str,
+        now: u64,
+        min_sats: u64,
+        include: impl Fn(&ClaimCredit) -> bool,
+    ) -> Result<Withdrawal, ClaimError> {
+        self.profile
+            .validate_address(address)
+            .map_err(|e| ClaimError::InvalidAddress {
+                address: address.to_string(),
+                reason: e.to_string(),
+            })?;
+        let credits: Vec<&ClaimCredit> = self.open_credits(user_id).filter(|credit| include(credit)).collect();
+        let amount = credits.iter().map(|credit| credit.amount).sum();
+        if amount == 0 || amount < min_sats {
+            return Err(ClaimError::BelowMinimum { amount, min: min_sats });
+        }
+
+        Ok(Withdrawal {
+            withdrawal_id: self.withdrawals.len() as u64 + 1,
+            user_id: user_id.to_string(),
+            address: address.to_string(),
+            amount,
+            credit_ids: credits.iter().map(|credit| credit.credit_id).collect(),
+            requested_at: now,
+            txid: None,
+        })
+    }
+
+    /// 준비한 출금 반영 (묶인 적립은 출금 처리)
+    pub fn apply_withdrawal(&mut self, withdrawal: Withdrawal) {
+        for credit in self
+            .credits
+            .iter_mut()
+            .filter(|credit| withdrawal.credit_ids.contains(&credit.credit_id))
+        {
+            credit.withdrawal_id = Some(withdrawal.withdrawal_id);
+        }
+        self.withdrawals.push(withdrawal);
+    }
+
+    /// 지급 트랜잭션을 기록할 수 있는지 확인 (아직 전송하지 않은 출금이어야 함)
+    pub fn check_broadcast(&self, withdrawal_id: u64) -> Result<(), ClaimError> {
+        let withdrawal = self
+            .withdrawals
+            .iter()
+            .find(|withdrawal| withdrawal.withdrawal_id == withdrawal_id)
+            .ok_or(ClaimError::WithdrawalNotFound(withdrawal_id))?;
+        if withdrawal.txid.is_some() {
+            return Err(ClaimError::AlreadyBroadcast(withdrawal_id));
+        }
+        Ok(())
+    }
+
+    /// 지급 트랜잭션 전송 기록
+    pub fn mark_broadcast(&mut self, withdrawal_id: u64, txid: &str) -> Result<(), ClaimError> {
+        self.check_broadcast(withdrawal_id)?;
+        if let Some(withdrawal) = self
+            .withdrawals
+            .iter_mut()
+            .find(|withdrawal| withdrawal.withdrawal_id == withdrawal_id)
+        {
+            withdrawal.txid = Some(txid.to_string());
+        }
+        Ok(())
+    }
+
+    /// 사용자별 미출금 잔고 합계 (풀 부채)
+    pub fn liabilities(&self) -> HashMap<String, u64> {
+        let mut totals = HashMap::new();
+        for credit in self.credits.iter().filter(|credit| credit.withdrawal_id.is_none()) {
+            *totals.entry(credit.user_id.clone()).or_default() += credit.amount;
+        }
+        totals
+    }
+}
+
+/// 출금 지급 트랜잭션 메모 (출금 ID는 풀마다 따로 매기므로 풀 이름 포함)
+pub fn payout_comment(pool: &str, withdrawal_id: u64) -> String {
+    format!("btcfi:{}:withdrawal:{}", pool, withdrawal_id)
+}
+
+/// 출금을 bitcoind 지급 지갑에서 보내고 txid 반환
+///
+/// 같은 메모로 보낸 지급이 지갑에 이미 있으면 다시 보내지 않고 그 txid를 돌려주므로,
+/// 전송 후 기록 전에 멈췄다가 다시 돌아도 이중 지급하지 않습니다.
+pub async fn pay_withdrawal(
+    rpc: &dyn BitcoindRpc,
+    wallet: &str,
+    pool: &str,
+    withdrawal: &Withdrawal,
+) -> anyhow::Result<String> {
+    let comment = payout_comment(pool, withdrawal.withdrawal_id);
+    let recent = rpc
+        .call(
+            Some(wallet),
+            "listtransactions",
+            vec![json!("*"), json!(PAYOUT_LOOKBACK), json!(0), json!(false)],
+        )
+        .await?;
+    let sent = recent.as_array().into_iter().flatten().find(|tx| {
+        tx["category"] == "send" && tx["comment"] == comment.as_str() && tx["address"] == withdrawal.address.as_str()
+    });
+    if let Some(txid) = sent.and_then(|tx| tx["txid"].as_str()) {
+        return Ok(txid.to_string());
+    }
+
+    let txid = rpc
+        .call(
+            Some(wallet),
+            "sendtoaddress",
+            vec![
+                json!(withdrawal.address),
+                json!(Amount::from_sat(withdrawal.amount).to_btc()),
+                json!(comment),
+            ],
+        )
+        .await?;
+    txid.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("sendtoaddress returned {}", txid))
+}
+
+/// 청구 잔고 HTTP API (`/claims/*`)
+pub mod api {
+    use crate::account_keys::AccountSignature;
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use oracle_vm_common::ClaimError;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct WithdrawRequest {
+        pub address: String,
+        /// 보유자 키로 `account/withdraw/v1` (user_id, address, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/claims/{user_id}",
+        tag = "claims",
+        params(("user_id" = String, Path)),
+        responses(
+            (status = 200, body = Object),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn get_claims(Path(user_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let Some(claims) = manager.claims() else {
+            return error_response(ClaimError::Disabled);
+        };
+        Json(json!({
+            "user_id": user_id,
+            "balance": claims.balance(&user_id),
+            "min_withdrawal": claims.min_withdrawal_sats(),
+            "pool_key": claims.public_key().to_string(),
+            "credits": claims.credits(&user_id),
+            "withdrawals": claims.withdrawals(&user_id),
+        }))
+        .into_response()
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/claims/{user_id}/withdraw",
+        tag = "claims",
+        params(("user_id" = String, Path)),
+        request_body = WithdrawRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn withdraw(
+        Path(user_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<WithdrawRequest>,
+    ) -> Response {
+        if request.address.is_empty() {
+            return bad_request("address is required");
+        }
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.withdraw_claims(&user_id, &request.address, &request.auth) {
+            Ok(withdrawal) => Json(withdrawal).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/claims` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/claims/:user_id", get(get_claims))
+            .route("/claims/:user_id/withdraw", post(withdraw))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::crypto::generate_keypair;
+
+    const ADDRESS: &str = "tb1qerq9kwplk0we7ql3agkapdt39d0ahmtvsptj3e";
+
+    #[test]
+    fn test_credits_aggregate_into_one_withdrawal() {
+        let (secret_key, _) = generate_keypair();
+        let mut ledger = ClaimableLedger::new(NetworkProfile::TESTNET, secret_key, 10_000);
+
+        for (option_id, amount) in [("CALL-1", 6_000), ("PUT-1", 500), ("CALL-2", 5_000)] {
+            let credit = ledger.prepare_credit("alice", option_id, amount, 1_000).unwrap();
+            assert!(credit.verify(&ledger.public_key()));
+            ledger.apply_credit(credit);
+        }
+        assert_eq!(ledger.balance("alice"), 11_500);
+
+        // 변조된 영수증은 검증 실패
+        let mut forged = ledger.credits("alice")[0].clone();
+        forged.amount = 60_000;
+        assert!(!forged.verify(&ledger.public_key()));
+
+        assert!(matches!(
+            ledger.withdraw("alice", "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", 2_000),
+            Err(ClaimError::InvalidAddress { .. })
+        ));
+        let withdrawal = ledger.withdraw("alice", ADDRESS, 2_000).unwrap();
+        assert_eq!((withdrawal.amount, withdrawal.credit_ids.clone()), (11_500, vec![1, 2, 3]));
+        assert_eq!(ledger.balance("alice"), 0);
+        assert_eq!(ledger.pending_withdrawals().count(), 1);
+
+        let credit = ledger.prepare_credit("alice", "CALL-3", 500, 3_000).unwrap();
+        ledger.apply_credit(credit);
+        assert_eq!(
+            ledger.withdraw("alice", ADDRESS, 3_000),
+            Err(ClaimError::BelowMinimum { amount: 500, min: 10_000 })
+        );
+
+        ledger.mark_broadcast(1, "txid-1").unwrap();
+        assert_eq!(ledger.mark_broadcast(1, "txid-2"), Err(ClaimError::AlreadyBroadcast(1)));
+        assert_eq!(ledger.liabilities()["alice"], 500);
+    }
+
+    #[test]
+    fn test_legacy_receipts_still_verify() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut credit = ClaimCredit {
+            credit_id: 1,
+            user_id: "alice".to_string(),
+            option_id: "CALL-1".to_string(),
+            amount: 6_000,
+            credited_at: 1_000,
+            receipt: String::new(),
+            withdrawal_id: None,
+        };
+        let legacy = legacy_credit_receipt_payload(1, "alice", "CALL-1", 6_000, 1_000);
+        credit.receipt = sign_data(&legacy, &secret_key).unwrap().to_string();
+        assert!(credit.verify(&public_key));
+
+        credit.amount = 60_000;
+        assert!(!credit.verify(&public_key));
+    }
+
+    /// 지급 지갑 흉내 (sendtoaddress 호출을 기억하고 listtransactions로 돌려줌)
+    #[derive(Default)]
+    struct PayoutWallet {
+        sent: std::sync::Mutex<Vec<serde_json::Value>>,
+    }
+
+    #[async_trait::async_trait]
+    impl BitcoindRpc for PayoutWallet {
+        async fn call(
+            &self,
+            wallet: Option<&str>,
+            method: &str,
+            params: Vec<serde_json::Value>,
+        ) -> anyhow::Result<serde_json::Value> {
+            assert_eq!(wallet, Some("payout"));
+            let mut sent = self.sent.lock().unwrap();
+            Ok(match method {
+                "listtransactions" => json!(*sent),
+                "sendtoaddress" => {
+                    let txid = format!("{:064x}", sent.len() + 1);
+                    sent.push(json!({
+                        "category": "send",
+                        "address": params[0],
+                        "comment": params[2],
+                        "txid": txid,
+                    }));
+                    json!(txid)
+                }
+                _ => serde_json::Value::Null,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_payout_is_not_sent_twice() {
+        let (secret_key, _) = generate_keypair();
+        let mut ledger = ClaimableLedger::new(NetworkProfile::TESTNET, secret_key, 10_000);
+        let credit = ledger.prepare_credit("alice", "CALL-1", 12_000, 1_000).unwrap();
+        ledger.apply_credit(credit);
+        let withdrawal = ledger.withdraw("alice", ADDRESS, 2_000).unwrap();
+
+        // 전송 후 기록 전에 멈췄다가 다시 돌아도 같은 txid
+        let wallet = PayoutWallet::default();
+        let txid = pay_withdrawal(&wallet, "payout", "default", &withdrawal).await.unwrap();
+        assert_eq!(pay_withdrawal(&wallet, "payout", "default", &withdrawal).await.unwrap(), txid);
+        assert_eq!(wallet.sent.lock().unwrap().len(), 1);
+        assert_eq!(wallet.sent.lock().unwrap()[0]["comment"], "btcfi:default:withdrawal:1");
+
+        // 다른 풀의 같은 출금 ID는 따로 지급
+        assert_ne!(pay_withdrawal(&wallet, "payout", "acme", &withdrawal).await.unwrap(), txid);
+        ledger.mark_broadcast(withdrawal.withdrawal_id, &txid).unwrap();
+        assert_eq!(ledger.pending_withdrawals().count(), 0);
+    }
+}
@@ -0,0 +1,564 @@
+//! 동시 처리용 컨트랙트 서비스
+//!
+//! SimpleContractManager는 모든 작업에 `&mut self`가 필요해 서버에서 전역 잠금 하나로
+//! 감싸야 합니다. ContractService는 옵션별 상태를 샤딩된 DashMap 엔트리 잠금으로,
+//! 풀 회계(원장/이벤트/인덱스)는 짧은 Mutex 구간으로 나눠, 서로 다른 옵션에 대한
+//! 호가 검증/생성/정산이 원장 반영 순간을 제외하고 동시에 진행되게 합니다.
+//!
+//! 잠금 순서는 항상 옵션 엔트리 → 풀입니다. 풀 잠금을 잡은 채 옵션 엔트리를 잠그지 않습니다.
+
+use crate::eligibility::{EligibilityProvider, EligibilityRequest};
+use crate::event_store::{EventStore, InMemoryEventStore, PoolEvent, PoolEventKind};
+use crate::option_index::OptionIndex;
+use crate::pool_ledger::{PoolLedger, Posting};
+use crate::simple_contract::{
+    collateral_for, exercised_amount, CreateOptionRequest, OptionStatus, OptionTerms, SimpleOption,
+    SimplePoolState, TradingHalt,
+};
+use dashmap::mapref::entry::Entry;
+use dashmap::{DashMap, DashSet};
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{
+    ContractError, ContractSpec, DustHandling, Exercise, ExercisePolicy, ExpiryCalendar,
+    OptionQuote, SettlementError, SharedClock, SystemClock, SystemEvent,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// 풀 회계 상태 (한 번에 한 작업만 반영)
+struct PoolCore {
+    state: SimplePoolState,
+    ledger: PoolLedger,
+    event_store: Box<dyn EventStore>,
+    index: OptionIndex,
+    /// 사용자 → 누적된 dust 지급액 (satoshis)
+    dust_balances: HashMap<String, u64>,
+    clock: SharedClock,
+}
+
+impl PoolCore {
+    fn record_event(&mut self, kind: PoolEventKind) -> Result<(), String> {
+        let timestamp = self.clock.now();
+        self.event_store
+            .append(timestamp, kind)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// 동시 호출 가능한 컨트랙트 서비스
+pub struct ContractService {
+    options: DashMap<String, SimpleOption>,
+    pool: Mutex<PoolCore>,
+    trading_halt: RwLock<Option<TradingHalt>>,
+    /// 확정 호가 서명 공개키 (설정 시 호가 없는 옵션 생성 거부)
+    quote_key: Option<PublicKey>,
+    used_quotes: DashSet<String>,
+    /// 만기 캘린더 (설정 시 OTC가 아닌 호가는 캘린더 만기만 허용)
+    calendar: Option<ExpiryCalendar>,
+    /// 표준 계약 단위/프리미엄 틱 (설정 시 규격 외 수량/프리미엄 거부)
+    contract_spec: Option<ContractSpec>,
+    /// 자동 행사/dust 지급 정책
+    exercise_policy: ExercisePolicy,
+    /// 구매 자격 확인 (설정 시 모든 생성 경로에서 확인)
+    eligibility: Option<Arc<dyn EligibilityProvider>>,
+    /// 옵션 ID → 생성 시 자격 확인 결과 해시
+    eligibility_hashes: DashMap<String, String>,
+    /// 호가 만료/거래 중단 재개 판단용 시계
+    clock: SharedClock,
+}
+
+impl ContractService {
+    pub fn new() -> Self {
+        Self::with_event_store(Box::new(InMemoryEventStore::new()))
+    }
+
+    pub fn with_event_store(event_store: Box<dyn EventStore>) -> Self {
+        let clock = SystemClock::shared();
+        Self {
+            options: DashMap::new(),
+            pool: Mutex::new(PoolCore {
+                state: SimplePoolState::new(),
+                ledger: PoolLedger::new(),
+                event_store,
+                index: OptionIndex::new(),
+                dust_balances: HashMap::new(),
+                clock: clock.clone(),
+            }),
+            trading_halt: RwLock::new(None),
+            quote_key: None,
+            used_quotes: DashSet::new(),
+            calendar: None,
+            contract_spec: None,
+            exercise_policy: ExercisePolicy::default(),
+            eligibility: None,
+            eligibility_hashes: DashMap::new(),
+            clock,
+        }
+    }
+
+    /// 시계 교체 (테스트에서 ManualClock으로 시간 이동)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.pool.get_mut().unwrap().clock = clock.clone();
+        self.clock = clock;
+        self
+    }
+
+    /// Calculation 호가 서명키 등록, 이후 옵션은 확정 호가로만 생성
+    pub fn with_quote_key(mut self, quote_key: PublicKey) -> Self {
+        self.quote_key = Some(quote_key);
+        self
+    }
+
+    /// 만기 캘린더 등록, 이후 호가 만기가 캘린더에 없으면 거부 (OTC 호가 제외)
+    pub fn with_calendar(mut self, calendar: ExpiryCalendar) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// 표준 계약 규격 등록, 이후 모든 생성 경로에서 수량/프리미엄 규격 검사
+    pub fn with_contract_spec(mut self, spec: ContractSpec) -> Self {
+        self.contract_spec = Some(spec);
+        self
+    }
+
+    /// 자동 행사 정책 설정, 이후 호가의 정책이 다르면 거부
+    pub fn with_exercise_policy(mut self, policy: ExercisePolicy) -> Self {
+        self.exercise_policy = policy;
+        self
+    }
+
+    /// 구매 자격 확인 훅 등록, 이후 거부된 사용자는 옵션 생성 불가
+    pub fn with_eligibility(mut self, provider: Arc<dyn EligibilityProvider>) -> Self {
+        self.eligibility = Some(provider);
+        self
+    }
+
+    /// 옵션 생성 시 자격 확인 결과 해시 (확인 없이 생성됐으면 None)
+    pub async fn eligibility_hash(&self, option_id: &str) -> Option<String> {
+        self.eligibility_hashes.get(option_id).map(|hash| hash.clone())
+    }
+
+    /// 자격 확인 (훅이 없으면 통과), 거부는 풀 이벤트로 남김
+    async fn check_eligibility(&self, request: &EligibilityRequest) -> Result<Option<String>, ContractError> {
+        let Some(provider) = &self.eligibility else {
+            return Ok(None);
+        };
+        let decision = provider.check(request).await?;
+        let decision_hash = decision.hash(request);
+        if let Err(e) = decision.ensure_eligible(request) {
+            self.pool
+                .lock()
+                .unwrap()
+                .record_event(PoolEventKind::EligibilityDenied {
+                    option_id: request.option_id.clone(),
+                    user_id: request.user_id.clone(),
+                    provider: decision.provider,
+                    reason: decision.reason,
+                    decision_hash,
+                })
+                .map_err(ContractError::Storage)?;
+            return Err(e);
+        }
+        Ok(Some(decision_hash))
+    }
+
+    /// 사용자의 누적 dust 지급액 (satoshis)
+    pub async fn dust_balance(&self, user_id: &str) -> u64 {
+        let pool = self.pool.lock().unwrap();
+        pool.dust_balances.get(user_id).copied().unwrap_or(0)
+    }
+
+    /// Aggregator의 거래 중단/재개 이벤트 반영
+    pub async fn apply_system_event(&self, event: &SystemEvent) {
+        let mut halt = self.trading_halt.write().unwrap();
+        match event {
+            SystemEvent::TradingHalted {
+                reason,
+                timestamp,
+                resume_at,
+            } => {
+                *halt = Some(TradingHalt {
+                    reason: reason.clone(),
+                    halted_at: *timestamp,
+                    resume_at: *resume_at,
+                });
+            }
+            SystemEvent::TradingResumed { .. } => *halt = None,
+        }
+    }
+
+    /// 현재 거래 중단 사유 (자동 재개 시각이 지났으면 해제)
+    fn halt_reason(&self) -> Option<String> {
+        let now = self.clock.now();
+        {
+            let halt = self.trading_halt.read().unwrap();
+            match halt.as_ref() {
+                None => return None,
+                Some(h) if h.resume_at.is_none_or(|resume_at| now < resume_at) => {
+                    return Some(h.reason.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        *self.trading_halt.write().unwrap() = None;
+        None
+    }
+
+    /// 풀 상태 스냅샷
+    pub async fn pool_state(&self) -> SimplePoolState {
+        self.pool.lock().unwrap().state.clone()
+    }
+
+    /// 풀 이벤트 기록 (시퀀스 순 복사본)
+    pub async fn events(&self) -> Vec<PoolEvent> {
+        self.pool.lock().unwrap().event_store.events().to_vec()
+    }
+
+    pub async fn get_option(&self, option_id: &str) -> Option<SimpleOption> {
+        self.options.get(option_id).map(|option| option.clone())
+    }
+
+    /// 만료된 활성 옵션 조회
+    pub async fn get_expired_options(&self, current_height: u32) -> Vec<SimpleOption> {
+        // 풀 잠금 안에서는 ID만 수집 (옵션 엔트리 잠금과 순서가 엇갈리지 않도록)
+        let ids: Vec<String> = {
+            let pool = self.pool.lock().unwrap();
+            pool.index
+                .by_expiry_range(..=current_height)
+                .filter(|id| pool.index.has_status(id, OptionStatus::Active))
+                .cloned()
+                .collect()
+        };
+        ids.iter()
+            .filter_map(|id| self.options.get(id).map(|option| option.clone()))
+            .collect()
+    }
+
+    /// 유동성 추가
+    pub async fn add_liquidity(&self, amount: u64) -> Result<(), ContractError> {
+        let mut pool = self.pool.lock().unwrap();
+        let pending = pool
+            .ledger
+            .prepare(&pool.state, "liquidity", vec![Posting::deposit(amount)], 0)?;
+        pool.record_event(PoolEventKind::LiquidityAdded {
+            provider_id: None,
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        let core = &mut *pool;
+        core.ledger.commit(&mut core.state, pending);
+        Ok(())
+    }
+
+    /// 옵션 생성
+    pub async fn create_option(&self, request: CreateOptionRequest) -> Result<(), ContractError> {
+        if self.quote_key.is_some() {
+            return Err(ContractError::QuoteRequired);
+        }
+        let eligibility_hash = self
+            .check_eligibility(&EligibilityRequest {
+                user_id: request.user_id.clone(),
+                address: None,
+                option_id: request.option_id.clone(),
+            })
+            .await?;
+        self.open_option(request, eligibility_hash)
+    }
+
+    /// 확정 호가로 옵션 생성 (서명 검증은 잠금 밖에서 수행)
+    pub async fn create_option_from_quote(
+        &self,
+        quote: &OptionQuote,
+        option_id: String,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        let quote_key = self.quote_key.ok_or(ContractError::QuoteKeyMissing)?;
+        quote
+            .verify(&quote_key)
+            .map_err(|e| ContractError::InvalidQuote(e.to_string()))?;
+
+        let now = self.clock.now();
+        if quote.is_expired(now) {
+            return Err(ContractError::QuoteExpired {
+                quote_id: quote.quote_id.clone(),
+                valid_until: quote.valid_until,
+            });
+        }
+
+        if quote.exercise != self.exercise_policy {
+            return Err(ContractError::InvalidQuote(format!(
+                "Quote {} discloses a different exercise policy",
+                quote.quote_id
+            )));
+        }
+        if let Some(calendar) = &self.calendar {
+            calendar.resolve(&quote.expiry, quote.issued_at, quote.otc)?;
+        }
+
+        let eligibility_hash = self
+            .check_eligibility(&EligibilityRequest {
+                user_id: user_id.clone(),
+                address: None,
+                option_id: option_id.clone(),
+            })
+            .await?;
+
+        // 호가 선점 후 생성 실패 시 반환
+        if !self.used_quotes.insert(quote.quote_id.clone()) {
+            return Err(ContractError::QuoteReused(quote.quote_id.clone()));
+        }
+        let request = CreateOptionRequest {
+            option_id,
+            option_type: quote.option_type,
+            strike_price: quote.strike_price,
+            quantity: quote.quantity,
+            premium: quote.premium,
+            expiry_height,
+            user_id,
+            referral_code: None,
+        };
+        let result = self.open_option(request, eligibility_hash);
+        if result.is_err() {
+            self.used_quotes.remove(&quote.quote_id);
+        }
+        result
+    }
+
+    fn open_option(
+        &self,
+        request: CreateOptionRequest,
+        eligibility_hash: Option<String>,
+    ) -> Result<(), ContractError> {
+        if let Some(reason) = self.halt_reason() {
+            return Err(ContractError::TradingHalted(reason));
+        }
+
+        if let Some(spec) = &self.contract_spec {
+            spec.contracts(request.quantity)?;
+            spec.check_premium(request.premium)?;
+        }
+
+        // 같은 ID에 대한 동시 생성은 엔트리 잠금으로 직렬화
+        let entry = match self.options.entry(request.option_id.clone()) {
+            Entry::Occupied(_) => return Err(ContractError::DuplicateOption(request.option_id)),
+            Entry::Vacant(entry) => entry,
+        };
+
+        let collateral = collateral_for(request.option_type, request.strike_price, request.quantity);
+        let option = SimpleOption {
+            option_id: request.option_id.clone(),
+            option_type: request.option_type,
+            strike_price: request.strike_price,
+            quantity: request.quantity,
+            premium_paid: request.premium,
+            expiry_height: request.expiry_height,
+            status: OptionStatus::Active,
+            user_id: request.user_id.clone(),
+            terms: OptionTerms::default(),
+        };
+
+        let mut pool = self.pool.lock().unwrap();
+        if pool.state.available_liquidity < collateral {
+            return Err(ContractError::InsufficientLiquidity {
+                required: collateral,
+                available: pool.state.available_liquidity,
+            });
+        }
+        let pending = pool.ledger.prepare(
+            &pool.state,
+            request.option_id.as_str(),
+            vec![Posting::lock(collateral), Posting::premium(request.premium)],
+            1,
+        )?;
+        pool.record_event(PoolEventKind::OptionCreated {
+            option_id: request.option_id,
+            option_type: request.option_type,
+            strike_price: request.strike_price,
+            quantity: request.quantity,
+            premium: request.premium,
+            collateral,
+            user_id: request.user_id,
+        })
+        .map_err(ContractError::Storage)?;
+
+        let core = &mut *pool;
+        core.ledger.commit(&mut core.state, pending);
+        core.index.insert(&option);
+        if let Some(hash) = eligibility_hash {
+            self.eligibility_hashes.insert(option.option_id.clone(), hash);
+        }
+        entry.insert(option);
+        Ok(())
+    }
+
+    /// 옵션 정산 (같은 옵션의 동시 정산은 엔트리 잠금으로 한 번만 성공)
+    pub async fn settle_option(
+        &self,
+        option_id: &str,
+        spot_price: u64,
+    ) -> Result<u64, SettlementError> {
+        if let Some(reason) = self.halt_reason() {
+            return Err(SettlementError::Postponed(reason));
+        }
+
+        let mut option = self
+            .options
+            .get_mut(option_id)
+            .ok_or_else(|| SettlementError::OptionNotFound(option_id.to_string()))?;
+        if option.status != OptionStatus::Active {
+            return Err(SettlementError::OptionNotActive(option_id.to_string()));
+        }
+
+        let exercise = self.exercise_policy.apply(option.payout_at(spot_price));
+        let payout = exercised_amount(&exercise);
+        let dust = match exercise {
+            Exercise::Dust { amount, .. } => amount,
+            _ => 0,
+        };
+        let collateral = option.collateral();
+
+        let mut pool = self.pool.lock().unwrap();
+        let pending = pool
+            .ledger
+            .prepare(
+                &pool.state,
+                option_id,
+                vec![Posting::payout(payout), Posting::release(collateral.saturating_sub(payout))],
+                -1,
+            )
+            .map_err(|e| SettlementError::Ledger(e.to_string()))?;
+        pool.record_event(PoolEventKind::OptionSettled {
+            option_id: option_id.to_string(),
+            spot_price,
+            payout,
+            dust,
+        })
+        .map_err(SettlementError::Storage)?;
+
+        let core = &mut *pool;
+        core.ledger.commit(&mut core.state, pending);
+        core.index
+            .update_status(option_id, option.status, OptionStatus::Settled);
+        option.status = OptionStatus::Settled;
+        if let Exercise::Dust {
+            amount,
+            handling: DustHandling::Accumulate,
+        } = exercise
+        {
+            *core.dust_balances.entry(option.user_id.clone()).or_default() += amount;
+        }
+
+        Ok(match exercise {
+            Exercise::Paid { amount } => amount,
+            _ => 0,
+        })
+    }
+}
+
+impl Default for ContractService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::types::OptionType;
+    use std::sync::Arc;
+
+    fn request(option_id: String) -> CreateOptionRequest {
+        CreateOptionRequest {
+            option_id,
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            quantity: 1_000_000,
+            premium: 25_000,
+            expiry_height: 800_000,
+            user_id: "user".to_string(),
+            referral_code: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_create_and_settle() {
+        let service = Arc::new(ContractService::new());
+        service.add_liquidity(100_000_000).await.unwrap();
+
+        let handles: Vec<_> = (0..32)
+            .map(|i| {
+                let service = service.clone();
+                tokio::spawn(async move {
+                    let option_id = format!("OPT-{}", i);
+                    service.create_option(request(option_id.clone())).await.unwrap();
+                    service.settle_option(&option_id, 7_100_000).await.unwrap()
+                })
+            })
+            .collect();
+
+        let mut total_payout = 0;
+        for handle in handles {
+            total_payout += handle.await.unwrap();
+        }
+
+        let pool = service.pool_state().await;
+        assert_eq!(pool.active_options, 0);
+        assert_eq!(pool.locked_collateral, 0);
+        assert_eq!(pool.total_payout, total_payout);
+        assert_eq!(pool.total_liquidity, 100_000_000 + 32 * 25_000 - total_payout);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_settle_pays_once() {
+        let service = Arc::new(ContractService::new());
+        service.add_liquidity(100_000_000).await.unwrap();
+        service.create_option(request("OPT-X".to_string())).await.unwrap();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let service = service.clone();
+                tokio::spawn(async move { service.settle_option("OPT-X", 7_500_000).await })
+            })
+            .collect();
+
+        let mut settled = 0;
+        for handle in handles {
+            match handle.await.unwrap() {
+                Ok(_) => settled += 1,
+                Err(e) => assert_eq!(e, SettlementError::OptionNotActive("OPT-X".to_string())),
+            }
+        }
+        assert_eq!(settled, 1);
+        assert!(service.get_expired_options(800_000).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_eligibility_denial_is_typed_and_recorded() {
+        use crate::eligibility::AllowlistEligibility;
+
+        let service = ContractService::new()
+            .with_eligibility(Arc::new(AllowlistEligibility::new(["user".to_string()])));
+        service.add_liquidity(100_000_000).await.unwrap();
+
+        service.create_option(request("OPT-OK".to_string())).await.unwrap();
+        let hash = service.eligibility_hash("OPT-OK").await.unwrap();
+        assert_eq!(hash.len(), 64);
+
+        let denied = CreateOptionRequest {
+            user_id: "mallory".to_string(),
+            ..request("OPT-NO".to_string())
+        };
+        let err = service.create_option(denied).await.unwrap_err();
+        assert!(matches!(err, ContractError::Ineligible { ref user_id, .. } if user_id == "mallory"));
+        assert!(!oracle_vm_common::ErrorClass::is_retryable(&err));
+        assert!(service.get_option("OPT-NO").await.is_none());
+        assert_eq!(service.pool_state().await.active_options, 1);
+        assert!(service.events().await.iter().any(|event| matches!(
+            &event.kind,
+            PoolEventKind::EligibilityDenied { user_id, provider, .. } if user_id == "mallory" && provider == "allowlist"
+        )));
+    }
+}
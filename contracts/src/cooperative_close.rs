@@ -0,0 +1,785 @@
+//! 협의 해지 (만기 전 구매자/풀 공동 서명 조기 종료)
+//!
+//! 옵션 Taproot 출력의 협의 해지 경로(`<buyer> CHECKSIGVERIFY <pool> CHECKSIG`)를
+//! 쓰는 흐름입니다.
+//!
+//! 1. 보유자 또는 풀이 분배(보유자 몫, 나머지는 풀)를 제안하며 자기 서명을 넣은
+//!    해지 PSBT와 Calculation 되사기 호가(현재 이론가)를 제출합니다.
+//! 2. 상대가 같은 PSBT에 서명해 돌려주면 컨트랙트 서비스가 두 서명과 출력을
+//!    확인하고, 분배를 그 시점의 이론가와 다시 대조한 뒤 witness를 완성해 전송합니다.
+//! 3. 옵션은 Cancelled로 닫히고 감사 기록(`CooperativelyClosed`)과 CNL 앵커로 남습니다.
+//!
+//! 해지 트랜잭션은 옵션 UTXO를 쓰므로 정산 트랜잭션과 같이 풀 관리자의
+//! `SettlementBroadcastManager`로 전송해 같은 UTXO의 이중 지출을 막습니다.
+//! 대기 중인 제안은 파일에 남겨 재시작 후에도 상대방이 이어서 서명할 수 있습니다.
+
+use crate::anchor_tracker::AnchorBroadcaster;
+use crate::simple_contract::SimpleContractManager;
+use crate::taproot_address::{LeafKind, OptionTaprootRecord, TaprootLeaf};
+use bitcoin::hashes::Hash;
+use bitcoin::psbt::Psbt;
+use bitcoin::script::Instruction;
+use bitcoin::secp256k1::{Keypair, Message, Secp256k1, SecretKey};
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use bitcoin::taproot::{self, LeafVersion, TapLeafHash};
+use bitcoin::{absolute::LockTime, transaction, Amount, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness, XOnlyPublicKey};
+use oracle_vm_common::{BuyBackQuote, ContractError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// 보유자 몫이 이론가에서 벗어날 수 있는 최대 편차 (bps)
+pub const DEFAULT_MAX_DEVIATION_BPS: u64 = 200;
+
+/// 해지 당사자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloseParty {
+    Holder,
+    Pool,
+}
+
+impl CloseParty {
+    pub fn counterparty(self) -> Self {
+        match self {
+            Self::Holder => Self::Pool,
+            Self::Pool => Self::Holder,
+        }
+    }
+}
+
+/// 대기 중인 해지 제안
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseProposal {
+    pub option_id: String,
+    pub proposer: CloseParty,
+    /// 옵션 보유자
+    pub user_id: String,
+    /// 분배 기준 이론가를 담은 되사기 호가
+    pub quote: BuyBackQuote,
+    pub holder_amount: u64, // satoshis
+    pub holder_script: ScriptBuf,
+    pub pool_script: ScriptBuf,
+    /// 제안자 서명이 들어간 PSBT (hex)
+    pub psbt: String,
+    pub proposed_at: u64,
+}
+
+/// 해지 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CloseOutcome {
+    pub option_id: String,
+    pub txid: String,
+    pub holder_amount: u64, // satoshis
+    pub pool_amount: u64,   // satoshis, 수수료 제외
+    /// CNL 앵커 페이로드 (hex)
+    pub cancel_anchor: Option<String>,
+    pub anchor_txid: Option<String>,
+}
+
+fn reject<T>(option_id: &str, reason: impl std::fmt::Display) -> Result<T, ContractError> {
+    Err(ContractError::CooperativeClose(format!("{}: {}", option_id, reason)))
+}
+
+fn cooperative_leaf(record: &OptionTaprootRecord) -> Result<&TaprootLeaf, ContractError> {
+    match record.leaf(LeafKind::Cooperative) {
+        Some(leaf) => Ok(leaf),
+        None => reject(&record.option_id, "no cooperative leaf"),
+    }
+}
+
+/// 협의 해지 리프의 (구매자, 풀) 키
+pub fn cooperative_keys(record: &OptionTaprootRecord) -> Result<(XOnlyPublicKey, XOnlyPublicKey), ContractError> {
+    let keys: Vec<XOnlyPublicKey> = cooperative_leaf(record)?
+        .script
+        .instructions()
+        .filter_map(|instruction| match instruction {
+            Ok(Instruction::PushBytes(bytes)) => XOnlyPublicKey::from_slice(bytes.as_bytes()).ok(),
+            _ => None,
+        })
+        .collect();
+    match keys[..] {
+        [buyer, pool] => Ok((buyer, pool)),
+        _ => reject(&record.option_id, "malformed cooperative leaf"),
+    }
+}
+
+/// 서명 전 해지 PSBT: 옵션 출력 하나를 보유자 몫과 풀 몫(수수료 차감)으로 나눔
+///
+/// 0인 출력은 만들지 않습니다.
+pub fn close_psbt(
+    record: &OptionTaprootRecord,
+    option_utxo: OutPoint,
+    collateral: u64,
+    holder_amount: u64,
+    holder_script: ScriptBuf,
+    pool_script: ScriptBuf,
+    fee: u64,
+) -> Result<Psbt, ContractError> {
+    let Some(pool_amount) = collateral.checked_sub(holder_amount).and_then(|rest| rest.checked_sub(fee)) else {
+        return reject(&record.option_id, format!("{} + fee {} exceeds collateral {}", holder_amount, fee, collateral));
+    };
+    let output = [(holder_amount, holder_script), (pool_amount, pool_script)]
+        .into_iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, script_pubkey)| TxOut {
+            value: Amount::from_sat(amount),
+            script_pubkey,
+        })
+        .collect();
+    let tx = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: option_utxo,
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        }],
+        output,
+    };
+    let mut psbt = Psbt::from_unsigned_tx(tx).map_err(|e| ContractError::CooperativeClose(e.to_string()))?;
+    let leaf = cooperative_leaf(record)?;
+    let control_block = leaf
+        .control_block()
+        .map_err(|e| ContractError::CooperativeClose(e.to_string()))?;
+    let input = &mut psbt.inputs[0];
+    input.witness_utxo = Some(TxOut {
+        value: Amount::from_sat(collateral),
+        script_pubkey: record.script_pubkey.clone(),
+    });
+    input.tap_internal_key = Some(record.internal_key);
+    input
+        .tap_scripts
+        .insert(control_block, (leaf.script.clone(), LeafVersion::TapScript));
+    Ok(psbt)
+}
+
+/// 협의 해지 리프 sighash
+fn close_sighash(psbt: &Psbt, leaf_hash: TapLeafHash) -> Result<Message, ContractError> {
+    let prevouts: Vec<TxOut> = psbt
+        .inputs
+        .iter()
+        .map(|input| input.witness_utxo.clone())
+        .collect::<Option<_>>()
+        .ok_or_else(|| ContractError::CooperativeClose("PSBT input without witness UTXO".to_string()))?;
+    let sighash = SighashCache::new(&psbt.unsigned_tx)
+        .taproot_script_spend_signature_hash(0, &Prevouts::All(&prevouts), leaf_hash, TapSighashType::Default)
+        .map_err(|e| ContractError::CooperativeClose(e.to_string()))?;
+    Ok(Message::from_digest(sighash.to_byte_array()))
+}
+
+/// 해지 PSBT에 협의 해지 경로 서명 추가 (보유자 또는 풀 키)
+pub fn sign_close(psbt: &mut Psbt, record: &OptionTaprootRecord, secret_key: &SecretKey) -> Result<(), ContractError> {
+    let secp = Secp256k1::new();
+    let leaf_hash = TapLeafHash::from_script(&cooperative_leaf(record)?.script, LeafVersion::TapScript);
+    let message = close_sighash(psbt, leaf_hash)?;
+    let keypair = Keypair::from_secret_key(&secp, secret_key);
+    let signature = taproot::Signature {
+        signature: secp.sign_schnorr(&message, &keypair),
+        sighash_type: TapSighashType::Default,
+    };
+    psbt.inputs[0]
+        .tap_script_sigs
+        .insert((keypair.x_only_public_key().0, leaf_hash), signature);
+    Ok(())
+}
+
+/// 해지 PSBT 검사: 옵션 출력 하나를 쓰고 보유자 몫을 정확히 지급하며 나머지는 풀로
+fn check_close_psbt(psbt: &Psbt, record: &OptionTaprootRecord, proposal: &CloseProposal) -> Result<(), ContractError> {
+    let option_id = &record.option_id;
+    let tx = &psbt.unsigned_tx;
+    let Some(utxo) = psbt.inputs.first().and_then(|input| input.witness_utxo.as_ref()) else {
+        return reject(option_id, "PSBT input without witness UTXO");
+    };
+    if tx.input.len() != 1 || utxo.script_pubkey != record.script_pubkey {
+        return reject(option_id, "PSBT must spend the option output only");
+    }
+    let holder_paid: u64 = tx
+        .output
+        .iter()
+        .filter(|output| output.script_pubkey == proposal.holder_script)
+        .map(|output| output.value.to_sat())
+        .sum();
+    if holder_paid != proposal.holder_amount {
+        return reject(option_id, format!("PSBT pays holder {}, proposed {}", holder_paid, proposal.holder_amount));
+    }
+    if tx
+        .output
+        .iter()
+        .any(|output| output.script_pubkey != proposal.holder_script && output.script_pubkey != proposal.pool_script)
+    {
+        return reject(option_id, "PSBT pays outside the holder and pool");
+    }
+    let total: u64 = tx.output.iter().map(|output| output.value.to_sat()).sum();
+    if total > utxo.value.to_sat() {
+        return reject(option_id, format!("outputs {} exceed option output {}", total, utxo.value.to_sat()));
+    }
+    Ok(())
+}
+
+/// `key`의 협의 해지 서명 확인 후 반환
+fn verified_signature(
+    psbt: &Psbt,
+    record: &OptionTaprootRecord,
+    key: XOnlyPublicKey,
+    party: CloseParty,
+) -> Result<taproot::Signature, ContractError> {
+    let leaf_hash = TapLeafHash::from_script(&cooperative_leaf(record)?.script, LeafVersion::TapScript);
+    let Some(signature) = psbt.inputs[0].tap_script_sigs.get(&(key, leaf_hash)).copied() else {
+        return reject(&record.option_id, format!("missing {:?} signature", party));
+    };
+    let message = close_sighash(psbt, leaf_hash)?;
+    if signature.sighash_type != TapSighashType::Default
+        || Secp256k1::verification_only()
+            .verify_schnorr(&signature.signature, &message, &key)
+            .is_err()
+    {
+        return reject(&record.option_id, format!("invalid {:?} signature", party));
+    }
+    Ok(signature)
+}
+
+fn decode_psbt(option_id: &str, psbt: &str) -> Result<Psbt, ContractError> {
+    hex::decode(psbt)
+        .ok()
+        .and_then(|bytes| Psbt::deserialize(&bytes).ok())
+        .map_or_else(|| reject(option_id, "invalid PSBT"), Ok)
+}
+
+/// 옵션별 해지 제안 보관과 공동 서명 처리
+pub struct CooperativeCloseDesk {
+    proposals: HashMap<String, CloseProposal>,
+    max_deviation_bps: u64,
+    /// 대기 중인 제안 파일 (없으면 메모리에만 보관)
+    path: Option<PathBuf>,
+}
+
+impl Default for CooperativeCloseDesk {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CooperativeCloseDesk {
+    pub fn new() -> Self {
+        Self {
+            proposals: HashMap::new(),
+            max_deviation_bps: DEFAULT_MAX_DEVIATION_BPS,
+            path: None,
+        }
+    }
+
+    /// 제안 파일에서 열기 (없으면 빈 상태로 시작, 이후 변경마다 기록)
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let proposals = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            proposals,
+            path: Some(path),
+            ..Self::new()
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.proposals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proposals.is_empty()
+    }
+
+    /// 제안 목록 기록 (임시 파일에 쓴 뒤 교체)
+    fn persist(&self, proposals: &HashMap<String, CloseProposal>) -> Result<(), ContractError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        serde_json::to_vec_pretty(proposals)
+            .map_err(|e| ContractError::Storage(e.to_string()))
+            .and_then(|bytes| {
+                std::fs::write(&tmp, bytes)
+                    .and_then(|_| std::fs::rename(&tmp, path))
+                    .map_err(|e| ContractError::Storage(format!("{}: {}", path.display(), e)))
+            })
+    }
+
+    pub fn with_max_deviation_bps(mut self, max_deviation_bps: u64) -> Self {
+        self.max_deviation_bps = max_deviation_bps;
+        self
+    }
+
+    pub fn proposal(&self, option_id: &str) -> Option<&CloseProposal> {
+        self.proposals.get(option_id)
+    }
+
+    /// 해지 제안 접수 (같은 옵션의 이전 제안은 대체)
+    ///
+    /// 분배를 이론가와 대조하고, PSBT 출력과 제안자 서명을 확인합니다.
+    pub fn propose(
+        &mut self,
+        manager: &SimpleContractManager,
+        record: &OptionTaprootRecord,
+        proposal: CloseProposal,
+    ) -> Result<(), ContractError> {
+        if proposal.option_id != record.option_id || proposal.quote.option_id != record.option_id {
+            return reject(&proposal.option_id, "proposal is for a different option");
+        }
+        manager.check_cooperative_close(
+            &proposal.quote,
+            proposal.holder_amount,
+            &proposal.user_id,
+            self.max_deviation_bps,
+        )?;
+        let psbt = decode_psbt(&proposal.option_id, &proposal.psbt)?;
+        check_close_psbt(&psbt, record, &proposal)?;
+        let (buyer, pool) = cooperative_keys(record)?;
+        let key = match proposal.proposer {
+            CloseParty::Holder => buyer,
+            CloseParty::Pool => pool,
+        };
+        verified_signature(&psbt, record, key, proposal.proposer)?;
+        info!(
+            "Cooperative close of {} proposed by {:?}: holder {} sats",
+            proposal.option_id, proposal.proposer, proposal.holder_amount
+        );
+        let mut proposals = self.proposals.clone();
+        proposals.insert(proposal.option_id.clone(), proposal);
+        self.persist(&proposals)?;
+        self.proposals = proposals;
+        Ok(())
+    }
+
+    /// 상대방 서명 PSBT 접수: 두 서명 확인, 분배 재검사 후 전송하고 해지 반영
+    ///
+    /// 분배는 제출 시점의 이론가와 다시 대조하므로 되사기 호가가 만료됐으면 새 호가로
+    /// 다시 제안해야 합니다.
+    pub fn countersign(
+        &mut self,
+        manager: &mut SimpleContractManager,
+        record: &OptionTaprootRecord,
+        psbt: &str,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<CloseOutcome, ContractError> {
+        let option_id = record.option_id.as_str();
+        let Some(proposal) = self.proposals.get(option_id) else {
+            return reject(option_id, "no pending close proposal");
+        };
+        let proposed = decode_psbt(option_id, &proposal.psbt)?;
+        let psbt = decode_psbt(option_id, psbt)?;
+        if psbt.unsigned_tx != proposed.unsigned_tx || psbt.inputs[0].witness_utxo != proposed.inputs[0].witness_utxo {
+            return reject(option_id, "countersigned PSBT differs from the proposal");
+        }
+        check_close_psbt(&psbt, record, proposal)?;
+        manager.check_cooperative_close(
+            &proposal.quote,
+            proposal.holder_amount,
+            &proposal.user_id,
+            self.max_deviation_bps,
+        )?;
+
+        // 스택 위부터 구매자(CHECKSIGVERIFY), 풀(CHECKSIG) 서명
+        let (buyer, pool) = cooperative_keys(record)?;
+        let buyer_sig = verified_signature(&psbt, record, buyer, CloseParty::Holder)?;
+        let pool_sig = verified_signature(&psbt, record, pool, CloseParty::Pool)?;
+        let mut tx = psbt.unsigned_tx.clone();
+        tx.input[0].witness = record
+            .script_path_witness(LeafKind::Cooperative, vec![pool_sig.to_vec(), buyer_sig.to_vec()])
+            .map_err(|e| ContractError::CooperativeClose(e.to_string()))?;
+
+        // 옵션 UTXO를 점유하고, 노드가 아직 받지 않았으면 다음 조회에서 재전송
+        let txid = match manager.broadcast_settlement(option_id, &tx, broadcaster) {
+            Ok(txid) => txid,
+            Err(ContractError::Ledger(reason)) => return reject(option_id, reason),
+            Err(e) => return Err(e),
+        };
+        let holder_amount = manager.close_cooperatively(
+            &proposal.quote,
+            proposal.holder_amount,
+            &txid,
+            &proposal.user_id,
+            self.max_deviation_bps,
+        )?;
+        let pool_amount = tx
+            .output
+            .iter()
+            .filter(|output| output.script_pubkey == proposal.pool_script)
+            .map(|output| output.value.to_sat())
+            .sum();
+        self.proposals.remove(option_id);
+        // 옵션은 이미 닫혔으므로 남은 제안은 다음 서명 시 거부될 뿐
+        if let Err(e) = self.persist(&self.proposals) {
+            warn!("Failed to drop close proposal for {}: {}", option_id, e);
+        }
+
+        let payload = manager.cancel_anchor_payload(option_id);
+        let anchor_txid = payload.as_ref().and_then(|payload| match broadcaster.anchor(payload) {
+            Ok(anchor_txid) => {
+                if let Err(e) = manager.track_anchor(option_id, &anchor_txid, payload.clone(), None) {
+                    warn!("CNL anchor {} for {} not tracked: {}", anchor_txid, option_id, e);
+                }
+                Some(anchor_txid)
+            }
+            Err(e) => {
+                warn!("CNL anchor for {} failed: {}", option_id, e);
+                None
+            }
+        });
+        info!("Option {} closed cooperatively in {}", option_id, txid);
+        Ok(CloseOutcome {
+            option_id: option_id.to_string(),
+            txid,
+            holder_amount,
+            pool_amount,
+            cancel_anchor: payload.map(hex::encode),
+            anchor_txid,
+        })
+    }
+}
+
+/// `/options/{id}/close` API
+///
+/// 전송/앵커링에 쓸 `AnchorBroadcaster`와 옵션 Taproot 기록 저장소가 필요하므로
+/// 풀 라우터와 따로 마운트합니다.
+pub mod api {
+    use super::*;
+    use crate::admin_api::{bad_request, error_response, SharedManager};
+    use crate::taproot_address::TaprootStore;
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// 협의 해지 서비스 상태
+    pub struct CloseService {
+        pub manager: SharedManager,
+        pub desk: Mutex<CooperativeCloseDesk>,
+        pub store: TaprootStore,
+        pub broadcaster: Box<dyn AnchorBroadcaster + Send + Sync>,
+    }
+
+    pub type SharedCloseService = Arc<CloseService>;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct CloseSubmission {
+        pub proposer: CloseParty,
+        pub user_id: String,
+        pub quote: BuyBackQuote,
+        pub holder_amount: u64,
+        pub holder_script: ScriptBuf,
+        pub pool_script: ScriptBuf,
+        pub psbt: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct CountersignSubmission {
+        pub psbt: String,
+    }
+
+    fn not_found(message: String) -> Response {
+        (StatusCode::NOT_FOUND, Json(json!({ "error": message }))).into_response()
+    }
+
+    async fn propose(
+        Path(option_id): Path<String>,
+        State(service): State<SharedCloseService>,
+        Json(request): Json<CloseSubmission>,
+    ) -> Response {
+        let record = match service.store.get(&option_id) {
+            Ok(Some(record)) => record,
+            Ok(None) => return not_found(format!("No taproot output for {}", option_id)),
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let (Ok(manager), Ok(mut desk)) = (service.manager.read(), service.desk.lock()) else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let proposer = request.proposer;
+        let proposal = CloseProposal {
+            option_id: option_id.clone(),
+            proposer,
+            user_id: request.user_id,
+            quote: request.quote,
+            holder_amount: request.holder_amount,
+            holder_script: request.holder_script,
+            pool_script: request.pool_script,
+            psbt: request.psbt,
+            proposed_at: manager.now(),
+        };
+        match desk.propose(&manager, &record, proposal) {
+            Ok(()) => Json(json!({
+                "option_id": option_id,
+                "proposer": proposer,
+                "awaiting": proposer.counterparty(),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    async fn get_proposal(Path(option_id): Path<String>, State(service): State<SharedCloseService>) -> Response {
+        let Ok(desk) = service.desk.lock() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match desk.proposal(&option_id) {
+            Some(proposal) => Json(proposal.clone()).into_response(),
+            None => not_found(format!("No close proposal for {}", option_id)),
+        }
+    }
+
+    async fn countersign(
+        Path(option_id): Path<String>,
+        State(service): State<SharedCloseService>,
+        Json(request): Json<CountersignSubmission>,
+    ) -> Response {
+        let record = match service.store.get(&option_id) {
+            Ok(Some(record)) => record,
+            Ok(None) => return not_found(format!("No taproot output for {}", option_id)),
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let (Ok(mut manager), Ok(mut desk)) = (service.manager.write(), service.desk.lock()) else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match desk.countersign(&mut manager, &record, &request.psbt, service.broadcaster.as_ref()) {
+            Ok(outcome) => Json(outcome).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/options/{id}/close` 라우터 생성
+    pub fn router(service: SharedCloseService) -> Router {
+        Router::new()
+            .route("/options/:id/close", get(get_proposal).post(propose))
+            .route("/options/:id/close/countersign", post(countersign))
+            .with_state(service)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditAction;
+    use crate::buy_back::CANCEL_ANCHOR_TAG;
+    use crate::settlement_broadcast::SettlementBroadcastManager;
+    use crate::simple_contract::OptionStatus;
+    use crate::taproot_address::TaprootAddressBuilder;
+    use bitcoin::secp256k1::PublicKey;
+    use bitcoin::Txid;
+    use oracle_vm_common::crypto::generate_keypair;
+    use oracle_vm_common::types::OptionType;
+    use oracle_vm_common::{AnchorError, ManualClock, NetworkProfile};
+    use std::cell::RefCell;
+    use std::str::FromStr;
+
+    const NOW: u64 = 1_704_888_000;
+    const COLLATERAL: u64 = 10_000_000;
+    const FEE: u64 = 500;
+
+    #[derive(Default)]
+    struct RecordingBroadcaster {
+        sent: RefCell<Vec<Transaction>>,
+        anchors: RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl AnchorBroadcaster for RecordingBroadcaster {
+        fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+            let tx: Transaction = bitcoin::consensus::deserialize(raw_tx).unwrap();
+            let txid = tx.compute_txid().to_string();
+            self.sent.borrow_mut().push(tx);
+            Ok(txid)
+        }
+
+        fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+            self.anchors.borrow_mut().push(payload.to_vec());
+            Ok("anchor-tx".to_string())
+        }
+    }
+
+    struct Fixture {
+        manager: SimpleContractManager,
+        quote_key: SecretKey,
+        buyer_key: SecretKey,
+        pool_key: SecretKey,
+        record: OptionTaprootRecord,
+    }
+
+    fn secret(byte: u8) -> SecretKey {
+        SecretKey::from_slice(&[byte; 32]).unwrap()
+    }
+
+    fn fixture() -> Fixture {
+        let (quote_key, quote_public) = generate_keypair();
+        let mut manager = SimpleContractManager::with_clock(ManualClock::new(NOW).shared());
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option("CALL-CC".to_string(), OptionType::Call, 7_000_000, COLLATERAL, 250_000, 800_000, "user9".to_string())
+            .unwrap();
+        manager.require_quotes(quote_public);
+
+        let secp = Secp256k1::new();
+        let (buyer_key, pool_key) = (secret(3), secret(1));
+        let builder = TaprootAddressBuilder::new(
+            NetworkProfile::REGTEST,
+            PublicKey::from_secret_key(&secp, &pool_key),
+            PublicKey::from_secret_key(&secp, &secret(2)),
+        );
+        let record = builder
+            .build("CALL-CC", &PublicKey::from_secret_key(&secp, &buyer_key), 800_000)
+            .unwrap()
+            .record;
+        Fixture { manager, quote_key, buyer_key, pool_key, record }
+    }
+
+    fn quote(fixture: &Fixture, theoretical_value: u64) -> BuyBackQuote {
+        let mut quote = BuyBackQuote {
+            quote_id: "B-1".to_string(),
+            option_id: "CALL-CC".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: COLLATERAL,
+            value: theoretical_value - 10_000,
+            theoretical_value,
+            spot_price: 7_000_000,
+            issued_at: NOW,
+            valid_until: NOW + 30,
+            tenant_id: None,
+            signature: String::new(),
+        };
+        quote.sign(&fixture.quote_key).unwrap();
+        quote
+    }
+
+    fn proposal(fixture: &Fixture, proposer: CloseParty, holder_amount: u64, psbt: &Psbt) -> CloseProposal {
+        CloseProposal {
+            option_id: "CALL-CC".to_string(),
+            proposer,
+            user_id: "user9".to_string(),
+            quote: quote(fixture, 190_000),
+            holder_amount,
+            holder_script: ScriptBuf::from_bytes(vec![0x51]),
+            pool_script: ScriptBuf::from_bytes(vec![0x52]),
+            psbt: hex::encode(psbt.serialize()),
+            proposed_at: NOW,
+        }
+    }
+
+    fn unsigned(fixture: &Fixture, holder_amount: u64) -> Psbt {
+        let utxo = OutPoint {
+            txid: Txid::from_str("0000000000000000000000000000000000000000000000000000000000000007").unwrap(),
+            vout: 0,
+        };
+        close_psbt(
+            &fixture.record,
+            utxo,
+            COLLATERAL,
+            holder_amount,
+            ScriptBuf::from_bytes(vec![0x51]),
+            ScriptBuf::from_bytes(vec![0x52]),
+            FEE,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cooperative_close_with_both_signatures() {
+        let mut fixture = fixture();
+        let path = std::env::temp_dir().join(format!("btcfi-closes-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut desk = CooperativeCloseDesk::open(&path).unwrap();
+        fixture
+            .manager
+            .enable_settlement_broadcasts(SettlementBroadcastManager::new(NetworkProfile::REGTEST));
+        let broadcaster = RecordingBroadcaster::default();
+
+        // 보유자가 이론가 근처(190,000 → 188,000)로 제안하고 자기 서명
+        let mut psbt = unsigned(&fixture, 188_000);
+        sign_close(&mut psbt, &fixture.record, &fixture.buyer_key).unwrap();
+        desk.propose(&fixture.manager, &fixture.record, proposal(&fixture, CloseParty::Holder, 188_000, &psbt))
+            .unwrap();
+        // 재시작해도 대기 중인 제안은 남음
+        let reopened = CooperativeCloseDesk::open(&path).unwrap();
+        assert_eq!(reopened.proposal("CALL-CC").unwrap().holder_amount, 188_000);
+
+        // 풀 서명 없이 제출하면 거부
+        let holder_only = hex::encode(psbt.serialize());
+        assert!(matches!(
+            desk.countersign(&mut fixture.manager, &fixture.record, &holder_only, &broadcaster),
+            Err(ContractError::CooperativeClose(_))
+        ));
+
+        sign_close(&mut psbt, &fixture.record, &fixture.pool_key).unwrap();
+        let outcome = desk
+            .countersign(
+                &mut fixture.manager,
+                &fixture.record,
+                &hex::encode(psbt.serialize()),
+                &broadcaster,
+            )
+            .unwrap();
+        assert_eq!(outcome.holder_amount, 188_000);
+        assert_eq!(outcome.pool_amount, COLLATERAL - 188_000 - FEE);
+        assert_eq!(outcome.anchor_txid.as_deref(), Some("anchor-tx"));
+        assert!(desk.proposal("CALL-CC").is_none());
+        assert!(CooperativeCloseDesk::open(&path).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+
+        // 전송된 트랜잭션: [풀 서명, 구매자 서명, 리프 스크립트, control block]
+        let sent = broadcaster.sent.borrow();
+        assert_eq!(sent[0].compute_txid().to_string(), outcome.txid);
+        let witness: Vec<&[u8]> = sent[0].input[0].witness.iter().collect();
+        let leaf = fixture.record.leaf(LeafKind::Cooperative).unwrap();
+        assert_eq!(witness.len(), 4);
+        assert_eq!(witness[2], leaf.script.as_bytes());
+        assert_eq!(hex::encode(witness[3]), leaf.control_block);
+        let settlements = fixture.manager.settlement_broadcasts().unwrap();
+        assert_eq!(settlements.get("CALL-CC").unwrap().txid, outcome.txid);
+
+        // 옵션 종료, 담보 반환, 감사 기록과 CNL 앵커
+        let manager = &fixture.manager;
+        assert_eq!(manager.options["CALL-CC"].status, OptionStatus::Cancelled);
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+        let last = manager.audit().trail("CALL-CC").last().unwrap();
+        assert_eq!(
+            last.action,
+            AuditAction::CooperativelyClosed {
+                spot_price: 7_000_000,
+                amount: 188_000,
+                txid: outcome.txid.clone(),
+            }
+        );
+        let anchors = broadcaster.anchors.borrow();
+        assert!(anchors[0].starts_with(CANCEL_ANCHOR_TAG));
+        assert_eq!(outcome.cancel_anchor, Some(hex::encode(&anchors[0])));
+    }
+
+    #[test]
+    fn test_rejects_split_far_from_theoretical_value() {
+        let fixture = fixture();
+        let mut desk = CooperativeCloseDesk::new();
+
+        // 이론가 190,000에서 2% 넘게 벗어난 분배
+        let mut psbt = unsigned(&fixture, 150_000);
+        sign_close(&mut psbt, &fixture.record, &fixture.pool_key).unwrap();
+        let result = desk.propose(&fixture.manager, &fixture.record, proposal(&fixture, CloseParty::Pool, 150_000, &psbt));
+        assert!(matches!(result, Err(ContractError::CooperativeClose(_))));
+
+        // 제안 금액과 PSBT 지급액이 다르거나 제안자 서명이 없으면 거부
+        let psbt = unsigned(&fixture, 188_000);
+        let mut mismatched = proposal(&fixture, CloseParty::Pool, 189_000, &psbt);
+        assert!(desk.propose(&fixture.manager, &fixture.record, mismatched.clone()).is_err());
+        mismatched.holder_amount = 188_000;
+        assert!(desk.propose(&fixture.manager, &fixture.record, mismatched).is_err());
+        assert!(desk.proposal("CALL-CC").is_none());
+    }
+}
@@ -0,0 +1,182 @@
+//! USD 결제 옵션용 이중 통화 풀 회계
+//!
+//! 담보는 계속 BTC 원장(`PoolLedger`)에 잠그고, USD로 결제하는 옵션의 프리미엄과
+//! 지급액은 별도의 USD 잔고(합성 USD 또는 Taproot Assets USDt)에서 처리합니다.
+//! USD 잔고가 부족하면 모자란 금액만큼 BTC 담보를 오라클 가격으로 환산해 지급하고
+//! 환산 내역을 남깁니다.
+
+use oracle_vm_common::settlement_currency::{cents_to_sats, sats_to_cents, Money, UsdRail};
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 오라클 가격 환산 기록
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Conversion {
+    pub option_id: String,
+    pub from: Money,
+    pub to: Money,
+    /// USD cents / BTC
+    pub price_cents: u64,
+    pub timestamp: u64,
+}
+
+/// USD 결제 옵션 지급 계획
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsdPayout {
+    pub option_id: String,
+    /// 지급해야 할 금액 (USD cents)
+    pub owed_cents: u64,
+    /// USD 잔고에서 지급
+    pub from_balance_cents: u64,
+    /// BTC 담보에서 환산 지급 (satoshis)
+    pub converted_sats: u64,
+    /// 담보로도 못 채운 금액 (USD cents)
+    pub shortfall_cents: u64,
+    pub price_cents: u64,
+}
+
+/// 풀의 USD 측 잔고와 USD 결제 옵션
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsdPoolBook {
+    pub rail: UsdRail,
+    /// USD cents
+    pub balance_cents: u64,
+    pub premiums_cents: u64,
+    pub payouts_cents: u64,
+    /// USD 결제 옵션 ID → 받은 프리미엄 (USD cents)
+    options: BTreeMap<String, u64>,
+    conversions: Vec<Conversion>,
+}
+
+impl UsdPoolBook {
+    pub fn new(rail: UsdRail) -> Self {
+        Self {
+            rail,
+            balance_cents: 0,
+            premiums_cents: 0,
+            payouts_cents: 0,
+            options: BTreeMap::new(),
+            conversions: Vec::new(),
+        }
+    }
+
+    /// LP USD 예치
+    pub fn deposit(&mut self, cents: u64) {
+        self.balance_cents += cents;
+    }
+
+    /// LP USD 인출
+    pub fn withdraw(&mut self, cents: u64) -> Result<(), ContractError> {
+        if cents > self.balance_cents {
+            return Err(ContractError::Ledger(format!(
+                "USD withdrawal of {} cents exceeds balance {}",
+                cents, self.balance_cents
+            )));
+        }
+        self.balance_cents -= cents;
+        Ok(())
+    }
+
+    /// USD로 결제하는 옵션인지
+    pub fn is_usd(&self, option_id: &str) -> bool {
+        self.options.contains_key(option_id)
+    }
+
+    /// 옵션이 낸 USD 프리미엄
+    pub fn premium_cents(&self, option_id: &str) -> Option<u64> {
+        self.options.get(option_id).copied()
+    }
+
+    /// USD 프리미엄 수취
+    pub fn collect_premium(&mut self, option_id: &str, cents: u64) {
+        self.options.insert(option_id.to_string(), cents);
+        self.balance_cents += cents;
+        self.premiums_cents += cents;
+    }
+
+    /// 지급 계획 (USD 잔고 우선, 부족분은 최대 `max_sats`까지 BTC 환산)
+    pub fn plan_payout(
+        &self,
+        option_id: &str,
+        owed_cents: u64,
+        price_cents: u64,
+        max_sats: u64,
+    ) -> UsdPayout {
+        let from_balance_cents = owed_cents.min(self.balance_cents);
+        let remaining = owed_cents - from_balance_cents;
+        let needed_sats = cents_to_sats(remaining, price_cents).unwrap_or(u64::MAX);
+        let converted_sats = needed_sats.min(max_sats);
+        let covered = if needed_sats > max_sats {
+            sats_to_cents(converted_sats, price_cents)
+        } else {
+            remaining
+        };
+
+        UsdPayout {
+            option_id: option_id.to_string(),
+            owed_cents,
+            from_balance_cents,
+            converted_sats,
+            shortfall_cents: remaining.saturating_sub(covered),
+            price_cents,
+        }
+    }
+
+    /// 지급 계획 반영 (BTC 원장 반영에 성공한 뒤 호출)
+    pub fn apply_payout(&mut self, payout: &UsdPayout, timestamp: u64) {
+        self.options.remove(&payout.option_id);
+        self.balance_cents -= payout.from_balance_cents;
+        self.payouts_cents += payout.owed_cents - payout.shortfall_cents;
+        if payout.converted_sats > 0 {
+            self.conversions.push(Conversion {
+                option_id: payout.option_id.clone(),
+                from: Money::sats(payout.converted_sats),
+                to: Money::cents(sats_to_cents(payout.converted_sats, payout.price_cents)),
+                price_cents: payout.price_cents,
+                timestamp,
+            });
+        }
+    }
+
+    /// 지급 없이 만료된 옵션 정리
+    pub fn forget(&mut self, option_id: &str) {
+        self.options.remove(option_id);
+    }
+
+    pub fn conversions(&self) -> &[Conversion] {
+        &self.conversions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payout_uses_usd_balance_then_btc_collateral() {
+        let mut book = UsdPoolBook::new(UsdRail::Synthetic);
+        book.deposit(100_000);
+        book.collect_premium("OPT-1", 50_000);
+        assert_eq!(book.balance_cents, 150_000);
+
+        // $5,000 지급: $1,500은 잔고, $3,500은 BTC ($70,000) 환산
+        let payout = book.plan_payout("OPT-1", 500_000, 7_000_000, 10_000_000);
+        assert_eq!(payout.from_balance_cents, 150_000);
+        assert_eq!(payout.converted_sats, 5_000_000);
+        assert_eq!(payout.shortfall_cents, 0);
+
+        book.apply_payout(&payout, 1);
+        assert_eq!(book.balance_cents, 0);
+        assert_eq!(book.payouts_cents, 500_000);
+        assert!(!book.is_usd("OPT-1"));
+        assert_eq!(book.conversions()[0].to, Money::cents(350_000));
+
+        // 담보 상한에 걸리면 부족분 기록
+        book.collect_premium("OPT-2", 0);
+        let capped = book.plan_payout("OPT-2", 1_000_000, 7_000_000, 1_000_000);
+        assert_eq!(capped.converted_sats, 1_000_000);
+        assert_eq!(capped.shortfall_cents, 930_000);
+        assert!(book.withdraw(1).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! 미국식 옵션 조기 행사
+//!
+//! 미국식 호가(`style: american`)로 연 옵션은 보유자가 만기 전 언제든
+//! `POST /options/{id}/exercise`로 행사할 수 있습니다. 가격과 블록 높이는 요청에
+//! 담지 않고 관리자가 마지막으로 받은 합의 가격과 팁 높이를 쓰며, 지급은 만기
+//! 정산과 같은 경로를 탑니다. 조기 행사는 STL과 구분되는 EXR 앵커로 온체인에
+//! 남깁니다.
+
+use oracle_vm_common::crypto::sha256;
+
+/// 조기 행사에 쓸 합의 가격의 기본 최대 나이 (초, 합의 가격은 분 단위로 갱신)
+pub const DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS: u64 = 180;
+
+/// 조기 행사 앵커 태그
+pub const EXERCISE_ANCHOR_TAG: &[u8; 3] = b"EXR";
+
+/// EXR 앵커 페이로드: "EXR" || SHA256(옵션 ID) || 마지막 감사 해시 (STL과 같은 67 bytes)
+pub fn exercise_anchor_payload(option_id: &str, audit_hash: &[u8; 32]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(67);
+    payload.extend_from_slice(EXERCISE_ANCHOR_TAG);
+    payload.extend_from_slice(&sha256(option_id.as_bytes()));
+    payload.extend_from_slice(audit_hash);
+    payload
+}
+
+/// `/options/{id}/exercise` 조기 행사 API
+pub mod api {
+    use crate::account_keys::{exercise_payload, AccountSignature};
+    use crate::admin_api::{error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::post,
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct ExerciseSubmission {
+        pub user_id: String,
+        /// 보유자 키로 `account/exercise/v1` (option_id, user_id, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/options/{id}/exercise",
+        tag = "options",
+        params(("id" = String, Path, description = "옵션 ID")),
+        request_body = ExerciseSubmission,
+        responses(
+            (status = 200, body = Object),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn exercise(
+        Path(option_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<ExerciseSubmission>,
+    ) -> Response {
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = exercise_payload(&option_id, &request.user_id, request.auth.nonce);
+        if let Err(e) = manager.authorize_account(&request.user_id, &payload, &request.auth) {
+            return error_response(e);
+        }
+        match manager.exercise_option(&option_id, &request.user_id) {
+            Ok(amount) => Json(json!({
+                "option_id": option_id,
+                "amount": amount,
+                "exercise_anchor": manager.exercise_anchor_payload(&option_id).map(hex::encode),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/options/{id}/exercise` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/options/:id/exercise", post(exercise))
+            .with_state(manager)
+    }
+}
@@ -0,0 +1,312 @@
+//! 옵션 구매 자격 확인 (KYC/허용 목록 훅)
+//!
+//! 옵션을 생성/구매하기 전에 `EligibilityProvider`로 사용자 ID/주소를 확인합니다.
+//! 허용 목록 파일, 외부 HTTP 서비스, 확인 없음(no-op) 중 하나를 끼워 쓰고, 거부는
+//! `ContractError::Ineligible`로 돌려주며 로그로 남깁니다. 확인 결과의 해시는
+//! 옵션과 함께 보관해 나중에 어떤 판단으로 생성됐는지 감사할 수 있게 합니다.
+
+use crate::admin_api::SharedManager;
+use async_trait::async_trait;
+use oracle_vm_common::{CanonicalEncoder, ContractError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::warn;
+
+/// 자격 확인 요청
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EligibilityRequest {
+    pub user_id: String,
+    /// 지급 주소 (알 수 있을 때)
+    pub address: Option<String>,
+    pub option_id: String,
+}
+
+/// 자격 확인 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EligibilityDecision {
+    pub provider: String,
+    pub eligible: bool,
+    pub reason: Option<String>,
+    pub checked_at: u64,
+}
+
+impl EligibilityDecision {
+    /// 요청과 결과를 묶은 감사용 해시 (SHA256 hex)
+    pub fn hash(&self, request: &EligibilityRequest) -> String {
+        let mut encoder = CanonicalEncoder::new("eligibility/v2");
+        encoder
+            .str(&self.provider)
+            .str(&request.user_id)
+            .option(request.address.as_deref(), |e, address| {
+                e.str(address);
+            })
+            .str(&request.option_id)
+            .bool(self.eligible)
+            .option(self.reason.as_deref(), |e, reason| {
+                e.str(reason);
+            })
+            .u64(self.checked_at);
+        hex::encode(encoder.hash())
+    }
+
+    /// 거부 결과면 로그를 남기고 `Ineligible` 반환
+    pub fn ensure_eligible(&self, request: &EligibilityRequest) -> Result<(), ContractError> {
+        if self.eligible {
+            return Ok(());
+        }
+        let reason = self.reason.clone().unwrap_or_else(|| "not eligible".to_string());
+        warn!(
+            "Eligibility denied by {} for {} ({}): {} [{}]",
+            self.provider,
+            request.user_id,
+            request.option_id,
+            reason,
+            self.hash(request)
+        );
+        Err(ContractError::Ineligible {
+            user_id: request.user_id.clone(),
+            reason,
+        })
+    }
+}
+
+/// 자격 확인 인터페이스
+#[async_trait]
+pub trait EligibilityProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// 확인 자체가 실패하면(서비스 장애 등) `EligibilityUnavailable`
+    async fn check(&self, request: &EligibilityRequest) -> Result<EligibilityDecision, ContractError>;
+}
+
+/// 확인 후 거부면 로그를 남기고 `Ineligible` 반환, 허용이면 결과 반환
+pub async fn screen(
+    provider: &dyn EligibilityProvider,
+    request: &EligibilityRequest,
+) -> Result<EligibilityDecision, ContractError> {
+    let decision = provider.check(request).await?;
+    decision.ensure_eligible(request)?;
+    Ok(decision)
+}
+
+/// 풀에 등록된 훅으로 자격 확인 (훅이 없으면 통과)
+///
+/// 확인은 외부 호출일 수 있으므로 풀 잠금 밖에서 하고, 거부는 풀 이벤트로 남깁니다.
+/// 허용이면 옵션과 함께 보관할 결과 해시를 반환합니다.
+pub async fn screen_pool(
+    manager: &SharedManager,
+    request: &EligibilityRequest,
+) -> Result<Option<String>, ContractError> {
+    let poisoned = || ContractError::Storage("pool lock poisoned".to_string());
+    let Some(provider) = manager.read().map_err(|_| poisoned())?.eligibility() else {
+        return Ok(None);
+    };
+    let decision = provider.check(request).await?;
+    if let Err(e) = decision.ensure_eligible(request) {
+        manager
+            .write()
+            .map_err(|_| poisoned())?
+            .record_eligibility_denied(request, &decision)?;
+        return Err(e);
+    }
+    Ok(Some(decision.hash(request)))
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// 확인 없이 모두 허용 (기본값)
+pub struct NoopEligibility;
+
+#[async_trait]
+impl EligibilityProvider for NoopEligibility {
+    fn name(&self) -> &str {
+        "noop"
+    }
+
+    async fn check(&self, _request: &EligibilityRequest) -> Result<EligibilityDecision, ContractError> {
+        Ok(EligibilityDecision {
+            provider: self.name().to_string(),
+            eligible: true,
+            reason: None,
+            checked_at: now(),
+        })
+    }
+}
+
+/// 허용 목록 (사용자 ID 또는 주소가 목록에 있으면 허용)
+pub struct AllowlistEligibility {
+    entries: HashSet<String>,
+}
+
+impl AllowlistEligibility {
+    pub fn new(entries: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    /// 한 줄에 하나씩 (빈 줄과 `#` 주석은 무시)
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let body = std::fs::read_to_string(path.as_ref())?;
+        Ok(Self::new(
+            body.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        ))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[async_trait]
+impl EligibilityProvider for AllowlistEligibility {
+    fn name(&self) -> &str {
+        "allowlist"
+    }
+
+    async fn check(&self, request: &EligibilityRequest) -> Result<EligibilityDecision, ContractError> {
+        let listed = self.entries.contains(&request.user_id)
+            || request
+                .address
+                .as_ref()
+                .is_some_and(|address| self.entries.contains(address));
+        Ok(EligibilityDecision {
+            provider: self.name().to_string(),
+            eligible: listed,
+            reason: (!listed).then(|| "not on allowlist".to_string()),
+            checked_at: now(),
+        })
+    }
+}
+
+/// 외부 컴플라이언스 서비스 응답
+#[derive(Debug, Deserialize)]
+struct HttpEligibilityResponse {
+    eligible: bool,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// 외부 HTTP 서비스 (`POST <url>`에 요청 JSON, `{"eligible": bool, "reason": ...}` 응답)
+pub struct HttpEligibility {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpEligibility {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(5))
+                .build()
+                .expect("reqwest client builds"),
+        }
+    }
+}
+
+#[async_trait]
+impl EligibilityProvider for HttpEligibility {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn check(&self, request: &EligibilityRequest) -> Result<EligibilityDecision, ContractError> {
+        let unavailable = |e: reqwest::Error| ContractError::EligibilityUnavailable(e.to_string());
+        let response: HttpEligibilityResponse = crate::tracing_context::outbound(self.client.post(&self.url))
+            .json(request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(unavailable)?
+            .json()
+            .await
+            .map_err(unavailable)?;
+        Ok(EligibilityDecision {
+            provider: self.name().to_string(),
+            eligible: response.eligible,
+            reason: response.reason,
+            checked_at: now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(user_id: &str, address: Option<&str>) -> EligibilityRequest {
+        EligibilityRequest {
+            user_id: user_id.to_string(),
+            address: address.map(str::to_string),
+            option_id: "OPT-1".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_matches_user_or_address() {
+        let allowlist = AllowlistEligibility::new(["alice".to_string(), "bc1qbob".to_string()]);
+        assert!(screen(&allowlist, &request("alice", None)).await.is_ok());
+        assert!(screen(&allowlist, &request("bob", Some("bc1qbob"))).await.is_ok());
+
+        let denied = screen(&allowlist, &request("mallory", Some("bc1qmallory"))).await;
+        assert!(matches!(denied, Err(ContractError::Ineligible { ref user_id, .. }) if user_id == "mallory"));
+        assert!(screen(&NoopEligibility, &request("mallory", None)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_screen_pool_uses_manager_hook_and_records_denials() {
+        use crate::event_store::PoolEventKind;
+        use crate::simple_contract::SimpleContractManager;
+        use std::sync::{Arc, RwLock};
+
+        let manager: SharedManager = Arc::new(RwLock::new(SimpleContractManager::new()));
+        assert_eq!(screen_pool(&manager, &request("mallory", None)).await, Ok(None));
+
+        manager
+            .write()
+            .unwrap()
+            .enable_eligibility(Arc::new(AllowlistEligibility::new(["alice".to_string()])));
+        let hash = screen_pool(&manager, &request("alice", None)).await.unwrap();
+        assert_eq!(hash.map(|hash| hash.len()), Some(64));
+
+        let denied = screen_pool(&manager, &request("mallory", None)).await;
+        assert!(matches!(denied, Err(ContractError::Ineligible { ref user_id, .. }) if user_id == "mallory"));
+        let manager = manager.read().unwrap();
+        assert!(matches!(
+            &manager.event_store().events().last().unwrap().kind,
+            PoolEventKind::EligibilityDenied { user_id, provider, .. } if user_id == "mallory" && provider == "allowlist"
+        ));
+    }
+
+    #[test]
+    fn test_decision_hash_covers_request_and_result() {
+        let decision = EligibilityDecision {
+            provider: "allowlist".to_string(),
+            eligible: true,
+            reason: None,
+            checked_at: 1_000,
+        };
+        let hash = decision.hash(&request("alice", None));
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, decision.hash(&request("alice", None)));
+        assert_ne!(hash, decision.hash(&request("alice", Some("bc1qalice"))));
+        // 빈 주소와 주소 없음은 다른 요청
+        assert_ne!(hash, decision.hash(&request("alice", Some(""))));
+        let denied = EligibilityDecision {
+            eligible: false,
+            ..decision.clone()
+        };
+        assert_ne!(hash, denied.hash(&request("alice", None)));
+    }
+}
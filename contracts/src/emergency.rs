@@ -0,0 +1,554 @@
+//! 데드맨 스위치 / 비상 출금
+//!
+//! 풀 자금은 평소에는 운영자 M-of-N 멀티시그로, 출력이 N블록 동안 움직이지
+//! 않으면 복구 키 서명(CSV)으로 쓸 수 있는 스크립트에 묶입니다. 풀 출력을
+//! 만들 때마다 LP들에게 지분대로 지급하는 복구 트랜잭션을 미리 서명해 두고,
+//! 시스템이 정상인 동안 운영자가 주기적으로 풀 출력을 새 출력으로 옮기며
+//! (heartbeat) 복구 트랜잭션을 다시 서명합니다. 옮겨진 출력을 쓰는 이전 복구
+//! 트랜잭션은 자동으로 무효가 되고, 운영자가 사라져 heartbeat가 끊기면 LP가
+//! 최신 복구 트랜잭션을 그대로 전송할 수 있습니다.
+
+use crate::anchor_tracker::{AnchorBroadcaster, ChainSource, TxStatus};
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::blockdata::opcodes::all as op;
+use bitcoin::hashes::Hash;
+use bitcoin::script::Builder;
+use bitcoin::sighash::{EcdsaSighashType, SighashCache};
+use bitcoin::{
+    absolute::LockTime, Address, Amount, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction,
+    TxIn, TxOut, Witness,
+};
+use oracle_vm_common::crypto::KeyStore;
+use oracle_vm_common::{EmergencyError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// 복구 경로 서명 키 이름
+pub const RECOVERY_KEY: &str = "pool-recovery";
+
+/// 데드맨 스위치 설정
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EmergencyConfig {
+    /// 풀 출력이 이 블록 수만큼 움직이지 않으면 복구 가능 (CSV)
+    pub timeout_blocks: u16,
+    /// heartbeat 주기 (블록, timeout_blocks보다 짧아야 함)
+    pub refresh_interval_blocks: u32,
+    /// 복구 트랜잭션 수수료 (sats, LP 지분대로 차감)
+    pub fee_sats: u64,
+}
+
+impl Default for EmergencyConfig {
+    fn default() -> Self {
+        Self {
+            timeout_blocks: 4_320,          // 약 30일
+            refresh_interval_blocks: 1_008, // 약 1주
+            fee_sats: 2_000,
+        }
+    }
+}
+
+/// 풀 자금 출력
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolFunding {
+    pub outpoint: OutPoint,
+    pub value: Amount,
+    /// 출력이 확인된 블록 높이 (CSV 기준점)
+    pub confirmed_height: u32,
+}
+
+/// LP 복구 지급액
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryPayout {
+    pub lp_address: String,
+    pub amount: u64,
+}
+
+/// LP에게 배포하는 미리 서명된 복구 트랜잭션
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryPackage {
+    pub funding: PoolFunding,
+    pub payouts: Vec<RecoveryPayout>,
+    pub txid: String,
+    /// 서명 완료된 트랜잭션 (hex)
+    pub raw_tx: String,
+    /// 복구 트랜잭션이 처음 블록에 포함될 수 있는 높이
+    pub broadcastable_at: u32,
+}
+
+impl RecoveryPackage {
+    /// 다음 블록에 포함될 수 있으면 전송 가능
+    pub fn is_broadcastable(&self, tip: u32) -> bool {
+        tip + 1 >= self.broadcastable_at
+    }
+}
+
+/// LP 지분을 수수료 차감 후 금액으로 분배 (나머지는 마지막 LP에게)
+pub fn split_payouts(
+    value: Amount,
+    fee_sats: u64,
+    shares: &[(String, u64)],
+) -> Result<Vec<RecoveryPayout>, EmergencyError> {
+    let distributable = value
+        .to_sat()
+        .checked_sub(fee_sats)
+        .filter(|amount| *amount > 0)
+        .ok_or(EmergencyError::InsufficientValue {
+            value: value.to_sat(),
+            fee: fee_sats,
+        })?;
+    let total_shares: u64 = shares.iter().map(|(_, share)| share).sum();
+    if total_shares == 0 {
+        return Err(EmergencyError::InvalidConfig("no LP shares".to_string()));
+    }
+
+    let mut payouts: Vec<RecoveryPayout> = shares
+        .iter()
+        .map(|(address, share)| RecoveryPayout {
+            lp_address: address.clone(),
+            amount: (distributable as u128 * *share as u128 / total_shares as u128) as u64,
+        })
+        .collect();
+    let paid: u64 = payouts.iter().map(|p| p.amount).sum();
+    if let Some(last) = payouts.last_mut() {
+        last.amount += distributable - paid;
+    }
+    Ok(payouts)
+}
+
+/// 운영자 측 비상 복구 관리자
+pub struct EmergencyVault {
+    profile: NetworkProfile,
+    config: EmergencyConfig,
+    managers: Vec<PublicKey>,
+    threshold: usize,
+    recovery_key: PublicKey,
+    packages: Vec<RecoveryPackage>,
+    /// 서명한 복구 트랜잭션 기록 파일 (없으면 메모리에만 보관)
+    path: Option<PathBuf>,
+}
+
+impl EmergencyVault {
+    pub fn new(
+        profile: NetworkProfile,
+        config: EmergencyConfig,
+        managers: Vec<PublicKey>,
+        threshold: usize,
+        recovery_key: PublicKey,
+    ) -> Result<Self, EmergencyError> {
+        if threshold == 0 || threshold > managers.len() {
+            return Err(EmergencyError::InvalidConfig(format!(
+                "threshold {} of {} managers",
+                threshold,
+                managers.len()
+            )));
+        }
+        if config.refresh_interval_blocks >= config.timeout_blocks as u32 {
+            return Err(EmergencyError::InvalidConfig(format!(
+                "refresh interval {} must be shorter than timeout {}",
+                config.refresh_interval_blocks, config.timeout_blocks
+            )));
+        }
+        Ok(Self {
+            profile,
+            config,
+            managers,
+            threshold,
+            recovery_key,
+            packages: Vec::new(),
+            path: None,
+        })
+    }
+
+    /// 복구 트랜잭션 기록 파일에서 이어서 시작 (없으면 빈 상태, 이후 서명마다 기록)
+    ///
+    /// 기록된 트랜잭션이 다른 풀 스크립트를 쓰면 거부합니다.
+    pub fn with_store(mut self, path: impl AsRef<Path>) -> Result<Self, EmergencyError> {
+        let path = path.as_ref().to_path_buf();
+        let storage_error = |e: String| EmergencyError::Storage(format!("{}: {}", path.display(), e));
+        let packages: Vec<RecoveryPackage> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| storage_error(e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(storage_error(e.to_string())),
+        };
+        let script = self.pool_script();
+        for package in &packages {
+            let witness_script = hex::decode(&package.raw_tx)
+                .ok()
+                .and_then(|raw| bitcoin::consensus::deserialize::<Transaction>(&raw).ok())
+                .and_then(|tx| tx.input.into_iter().next())
+                .and_then(|input| input.witness.last().map(<[u8]>::to_vec));
+            if witness_script.as_deref() != Some(script.as_bytes()) {
+                return Err(storage_error(format!(
+                    "recovery tx {} does not spend the configured pool script",
+                    package.txid
+                )));
+            }
+        }
+        self.packages = packages;
+        self.path = Some(path);
+        Ok(self)
+    }
+
+    /// 복구 트랜잭션 기록 (임시 파일에 쓴 뒤 교체)
+    fn persist(&self, packages: &[RecoveryPackage]) -> Result<(), EmergencyError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("tmp");
+        serde_json::to_vec_pretty(packages)
+            .map_err(|e| EmergencyError::Storage(e.to_string()))
+            .and_then(|bytes| {
+                std::fs::write(&tmp, bytes)
+                    .and_then(|_| std::fs::rename(&tmp, path))
+                    .map_err(|e| EmergencyError::Storage(format!("{}: {}", path.display(), e)))
+            })
+    }
+
+    pub fn config(&self) -> &EmergencyConfig {
+        &self.config
+    }
+
+    /// 풀 witness 스크립트
+    ///
+    /// `IF <M> <keys..> <N> CHECKMULTISIG ELSE <timeout> CSV DROP <recovery> CHECKSIG ENDIF`
+    pub fn pool_script(&self) -> ScriptBuf {
+        let mut builder = Builder::new()
+            .push_opcode(op::OP_IF)
+            .push_int(self.threshold as i64);
+        for key in &self.managers {
+            builder = builder.push_key(key);
+        }
+        builder
+            .push_int(self.managers.len() as i64)
+            .push_opcode(op::OP_CHECKMULTISIG)
+            .push_opcode(op::OP_ELSE)
+            .push_int(self.config.timeout_blocks as i64)
+            .push_opcode(op::OP_CSV)
+            .push_opcode(op::OP_DROP)
+            .push_key(&self.recovery_key)
+            .push_opcode(op::OP_CHECKSIG)
+            .push_opcode(op::OP_ENDIF)
+            .into_script()
+    }
+
+    /// 풀 자금을 받을 P2WSH 주소
+    pub fn pool_address(&self) -> Address {
+        Address::p2wsh(&self.pool_script(), self.profile.network)
+    }
+
+    /// 가장 최근에 서명한 복구 트랜잭션
+    pub fn current(&self) -> Option<&RecoveryPackage> {
+        self.packages.last()
+    }
+
+    /// 지금까지 서명한 복구 트랜잭션 (마지막이 유효)
+    pub fn history(&self) -> &[RecoveryPackage] {
+        &self.packages
+    }
+
+    /// 다시 서명할 때가 됐는지 (아직 서명한 적이 없어도 true)
+    pub fn needs_refresh(&self, tip: u32) -> bool {
+        self.current().is_none_or(|package| {
+            tip >= package.funding.confirmed_height + self.config.refresh_interval_blocks
+        })
+    }
+
+    /// 새 풀 출력(펀딩 또는 heartbeat)에 대한 복구 트랜잭션 서명 (`pool-recovery` 키)
+    ///
+    /// 이전 출력은 heartbeat로 이미 소비되므로 이전 복구 트랜잭션은 무효가 됩니다.
+    pub fn presign(
+        &mut self,
+        funding: PoolFunding,
+        shares: &[(String, u64)],
+        keys: &dyn KeyStore,
+    ) -> Result<&RecoveryPackage, EmergencyError> {
+        let signing_error = |e: oracle_vm_common::KeyStoreError| EmergencyError::Signing(e.to_string());
+        if PublicKey::new(keys.public_key(RECOVERY_KEY).map_err(signing_error)?) != self.recovery_key {
+            return Err(EmergencyError::Signing(
+                "secret does not match the recovery key".to_string(),
+            ));
+        }
+
+        let payouts = split_payouts(funding.value, self.config.fee_sats, shares)?;
+        let output = payouts
+            .iter()
+            .map(|payout| {
+                let address = payout
+                    .lp_address
+                    .parse::<Address<NetworkUnchecked>>()
+                    .and_then(|a| a.require_network(self.profile.network))
+                    .map_err(|e| EmergencyError::InvalidAddress {
+                        address: payout.lp_address.clone(),
+                        reason: e.to_string(),
+                    })?;
+                Ok(TxOut {
+                    value: Amount::from_sat(payout.amount),
+                    script_pubkey: address.script_pubkey(),
+                })
+            })
+            .collect::<Result<Vec<_>, EmergencyError>>()?;
+
+        let mut tx = Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: funding.outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::from_height(self.config.timeout_blocks),
+                witness: Witness::new(),
+            }],
+            output,
+        };
+
+        let script = self.pool_script();
+        let sighash = SighashCache::new(&tx)
+            .p2wsh_signature_hash(0, &script, funding.value, EcdsaSighashType::All)
+            .map_err(|e| EmergencyError::Signing(e.to_string()))?;
+        let signature = bitcoin::ecdsa::Signature {
+            signature: keys
+                .sign_digest(RECOVERY_KEY, &sighash.to_byte_array())
+                .map_err(signing_error)?,
+            sighash_type: EcdsaSighashType::All,
+        };
+        // 빈 항목으로 ELSE(타임아웃) 경로 선택
+        tx.input[0].witness = Witness::from_slice(&[signature.to_vec(), Vec::new(), script.to_bytes()]);
+
+        let package = RecoveryPackage {
+            funding,
+            payouts,
+            txid: tx.compute_txid().to_string(),
+            raw_tx: bitcoin::consensus::encode::serialize_hex(&tx),
+            broadcastable_at: funding.confirmed_height + self.config.timeout_blocks as u32,
+        };
+        info!(
+            "🛟 Recovery tx {} pre-signed for pool output {} (broadcastable at height {})",
+            package.txid, funding.outpoint, package.broadcastable_at
+        );
+        // 기록에 실패하면 LP에게 배포하지 않은 것으로 보고 메모리에도 남기지 않음
+        let mut packages = self.packages.clone();
+        packages.push(package);
+        self.persist(&packages)?;
+        self.packages = packages;
+        Ok(self.packages.last().unwrap())
+    }
+}
+
+/// LP 측 감시 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum RecoveryState {
+    /// 운영자 heartbeat 대기 중
+    Waiting { blocks_left: u32 },
+    /// 복구 트랜잭션 전송
+    Broadcast { txid: String },
+    /// 이미 멤풀/블록에 있음
+    AlreadyBroadcast { txid: String },
+}
+
+/// LP가 보관한 복구 트랜잭션을 타임락이 풀리면 전송
+pub fn watch_recovery(
+    package: &RecoveryPackage,
+    chain: &dyn ChainSource,
+    broadcaster: &dyn AnchorBroadcaster,
+) -> Result<RecoveryState, EmergencyError> {
+    let tip = chain.tip_height()?;
+    if !package.is_broadcastable(tip) {
+        return Ok(RecoveryState::Waiting {
+            blocks_left: package.broadcastable_at - (tip + 1),
+        });
+    }
+    if chain.tx_status(&package.txid)? != TxStatus::NotFound {
+        return Ok(RecoveryState::AlreadyBroadcast {
+            txid: package.txid.clone(),
+        });
+    }
+
+    let raw = hex::decode(&package.raw_tx)
+        .map_err(|e| EmergencyError::Signing(format!("corrupt recovery tx: {}", e)))?;
+    let txid = broadcaster.rebroadcast(&raw).map_err(|e| {
+        warn!("Recovery tx {} rejected: {}", package.txid, e);
+        e
+    })?;
+    warn!(
+        "🚨 Operators silent for {} blocks, recovery tx {} broadcast",
+        tip + 1 - package.funding.confirmed_height,
+        txid
+    );
+    Ok(RecoveryState::Broadcast { txid })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+    use oracle_vm_common::crypto::MemoryKeyStore;
+    use oracle_vm_common::{AnchorError, ErrorClass};
+    use std::cell::{Cell, RefCell};
+
+    struct MockChain {
+        tip: Cell<u32>,
+    }
+
+    impl ChainSource for MockChain {
+        fn tip_height(&self) -> Result<u32, AnchorError> {
+            Ok(self.tip.get())
+        }
+
+        fn tx_status(&self, _txid: &str) -> Result<TxStatus, AnchorError> {
+            Ok(TxStatus::NotFound)
+        }
+    }
+
+    #[derive(Default)]
+    struct MockBroadcaster {
+        sent: RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl AnchorBroadcaster for MockBroadcaster {
+        fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+            self.sent.borrow_mut().push(raw_tx.to_vec());
+            let tx: Transaction = bitcoin::consensus::deserialize(raw_tx)
+                .map_err(|e| AnchorError::BroadcastRejected(e.to_string()))?;
+            Ok(tx.compute_txid().to_string())
+        }
+
+        fn anchor(&self, _payload: &[u8]) -> Result<String, AnchorError> {
+            unreachable!()
+        }
+    }
+
+    fn key(byte: u8) -> (SecretKey, PublicKey) {
+        let secret = SecretKey::from_slice(&[byte; 32]).unwrap();
+        (secret, PublicKey::new(secret.public_key(&Secp256k1::new())))
+    }
+
+    fn lp_address(byte: u8) -> String {
+        let (_, public) = key(byte);
+        Address::p2wpkh(&public.try_into().unwrap(), NetworkProfile::TESTNET.network).to_string()
+    }
+
+    fn funding(vout: u32, confirmed_height: u32) -> PoolFunding {
+        PoolFunding {
+            outpoint: OutPoint {
+                txid: bitcoin::Txid::all_zeros(),
+                vout,
+            },
+            value: Amount::from_sat(1_000_000),
+            confirmed_height,
+        }
+    }
+
+    fn vault() -> (EmergencyVault, MemoryKeyStore) {
+        let managers = (1..=3).map(|b| key(b).1).collect();
+        let (secret, recovery) = key(9);
+        let config = EmergencyConfig {
+            timeout_blocks: 144,
+            refresh_interval_blocks: 36,
+            fee_sats: 1_000,
+        };
+        let vault =
+            EmergencyVault::new(NetworkProfile::TESTNET, config, managers, 2, recovery).unwrap();
+        (vault, MemoryKeyStore::with_key(RECOVERY_KEY, secret))
+    }
+
+    #[test]
+    fn test_presigned_recovery_pays_lps_after_timeout() {
+        let (mut vault, secret) = vault();
+        let shares = vec![(lp_address(20), 3), (lp_address(21), 1)];
+        assert!(vault.needs_refresh(800_000));
+
+        let package = vault.presign(funding(0, 800_000), &shares, &secret).unwrap().clone();
+        assert_eq!(package.broadcastable_at, 800_144);
+        assert_eq!(package.payouts[0].amount, 749_250);
+        assert_eq!(package.payouts[1].amount, 249_750);
+
+        // CSV 타임아웃 경로 서명 검증
+        let tx: Transaction =
+            bitcoin::consensus::deserialize(&hex::decode(&package.raw_tx).unwrap()).unwrap();
+        assert_eq!(tx.input[0].sequence, Sequence::from_height(144));
+        let witness: Vec<&[u8]> = tx.input[0].witness.iter().collect();
+        assert!(witness[1].is_empty());
+        assert_eq!(witness[2], vault.pool_script().as_bytes());
+        let sighash = SighashCache::new(&tx)
+            .p2wsh_signature_hash(0, &vault.pool_script(), Amount::from_sat(1_000_000), EcdsaSighashType::All)
+            .unwrap();
+        let signature = bitcoin::ecdsa::Signature::from_slice(witness[0]).unwrap();
+        let secp = Secp256k1::new();
+        assert!(secp
+            .verify_ecdsa(&Message::from_digest(sighash.to_byte_array()), &signature.signature, &key(9).1.inner)
+            .is_ok());
+
+        // 운영자가 살아있는 동안은 대기, 타임아웃 후 LP가 전송
+        let chain = MockChain { tip: Cell::new(800_100) };
+        let broadcaster = MockBroadcaster::default();
+        assert_eq!(
+            watch_recovery(&package, &chain, &broadcaster).unwrap(),
+            RecoveryState::Waiting { blocks_left: 43 }
+        );
+        chain.tip.set(800_143);
+        assert_eq!(
+            watch_recovery(&package, &chain, &broadcaster).unwrap(),
+            RecoveryState::Broadcast { txid: package.txid.clone() }
+        );
+        assert_eq!(broadcaster.sent.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_heartbeat_refreshes_recovery() {
+        let (mut vault, secret) = vault();
+        let shares = vec![(lp_address(20), 1)];
+        vault.presign(funding(0, 800_000), &shares, &secret).unwrap();
+        assert!(!vault.needs_refresh(800_035));
+        assert!(vault.needs_refresh(800_036));
+
+        let refreshed = vault.presign(funding(1, 800_036), &shares, &secret).unwrap();
+        assert_eq!(refreshed.broadcastable_at, 800_180);
+        assert_eq!(vault.history().len(), 2);
+        assert_eq!(vault.current().unwrap().funding.outpoint.vout, 1);
+
+        // 잘못된 키, 네트워크가 다른 주소, 수수료보다 작은 출력
+        let wrong = MemoryKeyStore::with_key(RECOVERY_KEY, SecretKey::from_slice(&[8u8; 32]).unwrap());
+        assert!(matches!(
+            vault.presign(funding(2, 800_072), &shares, &wrong),
+            Err(EmergencyError::Signing(_))
+        ));
+        let mainnet = vec![("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(), 1)];
+        assert_eq!(
+            vault.presign(funding(2, 800_072), &mainnet, &secret).unwrap_err().code(),
+            "EMERGENCY_INVALID_ADDRESS"
+        );
+        let dust = PoolFunding {
+            value: Amount::from_sat(500),
+            ..funding(2, 800_072)
+        };
+        assert!(matches!(
+            vault.presign(dust, &shares, &secret),
+            Err(EmergencyError::InsufficientValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_recovery_packages_survive_restart() {
+        let path = std::env::temp_dir().join(format!("btcfi-recovery-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let shares = vec![(lp_address(20), 1)];
+
+        let (vault_a, secret) = vault();
+        let mut vault_a = vault_a.with_store(&path).unwrap();
+        vault_a.presign(funding(0, 800_000), &shares, &secret).unwrap();
+        vault_a.presign(funding(1, 800_036), &shares, &secret).unwrap();
+
+        let restored = vault().0.with_store(&path).unwrap();
+        assert_eq!(restored.history(), vault_a.history());
+        assert!(!restored.needs_refresh(800_071));
+
+        // 다른 복구 키로 만든 금고는 기존 기록을 거부
+        let managers = (1..=3).map(|b| key(b).1).collect();
+        let other = EmergencyVault::new(NetworkProfile::TESTNET, *restored.config(), managers, 2, key(10).1)
+            .unwrap()
+            .with_store(&path);
+        assert_eq!(other.err().unwrap().code(), "EMERGENCY_STORAGE");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
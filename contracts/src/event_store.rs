@@ -0,0 +1,427 @@
+//! 풀 이벤트 저장소
+//!
+//! 유동성 입출금, 옵션 생성/정산 등 풀 상태를 바꾸는 모든 이벤트를
+//! append-only 로그로 기록합니다. 리포트와 감사는 이 로그를 기준으로 합니다.
+
+use crate::fees::FeeKind;
+use crate::reserve::ReserveMovement;
+use anyhow::{Context, Result};
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::BarrierKind;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// 풀 이벤트 종류
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PoolEventKind {
+    /// 유동성 추가
+    LiquidityAdded {
+        provider_id: Option<String>,
+        amount: u64, // satoshis
+    },
+    /// 유동성 제거
+    LiquidityRemoved {
+        provider_id: Option<String>,
+        amount: u64, // satoshis
+    },
+    /// 옵션 생성 (프리미엄 수취)
+    OptionCreated {
+        option_id: String,
+        option_type: OptionType,
+        strike_price: u64, // USD cents
+        quantity: u64,     // satoshis
+        premium: u64,      // satoshis
+        collateral: u64,   // satoshis
+        user_id: String,
+    },
+    /// 옵션 정산 (지급)
+    OptionSettled {
+        option_id: String,
+        spot_price: u64, // USD cents
+        payout: u64,     // satoshis
+        /// dust 기준 미만으로 지급하지 않은 금액 (satoshis)
+        #[serde(default)]
+        dust: u64,
+    },
+    /// 운영자 강제 만료 (지급 없이 담보 반환)
+    OptionExpired {
+        option_id: String,
+        reason: String,
+    },
+    /// 만기 전 되사기 (지급 후 잔여 담보 반환, 옵션 종료)
+    OptionBoughtBack {
+        option_id: String,
+        quote_id: String,
+        spot_price: u64, // USD cents
+        amount: u64,     // satoshis
+    },
+    /// 협의 해지 (구매자/풀 공동 서명 트랜잭션으로 보유자 몫 지급, 잔여 담보 반환, 옵션 종료)
+    OptionClosedCooperatively {
+        option_id: String,
+        quote_id: String,
+        spot_price: u64,    // USD cents
+        holder_amount: u64, // satoshis
+        txid: String,
+    },
+    /// 배리어 첫 접촉 (knock-in 활성화 / knock-out 무효화)
+    BarrierTouched {
+        option_id: String,
+        kind: BarrierKind,
+        level: u64,      // USD cents
+        spot_price: u64, // USD cents
+        /// 합의 가격 관측 시각
+        observed_at: u64,
+    },
+    /// 미국식 옵션 조기 행사 (뒤이어 같은 옵션의 OptionSettled가 기록됨)
+    OptionExercised {
+        option_id: String,
+        user_id: String,
+        spot_price: u64, // USD cents
+    },
+    /// 롤: 기존 옵션 되사기와 더 늦은 만기 옵션 생성을 한 건으로 반영
+    OptionRolled {
+        from_option_id: String,
+        buy_back_quote_id: String,
+        buy_back_amount: u64, // satoshis
+        option_id: String,
+        option_type: OptionType,
+        strike_price: u64, // USD cents
+        quantity: u64,     // satoshis
+        premium: u64,      // satoshis
+        collateral: u64,   // satoshis
+        user_id: String,
+    },
+    /// 프로토콜/정산 수수료 재무 계정 적립
+    FeeCharged {
+        option_id: String,
+        fee: FeeKind,
+        amount: u64, // satoshis
+    },
+    /// 자격 확인 거부 (옵션은 생성되지 않음)
+    EligibilityDenied {
+        option_id: String,
+        user_id: String,
+        provider: String,
+        reason: Option<String>,
+        decision_hash: String, // hex
+    },
+    /// 추천 코드 거래 (리베이트는 추천인 청구 잔고로)
+    ReferralAttributed {
+        option_id: String,
+        code: String,
+        referrer_id: String,
+        premium: u64, // satoshis
+        rebate: u64,  // satoshis
+    },
+    /// 부분 출금 시 잠긴 몫에 대한 청구권 발행
+    ExitClaimIssued {
+        claim_id: u64,
+        provider_id: String,
+        shares: u64,
+        immediate: u64, // satoshis
+        locked: u64,    // satoshis
+    },
+    /// 출금 청구권 양도
+    ExitClaimTransferred {
+        claim_id: u64,
+        from: String,
+        to: String,
+    },
+    /// 재무 계정 출금
+    TreasuryWithdrawn {
+        amount: u64, // satoshis
+        destination: String,
+    },
+    /// 유휴 유동성 수익처 예치/회수/이자
+    ReserveMoved {
+        venue: String,
+        movement: ReserveMovement,
+        amount: u64, // satoshis
+    },
+    /// 옵션 프리미엄 중 담보 사용료 선납분 (LP에게 일할 적립)
+    FundingPrepaid {
+        option_id: String,
+        amount: u64, // satoshis
+        accrue_until: u64,
+    },
+    /// 선납 담보 사용료를 LP 유동성으로 적립
+    FundingAccrued {
+        amount: u64, // satoshis
+        options: u32,
+    },
+    /// 풀 부족분 손실 분담: 정산 시 지급액에서 빠질 삭감
+    PayoutHaircut {
+        option_id: String,
+        user_id: String,
+        owed: u64,      // satoshis, 삭감 전
+        haircut: u64,   // satoshis
+        shortfall: u64, // satoshis, 풀 전체 부족분
+        rank: u32,
+    },
+    /// 청구 잔고 적립 (정산/되사기/롤 지급, 추천 리베이트)
+    ClaimCredited {
+        credit_id: u64,
+        user_id: String,
+        option_id: String,
+        amount: u64, // satoshis
+    },
+    /// 청구 잔고 출금 (적립 여러 건을 한 번의 지급으로)
+    ClaimWithdrawn {
+        withdrawal_id: u64,
+        user_id: String,
+        address: String,
+        amount: u64, // satoshis
+        credit_ids: Vec<u64>,
+    },
+    /// 청구 잔고 출금 지급 트랜잭션 전송
+    ClaimPaid {
+        withdrawal_id: u64,
+        txid: String,
+    },
+    /// 계정 공개키 등록 (보유자/LP 요청 서명 검증용)
+    AccountKeyBound {
+        account: String,
+        public_key: String, // 압축 공개키 hex
+    },
+    /// 운영자의 호가 서명 공개키 교체
+    QuoteKeyRotated {
+        public_key: String, // 압축 공개키 hex
+    },
+    /// 옵션 수익자(정산 지급 주소) 등록
+    BeneficiaryRegistered {
+        option_id: String,
+        address: String,
+    },
+    /// 소유자 서명으로 수익자 지급 주소 변경
+    BeneficiaryChanged {
+        option_id: String,
+        address: String,
+        nonce: u64,
+    },
+    /// 앵커 트랜잭션 전송 (확인 추적 시작)
+    AnchorBroadcast {
+        option_id: String,
+        txid: String,
+    },
+    /// 앵커 트랜잭션이 필요한 확인 수에 도달
+    AnchorConfirmed {
+        option_id: String,
+        txid: String,
+        confirmations: u32,
+    },
+    /// 축출된 앵커를 같은 페이로드의 새 트랜잭션으로 대체
+    AnchorReplaced {
+        option_id: String,
+        old_txid: String,
+        new_txid: String,
+    },
+    /// 서명된 정산 트랜잭션 전송 (옵션 UTXO 점유, 확인 추적 시작)
+    SettlementTxBroadcast {
+        option_id: String,
+        txid: String,
+    },
+    /// 정산 트랜잭션이 필요한 확인 수에 도달
+    SettlementTxConfirmed {
+        option_id: String,
+        txid: String,
+        confirmations: u32,
+    },
+    /// 옵션 UTXO 경쟁 지출 감시 시작
+    UtxoWatched {
+        option_id: String,
+        outpoint: String, // txid:vout
+    },
+    /// 감시 중인 UTXO를 예상하지 않은 트랜잭션이 지출
+    CompetingSpendDetected {
+        option_id: String,
+        outpoint: String,
+        txid: String,
+    },
+    /// 경쟁 지출에 대한 챌린지를 워치타워가 받아들임
+    ChallengeArmed {
+        option_id: String,
+        outpoint: String,
+        txid: String,
+    },
+}
+
+/// 시퀀스 번호와 시간이 붙은 풀 이벤트
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolEvent {
+    pub sequence: u64,
+    pub timestamp: u64, // Unix timestamp (초)
+    pub kind: PoolEventKind,
+}
+
+/// 이벤트 저장소 인터페이스
+pub trait EventStore: Send + Sync {
+    /// 이벤트 추가 후 부여된 시퀀스 번호 반환
+    fn append(&mut self, timestamp: u64, kind: PoolEventKind) -> Result<u64>;
+
+    /// 전체 이벤트 (시퀀스 순)
+    fn events(&self) -> &[PoolEvent];
+
+    /// [from, to] 구간의 이벤트
+    fn events_between(&self, from: u64, to: u64) -> Vec<&PoolEvent> {
+        self.events()
+            .iter()
+            .filter(|event| event.timestamp >= from && event.timestamp <= to)
+            .collect()
+    }
+
+    /// 기록된 이벤트를 디스크까지 내림 (종료 직전 호출)
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 인메모리 이벤트 저장소 (테스트 및 기본값)
+#[derive(Debug, Default)]
+pub struct InMemoryEventStore {
+    events: Vec<PoolEvent>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EventStore for InMemoryEventStore {
+    fn append(&mut self, timestamp: u64, kind: PoolEventKind) -> Result<u64> {
+        let sequence = self.events.len() as u64;
+        self.events.push(PoolEvent {
+            sequence,
+            timestamp,
+            kind,
+        });
+        Ok(sequence)
+    }
+
+    fn events(&self) -> &[PoolEvent] {
+        &self.events
+    }
+}
+
+/// JSON Lines 파일 기반 영구 이벤트 저장소
+///
+/// 한 줄에 이벤트 하나씩 기록하며, 열 때 기존 로그를 모두 읽어 들입니다.
+pub struct FileEventStore {
+    path: PathBuf,
+    events: Vec<PoolEvent>,
+}
+
+impl FileEventStore {
+    /// 파일을 열고 기존 이벤트를 로드 (없으면 새로 생성)
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut events = Vec::new();
+
+        if path.exists() {
+            let file = File::open(&path)
+                .with_context(|| format!("Failed to open event log {}", path.display()))?;
+            for (line_no, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event: PoolEvent = serde_json::from_str(&line).with_context(|| {
+                    format!("Corrupted event log {} at line {}", path.display(), line_no + 1)
+                })?;
+                events.push(event);
+            }
+        }
+
+        Ok(Self { path, events })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl EventStore for FileEventStore {
+    fn append(&mut self, timestamp: u64, kind: PoolEventKind) -> Result<u64> {
+        let event = PoolEvent {
+            sequence: self.events.len() as u64,
+            timestamp,
+            kind,
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open event log {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&event)?)?;
+
+        let sequence = event.sequence;
+        self.events.push(event);
+        Ok(sequence)
+    }
+
+    fn events(&self) -> &[PoolEvent] {
+        &self.events
+    }
+
+    fn flush(&self) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+        File::open(&self.path)
+            .and_then(|file| file.sync_all())
+            .with_context(|| format!("Failed to sync event log {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_memory_store_sequence() {
+        let mut store = InMemoryEventStore::new();
+        let seq0 = store
+            .append(100, PoolEventKind::LiquidityAdded { provider_id: None, amount: 1_000 })
+            .unwrap();
+        let seq1 = store
+            .append(200, PoolEventKind::LiquidityRemoved { provider_id: None, amount: 500 })
+            .unwrap();
+
+        assert_eq!((seq0, seq1), (0, 1));
+        assert_eq!(store.events_between(150, 300).len(), 1);
+    }
+
+    #[test]
+    fn test_file_store_reload() {
+        let path = std::env::temp_dir().join(format!(
+            "btcfi-event-store-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let mut store = FileEventStore::open(&path).unwrap();
+            store
+                .append(
+                    100,
+                    PoolEventKind::OptionSettled {
+                        option_id: "CALL-001".to_string(),
+                        spot_price: 7_200_000,
+                        payout: 277_777,
+                        dust: 0,
+                    },
+                )
+                .unwrap();
+        }
+
+        let reopened = FileEventStore::open(&path).unwrap();
+        assert_eq!(reopened.events().len(), 1);
+        assert_eq!(reopened.events()[0].timestamp, 100);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
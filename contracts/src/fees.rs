@@ -0,0 +1,308 @@
+//! 프로토콜 수수료와 재무 계정
+//!
+//! 옵션 생성 시 프리미엄의 일정 비율(프로토콜 수수료)을, 정산 시 BTC 지급액의
+//! 일정 비율(정산 수수료)을 떼어 LP 풀과 분리된 재무 계정에 적립합니다.
+//! 재무 계정 출금은 허용된 주소로만, 24시간 한도 안에서 할 수 있습니다.
+
+use oracle_vm_common::TreasuryError;
+use serde::{Deserialize, Serialize};
+
+/// 수수료 상한 (bps, 100%)
+const MAX_FEE_BPS: u32 = 10_000;
+/// 출금 한도 집계 구간 (초)
+const WITHDRAWAL_WINDOW_SECS: u64 = 86_400;
+
+/// 수수료 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeKind {
+    /// 옵션 생성 시 프리미엄에서 공제
+    Protocol,
+    /// 정산 시 BTC 지급액에서 공제
+    Settlement,
+}
+
+/// 수수료율 (기본값은 수수료 없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// 프리미엄 대비 프로토콜 수수료 (bps)
+    pub protocol_fee_bps: u32,
+    /// 지급액 대비 정산 수수료 (bps)
+    pub settlement_fee_bps: u32,
+}
+
+impl FeeSchedule {
+    pub fn validate(&self) -> Result<(), TreasuryError> {
+        for (name, bps) in [
+            ("protocol_fee_bps", self.protocol_fee_bps),
+            ("settlement_fee_bps", self.settlement_fee_bps),
+        ] {
+            if bps > MAX_FEE_BPS {
+                return Err(TreasuryError::InvalidSchedule(format!(
+                    "{} {} exceeds {}",
+                    name, bps, MAX_FEE_BPS
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 프리미엄에서 뗄 프로토콜 수수료 (satoshis, 내림)
+    pub fn protocol_fee(&self, premium: u64) -> u64 {
+        bps_of(premium, self.protocol_fee_bps)
+    }
+
+    /// 지급액에서 뗄 정산 수수료 (satoshis, 내림)
+    pub fn settlement_fee(&self, payout: u64) -> u64 {
+        bps_of(payout, self.settlement_fee_bps)
+    }
+}
+
+fn bps_of(amount: u64, bps: u32) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+/// 재무 계정 출금 통제 (기본값은 허용 주소가 없어 출금 불가)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryControls {
+    /// 출금 가능한 주소
+    pub allowed_destinations: Vec<String>,
+    /// 24시간 출금 한도 (satoshis, None = 무제한)
+    pub daily_limit: Option<u64>,
+}
+
+/// 재무 계정 출금 기록
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasuryWithdrawal {
+    pub amount: u64, // satoshis
+    pub destination: String,
+    pub timestamp: u64,
+}
+
+/// LP 유동성과 분리된 프로토콜 수익 계정
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Treasury {
+    /// 출금 가능 잔액 (satoshis)
+    pub balance: u64,
+    /// 누적 프로토콜 수수료 (satoshis)
+    pub protocol_fees: u64,
+    /// 누적 정산 수수료 (satoshis)
+    pub settlement_fees: u64,
+    /// 누적 출금액 (satoshis)
+    pub withdrawn: u64,
+    pub withdrawals: Vec<TreasuryWithdrawal>,
+    #[serde(default)]
+    pub controls: TreasuryControls,
+}
+
+impl Treasury {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 수수료도 출금 기록도 없는 상태 (스냅샷에서 생략)
+    pub fn is_empty(&self) -> bool {
+        self.balance == 0 && self.withdrawn == 0 && self.withdrawals.is_empty()
+    }
+
+    pub fn credit(&mut self, kind: FeeKind, amount: u64) {
+        self.balance += amount;
+        match kind {
+            FeeKind::Protocol => self.protocol_fees += amount,
+            FeeKind::Settlement => self.settlement_fees += amount,
+        }
+    }
+
+    /// `now` 기준 24시간 한도에서 남은 출금 가능액
+    pub fn remaining_daily_limit(&self, now: u64) -> Option<u64> {
+        let limit = self.controls.daily_limit?;
+        let since = now.saturating_sub(WITHDRAWAL_WINDOW_SECS);
+        let used: u64 = self
+            .withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.timestamp > since)
+            .map(|withdrawal| withdrawal.amount)
+            .sum();
+        Some(limit.saturating_sub(used))
+    }
+
+    /// 출금 가능 여부 검사 (상태는 바꾸지 않음)
+    pub fn check_withdrawal(
+        &self,
+        amount: u64,
+        destination: &str,
+        now: u64,
+    ) -> Result<(), TreasuryError> {
+        if !self
+            .controls
+            .allowed_destinations
+            .iter()
+            .any(|allowed| allowed == destination)
+        {
+            return Err(TreasuryError::DestinationNotAllowed(destination.to_string()));
+        }
+        if amount > self.balance {
+            return Err(TreasuryError::InsufficientBalance {
+                requested: amount,
+                available: self.balance,
+            });
+        }
+        if let Some(remaining) = self.remaining_daily_limit(now) {
+            if amount > remaining {
+                return Err(TreasuryError::DailyLimitExceeded {
+                    requested: amount,
+                    remaining,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 검사를 마친 출금 반영
+    pub fn apply_withdrawal(&mut self, amount: u64, destination: &str, now: u64) -> TreasuryWithdrawal {
+        self.balance -= amount;
+        self.withdrawn += amount;
+        let withdrawal = TreasuryWithdrawal {
+            amount,
+            destination: destination.to_string(),
+            timestamp: now,
+        };
+        self.withdrawals.push(withdrawal.clone());
+        withdrawal
+    }
+}
+
+/// 재무 계정 HTTP API (`/admin/treasury`)
+pub mod api {
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use axum::{
+        extract::State,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct TreasuryWithdrawRequest {
+        pub amount: u64,
+        pub destination: String,
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/admin/treasury",
+        tag = "treasury",
+        responses((status = 200, body = Object))
+    )]
+    async fn get_treasury(State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let now = chrono::Utc::now().timestamp() as u64;
+        Json(json!({
+            "fee_schedule": manager.fee_schedule(),
+            "treasury": manager.treasury(),
+            "remaining_daily_limit": manager.treasury().remaining_daily_limit(now),
+        }))
+        .into_response()
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/treasury/withdraw",
+        tag = "treasury",
+        request_body = TreasuryWithdrawRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn withdraw(
+        State(manager): State<SharedManager>,
+        Json(request): Json<TreasuryWithdrawRequest>,
+    ) -> Response {
+        if request.amount == 0 || request.destination.is_empty() {
+            return bad_request("amount and destination are required");
+        }
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.withdraw_treasury(request.amount, &request.destination) {
+            Ok(withdrawal) => Json(withdrawal).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/admin/treasury` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/admin/treasury", get(get_treasury))
+            .route("/admin/treasury/withdraw", post(withdraw))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_schedule_rounds_down_and_validates() {
+        let schedule = FeeSchedule {
+            protocol_fee_bps: 250,
+            settlement_fee_bps: 10,
+        };
+        assert!(schedule.validate().is_ok());
+        assert_eq!(schedule.protocol_fee(250_000), 6_250);
+        assert_eq!(schedule.settlement_fee(999), 0);
+        assert_eq!(FeeSchedule::default().protocol_fee(250_000), 0);
+
+        let invalid = FeeSchedule {
+            protocol_fee_bps: 10_001,
+            settlement_fee_bps: 0,
+        };
+        assert!(matches!(invalid.validate(), Err(TreasuryError::InvalidSchedule(_))));
+    }
+
+    #[test]
+    fn test_withdrawal_controls() {
+        let mut treasury = Treasury::new();
+        treasury.credit(FeeKind::Protocol, 60_000);
+        treasury.credit(FeeKind::Settlement, 40_000);
+        assert_eq!(treasury.balance, 100_000);
+
+        // 허용 주소가 없으면 출금 불가
+        assert!(matches!(
+            treasury.check_withdrawal(1_000, "treasury-cold", 1_000),
+            Err(TreasuryError::DestinationNotAllowed(_))
+        ));
+
+        treasury.controls = TreasuryControls {
+            allowed_destinations: vec!["treasury-cold".to_string()],
+            daily_limit: Some(50_000),
+        };
+        assert!(matches!(
+            treasury.check_withdrawal(200_000, "treasury-cold", 1_000),
+            Err(TreasuryError::InsufficientBalance { .. })
+        ));
+        treasury.check_withdrawal(30_000, "treasury-cold", 1_000).unwrap();
+        treasury.apply_withdrawal(30_000, "treasury-cold", 1_000);
+        assert_eq!(
+            treasury.check_withdrawal(30_000, "treasury-cold", 2_000),
+            Err(TreasuryError::DailyLimitExceeded {
+                requested: 30_000,
+                remaining: 20_000,
+            })
+        );
+
+        // 24시간이 지나면 한도 회복
+        treasury.check_withdrawal(50_000, "treasury-cold", 1_000 + 86_400).unwrap();
+        assert_eq!(treasury.balance, 70_000);
+        assert_eq!(treasury.withdrawn, 30_000);
+    }
+}
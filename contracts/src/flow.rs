@@ -0,0 +1,456 @@
+//! 주기 작업 흐름(flow) 프레임워크
+//!
+//! 서버의 백그라운드 작업(웹훅 전달, 가격 커밋먼트 등)을 단계(`Step`)의
+//! 연결로 선언합니다. 각 단계는 입력/출력 타입을 가지며 재시도·백오프·타임아웃
+//! 정책(`StepPolicy`)을 따로 갖습니다. 실행 결과는 `FlowMetrics`에 단계별로
+//! 쌓이고, 흐름/단계마다 tracing span이 열립니다.
+//!
+//! ```ignore
+//! let flow = Flow::new("price_commitment.record", metrics)
+//!     .then(FetchConsensusPrice::new(url))
+//!     .then(RecordPrice::new(log));
+//! flow.run_every(Duration::from_secs(60), now, shutdown.signal()).await;
+//! ```
+
+use async_trait::async_trait;
+use oracle_vm_common::{FlowError, ShutdownSignal};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info_span, warn, Instrument};
+
+/// 단계별 재시도/타임아웃 정책
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepPolicy {
+    /// 최초 시도를 포함한 최대 시도 횟수
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// 시도 1회당 제한 시간
+    pub timeout: Option<Duration>,
+}
+
+impl Default for StepPolicy {
+    fn default() -> Self {
+        Self::once()
+    }
+}
+
+impl StepPolicy {
+    /// 재시도 없이 한 번만 실행
+    pub fn once() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            timeout: None,
+        }
+    }
+
+    /// 지수 백오프 재시도 (최대 대기 30초)
+    pub fn retry(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay: Duration::from_secs(30),
+            timeout: None,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// `attempts`번 실패한 뒤 다음 시도까지 대기 시간
+    pub fn delay_after(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+/// 흐름의 한 단계
+///
+/// 재시도 때 같은 입력으로 다시 실행되므로 입력은 참조로 받습니다.
+#[async_trait]
+pub trait Step: Send + Sync {
+    type Input: Send + Sync;
+    type Output: Send;
+
+    fn name(&self) -> &'static str;
+
+    fn policy(&self) -> StepPolicy {
+        StepPolicy::once()
+    }
+
+    async fn run(&self, input: &Self::Input) -> Result<Self::Output, String>;
+}
+
+/// 단계별 실행 통계
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StepStats {
+    pub flow: String,
+    pub step: String,
+    pub attempts: u64,
+    pub successes: u64,
+    /// 실패한 시도 (타임아웃 포함)
+    pub failures: u64,
+    pub timeouts: u64,
+    /// 마지막 성공 이후 연속 실패 (경보 규칙 입력)
+    pub consecutive_failures: u64,
+    pub last_duration_ms: u64,
+    pub last_error: Option<String>,
+}
+
+/// 흐름 실행 통계 (여러 흐름이 공유)
+#[derive(Debug, Default)]
+pub struct FlowMetrics {
+    steps: Mutex<BTreeMap<(&'static str, &'static str), StepStats>>,
+}
+
+/// 실패한 시도
+enum Failure {
+    Error(String),
+    TimedOut,
+}
+
+impl FlowMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(
+        &self,
+        flow: &'static str,
+        step: &'static str,
+        failure: Option<&Failure>,
+        elapsed: Duration,
+    ) {
+        let mut steps = self.steps.lock().unwrap();
+        let stats = steps.entry((flow, step)).or_insert_with(|| StepStats {
+            flow: flow.to_string(),
+            step: step.to_string(),
+            ..StepStats::default()
+        });
+        stats.attempts += 1;
+        stats.last_duration_ms = elapsed.as_millis() as u64;
+        match failure {
+            None => {
+                stats.successes += 1;
+                stats.consecutive_failures = 0;
+            }
+            Some(Failure::Error(reason)) => {
+                stats.failures += 1;
+                stats.consecutive_failures += 1;
+                stats.last_error = Some(reason.clone());
+            }
+            Some(Failure::TimedOut) => {
+                stats.failures += 1;
+                stats.consecutive_failures += 1;
+                stats.timeouts += 1;
+                stats.last_error = Some("timed out".to_string());
+            }
+        }
+    }
+
+    pub fn step(&self, flow: &str, step: &str) -> Option<StepStats> {
+        self.steps
+            .lock()
+            .unwrap()
+            .values()
+            .find(|stats| stats.flow == flow && stats.step == step)
+            .cloned()
+    }
+
+    /// 흐름/단계 이름 순 전체 통계
+    pub fn snapshot(&self) -> Vec<StepStats> {
+        self.steps.lock().unwrap().values().cloned().collect()
+    }
+}
+
+/// 정책에 따라 한 단계를 실행 (재시도/타임아웃/통계)
+async fn execute<S: Step>(
+    flow: &'static str,
+    step: &S,
+    input: &S::Input,
+    metrics: &FlowMetrics,
+) -> Result<S::Output, FlowError> {
+    let policy = step.policy();
+    let name = step.name();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        let started = Instant::now();
+        let result = match policy.timeout {
+            Some(limit) => match tokio::time::timeout(limit, step.run(input)).await {
+                Ok(result) => result.map_err(Failure::Error),
+                Err(_) => Err(Failure::TimedOut),
+            },
+            None => step.run(input).await.map_err(Failure::Error),
+        };
+        metrics.record(flow, name, result.as_ref().err(), started.elapsed());
+
+        let failure = match result {
+            Ok(output) => return Ok(output),
+            Err(failure) => failure,
+        };
+        if attempts >= policy.max_attempts {
+            return Err(match failure {
+                Failure::Error(reason) => FlowError::StepFailed {
+                    flow: flow.to_string(),
+                    step: name.to_string(),
+                    attempts,
+                    reason,
+                },
+                Failure::TimedOut => FlowError::StepTimedOut {
+                    flow: flow.to_string(),
+                    step: name.to_string(),
+                    attempts,
+                    timeout_ms: policy.timeout.unwrap_or_default().as_millis() as u64,
+                },
+            });
+        }
+        let delay = policy.delay_after(attempts);
+        warn!(
+            "Flow {} step {} attempt {}/{} failed, retrying in {:?}",
+            flow, name, attempts, policy.max_attempts, delay
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+type StageFuture<O> = Pin<Box<dyn Future<Output = Result<O, FlowError>> + Send>>;
+type Stage<I, O> = Arc<dyn Fn(I) -> StageFuture<O> + Send + Sync>;
+
+/// 단계들을 순서대로 잇는 흐름 (`I` → ... → `O`)
+pub struct Flow<I, O> {
+    name: &'static str,
+    metrics: Arc<FlowMetrics>,
+    stage: Stage<I, O>,
+}
+
+impl<I: Send + 'static> Flow<I, I> {
+    /// 단계가 없는 흐름 (입력을 그대로 반환)
+    pub fn new(name: &'static str, metrics: Arc<FlowMetrics>) -> Self {
+        Self {
+            name,
+            metrics,
+            stage: Arc::new(|input| Box::pin(async move { Ok(input) })),
+        }
+    }
+}
+
+impl<I: Send + 'static, O: Send + Sync + 'static> Flow<I, O> {
+    /// 마지막 단계의 출력을 입력으로 받는 단계 추가
+    pub fn then<S>(self, step: S) -> Flow<I, S::Output>
+    where
+        S: Step<Input = O> + 'static,
+        S::Output: 'static,
+    {
+        let flow = self.name;
+        let previous = self.stage;
+        let metrics = self.metrics.clone();
+        let step = Arc::new(step);
+        let stage: Stage<I, S::Output> = Arc::new(move |input| {
+            let previous = previous.clone();
+            let metrics = metrics.clone();
+            let step = step.clone();
+            Box::pin(async move {
+                let output = previous(input).await?;
+                let span = info_span!("flow_step", flow, step = step.name());
+                execute(flow, step.as_ref(), &output, &metrics)
+                    .instrument(span)
+                    .await
+            })
+        });
+        Flow {
+            name: self.name,
+            metrics: self.metrics,
+            stage,
+        }
+    }
+}
+
+impl<I, O> Flow<I, O> {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn metrics(&self) -> &Arc<FlowMetrics> {
+        &self.metrics
+    }
+
+    /// 흐름을 한 번 실행 (처음 실패한 단계에서 중단)
+    pub async fn run(&self, input: I) -> Result<O, FlowError> {
+        (self.stage)(input)
+            .instrument(info_span!("flow", flow = self.name))
+            .await
+    }
+
+    /// `period`마다 실행, 실패는 로그만 남기고 다음 주기에 다시 시도
+    ///
+    /// 종료 신호를 받으면 새 실행을 시작하지 않고, 진행 중인 실행은 끝까지 마칩니다.
+    pub async fn run_every(
+        &self,
+        period: Duration,
+        mut input: impl FnMut() -> I,
+        mut shutdown: ShutdownSignal,
+    ) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => return,
+            }
+            let Some(_work) = shutdown.begin_work() else {
+                return;
+            };
+            if let Err(e) = self.run(input()).await {
+                warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// `/admin/flows` 흐름 실행 통계 API
+pub mod api {
+    use super::FlowMetrics;
+    use axum::{extract::State, routing::get, Json, Router};
+    use std::sync::Arc;
+
+    #[utoipa::path(
+        get,
+        path = "/admin/flows",
+        tag = "admin",
+        responses((status = 200, body = [Object]))
+    )]
+    async fn list_flows(State(metrics): State<Arc<FlowMetrics>>) -> Json<Vec<super::StepStats>> {
+        Json(metrics.snapshot())
+    }
+
+    /// `/admin/flows` 라우터 생성
+    pub fn router(metrics: Arc<FlowMetrics>) -> Router {
+        Router::new()
+            .route("/admin/flows", get(list_flows))
+            .with_state(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Flaky {
+        failures_left: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Step for Flaky {
+        type Input = u64;
+        type Output = u64;
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn policy(&self) -> StepPolicy {
+            StepPolicy::retry(3, Duration::from_millis(1))
+        }
+
+        async fn run(&self, input: &u64) -> Result<u64, String> {
+            if self.failures_left.load(Ordering::SeqCst) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::SeqCst);
+                return Err("connection reset".to_string());
+            }
+            Ok(input * 2)
+        }
+    }
+
+    struct Describe;
+
+    #[async_trait]
+    impl Step for Describe {
+        type Input = u64;
+        type Output = String;
+
+        fn name(&self) -> &'static str {
+            "describe"
+        }
+
+        async fn run(&self, input: &u64) -> Result<String, String> {
+            Ok(format!("value={}", input))
+        }
+    }
+
+    struct Hang;
+
+    #[async_trait]
+    impl Step for Hang {
+        type Input = String;
+        type Output = ();
+
+        fn name(&self) -> &'static str {
+            "hang"
+        }
+
+        fn policy(&self) -> StepPolicy {
+            StepPolicy::retry(2, Duration::from_millis(1)).with_timeout(Duration::from_millis(5))
+        }
+
+        async fn run(&self, _input: &String) -> Result<(), String> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        }
+    }
+
+    fn flaky(failures: u32) -> Flaky {
+        Flaky {
+            failures_left: AtomicU32::new(failures),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_steps_retry_and_chain() {
+        let metrics = Arc::new(FlowMetrics::new());
+        let flow = Flow::new("double", metrics.clone())
+            .then(flaky(2))
+            .then(Describe);
+        assert_eq!(flow.run(21).await.unwrap(), "value=42");
+
+        let stats = metrics.step("double", "flaky").unwrap();
+        assert_eq!((stats.attempts, stats.successes, stats.failures), (3, 1, 2));
+        assert_eq!(stats.consecutive_failures, 0);
+        assert_eq!(metrics.step("double", "describe").unwrap().attempts, 1);
+
+        let exhausted = Flow::new("exhausted", metrics.clone()).then(flaky(5));
+        assert_eq!(
+            exhausted.run(1).await,
+            Err(FlowError::StepFailed {
+                flow: "exhausted".to_string(),
+                step: "flaky".to_string(),
+                attempts: 3,
+                reason: "connection reset".to_string(),
+            })
+        );
+        assert_eq!(metrics.step("exhausted", "flaky").unwrap().consecutive_failures, 3);
+    }
+
+    #[tokio::test]
+    async fn test_step_timeout_stops_flow() {
+        let metrics = Arc::new(FlowMetrics::new());
+        let flow = Flow::new("slow", metrics.clone()).then(Describe).then(Hang);
+        assert!(matches!(
+            flow.run(1).await,
+            Err(FlowError::StepTimedOut { attempts: 2, timeout_ms: 5, .. })
+        ));
+        assert_eq!(metrics.step("slow", "hang").unwrap().timeouts, 2);
+        assert_eq!(metrics.snapshot().len(), 2);
+    }
+}
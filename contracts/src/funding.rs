@@ -0,0 +1,197 @@
+//! 잠긴 담보 사용료 (funding) 일할 적립
+//!
+//! 옵션 매수자는 만기까지 풀 담보를 잡아 두는 대가로 담보 × 연율 × 잔존 기간만큼의
+//! 사용료를 프리미엄 안에 선납합니다. 선납분은 생성 시 LP 유동성에 바로 더하지 않고
+//! 풀 원장의 `Funding` 계정에 두었다가, 하루 단위로 경과 기간만큼 사용 가능한
+//! 유동성으로 옮깁니다. 그래서 LP 순자산은 담보가 실제로 잠겨 있는 기간에 걸쳐
+//! 늘어납니다. 만기 전에 정산/되사기/취소로 담보가 풀린 옵션의 남은 선납분은
+//! 다음 적립 때 한 번에 옮깁니다.
+
+use crate::reserve::SECONDS_PER_YEAR;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 하루 (초, 적립 단위)
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// 옵션 하나의 선납 사용료 적립 일정
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingSchedule {
+    /// 선납 사용료 (satoshis)
+    pub prepaid: u64,
+    /// LP 유동성으로 옮긴 누적액 (satoshis)
+    pub accrued: u64,
+    /// 적립 시작 시각 (옵션 생성, Unix seconds)
+    pub start: u64,
+    /// 적립 종료 시각 (예상 만기, Unix seconds)
+    pub end: u64,
+}
+
+impl FundingSchedule {
+    /// `now`까지 옮겨야 할 누적액 (경과 일수 기준 내림, 종료 후에는 전액)
+    pub fn accrued_by(&self, now: u64) -> u64 {
+        if now >= self.end || self.end <= self.start {
+            return self.prepaid;
+        }
+        let elapsed = now.saturating_sub(self.start) / SECONDS_PER_DAY * SECONDS_PER_DAY;
+        (self.prepaid as u128 * elapsed as u128 / (self.end - self.start) as u128) as u64
+    }
+
+    pub fn unaccrued(&self) -> u64 {
+        self.prepaid - self.accrued
+    }
+}
+
+/// 한 번의 적립에서 옵션별로 옮길 금액
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccrualPlan {
+    pub entries: Vec<(String, u64)>,
+    /// 전액 적립되어 일정에서 빠질 옵션
+    pub finished: Vec<String>,
+}
+
+impl AccrualPlan {
+    pub fn total(&self) -> u64 {
+        self.entries.iter().map(|(_, amount)| amount).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.finished.is_empty()
+    }
+}
+
+/// 사용료율과 옵션별 적립 일정
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FundingBook {
+    /// 잠긴 담보에 대한 연 사용료율 (bps, 0이면 사용료 없음)
+    annual_bps: u32,
+    schedules: BTreeMap<String, FundingSchedule>,
+}
+
+impl FundingBook {
+    pub fn new(annual_bps: u32) -> Self {
+        Self {
+            annual_bps,
+            schedules: BTreeMap::new(),
+        }
+    }
+
+    pub fn annual_bps(&self) -> u32 {
+        self.annual_bps
+    }
+
+    /// 사용료율 변경 (이후 생성 옵션부터 적용, 기존 일정은 유지)
+    pub fn set_annual_bps(&mut self, annual_bps: u32) {
+        self.annual_bps = annual_bps;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.annual_bps == 0 && self.schedules.is_empty()
+    }
+
+    /// 담보를 `secs` 동안 잡는 사용료 (satoshis, 내림)
+    pub fn charge(&self, collateral: u64, secs: u64) -> u64 {
+        (collateral as u128 * self.annual_bps as u128 * secs as u128
+            / (10_000 * SECONDS_PER_YEAR as u128)) as u64
+    }
+
+    pub fn schedule(&self, option_id: &str) -> Option<&FundingSchedule> {
+        self.schedules.get(option_id)
+    }
+
+    /// 선납 사용료 적립 일정 등록
+    pub fn open(&mut self, option_id: &str, prepaid: u64, start: u64, end: u64) {
+        self.schedules.insert(
+            option_id.to_string(),
+            FundingSchedule {
+                prepaid,
+                accrued: 0,
+                start,
+                end,
+            },
+        );
+    }
+
+    /// 아직 LP 유동성으로 옮기지 않은 선납 사용료 합계
+    pub fn unaccrued(&self) -> u64 {
+        self.schedules.values().map(FundingSchedule::unaccrued).sum()
+    }
+
+    /// `now`까지 옮길 금액 계산 (`is_open`이 false인 옵션은 남은 선납분 전액)
+    pub fn plan(&self, now: u64, is_open: impl Fn(&str) -> bool) -> AccrualPlan {
+        let mut plan = AccrualPlan::default();
+        for (option_id, schedule) in &self.schedules {
+            let target = if is_open(option_id) {
+                schedule.accrued_by(now)
+            } else {
+                schedule.prepaid
+            };
+            let amount = target.saturating_sub(schedule.accrued);
+            if amount > 0 {
+                plan.entries.push((option_id.clone(), amount));
+            }
+            if target == schedule.prepaid {
+                plan.finished.push(option_id.clone());
+            }
+        }
+        plan
+    }
+
+    /// 원장에 반영한 적립 계획을 일정에 반영
+    pub fn apply(&mut self, plan: &AccrualPlan) {
+        for (option_id, amount) in &plan.entries {
+            if let Some(schedule) = self.schedules.get_mut(option_id) {
+                schedule.accrued += amount;
+            }
+        }
+        for option_id in &plan.finished {
+            self.schedules.remove(option_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charge_scales_with_collateral_rate_and_time() {
+        let book = FundingBook::new(500); // 연 5%
+        assert_eq!(book.charge(100_000_000, SECONDS_PER_YEAR), 5_000_000);
+        assert_eq!(book.charge(100_000_000, SECONDS_PER_YEAR / 2), 2_500_000);
+        assert_eq!(FundingBook::new(0).charge(100_000_000, SECONDS_PER_YEAR), 0);
+    }
+
+    #[test]
+    fn test_accrues_by_whole_days() {
+        let mut book = FundingBook::new(500);
+        book.open("CALL-1", 10_000, 0, 10 * SECONDS_PER_DAY);
+
+        // 하루가 지나기 전에는 적립 없음
+        assert!(book.plan(SECONDS_PER_DAY - 1, |_| true).is_empty());
+
+        let plan = book.plan(3 * SECONDS_PER_DAY + 100, |_| true);
+        assert_eq!(plan.entries, vec![("CALL-1".to_string(), 3_000)]);
+        assert!(plan.finished.is_empty());
+        book.apply(&plan);
+        assert_eq!(book.unaccrued(), 7_000);
+
+        // 만기 이후 나머지 전액
+        let plan = book.plan(11 * SECONDS_PER_DAY, |_| true);
+        assert_eq!(plan.total(), 7_000);
+        book.apply(&plan);
+        assert!(book.schedule("CALL-1").is_none());
+    }
+
+    #[test]
+    fn test_closed_option_accrues_remainder_at_once() {
+        let mut book = FundingBook::new(500);
+        book.open("PUT-1", 9_000, 0, 30 * SECONDS_PER_DAY);
+
+        let plan = book.plan(SECONDS_PER_DAY, |_| false);
+        assert_eq!(plan.total(), 9_000);
+        assert_eq!(plan.finished, vec!["PUT-1".to_string()]);
+        book.apply(&plan);
+        assert_eq!(book.unaccrued(), 0);
+    }
+}
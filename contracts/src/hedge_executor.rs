@@ -0,0 +1,113 @@
+//! 델타 헷지 리밸런싱 큐
+//!
+//! `BuyerOnlyOptionManager`가 순 델타가 임계값을 넘으면 `RebalanceRequest`를
+//! 큐에 넣고, 워커가 `HedgeExecutor`로 외부 거래소 주문을 실행한 뒤 체결
+//! 결과를 다시 풀의 `HedgePositions`에 기록합니다.
+
+use crate::buyer_only_option::BuyerOnlyOptionManager;
+use async_trait::async_trait;
+use oracle_vm_common::HedgeError;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::{info, warn};
+
+/// 헷지 리밸런싱 요청
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceRequest {
+    pub request_id: u64,
+    /// 요청 시점 옵션 순 델타 (BTC)
+    pub net_delta: f64,
+    /// 매수(+)/매도(-)할 BTC 수량
+    pub size: f64,
+    pub requested_at: u64,
+}
+
+/// 헷지 주문 체결 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HedgeFill {
+    pub request_id: u64,
+    pub venue: String,
+    /// 실제 체결 수량 (부분 체결 가능)
+    pub executed_size: f64,
+    pub executed_at: u64,
+}
+
+/// 리밸런싱 이력 (의도한 수량 대비 체결 수량)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceRecord {
+    pub request_id: u64,
+    pub net_delta: f64,
+    pub intended_size: f64,
+    pub executed_size: Option<f64>,
+    pub venue: Option<String>,
+    pub requested_at: u64,
+    pub executed_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl RebalanceRecord {
+    /// 워커가 아직 처리하지 않은 요청
+    pub fn is_pending(&self) -> bool {
+        self.executed_size.is_none() && self.error.is_none()
+    }
+}
+
+/// 외부 거래소 헷지 주문 실행기
+#[async_trait]
+pub trait HedgeExecutor: Send + Sync {
+    async fn execute(&self, request: &RebalanceRequest) -> Result<HedgeFill, HedgeError>;
+}
+
+/// 주문 없이 요청 수량 그대로 체결 처리 (테스트넷/모의 운용)
+pub struct PaperHedgeExecutor {
+    venue: String,
+}
+
+impl PaperHedgeExecutor {
+    pub fn new(venue: &str) -> Self {
+        Self {
+            venue: venue.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl HedgeExecutor for PaperHedgeExecutor {
+    async fn execute(&self, request: &RebalanceRequest) -> Result<HedgeFill, HedgeError> {
+        Ok(HedgeFill {
+            request_id: request.request_id,
+            venue: self.venue.clone(),
+            executed_size: request.size,
+            executed_at: chrono::Utc::now().timestamp() as u64,
+        })
+    }
+}
+
+/// 리밸런싱 큐 소비 워커 (큐의 송신측이 모두 닫히면 종료)
+pub async fn run_hedge_worker(
+    mut requests: UnboundedReceiver<RebalanceRequest>,
+    executor: Arc<dyn HedgeExecutor>,
+    manager: Arc<Mutex<BuyerOnlyOptionManager>>,
+) {
+    while let Some(request) = requests.recv().await {
+        let result = executor.execute(&request).await;
+        let mut manager = manager.lock().unwrap();
+        let recorded = match result {
+            Ok(fill) => {
+                info!(
+                    "Hedge {} filled on {}: {:.4} of {:.4} BTC",
+                    request.request_id, fill.venue, fill.executed_size, request.size
+                );
+                manager.record_hedge_fill(&fill)
+            }
+            Err(e) => {
+                warn!("Hedge {} failed: {}", request.request_id, e);
+                manager.record_hedge_failure(request.request_id, &e)
+            }
+        };
+        if let Err(e) = recorded {
+            warn!("Could not record hedge result: {}", e);
+        }
+    }
+}
@@ -1,18 +1,110 @@
 pub mod simple_contract;
 pub mod bitcoin_option;
 pub mod bitvmx_bridge;
+pub mod bitvmx_backend;
+pub mod program_binding;
 pub mod testnet_deployer;
 pub mod buyer_only_option;
 pub mod price_feed_client;
 pub mod bitvmx_proof_generator;
 pub mod bitvmx_presign;
 pub mod bitvmx_emulator_integration;
+pub mod event_store;
+pub mod fees;
+pub mod referral;
+pub mod lp_book;
+pub mod tenant;
+pub mod eligibility;
+pub mod theta_policy;
+pub mod pool_ledger;
+pub mod option_index;
+pub mod reporting;
+pub mod contract_service;
+pub mod snapshot;
+pub mod structured_notes;
+pub mod admin_api;
+pub mod adl;
+pub mod beneficiary;
+pub mod barrier;
+pub mod binary;
+pub mod buy_back;
+pub mod early_exercise;
+pub mod webhooks;
+pub mod anchor_tracker;
+pub mod settlement_broadcast;
+pub mod mempool_watch;
+pub mod anchor_backend;
+pub mod price_commitment;
+pub mod price_guard;
+pub mod hedge_executor;
+pub mod emergency;
+pub mod dual_currency;
+pub mod claimable;
+pub mod account_keys;
+pub mod flow;
+pub mod audit;
+pub mod proof_archive;
+pub mod openapi;
+pub mod bootstrap;
+pub mod tracing_context;
+pub mod alerting;
+pub mod otc;
+pub mod reserve;
+pub mod funding;
+pub mod block_time;
+pub mod taproot_address;
+pub mod cooperative_close;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(feature = "position-tokens")]
+pub mod position_token;
 
 pub use simple_contract::{
-    OptionStatus, SimpleContractManager, SimpleOption, SimplePoolState,
+    CreateOptionRequest, OptionStatus, OptionTerms, RiskLimits, SimpleContractManager, SimpleOption, SimplePoolState,
+    TradingHalt,
 };
 pub use buyer_only_option::{
     BuyerOnlyOption, BuyerOnlyOptionManager, DeltaNeutralPool, AggregatedPrice,
 };
-pub use price_feed_client::{PriceFeedClient, PriceFeedService};
+pub use price_feed_client::{PriceFeedClient, PriceFeedService, TradingStatusSync};
+pub use event_store::{EventStore, FileEventStore, InMemoryEventStore, PoolEvent, PoolEventKind};
+pub use pool_ledger::{LedgerTransaction, PoolLedger, Posting};
+pub use fees::{FeeKind, FeeSchedule, Treasury, TreasuryControls, TreasuryWithdrawal};
+pub use lp_book::{ExitClaim, ExitPlan, LpBook};
+pub use referral::{RebateTier, ReferralAttribution, ReferralCode, ReferralProgram, ReferralStats};
+pub use theta_policy::{ThetaBand, ThetaPolicyConfig, ThetaPolicyEngine, ThetaPolicyInputs};
+pub use tenant::{Tenant, TenantConfig, TenantRegistry};
+pub use eligibility::{
+    AllowlistEligibility, EligibilityDecision, EligibilityProvider, EligibilityRequest, HttpEligibility, NoopEligibility,
+};
+pub use contract_service::ContractService;
+pub use snapshot::{AnchorRecord, AnchorSource, PendingSettlement, SystemSnapshot, SNAPSHOT_SCHEMA_VERSION};
+pub use beneficiary::{Beneficiary, BeneficiaryRegistry, BeneficiaryUpdate};
+pub use anchor_backend::{
+    AnchorBackend, AnchorChain, AnchorKind, AnchorRouting, BitcoinAnchorer, LiquidAnchorer, RoutedAnchorer,
+};
+pub use anchor_tracker::{AnchorAlert, AnchorStatus, AnchorTracker};
+pub use hedge_executor::{HedgeExecutor, HedgeFill, PaperHedgeExecutor, RebalanceRecord, RebalanceRequest};
+pub use audit::{AuditAction, AuditLog, AuditRecord};
+pub use program_binding::ProgramBinding;
+pub use proof_archive::{ArchiveEntry, FileProofStore, InMemoryProofStore, ProofArchive, ProofBundle, ProofStore};
+pub use claimable::{ClaimCredit, ClaimRecords, ClaimableLedger, Withdrawal};
+pub use account_keys::{AccountKeys, AccountSignature};
+pub use flow::{Flow, FlowMetrics, Step, StepPolicy, StepStats};
+#[cfg(feature = "chaos")]
+pub use chaos::{ChaosBitVmx, ChaosBroadcaster, ChaosConfig, ChaosInjector, ChaosStats, ChaosStep, ChaosTransport};
+pub use dual_currency::{Conversion, UsdPayout, UsdPoolBook};
+pub use emergency::{EmergencyConfig, EmergencyVault, PoolFunding, RecoveryPackage, RecoveryState};
+#[cfg(feature = "position-tokens")]
+pub use position_token::{
+    IssueRequest, MockIssuer, PositionToken, PositionTokenIssuer, PositionTokenRegistry, TokenStandard,
+};
+pub use price_commitment::{PriceCommitment, PriceCommitmentLog, PriceProof};
+pub use price_guard::{PriceBandAlert, PriceBandConfig, PriceBandGuard};
+pub use webhooks::{WebhookDispatcher, WebhookEvent, WebhookEventKind};
+pub use reporting::{ReportFormat, ReportGenerator, ReportKind, ReportTable};
 pub use oracle_vm_common::types::OptionType;
+pub use oracle_vm_common::{
+    BeneficiaryError, ClaimError, ContractError, EmergencyError, ErrorClass, FlowError,
+    SettlementError, SnapshotError, TokenError, TreasuryError,
+};
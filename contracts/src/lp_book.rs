@@ -0,0 +1,627 @@
+//! LP 지분과 잠긴 담보에 대한 출금 청구권
+//!
+//! 유동성 대부분이 옵션 담보로 잠겨 있으면 LP는 지분을 전혀 뺄 수 없습니다.
+//! 부분 출금 모드에서는 나가는 LP가 지분 가치 중 사용 가능한 유동성 몫을 바로
+//! 받고, 잠긴 몫은 출금 청구권(`ExitClaim`)으로 받습니다. 청구권은 출금 시점에
+//! 활성이던 옵션들의 담보에 대한 비례 지분이라, 그 옵션들이 정산/만료되어 담보가
+//! 풀리는 만큼 상환 가능액이 늘고 지급액만큼은 같은 비율로 손실을 나눠 집니다.
+//! 청구권은 다른 사용자에게 양도할 수 있습니다.
+//!
+//! 지분 가치는 풀 총 유동성에서 청구권 몫을 뺀 순자산 기준입니다. 지분 없이 들어온
+//! 유동성(`add_liquidity`)은 지분 보유자에게 귀속되며, 첫 LP 입금 전에 이미 있던
+//! 순자산은 재무 계정(`TREASURY_PROVIDER`) 지분으로 먼저 발행해 첫 입금자가 가져가지
+//! 못하게 합니다.
+
+use crate::simple_contract::SimplePoolState;
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 첫 LP 입금 전 순자산을 받는 재무 계정 (키는 운영자만 묶을 수 있음)
+pub const TREASURY_PROVIDER: &str = "treasury";
+
+/// 잠긴 담보에 대한 출금 청구권
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitClaim {
+    pub claim_id: u64,
+    pub holder: String,
+    /// 발행 시 청구권 몫 (satoshis)
+    pub face_amount: u64,
+    /// 발행 시 대상 옵션 담보 합계 (satoshis, 청구권 비율의 분모)
+    pub locked_at_exit: u64,
+    /// 아직 풀리지 않은 대상 옵션 → 담보 (satoshis)
+    pub pending: BTreeMap<String, u64>,
+    /// 풀려서 상환 가능한 누적액 (satoshis)
+    pub released: u64,
+    /// 대상 옵션 지급으로 잃은 누적액 (satoshis)
+    pub losses: u64,
+    /// 상환한 누적액 (satoshis)
+    pub redeemed: u64,
+}
+
+impl ExitClaim {
+    /// 남은 담보 중 청구권 몫 (내림)
+    fn exposure(&self, pending_collateral: u64) -> u64 {
+        if self.locked_at_exit == 0 {
+            return 0;
+        }
+        (self.face_amount as u128 * pending_collateral as u128 / self.locked_at_exit as u128) as u64
+    }
+
+    fn pending_collateral(&self) -> u64 {
+        self.pending.values().sum()
+    }
+
+    /// 지금 상환할 수 있는 금액
+    pub fn redeemable(&self) -> u64 {
+        self.released - self.redeemed
+    }
+
+    /// 풀이 청구권에 진 금액 (잠긴 몫 + 미상환액)
+    pub fn outstanding(&self) -> u64 {
+        self.exposure(self.pending_collateral()) + self.redeemable()
+    }
+
+    /// 대상 옵션이 모두 풀리고 전액 상환됨
+    pub fn is_settled(&self) -> bool {
+        self.pending.is_empty() && self.redeemable() == 0
+    }
+}
+
+/// 부분 출금 계산 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitPlan {
+    pub shares: u64,
+    /// 지분 가치 (satoshis)
+    pub value: u64,
+    /// 바로 출금하는 몫 (satoshis)
+    pub immediate: u64,
+    /// 청구권으로 받는 몫 (satoshis)
+    pub locked: u64,
+}
+
+/// LP 지분 장부
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LpBook {
+    shares: BTreeMap<String, u64>,
+    total_shares: u64,
+    claims: BTreeMap<u64, ExitClaim>,
+    next_claim_id: u64,
+}
+
+impl LpBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 지분도 청구권도 없는 상태 (스냅샷에서 생략)
+    pub fn is_empty(&self) -> bool {
+        self.total_shares == 0 && self.claims.is_empty()
+    }
+
+    pub fn shares(&self, provider_id: &str) -> u64 {
+        self.shares.get(provider_id).copied().unwrap_or(0)
+    }
+
+    pub fn total_shares(&self) -> u64 {
+        self.total_shares
+    }
+
+    pub fn claim(&self, claim_id: u64) -> Option<&ExitClaim> {
+        self.claims.get(&claim_id)
+    }
+
+    /// 보유자의 청구권 (ID 순)
+    pub fn claims_of<'a>(&'a self, holder: &'a str) -> impl Iterator<Item = &'a ExitClaim> + 'a {
+        self.claims.values().filter(move |claim| claim.holder == holder)
+    }
+
+    /// 청구권 몫 합계
+    pub fn claims_outstanding(&self) -> u64 {
+        self.claims.values().map(ExitClaim::outstanding).sum()
+    }
+
+    /// 지분 보유자 몫 순자산 (총 유동성 - 청구권 몫)
+    pub fn net_asset_value(&self, pool: &SimplePoolState) -> u64 {
+        pool.total_liquidity.saturating_sub(self.claims_outstanding())
+    }
+
+    /// 지분이 없는데 순자산이 있으면 그만큼 재무 계정 지분을 1:1로 발행 (발행한 지분 반환)
+    pub fn bootstrap(&mut self, pool: &SimplePoolState) -> u64 {
+        let nav = self.net_asset_value(pool);
+        if self.total_shares > 0 || nav == 0 {
+            return 0;
+        }
+        self.mint(TREASURY_PROVIDER, nav);
+        nav
+    }
+
+    /// 입금액에 해당하는 지분 (지분이 없으면 1:1, 기존 순자산은 먼저 `bootstrap`)
+    pub fn shares_for_deposit(&self, amount: u64, pool: &SimplePoolState) -> u64 {
+        let nav = self.net_asset_value(pool);
+        if self.total_shares == 0 || nav == 0 {
+            return amount;
+        }
+        (amount as u128 * self.total_shares as u128 / nav as u128) as u64
+    }
+
+    pub fn mint(&mut self, provider_id: &str, shares: u64) {
+        *self.shares.entry(provider_id.to_string()).or_default() += shares;
+        self.total_shares += shares;
+    }
+
+    /// 부분 출금 계산 (상태는 바꾸지 않음)
+    ///
+    /// 지분 가치를 순자산 중 사용 가능한 몫과 잠긴 몫의 비율로 나눕니다.
+    pub fn plan_exit(
+        &self,
+        provider_id: &str,
+        shares: u64,
+        pool: &SimplePoolState,
+    ) -> Result<ExitPlan, ContractError> {
+        let held = self.shares(provider_id);
+        if shares == 0 || shares > held {
+            return Err(ContractError::InsufficientShares {
+                requested: shares,
+                available: held,
+            });
+        }
+        let nav = self.net_asset_value(pool);
+        let value = (shares as u128 * nav as u128 / self.total_shares as u128) as u64;
+
+        // 청구권에 묶인 몫을 빼고 남은 사용 가능/잠긴 유동성
+        let claims_locked: u64 = self
+            .claims
+            .values()
+            .map(|claim| claim.exposure(claim.pending_collateral()))
+            .sum();
+        let free_locked = pool.locked_collateral.saturating_sub(claims_locked);
+        let locked = if nav == 0 {
+            0
+        } else {
+            (value as u128 * free_locked.min(nav) as u128 / nav as u128) as u64
+        };
+        Ok(ExitPlan {
+            shares,
+            value,
+            immediate: value - locked,
+            locked,
+        })
+    }
+
+    /// 계산한 출금 반영, 잠긴 몫이 있으면 청구권 발행
+    ///
+    /// `active`는 지금 활성인 옵션과 담보이며 청구권의 대상이 됩니다.
+    pub fn apply_exit(
+        &mut self,
+        provider_id: &str,
+        plan: &ExitPlan,
+        active: impl IntoIterator<Item = (String, u64)>,
+    ) -> Option<u64> {
+        if let Some(held) = self.shares.get_mut(provider_id) {
+            *held -= plan.shares;
+            if *held == 0 {
+                self.shares.remove(provider_id);
+            }
+        }
+        self.total_shares -= plan.shares;
+        if plan.locked == 0 {
+            return None;
+        }
+
+        let pending: BTreeMap<String, u64> = active.into_iter().collect();
+        self.next_claim_id += 1;
+        let claim_id = self.next_claim_id;
+        self.claims.insert(
+            claim_id,
+            ExitClaim {
+                claim_id,
+                holder: provider_id.to_string(),
+                face_amount: plan.locked,
+                locked_at_exit: pending.values().sum(),
+                pending,
+                released: 0,
+                losses: 0,
+                redeemed: 0,
+            },
+        );
+        Some(claim_id)
+    }
+
+    /// 옵션 담보가 풀림 (`lost`는 지급/수수료로 풀 밖으로 나간 금액)
+    pub fn on_release(&mut self, option_id: &str, lost: u64) {
+        for claim in self.claims.values_mut() {
+            let before = claim.exposure(claim.pending_collateral());
+            let Some(collateral) = claim.pending.remove(option_id) else {
+                continue;
+            };
+            let after = claim.exposure(claim.pending_collateral());
+            let share = before - after;
+            let loss = claim.exposure(lost.min(collateral)).min(share);
+            claim.released += share - loss;
+            claim.losses += loss;
+        }
+    }
+
+    /// 상환 가능액 확인 (상태는 바꾸지 않음)
+    pub fn check_redeem(&self, claim_id: u64, holder: &str) -> Result<u64, ContractError> {
+        let claim = self.owned_claim(claim_id, holder)?;
+        Ok(claim.redeemable())
+    }
+
+    /// 상환 반영, 전부 끝난 청구권은 정리
+    pub fn apply_redeem(&mut self, claim_id: u64, amount: u64) {
+        if let Some(claim) = self.claims.get_mut(&claim_id) {
+            claim.redeemed += amount;
+            if claim.is_settled() {
+                self.claims.remove(&claim_id);
+            }
+        }
+    }
+
+    /// 청구권 양도
+    pub fn transfer(&mut self, claim_id: u64, from: &str, to: &str) -> Result<(), ContractError> {
+        self.owned_claim(claim_id, from)?;
+        if to.is_empty() {
+            return Err(ContractError::ExitClaim("recipient is required".to_string()));
+        }
+        if let Some(claim) = self.claims.get_mut(&claim_id) {
+            claim.holder = to.to_string();
+        }
+        Ok(())
+    }
+
+    fn owned_claim(&self, claim_id: u64, holder: &str) -> Result<&ExitClaim, ContractError> {
+        let claim = self
+            .claims
+            .get(&claim_id)
+            .ok_or_else(|| ContractError::ExitClaim(format!("claim {} not found", claim_id)))?;
+        if claim.holder != holder {
+            return Err(ContractError::ExitClaim(format!(
+                "claim {} is not held by {}",
+                claim_id, holder
+            )));
+        }
+        Ok(claim)
+    }
+}
+
+/// LP 지분/출금 청구권 HTTP API (`/lp`, `/exit-claims`, `/admin/lp`)
+pub mod api {
+    use crate::account_keys::{lp_exit_payload, redeem_payload, transfer_payload, AccountKeys, AccountSignature};
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct ExitRequest {
+        pub shares: u64,
+        /// LP 키로 `account/lp-exit/v1` (provider_id, shares, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct DepositRequest {
+        /// 입금 확인된 금액 (satoshis)
+        pub amount: u64,
+        /// LP 공개키 hex (키가 없는 LP면 입금 전에 묶음, 이후 출금 서명에 사용)
+        pub public_key: Option<String>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct HolderRequest {
+        pub holder: String,
+        /// 보유자 키로 `account/exit-claim-redeem/v1` (claim_id, holder, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct TransferRequest {
+        pub from: String,
+        pub to: String,
+        /// 양수인 공개키 hex (새 계정이면 이 키가 묶임)
+        pub to_pubkey: String,
+        /// 양도인 키로 `account/exit-claim-transfer/v1` (claim_id, from, to, to_pubkey, nonce)에 서명
+        #[serde(flatten)]
+        pub auth: AccountSignature,
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/lp/{provider_id}",
+        tag = "lp",
+        params(("provider_id" = String, Path)),
+        responses((status = 200, body = Object))
+    )]
+    async fn get_position(Path(provider_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let book = manager.lp_book();
+        let shares = book.shares(&provider_id);
+        let value = match book.total_shares() {
+            0 => 0,
+            total => (shares as u128 * book.net_asset_value(&manager.pool_state) as u128 / total as u128) as u64,
+        };
+        Json(json!({
+            "provider_id": provider_id,
+            "shares": shares,
+            "total_shares": book.total_shares(),
+            "value": value,
+            "exit_claims": book.claims_of(&provider_id).collect::<Vec<_>>(),
+        }))
+        .into_response()
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/admin/lp/{provider_id}/deposit",
+        tag = "lp",
+        params(("provider_id" = String, Path)),
+        request_body = DepositRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn deposit(
+        Path(provider_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<DepositRequest>,
+    ) -> Response {
+        if request.amount == 0 {
+            return bad_request("amount must be positive");
+        }
+        let key = match request.public_key.as_deref().map(AccountKeys::parse_key).transpose() {
+            Ok(key) => key,
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match key {
+            Some(key) => {
+                if let Err(e) = manager.bind_account_key(&provider_id, key) {
+                    return error_response(e);
+                }
+            }
+            None if manager.account_keys().key(&provider_id).is_none() => {
+                return bad_request("public_key required for a provider without a key");
+            }
+            None => {}
+        }
+        match manager.deposit_liquidity(&provider_id, request.amount) {
+            Ok(shares) => Json(json!({
+                "provider_id": provider_id,
+                "minted": shares,
+                "shares": manager.lp_book().shares(&provider_id),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/lp/{provider_id}/exit",
+        tag = "lp",
+        params(("provider_id" = String, Path)),
+        request_body = ExitRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn exit(
+        Path(provider_id): Path<String>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<ExitRequest>,
+    ) -> Response {
+        if request.shares == 0 {
+            return bad_request("shares must be positive");
+        }
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = lp_exit_payload(&provider_id, request.shares, request.auth.nonce);
+        if let Err(e) = manager.authorize_account(&provider_id, &payload, &request.auth) {
+            return error_response(e);
+        }
+        match manager.exit_liquidity(&provider_id, request.shares) {
+            Ok((plan, claim_id)) => Json(json!({ "exit": plan, "claim_id": claim_id })).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/exit-claims/{claim_id}/redeem",
+        tag = "lp",
+        params(("claim_id" = u64, Path)),
+        request_body = HolderRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn redeem(
+        Path(claim_id): Path<u64>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<HolderRequest>,
+    ) -> Response {
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = redeem_payload(claim_id, &request.holder, request.auth.nonce);
+        if let Err(e) = manager.authorize_account(&request.holder, &payload, &request.auth) {
+            return error_response(e);
+        }
+        match manager.redeem_exit_claim(claim_id, &request.holder) {
+            Ok(amount) => Json(json!({ "claim_id": claim_id, "redeemed": amount })).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/exit-claims/{claim_id}/transfer",
+        tag = "lp",
+        params(("claim_id" = u64, Path)),
+        request_body = TransferRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 401, body = AdminError),
+            (status = 404, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn transfer(
+        Path(claim_id): Path<u64>,
+        State(manager): State<SharedManager>,
+        Json(request): Json<TransferRequest>,
+    ) -> Response {
+        let to_key = match AccountKeys::parse_key(&request.to_pubkey) {
+            Ok(key) => key,
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let payload = transfer_payload(claim_id, &request.from, &request.to, &request.to_pubkey, request.auth.nonce);
+        if let Err(e) = manager.authorize_account(&request.from, &payload, &request.auth) {
+            return error_response(e);
+        }
+        // 받는 계정의 키는 양도가 성공한 뒤에만 묶음 (실패한 양도로 남의 계정 키를 선점하지 못함)
+        if let Err(e) = manager.check_account_key(&request.to, &to_key) {
+            return error_response(e);
+        }
+        if let Err(e) = manager.transfer_exit_claim(claim_id, &request.from, &request.to) {
+            return error_response(e);
+        }
+        match manager.bind_account_key(&request.to, to_key) {
+            Ok(()) => Json(manager.lp_book().claim(claim_id)).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    /// `/lp`, `/exit-claims`, `/admin/lp` 라우터 생성
+    ///
+    /// 입금은 운영자가 자금 수령을 확인한 뒤 기록하고(`/admin/lp/{id}/deposit`),
+    /// 출금은 LP가 서명한 `/lp/{id}/exit`로 합니다.
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/admin/lp/:provider_id/deposit", post(deposit))
+            .route("/lp/:provider_id", get(get_position))
+            .route("/lp/:provider_id/exit", post(exit))
+            .route("/exit-claims/:claim_id/redeem", post(redeem))
+            .route("/exit-claims/:claim_id/transfer", post(transfer))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(total: u64, locked: u64) -> SimplePoolState {
+        SimplePoolState {
+            total_liquidity: total,
+            locked_collateral: locked,
+            available_liquidity: total - locked,
+            ..SimplePoolState::new()
+        }
+    }
+
+    #[test]
+    fn test_exit_splits_available_and_locked_pro_rata() {
+        let mut book = LpBook::new();
+        book.mint("lp1", 60_000_000);
+        book.mint("lp2", 40_000_000);
+        // 80% 잠김
+        let state = pool(100_000_000, 80_000_000);
+
+        let plan = book.plan_exit("lp2", 40_000_000, &state).unwrap();
+        assert_eq!(
+            plan,
+            ExitPlan {
+                shares: 40_000_000,
+                value: 40_000_000,
+                immediate: 8_000_000,
+                locked: 32_000_000,
+            }
+        );
+        let active = vec![("OPT-A".to_string(), 50_000_000), ("OPT-B".to_string(), 30_000_000)];
+        let claim_id = book.apply_exit("lp2", &plan, active).unwrap();
+        assert_eq!(book.total_shares(), 60_000_000);
+        assert!(matches!(
+            book.plan_exit("lp2", 1, &state),
+            Err(ContractError::InsufficientShares { .. })
+        ));
+
+        // 남은 LP 순자산은 즉시 출금분과 청구권을 뺀 금액
+        let after_exit = pool(92_000_000, 80_000_000);
+        assert_eq!(book.net_asset_value(&after_exit), 60_000_000);
+
+        // OPT-A는 1,000만 지급 후 정산: 청구권은 40%의 담보와 40%의 손실을 나눔
+        book.on_release("OPT-A", 10_000_000);
+        let claim = book.claim(claim_id).unwrap();
+        assert_eq!(claim.redeemable(), 16_000_000);
+        assert_eq!(claim.losses, 4_000_000);
+        book.on_release("OPT-B", 0);
+        assert_eq!(book.check_redeem(claim_id, "lp2").unwrap(), 28_000_000);
+        assert_eq!(book.claim(claim_id).unwrap().released + book.claim(claim_id).unwrap().losses, 32_000_000);
+    }
+
+    #[test]
+    fn test_bootstrap_keeps_existing_liquidity_from_first_depositor() {
+        let mut book = LpBook::new();
+        let seeded = pool(100_000_000, 0);
+        assert_eq!(book.bootstrap(&seeded), 100_000_000);
+        assert_eq!(book.bootstrap(&seeded), 0);
+
+        // 1 sat 입금은 1 지분, 기존 1억은 재무 계정 몫으로 남음
+        assert_eq!(book.shares_for_deposit(1, &seeded), 1);
+        book.mint("lp1", 1);
+        assert_eq!(book.shares(TREASURY_PROVIDER), 100_000_000);
+        assert!(LpBook::new().bootstrap(&pool(0, 0)) == 0);
+    }
+
+    #[test]
+    fn test_claims_transfer_and_redeem() {
+        let mut book = LpBook::new();
+        book.mint("lp1", 10_000_000);
+        let state = pool(10_000_000, 10_000_000);
+        let plan = book.plan_exit("lp1", 10_000_000, &state).unwrap();
+        assert_eq!(plan.immediate, 0);
+        let claim_id = book
+            .apply_exit("lp1", &plan, vec![("OPT-A".to_string(), 10_000_000)])
+            .unwrap();
+
+        assert!(book.transfer(claim_id, "mallory", "bob").is_err());
+        book.transfer(claim_id, "lp1", "bob").unwrap();
+        assert!(book.check_redeem(claim_id, "lp1").is_err());
+        assert_eq!(book.check_redeem(claim_id, "bob").unwrap(), 0);
+
+        book.on_release("OPT-A", 0);
+        assert_eq!(book.check_redeem(claim_id, "bob").unwrap(), 10_000_000);
+        book.apply_redeem(claim_id, 10_000_000);
+        assert!(book.claim(claim_id).is_none());
+        assert!(book.is_empty());
+    }
+}
@@ -0,0 +1,1385 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use btcfi_contracts::admin_api;
+use btcfi_contracts::alerting::{AlertInputs, AlertManager, AlertingConfig};
+use btcfi_contracts::anchor_backend::BitcoinAnchorer;
+use btcfi_contracts::anchor_tracker::{AnchorTracker, BitcoindChain};
+use btcfi_contracts::beneficiary::{self, BeneficiaryRegistry};
+use btcfi_contracts::block_time::BlockClock;
+use btcfi_contracts::bootstrap::{BitcoindRpc, HttpBitcoindRpc};
+use btcfi_contracts::claimable::{self, ClaimableLedger, DEFAULT_MIN_WITHDRAWAL_SATS};
+use btcfi_contracts::cooperative_close::{self, CooperativeCloseDesk};
+use btcfi_contracts::early_exercise::DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS;
+use btcfi_contracts::eligibility::{AllowlistEligibility, EligibilityProvider, HttpEligibility};
+use btcfi_contracts::fees::{FeeSchedule, TreasuryControls};
+use btcfi_contracts::mempool_watch::{HttpChallengeResponder, ZmqRawTxSubscriber};
+use btcfi_contracts::flow::{self, Flow, FlowMetrics, Step, StepPolicy};
+use btcfi_contracts::openapi;
+use btcfi_contracts::price_commitment::{self, PriceCommitmentLog};
+use btcfi_contracts::price_guard::{PriceBandConfig, DEFAULT_BAND_WINDOW, DEFAULT_MAX_DEVIATION_BPS};
+use btcfi_contracts::proof_archive::{self, FileProofStore, ProofArchive};
+use btcfi_contracts::reserve::{self, ReserveManager, ReservePolicy, SimulatedVenue};
+use btcfi_contracts::settlement_broadcast::{self, SettlementBroadcastManager};
+use btcfi_contracts::snapshot::{open_pool, persist_pool};
+use btcfi_contracts::taproot_address::TaprootStore;
+use btcfi_contracts::tenant::{self, TenantConfig, TenantRegistry};
+use btcfi_contracts::tracing_context;
+use btcfi_contracts::webhooks::{self, HttpTransport, RetryPolicy, WebhookDispatcher, WebhookEvent};
+use btcfi_contracts::{
+    AggregatedPrice, EventStore, FileEventStore, PriceFeedService, ReportFormat, ReportGenerator,
+    ReportKind, SimpleContractManager, TradingStatusSync,
+};
+use clap::{Parser, Subcommand};
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{FeedStatus, NetworkProfile, PriceFeed, Shutdown, ShutdownSignal};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// 종료 시 진행 중인 백그라운드 작업을 기다리는 최대 시간
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// ZMQ rawtx 연결이 끊겼을 때 다시 연결하기까지 대기
+const ZMQ_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// 합의 가격 피드 폴링 주기 (기록은 1분마다, 캐시는 그 사이에도 갱신)
+const PRICE_FEED_INTERVAL_SECS: u64 = 10;
+
+/// Contracts 모듈 운영 CLI
+#[derive(Parser)]
+#[command(name = "contracts")]
+#[command(about = "BTCFi contract/pool management tools")]
+struct Args {
+    /// 풀 이벤트 로그 경로 (JSON Lines)
+    #[arg(long, default_value = "data/pool_events.jsonl")]
+    events: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 정산/풀 이력 리포트 내보내기
+    Report {
+        /// 리포트 종류 (settlements, liquidity, premiums, payouts, fees, referrals, funding)
+        #[arg(long, default_value = "settlements")]
+        kind: String,
+
+        /// 시작 시각 (Unix timestamp, 초)
+        #[arg(long)]
+        from: Option<u64>,
+
+        /// 종료 시각 (Unix timestamp, 초)
+        #[arg(long)]
+        to: Option<u64>,
+
+        /// 출력 형식 (csv, json, parquet)
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// 출력 파일 (생략 시 stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// 리포트/관리 HTTP API 실행
+    Serve(Box<ServeArgs>),
+}
+
+/// `serve` 실행 옵션
+#[derive(clap::Args)]
+struct ServeArgs {
+    #[arg(long, default_value = "127.0.0.1:3100")]
+    listen: String,
+
+    /// 풀 상태 스냅샷 경로 (시작 시 복원, 실행 중 주기적으로 갱신)
+    #[arg(long, default_value = "data/pool_snapshot.json")]
+    snapshot: String,
+
+    /// 운영자 토큰 SHA256 해시 (hex, 여러 번 지정 가능, 없으면 `/admin/*`, `/reports/*` 전체 거부)
+    #[arg(long)]
+    admin_token_hash: Vec<String>,
+
+    /// 기본 풀 API 키 SHA256 해시 (hex, 여러 번 지정 가능, 없으면 기본 풀 API 전체 거부)
+    #[arg(long)]
+    api_key_hash: Vec<String>,
+
+    /// Calculation 호가 서명 공개키 (hex, 설정 시 확정 호가로만 옵션 생성)
+    #[arg(long)]
+    quote_public_key: Option<String>,
+
+    /// 네트워크 프로필 (mainnet, testnet, signet, regtest)
+    #[arg(long, default_value = "testnet")]
+    network: NetworkProfile,
+
+    /// 분 단위 합의 가격을 수집할 Aggregator (일일 가격 커밋먼트)
+    #[arg(long)]
+    aggregator: Option<String>,
+
+    /// 청구 잔고 영수증 서명키 (hex, 설정 시 정산 지급을 잔고에 적립)
+    #[arg(long)]
+    claim_signing_key: Option<String>,
+
+    /// 청구 잔고 출금을 보낼 bitcoind 지갑 (`--claim-signing-key`와 `--bitcoind-rpc` 필요)
+    #[arg(long)]
+    claim_payout_wallet: Option<String>,
+
+    /// 최소 출금액 (satoshis)
+    #[arg(long, default_value_t = DEFAULT_MIN_WITHDRAWAL_SATS)]
+    min_withdrawal: u64,
+
+    /// 정산 증명 보관 디렉터리
+    #[arg(long, default_value = "data/proofs")]
+    proof_dir: String,
+
+    /// 정산 가격과 비교할 직전 합의 가격 수
+    #[arg(long, default_value_t = DEFAULT_BAND_WINDOW)]
+    price_band_window: usize,
+
+    /// 직전 합의 가격 중앙값 대비 허용 편차 (bps, 벗어나면 정산 보류)
+    #[arg(long, default_value_t = DEFAULT_MAX_DEVIATION_BPS)]
+    price_band_bps: u64,
+
+    /// 미국식 조기 행사에 쓸 합의 가격의 최대 나이 (초, 넘으면 행사를 미룸)
+    #[arg(long, default_value_t = DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS)]
+    max_exercise_price_age: u64,
+
+    /// 프리미엄 대비 프로토콜 수수료 (bps)
+    #[arg(long, default_value_t = 0)]
+    protocol_fee_bps: u32,
+
+    /// BTC 지급액 대비 정산 수수료 (bps)
+    #[arg(long, default_value_t = 0)]
+    settlement_fee_bps: u32,
+
+    /// 재무 계정 출금 허용 주소 (여러 번 지정 가능, 없으면 출금 불가)
+    #[arg(long)]
+    treasury_destination: Vec<String>,
+
+    /// 재무 계정 24시간 출금 한도 (satoshis)
+    #[arg(long)]
+    treasury_daily_limit: Option<u64>,
+
+    /// 테넌트 설정 파일 (JSON 배열, 설정 시 `/tenants/{id}` 아래에 테넌트별 풀 API 제공)
+    #[arg(long)]
+    tenants: Option<String>,
+
+    /// 테넌트별 풀 이벤트 로그/스냅샷 디렉터리 (`<dir>/<id>.jsonl`, `<dir>/<id>.snapshot.json`)
+    #[arg(long, default_value = "data/tenants")]
+    tenant_events_dir: String,
+
+    /// 경보 규칙/알림 채널 설정 파일 (JSON, 설정 시 1분마다 평가)
+    #[arg(long)]
+    alerting: Option<String>,
+
+    /// 유휴 유동성 중 수익처에 예치할 비율 (0..=1, 설정 시 1분마다 리밸런싱)
+    #[arg(long)]
+    reserve_fraction: Option<f64>,
+
+    /// 예치금을 전액 회수하는 풀 사용률 (%)
+    #[arg(long, default_value_t = ReservePolicy::default().recall_utilization)]
+    reserve_recall_utilization: f64,
+
+    /// 시뮬레이션 수익처 연 수익률 (bps, 0이면 no-op)
+    #[arg(long, default_value_t = 0)]
+    reserve_apy_bps: u32,
+
+    /// 잠긴 담보 연 사용료율 (bps, 프리미엄에서 선납받아 LP에게 매일 적립, 0이면 없음)
+    #[arg(long, default_value_t = 0)]
+    funding_rate_bps: u32,
+
+    /// 블록 헤더를 받을 bitcoind RPC (설정 시 1분마다 동기화해 높이 ↔ 시각 환산에 사용)
+    #[arg(long)]
+    bitcoind_rpc: Option<String>,
+
+    /// bitcoind `.cookie` 파일 (rpc-user/rpc-password 대신)
+    #[arg(long)]
+    bitcoind_cookie: Option<String>,
+
+    #[arg(long, default_value = "btcfi")]
+    rpc_user: String,
+
+    #[arg(long, default_value = "btcfi")]
+    rpc_password: String,
+
+    /// 구매 자격 허용 목록 파일 (한 줄에 사용자 ID 또는 주소, 옵션 생성/롤 API에서 확인)
+    #[arg(long, conflicts_with = "eligibility_url")]
+    eligibility_allowlist: Option<String>,
+
+    /// 외부 자격 확인 서비스 (`POST <url>`, 옵션 생성/롤 API에서 확인)
+    #[arg(long)]
+    eligibility_url: Option<String>,
+
+    /// 미결제약정을 보고할 Calculation API (설정 시 30초마다 기본 풀의 행사가/만기별 담보 전송)
+    #[arg(long)]
+    calculation_url: Option<String>,
+
+    /// Calculation 운영자 토큰 (Calculation OPERATOR_TOKEN_HASH의 평문)
+    #[arg(long)]
+    calculation_token: Option<String>,
+
+    /// 앵커/해지 트랜잭션 수수료를 채울 bitcoind 지갑 (`--bitcoind-rpc` 필요, 설정 시 협의 해지/정산 전송 API와 앵커/정산 확인 추적 제공)
+    #[arg(long, requires = "bitcoind_rpc")]
+    anchor_wallet: Option<String>,
+
+    /// 옵션 Taproot 기록 디렉터리 (`<dir>/<option_id>.json`, 협의 해지 PSBT 검증용)
+    #[arg(long, default_value = "data/taproot")]
+    taproot_dir: String,
+
+    /// 대기 중인 협의 해지 제안 파일 (재시작 후에도 상대방 서명을 이어받음)
+    #[arg(long, default_value = "data/close_proposals.json")]
+    close_proposals: String,
+
+    /// bitcoind `zmqpubrawtx` 엔드포인트 (설정 시 감시 중인 옵션 UTXO의 경쟁 지출에 챌린지 무장)
+    #[arg(long, requires = "watchtower_url")]
+    zmq_rawtx: Option<String>,
+
+    /// 챌린지를 무장할 워치타워 (`POST <url>`에 경쟁 트랜잭션 JSON)
+    #[arg(long)]
+    watchtower_url: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let _tracing = oracle_vm_common::init_tracing("contracts");
+
+    let store = FileEventStore::open(&args.events)?;
+
+    match args.command {
+        Command::Report {
+            kind,
+            from,
+            to,
+            format,
+            output,
+        } => {
+            let kind: ReportKind = kind.parse()?;
+            let format: ReportFormat = format.parse()?;
+            let table = ReportGenerator::new(&store).generate(
+                kind,
+                from.unwrap_or(0),
+                to.unwrap_or(u64::MAX),
+            );
+            let body = table.export(format)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, body)?;
+                    info!("Wrote {} rows to {}", table.rows.len(), path);
+                }
+                None => {
+                    use std::io::Write;
+                    std::io::stdout().write_all(&body)?;
+                }
+            }
+        }
+        Command::Serve(serve) => {
+            let ServeArgs {
+                listen,
+                snapshot,
+                admin_token_hash,
+                api_key_hash,
+                quote_public_key,
+                network,
+                aggregator,
+                claim_signing_key,
+                claim_payout_wallet,
+                min_withdrawal,
+                proof_dir,
+                price_band_window,
+                price_band_bps,
+                max_exercise_price_age,
+                protocol_fee_bps,
+                settlement_fee_bps,
+                treasury_destination,
+                treasury_daily_limit,
+                tenants,
+                tenant_events_dir,
+                alerting,
+                reserve_fraction,
+                reserve_recall_utilization,
+                reserve_apy_bps,
+                funding_rate_bps,
+                bitcoind_rpc,
+                bitcoind_cookie,
+                rpc_user,
+                rpc_password,
+                eligibility_allowlist,
+                eligibility_url,
+                calculation_url,
+                calculation_token,
+                anchor_wallet,
+                taproot_dir,
+                close_proposals,
+                zmq_rawtx,
+                watchtower_url,
+            } = *serve;
+            info!(
+                "Serving reports from {} ({} events)",
+                args.events,
+                store.events().len()
+            );
+            let claim_key = claim_signing_key
+                .map(|key| key.parse())
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Invalid claim signing key: {}", e))?;
+            // 지급할 지갑 없이 잔고만 차감하지 않도록 출금 경로 전체를 함께 요구
+            if claim_key.is_some() && (claim_payout_wallet.is_none() || bitcoind_rpc.is_none()) {
+                anyhow::bail!("--claim-signing-key needs --claim-payout-wallet and --bitcoind-rpc to pay withdrawals");
+            }
+            let quote_key = quote_public_key
+                .map(|key| PublicKey::from_str(&key))
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Invalid quote public key: {}", e))?;
+            let operator_auth = admin_api::OperatorAuth::new(admin_token_hash).map_err(|e| anyhow::anyhow!(e))?;
+            if !operator_auth.is_configured() {
+                warn!("No --admin-token-hash configured, /admin/* and /reports/* are disabled");
+            }
+            if api_key_hash.is_empty() {
+                warn!("No --api-key-hash configured, the default pool API rejects every request");
+            }
+            let price_band = PriceBandConfig {
+                window: price_band_window,
+                max_deviation_bps: price_band_bps,
+            };
+            // 스냅샷 앵커 대조, 헤더 동기화, 청구 출금 지급에 쓰는 bitcoind 연결
+            let connect = |url: &str| match &bitcoind_cookie {
+                Some(path) => HttpBitcoindRpc::from_cookie(url, Path::new(path)),
+                None => Ok(HttpBitcoindRpc::new(url, &rpc_user, &rpc_password)),
+            };
+            let chain = bitcoind_rpc.as_deref().map(connect).transpose()?;
+            let chain = chain.as_ref().map(|rpc| rpc as &dyn BitcoindRpc);
+            let eligibility: Option<Arc<dyn EligibilityProvider>> = match (eligibility_allowlist, eligibility_url) {
+                (Some(path), _) => {
+                    let allowlist = AllowlistEligibility::load(&path)?;
+                    info!("Eligibility: {} allowlist entries from {}", allowlist.len(), path);
+                    Some(Arc::new(allowlist))
+                }
+                (None, Some(url)) => {
+                    info!("Eligibility checked by {}", url);
+                    Some(Arc::new(HttpEligibility::new(url)))
+                }
+                (None, None) => None,
+            };
+            // 기본 풀과 테넌트 풀 공통 설정 (청구 잔고, 수익자, 자격 확인, 가격 밴드 가드, 담보 사용료율, 호가 서명키, 앵커 추적)
+            let pool_manager = |mut manager: SimpleContractManager| -> SimpleContractManager {
+                if let Some(key) = claim_key {
+                    manager.enable_claimable_balances(ClaimableLedger::new(network, key, min_withdrawal));
+                }
+                manager.enable_beneficiaries(BeneficiaryRegistry::new(network));
+                if let Some(provider) = &eligibility {
+                    manager.enable_eligibility(provider.clone());
+                }
+                manager.enable_price_guard(price_band);
+                manager.set_max_exercise_price_age(max_exercise_price_age);
+                manager.set_funding_rate(funding_rate_bps);
+                // 운영 중 교체해 스냅샷에 남은 키가 설정 파일의 키보다 우선
+                match (quote_key, manager.quote_key()) {
+                    (Some(key), None) => manager.require_quotes(key),
+                    (Some(key), Some(rotated)) if *rotated != key => {
+                        warn!("Keeping quote key {} rotated at runtime instead of --quote-public-key {}", rotated, key)
+                    }
+                    _ => {}
+                }
+                manager.enable_anchor_tracking(AnchorTracker::new(network));
+                manager.enable_settlement_broadcasts(SettlementBroadcastManager::new(network));
+                manager
+            };
+            let snapshot = PathBuf::from(snapshot);
+            let mut manager = pool_manager(open_pool(&snapshot, Box::new(store), chain).await?);
+            info!(
+                "Pool restored: {} options, {} sats liquidity",
+                manager.options.len(),
+                manager.pool_state.total_liquidity
+            );
+            manager.set_fee_schedule(FeeSchedule {
+                protocol_fee_bps,
+                settlement_fee_bps,
+            })?;
+            manager.set_treasury_controls(TreasuryControls {
+                allowed_destinations: treasury_destination,
+                daily_limit: treasury_daily_limit,
+            });
+            let shared: admin_api::SharedManager = Arc::new(RwLock::new(manager));
+            let mut tenant_registry = TenantRegistry::new();
+            let mut snapshots = vec![(snapshot, shared.clone())];
+            if let Some(path) = tenants {
+                std::fs::create_dir_all(&tenant_events_dir)?;
+                for config in TenantConfig::load(&path)? {
+                    let events = format!("{}/{}.jsonl", tenant_events_dir, config.id);
+                    let snapshot = PathBuf::from(format!("{}/{}.snapshot.json", tenant_events_dir, config.id));
+                    let manager = pool_manager(open_pool(&snapshot, Box::new(FileEventStore::open(&events)?), chain).await?);
+                    let tenant = tenant_registry.register(config, manager)?;
+                    info!("Tenant {} ({}) events at {}", tenant.config().id, tenant.config().name, events);
+                    snapshots.push((snapshot, tenant.manager().clone()));
+                }
+            }
+            let dispatcher: webhooks::api::SharedDispatcher =
+                Arc::new(tokio::sync::Mutex::new(WebhookDispatcher::new(RetryPolicy::default())));
+            // Ctrl-C / SIGTERM: 새 요청/작업을 받지 않고 진행 중인 작업을 마친 뒤 종료
+            let shutdown = Shutdown::new();
+            shutdown.listen_for_signals();
+            let flows = Arc::new(FlowMetrics::new());
+            tokio::spawn(run_webhook_delivery(
+                shared.clone(),
+                dispatcher.clone(),
+                flows.clone(),
+                shutdown.signal(),
+            ));
+            tokio::spawn(run_snapshot_persistence(
+                snapshots.clone(),
+                flows.clone(),
+                shutdown.signal(),
+            ));
+
+            let listener = TcpListener::bind(&listen).await?;
+            let proofs: proof_archive::api::SharedArchive = Arc::new(RwLock::new(ProofArchive::new(
+                Box::new(FileProofStore::open(&proof_dir)?),
+            )?));
+            let commitments: price_commitment::api::SharedCommitments =
+                Arc::new(RwLock::new(PriceCommitmentLog::new()));
+            if let Some(url) = aggregator {
+                let managers = std::iter::once(shared.clone())
+                    .chain(tenant_registry.tenants().map(|tenant| tenant.manager().clone()))
+                    .collect();
+                tokio::spawn(run_price_commitments(
+                    url,
+                    managers,
+                    commitments.clone(),
+                    flows.clone(),
+                    shutdown.signal(),
+                ));
+            }
+            if let Some(path) = alerting {
+                let config = AlertingConfig::load(&path)?;
+                info!("Alerting: {} rules, {} sinks", config.rules.len(), config.sinks.len());
+                let pools = std::iter::once(("default".to_string(), shared.clone()))
+                    .chain(
+                        tenant_registry
+                            .tenants()
+                            .map(|tenant| (tenant.config().id.clone(), tenant.manager().clone())),
+                    )
+                    .collect();
+                tokio::spawn(run_alerting(
+                    AlertManager::from_config(&config),
+                    pools,
+                    flows.clone(),
+                    shutdown.signal(),
+                ));
+            }
+            let reserve_manager = reserve_fraction.map(|sweep_fraction| {
+                let policy = ReservePolicy {
+                    sweep_fraction,
+                    recall_utilization: reserve_recall_utilization,
+                    ..ReservePolicy::default()
+                };
+                Arc::new(ReserveManager::new(Arc::new(SimulatedVenue::new(reserve_apy_bps)), policy))
+            });
+            if let Some(reserve) = &reserve_manager {
+                tokio::spawn(run_reserve_rebalancing(
+                    shared.clone(),
+                    reserve.clone(),
+                    flows.clone(),
+                    shutdown.signal(),
+                ));
+            }
+            if funding_rate_bps > 0 {
+                let managers = std::iter::once(shared.clone())
+                    .chain(tenant_registry.tenants().map(|tenant| tenant.manager().clone()))
+                    .collect();
+                tokio::spawn(run_funding_accrual(managers, flows.clone(), shutdown.signal()));
+            }
+            if let Some(url) = &bitcoind_rpc {
+                let managers = std::iter::once(shared.clone())
+                    .chain(tenant_registry.tenants().map(|tenant| tenant.manager().clone()))
+                    .collect();
+                tokio::spawn(run_header_sync(connect(url)?, managers, flows.clone(), shutdown.signal()));
+                if let Some(wallet) = claim_payout_wallet.filter(|_| claim_key.is_some()) {
+                    let pools = std::iter::once(("default".to_string(), shared.clone()))
+                        .chain(
+                            tenant_registry
+                                .tenants()
+                                .map(|tenant| (tenant.config().id.clone(), tenant.manager().clone())),
+                        )
+                        .collect();
+                    info!("Claim withdrawals paid from bitcoind wallet {}", wallet);
+                    tokio::spawn(run_claim_payouts(connect(url)?, wallet, pools, flows.clone(), shutdown.signal()));
+                }
+                if let Some(wallet) = &anchor_wallet {
+                    let pools = std::iter::once(("default".to_string(), shared.clone()))
+                        .chain(
+                            tenant_registry
+                                .tenants()
+                                .map(|tenant| (tenant.config().id.clone(), tenant.manager().clone())),
+                        )
+                        .collect();
+                    info!("Tracking anchor and settlement confirmations via bitcoind wallet {}", wallet);
+                    tokio::spawn(run_anchor_tracking(
+                        pools,
+                        BitcoindChain::new(Arc::new(connect(url)?), Some(wallet.clone())),
+                        BitcoinAnchorer::new(Arc::new(connect(url)?), wallet.clone()),
+                        flows.clone(),
+                        shutdown.signal(),
+                    ));
+                    let pools = std::iter::once(("default".to_string(), shared.clone()))
+                        .chain(
+                            tenant_registry
+                                .tenants()
+                                .map(|tenant| (tenant.config().id.clone(), tenant.manager().clone())),
+                        )
+                        .collect();
+                    tokio::spawn(run_settlement_tracking(
+                        pools,
+                        BitcoindChain::new(Arc::new(connect(url)?), Some(wallet.clone())),
+                        BitcoinAnchorer::new(Arc::new(connect(url)?), wallet.clone()),
+                        flows.clone(),
+                        shutdown.signal(),
+                    ));
+                }
+            }
+            if let Some(url) = calculation_url {
+                let token = calculation_token
+                    .ok_or_else(|| anyhow::anyhow!("--calculation-url needs --calculation-token"))?;
+                info!("Reporting open interest to {}", url);
+                tokio::spawn(run_open_interest_reports(
+                    url,
+                    token,
+                    shared.clone(),
+                    flows.clone(),
+                    shutdown.signal(),
+                ));
+            }
+            if let (Some(endpoint), Some(url)) = (zmq_rawtx, watchtower_url) {
+                let managers = std::iter::once(shared.clone())
+                    .chain(tenant_registry.tenants().map(|tenant| tenant.manager().clone()))
+                    .collect();
+                info!("Watching mempool via {}, challenges armed at {}", endpoint, url);
+                tokio::spawn(run_mempool_watch(
+                    endpoint,
+                    HttpChallengeResponder::new(url),
+                    managers,
+                    shutdown.signal(),
+                ));
+            }
+            let mut app = tenant::api::default_pool_router(shared.clone(), api_key_hash.clone())
+                .merge(tenant::api::router(&tenant_registry))
+                .merge(webhooks::api::router(dispatcher))
+                .merge(beneficiary::api::router(shared.clone()))
+                .merge(price_commitment::api::router(commitments))
+                .merge(proof_archive::api::router(proofs))
+                .merge(flow::api::router(flows))
+                .merge(openapi::router());
+            if let Some(reserve) = reserve_manager {
+                app = app.merge(reserve::api::router(shared.clone(), reserve));
+            }
+            if let (Some(url), Some(wallet)) = (&bitcoind_rpc, &anchor_wallet) {
+                let desk = CooperativeCloseDesk::open(&close_proposals)?;
+                info!("Cooperative close: {} pending proposals in {}", desk.len(), close_proposals);
+                let service = Arc::new(cooperative_close::api::CloseService {
+                    manager: shared.clone(),
+                    desk: Mutex::new(desk),
+                    store: TaprootStore::open(&taproot_dir)?,
+                    broadcaster: Box::new(BitcoinAnchorer::new(Arc::new(connect(url)?), wallet.clone())),
+                });
+                app = app.merge(tenant::api::with_api_key(
+                    cooperative_close::api::router(service),
+                    "default",
+                    api_key_hash.clone(),
+                ));
+                app = app.merge(settlement_broadcast::api::router(
+                    shared.clone(),
+                    Arc::new(BitcoinAnchorer::new(Arc::new(connect(url)?), wallet.clone())),
+                ));
+            }
+            let app = tracing_context::with_correlation(admin_api::with_operator_auth(app, operator_auth));
+
+            info!("Report/admin API listening on http://{}", listen);
+            info!("  default pool routes below require X-Api-Key");
+            info!("  GET /reports/settlements?from=&to=&format=csv (operator token)");
+            info!("  GET /admin/options, /admin/pool (btcfi-admin, operator token), /admin/flows");
+            info!("  GET /admin/treasury, POST /admin/treasury/withdraw");
+            info!("  POST /admin/keys/quote, POST /admin/anchors/requeue");
+            info!("  GET /admin/mempool, POST /admin/mempool/watch");
+            info!("  POST /admin/lp/{{id}}/deposit (operator token), POST /lp/{{id}}/exit");
+            info!("  POST /referrals, GET /referrals/{{code}}");
+            info!("  POST /options (Idempotency-Key), GET /options/{{id}}");
+            info!("  GET /options/{{id}}/audit, /proofs/{{option_id}}");
+            info!("  POST /options/{{id}}/buy-back");
+            info!("  POST /options/{{id}}/roll");
+            info!("  POST /options/{{id}}/exercise (American)");
+            info!("  GET /adl/haircuts, POST /admin/adl/deleverage");
+            info!("  POST /webhooks, GET /webhooks/deliveries (operator token)");
+            info!("  GET/POST/PUT /beneficiaries/{{option_id}} ({})", network);
+            info!("  GET /claims/{{user_id}}, POST /claims/{{user_id}}/withdraw");
+            info!("  GET /prices/proof?timestamp=, GET /prices/commitments");
+            info!("  GET /openapi.json, GET /docs (Swagger UI)");
+            if reserve_fraction.is_some() {
+                info!("  GET /admin/reserve (idle liquidity sweep, blended APY)");
+            }
+            if let Some(wallet) = &anchor_wallet {
+                info!("  GET/POST /options/{{id}}/close, POST /options/{{id}}/close/countersign (fees from {})", wallet);
+                info!("  GET /admin/settlements, POST /admin/settlements/{{id}} (signed settlement tx)");
+            }
+            if !tenant_registry.is_empty() {
+                info!("  /tenants/{{id}}/... ({} tenants, X-Api-Key required)", tenant_registry.len());
+            }
+            let mut signal = shutdown.signal();
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move { signal.recv().await })
+                .await?;
+
+            info!("Shutting down, draining background jobs...");
+            if !shutdown.drain(DRAIN_TIMEOUT).await {
+                warn!("{} jobs still in flight after {:?}", shutdown.in_flight(), DRAIN_TIMEOUT);
+            }
+            for (path, pool) in &snapshots {
+                let manager = pool
+                    .read()
+                    .map_err(|e| anyhow::anyhow!("Pool state lock poisoned: {}", e))?;
+                manager.event_store().flush()?;
+                persist_pool(&manager, path)?;
+            }
+            info!("Event logs flushed and snapshots written, bye");
+        }
+    }
+
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// 새 풀 이벤트를 웹훅 이벤트로 변환 (서버 시작 이전 이벤트는 보내지 않음)
+struct CollectPoolEvents {
+    manager: admin_api::SharedManager,
+    cursor: Mutex<usize>,
+}
+
+impl CollectPoolEvents {
+    fn new(manager: admin_api::SharedManager) -> Self {
+        let cursor = manager.read().unwrap().event_store().events().len();
+        Self {
+            manager,
+            cursor: Mutex::new(cursor),
+        }
+    }
+}
+
+#[async_trait]
+impl Step for CollectPoolEvents {
+    type Input = u64;
+    type Output = (u64, Vec<WebhookEvent>);
+
+    fn name(&self) -> &'static str {
+        "collect_pool_events"
+    }
+
+    async fn run(&self, now: &u64) -> Result<Self::Output, String> {
+        let manager = self.manager.read().map_err(|e| e.to_string())?;
+        let mut cursor = self.cursor.lock().unwrap();
+        let new_events = &manager.event_store().events()[*cursor..];
+        *cursor += new_events.len();
+        let events = new_events.iter().flat_map(WebhookEvent::from_pool_event).collect();
+        Ok((*now, events))
+    }
+}
+
+/// 웹훅 예약 후 시각이 된 전달 시도 (재시도는 디스패처 정책을 따름)
+struct DeliverWebhooks {
+    dispatcher: webhooks::api::SharedDispatcher,
+    transport: HttpTransport,
+}
+
+#[async_trait]
+impl Step for DeliverWebhooks {
+    type Input = (u64, Vec<WebhookEvent>);
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "deliver_webhooks"
+    }
+
+    async fn run(&self, (now, events): &Self::Input) -> Result<(), String> {
+        let mut dispatcher = self.dispatcher.lock().await;
+        for event in events {
+            dispatcher.enqueue(event, *now);
+        }
+        dispatcher.deliver_due(&self.transport, *now).await;
+        Ok(())
+    }
+}
+
+/// 이벤트가 늘어난 풀의 스냅샷 갱신 (재시작 시 이 스냅샷에서 복원)
+struct PersistSnapshots {
+    pools: Vec<(PathBuf, admin_api::SharedManager)>,
+    /// 풀별 마지막으로 저장한 시점의 이벤트 수
+    written: Mutex<Vec<Option<usize>>>,
+}
+
+#[async_trait]
+impl Step for PersistSnapshots {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "persist_snapshots"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        let mut written = self.written.lock().unwrap();
+        for ((path, pool), written) in self.pools.iter().zip(written.iter_mut()) {
+            let manager = pool.read().map_err(|e| e.to_string())?;
+            let events = manager.event_store().events().len();
+            if *written == Some(events) {
+                continue;
+            }
+            persist_pool(&manager, path).map_err(|e| e.to_string())?;
+            *written = Some(events);
+        }
+        Ok(())
+    }
+}
+
+/// 5초마다 기본/테넌트 풀 스냅샷 갱신
+async fn run_snapshot_persistence(
+    pools: Vec<(PathBuf, admin_api::SharedManager)>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let written = Mutex::new(vec![None; pools.len()]);
+    let flow = Flow::new("snapshots", metrics).then(PersistSnapshots { pools, written });
+    flow.run_every(Duration::from_secs(5), unix_now, shutdown).await;
+}
+
+/// bitcoind rawtx 알림을 받아 풀마다 감시 중인 UTXO의 경쟁 지출 검사 (끊기면 다시 연결)
+async fn run_mempool_watch(
+    endpoint: String,
+    responder: HttpChallengeResponder,
+    managers: Vec<admin_api::SharedManager>,
+    mut shutdown: ShutdownSignal,
+) {
+    loop {
+        let connected = tokio::select! {
+            _ = shutdown.recv() => return,
+            connected = ZmqRawTxSubscriber::connect(&endpoint) => connected,
+        };
+        let mut subscriber = match connected {
+            Ok(subscriber) => {
+                info!("Subscribed to rawtx at {}", endpoint);
+                subscriber
+            }
+            Err(e) => {
+                warn!("ZMQ rawtx connect to {} failed: {}", endpoint, e);
+                tokio::select! {
+                    _ = shutdown.recv() => return,
+                    _ = tokio::time::sleep(ZMQ_RECONNECT_DELAY) => continue,
+                }
+            }
+        };
+        loop {
+            let raw_tx = tokio::select! {
+                _ = shutdown.recv() => return,
+                raw_tx = subscriber.next_raw_tx() => match raw_tx {
+                    Ok(raw_tx) => raw_tx,
+                    Err(e) => {
+                        warn!("ZMQ rawtx stream from {} closed: {}", endpoint, e);
+                        break;
+                    }
+                },
+            };
+            let Ok(tx) = bitcoin::consensus::deserialize::<bitcoin::Transaction>(&raw_tx) else {
+                continue;
+            };
+            for manager in &managers {
+                // 대부분의 멤풀 트랜잭션은 감시 대상과 무관하므로 읽기 잠금으로 먼저 거름
+                let spends_watched = manager.read().is_ok_and(|manager| {
+                    tx.input
+                        .iter()
+                        .any(|input| manager.mempool_watch().watched(&input.previous_output).is_some())
+                });
+                if !spends_watched {
+                    continue;
+                }
+                let Ok(mut manager) = manager.write() else {
+                    continue;
+                };
+                if let Err(e) = manager.observe_mempool_tx(&raw_tx, &responder) {
+                    warn!("Failed to record competing spend {}: {}", tx.compute_txid(), e);
+                }
+            }
+        }
+    }
+}
+
+/// 공유 가격 피드에서 합의 가격 조회 (연결과 폴링은 PriceFeedService가 맡음)
+struct FetchConsensusPrice {
+    feed: Arc<PriceFeed<AggregatedPrice>>,
+}
+
+#[async_trait]
+impl Step for FetchConsensusPrice {
+    type Input = u64;
+    type Output = (u64, u64);
+
+    fn name(&self) -> &'static str {
+        "fetch_consensus_price"
+    }
+
+    fn policy(&self) -> StepPolicy {
+        StepPolicy::retry(3, Duration::from_secs(2)).with_timeout(Duration::from_secs(10))
+    }
+
+    async fn run(&self, now: &u64) -> Result<(u64, u64), String> {
+        let snapshot = self.feed.snapshot(*now);
+        match (snapshot.status, snapshot.value) {
+            (FeedStatus::Fresh, Some(price)) => Ok((snapshot.observed_at, price.average_price)),
+            (status, _) => Err(format!(
+                "No consensus price this minute ({:?}): {}",
+                status,
+                snapshot.last_error.unwrap_or_else(|| "no update".to_string())
+            )),
+        }
+    }
+}
+
+/// 분 단위 가격 기록 (정산 가격 가드의 기준 가격도 갱신)
+struct RecordPrice {
+    /// 기본 풀과 테넌트 풀 (모두 같은 합의 가격으로 가드 갱신)
+    managers: Vec<admin_api::SharedManager>,
+    log: price_commitment::api::SharedCommitments,
+}
+
+#[async_trait]
+impl Step for RecordPrice {
+    type Input = (u64, u64);
+    type Output = (u64, u64);
+
+    fn name(&self) -> &'static str {
+        "record_price"
+    }
+
+    async fn run(&self, (timestamp, price): &(u64, u64)) -> Result<(u64, u64), String> {
+        self.log
+            .write()
+            .map_err(|e| e.to_string())?
+            .record(*timestamp, *price);
+        for manager in &self.managers {
+            manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .observe_consensus_price(*price);
+        }
+        Ok((*timestamp, *price))
+    }
+}
+
+/// 합의 가격으로 배리어 옵션 감시 (처음 닿은 옵션은 접촉 기록을 남김)
+struct MonitorBarriers {
+    managers: Vec<admin_api::SharedManager>,
+}
+
+#[async_trait]
+impl Step for MonitorBarriers {
+    type Input = (u64, u64);
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "monitor_barriers"
+    }
+
+    async fn run(&self, (timestamp, price): &(u64, u64)) -> Result<(), String> {
+        for manager in &self.managers {
+            let touched = manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .monitor_barriers(*timestamp, *price)
+                .map_err(|e| e.to_string())?;
+            for option_id in touched {
+                info!("Barrier of {} touched at {}", option_id, price);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 끝난 날을 커밋먼트로 봉인
+///
+/// 이 서버에는 지갑이 없으므로 봉인된 커밋먼트는 OP_RETURN payload를
+/// 로그로 남기고, 앵커링은 `PriceCommitmentLog::anchor_pending`을 쓰는 쪽에서 합니다.
+struct SealCompletedDays {
+    log: price_commitment::api::SharedCommitments,
+}
+
+#[async_trait]
+impl Step for SealCompletedDays {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "seal_completed_days"
+    }
+
+    async fn run(&self, now: &u64) -> Result<(), String> {
+        let mut log = self.log.write().map_err(|e| e.to_string())?;
+        for date in log.seal_completed_days(*now) {
+            if let Some(commitment) = log.seal_day(date) {
+                info!(
+                    "Sealed price commitment for {} ({} prices), OP_RETURN {}",
+                    date,
+                    commitment.prices,
+                    hex::encode(commitment.payload())
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 새 풀 이벤트를 웹훅으로 변환해 예약하고, 시각이 된 전달을 시도 (1초마다)
+async fn run_webhook_delivery(
+    manager: admin_api::SharedManager,
+    dispatcher: webhooks::api::SharedDispatcher,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("webhooks", metrics)
+        .then(CollectPoolEvents::new(manager))
+        .then(DeliverWebhooks {
+            dispatcher,
+            transport: HttpTransport::new(Duration::from_secs(10)),
+        });
+    flow.run_every(Duration::from_secs(1), unix_now, shutdown).await;
+}
+
+/// 합의 가격 수집 실패, 풀 사용률, 미정산 옵션을 모아 경보 규칙 평가
+///
+/// 앵커 전송 실패는 각 풀의 마지막 앵커 조회 알림에서 가져옵니다.
+struct EvaluateAlerts {
+    manager: tokio::sync::Mutex<AlertManager>,
+    pools: Vec<(String, admin_api::SharedManager)>,
+    metrics: Arc<FlowMetrics>,
+}
+
+#[async_trait]
+impl Step for EvaluateAlerts {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "evaluate_alerts"
+    }
+
+    async fn run(&self, now: &u64) -> Result<(), String> {
+        let mut inputs = AlertInputs {
+            consecutive_consensus_failures: self
+                .metrics
+                .step("price_commitment.record", "fetch_consensus_price")
+                .map_or(0, |stats| stats.consecutive_failures),
+            ..AlertInputs::default()
+        };
+        for (name, pool) in &self.pools {
+            let manager = pool.read().map_err(|e| e.to_string())?;
+            inputs
+                .utilization
+                .push((name.clone(), manager.pool_state.utilization_rate()));
+            inputs.observe_anchor_alerts(manager.anchor_alerts());
+            if let Some(tip) = manager.tip_height() {
+                inputs.pending_settlements.extend(
+                    manager
+                        .get_expired_options(tip)
+                        .into_iter()
+                        .map(|option| option.option_id.clone()),
+                );
+            }
+        }
+        self.manager.lock().await.evaluate(&inputs, *now).await;
+        Ok(())
+    }
+}
+
+/// 정책에 맞춰 유휴 유동성을 수익처에 예치/회수
+struct RebalanceReserve {
+    manager: admin_api::SharedManager,
+    reserve: Arc<ReserveManager>,
+}
+
+#[async_trait]
+impl Step for RebalanceReserve {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "rebalance_reserve"
+    }
+
+    async fn run(&self, now: &u64) -> Result<(), String> {
+        let mut manager = self.manager.write().map_err(|e| e.to_string())?;
+        self.reserve.rebalance(&mut manager, *now).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+/// 1분마다 기본 풀의 유휴 유동성 리밸런싱
+async fn run_reserve_rebalancing(
+    manager: admin_api::SharedManager,
+    reserve: Arc<ReserveManager>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("reserve", metrics).then(RebalanceReserve { manager, reserve });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 선납 담보 사용료를 경과 일수만큼 LP 유동성으로 적립
+struct AccrueFunding {
+    managers: Vec<admin_api::SharedManager>,
+}
+
+#[async_trait]
+impl Step for AccrueFunding {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "accrue_funding"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        for manager in &self.managers {
+            let mut manager = manager.write().map_err(|e| e.to_string())?;
+            let amount = manager.accrue_funding().map_err(|e| e.to_string())?;
+            if amount > 0 {
+                info!("Accrued {} sats of collateral funding to LPs", amount);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1시간마다 기본/테넌트 풀의 담보 사용료 적립 (적립액은 옵션별 경과 일수 단위)
+async fn run_funding_accrual(
+    managers: Vec<admin_api::SharedManager>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("funding", metrics).then(AccrueFunding { managers });
+    flow.run_every(Duration::from_secs(3_600), unix_now, shutdown).await;
+}
+
+/// bitcoind 최근 블록 헤더를 받아 기본/테넌트 풀의 높이 ↔ 시각 환산 갱신
+struct SyncBlockHeaders {
+    rpc: HttpBitcoindRpc,
+    block_clock: tokio::sync::Mutex<BlockClock>,
+    managers: Vec<admin_api::SharedManager>,
+}
+
+#[async_trait]
+impl Step for SyncBlockHeaders {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "sync_block_headers"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        let mut block_clock = self.block_clock.lock().await;
+        block_clock.sync(&self.rpc).await.map_err(|e| e.to_string())?;
+        for manager in &self.managers {
+            manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .set_block_clock(block_clock.clone());
+        }
+        Ok(())
+    }
+}
+
+/// 1분마다 블록 헤더 동기화
+async fn run_header_sync(
+    rpc: HttpBitcoindRpc,
+    managers: Vec<admin_api::SharedManager>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("block_headers", metrics).then(SyncBlockHeaders {
+        rpc,
+        block_clock: tokio::sync::Mutex::new(BlockClock::new()),
+        managers,
+    });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 청구 잔고 출금 지급 (지급 지갑에서 보내고 txid 기록)
+struct PayClaimWithdrawals {
+    rpc: HttpBitcoindRpc,
+    wallet: String,
+    pools: Vec<(String, admin_api::SharedManager)>,
+}
+
+#[async_trait]
+impl Step for PayClaimWithdrawals {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "pay_claim_withdrawals"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        for (pool, manager) in &self.pools {
+            // 수익자가 지정된 옵션의 적립은 수익자 주소로 출금을 만든 뒤 함께 지급
+            let designated = manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .withdraw_to_beneficiaries()
+                .map_err(|e| e.to_string())?;
+            for withdrawal in &designated {
+                info!("Withdrawal {} ({}) to beneficiary {}", withdrawal.withdrawal_id, pool, withdrawal.address);
+            }
+            let pending: Vec<_> = manager
+                .read()
+                .map_err(|e| e.to_string())?
+                .claims()
+                .map(|claims| claims.pending_withdrawals().cloned().collect())
+                .unwrap_or_default();
+            for withdrawal in pending {
+                let txid = claimable::pay_withdrawal(&self.rpc, &self.wallet, pool, &withdrawal)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                manager
+                    .write()
+                    .map_err(|e| e.to_string())?
+                    .record_claim_payout(withdrawal.withdrawal_id, &txid)
+                    .map_err(|e| e.to_string())?;
+                info!("Paid withdrawal {} ({}) in {}", withdrawal.withdrawal_id, pool, txid);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1분마다 청구 잔고 출금 지급
+async fn run_claim_payouts(
+    rpc: HttpBitcoindRpc,
+    wallet: String,
+    pools: Vec<(String, admin_api::SharedManager)>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("claim_payouts", metrics).then(PayClaimWithdrawals { rpc, wallet, pools });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 풀별 추적 중인 앵커의 확인 수 조회, 축출된 앵커 재전송/재앵커링
+struct PollAnchors {
+    pools: Vec<(String, admin_api::SharedManager)>,
+    chain: BitcoindChain,
+    broadcaster: BitcoinAnchorer,
+}
+
+#[async_trait]
+impl Step for PollAnchors {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "poll_anchors"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        for (pool, manager) in &self.pools {
+            let unsettled = manager
+                .read()
+                .map_err(|e| e.to_string())?
+                .anchor_tracker()
+                .is_some_and(|tracker| tracker.unsettled().any(|anchor| !anchor.is_parked()));
+            if !unsettled {
+                continue;
+            }
+            let alerts = manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .poll_anchors(&self.chain, &self.broadcaster)
+                .map_err(|e| format!("{}: {}", pool, e))?;
+            for alert in &alerts {
+                info!("Anchor update ({}): {:?}", pool, alert);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1분마다 앵커 확인 추적
+async fn run_anchor_tracking(
+    pools: Vec<(String, admin_api::SharedManager)>,
+    chain: BitcoindChain,
+    broadcaster: BitcoinAnchorer,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("anchors", metrics).then(PollAnchors {
+        pools,
+        chain,
+        broadcaster,
+    });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 풀별 정산 트랜잭션 확인 수 조회, 빠진 트랜잭션 재전송
+struct PollSettlements {
+    pools: Vec<(String, admin_api::SharedManager)>,
+    chain: BitcoindChain,
+    broadcaster: BitcoinAnchorer,
+}
+
+#[async_trait]
+impl Step for PollSettlements {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "poll_settlements"
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        for (pool, manager) in &self.pools {
+            let unsettled = manager
+                .read()
+                .map_err(|e| e.to_string())?
+                .settlement_broadcasts()
+                .is_some_and(|settlements| settlements.settlements().any(|settlement| !settlement.is_settled()));
+            if !unsettled {
+                continue;
+            }
+            let alerts = manager
+                .write()
+                .map_err(|e| e.to_string())?
+                .poll_settlements(&self.chain, &self.broadcaster)
+                .map_err(|e| format!("{}: {}", pool, e))?;
+            for alert in &alerts {
+                info!("Settlement update ({}): {:?}", pool, alert);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 1분마다 정산 트랜잭션 확인 추적
+async fn run_settlement_tracking(
+    pools: Vec<(String, admin_api::SharedManager)>,
+    chain: BitcoindChain,
+    broadcaster: BitcoinAnchorer,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("settlements", metrics).then(PollSettlements {
+        pools,
+        chain,
+        broadcaster,
+    });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 기본 풀의 활성 옵션 담보를 Calculation 미결제약정으로 보고 (종료/행사/만기분은 빠짐)
+struct ReportOpenInterest {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+    manager: admin_api::SharedManager,
+}
+
+#[async_trait]
+impl Step for ReportOpenInterest {
+    type Input = u64;
+    type Output = ();
+
+    fn name(&self) -> &'static str {
+        "report_open_interest"
+    }
+
+    fn policy(&self) -> StepPolicy {
+        StepPolicy::retry(3, Duration::from_secs(2)).with_timeout(Duration::from_secs(10))
+    }
+
+    async fn run(&self, _now: &u64) -> Result<(), String> {
+        let entries = self.manager.read().map_err(|e| e.to_string())?.open_interest_report();
+        self.client
+            .put(format!("{}/api/rfq/open-interest", self.url.trim_end_matches('/')))
+            .bearer_auth(&self.token)
+            .json(&entries)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+/// 30초마다 미결제약정 보고
+async fn run_open_interest_reports(
+    url: String,
+    token: String,
+    manager: admin_api::SharedManager,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("open_interest", metrics).then(ReportOpenInterest {
+        client: reqwest::Client::new(),
+        url,
+        token,
+        manager,
+    });
+    flow.run_every(Duration::from_secs(30), unix_now, shutdown).await;
+}
+
+/// 1분마다 경보 규칙 평가
+async fn run_alerting(
+    manager: AlertManager,
+    pools: Vec<(String, admin_api::SharedManager)>,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let flow = Flow::new("alerting", metrics.clone()).then(EvaluateAlerts {
+        manager: tokio::sync::Mutex::new(manager),
+        pools,
+        metrics,
+    });
+    flow.run_every(Duration::from_secs(60), unix_now, shutdown).await;
+}
+
+/// 1분마다 Aggregator 합의 가격을 기록해 배리어를 감시하고, 끝난 날은 커밋먼트로 봉인하며,
+/// Aggregator의 거래 중단/재개를 모든 풀에 반영
+async fn run_price_commitments(
+    url: String,
+    managers: Vec<admin_api::SharedManager>,
+    log: price_commitment::api::SharedCommitments,
+    metrics: Arc<FlowMetrics>,
+    shutdown: ShutdownSignal,
+) {
+    let service = PriceFeedService::lazy(&url, PRICE_FEED_INTERVAL_SECS);
+    let trading_status = TradingStatusSync::new(&url, managers.clone());
+    let record = Flow::new("price_commitment.record", metrics.clone())
+        .then(FetchConsensusPrice {
+            feed: service.feed(),
+        })
+        .then(RecordPrice {
+            managers: managers.clone(),
+            log: log.clone(),
+        })
+        .then(MonitorBarriers { managers });
+    let seal = Flow::new("price_commitment.seal", metrics).then(SealCompletedDays { log });
+
+    tokio::join!(
+        service.run_until(shutdown.clone()),
+        trading_status.run_until(Duration::from_secs(PRICE_FEED_INTERVAL_SECS), shutdown.clone()),
+        record.run_every(Duration::from_secs(60), unix_now, shutdown.clone()),
+        seal.run_every(Duration::from_secs(60), unix_now, shutdown),
+    );
+}
@@ -0,0 +1,646 @@
+//! 옵션 UTXO 경쟁 지출 감시
+//!
+//! bitcoind ZMQ `rawtx` 알림으로 들어오는 트랜잭션마다 입력을 확인해, 감시 중인
+//! 옵션/계약 UTXO를 쓰는 트랜잭션을 찾습니다. 정산 전송 관리자가 만든 정산
+//! 트랜잭션처럼 예상한 지출은 넘어가고, 그 외 지출은 알림과 함께 챌린지
+//! 경로를 바로 무장시켜 상대방이 잘못된 정산을 블록에 밀어넣기 전에 대응할 수
+//! 있게 합니다.
+//!
+//! `ZmqRawTxSubscriber`는 bitcoind의 `zmqpubrawtx` 엔드포인트에 붙는 최소한의
+//! ZMTP 3.0 SUB 소켓(NULL 메커니즘)이고, `HttpChallengeResponder`는 무장할
+//! 챌린지를 워치타워 URL로 보냅니다. 감시 대상과 챌린지는 풀 스냅샷에 남습니다.
+
+use crate::settlement_broadcast::SettlementBroadcastManager;
+use bitcoin::{OutPoint, Transaction};
+use oracle_vm_common::AnchorError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::{info, warn};
+
+/// ZMQ `rawtx` 토픽
+pub const ZMQ_RAWTX_TOPIC: &[u8] = b"rawtx";
+
+/// ZMQ 멀티파트 메시지 [토픽, 트랜잭션, 시퀀스(u32 LE)]에서 트랜잭션과 시퀀스 추출
+pub fn parse_zmq_rawtx(frames: &[Vec<u8>]) -> Option<(&[u8], u32)> {
+    let [topic, body, sequence] = frames else {
+        return None;
+    };
+    if topic.as_slice() != ZMQ_RAWTX_TOPIC {
+        return None;
+    }
+    let sequence = u32::from_le_bytes(sequence.as_slice().try_into().ok()?);
+    Some((body.as_slice(), sequence))
+}
+
+/// ZMTP 프레임 플래그
+const FLAG_MORE: u8 = 0x01;
+const FLAG_LONG: u8 = 0x02;
+const FLAG_COMMAND: u8 = 0x04;
+/// 프레임 하나의 최대 크기 (블록 무게 한도 4MWU보다 넉넉하게)
+const MAX_FRAME_LEN: u64 = 8 * 1024 * 1024;
+
+/// ZMTP 3.0 인사 (NULL 메커니즘, 클라이언트)
+fn zmtp_greeting() -> [u8; 64] {
+    let mut greeting = [0u8; 64];
+    greeting[0] = 0xff;
+    greeting[9] = 0x7f;
+    greeting[10] = 3;
+    greeting[12..16].copy_from_slice(b"NULL");
+    greeting
+}
+
+/// READY 명령 본문 (Socket-Type 속성 하나)
+fn ready_command(socket_type: &str) -> Vec<u8> {
+    let mut body = vec![5];
+    body.extend_from_slice(b"READY");
+    body.push(11);
+    body.extend_from_slice(b"Socket-Type");
+    body.extend_from_slice(&(socket_type.len() as u32).to_be_bytes());
+    body.extend_from_slice(socket_type.as_bytes());
+    body
+}
+
+fn protocol_error(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+async fn write_frame<S: AsyncWrite + Unpin>(stream: &mut S, flags: u8, body: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(body.len() + 9);
+    if body.len() > u8::MAX as usize {
+        frame.push(flags | FLAG_LONG);
+        frame.extend_from_slice(&(body.len() as u64).to_be_bytes());
+    } else {
+        frame.push(flags);
+        frame.push(body.len() as u8);
+    }
+    frame.extend_from_slice(body);
+    stream.write_all(&frame).await
+}
+
+async fn read_frame<S: AsyncRead + Unpin>(stream: &mut S) -> io::Result<(u8, Vec<u8>)> {
+    let flags = stream.read_u8().await?;
+    let len = if flags & FLAG_LONG != 0 {
+        stream.read_u64().await?
+    } else {
+        u64::from(stream.read_u8().await?)
+    };
+    if len > MAX_FRAME_LEN {
+        return Err(protocol_error(format!("frame of {} bytes exceeds limit", len)));
+    }
+    let mut body = vec![0u8; len as usize];
+    stream.read_exact(&mut body).await?;
+    Ok((flags, body))
+}
+
+/// bitcoind `zmqpubrawtx` 구독자
+///
+/// ZMTP 3.0 NULL 메커니즘으로 인사/READY를 주고받은 뒤 `rawtx` 토픽만 구독합니다.
+/// bitcoind는 재연결 시 보내지 못한 알림을 다시 보내지 않으므로 시퀀스가 건너뛰면
+/// 경고만 남깁니다.
+pub struct ZmqRawTxSubscriber<S = TcpStream> {
+    stream: S,
+    last_sequence: Option<u32>,
+}
+
+impl ZmqRawTxSubscriber<TcpStream> {
+    /// `tcp://host:port` 엔드포인트에 연결
+    pub async fn connect(endpoint: &str) -> io::Result<Self> {
+        let address = endpoint.strip_prefix("tcp://").unwrap_or(endpoint);
+        Self::handshake(TcpStream::connect(address).await?).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> ZmqRawTxSubscriber<S> {
+    /// 이미 열린 스트림에서 인사/READY 교환 후 `rawtx` 구독
+    pub async fn handshake(mut stream: S) -> io::Result<Self> {
+        stream.write_all(&zmtp_greeting()).await?;
+        let mut greeting = [0u8; 64];
+        stream.read_exact(&mut greeting).await?;
+        if greeting[0] != 0xff || greeting[9] != 0x7f || greeting[10] < 3 {
+            return Err(protocol_error("peer is not a ZMTP 3 socket"));
+        }
+        if &greeting[12..16] != b"NULL" {
+            return Err(protocol_error("peer requires a security mechanism other than NULL"));
+        }
+
+        write_frame(&mut stream, FLAG_COMMAND, &ready_command("SUB")).await?;
+        let (flags, body) = read_frame(&mut stream).await?;
+        if flags & FLAG_COMMAND == 0 || !body.starts_with(b"\x05READY") {
+            return Err(protocol_error("peer did not send READY"));
+        }
+
+        // ZMTP 3.0 구독: 0x01 + 토픽 접두사 메시지
+        let mut subscribe = vec![0x01];
+        subscribe.extend_from_slice(ZMQ_RAWTX_TOPIC);
+        write_frame(&mut stream, 0, &subscribe).await?;
+        Ok(Self {
+            stream,
+            last_sequence: None,
+        })
+    }
+
+    /// 다음 `rawtx` 알림의 원시 트랜잭션
+    pub async fn next_raw_tx(&mut self) -> io::Result<Vec<u8>> {
+        loop {
+            let frames = self.next_message().await?;
+            let Some((raw_tx, sequence)) = parse_zmq_rawtx(&frames) else {
+                continue;
+            };
+            if let Some(last) = self.last_sequence {
+                if sequence != last.wrapping_add(1) {
+                    warn!("ZMQ rawtx sequence jumped from {} to {}, notifications were missed", last, sequence);
+                }
+            }
+            self.last_sequence = Some(sequence);
+            return Ok(raw_tx.to_vec());
+        }
+    }
+
+    /// 멀티파트 메시지 하나 (PING 같은 명령 프레임은 건너뜀)
+    async fn next_message(&mut self) -> io::Result<Vec<Vec<u8>>> {
+        let mut frames = Vec::new();
+        loop {
+            let (flags, body) = read_frame(&mut self.stream).await?;
+            if flags & FLAG_COMMAND != 0 {
+                continue;
+            }
+            frames.push(body);
+            if flags & FLAG_MORE == 0 {
+                return Ok(frames);
+            }
+        }
+    }
+}
+
+/// 원시 트랜잭션 공급원 (ZMQ 구독자가 구현, 더 없으면 None)
+pub trait RawTxSource {
+    fn next_raw_tx(&mut self) -> Option<Vec<u8>>;
+}
+
+/// 챌린지/워치타워 경로
+pub trait ChallengeResponder {
+    /// 경쟁 지출에 대한 챌린지 무장
+    fn arm(&self, challenge: &ArmedChallenge) -> Result<(), AnchorError>;
+}
+
+/// 워치타워 HTTP 경로 (`POST <url>`에 무장할 챌린지 JSON, 2xx면 무장 완료)
+///
+/// 감시기 인터페이스가 동기이므로 멀티스레드 런타임 안에서 블로킹으로 보냅니다.
+pub struct HttpChallengeResponder {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpChallengeResponder {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(5))
+                .build()
+                .expect("reqwest client builds"),
+        }
+    }
+}
+
+impl ChallengeResponder for HttpChallengeResponder {
+    fn arm(&self, challenge: &ArmedChallenge) -> Result<(), AnchorError> {
+        let request = crate::tracing_context::outbound(self.client.post(&self.url)).json(challenge);
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                request
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map(|_| ())
+                    .map_err(|e| AnchorError::Rpc(format!("watchtower: {}", e)))
+            })
+        })
+    }
+}
+
+/// 감시 중인 UTXO
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedUtxo {
+    pub option_id: String,
+    /// 우리가 만든 지출 트랜잭션 (이 txid의 지출은 정상)
+    pub expected_txid: Option<String>,
+}
+
+/// 무장된 챌린지
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArmedChallenge {
+    pub option_id: String,
+    pub outpoint: OutPoint,
+    pub spending_txid: String,
+    /// 경쟁 트랜잭션 원본 (챌린지 증거)
+    pub raw_tx: Vec<u8>,
+    /// 챌린지 경로가 무장을 받아들였는지 (실패하면 다음 관찰 때 재시도)
+    pub armed: bool,
+}
+
+/// 감시 결과 알림
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SpendAlert {
+    /// 예상하지 않은 지출, 챌린지 무장
+    Unexpected { option_id: String, outpoint: OutPoint, txid: String },
+    /// 챌린지 무장 실패
+    ArmFailed { option_id: String, txid: String, error: String },
+}
+
+/// 스냅샷에 싣는 감시 대상과 챌린지
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolWatchRecords {
+    pub watched: Vec<(OutPoint, WatchedUtxo)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub challenges: Vec<ArmedChallenge>,
+}
+
+/// 멤풀 경쟁 지출 감시기
+#[derive(Debug, Default)]
+pub struct MempoolWatcher {
+    watched: HashMap<OutPoint, WatchedUtxo>,
+    challenges: Vec<ArmedChallenge>,
+}
+
+impl MempoolWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 스냅샷 기록에서 복원
+    pub fn restore(records: MempoolWatchRecords) -> Self {
+        Self {
+            watched: records.watched.into_iter().collect(),
+            challenges: records.challenges,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watched.is_empty() && self.challenges.is_empty()
+    }
+
+    /// 스냅샷 기록 (outpoint 순)
+    pub fn records(&self) -> MempoolWatchRecords {
+        let mut watched: Vec<_> = self
+            .watched
+            .iter()
+            .map(|(outpoint, utxo)| (*outpoint, utxo.clone()))
+            .collect();
+        watched.sort_by_key(|(outpoint, _)| *outpoint);
+        MempoolWatchRecords {
+            watched,
+            challenges: self.challenges.clone(),
+        }
+    }
+
+    /// 옵션/계약 UTXO 감시 시작
+    pub fn watch(&mut self, option_id: &str, outpoint: OutPoint) {
+        self.watched.entry(outpoint).or_insert_with(|| WatchedUtxo {
+            option_id: option_id.to_string(),
+            expected_txid: None,
+        });
+    }
+
+    /// 감시 종료 (최종 확인된 정산 등)
+    pub fn unwatch(&mut self, outpoint: &OutPoint) {
+        self.watched.remove(outpoint);
+    }
+
+    pub fn watched(&self, outpoint: &OutPoint) -> Option<&WatchedUtxo> {
+        self.watched.get(outpoint)
+    }
+
+    /// 우리가 만든 지출 트랜잭션 등록
+    pub fn expect_spend(&mut self, outpoint: &OutPoint, txid: &str) {
+        if let Some(watched) = self.watched.get_mut(outpoint) {
+            watched.expected_txid = Some(txid.to_string());
+        }
+    }
+
+    /// 정산 전송 관리자가 추적 중인 정산 트랜잭션을 예상 지출로 등록
+    pub fn expect_settlements(&mut self, settlements: &SettlementBroadcastManager) {
+        for settlement in settlements.settlements() {
+            for outpoint in &settlement.inputs {
+                self.expect_spend(outpoint, &settlement.txid);
+            }
+        }
+    }
+
+    pub fn challenges(&self) -> &[ArmedChallenge] {
+        &self.challenges
+    }
+
+    /// 멤풀에 들어온 원시 트랜잭션 하나 검사
+    ///
+    /// 같은 경쟁 트랜잭션은 한 번만 알리고, 무장에 실패한 챌린지만 다시
+    /// 시도합니다.
+    pub fn observe(
+        &mut self,
+        raw_tx: &[u8],
+        responder: &dyn ChallengeResponder,
+    ) -> Result<Vec<SpendAlert>, AnchorError> {
+        let tx: Transaction = bitcoin::consensus::deserialize(raw_tx)
+            .map_err(|e| AnchorError::InvalidPayload(e.to_string()))?;
+        let txid = tx.compute_txid().to_string();
+        let mut alerts = Vec::new();
+
+        for input in &tx.input {
+            let outpoint = input.previous_output;
+            let Some(watched) = self.watched.get(&outpoint) else {
+                continue;
+            };
+            if watched.expected_txid.as_deref() == Some(txid.as_str()) {
+                continue;
+            }
+
+            let index = match self
+                .challenges
+                .iter()
+                .position(|challenge| challenge.outpoint == outpoint && challenge.spending_txid == txid)
+            {
+                Some(index) if self.challenges[index].armed => continue,
+                Some(index) => index,
+                None => {
+                    warn!(
+                        "🚨 Unexpected spend of {} ({}) by {}",
+                        outpoint, watched.option_id, txid
+                    );
+                    alerts.push(SpendAlert::Unexpected {
+                        option_id: watched.option_id.clone(),
+                        outpoint,
+                        txid: txid.clone(),
+                    });
+                    self.challenges.push(ArmedChallenge {
+                        option_id: watched.option_id.clone(),
+                        outpoint,
+                        spending_txid: txid.clone(),
+                        raw_tx: raw_tx.to_vec(),
+                        armed: false,
+                    });
+                    self.challenges.len() - 1
+                }
+            };
+
+            let challenge = &mut self.challenges[index];
+            match responder.arm(challenge) {
+                Ok(()) => {
+                    info!("Challenge armed for {} against {}", challenge.option_id, txid);
+                    challenge.armed = true;
+                }
+                Err(e) => alerts.push(SpendAlert::ArmFailed {
+                    option_id: challenge.option_id.clone(),
+                    txid: txid.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// 공급원이 빌 때까지 검사 (해석할 수 없는 트랜잭션은 건너뜀)
+    pub fn drain(&mut self, source: &mut dyn RawTxSource, responder: &dyn ChallengeResponder) -> Vec<SpendAlert> {
+        let mut alerts = Vec::new();
+        while let Some(raw_tx) = source.next_raw_tx() {
+            match self.observe(&raw_tx, responder) {
+                Ok(found) => alerts.extend(found),
+                Err(e) => warn!("Skipping undecodable mempool tx: {}", e),
+            }
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::anchor_tracker::AnchorBroadcaster;
+    use bitcoin::hashes::Hash;
+    use bitcoin::{absolute::LockTime, Amount, ScriptBuf, Sequence, TxIn, TxOut, Txid, Witness};
+    use oracle_vm_common::NetworkProfile;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+
+    fn spend(outpoint: OutPoint, value: u64) -> Transaction {
+        Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    struct Broadcaster;
+
+    impl AnchorBroadcaster for Broadcaster {
+        fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+            Ok(String::new())
+        }
+
+        fn anchor(&self, _payload: &[u8]) -> Result<String, AnchorError> {
+            unreachable!()
+        }
+    }
+
+    /// 첫 무장은 실패, 이후 성공
+    #[derive(Default)]
+    struct Responder {
+        calls: Cell<u32>,
+        armed: RefCell<Vec<String>>,
+    }
+
+    impl ChallengeResponder for Responder {
+        fn arm(&self, challenge: &ArmedChallenge) -> Result<(), AnchorError> {
+            self.calls.set(self.calls.get() + 1);
+            if self.calls.get() == 1 {
+                return Err(AnchorError::Rpc("watchtower offline".to_string()));
+            }
+            self.armed.borrow_mut().push(challenge.spending_txid.clone());
+            Ok(())
+        }
+    }
+
+    struct Feed(VecDeque<Vec<u8>>);
+
+    impl RawTxSource for Feed {
+        fn next_raw_tx(&mut self) -> Option<Vec<u8>> {
+            self.0.pop_front()
+        }
+    }
+
+    #[test]
+    fn test_competing_spend_arms_challenge() {
+        let utxo = OutPoint::new(Txid::all_zeros(), 0);
+        let mut settlements = SettlementBroadcastManager::new(NetworkProfile::TESTNET);
+        let ours = spend(utxo, 90_000);
+        settlements.broadcast("CALL-1", &ours, &Broadcaster).unwrap();
+
+        let mut watcher = MempoolWatcher::new();
+        watcher.watch("CALL-1", utxo);
+        watcher.expect_settlements(&settlements);
+        let responder = Responder::default();
+
+        // 우리 정산과 무관한 UTXO 지출은 무시
+        let theirs = bitcoin::consensus::serialize(&spend(utxo, 99_000));
+        let unrelated = bitcoin::consensus::serialize(&spend(OutPoint::new(Txid::all_zeros(), 9), 1));
+        let mut feed = Feed(VecDeque::from([
+            bitcoin::consensus::serialize(&ours),
+            unrelated,
+            vec![0xff],
+            theirs.clone(),
+        ]));
+        let alerts = watcher.drain(&mut feed, &responder);
+        let txid = spend(utxo, 99_000).compute_txid().to_string();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(
+            alerts[0],
+            SpendAlert::Unexpected {
+                option_id: "CALL-1".to_string(),
+                outpoint: utxo,
+                txid: txid.clone()
+            }
+        );
+        assert!(matches!(alerts[1], SpendAlert::ArmFailed { .. }));
+        assert!(!watcher.challenges()[0].armed);
+
+        // 같은 트랜잭션을 다시 보면 알림 없이 무장만 재시도, 이후엔 조용함
+        assert!(watcher.observe(&theirs, &responder).unwrap().is_empty());
+        assert!(watcher.challenges()[0].armed);
+        assert!(watcher.observe(&theirs, &responder).unwrap().is_empty());
+        assert_eq!(*responder.armed.borrow(), vec![txid]);
+        assert_eq!(watcher.challenges().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_zmq_rawtx() {
+        let frames = vec![b"rawtx".to_vec(), vec![1, 2, 3], 7u32.to_le_bytes().to_vec()];
+        assert_eq!(parse_zmq_rawtx(&frames), Some((&[1u8, 2, 3][..], 7)));
+        let hashtx = vec![b"hashtx".to_vec(), vec![1], 7u32.to_le_bytes().to_vec()];
+        assert_eq!(parse_zmq_rawtx(&hashtx), None);
+        assert_eq!(parse_zmq_rawtx(&frames[..2]), None);
+    }
+
+    /// 가짜 bitcoind PUB 소켓: 인사/READY 교환, 구독 확인 후 알림 전송
+    async fn publish<S: AsyncRead + AsyncWrite + Unpin>(mut peer: S, messages: Vec<Vec<Vec<u8>>>) -> S {
+        let mut greeting = [0u8; 64];
+        peer.read_exact(&mut greeting).await.unwrap();
+        assert_eq!(&greeting[12..16], b"NULL");
+        peer.write_all(&zmtp_greeting()).await.unwrap();
+        let (flags, ready) = read_frame(&mut peer).await.unwrap();
+        assert_eq!(flags, FLAG_COMMAND);
+        assert!(ready.ends_with(b"Socket-Type\x00\x00\x00\x03SUB"));
+        write_frame(&mut peer, FLAG_COMMAND, &ready_command("PUB")).await.unwrap();
+        let (_, subscribe) = read_frame(&mut peer).await.unwrap();
+        assert_eq!(subscribe, b"\x01rawtx");
+
+        write_frame(&mut peer, FLAG_COMMAND, b"\x04PING\x00\x00").await.unwrap();
+        for frames in messages {
+            let last = frames.len() - 1;
+            for (index, frame) in frames.iter().enumerate() {
+                let flags = if index < last { FLAG_MORE } else { 0 };
+                write_frame(&mut peer, flags, frame).await.unwrap();
+            }
+        }
+        peer
+    }
+
+    #[tokio::test]
+    async fn test_zmq_subscriber_reads_rawtx_notifications() {
+        let (client, server) = tokio::io::duplex(1 << 16);
+        let large = bitcoin::consensus::serialize(&spend(OutPoint::new(Txid::all_zeros(), 1), 5)).repeat(8);
+        assert!(large.len() > u8::MAX as usize);
+        let publisher = tokio::spawn(publish(
+            server,
+            vec![
+                vec![b"hashtx".to_vec(), vec![0; 32], 0u32.to_le_bytes().to_vec()],
+                vec![b"rawtx".to_vec(), vec![1, 2, 3], 0u32.to_le_bytes().to_vec()],
+                vec![b"rawtx".to_vec(), large.clone(), 2u32.to_le_bytes().to_vec()],
+            ],
+        ));
+
+        let mut subscriber = ZmqRawTxSubscriber::handshake(client).await.unwrap();
+        assert_eq!(subscriber.next_raw_tx().await.unwrap(), vec![1, 2, 3]);
+        // 긴 프레임도 읽고, 건너뛴 시퀀스는 경고만 남김
+        assert_eq!(subscriber.next_raw_tx().await.unwrap(), large);
+        assert_eq!(subscriber.last_sequence, Some(2));
+        drop(publisher.await.unwrap());
+        assert!(subscriber.next_raw_tx().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_zmq_subscriber_rejects_non_zmtp_peer() {
+        let (client, mut server) = tokio::io::duplex(1024);
+        tokio::spawn(async move {
+            let mut greeting = [0u8; 64];
+            server.read_exact(&mut greeting).await.unwrap();
+            server.write_all(&[b'H'; 64]).await.unwrap();
+        });
+        let error = ZmqRawTxSubscriber::handshake(client).await.err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_manager_records_competing_spends_in_events_and_snapshot() {
+        use crate::event_store::PoolEventKind;
+        use crate::simple_contract::SimpleContractManager;
+        use oracle_vm_common::types::OptionType;
+
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option(
+                "CALL-1".to_string(),
+                OptionType::Call,
+                7_000_000,
+                1_000_000,
+                25_000,
+                800_000,
+                "user".to_string(),
+            )
+            .unwrap();
+        let utxo = OutPoint::new(Txid::all_zeros(), 0);
+        manager.watch_utxo("CALL-1", utxo).unwrap();
+        manager.watch_utxo("CALL-1", utxo).unwrap();
+        assert!(manager.watch_utxo("CALL-9", utxo).is_err());
+
+        let theirs = bitcoin::consensus::serialize(&spend(utxo, 99_000));
+        let responder = Responder::default();
+        assert_eq!(manager.observe_mempool_tx(&theirs, &responder).unwrap().len(), 2);
+        assert!(manager.observe_mempool_tx(&theirs, &responder).unwrap().is_empty());
+        assert!(manager.observe_mempool_tx(&[0xff], &responder).unwrap().is_empty());
+
+        let kinds: Vec<_> = manager
+            .event_store()
+            .events()
+            .iter()
+            .filter_map(|event| match &event.kind {
+                PoolEventKind::UtxoWatched { .. } => Some("watched"),
+                PoolEventKind::CompetingSpendDetected { .. } => Some("detected"),
+                PoolEventKind::ChallengeArmed { .. } => Some("armed"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(kinds, vec!["watched", "detected", "armed"]);
+
+        // 재시작해도 감시 대상과 무장한 챌린지가 남아 같은 지출을 다시 알리지 않음
+        let records = manager.snapshot(0, Vec::new()).mempool_watch.unwrap();
+        let mut restored = MempoolWatcher::restore(records);
+        assert!(restored.watched(&utxo).is_some());
+        assert!(restored.challenges()[0].armed);
+        assert!(restored.observe(&theirs, &responder).unwrap().is_empty());
+    }
+}
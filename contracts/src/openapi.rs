@@ -0,0 +1,97 @@
+//! HTTP API 명세 (`/openapi.json`, Swagger UI `/docs`)
+//!
+//! 각 모듈 `api` 핸들러의 `#[utoipa::path]` 주석에서 명세를 생성하므로 라우트를
+//! 추가하면 여기 `paths`에도 등록해야 합니다. 프론트엔드와 파트너 SDK는 이
+//! 명세로 자동 생성합니다.
+//!
+//! 테넌트 풀은 풀 API를 같은 경로로 `/tenants/{id}` 아래에 제공하며
+//! `X-Api-Key` 헤더가 필요합니다 (명세에는 기본 풀 경로만 싣습니다).
+//! `/admin/*`, `/reports/*`, `/webhooks*`는 운영자 토큰(`Authorization: Bearer`)이 필요합니다.
+
+use axum::Router;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "BTCFi Contracts API"),
+    paths(
+        crate::simple_contract::api::open_option,
+        crate::simple_contract::api::get_option,
+        crate::admin_api::list_options,
+        crate::admin_api::get_option,
+        crate::admin_api::expire_option,
+        crate::admin_api::settle_option,
+        crate::admin_api::pause_trading,
+        crate::admin_api::resume_trading,
+        crate::admin_api::pool_metrics,
+        crate::admin_api::bind_account_key,
+        crate::admin_api::rotate_quote_key,
+        crate::admin_api::requeue_anchors,
+        crate::admin_api::watch_utxo,
+        crate::admin_api::mempool_watch,
+        crate::admin_api::get_report,
+        crate::fees::api::get_treasury,
+        crate::fees::api::withdraw,
+        crate::lp_book::api::deposit,
+        crate::lp_book::api::get_position,
+        crate::lp_book::api::exit,
+        crate::lp_book::api::redeem,
+        crate::lp_book::api::transfer,
+        crate::referral::api::register,
+        crate::referral::api::get_code,
+        crate::claimable::api::get_claims,
+        crate::claimable::api::withdraw,
+        crate::buy_back::api::buy_back,
+        crate::buy_back::api::roll,
+        crate::early_exercise::api::exercise,
+        crate::adl::api::list_haircuts,
+        crate::adl::api::get_haircut,
+        crate::adl::api::deleverage,
+        crate::audit::api::get_audit,
+        crate::webhooks::api::register,
+        crate::webhooks::api::list,
+        crate::webhooks::api::unregister,
+        crate::webhooks::api::deliveries,
+        crate::beneficiary::api::register,
+        crate::beneficiary::api::get_beneficiary,
+        crate::beneficiary::api::change,
+        crate::price_commitment::api::prove_price,
+        crate::price_commitment::api::list_commitments,
+        crate::proof_archive::api::list_proofs,
+        crate::proof_archive::api::get_proof,
+        crate::flow::api::list_flows,
+    )
+)]
+pub struct ContractsApi;
+
+/// `/openapi.json`과 Swagger UI(`/docs`) 라우터 생성
+pub fn router() -> Router {
+    SwaggerUi::new("/docs")
+        .url("/openapi.json", ContractsApi::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_openapi_json_lists_routes() {
+        let request = Request::get("/openapi.json").body(Body::empty()).unwrap();
+        let response = router().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let paths = spec["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), 42);
+        // 같은 경로의 여러 메서드는 한 항목에 모임
+        let beneficiary = &paths["/beneficiaries/{option_id}"];
+        assert!(beneficiary["get"].is_object() && beneficiary["post"].is_object() && beneficiary["put"].is_object());
+        assert!(spec["components"]["schemas"]["AdminError"].is_object());
+    }
+}
@@ -0,0 +1,78 @@
+//! 옵션 보조 인덱스
+//!
+//! 옵션 ID 외에 만기 높이, 행사가, 상태, 사용자별로 옵션 ID를 찾을 수 있게 합니다.
+//! SimpleContractManager가 옵션을 추가하거나 상태를 바꿀 때마다 함께 갱신합니다.
+
+use crate::simple_contract::{OptionStatus, SimpleOption};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::RangeBounds;
+
+/// 만기/행사가/상태/사용자별 옵션 ID 인덱스
+#[derive(Debug, Default)]
+pub struct OptionIndex {
+    by_expiry: BTreeMap<u32, BTreeSet<String>>,
+    by_strike: BTreeMap<u64, BTreeSet<String>>,
+    by_status: HashMap<OptionStatus, BTreeSet<String>>,
+    by_user: HashMap<String, BTreeSet<String>>,
+}
+
+impl OptionIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 새 옵션 등록
+    pub fn insert(&mut self, option: &SimpleOption) {
+        let id = option.option_id.clone();
+        self.by_expiry
+            .entry(option.expiry_height)
+            .or_default()
+            .insert(id.clone());
+        self.by_strike
+            .entry(option.strike_price)
+            .or_default()
+            .insert(id.clone());
+        self.by_status.entry(option.status).or_default().insert(id.clone());
+        self.by_user
+            .entry(option.user_id.clone())
+            .or_default()
+            .insert(id);
+    }
+
+    /// 상태 변경 반영
+    pub fn update_status(&mut self, option_id: &str, from: OptionStatus, to: OptionStatus) {
+        if let Some(ids) = self.by_status.get_mut(&from) {
+            ids.remove(option_id);
+        }
+        self.by_status
+            .entry(to)
+            .or_default()
+            .insert(option_id.to_string());
+    }
+
+    /// 만기 높이 구간에 속한 옵션 ID (만기 순)
+    pub fn by_expiry_range<R: RangeBounds<u32>>(&self, range: R) -> impl Iterator<Item = &String> {
+        self.by_expiry.range(range).flat_map(|(_, ids)| ids.iter())
+    }
+
+    /// 행사가가 같은 옵션 ID
+    pub fn by_strike(&self, strike_price: u64) -> impl Iterator<Item = &String> {
+        self.by_strike.get(&strike_price).into_iter().flatten()
+    }
+
+    pub fn has_status(&self, option_id: &str, status: OptionStatus) -> bool {
+        self.by_status
+            .get(&status)
+            .is_some_and(|ids| ids.contains(option_id))
+    }
+
+    /// 상태별 옵션 ID
+    pub fn by_status(&self, status: OptionStatus) -> impl Iterator<Item = &String> {
+        self.by_status.get(&status).into_iter().flatten()
+    }
+
+    /// 사용자별 옵션 ID
+    pub fn by_user(&self, user_id: &str) -> impl Iterator<Item = &String> {
+        self.by_user.get(user_id).into_iter().flatten()
+    }
+}
@@ -0,0 +1,634 @@
+//! 장외(OTC) 양자 거래
+//!
+//! 이름이 있는 두 거래 상대방이 표준 행사가/만기 사다리 밖의 조건(행사가,
+//! 만기 높이, 수량, 프리미엄)을 직접 정하는 큰 거래를 공유 풀 밖에서
+//! 처리합니다.
+//!
+//! 1. 한쪽이 조건을 제안 (`propose`)
+//! 2. 양쪽이 조건의 정규 인코딩에 서명 (`confirm`), 둘 다 서명하면 에스크로
+//!    Taproot 출력이 정해짐
+//! 3. 구매자는 프리미엄, 판매자는 담보를 한 트랜잭션으로 에스크로 출력에 예치
+//!    (`record_funding`)
+//! 4. 만기 후 풀 옵션과 같은 합의 가격과 가격 밴드 검사로 정산 (`settle`).
+//!    에스크로는 BitVMX 검증자 키로 정산 트랜잭션에 서명해 풉니다
+//!
+//! 에스크로 출력의 key path는 NUMS 키라 쓸 수 없고, script path는 두 가지입니다.
+//!
+//! ```text
+//! 협의 해지: <buyer> CHECKSIGVERIFY <seller> CHECKSIG
+//! 오라클 정산: <expiry> CLTV DROP <verifier> CHECKSIG
+//! ```
+
+use crate::price_guard::{PriceBandConfig, PriceBandGuard};
+use crate::simple_contract::{collateral_for, OptionStatus, OptionTerms, SimpleOption};
+use crate::taproot_address::nums_internal_key;
+use bitcoin::blockdata::opcodes::all as op;
+use bitcoin::script::Builder;
+use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+use bitcoin::taproot::{TaprootBuilder, TaprootSpendInfo};
+use bitcoin::{Address, OutPoint, ScriptBuf, XOnlyPublicKey};
+use oracle_vm_common::crypto::{sign_data, verify_signature, Signature};
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{CanonicalEncode, CanonicalEncoder, NetworkProfile, OtcError, SharedClock, SystemClock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::info;
+
+/// 거래 상대방
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counterparty {
+    pub name: String,
+    pub pubkey: PublicKey,
+}
+
+/// 양쪽이 서명하는 거래 조건
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtcTerms {
+    pub deal_id: String,
+    pub buyer: Counterparty,
+    pub seller: Counterparty,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub expiry_height: u32,
+    pub quantity: u64, // satoshis
+    pub premium: u64,  // satoshis, 구매자가 예치
+}
+
+impl OtcTerms {
+    /// 판매자가 예치할 담보 (풀 옵션과 같은 기준)
+    pub fn seller_collateral(&self) -> u64 {
+        collateral_for(self.option_type, self.strike_price, self.quantity)
+    }
+
+    /// 에스크로 출력에 들어가야 하는 금액 (프리미엄 + 담보)
+    pub fn escrow_amount(&self) -> u64 {
+        self.premium + self.seller_collateral()
+    }
+
+    /// 서명 대상 바이트
+    pub fn signing_payload(&self) -> Vec<u8> {
+        self.canonical_bytes()
+    }
+
+    /// 조건에 서명 (DER hex)
+    pub fn sign(&self, secret_key: &SecretKey) -> Result<String, OtcError> {
+        sign_data(&self.signing_payload(), secret_key)
+            .map(|signature| signature.to_string())
+            .map_err(|e| OtcError::InvalidTerms(e.to_string()))
+    }
+
+    fn validate(&self) -> Result<(), OtcError> {
+        let invalid = |reason: &str| Err(OtcError::InvalidTerms(format!("{}: {}", self.deal_id, reason)));
+        if self.deal_id.is_empty() {
+            return Err(OtcError::InvalidTerms("empty deal id".to_string()));
+        }
+        if self.buyer.name.trim().is_empty() || self.seller.name.trim().is_empty() {
+            return invalid("counterparties must be named");
+        }
+        if self.buyer.name == self.seller.name || self.buyer.pubkey == self.seller.pubkey {
+            return invalid("buyer and seller must be different counterparties");
+        }
+        if self.strike_price == 0 || self.quantity == 0 {
+            return invalid("strike and quantity must be positive");
+        }
+        if self.seller_collateral() == 0 {
+            return invalid("size too small to collateralize");
+        }
+        Ok(())
+    }
+
+    /// 정산 계산용 옵션 (풀 옵션과 같은 페이오프)
+    fn as_option(&self) -> SimpleOption {
+        SimpleOption {
+            option_id: self.deal_id.clone(),
+            option_type: self.option_type,
+            strike_price: self.strike_price,
+            quantity: self.quantity,
+            premium_paid: self.premium,
+            expiry_height: self.expiry_height,
+            status: OptionStatus::Active,
+            user_id: self.buyer.name.clone(),
+            terms: OptionTerms::default(),
+        }
+    }
+}
+
+impl CanonicalEncode for OtcTerms {
+    const DOMAIN: &'static str = "otc_deal/v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder
+            .str(&self.deal_id)
+            .str(&self.buyer.name)
+            .fixed(&self.buyer.pubkey.serialize())
+            .str(&self.seller.name)
+            .fixed(&self.seller.pubkey.serialize())
+            .option_type(self.option_type)
+            .u64(self.strike_price)
+            .u32(self.expiry_height)
+            .u64(self.quantity)
+            .u64(self.premium);
+    }
+}
+
+/// 거래 쪽
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtcSide {
+    Buyer,
+    Seller,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtcStatus {
+    /// 서명 대기
+    Proposed,
+    /// 양쪽 서명 완료, 예치 대기
+    Confirmed,
+    /// 에스크로 예치 완료
+    Funded,
+    Settled,
+    Cancelled,
+}
+
+impl fmt::Display for OtcStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OtcStatus::Proposed => "proposed",
+            OtcStatus::Confirmed => "confirmed",
+            OtcStatus::Funded => "funded",
+            OtcStatus::Settled => "settled",
+            OtcStatus::Cancelled => "cancelled",
+        })
+    }
+}
+
+/// 에스크로 예치 출력
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscrowFunding {
+    pub outpoint: OutPoint,
+    pub value: u64,
+}
+
+/// 정산 결과 (에스크로 금액을 양쪽에 분배)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtcSettlement {
+    pub settlement_price: u64, // USD cents
+    /// 구매자 지급액 (담보 한도 내 내재가치)
+    pub buyer_payout: u64,
+    /// 판매자 수령액 (프리미엄 + 남은 담보)
+    pub seller_payout: u64,
+    pub settled_height: u32,
+}
+
+/// OTC 거래
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtcDeal {
+    pub terms: OtcTerms,
+    pub status: OtcStatus,
+    pub buyer_signature: Option<String>,
+    pub seller_signature: Option<String>,
+    pub funding: Option<EscrowFunding>,
+    pub settlement: Option<OtcSettlement>,
+}
+
+impl OtcDeal {
+    fn invalid_state(&self, expected: &str) -> OtcError {
+        OtcError::InvalidState {
+            deal_id: self.terms.deal_id.clone(),
+            status: self.status.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    fn expect(&self, expected: OtcStatus) -> Result<(), OtcError> {
+        if self.status != expected {
+            return Err(self.invalid_state(&expected.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 거래 전용 에스크로 Taproot 출력
+pub struct EscrowOutput {
+    spend_info: TaprootSpendInfo,
+    cooperative: ScriptBuf,
+    settlement: ScriptBuf,
+}
+
+impl EscrowOutput {
+    pub fn new(terms: &OtcTerms, verifier: &PublicKey) -> Self {
+        let secp = Secp256k1::verification_only();
+        let cooperative = Builder::new()
+            .push_x_only_key(&XOnlyPublicKey::from(terms.buyer.pubkey))
+            .push_opcode(op::OP_CHECKSIGVERIFY)
+            .push_x_only_key(&XOnlyPublicKey::from(terms.seller.pubkey))
+            .push_opcode(op::OP_CHECKSIG)
+            .into_script();
+        let settlement = Builder::new()
+            .push_int(terms.expiry_height as i64)
+            .push_opcode(op::OP_CLTV)
+            .push_opcode(op::OP_DROP)
+            .push_x_only_key(&XOnlyPublicKey::from(*verifier))
+            .push_opcode(op::OP_CHECKSIG)
+            .into_script();
+        let internal = nums_internal_key();
+        let spend_info = TaprootBuilder::new()
+            .add_leaf(1, cooperative.clone())
+            .and_then(|builder| builder.add_leaf(1, settlement.clone()))
+            .expect("two leaves at depth 1")
+            .finalize(&secp, internal)
+            .expect("complete taproot tree");
+        Self {
+            spend_info,
+            cooperative,
+            settlement,
+        }
+    }
+
+    /// 양쪽이 함께 서명하는 해지 경로
+    pub fn cooperative_script(&self) -> &ScriptBuf {
+        &self.cooperative
+    }
+
+    /// 만기 후 검증자가 서명하는 정산 경로
+    pub fn settlement_script(&self) -> &ScriptBuf {
+        &self.settlement
+    }
+
+    pub fn spend_info(&self) -> &TaprootSpendInfo {
+        &self.spend_info
+    }
+
+    pub fn script_pubkey(&self) -> ScriptBuf {
+        ScriptBuf::new_p2tr_tweaked(self.spend_info.output_key())
+    }
+
+    pub fn address(&self, profile: NetworkProfile) -> Address {
+        Address::p2tr_tweaked(self.spend_info.output_key(), profile.network)
+    }
+}
+
+/// OTC 거래 데스크 (공유 풀과 분리된 장부)
+pub struct OtcDesk {
+    profile: NetworkProfile,
+    /// 정산 경로에 서명하는 BitVMX 검증자 키
+    verifier: PublicKey,
+    deals: HashMap<String, OtcDeal>,
+    price_guard: Option<PriceBandGuard>,
+    /// 가격 밴드 경보 시각용 시계
+    clock: SharedClock,
+}
+
+impl OtcDesk {
+    pub fn new(profile: NetworkProfile, verifier: PublicKey) -> Self {
+        Self {
+            profile,
+            verifier,
+            deals: HashMap::new(),
+            price_guard: None,
+            clock: SystemClock::shared(),
+        }
+    }
+
+    /// 시계 교체 (테스트)
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 풀 정산과 같은 가격 밴드 검사 사용
+    pub fn enable_price_guard(&mut self, config: PriceBandConfig) {
+        self.price_guard = Some(PriceBandGuard::new(config));
+    }
+
+    /// 합의 가격 기록 (가격 밴드 기준)
+    pub fn observe_price(&mut self, price: u64) {
+        if let Some(guard) = self.price_guard.as_mut() {
+            guard.observe(price);
+        }
+    }
+
+    pub fn deal(&self, deal_id: &str) -> Option<&OtcDeal> {
+        self.deals.get(deal_id)
+    }
+
+    fn deal_mut(&mut self, deal_id: &str) -> Result<&mut OtcDeal, OtcError> {
+        self.deals
+            .get_mut(deal_id)
+            .ok_or_else(|| OtcError::DealNotFound(deal_id.to_string()))
+    }
+
+    /// 조건 제안 (서명 대기)
+    pub fn propose(&mut self, terms: OtcTerms) -> Result<&OtcDeal, OtcError> {
+        terms.validate()?;
+        if self.deals.contains_key(&terms.deal_id) {
+            return Err(OtcError::DuplicateDeal(terms.deal_id));
+        }
+        let deal_id = terms.deal_id.clone();
+        info!(
+            "OTC deal {} proposed: {} buys from {}, {} sats escrow",
+            deal_id,
+            terms.buyer.name,
+            terms.seller.name,
+            terms.escrow_amount()
+        );
+        Ok(self.deals.entry(deal_id).or_insert(OtcDeal {
+            terms,
+            status: OtcStatus::Proposed,
+            buyer_signature: None,
+            seller_signature: None,
+            funding: None,
+            settlement: None,
+        }))
+    }
+
+    /// 한쪽의 조건 서명 등록, 양쪽이 모두 서명하면 `Confirmed`
+    pub fn confirm(&mut self, deal_id: &str, side: OtcSide, signature: &str) -> Result<OtcStatus, OtcError> {
+        let deal = self.deal_mut(deal_id)?;
+        deal.expect(OtcStatus::Proposed)?;
+        let counterparty = match side {
+            OtcSide::Buyer => &deal.terms.buyer,
+            OtcSide::Seller => &deal.terms.seller,
+        };
+        let invalid = || OtcError::InvalidSignature {
+            deal_id: deal_id.to_string(),
+            counterparty: counterparty.name.clone(),
+        };
+        let parsed = Signature::from_str(signature).map_err(|_| invalid())?;
+        if !verify_signature(&deal.terms.signing_payload(), &parsed, &counterparty.pubkey).unwrap_or(false) {
+            return Err(invalid());
+        }
+
+        match side {
+            OtcSide::Buyer => deal.buyer_signature = Some(signature.to_string()),
+            OtcSide::Seller => deal.seller_signature = Some(signature.to_string()),
+        }
+        if deal.buyer_signature.is_some() && deal.seller_signature.is_some() {
+            deal.status = OtcStatus::Confirmed;
+            info!("OTC deal {} confirmed by both counterparties", deal_id);
+        }
+        Ok(deal.status)
+    }
+
+    /// 서명된 조건의 에스크로 출력
+    pub fn escrow(&self, deal_id: &str) -> Result<EscrowOutput, OtcError> {
+        let deal = self
+            .deals
+            .get(deal_id)
+            .ok_or_else(|| OtcError::DealNotFound(deal_id.to_string()))?;
+        if matches!(deal.status, OtcStatus::Proposed | OtcStatus::Cancelled) {
+            return Err(deal.invalid_state("confirmed"));
+        }
+        Ok(EscrowOutput::new(&deal.terms, &self.verifier))
+    }
+
+    /// 양쪽이 예치할 에스크로 주소
+    pub fn escrow_address(&self, deal_id: &str) -> Result<Address, OtcError> {
+        Ok(self.escrow(deal_id)?.address(self.profile))
+    }
+
+    /// 에스크로 예치 기록 (프리미엄 + 담보와 정확히 같아야 함)
+    pub fn record_funding(&mut self, deal_id: &str, funding: EscrowFunding) -> Result<(), OtcError> {
+        let deal = self.deal_mut(deal_id)?;
+        deal.expect(OtcStatus::Confirmed)?;
+        let required = deal.terms.escrow_amount();
+        if funding.value != required {
+            return Err(OtcError::EscrowMismatch {
+                required,
+                actual: funding.value,
+            });
+        }
+        deal.funding = Some(funding);
+        deal.status = OtcStatus::Funded;
+        info!("OTC deal {} funded at {}", deal_id, funding.outpoint);
+        Ok(())
+    }
+
+    /// 예치 전 취소
+    pub fn cancel(&mut self, deal_id: &str) -> Result<(), OtcError> {
+        let deal = self.deal_mut(deal_id)?;
+        if !matches!(deal.status, OtcStatus::Proposed | OtcStatus::Confirmed) {
+            return Err(deal.invalid_state("proposed or confirmed"));
+        }
+        deal.status = OtcStatus::Cancelled;
+        info!("OTC deal {} cancelled", deal_id);
+        Ok(())
+    }
+
+    /// 만기 정산: 합의 가격으로 에스크로를 양쪽에 분배
+    ///
+    /// 가격 밴드를 벗어나면 풀 정산과 같이 `PriceOutOfBand`로 미룹니다.
+    /// 구매자 지급은 판매자 담보를 넘지 않습니다.
+    pub fn settle(&mut self, deal_id: &str, settlement_price: u64, tip: u32) -> Result<OtcSettlement, OtcError> {
+        let deal = self
+            .deals
+            .get(deal_id)
+            .ok_or_else(|| OtcError::DealNotFound(deal_id.to_string()))?;
+        deal.expect(OtcStatus::Funded)?;
+        if tip < deal.terms.expiry_height {
+            return Err(OtcError::NotExpired {
+                deal_id: deal_id.to_string(),
+                expiry_height: deal.terms.expiry_height,
+                tip,
+            });
+        }
+        let now = self.clock.now();
+        if let Some(guard) = self.price_guard.as_mut() {
+            guard.check_settlement(deal_id, settlement_price, now)?;
+        }
+
+        let terms = &deal.terms;
+        let collateral = terms.seller_collateral();
+        let buyer_payout = terms.as_option().payout_at(settlement_price).min(collateral);
+        let settlement = OtcSettlement {
+            settlement_price,
+            buyer_payout,
+            seller_payout: terms.escrow_amount() - buyer_payout,
+            settled_height: tip,
+        };
+        let deal = self.deal_mut(deal_id)?;
+        deal.settlement = Some(settlement);
+        deal.status = OtcStatus::Settled;
+        info!(
+            "OTC deal {} settled at {}: buyer {} sats, seller {} sats",
+            deal_id, settlement_price, settlement.buyer_payout, settlement.seller_payout
+        );
+        Ok(settlement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::Hash;
+    use bitcoin::Txid;
+    use oracle_vm_common::crypto::{generate_keypair, public_key_from_secret};
+    use oracle_vm_common::SettlementError;
+
+    struct Parties {
+        buyer: SecretKey,
+        seller: SecretKey,
+        verifier: PublicKey,
+    }
+
+    fn parties() -> Parties {
+        Parties {
+            buyer: generate_keypair().0,
+            seller: generate_keypair().0,
+            verifier: generate_keypair().1,
+        }
+    }
+
+    fn terms(parties: &Parties) -> OtcTerms {
+        OtcTerms {
+            deal_id: "OTC-1".to_string(),
+            buyer: Counterparty {
+                name: "Fund A".to_string(),
+                pubkey: public_key_from_secret(&parties.buyer),
+            },
+            seller: Counterparty {
+                name: "Desk B".to_string(),
+                pubkey: public_key_from_secret(&parties.seller),
+            },
+            option_type: OptionType::Call,
+            strike_price: 7_312_345, // 행사가 사다리 밖 ($73,123.45)
+            expiry_height: 900_123,
+            quantity: 25 * 100_000_000,
+            premium: 12_000_000,
+        }
+    }
+
+    fn funding(value: u64) -> EscrowFunding {
+        EscrowFunding {
+            outpoint: OutPoint::new(Txid::all_zeros(), 0),
+            value,
+        }
+    }
+
+    fn funded_desk(parties: &Parties) -> OtcDesk {
+        let mut desk = OtcDesk::new(NetworkProfile::TESTNET, parties.verifier);
+        let terms = terms(parties);
+        let escrow = terms.escrow_amount();
+        desk.propose(terms.clone()).unwrap();
+        desk.confirm("OTC-1", OtcSide::Buyer, &terms.sign(&parties.buyer).unwrap())
+            .unwrap();
+        desk.confirm("OTC-1", OtcSide::Seller, &terms.sign(&parties.seller).unwrap())
+            .unwrap();
+        desk.record_funding("OTC-1", funding(escrow)).unwrap();
+        desk
+    }
+
+    #[test]
+    fn test_deal_requires_both_signatures_before_escrow() {
+        let parties = parties();
+        let terms = terms(&parties);
+        let mut desk = OtcDesk::new(NetworkProfile::TESTNET, parties.verifier);
+        desk.propose(terms.clone()).unwrap();
+        assert!(matches!(desk.propose(terms.clone()), Err(OtcError::DuplicateDeal(_))));
+        assert!(matches!(desk.escrow_address("OTC-1"), Err(OtcError::InvalidState { .. })));
+
+        // 상대방 키로 만든 서명은 거부
+        let wrong = terms.sign(&parties.seller).unwrap();
+        assert!(matches!(
+            desk.confirm("OTC-1", OtcSide::Buyer, &wrong),
+            Err(OtcError::InvalidSignature { .. })
+        ));
+        // 다른 조건에 한 서명도 거부
+        let mut altered = terms.clone();
+        altered.premium = 1;
+        let altered = altered.sign(&parties.buyer).unwrap();
+        assert!(desk.confirm("OTC-1", OtcSide::Buyer, &altered).is_err());
+
+        let buyer = terms.sign(&parties.buyer).unwrap();
+        assert_eq!(desk.confirm("OTC-1", OtcSide::Buyer, &buyer).unwrap(), OtcStatus::Proposed);
+        let seller = terms.sign(&parties.seller).unwrap();
+        assert_eq!(desk.confirm("OTC-1", OtcSide::Seller, &seller).unwrap(), OtcStatus::Confirmed);
+
+        let address = desk.escrow_address("OTC-1").unwrap();
+        assert!(address.script_pubkey().is_p2tr());
+        assert!(address.to_string().starts_with("tb1p"));
+
+        // 프리미엄 + 담보와 다른 예치는 거부
+        let required = terms.escrow_amount();
+        assert_eq!(required, 12_000_000 + 25 * 100_000_000);
+        assert_eq!(
+            desk.record_funding("OTC-1", funding(required - 1)),
+            Err(OtcError::EscrowMismatch {
+                required,
+                actual: required - 1
+            })
+        );
+        desk.record_funding("OTC-1", funding(required)).unwrap();
+        assert_eq!(desk.deal("OTC-1").unwrap().status, OtcStatus::Funded);
+        assert!(desk.cancel("OTC-1").is_err());
+    }
+
+    #[test]
+    fn test_escrow_is_dedicated_to_the_deal() {
+        let parties = parties();
+        let terms = terms(&parties);
+        let escrow = EscrowOutput::new(&terms, &parties.verifier);
+        assert_eq!(escrow.script_pubkey(), escrow.address(NetworkProfile::TESTNET).script_pubkey());
+        for leaf in [escrow.cooperative_script(), escrow.settlement_script()] {
+            assert!(escrow
+                .spend_info()
+                .control_block(&(leaf.clone(), bitcoin::taproot::LeafVersion::TapScript))
+                .is_some());
+        }
+
+        let mut other = terms.clone();
+        other.expiry_height += 1;
+        assert_ne!(
+            EscrowOutput::new(&other, &parties.verifier).script_pubkey(),
+            escrow.script_pubkey()
+        );
+    }
+
+    #[test]
+    fn test_settlement_splits_escrow_at_oracle_price() {
+        let parties = parties();
+        let mut desk = funded_desk(&parties);
+        assert!(matches!(
+            desk.settle("OTC-1", 8_000_000, 900_000),
+            Err(OtcError::NotExpired { .. })
+        ));
+
+        // 내재가치 687,655 cents × 25 BTC (풀 옵션의 `payout_at`과 같은 계산)
+        let settlement = desk.settle("OTC-1", 8_000_000, 900_123).unwrap();
+        assert_eq!(settlement.buyer_payout, 687_655 * 25);
+        assert_eq!(
+            settlement.buyer_payout + settlement.seller_payout,
+            terms(&parties).escrow_amount()
+        );
+        assert_eq!(desk.deal("OTC-1").unwrap().status, OtcStatus::Settled);
+        assert!(matches!(
+            desk.settle("OTC-1", 8_000_000, 900_123),
+            Err(OtcError::InvalidState { .. })
+        ));
+    }
+
+    #[test]
+    fn test_settlement_uses_price_band_guard() {
+        let parties = parties();
+        let mut desk = funded_desk(&parties);
+        desk.enable_price_guard(PriceBandConfig {
+            window: 3,
+            max_deviation_bps: 500,
+        });
+        for price in [7_000_000, 7_010_000, 6_990_000] {
+            desk.observe_price(price);
+        }
+        let err = desk.settle("OTC-1", 9_000_000, 900_200).unwrap_err();
+        assert!(matches!(err, OtcError::Settlement(SettlementError::PriceOutOfBand { .. })));
+        assert_eq!(desk.deal("OTC-1").unwrap().status, OtcStatus::Funded);
+
+        // OTM: 판매자가 프리미엄과 담보 전액 회수
+        let settlement = desk.settle("OTC-1", 7_000_000, 900_200).unwrap();
+        assert_eq!(settlement.buyer_payout, 0);
+        assert_eq!(settlement.seller_payout, terms(&parties).escrow_amount());
+    }
+}
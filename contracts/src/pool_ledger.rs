@@ -0,0 +1,466 @@
+//! 풀 원장 (double-entry)
+//!
+//! 풀 회계를 계정 간 이동(posting) 묶음으로 기록합니다. 한 작업의 posting은
+//! 복사본에 먼저 적용해 잔액과 불변식(total = available + locked)을 검사한 뒤
+//! 한 번에 반영하므로, 중간에 실패해도 풀 상태가 어긋나지 않습니다.
+//! 수익처에 예치한 유휴 유동성(`Reserve`)도 풀 유동성이므로 불변식은
+//! total = available + locked + reserve 입니다. 매수자가 선납한 담보 사용료
+//! (`Funding`)는 LP 유동성으로 적립되기 전까지 풀 유동성에 들어가지 않습니다.
+
+use crate::simple_contract::SimplePoolState;
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+
+/// 원장 계정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Account {
+    /// 풀 외부 (LP, 옵션 매수자)
+    External,
+    /// 사용 가능한 유동성
+    Available,
+    /// 옵션 담보로 잠긴 유동성
+    Locked,
+    /// 수익처에 예치한 유휴 유동성
+    Reserve,
+    /// 매수자가 선납한 담보 사용료 중 LP에게 아직 적립하지 않은 몫
+    Funding,
+    /// 프로토콜 수익 (LP 유동성과 분리, 잔액은 `Treasury`가 관리)
+    Treasury,
+}
+
+impl Account {
+    /// LP 풀 유동성에 속하는 계정
+    fn in_pool(self) -> bool {
+        matches!(self, Self::Available | Self::Locked | Self::Reserve)
+    }
+}
+
+/// 이동 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostingKind {
+    Deposit,
+    Withdrawal,
+    Premium,
+    Lock,
+    Release,
+    Payout,
+    ProtocolFee,
+    SettlementFee,
+    BuyBack,
+    ReserveSweep,
+    ReserveRecall,
+    ReserveYield,
+    FundingPrepaid,
+    FundingAccrual,
+}
+
+/// 계정 간 이동 한 건
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Posting {
+    pub kind: PostingKind,
+    pub from: Account,
+    pub to: Account,
+    pub amount: u64, // satoshis
+}
+
+impl Posting {
+    /// LP 입금: 외부 → 사용 가능
+    pub fn deposit(amount: u64) -> Self {
+        Self::new(PostingKind::Deposit, Account::External, Account::Available, amount)
+    }
+
+    /// LP 출금: 사용 가능 → 외부
+    pub fn withdrawal(amount: u64) -> Self {
+        Self::new(PostingKind::Withdrawal, Account::Available, Account::External, amount)
+    }
+
+    /// 프리미엄 수취: 외부 → 사용 가능
+    pub fn premium(amount: u64) -> Self {
+        Self::new(PostingKind::Premium, Account::External, Account::Available, amount)
+    }
+
+    /// 담보 잠금: 사용 가능 → 잠김
+    pub fn lock(amount: u64) -> Self {
+        Self::new(PostingKind::Lock, Account::Available, Account::Locked, amount)
+    }
+
+    /// 담보 해제: 잠김 → 사용 가능
+    pub fn release(amount: u64) -> Self {
+        Self::new(PostingKind::Release, Account::Locked, Account::Available, amount)
+    }
+
+    /// 정산 지급: 잠김 → 외부
+    pub fn payout(amount: u64) -> Self {
+        Self::new(PostingKind::Payout, Account::Locked, Account::External, amount)
+    }
+
+    /// 프로토콜 수수료: 외부(프리미엄 중 수수료분) → 재무
+    pub fn protocol_fee(amount: u64) -> Self {
+        Self::new(PostingKind::ProtocolFee, Account::External, Account::Treasury, amount)
+    }
+
+    /// 정산 수수료: 잠김(지급액 중 수수료분) → 재무
+    pub fn settlement_fee(amount: u64) -> Self {
+        Self::new(PostingKind::SettlementFee, Account::Locked, Account::Treasury, amount)
+    }
+
+    /// 만기 전 되사기 지급: 잠김 → 외부
+    pub fn buy_back(amount: u64) -> Self {
+        Self::new(PostingKind::BuyBack, Account::Locked, Account::External, amount)
+    }
+
+    /// 유휴 유동성 수익처 예치: 사용 가능 → 예치
+    pub fn reserve_sweep(amount: u64) -> Self {
+        Self::new(PostingKind::ReserveSweep, Account::Available, Account::Reserve, amount)
+    }
+
+    /// 수익처 회수: 예치 → 사용 가능
+    pub fn reserve_recall(amount: u64) -> Self {
+        Self::new(PostingKind::ReserveRecall, Account::Reserve, Account::Available, amount)
+    }
+
+    /// 수익처 이자: 외부 → 예치 (LP 순자산 증가)
+    pub fn reserve_yield(amount: u64) -> Self {
+        Self::new(PostingKind::ReserveYield, Account::External, Account::Reserve, amount)
+    }
+
+    /// 담보 사용료 선납: 외부(프리미엄 중 사용료분) → 사용료
+    pub fn funding_prepaid(amount: u64) -> Self {
+        Self::new(PostingKind::FundingPrepaid, Account::External, Account::Funding, amount)
+    }
+
+    /// 담보 사용료 적립: 사용료 → 사용 가능 (LP 순자산 증가)
+    pub fn funding_accrual(amount: u64) -> Self {
+        Self::new(PostingKind::FundingAccrual, Account::Funding, Account::Available, amount)
+    }
+
+    fn new(kind: PostingKind, from: Account, to: Account, amount: u64) -> Self {
+        Self {
+            kind,
+            from,
+            to,
+            amount,
+        }
+    }
+}
+
+/// 한 작업 단위로 반영되는 posting 묶음
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerTransaction {
+    pub sequence: u64,
+    pub reference: String, // 옵션 ID 등 작업 식별자
+    pub postings: Vec<Posting>,
+    pub active_options_delta: i32,
+}
+
+/// 검사를 마치고 반영만 남은 거래
+#[derive(Debug)]
+pub struct PendingTransaction {
+    transaction: LedgerTransaction,
+    next_state: SimplePoolState,
+}
+
+/// 풀 원장
+#[derive(Debug, Default)]
+pub struct PoolLedger {
+    transactions: Vec<LedgerTransaction>,
+}
+
+impl PoolLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 스냅샷 등에서 읽은 거래로 원장 복원 (상태 검증은 rebuild로 수행)
+    pub fn from_transactions(transactions: Vec<LedgerTransaction>) -> Self {
+        Self { transactions }
+    }
+
+    pub fn transactions(&self) -> &[LedgerTransaction] {
+        &self.transactions
+    }
+
+    /// posting을 현재 상태의 복사본에 적용하고 불변식 검사 (상태는 바꾸지 않음)
+    pub fn prepare(
+        &self,
+        state: &SimplePoolState,
+        reference: impl Into<String>,
+        postings: Vec<Posting>,
+        active_options_delta: i32,
+    ) -> Result<PendingTransaction, ContractError> {
+        let transaction = LedgerTransaction {
+            sequence: self.transactions.len() as u64 + 1,
+            reference: reference.into(),
+            postings,
+            active_options_delta,
+        };
+        let next_state = apply(state, &transaction)?;
+        Ok(PendingTransaction {
+            transaction,
+            next_state,
+        })
+    }
+
+    /// 검사된 거래를 상태와 원장에 한 번에 반영
+    pub fn commit(&mut self, state: &mut SimplePoolState, pending: PendingTransaction) {
+        *state = pending.next_state;
+        self.transactions.push(pending.transaction);
+    }
+
+    /// 원장 전체를 처음부터 다시 적용해 풀 상태 재구성
+    pub fn rebuild(&self) -> Result<SimplePoolState, ContractError> {
+        self.transactions
+            .iter()
+            .try_fold(SimplePoolState::new(), |state, transaction| {
+                apply(&state, transaction)
+            })
+    }
+}
+
+/// 거래 하나를 적용한 새 상태 (잔액 부족/불변식 위반 시 오류)
+fn apply(
+    state: &SimplePoolState,
+    transaction: &LedgerTransaction,
+) -> Result<SimplePoolState, ContractError> {
+    let fail = |reason: &str| {
+        ContractError::Ledger(format!(
+            "{} (transaction {} for {})",
+            reason, transaction.sequence, transaction.reference
+        ))
+    };
+
+    let mut next = state.clone();
+    for posting in &transaction.postings {
+        let amount = posting.amount;
+        match posting.from {
+            Account::External => {}
+            Account::Available => {
+                next.available_liquidity = next
+                    .available_liquidity
+                    .checked_sub(amount)
+                    .ok_or_else(|| fail("available liquidity would go negative"))?;
+            }
+            Account::Locked => {
+                next.locked_collateral = next
+                    .locked_collateral
+                    .checked_sub(amount)
+                    .ok_or_else(|| fail("locked collateral would go negative"))?;
+            }
+            Account::Reserve => {
+                next.reserve_balance = next
+                    .reserve_balance
+                    .checked_sub(amount)
+                    .ok_or_else(|| fail("reserve balance would go negative"))?;
+            }
+            Account::Funding => {
+                next.unaccrued_funding = next
+                    .unaccrued_funding
+                    .checked_sub(amount)
+                    .ok_or_else(|| fail("unaccrued funding would go negative"))?;
+            }
+            // 재무 계정 출금은 풀 원장 밖에서 처리
+            Account::Treasury => return Err(fail("treasury is not debited through the pool ledger")),
+        }
+        match posting.to {
+            Account::External | Account::Treasury => {}
+            Account::Available => next.available_liquidity += amount,
+            Account::Locked => next.locked_collateral += amount,
+            Account::Reserve => next.reserve_balance += amount,
+            Account::Funding => next.unaccrued_funding += amount,
+        }
+        // 풀 경계를 넘는 이동만 총 유동성을 바꿈
+        match (posting.from.in_pool(), posting.to.in_pool()) {
+            (false, true) => {
+                next.total_liquidity = next
+                    .total_liquidity
+                    .checked_add(amount)
+                    .ok_or_else(|| fail("total liquidity overflow"))?;
+            }
+            (true, false) => {
+                next.total_liquidity = next
+                    .total_liquidity
+                    .checked_sub(amount)
+                    .ok_or_else(|| fail("total liquidity would go negative"))?;
+            }
+            _ => {}
+        }
+        match posting.kind {
+            PostingKind::Premium => next.total_premium_collected += amount,
+            PostingKind::Payout | PostingKind::BuyBack => next.total_payout += amount,
+            PostingKind::FundingAccrual => next.total_funding_accrued += amount,
+            PostingKind::Deposit
+            | PostingKind::Withdrawal
+            | PostingKind::Lock
+            | PostingKind::Release
+            | PostingKind::ProtocolFee
+            | PostingKind::SettlementFee
+            | PostingKind::ReserveSweep
+            | PostingKind::ReserveRecall
+            | PostingKind::ReserveYield
+            | PostingKind::FundingPrepaid => {}
+        }
+    }
+
+    next.active_options = next
+        .active_options
+        .checked_add_signed(transaction.active_options_delta)
+        .ok_or_else(|| fail("active option count would go negative"))?;
+
+    if next.total_liquidity != next.available_liquidity + next.locked_collateral + next.reserve_balance {
+        return Err(fail("total != available + locked + reserve"));
+    }
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_and_rebuild() {
+        let mut ledger = PoolLedger::new();
+        let mut state = SimplePoolState::new();
+
+        let deposit = ledger
+            .prepare(&state, "lp", vec![Posting::deposit(100_000_000)], 0)
+            .unwrap();
+        ledger.commit(&mut state, deposit);
+
+        let open = ledger
+            .prepare(
+                &state,
+                "CALL-1",
+                vec![Posting::lock(10_000_000), Posting::premium(250_000)],
+                1,
+            )
+            .unwrap();
+        ledger.commit(&mut state, open);
+
+        let settle = ledger
+            .prepare(
+                &state,
+                "CALL-1",
+                vec![Posting::payout(300_000), Posting::release(9_700_000)],
+                -1,
+            )
+            .unwrap();
+        ledger.commit(&mut state, settle);
+
+        assert_eq!(state.total_liquidity, 100_000_000 + 250_000 - 300_000);
+        assert_eq!(state.locked_collateral, 0);
+        assert_eq!(state.total_payout, 300_000);
+        assert_eq!(state.active_options, 0);
+
+        let rebuilt = ledger.rebuild().unwrap();
+        assert_eq!(rebuilt.total_liquidity, state.total_liquidity);
+        assert_eq!(rebuilt.available_liquidity, state.available_liquidity);
+        assert_eq!(rebuilt.total_premium_collected, state.total_premium_collected);
+    }
+
+    #[test]
+    fn test_rejected_transaction_leaves_state_untouched() {
+        let mut ledger = PoolLedger::new();
+        let mut state = SimplePoolState::new();
+        let deposit = ledger
+            .prepare(&state, "lp", vec![Posting::deposit(1_000)], 0)
+            .unwrap();
+        ledger.commit(&mut state, deposit);
+
+        // 두 번째 posting에서 잔액 부족
+        let result = ledger.prepare(
+            &state,
+            "CALL-2",
+            vec![Posting::lock(800), Posting::lock(800)],
+            1,
+        );
+        assert!(matches!(result, Err(ContractError::Ledger(_))));
+        assert_eq!(state.available_liquidity, 1_000);
+        assert_eq!(ledger.transactions().len(), 1);
+    }
+
+    #[test]
+    fn test_fees_leave_the_pool() {
+        let mut ledger = PoolLedger::new();
+        let mut state = SimplePoolState::new();
+        let deposit = ledger
+            .prepare(&state, "lp", vec![Posting::deposit(100_000_000)], 0)
+            .unwrap();
+        ledger.commit(&mut state, deposit);
+
+        // 프리미엄 250,000 중 5,000은 재무 계정으로
+        let open = ledger
+            .prepare(
+                &state,
+                "CALL-1",
+                vec![
+                    Posting::lock(10_000_000),
+                    Posting::premium(245_000),
+                    Posting::protocol_fee(5_000),
+                ],
+                1,
+            )
+            .unwrap();
+        ledger.commit(&mut state, open);
+        assert_eq!(state.total_liquidity, 100_245_000);
+
+        // 지급 300,000 중 3,000은 정산 수수료
+        let settle = ledger
+            .prepare(
+                &state,
+                "CALL-1",
+                vec![
+                    Posting::payout(297_000),
+                    Posting::settlement_fee(3_000),
+                    Posting::release(9_700_000),
+                ],
+                -1,
+            )
+            .unwrap();
+        ledger.commit(&mut state, settle);
+        assert_eq!(state.total_liquidity, 100_245_000 - 300_000);
+        assert_eq!(state.total_payout, 297_000);
+        assert_eq!(ledger.rebuild().unwrap(), state);
+    }
+
+    #[test]
+    fn test_funding_enters_pool_only_when_accrued() {
+        let mut ledger = PoolLedger::new();
+        let mut state = SimplePoolState::new();
+        let deposit = ledger
+            .prepare(&state, "lp", vec![Posting::deposit(100_000_000)], 0)
+            .unwrap();
+        ledger.commit(&mut state, deposit);
+
+        // 프리미엄 250,000 중 20,000은 담보 사용료 선납
+        let open = ledger
+            .prepare(
+                &state,
+                "CALL-1",
+                vec![
+                    Posting::lock(10_000_000),
+                    Posting::premium(230_000),
+                    Posting::funding_prepaid(20_000),
+                ],
+                1,
+            )
+            .unwrap();
+        ledger.commit(&mut state, open);
+        assert_eq!(state.total_liquidity, 100_230_000);
+        assert_eq!(state.unaccrued_funding, 20_000);
+
+        let accrue = ledger
+            .prepare(&state, "funding", vec![Posting::funding_accrual(5_000)], 0)
+            .unwrap();
+        ledger.commit(&mut state, accrue);
+        assert_eq!(state.total_liquidity, 100_235_000);
+        assert_eq!(state.unaccrued_funding, 15_000);
+        assert_eq!(state.total_funding_accrued, 5_000);
+
+        // 선납분보다 많이 적립할 수 없음
+        let result = ledger.prepare(&state, "funding", vec![Posting::funding_accrual(15_001)], 0);
+        assert!(matches!(result, Err(ContractError::Ledger(_))));
+        assert_eq!(ledger.rebuild().unwrap(), state);
+    }
+}
@@ -0,0 +1,333 @@
+//! 옵션 포지션 토큰 (Taproot Assets / RGB)
+//!
+//! 옵션이 생성되면 포지션을 외부 지갑으로 옮길 수 있는 토큰으로 발행하고,
+//! 정산/만료되면 소각합니다. 풀 이벤트(`PoolEvent`)를 받아 동작하므로 웹훅과
+//! 같은 위치에 붙일 수 있고, 실제 발행은 `PositionTokenIssuer` 구현
+//! (tapd / RGB 노드)에 맡깁니다. `position-tokens` feature로만 빌드됩니다.
+
+use crate::event_store::{PoolEvent, PoolEventKind};
+use async_trait::async_trait;
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::TokenError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 토큰 표준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStandard {
+    TaprootAsset,
+    Rgb,
+}
+
+/// 발행 요청 (옵션 생성 이벤트에서 만듦)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueRequest {
+    pub option_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub quantity: u64,     // satoshis
+    /// 최초 보유자
+    pub owner: String,
+}
+
+/// 발행된 포지션 토큰
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionToken {
+    pub option_id: String,
+    pub standard: TokenStandard,
+    /// Taproot Asset ID 또는 RGB contract ID
+    pub asset_id: String,
+    /// 마지막으로 확인한 보유자
+    pub owner: String,
+    pub issued_at: u64,
+    pub burned_at: Option<u64>,
+}
+
+impl PositionToken {
+    pub fn is_active(&self) -> bool {
+        self.burned_at.is_none()
+    }
+}
+
+/// 토큰 발행기 (tapd / RGB 노드 연동)
+#[async_trait]
+pub trait PositionTokenIssuer: Send + Sync {
+    fn standard(&self) -> TokenStandard;
+
+    /// 토큰 발행 후 asset ID 반환
+    async fn issue(&self, request: &IssueRequest) -> Result<String, TokenError>;
+
+    /// 토큰 소각
+    async fn burn(&self, asset_id: &str) -> Result<(), TokenError>;
+
+    /// 현재 보유자 (외부 지갑으로 이동했을 수 있음)
+    async fn holder(&self, asset_id: &str) -> Result<String, TokenError>;
+}
+
+/// 옵션별 포지션 토큰 관리
+#[derive(Debug, Default)]
+pub struct PositionTokenRegistry {
+    tokens: HashMap<String, PositionToken>,
+}
+
+impl PositionTokenRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn token(&self, option_id: &str) -> Option<&PositionToken> {
+        self.tokens.get(option_id)
+    }
+
+    /// 소각되지 않은 토큰
+    pub fn active(&self) -> Vec<&PositionToken> {
+        self.tokens.values().filter(|token| token.is_active()).collect()
+    }
+
+    /// 옵션 생성 시 토큰 발행
+    pub async fn issue(
+        &mut self,
+        issuer: &dyn PositionTokenIssuer,
+        request: IssueRequest,
+        now: u64,
+    ) -> Result<&PositionToken, TokenError> {
+        if self.tokens.contains_key(&request.option_id) {
+            return Err(TokenError::AlreadyIssued(request.option_id));
+        }
+        let asset_id = issuer.issue(&request).await?;
+        let token = PositionToken {
+            option_id: request.option_id.clone(),
+            standard: issuer.standard(),
+            asset_id,
+            owner: request.owner,
+            issued_at: now,
+            burned_at: None,
+        };
+        Ok(self.tokens.entry(request.option_id).or_insert(token))
+    }
+
+    /// 정산/만료 시 토큰 소각 (이미 소각됐으면 그대로)
+    pub async fn burn(
+        &mut self,
+        issuer: &dyn PositionTokenIssuer,
+        option_id: &str,
+        now: u64,
+    ) -> Result<&PositionToken, TokenError> {
+        let token = self
+            .tokens
+            .get_mut(option_id)
+            .ok_or_else(|| TokenError::NotIssued(option_id.to_string()))?;
+        if token.is_active() {
+            issuer.burn(&token.asset_id).await?;
+            token.burned_at = Some(now);
+        }
+        Ok(token)
+    }
+
+    /// 발행기에서 현재 보유자를 다시 읽음 (정산 지급 대상)
+    pub async fn refresh_owner(
+        &mut self,
+        issuer: &dyn PositionTokenIssuer,
+        option_id: &str,
+    ) -> Result<String, TokenError> {
+        let token = self
+            .tokens
+            .get_mut(option_id)
+            .ok_or_else(|| TokenError::NotIssued(option_id.to_string()))?;
+        token.owner = issuer.holder(&token.asset_id).await?;
+        Ok(token.owner.clone())
+    }
+
+    /// 풀 이벤트 처리 (생성 → 발행, 정산/만료/되사기 → 소각, 롤 → 소각 후 발행)
+    pub async fn handle_event(
+        &mut self,
+        issuer: &dyn PositionTokenIssuer,
+        event: &PoolEvent,
+    ) -> Result<(), TokenError> {
+        match &event.kind {
+            PoolEventKind::OptionCreated {
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                user_id,
+                ..
+            } => {
+                let request = IssueRequest {
+                    option_id: option_id.clone(),
+                    option_type: *option_type,
+                    strike_price: *strike_price,
+                    quantity: *quantity,
+                    owner: user_id.clone(),
+                };
+                self.issue(issuer, request, event.timestamp).await?;
+            }
+            PoolEventKind::OptionSettled { option_id, .. }
+            | PoolEventKind::OptionExpired { option_id, .. }
+            | PoolEventKind::OptionBoughtBack { option_id, .. }
+            | PoolEventKind::OptionClosedCooperatively { option_id, .. } => {
+                self.burn(issuer, option_id, event.timestamp).await?;
+            }
+            PoolEventKind::OptionRolled {
+                from_option_id,
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                user_id,
+                ..
+            } => {
+                self.burn(issuer, from_option_id, event.timestamp).await?;
+                let request = IssueRequest {
+                    option_id: option_id.clone(),
+                    option_type: *option_type,
+                    strike_price: *strike_price,
+                    quantity: *quantity,
+                    owner: user_id.clone(),
+                };
+                self.issue(issuer, request, event.timestamp).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MockAsset {
+    holder: String,
+    burned: bool,
+}
+
+/// 테스트용 인메모리 발행기
+pub struct MockIssuer {
+    standard: TokenStandard,
+    assets: Mutex<HashMap<String, MockAsset>>,
+}
+
+impl MockIssuer {
+    pub fn new(standard: TokenStandard) -> Self {
+        Self {
+            standard,
+            assets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 외부 지갑 간 이동 시뮬레이션
+    pub fn transfer(&self, asset_id: &str, to: &str) -> Result<(), TokenError> {
+        let mut assets = self.assets.lock().unwrap();
+        match assets.get_mut(asset_id) {
+            Some(asset) if !asset.burned => {
+                asset.holder = to.to_string();
+                Ok(())
+            }
+            _ => Err(TokenError::NotIssued(asset_id.to_string())),
+        }
+    }
+
+    pub fn is_burned(&self, asset_id: &str) -> bool {
+        self.assets
+            .lock()
+            .unwrap()
+            .get(asset_id)
+            .is_some_and(|asset| asset.burned)
+    }
+}
+
+#[async_trait]
+impl PositionTokenIssuer for MockIssuer {
+    fn standard(&self) -> TokenStandard {
+        self.standard
+    }
+
+    async fn issue(&self, request: &IssueRequest) -> Result<String, TokenError> {
+        let asset_id = hex::encode(Sha256::digest(request.option_id.as_bytes()));
+        self.assets.lock().unwrap().insert(
+            asset_id.clone(),
+            MockAsset {
+                holder: request.owner.clone(),
+                burned: false,
+            },
+        );
+        Ok(asset_id)
+    }
+
+    async fn burn(&self, asset_id: &str) -> Result<(), TokenError> {
+        let mut assets = self.assets.lock().unwrap();
+        let asset = assets
+            .get_mut(asset_id)
+            .ok_or_else(|| TokenError::NotIssued(asset_id.to_string()))?;
+        asset.burned = true;
+        Ok(())
+    }
+
+    async fn holder(&self, asset_id: &str) -> Result<String, TokenError> {
+        self.assets
+            .lock()
+            .unwrap()
+            .get(asset_id)
+            .map(|asset| asset.holder.clone())
+            .ok_or_else(|| TokenError::NotIssued(asset_id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(timestamp: u64, kind: PoolEventKind) -> PoolEvent {
+        PoolEvent {
+            sequence: timestamp,
+            timestamp,
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_lifecycle_follows_pool_events() {
+        let issuer = MockIssuer::new(TokenStandard::TaprootAsset);
+        let mut registry = PositionTokenRegistry::new();
+
+        let created = event(
+            1_000,
+            PoolEventKind::OptionCreated {
+                option_id: "CALL-1".to_string(),
+                option_type: OptionType::Call,
+                strike_price: 7_000_000,
+                quantity: 10_000_000,
+                premium: 250_000,
+                collateral: 10_000_000,
+                user_id: "alice".to_string(),
+            },
+        );
+        registry.handle_event(&issuer, &created).await.unwrap();
+        assert_eq!(
+            registry.handle_event(&issuer, &created).await,
+            Err(TokenError::AlreadyIssued("CALL-1".to_string()))
+        );
+
+        // 외부 지갑으로 이동한 뒤 보유자 갱신
+        let asset_id = registry.token("CALL-1").unwrap().asset_id.clone();
+        issuer.transfer(&asset_id, "bob").unwrap();
+        assert_eq!(registry.refresh_owner(&issuer, "CALL-1").await.unwrap(), "bob");
+        assert_eq!(registry.active().len(), 1);
+
+        let settled = event(
+            2_000,
+            PoolEventKind::OptionSettled {
+                option_id: "CALL-1".to_string(),
+                spot_price: 7_500_000,
+                payout: 666_666,
+                dust: 0,
+            },
+        );
+        registry.handle_event(&issuer, &settled).await.unwrap();
+        assert!(issuer.is_burned(&asset_id));
+        assert_eq!(registry.token("CALL-1").unwrap().burned_at, Some(2_000));
+        assert!(registry.active().is_empty());
+        assert!(issuer.transfer(&asset_id, "carol").is_err());
+    }
+}
@@ -0,0 +1,359 @@
+//! 일일 가격 커밋먼트 (오라클 투명성 증명)
+//!
+//! 분 단위 합의 가격을 모아 하루(UTC)가 끝나면 Merkle root를 계산해
+//! OP_RETURN으로 앵커링합니다. `prove_price`는 특정 시각의 가격과 Merkle
+//! 경로를 돌려주므로, 누구나 정산에 쓰인 가격을 온체인 커밋먼트와 대조할 수
+//! 있습니다.
+
+use crate::anchor_tracker::AnchorBroadcaster;
+use chrono::{DateTime, NaiveDate};
+use oracle_vm_common::crypto::{sha256, MerkleTree};
+use oracle_vm_common::{AnchorError, OracleError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, instrument, warn};
+
+/// 일일 가격 커밋먼트 OP_RETURN 태그
+pub const PRICE_ANCHOR_TAG: &[u8; 3] = b"PRC";
+
+const SECS_PER_MINUTE: u64 = 60;
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Merkle leaf: "price|분 시작 timestamp|가격(cents)"
+pub fn price_leaf(minute: u64, price: u64) -> [u8; 32] {
+    sha256(format!("price|{}|{}", minute, price).as_bytes())
+}
+
+fn day_start(date: NaiveDate) -> u64 {
+    date.and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc().timestamp().max(0) as u64)
+        .unwrap_or(0)
+}
+
+fn utc_date(timestamp: u64) -> NaiveDate {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default()
+}
+
+/// OP_RETURN payload: 태그(3) + YYYYMMDD(4, BE) + 가격 수(4, BE) + root(32) = 43 bytes
+pub fn commitment_payload(date: NaiveDate, prices: u32, merkle_root: &[u8; 32]) -> Vec<u8> {
+    let date = date.format("%Y%m%d").to_string().parse::<u32>().unwrap_or(0);
+    let mut payload = Vec::with_capacity(43);
+    payload.extend_from_slice(PRICE_ANCHOR_TAG);
+    payload.extend_from_slice(&date.to_be_bytes());
+    payload.extend_from_slice(&prices.to_be_bytes());
+    payload.extend_from_slice(merkle_root);
+    payload
+}
+
+/// 하루치 가격 커밋먼트
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceCommitment {
+    pub date: NaiveDate,
+    pub prices: u32,
+    pub merkle_root: String, // hex
+    /// 앵커 트랜잭션 (앵커링 전이면 None)
+    pub txid: Option<String>,
+}
+
+impl PriceCommitment {
+    pub fn payload(&self) -> Vec<u8> {
+        let mut root = [0u8; 32];
+        if let Ok(bytes) = hex::decode(&self.merkle_root) {
+            if bytes.len() == 32 {
+                root.copy_from_slice(&bytes);
+            }
+        }
+        commitment_payload(self.date, self.prices, &root)
+    }
+}
+
+/// 특정 분의 가격 포함 증명
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceProof {
+    /// 분 시작 timestamp
+    pub minute: u64,
+    pub price: u64, // cents
+    pub date: NaiveDate,
+    pub leaf_index: usize,
+    pub leaf_count: usize,
+    pub path: Vec<String>, // hex, leaf 쪽부터
+    pub merkle_root: String,
+    pub txid: Option<String>,
+}
+
+impl PriceProof {
+    /// Merkle 경로로 root 재계산
+    pub fn verify(&self) -> bool {
+        let (Ok(root), Ok(path)) = (
+            decode_hash(&self.merkle_root),
+            self.path.iter().map(|h| decode_hash(h)).collect::<Result<Vec<_>, _>>(),
+        ) else {
+            return false;
+        };
+        MerkleTree::verify_proof(
+            price_leaf(self.minute, self.price),
+            self.leaf_index,
+            self.leaf_count,
+            &path,
+            root,
+        )
+    }
+
+    /// 증명이 온체인 OP_RETURN payload와 같은 커밋먼트를 가리키는지
+    pub fn verify_against_anchor(&self, payload: &[u8]) -> bool {
+        let Ok(root) = decode_hash(&self.merkle_root) else {
+            return false;
+        };
+        self.verify() && payload == commitment_payload(self.date, self.leaf_count as u32, &root)
+    }
+}
+
+fn decode_hash(value: &str) -> Result<[u8; 32], ()> {
+    hex::decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(())
+}
+
+/// 분 단위 합의 가격과 일일 커밋먼트
+#[derive(Debug, Default)]
+pub struct PriceCommitmentLog {
+    /// 분 시작 timestamp → 가격 (cents)
+    prices: BTreeMap<u64, u64>,
+    commitments: BTreeMap<NaiveDate, PriceCommitment>,
+}
+
+impl PriceCommitmentLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 합의 가격 기록 (같은 분이면 마지막 가격), 이미 봉인된 날이면 false
+    pub fn record(&mut self, timestamp: u64, price: u64) -> bool {
+        if self.commitments.contains_key(&utc_date(timestamp)) {
+            return false;
+        }
+        self.prices.insert(timestamp - timestamp % SECS_PER_MINUTE, price);
+        true
+    }
+
+    fn day_prices(&self, date: NaiveDate) -> impl Iterator<Item = (&u64, &u64)> {
+        let start = day_start(date);
+        self.prices.range(start..start + SECS_PER_DAY)
+    }
+
+    /// 하루를 봉인하고 커밋먼트 계산 (이미 봉인됐으면 기존 값, 가격이 없으면 None)
+    pub fn seal_day(&mut self, date: NaiveDate) -> Option<&PriceCommitment> {
+        if !self.commitments.contains_key(&date) {
+            let leaves: Vec<[u8; 32]> = self
+                .day_prices(date)
+                .map(|(minute, price)| price_leaf(*minute, *price))
+                .collect();
+            if leaves.is_empty() {
+                return None;
+            }
+            let commitment = PriceCommitment {
+                date,
+                prices: leaves.len() as u32,
+                merkle_root: hex::encode(MerkleTree::new(leaves).root()),
+                txid: None,
+            };
+            self.commitments.insert(date, commitment);
+        }
+        self.commitments.get(&date)
+    }
+
+    /// `now` 기준으로 끝난 날을 모두 봉인
+    pub fn seal_completed_days(&mut self, now: u64) -> Vec<NaiveDate> {
+        let today = utc_date(now);
+        let mut days: Vec<NaiveDate> = self
+            .prices
+            .keys()
+            .map(|minute| utc_date(*minute))
+            .filter(|date| *date < today && !self.commitments.contains_key(date))
+            .collect();
+        days.dedup();
+        for date in &days {
+            self.seal_day(*date);
+        }
+        days
+    }
+
+    /// 봉인됐지만 아직 앵커링되지 않은 커밋먼트를 앵커링
+    #[instrument(level = "info", skip_all)]
+    pub fn anchor_pending(
+        &mut self,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Vec<(NaiveDate, Result<String, AnchorError>)> {
+        let mut results = Vec::new();
+        for commitment in self.commitments.values_mut().filter(|c| c.txid.is_none()) {
+            let result = broadcaster.anchor(&commitment.payload());
+            match &result {
+                Ok(txid) => {
+                    info!("⚓ Anchored price commitment for {}: {}", commitment.date, txid);
+                    commitment.txid = Some(txid.clone());
+                }
+                Err(e) => warn!("❌ Price commitment anchoring for {} failed: {}", commitment.date, e),
+            }
+            results.push((commitment.date, result));
+        }
+        results
+    }
+
+    pub fn commitments(&self) -> impl Iterator<Item = &PriceCommitment> {
+        self.commitments.values()
+    }
+
+    /// `timestamp`가 속한 분의 가격과 Merkle 경로 (봉인된 날만)
+    pub fn prove_price(&self, timestamp: u64) -> Result<PriceProof, OracleError> {
+        let date = utc_date(timestamp);
+        let minute = timestamp - timestamp % SECS_PER_MINUTE;
+        let not_found = || OracleError::CommitmentNotFound(format!("timestamp {}", timestamp));
+        let commitment = self.commitments.get(&date).ok_or_else(not_found)?;
+
+        let minutes: Vec<(u64, u64)> = self.day_prices(date).map(|(m, p)| (*m, *p)).collect();
+        let leaf_index = minutes
+            .iter()
+            .position(|(m, _)| *m == minute)
+            .ok_or_else(not_found)?;
+        let leaves: Vec<[u8; 32]> = minutes.iter().map(|(m, p)| price_leaf(*m, *p)).collect();
+        let path = MerkleTree::new(leaves).proof(leaf_index).ok_or_else(not_found)?;
+
+        Ok(PriceProof {
+            minute,
+            price: minutes[leaf_index].1,
+            date,
+            leaf_index,
+            leaf_count: minutes.len(),
+            path: path.iter().map(hex::encode).collect(),
+            merkle_root: commitment.merkle_root.clone(),
+            txid: commitment.txid.clone(),
+        })
+    }
+}
+
+/// 가격 증명 HTTP API
+pub mod api {
+    use super::PriceCommitmentLog;
+    use crate::admin_api::{error_response, AdminError};
+    use axum::{
+        extract::{Query, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    };
+    use serde::Deserialize;
+    use std::sync::{Arc, RwLock};
+    use utoipa::IntoParams;
+
+    pub type SharedCommitments = Arc<RwLock<PriceCommitmentLog>>;
+
+    #[derive(Debug, Deserialize, IntoParams)]
+    pub struct ProofQuery {
+        pub timestamp: u64,
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/prices/proof",
+        tag = "prices",
+        params(ProofQuery),
+        responses(
+            (status = 200, body = Object),
+            (status = 404, body = AdminError)
+        )
+    )]
+    async fn prove_price(
+        Query(query): Query<ProofQuery>,
+        State(log): State<SharedCommitments>,
+    ) -> Response {
+        let Ok(log) = log.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match log.prove_price(query.timestamp) {
+            Ok(proof) => Json(proof).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/prices/commitments",
+        tag = "prices",
+        responses((status = 200, body = [Object]))
+    )]
+    async fn list_commitments(State(log): State<SharedCommitments>) -> Response {
+        let Ok(log) = log.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        Json(log.commitments().cloned().collect::<Vec<_>>()).into_response()
+    }
+
+    /// `/prices` 라우터 생성
+    pub fn router(log: SharedCommitments) -> Router {
+        Router::new()
+            .route("/prices/proof", get(prove_price))
+            .route("/prices/commitments", get(list_commitments))
+            .with_state(log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const DAY: u64 = 1_700_006_400; // 2023-11-15 00:00:00 UTC
+
+    struct RecordingBroadcaster(RefCell<Vec<Vec<u8>>>);
+
+    impl AnchorBroadcaster for RecordingBroadcaster {
+        fn rebroadcast(&self, _raw_tx: &[u8]) -> Result<String, AnchorError> {
+            unreachable!()
+        }
+
+        fn anchor(&self, payload: &[u8]) -> Result<String, AnchorError> {
+            self.0.borrow_mut().push(payload.to_vec());
+            Ok(format!("tx{}", self.0.borrow().len()))
+        }
+    }
+
+    #[test]
+    fn test_daily_commitment_and_proof() {
+        let mut log = PriceCommitmentLog::new();
+        for i in 0..5 {
+            log.record(DAY + i * 60 + 15, 6_500_000 + i);
+        }
+        log.record(DAY + 30, 6_499_999); // 같은 분: 마지막 가격
+        log.record(DAY + SECS_PER_DAY + 10, 6_600_000); // 다음 날
+
+        assert!(log.prove_price(DAY + 60).is_err());
+        assert_eq!(log.seal_completed_days(DAY + SECS_PER_DAY + 100), vec![utc_date(DAY)]);
+        assert!(!log.record(DAY + 200, 1));
+
+        let broadcaster = RecordingBroadcaster(RefCell::new(Vec::new()));
+        let results = log.anchor_pending(&broadcaster);
+        assert_eq!(results.len(), 1);
+        assert!(log.anchor_pending(&broadcaster).is_empty());
+
+        let proof = log.prove_price(DAY + 59).unwrap();
+        assert_eq!((proof.minute, proof.price), (DAY, 6_499_999));
+        assert_eq!(proof.leaf_count, 5);
+        assert_eq!(proof.txid.as_deref(), Some("tx1"));
+        assert!(proof.verify());
+        assert!(proof.verify_against_anchor(&broadcaster.0.borrow()[0]));
+
+        let proof = log.prove_price(DAY + 4 * 60).unwrap();
+        assert!(proof.verify());
+        let mut forged = proof.clone();
+        forged.price += 1;
+        assert!(!forged.verify());
+
+        // 기록이 없는 분, 봉인 전인 날
+        assert!(log.prove_price(DAY + 3_600).is_err());
+        assert!(log.prove_price(DAY + SECS_PER_DAY + 10).is_err());
+    }
+}
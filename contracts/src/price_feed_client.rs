@@ -1,20 +1,33 @@
 use anyhow::Result;
+use oracle_vm_common::price::{cents_from_dollars, Rounding};
+use oracle_vm_common::price_feed::{FeedStatus, PriceFeed};
+use oracle_vm_common::shutdown::ShutdownSignal;
+use oracle_vm_common::SystemEvent;
+use std::sync::Arc;
 use tonic::transport::Channel;
 use tonic::Request;
-use tracing::{info, error};
+use tracing::{info, error, warn};
 
-// gRPC 클라이언트 코드
-pub mod oracle {
-    tonic::include_proto!("oracle");
-}
+// gRPC 클라이언트 코드 (oracle-vm-proto)
+pub use oracle_vm_proto::oracle;
 
 use oracle::{
     oracle_service_client::OracleServiceClient,
-    GetPriceRequest,
+    GetPriceRequest, GetVolSurfaceRequest, GetVolSurfaceResponse, TradingStatusRequest,
 };
 
+use crate::admin_api::SharedManager;
 use crate::buyer_only_option::AggregatedPrice;
 
+/// gRPC 가격 필드를 USD 센트로 (센트 필드 우선, 구버전 Aggregator는 double을 half-even 반올림)
+fn wire_cents(cents: u64, dollars: f64) -> u64 {
+    if cents > 0 {
+        cents
+    } else {
+        cents_from_dollars(dollars, Rounding::HalfEven).unwrap_or(0)
+    }
+}
+
 /// Aggregator에서 가격을 가져오는 클라이언트
 pub struct PriceFeedClient {
     client: OracleServiceClient<Channel>,
@@ -53,7 +66,7 @@ impl PriceFeedClient {
         let mut kraken_price = 0u64;
         
         for data_point in &price_response.recent_prices {
-            let price_cents = (data_point.price * 100.0) as u64;
+            let price_cents = wire_cents(data_point.price_cents, data_point.price);
             match data_point.source.as_str() {
                 "binance" => binance_price = price_cents,
                 "coinbase" => coinbase_price = price_cents,
@@ -62,8 +75,11 @@ impl PriceFeedClient {
             }
         }
         
-        // 평균 가격 계산
-        let average_price = (price_response.aggregated_price * 100.0) as u64;
+        // 집계 가격 (정산 기준값, USD 센트)
+        let average_price = wire_cents(
+            price_response.aggregated_price_cents,
+            price_response.aggregated_price,
+        );
         
         Ok(AggregatedPrice {
             binance_price,
@@ -73,23 +89,115 @@ impl PriceFeedClient {
             timestamp: price_response.last_update,
         })
     }
+
+    /// Aggregator에서 `source`의 최신 옵션 IV 곡면 가져오기
+    pub async fn get_vol_surface(&mut self, source: &str) -> Result<GetVolSurfaceResponse> {
+        let request = Request::new(GetVolSurfaceRequest {
+            source: Some(source.to_string()),
+        });
+
+        let response = self.client.get_vol_surface(request).await?;
+        let surface = response.into_inner();
+
+        if !surface.success {
+            anyhow::bail!("No IV surface available");
+        }
+
+        Ok(surface)
+    }
+
+    /// Aggregator의 거래 중단 상태를 이벤트로 변환
+    ///
+    /// SimpleContractManager::apply_system_event에 그대로 전달할 수 있습니다.
+    pub async fn get_trading_status(&mut self) -> Result<SystemEvent> {
+        let response = self
+            .client
+            .get_trading_status(Request::new(TradingStatusRequest {}))
+            .await?
+            .into_inner();
+
+        if response.halted {
+            Ok(SystemEvent::TradingHalted {
+                reason: response.reason,
+                timestamp: response.halted_at,
+                resume_at: response.resume_at,
+            })
+        } else {
+            Ok(SystemEvent::TradingResumed {
+                timestamp: chrono::Utc::now().timestamp() as u64,
+                manual: false,
+            })
+        }
+    }
 }
 
-/// 정기적으로 가격을 업데이트하는 서비스
+/// 캐시된 가격을 stale로 보는 기본 나이 (초)
+pub const DEFAULT_MAX_AGE_SECS: u64 = 120;
+
+/// Aggregator 가격 폴링 서비스
+///
+/// 프로세스마다 하나가 Aggregator 연결을 소유하고 최신 가격을 공유
+/// `PriceFeed`에 게시합니다. 소비자는 `feed()`로 캐시를 읽거나 구독하며,
+/// 연결이 끊기면 다음 폴링에서 다시 맺습니다.
 pub struct PriceFeedService {
-    client: PriceFeedClient,
+    aggregator_url: String,
+    client: Option<PriceFeedClient>,
     update_interval: std::time::Duration,
+    feed: Arc<PriceFeed<AggregatedPrice>>,
 }
 
 impl PriceFeedService {
+    /// 즉시 연결 (연결 실패 시 오류)
     pub async fn new(aggregator_url: &str, update_interval_secs: u64) -> Result<Self> {
         let client = PriceFeedClient::new(aggregator_url).await?;
-        let update_interval = std::time::Duration::from_secs(update_interval_secs);
-        
-        Ok(Self {
-            client,
-            update_interval,
-        })
+        let mut service = Self::lazy(aggregator_url, update_interval_secs);
+        service.client = Some(client);
+        Ok(service)
+    }
+
+    /// 첫 폴링 때 연결 (Aggregator보다 먼저 시작해도 됨)
+    pub fn lazy(aggregator_url: &str, update_interval_secs: u64) -> Self {
+        Self {
+            aggregator_url: aggregator_url.to_string(),
+            client: None,
+            update_interval: std::time::Duration::from_secs(update_interval_secs),
+            feed: Arc::new(PriceFeed::new(DEFAULT_MAX_AGE_SECS)),
+        }
+    }
+
+    pub fn with_max_age(mut self, max_age_secs: u64) -> Self {
+        self.feed = Arc::new(PriceFeed::new(max_age_secs));
+        self
+    }
+
+    /// 캐시와 구독 (폴링 루프가 옮겨가도 같은 피드를 가리킴)
+    pub fn feed(&self) -> Arc<PriceFeed<AggregatedPrice>> {
+        self.feed.clone()
+    }
+
+    async fn fetch(&mut self) -> Result<AggregatedPrice> {
+        if self.client.is_none() {
+            self.client = Some(PriceFeedClient::new(&self.aggregator_url).await?);
+        }
+        let client = self.client.as_mut().expect("connected above");
+        client.get_aggregated_price().await
+    }
+
+    /// 한 번 폴링해 캐시에 반영 (실패하면 연결을 버리고 다음 폴링에서 재연결)
+    pub async fn poll_once(&mut self) -> Result<AggregatedPrice> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        match self.fetch().await {
+            Ok(price) => {
+                let observed_at = if price.timestamp > 0 { price.timestamp } else { now };
+                self.feed.publish(price.clone(), observed_at, now);
+                Ok(price)
+            }
+            Err(e) => {
+                self.client = None;
+                self.feed.record_failure(e.to_string(), now);
+                Err(e)
+            }
+        }
     }
     
     /// 가격 피드 서비스 실행
@@ -102,7 +210,7 @@ impl PriceFeedService {
         loop {
             interval.tick().await;
             
-            match self.client.get_aggregated_price().await {
+            match self.poll_once().await {
                 Ok(price) => {
                     info!(
                         "Received aggregated price: ${:.2} (Binance: ${:.2}, Coinbase: ${:.2}, Kraken: ${:.2})",
@@ -119,6 +227,105 @@ impl PriceFeedService {
             }
         }
     }
+
+    /// 종료 신호까지 캐시만 갱신 (소비자는 `feed()`로 읽거나 구독)
+    pub async fn run_until(mut self, mut shutdown: ShutdownSignal) {
+        let mut interval = tokio::time::interval(self.update_interval);
+        let mut was_stale = false;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => return,
+            }
+
+            let result = self.poll_once().await;
+            let stale = self.feed.status(chrono::Utc::now().timestamp() as u64) == FeedStatus::Stale;
+            match (stale, was_stale) {
+                (true, false) => match result {
+                    Err(e) => warn!("Price feed from {} is stale: {}", self.aggregator_url, e),
+                    Ok(_) => warn!("Price feed from {} is stale: aggregator price not updating", self.aggregator_url),
+                },
+                (false, true) => info!("Price feed from {} recovered", self.aggregator_url),
+                _ => {}
+            }
+            was_stale = stale;
+        }
+    }
+}
+
+/// Aggregator kill-switch를 풀에 반영하는 폴링 작업
+///
+/// 이상 감지로 Aggregator가 거래를 멈추면 모든 풀에 `TradingHalted`를 반영해 옵션
+/// 생성을 막고 정산을 미루며, 중단이 풀리면 `TradingResumed`를 반영합니다. 상태가
+/// 바뀔 때만 반영하므로 운영자가 `/admin/trading/pause`로 직접 멈춘 풀을 폴링이
+/// 풀어 버리지 않습니다.
+pub struct TradingStatusSync {
+    aggregator_url: String,
+    client: Option<PriceFeedClient>,
+    managers: Vec<SharedManager>,
+    /// 마지막으로 반영한 Aggregator 중단 시각 (중단 중이 아니면 None)
+    halted_at: Option<u64>,
+}
+
+impl TradingStatusSync {
+    /// 첫 폴링 때 연결 (Aggregator보다 먼저 시작해도 됨)
+    pub fn new(aggregator_url: &str, managers: Vec<SharedManager>) -> Self {
+        Self {
+            aggregator_url: aggregator_url.to_string(),
+            client: None,
+            managers,
+            halted_at: None,
+        }
+    }
+
+    /// 한 번 폴링해 상태가 바뀌었으면 모든 풀에 반영 (반영한 이벤트 반환)
+    pub async fn poll_once(&mut self) -> Result<Option<SystemEvent>> {
+        if self.client.is_none() {
+            self.client = Some(PriceFeedClient::new(&self.aggregator_url).await?);
+        }
+        let client = self.client.as_mut().expect("connected above");
+        let event = match client.get_trading_status().await {
+            Ok(event) => event,
+            Err(e) => {
+                self.client = None;
+                return Err(e);
+            }
+        };
+
+        let changed = match &event {
+            SystemEvent::TradingHalted { timestamp, .. } => self.halted_at.replace(*timestamp) != Some(*timestamp),
+            SystemEvent::TradingResumed { .. } => self.halted_at.take().is_some(),
+        };
+        if !changed {
+            return Ok(None);
+        }
+        for manager in &self.managers {
+            manager
+                .write()
+                .map_err(|e| anyhow::anyhow!("Pool state lock poisoned: {}", e))?
+                .apply_system_event(&event);
+        }
+        match &event {
+            SystemEvent::TradingHalted { reason, .. } => warn!("Trading halted by {}: {}", self.aggregator_url, reason),
+            SystemEvent::TradingResumed { .. } => info!("Trading resumed by {}", self.aggregator_url),
+        }
+        Ok(Some(event))
+    }
+
+    /// 종료 신호까지 `interval`마다 폴링
+    pub async fn run_until(mut self, interval: std::time::Duration, mut shutdown: ShutdownSignal) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.recv() => return,
+            }
+            if let Err(e) = self.poll_once().await {
+                warn!("Trading status from {} unavailable: {}", self.aggregator_url, e);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -139,4 +346,11 @@ mod tests {
         assert_eq!(price.average_price, 7000000);
         assert_eq!(price.binance_price, 7000000);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_wire_cents_prefers_integer_field() {
+        assert_eq!(wire_cents(7_000_012, 70000.0), 7_000_012);
+        // 구버전 Aggregator: 70000.12 * 100 = 7000011.999... 이지만 반올림으로 정확히 복원
+        assert_eq!(wire_cents(0, 70000.12), 7_000_012);
+        assert_eq!(wire_cents(0, -1.0), 0);
+    }
+}
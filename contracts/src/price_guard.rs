@@ -0,0 +1,201 @@
+//! 정산 가격 변화율 가드
+//!
+//! 정산에 쓰는 가격이 직전 N개 합의 가격의 중앙값에서 설정한 밴드 이상
+//! 벗어나면 정산을 미루고 경보를 남깁니다. 오라클이 한 틱만 튀어도 풀 전체가
+//! 지급되는 일을 막기 위한 것으로, 가격이 밴드 안으로 돌아오면 같은 정산을
+//! 다시 시도하면 됩니다.
+
+use oracle_vm_common::SettlementError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 기본 비교 구간 (합의 가격 5개 = 5분)
+pub const DEFAULT_BAND_WINDOW: usize = 5;
+/// 기본 허용 편차 (5%)
+pub const DEFAULT_MAX_DEVIATION_BPS: u64 = 500;
+
+/// 가드 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceBandConfig {
+    /// 비교할 직전 합의 가격 수
+    pub window: usize,
+    /// 중앙값 대비 허용 편차 (bps)
+    pub max_deviation_bps: u64,
+}
+
+impl Default for PriceBandConfig {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_BAND_WINDOW,
+            max_deviation_bps: DEFAULT_MAX_DEVIATION_BPS,
+        }
+    }
+}
+
+/// 밴드를 벗어나 정산을 미룬 기록
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceBandAlert {
+    pub option_id: String,
+    pub price: u64,     // USD cents
+    pub reference: u64, // USD cents
+    pub deviation_bps: u64,
+    pub timestamp: u64,
+}
+
+/// 직전 합의 가격을 보관하고 정산 가격을 검사
+#[derive(Debug, Clone)]
+pub struct PriceBandGuard {
+    config: PriceBandConfig,
+    observations: VecDeque<u64>,
+    alerts: Vec<PriceBandAlert>,
+}
+
+impl PriceBandGuard {
+    pub fn new(config: PriceBandConfig) -> Self {
+        Self {
+            config,
+            observations: VecDeque::with_capacity(config.window),
+            alerts: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> PriceBandConfig {
+        self.config
+    }
+
+    /// 합의 가격 기록 (USD cents, 가장 오래된 값부터 밀려남)
+    pub fn observe(&mut self, price: u64) {
+        if self.config.window == 0 || price == 0 {
+            return;
+        }
+        if self.observations.len() == self.config.window {
+            self.observations.pop_front();
+        }
+        self.observations.push_back(price);
+    }
+
+    /// 비교 기준 가격 (직전 합의 가격의 중앙값, 기록이 없으면 None)
+    ///
+    /// 평균 대신 중앙값을 써서 구간 안의 튄 값 하나가 기준을 끌고 가지 않게 합니다.
+    pub fn reference(&self) -> Option<u64> {
+        if self.observations.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.observations.iter().copied().collect();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        Some(if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        })
+    }
+
+    /// 정산 가격 검사 (기록이 없으면 비교 대상이 없으므로 통과)
+    pub fn check(&self, price: u64) -> Result<(), SettlementError> {
+        let Some(reference) = self.reference() else {
+            return Ok(());
+        };
+        let deviation_bps = deviation_bps(price, reference);
+        if deviation_bps > self.config.max_deviation_bps {
+            return Err(SettlementError::PriceOutOfBand {
+                price,
+                reference,
+                deviation_bps,
+            });
+        }
+        Ok(())
+    }
+
+    /// 정산 직전 검사, 벗어나면 경보를 남기고 오류 반환
+    pub fn check_settlement(
+        &mut self,
+        option_id: &str,
+        price: u64,
+        now: u64,
+    ) -> Result<(), SettlementError> {
+        let result = self.check(price);
+        if let Err(SettlementError::PriceOutOfBand {
+            reference,
+            deviation_bps,
+            ..
+        }) = result
+        {
+            self.alerts.push(PriceBandAlert {
+                option_id: option_id.to_string(),
+                price,
+                reference,
+                deviation_bps,
+                timestamp: now,
+            });
+        }
+        result
+    }
+
+    /// 지금까지 남긴 경보 (오래된 순)
+    pub fn alerts(&self) -> &[PriceBandAlert] {
+        &self.alerts
+    }
+}
+
+/// 기준 대비 편차 (bps, 올림)
+fn deviation_bps(price: u64, reference: u64) -> u64 {
+    let diff = price.abs_diff(reference) as u128 * 10_000;
+    diff.div_ceil(reference as u128).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> PriceBandGuard {
+        PriceBandGuard::new(PriceBandConfig {
+            window: 5,
+            max_deviation_bps: 300,
+        })
+    }
+
+    #[test]
+    fn test_band_uses_median_of_recent_observations() {
+        let mut guard = guard();
+        assert!(guard.check(1).is_ok());
+
+        for price in [7_000_000, 7_010_000, 6_990_000, 7_005_000, 6_995_000] {
+            guard.observe(price);
+        }
+        assert_eq!(guard.reference(), Some(7_000_000));
+        assert!(guard.check(7_200_000).is_ok());
+        assert!(guard.check(6_800_000).is_ok());
+
+        // 한 틱 튄 값은 중앙값을 거의 움직이지 않음
+        guard.observe(14_000_000);
+        assert_eq!(guard.reference(), Some(7_005_000));
+        assert!(matches!(
+            guard.check(14_000_000),
+            Err(SettlementError::PriceOutOfBand { reference: 7_005_000, .. })
+        ));
+    }
+
+    #[test]
+    fn test_out_of_band_settlement_records_alert() {
+        let mut guard = guard();
+        for _ in 0..5 {
+            guard.observe(7_000_000);
+        }
+
+        let err = guard.check_settlement("OPT-1", 7_500_000, 100).unwrap_err();
+        assert_eq!(
+            err,
+            SettlementError::PriceOutOfBand {
+                price: 7_500_000,
+                reference: 7_000_000,
+                deviation_bps: 715,
+            }
+        );
+        assert!(oracle_vm_common::ErrorClass::is_retryable(&err));
+        assert!(guard.check_settlement("OPT-1", 7_100_000, 160).is_ok());
+        assert_eq!(guard.alerts().len(), 1);
+        assert_eq!(guard.alerts()[0].option_id, "OPT-1");
+        assert_eq!(guard.alerts()[0].timestamp, 100);
+    }
+}
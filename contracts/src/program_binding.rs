@@ -0,0 +1,83 @@
+//! 옵션 앵커와 BitVMX 정산 프로그램 바인딩
+//!
+//! CREATE 앵커에는 (정산 프로그램 ELF 해시, 옵션 조건의 정규 입력 인코딩,
+//! 오라클 키 집합)에 대한 커밋먼트를 넣습니다. 정산 증명은 같은 프로그램을 같은
+//! 조건으로 실행했고 현물가가 키 집합에 속한 Aggregator의 합의 증명에서 왔을
+//! 때만 `BitVmxBridge::validate_proof`를 통과합니다.
+
+use oracle_vm_common::crypto::{sha256, PublicKey};
+
+/// 커밋먼트 도메인 구분자 (레이아웃이 바뀌면 버전을 올림)
+pub const BINDING_DOMAIN: &[u8] = b"oraclevm/program-binding/v1";
+
+/// 옵션 조건 입력 길이: option_type, strike(cents), quantity (각 u32 LE)
+pub const OPTION_INPUT_LEN: usize = 12;
+
+/// 옵션 생성 시 고정하는 정산 조건
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramBinding {
+    /// 정산 프로그램 ELF SHA-256
+    pub program_hash: [u8; 32],
+    /// 현물가를 뺀 정산 프로그램 입력 (`BitVmxBridge::option_input`)
+    pub option_input: [u8; OPTION_INPUT_LEN],
+    /// 합의 증명에 서명할 수 있는 Aggregator 키 (정렬, 중복 제거)
+    oracle_keys: Vec<PublicKey>,
+}
+
+impl ProgramBinding {
+    pub fn new(program_hash: [u8; 32], option_input: [u8; OPTION_INPUT_LEN], mut oracle_keys: Vec<PublicKey>) -> Self {
+        oracle_keys.sort_by_key(|key| key.serialize());
+        oracle_keys.dedup();
+        Self {
+            program_hash,
+            option_input,
+            oracle_keys,
+        }
+    }
+
+    pub fn oracle_keys(&self) -> &[PublicKey] {
+        &self.oracle_keys
+    }
+
+    pub fn contains_key(&self, key: &PublicKey) -> bool {
+        self.oracle_keys.contains(key)
+    }
+
+    /// 도메인 || 프로그램 해시 || 조건 입력 || 키 수(u16 LE) || 압축 공개키(33)...의 SHA-256
+    pub fn commitment(&self) -> [u8; 32] {
+        let mut data = Vec::with_capacity(
+            BINDING_DOMAIN.len() + 32 + OPTION_INPUT_LEN + 2 + 33 * self.oracle_keys.len(),
+        );
+        data.extend_from_slice(BINDING_DOMAIN);
+        data.extend_from_slice(&self.program_hash);
+        data.extend_from_slice(&self.option_input);
+        data.extend_from_slice(&(self.oracle_keys.len() as u16).to_le_bytes());
+        for key in &self.oracle_keys {
+            data.extend_from_slice(&key.serialize());
+        }
+        sha256(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::crypto::generate_keypair;
+
+    #[test]
+    fn test_commitment_is_canonical_and_binding() {
+        let (_, a) = generate_keypair();
+        let (_, b) = generate_keypair();
+        let input = [1u8; OPTION_INPUT_LEN];
+        let binding = ProgramBinding::new([7; 32], input, vec![a, b]);
+
+        // 키 순서와 중복은 커밋먼트에 영향 없음
+        assert_eq!(binding.commitment(), ProgramBinding::new([7; 32], input, vec![b, a, b]).commitment());
+        assert_eq!(binding.oracle_keys().len(), 2);
+
+        // 프로그램, 조건, 키 집합 중 하나라도 바뀌면 커밋먼트가 바뀜
+        assert_ne!(binding.commitment(), ProgramBinding::new([8; 32], input, vec![a, b]).commitment());
+        assert_ne!(binding.commitment(), ProgramBinding::new([7; 32], [2; OPTION_INPUT_LEN], vec![a, b]).commitment());
+        assert_ne!(binding.commitment(), ProgramBinding::new([7; 32], input, vec![a]).commitment());
+    }
+}
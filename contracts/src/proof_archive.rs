@@ -0,0 +1,425 @@
+//! 정산 증명 보관소
+//!
+//! BitVMX 정산 증명은 만들어진 뒤 버려지고 있었습니다. 분쟁/챌린지 처리나
+//! 외부 검증자가 나중에 다시 확인할 수 있도록 증명 데이터, 실행 트레이스,
+//! 옵션 감사 기록(해시 체인)을 묶어 내용 주소(SHA256) 방식으로 보관하고
+//! 옵션 ID로 찾을 수 있게 합니다.
+//!
+//! 저장 방식은 `ProofStore` 뒤로 분리되어 있어 디스크 대신 S3 호환 저장소를
+//! 붙일 수 있습니다.
+
+use crate::audit::{verify_trail, AuditRecord};
+use crate::bitvmx_bridge::SettlementProof;
+use anyhow::{bail, Context, Result};
+use oracle_vm_common::crypto::sha256;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 내용 주소 저장소 (키 = 내용의 SHA256 hex)
+pub trait ProofStore: Send + Sync {
+    /// 저장 후 내용 해시 반환 (이미 있으면 그대로 반환)
+    fn put(&self, content: &[u8]) -> Result<String>;
+
+    fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>>;
+
+    /// 저장된 모든 내용 해시
+    fn list(&self) -> Result<Vec<String>>;
+}
+
+/// 인메모리 저장소 (테스트용)
+#[derive(Debug, Default)]
+pub struct InMemoryProofStore {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryProofStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ProofStore for InMemoryProofStore {
+    fn put(&self, content: &[u8]) -> Result<String> {
+        let hash = hex::encode(sha256(content));
+        self.objects
+            .lock()
+            .unwrap()
+            .entry(hash.clone())
+            .or_insert_with(|| content.to_vec());
+        Ok(hash)
+    }
+
+    fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.objects.lock().unwrap().get(content_hash).cloned())
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.objects.lock().unwrap().keys().cloned().collect())
+    }
+}
+
+/// 디렉터리 저장소: `{root}/{해시 앞 2자리}/{해시}`
+pub struct FileProofStore {
+    root: PathBuf,
+}
+
+impl FileProofStore {
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create proof archive {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    fn object_path(&self, content_hash: &str) -> Result<PathBuf> {
+        if content_hash.len() != 64 || !content_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid content hash {}", content_hash);
+        }
+        Ok(self.root.join(&content_hash[..2]).join(content_hash))
+    }
+}
+
+impl ProofStore for FileProofStore {
+    fn put(&self, content: &[u8]) -> Result<String> {
+        let hash = hex::encode(sha256(content));
+        let path = self.object_path(&hash)?;
+        if path.exists() {
+            return Ok(hash);
+        }
+        let dir = path.parent().expect("object path has a parent");
+        std::fs::create_dir_all(dir)?;
+        // 임시 파일에 쓴 뒤 교체 (중간에 끊겨도 반쯤 쓴 객체가 남지 않음)
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .with_context(|| format!("Failed to write proof {}", path.display()))?;
+        Ok(hash)
+    }
+
+    fn get(&self, content_hash: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.object_path(content_hash)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read(&path)
+            .map(Some)
+            .with_context(|| format!("Failed to read proof {}", path.display()))
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        for dir in std::fs::read_dir(&self.root)? {
+            let dir = dir?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            for object in std::fs::read_dir(&dir)? {
+                let name = object?.file_name().to_string_lossy().into_owned();
+                if self.object_path(&name).is_ok() {
+                    hashes.push(name);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+/// 보관하는 증명 묶음
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub option_id: String,
+    pub archived_at: u64,
+    pub proof_data: String, // hex
+    pub proof_hash: String, // hex
+    pub settlement_amount: u64, // satoshis
+    /// BitVMX 실행 트레이스 전체
+    pub execution_trace: String,
+    /// 보관 시점까지의 옵션 감사 기록
+    pub audit: Vec<AuditRecord>,
+}
+
+impl ProofBundle {
+    pub fn new(option_id: &str, proof: &SettlementProof, audit: &[AuditRecord], archived_at: u64) -> Self {
+        Self {
+            option_id: option_id.to_string(),
+            archived_at,
+            proof_data: hex::encode(&proof.proof_data),
+            proof_hash: hex::encode(proof.proof_hash),
+            settlement_amount: proof.settlement_amount,
+            execution_trace: proof.execution_trace.clone(),
+            audit: audit.to_vec(),
+        }
+    }
+
+    /// 증명 해시와 감사 기록 체인 검증
+    pub fn verify(&self) -> bool {
+        let proof_matches = hex::decode(&self.proof_data)
+            .map(|data| hex::encode(sha256(&data)) == self.proof_hash)
+            .unwrap_or(false);
+        proof_matches
+            && verify_trail(&self.audit).is_ok()
+            && self.audit.iter().all(|record| record.option_id == self.option_id)
+    }
+}
+
+/// 옵션별 보관 목록 항목
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub option_id: String,
+    pub content_hash: String,
+    pub proof_hash: String,
+    pub settlement_amount: u64,
+    pub archived_at: u64,
+}
+
+impl ArchiveEntry {
+    fn of(bundle: &ProofBundle, content_hash: String) -> Self {
+        Self {
+            option_id: bundle.option_id.clone(),
+            content_hash,
+            proof_hash: bundle.proof_hash.clone(),
+            settlement_amount: bundle.settlement_amount,
+            archived_at: bundle.archived_at,
+        }
+    }
+}
+
+/// 증명 보관소 (옵션 ID → 보관 목록)
+pub struct ProofArchive {
+    store: Box<dyn ProofStore>,
+    index: BTreeMap<String, Vec<ArchiveEntry>>,
+}
+
+impl ProofArchive {
+    /// 저장소의 기존 묶음을 읽어 색인 구성
+    pub fn new(store: Box<dyn ProofStore>) -> Result<Self> {
+        let mut archive = Self {
+            store,
+            index: BTreeMap::new(),
+        };
+        for hash in archive.store.list()? {
+            let bundle = archive
+                .load(&hash)?
+                .with_context(|| format!("Proof {} listed but missing", hash))?;
+            archive.insert(ArchiveEntry::of(&bundle, hash));
+        }
+        Ok(archive)
+    }
+
+    fn insert(&mut self, entry: ArchiveEntry) {
+        let entries = self.index.entry(entry.option_id.clone()).or_default();
+        if !entries.iter().any(|e| e.content_hash == entry.content_hash) {
+            entries.push(entry);
+            entries.sort_by(|a, b| (a.archived_at, &a.content_hash).cmp(&(b.archived_at, &b.content_hash)));
+        }
+    }
+
+    /// 정산 증명 보관 후 색인 항목 반환
+    pub fn archive(
+        &mut self,
+        option_id: &str,
+        proof: &SettlementProof,
+        audit: &[AuditRecord],
+        now: u64,
+    ) -> Result<ArchiveEntry> {
+        let bundle = ProofBundle::new(option_id, proof, audit, now);
+        let content = serde_json::to_vec(&bundle)?;
+        let hash = self.store.put(&content)?;
+        let entry = ArchiveEntry::of(&bundle, hash);
+        self.insert(entry.clone());
+        Ok(entry)
+    }
+
+    /// 옵션의 보관 목록 (보관 시각 순)
+    pub fn entries(&self, option_id: &str) -> &[ArchiveEntry] {
+        self.index.get(option_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 묶음 조회 (내용 해시가 맞지 않으면 오류)
+    pub fn load(&self, content_hash: &str) -> Result<Option<ProofBundle>> {
+        let Some(content) = self.store.get(content_hash)? else {
+            return Ok(None);
+        };
+        if hex::encode(sha256(&content)) != content_hash {
+            bail!("Proof {} does not match its content hash", content_hash);
+        }
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+}
+
+/// `/proofs/{option_id}` 증명 조회 API (분쟁 처리/외부 검증자용)
+pub mod api {
+    use super::ProofArchive;
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::get,
+        Json, Router,
+    };
+    use serde_json::json;
+    use std::sync::{Arc, RwLock};
+
+    pub type SharedArchive = Arc<RwLock<ProofArchive>>;
+
+    #[utoipa::path(
+        get,
+        path = "/proofs/{option_id}",
+        tag = "proofs",
+        params(("option_id" = String, Path, description = "옵션 ID")),
+        responses((status = 200, body = Object))
+    )]
+    async fn list_proofs(Path(option_id): Path<String>, State(archive): State<SharedArchive>) -> Response {
+        let Ok(archive) = archive.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        Json(json!({
+            "option_id": option_id,
+            "proofs": archive.entries(&option_id),
+        }))
+        .into_response()
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/proofs/{option_id}/{content_hash}",
+        tag = "proofs",
+        params(("option_id" = String, Path, description = "옵션 ID"), ("content_hash" = String, Path)),
+        responses((status = 200, body = Object),
+            (status = 404))
+    )]
+    async fn get_proof(
+        Path((option_id, content_hash)): Path<(String, String)>,
+        State(archive): State<SharedArchive>,
+    ) -> Response {
+        let Ok(archive) = archive.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        if !archive.entries(&option_id).iter().any(|e| e.content_hash == content_hash) {
+            return StatusCode::NOT_FOUND.into_response();
+        }
+        match archive.load(&content_hash) {
+            Ok(Some(bundle)) => {
+                let verified = bundle.verify();
+                Json(json!({ "content_hash": content_hash, "verified": verified, "bundle": bundle }))
+                    .into_response()
+            }
+            Ok(None) => StatusCode::NOT_FOUND.into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+
+    /// `/proofs` 라우터 생성
+    pub fn router(archive: SharedArchive) -> Router {
+        Router::new()
+            .route("/proofs/:option_id", get(list_proofs))
+            .route("/proofs/:option_id/:content_hash", get(get_proof))
+            .with_state(archive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditAction, AuditLog};
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use std::sync::{Arc, RwLock};
+    use tower::ServiceExt;
+
+    fn proof(amount: u64) -> SettlementProof {
+        let proof_data = amount.to_le_bytes().to_vec();
+        SettlementProof {
+            proof_hash: sha256(&proof_data),
+            proof_data,
+            settlement_amount: amount,
+            execution_trace: format!("Settlement amount: {} cents\nHalt: 0\n", amount / 1_000),
+            settlement_input: vec![0; 16],
+            program_hash: [0; 32],
+        }
+    }
+
+    fn audit() -> AuditLog {
+        let mut log = AuditLog::new();
+        log.append("OPT-1", 100, AuditAction::PremiumReceived { amount: 25_000 });
+        log.append("OPT-1", 200, AuditAction::Payout { spot_price: 7_200_000, amount: 2_000_000, dust: 0 });
+        log
+    }
+
+    #[test]
+    fn test_file_archive_reload_and_tamper_detection() {
+        let dir = std::env::temp_dir().join(format!("btcfi-proofs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let log = audit();
+
+        let entry = {
+            let mut archive = ProofArchive::new(Box::new(FileProofStore::open(&dir).unwrap())).unwrap();
+            let entry = archive.archive("OPT-1", &proof(2_000_000), log.trail("OPT-1"), 300).unwrap();
+            // 같은 묶음은 한 번만 보관
+            archive.archive("OPT-1", &proof(2_000_000), log.trail("OPT-1"), 300).unwrap();
+            archive.archive("OPT-2", &proof(0), &[], 400).unwrap();
+            entry
+        };
+
+        let archive = ProofArchive::new(Box::new(FileProofStore::open(&dir).unwrap())).unwrap();
+        assert_eq!(archive.entries("OPT-1"), std::slice::from_ref(&entry));
+        assert_eq!(archive.entries("OPT-2").len(), 1);
+        let bundle = archive.load(&entry.content_hash).unwrap().unwrap();
+        assert!(bundle.verify());
+        assert_eq!(bundle.audit, log.trail("OPT-1"));
+        assert!(bundle.execution_trace.contains("Halt: 0"));
+
+        // 디스크에서 고친 묶음은 내용 해시로 걸러짐
+        let path = dir.join(&entry.content_hash[..2]).join(&entry.content_hash);
+        let edited = String::from_utf8(std::fs::read(&path).unwrap())
+            .unwrap()
+            .replace("2000000", "9000000");
+        std::fs::write(&path, edited).unwrap();
+        assert!(archive.load(&entry.content_hash).is_err());
+        assert!(archive.load(&"0".repeat(64)).unwrap().is_none());
+        assert!(archive.load("../etc").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bundle_verify_rejects_mismatched_proof() {
+        let log = audit();
+        let mut bundle = ProofBundle::new("OPT-1", &proof(2_000_000), log.trail("OPT-1"), 300);
+        assert!(bundle.verify());
+
+        bundle.proof_hash = hex::encode([0u8; 32]);
+        assert!(!bundle.verify());
+
+        let other = ProofBundle::new("OPT-2", &proof(2_000_000), log.trail("OPT-1"), 300);
+        assert!(!other.verify());
+    }
+
+    #[tokio::test]
+    async fn test_proof_endpoints() {
+        let mut archive = ProofArchive::new(Box::new(InMemoryProofStore::new())).unwrap();
+        let entry = archive.archive("OPT-1", &proof(2_000_000), audit().trail("OPT-1"), 300).unwrap();
+        let shared = Arc::new(RwLock::new(archive));
+        let get = |uri: String| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = api::router(shared.clone()).oneshot(get("/proofs/OPT-1".into())).await.unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body["proofs"][0]["content_hash"], entry.content_hash);
+
+        let uri = format!("/proofs/OPT-1/{}", entry.content_hash);
+        let response = api::router(shared.clone()).oneshot(get(uri)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap()).unwrap();
+        assert_eq!(body["verified"], true);
+        assert_eq!(body["bundle"]["settlement_amount"], 2_000_000);
+
+        // 다른 옵션의 경로로는 조회되지 않음
+        let uri = format!("/proofs/OPT-2/{}", entry.content_hash);
+        let response = api::router(shared).oneshot(get(uri)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
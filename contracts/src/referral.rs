@@ -0,0 +1,346 @@
+//! 추천 코드와 수수료 리베이트
+//!
+//! 추천 코드가 붙은 거래는 프로토콜 수수료의 일부를 추천인의 청구 가능 잔고로
+//! 돌려줍니다. 리베이트 비율은 코드별 누적 프리미엄 거래량에 따른 등급으로
+//! 정하며, 추천인 본인의 거래에는 코드를 쓸 수 없습니다.
+
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 추천 코드 최대 길이
+const MAX_CODE_LEN: usize = 32;
+
+/// 리베이트 등급: 누적 거래량이 `min_volume` 이상이면 수수료의 `rebate_bps` 환급
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RebateTier {
+    /// 코드별 누적 프리미엄 (satoshis)
+    pub min_volume: u64,
+    /// 프로토콜 수수료 대비 리베이트 (bps)
+    pub rebate_bps: u32,
+}
+
+/// 기본 등급: 10% → 10 BTC 이상 20% → 100 BTC 이상 30%
+pub const DEFAULT_REBATE_TIERS: [RebateTier; 3] = [
+    RebateTier {
+        min_volume: 0,
+        rebate_bps: 1_000,
+    },
+    RebateTier {
+        min_volume: 1_000_000_000,
+        rebate_bps: 2_000,
+    },
+    RebateTier {
+        min_volume: 10_000_000_000,
+        rebate_bps: 3_000,
+    },
+];
+
+/// 등록된 추천 코드
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferralCode {
+    pub code: String,
+    pub referrer_id: String,
+    pub registered_at: u64,
+}
+
+/// 코드별 누적 집계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferralStats {
+    pub trades: u64,
+    pub premium_volume: u64, // satoshis
+    pub protocol_fees: u64,  // satoshis
+    pub rebates: u64,        // satoshis
+}
+
+/// 거래 한 건의 추천 귀속 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferralAttribution {
+    pub code: String,
+    pub referrer_id: String,
+    pub premium: u64,      // satoshis
+    pub protocol_fee: u64, // satoshis
+    pub rebate: u64,       // satoshis
+}
+
+/// 추천 프로그램
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferralProgram {
+    codes: BTreeMap<String, ReferralCode>,
+    tiers: Vec<RebateTier>,
+    stats: BTreeMap<String, ReferralStats>,
+}
+
+impl Default for ReferralProgram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReferralProgram {
+    pub fn new() -> Self {
+        Self {
+            codes: BTreeMap::new(),
+            tiers: DEFAULT_REBATE_TIERS.to_vec(),
+            stats: BTreeMap::new(),
+        }
+    }
+
+    /// 리베이트 등급 교체 (0부터 시작하고 거래량 오름차순이어야 함)
+    pub fn set_tiers(&mut self, tiers: Vec<RebateTier>) -> Result<(), ContractError> {
+        let invalid = |reason: &str| ContractError::InvalidReferral(format!("rebate tiers {}", reason));
+        if tiers.first().map(|tier| tier.min_volume) != Some(0) {
+            return Err(invalid("must start at zero volume"));
+        }
+        if tiers.windows(2).any(|pair| pair[0].min_volume >= pair[1].min_volume) {
+            return Err(invalid("must have increasing volumes"));
+        }
+        if tiers.iter().any(|tier| tier.rebate_bps > 10_000) {
+            return Err(invalid("cannot rebate more than the fee"));
+        }
+        self.tiers = tiers;
+        Ok(())
+    }
+
+    pub fn tiers(&self) -> &[RebateTier] {
+        &self.tiers
+    }
+
+    /// 추천 코드 등록
+    pub fn register(
+        &mut self,
+        code: &str,
+        referrer_id: &str,
+        now: u64,
+    ) -> Result<&ReferralCode, ContractError> {
+        if code.is_empty()
+            || code.len() > MAX_CODE_LEN
+            || !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ContractError::InvalidReferral(format!(
+                "code {:?} must be 1-{} characters of [A-Za-z0-9_-]",
+                code, MAX_CODE_LEN
+            )));
+        }
+        if self.codes.contains_key(code) {
+            return Err(ContractError::InvalidReferral(format!("code {} already registered", code)));
+        }
+        Ok(self.codes.entry(code.to_string()).or_insert(ReferralCode {
+            code: code.to_string(),
+            referrer_id: referrer_id.to_string(),
+            registered_at: now,
+        }))
+    }
+
+    /// 등록된 코드가 없는 상태 (스냅샷에서 생략)
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    pub fn code(&self, code: &str) -> Option<&ReferralCode> {
+        self.codes.get(code)
+    }
+
+    /// 누적 거래량에 해당하는 등급
+    pub fn tier_for(&self, volume: u64) -> RebateTier {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .copied()
+            .unwrap_or(RebateTier {
+                min_volume: 0,
+                rebate_bps: 0,
+            })
+    }
+
+    /// 거래를 코드에 귀속 (상태는 바꾸지 않음, 등급은 이번 거래 이전 누적 거래량 기준)
+    pub fn attribute(
+        &self,
+        code: &str,
+        trader_id: &str,
+        premium: u64,
+        protocol_fee: u64,
+    ) -> Result<ReferralAttribution, ContractError> {
+        let referral = self
+            .codes
+            .get(code)
+            .ok_or_else(|| ContractError::InvalidReferral(format!("unknown code {}", code)))?;
+        if referral.referrer_id == trader_id {
+            return Err(ContractError::InvalidReferral(format!(
+                "{} cannot use their own code {}",
+                trader_id, code
+            )));
+        }
+        let volume = self.stats(code).premium_volume;
+        let rebate_bps = self.tier_for(volume).rebate_bps;
+        Ok(ReferralAttribution {
+            code: code.to_string(),
+            referrer_id: referral.referrer_id.clone(),
+            premium,
+            protocol_fee,
+            rebate: (protocol_fee as u128 * rebate_bps as u128 / 10_000) as u64,
+        })
+    }
+
+    /// 체결된 거래를 집계에 반영
+    pub fn record(&mut self, attribution: &ReferralAttribution) {
+        let stats = self.stats.entry(attribution.code.clone()).or_default();
+        stats.trades += 1;
+        stats.premium_volume += attribution.premium;
+        stats.protocol_fees += attribution.protocol_fee;
+        stats.rebates += attribution.rebate;
+    }
+
+    pub fn stats(&self, code: &str) -> ReferralStats {
+        self.stats.get(code).copied().unwrap_or_default()
+    }
+
+    /// 등록된 코드와 집계 (코드 순)
+    pub fn summary(&self) -> Vec<(&ReferralCode, ReferralStats)> {
+        self.codes
+            .values()
+            .map(|code| (code, self.stats(&code.code)))
+            .collect()
+    }
+}
+
+/// 추천 코드 HTTP API (`/referrals`)
+pub mod api {
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct RegisterRequest {
+        pub code: String,
+        pub referrer_id: String,
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/referrals",
+        tag = "referrals",
+        request_body = RegisterRequest,
+        responses(
+            (status = 201, body = Object),
+            (status = 400, body = AdminError),
+            (status = 409, body = AdminError)
+        )
+    )]
+    async fn register(
+        State(manager): State<SharedManager>,
+        Json(request): Json<RegisterRequest>,
+    ) -> Response {
+        if request.referrer_id.is_empty() {
+            return bad_request("referrer_id is required");
+        }
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let now = chrono::Utc::now().timestamp() as u64;
+        match manager
+            .referrals_mut()
+            .register(&request.code, &request.referrer_id, now)
+        {
+            Ok(code) => (StatusCode::CREATED, Json(code.clone())).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/referrals/{code}",
+        tag = "referrals",
+        params(("code" = String, Path)),
+        responses((status = 200, body = Object),
+            (status = 404))
+    )]
+    async fn get_code(Path(code): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let referrals = manager.referrals();
+        let Some(referral) = referrals.code(&code) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let stats = referrals.stats(&code);
+        Json(json!({
+            "referral": referral,
+            "stats": stats,
+            "tier": referrals.tier_for(stats.premium_volume),
+        }))
+        .into_response()
+    }
+
+    /// `/referrals` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/referrals", post(register))
+            .route("/referrals/:code", get(get_code))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribution_uses_volume_tiers() {
+        let mut program = ReferralProgram::new();
+        program.register("ALICE", "alice", 1).unwrap();
+        assert!(program.register("ALICE", "mallory", 2).is_err());
+        assert!(program.register("bad code!", "bob", 2).is_err());
+
+        let first = program.attribute("ALICE", "bob", 1_000_000_000, 20_000_000).unwrap();
+        assert_eq!(first.referrer_id, "alice");
+        assert_eq!(first.rebate, 2_000_000);
+        program.record(&first);
+
+        // 10 BTC 누적 후 20% 등급
+        let second = program.attribute("ALICE", "carol", 100_000, 2_000).unwrap();
+        assert_eq!(second.rebate, 400);
+        program.record(&second);
+        assert_eq!(
+            program.stats("ALICE"),
+            ReferralStats {
+                trades: 2,
+                premium_volume: 1_000_100_000,
+                protocol_fees: 20_002_000,
+                rebates: 2_000_400,
+            }
+        );
+    }
+
+    #[test]
+    fn test_rejects_self_referral_and_unknown_codes() {
+        let mut program = ReferralProgram::new();
+        program.register("ALICE", "alice", 1).unwrap();
+        assert!(matches!(
+            program.attribute("ALICE", "alice", 100_000, 2_000),
+            Err(ContractError::InvalidReferral(_))
+        ));
+        assert!(program.attribute("NOPE", "bob", 100_000, 2_000).is_err());
+
+        assert!(program.set_tiers(vec![]).is_err());
+        assert!(program
+            .set_tiers(vec![
+                RebateTier { min_volume: 0, rebate_bps: 500 },
+                RebateTier { min_volume: 0, rebate_bps: 900 },
+            ])
+            .is_err());
+        program
+            .set_tiers(vec![RebateTier { min_volume: 0, rebate_bps: 5_000 }])
+            .unwrap();
+        assert_eq!(program.attribute("ALICE", "bob", 100_000, 2_000).unwrap().rebate, 1_000);
+    }
+}
@@ -0,0 +1,758 @@
+//! 정산/풀 이력 리포트
+//!
+//! 이벤트 저장소의 기록을 회계용 표(정산 내역, LP 입출금, 프리미엄 수입,
+//! 지급액, 프로토콜 수수료, 추천 코드별 집계)로 변환하고 CSV/JSON/Parquet로 내보냅니다.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::event_store::{EventStore, PoolEvent, PoolEventKind};
+use crate::fees::FeeKind;
+use oracle_vm_common::types::OptionType;
+
+/// 리포트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    Settlements,
+    Liquidity,
+    Premiums,
+    Payouts,
+    Fees,
+    Referrals,
+    Funding,
+}
+
+impl FromStr for ReportKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "settlements" => Ok(Self::Settlements),
+            "liquidity" => Ok(Self::Liquidity),
+            "premiums" => Ok(Self::Premiums),
+            "payouts" => Ok(Self::Payouts),
+            "fees" => Ok(Self::Fees),
+            "referrals" => Ok(Self::Referrals),
+            "funding" => Ok(Self::Funding),
+            _ => anyhow::bail!(
+                "Unknown report: {}. Supported: settlements, liquidity, premiums, payouts, fees, referrals, funding",
+                s
+            ),
+        }
+    }
+}
+
+/// 내보내기 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "parquet" => Ok(Self::Parquet),
+            _ => anyhow::bail!("Unknown format: {}. Supported: csv, json, parquet", s),
+        }
+    }
+}
+
+/// 컬럼 타입
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Int,
+    Text,
+}
+
+/// 셀 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportCell {
+    Int(i64),
+    Text(String),
+}
+
+impl ReportCell {
+    fn to_csv_field(&self) -> String {
+        match self {
+            ReportCell::Int(value) => value.to_string(),
+            ReportCell::Text(text) => {
+                if text.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", text.replace('"', "\"\""))
+                } else {
+                    text.clone()
+                }
+            }
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            ReportCell::Int(value) => serde_json::json!(value),
+            ReportCell::Text(text) => serde_json::json!(text),
+        }
+    }
+}
+
+/// 리포트 표
+#[derive(Debug, Clone)]
+pub struct ReportTable {
+    pub name: &'static str,
+    pub columns: Vec<(&'static str, ColumnKind)>,
+    pub rows: Vec<Vec<ReportCell>>,
+}
+
+impl ReportTable {
+    fn new(name: &'static str, columns: Vec<(&'static str, ColumnKind)>) -> Self {
+        Self {
+            name,
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// CSV 문자열로 변환 (헤더 포함)
+    pub fn to_csv(&self) -> String {
+        let mut out = self
+            .columns
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(",");
+        out.push('\n');
+
+        for row in &self.rows {
+            let line = row
+                .iter()
+                .map(ReportCell::to_csv_field)
+                .collect::<Vec<_>>()
+                .join(",");
+            out.push_str(&line);
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// 행마다 객체 하나인 JSON 배열로 변환
+    pub fn to_json(&self) -> serde_json::Value {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object: serde_json::Map<String, serde_json::Value> = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|((name, _), cell)| (name.to_string(), cell.to_json()))
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        serde_json::Value::Array(rows)
+    }
+
+    /// Parquet 파일로 변환 (`parquet` feature 필요)
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet(&self) -> Result<Vec<u8>> {
+        use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let fields = self
+            .columns
+            .iter()
+            .map(|(name, kind)| match kind {
+                ColumnKind::Int => format!("REQUIRED INT64 {};", name),
+                ColumnKind::Text => format!("REQUIRED BYTE_ARRAY {} (UTF8);", name),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let schema = Arc::new(parse_message_type(&format!(
+            "message {} {{ {} }}",
+            self.name, fields
+        ))?);
+
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            schema,
+            Arc::new(WriterProperties::builder().build()),
+        )?;
+        let mut row_group = writer.next_row_group()?;
+
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            match self.columns[index].1 {
+                ColumnKind::Int => {
+                    let values: Vec<i64> = self
+                        .rows
+                        .iter()
+                        .map(|row| match &row[index] {
+                            ReportCell::Int(value) => *value,
+                            ReportCell::Text(_) => 0,
+                        })
+                        .collect();
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, None, None)?;
+                }
+                ColumnKind::Text => {
+                    let values: Vec<ByteArray> = self
+                        .rows
+                        .iter()
+                        .map(|row| match &row[index] {
+                            ReportCell::Text(text) => ByteArray::from(text.as_str()),
+                            ReportCell::Int(value) => ByteArray::from(value.to_string().as_str()),
+                        })
+                        .collect();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)?;
+                }
+            }
+            column.close()?;
+            index += 1;
+        }
+
+        row_group.close()?;
+        writer.close()?;
+        Ok(buffer)
+    }
+
+    /// 지정 형식으로 직렬화
+    pub fn export(&self, format: ReportFormat) -> Result<Vec<u8>> {
+        match format {
+            ReportFormat::Csv => Ok(self.to_csv().into_bytes()),
+            ReportFormat::Json => Ok(serde_json::to_vec_pretty(&self.to_json())?),
+            #[cfg(feature = "parquet")]
+            ReportFormat::Parquet => self.to_parquet(),
+            #[cfg(not(feature = "parquet"))]
+            ReportFormat::Parquet => {
+                anyhow::bail!("Parquet export requires the `parquet` feature")
+            }
+        }
+    }
+}
+
+/// 정산 시점에 필요한 옵션 생성 정보
+struct CreatedOption<'a> {
+    option_type: OptionType,
+    strike_price: u64,
+    quantity: u64,
+    premium: u64,
+    user_id: &'a str,
+}
+
+/// 이벤트 저장소 기반 리포트 생성기
+pub struct ReportGenerator<'a> {
+    store: &'a dyn EventStore,
+}
+
+impl<'a> ReportGenerator<'a> {
+    pub fn new(store: &'a dyn EventStore) -> Self {
+        Self { store }
+    }
+
+    /// [from, to] 구간의 리포트 생성 (Unix timestamp, 초)
+    pub fn generate(&self, kind: ReportKind, from: u64, to: u64) -> ReportTable {
+        let events = self.store.events_between(from, to);
+        match kind {
+            ReportKind::Settlements => self.settlements(&events),
+            ReportKind::Liquidity => Self::liquidity(&events),
+            ReportKind::Premiums => Self::premiums(&events),
+            ReportKind::Payouts => self.payouts(&events),
+            ReportKind::Fees => Self::fees(&events),
+            ReportKind::Referrals => Self::referrals(&events),
+            ReportKind::Funding => Self::funding(&events),
+        }
+    }
+
+    /// 옵션 ID → 생성 정보 (구간 밖에서 생성된 옵션도 포함)
+    fn created_options(&self) -> HashMap<&'a str, CreatedOption<'a>> {
+        self.store
+            .events()
+            .iter()
+            .filter_map(|event| match &event.kind {
+                PoolEventKind::OptionCreated {
+                    option_id,
+                    option_type,
+                    strike_price,
+                    quantity,
+                    premium,
+                    user_id,
+                    ..
+                }
+                | PoolEventKind::OptionRolled {
+                    option_id,
+                    option_type,
+                    strike_price,
+                    quantity,
+                    premium,
+                    user_id,
+                    ..
+                } => Some((
+                    option_id.as_str(),
+                    CreatedOption {
+                        option_type: *option_type,
+                        strike_price: *strike_price,
+                        quantity: *quantity,
+                        premium: *premium,
+                        user_id: user_id.as_str(),
+                    },
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn settlements(&self, events: &[&PoolEvent]) -> ReportTable {
+        let created = self.created_options();
+        let mut table = ReportTable::new(
+            "settlements",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("option_id", ColumnKind::Text),
+                ("option_type", ColumnKind::Text),
+                ("user_id", ColumnKind::Text),
+                ("strike_price_cents", ColumnKind::Int),
+                ("spot_price_cents", ColumnKind::Int),
+                ("quantity_sats", ColumnKind::Int),
+                ("premium_sats", ColumnKind::Int),
+                ("payout_sats", ColumnKind::Int),
+                ("pool_pnl_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            if let PoolEventKind::OptionSettled {
+                option_id,
+                spot_price,
+                payout,
+                ..
+            } = &event.kind
+            {
+                let option = created.get(option_id.as_str());
+                let premium = option.map(|o| o.premium).unwrap_or(0);
+                table.rows.push(vec![
+                    ReportCell::Int(event.timestamp as i64),
+                    ReportCell::Text(option_id.clone()),
+                    ReportCell::Text(
+                        option
+                            .map(|o| format!("{:?}", o.option_type))
+                            .unwrap_or_default(),
+                    ),
+                    ReportCell::Text(option.map(|o| o.user_id.to_string()).unwrap_or_default()),
+                    ReportCell::Int(option.map(|o| o.strike_price).unwrap_or(0) as i64),
+                    ReportCell::Int(*spot_price as i64),
+                    ReportCell::Int(option.map(|o| o.quantity).unwrap_or(0) as i64),
+                    ReportCell::Int(premium as i64),
+                    ReportCell::Int(*payout as i64),
+                    ReportCell::Int(premium as i64 - *payout as i64),
+                ]);
+            }
+        }
+
+        table
+    }
+
+    fn liquidity(events: &[&PoolEvent]) -> ReportTable {
+        let mut table = ReportTable::new(
+            "liquidity",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("provider_id", ColumnKind::Text),
+                ("direction", ColumnKind::Text),
+                ("amount_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            let (provider_id, direction, amount) = match &event.kind {
+                PoolEventKind::LiquidityAdded {
+                    provider_id,
+                    amount,
+                } => (provider_id, "deposit", *amount as i64),
+                PoolEventKind::LiquidityRemoved {
+                    provider_id,
+                    amount,
+                } => (provider_id, "withdrawal", -(*amount as i64)),
+                _ => continue,
+            };
+            table.rows.push(vec![
+                ReportCell::Int(event.timestamp as i64),
+                ReportCell::Text(provider_id.clone().unwrap_or_default()),
+                ReportCell::Text(direction.to_string()),
+                ReportCell::Int(amount),
+            ]);
+        }
+
+        table
+    }
+
+    fn premiums(events: &[&PoolEvent]) -> ReportTable {
+        let mut table = ReportTable::new(
+            "premiums",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("option_id", ColumnKind::Text),
+                ("user_id", ColumnKind::Text),
+                ("premium_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            if let PoolEventKind::OptionCreated {
+                option_id,
+                premium,
+                user_id,
+                ..
+            }
+            | PoolEventKind::OptionRolled {
+                option_id,
+                premium,
+                user_id,
+                ..
+            } = &event.kind
+            {
+                table.rows.push(vec![
+                    ReportCell::Int(event.timestamp as i64),
+                    ReportCell::Text(option_id.clone()),
+                    ReportCell::Text(user_id.clone()),
+                    ReportCell::Int(*premium as i64),
+                ]);
+            }
+        }
+
+        table
+    }
+
+    fn payouts(&self, events: &[&PoolEvent]) -> ReportTable {
+        let created = self.created_options();
+        let mut table = ReportTable::new(
+            "payouts",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("option_id", ColumnKind::Text),
+                ("user_id", ColumnKind::Text),
+                ("payout_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            if let PoolEventKind::OptionSettled {
+                option_id, payout, ..
+            } = &event.kind
+            {
+                if *payout == 0 {
+                    continue;
+                }
+                table.rows.push(vec![
+                    ReportCell::Int(event.timestamp as i64),
+                    ReportCell::Text(option_id.clone()),
+                    ReportCell::Text(
+                        created
+                            .get(option_id.as_str())
+                            .map(|o| o.user_id.to_string())
+                            .unwrap_or_default(),
+                    ),
+                    ReportCell::Int(*payout as i64),
+                ]);
+            }
+        }
+
+        table
+    }
+
+    /// 재무 계정 입출금 (수수료 적립은 +, 출금은 -)
+    fn fees(events: &[&PoolEvent]) -> ReportTable {
+        let mut table = ReportTable::new(
+            "fees",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("option_id", ColumnKind::Text),
+                ("kind", ColumnKind::Text),
+                ("destination", ColumnKind::Text),
+                ("amount_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            let (option_id, kind, destination, amount) = match &event.kind {
+                PoolEventKind::FeeCharged {
+                    option_id,
+                    fee,
+                    amount,
+                } => (
+                    option_id.as_str(),
+                    match fee {
+                        FeeKind::Protocol => "protocol_fee",
+                        FeeKind::Settlement => "settlement_fee",
+                    },
+                    "",
+                    *amount as i64,
+                ),
+                PoolEventKind::TreasuryWithdrawn {
+                    amount,
+                    destination,
+                } => ("", "withdrawal", destination.as_str(), -(*amount as i64)),
+                _ => continue,
+            };
+            table.rows.push(vec![
+                ReportCell::Int(event.timestamp as i64),
+                ReportCell::Text(option_id.to_string()),
+                ReportCell::Text(kind.to_string()),
+                ReportCell::Text(destination.to_string()),
+                ReportCell::Int(amount),
+            ]);
+        }
+
+        table
+    }
+
+    /// 추천 코드별 거래 수, 프리미엄, 리베이트 합계 (코드 순)
+    fn referrals(events: &[&PoolEvent]) -> ReportTable {
+        let mut table = ReportTable::new(
+            "referrals",
+            vec![
+                ("code", ColumnKind::Text),
+                ("referrer_id", ColumnKind::Text),
+                ("trades", ColumnKind::Int),
+                ("premium_sats", ColumnKind::Int),
+                ("rebate_sats", ColumnKind::Int),
+            ],
+        );
+
+        let mut totals: std::collections::BTreeMap<&str, (&str, i64, i64, i64)> =
+            std::collections::BTreeMap::new();
+        for event in events {
+            if let PoolEventKind::ReferralAttributed {
+                code,
+                referrer_id,
+                premium,
+                rebate,
+                ..
+            } = &event.kind
+            {
+                let entry = totals
+                    .entry(code.as_str())
+                    .or_insert((referrer_id.as_str(), 0, 0, 0));
+                entry.1 += 1;
+                entry.2 += *premium as i64;
+                entry.3 += *rebate as i64;
+            }
+        }
+        for (code, (referrer_id, trades, premium, rebate)) in totals {
+            table.rows.push(vec![
+                ReportCell::Text(code.to_string()),
+                ReportCell::Text(referrer_id.to_string()),
+                ReportCell::Int(trades),
+                ReportCell::Int(premium),
+                ReportCell::Int(rebate),
+            ]);
+        }
+
+        table
+    }
+
+    /// 담보 사용료 선납(매수자 → 대기)과 LP 적립 내역
+    fn funding(events: &[&PoolEvent]) -> ReportTable {
+        let mut table = ReportTable::new(
+            "funding",
+            vec![
+                ("timestamp", ColumnKind::Int),
+                ("option_id", ColumnKind::Text),
+                ("entry", ColumnKind::Text),
+                ("amount_sats", ColumnKind::Int),
+            ],
+        );
+
+        for event in events {
+            let (option_id, entry, amount) = match &event.kind {
+                PoolEventKind::FundingPrepaid { option_id, amount, .. } => {
+                    (option_id.as_str(), "prepaid", *amount)
+                }
+                PoolEventKind::FundingAccrued { amount, .. } => ("", "accrued", *amount),
+                _ => continue,
+            };
+            table.rows.push(vec![
+                ReportCell::Int(event.timestamp as i64),
+                ReportCell::Text(option_id.to_string()),
+                ReportCell::Text(entry.to_string()),
+                ReportCell::Int(amount as i64),
+            ]);
+        }
+
+        table
+    }
+}
+
+/// 리포트 HTTP API (`GET /reports/{kind}?from=&to=&format=`)
+pub mod api {
+    use super::{ReportFormat, ReportGenerator, ReportKind};
+    use crate::event_store::EventStore;
+    use axum::{
+        extract::{Path, Query, State},
+        http::{header, StatusCode},
+        response::{IntoResponse, Response},
+        routing::get,
+        Router,
+    };
+    use serde::Deserialize;
+    use std::sync::{Arc, RwLock};
+    use utoipa::IntoParams;
+
+    /// 리포트 API 공유 상태
+    pub type SharedEventStore = Arc<RwLock<Box<dyn EventStore>>>;
+
+    /// 리포트 쿼리 파라미터
+    #[derive(Debug, Deserialize, IntoParams)]
+    pub struct ReportQuery {
+        pub from: Option<u64>,
+        pub to: Option<u64>,
+        pub format: Option<String>,
+    }
+
+    /// 이벤트 저장소에서 리포트를 만들어 HTTP 응답으로 변환
+    pub fn render_report(
+        store: &dyn EventStore,
+        kind: &str,
+        params: &ReportQuery,
+    ) -> Result<Response, StatusCode> {
+        let kind: ReportKind = kind.parse().map_err(|_| StatusCode::NOT_FOUND)?;
+        let format: ReportFormat = params
+            .format
+            .as_deref()
+            .unwrap_or("csv")
+            .parse()
+            .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+        let table = ReportGenerator::new(store).generate(
+            kind,
+            params.from.unwrap_or(0),
+            params.to.unwrap_or(u64::MAX),
+        );
+        let body = table
+            .export(format)
+            .map_err(|_| StatusCode::NOT_IMPLEMENTED)?;
+
+        let content_type = match format {
+            ReportFormat::Csv => "text/csv",
+            ReportFormat::Json => "application/json",
+            ReportFormat::Parquet => "application/vnd.apache.parquet",
+        };
+        Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+    }
+
+    async fn get_report(
+        Path(kind): Path<String>,
+        Query(params): Query<ReportQuery>,
+        State(store): State<SharedEventStore>,
+    ) -> Result<Response, StatusCode> {
+        let store = store.read().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        render_report(store.as_ref(), &kind, &params)
+    }
+
+    /// `/reports/{kind}` 라우터 생성
+    pub fn router(store: SharedEventStore) -> Router {
+        Router::new()
+            .route("/reports/:kind", get(get_report))
+            .with_state(store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::InMemoryEventStore;
+
+    fn sample_store() -> InMemoryEventStore {
+        let mut store = InMemoryEventStore::new();
+        store
+            .append(100, PoolEventKind::LiquidityAdded { provider_id: Some("lp1".to_string()), amount: 100_000_000 })
+            .unwrap();
+        store
+            .append(
+                200,
+                PoolEventKind::OptionCreated {
+                    option_id: "CALL-001".to_string(),
+                    option_type: OptionType::Call,
+                    strike_price: 7_000_000,
+                    quantity: 10_000_000,
+                    premium: 250_000,
+                    collateral: 10_000_000,
+                    user_id: "user1".to_string(),
+                },
+            )
+            .unwrap();
+        store
+            .append(
+                300,
+                PoolEventKind::OptionSettled {
+                    option_id: "CALL-001".to_string(),
+                    spot_price: 7_200_000,
+                    payout: 200_000,
+                    dust: 0,
+                },
+            )
+            .unwrap();
+        store
+    }
+
+    #[test]
+    fn test_settlement_report_joins_creation_data() {
+        let store = sample_store();
+        let table = ReportGenerator::new(&store).generate(ReportKind::Settlements, 250, 400);
+
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(table.rows[0][3], ReportCell::Text("user1".to_string()));
+        assert_eq!(table.rows[0][9], ReportCell::Int(50_000));
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let store = sample_store();
+        let table = ReportGenerator::new(&store).generate(ReportKind::Liquidity, 0, u64::MAX);
+        let csv = table.to_csv();
+
+        assert_eq!(
+            csv,
+            "timestamp,provider_id,direction,amount_sats\n100,lp1,deposit,100000000\n"
+        );
+        // 구분자/따옴표/줄바꿈(CR 포함)이 든 필드는 따옴표로 감쌈
+        assert_eq!(ReportCell::Text("a\rb".to_string()).to_csv_field(), "\"a\rb\"");
+        assert_eq!(ReportCell::Text("say \"hi\"".to_string()).to_csv_field(), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_report_window_filters_events() {
+        let store = sample_store();
+        let generator = ReportGenerator::new(&store);
+
+        assert_eq!(generator.generate(ReportKind::Premiums, 0, 150).rows.len(), 0);
+        assert_eq!(generator.generate(ReportKind::Premiums, 0, 250).rows.len(), 1);
+        assert_eq!(generator.generate(ReportKind::Payouts, 0, u64::MAX).rows.len(), 1);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_export() {
+        let store = sample_store();
+        let table = ReportGenerator::new(&store).generate(ReportKind::Settlements, 0, u64::MAX);
+        let bytes = table.export(ReportFormat::Parquet).unwrap();
+
+        assert_eq!(&bytes[..4], b"PAR1");
+        assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+    }
+}
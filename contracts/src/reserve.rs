@@ -0,0 +1,373 @@
+//! 유휴 유동성 수익처 예치 (풀 리밸런싱)
+//!
+//! 옵션 담보로 잠기지 않은 유동성은 그대로 두면 수익이 없습니다.
+//! `ReserveManager`는 유휴 유동성(사용 가능 + 예치)의 설정 비율을 수익처
+//! (`YieldVenue`)에 예치하고, 사용률이 오르면 회수하며, 수익처 이자를 풀
+//! 유동성으로 반영합니다. 예치금은 풀 원장의 `Reserve` 계정에 있으므로 LP
+//! 순자산에 포함되지만 새 옵션 담보로는 쓸 수 없습니다.
+//!
+//! 처음에는 시뮬레이션 수익처(`SimulatedVenue`, APY 0이면 no-op)만 있고,
+//! Lightning 라우팅이나 대출 같은 실제 수익처는 같은 trait으로 붙입니다.
+
+use crate::simple_contract::{SimpleContractManager, SimplePoolState};
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// 1년 (초)
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 3_600;
+
+/// 예치금 이동 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReserveMovement {
+    /// 사용 가능 → 수익처
+    Sweep,
+    /// 수익처 → 사용 가능
+    Recall,
+    /// 수익처 이자 적립
+    Yield,
+}
+
+/// 수익처 인터페이스
+pub trait YieldVenue: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// 현재 연 수익률 (bps)
+    fn apy_bps(&self) -> u32;
+
+    fn deposit(&self, amount: u64, now: u64) -> Result<(), String>;
+
+    fn withdraw(&self, amount: u64, now: u64) -> Result<(), String>;
+
+    /// 이자를 포함한 현재 잔고 (satoshis)
+    fn balance(&self, now: u64) -> Result<u64, String>;
+}
+
+#[derive(Debug, Default)]
+struct SimulatedBalance {
+    balance: u64,
+    /// 마지막으로 이자를 붙인 시각
+    accrued_at: u64,
+}
+
+/// 고정 APY 단리 수익처 시뮬레이션 (APY 0이면 예치만 하는 no-op 수익처)
+#[derive(Debug)]
+pub struct SimulatedVenue {
+    apy_bps: u32,
+    state: Mutex<SimulatedBalance>,
+}
+
+impl SimulatedVenue {
+    pub fn new(apy_bps: u32) -> Self {
+        Self {
+            apy_bps,
+            state: Mutex::new(SimulatedBalance::default()),
+        }
+    }
+
+    /// 이자 적립 (1 sat 미만 이자는 시각을 넘기지 않고 다음 적립에 합산)
+    fn accrue(&self, state: &mut SimulatedBalance, now: u64) {
+        let elapsed = now.saturating_sub(state.accrued_at);
+        let interest = (state.balance as u128 * self.apy_bps as u128 * elapsed as u128
+            / (10_000 * SECONDS_PER_YEAR as u128)) as u64;
+        if interest > 0 || state.balance == 0 {
+            state.balance += interest;
+            state.accrued_at = now;
+        }
+    }
+}
+
+impl YieldVenue for SimulatedVenue {
+    fn name(&self) -> &str {
+        "simulated"
+    }
+
+    fn apy_bps(&self) -> u32 {
+        self.apy_bps
+    }
+
+    fn deposit(&self, amount: u64, now: u64) -> Result<(), String> {
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        self.accrue(&mut state, now);
+        state.balance += amount;
+        Ok(())
+    }
+
+    fn withdraw(&self, amount: u64, now: u64) -> Result<(), String> {
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        self.accrue(&mut state, now);
+        state.balance = state
+            .balance
+            .checked_sub(amount)
+            .ok_or_else(|| format!("withdrawal of {} exceeds balance {}", amount, state.balance))?;
+        Ok(())
+    }
+
+    fn balance(&self, now: u64) -> Result<u64, String> {
+        let mut state = self.state.lock().map_err(|e| e.to_string())?;
+        self.accrue(&mut state, now);
+        Ok(state.balance)
+    }
+}
+
+/// 리밸런싱 정책
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReservePolicy {
+    /// 유휴 유동성(사용 가능 + 예치) 중 수익처에 둘 비율 (0..=1)
+    pub sweep_fraction: f64,
+    /// 사용률(%)이 이 값 이상이면 예치금 전액 회수
+    pub recall_utilization: f64,
+    /// 이보다 작은 이동은 하지 않음 (satoshis, 전액 회수는 예외)
+    pub min_move: u64,
+}
+
+impl Default for ReservePolicy {
+    fn default() -> Self {
+        Self {
+            sweep_fraction: 0.5,
+            recall_utilization: 70.0,
+            min_move: 100_000,
+        }
+    }
+}
+
+impl ReservePolicy {
+    /// 목표 예치금
+    pub fn target_reserve(&self, pool: &SimplePoolState) -> u64 {
+        if pool.utilization_rate() >= self.recall_utilization {
+            return 0;
+        }
+        let idle = pool.available_liquidity + pool.reserve_balance;
+        (idle as f64 * self.sweep_fraction.clamp(0.0, 1.0)).floor() as u64
+    }
+
+    /// 목표로 가기 위한 이동 (없으면 None)
+    pub fn plan(&self, pool: &SimplePoolState) -> Option<(ReserveMovement, u64)> {
+        let target = self.target_reserve(pool);
+        let current = pool.reserve_balance;
+        if target > current && target - current >= self.min_move {
+            Some((ReserveMovement::Sweep, target - current))
+        } else if current > target && (target == 0 || current - target >= self.min_move) {
+            Some((ReserveMovement::Recall, current - target))
+        } else {
+            None
+        }
+    }
+}
+
+/// 예치 현황과 혼합 APY
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReserveReport {
+    pub venue: String,
+    pub venue_apy_bps: u32,
+    pub reserve_balance: u64,
+    pub available_liquidity: u64,
+    pub locked_collateral: u64,
+    pub total_liquidity: u64,
+    /// 풀 전체 유동성 대비 수익처 수익률 (bps, 프리미엄 수익 제외)
+    pub blended_apy_bps: f64,
+}
+
+/// 수익처 예치 관리자
+pub struct ReserveManager {
+    venue: Arc<dyn YieldVenue>,
+    policy: ReservePolicy,
+}
+
+impl ReserveManager {
+    pub fn new(venue: Arc<dyn YieldVenue>, policy: ReservePolicy) -> Self {
+        Self { venue, policy }
+    }
+
+    pub fn policy(&self) -> ReservePolicy {
+        self.policy
+    }
+
+    /// 이자 반영 후 정책에 맞춰 예치/회수, 반영한 이동 반환
+    ///
+    /// 수익처 이동이 먼저 성공해야 원장에 기록하므로, 수익처 오류 시 풀 상태는
+    /// 바뀌지 않습니다. 잔고가 원장보다 적으면(수익처 손실) 경보만 남깁니다.
+    pub fn rebalance(
+        &self,
+        manager: &mut SimpleContractManager,
+        now: u64,
+    ) -> Result<Vec<(ReserveMovement, u64)>, ContractError> {
+        let venue_error = |e: String| ContractError::YieldVenue(format!("{}: {}", self.venue.name(), e));
+        let mut moved = Vec::new();
+
+        let balance = self.venue.balance(now).map_err(venue_error)?;
+        let recorded = manager.pool_state.reserve_balance;
+        if balance > recorded {
+            manager.record_reserve_movement(self.venue.name(), ReserveMovement::Yield, balance - recorded)?;
+            moved.push((ReserveMovement::Yield, balance - recorded));
+        } else if balance < recorded {
+            warn!(
+                "Yield venue {} holds {} sats, ledger expects {}",
+                self.venue.name(),
+                balance,
+                recorded
+            );
+        }
+
+        if let Some((movement, amount)) = self.policy.plan(&manager.pool_state) {
+            match movement {
+                ReserveMovement::Sweep => self.venue.deposit(amount, now),
+                ReserveMovement::Recall => self.venue.withdraw(amount, now),
+                ReserveMovement::Yield => unreachable!("plan never yields"),
+            }
+            .map_err(venue_error)?;
+            manager.record_reserve_movement(self.venue.name(), movement, amount)?;
+            info!(
+                "Reserve {:?} {} sats ({}), utilization {:.1}%",
+                movement,
+                amount,
+                self.venue.name(),
+                manager.pool_state.utilization_rate()
+            );
+            moved.push((movement, amount));
+        }
+        Ok(moved)
+    }
+
+    pub fn report(&self, pool: &SimplePoolState) -> ReserveReport {
+        let venue_apy_bps = self.venue.apy_bps();
+        let blended_apy_bps = if pool.total_liquidity == 0 {
+            0.0
+        } else {
+            venue_apy_bps as f64 * pool.reserve_balance as f64 / pool.total_liquidity as f64
+        };
+        ReserveReport {
+            venue: self.venue.name().to_string(),
+            venue_apy_bps,
+            reserve_balance: pool.reserve_balance,
+            available_liquidity: pool.available_liquidity,
+            locked_collateral: pool.locked_collateral,
+            total_liquidity: pool.total_liquidity,
+            blended_apy_bps,
+        }
+    }
+}
+
+/// 예치 현황 API (`GET /admin/reserve`)
+pub mod api {
+    use super::*;
+    use crate::admin_api::SharedManager;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::{Json, Router};
+
+    type ReserveState = (SharedManager, Arc<ReserveManager>);
+
+    async fn report(
+        State((manager, reserve)): State<ReserveState>,
+    ) -> Result<Json<ReserveReport>, StatusCode> {
+        let manager = manager.read().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        Ok(Json(reserve.report(&manager.pool_state)))
+    }
+
+    pub fn router(manager: SharedManager, reserve: Arc<ReserveManager>) -> Router {
+        Router::new()
+            .route("/admin/reserve", get(report))
+            .with_state((manager, reserve))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool_ledger::PostingKind;
+
+    fn manager_with(liquidity: u64) -> SimpleContractManager {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(liquidity).unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_sweeps_idle_liquidity_and_recalls_on_utilization() {
+        let venue = Arc::new(SimulatedVenue::new(0));
+        let reserve = ReserveManager::new(venue.clone(), ReservePolicy::default());
+        let mut manager = manager_with(100_000_000);
+
+        assert_eq!(
+            reserve.rebalance(&mut manager, 0).unwrap(),
+            vec![(ReserveMovement::Sweep, 50_000_000)]
+        );
+        let pool = &manager.pool_state;
+        assert_eq!((pool.available_liquidity, pool.reserve_balance), (50_000_000, 50_000_000));
+        assert_eq!(pool.total_liquidity, 100_000_000);
+        assert_eq!(venue.balance(0).unwrap(), 50_000_000);
+        // 목표에 도달하면 움직이지 않음
+        assert!(reserve.rebalance(&mut manager, 60).unwrap().is_empty());
+
+        manager
+            .create_option(
+                "CALL-1".to_string(),
+                oracle_vm_common::types::OptionType::Call,
+                7_000_000,
+                45_000_000,
+                1_000_000,
+                800_000,
+                "alice".to_string(),
+            )
+            .unwrap();
+        assert!(manager.pool_state.utilization_rate() < 70.0);
+        // 담보로 유휴 유동성이 줄면 목표도 줄어 일부 회수: (6M + 50M) / 2 = 28M
+        assert_eq!(
+            reserve.rebalance(&mut manager, 120).unwrap(),
+            vec![(ReserveMovement::Recall, 22_000_000)]
+        );
+
+        // 사용률(45%)이 기준 이상이면 전액 회수
+        let policy = ReservePolicy {
+            recall_utilization: 40.0,
+            ..ReservePolicy::default()
+        };
+        let strict = ReserveManager::new(venue.clone(), policy);
+        strict.rebalance(&mut manager, 180).unwrap();
+        assert_eq!(manager.pool_state.reserve_balance, 0);
+        assert_eq!(venue.balance(180).unwrap(), 0);
+        manager.rebuild_pool_state().unwrap();
+    }
+
+    #[test]
+    fn test_yield_accrues_to_pool_and_blended_apy() {
+        let venue = Arc::new(SimulatedVenue::new(500));
+        let reserve = ReserveManager::new(venue.clone(), ReservePolicy::default());
+        let mut manager = manager_with(200_000_000);
+        reserve.rebalance(&mut manager, 0).unwrap();
+
+        // 1억 sats를 5%로 1년
+        let moved = reserve.rebalance(&mut manager, SECONDS_PER_YEAR).unwrap();
+        assert_eq!(moved[0], (ReserveMovement::Yield, 5_000_000));
+        assert_eq!(manager.pool_state.total_liquidity, 205_000_000);
+        assert!(manager
+            .ledger()
+            .transactions()
+            .iter()
+            .flat_map(|tx| &tx.postings)
+            .any(|posting| posting.kind == PostingKind::ReserveYield));
+
+        let report = reserve.report(&manager.pool_state);
+        assert_eq!(report.venue_apy_bps, 500);
+        let expected = 500.0 * report.reserve_balance as f64 / report.total_liquidity as f64;
+        assert!((report.blended_apy_bps - expected).abs() < 1e-9);
+        assert!(report.blended_apy_bps > 240.0 && report.blended_apy_bps < 260.0);
+    }
+
+    #[test]
+    fn test_small_moves_are_skipped() {
+        let policy = ReservePolicy::default();
+        let pool = SimplePoolState {
+            total_liquidity: 100_000_000,
+            available_liquidity: 50_050_000,
+            reserve_balance: 49_950_000,
+            ..SimplePoolState::new()
+        };
+        assert_eq!(policy.plan(&pool), None);
+        assert_eq!(policy.target_reserve(&pool), 50_000_000);
+    }
+}
@@ -0,0 +1,601 @@
+//! 정산 트랜잭션 전송 추적 (이중 지출/reorg 대응)
+//!
+//! 정산 트랜잭션을 전송하고 잊어버리던 흐름을 보완합니다. 옵션마다 정산
+//! txid와 서명된 원본을 보관해 필요한 확인 수에 도달해야 정산 완료로
+//! 표시하고, 블록에서 빠지거나 멤풀에서 축출되면 같은 원본을 재전송합니다.
+//! 옵션 UTXO는 처음 정산 트랜잭션을 만들 때 점유되므로, 같은 UTXO를 쓰는 다른
+//! 정산 트랜잭션은 전송 전에 거부됩니다.
+
+use crate::anchor_tracker::{AnchorBroadcaster, ChainSource, TxStatus};
+use crate::simple_contract::SimpleContractManager;
+use bitcoin::{OutPoint, Transaction};
+use oracle_vm_common::{AnchorError, NetworkProfile};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tracing::{info, instrument, warn};
+
+/// 옵션 기록에 남기는 정산 트랜잭션 상태
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SettlementTxStatus {
+    /// 전송됨 (멤풀 또는 전송 재시도 대기)
+    Broadcast { txid: String },
+    Confirmed { txid: String, block_height: u32, confirmations: u32 },
+    /// 블록/멤풀에서 빠져 재전송 대기 중
+    Reorged { txid: String },
+    /// 필요한 확인 수 도달 (더 이상 조회하지 않음)
+    Settled { txid: String, block_height: u32 },
+}
+
+/// 추적 중인 정산 트랜잭션
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedSettlement {
+    pub option_id: String,
+    /// 정산 트랜잭션이 쓰는 옵션 UTXO
+    pub inputs: Vec<OutPoint>,
+    pub txid: String,
+    /// 재전송용 서명된 원본 트랜잭션
+    pub raw_tx: Vec<u8>,
+    pub status: SettlementTxStatus,
+    pub block_hash: Option<String>,
+    /// 재전송 횟수
+    pub rebroadcasts: u32,
+}
+
+impl TrackedSettlement {
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, SettlementTxStatus::Settled { .. })
+    }
+}
+
+/// 추적 결과 알림
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SettlementAlert {
+    /// 필요한 확인 수 도달, 정산 완료
+    Settled { option_id: String, txid: String, confirmations: u32 },
+    /// 확인됐던 정산 트랜잭션이 블록에서 빠지거나 다른 블록으로 옮겨짐
+    Reorged { option_id: String, txid: String },
+    /// 원본 트랜잭션 재전송
+    Rebroadcast { option_id: String, txid: String },
+    /// 재전송 실패 (다음 조회에서 재시도)
+    Failed { option_id: String, txid: String, error: String },
+}
+
+/// 정산 트랜잭션 전송 관리자
+pub struct SettlementBroadcastManager {
+    profile: NetworkProfile,
+    /// 옵션 ID → 정산 트랜잭션
+    settlements: BTreeMap<String, TrackedSettlement>,
+    /// 점유된 UTXO → 옵션 ID
+    spends: HashMap<OutPoint, String>,
+}
+
+impl SettlementBroadcastManager {
+    pub fn new(profile: NetworkProfile) -> Self {
+        Self {
+            profile,
+            settlements: BTreeMap::new(),
+            spends: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, option_id: &str) -> Option<&TrackedSettlement> {
+        self.settlements.get(option_id)
+    }
+
+    pub fn settlements(&self) -> impl Iterator<Item = &TrackedSettlement> {
+        self.settlements.values()
+    }
+
+    /// 필요한 확인 수에 도달한 정산인지
+    pub fn is_settled(&self, option_id: &str) -> bool {
+        self.get(option_id).is_some_and(TrackedSettlement::is_settled)
+    }
+
+    /// UTXO를 점유한 옵션 ID
+    pub fn spender(&self, outpoint: &OutPoint) -> Option<&str> {
+        self.spends.get(outpoint).map(String::as_str)
+    }
+
+    /// 스냅샷에서 읽은 정산 트랜잭션으로 추적 재개 (점유 UTXO도 복원)
+    pub fn restore(&mut self, settlements: Vec<TrackedSettlement>) {
+        for settlement in settlements {
+            for outpoint in &settlement.inputs {
+                self.spends.insert(*outpoint, settlement.option_id.clone());
+            }
+            self.settlements.insert(settlement.option_id.clone(), settlement);
+        }
+    }
+
+    /// 스냅샷에 싣는 정산 트랜잭션 기록
+    pub fn records(&self) -> Vec<TrackedSettlement> {
+        self.settlements.values().cloned().collect()
+    }
+
+    /// 전송 전 확인: 이미 같은 트랜잭션을 추적 중이면 true
+    ///
+    /// 이미 다른 정산 트랜잭션이 있는 옵션이나 점유된 UTXO를 쓰는 트랜잭션은
+    /// 거부합니다.
+    pub fn check(&self, option_id: &str, tx: &Transaction) -> Result<bool, AnchorError> {
+        let txid = tx.compute_txid().to_string();
+        if let Some(existing) = self.settlements.get(option_id) {
+            if existing.txid == txid {
+                return Ok(true);
+            }
+            return Err(AnchorError::ConflictingSpend(format!(
+                "option {} already settling in {}",
+                option_id, existing.txid
+            )));
+        }
+        if let Some((outpoint, owner)) = tx
+            .input
+            .iter()
+            .find_map(|input| self.spends.get(&input.previous_output).map(|owner| (input.previous_output, owner)))
+        {
+            return Err(AnchorError::ConflictingSpend(format!(
+                "{} already spent by settlement of {}",
+                outpoint, owner
+            )));
+        }
+        Ok(false)
+    }
+
+    /// 정산 트랜잭션 전송과 추적 시작
+    ///
+    /// 같은 트랜잭션을 다시 넘기면 기존 txid를 돌려주고, 충돌하는 트랜잭션은
+    /// `check`와 같이 거부합니다. UTXO는 전송 전에 점유하므로 전송이 실패해도
+    /// 추적은 유지되고 다음 조회에서 같은 원본을 재전송합니다.
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, txid = %tx.compute_txid()))]
+    pub fn broadcast(
+        &mut self,
+        option_id: &str,
+        tx: &Transaction,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<String, AnchorError> {
+        let txid = tx.compute_txid().to_string();
+        if self.check(option_id, tx)? {
+            return Ok(txid);
+        }
+        let inputs: Vec<OutPoint> = tx.input.iter().map(|input| input.previous_output).collect();
+        for outpoint in &inputs {
+            self.spends.insert(*outpoint, option_id.to_string());
+        }
+        let raw_tx = bitcoin::consensus::serialize(tx);
+        let sent = broadcaster.rebroadcast(&raw_tx);
+        self.settlements.insert(
+            option_id.to_string(),
+            TrackedSettlement {
+                option_id: option_id.to_string(),
+                inputs,
+                txid: txid.clone(),
+                raw_tx,
+                status: SettlementTxStatus::Broadcast { txid: txid.clone() },
+                block_hash: None,
+                rebroadcasts: 0,
+            },
+        );
+
+        match sent {
+            Ok(_) => {
+                info!("Settlement tx {} for {} broadcast", txid, option_id);
+                Ok(txid)
+            }
+            Err(e) => {
+                warn!("Settlement tx {} for {} not accepted yet: {}", txid, option_id, e);
+                Err(e)
+            }
+        }
+    }
+
+    /// 체인 상태를 조회해 정산 트랜잭션 상태 갱신
+    ///
+    /// 정산 완료된 트랜잭션은 더 이상 조회하지 않습니다.
+    #[instrument(level = "info", skip_all, fields(settlements = self.settlements.len()))]
+    pub fn poll(
+        &mut self,
+        chain: &dyn ChainSource,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<Vec<SettlementAlert>, AnchorError> {
+        let tip = chain.tip_height()?;
+        let profile = self.profile;
+        let mut alerts = Vec::new();
+
+        for settlement in self.settlements.values_mut().filter(|s| !s.is_settled()) {
+            let was_confirmed = matches!(settlement.status, SettlementTxStatus::Confirmed { .. });
+            let reorged = |settlement: &TrackedSettlement| SettlementAlert::Reorged {
+                option_id: settlement.option_id.clone(),
+                txid: settlement.txid.clone(),
+            };
+
+            match chain.tx_status(&settlement.txid)? {
+                TxStatus::Confirmed {
+                    block_height,
+                    block_hash,
+                } => {
+                    if settlement.block_hash.as_ref().is_some_and(|hash| *hash != block_hash) {
+                        warn!(
+                            "Settlement tx {} for {} moved to block {}",
+                            settlement.txid, settlement.option_id, block_hash
+                        );
+                        alerts.push(reorged(settlement));
+                    }
+                    let confirmations = tip.saturating_sub(block_height) + 1;
+                    settlement.block_hash = Some(block_hash);
+                    if profile.is_final(confirmations) {
+                        info!(
+                            "Settlement tx {} for {} final ({} confirmations)",
+                            settlement.txid, settlement.option_id, confirmations
+                        );
+                        settlement.status = SettlementTxStatus::Settled {
+                            txid: settlement.txid.clone(),
+                            block_height,
+                        };
+                        alerts.push(SettlementAlert::Settled {
+                            option_id: settlement.option_id.clone(),
+                            txid: settlement.txid.clone(),
+                            confirmations,
+                        });
+                    } else {
+                        settlement.status = SettlementTxStatus::Confirmed {
+                            txid: settlement.txid.clone(),
+                            block_height,
+                            confirmations,
+                        };
+                    }
+                }
+                TxStatus::InMempool => {
+                    if was_confirmed {
+                        warn!("Settlement tx {} for {} dropped back to mempool", settlement.txid, settlement.option_id);
+                        alerts.push(reorged(settlement));
+                    }
+                    settlement.block_hash = None;
+                    settlement.status = SettlementTxStatus::Broadcast {
+                        txid: settlement.txid.clone(),
+                    };
+                }
+                TxStatus::NotFound => {
+                    warn!("Settlement tx {} for {} not found on chain", settlement.txid, settlement.option_id);
+                    if was_confirmed {
+                        alerts.push(reorged(settlement));
+                    }
+                    settlement.block_hash = None;
+                    alerts.push(Self::rebroadcast(settlement, broadcaster));
+                }
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// 같은 원본 재전송 (새 트랜잭션을 만들지 않으므로 UTXO 충돌 없음)
+    fn rebroadcast(settlement: &mut TrackedSettlement, broadcaster: &dyn AnchorBroadcaster) -> SettlementAlert {
+        match broadcaster.rebroadcast(&settlement.raw_tx) {
+            Ok(_) => {
+                settlement.rebroadcasts += 1;
+                settlement.status = SettlementTxStatus::Broadcast {
+                    txid: settlement.txid.clone(),
+                };
+                SettlementAlert::Rebroadcast {
+                    option_id: settlement.option_id.clone(),
+                    txid: settlement.txid.clone(),
+                }
+            }
+            Err(e) => {
+                settlement.status = SettlementTxStatus::Reorged {
+                    txid: settlement.txid.clone(),
+                };
+                SettlementAlert::Failed {
+                    option_id: settlement.option_id.clone(),
+                    txid: settlement.txid.clone(),
+                    error: e.to_string(),
+                }
+            }
+        }
+    }
+
+    /// 옵션 기록에 정산 트랜잭션 상태 반영
+    pub fn sync(&self, manager: &mut SimpleContractManager) {
+        for settlement in self.settlements.values() {
+            manager.set_settlement_tx_status(&settlement.option_id, settlement.status.clone());
+        }
+    }
+}
+
+/// `/admin/settlements` API (운영자가 서명한 정산 트랜잭션 제출/상태 조회)
+///
+/// 전송에 쓸 `AnchorBroadcaster`가 필요하므로 관리자 라우터와 따로 마운트하며,
+/// 경로가 `/admin/` 아래라 운영자 토큰이 필요합니다.
+pub mod api {
+    use super::*;
+    use crate::admin_api::{bad_request, error_response, SharedManager};
+    use crate::simple_contract::OptionStatus;
+    use axum::{
+        extract::{Path, State},
+        http::StatusCode,
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use oracle_vm_common::SettlementError;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    type SettlementState = (SharedManager, Arc<dyn AnchorBroadcaster + Send + Sync>);
+
+    /// 정산 트랜잭션 제출 요청
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct SettlementSubmission {
+        /// 서명된 원시 트랜잭션 (hex)
+        pub tx: String,
+    }
+
+    async fn list(State((manager, _)): State<SettlementState>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let settlements: Vec<_> = manager
+            .settlement_broadcasts()
+            .map(|settlements| {
+                settlements
+                    .settlements()
+                    .map(|settlement| {
+                        json!({
+                            "option_id": settlement.option_id,
+                            "txid": settlement.txid,
+                            "status": settlement.status,
+                            "rebroadcasts": settlement.rebroadcasts,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Json(settlements).into_response()
+    }
+
+    async fn submit(
+        Path(option_id): Path<String>,
+        State((manager, broadcaster)): State<SettlementState>,
+        Json(request): Json<SettlementSubmission>,
+    ) -> Response {
+        let tx: Transaction = match hex::decode(&request.tx)
+            .map_err(|e| e.to_string())
+            .and_then(|raw| bitcoin::consensus::deserialize(&raw).map_err(|e| e.to_string()))
+        {
+            Ok(tx) => tx,
+            Err(e) => return bad_request(format!("Invalid transaction: {}", e)),
+        };
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        // 정산 트랜잭션은 만기/행사로 끝난 옵션만 (해지는 협의 해지 API로)
+        match manager.options.get(&option_id).map(|option| option.status) {
+            None => return error_response(SettlementError::OptionNotFound(option_id)),
+            Some(OptionStatus::Active) => {
+                return bad_request(format!("Option {} is still active", option_id));
+            }
+            Some(_) => {}
+        }
+        match manager.broadcast_settlement(&option_id, &tx, broadcaster.as_ref()) {
+            Ok(txid) => Json(json!({
+                "option_id": option_id,
+                "txid": txid,
+                "status": manager.settlement_tx_status(&option_id),
+            }))
+            .into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    pub fn router(manager: SharedManager, broadcaster: Arc<dyn AnchorBroadcaster + Send + Sync>) -> Router {
+        Router::new()
+            .route("/admin/settlements", get(list))
+            .route("/admin/settlements/:id", post(submit))
+            .with_state((manager, broadcaster))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::hashes::Hash;
+    use bitcoin::{absolute::LockTime, Amount, ScriptBuf, Sequence, TxIn, TxOut, Txid, Witness};
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct MockChain {
+        tip: RefCell<u32>,
+        txs: RefCell<HashMap<String, TxStatus>>,
+    }
+
+    impl ChainSource for MockChain {
+        fn tip_height(&self) -> Result<u32, AnchorError> {
+            Ok(*self.tip.borrow())
+        }
+
+        fn tx_status(&self, txid: &str) -> Result<TxStatus, AnchorError> {
+            Ok(self.txs.borrow().get(txid).cloned().unwrap_or(TxStatus::NotFound))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockBroadcaster {
+        sent: RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl AnchorBroadcaster for MockBroadcaster {
+        fn rebroadcast(&self, raw_tx: &[u8]) -> Result<String, AnchorError> {
+            self.sent.borrow_mut().push(raw_tx.to_vec());
+            let tx: Transaction = bitcoin::consensus::deserialize(raw_tx)
+                .map_err(|e| AnchorError::BroadcastRejected(e.to_string()))?;
+            Ok(tx.compute_txid().to_string())
+        }
+
+        fn anchor(&self, _payload: &[u8]) -> Result<String, AnchorError> {
+            unreachable!()
+        }
+    }
+
+    fn settlement_tx(option_utxo: OutPoint, payout: u64) -> Transaction {
+        Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: option_utxo,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(payout),
+                script_pubkey: ScriptBuf::new(),
+            }],
+        }
+    }
+
+    fn confirmed(height: u32, hash: &str) -> TxStatus {
+        TxStatus::Confirmed {
+            block_height: height,
+            block_hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_settled_only_after_confirmations_and_rebroadcast_after_reorg() {
+        let chain = MockChain::default();
+        let broadcaster = MockBroadcaster::default();
+        let mut settlements = SettlementBroadcastManager::new(NetworkProfile::TESTNET);
+        let utxo = OutPoint::new(Txid::all_zeros(), 0);
+        let tx = settlement_tx(utxo, 90_000);
+        let txid = settlements.broadcast("CALL-1", &tx, &broadcaster).unwrap();
+        assert_eq!(settlements.spender(&utxo), Some("CALL-1"));
+
+        chain.txs.borrow_mut().insert(txid.clone(), confirmed(101, "b101"));
+        *chain.tip.borrow_mut() = 102;
+        assert!(settlements.poll(&chain, &broadcaster).unwrap().is_empty());
+        assert!(!settlements.is_settled("CALL-1"));
+
+        // 확인 전 reorg: 원본 그대로 재전송
+        chain.txs.borrow_mut().remove(&txid);
+        let alerts = settlements.poll(&chain, &broadcaster).unwrap();
+        assert_eq!(
+            alerts,
+            vec![
+                SettlementAlert::Reorged {
+                    option_id: "CALL-1".to_string(),
+                    txid: txid.clone()
+                },
+                SettlementAlert::Rebroadcast {
+                    option_id: "CALL-1".to_string(),
+                    txid: txid.clone()
+                },
+            ]
+        );
+        assert_eq!(broadcaster.sent.borrow().len(), 2);
+        assert_eq!(broadcaster.sent.borrow()[0], broadcaster.sent.borrow()[1]);
+
+        chain.txs.borrow_mut().insert(txid.clone(), confirmed(103, "b103"));
+        *chain.tip.borrow_mut() = 105;
+        let alerts = settlements.poll(&chain, &broadcaster).unwrap();
+        assert_eq!(
+            alerts,
+            vec![SettlementAlert::Settled {
+                option_id: "CALL-1".to_string(),
+                txid: txid.clone(),
+                confirmations: 3
+            }]
+        );
+        assert!(settlements.is_settled("CALL-1"));
+
+        let mut manager = SimpleContractManager::new();
+        settlements.sync(&mut manager);
+        assert_eq!(
+            manager.settlement_tx_status("CALL-1"),
+            Some(&SettlementTxStatus::Settled { txid, block_height: 103 })
+        );
+    }
+
+    #[test]
+    fn test_conflicting_settlement_rejected() {
+        let broadcaster = MockBroadcaster::default();
+        let mut settlements = SettlementBroadcastManager::new(NetworkProfile::TESTNET);
+        let utxo = OutPoint::new(Txid::all_zeros(), 1);
+        let tx = settlement_tx(utxo, 90_000);
+        let txid = settlements.broadcast("PUT-1", &tx, &broadcaster).unwrap();
+
+        // 같은 트랜잭션은 멱등, 같은 옵션의 다른 트랜잭션은 거부
+        assert_eq!(settlements.broadcast("PUT-1", &tx, &broadcaster).unwrap(), txid);
+        let bumped = settlement_tx(utxo, 80_000);
+        assert!(matches!(
+            settlements.broadcast("PUT-1", &bumped, &broadcaster),
+            Err(AnchorError::ConflictingSpend(_))
+        ));
+        // 다른 옵션이라도 점유된 UTXO는 쓸 수 없음
+        assert!(matches!(
+            settlements.broadcast("PUT-2", &bumped, &broadcaster),
+            Err(AnchorError::ConflictingSpend(_))
+        ));
+        assert_eq!(broadcaster.sent.borrow().len(), 1);
+        assert!(settlements.get("PUT-2").is_none());
+    }
+
+    #[test]
+    fn test_manager_tracks_settlements_across_snapshot() {
+        use crate::event_store::PoolEventKind;
+        use oracle_vm_common::types::OptionType;
+
+        let chain = MockChain::default();
+        let broadcaster = MockBroadcaster::default();
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option(
+                "CALL-1".to_string(),
+                OptionType::Call,
+                7_000_000,
+                1_000_000,
+                25_000,
+                800_000,
+                "user".to_string(),
+            )
+            .unwrap();
+        let utxo = OutPoint::new(Txid::all_zeros(), 0);
+        manager.watch_utxo("CALL-1", utxo).unwrap();
+        let tx = settlement_tx(utxo, 90_000);
+        // 추적이 꺼져 있으면 전송하지 않음
+        assert!(manager.broadcast_settlement("CALL-1", &tx, &broadcaster).is_err());
+        assert!(broadcaster.sent.borrow().is_empty());
+
+        manager.enable_settlement_broadcasts(SettlementBroadcastManager::new(NetworkProfile::TESTNET));
+        let txid = manager.broadcast_settlement("CALL-1", &tx, &broadcaster).unwrap();
+        assert_eq!(manager.broadcast_settlement("CALL-1", &tx, &broadcaster).unwrap(), txid);
+        assert!(manager
+            .broadcast_settlement("CALL-1", &settlement_tx(utxo, 80_000), &broadcaster)
+            .is_err());
+        assert_eq!(broadcaster.sent.borrow().len(), 1);
+        // 우리 정산 트랜잭션은 경쟁 지출로 보지 않음
+        assert_eq!(manager.mempool_watch().watched(&utxo).unwrap().expected_txid, Some(txid.clone()));
+
+        // 재시작해도 점유 UTXO와 추적이 남아 확인 수를 이어서 셈
+        let snapshot = manager.snapshot(0, Vec::new());
+        let mut restored = SimpleContractManager::restore(snapshot, &HashMap::new()).unwrap();
+        restored.enable_settlement_broadcasts(SettlementBroadcastManager::new(NetworkProfile::TESTNET));
+        assert_eq!(
+            restored.settlement_tx_status("CALL-1"),
+            Some(&SettlementTxStatus::Broadcast { txid: txid.clone() })
+        );
+        assert!(restored
+            .broadcast_settlement("CALL-1", &settlement_tx(utxo, 80_000), &broadcaster)
+            .is_err());
+
+        chain.txs.borrow_mut().insert(txid.clone(), confirmed(101, "b101"));
+        *chain.tip.borrow_mut() = 103;
+        let alerts = restored.poll_settlements(&chain, &broadcaster).unwrap();
+        assert!(matches!(alerts[..], [SettlementAlert::Settled { confirmations: 3, .. }]));
+        assert!(restored.settlement_broadcasts().unwrap().is_settled("CALL-1"));
+        assert!(restored.event_store().events().iter().any(|event| matches!(
+            &event.kind,
+            PoolEventKind::SettlementTxConfirmed { option_id, .. } if option_id == "CALL-1"
+        )));
+    }
+}
@@ -1,18 +1,69 @@
-use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::RangeBounds;
+use bitcoin::OutPoint;
+use oracle_vm_common::crypto::PublicKey;
 use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{
+    binary_payout, expiry_date_timestamp, AccountKeyError, Barrier, CanonicalEncode, CanonicalEncoder, ContractError, ContractSpec, DustHandling, ErrorClass, Exercise,
+    ExercisePolicy, ExerciseStyle, OpenInterest, OpenInterestCapacity, OpenInterestCaps, OpenInterestEntry, OptionQuote,
+    Payoff, PricingError, SettlementCurrency, SettlementError, SharedClock, SnapshotError, SystemClock,
+    SystemEvent, TreasuryError, UsdRail,
+};
+
+use crate::adl::{plan_haircuts, Haircut, HaircutPlan, PendingPayout};
+use crate::audit::{settle_anchor_payload, AuditAction, AuditLog};
+use crate::barrier::{BarrierState, BarrierTouch};
+use crate::beneficiary::{Beneficiary, BeneficiaryRegistry};
+use crate::block_time::BlockClock;
+use crate::binary::binary_settle_anchor_payload;
+use crate::early_exercise::exercise_anchor_payload;
+use crate::account_keys::{AccountKeys, AccountSignature};
+use crate::claimable::{ClaimRecords, ClaimableLedger};
+use crate::dual_currency::UsdPoolBook;
+use crate::event_store::{EventStore, InMemoryEventStore, PoolEventKind};
+use crate::funding::FundingBook;
+use crate::fees::{FeeKind, FeeSchedule, Treasury, TreasuryControls, TreasuryWithdrawal};
+use crate::lp_book::{ExitPlan, LpBook};
+use crate::mempool_watch::{ChallengeResponder, MempoolWatcher, SpendAlert};
+use crate::option_index::OptionIndex;
+use crate::pool_ledger::{PoolLedger, Posting};
+use crate::referral::ReferralProgram;
+use crate::reserve::ReserveMovement;
+use crate::snapshot::{AnchorRecord, AnchorSource, PendingSettlement, SystemSnapshot};
+use tracing::{instrument, warn};
+
+// 옵션 수명 주기 작업 (되사기, 롤, 협의 해지, 청구 잔고)
+mod buy_back;
+mod claims;
+mod cooperative_close;
+mod roll;
+
+// 관리자가 소유하는 하위 상태 (앵커, 정산 전송, 자격 확인, 합의 가격, 호가 규칙)
+mod anchors;
+mod consensus;
+mod eligibility;
+mod quotes;
+mod settlement_txs;
+
+use anchors::Anchors;
+use consensus::ConsensusPrice;
+use eligibility::Eligibility;
+use quotes::QuoteRules;
+use settlement_txs::SettlementTxs;
 
 /// 옵션 상태
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OptionStatus {
     Active,
     Expired,
     Settled,
+    /// 만기 전 풀이 되사서 종료
+    Cancelled,
 }
 
 /// 간단한 옵션 데이터
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SimpleOption {
     pub option_id: String,
     pub option_type: OptionType,
@@ -22,10 +73,125 @@ pub struct SimpleOption {
     pub expiry_height: u32,
     pub status: OptionStatus,
     pub user_id: String, // 사용자 식별자
+    /// 옵션별 계약 조건 (기본값이면 직렬화하지 않음)
+    #[serde(flatten)]
+    pub terms: OptionTerms,
+}
+
+/// 옵션별 계약 조건 (옵션과 함께 스냅샷에 저장/복원)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionTerms {
+    /// 미국식이면 만기 전 행사 가능
+    #[serde(default, skip_serializing_if = "ExerciseStyle::is_european")]
+    pub style: ExerciseStyle,
+    /// 바이너리면 ITM일 때 수량(satoshis)을 고정 지급
+    #[serde(default, skip_serializing_if = "Payoff::is_vanilla")]
+    pub payoff: Payoff,
+    /// 배리어와 접촉 기록
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barrier: Option<BarrierState>,
+    /// USD면 프리미엄/지급을 USD 잔고로 처리
+    #[serde(default, skip_serializing_if = "SettlementCurrency::is_btc")]
+    pub settlement_currency: SettlementCurrency,
+    /// 확정 호가로 연 옵션의 호가 만기 (Calculation에 미결제약정을 보고할 때의 키)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_expiry: Option<String>,
+}
+
+impl OptionTerms {
+    /// 확정 호가의 조건 (배리어는 아직 접촉 전)
+    pub fn from_quote(quote: &OptionQuote) -> Self {
+        Self {
+            style: quote.style,
+            payoff: quote.payoff,
+            barrier: quote.barrier.map(|barrier| BarrierState {
+                barrier,
+                touched: None,
+            }),
+            settlement_currency: SettlementCurrency::Btc,
+            quote_expiry: Some(quote.expiry.clone()),
+        }
+    }
+
+    /// USD로 프리미엄을 받고 USD로 지급하는 조건
+    pub fn usd() -> Self {
+        Self {
+            settlement_currency: SettlementCurrency::Usd,
+            ..Self::default()
+        }
+    }
+}
+
+/// 블록 헤더를 모를 때 쓰는 평균 블록 간격 (초)
+pub const AVG_BLOCK_SECS: u64 = 600;
+
+/// 옵션 담보금 (satoshis)
+pub fn collateral_for(option_type: OptionType, strike_price: u64, quantity: u64) -> u64 {
+    match option_type {
+        OptionType::Call => quantity,
+        OptionType::Put => (strike_price * quantity) / 100_000_000, // USD to BTC conversion
+    }
+}
+
+/// 페이오프별 담보금 (바이너리는 고정 지급액인 수량만 잠금)
+pub fn payoff_collateral(payoff: Payoff, option_type: OptionType, strike_price: u64, quantity: u64) -> u64 {
+    match payoff {
+        Payoff::Vanilla => collateral_for(option_type, strike_price, quantity),
+        Payoff::Binary => quantity,
+    }
+}
+
+impl SimpleOption {
+    /// 옵션 담보금 (바이너리는 고정 지급액)
+    pub fn collateral(&self) -> u64 {
+        payoff_collateral(self.terms.payoff, self.option_type, self.strike_price, self.quantity)
+    }
+
+    /// 정산 시 지급 대상인지 (배리어가 없으면 항상)
+    pub fn is_live(&self) -> bool {
+        self.terms.barrier.as_ref().is_none_or(BarrierState::is_live)
+    }
+
+    /// 정산 가격 기준 지급액 (OTM이면 0, 바이너리는 ITM이면 고정 지급액)
+    pub fn payout_at(&self, spot_price: u64) -> u64 {
+        if self.terms.payoff.is_binary() {
+            return binary_payout(self.option_type, self.strike_price, spot_price, self.quantity);
+        }
+        // ITM 여부 확인
+        let intrinsic_value = match self.option_type {
+            OptionType::Call => spot_price.saturating_sub(self.strike_price),
+            OptionType::Put => self.strike_price.saturating_sub(spot_price),
+        };
+        // USD cents를 satoshis로 변환
+        (intrinsic_value * self.quantity) / 100_000_000
+    }
+}
+
+/// 자동 행사 결과 중 풀에서 나가는 금액 (풀 수익으로 남긴 dust는 0)
+pub fn exercised_amount(exercise: &Exercise) -> u64 {
+    match *exercise {
+        Exercise::Paid { amount }
+        | Exercise::Dust {
+            amount,
+            handling: DustHandling::Accumulate,
+        } => amount,
+        _ => 0,
+    }
+}
+
+/// 옵션 정산 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementRecord {
+    pub option_id: String,
+    pub spot_price: u64, // USD cents
+    pub currency: SettlementCurrency,
+    /// 자동 행사 정책 적용 결과 (금액은 결제 통화 단위)
+    pub exercise: Exercise,
+    pub settled_at: u64,
 }
 
 /// 간단한 풀 상태
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SimplePoolState {
     pub total_liquidity: u64,         // satoshis
     pub locked_collateral: u64,       // satoshis
@@ -33,6 +199,15 @@ pub struct SimplePoolState {
     pub total_premium_collected: u64, // satoshis
     pub total_payout: u64,            // satoshis
     pub active_options: u32,
+    /// 수익처에 예치한 유휴 유동성 (satoshis, total에 포함)
+    #[serde(default)]
+    pub reserve_balance: u64,
+    /// 선납받았지만 LP에게 아직 적립하지 않은 담보 사용료 (satoshis, total에 미포함)
+    #[serde(default)]
+    pub unaccrued_funding: u64,
+    /// LP 유동성으로 적립한 누적 담보 사용료 (satoshis)
+    #[serde(default)]
+    pub total_funding_accrued: u64,
 }
 
 impl SimplePoolState {
@@ -44,6 +219,9 @@ impl SimplePoolState {
             total_premium_collected: 0,
             total_payout: 0,
             active_options: 0,
+            reserve_balance: 0,
+            unaccrued_funding: 0,
+            total_funding_accrued: 0,
         }
     }
 
@@ -61,251 +239,3746 @@ impl Default for SimplePoolState {
     }
 }
 
+/// 옵션 생성 요청 (멱등 키 재시도 비교용)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateOptionRequest {
+    pub option_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub quantity: u64,     // satoshis
+    pub premium: u64,      // satoshis
+    pub expiry_height: u32,
+    pub user_id: String,
+    /// 추천 코드 (없으면 확장 필드를 쓰지 않아 기존 요청 해시 유지)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+}
+
+impl CreateOptionRequest {
+    /// 요청 내용 해시 (같은 키로 다른 요청을 보내면 감지)
+    pub fn request_hash(&self) -> String {
+        hex::encode(self.canonical_hash())
+    }
+}
+
+impl CanonicalEncode for CreateOptionRequest {
+    const DOMAIN: &'static str = "create_option/v1";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder
+            .str(&self.option_id)
+            .option_type(self.option_type)
+            .u64(self.strike_price)
+            .u64(self.quantity)
+            .u64(self.premium)
+            .u32(self.expiry_height)
+            .str(&self.user_id);
+        if let Some(code) = &self.referral_code {
+            encoder.extension(1).str(code);
+        }
+    }
+}
+
+/// 멱등 키별로 저장한 처리 결과
+#[derive(Debug, Clone)]
+struct IdempotentOutcome {
+    request_hash: String,
+    result: Result<(), ContractError>,
+}
+
+/// 새 옵션이 프리미엄에서 선납할 담보 사용료와 적립 기간
+#[derive(Debug, Clone, Copy, Default)]
+struct FundingCharge {
+    amount: u64,
+    secs: u64,
+}
+
+/// 거래 중단 상태 (kill-switch)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradingHalt {
+    pub reason: String,
+    pub halted_at: u64,
+    pub resume_at: Option<u64>, // 자동 재개 시각 (None이면 수동 재개)
+}
+
+/// 풀 리스크 한도 (None = 제한 없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskLimits {
+    /// 옵션 한 건의 최대 담보 (satoshis)
+    pub max_option_collateral: Option<u64>,
+    /// 풀 전체 최대 잠금 담보 (satoshis)
+    pub max_locked_collateral: Option<u64>,
+}
+
+impl RiskLimits {
+    /// 잠금 담보가 `locked`인 풀에 `collateral`을 더 잠글 수 있는지 검사
+    pub fn check(&self, collateral: u64, locked: u64) -> Result<(), ContractError> {
+        if let Some(max) = self.max_option_collateral {
+            if collateral > max {
+                return Err(ContractError::RiskLimitExceeded(format!(
+                    "option collateral {} sats exceeds {}",
+                    collateral, max
+                )));
+            }
+        }
+        if let Some(max) = self.max_locked_collateral {
+            if locked + collateral > max {
+                return Err(ContractError::RiskLimitExceeded(format!(
+                    "locked collateral {} sats would exceed {}",
+                    locked + collateral,
+                    max
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// 간단한 컨트랙트 관리자
 pub struct SimpleContractManager {
     pub options: HashMap<String, SimpleOption>,
+    /// 만기/행사가/상태/사용자 보조 인덱스
+    index: OptionIndex,
     pub pool_state: SimplePoolState,
+    ledger: PoolLedger,
+    event_store: Box<dyn EventStore>,
+    trading_halt: Option<TradingHalt>,
+    /// 호가 서명키, 사용한 호가, 캘린더, 계약 규격, 테넌트
+    quotes: QuoteRules,
+    /// 멱등 키 → 요청 해시/결과
+    idempotency: HashMap<String, IdempotentOutcome>,
+    /// 앵커 상태와 확인 추적
+    anchors: Anchors,
+    /// 옵션 UTXO 경쟁 지출 감시 (감시 대상과 챌린지는 스냅샷에 기록)
+    mempool_watch: MempoolWatcher,
+    /// 정산 트랜잭션 상태와 전송 추적
+    settlement_txs: SettlementTxs,
+    /// USD 결제 옵션용 USD 잔고 (설정 시 USD 결제 옵션 허용)
+    usd_book: Option<UsdPoolBook>,
+    /// 자동 행사/dust 지급 정책
+    exercise_policy: ExercisePolicy,
+    /// 옵션 ID → 정산 결과
+    settlements: HashMap<String, SettlementRecord>,
+    /// 사용자 → 누적된 dust 지급액 (satoshis)
+    dust_balances: HashMap<String, u64>,
+    /// 사용자별 청구 가능 잔고 (설정 시 BTC 지급을 적립 후 모아서 출금)
+    claims: Option<ClaimableLedger>,
+    /// 스냅샷에서 복원했지만 청구 잔고가 아직 활성화되지 않은 적립/출금 기록
+    claim_records: Option<ClaimRecords>,
+    /// 옵션별 수익자 (설정 시 수익자가 있는 옵션의 적립은 수익자 주소로 지급)
+    beneficiaries: Option<BeneficiaryRegistry>,
+    /// 스냅샷에서 복원했지만 수익자 등록부가 아직 활성화되지 않은 수익자
+    beneficiary_records: Vec<Beneficiary>,
+    /// 보유자/LP 계정 공개키와 요청 nonce
+    account_keys: AccountKeys,
+    /// 구매 자격 확인 훅과 옵션별 확인 결과 해시
+    eligibility: Eligibility,
+    /// 옵션별 해시 체인 감사 기록
+    audit: AuditLog,
+    /// 프로토콜/정산 수수료율
+    fee_schedule: FeeSchedule,
+    /// 수수료가 적립되는 재무 계정 (LP 유동성과 분리)
+    treasury: Treasury,
+    /// 추천 코드와 리베이트 집계
+    referrals: ReferralProgram,
+    /// 옵션/풀 담보 한도
+    risk_limits: RiskLimits,
+    /// 행사가/만기별 미결제약정 한도 (풀 유동성 대비 %)
+    open_interest_caps: OpenInterestCaps,
+    /// 마지막으로 본 블록 높이 (없으면 만기 감축 없이 전체 한도 적용)
+    tip_height: Option<u32>,
+    /// 실제 블록 헤더 기반 높이 ↔ 시각 환산 (비어 있으면 `AVG_BLOCK_SECS`로 어림)
+    block_clock: BlockClock,
+    /// LP 지분과 부분 출금 청구권
+    lp_book: LpBook,
+    /// 마지막 합의 가격과 정산 가격 가드
+    consensus: ConsensusPrice,
+    /// 풀 부족분 분담으로 정한 옵션별 지급 삭감
+    haircuts: BTreeMap<String, Haircut>,
+    /// 잠긴 담보 사용료율과 옵션별 선납분 적립 일정
+    funding: FundingBook,
+    /// 현재 시각 (테스트에서는 ManualClock으로 시간 이동)
+    clock: SharedClock,
 }
 
 impl SimpleContractManager {
     pub fn new() -> Self {
+        Self::with_event_store(Box::new(InMemoryEventStore::new()))
+    }
+
+    /// 지정한 시계를 사용하는 관리자 생성
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self::with_event_store_and_clock(Box::new(InMemoryEventStore::new()), clock)
+    }
+
+    /// 지정한 이벤트 저장소를 사용하는 관리자 생성
+    pub fn with_event_store(event_store: Box<dyn EventStore>) -> Self {
+        Self::with_event_store_and_clock(event_store, SystemClock::shared())
+    }
+
+    /// 지정한 이벤트 저장소와 시계를 사용하는 관리자 생성
+    pub fn with_event_store_and_clock(event_store: Box<dyn EventStore>, clock: SharedClock) -> Self {
         Self {
             options: HashMap::new(),
+            index: OptionIndex::new(),
             pool_state: SimplePoolState::new(),
+            ledger: PoolLedger::new(),
+            event_store,
+            trading_halt: None,
+            quotes: QuoteRules::default(),
+            idempotency: HashMap::new(),
+            anchors: Anchors::default(),
+            mempool_watch: MempoolWatcher::new(),
+            settlement_txs: SettlementTxs::default(),
+            usd_book: None,
+            exercise_policy: ExercisePolicy::default(),
+            settlements: HashMap::new(),
+            dust_balances: HashMap::new(),
+            claims: None,
+            claim_records: None,
+            beneficiaries: None,
+            beneficiary_records: Vec::new(),
+            account_keys: AccountKeys::new(),
+            eligibility: Eligibility::default(),
+            audit: AuditLog::new(),
+            fee_schedule: FeeSchedule::default(),
+            treasury: Treasury::new(),
+            referrals: ReferralProgram::new(),
+            risk_limits: RiskLimits::default(),
+            open_interest_caps: OpenInterestCaps::default(),
+            tip_height: None,
+            block_clock: BlockClock::new(),
+            lp_book: LpBook::new(),
+            consensus: ConsensusPrice::default(),
+            haircuts: BTreeMap::new(),
+            funding: FundingBook::default(),
+            clock,
         }
     }
 
-}
-
-impl Default for SimpleContractManager {
-    fn default() -> Self {
-        Self::new()
+    /// 현재 시각 (Unix seconds)
+    pub fn now(&self) -> u64 {
+        self.clock.now()
     }
-}
 
-impl SimpleContractManager {
-    /// 유동성 추가
-    pub fn add_liquidity(&mut self, amount: u64) -> Result<()> {
-        self.pool_state.total_liquidity += amount;
-        self.pool_state.available_liquidity += amount;
-        Ok(())
+    /// USD 결제 활성화, 이후 USD 프리미엄/지급 옵션 생성 가능
+    pub fn enable_usd_settlement(&mut self, rail: UsdRail) {
+        self.usd_book.get_or_insert_with(|| UsdPoolBook::new(rail));
     }
 
-    /// 옵션 생성
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_option(
-        &mut self,
-        option_id: String,
-        option_type: OptionType,
-        strike_price: u64,
-        quantity: u64,
-        premium: u64,
-        expiry_height: u32,
-        user_id: String,
-    ) -> Result<()> {
-        // 담보금 계산
-        let collateral = match option_type {
-            OptionType::Call => quantity,
-            OptionType::Put => (strike_price * quantity) / 100_000_000, // USD to BTC conversion
-        };
-
-        // 사용 가능한 유동성 확인
-        if self.pool_state.available_liquidity < collateral {
-            return Err(anyhow::anyhow!("Insufficient liquidity"));
-        }
+    /// 자동 행사 정책 설정, 이후 호가의 정책이 다르면 거부
+    pub fn set_exercise_policy(&mut self, policy: ExercisePolicy) {
+        self.exercise_policy = policy;
+    }
 
-        // 옵션 생성
-        let option = SimpleOption {
-            option_id: option_id.clone(),
-            option_type,
-            strike_price,
-            quantity,
-            premium_paid: premium,
-            expiry_height,
-            status: OptionStatus::Active,
-            user_id,
-        };
+    pub fn exercise_policy(&self) -> &ExercisePolicy {
+        &self.exercise_policy
+    }
 
-        // 상태 업데이트
-        self.options.insert(option_id, option);
-        self.pool_state.available_liquidity -= collateral;
-        self.pool_state.locked_collateral += collateral;
-        self.pool_state.total_premium_collected += premium;
-        self.pool_state.total_liquidity += premium;
-        self.pool_state.available_liquidity += premium; // 프리미엄은 사용 가능한 유동성에 추가
-        self.pool_state.active_options += 1;
+    /// 정산 결과 (정산 전이면 None)
+    pub fn settlement(&self, option_id: &str) -> Option<&SettlementRecord> {
+        self.settlements.get(option_id)
+    }
 
-        Ok(())
+    /// 사용자의 누적 dust 지급액 (satoshis)
+    pub fn dust_balance(&self, user_id: &str) -> u64 {
+        self.dust_balances.get(user_id).copied().unwrap_or(0)
     }
 
-    /// 옵션 정산
-    pub fn settle_option(&mut self, option_id: &str, spot_price: u64) -> Result<u64> {
-        let option = self
-            .options
-            .get_mut(option_id)
-            .ok_or_else(|| anyhow::anyhow!("Option not found"))?;
+    /// 리스크 한도 변경 (이후 생성부터 적용)
+    pub fn set_risk_limits(&mut self, limits: RiskLimits) {
+        self.risk_limits = limits;
+    }
 
-        if option.status != OptionStatus::Active {
-            return Err(anyhow::anyhow!("Option not active"));
-        }
+    pub fn risk_limits(&self) -> RiskLimits {
+        self.risk_limits
+    }
 
-        // ITM 여부 확인
-        let is_itm = match option.option_type {
-            OptionType::Call => spot_price > option.strike_price,
-            OptionType::Put => spot_price < option.strike_price,
-        };
+    /// 행사가/만기별 미결제약정 한도 변경 (이후 생성부터 적용)
+    pub fn set_open_interest_caps(&mut self, caps: OpenInterestCaps) {
+        self.open_interest_caps = caps;
+    }
 
-        let payout = if is_itm {
-            let intrinsic_value = match option.option_type {
-                OptionType::Call => spot_price - option.strike_price,
-                OptionType::Put => option.strike_price - spot_price,
-            };
-            // USD cents를 satoshis로 변환
-            (intrinsic_value * option.quantity) / 100_000_000
-        } else {
-            0
-        };
+    pub fn open_interest_caps(&self) -> OpenInterestCaps {
+        self.open_interest_caps
+    }
 
-        // 담보금 계산
-        let collateral = match option.option_type {
-            OptionType::Call => option.quantity,
-            OptionType::Put => (option.strike_price * option.quantity) / 100_000_000,
-        };
+    /// 현재 블록 높이 기록 (만기가 가까운 한도 감축 기준)
+    pub fn observe_height(&mut self, height: u32) {
+        self.tip_height = Some(self.tip_height.map_or(height, |tip| tip.max(height)));
+    }
 
-        // 상태 업데이트
-        option.status = OptionStatus::Settled;
-        self.pool_state.locked_collateral -= collateral;
+    /// 마지막으로 관측한 블록 높이
+    pub fn tip_height(&self) -> Option<u32> {
+        self.tip_height
+    }
 
-        if payout > 0 {
-            self.pool_state.total_payout += payout;
-            self.pool_state.total_liquidity -= payout;
-            // 잔여 담보금은 풀로 반환
-            self.pool_state.available_liquidity += collateral - payout;
-        } else {
-            // OTM인 경우 전체 담보금이 풀로 반환
-            self.pool_state.available_liquidity += collateral;
+    /// 노드에서 동기화한 블록 헤더 반영 (팁 높이도 함께 기록)
+    pub fn set_block_clock(&mut self, block_clock: BlockClock) {
+        if let Some((tip, _)) = block_clock.tip() {
+            self.observe_height(tip);
         }
+        self.block_clock = block_clock;
+    }
 
-        self.pool_state.active_options -= 1;
+    pub fn block_clock(&self) -> &BlockClock {
+        &self.block_clock
+    }
 
-        Ok(payout)
+    /// 활성 옵션이 잠근 담보 (같은 만기의 `strike_price`, 만기 전체)
+    pub fn open_interest(&self, strike_price: u64, expiry_height: u32) -> OpenInterest {
+        self.index
+            .by_expiry_range(expiry_height..=expiry_height)
+            .filter(|id| self.index.has_status(id, OptionStatus::Active))
+            .filter_map(|id| self.options.get(id))
+            .fold(OpenInterest::default(), |mut open, option| {
+                let collateral = option.collateral();
+                open.expiry += collateral;
+                if option.strike_price == strike_price {
+                    open.strike += collateral;
+                }
+                open
+            })
     }
 
-    /// 만료된 옵션 조회
-    pub fn get_expired_options(&self, current_height: u32) -> Vec<&SimpleOption> {
-        self.options
-            .values()
-            .filter(|option| {
-                option.status == OptionStatus::Active && current_height >= option.expiry_height
+    /// 확정 호가로 연 활성 옵션의 행사가/호가 만기별 잠긴 담보
+    ///
+    /// Calculation은 이 표로 미결제약정을 통째로 바꾸므로 종료/행사/만기된 옵션은
+    /// 다음 보고에서 빠집니다.
+    pub fn open_interest_report(&self) -> Vec<OpenInterestEntry> {
+        let mut buckets: BTreeMap<(&str, u64), u64> = BTreeMap::new();
+        for option in self.options.values() {
+            let Some(expiry) = option.terms.quote_expiry.as_deref() else {
+                continue;
+            };
+            if option.status != OptionStatus::Active {
+                continue;
+            }
+            *buckets.entry((expiry, option.strike_price)).or_default() += option.collateral();
+        }
+        buckets
+            .into_iter()
+            .map(|((expiry, strike_price), collateral)| OpenInterestEntry {
+                strike_price,
+                expiry: expiry.to_string(),
+                collateral,
             })
             .collect()
     }
 
-    /// 시스템 상태 조회
-    pub fn get_system_status(&self) -> serde_json::Value {
-        serde_json::json!({
-            "pool_state": self.pool_state,
-            "total_options": self.options.len(),
-            "active_options": self.pool_state.active_options,
-            "utilization_rate": format!("{:.2}%", self.pool_state.utilization_rate()),
-            "profit_loss": self.pool_state.total_premium_collected as i64 - self.pool_state.total_payout as i64
-        })
+    /// 행사가/만기에 새로 잠글 수 있는 담보 (호가 화면 공시용)
+    pub fn open_interest_capacity(&self, strike_price: u64, expiry_height: u32) -> OpenInterestCapacity {
+        self.open_interest_caps.capacity(
+            self.pool_state.total_liquidity,
+            self.open_interest(strike_price, expiry_height),
+            self.secs_to_expiry(expiry_height),
+        )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// 만기까지 남은 예상 시간 (헤더가 있으면 헤더 시각 기준, 높이를 모르면 감축하지 않도록 최대값)
+    fn secs_to_expiry(&self, expiry_height: u32) -> u64 {
+        if let Some(secs) = self.block_clock.secs_until(expiry_height, self.clock.now()) {
+            return secs;
+        }
+        match self.tip_height {
+            Some(tip) => expiry_height.saturating_sub(tip) as u64 * AVG_BLOCK_SECS,
+            None => u64::MAX,
+        }
+    }
 
-    #[test]
-    fn test_call_option_itm() {
-        let mut manager = SimpleContractManager::new();
+    /// 수수료율 변경 (이후 생성/정산부터 적용)
+    pub fn set_fee_schedule(&mut self, schedule: FeeSchedule) -> Result<(), TreasuryError> {
+        schedule.validate()?;
+        self.fee_schedule = schedule;
+        Ok(())
+    }
 
-        // 유동성 추가: 1 BTC
-        manager.add_liquidity(100_000_000).unwrap();
+    pub fn fee_schedule(&self) -> FeeSchedule {
+        self.fee_schedule
+    }
 
-        // Call 옵션 생성: Strike $70,000, Quantity 0.1 BTC, Premium 0.0025 BTC
-        manager
-            .create_option(
-                "CALL-001".to_string(),
-                OptionType::Call,
-                7_000_000,  // $70,000 in cents
-                10_000_000, // 0.1 BTC in sats
-                250_000,    // 0.0025 BTC premium
-                800_000,
-                "user1".to_string(),
-            )
-            .unwrap();
+    /// 잠긴 담보 연 사용료율 설정 (bps, 이후 생성 옵션부터 프리미엄에서 선납분을 뗌)
+    pub fn set_funding_rate(&mut self, annual_bps: u32) {
+        self.funding.set_annual_bps(annual_bps);
+    }
 
-        // 정산: Spot $72,000 (ITM)
-        let payout = manager.settle_option("CALL-001", 7_200_000).unwrap();
+    pub fn funding(&self) -> &FundingBook {
+        &self.funding
+    }
 
-        // $2,000 profit on 0.1 BTC ≈ 277,777 sats (assuming $72k BTC price)
-        assert!(payout > 0);
-        assert_eq!(manager.pool_state.active_options, 0);
+    pub fn treasury(&self) -> &Treasury {
+        &self.treasury
+    }
 
-        println!("Call ITM Payout: {} sats", payout);
-        println!(
-            "Pool utilization: {:.2}%",
-            manager.pool_state.utilization_rate()
-        );
+    /// 재무 계정 출금 통제 설정
+    pub fn set_treasury_controls(&mut self, controls: TreasuryControls) {
+        self.treasury.controls = controls;
     }
 
-    #[test]
-    fn test_put_option_itm() {
-        let mut manager = SimpleContractManager::new();
+    /// 재무 계정 출금 (허용 주소, 24시간 한도 검사 후 기록)
+    pub fn withdraw_treasury(
+        &mut self,
+        amount: u64,
+        destination: &str,
+    ) -> Result<TreasuryWithdrawal, TreasuryError> {
+        let now = self.clock.now();
+        self.treasury.check_withdrawal(amount, destination, now)?;
+        self.record_event(PoolEventKind::TreasuryWithdrawn {
+            amount,
+            destination: destination.to_string(),
+        })
+        .map_err(TreasuryError::Storage)?;
+        Ok(self.treasury.apply_withdrawal(amount, destination, now))
+    }
 
-        // 유동성 추가: 1 BTC
-        manager.add_liquidity(100_000_000).unwrap();
+    pub fn referrals(&self) -> &ReferralProgram {
+        &self.referrals
+    }
 
-        // Put 옵션 생성: Strike $65,000, Quantity 0.2 BTC
-        manager
-            .create_option(
-                "PUT-001".to_string(),
-                OptionType::Put,
-                6_500_000,  // $65,000 in cents
-                20_000_000, // 0.2 BTC in sats
-                180_000,    // 0.0018 BTC premium
-                800_000,
-                "user2".to_string(),
-            )
-            .unwrap();
+    pub fn referrals_mut(&mut self) -> &mut ReferralProgram {
+        &mut self.referrals
+    }
 
-        // 정산: Spot $63,000 (ITM)
-        let payout = manager.settle_option("PUT-001", 6_300_000).unwrap();
+    pub fn lp_book(&self) -> &LpBook {
+        &self.lp_book
+    }
 
-        // $2,000 profit on 0.2 BTC
-        assert!(payout > 0);
+    /// 만기까지의 담보 사용료 (수수료를 뺀 프리미엄 `premium_net`을 넘지 않음)
+    ///
+    /// 블록 높이를 모르면 잔존 기간을 알 수 없으므로 떼지 않습니다. 생성과 롤이
+    /// 같은 계산을 씁니다.
+    fn funding_charge(&self, collateral: u64, expiry_height: u32, premium_net: u64) -> FundingCharge {
+        let secs = self
+            .tip_height
+            .map_or(0, |_| self.secs_to_expiry(expiry_height));
+        FundingCharge {
+            amount: self.funding.charge(collateral, secs).min(premium_net),
+            secs,
+        }
+    }
 
-        println!("Put ITM Payout: {} sats", payout);
-        println!("System status: {}", manager.get_system_status());
+    /// 선납 사용료를 이벤트로 기록 (선납분이 없으면 아무것도 하지 않음)
+    fn record_funding_prepaid(&mut self, option_id: &str, charge: FundingCharge, now: u64) -> Result<(), String> {
+        if charge.amount == 0 {
+            return Ok(());
+        }
+        self.record_event(PoolEventKind::FundingPrepaid {
+            option_id: option_id.to_string(),
+            amount: charge.amount,
+            accrue_until: now + charge.secs,
+        })
     }
 
-    #[test]
-    fn test_option_otm() {
-        let mut manager = SimpleContractManager::new();
+    /// 기록한 선납 사용료의 적립 일정 시작
+    fn open_funding(&mut self, option_id: &str, charge: FundingCharge, now: u64) {
+        if charge.amount > 0 {
+            self.funding.open(option_id, charge.amount, now, now + charge.secs);
+        }
+    }
 
-        manager.add_liquidity(100_000_000).unwrap();
+    pub fn account_keys(&self) -> &AccountKeys {
+        &self.account_keys
+    }
 
-        // Call 옵션 생성
-        manager
-            .create_option(
-                "CALL-OTM".to_string(),
-                OptionType::Call,
-                7_500_000,  // $75,000 strike
-                10_000_000, // 0.1 BTC
-                300_000,    // premium
-                800_000,
-                "user3".to_string(),
-            )
-            .unwrap();
+    /// 새 계정에 공개키 등록 (이미 같은 키면 그대로 통과)
+    ///
+    /// 옵션, 청구 잔고, LP 지분이 이미 있는 계정은 ID를 아는 누구나 키를 선점할 수
+    /// 있으므로 거부하며, 그런 계정은 운영자가 `bind_account_key`로 묶습니다.
+    pub fn register_account_key(&mut self, account: &str, key: PublicKey) -> Result<(), AccountKeyError> {
+        self.check_account_key(account, &key)?;
+        self.bind_account_key(account, key)
+    }
 
-        // 정산: Spot $73,000 (OTM)
-        let payout = manager.settle_option("CALL-OTM", 7_300_000).unwrap();
+    /// 사용자가 키를 묶을 수 있는지 확인 (상태는 바꾸지 않음)
+    pub fn check_account_key(&self, account: &str, key: &PublicKey) -> Result<(), AccountKeyError> {
+        self.account_keys.check_bind(account, key)?;
+        if self.account_keys.key(account).is_none() && self.has_account_state(account) {
+            return Err(AccountKeyError::Existing(account.to_string()));
+        }
+        Ok(())
+    }
 
-        assert_eq!(payout, 0);
-        assert_eq!(manager.pool_state.active_options, 0);
+    /// 운영자 계정 키 등록 (기존 계정 포함, 다른 키가 이미 묶였으면 거부)
+    pub fn bind_account_key(&mut self, account: &str, key: PublicKey) -> Result<(), AccountKeyError> {
+        self.account_keys.check_bind(account, &key)?;
+        if self.account_keys.key(account).is_some() {
+            return Ok(());
+        }
+        self.record_event(PoolEventKind::AccountKeyBound {
+            account: account.to_string(),
+            public_key: key.to_string(),
+        })
+        .map_err(AccountKeyError::Storage)?;
+        self.account_keys.bind(account, key)?;
+        Ok(())
+    }
 
-        println!("Call OTM Payout: {} sats (should be 0)", payout);
+    /// 계정 서명 확인 후 nonce 사용 처리
+    pub fn authorize_account(
+        &mut self,
+        account: &str,
+        payload: &[u8],
+        auth: &AccountSignature,
+    ) -> Result<(), AccountKeyError> {
+        self.account_keys.authorize(account, payload, auth)
+    }
+
+    /// 옵션, 청구 잔고, dust, LP 지분/청구권 중 하나라도 있는 계정인지
+    fn has_account_state(&self, account: &str) -> bool {
+        self.index.by_user(account).next().is_some()
+            || self.dust_balances.contains_key(account)
+            || self
+                .claims
+                .as_ref()
+                .is_some_and(|claims| !claims.credits(account).is_empty())
+            || self.lp_book.shares(account) > 0
+            || self.lp_book.claims_of(account).next().is_some()
+    }
+
+    /// 풀의 USD 측 회계 (USD 결제 비활성이면 None)
+    pub fn usd_book(&self) -> Option<&UsdPoolBook> {
+        self.usd_book.as_ref()
+    }
+
+    /// 풀 이벤트 저장소
+    pub fn event_store(&self) -> &dyn EventStore {
+        self.event_store.as_ref()
+    }
+
+    /// 풀 회계 원장
+    pub fn ledger(&self) -> &PoolLedger {
+        &self.ledger
+    }
+
+    /// 옵션 UTXO를 경쟁 지출 감시 대상으로 등록
+    pub fn watch_utxo(&mut self, option_id: &str, outpoint: OutPoint) -> Result<(), ContractError> {
+        if !self.options.contains_key(option_id) {
+            return Err(ContractError::Ledger(format!("Option {} not found", option_id)));
+        }
+        if self.mempool_watch.watched(&outpoint).is_some() {
+            return Ok(());
+        }
+        self.record_event(PoolEventKind::UtxoWatched {
+            option_id: option_id.to_string(),
+            outpoint: outpoint.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+        self.mempool_watch.watch(option_id, outpoint);
+        if let Some(settlements) = self.settlement_txs.broadcasts() {
+            self.mempool_watch.expect_settlements(settlements);
+        }
+        Ok(())
+    }
+
+    pub fn mempool_watch(&self) -> &MempoolWatcher {
+        &self.mempool_watch
+    }
+
+    /// 멤풀 트랜잭션 하나를 감시 대상과 대조하고 경쟁 지출이면 챌린지 무장
+    ///
+    /// 새 경쟁 지출과 무장 완료를 이벤트로 남겨 스냅샷/웹훅에 반영합니다. 해석할
+    /// 수 없는 트랜잭션은 건너뜁니다.
+    pub fn observe_mempool_tx(
+        &mut self,
+        raw_tx: &[u8],
+        responder: &dyn ChallengeResponder,
+    ) -> Result<Vec<SpendAlert>, ContractError> {
+        let armed_before: HashSet<(OutPoint, String)> = self
+            .mempool_watch
+            .challenges()
+            .iter()
+            .filter(|challenge| challenge.armed)
+            .map(|challenge| (challenge.outpoint, challenge.spending_txid.clone()))
+            .collect();
+        let alerts = match self.mempool_watch.observe(raw_tx, responder) {
+            Ok(alerts) => alerts,
+            Err(e) => {
+                warn!("Skipping undecodable mempool tx: {}", e);
+                return Ok(Vec::new());
+            }
+        };
+
+        let mut events = Vec::new();
+        for alert in &alerts {
+            if let SpendAlert::Unexpected {
+                option_id,
+                outpoint,
+                txid,
+            } = alert
+            {
+                events.push(PoolEventKind::CompetingSpendDetected {
+                    option_id: option_id.clone(),
+                    outpoint: outpoint.to_string(),
+                    txid: txid.clone(),
+                });
+            }
+        }
+        for challenge in self.mempool_watch.challenges() {
+            if challenge.armed && !armed_before.contains(&(challenge.outpoint, challenge.spending_txid.clone())) {
+                events.push(PoolEventKind::ChallengeArmed {
+                    option_id: challenge.option_id.clone(),
+                    outpoint: challenge.outpoint.to_string(),
+                    txid: challenge.spending_txid.clone(),
+                });
+            }
+        }
+        for event in events {
+            self.record_event(event).map_err(ContractError::Storage)?;
+        }
+        Ok(alerts)
+    }
+
+    /// 옵션별 감사 기록
+    pub fn audit(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    /// BitVMX 정산 증명 해시를 감사 기록에 남김
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, proof_hash = %hex::encode(proof_hash)))]
+    pub fn record_settlement_proof(
+        &mut self,
+        option_id: &str,
+        proof_hash: &[u8; 32],
+    ) -> Result<(), SettlementError> {
+        if !self.options.contains_key(option_id) {
+            return Err(SettlementError::OptionNotFound(option_id.to_string()));
+        }
+        let now = self.clock.now();
+        self.audit.append(
+            option_id,
+            now,
+            AuditAction::SettlementProof {
+                proof_hash: hex::encode(proof_hash),
+            },
+        );
+        Ok(())
+    }
+
+    /// 정산된 옵션의 STL 앵커 페이로드 (마지막 감사 해시 포함, 정산 전이면 None)
+    ///
+    /// 바이너리 옵션은 고정 지급액을 담은 BIN 앵커 페이로드를 반환합니다.
+    pub fn settle_anchor_payload(&self, option_id: &str) -> Option<Vec<u8>> {
+        self.settlements.get(option_id)?;
+        let head = self.audit.head_hash(option_id)?;
+        let option = self.options.get(option_id)?;
+        if option.terms.payoff.is_binary() {
+            return Some(binary_settle_anchor_payload(option_id, option.quantity, &head));
+        }
+        Some(settle_anchor_payload(option_id, &head))
+    }
+
+    /// 원장을 다시 적용해 풀 상태 재구성 (현재 상태와 다르면 원장 기준으로 교체)
+    pub fn rebuild_pool_state(&mut self) -> Result<(), ContractError> {
+        self.pool_state = self.ledger.rebuild()?;
+        Ok(())
+    }
+
+    /// 업그레이드용 전체 상태 스냅샷 (current_height 기준 정산 대기 목록 포함)
+    pub fn snapshot(&self, current_height: u32, anchors: Vec<AnchorRecord>) -> SystemSnapshot {
+        let mut options: Vec<SimpleOption> = self.options.values().cloned().collect();
+        options.sort_by(|a, b| a.option_id.cmp(&b.option_id));
+        let pending_settlements = self
+            .get_expired_options(current_height)
+            .into_iter()
+            .map(|option| PendingSettlement {
+                option_id: option.option_id.clone(),
+                expiry_height: option.expiry_height,
+            })
+            .collect();
+        let mut settlements: Vec<SettlementRecord> = self.settlements.values().cloned().collect();
+        settlements.sort_by(|a, b| a.option_id.cmp(&b.option_id));
+
+        SystemSnapshot {
+            created_at: self.clock.now(),
+            block_height: current_height,
+            pool_state: self.pool_state.clone(),
+            ledger: self.ledger.transactions().to_vec(),
+            options,
+            pending_settlements,
+            anchors,
+            used_quotes: self.quotes.used(),
+            trading_halt: self.trading_halt.clone(),
+            audit: self.audit.records().cloned().collect(),
+            treasury: (!self.treasury.is_empty()).then(|| self.treasury.clone()),
+            referrals: (!self.referrals.is_empty()).then(|| self.referrals.clone()),
+            lp_book: (!self.lp_book.is_empty()).then(|| self.lp_book.clone()),
+            eligibility_hashes: self.eligibility.hashes().clone(),
+            haircuts: self.haircuts.values().cloned().collect(),
+            funding: (!self.funding.is_empty()).then(|| self.funding.clone()),
+            claims: match &self.claims {
+                Some(claims) => (!claims.is_empty()).then(|| claims.records()),
+                None => self.claim_records.clone(),
+            },
+            account_keys: (!self.account_keys.is_empty()).then(|| self.account_keys.clone()),
+            beneficiaries: match &self.beneficiaries {
+                Some(registry) => registry.entries(),
+                None => self.beneficiary_records.clone(),
+            },
+            tracked_anchors: self.anchors.tracked(),
+            mempool_watch: (!self.mempool_watch.is_empty()).then(|| self.mempool_watch.records()),
+            tracked_settlements: self.settlement_txs.tracked(),
+            settlements,
+            dust_balances: self.dust_balances.clone().into_iter().collect(),
+            usd_book: self.usd_book.clone(),
+            quote_key: self.quotes.key(),
+            event_count: Some(self.event_store.events().len() as u64),
+        }
+    }
+
+    /// 스냅샷에서 관리자 복원 (이전 이벤트 없이 새 인메모리 이벤트 저장소로 시작)
+    pub fn restore(
+        snapshot: SystemSnapshot,
+        anchors: &dyn AnchorSource,
+    ) -> Result<Self, SnapshotError> {
+        Self::restore_state(snapshot, anchors, Box::new(InMemoryEventStore::new()))
+    }
+
+    /// 스냅샷 검증 후 관리자 복원
+    ///
+    /// 이벤트만으로는 옵션 상태를 다시 만들 수 없으므로, 저장소의 이벤트 수가 스냅샷이
+    /// 기록한 수와 다르면(스냅샷 이후 이벤트가 남았거나 로그가 잘림) 되감긴 상태로
+    /// 뜨지 않고 거부합니다. 멱등 키 캐시는 스냅샷에 포함되지 않습니다.
+    pub fn restore_with_event_store(
+        snapshot: SystemSnapshot,
+        anchors: &dyn AnchorSource,
+        event_store: Box<dyn EventStore>,
+    ) -> Result<Self, SnapshotError> {
+        let logged = event_store.events().len() as u64;
+        if let Some(covered) = snapshot.event_count.filter(|covered| *covered != logged) {
+            return Err(SnapshotError::Inconsistent(format!(
+                "event log has {} events but the snapshot covers {}",
+                logged, covered
+            )));
+        }
+        Self::restore_state(snapshot, anchors, event_store)
+    }
+
+    fn restore_state(
+        snapshot: SystemSnapshot,
+        anchors: &dyn AnchorSource,
+        event_store: Box<dyn EventStore>,
+    ) -> Result<Self, SnapshotError> {
+        snapshot.validate(anchors)?;
+
+        let mut manager = Self::with_event_store(event_store);
+        for option in snapshot.options {
+            manager.index.insert(&option);
+            manager.options.insert(option.option_id.clone(), option);
+        }
+        manager.pool_state = snapshot.pool_state;
+        manager.ledger = PoolLedger::from_transactions(snapshot.ledger);
+        manager.quotes = QuoteRules::restore(snapshot.quote_key, snapshot.used_quotes);
+        manager.trading_halt = snapshot.trading_halt;
+        manager.audit = AuditLog::from_records(snapshot.audit).map_err(SnapshotError::Inconsistent)?;
+        manager.treasury = snapshot.treasury.unwrap_or_default();
+        manager.referrals = snapshot.referrals.unwrap_or_default();
+        manager.lp_book = snapshot.lp_book.unwrap_or_default();
+        manager.eligibility = Eligibility::restore(snapshot.eligibility_hashes);
+        manager.haircuts = snapshot
+            .haircuts
+            .into_iter()
+            .map(|haircut| (haircut.option_id.clone(), haircut))
+            .collect();
+        manager.funding = snapshot.funding.unwrap_or_default();
+        manager.claim_records = snapshot.claims;
+        manager.account_keys = snapshot.account_keys.unwrap_or_default();
+        manager.beneficiary_records = snapshot.beneficiaries;
+        manager.anchors = Anchors::restore(snapshot.tracked_anchors);
+        manager.mempool_watch = snapshot.mempool_watch.map(MempoolWatcher::restore).unwrap_or_default();
+        manager.settlement_txs = SettlementTxs::restore(snapshot.tracked_settlements);
+        manager.settlements = snapshot
+            .settlements
+            .into_iter()
+            .map(|record| (record.option_id.clone(), record))
+            .collect();
+        manager.dust_balances = snapshot.dust_balances.into_iter().collect();
+        manager.usd_book = snapshot.usd_book;
+        Ok(manager)
+    }
+
+    /// Aggregator의 거래 중단/재개 이벤트 반영
+    pub fn apply_system_event(&mut self, event: &SystemEvent) {
+        match event {
+            SystemEvent::TradingHalted {
+                reason,
+                timestamp,
+                resume_at,
+            } => {
+                self.trading_halt = Some(TradingHalt {
+                    reason: reason.clone(),
+                    halted_at: *timestamp,
+                    resume_at: *resume_at,
+                });
+            }
+            SystemEvent::TradingResumed { .. } => self.trading_halt = None,
+        }
+    }
+
+    /// 현재 거래 중단 상태 (자동 재개 시각이 지났으면 해제)
+    pub fn trading_halt(&mut self) -> Option<&TradingHalt> {
+        let now = self.clock.now();
+        if let Some(resume_at) = self.trading_halt.as_ref().and_then(|halt| halt.resume_at) {
+            if now >= resume_at {
+                self.trading_halt = None;
+            }
+        }
+        self.trading_halt.as_ref()
+    }
+
+    fn record_event(&mut self, kind: PoolEventKind) -> Result<(), String> {
+        let timestamp = self.clock.now();
+        self.event_store
+            .append(timestamp, kind.clone())
+            .map_err(|e| e.to_string())?;
+        self.audit_event(timestamp, kind);
+        Ok(())
+    }
+
+    /// 옵션 이벤트를 감사 기록으로 남김 (유동성 이벤트는 옵션 기록이 아님)
+    fn audit_event(&mut self, timestamp: u64, kind: PoolEventKind) {
+        match kind {
+            PoolEventKind::OptionCreated {
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                premium,
+                collateral,
+                user_id,
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::Created {
+                        option_type,
+                        strike_price,
+                        quantity,
+                        collateral,
+                        user_id,
+                    },
+                );
+                self.audit
+                    .append(&option_id, timestamp, AuditAction::PremiumReceived { amount: premium });
+            }
+            PoolEventKind::OptionSettled {
+                option_id,
+                spot_price,
+                payout,
+                dust,
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::Payout {
+                        spot_price,
+                        amount: payout,
+                        dust,
+                    },
+                );
+            }
+            PoolEventKind::OptionExpired { option_id, reason } => {
+                self.audit
+                    .append(&option_id, timestamp, AuditAction::Expired { reason });
+            }
+            PoolEventKind::OptionBoughtBack {
+                option_id,
+                spot_price,
+                amount,
+                ..
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::BoughtBack { spot_price, amount },
+                );
+            }
+            PoolEventKind::OptionClosedCooperatively {
+                option_id,
+                spot_price,
+                holder_amount,
+                txid,
+                ..
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::CooperativelyClosed {
+                        spot_price,
+                        amount: holder_amount,
+                        txid,
+                    },
+                );
+            }
+            PoolEventKind::BarrierTouched {
+                option_id,
+                kind,
+                level,
+                spot_price,
+                observed_at,
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::BarrierTouched {
+                        kind,
+                        level,
+                        spot_price,
+                        observed_at,
+                    },
+                );
+            }
+            PoolEventKind::PayoutHaircut {
+                option_id,
+                owed,
+                haircut,
+                rank,
+                ..
+            } => {
+                self.audit
+                    .append(&option_id, timestamp, AuditAction::Haircut { owed, haircut, rank });
+            }
+            PoolEventKind::OptionExercised {
+                option_id,
+                user_id,
+                spot_price,
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::Exercised { spot_price, user_id },
+                );
+            }
+            PoolEventKind::OptionRolled {
+                from_option_id,
+                buy_back_amount,
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                premium,
+                collateral,
+                user_id,
+                ..
+            } => {
+                self.audit.append(
+                    &option_id,
+                    timestamp,
+                    AuditAction::Rolled {
+                        from_option_id,
+                        buy_back_amount,
+                        option_type,
+                        strike_price,
+                        quantity,
+                        premium,
+                        collateral,
+                        user_id,
+                    },
+                );
+            }
+            PoolEventKind::LiquidityAdded { .. }
+            | PoolEventKind::LiquidityRemoved { .. }
+            | PoolEventKind::FeeCharged { .. }
+            | PoolEventKind::ReferralAttributed { .. }
+            | PoolEventKind::EligibilityDenied { .. }
+            | PoolEventKind::ExitClaimIssued { .. }
+            | PoolEventKind::ExitClaimTransferred { .. }
+            | PoolEventKind::TreasuryWithdrawn { .. }
+            | PoolEventKind::ReserveMoved { .. }
+            | PoolEventKind::FundingPrepaid { .. }
+            | PoolEventKind::FundingAccrued { .. }
+            | PoolEventKind::ClaimCredited { .. }
+            | PoolEventKind::ClaimWithdrawn { .. }
+            | PoolEventKind::ClaimPaid { .. }
+            | PoolEventKind::AccountKeyBound { .. }
+            | PoolEventKind::QuoteKeyRotated { .. }
+            | PoolEventKind::BeneficiaryRegistered { .. }
+            | PoolEventKind::BeneficiaryChanged { .. }
+            | PoolEventKind::AnchorBroadcast { .. }
+            | PoolEventKind::SettlementTxBroadcast { .. }
+            | PoolEventKind::SettlementTxConfirmed { .. }
+            | PoolEventKind::AnchorConfirmed { .. }
+            | PoolEventKind::AnchorReplaced { .. }
+            | PoolEventKind::UtxoWatched { .. }
+            | PoolEventKind::CompetingSpendDetected { .. }
+            | PoolEventKind::ChallengeArmed { .. } => {}
+        }
+    }
+}
+
+impl Default for SimpleContractManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimpleContractManager {
+    /// 유동성 추가
+    pub fn add_liquidity(&mut self, amount: u64) -> Result<(), ContractError> {
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, "liquidity", vec![Posting::deposit(amount)], 0)?;
+        self.record_event(PoolEventKind::LiquidityAdded {
+            provider_id: None,
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        self.ledger.commit(&mut self.pool_state, pending);
+        Ok(())
+    }
+
+    /// 유동성 제거 (잠기지 않은 유동성만 출금 가능)
+    pub fn remove_liquidity(&mut self, amount: u64) -> Result<(), ContractError> {
+        if self.pool_state.available_liquidity < amount {
+            return Err(ContractError::InsufficientLiquidity {
+                required: amount,
+                available: self.pool_state.available_liquidity,
+            });
+        }
+
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, "liquidity", vec![Posting::withdrawal(amount)], 0)?;
+        self.record_event(PoolEventKind::LiquidityRemoved {
+            provider_id: None,
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        self.ledger.commit(&mut self.pool_state, pending);
+        Ok(())
+    }
+
+    /// LP 입금: 현재 순자산 기준으로 지분 발행
+    ///
+    /// 첫 입금 전에 지분 없이 들어와 있던 순자산은 재무 계정 지분으로 먼저 발행합니다.
+    pub fn deposit_liquidity(&mut self, provider_id: &str, amount: u64) -> Result<u64, ContractError> {
+        let mut book = self.lp_book.clone();
+        book.bootstrap(&self.pool_state);
+        let shares = book.shares_for_deposit(amount, &self.pool_state);
+        if shares == 0 {
+            return Err(ContractError::Ledger(format!(
+                "deposit of {} sats is too small to mint shares",
+                amount
+            )));
+        }
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, "liquidity", vec![Posting::deposit(amount)], 0)?;
+        self.record_event(PoolEventKind::LiquidityAdded {
+            provider_id: Some(provider_id.to_string()),
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        self.ledger.commit(&mut self.pool_state, pending);
+        book.mint(provider_id, shares);
+        self.lp_book = book;
+        Ok(shares)
+    }
+
+    /// LP 부분 출금: 지분 가치 중 사용 가능한 몫은 바로 출금하고, 잠긴 몫은
+    /// 지금 활성인 옵션 담보에 대한 출금 청구권으로 발행
+    pub fn exit_liquidity(
+        &mut self,
+        provider_id: &str,
+        shares: u64,
+    ) -> Result<(ExitPlan, Option<u64>), ContractError> {
+        let plan = self.lp_book.plan_exit(provider_id, shares, &self.pool_state)?;
+        let pending = if plan.immediate > 0 {
+            Some(self.ledger.prepare(
+                &self.pool_state,
+                "liquidity",
+                vec![Posting::withdrawal(plan.immediate)],
+                0,
+            )?)
+        } else {
+            None
+        };
+
+        if plan.immediate > 0 {
+            self.record_event(PoolEventKind::LiquidityRemoved {
+                provider_id: Some(provider_id.to_string()),
+                amount: plan.immediate,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+        let active: Vec<(String, u64)> = self
+            .index
+            .by_status(OptionStatus::Active)
+            .filter_map(|id| self.options.get(id))
+            .map(|option| (option.option_id.clone(), option.collateral()))
+            .collect();
+        let mut book = self.lp_book.clone();
+        let claim_id = book.apply_exit(provider_id, &plan, active);
+        if let Some(claim_id) = claim_id {
+            self.record_event(PoolEventKind::ExitClaimIssued {
+                claim_id,
+                provider_id: provider_id.to_string(),
+                shares: plan.shares,
+                immediate: plan.immediate,
+                locked: plan.locked,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+
+        if let Some(pending) = pending {
+            self.ledger.commit(&mut self.pool_state, pending);
+        }
+        self.lp_book = book;
+        Ok((plan, claim_id))
+    }
+
+    /// 출금 청구권에서 풀린 금액 출금
+    pub fn redeem_exit_claim(&mut self, claim_id: u64, holder: &str) -> Result<u64, ContractError> {
+        let amount = self.lp_book.check_redeem(claim_id, holder)?;
+        if amount == 0 {
+            return Ok(0);
+        }
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, "liquidity", vec![Posting::withdrawal(amount)], 0)?;
+        self.record_event(PoolEventKind::LiquidityRemoved {
+            provider_id: Some(holder.to_string()),
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.lp_book.apply_redeem(claim_id, amount);
+        Ok(amount)
+    }
+
+    /// 수익처 예치/회수/이자를 풀 원장에 반영 (`ReserveManager`가 수익처 이동 후 호출)
+    pub fn record_reserve_movement(
+        &mut self,
+        venue: &str,
+        movement: ReserveMovement,
+        amount: u64,
+    ) -> Result<(), ContractError> {
+        let posting = match movement {
+            ReserveMovement::Sweep => Posting::reserve_sweep(amount),
+            ReserveMovement::Recall => Posting::reserve_recall(amount),
+            ReserveMovement::Yield => Posting::reserve_yield(amount),
+        };
+        let pending = self.ledger.prepare(&self.pool_state, "reserve", vec![posting], 0)?;
+        self.record_event(PoolEventKind::ReserveMoved {
+            venue: venue.to_string(),
+            movement,
+            amount,
+        })
+        .map_err(ContractError::Storage)?;
+
+        self.ledger.commit(&mut self.pool_state, pending);
+        Ok(())
+    }
+
+    /// 선납 담보 사용료를 경과 일수만큼 LP 유동성으로 적립 (적립액 반환)
+    ///
+    /// 정산/되사기 등으로 더 이상 활성이 아닌 옵션의 남은 선납분은 한 번에 적립합니다.
+    pub fn accrue_funding(&mut self) -> Result<u64, ContractError> {
+        let options = &self.options;
+        let plan = self.funding.plan(self.clock.now(), |option_id| {
+            options
+                .get(option_id)
+                .is_some_and(|option| option.status == OptionStatus::Active)
+        });
+        let amount = plan.total();
+        if amount > 0 {
+            let pending = self.ledger.prepare(
+                &self.pool_state,
+                "funding",
+                vec![Posting::funding_accrual(amount)],
+                0,
+            )?;
+            self.record_event(PoolEventKind::FundingAccrued {
+                amount,
+                options: plan.entries.len() as u32,
+            })
+            .map_err(ContractError::Storage)?;
+            self.ledger.commit(&mut self.pool_state, pending);
+        }
+        self.funding.apply(&plan);
+        Ok(amount)
+    }
+
+    /// 출금 청구권 양도
+    pub fn transfer_exit_claim(&mut self, claim_id: u64, from: &str, to: &str) -> Result<(), ContractError> {
+        let mut book = self.lp_book.clone();
+        book.transfer(claim_id, from, to)?;
+        self.record_event(PoolEventKind::ExitClaimTransferred {
+            claim_id,
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+        self.lp_book = book;
+        Ok(())
+    }
+
+    /// 옵션 생성
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_option(
+        &mut self,
+        option_id: String,
+        option_type: OptionType,
+        strike_price: u64,
+        quantity: u64,
+        premium: u64,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        self.create_option_with_request(CreateOptionRequest {
+            option_id,
+            option_type,
+            strike_price,
+            quantity,
+            premium,
+            expiry_height,
+            user_id,
+            referral_code: None,
+        })
+    }
+
+    /// 요청 구조체로 옵션 생성 (추천 코드가 있으면 프로토콜 수수료 일부를 추천인에게 환급)
+    pub fn create_option_with_request(&mut self, request: CreateOptionRequest) -> Result<(), ContractError> {
+        if self.quotes.required() {
+            return Err(ContractError::QuoteRequired);
+        }
+
+        self.open_option(
+            request.option_id,
+            request.option_type,
+            request.strike_price,
+            request.quantity,
+            request.premium,
+            request.expiry_height,
+            request.user_id,
+            request.referral_code.as_deref(),
+            OptionTerms::default(),
+        )
+    }
+
+    /// USD로 프리미엄을 받고 USD로 지급하는 옵션 생성 (담보는 BTC로 잠금)
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_usd_option(
+        &mut self,
+        option_id: String,
+        option_type: OptionType,
+        strike_price: u64,
+        quantity: u64,
+        premium_cents: u64,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        if self.usd_book.is_none() {
+            return Err(ContractError::UnsupportedCurrency(
+                "USD settlement is not enabled".to_string(),
+            ));
+        }
+        if self.quotes.required() {
+            return Err(ContractError::QuoteRequired);
+        }
+
+        // BTC 원장에는 담보만 잠그고 프리미엄은 USD 잔고로 받음
+        self.open_option(
+            option_id.clone(),
+            option_type,
+            strike_price,
+            quantity,
+            0,
+            expiry_height,
+            user_id,
+            None,
+            OptionTerms::usd(),
+        )?;
+        if let Some(book) = self.usd_book.as_mut() {
+            book.collect_premium(&option_id, premium_cents);
+        }
+        Ok(())
+    }
+
+    /// LP USD 유동성 추가
+    pub fn add_usd_liquidity(&mut self, cents: u64) -> Result<(), ContractError> {
+        let book = self.usd_book.as_mut().ok_or_else(|| {
+            ContractError::UnsupportedCurrency("USD settlement is not enabled".to_string())
+        })?;
+        book.deposit(cents);
+        Ok(())
+    }
+
+    /// 멱등 키를 사용한 옵션 생성
+    ///
+    /// 같은 키로 같은 요청을 재시도하면 상태를 바꾸지 않고 처음 결과를 돌려줍니다.
+    /// 재시도 가능한 오류(거래 중단, 유동성 부족, 저장소 오류)는 기록하지 않으므로
+    /// 같은 키로 다시 시도할 수 있습니다.
+    pub fn create_option_idempotent(
+        &mut self,
+        idempotency_key: &str,
+        request: CreateOptionRequest,
+    ) -> Result<(), ContractError> {
+        let request_hash = request.request_hash();
+        self.idempotent(idempotency_key, request_hash, |manager| {
+            manager.create_option_with_request(request)
+        })
+    }
+
+    /// 멱등 키를 사용한 확정 호가 체결 (외부 연동 클라이언트의 재시도용)
+    ///
+    /// 호가는 한 번만 체결되므로, 응답을 받지 못해 같은 키로 재시도하면
+    /// `QuoteReused` 대신 처음 결과를 돌려줍니다.
+    pub fn fill_quote_idempotent(
+        &mut self,
+        idempotency_key: &str,
+        quote: &OptionQuote,
+        option_id: String,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        let mut encoder = CanonicalEncoder::new("fill_quote/v1");
+        encoder
+            .value(quote)
+            .str(&quote.signature)
+            .str(&option_id)
+            .u32(expiry_height)
+            .str(&user_id);
+        let request_hash = hex::encode(encoder.hash());
+        self.idempotent(idempotency_key, request_hash, |manager| {
+            manager.create_option_from_quote(quote, option_id, expiry_height, user_id)
+        })
+    }
+
+    fn idempotent(
+        &mut self,
+        idempotency_key: &str,
+        request_hash: String,
+        apply: impl FnOnce(&mut Self) -> Result<(), ContractError>,
+    ) -> Result<(), ContractError> {
+        if let Some(outcome) = self.idempotency.get(idempotency_key) {
+            if outcome.request_hash != request_hash {
+                return Err(ContractError::IdempotencyConflict(idempotency_key.to_string()));
+            }
+            return outcome.result.clone();
+        }
+
+        let result = apply(self);
+
+        if !matches!(&result, Err(e) if e.is_retryable()) {
+            self.idempotency.insert(
+                idempotency_key.to_string(),
+                IdempotentOutcome {
+                    request_hash,
+                    result: result.clone(),
+                },
+            );
+        }
+        result
+    }
+
+    /// 확정 호가로 옵션 생성 (서명/만료/재사용 검사)
+    pub fn create_option_from_quote(
+        &mut self,
+        quote: &OptionQuote,
+        option_id: String,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        self.fill_quote(quote, quote.quantity, option_id, expiry_height, user_id)
+    }
+
+    /// 확정 호가의 일부 수량만 체결 (프리미엄은 비례 배분, 남은 수량은 취소)
+    ///
+    /// 체결 후 호가는 사용 처리되므로 같은 호가로 다시 체결할 수 없습니다.
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, quote_id = %quote.quote_id, fill_quantity = fill_quantity))]
+    pub fn fill_quote(
+        &mut self,
+        quote: &OptionQuote,
+        fill_quantity: u64,
+        option_id: String,
+        expiry_height: u32,
+        user_id: String,
+    ) -> Result<(), ContractError> {
+        self.check_option_quote(quote, self.clock.now())?;
+        self.check_expiry_height(quote, expiry_height)?;
+        if fill_quantity == 0 || fill_quantity > quote.quantity {
+            return Err(PricingError::InvalidInput(format!(
+                "Fill quantity {} outside quoted quantity {}",
+                fill_quantity, quote.quantity
+            ))
+            .into());
+        }
+
+        let premium = if fill_quantity == quote.quantity {
+            quote.premium
+        } else {
+            let spec = self.contract_spec().unwrap_or(ContractSpec {
+                premium_tick: 1,
+                ..ContractSpec::default()
+            });
+            spec.pro_rata_premium(quote.premium, quote.quantity, fill_quantity)
+        };
+
+        self.check_barrier(quote)?;
+        self.open_option(
+            option_id,
+            quote.option_type,
+            quote.strike_price,
+            fill_quantity,
+            premium,
+            expiry_height,
+            user_id,
+            quote.referral_code.as_deref(),
+            OptionTerms::from_quote(quote),
+        )?;
+        self.quotes.mark_used(&quote.quote_id);
+        Ok(())
+    }
+
+    /// 확정 호가 만기 날짜의 정산 시각 이후 처음 나올 블록 높이
+    ///
+    /// 정산 시각은 캘린더가 있으면 캘린더로, 없으면 날짜의 08:00 UTC로 정하고,
+    /// 블록 헤더(없으면 팁 높이와 `AVG_BLOCK_SECS`)로 높이를 셉니다. 높이를 하나도
+    /// 모르면 셀 수 없으므로 재시도 가능한 오류입니다.
+    pub fn quote_expiry_height(&self, quote: &OptionQuote) -> Result<u32, ContractError> {
+        let timestamp = match self.quotes.calendar() {
+            Some(calendar) => calendar.resolve(&quote.expiry, quote.issued_at, quote.otc)?,
+            None => expiry_date_timestamp(&quote.expiry)?,
+        };
+        if let Some(height) = self.block_clock.height_at(timestamp) {
+            return Ok(height);
+        }
+        let tip = self
+            .tip_height
+            .ok_or_else(|| ContractError::ExpiryHeightUnknown("no block height observed yet".to_string()))?;
+        let blocks = timestamp.saturating_sub(self.clock.now()).div_ceil(AVG_BLOCK_SECS);
+        Ok(tip.saturating_add(blocks as u32))
+    }
+
+    /// 요청한 만기 높이가 호가 만기에서 센 높이와 다르면 거부
+    ///
+    /// 호가는 만기 날짜까지의 기간으로 가격을 매겼으므로, 다른 높이로 열면 풀이
+    /// 가격을 매기지 않은 기간 동안 담보를 잠그고 사용료를 받게 됩니다.
+    fn check_expiry_height(&self, quote: &OptionQuote, expiry_height: u32) -> Result<(), ContractError> {
+        let quoted = self.quote_expiry_height(quote)?;
+        if quoted != expiry_height {
+            return Err(ContractError::ExpiryMismatch {
+                quote_id: quote.quote_id.clone(),
+                quoted,
+                requested: expiry_height,
+            });
+        }
+        Ok(())
+    }
+
+    /// 배리어 호가는 마지막 합의 가격이 이미 배리어에 닿았으면 거부
+    fn check_barrier(&self, quote: &OptionQuote) -> Result<(), ContractError> {
+        match (quote.barrier, self.consensus.last_price()) {
+            (Some(barrier), Some(price)) if barrier.touched_by(price) => Err(ContractError::InvalidQuote(format!(
+                "Quote {} barrier {} already touched at {}",
+                quote.quote_id, barrier.level, price
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, expiry_height = expiry_height, quantity = quantity))]
+    fn open_option(
+        &mut self,
+        option_id: String,
+        option_type: OptionType,
+        strike_price: u64,
+        quantity: u64,
+        premium: u64,
+        expiry_height: u32,
+        user_id: String,
+        referral_code: Option<&str>,
+        terms: OptionTerms,
+    ) -> Result<(), ContractError> {
+        if let Some(halt) = self.trading_halt() {
+            return Err(ContractError::TradingHalted(halt.reason.clone()));
+        }
+        if self.options.contains_key(&option_id) {
+            return Err(ContractError::DuplicateOption(option_id));
+        }
+        if let Some(spec) = self.contract_spec() {
+            spec.contracts(quantity)?;
+            spec.check_premium(premium)?;
+        }
+
+        // 담보금 계산
+        let collateral = payoff_collateral(terms.payoff, option_type, strike_price, quantity);
+
+        self.risk_limits
+            .check(collateral, self.pool_state.locked_collateral)?;
+        if !self.open_interest_caps.is_unlimited() {
+            self.open_interest_caps.check(
+                collateral,
+                self.pool_state.total_liquidity,
+                self.open_interest(strike_price, expiry_height),
+                self.secs_to_expiry(expiry_height),
+            )?;
+        }
+
+        // 사용 가능한 유동성 확인
+        if self.pool_state.available_liquidity < collateral {
+            return Err(ContractError::InsufficientLiquidity {
+                required: collateral,
+                available: self.pool_state.available_liquidity,
+            });
+        }
+
+        // 담보 잠금 + 프리미엄 수취 (프로토콜 수수료를 뺀 프리미엄은 사용 가능한 유동성에 추가)
+        let protocol_fee = self.fee_schedule.protocol_fee(premium);
+        // 추천 거래는 수수료 일부를 추천인 청구 잔고로 (청구 잔고가 없으면 전액 재무 계정)
+        let referral = match referral_code {
+            Some(code) => {
+                let mut attribution = self.referrals.attribute(code, &user_id, premium, protocol_fee)?;
+                if self.claims.is_none() {
+                    attribution.rebate = 0;
+                }
+                Some(attribution)
+            }
+            None => None,
+        };
+        let rebate = referral.as_ref().map_or(0, |attribution| attribution.rebate);
+        let rebate_credit = match (&self.claims, &referral) {
+            (Some(claims), Some(attribution)) if rebate > 0 => Some(
+                claims
+                    .prepare_credit(
+                        &attribution.referrer_id,
+                        &option_id,
+                        rebate,
+                        self.clock.now(),
+                    )
+                    .map_err(|e| ContractError::InvalidReferral(e.to_string()))?,
+            ),
+            _ => None,
+        };
+        let treasury_fee = protocol_fee - rebate;
+        // 프리미엄 중 만기까지의 담보 사용료는 선납받아 LP에게 일할 적립
+        let funding = self.funding_charge(collateral, expiry_height, premium - protocol_fee);
+        let mut postings = vec![
+            Posting::lock(collateral),
+            Posting::premium(premium - protocol_fee - funding.amount),
+        ];
+        if funding.amount > 0 {
+            postings.push(Posting::funding_prepaid(funding.amount));
+        }
+        if treasury_fee > 0 {
+            postings.push(Posting::protocol_fee(treasury_fee));
+        }
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, option_id.as_str(), postings, 1)?;
+
+        // 옵션 생성
+        let option = SimpleOption {
+            option_id: option_id.clone(),
+            option_type,
+            strike_price,
+            quantity,
+            premium_paid: premium,
+            expiry_height,
+            status: OptionStatus::Active,
+            user_id: user_id.clone(),
+            terms,
+        };
+
+        // 이벤트 기록에 성공한 뒤에만 상태 반영
+        self.record_event(PoolEventKind::OptionCreated {
+            option_id: option_id.clone(),
+            option_type,
+            strike_price,
+            quantity,
+            premium,
+            collateral,
+            user_id,
+        })
+        .map_err(ContractError::Storage)?;
+        if treasury_fee > 0 {
+            self.record_event(PoolEventKind::FeeCharged {
+                option_id: option_id.clone(),
+                fee: FeeKind::Protocol,
+                amount: treasury_fee,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+        let now = self.clock.now();
+        self.record_funding_prepaid(&option_id, funding, now)
+            .map_err(ContractError::Storage)?;
+        if let Some(attribution) = &referral {
+            self.record_event(PoolEventKind::ReferralAttributed {
+                option_id: option_id.clone(),
+                code: attribution.code.clone(),
+                referrer_id: attribution.referrer_id.clone(),
+                premium,
+                rebate,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+        self.record_claim_credit(rebate_credit.as_ref())
+            .map_err(ContractError::Storage)?;
+
+        self.index.insert(&option);
+        self.open_funding(&option_id, funding, now);
+        self.options.insert(option_id, option);
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.treasury.credit(FeeKind::Protocol, treasury_fee);
+        if let Some(attribution) = referral {
+            self.referrals.record(&attribution);
+        }
+        if let (Some(claims), Some(credit)) = (self.claims.as_mut(), rebate_credit) {
+            claims.apply_credit(credit);
+        }
+        Ok(())
+    }
+
+    /// 옵션 정산
+    ///
+    /// 지금 지급할 금액을 옵션의 결제 통화 단위로 반환합니다 (USD 결제 옵션은
+    /// USD cents). dust로 처리된 지급은 0이며 내역은 `settlement`에 남습니다.
+    /// 청구 잔고가 활성화되어 있으면 BTC 지급액은 전송 대신 사용자 잔고에 적립됩니다.
+    pub fn settle_option(
+        &mut self,
+        option_id: &str,
+        spot_price: u64,
+    ) -> Result<u64, SettlementError> {
+        self.settle_at(option_id, spot_price, None)
+    }
+
+    /// 미국식 옵션 조기 행사: 보유자가 만기 전에 마지막 합의 가격으로 정산
+    ///
+    /// 담보는 유럽식과 같이 전액 잠겨 있으므로 지급과 담보 반환은 만기 정산과
+    /// 같은 경로(dust 정책, 정산 수수료, 청구 잔고)를 탑니다. 행사 기록은
+    /// `exercise_anchor_payload`로 EXR 앵커에 남깁니다.
+    ///
+    /// 만기 판단은 요청이 아니라 관리자가 관측한 팁 높이로 하고, 팁을 모르거나
+    /// 합의 가격이 `max_exercise_price_age_secs`보다 오래되었으면 행사를 미룹니다.
+    pub fn exercise_option(&mut self, option_id: &str, user_id: &str) -> Result<u64, SettlementError> {
+        let option = self
+            .options
+            .get(option_id)
+            .ok_or_else(|| SettlementError::OptionNotFound(option_id.to_string()))?;
+        if option.status != OptionStatus::Active {
+            return Err(SettlementError::OptionNotActive(option_id.to_string()));
+        }
+        let reject = |reason: String| Err(SettlementError::EarlyExercise(format!("{}: {}", option_id, reason)));
+        if option.terms.style.is_european() {
+            return reject("European-style options settle at expiry".to_string());
+        }
+        if option.user_id != user_id {
+            return reject(format!("not held by {}", user_id));
+        }
+        let tip_height = self
+            .tip_height
+            .ok_or_else(|| SettlementError::Postponed("chain tip height unknown".to_string()))?;
+        if tip_height >= option.expiry_height {
+            return reject(format!("expired at height {}", option.expiry_height));
+        }
+        let spot_price = self.consensus.exercise_price(self.clock.now())?;
+        if option.payout_at(spot_price) == 0 {
+            return reject(format!("out of the money at {}", spot_price));
+        }
+        self.settle_at(option_id, spot_price, Some(user_id))
+    }
+
+    /// 합의 가격으로 배리어 감시: 처음 배리어에 닿은 활성 옵션을 기록하고 ID 반환
+    pub fn monitor_barriers(&mut self, observed_at: u64, spot_price: u64) -> Result<Vec<String>, ContractError> {
+        let mut touched: Vec<(String, Barrier)> = self
+            .options
+            .values()
+            .filter(|option| option.status == OptionStatus::Active)
+            .filter_map(|option| match &option.terms.barrier {
+                Some(state) if state.touched.is_none() && state.barrier.touched_by(spot_price) => {
+                    Some((option.option_id.clone(), state.barrier))
+                }
+                _ => None,
+            })
+            .collect();
+        touched.sort_by(|a, b| a.0.cmp(&b.0));
+        for (option_id, barrier) in &touched {
+            self.record_event(PoolEventKind::BarrierTouched {
+                option_id: option_id.clone(),
+                kind: barrier.kind,
+                level: barrier.level,
+                spot_price,
+                observed_at,
+            })
+            .map_err(ContractError::Storage)?;
+            if let Some(state) = self
+                .options
+                .get_mut(option_id)
+                .and_then(|option| option.terms.barrier.as_mut())
+            {
+                state.touched = Some(BarrierTouch {
+                    observed_at,
+                    spot_price,
+                });
+            }
+        }
+        Ok(touched.into_iter().map(|(option_id, _)| option_id).collect())
+    }
+
+    /// 자동 디레버리징: 만기가 지난 옵션의 지급액 합계가 사용 가능한 유동성과
+    /// 그 옵션들의 담보를 합친 자산을 넘으면 부족분을 수익성 순으로 지급 대기
+    /// 보유자에게 나눠 삭감
+    ///
+    /// 다시 호출하면 지급 대기 옵션의 삭감을 새로 계산해 바꿉니다. 삭감이 생긴
+    /// 보유자마다 `PayoutHaircut` 이벤트를 남기며, USD 결제 옵션은 USD 잔고에서
+    /// 지급되므로 제외합니다.
+    pub fn deleverage(&mut self, spot_price: u64, current_height: u32) -> Result<HaircutPlan, ContractError> {
+        let payouts: Vec<PendingPayout> = self
+            .get_expired_options(current_height)
+            .into_iter()
+            .filter(|option| option.terms.settlement_currency.is_btc())
+            .map(|option| PendingPayout {
+                option_id: option.option_id.clone(),
+                user_id: option.user_id.clone(),
+                owed: if option.is_live() {
+                    option.payout_at(spot_price)
+                } else {
+                    0
+                },
+                premium: option.premium_paid,
+            })
+            .filter(|payout| payout.owed > 0)
+            .collect();
+        let pending: Vec<String> = payouts.iter().map(|payout| payout.option_id.clone()).collect();
+        // 지급에 쓸 수 있는 자산: 사용 가능한 유동성 + 지급 대기 옵션의 담보
+        let pending_collateral: u64 = self
+            .get_expired_options(current_height)
+            .into_iter()
+            .filter(|option| option.terms.settlement_currency.is_btc())
+            .map(|option| option.collateral())
+            .sum();
+        let plan = plan_haircuts(payouts, self.pool_state.available_liquidity + pending_collateral);
+
+        for haircut in &plan.haircuts {
+            self.record_event(PoolEventKind::PayoutHaircut {
+                option_id: haircut.option_id.clone(),
+                user_id: haircut.user_id.clone(),
+                owed: haircut.owed,
+                haircut: haircut.haircut,
+                shortfall: plan.shortfall,
+                rank: haircut.rank,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+        for option_id in &pending {
+            self.haircuts.remove(option_id);
+        }
+        for haircut in &plan.haircuts {
+            self.haircuts.insert(haircut.option_id.clone(), haircut.clone());
+        }
+        if !plan.is_empty() {
+            warn!(
+                "🚨 Pool shortfall of {} sats socialized across {} payouts",
+                plan.shortfall,
+                plan.haircuts.len()
+            );
+        }
+        Ok(plan)
+    }
+
+    /// 옵션에 적용된 지급 삭감
+    pub fn haircut(&self, option_id: &str) -> Option<&Haircut> {
+        self.haircuts.get(option_id)
+    }
+
+    /// 적용된 지급 삭감 전체 (옵션 ID 순)
+    pub fn haircuts(&self) -> impl Iterator<Item = &Haircut> {
+        self.haircuts.values()
+    }
+
+    /// 조기 행사된 옵션의 EXR 앵커 페이로드 (마지막 감사 해시 포함)
+    pub fn exercise_anchor_payload(&self, option_id: &str) -> Option<Vec<u8>> {
+        self.audit
+            .trail(option_id)
+            .iter()
+            .find(|record| matches!(record.action, AuditAction::Exercised { .. }))?;
+        let head = self.audit.head_hash(option_id)?;
+        Some(exercise_anchor_payload(option_id, &head))
+    }
+
+    /// 정산 공통 경로 (`exercised_by`가 있으면 조기 행사로 기록)
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, spot_price = spot_price))]
+    fn settle_at(
+        &mut self,
+        option_id: &str,
+        spot_price: u64,
+        exercised_by: Option<&str>,
+    ) -> Result<u64, SettlementError> {
+        // 거래 중단 중에는 정산을 미룸 (옵션은 Active 상태로 유지)
+        if let Some(halt) = self.trading_halt() {
+            return Err(SettlementError::Postponed(halt.reason.clone()));
+        }
+
+        let option = self
+            .options
+            .get(option_id)
+            .ok_or_else(|| SettlementError::OptionNotFound(option_id.to_string()))?;
+
+        if option.status != OptionStatus::Active {
+            return Err(SettlementError::OptionNotActive(option_id.to_string()));
+        }
+
+        // 직전 합의 가격 밴드를 벗어난 가격이면 정산을 미루고 경보
+        let now = self.clock.now();
+        if let Err(err) = self.consensus.check_settlement(option_id, spot_price, now) {
+            warn!("🚨 Settlement of {} deferred: {}", option_id, err);
+            return Err(err);
+        }
+
+        let collateral = option.collateral();
+        // knock-out된 옵션과 knock-in되지 않은 옵션은 지급 없음
+        let intrinsic = if option.is_live() {
+            option.payout_at(spot_price)
+        } else {
+            0
+        };
+        // 풀 부족분 분담으로 정한 삭감 반영
+        let intrinsic = intrinsic.saturating_sub(self.haircuts.get(option_id).map_or(0, |cut| cut.haircut));
+        // USD 결제 옵션: 내재가치(USD cents)를 USD 잔고 → BTC 담보 환산 순으로 지급
+        let usd_payout = self
+            .usd_book
+            .as_ref()
+            .filter(|_| option.terms.settlement_currency.is_usd())
+            .map(|book| {
+                book.plan_payout(option_id, intrinsic, spot_price, collateral)
+            });
+        // BTC 결제 옵션: dust 기준 미만이면 풀 수익 또는 사용자 누적 잔고로 처리
+        let mut exercise = match &usd_payout {
+            Some(usd) => Exercise::Paid {
+                amount: usd.owed_cents - usd.shortfall_cents,
+            },
+            None => self.exercise_policy.apply(intrinsic),
+        };
+        // BTC 지급에서 정산 수수료를 떼어 재무 계정으로
+        let settlement_fee = match (&usd_payout, &mut exercise) {
+            (None, Exercise::Paid { amount }) => {
+                let fee = self.fee_schedule.settlement_fee(*amount);
+                *amount -= fee;
+                fee
+            }
+            _ => 0,
+        };
+        let payout = match &usd_payout {
+            Some(usd) => usd.converted_sats,
+            None => exercised_amount(&exercise),
+        };
+        let dust = match exercise {
+            Exercise::Dust { amount, .. } => amount,
+            _ => 0,
+        };
+        let user_id = option.user_id.clone();
+
+        // 청구 잔고 사용 시 BTC 지급은 건별 전송 대신 사용자 잔고에 적립
+        let credit = match &self.claims {
+            Some(claims) if usd_payout.is_none() && payout > 0 => Some(
+                claims
+                    .prepare_credit(&user_id, option_id, payout, now)
+                    .map_err(|e| SettlementError::Ledger(e.to_string()))?,
+            ),
+            _ => None,
+        };
+
+        // 지급 후 잔여 담보금은 풀로 반환 (OTM이면 전체 반환). 담보를 넘는 지급은
+        // 사용 가능한 유동성을 추가로 잠가 충당 (부족하면 `deleverage`로 먼저 삭감)
+        let mut postings = Vec::new();
+        if payout + settlement_fee > collateral {
+            postings.push(Posting::lock(payout + settlement_fee - collateral));
+        }
+        postings.push(Posting::payout(payout));
+        postings.push(Posting::release(collateral.saturating_sub(payout + settlement_fee)));
+        if settlement_fee > 0 {
+            postings.push(Posting::settlement_fee(settlement_fee));
+        }
+        let pending = self
+            .ledger
+            .prepare(&self.pool_state, option_id, postings, -1)
+            .map_err(|e| SettlementError::Ledger(e.to_string()))?;
+
+        if let Some(holder) = exercised_by {
+            self.record_event(PoolEventKind::OptionExercised {
+                option_id: option_id.to_string(),
+                user_id: holder.to_string(),
+                spot_price,
+            })
+            .map_err(SettlementError::Storage)?;
+        }
+        self.record_event(PoolEventKind::OptionSettled {
+            option_id: option_id.to_string(),
+            spot_price,
+            payout,
+            dust,
+        })
+        .map_err(SettlementError::Storage)?;
+        if settlement_fee > 0 {
+            self.record_event(PoolEventKind::FeeCharged {
+                option_id: option_id.to_string(),
+                fee: FeeKind::Settlement,
+                amount: settlement_fee,
+            })
+            .map_err(SettlementError::Storage)?;
+        }
+        self.record_claim_credit(credit.as_ref())
+            .map_err(SettlementError::Storage)?;
+
+        if let Some(option) = self.options.get_mut(option_id) {
+            self.index
+                .update_status(option_id, option.status, OptionStatus::Settled);
+            option.status = OptionStatus::Settled;
+        }
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.treasury.credit(FeeKind::Settlement, settlement_fee);
+        self.lp_book.on_release(option_id, payout + settlement_fee);
+
+        let currency = if usd_payout.is_some() {
+            SettlementCurrency::Usd
+        } else {
+            SettlementCurrency::Btc
+        };
+        match (self.claims.as_mut(), credit) {
+            (Some(claims), Some(credit)) => claims.apply_credit(credit),
+            _ => {
+                if let Exercise::Dust {
+                    amount,
+                    handling: DustHandling::Accumulate,
+                } = exercise
+                {
+                    *self.dust_balances.entry(user_id).or_default() += amount;
+                }
+            }
+        }
+        self.settlements.insert(
+            option_id.to_string(),
+            SettlementRecord {
+                option_id: option_id.to_string(),
+                spot_price,
+                currency,
+                exercise,
+                settled_at: now,
+            },
+        );
+
+        if let (Some(book), Some(usd)) = (self.usd_book.as_mut(), usd_payout) {
+            book.apply_payout(&usd, now);
+            return Ok(usd.owed_cents - usd.shortfall_cents);
+        }
+        Ok(match exercise {
+            Exercise::Paid { amount } => amount,
+            _ => 0,
+        })
+    }
+
+    /// 운영자 강제 만료: 지급 없이 담보를 풀로 반환하고 Expired로 전환
+    pub fn force_expire(&mut self, option_id: &str, reason: &str) -> Result<(), SettlementError> {
+        let option = self
+            .options
+            .get(option_id)
+            .ok_or_else(|| SettlementError::OptionNotFound(option_id.to_string()))?;
+        if option.status != OptionStatus::Active {
+            return Err(SettlementError::OptionNotActive(option_id.to_string()));
+        }
+
+        let pending = self
+            .ledger
+            .prepare(
+                &self.pool_state,
+                option_id,
+                vec![Posting::release(option.collateral())],
+                -1,
+            )
+            .map_err(|e| SettlementError::Ledger(e.to_string()))?;
+        self.record_event(PoolEventKind::OptionExpired {
+            option_id: option_id.to_string(),
+            reason: reason.to_string(),
+        })
+        .map_err(SettlementError::Storage)?;
+
+        if let Some(option) = self.options.get_mut(option_id) {
+            self.index
+                .update_status(option_id, option.status, OptionStatus::Expired);
+            option.status = OptionStatus::Expired;
+        }
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.lp_book.on_release(option_id, 0);
+        if let Some(book) = self.usd_book.as_mut() {
+            book.forget(option_id);
+        }
+        Ok(())
+    }
+
+    /// 만료된 옵션 조회
+    pub fn get_expired_options(&self, current_height: u32) -> Vec<&SimpleOption> {
+        self.index
+            .by_expiry_range(..=current_height)
+            .filter(|id| self.index.has_status(id, OptionStatus::Active))
+            .filter_map(|id| self.options.get(id))
+            .collect()
+    }
+
+    /// 만기 높이 구간의 옵션 조회 (만기 순)
+    pub fn get_options_by_expiry_range<R: RangeBounds<u32>>(&self, range: R) -> Vec<&SimpleOption> {
+        self.index
+            .by_expiry_range(range)
+            .filter_map(|id| self.options.get(id))
+            .collect()
+    }
+
+    /// 행사가가 같은 활성 옵션 조회
+    pub fn get_active_by_strike(&self, strike_price: u64) -> Vec<&SimpleOption> {
+        self.index
+            .by_strike(strike_price)
+            .filter(|id| self.index.has_status(id, OptionStatus::Active))
+            .filter_map(|id| self.options.get(id))
+            .collect()
+    }
+
+    /// 사용자별 옵션 조회
+    pub fn get_options_by_user(&self, user_id: &str) -> Vec<&SimpleOption> {
+        self.index
+            .by_user(user_id)
+            .filter_map(|id| self.options.get(id))
+            .collect()
+    }
+
+    /// 시스템 상태 조회
+    pub fn get_system_status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "pool_state": self.pool_state,
+            "total_options": self.options.len(),
+            "active_options": self.pool_state.active_options,
+            "utilization_rate": format!("{:.2}%", self.pool_state.utilization_rate()),
+            "trading_halt": self.trading_halt,
+            "usd_book": self.usd_book,
+            "exercise_policy": self.exercise_policy,
+            "fee_schedule": self.fee_schedule,
+            "tenant_id": self.tenant_id(),
+            "risk_limits": self.risk_limits,
+            "open_interest_caps": self.open_interest_caps,
+            "treasury": {
+                "balance": self.treasury.balance,
+                "protocol_fees": self.treasury.protocol_fees,
+                "settlement_fees": self.treasury.settlement_fees,
+                "withdrawn": self.treasury.withdrawn,
+            },
+            "funding": {
+                "annual_bps": self.funding.annual_bps(),
+                "unaccrued": self.pool_state.unaccrued_funding,
+                "accrued": self.pool_state.total_funding_accrued,
+            },
+            "profit_loss": (self.pool_state.total_premium_collected + self.pool_state.total_funding_accrued) as i64
+                - self.pool_state.total_payout as i64
+        })
+    }
+}
+
+/// 외부 연동용 옵션 API (`POST /options`, `GET /options/{id}`)
+pub mod api {
+    use crate::account_keys::AccountKeys;
+    use crate::admin_api::{bad_request, error_response, AdminError, SharedManager};
+    use crate::eligibility::{screen_pool, EligibilityRequest};
+    use axum::{
+        extract::{Path, State},
+        http::{HeaderMap, StatusCode},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use oracle_vm_common::{OptionQuote, SettlementError};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    /// 멱등 키 헤더 (같은 키의 재시도는 처음 결과를 돌려받음)
+    pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+    /// 확정 호가로 옵션 생성 요청
+    #[derive(Debug, Serialize, Deserialize, ToSchema)]
+    pub struct OpenOptionRequest {
+        #[schema(value_type = Object)]
+        pub quote: OptionQuote,
+        pub option_id: String,
+        /// 만기 블록 높이 (생략하면 호가 만기로 계산, 보내면 계산한 높이와 같아야 함)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub expiry_height: Option<u32>,
+        pub user_id: String,
+        /// 보유자 공개키 (hex, 새 계정이면 이 키가 묶이고 이후 출금/행사 요청에 서명)
+        pub holder_pubkey: String,
+    }
+
+    fn option_view(manager: &super::SimpleContractManager, option_id: &str) -> Response {
+        match manager.options.get(option_id) {
+            Some(option) => Json(json!({
+                "option": option,
+                "settlement": manager.settlement(option_id),
+                "eligibility_hash": manager.eligibility_hash(option_id),
+            }))
+            .into_response(),
+            None => error_response(SettlementError::OptionNotFound(option_id.to_string())),
+        }
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/options",
+        tag = "options",
+        params(("Idempotency-Key" = String, Header, description = "재시도 시 같은 값을 보냄")),
+        request_body = OpenOptionRequest,
+        responses(
+            (status = 200, body = Object),
+            (status = 400, body = AdminError),
+            (status = 409, body = AdminError),
+            (status = 503, description = "재시도 가능한 오류", body = AdminError)
+        )
+    )]
+    async fn open_option(
+        State(manager): State<SharedManager>,
+        headers: HeaderMap,
+        Json(request): Json<OpenOptionRequest>,
+    ) -> Response {
+        let Some(key) = headers
+            .get(IDEMPOTENCY_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|key| !key.is_empty())
+        else {
+            return bad_request("Idempotency-Key header is required");
+        };
+        let holder_key = match AccountKeys::parse_key(&request.holder_pubkey) {
+            Ok(key) => key,
+            Err(e) => return bad_request(e.to_string()),
+        };
+        let eligibility = EligibilityRequest {
+            user_id: request.user_id.clone(),
+            address: None,
+            option_id: request.option_id.clone(),
+        };
+        let decision_hash = match screen_pool(&manager, &eligibility).await {
+            Ok(hash) => hash,
+            Err(e) => return error_response(e),
+        };
+        let Ok(mut manager) = manager.write() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        // 키는 옵션이 실제로 열린 뒤에만 묶음 (거부된 요청으로 남의 계정 키를 선점하지 못함)
+        if let Err(e) = manager.check_account_key(&request.user_id, &holder_key) {
+            return error_response(e);
+        }
+        let expiry_height = match request.expiry_height {
+            Some(height) => height,
+            None => match manager.quote_expiry_height(&request.quote) {
+                Ok(height) => height,
+                Err(e) => return error_response(e),
+            },
+        };
+        if let Err(e) = manager.fill_quote_idempotent(
+            key,
+            &request.quote,
+            request.option_id.clone(),
+            expiry_height,
+            request.user_id.clone(),
+        ) {
+            return error_response(e);
+        }
+        if let Some(hash) = decision_hash {
+            manager.record_eligibility_hash(&request.option_id, hash);
+        }
+        match manager.bind_account_key(&request.user_id, holder_key) {
+            Ok(()) => option_view(&manager, &request.option_id),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/options/{id}",
+        tag = "options",
+        params(("id" = String, Path, description = "옵션 ID")),
+        responses((status = 200, body = Object), (status = 404, body = AdminError))
+    )]
+    async fn get_option(Path(option_id): Path<String>, State(manager): State<SharedManager>) -> Response {
+        let Ok(manager) = manager.read() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        option_view(&manager, &option_id)
+    }
+
+    /// `/options` 라우터 생성
+    pub fn router(manager: SharedManager) -> Router {
+        Router::new()
+            .route("/options", post(open_option))
+            .route("/options/:id", get(get_option))
+            .with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::ExerciseStyle;
+    use crate::account_keys::{sign_request, withdraw_payload};
+    use crate::early_exercise::DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS;
+    use crate::price_guard::PriceBandConfig;
+    use oracle_vm_common::{expiry_date_timestamp, BuyBackQuote, ClaimError, ExpiryCalendar};
+
+    /// 1 BTC 유동성을 넣은 관리자 (시스템 시계)
+    fn funded_manager() -> SimpleContractManager {
+        funded_manager_at(SystemClock::shared())
+    }
+
+    /// 1 BTC 유동성을 넣은 관리자
+    fn funded_manager_at(clock: SharedClock) -> SimpleContractManager {
+        let mut manager = SimpleContractManager::with_clock(clock);
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+    }
+
+    /// 확정 호가로만 옵션을 여는 1 BTC 풀과 호가 서명키 (시스템 시계)
+    fn quoting_manager() -> (SimpleContractManager, oracle_vm_common::crypto::SecretKey) {
+        quoting_manager_at(SystemClock::shared())
+    }
+
+    /// 확정 호가로만 옵션을 여는 1 BTC 풀과 호가 서명키
+    fn quoting_manager_at(clock: SharedClock) -> (SimpleContractManager, oracle_vm_common::crypto::SecretKey) {
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager_at(clock);
+        manager.require_quotes(public_key);
+        (manager, secret_key)
+    }
+
+    #[test]
+    fn test_call_option_itm() {
+        let mut manager = funded_manager();
+
+        // Call 옵션 생성: Strike $70,000, Quantity 0.1 BTC, Premium 0.0025 BTC
+        manager
+            .create_option(
+                "CALL-001".to_string(),
+                OptionType::Call,
+                7_000_000,  // $70,000 in cents
+                10_000_000, // 0.1 BTC in sats
+                250_000,    // 0.0025 BTC premium
+                800_000,
+                "user1".to_string(),
+            )
+            .unwrap();
+
+        // 정산: Spot $72,000 (ITM)
+        let payout = manager.settle_option("CALL-001", 7_200_000).unwrap();
+
+        // $2,000 profit on 0.1 BTC ≈ 277,777 sats (assuming $72k BTC price)
+        assert!(payout > 0);
+        assert_eq!(manager.pool_state.active_options, 0);
+
+        println!("Call ITM Payout: {} sats", payout);
+        println!(
+            "Pool utilization: {:.2}%",
+            manager.pool_state.utilization_rate()
+        );
+    }
+
+    #[test]
+    fn test_put_option_itm() {
+        let mut manager = funded_manager();
+
+        // Put 옵션 생성: Strike $65,000, Quantity 0.2 BTC
+        manager
+            .create_option(
+                "PUT-001".to_string(),
+                OptionType::Put,
+                6_500_000,  // $65,000 in cents
+                20_000_000, // 0.2 BTC in sats
+                180_000,    // 0.0018 BTC premium
+                800_000,
+                "user2".to_string(),
+            )
+            .unwrap();
+
+        // 정산: Spot $63,000 (ITM)
+        let payout = manager.settle_option("PUT-001", 6_300_000).unwrap();
+
+        // $2,000 profit on 0.2 BTC
+        assert!(payout > 0);
+
+        println!("Put ITM Payout: {} sats", payout);
+        println!("System status: {}", manager.get_system_status());
+    }
+
+    #[test]
+    fn test_option_otm() {
+        let mut manager = SimpleContractManager::new();
+
+        manager.add_liquidity(100_000_000).unwrap();
+
+        // Call 옵션 생성
+        manager
+            .create_option(
+                "CALL-OTM".to_string(),
+                OptionType::Call,
+                7_500_000,  // $75,000 strike
+                10_000_000, // 0.1 BTC
+                300_000,    // premium
+                800_000,
+                "user3".to_string(),
+            )
+            .unwrap();
+
+        // 정산: Spot $73,000 (OTM)
+        let payout = manager.settle_option("CALL-OTM", 7_300_000).unwrap();
+
+        assert_eq!(payout, 0);
+        assert_eq!(manager.pool_state.active_options, 0);
+
+        println!("Call OTM Payout: {} sats (should be 0)", payout);
+    }
+
+    #[test]
+    fn test_trading_halt_blocks_create_and_settle() {
+        let mut manager = funded_manager();
+        manager
+            .create_option(
+                "CALL-HALT".to_string(),
+                OptionType::Call,
+                7_000_000,
+                10_000_000,
+                300_000,
+                800_000,
+                "user4".to_string(),
+            )
+            .unwrap();
+
+        manager.apply_system_event(&SystemEvent::TradingHalted {
+            reason: "Price moved 15.00% in 300s".to_string(),
+            timestamp: 1_700_000_000,
+            resume_at: None,
+        });
+
+        let created = manager.create_option(
+            "CALL-BLOCKED".to_string(),
+            OptionType::Call,
+            7_000_000,
+            10_000_000,
+            300_000,
+            800_000,
+            "user4".to_string(),
+        );
+        assert!(matches!(created, Err(ContractError::TradingHalted(_))));
+        assert!(matches!(
+            manager.settle_option("CALL-HALT", 7_500_000),
+            Err(SettlementError::Postponed(_))
+        ));
+        assert_eq!(manager.options["CALL-HALT"].status, OptionStatus::Active);
+
+        manager.apply_system_event(&SystemEvent::TradingResumed {
+            timestamp: 1_700_000_600,
+            manual: true,
+        });
+        assert!(manager.settle_option("CALL-HALT", 7_500_000).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_fees_accrue_to_treasury_separately_from_pool() {
+        let mut manager = funded_manager();
+        manager
+            .set_fee_schedule(FeeSchedule {
+                protocol_fee_bps: 200,
+                settlement_fee_bps: 100,
+            })
+            .unwrap();
+        manager
+            .create_option(
+                "CALL-FEE".to_string(),
+                OptionType::Call,
+                7_000_000,
+                10_000_000,
+                250_000,
+                800_000,
+                "user5".to_string(),
+            )
+            .unwrap();
+        // 프리미엄 2%는 재무 계정, 나머지만 LP 풀로
+        assert_eq!(manager.treasury().protocol_fees, 5_000);
+        assert_eq!(manager.pool_state.total_liquidity, 100_245_000);
+
+        let gross = manager.options["CALL-FEE"].payout_at(7_200_000);
+        let payout = manager.settle_option("CALL-FEE", 7_200_000).unwrap();
+        let fee = gross / 100;
+        assert_eq!(payout, gross - fee);
+        assert_eq!(manager.treasury().settlement_fees, fee);
+        assert_eq!(manager.treasury().balance, 5_000 + fee);
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+        assert_eq!(manager.pool_state.total_liquidity, 100_245_000 - gross);
+        assert_eq!(manager.get_system_status()["treasury"]["balance"], 5_000 + fee);
+
+        // 허용 주소로만 출금
+        assert!(matches!(
+            manager.withdraw_treasury(1_000, "bc1qtreasury"),
+            Err(TreasuryError::DestinationNotAllowed(_))
+        ));
+        manager.set_treasury_controls(TreasuryControls {
+            allowed_destinations: vec!["bc1qtreasury".to_string()],
+            daily_limit: None,
+        });
+        manager.withdraw_treasury(5_000, "bc1qtreasury").unwrap();
+        assert_eq!(manager.treasury().balance, fee);
+        assert_eq!(manager.pool_state.total_liquidity, 100_245_000 - gross);
+
+        let report = crate::reporting::ReportGenerator::new(manager.event_store()).generate(
+            crate::reporting::ReportKind::Fees,
+            0,
+            u64::MAX,
+        );
+        let amounts: Vec<_> = report.rows.iter().map(|row| row[4].clone()).collect();
+        assert_eq!(
+            amounts,
+            vec![
+                crate::reporting::ReportCell::Int(5_000),
+                crate::reporting::ReportCell::Int(fee as i64),
+                crate::reporting::ReportCell::Int(-5_000),
+            ]
+        );
+
+        let restored = SimpleContractManager::restore(
+            manager.snapshot(800_001, Vec::new()),
+            &HashMap::<String, Vec<u8>>::new(),
+        )
+        .unwrap();
+        assert_eq!(restored.treasury(), manager.treasury());
+    }
+
+    #[test]
+    fn test_referral_rebate_credits_referrer() {
+        let (secret_key, _) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager();
+        manager
+            .set_fee_schedule(FeeSchedule {
+                protocol_fee_bps: 200,
+                settlement_fee_bps: 0,
+            })
+            .unwrap();
+        manager.enable_claimable_balances(ClaimableLedger::new(oracle_vm_common::NetworkProfile::TESTNET, secret_key, 10_000));
+        manager.referrals_mut().register("ALICE", "alice", 1).unwrap();
+
+        // 추천인 본인 거래는 거부하고 아무것도 잠그지 않음
+        let own = CreateOptionRequest {
+            user_id: "alice".to_string(),
+            referral_code: Some("ALICE".to_string()),
+            ..call_request("CALL-REF-0")
+        };
+        assert!(matches!(
+            manager.create_option_idempotent("key-ref-0", own),
+            Err(ContractError::InvalidReferral(_))
+        ));
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+
+        let referred = CreateOptionRequest {
+            referral_code: Some("ALICE".to_string()),
+            ..call_request("CALL-REF-1")
+        };
+        manager.create_option_idempotent("key-ref-1", referred).unwrap();
+
+        // 수수료 5,000 중 10%는 추천인 청구 잔고, 나머지는 재무 계정
+        assert_eq!(manager.claims().unwrap().balance("alice"), 500);
+        assert_eq!(manager.treasury().protocol_fees, 4_500);
+        assert_eq!(manager.pool_state.total_liquidity, 100_245_000);
+        assert_eq!(manager.referrals().stats("ALICE").rebates, 500);
+
+        let report = crate::reporting::ReportGenerator::new(manager.event_store()).generate(
+            crate::reporting::ReportKind::Referrals,
+            0,
+            u64::MAX,
+        );
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.rows[0][2], crate::reporting::ReportCell::Int(1));
+        assert_eq!(report.rows[0][4], crate::reporting::ReportCell::Int(500));
+    }
+
+    #[test]
+    fn test_partial_exit_issues_claim_on_locked_collateral() {
+        let mut manager = SimpleContractManager::new();
+        assert_eq!(manager.deposit_liquidity("lp1", 60_000_000).unwrap(), 60_000_000);
+        assert_eq!(manager.deposit_liquidity("lp2", 40_000_000).unwrap(), 40_000_000);
+        manager
+            .create_option(
+                "CALL-LOCK".to_string(),
+                OptionType::Call,
+                7_000_000,
+                80_000_000,
+                1_000_000,
+                800_000,
+                "user1".to_string(),
+            )
+            .unwrap();
+
+        // 지분 가치 4,040만 중 사용 가능 비율(21/101)만 바로 출금
+        let (plan, claim_id) = manager.exit_liquidity("lp2", 40_000_000).unwrap();
+        assert_eq!(plan.immediate, 8_400_000);
+        assert_eq!(plan.locked, 32_000_000);
+        let claim_id = claim_id.unwrap();
+        assert_eq!(manager.pool_state.total_liquidity, 92_600_000);
+        assert_eq!(manager.redeem_exit_claim(claim_id, "lp2").unwrap(), 0);
+
+        manager.transfer_exit_claim(claim_id, "lp2", "carol").unwrap();
+        assert!(manager.redeem_exit_claim(claim_id, "lp2").is_err());
+
+        // OTM 정산으로 담보가 풀리면 청구권 전액 상환
+        manager.settle_option("CALL-LOCK", 6_000_000).unwrap();
+        assert_eq!(manager.redeem_exit_claim(claim_id, "carol").unwrap(), 32_000_000);
+        assert_eq!(manager.pool_state.total_liquidity, 60_600_000);
+        assert_eq!(manager.lp_book().net_asset_value(&manager.pool_state), 60_600_000);
+        assert!(manager.lp_book().claim(claim_id).is_none());
+
+        let snapshot = manager.snapshot(800_000, vec![]);
+        assert_eq!(snapshot.lp_book.as_ref().map(|book| book.shares("lp1")), Some(60_000_000));
+    }
+
+    #[test]
+    fn test_first_deposit_does_not_capture_existing_liquidity() {
+        let mut manager = funded_manager();
+        assert!(manager.deposit_liquidity("lp1", 0).is_err());
+        assert_eq!(manager.deposit_liquidity("lp1", 1_000).unwrap(), 1_000);
+        assert_eq!(manager.lp_book().shares(crate::lp_book::TREASURY_PROVIDER), 100_000_000);
+
+        // 첫 입금자가 전부 빼도 자기 입금액만 받음
+        let (plan, _) = manager.exit_liquidity("lp1", 1_000).unwrap();
+        assert_eq!(plan.immediate, 1_000);
+        assert_eq!(manager.pool_state.total_liquidity, 100_000_000);
+    }
+
+    #[test]
+    fn test_price_guard_defers_out_of_band_settlement() {
+        let mut manager = funded_manager();
+        manager
+            .create_option(
+                "CALL-GLITCH".to_string(),
+                OptionType::Call,
+                7_000_000,
+                10_000_000,
+                300_000,
+                800_000,
+                "user4".to_string(),
+            )
+            .unwrap();
+        manager.enable_price_guard(PriceBandConfig::default());
+        for price in [7_100_000, 7_120_000, 7_110_000] {
+            manager.observe_consensus_price(price);
+        }
+
+        // 한 틱 튄 가격으로는 지급하지 않고 옵션은 Active로 남음
+        assert!(matches!(
+            manager.settle_option("CALL-GLITCH", 14_000_000),
+            Err(SettlementError::PriceOutOfBand { reference: 7_110_000, .. })
+        ));
+        assert_eq!(manager.options["CALL-GLITCH"].status, OptionStatus::Active);
+        assert_eq!(manager.price_guard().unwrap().alerts().len(), 1);
+
+        assert!(manager.settle_option("CALL-GLITCH", 7_150_000).unwrap() > 0);
+    }
+
+    fn signed_quote(secret_key: &oracle_vm_common::crypto::SecretKey, valid_until: u64) -> OptionQuote {
+        let mut quote = OptionQuote {
+            quote_id: "Q-1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            premium: 250_000,
+            spot_price: 7_000_000,
+            issued_at: valid_until - 30,
+            valid_until,
+            premium_usd: 17_500,
+            otc: false,
+            exercise: ExercisePolicy::default(),
+            referral_code: None,
+            tenant_id: None,
+            theoretical_premium: None,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+            signature: String::new(),
+        };
+        quote.sign(secret_key).unwrap();
+        quote
+    }
+
+    /// 만기 1,000블록 전 팁 헤더를 기록해 `expiry` 날짜의 정산 시각이 `expiry_height`가 되게 함
+    fn chain_to_expiry(manager: &mut SimpleContractManager, expiry: &str, expiry_height: u32) {
+        let mut headers = BlockClock::new();
+        let expiry_time = expiry_date_timestamp(expiry).unwrap();
+        headers.observe(expiry_height - 1_000, expiry_time - 1_000 * AVG_BLOCK_SECS);
+        manager.set_block_clock(headers);
+    }
+
+    #[test]
+    fn test_contract_spec_and_partial_fill() {
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager();
+        manager.require_contract_spec(ContractSpec::default());
+
+        // 계약 단위(0.01 BTC) 배수가 아니거나 틱에 맞지 않으면 거부
+        let fractional = manager.create_option("F-1".to_string(), OptionType::Call, 7_000_000, 1_500_000, 25_000, 800_000, "user".to_string());
+        assert!(matches!(fractional, Err(ContractError::Pricing(PricingError::NonConformingSize { .. }))));
+        let off_tick = manager.create_option("F-2".to_string(), OptionType::Call, 7_000_000, 1_000_000, 25_050, 800_000, "user".to_string());
+        assert!(matches!(off_tick, Err(ContractError::Pricing(PricingError::OffTickPremium { .. }))));
+
+        // 10계약 호가 중 3계약만 체결, 나머지는 취소
+        manager.require_quotes(public_key);
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+        let now = chrono::Utc::now().timestamp() as u64;
+        let quote = signed_quote(&secret_key, now + 30);
+        manager
+            .fill_quote(&quote, 3_000_000, "F-3".to_string(), 800_000, "user".to_string())
+            .unwrap();
+        assert_eq!(manager.options["F-3"].quantity, 3_000_000);
+        assert_eq!(manager.options["F-3"].premium_paid, 75_000);
+        assert!(matches!(
+            manager.fill_quote(&quote, 1_000_000, "F-4".to_string(), 800_000, "user".to_string()),
+            Err(ContractError::QuoteReused(_))
+        ));
+
+        let mut quote = OptionQuote { quote_id: "Q-2".to_string(), ..signed_quote(&secret_key, now + 30) };
+        quote.sign(&secret_key).unwrap();
+        assert!(manager
+            .fill_quote(&quote, 11_000_000, "F-5".to_string(), 800_000, "user".to_string())
+            .is_err());
+        assert!(matches!(
+            manager.fill_quote(&quote, 2_500_000, "F-5".to_string(), 800_000, "user".to_string()),
+            Err(ContractError::Pricing(PricingError::NonConformingSize { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_calendar_expiry_required_for_quotes() {
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+        manager.require_calendar(ExpiryCalendar::default());
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut quote = signed_quote(&secret_key, now + 30);
+        let result = manager.create_option_from_quote(&quote, "CAL-1".to_string(), 800_000, "user".to_string());
+        assert!(matches!(
+            result,
+            Err(ContractError::Pricing(oracle_vm_common::PricingError::NonStandardExpiry(_)))
+        ));
+
+        quote.expiry = ExpiryCalendar::default().expiries(quote.issued_at)[0].date.clone();
+        quote.sign(&secret_key).unwrap();
+        let expiry_height = manager.quote_expiry_height(&quote).unwrap();
+        manager
+            .create_option_from_quote(&quote, "CAL-1".to_string(), expiry_height, "user".to_string())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_quote_required_and_single_use() {
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        // 호가 없는 생성은 거부
+        assert_eq!(
+            manager.create_option(
+                "CALL-NOQUOTE".to_string(),
+                OptionType::Call,
+                7_000_000,
+                10_000_000,
+                250_000,
+                800_000,
+                "user5".to_string(),
+            ),
+            Err(ContractError::QuoteRequired)
+        );
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let quote = signed_quote(&secret_key, now + 30);
+        manager
+            .create_option_from_quote(&quote, "CALL-Q1".to_string(), 800_000, "user5".to_string())
+            .unwrap();
+        assert_eq!(manager.options["CALL-Q1"].premium_paid, 250_000);
+
+        // 같은 호가 재사용 거부
+        assert_eq!(
+            manager.create_option_from_quote(
+                &quote,
+                "CALL-Q2".to_string(),
+                800_000,
+                "user5".to_string()
+            ),
+            Err(ContractError::QuoteReused("Q-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_fill_must_use_quote_expiry_height() {
+        let (mut manager, secret_key) = quoting_manager();
+
+        // 블록 높이를 하나도 모르면 호가 만기를 셀 수 없음 (재시도 가능)
+        let now = chrono::Utc::now().timestamp() as u64;
+        let quote = signed_quote(&secret_key, now + 30);
+        let unknown = manager
+            .create_option_from_quote(&quote, "CALL-H1".to_string(), 800_000, "user".to_string())
+            .unwrap_err();
+        assert_eq!(unknown.code(), "CONTRACT_EXPIRY_HEIGHT_UNKNOWN");
+        assert!(unknown.is_retryable());
+
+        // 호가가 가격을 매긴 만기보다 늦은 높이는 거부하고 호가는 쓰지 않음
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+        assert_eq!(manager.quote_expiry_height(&quote), Ok(800_000));
+        assert_eq!(
+            manager.create_option_from_quote(&quote, "CALL-H1".to_string(), 800_000 + 4_320, "user".to_string()),
+            Err(ContractError::ExpiryMismatch {
+                quote_id: "Q-1".to_string(),
+                quoted: 800_000,
+                requested: 804_320,
+            })
+        );
+        assert!(manager.options.is_empty());
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+
+        manager
+            .create_option_from_quote(&quote, "CALL-H1".to_string(), 800_000, "user".to_string())
+            .unwrap();
+        assert_eq!(manager.options["CALL-H1"].expiry_height, 800_000);
+    }
+
+    #[test]
+    fn test_deleverage_socializes_shortfall_before_settlement() {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(30_000_000).unwrap();
+        manager
+            .create_option("BIG".to_string(), OptionType::Call, 7_000_000, 10_000_000, 100_000, 800_000, "user1".to_string())
+            .unwrap();
+        manager
+            .create_option("SMALL".to_string(), OptionType::Call, 7_000_000, 5_000_000, 200_000, 800_000, "user2".to_string())
+            .unwrap();
+
+        // 풀 자산 안이면 삭감 없음, 만기 전 옵션은 대상 아님
+        assert!(manager.deleverage(8_000_000, 800_000).unwrap().is_empty());
+        assert!(manager.deleverage(250_000_000, 799_999).unwrap().is_empty());
+
+        // 지급액이 담보를 크게 넘는 급등: 부족분을 수익성이 높은 BIG부터 분담
+        let plan = manager.deleverage(250_000_000, 800_000).unwrap();
+        assert_eq!(plan.liabilities, 24_300_000 + 12_150_000);
+        assert_eq!(plan.shortfall, plan.liabilities - manager.pool_state.total_liquidity);
+        assert_eq!(plan.assets, manager.pool_state.available_liquidity + 15_000_000);
+        assert_eq!(plan.haircuts.iter().map(|cut| cut.haircut).sum::<u64>(), plan.shortfall);
+        assert_eq!(plan.haircuts[0].option_id, "BIG");
+        assert_eq!(manager.deleverage(250_000_000, 800_000).unwrap(), plan);
+        assert!(matches!(
+            manager.audit().trail("BIG").last().unwrap().action,
+            AuditAction::Haircut { rank: 1, .. }
+        ));
+
+        // 정산은 삭감 후 지급액 기준, 풀 자산을 넘지 않음
+        let owed = manager.haircut("BIG").unwrap().paid();
+        assert_eq!(
+            manager.settle_option("BIG", 250_000_000).unwrap(),
+            owed - manager.fee_schedule.settlement_fee(owed)
+        );
+        manager.settle_option("SMALL", 250_000_000).unwrap();
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+        assert_eq!(manager.haircuts().count(), 2);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+    }
+
+    #[test]
+    fn test_buy_back_closes_option_and_releases_collateral() {
+        use crate::anchor_backend::AnchorKind;
+
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager();
+        manager
+            .create_option("CALL-BB".to_string(), OptionType::Call, 7_000_000, 10_000_000, 250_000, 800_000, "user9".to_string())
+            .unwrap();
+        manager.require_quotes(public_key);
+        let available = manager.pool_state.available_liquidity;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let sign = |quote: BuyBackQuote| {
+            let mut quote = quote;
+            quote.sign(&secret_key).unwrap();
+            quote
+        };
+        let quote = sign(BuyBackQuote {
+            quote_id: "B-1".to_string(),
+            option_id: "CALL-BB".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            value: 180_000,
+            theoretical_value: 190_000,
+            spot_price: 7_000_000,
+            issued_at: now,
+            valid_until: now + 30,
+            tenant_id: None,
+            signature: String::new(),
+        });
+
+        // 보유자가 아니거나 조건이 다른 호가는 거부
+        assert!(matches!(manager.buy_back_option(&quote, "mallory"), Err(ContractError::BuyBack(_))));
+        let other_strike = sign(BuyBackQuote { quote_id: "B-2".to_string(), strike_price: 6_000_000, ..quote.clone() });
+        assert!(matches!(manager.buy_back_option(&other_strike, "user9"), Err(ContractError::BuyBack(_))));
+        assert!(manager.cancel_anchor_payload("CALL-BB").is_none());
+
+        assert_eq!(manager.buy_back_option(&quote, "user9").unwrap(), 180_000);
+        assert_eq!(manager.options["CALL-BB"].status, OptionStatus::Cancelled);
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+        assert_eq!(manager.pool_state.active_options, 0);
+        assert_eq!(manager.pool_state.available_liquidity, available + 10_000_000 - 180_000);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+
+        // 종료된 옵션은 정산/재되사기 불가, 조기 종료는 CNL 앵커로 기록
+        assert!(manager.settle_option("CALL-BB", 7_500_000).is_err());
+        assert_eq!(
+            manager.buy_back_option(&quote, "user9"),
+            Err(ContractError::QuoteReused("B-1".to_string()))
+        );
+        let payload = manager.cancel_anchor_payload("CALL-BB").unwrap();
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::Cancel);
+        assert_eq!(payload[35..], manager.audit().head_hash("CALL-BB").unwrap());
+        assert!(matches!(
+            manager.audit().trail("CALL-BB").last().unwrap().action,
+            AuditAction::BoughtBack { amount: 180_000, .. }
+        ));
+    }
+
+    #[test]
+    fn test_roll_nets_premium_and_collateral() {
+        use crate::anchor_backend::AnchorKind;
+
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager();
+        manager
+            .create_option("CALL-R1".to_string(), OptionType::Call, 7_000_000, 10_000_000, 250_000, 800_144, "user9".to_string())
+            .unwrap();
+        manager.require_quotes(public_key);
+        chain_to_expiry(&mut manager, "2024-03-01", 800_144);
+        let available = manager.pool_state.available_liquidity;
+        let locked = manager.pool_state.locked_collateral;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut buy_back = BuyBackQuote {
+            quote_id: "B-R1".to_string(),
+            option_id: "CALL-R1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            value: 180_000,
+            theoretical_value: 190_000,
+            spot_price: 7_000_000,
+            issued_at: now,
+            valid_until: now + 30,
+            tenant_id: None,
+            signature: String::new(),
+        };
+        buy_back.sign(&secret_key).unwrap();
+        let quote = signed_quote(&secret_key, now + 30);
+
+        // 만기를 늦추지 않는 롤은 거부
+        assert!(matches!(
+            manager.roll_option(&buy_back, &quote, "CALL-R2".to_string(), 800_144, "user9"),
+            Err(ContractError::BuyBack(_))
+        ));
+
+        // 새 만기 높이는 호가의 만기 날짜에서 계산한 높이와 같아야 함
+        chain_to_expiry(&mut manager, "2024-03-01", 800_288);
+        assert_eq!(
+            manager.roll_option(&buy_back, &quote, "CALL-R2".to_string(), 800_432, "user9"),
+            Err(ContractError::ExpiryMismatch { quote_id: "Q-1".to_string(), quoted: 800_288, requested: 800_432 })
+        );
+        assert!(!manager.options.contains_key("CALL-R2"));
+
+        let outcome = manager
+            .roll_option(&buy_back, &quote, "CALL-R2".to_string(), 800_288, "user9")
+            .unwrap();
+        assert_eq!(outcome.net_premium, 70_000);
+        assert_eq!(outcome.collateral_delta, 0);
+
+        // 차액만 이동: 담보는 그대로, 풀은 수수료를 뺀 순프리미엄만 수취
+        let fee = manager.fee_schedule.protocol_fee(250_000);
+        assert_eq!(manager.options["CALL-R1"].status, OptionStatus::Cancelled);
+        assert_eq!(manager.options["CALL-R2"].status, OptionStatus::Active);
+        assert_eq!(manager.pool_state.locked_collateral, locked);
+        assert_eq!(manager.pool_state.active_options, 1);
+        assert_eq!(manager.pool_state.available_liquidity, available + 70_000 - fee);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+        let rolled = manager
+            .event_store()
+            .events()
+            .iter()
+            .filter(|event| matches!(event.kind, PoolEventKind::OptionRolled { .. }))
+            .count();
+        assert_eq!(rolled, 1);
+
+        // 새 옵션의 감사 기록은 Rolled 한 건으로 시작하고, 앵커는 RLL만 남김
+        let trail = manager.audit().trail("CALL-R2");
+        assert_eq!(trail.len(), 1);
+        assert!(matches!(&trail[0].action, AuditAction::Rolled { from_option_id, .. } if from_option_id == "CALL-R1"));
+        assert!(manager.cancel_anchor_payload("CALL-R1").is_none());
+        let payload = manager.roll_anchor_payload("CALL-R2").unwrap();
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::Roll);
+        assert_eq!(payload[3..35], oracle_vm_common::crypto::sha256(b"CALL-R1"));
+        assert_eq!(payload[35..], manager.audit().head_hash("CALL-R2").unwrap());
+        assert_eq!(
+            manager.roll_option(&buy_back, &quote, "CALL-R3".to_string(), 800_288, "user9"),
+            Err(ContractError::QuoteReused("B-R1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_roll_respects_open_interest_caps_and_reports_open_interest() {
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_144);
+        manager.set_open_interest_caps(OpenInterestCaps {
+            max_strike_pct: Some(15.0),
+            ..OpenInterestCaps::default()
+        });
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let quote = |quote_id: &str, expiry: &str| {
+            let mut quote = OptionQuote {
+                quote_id: quote_id.to_string(),
+                expiry: expiry.to_string(),
+                ..signed_quote(&secret_key, now + 30)
+            };
+            quote.sign(&secret_key).unwrap();
+            quote
+        };
+        manager
+            .create_option_from_quote(&quote("Q-1", "2024-03-01"), "CALL-Q1".to_string(), 800_144, "user9".to_string())
+            .unwrap();
+        manager
+            .create_option_from_quote(&quote("Q-2", "2024-04-01"), "CALL-Q2".to_string(), 804_608, "user9".to_string())
+            .unwrap();
+
+        let mut buy_back = BuyBackQuote {
+            quote_id: "B-Q1".to_string(),
+            option_id: "CALL-Q1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            value: 180_000,
+            theoretical_value: 190_000,
+            spot_price: 7_000_000,
+            issued_at: now,
+            valid_until: now + 30,
+            tenant_id: None,
+            signature: String::new(),
+        };
+        buy_back.sign(&secret_key).unwrap();
+
+        // 롤로 옮겨 갈 만기의 행사가 한도(약 15M)에 이미 10M이 잠겨 있으면 거부
+        let roll = quote("Q-3", "2024-04-01");
+        assert!(matches!(
+            manager.roll_option(&buy_back, &roll, "CALL-Q3".to_string(), 804_608, "user9"),
+            Err(ContractError::Pricing(PricingError::OpenInterestCapExceeded { ref scope, .. })) if scope == "strike"
+        ));
+        assert_eq!(manager.options["CALL-Q1"].status, OptionStatus::Active);
+
+        manager.set_open_interest_caps(OpenInterestCaps {
+            max_strike_pct: Some(25.0),
+            ..OpenInterestCaps::default()
+        });
+        manager
+            .roll_option(&buy_back, &roll, "CALL-Q3".to_string(), 804_608, "user9")
+            .unwrap();
+
+        // 닫힌 옵션은 보고에서 빠지고 새 옵션은 호가 만기로 묶임
+        assert_eq!(
+            manager.open_interest_report(),
+            vec![OpenInterestEntry {
+                strike_price: 7_000_000,
+                expiry: "2024-04-01".to_string(),
+                collateral: 20_000_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_american_option_exercises_early_at_consensus_price() {
+        use crate::anchor_backend::AnchorKind;
+        use oracle_vm_common::{Clock, ManualClock};
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let clock = ManualClock::new(now);
+        let (mut manager, secret_key) = quoting_manager_at(clock.shared());
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        let european = signed_quote(&secret_key, now + 30);
+        let mut american = OptionQuote {
+            quote_id: "Q-AM".to_string(),
+            style: ExerciseStyle::American,
+            ..european.clone()
+        };
+        american.sign(&secret_key).unwrap();
+        manager
+            .create_option_from_quote(&european, "CALL-EU".to_string(), 800_000, "user7".to_string())
+            .unwrap();
+        manager
+            .create_option_from_quote(&american, "CALL-AM".to_string(), 800_000, "user7".to_string())
+            .unwrap();
+        assert!(manager.options["CALL-AM"].terms.style.is_american());
+        assert!(manager.options["CALL-EU"].terms.style.is_european());
+
+        // 유럽식은 만기 전 행사 불가, 팁 높이나 합의 가격을 모르면 행사를 미룸
+        assert!(matches!(
+            manager.exercise_option("CALL-EU", "user7"),
+            Err(SettlementError::EarlyExercise(_))
+        ));
+        assert!(matches!(
+            manager.exercise_option("CALL-AM", "user7"),
+            Err(SettlementError::Postponed(_))
+        ));
+        manager.observe_height(799_000);
+        assert!(matches!(
+            manager.exercise_option("CALL-AM", "user7"),
+            Err(SettlementError::Postponed(_))
+        ));
+
+        // 보유자만, ITM일 때만, 최근 합의 가격으로만 행사
+        manager.observe_consensus_price(6_900_000);
+        assert!(matches!(
+            manager.exercise_option("CALL-AM", "user7"),
+            Err(SettlementError::EarlyExercise(_))
+        ));
+        manager.observe_consensus_price(7_500_000);
+        assert!(matches!(
+            manager.exercise_option("CALL-AM", "mallory"),
+            Err(SettlementError::EarlyExercise(_))
+        ));
+        clock.advance(DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS + 1);
+        assert!(matches!(
+            manager.exercise_option("CALL-AM", "user7"),
+            Err(SettlementError::Postponed(_))
+        ));
+        manager.observe_consensus_price(7_500_000);
+
+        let owed = manager.options["CALL-AM"].payout_at(7_500_000);
+        let paid = manager.exercise_option("CALL-AM", "user7").unwrap();
+        assert_eq!(paid, owed - manager.fee_schedule.settlement_fee(owed));
+        assert_eq!(manager.options["CALL-AM"].status, OptionStatus::Settled);
+        assert_eq!(manager.options["CALL-EU"].status, OptionStatus::Active);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+
+        // 행사 기록 뒤에 지급이 이어지고, EXR 앵커는 조기 행사한 옵션에만
+        let actions: Vec<_> = manager.audit().trail("CALL-AM").iter().map(|record| &record.action).collect();
+        assert!(matches!(actions[actions.len() - 2], AuditAction::Exercised { spot_price: 7_500_000, .. }));
+        assert!(matches!(actions[actions.len() - 1], AuditAction::Payout { .. }));
+        let payload = manager.exercise_anchor_payload("CALL-AM").unwrap();
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::Exercise);
+        assert_eq!(payload[35..], manager.audit().head_hash("CALL-AM").unwrap());
+        assert!(manager.exercise_anchor_payload("CALL-EU").is_none());
+
+        // 만기 높이에 닿으면 행사 불가
+        let mut late = OptionQuote {
+            quote_id: "Q-AM2".to_string(),
+            style: ExerciseStyle::American,
+            ..signed_quote(&secret_key, clock.now() + 30)
+        };
+        late.sign(&secret_key).unwrap();
+        manager
+            .create_option_from_quote(&late, "CALL-AM2".to_string(), 800_000, "user7".to_string())
+            .unwrap();
+        manager.observe_height(800_000);
+        assert!(matches!(
+            manager.exercise_option("CALL-AM2", "user7"),
+            Err(SettlementError::EarlyExercise(_))
+        ));
+    }
+
+    #[test]
+    fn test_barrier_touch_is_recorded_once_and_honored_at_settlement() {
+        use oracle_vm_common::{Barrier, BarrierKind};
+
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let barrier_quote = |quote_id: &str, kind: BarrierKind| {
+            let mut quote = OptionQuote {
+                quote_id: quote_id.to_string(),
+                barrier: Some(Barrier { kind, level: 8_000_000 }),
+                ..signed_quote(&secret_key, now + 30)
+            };
+            quote.sign(&secret_key).unwrap();
+            quote
+        };
+        manager
+            .create_option_from_quote(&barrier_quote("Q-KO", BarrierKind::UpAndOut), "CALL-KO".to_string(), 800_000, "user7".to_string())
+            .unwrap();
+        manager
+            .create_option_from_quote(&barrier_quote("Q-KI", BarrierKind::UpAndIn), "CALL-KI".to_string(), 800_000, "user7".to_string())
+            .unwrap();
+
+        // 배리어 아래에서는 변화 없음, 처음 닿을 때 한 번만 기록
+        assert!(manager.monitor_barriers(1_000, 7_900_000).unwrap().is_empty());
+        assert_eq!(manager.monitor_barriers(1_060, 8_100_000).unwrap(), ["CALL-KI", "CALL-KO"]);
+        assert!(manager.monitor_barriers(1_120, 8_200_000).unwrap().is_empty());
+        let touch = manager.options["CALL-KO"].terms.barrier.unwrap().touched.unwrap();
+        assert_eq!((touch.observed_at, touch.spot_price), (1_060, 8_100_000));
+        assert!(matches!(
+            manager.audit().trail("CALL-KO").last().unwrap().action,
+            AuditAction::BarrierTouched { observed_at: 1_060, .. }
+        ));
+
+        // 이미 닿은 배리어로는 새 옵션을 열 수 없음
+        manager.observe_consensus_price(8_100_000);
+        assert!(matches!(
+            manager.create_option_from_quote(&barrier_quote("Q-KO2", BarrierKind::UpAndOut), "CALL-KO2".to_string(), 800_000, "user7".to_string()),
+            Err(ContractError::InvalidQuote(_))
+        ));
+
+        // knock-out은 지급 없음, knock-in은 일반 콜처럼 지급
+        assert_eq!(manager.settle_option("CALL-KO", 7_500_000).unwrap(), 0);
+        let owed = manager.options["CALL-KI"].payout_at(7_500_000);
+        assert_eq!(
+            manager.settle_option("CALL-KI", 7_500_000).unwrap(),
+            owed - manager.fee_schedule.settlement_fee(owed)
+        );
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+    }
+
+    #[test]
+    fn test_binary_option_locks_and_pays_fixed_amount() {
+        use crate::anchor_backend::AnchorKind;
+
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let binary_quote = |quote_id: &str, option_type: OptionType| {
+            let mut quote = OptionQuote {
+                quote_id: quote_id.to_string(),
+                option_type,
+                payoff: Payoff::Binary,
+                ..signed_quote(&secret_key, now + 30)
+            };
+            quote.sign(&secret_key).unwrap();
+            quote
+        };
+        manager
+            .create_option_from_quote(&binary_quote("Q-BC", OptionType::Call), "BIN-CALL".to_string(), 800_000, "user8".to_string())
+            .unwrap();
+        manager
+            .create_option_from_quote(&binary_quote("Q-BP", OptionType::Put), "BIN-PUT".to_string(), 800_000, "user8".to_string())
+            .unwrap();
+
+        // 풋도 행사가와 무관하게 지급액(수량)만 잠금
+        assert!(manager.options["BIN-PUT"].terms.payoff.is_binary());
+        assert_eq!(manager.pool_state.locked_collateral, 2 * 10_000_000);
+
+        // 스냅샷 검증도 바이너리 담보 기준
+        let restored = SimpleContractManager::restore(
+            manager.snapshot(800_001, Vec::new()),
+            &HashMap::<String, Vec<u8>>::new(),
+        )
+        .unwrap();
+        assert_eq!(restored.options["BIN-CALL"].terms, manager.options["BIN-CALL"].terms);
+
+        // 행사가를 조금만 넘어도 전액, 반대쪽은 0
+        assert_eq!(
+            manager.settle_option("BIN-CALL", 7_000_100).unwrap(),
+            10_000_000 - manager.fee_schedule.settlement_fee(10_000_000)
+        );
+        assert_eq!(manager.settle_option("BIN-PUT", 7_000_100).unwrap(), 0);
+        assert_eq!(manager.pool_state.locked_collateral, 0);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+
+        // 정산 앵커는 고정 지급액을 담은 BIN 스키마
+        let payload = manager.settle_anchor_payload("BIN-CALL").unwrap();
+        assert_eq!(payload.len(), 75);
+        assert_eq!(AnchorKind::of_payload(&payload), AnchorKind::BinarySettle);
+        assert_eq!(payload[35..43], 10_000_000u64.to_be_bytes());
+    }
+
+    #[test]
+    fn test_expired_or_tampered_quote_rejected() {
+        let (mut manager, secret_key) = quoting_manager();
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let expired = signed_quote(&secret_key, now - 1);
+        assert!(matches!(
+            manager.create_option_from_quote(
+                &expired,
+                "CALL-OLD".to_string(),
+                800_000,
+                "user6".to_string()
+            ),
+            Err(ContractError::QuoteExpired { .. })
+        ));
+
+        let mut tampered = signed_quote(&secret_key, now + 30);
+        tampered.premium = 1;
+        assert!(matches!(
+            manager.create_option_from_quote(
+                &tampered,
+                "CALL-CHEAP".to_string(),
+                800_000,
+                "user6".to_string()
+            ),
+            Err(ContractError::InvalidQuote(_))
+        ));
+        assert!(manager.options.is_empty());
+    }
+
+    #[test]
+    fn test_tenant_pool_only_fills_its_own_quotes() {
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+        manager.set_tenant("acme");
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let default_quote = signed_quote(&secret_key, now + 30);
+        assert!(matches!(
+            manager.create_option_from_quote(&default_quote, "CALL-T0".to_string(), 800_000, "user8".to_string()),
+            Err(ContractError::InvalidQuote(_))
+        ));
+
+        let mut acme_quote = OptionQuote {
+            quote_id: "Q-ACME".to_string(),
+            tenant_id: Some("acme".to_string()),
+            ..signed_quote(&secret_key, now + 30)
+        };
+        acme_quote.sign(&secret_key).unwrap();
+        manager
+            .create_option_from_quote(&acme_quote, "CALL-T1".to_string(), 800_000, "user8".to_string())
+            .unwrap();
+        assert_eq!(manager.get_system_status()["tenant_id"], "acme");
+    }
+
+    #[test]
+    fn test_risk_limits_block_oversized_options() {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(1_000_000_000).unwrap();
+        manager.set_risk_limits(RiskLimits {
+            max_option_collateral: Some(15_000_000),
+            max_locked_collateral: Some(25_000_000),
+        });
+
+        let oversized = CreateOptionRequest {
+            quantity: 20_000_000,
+            ..call_request("CALL-BIG")
+        };
+        assert!(matches!(
+            manager.create_option_with_request(oversized),
+            Err(ContractError::RiskLimitExceeded(_))
+        ));
+
+        manager.create_option_with_request(call_request("CALL-L1")).unwrap();
+        manager.create_option_with_request(call_request("CALL-L2")).unwrap();
+        // 세 번째 10M 콜은 풀 한도 25M 초과
+        assert!(matches!(
+            manager.create_option_with_request(call_request("CALL-L3")),
+            Err(ContractError::RiskLimitExceeded(_))
+        ));
+        assert_eq!(manager.pool_state.locked_collateral, 20_000_000);
+    }
+
+    #[test]
+    fn test_open_interest_caps_shrink_near_expiry() {
+        let mut manager = funded_manager();
+        manager.set_open_interest_caps(OpenInterestCaps {
+            max_strike_pct: Some(25.0),
+            max_expiry_pct: Some(40.0),
+            taper_secs: 144 * AVG_BLOCK_SECS,
+            min_fraction: 0.5,
+        });
+
+        // 높이를 모르면 전체 한도: 행사가당 유동성의 25% (프리미엄 포함 약 25M)
+        manager.create_option_with_request(call_request("CALL-OI1")).unwrap();
+        manager.create_option_with_request(call_request("CALL-OI2")).unwrap();
+        let strike_cap = manager.pool_state.total_liquidity / 4;
+        assert_eq!(
+            manager.open_interest_capacity(7_000_000, 800_000).remaining,
+            Some(strike_cap - 20_000_000)
+        );
+        let crowded = manager.create_option_with_request(call_request("CALL-OI3"));
+        assert!(matches!(
+            crowded,
+            Err(ContractError::Pricing(PricingError::OpenInterestCapExceeded { ref scope, .. })) if scope == "strike"
+        ));
+
+        // 다른 행사가는 만기 한도(40M) 안에서만
+        let other_strike = |id: &str, quantity| CreateOptionRequest {
+            strike_price: 8_000_000,
+            quantity,
+            ..call_request(id)
+        };
+        assert!(manager.create_option_with_request(other_strike("CALL-OI4", 25_000_000)).is_err());
+        manager.create_option_with_request(other_strike("CALL-OI4", 15_000_000)).unwrap();
+
+        // 만기 72블록 전에는 한도가 75%로 줄어 이미 채운 행사가에는 더 열 수 없음
+        manager.observe_height(800_000 - 72);
+        let capacity = manager.open_interest_capacity(7_000_000, 800_000);
+        assert!(capacity.strike_cap < Some(20_000_000));
+        assert_eq!(capacity.remaining, Some(0));
+        assert_eq!(manager.pool_state.locked_collateral, 35_000_000);
+    }
+
+    fn call_request(option_id: &str) -> CreateOptionRequest {
+        CreateOptionRequest {
+            option_id: option_id.to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            quantity: 10_000_000,
+            premium: 250_000,
+            expiry_height: 800_000,
+            user_id: "user7".to_string(),
+            referral_code: None,
+        }
+    }
+
+    #[test]
+    fn test_idempotent_retry_does_not_double_lock() {
+        let mut manager = funded_manager();
+
+        manager
+            .create_option_idempotent("key-1", call_request("CALL-IDEM"))
+            .unwrap();
+        let locked = manager.pool_state.locked_collateral;
+
+        // 재시도는 처음 결과를 그대로 반환
+        manager
+            .create_option_idempotent("key-1", call_request("CALL-IDEM"))
+            .unwrap();
+        assert_eq!(manager.pool_state.locked_collateral, locked);
+        assert_eq!(manager.pool_state.active_options, 1);
+
+        // 같은 키로 다른 요청
+        assert_eq!(
+            manager.create_option_idempotent("key-1", call_request("CALL-OTHER")),
+            Err(ContractError::IdempotencyConflict("key-1".to_string()))
+        );
+        // 키 없이 같은 옵션 ID 재생성
+        assert_eq!(
+            manager.create_option_idempotent("key-2", call_request("CALL-IDEM")),
+            Err(ContractError::DuplicateOption("CALL-IDEM".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_quote_fill_idempotent_retry() {
+        let (mut manager, secret_key) = quoting_manager();
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let quote = signed_quote(&secret_key, now + 30);
+        manager
+            .fill_quote_idempotent("fill-1", &quote, "CALL-Q".to_string(), 800_000, "mm".to_string())
+            .unwrap();
+        let locked = manager.pool_state.locked_collateral;
+
+        // 응답을 놓친 재시도는 QuoteReused 대신 처음 결과
+        manager
+            .fill_quote_idempotent("fill-1", &quote, "CALL-Q".to_string(), 800_000, "mm".to_string())
+            .unwrap();
+        assert_eq!(manager.pool_state.locked_collateral, locked);
+        assert_eq!(
+            manager.fill_quote_idempotent("fill-1", &quote, "CALL-R".to_string(), 800_000, "mm".to_string()),
+            Err(ContractError::IdempotencyConflict("fill-1".to_string()))
+        );
+        assert_eq!(
+            manager.fill_quote_idempotent("fill-2", &quote, "CALL-R".to_string(), 800_000, "mm".to_string()),
+            Err(ContractError::QuoteReused(quote.quote_id.clone()))
+        );
+    }
+
+    #[test]
+    fn test_retryable_failure_not_cached() {
+        let mut manager = SimpleContractManager::new();
+
+        // 유동성 부족은 재시도 가능
+        assert!(matches!(
+            manager.create_option_idempotent("key-1", call_request("CALL-LATER")),
+            Err(ContractError::InsufficientLiquidity { .. })
+        ));
+
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option_idempotent("key-1", call_request("CALL-LATER"))
+            .unwrap();
+        assert!(manager.options.contains_key("CALL-LATER"));
+    }
+
+    /// 옵션 생성 이벤트만 기록에 실패하는 저장소
+    struct FailingStore(InMemoryEventStore);
+
+    impl EventStore for FailingStore {
+        fn append(&mut self, timestamp: u64, kind: PoolEventKind) -> anyhow::Result<u64> {
+            if matches!(kind, PoolEventKind::OptionCreated { .. }) {
+                anyhow::bail!("disk full");
+            }
+            self.0.append(timestamp, kind)
+        }
+
+        fn events(&self) -> &[crate::event_store::PoolEvent] {
+            self.0.events()
+        }
+    }
+
+    #[test]
+    fn test_failed_event_write_rolls_back_pool() {
+        let mut manager =
+            SimpleContractManager::with_event_store(Box::new(FailingStore(InMemoryEventStore::new())));
+        manager.add_liquidity(100_000_000).unwrap();
+        let before = manager.pool_state.clone();
+
+        assert!(matches!(
+            manager.create_option_idempotent("key-1", call_request("CALL-FAIL")),
+            Err(ContractError::Storage(_))
+        ));
+        assert!(manager.options.is_empty());
+        assert_eq!(manager.pool_state.locked_collateral, before.locked_collateral);
+        assert_eq!(manager.pool_state.available_liquidity, before.available_liquidity);
+        assert_eq!(manager.pool_state.total_liquidity, before.total_liquidity);
+    }
+
+    #[test]
+    fn test_pool_state_rebuilds_from_ledger() {
+        let mut manager = funded_manager();
+        manager
+            .create_option_idempotent("key-1", call_request("CALL-LEDGER"))
+            .unwrap();
+        manager.settle_option("CALL-LEDGER", 7_200_000).unwrap();
+
+        let live = manager.pool_state.clone();
+        manager.pool_state = SimplePoolState::new();
+        manager.rebuild_pool_state().unwrap();
+        assert_eq!(manager.pool_state.total_liquidity, live.total_liquidity);
+        assert_eq!(manager.pool_state.available_liquidity, live.available_liquidity);
+        assert_eq!(manager.pool_state.total_payout, live.total_payout);
+        assert_eq!(manager.ledger().transactions().len(), 3);
+    }
+
+    #[test]
+    fn test_index_queries_follow_mutations() {
+        let mut manager = funded_manager();
+        for (id, strike, expiry) in [
+            ("IDX-1", 7_000_000, 800_000),
+            ("IDX-2", 7_000_000, 800_144),
+            ("IDX-3", 7_500_000, 800_288),
+        ] {
+            manager
+                .create_option(
+                    id.to_string(),
+                    OptionType::Call,
+                    strike,
+                    1_000_000,
+                    10_000,
+                    expiry,
+                    "user8".to_string(),
+                )
+                .unwrap();
+        }
+
+        let ids = |options: Vec<&SimpleOption>| {
+            options.iter().map(|o| o.option_id.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(manager.get_options_by_expiry_range(800_000..=800_144)),
+            vec!["IDX-1", "IDX-2"]
+        );
+        assert_eq!(ids(manager.get_active_by_strike(7_000_000)).len(), 2);
+        assert_eq!(manager.get_options_by_user("user8").len(), 3);
+
+        // 정산 후 활성/만료 조회에서 제외
+        manager.settle_option("IDX-1", 6_900_000).unwrap();
+        assert_eq!(ids(manager.get_active_by_strike(7_000_000)), vec!["IDX-2"]);
+        assert_eq!(ids(manager.get_expired_options(800_200)), vec!["IDX-2"]);
+    }
+
+    #[test]
+    fn test_trading_halt_timed_resume() {
+        let mut manager = SimpleContractManager::new();
+        // 이미 지난 자동 재개 시각
+        manager.apply_system_event(&SystemEvent::TradingHalted {
+            reason: "Consensus failed 5 consecutive rounds".to_string(),
+            timestamp: 1_000,
+            resume_at: Some(2_000),
+        });
+
+        assert!(manager.trading_halt().is_none());
+    }
+
+    #[test]
+    fn test_manual_clock_drives_quote_expiry_and_halt_resume() {
+        use oracle_vm_common::{Clock, ManualClock};
+
+        let clock = ManualClock::new(1_700_000_000);
+        let (mut manager, secret_key) = quoting_manager_at(clock.shared());
+        chain_to_expiry(&mut manager, "2024-03-01", 800_000);
+
+        // 30초 유효 호가: 유효 기간이 지나면 체결 거부
+        let quote = signed_quote(&secret_key, clock.now() + 30);
+        clock.advance(31);
+        assert!(matches!(
+            manager.fill_quote(&quote, 10_000_000, "T-1".to_string(), 800_000, "user".to_string()),
+            Err(ContractError::QuoteExpired { .. })
+        ));
+        let mut quote = OptionQuote { quote_id: "Q-2".to_string(), ..signed_quote(&secret_key, clock.now() + 30) };
+        quote.sign(&secret_key).unwrap();
+        manager
+            .fill_quote(&quote, 10_000_000, "T-1".to_string(), 800_000, "user".to_string())
+            .unwrap();
+        assert_eq!(manager.options["T-1"].status, OptionStatus::Active);
+
+        // 자동 재개 시각 전까지는 중단 유지
+        manager.apply_system_event(&SystemEvent::TradingHalted {
+            reason: "Consensus failed 5 consecutive rounds".to_string(),
+            timestamp: clock.now(),
+            resume_at: Some(clock.now() + 60),
+        });
+        clock.advance(59);
+        assert!(manager.trading_halt().is_some());
+        clock.advance(1);
+        assert!(manager.trading_halt().is_none());
+    }
+
+    #[test]
+    fn test_funding_is_prepaid_and_accrued_to_lps_daily() {
+        use crate::funding::SECONDS_PER_DAY;
+        use oracle_vm_common::ManualClock;
+
+        let clock = ManualClock::new(1_700_000_000);
+        let mut manager = funded_manager_at(clock.shared());
+        manager.set_funding_rate(1_000); // 연 10%
+        manager.observe_height(800_000 - 4_320); // 만기까지 30일
+        manager.create_option_with_request(call_request("CALL-F")).unwrap();
+
+        // 담보 10,000,000 × 10% × 30/365 = 82,191 는 LP 유동성에 바로 들어가지 않음
+        assert_eq!(manager.pool_state.unaccrued_funding, 82_191);
+        assert_eq!(manager.pool_state.total_liquidity, 100_000_000 + 250_000 - 82_191);
+        assert_eq!(manager.accrue_funding().unwrap(), 0);
+
+        clock.advance(10 * SECONDS_PER_DAY + 1);
+        assert_eq!(manager.accrue_funding().unwrap(), 27_397);
+        assert_eq!(manager.accrue_funding().unwrap(), 0);
+        assert_eq!(manager.pool_state.total_funding_accrued, 27_397);
+
+        // 만기 전에 담보가 풀리면 남은 선납분을 한 번에 적립
+        manager.settle_option("CALL-F", 6_000_000).unwrap();
+        assert_eq!(manager.accrue_funding().unwrap(), 82_191 - 27_397);
+        assert_eq!(manager.pool_state.unaccrued_funding, 0);
+        assert_eq!(manager.pool_state.total_liquidity, 100_250_000);
+        assert!(manager.funding().schedule("CALL-F").is_none());
+        manager.rebuild_pool_state().unwrap();
+        assert_eq!(manager.pool_state.total_funding_accrued, 82_191);
+    }
+
+    #[test]
+    fn test_roll_prepays_funding_like_open() {
+        use oracle_vm_common::ManualClock;
+
+        // 호가 만기(2024-03-01)가 팁에서 정확히 8,640블록 뒤가 되는 시각
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let now = expiry_date_timestamp("2024-03-01").unwrap() - 8_640 * AVG_BLOCK_SECS;
+        let mut manager = funded_manager_at(ManualClock::new(now).shared());
+        manager.set_funding_rate(1_000);
+        let mut headers = BlockClock::new();
+        headers.observe(800_000 - 4_320, now);
+        manager.set_block_clock(headers);
+        manager.create_option_with_request(call_request("CALL-F1")).unwrap();
+        manager.require_quotes(public_key);
+        let prepaid = manager.pool_state.unaccrued_funding;
+
+        let mut buy_back = BuyBackQuote {
+            quote_id: "B-F1".to_string(),
+            option_id: "CALL-F1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            value: 180_000,
+            theoretical_value: 190_000,
+            spot_price: 7_000_000,
+            issued_at: now,
+            valid_until: now + 30,
+            tenant_id: None,
+            signature: String::new(),
+        };
+        buy_back.sign(&secret_key).unwrap();
+        let quote = signed_quote(&secret_key, now + 30);
+        manager
+            .roll_option(&buy_back, &quote, "CALL-F2".to_string(), 800_000 + 4_320, "user7")
+            .unwrap();
+
+        // 새 옵션은 60일치 사용료(담보 10,000,000 × 10% × 60/365)를 새 프리미엄에서 선납
+        let schedule = manager.funding().schedule("CALL-F2").unwrap();
+        assert_eq!(schedule.prepaid, 164_383);
+        assert_eq!(schedule.end, now + 8_640 * AVG_BLOCK_SECS);
+        assert_eq!(manager.pool_state.unaccrued_funding, prepaid + 164_383);
+        assert!(manager
+            .event_store()
+            .events()
+            .iter()
+            .any(|event| matches!(&event.kind, PoolEventKind::FundingPrepaid { option_id, .. } if option_id == "CALL-F2")));
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+
+        // 닫힌 옵션의 남은 선납분은 다음 적립에서 한 번에 LP에게
+        assert_eq!(manager.accrue_funding().unwrap(), prepaid);
+    }
+
+    #[test]
+    fn test_funding_uses_block_header_times() {
+        use crate::block_time::BlockClock;
+        use oracle_vm_common::ManualClock;
+
+        // 최근 블록이 500초 간격: 만기 4,320 블록 = 25일 (600초로 어림하면 30일)
+        let now = 1_700_000_000;
+        let mut headers = BlockClock::new();
+        for height in 800_000 - 4_320 - 144..=800_000 - 4_320 {
+            headers.observe(height, now - (800_000 - 4_320 - height) as u64 * 500);
+        }
+        let mut manager = funded_manager_at(ManualClock::new(now).shared());
+        manager.set_funding_rate(1_000);
+        manager.set_block_clock(headers);
+        assert_eq!(manager.tip_height(), Some(800_000 - 4_320));
+        manager.create_option_with_request(call_request("CALL-B")).unwrap();
+
+        // 담보 10,000,000 × 10% × 25/365 = 68,493
+        assert_eq!(manager.pool_state.unaccrued_funding, 68_493);
+        assert_eq!(manager.funding().schedule("CALL-B").unwrap().end, now + 4_320 * 500);
+    }
+
+    #[test]
+    fn test_usd_settled_option_uses_dual_currency_pool() {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(200_000_000).unwrap();
+        assert!(matches!(
+            manager.add_usd_liquidity(10_000),
+            Err(ContractError::UnsupportedCurrency(_))
+        ));
+
+        manager.enable_usd_settlement(UsdRail::Synthetic);
+        manager.add_usd_liquidity(10_000).unwrap();
+        manager
+            .create_usd_option(
+                "USD-CALL".to_string(),
+                OptionType::Call,
+                7_000_000,
+                100_000_000,
+                25_000, // $250 프리미엄
+                800_000,
+                "user1".to_string(),
+            )
+            .unwrap();
+        assert_eq!(manager.pool_state.total_premium_collected, 0);
+        assert_eq!(manager.usd_book().unwrap().balance_cents, 35_000);
+
+        // $72,000 정산: $2,000 중 $350은 USD 잔고, 나머지는 BTC 담보 환산
+        let payout = manager.settle_option("USD-CALL", 7_200_000).unwrap();
+        assert_eq!(payout, 200_000);
+        assert_eq!(manager.pool_state.total_payout, 2_291_666);
+        let book = manager.usd_book().unwrap();
+        assert_eq!((book.balance_cents, book.payouts_cents), (0, 200_000));
+        assert_eq!(book.conversions().len(), 1);
+    }
+
+    #[test]
+    fn test_dust_payout_policy() {
+        let mut manager = funded_manager();
+        manager.set_exercise_policy(ExercisePolicy::new(546, DustHandling::PoolRevenue));
+        for id in ["DUST-1", "DUST-2", "ITM-1"] {
+            manager
+                .create_option(id.to_string(), OptionType::Call, 7_000_000, 1_000_000, 25_000, 800_000, "user1".to_string())
+                .unwrap();
+        }
+
+        // $70,030 정산: 0.01 BTC 내재가치 30 sats는 풀 수익으로 남음
+        assert_eq!(manager.settle_option("DUST-1", 7_003_000).unwrap(), 0);
+        assert_eq!(manager.pool_state.total_payout, 0);
+        assert_eq!(
+            manager.settlement("DUST-1").unwrap().exercise,
+            Exercise::Dust { amount: 30, handling: DustHandling::PoolRevenue }
+        );
+
+        // 누적 정책이면 풀에서 빠져 사용자 잔고로 적립
+        manager.set_exercise_policy(ExercisePolicy::new(546, DustHandling::Accumulate));
+        assert_eq!(manager.settle_option("DUST-2", 7_003_000).unwrap(), 0);
+        assert_eq!(manager.pool_state.total_payout, 30);
+        assert_eq!(manager.dust_balance("user1"), 30);
+
+        assert_eq!(manager.settle_option("ITM-1", 7_500_000).unwrap(), 5_000);
+        assert!(matches!(
+            manager.event_store().events().last().unwrap().kind,
+            PoolEventKind::OptionSettled { payout: 5_000, dust: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_settlements_credit_claimable_balance() {
+        let (secret_key, _) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = funded_manager();
+        manager.enable_claimable_balances(ClaimableLedger::new(
+            oracle_vm_common::NetworkProfile::TESTNET,
+            secret_key,
+            10_000,
+        ));
+        for id in ["CALL-A", "CALL-B"] {
+            manager
+                .create_option(id.to_string(), OptionType::Call, 7_000_000, 1_000_000, 25_000, 800_000, "user1".to_string())
+                .unwrap();
+        }
+        manager.settle_option("CALL-A", 7_500_000).unwrap();
+        manager.settle_option("CALL-B", 7_600_000).unwrap();
+
+        assert_eq!(manager.claims().unwrap().balance("user1"), 11_000);
+        assert_eq!(manager.pool_state.total_payout, 11_000);
+
+        // 보유자 키는 이미 옵션이 있는 계정이라 운영자만 묶을 수 있음
+        let (holder_secret, holder_key) = oracle_vm_common::crypto::generate_keypair();
+        assert!(matches!(
+            manager.register_account_key("user1", holder_key),
+            Err(AccountKeyError::Existing(_))
+        ));
+        manager.bind_account_key("user1", holder_key).unwrap();
+
+        // 남의 키로 서명하거나 다른 주소로 바꾼 출금은 거부
+        const ADDRESS: &str = "tb1qerq9kwplk0we7ql3agkapdt39d0ahmtvsptj3e";
+        let payload = withdraw_payload("user1", ADDRESS, 1);
+        assert!(matches!(
+            manager.withdraw_claims("user1", ADDRESS, &sign_request(&payload, 1, &secret_key)),
+            Err(ClaimError::Account(AccountKeyError::Unauthorized(_)))
+        ));
+        let signed = sign_request(&payload, 1, &holder_secret);
+        assert!(manager
+            .withdraw_claims("user1", "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx", &signed)
+            .is_err());
+        let withdrawal = manager.withdraw_claims("user1", ADDRESS, &signed).unwrap();
+        assert_eq!(withdrawal.credit_ids.len(), 2);
+        assert!(matches!(
+            manager.withdraw_claims("user1", ADDRESS, &signed),
+            Err(ClaimError::Account(AccountKeyError::StaleNonce { .. }))
+        ));
+        assert!(matches!(
+            manager.event_store().events().last().unwrap().kind,
+            PoolEventKind::ClaimWithdrawn { amount: 11_000, .. }
+        ));
+
+        // 지급 트랜잭션은 한 번만 기록
+        manager.record_claim_payout(withdrawal.withdrawal_id, "txid-1").unwrap();
+        assert_eq!(manager.claims().unwrap().pending_withdrawals().count(), 0);
+        assert_eq!(
+            manager.record_claim_payout(withdrawal.withdrawal_id, "txid-2"),
+            Err(ClaimError::AlreadyBroadcast(withdrawal.withdrawal_id))
+        );
+
+        // 적립/출금 기록과 키는 스냅샷으로 복원되고, 청구 잔고 활성화 시 이어받음
+        let snapshot = manager.snapshot(800_000, Vec::new());
+        let mut restored = SimpleContractManager::restore(snapshot, &std::collections::HashMap::new()).unwrap();
+        assert_eq!(restored.account_keys().key("user1"), Some(&holder_key));
+        restored.enable_claimable_balances(ClaimableLedger::new(
+            oracle_vm_common::NetworkProfile::TESTNET,
+            secret_key,
+            10_000,
+        ));
+        let claims = restored.claims().unwrap();
+        assert_eq!(claims.balance("user1"), 0);
+        assert_eq!(claims.withdrawals("user1").len(), 1);
+        assert_eq!(claims.credits("user1").len(), 2);
     }
 }
@@ -0,0 +1,171 @@
+//! 앵커 상태와 확인 추적
+//!
+//! 옵션별 앵커 상태, 앵커 트랜잭션 추적기, 스냅샷에서 복원해 추적기를 기다리는
+//! 앵커와 마지막 조회 알림을 함께 둡니다.
+
+use super::SimpleContractManager;
+use crate::anchor_tracker::{AnchorAlert, AnchorBroadcaster, AnchorStatus, AnchorTracker, ChainSource, TrackedAnchor};
+use crate::audit::AuditAction;
+use crate::event_store::PoolEventKind;
+use crate::snapshot::AnchorRecord;
+use oracle_vm_common::{AnchorError, ContractError};
+use std::collections::HashMap;
+use tracing::{instrument, warn};
+
+/// 옵션 앵커 기록
+#[derive(Default)]
+pub(super) struct Anchors {
+    /// 옵션 ID → 앵커 확인 상태 (AnchorTracker가 갱신)
+    status: HashMap<String, AnchorStatus>,
+    /// 앵커 트랜잭션 확인 추적 (설정 시 스냅샷에 추적 중인 앵커와 확정 앵커 기록)
+    tracker: Option<AnchorTracker>,
+    /// 스냅샷에서 복원했지만 앵커 추적이 아직 활성화되지 않은 앵커
+    restored: Vec<TrackedAnchor>,
+    /// 마지막 앵커 조회 알림 (경보 평가용)
+    alerts: Vec<AnchorAlert>,
+}
+
+impl Anchors {
+    /// 스냅샷의 추적 중인 앵커로 시작 (앵커 추적을 켜면 이어서 추적)
+    pub(super) fn restore(tracked: Vec<TrackedAnchor>) -> Self {
+        Self {
+            restored: tracked,
+            ..Self::default()
+        }
+    }
+
+    /// 스냅샷에 싣는 추적 중인 앵커
+    pub(super) fn tracked(&self) -> Vec<TrackedAnchor> {
+        match &self.tracker {
+            Some(tracker) => tracker.anchors().to_vec(),
+            None => self.restored.clone(),
+        }
+    }
+}
+
+impl SimpleContractManager {
+    /// 옵션 앵커 상태 기록 (새 앵커 트랜잭션이면 감사 기록에도 남김)
+    #[instrument(level = "info", skip_all, fields(option_id = %option_id, status = ?status))]
+    pub fn set_anchor_status(&mut self, option_id: &str, status: AnchorStatus) {
+        let txid = match &status {
+            AnchorStatus::Pending { txid }
+            | AnchorStatus::Confirmed { txid, .. }
+            | AnchorStatus::Reorged { txid } => txid,
+        };
+        if self.audit.last_anchor(option_id) != Some(txid.as_str()) {
+            let now = self.clock.now();
+            self.audit
+                .append(option_id, now, AuditAction::Anchored { txid: txid.clone() });
+        }
+        self.anchors.status.insert(option_id.to_string(), status);
+    }
+
+    /// 옵션 앵커 상태 (앵커링 전이면 None)
+    pub fn anchor_status(&self, option_id: &str) -> Option<&AnchorStatus> {
+        self.anchors.status.get(option_id)
+    }
+
+    /// 앵커 확인 추적 활성화 (스냅샷에서 복원한 앵커가 있으면 이어서 추적)
+    pub fn enable_anchor_tracking(&mut self, mut tracker: AnchorTracker) {
+        let restored = std::mem::take(&mut self.anchors.restored);
+        if !restored.is_empty() {
+            tracker.restore(restored);
+        }
+        tracker.sync(self);
+        self.anchors.tracker = Some(tracker);
+    }
+
+    pub fn anchor_tracker(&self) -> Option<&AnchorTracker> {
+        self.anchors.tracker.as_ref()
+    }
+
+    /// 복구 실패로 보류된 앵커 재등록 (앵커 추적이 꺼져 있으면 빈 목록)
+    pub fn requeue_anchors(&mut self) -> Vec<String> {
+        self.anchors.tracker
+            .as_mut()
+            .map(AnchorTracker::requeue)
+            .unwrap_or_default()
+    }
+
+    /// 방금 전송한 앵커 추적 시작 (앵커 추적이 꺼져 있으면 무시)
+    pub fn track_anchor(
+        &mut self,
+        option_id: &str,
+        txid: &str,
+        payload: Vec<u8>,
+        raw_tx: Option<Vec<u8>>,
+    ) -> Result<(), ContractError> {
+        if self.anchors.tracker.is_none() {
+            return Ok(());
+        }
+        self.record_event(PoolEventKind::AnchorBroadcast {
+            option_id: option_id.to_string(),
+            txid: txid.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+        if let Some(tracker) = self.anchors.tracker.as_mut() {
+            tracker.track(option_id, txid, payload, raw_tx);
+        }
+        self.set_anchor_status(option_id, AnchorStatus::Pending { txid: txid.to_string() });
+        Ok(())
+    }
+
+    /// 추적 중인 앵커의 체인 상태 조회/복구 후 옵션 기록에 반영
+    ///
+    /// 최종 확인과 재앵커링은 이벤트로 남겨 스냅샷에 바로 반영되게 합니다.
+    pub fn poll_anchors(
+        &mut self,
+        chain: &dyn ChainSource,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<Vec<AnchorAlert>, AnchorError> {
+        let Some(mut tracker) = self.anchors.tracker.take() else {
+            return Ok(Vec::new());
+        };
+        let polled = tracker.poll(chain, broadcaster);
+        tracker.sync(self);
+        self.anchors.tracker = Some(tracker);
+        let alerts = polled?;
+
+        for alert in &alerts {
+            let event = match alert {
+                AnchorAlert::Confirmed {
+                    option_id,
+                    txid,
+                    confirmations,
+                } => PoolEventKind::AnchorConfirmed {
+                    option_id: option_id.clone(),
+                    txid: txid.clone(),
+                    confirmations: *confirmations,
+                },
+                AnchorAlert::Reanchored {
+                    option_id,
+                    old_txid,
+                    new_txid,
+                } => PoolEventKind::AnchorReplaced {
+                    option_id: option_id.clone(),
+                    old_txid: old_txid.clone(),
+                    new_txid: new_txid.clone(),
+                },
+                _ => continue,
+            };
+            if let Err(e) = self.record_event(event) {
+                warn!("Failed to record anchor update: {}", e);
+            }
+        }
+        self.anchors.alerts = alerts.clone();
+        Ok(alerts)
+    }
+
+    /// 마지막 앵커 조회 알림
+    pub fn anchor_alerts(&self) -> &[AnchorAlert] {
+        &self.anchors.alerts
+    }
+
+    /// 스냅샷에 싣는 최종 확인된 앵커 기록 (복원 시 온체인 페이로드와 대조)
+    pub fn anchor_records(&self) -> Vec<AnchorRecord> {
+        self.anchors.tracker
+            .as_ref()
+            .map(AnchorTracker::confirmed_records)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,121 @@
+//! 만기 전 되사기
+//!
+//! 서명된 되사기 호가로 옵션을 종료하고 담보를 해제합니다. 호가 검사는 협의
+//! 해지와 롤이 함께 씁니다.
+
+use super::{OptionStatus, SimpleContractManager};
+use crate::audit::AuditAction;
+use crate::buy_back::cancel_anchor_payload;
+use crate::event_store::PoolEventKind;
+use crate::pool_ledger::Posting;
+use oracle_vm_common::{BuyBackQuote, ContractError};
+use tracing::instrument;
+
+impl SimpleContractManager {
+    /// 만기 전 되사기: 서명된 되사기 호가로 옵션을 종료하고 담보를 해제
+    ///
+    /// 보유자에게 호가 금액을 지급하고(청구 잔고가 있으면 적립) 나머지 담보는
+    /// 풀로 돌립니다. 지급액(satoshis)을 반환하며, 종료 기록은
+    /// `cancel_anchor_payload`로 CNL 앵커에 남깁니다.
+    #[instrument(level = "info", skip_all, fields(option_id = %quote.option_id, quote_id = %quote.quote_id))]
+    pub fn buy_back_option(&mut self, quote: &BuyBackQuote, user_id: &str) -> Result<u64, ContractError> {
+        let now = self.clock.now();
+        if let Some(halt) = self.trading_halt() {
+            return Err(ContractError::TradingHalted(halt.reason.clone()));
+        }
+        let collateral = self.check_buy_back_quote(quote, user_id, now)?;
+        let option_id = quote.option_id.as_str();
+
+        let credit = match &self.claims {
+            Some(claims) if quote.value > 0 => Some(
+                claims
+                    .prepare_credit(user_id, option_id, quote.value, now)
+                    .map_err(|e| ContractError::Ledger(e.to_string()))?,
+            ),
+            _ => None,
+        };
+        let pending = self.ledger.prepare(
+            &self.pool_state,
+            option_id,
+            vec![
+                Posting::buy_back(quote.value),
+                Posting::release(collateral - quote.value),
+            ],
+            -1,
+        )?;
+        self.record_event(PoolEventKind::OptionBoughtBack {
+            option_id: option_id.to_string(),
+            quote_id: quote.quote_id.clone(),
+            spot_price: quote.spot_price,
+            amount: quote.value,
+        })
+        .map_err(ContractError::Storage)?;
+        self.record_claim_credit(credit.as_ref())
+            .map_err(ContractError::Storage)?;
+
+        if let Some(option) = self.options.get_mut(option_id) {
+            self.index
+                .update_status(option_id, option.status, OptionStatus::Cancelled);
+            option.status = OptionStatus::Cancelled;
+        }
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.lp_book.on_release(option_id, quote.value);
+        if let (Some(claims), Some(credit)) = (self.claims.as_mut(), credit) {
+            claims.apply_credit(credit);
+        }
+        self.quotes.mark_used(&quote.quote_id);
+        Ok(quote.value)
+    }
+
+    /// 되사기 호가 검사 후 대상 옵션의 담보 반환
+    pub(super) fn check_buy_back_quote(&self, quote: &BuyBackQuote, user_id: &str, now: u64) -> Result<u64, ContractError> {
+        self.quotes.check(
+            &quote.quote_id,
+            quote.tenant_id.as_deref(),
+            quote.valid_until,
+            quote.is_expired(now),
+            |key| quote.verify(key),
+        )?;
+
+        let option_id = quote.option_id.as_str();
+        let reject = |reason: String| Err(ContractError::BuyBack(format!("{}: {}", option_id, reason)));
+        let Some(option) = self.options.get(option_id) else {
+            return reject("option not found".to_string());
+        };
+        if option.status != OptionStatus::Active {
+            return reject(format!("option is {:?}", option.status));
+        }
+        if option.user_id != user_id {
+            return reject(format!("not held by {}", user_id));
+        }
+        if (option.option_type, option.strike_price, option.quantity)
+            != (quote.option_type, quote.strike_price, quote.quantity)
+        {
+            return reject(format!("quote {} prices different terms", quote.quote_id));
+        }
+        if option.terms.settlement_currency.is_usd() {
+            return reject("USD-settled options settle at expiry".to_string());
+        }
+        if option.terms.barrier.is_some_and(|state| state.touched.is_some()) {
+            return reject("barrier already touched, settles at expiry".to_string());
+        }
+        let collateral = option.collateral();
+        if quote.value > collateral {
+            return reject(format!("value {} exceeds collateral {}", quote.value, collateral));
+        }
+        Ok(collateral)
+    }
+
+    /// 되사기/협의 해지로 종료된 옵션의 CNL 앵커 페이로드 (마지막 감사 해시 포함)
+    pub fn cancel_anchor_payload(&self, option_id: &str) -> Option<Vec<u8>> {
+        let last = self.audit.trail(option_id).last()?;
+        if !matches!(
+            last.action,
+            AuditAction::BoughtBack { .. } | AuditAction::CooperativelyClosed { .. }
+        ) {
+            return None;
+        }
+        let head = self.audit.head_hash(option_id)?;
+        Some(cancel_anchor_payload(option_id, &head))
+    }
+}
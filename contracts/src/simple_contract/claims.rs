@@ -0,0 +1,194 @@
+//! 청구 잔고와 수익자
+//!
+//! BTC 지급을 사용자별 청구 잔고에 적립한 뒤 모아서 출금하고, 수익자가 지정된
+//! 옵션의 적립은 수익자 주소로 지급합니다.
+
+use super::SimpleContractManager;
+use crate::account_keys::{withdraw_payload, AccountSignature};
+use crate::beneficiary::{Beneficiary, BeneficiaryRegistry, BeneficiaryUpdate};
+use crate::claimable::{ClaimCredit, ClaimableLedger, Withdrawal};
+use crate::event_store::PoolEventKind;
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{BeneficiaryError, ClaimError};
+use tracing::instrument;
+
+impl SimpleContractManager {
+    /// 청구 잔고 활성화, 이후 BTC 정산 지급(누적 dust 포함)은 사용자 잔고에 적립
+    ///
+    /// 스냅샷에서 복원한 적립/출금 기록이 있으면 이어받습니다.
+    pub fn enable_claimable_balances(&mut self, mut claims: ClaimableLedger) {
+        if let Some(records) = self.claim_records.take() {
+            claims.restore_records(records);
+        }
+        self.claims = Some(claims);
+    }
+
+    pub fn claims(&self) -> Option<&ClaimableLedger> {
+        self.claims.as_ref()
+    }
+
+    pub fn claims_mut(&mut self) -> Option<&mut ClaimableLedger> {
+        self.claims.as_mut()
+    }
+
+    /// 청구 잔고 출금 (보유자 키로 user_id, 주소, nonce에 서명해야 함)
+    #[instrument(level = "info", skip_all, fields(user_id = %user_id))]
+    pub fn withdraw_claims(
+        &mut self,
+        user_id: &str,
+        address: &str,
+        auth: &AccountSignature,
+    ) -> Result<Withdrawal, ClaimError> {
+        let now = self.clock.now();
+        let claims = self.claims.as_ref().ok_or(ClaimError::Disabled)?;
+        self.account_keys
+            .verify(user_id, &withdraw_payload(user_id, address, auth.nonce), auth)?;
+        // 수익자가 지정된 옵션의 적립은 수익자 주소로만 지급
+        let withdrawal = claims.prepare_withdrawal_where(user_id, address, now, claims.min_withdrawal_sats(), |credit| {
+            self.beneficiary(&credit.option_id).is_none()
+        })?;
+        self.record_event(PoolEventKind::ClaimWithdrawn {
+            withdrawal_id: withdrawal.withdrawal_id,
+            user_id: user_id.to_string(),
+            address: address.to_string(),
+            amount: withdrawal.amount,
+            credit_ids: withdrawal.credit_ids.clone(),
+        })
+        .map_err(ClaimError::Storage)?;
+        self.account_keys.consume(user_id, auth.nonce);
+        if let Some(claims) = self.claims.as_mut() {
+            claims.apply_withdrawal(withdrawal.clone());
+        }
+        Ok(withdrawal)
+    }
+
+    /// 수익자가 지정된 옵션의 미출금 적립을 수익자 주소로 출금 (적립 한 건당 한 출금, 최소 출금액 없음)
+    pub fn withdraw_to_beneficiaries(&mut self) -> Result<Vec<Withdrawal>, ClaimError> {
+        let now = self.clock.now();
+        let Some(claims) = self.claims.as_ref() else {
+            return Ok(Vec::new());
+        };
+        let designated: Vec<(String, u64, String)> = claims
+            .unwithdrawn()
+            .filter_map(|credit| {
+                let address = self.beneficiary(&credit.option_id)?.address.clone();
+                Some((credit.user_id.clone(), credit.credit_id, address))
+            })
+            .collect();
+
+        let mut withdrawals = Vec::new();
+        for (user_id, credit_id, address) in designated {
+            let claims = self.claims.as_ref().ok_or(ClaimError::Disabled)?;
+            let withdrawal =
+                claims.prepare_withdrawal_where(&user_id, &address, now, 0, |credit| credit.credit_id == credit_id)?;
+            self.record_event(PoolEventKind::ClaimWithdrawn {
+                withdrawal_id: withdrawal.withdrawal_id,
+                user_id: user_id.clone(),
+                address: address.clone(),
+                amount: withdrawal.amount,
+                credit_ids: withdrawal.credit_ids.clone(),
+            })
+            .map_err(ClaimError::Storage)?;
+            if let Some(claims) = self.claims.as_mut() {
+                claims.apply_withdrawal(withdrawal.clone());
+            }
+            withdrawals.push(withdrawal);
+        }
+        Ok(withdrawals)
+    }
+
+    /// 수익자 등록부 활성화 (스냅샷에서 복원한 수익자가 있으면 이어서 사용)
+    pub fn enable_beneficiaries(&mut self, mut registry: BeneficiaryRegistry) {
+        let restored = std::mem::take(&mut self.beneficiary_records);
+        if !restored.is_empty() {
+            registry.restore(restored);
+        }
+        self.beneficiaries = Some(registry);
+    }
+
+    /// 옵션 수익자 (등록부가 꺼져 있거나 등록 전이면 None)
+    pub fn beneficiary(&self, option_id: &str) -> Option<&Beneficiary> {
+        self.beneficiaries.as_ref()?.get(option_id)
+    }
+
+    /// 옵션 수익자 등록 (BUY 앵커 페이로드 반환)
+    pub fn register_beneficiary(
+        &mut self,
+        option_id: &str,
+        address: &str,
+        owner_key: PublicKey,
+    ) -> Result<Vec<u8>, BeneficiaryError> {
+        let now = self.clock.now();
+        let option = self
+            .options
+            .get(option_id)
+            .ok_or_else(|| BeneficiaryError::NotFound(option_id.to_string()))?;
+        self.beneficiaries
+            .as_ref()
+            .ok_or(BeneficiaryError::Disabled)?
+            .check_register(option, address)?;
+        self.record_event(PoolEventKind::BeneficiaryRegistered {
+            option_id: option_id.to_string(),
+            address: address.to_string(),
+        })
+        .map_err(BeneficiaryError::Storage)?;
+        let registry = self.beneficiaries.as_mut().ok_or(BeneficiaryError::Disabled)?;
+        registry.register(&self.options[option_id], address, owner_key, now)
+    }
+
+    /// 소유자 서명 확인 후 수익자 지급 주소 변경 (변경 마감은 헤더 동기화가 갱신한 tip 기준)
+    pub fn change_beneficiary(
+        &mut self,
+        option_id: &str,
+        update: &BeneficiaryUpdate,
+    ) -> Result<&Beneficiary, BeneficiaryError> {
+        let now = self.clock.now();
+        let option = self
+            .options
+            .get(option_id)
+            .ok_or_else(|| BeneficiaryError::NotFound(option_id.to_string()))?;
+        let registry = self.beneficiaries.as_mut().ok_or(BeneficiaryError::Disabled)?;
+        if let Some(tip) = self.tip_height {
+            registry.observe_height(tip);
+        }
+        registry.check_change(option, update)?;
+        self.record_event(PoolEventKind::BeneficiaryChanged {
+            option_id: option_id.to_string(),
+            address: update.address.clone(),
+            nonce: update.nonce,
+        })
+        .map_err(BeneficiaryError::Storage)?;
+        let registry = self.beneficiaries.as_mut().ok_or(BeneficiaryError::Disabled)?;
+        registry.change(&self.options[option_id], update, now)
+    }
+
+    /// 청구 잔고 출금의 지급 트랜잭션 기록
+    pub fn record_claim_payout(&mut self, withdrawal_id: u64, txid: &str) -> Result<(), ClaimError> {
+        self.claims
+            .as_ref()
+            .ok_or(ClaimError::Disabled)?
+            .check_broadcast(withdrawal_id)?;
+        self.record_event(PoolEventKind::ClaimPaid {
+            withdrawal_id,
+            txid: txid.to_string(),
+        })
+        .map_err(ClaimError::Storage)?;
+        if let Some(claims) = self.claims.as_mut() {
+            claims.mark_broadcast(withdrawal_id, txid)?;
+        }
+        Ok(())
+    }
+
+    /// 준비한 적립을 이벤트로 기록 (적립이 없으면 아무것도 하지 않음)
+    pub(super) fn record_claim_credit(&mut self, credit: Option<&ClaimCredit>) -> Result<(), String> {
+        match credit {
+            Some(credit) => self.record_event(PoolEventKind::ClaimCredited {
+                credit_id: credit.credit_id,
+                user_id: credit.user_id.clone(),
+                option_id: credit.option_id.clone(),
+                amount: credit.amount,
+            }),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! 합의 가격
+//!
+//! 마지막 합의 가격과 정산 가격 밴드 가드를 둡니다. 배리어 호가 검사, 미국식
+//! 조기 행사, 정산 가격 검사가 이 가격을 씁니다.
+
+use super::SimpleContractManager;
+use crate::early_exercise::DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS;
+use crate::price_guard::{PriceBandConfig, PriceBandGuard};
+use oracle_vm_common::SettlementError;
+
+/// 마지막 합의 가격과 가격 가드
+pub(super) struct ConsensusPrice {
+    /// 정산 가격 변화율 가드 (설정 시 직전 합의 가격 밴드를 벗어난 정산을 미룸)
+    guard: Option<PriceBandGuard>,
+    /// 마지막 합의 가격 (USD cents, 미국식 조기 행사 가격)
+    last_price: Option<u64>,
+    /// 마지막 합의 가격을 받은 시각 (unix seconds)
+    last_at: u64,
+    /// 조기 행사에 쓸 수 있는 합의 가격의 최대 나이 (초)
+    max_exercise_age_secs: u64,
+}
+
+impl Default for ConsensusPrice {
+    fn default() -> Self {
+        Self {
+            guard: None,
+            last_price: None,
+            last_at: 0,
+            max_exercise_age_secs: DEFAULT_MAX_EXERCISE_PRICE_AGE_SECS,
+        }
+    }
+}
+
+impl ConsensusPrice {
+    fn observe(&mut self, price: u64, now: u64) {
+        self.last_price = Some(price);
+        self.last_at = now;
+        if let Some(guard) = self.guard.as_mut() {
+            guard.observe(price);
+        }
+    }
+
+    pub(super) fn last_price(&self) -> Option<u64> {
+        self.last_price
+    }
+
+    /// 조기 행사 가격 (가격이 없거나 최대 나이보다 오래되었으면 행사를 미룸)
+    pub(super) fn exercise_price(&self, now: u64) -> Result<u64, SettlementError> {
+        let price = self
+            .last_price
+            .ok_or_else(|| SettlementError::Postponed("no consensus price observed yet".to_string()))?;
+        let price_age = now.saturating_sub(self.last_at);
+        if price_age > self.max_exercise_age_secs {
+            return Err(SettlementError::Postponed(format!(
+                "consensus price is {}s old (max {}s)",
+                price_age, self.max_exercise_age_secs
+            )));
+        }
+        Ok(price)
+    }
+
+    /// 직전 합의 가격 밴드 검사 (가드가 꺼져 있으면 통과)
+    pub(super) fn check_settlement(&mut self, option_id: &str, price: u64, now: u64) -> Result<(), SettlementError> {
+        match self.guard.as_mut() {
+            Some(guard) => guard.check_settlement(option_id, price, now),
+            None => Ok(()),
+        }
+    }
+}
+
+impl SimpleContractManager {
+    /// 정산 가격 변화율 가드 활성화
+    pub fn enable_price_guard(&mut self, config: PriceBandConfig) {
+        self.consensus.guard = Some(PriceBandGuard::new(config));
+    }
+
+    pub fn price_guard(&self) -> Option<&PriceBandGuard> {
+        self.consensus.guard.as_ref()
+    }
+
+    /// 합의 가격 기록 (USD cents, 가격 가드와 미국식 조기 행사에 사용)
+    pub fn observe_consensus_price(&mut self, price: u64) {
+        let now = self.clock.now();
+        self.consensus.observe(price, now);
+    }
+
+    /// 조기 행사에 쓸 합의 가격의 최대 나이 변경 (초)
+    pub fn set_max_exercise_price_age(&mut self, secs: u64) {
+        self.consensus.max_exercise_age_secs = secs;
+    }
+}
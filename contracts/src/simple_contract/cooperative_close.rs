@@ -0,0 +1,89 @@
+//! 협의 해지
+//!
+//! 구매자와 풀이 함께 서명한 해지 트랜잭션으로 옵션을 닫습니다. 분배는 되사기
+//! 호가의 이론가를 기준으로 검사합니다.
+
+use super::{OptionStatus, SimpleContractManager};
+use crate::event_store::PoolEventKind;
+use crate::pool_ledger::Posting;
+use oracle_vm_common::{BuyBackQuote, ContractError};
+
+impl SimpleContractManager {
+    /// 협의 해지 분배 검사 후 대상 옵션의 담보 반환
+    ///
+    /// 보유자 몫은 되사기 호가의 이론가(스프레드 전)에서 `max_deviation_bps` 안이어야
+    /// 합니다. 호가 서명/만료/재사용과 옵션 조건 검사는 되사기와 같습니다.
+    pub fn check_cooperative_close(
+        &self,
+        quote: &BuyBackQuote,
+        holder_amount: u64,
+        user_id: &str,
+        max_deviation_bps: u64,
+    ) -> Result<u64, ContractError> {
+        let collateral = self
+            .check_buy_back_quote(quote, user_id, self.clock.now())
+            .map_err(|e| match e {
+                ContractError::BuyBack(reason) => ContractError::CooperativeClose(reason),
+                other => other,
+            })?;
+        let reject = |reason: String| Err(ContractError::CooperativeClose(format!("{}: {}", quote.option_id, reason)));
+        if holder_amount > collateral {
+            return reject(format!("holder amount {} exceeds collateral {}", holder_amount, collateral));
+        }
+        let deviation = holder_amount.abs_diff(quote.theoretical_value) as u128;
+        if deviation * 10_000 > quote.theoretical_value as u128 * max_deviation_bps as u128 {
+            return reject(format!(
+                "holder amount {} is more than {} bps from theoretical value {}",
+                holder_amount, max_deviation_bps, quote.theoretical_value
+            ));
+        }
+        Ok(collateral)
+    }
+
+    /// 협의 해지: 구매자와 풀이 함께 서명해 전송한 해지 트랜잭션 반영
+    ///
+    /// 해지 트랜잭션이 옵션 출력에서 보유자 몫을 직접 지급하므로 청구 잔고는 쌓지 않고,
+    /// 나머지 담보는 풀로 돌립니다. 되사기처럼 옵션을 Cancelled로 닫고 CNL 앵커를 남깁니다.
+    pub fn close_cooperatively(
+        &mut self,
+        quote: &BuyBackQuote,
+        holder_amount: u64,
+        txid: &str,
+        user_id: &str,
+        max_deviation_bps: u64,
+    ) -> Result<u64, ContractError> {
+        if let Some(halt) = self.trading_halt() {
+            return Err(ContractError::TradingHalted(halt.reason.clone()));
+        }
+        let collateral = self.check_cooperative_close(quote, holder_amount, user_id, max_deviation_bps)?;
+        let option_id = quote.option_id.as_str();
+
+        let pending = self.ledger.prepare(
+            &self.pool_state,
+            option_id,
+            vec![
+                Posting::buy_back(holder_amount),
+                Posting::release(collateral - holder_amount),
+            ],
+            -1,
+        )?;
+        self.record_event(PoolEventKind::OptionClosedCooperatively {
+            option_id: option_id.to_string(),
+            quote_id: quote.quote_id.clone(),
+            spot_price: quote.spot_price,
+            holder_amount,
+            txid: txid.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+
+        if let Some(option) = self.options.get_mut(option_id) {
+            self.index
+                .update_status(option_id, option.status, OptionStatus::Cancelled);
+            option.status = OptionStatus::Cancelled;
+        }
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.lp_book.on_release(option_id, holder_amount);
+        self.quotes.mark_used(&quote.quote_id);
+        Ok(holder_amount)
+    }
+}
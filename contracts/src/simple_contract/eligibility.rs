@@ -0,0 +1,68 @@
+//! 구매 자격 확인
+//!
+//! 옵션 생성/롤 API가 부르는 자격 확인 훅과 옵션별 확인 결과 해시를 둡니다.
+
+use super::SimpleContractManager;
+use crate::eligibility::{EligibilityDecision, EligibilityProvider, EligibilityRequest};
+use crate::event_store::PoolEventKind;
+use oracle_vm_common::ContractError;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// 자격 확인 훅과 확인 기록
+#[derive(Default)]
+pub(super) struct Eligibility {
+    /// 구매 자격 확인 훅 (설정 시 옵션 생성/롤 API에서 확인)
+    provider: Option<Arc<dyn EligibilityProvider>>,
+    /// 옵션 ID → 생성 시 자격 확인 결과 해시
+    hashes: BTreeMap<String, String>,
+}
+
+impl Eligibility {
+    /// 스냅샷의 확인 결과 해시로 시작 (훅은 실행 시 다시 등록)
+    pub(super) fn restore(hashes: BTreeMap<String, String>) -> Self {
+        Self { provider: None, hashes }
+    }
+
+    pub(super) fn hashes(&self) -> &BTreeMap<String, String> {
+        &self.hashes
+    }
+}
+
+impl SimpleContractManager {
+    /// 구매 자격 확인 훅 등록, 이후 옵션 생성/롤 API에서 거부된 사용자는 옵션을 열 수 없음
+    pub fn enable_eligibility(&mut self, provider: Arc<dyn EligibilityProvider>) {
+        self.eligibility.provider = Some(provider);
+    }
+
+    /// 등록된 자격 확인 훅 (확인은 외부 호출일 수 있어 풀 잠금 밖에서 하도록 복제해 반환)
+    pub fn eligibility(&self) -> Option<Arc<dyn EligibilityProvider>> {
+        self.eligibility.provider.clone()
+    }
+
+    /// 자격 확인 거부를 풀 이벤트로 기록
+    pub fn record_eligibility_denied(
+        &mut self,
+        request: &EligibilityRequest,
+        decision: &EligibilityDecision,
+    ) -> Result<(), ContractError> {
+        self.record_event(PoolEventKind::EligibilityDenied {
+            option_id: request.option_id.clone(),
+            user_id: request.user_id.clone(),
+            provider: decision.provider.clone(),
+            reason: decision.reason.clone(),
+            decision_hash: decision.hash(request),
+        })
+        .map_err(ContractError::Storage)
+    }
+
+    /// 열린 옵션의 자격 확인 결과 해시 보관 (스냅샷에 유지)
+    pub fn record_eligibility_hash(&mut self, option_id: &str, decision_hash: String) {
+        self.eligibility.hashes.insert(option_id.to_string(), decision_hash);
+    }
+
+    /// 옵션 생성 시 자격 확인 결과 해시 (확인 없이 생성됐으면 None)
+    pub fn eligibility_hash(&self, option_id: &str) -> Option<&str> {
+        self.eligibility.hashes.get(option_id).map(String::as_str)
+    }
+}
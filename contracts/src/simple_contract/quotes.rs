@@ -0,0 +1,161 @@
+//! 확정 호가 규칙
+//!
+//! 호가 서명키, 사용한 호가, 만기 캘린더, 표준 계약 규격과 테넌트를 둡니다.
+//! 확정 호가와 되사기 호가가 같은 서명/만료/재사용/테넌트 검사를 거칩니다.
+
+use super::SimpleContractManager;
+use crate::event_store::PoolEventKind;
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{ContractError, ContractSpec, ExpiryCalendar, OptionQuote};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// 호가 체결 규칙과 사용 기록
+#[derive(Default)]
+pub(super) struct QuoteRules {
+    /// 확정 호가 서명 공개키 (설정 시 호가 없는 옵션 생성 거부)
+    key: Option<PublicKey>,
+    used: HashSet<String>,
+    /// 만기 캘린더 (설정 시 OTC가 아닌 호가는 캘린더 만기만 허용)
+    calendar: Option<ExpiryCalendar>,
+    /// 표준 계약 단위/프리미엄 틱 (설정 시 규격 외 수량/프리미엄 거부)
+    spec: Option<ContractSpec>,
+    /// 이 풀을 운영하는 테넌트 (None = 기본 풀, 설정 시 같은 테넌트 호가만 체결)
+    tenant_id: Option<String>,
+}
+
+impl QuoteRules {
+    /// 스냅샷의 서명키와 사용한 호가로 시작
+    pub(super) fn restore(key: Option<PublicKey>, used: Vec<String>) -> Self {
+        Self {
+            key,
+            used: used.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn key(&self) -> Option<PublicKey> {
+        self.key
+    }
+
+    /// 확정 호가로만 옵션을 여는지
+    pub(super) fn required(&self) -> bool {
+        self.key.is_some()
+    }
+
+    pub(super) fn calendar(&self) -> Option<&ExpiryCalendar> {
+        self.calendar.as_ref()
+    }
+
+    /// 스냅샷에 싣는 사용한 호가 (정렬)
+    pub(super) fn used(&self) -> Vec<String> {
+        let mut used: Vec<String> = self.used.iter().cloned().collect();
+        used.sort();
+        used
+    }
+
+    /// 체결한 호가를 사용 처리
+    pub(super) fn mark_used(&mut self, quote_id: &str) {
+        self.used.insert(quote_id.to_string());
+    }
+
+    /// 서명/만료/재사용/테넌트 검사 (확정 호가와 되사기 호가 공통)
+    pub(super) fn check<E: Display>(
+        &self,
+        quote_id: &str,
+        tenant_id: Option<&str>,
+        valid_until: u64,
+        expired: bool,
+        verify: impl FnOnce(&PublicKey) -> Result<(), E>,
+    ) -> Result<(), ContractError> {
+        let key = self.key.ok_or(ContractError::QuoteKeyMissing)?;
+        verify(&key).map_err(|e| ContractError::InvalidQuote(e.to_string()))?;
+
+        if expired {
+            return Err(ContractError::QuoteExpired {
+                quote_id: quote_id.to_string(),
+                valid_until,
+            });
+        }
+        if self.used.contains(quote_id) {
+            return Err(ContractError::QuoteReused(quote_id.to_string()));
+        }
+        if tenant_id != self.tenant_id.as_deref() {
+            return Err(ContractError::InvalidQuote(format!(
+                "Quote {} was issued for tenant {}",
+                quote_id,
+                tenant_id.unwrap_or("(default)")
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SimpleContractManager {
+    /// Calculation 호가 서명키 등록, 이후 옵션은 확정 호가로만 생성
+    pub fn require_quotes(&mut self, quote_key: PublicKey) {
+        self.quotes.key = Some(quote_key);
+    }
+
+    /// 운영자의 호가 서명키 교체 (이벤트로 남기고 스냅샷에 유지되어 재시작 후에도 적용)
+    pub fn rotate_quote_key(&mut self, quote_key: PublicKey) -> Result<(), ContractError> {
+        if self.quotes.key == Some(quote_key) {
+            return Ok(());
+        }
+        self.record_event(PoolEventKind::QuoteKeyRotated {
+            public_key: quote_key.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+        self.quotes.key = Some(quote_key);
+        Ok(())
+    }
+
+    /// 현재 호가 서명 공개키 (확정 호가를 요구하지 않으면 None)
+    pub fn quote_key(&self) -> Option<&PublicKey> {
+        self.quotes.key.as_ref()
+    }
+
+    /// 만기 캘린더 등록, 이후 호가 만기가 캘린더에 없으면 거부 (OTC 호가 제외)
+    pub fn require_calendar(&mut self, calendar: ExpiryCalendar) {
+        self.quotes.calendar = Some(calendar);
+    }
+
+    /// 표준 계약 규격 등록, 이후 모든 생성 경로에서 수량/프리미엄 규격 검사
+    pub fn require_contract_spec(&mut self, spec: ContractSpec) {
+        self.quotes.spec = Some(spec);
+    }
+
+    pub fn contract_spec(&self) -> Option<ContractSpec> {
+        self.quotes.spec
+    }
+
+    /// 테넌트 풀로 지정, 이후 다른 테넌트(또는 테넌트 없는) 호가는 거부
+    pub fn set_tenant(&mut self, tenant_id: &str) {
+        self.quotes.tenant_id = Some(tenant_id.to_string());
+    }
+
+    pub fn tenant_id(&self) -> Option<&str> {
+        self.quotes.tenant_id.as_deref()
+    }
+
+    /// 확정 호가 서명/만료/재사용/테넌트/공시 정책/만기 검사
+    pub(super) fn check_option_quote(&self, quote: &OptionQuote, now: u64) -> Result<(), ContractError> {
+        self.quotes.check(
+            &quote.quote_id,
+            quote.tenant_id.as_deref(),
+            quote.valid_until,
+            quote.is_expired(now),
+            |key| quote.verify(key),
+        )?;
+        if quote.exercise != self.exercise_policy {
+            return Err(ContractError::InvalidQuote(format!(
+                "Quote {} discloses a different exercise policy",
+                quote.quote_id
+            )));
+        }
+        if let Some(calendar) = &self.quotes.calendar {
+            calendar.resolve(&quote.expiry, quote.issued_at, quote.otc)?;
+        }
+        Ok(())
+    }
+}
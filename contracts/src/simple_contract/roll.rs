@@ -0,0 +1,186 @@
+//! 옵션 롤
+//!
+//! 기존 옵션을 되사기 호가로 닫고 더 늦은 만기의 옵션을 확정 호가로 여는 작업을
+//! 한 번에 반영합니다.
+
+use super::{payoff_collateral, OptionStatus, OptionTerms, SimpleContractManager, SimpleOption};
+use crate::audit::AuditAction;
+use crate::buy_back::{roll_anchor_payload, RollOutcome};
+use crate::event_store::PoolEventKind;
+use crate::fees::FeeKind;
+use crate::pool_ledger::Posting;
+use oracle_vm_common::{BuyBackQuote, ContractError, OptionQuote};
+use tracing::instrument;
+
+impl SimpleContractManager {
+    /// 롤: 기존 옵션을 되사기 호가로 닫고 같은 유형의 더 늦은 만기 옵션을 확정
+    /// 호가로 여는 작업을 한 번에 반영
+    ///
+    /// 프리미엄과 담보는 차액만 움직입니다. 보유자는 새 프리미엄에서 되사기
+    /// 금액을 뺀 만큼 내고(음수면 받고), 풀은 두 담보의 차이만 잠그거나 풀어
+    /// 줍니다. 풀 이벤트, 감사 기록(새 옵션의 `Rolled`), RLL 앵커가 각각 한
+    /// 건씩 남습니다. 새 옵션의 담보 사용료는 생성과 같이 새 프리미엄에서
+    /// 선납받고, 추천 리베이트는 롤에 적용하지 않습니다.
+    #[instrument(level = "info", skip_all, fields(option_id = %buy_back.option_id, new_option_id = %new_option_id))]
+    pub fn roll_option(
+        &mut self,
+        buy_back: &BuyBackQuote,
+        quote: &OptionQuote,
+        new_option_id: String,
+        expiry_height: u32,
+        user_id: &str,
+    ) -> Result<RollOutcome, ContractError> {
+        let now = self.clock.now();
+        if let Some(halt) = self.trading_halt() {
+            return Err(ContractError::TradingHalted(halt.reason.clone()));
+        }
+        let old_collateral = self.check_buy_back_quote(buy_back, user_id, now)?;
+        self.check_option_quote(quote, now)?;
+        self.check_expiry_height(quote, expiry_height)?;
+        self.check_barrier(quote)?;
+
+        let from_id = buy_back.option_id.as_str();
+        let old_expiry = self.options[from_id].expiry_height;
+        let reject = |reason: String| Err(ContractError::BuyBack(format!("roll of {}: {}", from_id, reason)));
+        if quote.option_type != buy_back.option_type {
+            return reject("option type cannot change".to_string());
+        }
+        if expiry_height <= old_expiry {
+            return reject(format!("expiry {} is not after {}", expiry_height, old_expiry));
+        }
+        if self.options.contains_key(&new_option_id) {
+            return Err(ContractError::DuplicateOption(new_option_id));
+        }
+        if let Some(spec) = self.contract_spec() {
+            spec.contracts(quote.quantity)?;
+            spec.check_premium(quote.premium)?;
+        }
+
+        let collateral = payoff_collateral(quote.payoff, quote.option_type, quote.strike_price, quote.quantity);
+        self.risk_limits
+            .check(collateral, self.pool_state.locked_collateral - old_collateral)?;
+        // 새 만기는 기존 만기보다 늦으므로 기존 옵션의 담보는 새 만기의 미결제약정에 없음
+        if !self.open_interest_caps.is_unlimited() {
+            self.open_interest_caps.check(
+                collateral,
+                self.pool_state.total_liquidity,
+                self.open_interest(quote.strike_price, expiry_height),
+                self.secs_to_expiry(expiry_height),
+            )?;
+        }
+
+        // 풀 입장 순유입: 수수료와 새 옵션의 선납 사용료를 뺀 새 프리미엄 - 되사기 금액
+        let protocol_fee = self.fee_schedule.protocol_fee(quote.premium);
+        let funding = self.funding_charge(collateral, expiry_height, quote.premium - protocol_fee);
+        let pool_net = (quote.premium - protocol_fee - funding.amount) as i64 - buy_back.value as i64;
+        let paid_out = (-pool_net).max(0) as u64;
+        let locked_after_payout = old_collateral - paid_out;
+        let mut postings = Vec::new();
+        if pool_net > 0 {
+            postings.push(Posting::premium(pool_net as u64));
+        } else if paid_out > 0 {
+            postings.push(Posting::buy_back(paid_out));
+        }
+        if collateral > locked_after_payout {
+            let required = collateral - locked_after_payout;
+            let available = self.pool_state.available_liquidity + pool_net.max(0) as u64;
+            if available < required {
+                return Err(ContractError::InsufficientLiquidity { required, available });
+            }
+            postings.push(Posting::lock(required));
+        } else if collateral < locked_after_payout {
+            postings.push(Posting::release(locked_after_payout - collateral));
+        }
+        if funding.amount > 0 {
+            postings.push(Posting::funding_prepaid(funding.amount));
+        }
+        if protocol_fee > 0 {
+            postings.push(Posting::protocol_fee(protocol_fee));
+        }
+        let pending = self.ledger.prepare(&self.pool_state, new_option_id.as_str(), postings, 0)?;
+
+        // 보유자가 받는 차액은 청구 잔고가 있으면 적립
+        let net_premium = quote.premium as i64 - buy_back.value as i64;
+        let credit = match &self.claims {
+            Some(claims) if net_premium < 0 => Some(
+                claims
+                    .prepare_credit(user_id, from_id, net_premium.unsigned_abs(), now)
+                    .map_err(|e| ContractError::Ledger(e.to_string()))?,
+            ),
+            _ => None,
+        };
+
+        self.record_event(PoolEventKind::OptionRolled {
+            from_option_id: from_id.to_string(),
+            buy_back_quote_id: buy_back.quote_id.clone(),
+            buy_back_amount: buy_back.value,
+            option_id: new_option_id.clone(),
+            option_type: quote.option_type,
+            strike_price: quote.strike_price,
+            quantity: quote.quantity,
+            premium: quote.premium,
+            collateral,
+            user_id: user_id.to_string(),
+        })
+        .map_err(ContractError::Storage)?;
+        if protocol_fee > 0 {
+            self.record_event(PoolEventKind::FeeCharged {
+                option_id: new_option_id.clone(),
+                fee: FeeKind::Protocol,
+                amount: protocol_fee,
+            })
+            .map_err(ContractError::Storage)?;
+        }
+        self.record_funding_prepaid(&new_option_id, funding, now)
+            .map_err(ContractError::Storage)?;
+        self.record_claim_credit(credit.as_ref())
+            .map_err(ContractError::Storage)?;
+
+        if let Some(option) = self.options.get_mut(from_id) {
+            self.index
+                .update_status(from_id, option.status, OptionStatus::Cancelled);
+            option.status = OptionStatus::Cancelled;
+        }
+        let option = SimpleOption {
+            option_id: new_option_id.clone(),
+            option_type: quote.option_type,
+            strike_price: quote.strike_price,
+            quantity: quote.quantity,
+            premium_paid: quote.premium,
+            expiry_height,
+            status: OptionStatus::Active,
+            user_id: user_id.to_string(),
+            terms: OptionTerms::from_quote(quote),
+        };
+        self.index.insert(&option);
+        self.options.insert(new_option_id.clone(), option);
+        self.open_funding(&new_option_id, funding, now);
+        self.ledger.commit(&mut self.pool_state, pending);
+        self.treasury.credit(FeeKind::Protocol, protocol_fee);
+        self.lp_book.on_release(from_id, buy_back.value);
+        if let (Some(claims), Some(credit)) = (self.claims.as_mut(), credit) {
+            claims.apply_credit(credit);
+        }
+        self.quotes.mark_used(&buy_back.quote_id);
+        self.quotes.mark_used(&quote.quote_id);
+
+        Ok(RollOutcome {
+            from_option_id: from_id.to_string(),
+            option_id: new_option_id,
+            buy_back_amount: buy_back.value,
+            premium: quote.premium,
+            net_premium,
+            collateral_delta: collateral as i64 - old_collateral as i64,
+        })
+    }
+
+    /// 롤로 생성된 옵션의 RLL 앵커 페이로드 (이전 옵션 ID + 새 옵션의 마지막 감사 해시)
+    pub fn roll_anchor_payload(&self, option_id: &str) -> Option<Vec<u8>> {
+        let from_option_id = self.audit.trail(option_id).iter().find_map(|record| match &record.action {
+            AuditAction::Rolled { from_option_id, .. } => Some(from_option_id),
+            _ => None,
+        })?;
+        let head = self.audit.head_hash(option_id)?;
+        Some(roll_anchor_payload(from_option_id, &head))
+    }
+}
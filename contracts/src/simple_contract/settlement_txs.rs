@@ -0,0 +1,149 @@
+//! 정산 트랜잭션 전송과 확인 추적
+//!
+//! 옵션별 정산 트랜잭션 상태, 전송 추적기와 스냅샷에서 복원해 추적기를 기다리는
+//! 정산 트랜잭션을 함께 둡니다.
+
+use super::SimpleContractManager;
+use crate::anchor_tracker::{AnchorBroadcaster, ChainSource};
+use crate::event_store::PoolEventKind;
+use crate::settlement_broadcast::{SettlementAlert, SettlementBroadcastManager, SettlementTxStatus, TrackedSettlement};
+use bitcoin::Transaction;
+use oracle_vm_common::{AnchorError, ContractError};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// 옵션 정산 트랜잭션 기록
+#[derive(Default)]
+pub(super) struct SettlementTxs {
+    /// 옵션 ID → 정산 트랜잭션 상태 (SettlementBroadcastManager가 갱신)
+    status: HashMap<String, SettlementTxStatus>,
+    /// 정산 트랜잭션 전송/확인 추적 (설정 시 추적 중인 정산 트랜잭션을 스냅샷에 기록)
+    broadcasts: Option<SettlementBroadcastManager>,
+    /// 스냅샷에서 복원했지만 정산 전송 추적이 아직 활성화되지 않은 정산 트랜잭션
+    restored: Vec<TrackedSettlement>,
+}
+
+impl SettlementTxs {
+    /// 스냅샷의 추적 중인 정산 트랜잭션으로 시작 (전송 추적을 켜면 이어서 추적)
+    pub(super) fn restore(tracked: Vec<TrackedSettlement>) -> Self {
+        Self {
+            restored: tracked,
+            ..Self::default()
+        }
+    }
+
+    /// 스냅샷에 싣는 추적 중인 정산 트랜잭션
+    pub(super) fn tracked(&self) -> Vec<TrackedSettlement> {
+        match &self.broadcasts {
+            Some(broadcasts) => broadcasts.records(),
+            None => self.restored.clone(),
+        }
+    }
+
+    pub(super) fn broadcasts(&self) -> Option<&SettlementBroadcastManager> {
+        self.broadcasts.as_ref()
+    }
+}
+
+impl SimpleContractManager {
+    /// 옵션 정산 트랜잭션 상태 기록
+    pub fn set_settlement_tx_status(&mut self, option_id: &str, status: SettlementTxStatus) {
+        self.settlement_txs.status.insert(option_id.to_string(), status);
+    }
+
+    /// 옵션 정산 트랜잭션 상태 (전송 전이면 None, 확인 수를 채우면 Settled)
+    pub fn settlement_tx_status(&self, option_id: &str) -> Option<&SettlementTxStatus> {
+        self.settlement_txs.status.get(option_id)
+    }
+
+    /// 정산 트랜잭션 전송 추적 활성화 (스냅샷에서 복원한 정산 트랜잭션이 있으면 이어서 추적)
+    pub fn enable_settlement_broadcasts(&mut self, mut settlements: SettlementBroadcastManager) {
+        let restored = std::mem::take(&mut self.settlement_txs.restored);
+        if !restored.is_empty() {
+            settlements.restore(restored);
+        }
+        settlements.sync(self);
+        self.mempool_watch.expect_settlements(&settlements);
+        self.settlement_txs.broadcasts = Some(settlements);
+    }
+
+    pub fn settlement_broadcasts(&self) -> Option<&SettlementBroadcastManager> {
+        self.settlement_txs.broadcasts.as_ref()
+    }
+
+    /// 서명된 정산 트랜잭션 전송과 추적 시작
+    ///
+    /// 옵션 UTXO를 점유하고 경쟁 지출 감시에 우리 지출로 등록합니다. 노드가 아직
+    /// 받지 않았어도 추적은 유지되어 다음 조회에서 재전송하므로 txid를 돌려줍니다.
+    pub fn broadcast_settlement(
+        &mut self,
+        option_id: &str,
+        tx: &Transaction,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<String, ContractError> {
+        if !self.options.contains_key(option_id) {
+            return Err(ContractError::Ledger(format!("Option {} not found", option_id)));
+        }
+        let txid = tx.compute_txid().to_string();
+        let Some(settlements) = self.settlement_txs.broadcasts.as_ref() else {
+            return Err(ContractError::Ledger("Settlement broadcasting not enabled".to_string()));
+        };
+        if settlements
+            .check(option_id, tx)
+            .map_err(|e| ContractError::Ledger(e.to_string()))?
+        {
+            return Ok(txid);
+        }
+        self.record_event(PoolEventKind::SettlementTxBroadcast {
+            option_id: option_id.to_string(),
+            txid: txid.clone(),
+        })
+        .map_err(ContractError::Storage)?;
+
+        let Some(mut settlements) = self.settlement_txs.broadcasts.take() else {
+            return Err(ContractError::Ledger("Settlement broadcasting not enabled".to_string()));
+        };
+        // 실패해도 UTXO는 점유됐고 다음 조회에서 재전송
+        let _ = settlements.broadcast(option_id, tx, broadcaster);
+        settlements.sync(self);
+        self.mempool_watch.expect_settlements(&settlements);
+        self.settlement_txs.broadcasts = Some(settlements);
+        Ok(txid)
+    }
+
+    /// 추적 중인 정산 트랜잭션의 체인 상태 조회/재전송 후 옵션 기록에 반영
+    ///
+    /// 정산 완료는 이벤트로 남겨 스냅샷에 바로 반영되게 합니다.
+    pub fn poll_settlements(
+        &mut self,
+        chain: &dyn ChainSource,
+        broadcaster: &dyn AnchorBroadcaster,
+    ) -> Result<Vec<SettlementAlert>, AnchorError> {
+        let Some(mut settlements) = self.settlement_txs.broadcasts.take() else {
+            return Ok(Vec::new());
+        };
+        let polled = settlements.poll(chain, broadcaster);
+        settlements.sync(self);
+        self.settlement_txs.broadcasts = Some(settlements);
+        let alerts = polled?;
+
+        for alert in &alerts {
+            if let SettlementAlert::Settled {
+                option_id,
+                txid,
+                confirmations,
+            } = alert
+            {
+                let event = PoolEventKind::SettlementTxConfirmed {
+                    option_id: option_id.clone(),
+                    txid: txid.clone(),
+                    confirmations: *confirmations,
+                };
+                if let Err(e) = self.record_event(event) {
+                    warn!("Failed to record settlement confirmation: {}", e);
+                }
+            }
+        }
+        Ok(alerts)
+    }
+}
@@ -0,0 +1,564 @@
+//! 전체 시스템 상태 스냅샷
+//!
+//! 서비스 업그레이드 시 풀 상태, 원장, 옵션, 정산 대기 목록, 앵커 트랜잭션을
+//! 버전이 붙은 파일 하나로 저장하고, 복원할 때는 체크섬/원장 재적용/담보 합계/
+//! 온체인 앵커를 모두 검증한 뒤에만 관리자를 다시 만듭니다. 스냅샷은 그 시점의
+//! 이벤트 수를 기록하므로 이벤트 로그가 스냅샷보다 앞서 있으면(마지막 저장 후 비정상
+//! 종료) 이전 상태로 되감아 뜨지 않고 거부합니다.
+
+use crate::account_keys::AccountKeys;
+use crate::adl::Haircut;
+use crate::anchor_tracker::TrackedAnchor;
+use crate::audit::AuditRecord;
+use crate::beneficiary::Beneficiary;
+use crate::bootstrap::BitcoindRpc;
+use crate::claimable::ClaimRecords;
+use crate::dual_currency::UsdPoolBook;
+use crate::event_store::EventStore;
+use crate::fees::Treasury;
+use crate::funding::FundingBook;
+use crate::lp_book::LpBook;
+use crate::mempool_watch::MempoolWatchRecords;
+use crate::settlement_broadcast::TrackedSettlement;
+use crate::pool_ledger::{LedgerTransaction, PoolLedger};
+use crate::referral::ReferralProgram;
+use crate::simple_contract::{
+    OptionStatus, SettlementRecord, SimpleContractManager, SimpleOption, SimplePoolState, TradingHalt,
+};
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{AnchorError, SnapshotError};
+use bitcoin::script::{Instruction, ScriptBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// 현재 스냅샷 스키마 버전 (필드 구조가 바뀌면 올림)
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// 만기가 지났지만 아직 정산되지 않은 옵션
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSettlement {
+    pub option_id: String,
+    pub expiry_height: u32,
+}
+
+/// 온체인 앵커 기록 (OP_RETURN 페이로드)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnchorRecord {
+    pub txid: String,
+    pub reference: String, // 옵션 ID 또는 커밋 식별자
+    pub payload: String,   // hex
+}
+
+/// 스냅샷 본문
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    pub created_at: u64,
+    /// 스냅샷 시점 블록 높이 (정산 대기 목록 기준)
+    pub block_height: u32,
+    pub pool_state: SimplePoolState,
+    pub ledger: Vec<LedgerTransaction>,
+    pub options: Vec<SimpleOption>, // option_id 순
+    pub pending_settlements: Vec<PendingSettlement>,
+    pub anchors: Vec<AnchorRecord>,
+    pub used_quotes: Vec<String>,
+    pub trading_halt: Option<TradingHalt>,
+    /// 옵션별 감사 기록 (옵션 ID, 순번 순). 비어 있으면 직렬화하지 않아 이전 스냅샷 체크섬 유지
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audit: Vec<AuditRecord>,
+    /// 재무 계정 (수수료/출금 기록이 없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury: Option<Treasury>,
+    /// 추천 코드와 집계 (등록된 코드가 없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrals: Option<ReferralProgram>,
+    /// LP 지분과 출금 청구권 (지분/청구권이 없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_book: Option<LpBook>,
+    /// 옵션 생성 시 자격 확인 결과 해시 (없으면 생략)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub eligibility_hashes: BTreeMap<String, String>,
+    /// 풀 부족분 분담으로 정한 지급 삭감 (없으면 생략)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub haircuts: Vec<Haircut>,
+    /// 담보 사용료율과 선납분 적립 일정 (사용료율이 0이고 일정이 없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funding: Option<FundingBook>,
+    /// 청구 잔고 적립/출금 기록 (청구 잔고가 없거나 비어 있으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claims: Option<ClaimRecords>,
+    /// 보유자/LP 계정 공개키와 nonce (등록된 키가 없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_keys: Option<AccountKeys>,
+    /// 옵션별 수익자 (등록된 수익자가 없으면 생략)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub beneficiaries: Vec<Beneficiary>,
+    /// 확인을 추적 중인 앵커 트랜잭션 (없으면 생략)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracked_anchors: Vec<TrackedAnchor>,
+    /// 경쟁 지출 감시 대상 UTXO와 무장한 챌린지 (없으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mempool_watch: Option<MempoolWatchRecords>,
+    /// 전송/확인을 추적 중인 정산 트랜잭션 (없으면 생략)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tracked_settlements: Vec<TrackedSettlement>,
+    /// 정산 결과 (옵션 ID 순, 정산한 옵션이 없으면 생략)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub settlements: Vec<SettlementRecord>,
+    /// 사용자별 누적 dust 지급액 (없으면 생략)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dust_balances: BTreeMap<String, u64>,
+    /// USD 결제 옵션과 풀의 USD 잔고 (USD 결제를 켜지 않았으면 생략)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usd_book: Option<UsdPoolBook>,
+    /// Calculation 호가 서명 공개키 (확정 호가를 요구하지 않으면 생략, 교체한 키 유지용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_key: Option<PublicKey>,
+    /// 스냅샷 시점까지 이벤트 저장소에 기록된 이벤트 수 (이전 스냅샷에는 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_count: Option<u64>,
+}
+
+/// 파일 포맷: 스키마 버전 + 본문 체크섬 + 본문
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotFile {
+    schema_version: u32,
+    checksum: String,
+    snapshot: SystemSnapshot,
+}
+
+/// 온체인 앵커 조회 (txid → OP_RETURN 페이로드)
+pub trait AnchorSource {
+    fn anchored_payload(&self, txid: &str) -> Result<Option<Vec<u8>>, AnchorError>;
+}
+
+impl AnchorSource for HashMap<String, Vec<u8>> {
+    fn anchored_payload(&self, txid: &str) -> Result<Option<Vec<u8>>, AnchorError> {
+        Ok(self.get(txid).cloned())
+    }
+}
+
+impl SystemSnapshot {
+    /// 본문 SHA256 체크섬 (hex)
+    pub fn checksum(&self) -> String {
+        let encoded = serde_json::to_vec(self).expect("SystemSnapshot serializes");
+        hex::encode(Sha256::digest(&encoded))
+    }
+
+    /// 스냅샷 파일 저장 (임시 파일에 쓴 뒤 교체)
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let path = path.as_ref();
+        let file = SnapshotFile {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            checksum: self.checksum(),
+            snapshot: self.clone(),
+        };
+        let encoded =
+            serde_json::to_vec_pretty(&file).map_err(|e| SnapshotError::Corrupt(e.to_string()))?;
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, encoded)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| SnapshotError::Io(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 스냅샷 파일 읽기 (스키마 버전과 체크섬 검증)
+    pub fn read_from(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| SnapshotError::Io(format!("{}: {}", path.display(), e)))?;
+
+        // 본문 구조가 버전마다 다를 수 있으므로 버전부터 확인
+        let raw: serde_json::Value =
+            serde_json::from_slice(&bytes).map_err(|e| SnapshotError::Corrupt(e.to_string()))?;
+        let found = raw["schema_version"]
+            .as_u64()
+            .ok_or_else(|| SnapshotError::Corrupt("missing schema_version".to_string()))?
+            as u32;
+        if found != SNAPSHOT_SCHEMA_VERSION {
+            return Err(SnapshotError::UnsupportedVersion {
+                found,
+                expected: SNAPSHOT_SCHEMA_VERSION,
+            });
+        }
+
+        let file: SnapshotFile =
+            serde_json::from_value(raw).map_err(|e| SnapshotError::Corrupt(e.to_string()))?;
+        let computed = file.snapshot.checksum();
+        if computed != file.checksum {
+            return Err(SnapshotError::ChecksumMismatch {
+                expected: file.checksum,
+                computed,
+            });
+        }
+        Ok(file.snapshot)
+    }
+
+    /// 복원 전 검증: 원장 재적용 결과, 담보 합계, 정산 대기 목록, 온체인 앵커
+    pub fn validate(&self, anchors: &dyn AnchorSource) -> Result<(), SnapshotError> {
+        let rebuilt = PoolLedger::from_transactions(self.ledger.clone())
+            .rebuild()
+            .map_err(|e| SnapshotError::Inconsistent(e.to_string()))?;
+        if rebuilt != self.pool_state {
+            return Err(SnapshotError::Inconsistent(
+                "pool state does not match ledger".to_string(),
+            ));
+        }
+
+        let active: Vec<&SimpleOption> = self
+            .options
+            .iter()
+            .filter(|option| option.status == OptionStatus::Active)
+            .collect();
+        // 바이너리 옵션은 고정 지급액(수량)만 잠금
+        let locked: u64 = active.iter().map(|option| option.collateral()).sum();
+        if locked != self.pool_state.locked_collateral
+            || active.len() as u32 != self.pool_state.active_options
+        {
+            return Err(SnapshotError::Inconsistent(format!(
+                "{} active options lock {} sats, pool reports {} options / {} sats",
+                active.len(),
+                locked,
+                self.pool_state.active_options,
+                self.pool_state.locked_collateral
+            )));
+        }
+
+        for pending in &self.pending_settlements {
+            if !active.iter().any(|option| option.option_id == pending.option_id) {
+                return Err(SnapshotError::Inconsistent(format!(
+                    "pending settlement {} is not an active option",
+                    pending.option_id
+                )));
+            }
+        }
+
+        for anchor in &self.anchors {
+            let expected = hex::decode(&anchor.payload)
+                .map_err(|e| SnapshotError::Corrupt(format!("anchor {}: {}", anchor.txid, e)))?;
+            match anchors.anchored_payload(&anchor.txid)? {
+                None => return Err(SnapshotError::AnchorMissing(anchor.txid.clone())),
+                Some(payload) if payload != expected => {
+                    return Err(SnapshotError::AnchorMismatch(anchor.txid.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `OP_RETURN <payload>` 출력 스크립트(hex)의 페이로드
+fn op_return_payload(script_hex: &str) -> Option<Vec<u8>> {
+    let script = ScriptBuf::from_hex(script_hex).ok()?;
+    if !script.is_op_return() {
+        return None;
+    }
+    match script.instructions().nth(1) {
+        Some(Ok(Instruction::PushBytes(bytes))) => Some(bytes.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// bitcoind에서 앵커 트랜잭션의 OP_RETURN 페이로드 조회 (체인/멤풀에 없는 트랜잭션은 빠짐)
+///
+/// 지갑 밖의 확인된 트랜잭션까지 찾으려면 bitcoind에 `-txindex`가 켜져 있어야 합니다.
+pub async fn fetch_anchor_payloads(
+    rpc: &dyn BitcoindRpc,
+    anchors: &[AnchorRecord],
+) -> Result<HashMap<String, Vec<u8>>, SnapshotError> {
+    let mut found = HashMap::new();
+    for anchor in anchors {
+        let tx = match rpc
+            .call(None, "getrawtransaction", vec![json!(anchor.txid), json!(true)])
+            .await
+        {
+            Ok(tx) => tx,
+            Err(e) if e.to_string().contains("No such mempool or blockchain transaction") => continue,
+            Err(e) => return Err(AnchorError::Rpc(e.to_string()).into()),
+        };
+        let payload = tx["vout"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find_map(|output| op_return_payload(output["scriptPubKey"]["hex"].as_str()?));
+        if let Some(payload) = payload {
+            found.insert(anchor.txid.clone(), payload);
+        }
+    }
+    Ok(found)
+}
+
+/// 서버 시작 시 풀 관리자 열기
+///
+/// 스냅샷 파일이 있으면 이벤트 저장소와 함께 검증 후 복원하고, 없으면 이벤트가 없는
+/// 저장소에서만 새 풀로 시작합니다. 이벤트만으로는 옵션 상태를 다시 만들 수 없으므로
+/// 스냅샷 없이 이벤트가 남아 있거나 스냅샷 이후 이벤트가 더 쌓여 있으면 거부합니다.
+/// 스냅샷에 확정 앵커가 있으면 `chain`(bitcoind)에서 온체인 페이로드를 대조하며,
+/// 대조할 bitcoind가 없으면 복원하지 않습니다.
+pub async fn open_pool(
+    snapshot_path: impl AsRef<Path>,
+    event_store: Box<dyn EventStore>,
+    chain: Option<&dyn BitcoindRpc>,
+) -> Result<SimpleContractManager, SnapshotError> {
+    let snapshot_path = snapshot_path.as_ref();
+    if snapshot_path.exists() {
+        let snapshot = SystemSnapshot::read_from(snapshot_path)?;
+        let anchors = match chain {
+            Some(rpc) => fetch_anchor_payloads(rpc, &snapshot.anchors).await?,
+            None if snapshot.anchors.is_empty() => HashMap::new(),
+            None => {
+                return Err(SnapshotError::Inconsistent(format!(
+                    "{} has {} anchors to verify but no bitcoind RPC is configured",
+                    snapshot_path.display(),
+                    snapshot.anchors.len()
+                )))
+            }
+        };
+        return SimpleContractManager::restore_with_event_store(snapshot, &anchors, event_store);
+    }
+    let events = event_store.events().len();
+    if events > 0 {
+        return Err(SnapshotError::Inconsistent(format!(
+            "event log has {} events but {} is missing",
+            events,
+            snapshot_path.display()
+        )));
+    }
+    Ok(SimpleContractManager::with_event_store(event_store))
+}
+
+/// 서버용 스냅샷 저장 (마지막으로 본 블록 높이 기준, 최종 확인된 앵커 기록 포함)
+pub fn persist_pool(manager: &SimpleContractManager, snapshot_path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+    manager
+        .snapshot(manager.tip_height().unwrap_or(0), manager.anchor_records())
+        .write_to(snapshot_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_store::FileEventStore;
+    use oracle_vm_common::types::OptionType;
+    use oracle_vm_common::{DustHandling, ExercisePolicy};
+
+    fn manager() -> SimpleContractManager {
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager.set_exercise_policy(ExercisePolicy::new(546, DustHandling::Accumulate));
+        for (id, expiry) in [("OPT-1", 100), ("OPT-2", 200), ("OPT-3", 300), ("OPT-4", 300)] {
+            manager
+                .create_option(
+                    id.to_string(),
+                    OptionType::Call,
+                    7_000_000,
+                    1_000_000,
+                    25_000,
+                    expiry,
+                    "user".to_string(),
+                )
+                .unwrap();
+        }
+        manager.settle_option("OPT-3", 7_200_000).unwrap();
+        // 30 sats 내재가치는 dust로 사용자에게 누적
+        manager.settle_option("OPT-4", 7_003_000).unwrap();
+        manager
+    }
+
+    fn anchor() -> (AnchorRecord, HashMap<String, Vec<u8>>) {
+        let record = AnchorRecord {
+            txid: "ab".repeat(32),
+            reference: "OPT-3".to_string(),
+            payload: hex::encode([7u8; 32]),
+        };
+        let chain = HashMap::from([(record.txid.clone(), vec![7u8; 32])]);
+        (record, chain)
+    }
+
+    #[test]
+    fn test_snapshot_file_roundtrip_and_restore() {
+        let original = manager();
+        let (record, chain) = anchor();
+        let snapshot = original.snapshot(150, vec![record]);
+        assert_eq!(snapshot.pending_settlements.len(), 1);
+        assert_eq!(snapshot.settlements.len(), 2);
+        assert_eq!(snapshot.dust_balances["user"], 30);
+
+        let path = std::env::temp_dir().join(format!("btcfi-snapshot-{}.json", std::process::id()));
+        snapshot.write_to(&path).unwrap();
+        let loaded = SystemSnapshot::read_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, snapshot);
+
+        let mut restored = SimpleContractManager::restore(loaded, &chain).unwrap();
+        assert_eq!(restored.pool_state, original.pool_state);
+        assert_eq!(restored.ledger().transactions(), original.ledger().transactions());
+        assert_eq!(restored.get_expired_options(150).len(), 1);
+        assert_eq!(restored.settlement("OPT-3"), original.settlement("OPT-3"));
+        assert_eq!(restored.settlement("OPT-4"), original.settlement("OPT-4"));
+        assert_eq!(restored.dust_balance("user"), 30);
+
+        // 복원 후에도 기존 옵션은 한 번만 정산
+        assert!(restored.settle_option("OPT-3", 7_200_000).is_err());
+        restored.settle_option("OPT-1", 7_200_000).unwrap();
+        restored.rebuild_pool_state().unwrap();
+    }
+
+    #[test]
+    fn test_usd_book_survives_snapshot() {
+        use oracle_vm_common::settlement_currency::UsdRail;
+
+        let mut original = SimpleContractManager::new();
+        original.add_liquidity(100_000_000).unwrap();
+        original.enable_usd_settlement(UsdRail::Synthetic);
+        original.add_usd_liquidity(10_000).unwrap();
+        original
+            .create_usd_option("USD-CALL".to_string(), OptionType::Call, 7_000_000, 100_000_000, 25_000, 100, "user".to_string())
+            .unwrap();
+        let snapshot = original.snapshot(50, Vec::new());
+        assert_eq!(snapshot.usd_book.as_ref(), original.usd_book());
+
+        let path = std::env::temp_dir().join(format!("btcfi-snapshot-usd-{}.json", std::process::id()));
+        snapshot.write_to(&path).unwrap();
+        let loaded = SystemSnapshot::read_from(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut restored = SimpleContractManager::restore(loaded, &HashMap::new()).unwrap();
+        assert_eq!(restored.usd_book(), original.usd_book());
+        assert!(restored.options["USD-CALL"].terms.settlement_currency.is_usd());
+
+        // 복원 후에도 USD 잔고에서 먼저 지급하고 부족분만 BTC 담보 환산
+        original.settle_option("USD-CALL", 7_200_000).unwrap();
+        restored.settle_option("USD-CALL", 7_200_000).unwrap();
+        assert_eq!(restored.usd_book(), original.usd_book());
+        assert_eq!(restored.pool_state, original.pool_state);
+        assert_eq!(restored.usd_book().unwrap().conversions().len(), 1);
+    }
+
+    #[test]
+    fn test_tampered_or_unverified_snapshot_rejected() {
+        let (record, chain) = anchor();
+        let snapshot = manager().snapshot(150, vec![record.clone()]);
+
+        let path = std::env::temp_dir().join(format!("btcfi-snapshot-bad-{}.json", std::process::id()));
+        snapshot.write_to(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, text.replacen("100000000", "100000001", 1)).unwrap();
+        assert!(matches!(
+            SystemSnapshot::read_from(&path),
+            Err(SnapshotError::ChecksumMismatch { .. })
+        ));
+        std::fs::write(&path, text.replacen("\"schema_version\": 1", "\"schema_version\": 9", 1)).unwrap();
+        assert!(matches!(
+            SystemSnapshot::read_from(&path),
+            Err(SnapshotError::UnsupportedVersion { found: 9, .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        let mut wrong_chain = chain.clone();
+        wrong_chain.insert(record.txid.clone(), vec![8u8; 32]);
+        assert_eq!(
+            SimpleContractManager::restore(snapshot.clone(), &wrong_chain).err(),
+            Some(SnapshotError::AnchorMismatch(record.txid.clone()))
+        );
+        assert_eq!(
+            SimpleContractManager::restore(snapshot.clone(), &HashMap::new()).err(),
+            Some(SnapshotError::AnchorMissing(record.txid))
+        );
+
+        let mut inconsistent = snapshot;
+        inconsistent.options.retain(|option| option.option_id != "OPT-1");
+        assert!(matches!(
+            SimpleContractManager::restore(inconsistent, &chain),
+            Err(SnapshotError::Inconsistent(_))
+        ));
+    }
+
+    /// 앵커 트랜잭션 하나를 아는 bitcoind
+    struct AnchorChain {
+        txid: String,
+        payload: Vec<u8>,
+    }
+
+    #[async_trait::async_trait]
+    impl BitcoindRpc for AnchorChain {
+        async fn call(&self, _wallet: Option<&str>, method: &str, params: Vec<serde_json::Value>) -> anyhow::Result<serde_json::Value> {
+            assert_eq!(method, "getrawtransaction");
+            if params[0] != json!(self.txid) {
+                anyhow::bail!("No such mempool or blockchain transaction");
+            }
+            let script = ScriptBuf::new_op_return(<&bitcoin::script::PushBytes>::try_from(self.payload.as_slice()).unwrap());
+            Ok(json!({ "vout": [{ "scriptPubKey": { "hex": "0014aa" } }, { "scriptPubKey": { "hex": script.to_hex_string() } }] }))
+        }
+    }
+
+    fn confirmed_tracker() -> crate::anchor_tracker::AnchorTracker {
+        use crate::anchor_tracker::{AnchorStatus, AnchorTracker};
+        let mut tracker = AnchorTracker::new(oracle_vm_common::NetworkProfile::TESTNET);
+        tracker.restore(vec![TrackedAnchor {
+            option_id: "OPT-1".to_string(),
+            txid: "ab".repeat(32),
+            payload: vec![7u8; 32],
+            raw_tx: None,
+            status: AnchorStatus::Confirmed {
+                txid: "ab".repeat(32),
+                block_height: 90,
+                confirmations: 6,
+            },
+            block_hash: Some("b90".to_string()),
+            replaced: Vec::new(),
+            failures: 0,
+        }]);
+        tracker
+    }
+
+    #[tokio::test]
+    async fn test_open_pool_restores_persisted_state() {
+        let dir = std::env::temp_dir().join(format!("btcfi-open-pool-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let events = dir.join("events.jsonl");
+        let snapshot = dir.join("snapshot.json");
+        let store = || Box::new(FileEventStore::open(&events).unwrap());
+
+        let mut manager = open_pool(&snapshot, store(), None).await.unwrap();
+        manager.add_liquidity(100_000_000).unwrap();
+        manager
+            .create_option("OPT-1".to_string(), OptionType::Call, 7_000_000, 1_000_000, 25_000, 100, "user".to_string())
+            .unwrap();
+        manager.enable_anchor_tracking(confirmed_tracker());
+        persist_pool(&manager, &snapshot).unwrap();
+        let pool_state = manager.pool_state.clone();
+        drop(manager);
+
+        // 확정 앵커는 bitcoind의 OP_RETURN 페이로드와 대조
+        assert!(matches!(open_pool(&snapshot, store(), None).await, Err(SnapshotError::Inconsistent(_))));
+        let wrong = AnchorChain { txid: "ab".repeat(32), payload: vec![8u8; 32] };
+        assert_eq!(
+            open_pool(&snapshot, store(), Some(&wrong)).await.err(),
+            Some(SnapshotError::AnchorMismatch("ab".repeat(32)))
+        );
+        let chain = AnchorChain { txid: "ab".repeat(32), payload: vec![7u8; 32] };
+        let mut restored = open_pool(&snapshot, store(), Some(&chain)).await.unwrap();
+        assert!(restored.options.contains_key("OPT-1"));
+        assert_eq!(restored.pool_state, pool_state);
+        restored.enable_anchor_tracking(crate::anchor_tracker::AnchorTracker::new(oracle_vm_common::NetworkProfile::TESTNET));
+        assert_eq!(restored.anchor_records().len(), 1);
+        assert!(restored.anchor_status("OPT-1").is_some());
+
+        // 스냅샷 이후 이벤트가 남아 있으면 되감긴 상태로 뜨지 않음
+        restored
+            .create_option("OPT-2".to_string(), OptionType::Call, 7_000_000, 1_000_000, 25_000, 100, "user".to_string())
+            .unwrap();
+        drop(restored);
+        assert!(matches!(
+            open_pool(&snapshot, store(), Some(&chain)).await,
+            Err(SnapshotError::Inconsistent(_))
+        ));
+
+        // 이벤트는 있는데 스냅샷이 없으면 빈 풀로 시작하지 않음
+        std::fs::remove_file(&snapshot).unwrap();
+        assert!(matches!(open_pool(&snapshot, store(), None).await, Err(SnapshotError::Inconsistent(_))));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
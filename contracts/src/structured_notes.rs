@@ -0,0 +1,327 @@
+//! 원금 보장형 BTC 노트 (구조화 상품)
+//!
+//! 노트는 무이표채처럼 동작하는 풀 예치와 콜 옵션 오버레이로 구성됩니다.
+//! 보유자가 낸 원금 중 예치 이율로 할인한 현재가치만큼은 풀에 예치되고,
+//! 나머지(옵션 예산)로 현재 호가의 콜을 삽니다. 참여율은 예산으로 살 수 있는
+//! 콜 수량 / 원금이라 호가 프리미엄이 쌀수록 높아집니다.
+//!
+//! 내장 콜은 `note:<노트 ID>` 계정 명의로 SimpleContractManager에서 일반 옵션처럼
+//! 열리고 정산되므로 보유자가 따로 되사거나 행사할 수 없습니다. 만기에 풀은 예치의
+//! 액면인 원금 전액을 돌려주고, 보유자 몫은 원금 + 콜 정산액입니다.
+
+use crate::simple_contract::{exercised_amount, OptionStatus, SimpleContractManager};
+use oracle_vm_common::{ContractError, OptionQuote, OptionType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 연간 블록 수 (10분 블록 기준)
+pub const BLOCKS_PER_YEAR: u32 = 52_560;
+
+/// 노트 발행 조건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteTerms {
+    pub note_id: String,
+    pub holder: String,
+    /// 보장 원금 (satoshis)
+    pub principal: u64,
+    /// 만기 블록 높이 (내장 콜의 만기)
+    pub maturity_height: u32,
+    /// 풀 예치에 적용하는 연 이율 (0.05 = 5%)
+    pub deposit_rate: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteStatus {
+    Open,
+    Matured,
+}
+
+/// 발행된 노트와 보유자 권리
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Note {
+    pub note_id: String,
+    pub holder: String,
+    pub principal: u64, // satoshis
+    /// 풀에 예치된 무이표 부분 (원금 - 콜 프리미엄, satoshis)
+    pub deposit: u64,
+    pub option_id: String,
+    pub option_quantity: u64, // satoshis
+    pub premium: u64,         // satoshis
+    pub strike_price: u64,    // USD cents
+    pub maturity_height: u32,
+    pub status: NoteStatus,
+    /// 만기 시 콜 정산액 (satoshis, 만기 전 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub option_payout: Option<u64>,
+}
+
+impl Note {
+    /// 참여율 (콜 수량 / 원금)
+    pub fn participation_rate(&self) -> f64 {
+        self.option_quantity as f64 / self.principal as f64
+    }
+
+    /// 만기 확정 권리 (원금 + 콜 정산액, 만기 전 None)
+    pub fn entitlement(&self) -> Option<u64> {
+        self.option_payout.map(|payout| self.principal + payout)
+    }
+}
+
+/// 내장 콜을 보유하는 노트 계정
+pub fn note_account(note_id: &str) -> String {
+    format!("note:{}", note_id)
+}
+
+/// 옵션 예산: 원금 - 원금 / (1 + r)^T (T = 남은 블록 / 연간 블록)
+pub fn option_budget(principal: u64, deposit_rate: f64, term_blocks: u32) -> u64 {
+    let years = term_blocks as f64 / BLOCKS_PER_YEAR as f64;
+    let present_value = principal as f64 / (1.0 + deposit_rate).powf(years);
+    principal - (present_value.ceil() as u64).min(principal)
+}
+
+/// 노트 장부
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteBook {
+    notes: BTreeMap<String, Note>,
+}
+
+impl NoteBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, note_id: &str) -> Option<&Note> {
+        self.notes.get(note_id)
+    }
+
+    pub fn by_holder<'a>(&'a self, holder: &'a str) -> impl Iterator<Item = &'a Note> + 'a {
+        self.notes.values().filter(move |note| note.holder == holder)
+    }
+
+    /// 노트 발행: 예산으로 호가의 콜을 (계약 단위로 내림) 부분 체결하고 나머지를 풀에 예치
+    ///
+    /// 호가는 유럽식 바닐라 콜이어야 하며, 예산으로 한 계약도 살 수 없으면 거부합니다.
+    pub fn issue(
+        &mut self,
+        manager: &mut SimpleContractManager,
+        terms: NoteTerms,
+        quote: &OptionQuote,
+        current_height: u32,
+    ) -> Result<&Note, ContractError> {
+        let reject = |reason: String| Err(ContractError::StructuredNote(format!("{}: {}", terms.note_id, reason)));
+        if self.notes.contains_key(&terms.note_id) {
+            return reject("note already issued".to_string());
+        }
+        if terms.principal == 0 || terms.maturity_height <= current_height {
+            return reject("principal and remaining term must be positive".to_string());
+        }
+        if quote.option_type != OptionType::Call
+            || quote.style.is_american()
+            || quote.barrier.is_some()
+            || quote.payoff.is_binary()
+            || quote.premium == 0
+        {
+            return reject(format!("quote {} is not a vanilla European call", quote.quote_id));
+        }
+
+        let budget = option_budget(terms.principal, terms.deposit_rate, terms.maturity_height - current_height);
+        let affordable = (budget as u128 * quote.quantity as u128 / quote.premium as u128) as u64;
+        let lot = manager.contract_spec().map_or(1, |spec| spec.contract_size);
+        let quantity = affordable.min(quote.quantity) / lot * lot;
+        if quantity == 0 {
+            return reject(format!("budget {} buys no contracts at premium {}", budget, quote.premium));
+        }
+
+        let option_id = format!("{}-CALL", terms.note_id);
+        manager.fill_quote(
+            quote,
+            quantity,
+            option_id.clone(),
+            terms.maturity_height,
+            note_account(&terms.note_id),
+        )?;
+        let premium = manager.options[&option_id].premium_paid;
+        let deposit = terms.principal.saturating_sub(premium);
+        manager.add_liquidity(deposit)?;
+
+        let note = Note {
+            note_id: terms.note_id.clone(),
+            holder: terms.holder,
+            principal: terms.principal,
+            deposit,
+            option_id,
+            option_quantity: quantity,
+            premium,
+            strike_price: quote.strike_price,
+            maturity_height: terms.maturity_height,
+            status: NoteStatus::Open,
+            option_payout: None,
+        };
+        Ok(self.notes.entry(terms.note_id).or_insert(note))
+    }
+
+    /// 만기 전 예상 권리: 원금 + 주어진 가격의 콜 내재가치
+    pub fn indicative_entitlement(
+        &self,
+        manager: &SimpleContractManager,
+        note_id: &str,
+        spot_price: u64,
+    ) -> Option<u64> {
+        let note = self.notes.get(note_id)?;
+        if let Some(entitlement) = note.entitlement() {
+            return Some(entitlement);
+        }
+        let option = manager.options.get(&note.option_id)?;
+        Some(note.principal + option.payout_at(spot_price))
+    }
+
+    /// 노트 만기 처리: 내장 콜을 (아직이면) 정산하고 원금을 풀에서 인출해 권리 확정
+    ///
+    /// 확정된 권리(원금 + 콜 정산액)를 반환합니다.
+    pub fn mature(
+        &mut self,
+        manager: &mut SimpleContractManager,
+        note_id: &str,
+        spot_price: u64,
+        current_height: u32,
+    ) -> Result<u64, ContractError> {
+        let reject = |reason: String| Err(ContractError::StructuredNote(format!("{}: {}", note_id, reason)));
+        let Some(note) = self.notes.get(note_id) else {
+            return reject("note not found".to_string());
+        };
+        if note.status == NoteStatus::Matured {
+            return reject("note already matured".to_string());
+        }
+        if current_height < note.maturity_height {
+            return reject(format!("matures at height {}", note.maturity_height));
+        }
+
+        let active = manager
+            .options
+            .get(&note.option_id)
+            .is_some_and(|option| option.status == OptionStatus::Active);
+        if active {
+            manager
+                .settle_option(&note.option_id, spot_price)
+                .map_err(|e| ContractError::StructuredNote(format!("{}: {}", note_id, e)))?;
+        }
+        let option_payout = manager
+            .settlement(&note.option_id)
+            .map_or(0, |record| exercised_amount(&record.exercise));
+        manager.remove_liquidity(note.principal)?;
+
+        let note = self.notes.get_mut(note_id).expect("note checked above");
+        note.status = NoteStatus::Matured;
+        note.option_payout = Some(option_payout);
+        Ok(note.principal + option_payout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_time::BlockClock;
+    use crate::simple_contract::AVG_BLOCK_SECS;
+    use oracle_vm_common::{expiry_date_timestamp, ExercisePolicy, ExerciseStyle, Payoff};
+
+    fn call_quote(secret_key: &oracle_vm_common::crypto::SecretKey) -> OptionQuote {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut quote = OptionQuote {
+            quote_id: "Q-PPN".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 100_000_000,
+            premium: 5_000_000,
+            spot_price: 7_000_000,
+            issued_at: now,
+            valid_until: now + 30,
+            premium_usd: 3_500_000,
+            otc: false,
+            exercise: ExercisePolicy::default(),
+            referral_code: None,
+            tenant_id: None,
+            theoretical_premium: None,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+            signature: String::new(),
+        };
+        quote.sign(secret_key).unwrap();
+        quote
+    }
+
+    /// 팁 800,000에서 호가 만기(2024-03-01)까지 1년이 남은 체인
+    fn year_before_expiry(manager: &mut SimpleContractManager) {
+        let mut headers = BlockClock::new();
+        let expiry_time = expiry_date_timestamp("2024-03-01").unwrap();
+        headers.observe(800_000, expiry_time - BLOCKS_PER_YEAR as u64 * AVG_BLOCK_SECS);
+        manager.set_block_clock(headers);
+    }
+
+    #[test]
+    fn test_note_protects_principal_and_pays_participation() {
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(500_000_000).unwrap();
+        manager.require_quotes(public_key);
+        year_before_expiry(&mut manager);
+
+        // 1년 5% 예치: 예산 = 1억 - 1억/1.05 = 4,761,904 → 콜 0.95 BTC (프리미엄 5%)
+        let terms = NoteTerms {
+            note_id: "PPN-1".to_string(),
+            holder: "alice".to_string(),
+            principal: 100_000_000,
+            maturity_height: 800_000 + BLOCKS_PER_YEAR,
+            deposit_rate: 0.05,
+        };
+        let mut book = NoteBook::new();
+        let note = book.issue(&mut manager, terms.clone(), &call_quote(&secret_key), 800_000).unwrap().clone();
+        assert_eq!(note.option_quantity, 95_238_080);
+        assert!((note.participation_rate() - 0.952).abs() < 1e-3);
+        assert_eq!(note.deposit + note.premium, note.principal);
+        assert_eq!(manager.options["PPN-1-CALL"].user_id, "note:PPN-1");
+        assert!(matches!(
+            book.issue(&mut manager, terms, &call_quote(&secret_key), 800_000),
+            Err(ContractError::StructuredNote(_))
+        ));
+
+        // 만기 전에는 예상 권리만, 만기 후 확정
+        assert_eq!(book.indicative_entitlement(&manager, "PPN-1", 6_000_000), Some(100_000_000));
+        assert!(book.mature(&mut manager, "PPN-1", 8_000_000, 800_000).is_err());
+
+        // OTM 만기: 원금만 돌려받음
+        let total_before = manager.pool_state.total_liquidity;
+        assert_eq!(book.mature(&mut manager, "PPN-1", 6_000_000, note.maturity_height).unwrap(), 100_000_000);
+        assert_eq!(manager.pool_state.total_liquidity, total_before - 100_000_000);
+        assert_eq!(book.get("PPN-1").unwrap().entitlement(), Some(100_000_000));
+        assert_eq!(book.by_holder("alice").count(), 1);
+        assert_eq!(manager.ledger().rebuild().unwrap(), manager.pool_state);
+    }
+
+    #[test]
+    fn test_itm_note_adds_call_payout_to_principal() {
+        let (secret_key, public_key) = oracle_vm_common::crypto::generate_keypair();
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(500_000_000).unwrap();
+        manager.require_quotes(public_key);
+        year_before_expiry(&mut manager);
+
+        let terms = NoteTerms {
+            note_id: "PPN-2".to_string(),
+            holder: "bob".to_string(),
+            principal: 100_000_000,
+            maturity_height: 800_000 + BLOCKS_PER_YEAR,
+            deposit_rate: 0.05,
+        };
+        let mut book = NoteBook::new();
+        let note = book.issue(&mut manager, terms, &call_quote(&secret_key), 800_000).unwrap().clone();
+
+        // 내장 콜이 정산 흐름에서 먼저 정산돼도 같은 지급액으로 확정
+        let paid = manager.settle_option(&note.option_id, 8_000_000).unwrap();
+        assert!(paid > 0);
+        assert_eq!(book.mature(&mut manager, "PPN-2", 8_000_000, note.maturity_height).unwrap(), 100_000_000 + paid);
+        assert!(book.mature(&mut manager, "PPN-2", 8_000_000, note.maturity_height).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! 옵션 Taproot 주소와 스크립트 경로 정보
+//!
+//! 옵션마다 담보를 잠그는 Taproot 출력을 만들고, 나중에 정산/해지/회수
+//! 트랜잭션의 witness를 조립할 수 있도록 리프 스크립트와 control block을 옵션별로
+//! 보관합니다. 내부 키는 NUMS 점이라 key path는 쓸 수 없고, script path는 세 가지입니다.
+//!
+//! ```text
+//! 정산 (depth 1): <expiry> CLTV DROP <verifier> CHECKSIG
+//! 협의 해지 (depth 2): <buyer> CHECKSIGVERIFY <pool> CHECKSIG
+//! 회수 (depth 2): <expiry + timeout> CLTV DROP <pool> CHECKSIG
+//! ```
+//!
+//! 정산 경로는 만기 후 BitVMX 검증자가 서명하고, 검증자가 응답하지 않으면
+//! `timeout_blocks` 뒤에 풀이 담보를 회수합니다.
+
+use anyhow::{anyhow, bail, Context, Result};
+use bitcoin::blockdata::opcodes::all as op;
+use bitcoin::script::Builder;
+use bitcoin::secp256k1::{PublicKey, Secp256k1};
+use bitcoin::taproot::{ControlBlock, LeafVersion, TaprootBuilder, TaprootSpendInfo};
+use bitcoin::{Address, ScriptBuf, Witness, XOnlyPublicKey};
+use oracle_vm_common::NetworkProfile;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// BIP341 NUMS 점 (개인키가 알려지지 않은 내부 키, key path 비활성화)
+pub const NUMS_INTERNAL_KEY: [u8; 32] = [
+    0x50, 0x92, 0x9b, 0x74, 0xc1, 0xa0, 0x49, 0x54, 0xb7, 0x8b, 0x4b, 0x60, 0x35, 0xe9, 0x7a, 0x5e, 0x07, 0x8a,
+    0x5a, 0x0f, 0x28, 0xec, 0x96, 0xd5, 0x47, 0xbf, 0xee, 0x9a, 0xce, 0x80, 0x3a, 0xc0,
+];
+
+/// 만기 후 검증자 정산을 기다리는 블록 수 (약 하루, 이후 풀이 회수 가능)
+pub const DEFAULT_TIMEOUT_BLOCKS: u32 = 144;
+
+/// NUMS 내부 키
+pub fn nums_internal_key() -> XOnlyPublicKey {
+    XOnlyPublicKey::from_slice(&NUMS_INTERNAL_KEY).expect("NUMS point is a valid key")
+}
+
+/// 스크립트 경로 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeafKind {
+    /// 만기 후 검증자 서명
+    Settlement,
+    /// 구매자와 풀이 함께 서명 (만기 전 해지)
+    Cooperative,
+    /// 정산 기한이 지난 뒤 풀 단독 회수
+    Timeout,
+}
+
+/// 리프 스크립트 하나와 그 control block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaprootLeaf {
+    pub kind: LeafKind,
+    pub script: ScriptBuf,
+    /// 직렬화된 control block (hex)
+    pub control_block: String,
+}
+
+impl TaprootLeaf {
+    pub fn control_block(&self) -> Result<ControlBlock> {
+        let bytes = hex::decode(&self.control_block)?;
+        ControlBlock::decode(&bytes).map_err(|e| anyhow!("Invalid control block: {}", e))
+    }
+}
+
+/// 옵션별로 보관하는 Taproot 출력 정보
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionTaprootRecord {
+    pub option_id: String,
+    pub internal_key: XOnlyPublicKey,
+    pub address: String,
+    pub script_pubkey: ScriptBuf,
+    pub leaves: Vec<TaprootLeaf>,
+}
+
+impl OptionTaprootRecord {
+    pub fn leaf(&self, kind: LeafKind) -> Option<&TaprootLeaf> {
+        self.leaves.iter().find(|leaf| leaf.kind == kind)
+    }
+
+    /// script path witness: 스크립트 입력(서명 등, 스택 아래부터) + 리프 스크립트 + control block
+    pub fn script_path_witness(&self, kind: LeafKind, inputs: Vec<Vec<u8>>) -> Result<Witness> {
+        let leaf = self
+            .leaf(kind)
+            .ok_or_else(|| anyhow!("Option {} has no {:?} leaf", self.option_id, kind))?;
+        let mut witness = Witness::new();
+        for input in inputs {
+            witness.push(input);
+        }
+        witness.push(leaf.script.as_bytes());
+        witness.push(hex::decode(&leaf.control_block)?);
+        Ok(witness)
+    }
+}
+
+/// 완성된 Taproot 트리와 보관용 기록
+pub struct OptionTaproot {
+    pub spend_info: TaprootSpendInfo,
+    pub record: OptionTaprootRecord,
+}
+
+/// 옵션 Taproot 출력 생성기 (풀 키와 검증자 키는 운영 설정에서 고정)
+#[derive(Debug, Clone)]
+pub struct TaprootAddressBuilder {
+    profile: NetworkProfile,
+    pool_key: PublicKey,
+    verifier: PublicKey,
+    timeout_blocks: u32,
+}
+
+impl TaprootAddressBuilder {
+    pub fn new(profile: NetworkProfile, pool_key: PublicKey, verifier: PublicKey) -> Self {
+        Self {
+            profile,
+            pool_key,
+            verifier,
+            timeout_blocks: DEFAULT_TIMEOUT_BLOCKS,
+        }
+    }
+
+    /// 검증자 정산을 기다리는 블록 수 변경
+    pub fn with_timeout_blocks(mut self, timeout_blocks: u32) -> Self {
+        self.timeout_blocks = timeout_blocks;
+        self
+    }
+
+    pub fn settlement_script(&self, expiry_height: u32) -> ScriptBuf {
+        Builder::new()
+            .push_int(expiry_height as i64)
+            .push_opcode(op::OP_CLTV)
+            .push_opcode(op::OP_DROP)
+            .push_x_only_key(&XOnlyPublicKey::from(self.verifier))
+            .push_opcode(op::OP_CHECKSIG)
+            .into_script()
+    }
+
+    pub fn cooperative_script(&self, buyer: &PublicKey) -> ScriptBuf {
+        Builder::new()
+            .push_x_only_key(&XOnlyPublicKey::from(*buyer))
+            .push_opcode(op::OP_CHECKSIGVERIFY)
+            .push_x_only_key(&XOnlyPublicKey::from(self.pool_key))
+            .push_opcode(op::OP_CHECKSIG)
+            .into_script()
+    }
+
+    pub fn timeout_script(&self, expiry_height: u32) -> ScriptBuf {
+        Builder::new()
+            .push_int(expiry_height.saturating_add(self.timeout_blocks) as i64)
+            .push_opcode(op::OP_CLTV)
+            .push_opcode(op::OP_DROP)
+            .push_x_only_key(&XOnlyPublicKey::from(self.pool_key))
+            .push_opcode(op::OP_CHECKSIG)
+            .into_script()
+    }
+
+    /// 표준 세 경로로 옵션 출력 생성
+    pub fn build(&self, option_id: &str, buyer: &PublicKey, expiry_height: u32) -> Result<OptionTaproot> {
+        self.build_with_settlement(option_id, buyer, expiry_height, self.settlement_script(expiry_height))
+    }
+
+    /// 정산 경로만 옵션 고유 스크립트(예: BitVMX 증명 검증)로 바꿔 생성
+    pub fn build_with_settlement(
+        &self,
+        option_id: &str,
+        buyer: &PublicKey,
+        expiry_height: u32,
+        settlement: ScriptBuf,
+    ) -> Result<OptionTaproot> {
+        let leaves = [
+            (1, LeafKind::Settlement, settlement),
+            (2, LeafKind::Cooperative, self.cooperative_script(buyer)),
+            (2, LeafKind::Timeout, self.timeout_script(expiry_height)),
+        ];
+        let mut builder = TaprootBuilder::new();
+        for (depth, _, script) in &leaves {
+            builder = builder
+                .add_leaf(*depth, script.clone())
+                .map_err(|e| anyhow!("Invalid taproot tree: {}", e))?;
+        }
+        let secp = Secp256k1::verification_only();
+        let internal_key = nums_internal_key();
+        let spend_info = builder
+            .finalize(&secp, internal_key)
+            .map_err(|_| anyhow!("Incomplete taproot tree"))?;
+
+        let leaves = leaves
+            .into_iter()
+            .map(|(_, kind, script)| {
+                let control_block = spend_info
+                    .control_block(&(script.clone(), LeafVersion::TapScript))
+                    .ok_or_else(|| anyhow!("No control block for {:?} leaf", kind))?;
+                Ok(TaprootLeaf {
+                    kind,
+                    script,
+                    control_block: hex::encode(control_block.serialize()),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let address = Address::p2tr_tweaked(spend_info.output_key(), self.profile.network);
+        let record = OptionTaprootRecord {
+            option_id: option_id.to_string(),
+            internal_key,
+            address: address.to_string(),
+            script_pubkey: address.script_pubkey(),
+            leaves,
+        };
+        Ok(OptionTaproot { spend_info, record })
+    }
+}
+
+/// 옵션별 Taproot 기록 디렉터리: `{root}/{option_id}.json`
+pub struct TaprootStore {
+    root: PathBuf,
+}
+
+impl TaprootStore {
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create taproot store {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    fn record_path(&self, option_id: &str) -> Result<PathBuf> {
+        if option_id.is_empty()
+            || !option_id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            bail!("Invalid option id {}", option_id);
+        }
+        Ok(self.root.join(format!("{}.json", option_id)))
+    }
+
+    pub fn put(&self, record: &OptionTaprootRecord) -> Result<()> {
+        let path = self.record_path(&record.option_id)?;
+        // 임시 파일에 쓴 뒤 교체 (중간에 끊겨도 반쯤 쓴 기록이 남지 않음)
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(record)?)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .with_context(|| format!("Failed to write taproot record {}", path.display()))
+    }
+
+    pub fn get(&self, option_id: &str) -> Result<Option<OptionTaprootRecord>> {
+        let path = self.record_path(option_id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = std::fs::read(&path)
+            .with_context(|| format!("Failed to read taproot record {}", path.display()))?;
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::secp256k1::SecretKey;
+
+    fn key(byte: u8) -> PublicKey {
+        PublicKey::from_secret_key(&Secp256k1::new(), &SecretKey::from_slice(&[byte; 32]).unwrap())
+    }
+
+    fn builder() -> TaprootAddressBuilder {
+        TaprootAddressBuilder::new(NetworkProfile::REGTEST, key(1), key(2))
+    }
+
+    #[test]
+    fn test_control_blocks_commit_to_output_key() {
+        let taproot = builder().build("CALL-1", &key(3), 850_000).unwrap();
+        let record = &taproot.record;
+        assert!(record.script_pubkey.is_p2tr());
+        assert_eq!(record.internal_key, nums_internal_key());
+        assert_eq!(taproot.spend_info.internal_key(), nums_internal_key());
+
+        let secp = Secp256k1::verification_only();
+        let output_key = taproot.spend_info.output_key().to_x_only_public_key();
+        for leaf in &record.leaves {
+            let control_block = leaf.control_block().unwrap();
+            assert!(control_block.verify_taproot_commitment(&secp, output_key, &leaf.script));
+        }
+        assert_eq!(record.leaf(LeafKind::Settlement).unwrap().script, builder().settlement_script(850_000));
+        assert_eq!(record.leaf(LeafKind::Timeout).unwrap().script, builder().timeout_script(850_000));
+
+        // 같은 키와 조건이면 같은 주소, 구매자가 다르면 다른 주소
+        assert_eq!(builder().build("CALL-1", &key(3), 850_000).unwrap().record, *record);
+        assert_ne!(builder().build("CALL-1", &key(4), 850_000).unwrap().record.address, record.address);
+    }
+
+    #[test]
+    fn test_script_path_witness_layout() {
+        let record = builder().build("CALL-1", &key(3), 850_000).unwrap().record;
+        let witness = record
+            .script_path_witness(LeafKind::Cooperative, vec![vec![0xb0; 64], vec![0xb1; 64]])
+            .unwrap();
+        let leaf = record.leaf(LeafKind::Cooperative).unwrap();
+        let items: Vec<&[u8]> = witness.iter().collect();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[2], leaf.script.as_bytes());
+        assert_eq!(hex::encode(items[3]), leaf.control_block);
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("btcfi-taproot-{}", std::process::id()));
+        let store = TaprootStore::open(&dir).unwrap();
+        let record = builder().build("PUT-7", &key(3), 850_000).unwrap().record;
+        store.put(&record).unwrap();
+        assert_eq!(store.get("PUT-7").unwrap(), Some(record));
+        assert_eq!(store.get("PUT-8").unwrap(), None);
+        assert!(store.get("../etc/passwd").is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
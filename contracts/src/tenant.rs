@@ -0,0 +1,405 @@
+//! 테넌트 네임스페이스 (화이트라벨 풀)
+//!
+//! 한 배포에서 여러 운영사가 각자의 옵션 데스크를 운영할 수 있도록 테넌트마다
+//! 별도의 풀 관리자(유동성, 수수료율, 리스크 한도, 이벤트 로그)와 API 키를 둡니다.
+//! 테넌트 API는 기본 풀 API와 같은 경로를 `/tenants/{id}` 아래에 제공하고,
+//! 요청마다 `X-Api-Key` 헤더가 해당 테넌트의 키인지 확인합니다. 기본 풀도 같은
+//! 미들웨어로 `--api-key-hash`에 등록된 키만 받습니다.
+
+use crate::admin_api::SharedManager;
+use crate::fees::{FeeSchedule, TreasuryControls};
+use crate::simple_contract::{RiskLimits, SimpleContractManager};
+use oracle_vm_common::crypto::constant_time_eq;
+use oracle_vm_common::TenantError;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+/// 테넌트 ID 최대 길이
+const MAX_TENANT_ID_LEN: usize = 32;
+
+/// API 키 해시 (SHA256 hex). 설정 파일에는 평문 키 대신 이 값을 저장
+pub fn hash_api_key(api_key: &str) -> String {
+    hex::encode(Sha256::digest(api_key.as_bytes()))
+}
+
+/// API 키가 허용 해시 중 하나인지 확인 (해시를 상수 시간으로 비교, 대소문자 무시)
+pub fn api_key_allowed(api_key_hashes: &[String], api_key: Option<&str>) -> bool {
+    let Some(hash) = api_key.map(hash_api_key) else {
+        return false;
+    };
+    api_key_hashes.iter().fold(false, |found, allowed| {
+        found | constant_time_eq(allowed.to_ascii_lowercase().as_bytes(), hash.as_bytes())
+    })
+}
+
+/// 테넌트 설정 (`--tenants` JSON 배열의 한 항목)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// URL 경로와 호가에 쓰는 ID ([a-z0-9_-])
+    pub id: String,
+    /// 표시 이름 (브랜드)
+    pub name: String,
+    /// 허용 API 키의 SHA256 (여러 개면 키 교체 중에도 중단 없음)
+    pub api_key_hashes: Vec<String>,
+    #[serde(default)]
+    pub fee_schedule: FeeSchedule,
+    #[serde(default)]
+    pub risk_limits: RiskLimits,
+    #[serde(default)]
+    pub treasury: TreasuryControls,
+}
+
+impl TenantConfig {
+    pub fn validate(&self) -> Result<(), TenantError> {
+        let invalid = |reason: String| TenantError::InvalidConfig(format!("{}: {}", self.id, reason));
+        if self.id.is_empty()
+            || self.id.len() > MAX_TENANT_ID_LEN
+            || !self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            return Err(invalid(format!(
+                "id must be 1-{} characters of [a-z0-9_-]",
+                MAX_TENANT_ID_LEN
+            )));
+        }
+        if self.api_key_hashes.is_empty() {
+            return Err(invalid("at least one API key hash is required".to_string()));
+        }
+        if let Some(hash) = self
+            .api_key_hashes
+            .iter()
+            .find(|hash| hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            return Err(invalid(format!("{:?} is not a SHA256 hex digest", hash)));
+        }
+        self.fee_schedule
+            .validate()
+            .map_err(|e| invalid(e.to_string()))
+    }
+
+    /// 설정 파일 읽기 (JSON 배열)
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Self>, TenantError> {
+        let path = path.as_ref();
+        let body = std::fs::read_to_string(path)
+            .map_err(|e| TenantError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&body)
+            .map_err(|e| TenantError::InvalidConfig(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// 등록된 테넌트 (설정 + 전용 풀 관리자)
+#[derive(Clone)]
+pub struct Tenant {
+    config: TenantConfig,
+    manager: SharedManager,
+}
+
+impl Tenant {
+    pub fn config(&self) -> &TenantConfig {
+        &self.config
+    }
+
+    pub fn manager(&self) -> &SharedManager {
+        &self.manager
+    }
+
+    /// 요청의 API 키가 이 테넌트 키인지 확인
+    pub fn authorize(&self, api_key: Option<&str>) -> Result<(), TenantError> {
+        if api_key_allowed(&self.config.api_key_hashes, api_key) {
+            Ok(())
+        } else {
+            Err(TenantError::Unauthorized(self.config.id.clone()))
+        }
+    }
+}
+
+/// 테넌트 목록 (ID 순)
+#[derive(Clone, Default)]
+pub struct TenantRegistry {
+    tenants: BTreeMap<String, Tenant>,
+}
+
+impl TenantRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 테넌트 등록, 설정의 수수료율/리스크 한도/출금 통제를 관리자에 적용
+    pub fn register(
+        &mut self,
+        config: TenantConfig,
+        mut manager: SimpleContractManager,
+    ) -> Result<&Tenant, TenantError> {
+        config.validate()?;
+        if self.tenants.contains_key(&config.id) {
+            return Err(TenantError::InvalidConfig(format!("{} registered twice", config.id)));
+        }
+        manager.set_tenant(&config.id);
+        manager
+            .set_fee_schedule(config.fee_schedule)
+            .map_err(|e| TenantError::InvalidConfig(e.to_string()))?;
+        manager.set_risk_limits(config.risk_limits);
+        manager.set_treasury_controls(config.treasury.clone());
+
+        let id = config.id.clone();
+        Ok(self.tenants.entry(id).or_insert(Tenant {
+            config,
+            manager: Arc::new(RwLock::new(manager)),
+        }))
+    }
+
+    pub fn get(&self, tenant_id: &str) -> Result<&Tenant, TenantError> {
+        self.tenants
+            .get(tenant_id)
+            .ok_or_else(|| TenantError::NotFound(tenant_id.to_string()))
+    }
+
+    /// 테넌트를 찾아 API 키 확인
+    pub fn authorize(&self, tenant_id: &str, api_key: Option<&str>) -> Result<&Tenant, TenantError> {
+        let tenant = self.get(tenant_id)?;
+        tenant.authorize(api_key)?;
+        Ok(tenant)
+    }
+
+    pub fn tenants(&self) -> impl Iterator<Item = &Tenant> {
+        self.tenants.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tenants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tenants.is_empty()
+    }
+}
+
+/// 테넌트 HTTP API (`/tenants/{id}/...`)
+pub mod api {
+    use super::{api_key_allowed, TenantRegistry};
+    use crate::admin_api::{self, AdminError, SharedManager};
+    use crate::{adl, audit, buy_back, claimable, early_exercise, fees, lp_book, referral, simple_contract};
+    use axum::{
+        extract::{Request, State},
+        http::StatusCode,
+        middleware::{self, Next},
+        response::{IntoResponse, Response},
+        Json, Router,
+    };
+    use oracle_vm_common::{ErrorClass, TenantError};
+    use std::sync::Arc;
+
+    /// API 키 헤더
+    pub const API_KEY_HEADER: &str = "x-api-key";
+
+    /// 풀 하나의 API (옵션 생성/조회, 관리, 리포트, 청구 잔고, 재무 계정, 추천, 되사기, 손실 분담, 감사 기록)
+    ///
+    /// 기본 풀은 루트에, 테넌트 풀은 `/tenants/{id}` 아래에 같은 경로로 붙습니다.
+    pub fn pool_router(manager: SharedManager) -> Router {
+        admin_api::router(manager.clone())
+            .merge(simple_contract::api::router(manager.clone()))
+            .merge(claimable::api::router(manager.clone()))
+            .merge(fees::api::router(manager.clone()))
+            .merge(referral::api::router(manager.clone()))
+            .merge(lp_book::api::router(manager.clone()))
+            .merge(buy_back::api::router(manager.clone()))
+            .merge(early_exercise::api::router(manager.clone()))
+            .merge(adl::api::router(manager.clone()))
+            .merge(audit::api::router(manager))
+    }
+
+    /// 풀 API 키 검사 상태 (풀 이름과 허용 키 해시)
+    struct ApiKeyGuard {
+        pool: String,
+        api_key_hashes: Vec<String>,
+    }
+
+    async fn require_api_key(State(guard): State<Arc<ApiKeyGuard>>, request: Request, next: Next) -> Response {
+        let api_key = request
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if !api_key_allowed(&guard.api_key_hashes, api_key) {
+            let e = TenantError::Unauthorized(guard.pool.clone());
+            let body = AdminError {
+                code: e.code().to_string(),
+                message: e.to_string(),
+                retryable: e.is_retryable(),
+            };
+            return (StatusCode::UNAUTHORIZED, Json(body)).into_response();
+        }
+        next.run(request).await
+    }
+
+    /// `X-Api-Key`가 허용 해시 중 하나여야 하는 라우터 (키가 없으면 모든 요청 거부)
+    ///
+    /// 풀 라우터와 따로 마운트하는 풀 경로(협의 해지 등)도 같은 키로 막습니다.
+    pub fn with_api_key(router: Router, pool: &str, api_key_hashes: Vec<String>) -> Router {
+        let guard = ApiKeyGuard {
+            pool: pool.to_string(),
+            api_key_hashes,
+        };
+        router.layer(middleware::from_fn_with_state(Arc::new(guard), require_api_key))
+    }
+
+    /// `X-Api-Key`가 허용 해시 중 하나여야 하는 풀 API
+    fn authenticated_pool_router(manager: SharedManager, pool: &str, api_key_hashes: Vec<String>) -> Router {
+        with_api_key(pool_router(manager), pool, api_key_hashes)
+    }
+
+    /// 기본 풀 API (루트 경로, 테넌트와 같은 `X-Api-Key` 검사)
+    pub fn default_pool_router(manager: SharedManager, api_key_hashes: Vec<String>) -> Router {
+        authenticated_pool_router(manager, "default", api_key_hashes)
+    }
+
+    /// 테넌트마다 풀 API를 `/tenants/{id}`에 붙인 라우터 생성
+    pub fn router(registry: &TenantRegistry) -> Router {
+        registry.tenants().fold(Router::new(), |app, tenant| {
+            let routes = authenticated_pool_router(
+                tenant.manager().clone(),
+                &tenant.config().id,
+                tenant.config().api_key_hashes.clone(),
+            );
+            app.nest(&format!("/tenants/{}", tenant.config().id), routes)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use oracle_vm_common::types::OptionType;
+    use tower::ServiceExt;
+
+    fn config(id: &str, api_key: &str) -> TenantConfig {
+        TenantConfig {
+            id: id.to_string(),
+            name: format!("{} desk", id),
+            api_key_hashes: vec![hash_api_key(api_key)],
+            fee_schedule: FeeSchedule {
+                protocol_fee_bps: 100,
+                settlement_fee_bps: 0,
+            },
+            risk_limits: RiskLimits::default(),
+            treasury: TreasuryControls::default(),
+        }
+    }
+
+    fn registry() -> TenantRegistry {
+        let mut registry = TenantRegistry::new();
+        for (id, key) in [("acme", "acme-key"), ("globex", "globex-key")] {
+            let mut manager = SimpleContractManager::new();
+            manager.add_liquidity(100_000_000).unwrap();
+            registry.register(config(id, key), manager).unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn test_register_applies_config_and_validates() {
+        let registry = registry();
+        let acme = registry.authorize("acme", Some("acme-key")).unwrap();
+        let manager = acme.manager().read().unwrap();
+        assert_eq!(manager.tenant_id(), Some("acme"));
+        assert_eq!(manager.fee_schedule().protocol_fee_bps, 100);
+
+        assert_eq!(
+            registry.authorize("acme", Some("globex-key")).err(),
+            Some(TenantError::Unauthorized("acme".to_string()))
+        );
+        assert!(registry.authorize("acme", None).is_err());
+        assert_eq!(
+            registry.authorize("initech", Some("acme-key")).err(),
+            Some(TenantError::NotFound("initech".to_string()))
+        );
+
+        let mut registry = registry.clone();
+        assert!(registry
+            .register(config("acme", "other"), SimpleContractManager::new())
+            .is_err());
+        assert!(registry
+            .register(config("Bad Id", "key"), SimpleContractManager::new())
+            .is_err());
+        let keyless = TenantConfig {
+            api_key_hashes: Vec::new(),
+            ..config("initech", "key")
+        };
+        assert!(registry.register(keyless, SimpleContractManager::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_routes_are_isolated_and_authenticated() {
+        let registry = registry();
+        registry
+            .get("acme")
+            .unwrap()
+            .manager()
+            .write()
+            .unwrap()
+            .create_option(
+                "CALL-ACME".to_string(),
+                OptionType::Call,
+                7_000_000,
+                1_000_000,
+                25_000,
+                800_000,
+                "user".to_string(),
+            )
+            .unwrap();
+        let app = api::router(&registry);
+
+        let get = |uri: &str, api_key: Option<&str>| {
+            let mut request = Request::builder().uri(uri);
+            if let Some(api_key) = api_key {
+                request = request.header(api::API_KEY_HEADER, api_key);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = get("/tenants/acme/admin/options", Some("acme-key")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let options: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(options[0]["option_id"], "CALL-ACME");
+
+        // 다른 테넌트 풀에는 보이지 않음
+        let response = get("/tenants/globex/admin/options/CALL-ACME", Some("globex-key")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // 다른 테넌트 키나 키 없는 요청은 거부
+        let response = get("/tenants/acme/admin/options", Some("globex-key")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = get("/tenants/acme/admin/treasury", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 기본 풀도 같은 키 검사 (해시는 대소문자 무시, 키가 없으면 모두 거부)
+        let mut manager = SimpleContractManager::new();
+        manager.add_liquidity(100_000_000).unwrap();
+        let shared: SharedManager = Arc::new(RwLock::new(manager));
+        let hashes = vec![hash_api_key("desk-key").to_uppercase()];
+        let cases = [
+            (hashes.clone(), None, StatusCode::UNAUTHORIZED),
+            (hashes.clone(), Some("acme-key"), StatusCode::UNAUTHORIZED),
+            (hashes, Some("desk-key"), StatusCode::OK),
+            (Vec::new(), Some("desk-key"), StatusCode::UNAUTHORIZED),
+        ];
+        for (hashes, api_key, expected) in cases {
+            let mut request = Request::builder().uri("/admin/pool");
+            if let Some(api_key) = api_key {
+                request = request.header(api::API_KEY_HEADER, api_key);
+            }
+            let response = api::default_pool_router(shared.clone(), hashes)
+                .oneshot(request.body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected);
+        }
+    }
+}
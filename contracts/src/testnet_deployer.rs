@@ -1,4 +1,5 @@
 use crate::bitcoin_option::BitcoinOption;
+use crate::taproot_address::{OptionTaprootRecord, TaprootStore};
 use oracle_vm_common::types::OptionType;
 use bitcoin::{
     Network, Transaction, TxIn, TxOut, OutPoint, Sequence, Witness,
@@ -6,21 +7,28 @@ use bitcoin::{
 };
 use bitcoin::secp256k1::{Secp256k1, SecretKey};
 use bitcoin::{CompressedPublicKey, PublicKey};
+use oracle_vm_common::NetworkProfile;
 use anyhow::Result;
 
 /// Bitcoin Testnet 배포 및 테스트 도구
 pub struct TestnetDeployer {
+    profile: NetworkProfile,
     network: Network,
     secp: Secp256k1<bitcoin::secp256k1::All>,
 }
 
 impl TestnetDeployer {
-    pub fn new() -> Self {
+    pub fn new(profile: NetworkProfile) -> Self {
         Self {
-            network: Network::Testnet,
+            profile,
+            network: profile.network,
             secp: Secp256k1::new(),
         }
     }
+
+    pub fn profile(&self) -> &NetworkProfile {
+        &self.profile
+    }
     
     /// 옵션 생성 트랜잭션 만들기
     /// 구매자가 프리미엄을 지불하고, 판매자가 담보를 잠그는 트랜잭션
@@ -187,6 +195,18 @@ impl TestnetDeployer {
         let (script, _) = option.create_taproot_script()?;
         Ok(Address::from_script(&script, self.network)?)
     }
+
+    /// 옵션 Taproot 출력을 만들고 리프 스크립트/control block을 저장소에 기록
+    pub fn register_taproot(
+        &self,
+        option: &BitcoinOption,
+        option_id: &str,
+        store: &TaprootStore,
+    ) -> Result<OptionTaprootRecord> {
+        let record = option.taproot(self.profile, option_id)?.record;
+        store.put(&record)?;
+        Ok(record)
+    }
 }
 
 #[cfg(test)]
@@ -198,7 +218,7 @@ mod tests {
     
     #[test]
     fn test_create_funding_tx() {
-        let deployer = TestnetDeployer::new();
+        let deployer = TestnetDeployer::new(NetworkProfile::TESTNET);
         let mut rng = thread_rng();
         
         // 테스트 키 생성
@@ -213,9 +233,9 @@ mod tests {
             option_type: OptionType::Call,
             strike_price: 50_000_000_000,
             expiry_block: 850_000,
-            buyer_pubkey: PublicKey::from_secret_key(&secp, &buyer_key),
-            seller_pubkey: PublicKey::from_secret_key(&secp, &seller_key),
-            verifier_pubkey: PublicKey::from_secret_key(&secp, &verifier_key),
+            buyer_pubkey: bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &buyer_key),
+            seller_pubkey: bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &seller_key),
+            verifier_pubkey: bitcoin::secp256k1::PublicKey::from_secret_key(&secp, &verifier_key),
             premium: 1_000_000,
             collateral: 10_000_000,
         };
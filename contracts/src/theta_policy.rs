@@ -0,0 +1,275 @@
+//! 풀 성과 기반 target theta 밴드
+//!
+//! 지금까지는 클라이언트가 요청마다 target theta를 정했습니다. 정책 엔진은
+//! 실현/내재 변동성 비율, 최근 지급률, 풀 사용률로 풀이 받아들일 theta 밴드를
+//! 정하고, 밴드 밖의 요청은 `ContractError::ThetaOutOfBand`로 거부합니다.
+//! 실현 변동성이 내재 변동성보다 높거나 지급이 많거나 풀이 붐비면 밴드를 더
+//! 큰 감가(더 비싼 프리미엄) 쪽으로 옮깁니다.
+
+use oracle_vm_common::ContractError;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+
+/// 정책 설정 (theta는 일일 감가, 음수)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThetaPolicyConfig {
+    /// 기준 밴드에서 감가가 가장 큰 쪽
+    pub base_min_theta: f64,
+    /// 기준 밴드에서 감가가 가장 작은 쪽
+    pub base_max_theta: f64,
+    /// 실현/내재 변동성 비율 1.0당 밴드 배율 변화
+    pub vol_weight: f64,
+    /// 지급률이 이 값을 넘으면 밴드를 올림 (지급액 / 프리미엄)
+    pub target_payout_ratio: f64,
+    /// 지급률 초과분 1.0당 밴드 배율 변화
+    pub payout_weight: f64,
+    /// 이 사용률을 넘으면 밴드를 올림 (0.0 ~ 1.0)
+    pub utilization_kink: f64,
+    /// 사용률 초과분 1.0당 밴드 배율 변화
+    pub utilization_weight: f64,
+    /// 밴드 배율 범위
+    pub min_scale: f64,
+    pub max_scale: f64,
+    /// 실현 변동성 계산에 쓰는 최대 가격 표본 수
+    pub price_window: usize,
+}
+
+impl Default for ThetaPolicyConfig {
+    fn default() -> Self {
+        Self {
+            base_min_theta: -0.03,
+            base_max_theta: -0.01,
+            vol_weight: 1.0,
+            target_payout_ratio: 0.6,
+            payout_weight: 1.5,
+            utilization_kink: 0.7,
+            utilization_weight: 2.0,
+            min_scale: 0.5,
+            max_scale: 3.0,
+            price_window: 288,
+        }
+    }
+}
+
+/// 밴드 계산 입력
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ThetaPolicyInputs {
+    /// 연환산 실현 변동성 (표본이 부족하면 None)
+    pub realized_vol: Option<f64>,
+    /// 활성 옵션의 평균 내재 변동성 (활성 옵션이 없으면 None)
+    pub implied_vol: Option<f64>,
+    /// 누적 지급액 / 누적 프리미엄
+    pub payout_ratio: f64,
+    /// 잠긴 유동성 / 총 유동성
+    pub utilization: f64,
+}
+
+/// 현재 허용 theta 밴드
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThetaBand {
+    /// 허용하는 가장 큰 감가
+    pub min_theta: f64,
+    /// 허용하는 가장 작은 감가
+    pub max_theta: f64,
+    /// 기준 밴드 대비 배율
+    pub scale: f64,
+    pub inputs: ThetaPolicyInputs,
+    pub updated_at: u64,
+}
+
+impl ThetaBand {
+    pub fn contains(&self, target_theta: f64) -> bool {
+        (self.min_theta..=self.max_theta).contains(&target_theta)
+    }
+}
+
+/// target theta 정책 엔진
+#[derive(Debug, Clone)]
+pub struct ThetaPolicyEngine {
+    config: ThetaPolicyConfig,
+    /// (timestamp, 가격) 표본
+    prices: VecDeque<(u64, f64)>,
+    band: ThetaBand,
+}
+
+impl ThetaPolicyEngine {
+    pub fn new(config: ThetaPolicyConfig) -> Self {
+        let band = ThetaBand {
+            min_theta: config.base_min_theta,
+            max_theta: config.base_max_theta,
+            scale: 1.0,
+            inputs: ThetaPolicyInputs::default(),
+            updated_at: 0,
+        };
+        Self {
+            config,
+            prices: VecDeque::new(),
+            band,
+        }
+    }
+
+    pub fn config(&self) -> &ThetaPolicyConfig {
+        &self.config
+    }
+
+    pub fn band(&self) -> &ThetaBand {
+        &self.band
+    }
+
+    /// 가격 표본 추가 (이전 표본보다 오래되었거나 가격이 0이면 무시)
+    pub fn observe_price(&mut self, timestamp: u64, price: f64) {
+        if price <= 0.0 || self.prices.back().is_some_and(|&(last, _)| timestamp <= last) {
+            return;
+        }
+        self.prices.push_back((timestamp, price));
+        while self.prices.len() > self.config.price_window {
+            self.prices.pop_front();
+        }
+    }
+
+    /// 가격 표본의 로그 수익률로 계산한 연환산 실현 변동성
+    pub fn realized_vol(&self) -> Option<f64> {
+        if self.prices.len() < 3 {
+            return None;
+        }
+        let (variance, elapsed) = self
+            .prices
+            .iter()
+            .zip(self.prices.iter().skip(1))
+            .fold((0.0, 0.0), |(variance, elapsed), (&(t0, p0), &(t1, p1))| {
+                let ret = (p1 / p0).ln();
+                (variance + ret * ret, elapsed + (t1 - t0) as f64 / SECONDS_PER_YEAR)
+            });
+        Some((variance / elapsed).sqrt())
+    }
+
+    /// 입력에 대한 밴드 배율
+    pub fn scale_for(&self, inputs: &ThetaPolicyInputs) -> f64 {
+        let config = &self.config;
+        let vol_term = match (inputs.realized_vol, inputs.implied_vol) {
+            (Some(realized), Some(implied)) if implied > 0.0 => realized / implied - 1.0,
+            _ => 0.0,
+        };
+        let payout_term = inputs.payout_ratio - config.target_payout_ratio;
+        let utilization_term = (inputs.utilization - config.utilization_kink).max(0.0);
+        (1.0 + config.vol_weight * vol_term
+            + config.payout_weight * payout_term
+            + config.utilization_weight * utilization_term)
+            .clamp(config.min_scale, config.max_scale)
+    }
+
+    /// 풀 상태로 밴드 재계산 (실현 변동성은 관측한 가격 표본에서)
+    pub fn update(
+        &mut self,
+        implied_vol: Option<f64>,
+        payout_ratio: f64,
+        utilization: f64,
+        now: u64,
+    ) -> ThetaBand {
+        let inputs = ThetaPolicyInputs {
+            realized_vol: self.realized_vol(),
+            implied_vol,
+            payout_ratio,
+            utilization,
+        };
+        let scale = self.scale_for(&inputs);
+        self.band = ThetaBand {
+            min_theta: self.config.base_min_theta * scale,
+            max_theta: self.config.base_max_theta * scale,
+            scale,
+            inputs,
+            updated_at: now,
+        };
+        self.band
+    }
+
+    /// 클라이언트가 요청한 target theta 검사
+    pub fn check(&self, target_theta: f64) -> Result<(), ContractError> {
+        if self.band.contains(target_theta) {
+            return Ok(());
+        }
+        Err(ContractError::ThetaOutOfBand {
+            requested: target_theta,
+            min: self.band.min_theta,
+            max: self.band.max_theta,
+        })
+    }
+}
+
+/// theta 밴드 HTTP API (`/theta/band`)
+pub mod api {
+    use crate::buyer_only_option::BuyerOnlyOptionManager;
+    use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+    use std::sync::{Arc, Mutex};
+
+    async fn get_band(State(manager): State<Arc<Mutex<BuyerOnlyOptionManager>>>) -> impl IntoResponse {
+        let Ok(manager) = manager.lock() else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        match manager.theta_band() {
+            Some(band) => Json(band).into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    /// `/theta/band` 라우터 생성
+    pub fn router(manager: Arc<Mutex<BuyerOnlyOptionManager>>) -> Router {
+        Router::new().route("/theta/band", get(get_band)).with_state(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_band_widens_with_realized_vol_payouts_and_utilization() {
+        let mut engine = ThetaPolicyEngine::new(ThetaPolicyConfig::default());
+        let calm = engine.update(Some(0.8), 0.6, 0.5, 1);
+        assert_eq!(calm.scale, 1.0);
+        assert!(calm.contains(-0.02));
+        assert!(engine.check(-0.005).is_err());
+
+        // 실현 변동성 > 내재 변동성, 지급 증가, 사용률 kink 초과 → 더 큰 감가 요구
+        let stressed = ThetaPolicyInputs {
+            realized_vol: Some(1.2),
+            implied_vol: Some(0.8),
+            payout_ratio: 0.8,
+            utilization: 0.9,
+        };
+        assert!((engine.scale_for(&stressed) - 2.2).abs() < 1e-9);
+
+        // 실현 변동성이 낮고 지급이 적으면 밴드를 낮추되 하한 배율 유지
+        let quiet = ThetaPolicyInputs {
+            realized_vol: Some(0.2),
+            implied_vol: Some(0.8),
+            payout_ratio: 0.0,
+            utilization: 0.1,
+        };
+        assert_eq!(engine.scale_for(&quiet), 0.5);
+    }
+
+    #[test]
+    fn test_realized_vol_from_price_samples() {
+        let mut engine = ThetaPolicyEngine::new(ThetaPolicyConfig::default());
+        engine.observe_price(0, 70_000.0);
+        engine.observe_price(3_600, 70_700.0);
+        assert!(engine.realized_vol().is_none());
+        // 오래된 표본은 무시
+        engine.observe_price(1_800, 1.0);
+        engine.observe_price(7_200, 70_000.0);
+        let vol = engine.realized_vol().unwrap();
+        // 시간당 ~1% 움직임 → 연환산 ~93%
+        assert!(vol > 0.8 && vol < 1.1, "vol {}", vol);
+
+        let band = engine.update(Some(vol), 0.6, 0.0, 7_200);
+        assert!((band.scale - 1.0).abs() < 1e-9);
+        assert!(matches!(
+            engine.check(-0.5),
+            Err(ContractError::ThetaOutOfBand { .. })
+        ));
+    }
+}
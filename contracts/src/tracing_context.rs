@@ -0,0 +1,96 @@
+//! HTTP 요청 상관관계 ID 전파
+//!
+//! 들어온 요청의 `x-correlation-id` 헤더(없거나 잘못되면 새로 생성)로
+//! `http.request` span을 열고, 처리하는 동안 `CorrelationId::current()`로
+//! 꺼낼 수 있게 합니다. 응답에도 같은 헤더를 돌려주며, 처리 중 나가는 HTTP
+//! 호출(웹훅 등)은 `outbound`로 같은 ID를 붙입니다. calculation API도 같은
+//! 미들웨어를 씁니다.
+
+use axum::{
+    extract::Request,
+    http::HeaderValue,
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use oracle_vm_common::{CorrelationId, CORRELATION_ID_HEADER};
+use tracing::{info_span, Instrument};
+
+/// 상관관계 ID span을 여는 미들웨어
+pub async fn correlate(request: Request, next: Next) -> Response {
+    let id = CorrelationId::from_header(
+        request
+            .headers()
+            .get(CORRELATION_ID_HEADER)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let span = info_span!(
+        "http.request",
+        correlation_id = %id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let header = HeaderValue::from_str(id.as_str()).ok();
+    let mut response = id.scope(next.run(request)).instrument(span).await;
+    if let Some(header) = header {
+        response.headers_mut().insert(CORRELATION_ID_HEADER, header);
+    }
+    response
+}
+
+/// 라우터 전체에 상관관계 미들웨어 적용
+pub fn with_correlation(router: Router) -> Router {
+    router.layer(middleware::from_fn(correlate))
+}
+
+/// 나가는 요청에 현재 상관관계 ID 부착 (요청 밖이면 새 ID)
+pub fn outbound(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    request.header(CORRELATION_ID_HEADER, CorrelationId::current_or_generate().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_correlation_id_is_propagated_and_echoed() {
+        let app = with_correlation(Router::new().route(
+            "/id",
+            get(|| async { CorrelationId::current().map(|id| id.to_string()).unwrap_or_default() }),
+        ));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/id")
+                    .header(CORRELATION_ID_HEADER, "quote-42")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CORRELATION_ID_HEADER], "quote-42");
+        let body = axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+        assert_eq!(&body[..], b"quote-42");
+
+        // 헤더가 없거나 잘못된 값이면 새 ID를 만들어 돌려줌
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/id")
+                    .header(CORRELATION_ID_HEADER, "bad value")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let echoed = response.headers()[CORRELATION_ID_HEADER].to_str().unwrap().to_string();
+        assert_eq!(echoed.len(), 32);
+        let body = axum::body::to_bytes(response.into_body(), 1024).await.unwrap();
+        assert_eq!(body, echoed.as_bytes());
+    }
+}
@@ -0,0 +1,837 @@
+//! 옵션 생애주기 웹훅
+//!
+//! 외부 시스템이 URL을 등록하면 풀 이벤트를 웹훅 이벤트로 변환해 서명된 JSON으로
+//! 전달합니다. 실패한 전달은 지수 백오프로 재시도하고, 모든 전달 시도는 전달
+//! 로그로 남겨 `/webhooks/deliveries`로 조회할 수 있습니다.
+//!
+//! 서명: `X-BTCFi-Signature` = hex(HMAC-SHA256(secret, "{timestamp}.{body}")),
+//! timestamp는 `X-BTCFi-Timestamp` 헤더로 함께 보냅니다.
+//!
+//! 구독 관리는 운영자 토큰이 필요하고, 사설/루프백/링크 로컬 주소로는 등록도
+//! 전송도 하지 않습니다 (전송 직전에 호스트를 다시 해석해 확인, 리다이렉트 미추적).
+
+use crate::event_store::{PoolEvent, PoolEventKind};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+pub const SIGNATURE_HEADER: &str = "X-BTCFi-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-BTCFi-Timestamp";
+
+/// 웹훅 이벤트 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    OptionCreated,
+    PremiumPaid,
+    OptionExpired,
+    SettlementExecuted,
+    OptionExercised,
+    BarrierTouched,
+    PayoutHaircut,
+    OptionBoughtBack,
+    OptionClosedCooperatively,
+    OptionRolled,
+    AnchorConfirmed,
+    CompetingSpend,
+}
+
+/// 구독자에게 보내는 이벤트 본문
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub event_id: String,
+    pub kind: WebhookEventKind,
+    pub timestamp: u64,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    /// 풀 이벤트에 대응하는 웹훅 이벤트 (옵션 생성은 OptionCreated + PremiumPaid)
+    pub fn from_pool_event(event: &PoolEvent) -> Vec<Self> {
+        let make = |suffix: &str, kind, data| Self {
+            event_id: format!("pool-{}-{}", event.sequence, suffix),
+            kind,
+            timestamp: event.timestamp,
+            data,
+        };
+
+        match &event.kind {
+            PoolEventKind::OptionCreated {
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                premium,
+                collateral,
+                user_id,
+            } => vec![
+                make(
+                    "created",
+                    WebhookEventKind::OptionCreated,
+                    json!({
+                        "option_id": option_id,
+                        "option_type": option_type,
+                        "strike_price": strike_price,
+                        "quantity": quantity,
+                        "collateral": collateral,
+                        "user_id": user_id,
+                    }),
+                ),
+                make(
+                    "premium",
+                    WebhookEventKind::PremiumPaid,
+                    json!({ "option_id": option_id, "premium": premium, "user_id": user_id }),
+                ),
+            ],
+            PoolEventKind::OptionSettled {
+                option_id,
+                spot_price,
+                payout,
+                dust,
+            } => vec![make(
+                "settled",
+                WebhookEventKind::SettlementExecuted,
+                json!({
+                    "option_id": option_id,
+                    "spot_price": spot_price,
+                    "payout": payout,
+                    "dust": dust,
+                }),
+            )],
+            PoolEventKind::OptionExpired { option_id, reason } => vec![make(
+                "expired",
+                WebhookEventKind::OptionExpired,
+                json!({ "option_id": option_id, "reason": reason }),
+            )],
+            PoolEventKind::OptionBoughtBack {
+                option_id,
+                quote_id,
+                spot_price,
+                amount,
+            } => vec![make(
+                "bought-back",
+                WebhookEventKind::OptionBoughtBack,
+                json!({
+                    "option_id": option_id,
+                    "quote_id": quote_id,
+                    "spot_price": spot_price,
+                    "amount": amount,
+                }),
+            )],
+            PoolEventKind::OptionClosedCooperatively {
+                option_id,
+                quote_id,
+                spot_price,
+                holder_amount,
+                txid,
+            } => vec![make(
+                "closed-cooperatively",
+                WebhookEventKind::OptionClosedCooperatively,
+                json!({
+                    "option_id": option_id,
+                    "quote_id": quote_id,
+                    "spot_price": spot_price,
+                    "holder_amount": holder_amount,
+                    "txid": txid,
+                }),
+            )],
+            PoolEventKind::BarrierTouched {
+                option_id,
+                kind,
+                level,
+                spot_price,
+                observed_at,
+            } => vec![make(
+                "barrier-touched",
+                WebhookEventKind::BarrierTouched,
+                json!({
+                    "option_id": option_id,
+                    "kind": kind,
+                    "level": level,
+                    "spot_price": spot_price,
+                    "observed_at": observed_at,
+                }),
+            )],
+            PoolEventKind::PayoutHaircut {
+                option_id,
+                user_id,
+                owed,
+                haircut,
+                shortfall,
+                rank,
+            } => vec![make(
+                "haircut",
+                WebhookEventKind::PayoutHaircut,
+                json!({
+                    "option_id": option_id,
+                    "user_id": user_id,
+                    "owed": owed,
+                    "haircut": haircut,
+                    "shortfall": shortfall,
+                    "rank": rank,
+                }),
+            )],
+            PoolEventKind::OptionExercised {
+                option_id,
+                user_id,
+                spot_price,
+            } => vec![make(
+                "exercised",
+                WebhookEventKind::OptionExercised,
+                json!({ "option_id": option_id, "user_id": user_id, "spot_price": spot_price }),
+            )],
+            PoolEventKind::OptionRolled {
+                from_option_id,
+                buy_back_amount,
+                option_id,
+                option_type,
+                strike_price,
+                quantity,
+                premium,
+                collateral,
+                user_id,
+                ..
+            } => vec![make(
+                "rolled",
+                WebhookEventKind::OptionRolled,
+                json!({
+                    "from_option_id": from_option_id,
+                    "buy_back_amount": buy_back_amount,
+                    "option_id": option_id,
+                    "option_type": option_type,
+                    "strike_price": strike_price,
+                    "quantity": quantity,
+                    "premium": premium,
+                    "collateral": collateral,
+                    "user_id": user_id,
+                }),
+            )],
+            PoolEventKind::AnchorConfirmed {
+                option_id,
+                txid,
+                confirmations,
+            } => vec![make(
+                "anchor-confirmed",
+                WebhookEventKind::AnchorConfirmed,
+                json!({ "txid": txid, "reference": option_id, "confirmations": confirmations }),
+            )],
+            PoolEventKind::CompetingSpendDetected {
+                option_id,
+                outpoint,
+                txid,
+            } => vec![make(
+                "competing-spend",
+                WebhookEventKind::CompetingSpend,
+                json!({ "option_id": option_id, "outpoint": outpoint, "txid": txid }),
+            )],
+            PoolEventKind::LiquidityAdded { .. }
+            | PoolEventKind::LiquidityRemoved { .. }
+            | PoolEventKind::FeeCharged { .. }
+            | PoolEventKind::ReferralAttributed { .. }
+            | PoolEventKind::EligibilityDenied { .. }
+            | PoolEventKind::ExitClaimIssued { .. }
+            | PoolEventKind::ExitClaimTransferred { .. }
+            | PoolEventKind::TreasuryWithdrawn { .. }
+            | PoolEventKind::ReserveMoved { .. }
+            | PoolEventKind::FundingPrepaid { .. }
+            | PoolEventKind::FundingAccrued { .. }
+            | PoolEventKind::ClaimCredited { .. }
+            | PoolEventKind::ClaimWithdrawn { .. }
+            | PoolEventKind::ClaimPaid { .. }
+            | PoolEventKind::AccountKeyBound { .. }
+            | PoolEventKind::QuoteKeyRotated { .. }
+            | PoolEventKind::BeneficiaryRegistered { .. }
+            | PoolEventKind::BeneficiaryChanged { .. }
+            | PoolEventKind::AnchorBroadcast { .. }
+            | PoolEventKind::AnchorReplaced { .. }
+            | PoolEventKind::SettlementTxBroadcast { .. }
+            | PoolEventKind::SettlementTxConfirmed { .. }
+            | PoolEventKind::UtxoWatched { .. }
+            | PoolEventKind::ChallengeArmed { .. } => Vec::new(),
+        }
+    }
+
+    /// 앵커 트랜잭션 확정 알림
+    pub fn anchor_confirmed(txid: &str, reference: &str, confirmations: u32, timestamp: u64) -> Self {
+        Self {
+            event_id: format!("anchor-{}", txid),
+            kind: WebhookEventKind::AnchorConfirmed,
+            timestamp,
+            data: json!({ "txid": txid, "reference": reference, "confirmations": confirmations }),
+        }
+    }
+}
+
+/// 등록된 웹훅 구독
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// 받을 이벤트 종류 (비어 있으면 전체)
+    pub kinds: Vec<WebhookEventKind>,
+}
+
+impl WebhookSubscription {
+    fn wants(&self, kind: WebhookEventKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// 본문 서명 (hex HMAC-SHA256)
+pub fn sign_payload(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 수신 측 서명 검증
+pub fn verify_signature(secret: &str, timestamp: u64, body: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// 외부로 보내도 되는 주소인지 (사설, 루프백, 링크 로컬, CGNAT, 미지정 주소 제외)
+pub fn is_public_addr(addr: IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_addr(IpAddr::V4(v4)),
+            None => {
+                !(v6.is_loopback() || v6.is_unspecified() || v6.is_unique_local() || v6.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// 구독 URL 확인 (http(s), 호스트가 내부 주소나 localhost가 아니어야 함)
+pub fn check_url(url: &str) -> Result<reqwest::Url, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid url: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("url must be http(s)".to_string());
+    }
+    let host = parsed.host_str().ok_or("url has no host")?.trim_matches(['[', ']']);
+    let internal = match host.parse::<IpAddr>() {
+        Ok(addr) => !is_public_addr(addr),
+        Err(_) => {
+            let domain = host.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+    };
+    if internal {
+        return Err(format!("internal host not allowed: {}", host));
+    }
+    Ok(parsed)
+}
+
+/// 전송할 HTTP 요청
+#[derive(Debug, Clone)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: Vec<u8>,
+}
+
+/// 웹훅 전송 방식 (테스트에서는 mock 사용)
+#[async_trait]
+pub trait WebhookTransport: Send + Sync {
+    /// 요청 전송 후 HTTP 상태 코드 반환
+    async fn post(&self, request: &WebhookRequest) -> Result<u16, String>;
+}
+
+/// reqwest 기반 HTTP 전송
+pub struct HttpTransport {
+    timeout: std::time::Duration,
+}
+
+impl HttpTransport {
+    pub fn new(timeout: std::time::Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[async_trait]
+impl WebhookTransport for HttpTransport {
+    async fn post(&self, request: &WebhookRequest) -> Result<u16, String> {
+        // 등록 이후 DNS가 내부 주소를 가리키게 바뀌었을 수 있으므로 전송마다 확인하고,
+        // 다시 해석하지 않도록 확인한 주소로만 연결 (프록시도 다시 해석하므로 사용 안 함)
+        let url = check_url(&request.url)?;
+        let host = url.host_str().ok_or("url has no host")?.trim_matches(['[', ']']);
+        let port = url.port_or_known_default().ok_or("url has no port")?;
+        let addrs: Vec<_> = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| e.to_string())?
+            .collect();
+        if let Some(addr) = addrs.iter().find(|addr| !is_public_addr(addr.ip())) {
+            return Err(format!("{} resolves to internal address {}", host, addr.ip()));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .resolve_to_addrs(host, &addrs)
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let mut builder = crate::tracing_context::outbound(client.post(&request.url))
+            .header("Content-Type", "application/json")
+            .body(request.body.clone());
+        for (name, value) in &request.headers {
+            builder = builder.header(*name, value);
+        }
+        let response = builder.send().await.map_err(|e| e.to_string())?;
+        Ok(response.status().as_u16())
+    }
+}
+
+/// 재시도 정책 (지수 백오프)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_secs: u64,
+    pub max_delay_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 6,
+            base_delay_secs: 5,
+            max_delay_secs: 3_600,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempts`번 실패한 뒤 다음 시도까지 대기 시간 (초)
+    pub fn delay_after(&self, attempts: u32) -> u64 {
+        let exponent = attempts.saturating_sub(1).min(32);
+        self.base_delay_secs
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_delay_secs)
+    }
+}
+
+/// 전달 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    /// 최대 시도 횟수 초과
+    Failed,
+}
+
+/// 전달 로그 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    pub delivery_id: u64,
+    pub subscription_id: String,
+    pub event_id: String,
+    pub kind: WebhookEventKind,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at: u64,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+}
+
+/// 웹훅 구독/전달 관리
+#[derive(Default)]
+pub struct WebhookDispatcher {
+    subscriptions: Vec<WebhookSubscription>,
+    deliveries: Vec<DeliveryRecord>,
+    /// event_id → 직렬화된 이벤트 본문
+    payloads: HashMap<String, Vec<u8>>,
+    policy: RetryPolicy,
+    next_subscription: u64,
+}
+
+impl WebhookDispatcher {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    /// 구독 등록 후 ID 반환
+    pub fn register(&mut self, url: String, secret: String, kinds: Vec<WebhookEventKind>) -> String {
+        self.next_subscription += 1;
+        let id = format!("wh-{}", self.next_subscription);
+        self.subscriptions.push(WebhookSubscription {
+            id: id.clone(),
+            url,
+            secret,
+            kinds,
+        });
+        id
+    }
+
+    pub fn unregister(&mut self, id: &str) -> bool {
+        let before = self.subscriptions.len();
+        self.subscriptions.retain(|subscription| subscription.id != id);
+        self.subscriptions.len() != before
+    }
+
+    pub fn subscriptions(&self) -> &[WebhookSubscription] {
+        &self.subscriptions
+    }
+
+    /// 관심 있는 구독마다 전달 예약
+    pub fn enqueue(&mut self, event: &WebhookEvent, now: u64) {
+        let body = serde_json::to_vec(event).expect("WebhookEvent serializes");
+        for subscription in self.subscriptions.iter().filter(|s| s.wants(event.kind)) {
+            self.deliveries.push(DeliveryRecord {
+                delivery_id: self.deliveries.len() as u64 + 1,
+                subscription_id: subscription.id.clone(),
+                event_id: event.event_id.clone(),
+                kind: event.kind,
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_status_code: None,
+                last_error: None,
+            });
+        }
+        self.payloads.insert(event.event_id.clone(), body);
+    }
+
+    /// 시각이 된 전달을 시도하고 성공한 건수 반환
+    pub async fn deliver_due(&mut self, transport: &dyn WebhookTransport, now: u64) -> usize {
+        let mut delivered = 0;
+        for record in self
+            .deliveries
+            .iter_mut()
+            .filter(|record| record.status == DeliveryStatus::Pending && record.next_attempt_at <= now)
+        {
+            let Some(subscription) = self
+                .subscriptions
+                .iter()
+                .find(|subscription| subscription.id == record.subscription_id)
+            else {
+                record.status = DeliveryStatus::Failed;
+                record.last_error = Some("subscription removed".to_string());
+                continue;
+            };
+            let body = self.payloads[&record.event_id].clone();
+            let request = WebhookRequest {
+                url: subscription.url.clone(),
+                headers: vec![
+                    (SIGNATURE_HEADER, sign_payload(&subscription.secret, now, &body)),
+                    (TIMESTAMP_HEADER, now.to_string()),
+                ],
+                body,
+            };
+
+            record.attempts += 1;
+            let result = transport.post(&request).await;
+            let (ok, code, error) = match result {
+                Ok(code) => ((200..300).contains(&code), Some(code), None),
+                Err(e) => (false, None, Some(e)),
+            };
+            record.last_status_code = code;
+            record.last_error = error;
+
+            if ok {
+                record.status = DeliveryStatus::Delivered;
+                delivered += 1;
+            } else if record.attempts >= self.policy.max_attempts {
+                record.status = DeliveryStatus::Failed;
+            } else {
+                record.next_attempt_at = now + self.policy.delay_after(record.attempts);
+            }
+        }
+        delivered
+    }
+
+    /// 전달 로그 (구독 ID 지정 시 해당 구독만)
+    pub fn deliveries(&self, subscription_id: Option<&str>) -> Vec<&DeliveryRecord> {
+        self.deliveries
+            .iter()
+            .filter(|record| subscription_id.is_none_or(|id| record.subscription_id == id))
+            .collect()
+    }
+}
+
+/// 웹훅 관리 HTTP API
+///
+/// - `POST /webhooks` 구독 등록 (`{url, secret, kinds}`)
+/// - `GET /webhooks` 구독 목록
+/// - `DELETE /webhooks/{id}` 구독 해지
+/// - `GET /webhooks/deliveries?subscription=` 전달 로그
+pub mod api {
+    use super::{DeliveryRecord, WebhookDispatcher, WebhookEventKind, WebhookSubscription};
+    use axum::{
+        extract::{Path, Query, State},
+        http::StatusCode,
+        routing::{delete, get},
+        Json, Router,
+    };
+    use serde::Deserialize;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use utoipa::{IntoParams, ToSchema};
+
+    pub type SharedDispatcher = Arc<Mutex<WebhookDispatcher>>;
+
+    #[derive(Debug, Deserialize, ToSchema)]
+    pub struct RegisterRequest {
+        pub url: String,
+        pub secret: String,
+        #[serde(default)]
+        #[schema(value_type = Vec<String>)]
+        pub kinds: Vec<WebhookEventKind>,
+    }
+
+    #[derive(Debug, Deserialize, IntoParams)]
+    pub struct DeliveryQuery {
+        pub subscription: Option<String>,
+    }
+
+    #[utoipa::path(
+        post,
+        path = "/webhooks",
+        tag = "webhooks",
+        request_body = RegisterRequest,
+        responses((status = 201, body = Object),
+            (status = 400, description = "외부 http(s) URL과 비밀값 필요"))
+    )]
+    async fn register(
+        State(dispatcher): State<SharedDispatcher>,
+        Json(request): Json<RegisterRequest>,
+    ) -> Result<(StatusCode, Json<serde_json::Value>), StatusCode> {
+        if super::check_url(&request.url).is_err() || request.secret.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let id = dispatcher
+            .lock()
+            .await
+            .register(request.url, request.secret, request.kinds);
+        Ok((StatusCode::CREATED, Json(serde_json::json!({ "id": id }))))
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/webhooks",
+        tag = "webhooks",
+        responses((status = 200, body = [Object]))
+    )]
+    async fn list(State(dispatcher): State<SharedDispatcher>) -> Json<Vec<WebhookSubscription>> {
+        Json(dispatcher.lock().await.subscriptions().to_vec())
+    }
+
+    #[utoipa::path(
+        delete,
+        path = "/webhooks/{id}",
+        tag = "webhooks",
+        params(("id" = String, Path, description = "구독 ID")),
+        responses((status = 204),
+            (status = 404))
+    )]
+    async fn unregister(
+        Path(id): Path<String>,
+        State(dispatcher): State<SharedDispatcher>,
+    ) -> StatusCode {
+        if dispatcher.lock().await.unregister(&id) {
+            StatusCode::NO_CONTENT
+        } else {
+            StatusCode::NOT_FOUND
+        }
+    }
+
+    #[utoipa::path(
+        get,
+        path = "/webhooks/deliveries",
+        tag = "webhooks",
+        params(DeliveryQuery),
+        responses((status = 200, body = [Object]))
+    )]
+    async fn deliveries(
+        Query(query): Query<DeliveryQuery>,
+        State(dispatcher): State<SharedDispatcher>,
+    ) -> Json<Vec<DeliveryRecord>> {
+        let dispatcher = dispatcher.lock().await;
+        Json(
+            dispatcher
+                .deliveries(query.subscription.as_deref())
+                .into_iter()
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// `/webhooks` 라우터 생성
+    pub fn router(dispatcher: SharedDispatcher) -> Router {
+        Router::new()
+            .route("/webhooks", get(list).post(register))
+            .route("/webhooks/deliveries", get(deliveries))
+            .route("/webhooks/:id", delete(unregister))
+            .with_state(dispatcher)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::types::OptionType;
+    use std::sync::Mutex;
+
+    /// 응답 코드를 차례로 돌려주는 mock 전송
+    struct ScriptedTransport {
+        responses: Mutex<Vec<Result<u16, String>>>,
+        sent: Mutex<Vec<WebhookRequest>>,
+    }
+
+    #[async_trait]
+    impl WebhookTransport for ScriptedTransport {
+        async fn post(&self, request: &WebhookRequest) -> Result<u16, String> {
+            self.sent.lock().unwrap().push(request.clone());
+            self.responses.lock().unwrap().remove(0)
+        }
+    }
+
+    fn created_event() -> PoolEvent {
+        PoolEvent {
+            sequence: 3,
+            timestamp: 1_000,
+            kind: PoolEventKind::OptionCreated {
+                option_id: "CALL-1".to_string(),
+                option_type: OptionType::Call,
+                strike_price: 7_000_000,
+                quantity: 10_000_000,
+                premium: 250_000,
+                collateral: 10_000_000,
+                user_id: "user".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_pool_event_mapping() {
+        let events = WebhookEvent::from_pool_event(&created_event());
+        let kinds: Vec<_> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![WebhookEventKind::OptionCreated, WebhookEventKind::PremiumPaid]);
+        assert_eq!(events[1].data["premium"], 250_000);
+
+        let liquidity = PoolEvent {
+            sequence: 0,
+            timestamp: 1,
+            kind: PoolEventKind::LiquidityAdded { provider_id: None, amount: 1 },
+        };
+        assert!(WebhookEvent::from_pool_event(&liquidity).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_signed_delivery_with_backoff() {
+        let mut dispatcher = WebhookDispatcher::new(RetryPolicy {
+            max_attempts: 3,
+            base_delay_secs: 10,
+            max_delay_secs: 60,
+        });
+        let all = dispatcher.register("https://a.example/hook".to_string(), "s3cret".to_string(), vec![]);
+        let settled_only = dispatcher.register(
+            "https://b.example/hook".to_string(),
+            "other".to_string(),
+            vec![WebhookEventKind::SettlementExecuted],
+        );
+        for event in WebhookEvent::from_pool_event(&created_event()) {
+            dispatcher.enqueue(&event, 1_000);
+        }
+        assert_eq!(dispatcher.deliveries(Some(&settled_only)).len(), 0);
+
+        let transport = ScriptedTransport {
+            responses: Mutex::new(vec![Err("connection refused".to_string()), Ok(200), Ok(200)]),
+            sent: Mutex::new(Vec::new()),
+        };
+
+        // 첫 시도: 하나 실패(10초 뒤 재시도), 하나 성공
+        assert_eq!(dispatcher.deliver_due(&transport, 1_000).await, 1);
+        let log = dispatcher.deliveries(Some(&all));
+        assert_eq!(log[0].status, DeliveryStatus::Pending);
+        assert_eq!(log[0].next_attempt_at, 1_010);
+        assert_eq!(log[1].status, DeliveryStatus::Delivered);
+
+        assert_eq!(dispatcher.deliver_due(&transport, 1_005).await, 0);
+        assert_eq!(dispatcher.deliver_due(&transport, 1_010).await, 1);
+        assert_eq!(dispatcher.deliveries(None)[0].attempts, 2);
+
+        // 수신 측에서 서명 검증
+        let sent = transport.sent.lock().unwrap();
+        let request = &sent[2];
+        let signature = &request.headers.iter().find(|(name, _)| *name == SIGNATURE_HEADER).unwrap().1;
+        assert!(verify_signature("s3cret", 1_010, &request.body, signature));
+        assert!(!verify_signature("wrong", 1_010, &request.body, signature));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let mut dispatcher = WebhookDispatcher::new(RetryPolicy {
+            max_attempts: 2,
+            base_delay_secs: 1,
+            max_delay_secs: 1,
+        });
+        dispatcher.register("https://a.example/hook".to_string(), "s".to_string(), vec![]);
+        dispatcher.enqueue(&WebhookEvent::anchor_confirmed("ab", "OPT-1", 6, 0), 0);
+
+        let transport = ScriptedTransport {
+            responses: Mutex::new(vec![Ok(500), Ok(503)]),
+            sent: Mutex::new(Vec::new()),
+        };
+        dispatcher.deliver_due(&transport, 0).await;
+        dispatcher.deliver_due(&transport, 1).await;
+
+        let record = dispatcher.deliveries(None)[0];
+        assert_eq!(record.status, DeliveryStatus::Failed);
+        assert_eq!(record.last_status_code, Some(503));
+    }
+
+    #[test]
+    fn test_rejects_internal_urls() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://10.0.0.5:8080/hook",
+            "https://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://localhost:3000/hook",
+            "http://api.localhost./hook",
+            "ftp://a.example/hook",
+            "not a url",
+        ] {
+            assert!(check_url(url).is_err(), "{}", url);
+        }
+        assert!(check_url("https://a.example/hook").is_ok());
+        assert!(check_url("http://8.8.8.8/hook").is_ok());
+        assert!(check_url("https://[2001:4860::8888]/hook").is_ok());
+    }
+}
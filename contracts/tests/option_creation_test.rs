@@ -1,5 +1,5 @@
 use anyhow::Result;
-use btcfi_contracts::{OptionType, OptionStatus, SimpleOption};
+use btcfi_contracts::{OptionType, OptionStatus, OptionTerms, SimpleOption};
 
 /// 옵션 생성 파라미터
 #[derive(Debug, Clone)]
@@ -97,6 +97,7 @@ pub fn create_option(params: CreateOptionParams, option_id: String) -> Result<Si
         expiry_height: params.expiry_height,
         status: OptionStatus::Active,
         user_id: params.user_id,
+        terms: OptionTerms::default(),
     })
 }
 
@@ -0,0 +1,163 @@
+//! 풀 회계 불변식 property 테스트
+//!
+//! add_liquidity / create_option / settle_option / remove_liquidity를 임의 순서와
+//! 길이로 실행하면서 매 단계마다 풀 잔액 불변식을 검사합니다.
+
+use btcfi_contracts::{OptionStatus, OptionType, SettlementError, SimpleContractManager};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+enum Op {
+    AddLiquidity(u64),
+    RemoveLiquidity(u64),
+    CreateOption {
+        is_call: bool,
+        strike_price: u64,
+        quantity: u64,
+        premium: u64,
+    },
+    /// 지금까지 생성한 옵션 중 index % n 번째를 정산
+    Settle { index: usize, spot_price: u64 },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (1_000u64..200_000_000).prop_map(Op::AddLiquidity),
+        (1_000u64..200_000_000).prop_map(Op::RemoveLiquidity),
+        (
+            any::<bool>(),
+            2_000_000u64..12_000_000, // $20,000 ~ $120,000
+            100_000u64..50_000_000,   // 0.001 ~ 0.5 BTC
+            0u64..2_000_000,
+        )
+            .prop_map(|(is_call, strike_price, quantity, premium)| Op::CreateOption {
+                is_call,
+                strike_price,
+                quantity,
+                premium,
+            }),
+        (any::<usize>(), 1_000_000u64..15_000_000)
+            .prop_map(|(index, spot_price)| Op::Settle { index, spot_price }),
+    ]
+}
+
+/// 테스트 쪽에서 따로 집계한 입출금 합계
+#[derive(Default)]
+struct Totals {
+    deposits: u64,
+    withdrawals: u64,
+    premiums: u64,
+    payouts: u64,
+}
+
+fn collateral_of(option_type: OptionType, strike_price: u64, quantity: u64) -> u64 {
+    match option_type {
+        OptionType::Call => quantity,
+        OptionType::Put => strike_price * quantity / 100_000_000,
+    }
+}
+
+fn check_invariants(manager: &SimpleContractManager, totals: &Totals) {
+    let pool = &manager.pool_state;
+
+    assert_eq!(
+        pool.total_liquidity,
+        pool.available_liquidity + pool.locked_collateral,
+        "total = available + locked"
+    );
+    assert!(pool.locked_collateral <= pool.total_liquidity);
+    assert_eq!(
+        totals.deposits + totals.premiums - totals.payouts - totals.withdrawals,
+        pool.total_liquidity,
+        "deposits + premiums - payouts - withdrawals = total"
+    );
+    assert_eq!(pool.total_premium_collected, totals.premiums);
+    assert_eq!(pool.total_payout, totals.payouts);
+
+    let active: Vec<_> = manager
+        .options
+        .values()
+        .filter(|option| option.status == OptionStatus::Active)
+        .collect();
+    assert_eq!(pool.active_options as usize, active.len());
+    let locked: u64 = active
+        .iter()
+        .map(|option| collateral_of(option.option_type, option.strike_price, option.quantity))
+        .sum();
+    assert_eq!(pool.locked_collateral, locked);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn pool_invariants_hold(ops in prop::collection::vec(op_strategy(), 1..80)) {
+        let mut manager = SimpleContractManager::new();
+        let mut totals = Totals::default();
+        let mut created: Vec<String> = Vec::new();
+
+        for (step, op) in ops.into_iter().enumerate() {
+            match op {
+                Op::AddLiquidity(amount) => {
+                    manager.add_liquidity(amount).unwrap();
+                    totals.deposits += amount;
+                }
+                Op::RemoveLiquidity(amount) => {
+                    let available = manager.pool_state.available_liquidity;
+                    let result = manager.remove_liquidity(amount);
+                    prop_assert_eq!(result.is_ok(), amount <= available);
+                    if result.is_ok() {
+                        totals.withdrawals += amount;
+                    }
+                }
+                Op::CreateOption { is_call, strike_price, quantity, premium } => {
+                    let option_type = if is_call { OptionType::Call } else { OptionType::Put };
+                    let option_id = format!("OPT-{}", step);
+                    let result = manager.create_option(
+                        option_id.clone(),
+                        option_type,
+                        strike_price,
+                        quantity,
+                        premium,
+                        800_000,
+                        "prop-user".to_string(),
+                    );
+                    if result.is_ok() {
+                        totals.premiums += premium;
+                        created.push(option_id);
+                    }
+                }
+                Op::Settle { index, spot_price } => {
+                    if created.is_empty() {
+                        continue;
+                    }
+                    let option_id = created[index % created.len()].clone();
+                    let was_active = manager.options[&option_id].status == OptionStatus::Active;
+                    let before = manager.pool_state.clone();
+
+                    match manager.settle_option(&option_id, spot_price) {
+                        Ok(payout) => {
+                            prop_assert!(was_active);
+                            totals.payouts += payout;
+                        }
+                        Err(e) => {
+                            // 이미 정산된 옵션은 다시 정산되지 않고 풀도 그대로
+                            prop_assert!(!was_active);
+                            prop_assert_eq!(e, SettlementError::OptionNotActive(option_id.clone()));
+                            prop_assert_eq!(manager.pool_state.total_liquidity, before.total_liquidity);
+                            prop_assert_eq!(manager.pool_state.total_payout, before.total_payout);
+                        }
+                    }
+                    prop_assert_eq!(manager.options[&option_id].status, OptionStatus::Settled);
+                }
+            }
+
+            check_invariants(&manager, &totals);
+        }
+
+        // 원장 재적용 결과도 같아야 함
+        let rebuilt = manager.ledger().rebuild().unwrap();
+        prop_assert_eq!(rebuilt.total_liquidity, manager.pool_state.total_liquidity);
+        prop_assert_eq!(rebuilt.locked_collateral, manager.pool_state.locked_collateral);
+    }
+}
@@ -0,0 +1,124 @@
+//! bitcoind regtest 종단 간 테스트
+//!
+//! 옵션 생성 → 정산 증명 앵커링(OP_RETURN) → 앵커 검증 → 정산 지급까지 실제
+//! regtest 노드에서 실행하고 지갑 잔액을 확인합니다.
+//!
+//! 실행: `BITCOIND_EXE=/path/to/bitcoind cargo test -p btcfi-contracts --features regtest --test regtest_e2e_test`
+
+#![cfg(feature = "regtest")]
+
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+use bitcoin::Amount;
+use bitcoin_client::regtest::RegtestNode;
+use btcfi_contracts::bitcoin_option::BitcoinOption;
+use btcfi_contracts::{OptionType, SimpleContractManager};
+
+const LIQUIDITY: u64 = 100_000_000; // 1 BTC
+const BUYER_FUNDS: u64 = 10_000_000; // 0.1 BTC
+const PREMIUM: u64 = 250_000;
+const QUANTITY: u64 = 10_000_000; // 0.1 BTC
+const STRIKE: u64 = 7_000_000; // $70,000
+
+/// 정산 증명 (BitcoinOption 포맷, 오라클 서명 없음)
+fn settlement_proof(option_type: OptionType, spot_price: u64) -> Vec<u8> {
+    let secp = Secp256k1::new();
+    let key = |byte: u8| PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[byte; 32]).unwrap());
+    let option = BitcoinOption {
+        option_type,
+        strike_price: STRIKE,
+        expiry_block: 200,
+        buyer_pubkey: key(1),
+        seller_pubkey: key(2),
+        verifier_pubkey: key(3),
+        premium: PREMIUM,
+        collateral: QUANTITY,
+    };
+    option.create_settlement_proof(spot_price, vec![]).unwrap()
+}
+
+/// 한 옵션의 전체 생애주기 실행 후 구매자 지급액 반환
+fn run_lifecycle(option_type: OptionType, spot_price: u64) -> u64 {
+    let node = RegtestNode::start().expect("bitcoind regtest node");
+    let miner = node.miner();
+    let pool_wallet = node.create_wallet("pool").unwrap();
+    let buyer_wallet = node.create_wallet("buyer").unwrap();
+    let pool = pool_wallet.wallet();
+    let buyer = buyer_wallet.wallet();
+
+    let pool_address = pool.new_address().unwrap();
+    let buyer_address = buyer.new_address().unwrap();
+    miner.send(&pool_address, Amount::from_sat(LIQUIDITY)).unwrap();
+    miner.send(&buyer_address, Amount::from_sat(BUYER_FUNDS)).unwrap();
+    node.mine(1).unwrap();
+
+    let mut manager = SimpleContractManager::new();
+    manager.add_liquidity(LIQUIDITY).unwrap();
+
+    // 1. 옵션 생성: 구매자가 풀에 프리미엄 지불
+    let premium_txid = buyer.send(&pool_address, Amount::from_sat(PREMIUM)).unwrap();
+    node.mine(1).unwrap();
+    let buyer_fee = buyer.fee_paid(&premium_txid).unwrap();
+    manager
+        .create_option(
+            "E2E-1".to_string(),
+            option_type,
+            STRIKE,
+            QUANTITY,
+            PREMIUM,
+            node.block_height().unwrap() as u32 + 10,
+            "buyer".to_string(),
+        )
+        .unwrap();
+
+    // 2. 정산 증명 해시를 OP_RETURN으로 앵커링
+    let proof = settlement_proof(option_type, spot_price);
+    let proof_hash = sha256::Hash::hash(&proof).to_byte_array();
+    let anchor_txid = pool.anchor(&proof_hash).unwrap();
+    node.confirm().unwrap();
+    let mut pool_fees = pool.fee_paid(&anchor_txid).unwrap();
+
+    // 3. 앵커 검증: 체인에서 읽은 값이 다시 계산한 증명 해시와 일치
+    let anchored = pool.read_anchor(&anchor_txid).unwrap().expect("OP_RETURN output");
+    assert_eq!(anchored, sha256::Hash::hash(&settlement_proof(option_type, spot_price)).to_byte_array());
+
+    // 4. 정산 및 지급
+    let payout = manager.settle_option("E2E-1", spot_price).unwrap();
+    if payout > 0 {
+        let payout_txid = pool.send(&buyer_address, Amount::from_sat(payout)).unwrap();
+        node.mine(1).unwrap();
+        pool_fees += pool.fee_paid(&payout_txid).unwrap();
+    }
+
+    // 최종 잔액: 온체인 풀 잔액 = 장부상 총 유동성 - 네트워크 수수료
+    assert_eq!(
+        buyer.balance().unwrap(),
+        Amount::from_sat(BUYER_FUNDS - PREMIUM + payout) - buyer_fee
+    );
+    assert_eq!(
+        pool.balance().unwrap(),
+        Amount::from_sat(manager.pool_state.total_liquidity) - pool_fees
+    );
+    assert_eq!(manager.pool_state.total_liquidity, LIQUIDITY + PREMIUM - payout);
+    assert_eq!(manager.pool_state.locked_collateral, 0);
+
+    payout
+}
+
+#[test]
+fn test_itm_call_anchor_verify_settle() {
+    let payout = run_lifecycle(OptionType::Call, 7_200_000);
+    assert!(payout > 0);
+}
+
+#[test]
+fn test_otm_put_anchor_verify_settle() {
+    let payout = run_lifecycle(OptionType::Put, 7_200_000);
+    assert_eq!(payout, 0);
+}
+
+#[test]
+fn test_itm_put_anchor_verify_settle() {
+    let payout = run_lifecycle(OptionType::Put, 6_500_000);
+    assert!(payout > 0);
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use btcfi_contracts::{OptionType, OptionStatus, SimpleOption};
+use btcfi_contracts::{OptionType, OptionStatus, OptionTerms, SimpleOption};
 
 /// 정산 결과
 #[derive(Debug, Clone, PartialEq)]
@@ -127,6 +127,7 @@ mod tests {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         }
     }
 
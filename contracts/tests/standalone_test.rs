@@ -1,5 +1,5 @@
 // 독립적인 테스트 모듈 - 외부 의존성 최소화
-use btcfi_contracts::{OptionType, OptionStatus, SimpleOption, SimplePoolState};
+use btcfi_contracts::{OptionType, OptionStatus, OptionTerms, SimpleOption, SimplePoolState};
 
 #[test]
 fn test_option_creation() {
@@ -13,6 +13,7 @@ fn test_option_creation() {
         expiry_height: 801_000,
         status: OptionStatus::Active,
         user_id: "user123".to_string(),
+        terms: OptionTerms::default(),
     };
 
     // Then
@@ -33,6 +34,7 @@ fn test_option_settlement_calculation() {
         expiry_height: 801_000,
         status: OptionStatus::Active,
         user_id: "user123".to_string(),
+        terms: OptionTerms::default(),
     };
     
     let spot_price = 7_500_000; // $75,000
@@ -63,6 +65,7 @@ fn test_put_option_settlement() {
         expiry_height: 801_000,
         status: OptionStatus::Active,
         user_id: "user123".to_string(),
+        terms: OptionTerms::default(),
     };
     
     let spot_price = 6_500_000; // $65,000
@@ -191,6 +194,9 @@ fn test_utilization_rate() {
         total_premium_collected: 5_000_000,
         total_payout: 2_000_000,
         active_options: 3,
+        reserve_balance: 0,
+        unaccrued_funding: 0,
+        total_funding_accrued: 0,
     };
 
     // When
@@ -225,6 +231,7 @@ fn test_multiple_option_types() {
             expiry_height: 801_000,
             status: OptionStatus::Active,
             user_id: "user1".to_string(),
+            terms: OptionTerms::default(),
         },
         SimpleOption {
             option_id: "PUT-001".to_string(),
@@ -235,6 +242,7 @@ fn test_multiple_option_types() {
             expiry_height: 801_000,
             status: OptionStatus::Active,
             user_id: "user2".to_string(),
+            terms: OptionTerms::default(),
         },
     ];
 
@@ -1,4 +1,4 @@
-use btcfi_contracts::{OptionType, OptionStatus, SimpleOption};
+use btcfi_contracts::{OptionType, OptionStatus, OptionTerms, SimpleOption};
 
 #[cfg(test)]
 mod option_creation {
@@ -16,6 +16,7 @@ mod option_creation {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
 
         // Then
@@ -36,6 +37,7 @@ mod option_creation {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user456".to_string(),
+            terms: OptionTerms::default(),
         };
 
         // Then
@@ -128,6 +130,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let spot_price = 75_000_00; // $75,000
 
@@ -152,6 +155,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let spot_price = 65_000_00;
 
@@ -176,6 +180,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let spot_price = 65_000_00;
 
@@ -200,6 +205,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let spot_price = 75_000_00;
 
@@ -224,6 +230,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let put = SimpleOption {
             option_id: "PUT-ATM".to_string(),
@@ -234,6 +241,7 @@ mod option_settlement {
             expiry_height: 800_000,
             status: OptionStatus::Active,
             user_id: "user123".to_string(),
+            terms: OptionTerms::default(),
         };
         let spot_price = 70_000_00;
 
@@ -40,6 +40,9 @@ mod pool_state {
             total_premium_collected: 0,
             total_payout: 0,
             active_options: 3,
+            reserve_balance: 0,
+            unaccrued_funding: 0,
+            total_funding_accrued: 0,
         };
 
         // When
@@ -0,0 +1,250 @@
+//! 시장 이상 감지 (kill-switch)
+//!
+//! 합의 가격이 짧은 시간에 급변하거나 합의가 연속으로 실패하면
+//! `TradingHalted` 이벤트를 발행해 옵션 생성과 정산을 멈춥니다.
+
+use oracle_vm_common::SystemEvent;
+use std::collections::VecDeque;
+
+/// 합의 라운드 길이 (초) - Oracle Node 수집 주기와 동일
+const ROUND_SECS: u64 = 60;
+
+/// 이상 감지 설정
+#[derive(Debug, Clone)]
+pub struct AnomalyConfig {
+    /// 허용 가격 변동률 (%)
+    pub max_move_pct: f64,
+    /// 가격 변동 측정 구간 (초)
+    pub window_secs: u64,
+    /// 연속 합의 실패 허용 라운드 수
+    pub max_consecutive_failures: u32,
+    /// 자동 재개까지 대기 시간 (초, None이면 수동 재개만)
+    pub auto_resume_secs: Option<u64>,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            max_move_pct: 10.0,
+            window_secs: 300,
+            max_consecutive_failures: 5,
+            auto_resume_secs: Some(900),
+        }
+    }
+}
+
+/// 현재 거래 중단 상태
+#[derive(Debug, Clone)]
+pub struct HaltState {
+    pub reason: String,
+    pub halted_at: u64,
+    pub resume_at: Option<u64>,
+}
+
+/// 합의 결과를 관찰하여 거래 중단/재개 이벤트를 만드는 감지기
+pub struct AnomalyDetector {
+    config: AnomalyConfig,
+    history: VecDeque<(u64, f64)>,
+    consecutive_failures: u32,
+    last_failed_round: Option<u64>,
+    halt: Option<HaltState>,
+}
+
+impl AnomalyDetector {
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config,
+            history: VecDeque::new(),
+            consecutive_failures: 0,
+            last_failed_round: None,
+            halt: None,
+        }
+    }
+
+    pub fn halt_state(&self) -> Option<&HaltState> {
+        self.halt.as_ref()
+    }
+
+    /// 합의 결과 반영 (None = 합의 실패)
+    ///
+    /// 발생한 이벤트를 순서대로 반환합니다 (자동 재개 → 중단).
+    pub fn observe(&mut self, consensus_price: Option<f64>, now: u64) -> Vec<SystemEvent> {
+        let mut events = Vec::new();
+        events.extend(self.check_timed_resume(now));
+
+        match consensus_price {
+            Some(price) => {
+                self.consecutive_failures = 0;
+                self.history
+                    .retain(|(timestamp, _)| now.saturating_sub(*timestamp) <= self.config.window_secs);
+
+                let reference = self.history.front().map(|(_, price)| *price);
+                self.history.push_back((now, price));
+
+                if let Some(reference) = reference {
+                    let move_pct = ((price - reference) / reference * 100.0).abs();
+                    if move_pct > self.config.max_move_pct {
+                        events.extend(self.halt(
+                            format!(
+                                "Price moved {:.2}% in {}s (${:.2} -> ${:.2})",
+                                move_pct, self.config.window_secs, reference, price
+                            ),
+                            now,
+                        ));
+                    }
+                }
+            }
+            None => {
+                // 같은 라운드의 실패는 한 번만 센다
+                let round = now / ROUND_SECS;
+                if self.last_failed_round != Some(round) {
+                    self.last_failed_round = Some(round);
+                    self.consecutive_failures += 1;
+                }
+
+                if self.consecutive_failures >= self.config.max_consecutive_failures {
+                    events.extend(self.halt(
+                        format!(
+                            "Consensus failed {} consecutive rounds",
+                            self.consecutive_failures
+                        ),
+                        now,
+                    ));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// 자동 재개 시각이 지났으면 재개
+    pub fn check_timed_resume(&mut self, now: u64) -> Option<SystemEvent> {
+        let resume_at = self.halt.as_ref()?.resume_at?;
+        if now < resume_at {
+            return None;
+        }
+
+        self.clear(now, false)
+    }
+
+    /// 운영자 수동 재개
+    pub fn resume(&mut self, now: u64) -> Option<SystemEvent> {
+        self.clear(now, true)
+    }
+
+    fn halt(&mut self, reason: String, now: u64) -> Option<SystemEvent> {
+        if self.halt.is_some() {
+            return None;
+        }
+
+        let resume_at = self.config.auto_resume_secs.map(|secs| now + secs);
+        self.halt = Some(HaltState {
+            reason: reason.clone(),
+            halted_at: now,
+            resume_at,
+        });
+
+        Some(SystemEvent::TradingHalted {
+            reason,
+            timestamp: now,
+            resume_at,
+        })
+    }
+
+    fn clear(&mut self, now: u64, manual: bool) -> Option<SystemEvent> {
+        self.halt.take()?;
+        // 재개 후에는 새 기준 가격부터 다시 측정
+        self.history.clear();
+        self.consecutive_failures = 0;
+
+        Some(SystemEvent::TradingResumed {
+            timestamp: now,
+            manual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> AnomalyDetector {
+        AnomalyDetector::new(AnomalyConfig {
+            max_move_pct: 10.0,
+            window_secs: 300,
+            max_consecutive_failures: 3,
+            auto_resume_secs: Some(600),
+        })
+    }
+
+    #[test]
+    fn test_price_jump_halts_trading() {
+        let mut detector = detector();
+        assert!(detector.observe(Some(70000.0), 1_000).is_empty());
+        assert!(detector.observe(Some(72000.0), 1_060).is_empty());
+
+        let events = detector.observe(Some(80000.0), 1_120);
+        assert!(matches!(
+            events.as_slice(),
+            [SystemEvent::TradingHalted { resume_at: Some(1_720), .. }]
+        ));
+
+        // 중단 중에는 추가 이벤트 없음
+        assert!(detector.observe(Some(90000.0), 1_180).is_empty());
+    }
+
+    #[test]
+    fn test_old_prices_leave_window() {
+        let mut detector = detector();
+        detector.observe(Some(70000.0), 1_000);
+        assert!(detector.observe(Some(80000.0), 1_400).is_empty());
+        assert!(detector.halt_state().is_none());
+    }
+
+    #[test]
+    fn test_consecutive_failures_halt_trading() {
+        let mut detector = detector();
+        // 같은 라운드의 실패는 한 번만 집계
+        detector.observe(None, 60);
+        detector.observe(None, 70);
+        detector.observe(None, 120);
+        assert!(detector.halt_state().is_none());
+
+        let events = detector.observe(None, 180);
+        assert_eq!(events.len(), 1);
+        assert!(detector.halt_state().is_some());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let mut detector = detector();
+        detector.observe(None, 60);
+        detector.observe(None, 120);
+        detector.observe(Some(70000.0), 130);
+        detector.observe(None, 180);
+        assert!(detector.halt_state().is_none());
+    }
+
+    #[test]
+    fn test_timed_and_manual_resume() {
+        let mut detector = detector();
+        detector.observe(Some(70000.0), 1_000);
+        detector.observe(Some(80000.0), 1_060);
+        assert!(detector.check_timed_resume(1_600).is_none());
+        assert_eq!(
+            detector.check_timed_resume(1_660),
+            Some(SystemEvent::TradingResumed {
+                timestamp: 1_660,
+                manual: false
+            })
+        );
+
+        detector.observe(Some(70000.0), 2_000);
+        detector.observe(Some(60000.0), 2_060);
+        assert!(matches!(
+            detector.resume(2_100),
+            Some(SystemEvent::TradingResumed { manual: true, .. })
+        ));
+        assert!(detector.resume(2_200).is_none());
+    }
+}
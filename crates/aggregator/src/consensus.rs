@@ -0,0 +1,215 @@
+//! 거래소별 최신 가격으로 합의 가격 계산
+//!
+//! 서비스(`calculate_aggregated_price`)는 격리/시간 창 필터를 거친 거래소별 최신
+//! 가격을 모아 이 모듈에 넘기고, 통합 테스트는 같은 함수를 직접 호출합니다.
+//! 모든 계산은 정수 USD 센트로 하므로 같은 입력이면 항상 같은 센트가 나옵니다.
+
+use oracle_vm_common::config::ConsensusParams;
+use oracle_vm_common::price::{deviation_bps, format_cents, ratio_to_bps, weighted_mean_cents, Rounding};
+use std::collections::HashMap;
+use std::fmt;
+
+/// 합의 대상 거래소
+pub const REQUIRED_EXCHANGES: [&str; 3] = ["binance", "coinbase", "kraken"];
+
+/// 합의 가중치 (정수 비율로 계산해 반올림 오차 없음): 대체 거래소 가격은 정상의 절반
+const NORMAL_PRICE_WEIGHT: u64 = 2;
+const DEGRADED_PRICE_WEIGHT: u64 = 1;
+
+/// 집계 가격 상식선 범위 (USD 센트): $10,000 ~ $500,000
+const MIN_REALISTIC_PRICE_CENTS: u64 = 10_000 * 100;
+const MAX_REALISTIC_PRICE_CENTS: u64 = 500_000 * 100;
+
+/// 거래소 하나의 최신 가격
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExchangePrice {
+    /// USD 센트
+    pub price_cents: u64,
+    pub timestamp: u64,
+    /// 대체 거래소에서 수집된 가격 (가중치 절반)
+    pub degraded: bool,
+}
+
+/// 합의 실패 사유
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsensusRejection {
+    /// 참여 거래소 부족
+    InsufficientExchanges {
+        participating: usize,
+        required: usize,
+        missing: Vec<String>,
+    },
+    /// 거래소 간 가격 시각 차이가 1분 초과
+    TimestampMismatch { min: u64, max: u64 },
+    /// 평균에서 허용 편차 이상 벗어난 가격
+    PriceAnomaly {
+        exchange: String,
+        price_cents: u64,
+        deviation_bps: u64,
+        average_cents: u64,
+    },
+    /// 상식선 범위 밖 평균
+    UnrealisticPrice { average_cents: u64 },
+}
+
+impl fmt::Display for ConsensusRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientExchanges {
+                participating,
+                required,
+                missing,
+            } => write!(
+                f,
+                "Insufficient consensus: {} of {} exchanges (need at least {}). Missing: {:?}",
+                participating,
+                REQUIRED_EXCHANGES.len(),
+                required,
+                missing
+            ),
+            Self::TimestampMismatch { min, max } => write!(
+                f,
+                "Timestamp mismatch: {} second difference. Min: {}, Max: {}",
+                max - min,
+                min,
+                max
+            ),
+            Self::PriceAnomaly {
+                exchange,
+                price_cents,
+                deviation_bps,
+                average_cents,
+            } => write!(
+                f,
+                "Price anomaly detected: {} = ${} ({} bps deviation from average ${})",
+                exchange,
+                format_cents(*price_cents),
+                deviation_bps,
+                format_cents(*average_cents)
+            ),
+            Self::UnrealisticPrice { average_cents } => {
+                write!(f, "Unrealistic average price: ${}", format_cents(*average_cents))
+            }
+        }
+    }
+}
+
+/// 거래소별 최신 가격의 합의 가격 (USD 센트, 가중 평균 half-even)
+///
+/// 1. 전체 거래소 중 `min_consensus_ratio` 이상 참여 (기본 3개 중 2개)
+/// 2. 가격 시각 차이 1분 이내
+/// 3. 각 가격이 가중 평균에서 `max_price_deviation` 이내 (bps, 올림)
+/// 4. 평균이 상식선 범위 안
+pub fn consensus_price(
+    latest: &HashMap<String, ExchangePrice>,
+    params: &ConsensusParams,
+) -> Result<u64, ConsensusRejection> {
+    // ceil(ratio * n): 기본 0.66이면 3개 중 2개 이상
+    let required = (params.min_consensus_ratio * REQUIRED_EXCHANGES.len() as f64).ceil() as usize;
+    if latest.is_empty() || latest.len() < required {
+        return Err(ConsensusRejection::InsufficientExchanges {
+            participating: latest.len(),
+            required,
+            missing: REQUIRED_EXCHANGES
+                .iter()
+                .filter(|exchange| !latest.contains_key(**exchange))
+                .map(|exchange| exchange.to_string())
+                .collect(),
+        });
+    }
+
+    let min = latest.values().map(|price| price.timestamp).min().unwrap_or(0);
+    let max = latest.values().map(|price| price.timestamp).max().unwrap_or(0);
+    if max - min > 60 {
+        return Err(ConsensusRejection::TimestampMismatch { min, max });
+    }
+
+    let weighted: Vec<(u64, u64)> = latest
+        .values()
+        .map(|price| {
+            let weight = if price.degraded {
+                DEGRADED_PRICE_WEIGHT
+            } else {
+                NORMAL_PRICE_WEIGHT
+            };
+            (price.price_cents, weight)
+        })
+        .collect();
+    let average_cents = weighted_mean_cents(&weighted, Rounding::HalfEven)
+        .ok_or(ConsensusRejection::UnrealisticPrice { average_cents: 0 })?;
+
+    let max_deviation_bps = ratio_to_bps(params.max_price_deviation);
+    for (exchange, price) in latest {
+        let deviation = deviation_bps(price.price_cents, average_cents).unwrap_or(u64::MAX);
+        if deviation > max_deviation_bps {
+            return Err(ConsensusRejection::PriceAnomaly {
+                exchange: exchange.clone(),
+                price_cents: price.price_cents,
+                deviation_bps: deviation,
+                average_cents,
+            });
+        }
+    }
+
+    if !(MIN_REALISTIC_PRICE_CENTS..=MAX_REALISTIC_PRICE_CENTS).contains(&average_cents) {
+        return Err(ConsensusRejection::UnrealisticPrice { average_cents });
+    }
+    Ok(average_cents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latest(prices: &[(&str, u64, bool)]) -> HashMap<String, ExchangePrice> {
+        prices
+            .iter()
+            .map(|(exchange, price_cents, degraded)| {
+                (
+                    exchange.to_string(),
+                    ExchangePrice {
+                        price_cents: *price_cents,
+                        timestamp: 1_700_000_000,
+                        degraded: *degraded,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_weighted_mean_in_cents() {
+        let params = ConsensusParams::default();
+        let prices = latest(&[
+            ("binance", 6_501_950, false),
+            ("coinbase", 6_500_000, false),
+            ("kraken", 6_498_700, false),
+        ]);
+        // 19,500,650 / 3 = 6,500,216.67 → half-even
+        assert_eq!(consensus_price(&prices, &params), Ok(6_500_217));
+
+        // 대체 거래소 가격은 절반 가중치
+        let prices = latest(&[("binance", 6_500_300, true), ("coinbase", 6_500_000, false)]);
+        assert_eq!(consensus_price(&prices, &params), Ok(6_500_100));
+    }
+
+    #[test]
+    fn test_rejections() {
+        let params = ConsensusParams::default();
+        assert!(matches!(
+            consensus_price(&latest(&[("kraken", 6_500_000, false)]), &params),
+            Err(ConsensusRejection::InsufficientExchanges { participating: 1, required: 2, .. })
+        ));
+        assert!(matches!(
+            consensus_price(
+                &latest(&[("binance", 7_000_000, false), ("coinbase", 6_500_000, false)]),
+                &params
+            ),
+            Err(ConsensusRejection::PriceAnomaly { .. })
+        ));
+        assert_eq!(
+            consensus_price(&latest(&[("binance", 500_000, false), ("coinbase", 500_000, false)]), &params),
+            Err(ConsensusRejection::UnrealisticPrice { average_cents: 500_000 })
+        );
+    }
+}
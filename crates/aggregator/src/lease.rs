@@ -0,0 +1,135 @@
+//! 거래소별 제출 리스 (Oracle Node 다중 배포)
+//!
+//! 같은 거래소를 수집하는 Oracle Node 복제본이 여러 개일 때 리스를 가진
+//! 노드 하나만 가격을 제출합니다. 나머지는 대기하다가 리스가 만료되면 넘겨받습니다.
+
+use std::collections::HashMap;
+
+/// 기본 최대 리스 유효시간 (노드 기본값 180초보다 약간 길게)
+pub const DEFAULT_MAX_LEASE_TTL_SECS: u64 = 300;
+
+/// 리스 보유 정보
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    pub holder: String,
+    pub expires_at: u64,
+}
+
+/// 리스 요청 결과
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaseGrant {
+    pub granted: bool,
+    pub lease: Lease,
+}
+
+/// 거래소별 리스 테이블
+#[derive(Default)]
+pub struct LeaseTable {
+    leases: HashMap<String, Lease>,
+}
+
+impl LeaseTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 리스 획득/갱신 (보유자가 같거나 만료되었으면 부여)
+    pub fn acquire(&mut self, exchange: &str, node_id: &str, ttl_secs: u64, now: u64) -> LeaseGrant {
+        let available = match self.leases.get(exchange) {
+            Some(lease) => lease.holder == node_id || now >= lease.expires_at,
+            None => true,
+        };
+
+        if available {
+            let lease = Lease {
+                holder: node_id.to_string(),
+                expires_at: now.saturating_add(ttl_secs),
+            };
+            self.leases.insert(exchange.to_string(), lease.clone());
+            LeaseGrant {
+                granted: true,
+                lease,
+            }
+        } else {
+            LeaseGrant {
+                granted: false,
+                lease: self.leases[exchange].clone(),
+            }
+        }
+    }
+
+    /// 모든 리스 (거래소, 리스)
+    pub fn leases(&self) -> Vec<(String, Lease)> {
+        self.leases
+            .iter()
+            .map(|(exchange, lease)| (exchange.clone(), lease.clone()))
+            .collect()
+    }
+
+    /// primary에서 미러링한 리스로 교체
+    pub fn replace(&mut self, leases: Vec<(String, Lease)>) {
+        self.leases = leases.into_iter().collect();
+    }
+
+    /// 해당 노드의 제출 허용 여부 (리스가 없거나 만료된 거래소는 허용)
+    pub fn may_submit(&self, exchange: &str, node_id: &str, now: u64) -> bool {
+        match self.leases.get(exchange) {
+            Some(lease) => lease.holder == node_id || now >= lease.expires_at,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_active_submitter() {
+        let mut table = LeaseTable::new();
+        assert!(table.acquire("binance", "node-a", 180, 1_000).granted);
+
+        let standby = table.acquire("binance", "node-b", 180, 1_060);
+        assert!(!standby.granted);
+        assert_eq!(standby.lease.holder, "node-a");
+
+        assert!(table.may_submit("binance", "node-a", 1_060));
+        assert!(!table.may_submit("binance", "node-b", 1_060));
+        // 다른 거래소는 독립
+        assert!(table.acquire("kraken", "node-b", 180, 1_060).granted);
+    }
+
+    #[test]
+    fn test_renewal_and_takeover_on_expiry() {
+        let mut table = LeaseTable::new();
+        table.acquire("binance", "node-a", 180, 1_000);
+        assert_eq!(
+            table.acquire("binance", "node-a", 180, 1_060).lease.expires_at,
+            1_240
+        );
+
+        // 리더가 갱신을 멈추면 만료 후 대기 노드가 인계
+        assert!(!table.acquire("binance", "node-b", 180, 1_239).granted);
+        assert!(table.acquire("binance", "node-b", 180, 1_240).granted);
+        assert!(!table.may_submit("binance", "node-a", 1_241));
+    }
+
+    #[test]
+    fn test_expiry_saturates() {
+        let mut table = LeaseTable::new();
+        let grant = table.acquire("binance", "node-a", u64::MAX, 1_000);
+        assert_eq!(grant.lease.expires_at, u64::MAX);
+    }
+
+    #[test]
+    fn test_replace_keeps_holder_on_standby() {
+        let mut primary = LeaseTable::new();
+        primary.acquire("binance", "node-a", 180, 1_000);
+
+        // standby가 primary 리스를 그대로 받으면 만료 전 다른 노드는 가져갈 수 없음
+        let mut standby = LeaseTable::new();
+        standby.replace(primary.leases());
+        assert!(!standby.acquire("binance", "node-b", 180, 1_050).granted);
+        assert!(standby.acquire("binance", "node-a", 180, 1_050).granted);
+    }
+}
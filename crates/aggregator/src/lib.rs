@@ -0,0 +1,3 @@
+//! Aggregator 라이브러리 (바이너리와 통합 테스트가 공유하는 합의 계산)
+
+pub mod consensus;
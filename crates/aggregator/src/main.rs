@@ -1,150 +1,537 @@
-use anyhow::Result;
+use aggregator::consensus::{consensus_price, ExchangePrice, REQUIRED_EXCHANGES};
+use anyhow::{Context, Result};
 use chrono::Utc;
+use clap::Parser;
+use oracle_vm_common::config::{
+    ConfigLoader, ConsensusConfig, ConsensusConfigHandle, ConsensusParams,
+};
+use oracle_vm_common::price::{
+    cents_from_dollars, cents_to_dollars, deviation_bps, format_cents, ratio_to_bps,
+    weighted_mean_cents, Rounding,
+};
+use oracle_vm_common::crypto::{
+    lease_request_payload, open_service_key_store, threshold_commit_payload, vol_surface_payload, KeyStore,
+    MemoryKeyStore, PublicKey, SecretKey,
+};
+use frost_secp256k1_tr::round1::{NonceCommitment, SigningCommitments};
+use frost_secp256k1_tr::round2::SignatureShare;
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::{ConsensusProof, EventBus, ExpiryCalendar, Shutdown, SystemEvent, ThresholdError};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tonic::{transport::Server, Request, Response, Status};
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
-// gRPC 서비스 정의 (tonic-build로 자동 생성됨)
-pub mod oracle {
-    tonic::include_proto!("oracle");
-}
+mod anomaly;
+mod lease;
+mod node_auth;
+mod operator_auth;
+mod replication;
+mod reputation;
+mod settlement_proof;
+mod submission_ledger;
+mod threshold;
+
+use anomaly::{AnomalyConfig, AnomalyDetector};
+use lease::{Lease, LeaseTable, DEFAULT_MAX_LEASE_TTL_SECS};
+use node_auth::{NodeAllowlist, NodeRegistry, Submission};
+use operator_auth::OperatorAuth;
+use replication::{FailoverMonitor, MirrorCursor, Role, DEFAULT_FAILURE_THRESHOLD};
+use reputation::{Observation, ReputationConfig, ReputationTracker};
+use settlement_proof::{BackfillPolicy, ProofStore, SettlementQuorum, AGGREGATOR_KEY};
+use submission_ledger::{utc_date, LedgerEntry, SubmissionLedger, SubmissionQuery};
+use threshold::{GroupKey, SigningCoordinator};
+
+// gRPC 서비스 정의 (oracle-vm-proto)
+pub use oracle_vm_proto::oracle;
 
 use oracle::{
+    oracle_service_client::OracleServiceClient,
     oracle_service_server::{OracleService, OracleServiceServer},
-    AggregatedPriceUpdate, ConfigRequest, ConfigResponse, GetPriceRequest, GetPriceResponse,
-    HealthRequest, HealthResponse, PriceDataPoint, PriceRequest, PriceResponse,
+    AggregatedPriceUpdate, ClearQuarantineRequest, ConfigRequest, ConfigResponse,
+    ConsensusProofRequest, ConsensusProofResponse, DailyCommitmentRequest, DailyCommitmentResponse, ExchangeReputation, ExchangeReputationRequest, ExchangeReputationResponse, GetPriceRequest,
+    GetPriceResponse,
+    GetVolSurfaceRequest, GetVolSurfaceResponse, HealthRequest, HealthResponse, LeaseRequest,
+    LeaseResponse, PriceDataPoint,
+    PriceRequest, PriceResponse, RegisterNodeRequest, RegisterNodeResponse,
+    ReloadConsensusConfigRequest, ReloadConsensusConfigResponse, ReplicatedLease, ReplicatedNode,
+    ReplicationStateRequest, ReplicationStateResponse, ResumeTradingRequest, SubmissionQueryRequest, SubmissionQueryResponse, SubmissionRecord,
+    ThresholdAttestationRequest, ThresholdAttestationResponse, ThresholdCommitRequest,
+    ThresholdCommitResponse, ThresholdNonceCommitment, ThresholdSignRequest, ThresholdSignResponse,
+    TradingStatusRequest, TradingStatusResponse, VolPoint, VolSurfaceRequest, VolSurfaceResponse,
 };
 
 use futures::Stream;
 use std::pin::Pin;
 
+/// 제출 가격 (USD 센트)
+///
+/// 새 노드는 서명한 정수 센트를 `price_cents`로 보내고, 구버전 노드는
+/// `price`(double)만 보내므로 half-even으로 반올림해 센트로 맞춥니다.
+fn submitted_price_cents(request: &PriceRequest) -> Option<u64> {
+    if request.price_cents > 0 {
+        Some(request.price_cents)
+    } else {
+        cents_from_dollars(request.price, Rounding::HalfEven)
+    }
+}
+
 /// 가격 데이터 저장 구조체
 #[derive(Clone, Debug)]
 struct StoredPriceData {
-    price: f64,
+    /// USD 센트
+    price_cents: u64,
     timestamp: u64,
     source: String,
     node_id: String,
     received_at: u64,
+    degraded: bool,
+}
+
+/// IV 곡면 저장 구조체 (소스별 최신 1개)
+#[derive(Clone, Debug)]
+struct StoredVolSurface {
+    timestamp: u64,
+    underlying_price: f64,
+    points: Vec<VolPoint>,
+    received_at: u64,
 }
 
 /// Aggregator 서비스 구현
-#[derive(Default)]
 pub struct AggregatorService {
     // 메모리에 가격 데이터 저장 (실제로는 DB 사용)
     price_data: Arc<Mutex<Vec<StoredPriceData>>>,
     // 활성 노드 추적
     active_nodes: Arc<Mutex<HashMap<String, u64>>>,
+    // 소스별 최신 IV 곡면
+    vol_surfaces: Arc<Mutex<HashMap<String, StoredVolSurface>>>,
+    // 시장 이상 감지 (kill-switch)
+    anomaly_detector: Arc<Mutex<AnomalyDetector>>,
+    // 거래 재개 등 운영자 요청 토큰
+    operator_auth: OperatorAuth,
+    // 거래 중단/재개 이벤트 발행
+    event_bus: EventBus,
+    // 합의 파라미터 (핫 리로드 가능)
+    consensus_config: ConsensusConfigHandle,
+    consensus_config_path: String,
+    // 거래소 평판 및 격리
+    reputation: Arc<Mutex<ReputationTracker>>,
+    // 등록된 Oracle Node (서명/nonce 검증)
+    node_registry: Arc<Mutex<NodeRegistry>>,
+    // 거래소별 제출 리스 (복제 노드 중복 제출 방지)
+    leases: Arc<Mutex<LeaseTable>>,
+    // 노드가 요청할 수 있는 최대 리스 유효시간 (초)
+    max_lease_ttl_secs: u64,
+    // 노드 제출 원장 (감사/일일 커밋먼트)
+    submissions: Arc<Mutex<SubmissionLedger>>,
+    // 정산 시각별 합의 증명
+    consensus_proofs: Arc<Mutex<ProofStore>>,
+    // 합의 증명 서명 키 저장소
+    keys: Arc<dyn KeyStore>,
+    // 노드 임계 서명 라운드 (그룹 키가 설정된 경우)
+    threshold: Option<Arc<Mutex<SigningCoordinator>>>,
+    // primary/standby 역할 (standby는 승격 전까지 제출을 받지 않음)
+    role: Arc<Mutex<Role>>,
 }
 
 impl AggregatorService {
-    pub fn new() -> Self {
+    pub fn new(
+        event_bus: EventBus,
+        consensus_config: ConsensusConfigHandle,
+        consensus_config_path: String,
+    ) -> Self {
         Self {
             price_data: Arc::new(Mutex::new(Vec::new())),
             active_nodes: Arc::new(Mutex::new(HashMap::new())),
+            vol_surfaces: Arc::new(Mutex::new(HashMap::new())),
+            anomaly_detector: Arc::new(Mutex::new(AnomalyDetector::new(AnomalyConfig::default()))),
+            operator_auth: OperatorAuth::default(),
+            event_bus,
+            consensus_config,
+            consensus_config_path,
+            reputation: Arc::new(Mutex::new(ReputationTracker::new(ReputationConfig::default()))),
+            node_registry: Arc::new(Mutex::new(NodeRegistry::new())),
+            leases: Arc::new(Mutex::new(LeaseTable::new())),
+            max_lease_ttl_secs: DEFAULT_MAX_LEASE_TTL_SECS,
+            submissions: Arc::new(Mutex::new(SubmissionLedger::default())),
+            consensus_proofs: Arc::new(Mutex::new(ProofStore::default())),
+            keys: Arc::new(MemoryKeyStore::ephemeral()),
+            threshold: None,
+            role: Arc::new(Mutex::new(Role::Primary)),
         }
     }
 
-    /// 안전한 집계 가격 계산 (엄격한 조건 검증)
-    fn calculate_aggregated_price(&self) -> Option<f64> {
-        let price_data = self.price_data.lock().unwrap();
-        let now = Utc::now().timestamp() as u64;
+    /// `primary_url` Aggregator의 standby로 시작
+    pub fn with_replica_of(self, primary_url: String) -> Self {
+        *self.role.lock().unwrap() = Role::Standby { primary_url };
+        self
+    }
 
-        // Step 1: 각 거래소별 최신 데이터 수집 (거래소 이름으로 그룹핑)
-        let mut latest_per_exchange: std::collections::HashMap<String, (f64, u64)> =
-            std::collections::HashMap::new();
+    fn role(&self) -> Role {
+        self.role.lock().unwrap().clone()
+    }
 
-        for data in price_data.iter() {
-            // 최근 2분 내 데이터만 사용 (더 넉넉한 윈도우)
-            if now - data.received_at <= 120 {
-                // 2분 = 120초
-                latest_per_exchange
-                    .entry(data.source.clone()) // source = exchange name
-                    .and_modify(|(existing_price, existing_time)| {
-                        // 더 최신 데이터라면 업데이트
-                        if data.timestamp > *existing_time {
-                            *existing_price = data.price;
-                            *existing_time = data.timestamp;
-                        }
-                    })
-                    .or_insert((data.price, data.timestamp));
+    fn is_standby(&self) -> bool {
+        self.role.lock().unwrap().is_standby()
+    }
+
+    /// standby → primary 승격
+    fn promote(&self) {
+        let mut role = self.role.lock().unwrap();
+        if let Role::Standby { primary_url } = &*role {
+            warn!("🔁 Primary {} unreachable, promoting this standby to primary", primary_url);
+            *role = Role::Primary;
+        }
+    }
+
+    /// primary에서 끌어온 제출을 원장과 가격 창에 반영
+    fn mirror(&self, entries: Vec<LedgerEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        let count = entries.len();
+        // 승격 후 primary가 이미 받은 서명을 다시 받지 않도록 nonce도 따라감
+        self.node_registry.lock().unwrap().observe(&entries);
+        let mut ledger = self.submissions.lock().unwrap();
+        let mut price_data = self.price_data.lock().unwrap();
+        for entry in entries {
+            price_data.push(StoredPriceData {
+                price_cents: entry.price_cents,
+                timestamp: entry.timestamp,
+                source: entry.exchange.clone(),
+                node_id: entry.node_id.clone(),
+                received_at: entry.received_at,
+                degraded: entry.degraded,
+            });
+            if let Err(e) = ledger.record(entry) {
+                warn!("❌ Failed to persist mirrored submission: {}", e);
             }
         }
+        let excess = price_data.len().saturating_sub(100);
+        price_data.drain(..excess);
+        info!("🪞 Mirrored {} submissions from primary", count);
+    }
 
-        // Step 2: 2/3 이상 합의 조건 검증
-        let required_exchanges = vec!["binance", "coinbase", "kraken"];
-        let total_exchanges = required_exchanges.len();
-        let min_required = (total_exchanges * 2 + 2) / 3; // ceil(2/3) = 2개 이상
+    /// 최대 리스 유효시간 지정 (더 긴 요청은 이 값으로 줄여 부여)
+    pub fn with_max_lease_ttl(mut self, max_lease_ttl_secs: u64) -> Self {
+        self.max_lease_ttl_secs = max_lease_ttl_secs.max(1);
+        self
+    }
 
-        // 2.1 최소 필요 거래소 수 확인 (3개 중 2개 이상)
-        if latest_per_exchange.len() < min_required {
-            let missing: Vec<&str> = required_exchanges
-                .iter()
-                .filter(|&exchange| !latest_per_exchange.contains_key(*exchange))
-                .cloned()
-                .collect();
-            warn!(
-                "⚠️ Insufficient consensus: {} of {} exchanges (need at least {}). Missing: {:?}",
-                latest_per_exchange.len(),
-                total_exchanges,
-                min_required,
-                missing
-            );
+    /// 운영자 토큰 설정 (없으면 운영자 요청 전부 거부)
+    pub fn with_operator_auth(mut self, operator_auth: OperatorAuth) -> Self {
+        self.operator_auth = operator_auth;
+        self
+    }
+
+    /// 노드 등록부 교체 (허용 목록/파일 영속화 설정)
+    pub fn with_node_registry(mut self, registry: NodeRegistry) -> Self {
+        self.node_registry = Arc::new(Mutex::new(registry));
+        self
+    }
+
+    /// primary의 리스와 등록 노드 반영
+    ///
+    /// 승격 직후에도 리스 보유 노드가 그대로 제출하고, primary가 이미 받은 nonce는
+    /// 다시 받지 않습니다.
+    fn mirror_state(&self, state: ReplicationStateResponse) {
+        let leases = state
+            .leases
+            .into_iter()
+            .map(|lease| {
+                (
+                    lease.exchange,
+                    Lease {
+                        holder: lease.holder,
+                        expires_at: lease.expires_at,
+                    },
+                )
+            })
+            .collect();
+        self.leases.lock().unwrap().replace(leases);
+
+        let nodes = state
+            .nodes
+            .into_iter()
+            .filter_map(|node| match node.public_key.parse::<PublicKey>() {
+                Ok(key) => Some((node.node_id, key, node.last_nonce)),
+                Err(e) => {
+                    warn!("❌ Invalid mirrored key for {}: {}", node.node_id, e);
+                    None
+                }
+            })
+            .collect();
+        self.node_registry.lock().unwrap().mirror(nodes);
+    }
+
+    /// 합의 증명 서명 키 저장소 지정 (`aggregator` 키 사용, 없으면 실행마다 새 키)
+    pub fn with_key_store(mut self, keys: Arc<dyn KeyStore>) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    /// 노드 DKG 그룹 키 지정 (합의 증명마다 t-of-n 임계 서명 라운드를 엶)
+    pub fn with_threshold_group(mut self, group: GroupKey) -> Self {
+        self.threshold = Some(Arc::new(Mutex::new(SigningCoordinator::new(group))));
+        self
+    }
+
+    /// 정산 시각의 합의 증명 (이미 만든 증명이 있으면 그대로 반환)
+    ///
+    /// 임계 서명이 설정되어 있으면 같은 정산 시각의 서명 라운드도 엽니다.
+    fn consensus_proof(&self, settlement_time: u64) -> Result<ConsensusProof> {
+        let now = Utc::now().timestamp() as u64;
+        if settlement_time > now {
+            anyhow::bail!("Settlement time {} has not passed yet", settlement_time);
+        }
+        let ledger = self.submissions.lock().unwrap();
+        let registry = self.node_registry.lock().unwrap();
+        let proof = self.consensus_proofs.lock().unwrap().get_or_build(
+            AssetPair::btc_usd().as_str(),
+            settlement_time,
+            &ledger,
+            &registry,
+            self.keys.as_ref(),
+        )?;
+        if let Some(threshold) = &self.threshold {
+            threshold.lock().unwrap().open(settlement_time, &proof.to_bytes()?);
+        }
+        Ok(proof)
+    }
+
+    /// 임계 서명 조정자 (서명 RPC는 등록된 노드만, 조회는 node_id 없이 허용)
+    fn threshold_for(&self, node_id: &str) -> Option<&Mutex<SigningCoordinator>> {
+        let threshold = self.threshold.as_deref()?;
+        if !node_id.is_empty() && !self.node_registry.lock().unwrap().is_registered(node_id) {
             return None;
         }
+        Some(threshold)
+    }
 
+    /// 백필 제출 사용 정책과 정족수 지정 (이미 만든 증명은 그대로)
+    pub fn with_settlement_policy(mut self, policy: BackfillPolicy, quorum: SettlementQuorum) -> Self {
+        self.consensus_proofs = Arc::new(Mutex::new(
+            ProofStore::default().with_backfill_policy(policy).with_quorum(quorum),
+        ));
+        self
+    }
+
+    /// 제출 원장 교체 (보존 기간/파일 영속화 설정)
+    pub fn with_submission_ledger(mut self, ledger: SubmissionLedger) -> Self {
+        self.submissions = Arc::new(Mutex::new(ledger));
+        self
+    }
+
+    /// 백필 제출 원장 기록
+    fn record_backfill(&self, request: &PriceRequest, price_cents: u64, now: u64) -> Result<()> {
+        self.submissions
+            .lock()
+            .unwrap()
+            .record(LedgerEntry {
+                node_id: request.node_id.clone(),
+                exchange: request.source.clone(),
+                price_cents,
+                timestamp: request.timestamp,
+                nonce: request.nonce,
+                degraded: request.degraded,
+                backfilled: true,
+                signature: request.signature.clone(),
+                received_at: now,
+            })
+            .context("Failed to persist backfilled submission")?;
         info!(
-            "✅ Consensus achieved: {} of {} exchanges participating",
-            latest_per_exchange.len(),
-            total_exchanges
+            "🧩 Backfilled {} at {}: ${} (node: {})",
+            request.source,
+            request.timestamp,
+            format_cents(price_cents),
+            request.node_id
         );
+        Ok(())
+    }
 
-        // 2.2 timestamp 동일성 검증 (1분 이내 차이만 허용)
-        let timestamps: Vec<u64> = latest_per_exchange
-            .values()
-            .map(|(_, timestamp)| *timestamp)
-            .collect();
-        let min_timestamp = *timestamps.iter().min().unwrap();
-        let max_timestamp = *timestamps.iter().max().unwrap();
+    /// 보존 기간이 지난 제출 정리
+    fn prune_submissions(&self) {
+        let now = Utc::now().timestamp() as u64;
+        match self.submissions.lock().unwrap().prune(now) {
+            Ok(0) => {}
+            Ok(removed) => info!("🧹 Pruned {} expired submissions from ledger", removed),
+            Err(e) => warn!("❌ Submission ledger pruning failed: {}", e),
+        }
+    }
 
-        if max_timestamp - min_timestamp > 60 {
-            // 1분 초과 차이
-            warn!(
-                "⚠️ Timestamp mismatch: {} second difference. Min: {}, Max: {}",
-                max_timestamp - min_timestamp,
-                min_timestamp,
-                max_timestamp
-            );
-            return None;
+    /// 제출된 가격의 편차/지연 여부 판단
+    ///
+    /// 다른 (격리되지 않은) 거래소들의 최신 가격 평균과 비교합니다.
+    fn assess_submission(&self, request: &PriceRequest, price_cents: u64, now: u64) -> Observation {
+        let price_data = self.price_data.lock().unwrap();
+        let mut reputation = self.reputation.lock().unwrap();
+
+        if now.saturating_sub(request.timestamp) > reputation.config().stale_after_secs {
+            return Observation::Stale;
+        }
+
+        let mut latest_others: HashMap<&str, (u64, u64)> = HashMap::new();
+        for data in price_data.iter() {
+            if data.source == request.source
+                || now.saturating_sub(data.timestamp) > 120
+                || reputation.is_quarantined(&data.source, now)
+            {
+                continue;
+            }
+            let entry = latest_others
+                .entry(data.source.as_str())
+                .or_insert((data.price_cents, data.timestamp));
+            if data.timestamp > entry.1 {
+                *entry = (data.price_cents, data.timestamp);
+            }
+        }
+
+        if latest_others.is_empty() {
+            return Observation::Healthy;
         }
 
-        // Step 3: 가격 이상치 검증
-        let prices: Vec<f64> = latest_per_exchange
-            .values()
-            .map(|(price, _)| *price)
+        let others: Vec<(u64, u64)> = latest_others.values().map(|(price, _)| (*price, 1)).collect();
+        let Some(reference) = weighted_mean_cents(&others, Rounding::HalfEven) else {
+            return Observation::Healthy;
+        };
+        let max_deviation_bps = ratio_to_bps(
+            self.consensus_config
+                .snapshot()
+                .params_for(AssetPair::btc_usd().as_str())
+                .max_price_deviation,
+        );
+
+        if deviation_bps(price_cents, reference).is_none_or(|bps| bps > max_deviation_bps) {
+            Observation::Deviated
+        } else {
+            Observation::Healthy
+        }
+    }
+
+    /// 평판 지표를 gRPC 응답으로 변환
+    fn reputation_response(&self, quarantined_only: bool) -> ExchangeReputationResponse {
+        let now = Utc::now().timestamp() as u64;
+        let exchanges = self
+            .reputation
+            .lock()
+            .unwrap()
+            .snapshot(now)
+            .into_iter()
+            .filter(|(_, score)| !quarantined_only || score.quarantined_until.is_some())
+            .map(|(exchange, score)| ExchangeReputation {
+                exchange,
+                score: score.score,
+                observations: score.observations,
+                deviations: score.deviations,
+                stale: score.stale,
+                quarantine_count: score.quarantine_count,
+                quarantined_until: score.quarantined_until,
+            })
             .collect();
-        let avg_price = prices.iter().sum::<f64>() / prices.len() as f64;
 
-        // 3.1 개별 가격이 평균에서 5% 이상 벗어나는지 확인
-        for (exchange, (price, _)) in &latest_per_exchange {
-            let deviation = ((price - avg_price) / avg_price * 100.0).abs();
-            if deviation > 5.0 {
-                // 5% 초과 편차
-                warn!(
-                    "⚠️ Price anomaly detected: {} = ${:.2} ({}% deviation from average ${:.2})",
-                    exchange, price, deviation, avg_price
-                );
-                return None;
-            }
+        ExchangeReputationResponse { exchanges }
+    }
+
+    /// 합의 설정 파일을 다시 읽어 교체 (실패 시 기존 설정 유지)
+    fn reload_consensus_from_file(&self) -> Result<ConsensusParams> {
+        let config = self
+            .consensus_config
+            .reload_from_file(&self.consensus_config_path)?;
+        let params = config.params_for(AssetPair::btc_usd().as_str());
+        info!(
+            "🔄 Consensus config reloaded from {}: min ratio {:.2}, max deviation {:.1}%",
+            self.consensus_config_path,
+            params.min_consensus_ratio,
+            params.max_price_deviation * 100.0
+        );
+        Ok(params)
+    }
+
+    /// 합의 결과를 이상 감지기에 반영하고 발생한 이벤트 발행
+    fn observe_consensus(&self, consensus_price: Option<f64>) {
+        let now = Utc::now().timestamp() as u64;
+        let events = self
+            .anomaly_detector
+            .lock()
+            .unwrap()
+            .observe(consensus_price, now);
+
+        for event in events {
+            self.event_bus.publish(event);
         }
+    }
 
-        // 3.2 가격 범위 상식선 검증
-        if avg_price < 10000.0 || avg_price > 500000.0 {
-            warn!("⚠️ Unrealistic average price: ${:.2}", avg_price);
-            return None;
+    /// 현재 거래 상태 (자동 재개 시각이 지났으면 재개 처리)
+    fn trading_status(&self) -> TradingStatusResponse {
+        let now = Utc::now().timestamp() as u64;
+        let mut detector = self.anomaly_detector.lock().unwrap();
+        if let Some(event) = detector.check_timed_resume(now) {
+            self.event_bus.publish(event);
         }
 
+        match detector.halt_state() {
+            Some(halt) => TradingStatusResponse {
+                halted: true,
+                reason: halt.reason.clone(),
+                halted_at: halt.halted_at,
+                resume_at: halt.resume_at,
+            },
+            None => TradingStatusResponse {
+                halted: false,
+                reason: String::new(),
+                halted_at: 0,
+                resume_at: None,
+            },
+        }
+    }
+
+    /// 안전한 집계 가격 계산 (엄격한 조건 검증), USD 센트
+    fn calculate_aggregated_price(&self) -> Option<u64> {
+        let price_data = self.price_data.lock().unwrap();
+        let now = Utc::now().timestamp() as u64;
+        let params = self
+            .consensus_config
+            .snapshot()
+            .params_for(AssetPair::btc_usd().as_str());
+
+        // Step 1: 각 거래소별 최신 데이터 수집 (거래소 이름으로 그룹핑)
+        let mut latest_per_exchange: HashMap<String, ExchangePrice> = HashMap::new();
+
+        let mut reputation = self.reputation.lock().unwrap();
+        for data in price_data.iter() {
+            // 격리된 거래소는 합의에서 제외
+            if reputation.is_quarantined(&data.source, now) {
+                continue;
+            }
+            // 노드가 서명한 시각 기준 최근 2분 내 데이터만 사용 (더 넉넉한 윈도우)
+            if now.saturating_sub(data.timestamp) <= 120 {
+                // 2분 = 120초
+                let price = ExchangePrice {
+                    price_cents: data.price_cents,
+                    timestamp: data.timestamp,
+                    degraded: data.degraded,
+                };
+                latest_per_exchange
+                    .entry(data.source.clone()) // source = exchange name
+                    .and_modify(|existing| {
+                        // 더 최신 데이터라면 업데이트
+                        if data.timestamp > existing.timestamp {
+                            *existing = price;
+                        }
+                    })
+                    .or_insert(price);
+            }
+        }
+
+        // Step 2~3: 참여 거래소 수, 시각 차이, 이상치, 상식선 범위 검증 후 가중 평균
+        let avg_price = match consensus_price(&latest_per_exchange, &params) {
+            Ok(price) => price,
+            Err(rejection) => {
+                warn!("⚠️ {}", rejection);
+                return None;
+            }
+        };
+
         // Step 4: 모든 검증 통과 시 집계 수행
         let participating_exchanges: Vec<&String> = latest_per_exchange.keys().collect();
         info!(
@@ -152,15 +539,21 @@ impl AggregatorService {
             participating_exchanges
         );
         info!(
-            "📊 Consensus aggregated price: ${:.2} from {}/{} exchanges",
-            avg_price,
-            prices.len(),
-            total_exchanges
+            "📊 Consensus aggregated price: ${} from {}/{} exchanges",
+            format_cents(avg_price),
+            latest_per_exchange.len(),
+            REQUIRED_EXCHANGES.len()
         );
 
         // 개별 가격 로깅
-        for (exchange, (price, timestamp)) in &latest_per_exchange {
-            info!("   {}: ${:.2} (timestamp: {})", exchange, price, timestamp);
+        for (exchange, price) in &latest_per_exchange {
+            info!(
+                "   {}: ${} (timestamp: {}, degraded: {})",
+                exchange,
+                format_cents(price.price_cents),
+                price.timestamp,
+                price.degraded
+            );
         }
 
         Some(avg_price)
@@ -173,7 +566,7 @@ impl AggregatorService {
         active_nodes.insert(node_id.to_string(), now);
 
         // 2분 이상 비활성 노드 제거 (1분 수집 + 1분 여유)
-        active_nodes.retain(|_, &mut last_seen| now - last_seen <= 120);
+        active_nodes.retain(|_, &mut last_seen| now.saturating_sub(last_seen) <= 120);
     }
 }
 
@@ -182,36 +575,198 @@ impl OracleService for AggregatorService {
     /// 스트림 타입 정의
     type StreamPricesStream =
         Pin<Box<dyn Stream<Item = Result<AggregatedPriceUpdate, Status>> + Send>>;
+    /// 노드 공개키 등록
+    async fn register_node(
+        &self,
+        request: Request<RegisterNodeRequest>,
+    ) -> Result<Response<RegisterNodeResponse>, Status> {
+        if self.is_standby() {
+            return Err(Status::unavailable("Standby aggregator: use the primary"));
+        }
+        let register_request = request.into_inner();
+
+        let result = self.node_registry.lock().unwrap().register(
+            &register_request.node_id,
+            &register_request.public_key,
+            &register_request.signature,
+        );
+
+        match result {
+            Ok(last_nonce) => {
+                info!(
+                    "🔑 Registered node {} (last nonce: {})",
+                    register_request.node_id, last_nonce
+                );
+                Ok(Response::new(RegisterNodeResponse {
+                    success: true,
+                    message: "Node registered".to_string(),
+                    last_nonce,
+                }))
+            }
+            Err(e) => {
+                warn!(
+                    "❌ Node registration rejected for {}: {}",
+                    register_request.node_id, e
+                );
+                Ok(Response::new(RegisterNodeResponse {
+                    success: false,
+                    message: e.to_string(),
+                    last_nonce: 0,
+                }))
+            }
+        }
+    }
+
     /// 가격 데이터 제출 처리
     async fn submit_price(
         &self,
         request: Request<PriceRequest>,
     ) -> Result<Response<PriceResponse>, Status> {
+        if self.is_standby() {
+            return Err(Status::unavailable("Standby aggregator: use the primary"));
+        }
         let price_request = request.into_inner();
 
-        info!(
-            "📨 Received price: ${:.2} from {} (node: {})",
-            price_request.price, price_request.source, price_request.node_id
-        );
+        // 가격 검증 (서명 검증도 이 정수 센트 값 기준)
+        let price_cents = match submitted_price_cents(&price_request) {
+            Some(cents) if cents > 0 => cents,
+            _ => {
+                warn!(
+                    "❌ Invalid price: {} ({} cents)",
+                    price_request.price, price_request.price_cents
+                );
+                return Ok(Response::new(PriceResponse {
+                    success: false,
+                    message: "Price must be positive".to_string(),
+                    aggregated_price: None,
+                    timestamp: Utc::now().timestamp() as u64,
+                    aggregated_price_cents: None,
+                }));
+            }
+        };
 
-        // 가격 검증
-        if price_request.price <= 0.0 {
-            warn!("❌ Invalid price: {}", price_request.price);
+        // 합의 대상 거래소 중 이 노드에 배정된 거래소만 허용
+        if !REQUIRED_EXCHANGES.contains(&price_request.source.as_str()) {
+            return Err(Status::invalid_argument(format!(
+                "Unknown exchange {}",
+                price_request.source
+            )));
+        }
+        if !self
+            .node_registry
+            .lock()
+            .unwrap()
+            .is_assigned(&price_request.node_id, &price_request.source)
+        {
+            return Err(Status::permission_denied(format!(
+                "{} is not assigned to {}",
+                price_request.node_id, price_request.source
+            )));
+        }
+
+        // 노드 서명/nonce/시각 검증 (재전송, 같은 분 중복, 창 밖 timestamp 거부)
+        let now = Utc::now().timestamp() as u64;
+        let verified = self
+            .node_registry
+            .lock()
+            .unwrap()
+            .verify_submission(
+                &Submission {
+                    node_id: &price_request.node_id,
+                    source: &price_request.source,
+                    price_cents,
+                    timestamp: price_request.timestamp,
+                    nonce: price_request.nonce,
+                    degraded: price_request.degraded,
+                    backfilled: price_request.backfilled,
+                    signature: price_request.signature.as_deref(),
+                },
+                now,
+            );
+        if let Err(e) = verified {
+            warn!(
+                "❌ Rejected submission from {} ({}): {}",
+                price_request.node_id, price_request.source, e
+            );
+            return Err(Status::unauthenticated(e.to_string()));
+        }
+
+        // 백필 제출은 원장에만 기록 (실시간 가격 창/평판/리스와 무관)
+        if price_request.backfilled {
+            if price_request.timestamp / 60 >= now / 60 {
+                return Err(Status::invalid_argument(format!(
+                    "Backfilled submission for {} is not in the past",
+                    price_request.timestamp
+                )));
+            }
+            self.record_backfill(&price_request, price_cents, now)
+                .map_err(|e| Status::internal(e.to_string()))?;
             return Ok(Response::new(PriceResponse {
-                success: false,
-                message: "Price must be positive".to_string(),
+                success: true,
+                message: "Backfilled price recorded".to_string(),
                 aggregated_price: None,
-                timestamp: Utc::now().timestamp() as u64,
+                timestamp: now,
+                aggregated_price_cents: None,
             }));
         }
 
+        // 다른 복제 노드가 이 거래소의 리스를 보유 중이면 거부
+        if !self
+            .leases
+            .lock()
+            .unwrap()
+            .may_submit(&price_request.source, &price_request.node_id, now)
+        {
+            return Err(Status::failed_precondition(format!(
+                "{} does not hold the {} submission lease",
+                price_request.node_id, price_request.source
+            )));
+        }
+
+        info!(
+            "📨 Received price: ${} from {} (node: {})",
+            format_cents(price_cents),
+            price_request.source,
+            price_request.node_id
+        );
+
+        // 인증된 제출은 감사를 위해 모두 원장에 기록
+        if let Err(e) = self.submissions.lock().unwrap().record(LedgerEntry {
+            node_id: price_request.node_id.clone(),
+            exchange: price_request.source.clone(),
+            price_cents,
+            timestamp: price_request.timestamp,
+            nonce: price_request.nonce,
+            degraded: price_request.degraded,
+            backfilled: false,
+            signature: price_request.signature.clone(),
+            received_at: now,
+        }) {
+            warn!("❌ Failed to persist submission to ledger: {}", e);
+        }
+
+        // 거래소 평판 갱신
+        let observation = self.assess_submission(&price_request, price_cents, now);
+        if self
+            .reputation
+            .lock()
+            .unwrap()
+            .record(&price_request.source, observation, now)
+        {
+            warn!(
+                "🚫 {} quarantined after repeated {:?} submissions",
+                price_request.source, observation
+            );
+        }
+
         // 데이터 저장
         let stored_data = StoredPriceData {
-            price: price_request.price,
+            price_cents,
             timestamp: price_request.timestamp,
             source: price_request.source,
             node_id: price_request.node_id.clone(),
-            received_at: Utc::now().timestamp() as u64,
+            received_at: now,
+            degraded: price_request.degraded,
         };
 
         {
@@ -228,17 +783,19 @@ impl OracleService for AggregatorService {
         self.update_active_node(&price_request.node_id);
 
         // 집계 가격 계산
-        let aggregated_price = self.calculate_aggregated_price();
+        let aggregated_price_cents = self.calculate_aggregated_price();
+        self.observe_consensus(aggregated_price_cents.map(cents_to_dollars));
 
-        if let Some(agg_price) = aggregated_price {
-            info!("📊 Aggregated price: ${:.2}", agg_price);
+        if let Some(agg_price) = aggregated_price_cents {
+            info!("📊 Aggregated price: ${}", format_cents(agg_price));
         }
 
         Ok(Response::new(PriceResponse {
             success: true,
             message: "Price data received".to_string(),
-            aggregated_price,
+            aggregated_price: aggregated_price_cents.map(cents_to_dollars),
             timestamp: Utc::now().timestamp() as u64,
+            aggregated_price_cents,
         }))
     }
 
@@ -249,8 +806,10 @@ impl OracleService for AggregatorService {
     ) -> Result<Response<HealthResponse>, Status> {
         let health_request = request.into_inner();
 
-        // 활성 노드 업데이트
-        self.update_active_node(&health_request.node_id);
+        // 활성 노드 업데이트 (standby 헬스체크처럼 node_id가 없으면 제외)
+        if !health_request.node_id.is_empty() {
+            self.update_active_node(&health_request.node_id);
+        }
 
         let active_nodes = self.active_nodes.lock().unwrap();
         let active_count = active_nodes.len() as u32;
@@ -260,8 +819,9 @@ impl OracleService for AggregatorService {
             health_request.node_id, active_count
         );
 
+        // standby는 제출을 받지 않으므로 unhealthy로 응답
         Ok(Response::new(HealthResponse {
-            healthy: true,
+            healthy: !self.is_standby(),
             timestamp: Utc::now().timestamp() as u64,
             active_nodes: active_count,
             version: "1.0.0".to_string(),
@@ -287,19 +847,21 @@ impl OracleService for AggregatorService {
                     .rev()
                     .take(5)
                     .map(|data| PriceDataPoint {
-                        price: data.price,
+                        price: cents_to_dollars(data.price_cents),
                         timestamp: data.timestamp,
                         source: data.source.clone(),
                         node_id: data.node_id.clone(),
+                        price_cents: data.price_cents,
                     })
                     .collect();
 
                 Ok(Response::new(GetPriceResponse {
                     success: true,
-                    aggregated_price: price,
+                    aggregated_price: cents_to_dollars(price),
                     data_points,
                     last_update,
                     recent_prices,
+                    aggregated_price_cents: price,
                 }))
             }
             None => Ok(Response::new(GetPriceResponse {
@@ -308,10 +870,557 @@ impl OracleService for AggregatorService {
                 data_points: 0,
                 last_update: 0,
                 recent_prices: vec![],
+                aggregated_price_cents: 0,
             })),
         }
     }
 
+    /// IV 곡면 제출 처리
+    async fn submit_vol_surface(
+        &self,
+        request: Request<VolSurfaceRequest>,
+    ) -> Result<Response<VolSurfaceResponse>, Status> {
+        let surface_request = request.into_inner();
+
+        info!(
+            "📨 Received IV surface: {} points from {} (node: {})",
+            surface_request.points.len(),
+            surface_request.source,
+            surface_request.node_id
+        );
+
+        // IV는 0 초과 500% 이하만 허용
+        let valid_points = surface_request
+            .points
+            .iter()
+            .all(|point| point.mark_iv > 0.0 && point.mark_iv <= 5.0 && point.strike > 0.0);
+        if surface_request.points.is_empty()
+            || !valid_points
+            || surface_request.underlying_price <= 0.0
+        {
+            warn!("❌ Invalid IV surface from {}", surface_request.source);
+            return Ok(Response::new(VolSurfaceResponse {
+                success: false,
+                message: "IV surface must contain valid points".to_string(),
+                timestamp: Utc::now().timestamp() as u64,
+            }));
+        }
+
+        // 등록 노드 키 서명/nonce 확인 (가격 제출과 같은 카운터)
+        let payload = vol_surface_payload(
+            &surface_request.node_id,
+            &surface_request.source,
+            surface_request.timestamp,
+            surface_request.underlying_price,
+            surface_request
+                .points
+                .iter()
+                .map(|point| (point.expiry, point.strike, point.is_call, point.mark_iv)),
+            surface_request.nonce,
+        );
+        if let Err(e) = self.node_registry.lock().unwrap().verify_request(
+            &surface_request.node_id,
+            &payload,
+            surface_request.nonce,
+            &surface_request.signature,
+        ) {
+            warn!("❌ Rejected IV surface from {}: {}", surface_request.node_id, e);
+            return Err(Status::unauthenticated(e.to_string()));
+        }
+
+        self.vol_surfaces.lock().unwrap().insert(
+            surface_request.source,
+            StoredVolSurface {
+                timestamp: surface_request.timestamp,
+                underlying_price: surface_request.underlying_price,
+                points: surface_request.points,
+                received_at: Utc::now().timestamp() as u64,
+            },
+        );
+
+        self.update_active_node(&surface_request.node_id);
+
+        Ok(Response::new(VolSurfaceResponse {
+            success: true,
+            message: "IV surface received".to_string(),
+            timestamp: Utc::now().timestamp() as u64,
+        }))
+    }
+
+    /// 최신 IV 곡면 조회
+    async fn get_vol_surface(
+        &self,
+        request: Request<GetVolSurfaceRequest>,
+    ) -> Result<Response<GetVolSurfaceResponse>, Status> {
+        let source_filter = request.into_inner().source;
+        let vol_surfaces = self.vol_surfaces.lock().unwrap();
+
+        // 필터가 없으면 가장 최근에 받은 곡면
+        let latest = vol_surfaces
+            .iter()
+            .filter(|(source, _)| source_filter.as_ref().is_none_or(|filter| filter == *source))
+            .max_by_key(|(_, surface)| surface.received_at);
+
+        match latest {
+            Some((source, surface)) => Ok(Response::new(GetVolSurfaceResponse {
+                success: true,
+                source: source.clone(),
+                timestamp: surface.timestamp,
+                underlying_price: surface.underlying_price,
+                points: surface.points.clone(),
+            })),
+            None => Ok(Response::new(GetVolSurfaceResponse {
+                success: false,
+                source: String::new(),
+                timestamp: 0,
+                underlying_price: 0.0,
+                points: vec![],
+            })),
+        }
+    }
+
+    /// 거래 중단 상태 조회
+    async fn get_trading_status(
+        &self,
+        _request: Request<TradingStatusRequest>,
+    ) -> Result<Response<TradingStatusResponse>, Status> {
+        Ok(Response::new(self.trading_status()))
+    }
+
+    /// 거래 수동 재개 (운영자 토큰 필요)
+    async fn resume_trading(
+        &self,
+        request: Request<ResumeTradingRequest>,
+    ) -> Result<Response<TradingStatusResponse>, Status> {
+        self.operator_auth.authorize(&request)?;
+        let operator = request.into_inner().operator;
+        let now = Utc::now().timestamp() as u64;
+
+        let event = self.anomaly_detector.lock().unwrap().resume(now);
+        match event {
+            Some(event) => {
+                info!("▶️ Trading resumed manually by {}", operator);
+                self.event_bus.publish(event);
+            }
+            None => info!("Resume requested by {} but trading is not halted", operator),
+        }
+
+        Ok(Response::new(self.trading_status()))
+    }
+
+    /// 합의 설정 리로드 (관리자용)
+    async fn reload_consensus_config(
+        &self,
+        request: Request<ReloadConsensusConfigRequest>,
+    ) -> Result<Response<ReloadConsensusConfigResponse>, Status> {
+        let operator = request.into_inner().operator;
+        info!("Consensus config reload requested by {}", operator);
+
+        let response = match self.reload_consensus_from_file() {
+            Ok(params) => ReloadConsensusConfigResponse {
+                success: true,
+                message: "Consensus config reloaded".to_string(),
+                min_consensus_ratio: params.min_consensus_ratio,
+                max_price_deviation: params.max_price_deviation,
+            },
+            Err(e) => {
+                warn!("❌ Consensus config reload failed: {}", e);
+                let params = self
+                    .consensus_config
+                    .snapshot()
+                    .params_for(AssetPair::btc_usd().as_str());
+                ReloadConsensusConfigResponse {
+                    success: false,
+                    message: e.to_string(),
+                    min_consensus_ratio: params.min_consensus_ratio,
+                    max_price_deviation: params.max_price_deviation,
+                }
+            }
+        };
+
+        Ok(Response::new(response))
+    }
+
+    /// 거래소 평판/격리 현황 조회
+    async fn list_exchange_reputation(
+        &self,
+        request: Request<ExchangeReputationRequest>,
+    ) -> Result<Response<ExchangeReputationResponse>, Status> {
+        let quarantined_only = request.into_inner().quarantined_only;
+        Ok(Response::new(self.reputation_response(quarantined_only)))
+    }
+
+    /// 거래소 격리 수동 해제 (운영자 토큰 필요)
+    async fn clear_quarantine(
+        &self,
+        request: Request<ClearQuarantineRequest>,
+    ) -> Result<Response<ExchangeReputationResponse>, Status> {
+        self.operator_auth.authorize(&request)?;
+        let request = request.into_inner();
+        if !self.reputation.lock().unwrap().clear(&request.exchange) {
+            return Err(Status::not_found(format!(
+                "{} is not quarantined",
+                request.exchange
+            )));
+        }
+
+        info!(
+            "✅ Quarantine on {} cleared by {}",
+            request.exchange, request.operator
+        );
+        Ok(Response::new(self.reputation_response(false)))
+    }
+
+    /// 거래소 제출 리스 획득/갱신
+    async fn acquire_lease(
+        &self,
+        request: Request<LeaseRequest>,
+    ) -> Result<Response<LeaseResponse>, Status> {
+        if self.is_standby() {
+            return Err(Status::unavailable("Standby aggregator: use the primary"));
+        }
+        let request = request.into_inner();
+        if request.ttl_secs == 0 {
+            return Err(Status::invalid_argument("ttl_secs must be positive"));
+        }
+        if !REQUIRED_EXCHANGES.contains(&request.exchange.as_str()) {
+            return Err(Status::invalid_argument(format!("Unknown exchange {}", request.exchange)));
+        }
+
+        // 노드 키 서명/nonce 확인 후 배정된 거래소만 허용
+        {
+            let mut registry = self.node_registry.lock().unwrap();
+            let payload =
+                lease_request_payload(&request.node_id, &request.exchange, request.ttl_secs, request.nonce);
+            if let Err(e) = registry.verify_request(&request.node_id, &payload, request.nonce, &request.signature) {
+                warn!("❌ Rejected lease request from {}: {}", request.node_id, e);
+                return Err(Status::unauthenticated(e.to_string()));
+            }
+            if !registry.is_assigned(&request.node_id, &request.exchange) {
+                return Err(Status::permission_denied(format!(
+                    "{} is not assigned to {}",
+                    request.node_id, request.exchange
+                )));
+            }
+        }
+
+        let now = Utc::now().timestamp() as u64;
+        let ttl_secs = request.ttl_secs.min(self.max_lease_ttl_secs);
+        let grant = self
+            .leases
+            .lock()
+            .unwrap()
+            .acquire(&request.exchange, &request.node_id, ttl_secs, now);
+
+        Ok(Response::new(LeaseResponse {
+            granted: grant.granted,
+            holder: grant.lease.holder,
+            expires_at: grant.lease.expires_at,
+        }))
+    }
+
+    /// 노드 제출 원장 조회
+    async fn query_submissions(
+        &self,
+        request: Request<SubmissionQueryRequest>,
+    ) -> Result<Response<SubmissionQueryResponse>, Status> {
+        let request = request.into_inner();
+        let ledger = self.submissions.lock().unwrap();
+        let submissions = ledger
+            .query(&SubmissionQuery {
+                node_id: request.node_id,
+                exchange: request.exchange,
+                from: request.from,
+                to: request.to,
+                limit: request.limit as usize,
+            })
+            .into_iter()
+            .map(|entry| SubmissionRecord {
+                node_id: entry.node_id,
+                exchange: entry.exchange,
+                price: cents_to_dollars(entry.price_cents),
+                timestamp: entry.timestamp,
+                nonce: entry.nonce,
+                degraded: entry.degraded,
+                signature: entry.signature,
+                received_at: entry.received_at,
+                price_cents: entry.price_cents,
+                backfilled: entry.backfilled,
+            })
+            .collect();
+
+        Ok(Response::new(SubmissionQueryResponse {
+            submissions,
+            retention_secs: ledger.retention_secs(),
+            retained_since: ledger.retained_since(),
+        }))
+    }
+
+    /// 일일 제출 커밋먼트 조회
+    async fn get_daily_commitment(
+        &self,
+        request: Request<DailyCommitmentRequest>,
+    ) -> Result<Response<DailyCommitmentResponse>, Status> {
+        let date = request.into_inner().date;
+        let now = Utc::now().timestamp() as u64;
+        // 날짜가 없으면 마지막으로 끝난 날 (어제)
+        let date = if date.is_empty() {
+            utc_date(now.saturating_sub(24 * 60 * 60))
+        } else {
+            chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d").map_err(|e| {
+                Status::invalid_argument(format!("Invalid date {} (expected YYYY-MM-DD): {}", date, e))
+            })?
+        };
+
+        let ledger = self.submissions.lock().unwrap();
+        let commitment = ledger.daily_commitment(date);
+
+        Ok(Response::new(DailyCommitmentResponse {
+            date: date.to_string(),
+            submissions: commitment.submissions,
+            merkle_root: hex::encode(commitment.merkle_root),
+            op_return_payload: hex::encode(commitment.op_return_payload()),
+            complete: ledger.covers_day(date, now),
+        }))
+    }
+
+    /// 정산 시각 합의 증명 조회
+    ///
+    /// 임계 서명이 설정되어 있으면 Aggregator 키 서명만으로는 내보내지 않고,
+    /// 노드 그룹 서명이 완성된 뒤에야 그 서명과 함께 반환합니다.
+    async fn get_consensus_proof(
+        &self,
+        request: Request<ConsensusProofRequest>,
+    ) -> Result<Response<ConsensusProofResponse>, Status> {
+        let settlement_time = match request.into_inner().settlement_time {
+            // 가장 최근에 지난 정산 시각
+            0 => last_settlement_time(Utc::now().timestamp() as u64),
+            time => time,
+        };
+        let proof = self
+            .consensus_proof(settlement_time)
+            .map_err(|e| Status::failed_precondition(e.to_string()))?;
+        let (bytes, program_input) = proof
+            .to_bytes()
+            .and_then(|bytes| Ok((bytes, proof.to_program_input()?)))
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let (threshold_signature, group_pubkey) = match &self.threshold {
+            Some(threshold) => {
+                let coordinator = threshold.lock().unwrap();
+                let round = coordinator
+                    .round(settlement_time)
+                    .ok_or_else(|| Status::not_found(format!("Threshold round {} not found", settlement_time)))?;
+                let Some(signature) = round.signature else {
+                    return Err(Status::failed_precondition(format!(
+                        "Consensus proof for {} awaits the node group signature ({} of {} shares)",
+                        settlement_time,
+                        round.shares.len(),
+                        coordinator.group().threshold
+                    )));
+                };
+                (
+                    Some(signature_hex(&signature).map_err(|e| threshold_status(&e))?),
+                    Some(coordinator.group().x_only()),
+                )
+            }
+            None => (None, None),
+        };
+
+        Ok(Response::new(ConsensusProofResponse {
+            settlement_time,
+            median_price_cents: proof.settlement_price(),
+            submissions: proof.submissions.len() as u32,
+            aggregator_pubkey: proof.aggregator_pubkey.to_string(),
+            proof: hex::encode(bytes),
+            program_input: hex::encode(program_input),
+            threshold_signature,
+            group_pubkey,
+        }))
+    }
+
+    /// 임계 서명 라운드 조회 및 nonce 커밋먼트 제출
+    async fn threshold_commit(
+        &self,
+        request: Request<ThresholdCommitRequest>,
+    ) -> Result<Response<ThresholdCommitResponse>, Status> {
+        let request = request.into_inner();
+        if request.node_id.is_empty() {
+            return Err(Status::invalid_argument("node_id is required"));
+        }
+        // 조정자를 잠그기 전에 확인 (합의 증명 생성은 등록부 → 조정자 순서로 잠금)
+        let commitment = match &request.commitment {
+            Some(commitment) if request.round_id == 0 => {
+                return Err(Status::invalid_argument(format!(
+                    "round_id is required to commit as signer {}",
+                    commitment.signer
+                )))
+            }
+            Some(wire) => {
+                let (signer, commitment) = parse_commitment(wire).map_err(|e| threshold_status(&e))?;
+                // 노드 키로 서명했고, 허용 목록에서 그 참가자 번호를 배정받은 노드만
+                let registry = self.node_registry.lock().unwrap();
+                let payload =
+                    threshold_commit_payload(&request.node_id, request.round_id, signer, &wire.hiding, &wire.binding);
+                registry
+                    .verify_signed(&request.node_id, &payload, &request.signature)
+                    .map_err(|e| Status::unauthenticated(e.to_string()))?;
+                if registry.threshold_signer(&request.node_id) != Some(signer) {
+                    return Err(Status::permission_denied(format!(
+                        "{} is not threshold signer {}",
+                        request.node_id, signer
+                    )));
+                }
+                Some((signer, commitment))
+            }
+            None => None,
+        };
+        let threshold = self
+            .threshold_for(&request.node_id)
+            .ok_or_else(|| threshold_unavailable(&request.node_id))?;
+        if request.round_id == 0 {
+            // 정산 측 조회를 기다리지 않고 가장 최근 정산 시각 라운드를 엶
+            // (정족수가 모이지 않았으면 열 라운드가 없음)
+            let _ = self.consensus_proof(last_settlement_time(Utc::now().timestamp() as u64));
+        }
+        let mut coordinator = threshold.lock().unwrap();
+        let round_id = match request.round_id {
+            0 => match coordinator.latest_open() {
+                Some(round) => round.round_id,
+                None => {
+                    return Ok(Response::new(ThresholdCommitResponse {
+                        round_id: 0,
+                        message: String::new(),
+                        ready: false,
+                        commitments: vec![],
+                    }))
+                }
+            },
+            round_id => round_id,
+        };
+
+        let round = match commitment {
+            Some((signer, commitment)) => coordinator
+                .commit(round_id, signer, commitment)
+                .map_err(|e| threshold_status(&e))?,
+            None => coordinator
+                .round(round_id)
+                .ok_or_else(|| Status::not_found(format!("Threshold round {} not found", round_id)))?,
+        };
+
+        Ok(Response::new(ThresholdCommitResponse {
+            round_id,
+            message: hex::encode(round.message),
+            ready: round.package.is_some(),
+            commitments: round
+                .signers
+                .iter()
+                .map(|signer| encode_commitment(*signer, &round.commitments[signer]))
+                .collect::<Result<_, _>>()
+                .map_err(|e| threshold_status(&e))?,
+        }))
+    }
+
+    /// 임계 서명 조각 제출
+    async fn threshold_sign(
+        &self,
+        request: Request<ThresholdSignRequest>,
+    ) -> Result<Response<ThresholdSignResponse>, Status> {
+        let request = request.into_inner();
+        if request.node_id.is_empty() {
+            return Err(Status::invalid_argument("node_id is required"));
+        }
+        let signer = threshold_signer(request.signer).map_err(|e| threshold_status(&e))?;
+        let share = hex::decode(&request.share)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| SignatureShare::deserialize(&bytes).map_err(|e| e.to_string()))
+            .map_err(|e| Status::invalid_argument(format!("Invalid share: {}", e)))?;
+        let signature = self
+            .threshold_for(&request.node_id)
+            .ok_or_else(|| threshold_unavailable(&request.node_id))?
+            .lock()
+            .unwrap()
+            .submit_share(request.round_id, signer, share)
+            .map_err(|e| threshold_status(&e))?;
+        if signature.is_some() {
+            info!("🔏 Threshold attestation for round {} complete", request.round_id);
+        }
+
+        Ok(Response::new(ThresholdSignResponse {
+            accepted: true,
+            complete: signature.is_some(),
+            signature: signature
+                .as_ref()
+                .map(signature_hex)
+                .transpose()
+                .map_err(|e| threshold_status(&e))?,
+        }))
+    }
+
+    /// t-of-n 임계 서명된 합의 증명 조회
+    async fn get_threshold_attestation(
+        &self,
+        request: Request<ThresholdAttestationRequest>,
+    ) -> Result<Response<ThresholdAttestationResponse>, Status> {
+        let round_id = request.into_inner().round_id;
+        let coordinator = self
+            .threshold_for("")
+            .ok_or_else(|| threshold_unavailable(""))?
+            .lock()
+            .unwrap();
+        let round = coordinator
+            .round(round_id)
+            .ok_or_else(|| Status::not_found(format!("Threshold round {} not found", round_id)))?;
+        let Some(signature) = round.signature else {
+            return Err(Status::failed_precondition(format!(
+                "Threshold round {} has {} of {} signature shares",
+                round_id,
+                round.shares.len(),
+                coordinator.group().threshold
+            )));
+        };
+
+        Ok(Response::new(ThresholdAttestationResponse {
+            round_id,
+            payload: hex::encode(&round.payload),
+            group_pubkey: coordinator.group().x_only(),
+            signature: signature_hex(&signature).map_err(|e| threshold_status(&e))?,
+            signers: round.signers.iter().copied().map(u32::from).collect(),
+        }))
+    }
+
+    /// standby 미러링용 리스/등록 노드 상태
+    async fn get_replication_state(
+        &self,
+        _request: Request<ReplicationStateRequest>,
+    ) -> Result<Response<ReplicationStateResponse>, Status> {
+        let leases = self
+            .leases
+            .lock()
+            .unwrap()
+            .leases()
+            .into_iter()
+            .map(|(exchange, lease)| ReplicatedLease {
+                exchange,
+                holder: lease.holder,
+                expires_at: lease.expires_at,
+            })
+            .collect();
+        let nodes = self
+            .node_registry
+            .lock()
+            .unwrap()
+            .registered()
+            .into_iter()
+            .map(|(node_id, key, last_nonce)| ReplicatedNode {
+                node_id,
+                public_key: key.to_string(),
+                last_nonce,
+            })
+            .collect();
+
+        Ok(Response::new(ReplicationStateResponse { leases, nodes }))
+    }
+
     /// 설정 업데이트 (미구현)
     async fn update_config(
         &self,
@@ -333,26 +1442,792 @@ impl OracleService for AggregatorService {
     }
 }
 
+/// Aggregator CLI 인수
+#[derive(Parser)]
+#[command(name = "aggregator")]
+#[command(about = "BTCFi gRPC price aggregator")]
+struct Args {
+    /// 합의 설정 파일 경로 (SIGHUP 또는 ReloadConsensusConfig로 다시 읽음)
+    #[arg(long, default_value = "config/consensus.toml")]
+    consensus_config: String,
+
+    /// 노드 제출 원장 파일 (JSONL, 없으면 메모리에만 보관)
+    #[arg(long)]
+    submission_ledger: Option<String>,
+
+    /// 제출 원장 보존 기간 (일)
+    #[arg(long, default_value_t = 30)]
+    submission_retention_days: u64,
+
+    /// 노드 허용 목록 파일 (TOML, 노드별 공개키와 제출 거래소)
+    #[arg(long)]
+    node_allowlist: Option<String>,
+
+    /// 허용 목록 없이 누구나 노드로 등록, 키 저장소가 없으면 임시 키로 서명 (로컬 개발 전용)
+    #[arg(long)]
+    open_registration: bool,
+
+    /// 노드 등록 키/nonce 파일 (JSON, 없으면 메모리에만 보관)
+    #[arg(long)]
+    node_registry: Option<String>,
+
+    /// 노드가 요청할 수 있는 최대 리스 유효시간 (초)
+    #[arg(long, default_value_t = DEFAULT_MAX_LEASE_TTL_SECS)]
+    max_lease_ttl: u64,
+
+    /// 운영자 토큰 SHA256 해시 (hex, 여러 번 지정 가능, 없으면 거래 재개/격리 해제 요청 거부)
+    #[arg(long)]
+    operator_token_hash: Vec<String>,
+
+    /// 합의 증명 서명 키 (hex, --key-store보다 우선)
+    #[arg(long)]
+    signing_key: Option<String>,
+
+    /// 서명 키 저장소 (env[:PREFIX] | file:DIR | vault[:MOUNT], 없으면 --open-registration에서만 실행마다 새 키)
+    #[arg(long)]
+    key_store: Option<String>,
+
+    /// Oracle Node DKG 그룹 키 파일 (JSON, 있으면 그룹 서명이 완성된 합의 증명만 내보냄)
+    #[arg(long)]
+    threshold_group: Option<String>,
+
+    /// 백필 제출의 정산 증명 사용 정책 (exclude | fallback | accept)
+    #[arg(long, default_value = "exclude")]
+    backfill_policy: BackfillPolicy,
+
+    /// 정산 증명에 거래소를 쓰려면 필요한 서로 다른 노드 수 (리스로 한 노드만 제출하면 1)
+    #[arg(long, default_value_t = SettlementQuorum::default().nodes_per_exchange)]
+    settlement_node_quorum: usize,
+
+    /// 정산 증명에 필요한 (노드 정족수를 채운) 거래소 수
+    #[arg(long, default_value_t = SettlementQuorum::default().exchanges)]
+    settlement_exchange_quorum: usize,
+
+    /// standby로 시작해 이 primary Aggregator의 제출을 미러링 (예: http://primary:50051)
+    #[arg(long)]
+    replica_of: Option<String>,
+
+    /// standby 미러링/헬스체크 간격 (초)
+    #[arg(long, default_value_t = 5)]
+    mirror_interval: u64,
+
+    /// standby 승격 기준 (연속 헬스체크 실패 횟수)
+    #[arg(long, default_value_t = DEFAULT_FAILURE_THRESHOLD)]
+    failover_after: u32,
+}
+
+/// primary 헬스체크 후 커서 이후 제출과 리스/등록 노드 상태 조회 (primary가 unhealthy면 오류)
+async fn mirror_from_primary(
+    primary_url: &str,
+    cursor: &mut MirrorCursor,
+) -> Result<(Vec<LedgerEntry>, ReplicationStateResponse)> {
+    let mut client = OracleServiceClient::connect(primary_url.to_string()).await?;
+    let health = client
+        .health_check(Request::new(HealthRequest {
+            node_id: String::new(),
+        }))
+        .await?
+        .into_inner();
+    if !health.healthy {
+        anyhow::bail!("primary reports unhealthy");
+    }
+    let submissions = client
+        .query_submissions(Request::new(SubmissionQueryRequest {
+            node_id: None,
+            exchange: None,
+            from: cursor.since(),
+            to: 0,
+            limit: 0,
+        }))
+        .await?
+        .into_inner()
+        .submissions;
+    let state = client
+        .get_replication_state(Request::new(ReplicationStateRequest {}))
+        .await?
+        .into_inner();
+    let entries = cursor.fresh(
+        submissions
+            .into_iter()
+            .map(|record| LedgerEntry {
+                node_id: record.node_id,
+                exchange: record.exchange,
+                price_cents: record.price_cents,
+                timestamp: record.timestamp,
+                nonce: record.nonce,
+                degraded: record.degraded,
+                backfilled: record.backfilled,
+                signature: record.signature,
+                received_at: record.received_at,
+            })
+            .collect(),
+    );
+    Ok((entries, state))
+}
+
+/// 요청의 서명자 번호 검사
+fn threshold_signer(signer: u32) -> Result<u16, ThresholdError> {
+    u16::try_from(signer)
+        .ok()
+        .filter(|&signer| signer > 0)
+        .ok_or_else(|| ThresholdError::InvalidParameters(format!("invalid signer {}", signer)))
+}
+
+/// compressed hex nonce 커밋먼트 해석
+fn parse_point(hex_point: &str) -> Result<NonceCommitment, ThresholdError> {
+    hex::decode(hex_point)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| NonceCommitment::deserialize(&bytes).map_err(|e| e.to_string()))
+        .map_err(|e| ThresholdError::InvalidParameters(format!("invalid commitment {}: {}", hex_point, e)))
+}
+
+/// 요청의 nonce 커밋먼트 해석
+fn parse_commitment(commitment: &ThresholdNonceCommitment) -> Result<(u16, SigningCommitments), ThresholdError> {
+    Ok((
+        threshold_signer(commitment.signer)?,
+        SigningCommitments::new(parse_point(&commitment.hiding)?, parse_point(&commitment.binding)?),
+    ))
+}
+
+/// 확정된 서명자 커밋먼트를 응답 형식으로
+fn encode_commitment(signer: u16, commitment: &SigningCommitments) -> Result<ThresholdNonceCommitment, ThresholdError> {
+    let point = |point: &NonceCommitment| {
+        point
+            .serialize()
+            .map(hex::encode)
+            .map_err(|e| ThresholdError::Crypto(e.to_string()))
+    };
+    Ok(ThresholdNonceCommitment {
+        signer: signer as u32,
+        hiding: point(commitment.hiding())?,
+        binding: point(commitment.binding())?,
+    })
+}
+
+/// BIP340 서명 (64바이트 hex)
+fn signature_hex(signature: &frost_secp256k1_tr::Signature) -> Result<String, ThresholdError> {
+    signature
+        .serialize()
+        .map(hex::encode)
+        .map_err(|e| ThresholdError::Crypto(e.to_string()))
+}
+
+/// 임계 서명 미설정 또는 미등록 노드
+fn threshold_unavailable(node_id: &str) -> Status {
+    if node_id.is_empty() {
+        Status::failed_precondition("Threshold signing is not configured")
+    } else {
+        Status::unauthenticated(format!(
+            "Threshold signing is not configured or node {} is not registered",
+            node_id
+        ))
+    }
+}
+
+/// 임계 서명 오류 → gRPC 상태
+fn threshold_status(e: &ThresholdError) -> Status {
+    match e {
+        ThresholdError::RoundNotFound(_) => Status::not_found(e.to_string()),
+        ThresholdError::InvalidShare(_) | ThresholdError::InvalidProof(_) => {
+            Status::permission_denied(e.to_string())
+        }
+        ThresholdError::InsufficientSigners { .. } => Status::failed_precondition(e.to_string()),
+        ThresholdError::Crypto(_) => Status::internal(e.to_string()),
+        _ => Status::invalid_argument(e.to_string()),
+    }
+}
+
+/// `now` 이전 (포함) 가장 최근 정산 시각 (매일 08:00 UTC)
+fn last_settlement_time(now: u64) -> u64 {
+    ExpiryCalendar::default()
+        .expiries(now)
+        .first()
+        .map(|next| next.timestamp - 24 * 60 * 60)
+        .unwrap_or(0)
+}
+
+/// 설정 파일이 없을 때 사용하는 기본 합의 파라미터 (2/3, 5%)
+fn default_consensus_config() -> ConsensusConfig {
+    ConsensusConfig {
+        defaults: ConsensusParams {
+            min_consensus_ratio: 0.66,
+            max_price_deviation: 0.05,
+        },
+        ..ConsensusConfig::default()
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args = Args::parse();
+
     // 로깅 초기화
-    tracing_subscriber::fmt::init();
+    let _tracing = oracle_vm_common::init_tracing("aggregator");
+
+    let consensus_config = match ConsensusConfig::load_from_file(&args.consensus_config) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("⚠️ Using default consensus config: {}", e);
+            default_consensus_config()
+        }
+    };
+    let consensus_config = ConsensusConfigHandle::new(consensus_config);
 
     info!("🚀 Starting gRPC Aggregator on port 50051...");
 
     let addr = "0.0.0.0:50051".parse().unwrap();
-    let aggregator_service = AggregatorService::new();
+    let event_bus = EventBus::new();
+    let events = event_bus.subscribe();
+    let retention_secs = args.submission_retention_days * 24 * 60 * 60;
+    let ledger = match &args.submission_ledger {
+        Some(path) => SubmissionLedger::open(path, retention_secs)?,
+        None => SubmissionLedger::new(retention_secs),
+    };
+    info!(
+        "🗄️ Submission ledger: {} entries, retention {} days",
+        ledger.len(),
+        args.submission_retention_days
+    );
+    let mut registry = match &args.node_registry {
+        Some(path) => NodeRegistry::open(path)?,
+        None => NodeRegistry::new(),
+    };
+    match (&args.node_allowlist, args.open_registration) {
+        (Some(path), _) => {
+            let allowlist = NodeAllowlist::load(path)?;
+            info!("🔐 Node allowlist {}: {} nodes", path, allowlist.nodes.len());
+            registry = registry.with_allowlist(allowlist);
+        }
+        (None, true) => warn!("⚠️ --open-registration: any node can register and submit"),
+        (None, false) => {
+            anyhow::bail!("--node-allowlist is required (or --open-registration for local development)")
+        }
+    }
+    // 레지스트리 파일이 뒤처져 있어도 원장에 남은 nonce는 다시 받지 않음
+    registry.observe(&ledger.query(&SubmissionQuery::default()));
+    info!("🔑 Node registry: {} registered nodes", registry.len());
+    // 임시 키로 서명한 합의 증명은 재시작 후 검증할 수 없으므로 로컬 개발 실행에서만 허용
+    let keys: Arc<dyn KeyStore> = match &args.signing_key {
+        Some(hex_key) => Arc::new(MemoryKeyStore::with_key(AGGREGATOR_KEY, hex_key.parse::<SecretKey>()?)),
+        None => open_service_key_store(args.key_store.as_deref(), args.open_registration)?,
+    };
+    info!(
+        "🔏 Consensus proof signing key: {}",
+        keys.public_key(AGGREGATOR_KEY)?
+    );
+    let operator_auth = OperatorAuth::new(args.operator_token_hash.clone())?;
+    if !operator_auth.is_configured() {
+        warn!("⚠️ No --operator-token-hash configured, ResumeTrading and ClearQuarantine are disabled");
+    }
+    let mut aggregator_service =
+        AggregatorService::new(event_bus, consensus_config, args.consensus_config.clone())
+            .with_operator_auth(operator_auth)
+            .with_submission_ledger(ledger)
+            .with_node_registry(registry)
+            .with_max_lease_ttl(args.max_lease_ttl)
+            .with_key_store(keys)
+            .with_settlement_policy(
+                args.backfill_policy,
+                SettlementQuorum {
+                    nodes_per_exchange: args.settlement_node_quorum.max(1),
+                    exchanges: args.settlement_exchange_quorum.max(1),
+                },
+            );
+    if let Some(path) = &args.threshold_group {
+        let group: GroupKey = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        info!(
+            "🔏 Threshold attestations: {}-of-{} under {}",
+            group.threshold,
+            group.participants(),
+            group.x_only()
+        );
+        aggregator_service = aggregator_service.with_threshold_group(group);
+    }
+    if let Some(primary_url) = &args.replica_of {
+        info!(
+            "🪞 Standby of {}: mirroring every {}s, promoting after {} failed health checks",
+            primary_url, args.mirror_interval, args.failover_after
+        );
+        aggregator_service = aggregator_service.with_replica_of(primary_url.clone());
+    }
+    let aggregator_service = Arc::new(aggregator_service);
+
+    // Ctrl-C / SIGTERM: 새 RPC를 받지 않고, 진행 중인 RPC와 원장 정리를 마친 뒤 종료
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals();
+
+    // 보존 기간이 지난 제출 정리 (1시간마다, 원장 파일을 다시 쓰므로 종료 시 끝까지 마침)
+    {
+        let service = aggregator_service.clone();
+        let mut signal = shutdown.signal();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = signal.recv() => break,
+                }
+                let Some(_work) = signal.begin_work() else {
+                    break;
+                };
+                service.prune_submissions();
+            }
+        });
+    }
+
+    // 매 정산 시각 직후 합의 증명 생성 (늦게 도착한 제출을 위해 30초 대기)
+    {
+        let service = aggregator_service.clone();
+        let mut signal = shutdown.signal();
+        tokio::spawn(async move {
+            loop {
+                let now = Utc::now().timestamp() as u64;
+                let settlement_time = last_settlement_time(now) + 24 * 60 * 60;
+                let wait = std::time::Duration::from_secs((settlement_time + 30).saturating_sub(now));
+                tokio::select! {
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = signal.recv() => break,
+                }
+                match service.consensus_proof(settlement_time) {
+                    Ok(proof) => info!(
+                        "🧾 Consensus proof for {}: ${} from {} submissions",
+                        settlement_time,
+                        format_cents(proof.settlement_price()),
+                        proof.submissions.len()
+                    ),
+                    Err(e) => warn!("❌ Consensus proof for {} failed: {}", settlement_time, e),
+                }
+            }
+        });
+    }
+
+    // standby: primary 제출 미러링, 헬스체크가 연속 실패하면 승격
+    if let Role::Standby { primary_url } = aggregator_service.role() {
+        let service = aggregator_service.clone();
+        let mut signal = shutdown.signal();
+        let period = std::time::Duration::from_secs(args.mirror_interval.max(1));
+        let mut monitor = FailoverMonitor::new(args.failover_after);
+        tokio::spawn(async move {
+            let mut cursor = MirrorCursor::new();
+            let mut interval = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = signal.recv() => break,
+                }
+                let healthy = match tokio::time::timeout(period, mirror_from_primary(&primary_url, &mut cursor)).await {
+                    Ok(Ok((entries, state))) => {
+                        service.mirror(entries);
+                        service.mirror_state(state);
+                        true
+                    }
+                    Ok(Err(e)) => {
+                        warn!("⚠️ Primary {} health check failed: {}", primary_url, e);
+                        false
+                    }
+                    Err(_) => {
+                        warn!("⚠️ Primary {} health check timed out", primary_url);
+                        false
+                    }
+                };
+                if monitor.observe(healthy) {
+                    service.promote();
+                    break;
+                }
+                if !healthy {
+                    warn!("⚠️ Primary unreachable ({} consecutive failures)", monitor.failures());
+                }
+            }
+        });
+    }
+
+    // SIGHUP 수신 시 합의 설정 리로드
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let service = aggregator_service.clone();
+        let mut hangup = signal(SignalKind::hangup())?;
+        tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                if let Err(e) = service.reload_consensus_from_file() {
+                    warn!("❌ Consensus config reload failed: {}", e);
+                }
+            }
+        });
+    }
+
+    // 거래 중단/재개 이벤트 로깅
+    tokio::task::spawn_blocking(move || {
+        for event in events {
+            match event {
+                SystemEvent::TradingHalted {
+                    reason, resume_at, ..
+                } => error!("🛑 TRADING HALTED: {} (resume at: {:?})", reason, resume_at),
+                SystemEvent::TradingResumed { manual, .. } => {
+                    info!("▶️ Trading resumed (manual: {})", manual)
+                }
+            }
+        }
+    });
 
     info!("🔗 gRPC Aggregator listening on {}", addr);
     info!("📋 Available gRPC methods:");
+    info!("   - RegisterNode: 노드 공개키 등록");
     info!("   - SubmitPrice: 가격 데이터 제출");
     info!("   - HealthCheck: 상태체크");
     info!("   - GetAggregatedPrice: 집계 가격 조회");
+    info!("   - SubmitVolSurface: IV 곡면 제출");
+    info!("   - GetVolSurface: IV 곡면 조회");
+    info!("   - GetTradingStatus: 거래 중단 상태 조회");
+    info!("   - ResumeTrading: 거래 수동 재개");
+    info!("   - ReloadConsensusConfig: 합의 설정 리로드");
+    info!("   - ListExchangeReputation: 거래소 평판/격리 조회");
+    info!("   - ClearQuarantine: 거래소 격리 해제");
+    info!("   - AcquireLease: 거래소 제출 리스 획득/갱신");
+    info!("   - QuerySubmissions: 노드 제출 원장 조회");
+    info!("   - GetDailyCommitment: 일일 제출 커밋먼트 조회");
+    info!("   - GetConsensusProof: 정산 시각 합의 증명 조회");
+    info!("   - GetReplicationState: standby 미러링용 리스/등록 노드 조회");
+    info!("   (gRPC server reflection 활성화: grpcurl {} list)", addr);
+
+    // 서버 리플렉션 (proto 파일 없이 grpcurl 등으로 API 조회)
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(oracle_vm_proto::FILE_DESCRIPTOR_SET)
+        .build_v1()?;
 
+    let mut signal = shutdown.signal();
     Server::builder()
-        .add_service(OracleServiceServer::new(aggregator_service))
-        .serve(addr)
+        .trace_fn(oracle_vm_common::grpc_request_span)
+        .add_service(reflection)
+        .add_service(OracleServiceServer::from_arc(aggregator_service))
+        .serve_with_shutdown(addr, async move { signal.recv().await })
         .await?;
 
+    info!("🛑 Shutting down, waiting for submission ledger maintenance...");
+    let timeout = std::time::Duration::from_secs(30);
+    if !shutdown.drain(timeout).await {
+        warn!("⚠️ Ledger maintenance still running after {:?}", timeout);
+    }
+    info!("👋 gRPC Aggregator stopped");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_auth::AllowedNode;
+    use crate::threshold::tests::{dkg, group};
+    use btcfi_contracts::{ContractError, SettlementError, SimpleContractManager, TradingStatusSync};
+    use oracle_node::grpc_client::{GrpcAggregatorClient, NODE_KEY};
+    use oracle_node::threshold::{RoundStep, ThresholdParticipant};
+    use oracle_vm_common::crypto::{generate_keypair, sha256};
+    use oracle_vm_common::types::{OptionType, PriceData, VolQuote, VolSurface};
+    use std::collections::BTreeSet;
+    use tonic::transport::server::TcpIncoming;
+
+    async fn step((client, participant): &mut (GrpcAggregatorClient, ThresholdParticipant)) -> RoundStep {
+        participant.step(client).await.unwrap()
+    }
+
+    async fn serve(service: Arc<AggregatorService>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(OracleServiceServer::from_arc(service))
+                .serve_with_incoming(incoming),
+        );
+        url
+    }
+
+    /// IV 곡면은 등록 노드 키로 서명한 제출만 받음
+    #[tokio::test]
+    async fn test_vol_surface_requires_registered_node_signature() {
+        let (secret, public_key) = generate_keypair();
+        let allowlist = NodeAllowlist {
+            nodes: [(
+                "node-1".to_string(),
+                AllowedNode {
+                    public_key,
+                    exchanges: BTreeSet::from(["binance".to_string()]),
+                    signer: None,
+                },
+            )]
+            .into(),
+        };
+        let service = Arc::new(
+            AggregatorService::new(
+                EventBus::new(),
+                ConsensusConfigHandle::new(default_consensus_config()),
+                String::new(),
+            )
+            .with_node_registry(NodeRegistry::new().with_allowlist(allowlist)),
+        );
+        let url = serve(service.clone()).await;
+
+        // 서명 없는 헐값 곡면은 거부하고 저장하지 않음
+        let now = Utc::now().timestamp() as u64;
+        let forged = VolSurfaceRequest {
+            source: "deribit".to_string(),
+            node_id: "node-1".to_string(),
+            timestamp: now,
+            underlying_price: 65_000.0,
+            points: vec![VolPoint {
+                expiry: now + 86_400,
+                strike: 70_000.0,
+                is_call: true,
+                mark_iv: 0.01,
+            }],
+            nonce: u64::MAX,
+            signature: String::new(),
+        };
+        let refused = service.submit_vol_surface(Request::new(forged.clone())).await.unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::Unauthenticated);
+        let deribit = || Request::new(GetVolSurfaceRequest {
+            source: Some("deribit".to_string()),
+        });
+        assert!(!service.get_vol_surface(deribit()).await.unwrap().into_inner().success);
+
+        // 등록 노드가 서명한 곡면은 저장
+        let keys = Arc::new(MemoryKeyStore::with_key(NODE_KEY, secret));
+        let mut client = GrpcAggregatorClient::with_node_id(&url, Some("node-1"), keys).await.unwrap();
+        let surface = VolSurface {
+            pair: AssetPair::btc_usd(),
+            underlying_price: 6_500_000,
+            timestamp: Utc::now(),
+            quotes: vec![VolQuote {
+                instrument: "BTC-TEST-70000-C".to_string(),
+                expiry: Utc::now() + chrono::Duration::days(1),
+                strike: 7_000_000,
+                option_type: OptionType::Call,
+                mark_iv: 0.55,
+            }],
+            source: "deribit".to_string(),
+        };
+        client.submit_vol_surface(&surface).await.unwrap();
+        let stored = service.get_vol_surface(deribit()).await.unwrap().into_inner();
+        assert_eq!(stored.points[0].mark_iv, 0.55);
+
+        // 등록되지 않은 노드 이름으로는 제출 불가
+        let stranger = VolSurfaceRequest {
+            node_id: "node-9".to_string(),
+            ..forged
+        };
+        assert!(service.submit_vol_surface(Request::new(stranger)).await.is_err());
+    }
+
+    /// 이상 감지 중단이 폴링으로 모든 풀에 반영되고, 운영자 토큰으로만 재개됨
+    #[tokio::test]
+    async fn test_kill_switch_reaches_pools_and_resume_needs_operator_token() {
+        let service = Arc::new(
+            AggregatorService::new(
+                EventBus::new(),
+                ConsensusConfigHandle::new(default_consensus_config()),
+                String::new(),
+            )
+            .with_operator_auth(OperatorAuth::new(vec![hex::encode(sha256(b"operator-token"))]).unwrap()),
+        );
+        let url = serve(service.clone()).await;
+
+        let create = |manager: &mut SimpleContractManager, id: &str| {
+            manager.create_option(
+                id.to_string(),
+                OptionType::Call,
+                7_000_000,
+                10_000_000,
+                300_000,
+                800_000,
+                "user".to_string(),
+            )
+        };
+        let pools: Vec<_> = (0..2)
+            .map(|_| {
+                let mut manager = SimpleContractManager::new();
+                manager.add_liquidity(100_000_000).unwrap();
+                create(&mut manager, "CALL-1").unwrap();
+                Arc::new(std::sync::RwLock::new(manager))
+            })
+            .collect();
+        let mut sync = TradingStatusSync::new(&url, pools.clone());
+        assert!(sync.poll_once().await.unwrap().is_none());
+
+        // 5분 안에 20% 급등 → Aggregator 중단 → 다음 폴링에서 모든 풀 중단
+        let now = Utc::now().timestamp() as u64;
+        service.anomaly_detector.lock().unwrap().observe(Some(65_000.0), now - 60);
+        service.anomaly_detector.lock().unwrap().observe(Some(78_000.0), now);
+        assert!(matches!(
+            sync.poll_once().await.unwrap(),
+            Some(SystemEvent::TradingHalted { .. })
+        ));
+        assert!(sync.poll_once().await.unwrap().is_none());
+        for pool in &pools {
+            let mut manager = pool.write().unwrap();
+            assert!(matches!(create(&mut manager, "CALL-2"), Err(ContractError::TradingHalted(_))));
+            assert!(matches!(
+                manager.settle_option("CALL-1", 7_500_000),
+                Err(SettlementError::Postponed(_))
+            ));
+        }
+
+        // 토큰 없거나 틀린 재개 요청은 거부되고 중단 유지
+        let mut client = OracleServiceClient::connect(url.clone()).await.unwrap();
+        let resume = |token: Option<&str>| {
+            let mut request = Request::new(ResumeTradingRequest {
+                operator: "alice".to_string(),
+            });
+            if let Some(token) = token {
+                request
+                    .metadata_mut()
+                    .insert("authorization", format!("Bearer {}", token).parse().unwrap());
+            }
+            request
+        };
+        assert_eq!(
+            client.resume_trading(resume(None)).await.unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+        assert_eq!(
+            client.resume_trading(resume(Some("guess"))).await.unwrap_err().code(),
+            tonic::Code::Unauthenticated
+        );
+        assert!(sync.poll_once().await.unwrap().is_none());
+        assert!(pools[0].write().unwrap().trading_halt().is_some());
+
+        // 운영자 토큰으로 재개 → 다음 폴링에서 모든 풀 재개
+        let status = client.resume_trading(resume(Some("operator-token"))).await.unwrap().into_inner();
+        assert!(!status.halted);
+        assert!(matches!(
+            sync.poll_once().await.unwrap(),
+            Some(SystemEvent::TradingResumed { .. })
+        ));
+        for pool in &pools {
+            let mut manager = pool.write().unwrap();
+            create(&mut manager, "CALL-2").unwrap();
+            manager.settle_option("CALL-1", 7_500_000).unwrap();
+        }
+    }
+
+    /// 거래소 격리 해제도 운영자 토큰이 있어야 함
+    #[tokio::test]
+    async fn test_clear_quarantine_needs_operator_token() {
+        let service = AggregatorService::new(
+            EventBus::new(),
+            ConsensusConfigHandle::new(default_consensus_config()),
+            String::new(),
+        )
+        .with_operator_auth(OperatorAuth::new(vec![hex::encode(sha256(b"operator-token"))]).unwrap());
+        let now = Utc::now().timestamp() as u64;
+        for _ in 0..4 {
+            service.reputation.lock().unwrap().record("kraken", Observation::Deviated, now);
+        }
+        let clear = |token: Option<&str>| {
+            let mut request = Request::new(ClearQuarantineRequest {
+                exchange: "kraken".to_string(),
+                operator: "alice".to_string(),
+            });
+            if let Some(token) = token {
+                request
+                    .metadata_mut()
+                    .insert("authorization", format!("Bearer {}", token).parse().unwrap());
+            }
+            request
+        };
+
+        let refused = service.clear_quarantine(clear(None)).await.unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::Unauthenticated);
+        let refused = service.clear_quarantine(clear(Some("guess"))).await.unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::Unauthenticated);
+        assert!(service.reputation.lock().unwrap().is_quarantined("kraken", now));
+
+        service.clear_quarantine(clear(Some("operator-token"))).await.unwrap();
+        assert!(!service.reputation.lock().unwrap().is_quarantined("kraken", now));
+    }
+
+    /// 2-of-3 노드 그룹이 gRPC로 라운드를 진행해야 합의 증명이 나감
+    #[tokio::test]
+    async fn test_threshold_attestation_end_to_end() {
+        let shares = dkg(2, 3);
+        let group = group(&shares[0]);
+        let exchanges = ["binance", "coinbase", "kraken"];
+        let node_keys: Vec<(SecretKey, PublicKey)> = (0..3).map(|_| generate_keypair()).collect();
+        let allowlist = NodeAllowlist {
+            nodes: (0..3)
+                .map(|i| {
+                    let node = AllowedNode {
+                        public_key: node_keys[i].1,
+                        exchanges: BTreeSet::from([exchanges[i].to_string()]),
+                        signer: Some(shares[i].id),
+                    };
+                    (format!("node-{}", i + 1), node)
+                })
+                .collect(),
+        };
+        let service = Arc::new(
+            AggregatorService::new(
+                EventBus::new(),
+                ConsensusConfigHandle::new(default_consensus_config()),
+                String::new(),
+            )
+            .with_node_registry(NodeRegistry::new().with_allowlist(allowlist))
+            .with_threshold_group(group.clone()),
+        );
+        let url = serve(service.clone()).await;
+
+        let now = Utc::now();
+        let mut nodes = Vec::new();
+        for (i, (secret, _)) in node_keys.iter().enumerate() {
+            let keys = Arc::new(MemoryKeyStore::with_key(NODE_KEY, *secret));
+            let node_id = format!("node-{}", i + 1);
+            let mut client = GrpcAggregatorClient::with_node_id(&url, Some(&node_id), keys).await.unwrap();
+            client
+                .submit_price(&PriceData {
+                    pair: AssetPair::btc_usd(),
+                    price: 6_500_000,
+                    timestamp: now,
+                    volume: None,
+                    source: exchanges[i].to_string(),
+                    degraded: false,
+                })
+                .await
+                .unwrap();
+            nodes.push((client, ThresholdParticipant::new(shares[i].clone())));
+        }
+
+        // 그룹 서명 전에는 Aggregator 키 서명만 있는 증명을 내보내지 않음
+        let settlement_time = now.timestamp() as u64;
+        let proof_request = || Request::new(ConsensusProofRequest { settlement_time });
+        let refused = service.get_consensus_proof(proof_request()).await.unwrap_err();
+        assert_eq!(refused.code(), tonic::Code::FailedPrecondition);
+        assert!(refused.message().contains("0 of 2 shares"));
+
+        // 먼저 커밋한 두 노드가 서명자로 확정되고, 세 번째 노드는 빠짐
+        assert_eq!(step(&mut nodes[0]).await, RoundStep::Committed(settlement_time));
+        assert_eq!(
+            step(&mut nodes[1]).await,
+            RoundStep::Signed {
+                round_id: settlement_time,
+                complete: false
+            }
+        );
+        assert_eq!(step(&mut nodes[2]).await, RoundStep::Skipped(settlement_time));
+        assert!(service.get_consensus_proof(proof_request()).await.is_err());
+        assert_eq!(
+            step(&mut nodes[0]).await,
+            RoundStep::Signed {
+                round_id: settlement_time,
+                complete: true
+            }
+        );
+        assert_eq!(step(&mut nodes[0]).await, RoundStep::Idle);
+
+        let response = service.get_consensus_proof(proof_request()).await.unwrap().into_inner();
+        assert_eq!(response.median_price_cents, 6_500_000);
+        assert_eq!(response.group_pubkey, Some(group.x_only()));
+        let signature = hex::decode(response.threshold_signature.unwrap()).unwrap();
+        let signature = frost_secp256k1_tr::Signature::deserialize(&signature).unwrap();
+        let proof = hex::decode(&response.proof).unwrap();
+        let verifying_key = group.public_key_package.verifying_key();
+        assert!(verifying_key.verify(&sha256(&proof), &signature).is_ok());
+    }
+}
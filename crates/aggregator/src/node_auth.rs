@@ -0,0 +1,611 @@
+//! Oracle Node 인증 및 재전송 방지
+//!
+//! 노드는 먼저 공개키를 등록하고, 이후 모든 가격 제출에 단조 증가 nonce와
+//! 서명을 포함해야 합니다. 노드별로 최근 몇 분간의 (소스, 분) 기록을 유지해
+//! 같은 분에 대한 중복 제출도 거부합니다.
+//!
+//! 허용 목록([`NodeAllowlist`])이 설정되면 목록에 있는 노드만 목록의 공개키로
+//! 등록할 수 있고, 목록에 적힌 거래소만 제출할 수 있습니다. 등록 키와 마지막
+//! nonce는 파일에 남겨 재시작 후에도 예전 서명을 다시 받아들이지 않습니다.
+
+use crate::submission_ledger::LedgerEntry;
+use anyhow::{bail, Context, Result};
+use oracle_vm_common::crypto::{
+    backfill_submission_payload, node_registration_payload, price_submission_payload, verify_signature,
+    PublicKey, Signature,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::warn;
+
+/// 중복 제출 검사 구간 (분)
+const DUPLICATE_WINDOW_MINUTES: u64 = 10;
+
+/// 실시간 제출 timestamp가 과거로 허용되는 범위 (가격 유효 시간과 동일)
+pub const MAX_SUBMISSION_AGE_SECS: u64 = 120;
+
+/// 노드 시계가 Aggregator보다 앞설 수 있는 범위
+pub const MAX_CLOCK_SKEW_SECS: u64 = 30;
+
+/// 검증 대상 제출 데이터
+pub struct Submission<'a> {
+    pub node_id: &'a str,
+    pub source: &'a str,
+    /// USD 센트 (서명 payload와 동일한 정수 값)
+    pub price_cents: u64,
+    pub timestamp: u64,
+    pub nonce: u64,
+    pub degraded: bool,
+    /// 과거 K-line으로 채운 제출 (백필 payload로 서명)
+    pub backfilled: bool,
+    pub signature: Option<&'a str>,
+}
+
+/// 허용 목록의 노드 하나
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AllowedNode {
+    /// secp256k1 공개키 (compressed hex)
+    pub public_key: PublicKey,
+    /// 제출할 수 있는 거래소 (대체 거래소 포함)
+    pub exchanges: BTreeSet<String>,
+    /// 임계 서명 DKG 참가자 번호 (서명 그룹에 속한 노드만)
+    #[serde(default)]
+    pub signer: Option<u16>,
+}
+
+/// 운영자가 관리하는 노드 허용 목록 (TOML, `[nodes.<node_id>]`)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct NodeAllowlist {
+    #[serde(default)]
+    pub nodes: HashMap<String, AllowedNode>,
+}
+
+impl NodeAllowlist {
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read node allowlist {}", path))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse node allowlist {}", path))
+    }
+}
+
+/// 노드별 재전송 방지 상태
+#[derive(Default)]
+struct ReplayState {
+    last_nonce: u64,
+    /// 최근 제출한 (분, 소스) 기록
+    recent: VecDeque<(u64, String)>,
+}
+
+impl ReplayState {
+    fn is_duplicate(&mut self, minute: u64, source: &str) -> bool {
+        self.recent
+            .retain(|(seen, _)| seen + DUPLICATE_WINDOW_MINUTES > minute);
+        self.recent
+            .iter()
+            .any(|(seen, seen_source)| *seen == minute && seen_source == source)
+    }
+
+    fn accept(&mut self, nonce: u64, minute: u64, source: &str) {
+        self.last_nonce = self.last_nonce.max(nonce);
+        self.recent.push_back((minute, source.to_string()));
+    }
+}
+
+/// 파일에 남기는 등록 상태
+#[derive(Default, Serialize, Deserialize)]
+struct PersistedRegistry {
+    keys: BTreeMap<String, PublicKey>,
+    nonces: BTreeMap<String, u64>,
+}
+
+/// 등록 노드 목록
+#[derive(Default)]
+pub struct NodeRegistry {
+    keys: HashMap<String, PublicKey>,
+    replay: HashMap<String, ReplayState>,
+    /// 없으면 누구나 등록 가능 (로컬 개발용)
+    allowlist: Option<NodeAllowlist>,
+    path: Option<PathBuf>,
+}
+
+impl NodeRegistry {
+    /// 메모리 등록부 (허용 목록 없음)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 파일 등록부 (기존 파일이 있으면 키와 nonce를 불러옴)
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut registry = Self::new();
+        if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read node registry {}", path.display()))?;
+            let persisted: PersistedRegistry = serde_json::from_str(&content)
+                .with_context(|| format!("Corrupt node registry {}", path.display()))?;
+            registry.keys = persisted.keys.into_iter().collect();
+            registry.replay = persisted
+                .nonces
+                .into_iter()
+                .map(|(node_id, last_nonce)| {
+                    (
+                        node_id,
+                        ReplayState {
+                            last_nonce,
+                            recent: VecDeque::new(),
+                        },
+                    )
+                })
+                .collect();
+        }
+        registry.path = Some(path);
+        Ok(registry)
+    }
+
+    /// 허용 목록 지정 (목록 밖 노드나 다른 키로 등록된 노드는 제출할 수 없음)
+    pub fn with_allowlist(mut self, allowlist: NodeAllowlist) -> Self {
+        self.keys.retain(|node_id, key| {
+            allowlist
+                .nodes
+                .get(node_id)
+                .is_some_and(|allowed| allowed.public_key == *key)
+        });
+        self.allowlist = Some(allowlist);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// 공개키 등록 (서명으로 키 소유 증명), 마지막 nonce 반환
+    ///
+    /// 같은 키로 재등록하면 nonce 상태를 유지하고, 다른 키로는 덮어쓸 수 없습니다.
+    pub fn register(&mut self, node_id: &str, public_key: &str, signature: &str) -> Result<u64> {
+        let public_key = PublicKey::from_str(public_key)
+            .map_err(|e| anyhow::anyhow!("Invalid public key: {}", e))?;
+        let signature = Signature::from_str(signature)
+            .map_err(|e| anyhow::anyhow!("Invalid signature encoding: {}", e))?;
+
+        let payload = node_registration_payload(node_id, &public_key);
+        if !verify_signature(&payload, &signature, &public_key)? {
+            bail!("Registration signature does not match public key");
+        }
+
+        if let Some(allowlist) = &self.allowlist {
+            match allowlist.nodes.get(node_id) {
+                None => bail!("Node {} is not on the operator allowlist", node_id),
+                Some(allowed) if allowed.public_key != public_key => {
+                    bail!("Node {} key does not match the operator allowlist", node_id)
+                }
+                Some(_) => {}
+            }
+        }
+
+        let last_nonce = self.last_nonce(node_id);
+        match self.keys.get(node_id) {
+            Some(key) if *key != public_key => {
+                bail!("Node {} is already registered with a different key", node_id)
+            }
+            Some(_) => Ok(last_nonce),
+            None => {
+                self.keys.insert(node_id.to_string(), public_key);
+                self.persist();
+                Ok(last_nonce)
+            }
+        }
+    }
+
+    pub fn is_registered(&self, node_id: &str) -> bool {
+        self.keys.contains_key(node_id)
+    }
+
+    /// 등록된 노드 공개키 (standby처럼 등록을 받은 적 없으면 허용 목록의 키)
+    pub fn public_key(&self, node_id: &str) -> Option<PublicKey> {
+        self.keys.get(node_id).copied().or_else(|| {
+            self.allowlist
+                .as_ref()
+                .and_then(|allowlist| allowlist.nodes.get(node_id))
+                .map(|allowed| allowed.public_key)
+        })
+    }
+
+    /// 노드가 이 거래소를 제출할 수 있는지 (허용 목록이 없으면 모두 허용)
+    pub fn is_assigned(&self, node_id: &str, exchange: &str) -> bool {
+        self.allowlist.as_ref().is_none_or(|allowlist| {
+            allowlist
+                .nodes
+                .get(node_id)
+                .is_some_and(|allowed| allowed.exchanges.contains(exchange))
+        })
+    }
+
+    /// 노드에 배정된 임계 서명 참가자 번호 (허용 목록에 적힌 노드만)
+    pub fn threshold_signer(&self, node_id: &str) -> Option<u16> {
+        self.allowlist.as_ref()?.nodes.get(node_id)?.signer
+    }
+
+    /// 등록 키 서명 확인 (상태는 바꾸지 않음, 멱등 요청용)
+    pub fn verify_signed(&self, node_id: &str, payload: &[u8], signature: &str) -> Result<()> {
+        let Some(public_key) = self.keys.get(node_id) else {
+            bail!("Unknown node {}: register first", node_id);
+        };
+        check_signature(payload, signature, public_key)
+    }
+
+    fn last_nonce(&self, node_id: &str) -> u64 {
+        self.replay.get(node_id).map(|state| state.last_nonce).unwrap_or(0)
+    }
+
+    /// 가격 제출 외 노드 요청(리스 등)의 서명/nonce 검증 후 nonce 갱신
+    pub fn verify_request(&mut self, node_id: &str, payload: &[u8], nonce: u64, signature: &str) -> Result<()> {
+        self.verify_signed(node_id, payload, signature)?;
+
+        let state = self.replay.entry(node_id.to_string()).or_default();
+        if nonce <= state.last_nonce {
+            bail!("Replayed nonce {} (last accepted {})", nonce, state.last_nonce);
+        }
+        state.last_nonce = nonce;
+        self.persist();
+        Ok(())
+    }
+
+    /// 제출 검증 후 nonce/중복 기록 갱신
+    ///
+    /// 실시간 제출은 timestamp가 `now` 기준 [`MAX_SUBMISSION_AGE_SECS`] 이전부터
+    /// [`MAX_CLOCK_SKEW_SECS`] 이후까지만 받고, 백필 제출도 미래 시각은 거부합니다.
+    pub fn verify_submission(&mut self, submission: &Submission, now: u64) -> Result<()> {
+        let Some(public_key) = self.keys.get(submission.node_id) else {
+            bail!("Unknown node {}: register first", submission.node_id);
+        };
+
+        let Some(signature) = submission.signature else {
+            bail!("Missing signature");
+        };
+
+        let payload = if submission.backfilled {
+            backfill_submission_payload
+        } else {
+            price_submission_payload
+        };
+        let payload = payload(
+            submission.node_id,
+            submission.source,
+            submission.price_cents,
+            submission.timestamp,
+            submission.nonce,
+            submission.degraded,
+        );
+        check_signature(&payload, signature, public_key)?;
+
+        if submission.timestamp > now + MAX_CLOCK_SKEW_SECS {
+            bail!("Submission timestamp {} is ahead of {}", submission.timestamp, now);
+        }
+        if !submission.backfilled && now.saturating_sub(submission.timestamp) > MAX_SUBMISSION_AGE_SECS {
+            bail!(
+                "Stale submission timestamp {} ({}s old)",
+                submission.timestamp,
+                now - submission.timestamp
+            );
+        }
+
+        let state = self.replay.entry(submission.node_id.to_string()).or_default();
+        if submission.nonce <= state.last_nonce {
+            bail!(
+                "Replayed nonce {} (last accepted {})",
+                submission.nonce,
+                state.last_nonce
+            );
+        }
+
+        let minute = submission.timestamp / 60;
+        if state.is_duplicate(minute, submission.source) {
+            bail!(
+                "Duplicate submission for {} in minute {}",
+                submission.source,
+                minute
+            );
+        }
+
+        state.accept(submission.nonce, minute, submission.source);
+        self.persist();
+        Ok(())
+    }
+
+    /// 등록 노드 (노드, 키, 마지막 nonce), standby 미러링용
+    pub fn registered(&self) -> Vec<(String, PublicKey, u64)> {
+        self.keys
+            .iter()
+            .map(|(node_id, key)| (node_id.clone(), *key, self.last_nonce(node_id)))
+            .collect()
+    }
+
+    /// primary의 등록 노드 반영 (허용 목록과 다르거나 이미 다른 키로 묶인 노드는 건너뜀)
+    pub fn mirror(&mut self, nodes: Vec<(String, PublicKey, u64)>) {
+        if nodes.is_empty() {
+            return;
+        }
+        for (node_id, key, last_nonce) in nodes {
+            let allowed = self.allowlist.as_ref().is_none_or(|allowlist| {
+                allowlist
+                    .nodes
+                    .get(&node_id)
+                    .is_some_and(|allowed| allowed.public_key == key)
+            });
+            if !allowed || self.keys.get(&node_id).is_some_and(|bound| *bound != key) {
+                warn!("⚠️ Not mirroring node {}: key does not match", node_id);
+                continue;
+            }
+            self.keys.insert(node_id.clone(), key);
+            let state = self.replay.entry(node_id).or_default();
+            state.last_nonce = state.last_nonce.max(last_nonce);
+        }
+        self.persist();
+    }
+
+    /// 원장에 이미 있는 제출의 nonce/중복 기록 반영 (재시작, standby 미러링)
+    pub fn observe(&mut self, entries: &[LedgerEntry]) {
+        if entries.is_empty() {
+            return;
+        }
+        for entry in entries {
+            let state = self.replay.entry(entry.node_id.clone()).or_default();
+            let minute = entry.timestamp / 60;
+            if state.is_duplicate(minute, &entry.exchange) {
+                state.last_nonce = state.last_nonce.max(entry.nonce);
+            } else {
+                state.accept(entry.nonce, minute, &entry.exchange);
+            }
+        }
+        self.persist();
+    }
+
+    /// 등록 상태를 파일에 다시 씀 (실패해도 메모리 상태는 유지)
+    fn persist(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let persisted = PersistedRegistry {
+            keys: self.keys.iter().map(|(id, key)| (id.clone(), *key)).collect(),
+            nonces: self
+                .replay
+                .iter()
+                .map(|(id, state)| (id.clone(), state.last_nonce))
+                .collect(),
+        };
+        let tmp = path.with_extension("tmp");
+        let written = serde_json::to_vec_pretty(&persisted)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| {
+                let mut file = File::create(&tmp)?;
+                file.write_all(&bytes)?;
+                file.sync_all()?;
+                fs::rename(&tmp, path)?;
+                Ok(())
+            });
+        if let Err(e) = written {
+            warn!("❌ Failed to persist node registry {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// 노드 키 서명 확인
+fn check_signature(payload: &[u8], signature: &str, public_key: &PublicKey) -> Result<()> {
+    let signature = Signature::from_str(signature)
+        .map_err(|e| anyhow::anyhow!("Invalid signature encoding: {}", e))?;
+    if !verify_signature(payload, &signature, public_key)? {
+        bail!("Invalid signature");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oracle_vm_common::crypto::{generate_keypair, lease_request_payload, sign_data, SecretKey};
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn register(registry: &mut NodeRegistry, node_id: &str) -> SecretKey {
+        let (secret_key, public_key) = generate_keypair();
+        let signature =
+            sign_data(&node_registration_payload(node_id, &public_key), &secret_key).unwrap();
+        registry
+            .register(node_id, &public_key.to_string(), &signature.to_string())
+            .unwrap();
+        secret_key
+    }
+
+    fn signed(secret_key: &SecretKey, timestamp: u64, nonce: u64) -> String {
+        let payload =
+            price_submission_payload("node-1", "binance", 7_000_000, timestamp, nonce, false);
+        sign_data(&payload, secret_key).unwrap().to_string()
+    }
+
+    fn submission(signature: &str, timestamp: u64, nonce: u64) -> Submission<'_> {
+        Submission {
+            node_id: "node-1",
+            source: "binance",
+            price_cents: 7_000_000,
+            timestamp,
+            nonce,
+            degraded: false,
+            backfilled: false,
+            signature: Some(signature),
+        }
+    }
+
+    #[test]
+    fn test_valid_submission_then_replay_rejected() {
+        let mut registry = NodeRegistry::new();
+        let key = register(&mut registry, "node-1");
+
+        let sig = signed(&key, NOW, 1);
+        registry.verify_submission(&submission(&sig, NOW, 1), NOW).unwrap();
+
+        // 같은 요청 재전송
+        assert!(registry.verify_submission(&submission(&sig, NOW, 1), NOW).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_minute_rejected() {
+        let mut registry = NodeRegistry::new();
+        let key = register(&mut registry, "node-1");
+
+        let first = signed(&key, NOW, 1);
+        registry.verify_submission(&submission(&first, NOW, 1), NOW).unwrap();
+
+        // 새 nonce지만 같은 분
+        let same_minute = signed(&key, NOW + 10, 2);
+        assert!(registry
+            .verify_submission(&submission(&same_minute, NOW + 10, 2), NOW + 10)
+            .is_err());
+
+        let next_minute = signed(&key, NOW + 60, 3);
+        registry
+            .verify_submission(&submission(&next_minute, NOW + 60, 3), NOW + 60)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_timestamp_outside_window_rejected() {
+        let mut registry = NodeRegistry::new();
+        let key = register(&mut registry, "node-1");
+
+        let stale = signed(&key, NOW - MAX_SUBMISSION_AGE_SECS - 1, 1);
+        assert!(registry
+            .verify_submission(&submission(&stale, NOW - MAX_SUBMISSION_AGE_SECS - 1, 1), NOW)
+            .is_err());
+        let ahead = signed(&key, NOW + MAX_CLOCK_SKEW_SECS + 1, 2);
+        assert!(registry
+            .verify_submission(&submission(&ahead, NOW + MAX_CLOCK_SKEW_SECS + 1, 2), NOW)
+            .is_err());
+        let fresh = signed(&key, NOW - 30, 3);
+        registry.verify_submission(&submission(&fresh, NOW - 30, 3), NOW).unwrap();
+    }
+
+    #[test]
+    fn test_requests_share_the_nonce_counter() {
+        let mut registry = NodeRegistry::new();
+        let key = register(&mut registry, "node-1");
+
+        let payload = lease_request_payload("node-1", "binance", 180, 1);
+        let lease = sign_data(&payload, &key).unwrap().to_string();
+        registry.verify_request("node-1", &payload, 1, &lease).unwrap();
+        assert!(registry.verify_request("node-1", &payload, 1, &lease).is_err());
+        // 다른 노드 이름이나 바꾼 TTL로는 쓸 수 없음
+        let longer = lease_request_payload("node-1", "binance", 86_400, 2);
+        assert!(registry.verify_request("node-1", &longer, 2, &lease).is_err());
+
+        // 리스에 쓴 nonce는 가격 제출에도 다시 쓸 수 없음
+        let sig = signed(&key, NOW, 1);
+        assert!(registry.verify_submission(&submission(&sig, NOW, 1), NOW).is_err());
+    }
+
+    #[test]
+    fn test_unregistered_and_forged_rejected() {
+        let mut registry = NodeRegistry::new();
+        let (other_key, _) = generate_keypair();
+        let sig = signed(&other_key, NOW, 1);
+        assert!(registry.verify_submission(&submission(&sig, NOW, 1), NOW).is_err());
+
+        register(&mut registry, "node-1");
+        assert!(registry.verify_submission(&submission(&sig, NOW, 1), NOW).is_err());
+    }
+
+    #[test]
+    fn test_reregister_with_different_key_rejected() {
+        let mut registry = NodeRegistry::new();
+        register(&mut registry, "node-1");
+
+        let (secret_key, public_key) = generate_keypair();
+        let signature =
+            sign_data(&node_registration_payload("node-1", &public_key), &secret_key).unwrap();
+        assert!(registry
+            .register("node-1", &public_key.to_string(), &signature.to_string())
+            .is_err());
+    }
+
+    #[test]
+    fn test_allowlist_gates_registration_and_exchanges() {
+        let (secret_key, public_key) = generate_keypair();
+        let allowlist: NodeAllowlist = toml::from_str(&format!(
+            "[nodes.node-1]\npublic_key = \"{}\"\nexchanges = [\"binance\", \"coinbase\"]\nsigner = 2\n",
+            public_key
+        ))
+        .unwrap();
+        let mut registry = NodeRegistry::new().with_allowlist(allowlist);
+
+        // 목록 밖 노드, 목록과 다른 키는 키 소유를 증명해도 거부
+        let (stranger_secret, stranger_key) = generate_keypair();
+        for node_id in ["node-2", "node-1"] {
+            let signature =
+                sign_data(&node_registration_payload(node_id, &stranger_key), &stranger_secret).unwrap();
+            assert!(registry
+                .register(node_id, &stranger_key.to_string(), &signature.to_string())
+                .is_err());
+        }
+        assert_eq!(registry.public_key("node-1"), Some(public_key));
+        assert!(!registry.is_registered("node-1"));
+
+        let signature = sign_data(&node_registration_payload("node-1", &public_key), &secret_key).unwrap();
+        registry
+            .register("node-1", &public_key.to_string(), &signature.to_string())
+            .unwrap();
+        assert!(registry.is_assigned("node-1", "coinbase"));
+        assert!(!registry.is_assigned("node-1", "kraken"));
+        assert!(!registry.is_assigned("node-2", "binance"));
+        assert!(NodeRegistry::new().is_assigned("node-2", "binance"));
+        assert_eq!(registry.threshold_signer("node-1"), Some(2));
+        assert_eq!(NodeRegistry::new().threshold_signer("node-1"), None);
+    }
+
+    #[test]
+    fn test_nonces_survive_restart_and_mirroring() {
+        let path = std::env::temp_dir().join(format!("node-registry-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut registry = NodeRegistry::open(&path).unwrap();
+        let key = register(&mut registry, "node-1");
+        let sig = signed(&key, NOW, 5);
+        registry.verify_submission(&submission(&sig, NOW, 5), NOW).unwrap();
+
+        // 재시작 후에도 같은 서명은 재전송으로 거부
+        let mut reopened = NodeRegistry::open(&path).unwrap();
+        assert!(reopened.is_registered("node-1"));
+        assert!(reopened.verify_submission(&submission(&sig, NOW + 60, 5), NOW + 60).is_err());
+
+        // standby가 미러링한 제출의 nonce도 반영
+        let mut standby = NodeRegistry::new();
+        standby.observe(&[LedgerEntry {
+            node_id: "node-1".to_string(),
+            exchange: "binance".to_string(),
+            price_cents: 7_000_000,
+            timestamp: NOW,
+            nonce: 5,
+            degraded: false,
+            backfilled: false,
+            signature: Some(sig.clone()),
+            received_at: NOW,
+        }]);
+        register_with(&mut standby, "node-1", &key);
+        assert!(standby.verify_submission(&submission(&sig, NOW, 5), NOW).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    fn register_with(registry: &mut NodeRegistry, node_id: &str, secret_key: &SecretKey) {
+        let public_key = oracle_vm_common::crypto::public_key_from_secret(secret_key);
+        let signature = sign_data(&node_registration_payload(node_id, &public_key), secret_key).unwrap();
+        assert_eq!(
+            registry
+                .register(node_id, &public_key.to_string(), &signature.to_string())
+                .unwrap(),
+            5
+        );
+    }
+}
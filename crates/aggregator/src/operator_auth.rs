@@ -0,0 +1,108 @@
+//! 운영자 요청 인증
+//!
+//! 거래 재개나 거래소 격리 해제처럼 kill-switch를 푸는 요청은 gRPC 메타데이터
+//! `authorization: Bearer <token>`의 운영자 토큰을 설정된 SHA256 해시와 비교해
+//! 받습니다. 요청의 `operator` 필드는 로그에 남기는 이름일 뿐이며, 해시가 하나도
+//! 설정되지 않으면 운영자 요청을 전부 거부합니다.
+
+use anyhow::{bail, Result};
+use oracle_vm_common::crypto::{constant_time_eq, sha256};
+use tonic::{Request, Status};
+
+/// 운영자 요청 거부 사유
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorAuthError {
+    /// 운영자 토큰이 설정되지 않아 운영자 요청 비활성
+    Disabled,
+    /// `authorization: Bearer` 메타데이터 없음
+    MissingToken,
+    /// 설정된 해시와 일치하지 않는 토큰
+    InvalidToken,
+}
+
+impl From<OperatorAuthError> for Status {
+    fn from(error: OperatorAuthError) -> Self {
+        match error {
+            OperatorAuthError::Disabled => {
+                Status::permission_denied("Operator requests are disabled: no operator token configured")
+            }
+            OperatorAuthError::MissingToken => Status::unauthenticated("Missing operator token"),
+            OperatorAuthError::InvalidToken => Status::unauthenticated("Invalid operator token"),
+        }
+    }
+}
+
+/// 운영자 토큰 해시 목록
+#[derive(Debug, Clone, Default)]
+pub struct OperatorAuth {
+    token_hashes: Vec<String>,
+}
+
+impl OperatorAuth {
+    /// 토큰 해시 목록으로 생성 (64자리 hex가 아니면 거부)
+    pub fn new(token_hashes: Vec<String>) -> Result<Self> {
+        let mut hashes = Vec::with_capacity(token_hashes.len());
+        for hash in token_hashes {
+            if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                bail!("operator token hash must be 64 hex chars: {}", hash);
+            }
+            hashes.push(hash.to_ascii_lowercase());
+        }
+        Ok(Self { token_hashes: hashes })
+    }
+
+    /// 토큰이 하나라도 설정되어 있는지
+    pub fn is_configured(&self) -> bool {
+        !self.token_hashes.is_empty()
+    }
+
+    /// 요청 메타데이터의 운영자 토큰 검증 (해시를 상수 시간으로 비교)
+    pub fn authorize<T>(&self, request: &Request<T>) -> Result<(), OperatorAuthError> {
+        if !self.is_configured() {
+            return Err(OperatorAuthError::Disabled);
+        }
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(OperatorAuthError::MissingToken)?;
+        let hash = hex::encode(sha256(token.as_bytes()));
+        let found = self
+            .token_hashes
+            .iter()
+            .fold(false, |found, allowed| found | constant_time_eq(allowed.as_bytes(), hash.as_bytes()));
+        if !found {
+            return Err(OperatorAuthError::InvalidToken);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(token: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(token) = token {
+            request
+                .metadata_mut()
+                .insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_operator_token_checked_against_hashes() {
+        let auth = OperatorAuth::new(vec![hex::encode(sha256(b"s3cret")).to_ascii_uppercase()]).unwrap();
+        assert!(auth.authorize(&request(Some("s3cret"))).is_ok());
+        assert_eq!(auth.authorize(&request(Some("guess"))), Err(OperatorAuthError::InvalidToken));
+        assert_eq!(auth.authorize(&request(None)), Err(OperatorAuthError::MissingToken));
+
+        // 해시가 없으면 토큰과 무관하게 거부, 잘못된 해시는 시작 시 거부
+        let disabled = OperatorAuth::default();
+        assert_eq!(disabled.authorize(&request(Some("s3cret"))), Err(OperatorAuthError::Disabled));
+        assert!(OperatorAuth::new(vec!["abc".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! Aggregator 이중화 (primary/standby)
+//!
+//! standby는 primary의 제출 원장을 주기적으로 끌어와(QuerySubmissions) 자기
+//! 원장과 가격 창에 그대로 반영하고, 거래소 리스와 등록 노드 키/nonce도
+//! (GetReplicationState) 함께 맞춥니다. 같은 주기로 primary에 헬스체크를 보냅니다.
+//! 연속으로 응답이 없으면 스스로 primary로 승격해 제출을 받기 시작합니다.
+//! standby인 동안에는 등록/제출을 UNAVAILABLE로 거절하므로, 두 주소를 모두 아는
+//! Oracle Node는 살아 있는 쪽으로 넘어갑니다.
+//!
+//! 승격 후 예전 primary를 다시 띄울 때는 새 primary의 standby(`--replica-of`)로
+//! 띄워야 합니다. 두 프로세스가 동시에 primary가 되는 경우는 막지 않습니다.
+
+use crate::submission_ledger::LedgerEntry;
+use std::collections::HashSet;
+
+/// 늦게 도착한 제출을 놓치지 않도록 다시 조회하는 구간 (가격 유효 시간과 동일)
+pub const MIRROR_LOOKBACK_SECS: u64 = 120;
+
+/// 기본 승격 기준 (연속 헬스체크 실패 횟수)
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// 이 프로세스의 역할
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Role {
+    Primary,
+    /// primary 주소를 따라가는 대기 복제본
+    Standby { primary_url: String },
+}
+
+impl Role {
+    pub fn is_standby(&self) -> bool {
+        matches!(self, Role::Standby { .. })
+    }
+}
+
+/// primary 원장 미러링 커서
+///
+/// 원장 조회 조건은 노드 timestamp 기준이라 순서가 뒤섞여 도착할 수 있으므로,
+/// 마지막으로 본 timestamp보다 `MIRROR_LOOKBACK_SECS` 앞부터 다시 조회하고
+/// (노드, nonce)로 이미 반영한 제출을 걸러냅니다.
+#[derive(Debug, Default)]
+pub struct MirrorCursor {
+    latest: u64,
+    seen: HashSet<(String, u64)>,
+    /// `seen` 정리용 (노드, nonce, timestamp)
+    order: Vec<(String, u64, u64)>,
+}
+
+impl MirrorCursor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 다음 조회의 timestamp 하한
+    pub fn since(&self) -> u64 {
+        self.latest.saturating_sub(MIRROR_LOOKBACK_SECS)
+    }
+
+    /// 처음 보는 제출만 골라내고 커서 전진
+    pub fn fresh(&mut self, entries: Vec<LedgerEntry>) -> Vec<LedgerEntry> {
+        let fresh: Vec<LedgerEntry> = entries
+            .into_iter()
+            .filter(|entry| self.seen.insert((entry.node_id.clone(), entry.nonce)))
+            .collect();
+        for entry in &fresh {
+            self.latest = self.latest.max(entry.timestamp);
+            self.order.push((entry.node_id.clone(), entry.nonce, entry.timestamp));
+        }
+
+        // 다시 조회하지 않을 구간은 잊음
+        let since = self.since();
+        let seen = &mut self.seen;
+        self.order.retain(|(node_id, nonce, timestamp)| {
+            let keep = *timestamp >= since;
+            if !keep {
+                seen.remove(&(node_id.clone(), *nonce));
+            }
+            keep
+        });
+        fresh
+    }
+}
+
+/// primary 헬스체크 결과로 승격 시점 판단
+#[derive(Debug)]
+pub struct FailoverMonitor {
+    failure_threshold: u32,
+    failures: u32,
+}
+
+impl FailoverMonitor {
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            failures: 0,
+        }
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// 헬스체크 결과 기록, 지금 승격해야 하면 true
+    pub fn observe(&mut self, healthy: bool) -> bool {
+        if healthy {
+            self.failures = 0;
+            return false;
+        }
+        self.failures += 1;
+        self.failures >= self.failure_threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(node: &str, nonce: u64, timestamp: u64) -> LedgerEntry {
+        LedgerEntry {
+            node_id: node.to_string(),
+            exchange: "binance".to_string(),
+            price_cents: 6_500_000,
+            timestamp,
+            nonce,
+            degraded: false,
+            backfilled: false,
+            signature: None,
+            received_at: timestamp,
+        }
+    }
+
+    #[test]
+    fn test_mirror_cursor_skips_seen_entries() {
+        let t = 1_700_000_000;
+        let mut cursor = MirrorCursor::new();
+        assert_eq!(cursor.since(), 0);
+        assert_eq!(cursor.fresh(vec![entry("n1", 1, t), entry("n2", 1, t + 5)]).len(), 2);
+        assert_eq!(cursor.since(), t + 5 - MIRROR_LOOKBACK_SECS);
+
+        // 겹치는 재조회에서는 늦게 도착한 제출만 새로 반영
+        let fresh = cursor.fresh(vec![entry("n1", 1, t), entry("n3", 1, t - 30), entry("n2", 1, t + 5)]);
+        assert_eq!(fresh, vec![entry("n3", 1, t - 30)]);
+
+        // 조회 구간을 벗어난 기록은 정리
+        cursor.fresh(vec![entry("n1", 2, t + 1_000)]);
+        assert_eq!(cursor.seen.len(), 1);
+    }
+
+    #[test]
+    fn test_failover_after_consecutive_failures() {
+        let mut monitor = FailoverMonitor::new(3);
+        assert!(!monitor.observe(false));
+        assert!(!monitor.observe(false));
+        assert!(!monitor.observe(true));
+        assert_eq!(monitor.failures(), 0);
+        assert!(!monitor.observe(false));
+        assert!(!monitor.observe(false));
+        assert!(monitor.observe(false));
+    }
+}
@@ -0,0 +1,213 @@
+//! 거래소 평판 점수 및 자동 격리
+//!
+//! 제출마다 편차/지연 여부를 관찰해 거래소별 점수(EWMA)를 갱신하고,
+//! 점수가 임계값 아래로 떨어지면 cooldown 동안 합의에서 제외합니다.
+
+use std::collections::HashMap;
+
+/// 평판 설정
+#[derive(Debug, Clone)]
+pub struct ReputationConfig {
+    /// 이전 점수 가중치 (0.8이면 최근 관찰이 20% 반영)
+    pub decay: f64,
+    /// 격리 임계 점수
+    pub quarantine_threshold: f64,
+    /// 격리 유지 시간 (초)
+    pub cooldown_secs: u64,
+    /// 이 시간보다 오래된 가격은 지연으로 간주 (초)
+    pub stale_after_secs: u64,
+}
+
+impl Default for ReputationConfig {
+    fn default() -> Self {
+        Self {
+            decay: 0.8,
+            quarantine_threshold: 0.5,
+            cooldown_secs: 600,
+            stale_after_secs: 120,
+        }
+    }
+}
+
+/// 제출 1건에 대한 관찰 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Observation {
+    Healthy,
+    Deviated,
+    Stale,
+}
+
+/// 거래소별 평판 지표
+#[derive(Debug, Clone)]
+pub struct ExchangeScore {
+    /// 0.0 ~ 1.0 (1.0 = 항상 정상)
+    pub score: f64,
+    pub observations: u64,
+    pub deviations: u64,
+    pub stale: u64,
+    pub quarantine_count: u64,
+    pub quarantined_until: Option<u64>,
+}
+
+impl Default for ExchangeScore {
+    fn default() -> Self {
+        Self {
+            score: 1.0,
+            observations: 0,
+            deviations: 0,
+            stale: 0,
+            quarantine_count: 0,
+            quarantined_until: None,
+        }
+    }
+}
+
+/// 거래소 평판 추적기
+pub struct ReputationTracker {
+    config: ReputationConfig,
+    scores: HashMap<String, ExchangeScore>,
+}
+
+impl ReputationTracker {
+    pub fn new(config: ReputationConfig) -> Self {
+        Self {
+            config,
+            scores: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &ReputationConfig {
+        &self.config
+    }
+
+    /// 관찰 결과 반영, 새로 격리되면 true
+    pub fn record(&mut self, exchange: &str, observation: Observation, now: u64) -> bool {
+        self.expire(exchange, now);
+
+        let config = &self.config;
+        let entry = self.scores.entry(exchange.to_string()).or_default();
+        entry.observations += 1;
+        match observation {
+            Observation::Healthy => {}
+            Observation::Deviated => entry.deviations += 1,
+            Observation::Stale => entry.stale += 1,
+        }
+
+        let value = if observation == Observation::Healthy { 1.0 } else { 0.0 };
+        entry.score = config.decay * entry.score + (1.0 - config.decay) * value;
+
+        if entry.quarantined_until.is_none() && entry.score < config.quarantine_threshold {
+            entry.quarantined_until = Some(now + config.cooldown_secs);
+            entry.quarantine_count += 1;
+            return true;
+        }
+
+        false
+    }
+
+    /// 격리 여부 (cooldown이 지나면 해제)
+    pub fn is_quarantined(&mut self, exchange: &str, now: u64) -> bool {
+        self.expire(exchange, now);
+        self.scores
+            .get(exchange)
+            .is_some_and(|score| score.quarantined_until.is_some())
+    }
+
+    /// 운영자 수동 해제, 격리 중이었으면 true
+    pub fn clear(&mut self, exchange: &str) -> bool {
+        match self.scores.get_mut(exchange) {
+            Some(score) if score.quarantined_until.is_some() => {
+                Self::release(score);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 전체 거래소 지표 (이름순)
+    pub fn snapshot(&mut self, now: u64) -> Vec<(String, ExchangeScore)> {
+        let exchanges: Vec<String> = self.scores.keys().cloned().collect();
+        for exchange in &exchanges {
+            self.expire(exchange, now);
+        }
+
+        let mut scores: Vec<(String, ExchangeScore)> = self
+            .scores
+            .iter()
+            .map(|(exchange, score)| (exchange.clone(), score.clone()))
+            .collect();
+        scores.sort_by(|a, b| a.0.cmp(&b.0));
+        scores
+    }
+
+    fn expire(&mut self, exchange: &str, now: u64) {
+        if let Some(score) = self.scores.get_mut(exchange) {
+            if score.quarantined_until.is_some_and(|until| now >= until) {
+                Self::release(score);
+            }
+        }
+    }
+
+    /// 격리 해제 시 점수를 최소 0.75로 복원 (재발 시 몇 번 만에 재격리)
+    fn release(score: &mut ExchangeScore) {
+        score.quarantined_until = None;
+        score.score = score.score.max(0.75);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_deviation_quarantines() {
+        let mut tracker = ReputationTracker::new(ReputationConfig::default());
+
+        // 0.8 → 0.64 → 0.512 → 0.41 (4번째에 격리)
+        for _ in 0..3 {
+            assert!(!tracker.record("kraken", Observation::Deviated, 1_000));
+        }
+        assert!(tracker.record("kraken", Observation::Deviated, 1_000));
+        assert!(tracker.is_quarantined("kraken", 1_100));
+        assert!(!tracker.is_quarantined("binance", 1_100));
+
+        let snapshot = tracker.snapshot(1_100);
+        assert_eq!(snapshot[0].1.deviations, 4);
+        assert_eq!(snapshot[0].1.quarantine_count, 1);
+    }
+
+    #[test]
+    fn test_cooldown_expires() {
+        let mut tracker = ReputationTracker::new(ReputationConfig::default());
+        for _ in 0..4 {
+            tracker.record("coinbase", Observation::Stale, 1_000);
+        }
+
+        assert!(tracker.is_quarantined("coinbase", 1_599));
+        assert!(!tracker.is_quarantined("coinbase", 1_600));
+    }
+
+    #[test]
+    fn test_occasional_deviation_tolerated() {
+        let mut tracker = ReputationTracker::new(ReputationConfig::default());
+        // 3번 중 1번 편차 → 점수 약 0.59에서 수렴
+        for _ in 0..20 {
+            tracker.record("binance", Observation::Healthy, 1_000);
+            tracker.record("binance", Observation::Healthy, 1_000);
+            tracker.record("binance", Observation::Deviated, 1_000);
+        }
+        assert!(!tracker.is_quarantined("binance", 1_000));
+    }
+
+    #[test]
+    fn test_manual_clear() {
+        let mut tracker = ReputationTracker::new(ReputationConfig::default());
+        for _ in 0..4 {
+            tracker.record("kraken", Observation::Deviated, 1_000);
+        }
+
+        assert!(tracker.clear("kraken"));
+        assert!(!tracker.is_quarantined("kraken", 1_000));
+        assert!(!tracker.clear("kraken"));
+    }
+}
@@ -0,0 +1,420 @@
+//! 정산 시각 합의 증명
+//!
+//! 정산 시각 직전 구간에 원장에 기록된 서명 제출 중 (거래소, 노드)별 최신 1건을
+//! 모아 `ConsensusProof`로 묶고 Aggregator 키로 서명합니다. 합의 대상 거래소를
+//! 그 거래소에 배정된 노드가 제출한 것만 쓰고, [`SettlementQuorum`]을 채우지
+//! 못하면 증명을 만들지 않습니다. 만들어진 증명은 정산
+//! 시각별로 보관해, 정산 측이 같은 시각에 대해 항상 같은 바이트를 받도록 합니다.
+//! 중단 구간을 과거 K-line으로 채운 백필 제출은 [`BackfillPolicy`]에 따라서만
+//! 증명에 들어갑니다.
+
+use crate::node_auth::NodeRegistry;
+use aggregator::consensus::REQUIRED_EXCHANGES;
+use crate::submission_ledger::{LedgerEntry, SubmissionLedger, SubmissionQuery};
+use anyhow::{anyhow, Result};
+use oracle_vm_common::crypto::{KeyStore, Signature};
+use oracle_vm_common::{ConsensusProof, SignedSubmission};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+
+/// 정산 시각 이전 몇 초까지의 제출을 증명에 포함할지
+pub const PROOF_WINDOW_SECS: u64 = 120;
+
+/// 합의 증명 서명 키 이름
+pub const AGGREGATOR_KEY: &str = "aggregator";
+
+/// 보관하는 최근 증명 수
+const MAX_STORED_PROOFS: usize = 90;
+
+/// 백필 제출을 정산 증명에 쓸지 정하는 정산 정책
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackfillPolicy {
+    /// 실시간 제출만 사용
+    #[default]
+    Exclude,
+    /// 구간 안에 실시간 제출이 없는 거래소에 한해 백필 사용
+    Fallback,
+    /// 실시간 제출과 같이 취급 (거래소별 최신 1건)
+    Accept,
+}
+
+impl FromStr for BackfillPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "exclude" => Ok(Self::Exclude),
+            "fallback" => Ok(Self::Fallback),
+            "accept" => Ok(Self::Accept),
+            other => Err(anyhow!("Unknown backfill policy {} (exclude | fallback | accept)", other)),
+        }
+    }
+}
+
+/// 정산 증명 정족수
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementQuorum {
+    /// 거래소 하나를 쓰려면 필요한 서로 다른 노드 수
+    pub nodes_per_exchange: usize,
+    /// 노드 정족수를 채운 거래소가 이만큼 있어야 증명을 만듦
+    pub exchanges: usize,
+}
+
+impl Default for SettlementQuorum {
+    /// 거래소마다 노드 1개 (리스로 복제 노드 하나만 제출), 합의 대상 3곳 중 2곳
+    fn default() -> Self {
+        Self {
+            nodes_per_exchange: 1,
+            exchanges: 2,
+        }
+    }
+}
+
+/// (거래소, 노드)별 최신 제출
+fn latest_per_node(entries: impl Iterator<Item = LedgerEntry>) -> HashMap<(String, String), LedgerEntry> {
+    let mut latest: HashMap<(String, String), LedgerEntry> = HashMap::new();
+    for entry in entries {
+        let key = (entry.exchange.clone(), entry.node_id.clone());
+        match latest.get(&key) {
+            Some(existing) if existing.timestamp >= entry.timestamp => {}
+            _ => {
+                latest.insert(key, entry);
+            }
+        }
+    }
+    latest
+}
+
+/// 정산 시각 구간의 (거래소, 노드)별 최신 서명 제출
+///
+/// 합의 대상이 아닌 거래소, 그 거래소에 배정되지 않은 노드의 제출, 서명이 없거나
+/// (구버전 노드) 등록 키를 알 수 없는 제출은 제외하고, 서로 다른 노드 수가
+/// `nodes_per_exchange`에 못 미치는 거래소도 뺍니다.
+pub fn settlement_submissions(
+    ledger: &SubmissionLedger,
+    registry: &NodeRegistry,
+    settlement_time: u64,
+    policy: BackfillPolicy,
+    nodes_per_exchange: usize,
+) -> Vec<SignedSubmission> {
+    let (backfilled, live): (Vec<LedgerEntry>, Vec<LedgerEntry>) = ledger
+        .query(&SubmissionQuery {
+            from: settlement_time.saturating_sub(PROOF_WINDOW_SECS),
+            to: settlement_time,
+            ..Default::default()
+        })
+        .into_iter()
+        .filter(|entry| {
+            REQUIRED_EXCHANGES.contains(&entry.exchange.as_str())
+                && registry.is_assigned(&entry.node_id, &entry.exchange)
+        })
+        .partition(|entry| entry.backfilled);
+
+    let latest = match policy {
+        BackfillPolicy::Exclude => latest_per_node(live.into_iter()),
+        BackfillPolicy::Fallback => {
+            // 실시간 제출이 하나라도 있는 거래소는 백필을 쓰지 않음
+            let live_exchanges: HashSet<&str> = live.iter().map(|entry| entry.exchange.as_str()).collect();
+            let mut latest = latest_per_node(
+                backfilled
+                    .iter()
+                    .filter(|entry| !live_exchanges.contains(entry.exchange.as_str()))
+                    .cloned(),
+            );
+            latest.extend(latest_per_node(live.iter().cloned()));
+            latest
+        }
+        BackfillPolicy::Accept => latest_per_node(live.into_iter().chain(backfilled)),
+    };
+
+    let submissions: Vec<SignedSubmission> = latest
+        .into_values()
+        .filter_map(|entry| {
+            let signature = Signature::from_str(entry.signature.as_deref()?).ok()?;
+            Some(SignedSubmission {
+                node_pubkey: registry.public_key(&entry.node_id)?,
+                node_id: entry.node_id,
+                exchange: entry.exchange,
+                price_cents: entry.price_cents,
+                timestamp: entry.timestamp,
+                nonce: entry.nonce,
+                degraded: entry.degraded,
+                backfilled: entry.backfilled,
+                signature,
+            })
+        })
+        .collect();
+
+    let mut nodes: HashMap<&str, usize> = HashMap::new();
+    for submission in &submissions {
+        *nodes.entry(submission.exchange.as_str()).or_default() += 1;
+    }
+    let short: HashSet<String> = nodes
+        .into_iter()
+        .filter(|(_, count)| *count < nodes_per_exchange)
+        .map(|(exchange, _)| exchange.to_string())
+        .collect();
+    submissions
+        .into_iter()
+        .filter(|submission| !short.contains(&submission.exchange))
+        .collect()
+}
+
+/// 정산 시각별 증명 보관소
+#[derive(Default)]
+pub struct ProofStore {
+    proofs: BTreeMap<u64, ConsensusProof>,
+    backfill: BackfillPolicy,
+    quorum: SettlementQuorum,
+}
+
+impl ProofStore {
+    /// 백필 제출 사용 정책 지정 (기본: 실시간 제출만)
+    pub fn with_backfill_policy(mut self, policy: BackfillPolicy) -> Self {
+        self.backfill = policy;
+        self
+    }
+
+    /// 정족수 지정 (기본: 거래소마다 노드 1개, 거래소 2곳)
+    pub fn with_quorum(mut self, quorum: SettlementQuorum) -> Self {
+        self.quorum = quorum;
+        self
+    }
+
+    /// 이미 만든 증명이 있으면 그대로, 없으면 새로 만들어 보관
+    pub fn get_or_build(
+        &mut self,
+        pair: &str,
+        settlement_time: u64,
+        ledger: &SubmissionLedger,
+        registry: &NodeRegistry,
+        keys: &dyn KeyStore,
+    ) -> Result<ConsensusProof> {
+        if let Some(proof) = self.proofs.get(&settlement_time) {
+            return Ok(proof.clone());
+        }
+        let submissions = settlement_submissions(
+            ledger,
+            registry,
+            settlement_time,
+            self.backfill,
+            self.quorum.nodes_per_exchange,
+        );
+        let exchanges: HashSet<&str> = submissions.iter().map(|s| s.exchange.as_str()).collect();
+        if exchanges.is_empty() || exchanges.len() < self.quorum.exchanges {
+            return Err(anyhow!(
+                "Only {} exchanges reached a quorum of {} nodes within {}s before {} (need {})",
+                exchanges.len(),
+                self.quorum.nodes_per_exchange,
+                PROOF_WINDOW_SECS,
+                settlement_time,
+                self.quorum.exchanges
+            ));
+        }
+        let proof = ConsensusProof::build_signed(pair, settlement_time, submissions, keys, AGGREGATOR_KEY)?;
+        self.proofs.insert(settlement_time, proof.clone());
+        while self.proofs.len() > MAX_STORED_PROOFS {
+            self.proofs.pop_first();
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_auth::NodeAllowlist;
+    use oracle_vm_common::crypto::{
+        backfill_submission_payload, generate_keypair, node_registration_payload, price_submission_payload,
+        sign_data, MemoryKeyStore, SecretKey,
+    };
+
+    fn record(ledger: &mut SubmissionLedger, key: &SecretKey, node: &str, exchange: &str, price: u64, timestamp: u64) {
+        record_as(ledger, key, node, exchange, price, timestamp, false);
+    }
+
+    fn record_as(
+        ledger: &mut SubmissionLedger,
+        key: &SecretKey,
+        node: &str,
+        exchange: &str,
+        price: u64,
+        timestamp: u64,
+        backfilled: bool,
+    ) {
+        let payload = if backfilled {
+            backfill_submission_payload(node, exchange, price, timestamp, timestamp, false)
+        } else {
+            price_submission_payload(node, exchange, price, timestamp, timestamp, false)
+        };
+        ledger
+            .record(LedgerEntry {
+                node_id: node.to_string(),
+                exchange: exchange.to_string(),
+                price_cents: price,
+                timestamp,
+                nonce: timestamp,
+                degraded: false,
+                backfilled,
+                signature: Some(sign_data(&payload, key).unwrap().to_string()),
+                received_at: timestamp,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_proof_from_latest_signed_submissions() {
+        let settlement = 1_700_035_200;
+        let mut registry = NodeRegistry::new();
+        let (node_key, node_pubkey) = generate_keypair();
+        let registration = sign_data(&node_registration_payload("node-1", &node_pubkey), &node_key).unwrap();
+        registry
+            .register("node-1", &node_pubkey.to_string(), &registration.to_string())
+            .unwrap();
+
+        let mut ledger = SubmissionLedger::default();
+        record(&mut ledger, &node_key, "node-1", "binance", 6_400_000, settlement - 300);
+        record(&mut ledger, &node_key, "node-1", "binance", 6_500_000, settlement - 90);
+        record(&mut ledger, &node_key, "node-1", "binance", 6_500_200, settlement - 30);
+        record(&mut ledger, &node_key, "node-1", "kraken", 6_500_500, settlement - 20);
+        record(&mut ledger, &node_key, "node-1", "coinbase", 6_499_800, settlement);
+        // 정산 이후 제출과 미등록 노드 제출은 제외
+        record(&mut ledger, &node_key, "node-1", "coinbase", 7_000_000, settlement + 60);
+        let (stranger, _) = generate_keypair();
+        record(&mut ledger, &stranger, "node-9", "bitstamp", 1, settlement - 10);
+
+        let aggregator_key = MemoryKeyStore::with_key(AGGREGATOR_KEY, generate_keypair().0);
+        let mut store = ProofStore::default();
+        let proof = store
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .unwrap();
+        proof.verify().unwrap();
+        assert_eq!(proof.submissions.len(), 3);
+        assert_eq!(proof.settlement_price(), 6_500_200);
+
+        // 보관된 증명을 그대로 반환
+        record(&mut ledger, &node_key, "node-1", "kraken", 9_000_000, settlement - 1);
+        let again = store
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .unwrap();
+        assert_eq!(again, proof);
+
+        assert!(store
+            .get_or_build("BTC/USD", settlement + 86_400, &ledger, &registry, &aggregator_key)
+            .is_err());
+    }
+
+    #[test]
+    fn test_backfill_policy() {
+        let settlement = 1_700_035_200;
+        let mut registry = NodeRegistry::new();
+        let (node_key, node_pubkey) = generate_keypair();
+        let registration = sign_data(&node_registration_payload("node-1", &node_pubkey), &node_key).unwrap();
+        registry
+            .register("node-1", &node_pubkey.to_string(), &registration.to_string())
+            .unwrap();
+
+        let mut ledger = SubmissionLedger::default();
+        record(&mut ledger, &node_key, "node-1", "binance", 6_500_000, settlement - 60);
+        record_as(&mut ledger, &node_key, "node-1", "binance", 6_600_000, settlement - 10, true);
+        record_as(&mut ledger, &node_key, "node-1", "kraken", 6_500_400, settlement - 20, true);
+
+        let prices = |policy| {
+            let mut prices: Vec<(String, u64)> = settlement_submissions(&ledger, &registry, settlement, policy, 1)
+                .into_iter()
+                .map(|s| (s.exchange, s.price_cents))
+                .collect();
+            prices.sort();
+            prices
+        };
+        assert_eq!(prices(BackfillPolicy::Exclude), vec![("binance".to_string(), 6_500_000)]);
+        // 실시간 제출이 있는 거래소는 백필보다 실시간 우선
+        assert_eq!(
+            prices(BackfillPolicy::Fallback),
+            vec![("binance".to_string(), 6_500_000), ("kraken".to_string(), 6_500_400)]
+        );
+        assert_eq!(
+            prices(BackfillPolicy::Accept),
+            vec![("binance".to_string(), 6_600_000), ("kraken".to_string(), 6_500_400)]
+        );
+
+        let aggregator_key = MemoryKeyStore::with_key(AGGREGATOR_KEY, generate_keypair().0);
+        let proof = ProofStore::default()
+            .with_backfill_policy(BackfillPolicy::Fallback)
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .unwrap();
+        proof.verify().unwrap();
+        assert!(proof.submissions.iter().any(|s| s.backfilled));
+        assert_eq!("fallback".parse::<BackfillPolicy>().unwrap(), BackfillPolicy::Fallback);
+    }
+
+    #[test]
+    fn test_quorum_of_distinct_assigned_nodes() {
+        let settlement = 1_700_035_200;
+        let keys: Vec<(SecretKey, _)> = (0..3).map(|_| generate_keypair()).collect();
+        let allowlist: NodeAllowlist = toml::from_str(&format!(
+            "[nodes.node-1]\npublic_key = \"{}\"\nexchanges = [\"binance\", \"kraken\"]\n\
+             [nodes.node-2]\npublic_key = \"{}\"\nexchanges = [\"binance\", \"kraken\"]\n\
+             [nodes.node-3]\npublic_key = \"{}\"\nexchanges = [\"coinbase\"]\n",
+            keys[0].1, keys[1].1, keys[2].1
+        ))
+        .unwrap();
+        let mut registry = NodeRegistry::new().with_allowlist(allowlist);
+        for (index, (secret, public)) in keys.iter().enumerate() {
+            let node = format!("node-{}", index + 1);
+            let registration = sign_data(&node_registration_payload(&node, public), secret).unwrap();
+            registry
+                .register(&node, &public.to_string(), &registration.to_string())
+                .unwrap();
+        }
+
+        let mut ledger = SubmissionLedger::default();
+        record(&mut ledger, &keys[0].0, "node-1", "binance", 6_500_000, settlement - 30);
+        record(&mut ledger, &keys[1].0, "node-2", "binance", 6_500_400, settlement - 20);
+        record(&mut ledger, &keys[0].0, "node-1", "kraken", 6_500_200, settlement - 10);
+        record(&mut ledger, &keys[2].0, "node-3", "coinbase", 6_499_000, settlement - 5);
+        // 배정되지 않은 거래소 제출은 제외
+        record(&mut ledger, &keys[2].0, "node-3", "binance", 100_000, settlement - 5);
+
+        let aggregator_key = MemoryKeyStore::with_key(AGGREGATOR_KEY, generate_keypair().0);
+        let exchanges = |proof: &ConsensusProof| {
+            let mut exchanges: Vec<(String, String)> = proof
+                .submissions
+                .iter()
+                .map(|s| (s.exchange.clone(), s.node_id.clone()))
+                .collect();
+            exchanges.dedup();
+            exchanges
+        };
+
+        let proof = ProofStore::default()
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .unwrap();
+        assert_eq!(proof.submissions.len(), 4);
+        assert!(!exchanges(&proof).contains(&("binance".to_string(), "node-3".to_string())));
+
+        // 노드 2개 정족수: 두 노드가 낸 binance만 남음
+        let strict = SettlementQuorum {
+            nodes_per_exchange: 2,
+            exchanges: 1,
+        };
+        let proof = ProofStore::default()
+            .with_quorum(strict)
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .unwrap();
+        assert_eq!(
+            exchanges(&proof),
+            vec![
+                ("binance".to_string(), "node-1".to_string()),
+                ("binance".to_string(), "node-2".to_string())
+            ]
+        );
+        assert!(ProofStore::default()
+            .with_quorum(SettlementQuorum {
+                nodes_per_exchange: 2,
+                exchanges: 2,
+            })
+            .get_or_build("BTC/USD", settlement, &ledger, &registry, &aggregator_key)
+            .is_err());
+    }
+}
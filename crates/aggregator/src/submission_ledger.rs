@@ -0,0 +1,365 @@
+//! 노드 제출 원장 (정산 감사용)
+//!
+//! 검증을 통과한 모든 가격 제출을 (노드, 거래소, 가격, timestamp, 서명) 그대로
+//! 보존 기간 동안 보관합니다. 감사자는 gRPC로 원장을 조회할 수 있고, 하루치
+//! 제출은 Merkle root로 묶어 별도 OP_RETURN 앵커에 기록할 일일 커밋먼트가 됩니다.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate};
+use oracle_vm_common::crypto::{backfill_submission_payload, price_submission_payload, sha256, MerkleTree};
+use oracle_vm_common::price::deserialize_cents;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// 기본 보존 기간 (30일)
+pub const DEFAULT_RETENTION_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// 일일 커밋먼트 OP_RETURN 태그
+pub const SUBMISSION_ANCHOR_TAG: &[u8; 3] = b"SUB";
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// 원장에 기록된 제출 1건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub node_id: String,
+    pub exchange: String,
+    /// USD 센트 (이전 형식의 달러 실수 기록도 읽음)
+    #[serde(rename = "price_cents", alias = "price", deserialize_with = "deserialize_cents")]
+    pub price_cents: u64,
+    pub timestamp: u64,
+    pub nonce: u64,
+    pub degraded: bool,
+    /// 과거 K-line으로 채운 제출 (정산 사용 여부는 정산 정책이 결정)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub backfilled: bool,
+    pub signature: Option<String>,
+    /// Aggregator 수신 시각 (일 단위 커밋먼트 기준)
+    pub received_at: u64,
+}
+
+impl LedgerEntry {
+    /// Merkle leaf: 노드가 서명한 payload와 서명을 함께 해시
+    pub fn leaf_hash(&self) -> [u8; 32] {
+        let payload = if self.backfilled {
+            backfill_submission_payload
+        } else {
+            price_submission_payload
+        };
+        let mut data = payload(
+            &self.node_id,
+            &self.exchange,
+            self.price_cents,
+            self.timestamp,
+            self.nonce,
+            self.degraded,
+        );
+        data.push(b'|');
+        data.extend_from_slice(self.signature.as_deref().unwrap_or_default().as_bytes());
+        sha256(&data)
+    }
+}
+
+/// 원장 조회 조건 (None/0이면 제한 없음)
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionQuery {
+    pub node_id: Option<String>,
+    pub exchange: Option<String>,
+    pub from: u64,
+    pub to: u64,
+    pub limit: usize,
+}
+
+impl SubmissionQuery {
+    fn matches(&self, entry: &LedgerEntry) -> bool {
+        self.node_id.as_ref().is_none_or(|id| *id == entry.node_id)
+            && self.exchange.as_ref().is_none_or(|ex| *ex == entry.exchange)
+            && entry.timestamp >= self.from
+            && (self.to == 0 || entry.timestamp <= self.to)
+    }
+}
+
+/// 하루치 제출의 커밋먼트
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyCommitment {
+    pub date: NaiveDate,
+    pub submissions: u64,
+    pub merkle_root: [u8; 32],
+}
+
+impl DailyCommitment {
+    /// OP_RETURN payload: 태그(3) + YYYYMMDD(4, BE) + 제출 수(4, BE) + root(32) = 43 bytes
+    pub fn op_return_payload(&self) -> Vec<u8> {
+        let date = self.date.format("%Y%m%d").to_string().parse::<u32>().unwrap_or(0);
+        let mut payload = Vec::with_capacity(43);
+        payload.extend_from_slice(SUBMISSION_ANCHOR_TAG);
+        payload.extend_from_slice(&date.to_be_bytes());
+        payload.extend_from_slice(&(self.submissions.min(u32::MAX as u64) as u32).to_be_bytes());
+        payload.extend_from_slice(&self.merkle_root);
+        payload
+    }
+}
+
+/// 보존 기간이 있는 제출 원장 (선택적으로 JSONL 파일에 영속화)
+pub struct SubmissionLedger {
+    entries: VecDeque<LedgerEntry>,
+    retention_secs: u64,
+    path: Option<PathBuf>,
+}
+
+impl Default for SubmissionLedger {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETENTION_SECS)
+    }
+}
+
+impl SubmissionLedger {
+    /// 메모리 원장
+    pub fn new(retention_secs: u64) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            retention_secs,
+            path: None,
+        }
+    }
+
+    /// JSONL 파일 원장 (기존 파일이 있으면 불러와 이어서 기록)
+    pub fn open(path: impl Into<PathBuf>, retention_secs: u64) -> Result<Self> {
+        let path = path.into();
+        let mut entries = VecDeque::new();
+        if path.exists() {
+            let file = File::open(&path)
+                .with_context(|| format!("Failed to open submission ledger {}", path.display()))?;
+            for (line_no, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = serde_json::from_str(&line).with_context(|| {
+                    format!("Corrupt submission ledger {} line {}", path.display(), line_no + 1)
+                })?;
+                entries.push_back(entry);
+            }
+        }
+
+        Ok(Self {
+            entries,
+            retention_secs,
+            path: Some(path),
+        })
+    }
+
+    pub fn retention_secs(&self) -> u64 {
+        self.retention_secs
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 가장 오래 보관 중인 제출의 수신 시각
+    pub fn retained_since(&self) -> Option<u64> {
+        self.entries.front().map(|entry| entry.received_at)
+    }
+
+    /// 제출 기록 (파일 기록 실패 시에도 메모리에는 남김)
+    pub fn record(&mut self, entry: LedgerEntry) -> Result<()> {
+        let persisted = match &self.path {
+            Some(path) => Self::append(path, &entry),
+            None => Ok(()),
+        };
+        self.entries.push_back(entry);
+        persisted
+    }
+
+    fn append(path: &PathBuf, entry: &LedgerEntry) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open submission ledger {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    /// 보존 기간이 지난 제출 삭제 (파일도 다시 씀), 삭제 건수 반환
+    pub fn prune(&mut self, now: u64) -> Result<usize> {
+        let cutoff = now.saturating_sub(self.retention_secs);
+        let before = self.entries.len();
+        // 수신 순서로 쌓이므로 앞에서부터 제거
+        while self
+            .entries
+            .front()
+            .is_some_and(|entry| entry.received_at < cutoff)
+        {
+            self.entries.pop_front();
+        }
+        let removed = before - self.entries.len();
+
+        if removed > 0 {
+            if let Some(path) = &self.path {
+                let tmp = path.with_extension("tmp");
+                let mut file = File::create(&tmp)?;
+                for entry in &self.entries {
+                    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+                }
+                file.sync_all()?;
+                fs::rename(&tmp, path)?;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// 조건에 맞는 제출 (수신 순서, `limit` 건까지)
+    pub fn query(&self, query: &SubmissionQuery) -> Vec<LedgerEntry> {
+        let limit = if query.limit == 0 { usize::MAX } else { query.limit };
+        self.entries
+            .iter()
+            .filter(|entry| query.matches(entry))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// UTC 기준 `date`에 수신된 제출의 커밋먼트
+    pub fn daily_commitment(&self, date: NaiveDate) -> DailyCommitment {
+        let start = date
+            .and_hms_opt(0, 0, 0)
+            .map(|dt| dt.and_utc().timestamp().max(0) as u64)
+            .unwrap_or(0);
+        let leaves: Vec<[u8; 32]> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.received_at >= start && entry.received_at < start + SECS_PER_DAY)
+            .map(LedgerEntry::leaf_hash)
+            .collect();
+
+        DailyCommitment {
+            date,
+            submissions: leaves.len() as u64,
+            merkle_root: MerkleTree::new(leaves).root(),
+        }
+    }
+
+    /// 보존 기간 안에 하루 전체가 남아 있는지 (커밋먼트가 완전한지)
+    pub fn covers_day(&self, date: NaiveDate, now: u64) -> bool {
+        let Some(start) = date.and_hms_opt(0, 0, 0) else {
+            return false;
+        };
+        let start = start.and_utc().timestamp().max(0) as u64;
+        now >= start + SECS_PER_DAY && start >= now.saturating_sub(self.retention_secs)
+    }
+}
+
+/// Unix timestamp의 UTC 날짜
+pub fn utc_date(timestamp: u64) -> NaiveDate {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(node: &str, exchange: &str, received_at: u64) -> LedgerEntry {
+        LedgerEntry {
+            node_id: node.to_string(),
+            exchange: exchange.to_string(),
+            price_cents: 6_500_000,
+            timestamp: received_at,
+            nonce: received_at,
+            degraded: false,
+            backfilled: false,
+            signature: Some("3044".to_string()),
+            received_at,
+        }
+    }
+
+    #[test]
+    fn test_query_and_daily_commitment() {
+        let day = 1_700_006_400; // 2023-11-15 00:00:00 UTC
+        let mut ledger = SubmissionLedger::new(DEFAULT_RETENTION_SECS);
+        ledger.record(entry("node-1", "binance", day + 10)).unwrap();
+        ledger.record(entry("node-2", "kraken", day + 20)).unwrap();
+        ledger.record(entry("node-1", "kraken", day + SECS_PER_DAY + 5)).unwrap();
+
+        let by_node = ledger.query(&SubmissionQuery {
+            node_id: Some("node-1".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(by_node.len(), 2);
+        let kraken_first_day = ledger.query(&SubmissionQuery {
+            exchange: Some("kraken".to_string()),
+            to: day + SECS_PER_DAY - 1,
+            ..Default::default()
+        });
+        assert_eq!(kraken_first_day, vec![entry("node-2", "kraken", day + 20)]);
+
+        let date = utc_date(day);
+        let commitment = ledger.daily_commitment(date);
+        assert_eq!(commitment.submissions, 2);
+        assert_eq!(
+            commitment.merkle_root,
+            MerkleTree::new(vec![
+                entry("node-1", "binance", day + 10).leaf_hash(),
+                entry("node-2", "kraken", day + 20).leaf_hash(),
+            ])
+            .root()
+        );
+
+        let payload = commitment.op_return_payload();
+        assert_eq!(payload.len(), 43);
+        assert_eq!(&payload[..3], SUBMISSION_ANCHOR_TAG);
+        assert_eq!(u32::from_be_bytes(payload[3..7].try_into().unwrap()), 20231115);
+
+        // 서명이 바뀌면 커밋먼트도 바뀜
+        let mut tampered = SubmissionLedger::new(DEFAULT_RETENTION_SECS);
+        let mut forged = entry("node-1", "binance", day + 10);
+        forged.signature = Some("3045".to_string());
+        tampered.record(forged).unwrap();
+        tampered.record(entry("node-2", "kraken", day + 20)).unwrap();
+        assert_ne!(tampered.daily_commitment(date).merkle_root, commitment.merkle_root);
+
+        assert!(ledger.covers_day(date, day + SECS_PER_DAY));
+        assert!(!ledger.covers_day(date, day + 100));
+    }
+
+    #[test]
+    fn test_legacy_dollar_entries_keep_leaf_hash() {
+        let legacy = r#"{"node_id":"node-1","exchange":"binance","price":65000.0,"timestamp":10,"nonce":10,"degraded":false,"signature":"3044","received_at":10}"#;
+        let parsed: LedgerEntry = serde_json::from_str(legacy).unwrap();
+        assert_eq!(parsed, entry("node-1", "binance", 10));
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert!(json.contains(r#""price_cents":6500000"#));
+        let reparsed: LedgerEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed.leaf_hash(), parsed.leaf_hash());
+    }
+
+    #[test]
+    fn test_retention_and_persistence() {
+        let path = std::env::temp_dir().join(format!(
+            "submission-ledger-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let mut ledger = SubmissionLedger::open(&path, 100).unwrap();
+        ledger.record(entry("node-1", "binance", 1_000)).unwrap();
+        ledger.record(entry("node-1", "binance", 1_050)).unwrap();
+        ledger.record(entry("node-1", "binance", 1_200)).unwrap();
+
+        assert_eq!(ledger.prune(1_120).unwrap(), 1);
+        assert_eq!(ledger.retained_since(), Some(1_050));
+
+        let reopened = SubmissionLedger::open(&path, 100).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.query(&SubmissionQuery::default()), ledger.query(&SubmissionQuery::default()));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+//! 임계 서명(FROST) 라운드 조정
+//!
+//! 합의 증명을 Aggregator 키 하나로만 서명하면 그 키가 단일 장애점이 됩니다.
+//! 그룹 키가 설정되면 정산 시각마다 합의 증명 본문에 대한 서명 라운드를 열고,
+//! Oracle Node들이 nonce 커밋먼트를 보내면 먼저 도착한 t명으로 서명 패키지를
+//! 확정합니다. 서명 조각은 서명자의 검증 공유키로 하나씩 확인한 뒤 t개가
+//! 모이면 BIP340 서명으로 합칩니다 (검증과 합산은 `frost-secp256k1-tr`).
+
+use frost_secp256k1_tr::keys::PublicKeyPackage;
+use frost_secp256k1_tr::round1::SigningCommitments;
+use frost_secp256k1_tr::round2::SignatureShare;
+use frost_secp256k1_tr::{Identifier, Signature, SigningPackage};
+use oracle_vm_common::crypto::sha256;
+use oracle_vm_common::ThresholdError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 보관하는 최근 라운드 수
+const MAX_ROUNDS: usize = 90;
+
+/// DKG 참가자 번호 (1부터)
+pub type ParticipantId = u16;
+
+/// 참가자 번호 → FROST 식별자
+pub fn identifier(id: ParticipantId) -> Result<Identifier, ThresholdError> {
+    Identifier::try_from(id).map_err(|_| ThresholdError::InvalidParameters(format!("invalid signer {}", id)))
+}
+
+/// 노드 그룹 공개 정보 (`--threshold-group` 파일, DKG 결과의 `public_key_package`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupKey {
+    pub threshold: u16,
+    pub public_key_package: PublicKeyPackage,
+}
+
+impl GroupKey {
+    /// 합의 증명이 검증되는 BIP340 x-only 그룹 키 (hex)
+    pub fn x_only(&self) -> String {
+        let key = self
+            .public_key_package
+            .verifying_key()
+            .serialize()
+            .expect("group key is a valid point");
+        hex::encode(&key[1..])
+    }
+
+    pub fn participants(&self) -> usize {
+        self.public_key_package.verifying_shares().len()
+    }
+
+    fn is_member(&self, signer: ParticipantId) -> bool {
+        identifier(signer).is_ok_and(|id| self.public_key_package.verifying_shares().contains_key(&id))
+    }
+}
+
+/// 서명 라운드 하나 (라운드 ID = 정산 시각)
+#[derive(Debug, Clone)]
+pub struct SigningRound {
+    pub round_id: u64,
+    /// 서명 대상 원문 (합의 증명 본문)
+    pub payload: Vec<u8>,
+    pub message: [u8; 32],
+    pub commitments: BTreeMap<ParticipantId, SigningCommitments>,
+    /// t명이 모이면 확정된 서명자와 서명 패키지
+    pub signers: Vec<ParticipantId>,
+    pub package: Option<SigningPackage>,
+    pub shares: BTreeMap<ParticipantId, SignatureShare>,
+    pub signature: Option<Signature>,
+}
+
+/// 서명 라운드 조정자
+pub struct SigningCoordinator {
+    group: GroupKey,
+    rounds: BTreeMap<u64, SigningRound>,
+}
+
+impl SigningCoordinator {
+    pub fn new(group: GroupKey) -> Self {
+        Self {
+            group,
+            rounds: BTreeMap::new(),
+        }
+    }
+
+    pub fn group(&self) -> &GroupKey {
+        &self.group
+    }
+
+    /// 라운드 열기 (이미 열려 있으면 그대로)
+    pub fn open(&mut self, round_id: u64, payload: &[u8]) -> &SigningRound {
+        if !self.rounds.contains_key(&round_id) {
+            while self.rounds.len() >= MAX_ROUNDS {
+                self.rounds.pop_first();
+            }
+        }
+        self.rounds.entry(round_id).or_insert_with(|| SigningRound {
+            round_id,
+            payload: payload.to_vec(),
+            message: sha256(payload),
+            commitments: BTreeMap::new(),
+            signers: Vec::new(),
+            package: None,
+            shares: BTreeMap::new(),
+            signature: None,
+        })
+    }
+
+    pub fn round(&self, round_id: u64) -> Option<&SigningRound> {
+        self.rounds.get(&round_id)
+    }
+
+    /// 아직 서명이 완성되지 않은 가장 최근 라운드
+    pub fn latest_open(&self) -> Option<&SigningRound> {
+        self.rounds.values().rev().find(|round| round.signature.is_none())
+    }
+
+    /// nonce 커밋먼트 접수 (서명자마다 라운드당 하나, 같은 값의 재전송만 허용)
+    pub fn commit(
+        &mut self,
+        round_id: u64,
+        signer: ParticipantId,
+        commitment: SigningCommitments,
+    ) -> Result<&SigningRound, ThresholdError> {
+        if !self.group.is_member(signer) {
+            return Err(ThresholdError::MissingCommitment(signer));
+        }
+        let threshold = self.group.threshold as usize;
+        let round = self.rounds.get_mut(&round_id).ok_or(ThresholdError::RoundNotFound(round_id))?;
+        match round.commitments.get(&signer) {
+            Some(existing) if *existing == commitment => {}
+            Some(_) => {
+                return Err(ThresholdError::InvalidParameters(format!(
+                    "signer {} already committed in round {}",
+                    signer, round_id
+                )))
+            }
+            None if round.package.is_some() => {
+                return Err(ThresholdError::InvalidParameters(format!(
+                    "round {} already has its {} signers",
+                    round_id, threshold
+                )))
+            }
+            None => {
+                round.commitments.insert(signer, commitment);
+                if round.commitments.len() >= threshold {
+                    round.signers = round.commitments.keys().take(threshold).copied().collect();
+                    let chosen = round
+                        .signers
+                        .iter()
+                        .map(|signer| Ok((identifier(*signer)?, round.commitments[signer])))
+                        .collect::<Result<_, ThresholdError>>()?;
+                    round.package = Some(SigningPackage::new(chosen, &round.message));
+                }
+            }
+        }
+        Ok(round)
+    }
+
+    /// 서명 조각 접수 (서명자의 검증 공유키로 확인), t개가 모이면 서명 완성
+    pub fn submit_share(
+        &mut self,
+        round_id: u64,
+        signer: ParticipantId,
+        share: SignatureShare,
+    ) -> Result<Option<Signature>, ThresholdError> {
+        let round = self.rounds.get_mut(&round_id).ok_or(ThresholdError::RoundNotFound(round_id))?;
+        if let Some(signature) = round.signature {
+            return Ok(Some(signature));
+        }
+        let package = round.package.as_ref().ok_or(ThresholdError::InsufficientSigners {
+            have: round.commitments.len(),
+            need: self.group.threshold as usize,
+        })?;
+        if !round.signers.contains(&signer) {
+            return Err(ThresholdError::MissingCommitment(signer));
+        }
+        let id = identifier(signer)?;
+        let verifying_share = self
+            .group
+            .public_key_package
+            .verifying_shares()
+            .get(&id)
+            .ok_or(ThresholdError::MissingCommitment(signer))?;
+        frost_core::verify_signature_share(
+            id,
+            verifying_share,
+            &share,
+            package,
+            self.group.public_key_package.verifying_key(),
+        )
+        .map_err(|_| ThresholdError::InvalidShare(signer))?;
+        round.shares.insert(signer, share);
+
+        if round.shares.len() < round.signers.len() {
+            return Ok(None);
+        }
+        let shares = round
+            .shares
+            .iter()
+            .map(|(signer, share)| Ok((identifier(*signer)?, *share)))
+            .collect::<Result<_, ThresholdError>>()?;
+        let signature = frost_secp256k1_tr::aggregate(package, &shares, &self.group.public_key_package)
+            .map_err(|e| ThresholdError::Crypto(e.to_string()))?;
+        round.signature = Some(signature);
+        Ok(Some(signature))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use oracle_node::threshold::{DkgParticipant, KeyShare, NonceStore};
+
+    pub(crate) fn dkg(threshold: u16, n: u16) -> Vec<KeyShare> {
+        let (mut nodes, broadcasts): (Vec<_>, BTreeMap<_, _>) = (1..=n)
+            .map(|id| {
+                let (node, broadcast) = DkgParticipant::new(id, threshold, n).unwrap();
+                (node, (id, broadcast))
+            })
+            .unzip();
+        let dealt: Vec<_> = nodes
+            .iter_mut()
+            .map(|node| {
+                let mut others = broadcasts.clone();
+                others.remove(&node.id());
+                node.round2(others).unwrap()
+            })
+            .collect();
+        nodes
+            .into_iter()
+            .map(|node| {
+                let received = (1..=n)
+                    .filter(|&dealer| dealer != node.id())
+                    .map(|dealer| (dealer, dealt[dealer as usize - 1][&node.id()].clone()))
+                    .collect();
+                node.finish(received).unwrap()
+            })
+            .collect()
+    }
+
+    pub(crate) fn group(key: &KeyShare) -> GroupKey {
+        GroupKey {
+            threshold: key.threshold(),
+            public_key_package: key.public_key_package.clone(),
+        }
+    }
+
+    #[test]
+    fn test_round_collects_threshold_signers() {
+        let keys = dkg(2, 3);
+        let group = group(&keys[0]);
+        let mut coordinator = SigningCoordinator::new(group.clone());
+        let round_id = 1_700_035_200;
+        coordinator.open(round_id, b"consensus proof body");
+        let mut stores: Vec<NonceStore> = keys.iter().map(|_| NonceStore::new()).collect();
+
+        // 서명자는 라운드당 커밋먼트 하나 (같은 값 재전송만 허용)
+        let first = stores[2].commit(round_id, &keys[2].key_package);
+        coordinator.commit(round_id, 3, first).unwrap();
+        let swapped = SigningCommitments::new(*first.binding(), *first.hiding());
+        assert!(coordinator.commit(round_id, 3, swapped).is_err());
+        assert_eq!(
+            coordinator.commit(round_id, 4, first).unwrap_err(),
+            ThresholdError::MissingCommitment(4)
+        );
+
+        // 세 노드가 모두 커밋해도 먼저 온 두 명으로 확정
+        for (key, store) in keys.iter().zip(stores.iter_mut()).rev() {
+            let commitment = store.commit(round_id, &key.key_package);
+            let _ = coordinator.commit(round_id, key.id, commitment);
+        }
+        let round = coordinator.round(round_id).unwrap();
+        assert_eq!(round.signers, vec![2, 3]);
+        let package = round.package.clone().unwrap();
+
+        // 패키지 밖 서명자와 잘못된 조각은 거부
+        assert!(stores[0].sign(round_id, &keys[0].key_package, &package).is_err());
+        let good = stores[1].sign(round_id, &keys[1].key_package, &package).unwrap();
+        let last = stores[2].sign(round_id, &keys[2].key_package, &package).unwrap();
+        assert_eq!(coordinator.submit_share(round_id, 2, last), Err(ThresholdError::InvalidShare(2)));
+
+        assert_eq!(coordinator.submit_share(round_id, 2, good), Ok(None));
+        let signature = coordinator.submit_share(round_id, 3, last).unwrap().unwrap();
+        let message = sha256(b"consensus proof body");
+        assert!(group.public_key_package.verifying_key().verify(&message, &signature).is_ok());
+        assert!(coordinator.latest_open().is_none());
+        assert_eq!(coordinator.submit_share(9, 3, last), Err(ThresholdError::RoundNotFound(9)));
+    }
+}
@@ -4,4 +4,7 @@ pub mod client;
 pub mod scripts;
 pub mod utxo;
 
+#[cfg(feature = "regtest")]
+pub mod regtest;
+
 pub use client::*;
@@ -0,0 +1,189 @@
+//! Regtest harness for integration tests (`regtest` feature)
+//!
+//! Starts a throwaway bitcoind in regtest mode and exposes a few wallet
+//! helpers. The binary is located through `BITCOIND_EXE` or `PATH`.
+//! RPC results are read as JSON so this module does not depend on the
+//! bitcoin version used by the RPC crate.
+
+use crate::scripts::{extract_op_return, op_return_script};
+use bitcoin::consensus::encode::deserialize_hex;
+use bitcoin::hex::DisplayHex;
+use bitcoin::{Amount, Transaction, Txid};
+use bitcoind::bitcoincore_rpc::{Client, RpcApi};
+use bitcoind::BitcoinD;
+use oracle_vm_common::{AnchorError, NetworkProfile};
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+fn rpc_error(e: impl std::fmt::Display) -> AnchorError {
+    AnchorError::Rpc(e.to_string())
+}
+
+fn btc_to_amount(value: &Value) -> Result<Amount, AnchorError> {
+    let btc = value
+        .as_f64()
+        .ok_or_else(|| rpc_error(format!("Expected BTC amount, got {}", value)))?;
+    Amount::from_btc(btc.abs()).map_err(rpc_error)
+}
+
+/// Running regtest node; the default wallet acts as the miner
+pub struct RegtestNode {
+    node: BitcoinD,
+    mining_address: String,
+}
+
+impl RegtestNode {
+    /// Start bitcoind and mine 101 blocks so the miner has spendable coins
+    pub fn start() -> Result<Self, AnchorError> {
+        let exe = bitcoind::exe_path().map_err(rpc_error)?;
+        let node = BitcoinD::new(exe).map_err(rpc_error)?;
+        let mining_address: String = node.client.call("getnewaddress", &[]).map_err(rpc_error)?;
+
+        let regtest = Self {
+            node,
+            mining_address,
+        };
+        regtest.mine(101)?;
+        Ok(regtest)
+    }
+
+    /// Miner wallet
+    pub fn miner(&self) -> RegtestWallet<'_> {
+        RegtestWallet {
+            client: &self.node.client,
+        }
+    }
+
+    /// Create a named wallet (owned by the returned handle)
+    pub fn create_wallet(&self, name: &str) -> Result<OwnedWallet, AnchorError> {
+        let client = self.node.create_wallet(name).map_err(rpc_error)?;
+        Ok(OwnedWallet { client })
+    }
+
+    pub fn mine(&self, blocks: u64) -> Result<(), AnchorError> {
+        let _: Vec<String> = self
+            .node
+            .client
+            .call(
+                "generatetoaddress",
+                &[json!(blocks), json!(self.mining_address)],
+            )
+            .map_err(rpc_error)?;
+        Ok(())
+    }
+
+    /// Mine enough blocks for the regtest profile to treat pending transactions as final
+    pub fn confirm(&self) -> Result<(), AnchorError> {
+        self.mine(self.profile().required_confirmations as u64)
+    }
+
+    pub fn profile(&self) -> NetworkProfile {
+        NetworkProfile::REGTEST
+    }
+
+    pub fn block_height(&self) -> Result<u64, AnchorError> {
+        self.node.client.call("getblockcount", &[]).map_err(rpc_error)
+    }
+}
+
+/// Wallet created through [`RegtestNode::create_wallet`]
+pub struct OwnedWallet {
+    client: Client,
+}
+
+impl OwnedWallet {
+    pub fn wallet(&self) -> RegtestWallet<'_> {
+        RegtestWallet {
+            client: &self.client,
+        }
+    }
+}
+
+/// Wallet RPC helpers
+pub struct RegtestWallet<'a> {
+    client: &'a Client,
+}
+
+impl RegtestWallet<'_> {
+    pub fn new_address(&self) -> Result<String, AnchorError> {
+        self.client.call("getnewaddress", &[]).map_err(rpc_error)
+    }
+
+    /// Confirmed + trusted unconfirmed balance
+    pub fn balance(&self) -> Result<Amount, AnchorError> {
+        let balance: Value = self.client.call("getbalance", &[]).map_err(rpc_error)?;
+        btc_to_amount(&balance)
+    }
+
+    pub fn send(&self, address: &str, amount: Amount) -> Result<Txid, AnchorError> {
+        let txid: String = self
+            .client
+            .call("sendtoaddress", &[json!(address), json!(amount.to_btc())])
+            .map_err(|e| AnchorError::BroadcastRejected(e.to_string()))?;
+        Txid::from_str(&txid).map_err(rpc_error)
+    }
+
+    /// Broadcast an OP_RETURN output carrying `payload`, funded by this wallet
+    pub fn anchor(&self, payload: &[u8]) -> Result<Txid, AnchorError> {
+        op_return_script(payload)?;
+        let data = payload.to_lower_hex_string();
+
+        let raw: String = self
+            .client
+            .call("createrawtransaction", &[json!([]), json!([{ "data": data }])])
+            .map_err(rpc_error)?;
+        let funded: Value = self
+            .client
+            .call("fundrawtransaction", &[json!(raw)])
+            .map_err(rpc_error)?;
+        let signed: Value = self
+            .client
+            .call("signrawtransactionwithwallet", &[funded["hex"].clone()])
+            .map_err(rpc_error)?;
+        if signed["complete"] != json!(true) {
+            return Err(AnchorError::BroadcastRejected(format!(
+                "Wallet could not sign anchor: {}",
+                signed["errors"]
+            )));
+        }
+
+        let txid: String = self
+            .client
+            .call("sendrawtransaction", &[signed["hex"].clone()])
+            .map_err(|e| AnchorError::BroadcastRejected(e.to_string()))?;
+        Txid::from_str(&txid).map_err(rpc_error)
+    }
+
+    /// Wallet transaction decoded with this crate's bitcoin types
+    pub fn transaction(&self, txid: &Txid) -> Result<Transaction, AnchorError> {
+        let entry: Value = self
+            .client
+            .call("gettransaction", &[json!(txid.to_string())])
+            .map_err(rpc_error)?;
+        let hex = entry["hex"]
+            .as_str()
+            .ok_or_else(|| rpc_error(format!("Transaction {} has no hex", txid)))?;
+        deserialize_hex(hex).map_err(|e| AnchorError::InvalidPayload(e.to_string()))
+    }
+
+    /// Fee this wallet paid for `txid`
+    pub fn fee_paid(&self, txid: &Txid) -> Result<Amount, AnchorError> {
+        let entry: Value = self
+            .client
+            .call("gettransaction", &[json!(txid.to_string())])
+            .map_err(rpc_error)?;
+        match entry.get("fee") {
+            Some(fee) => btc_to_amount(fee),
+            None => Ok(Amount::ZERO),
+        }
+    }
+
+    /// OP_RETURN payload of a wallet transaction, if any
+    pub fn read_anchor(&self, txid: &Txid) -> Result<Option<Vec<u8>>, AnchorError> {
+        let tx = self.transaction(txid)?;
+        Ok(tx
+            .output
+            .iter()
+            .find_map(|output| extract_op_return(&output.script_pubkey)))
+    }
+}
@@ -1,3 +1,63 @@
 //! Bitcoin script generation
 
-// TODO: Implement script templates
+use bitcoin::script::{Instruction, PushBytesBuf, Script, ScriptBuf};
+use oracle_vm_common::AnchorError;
+
+/// Largest OP_RETURN payload relayed by default (standardness limit)
+pub const MAX_OP_RETURN_PAYLOAD: usize = 80;
+
+/// Build an `OP_RETURN <payload>` output script
+pub fn op_return_script(payload: &[u8]) -> Result<ScriptBuf, AnchorError> {
+    if payload.len() > MAX_OP_RETURN_PAYLOAD {
+        return Err(AnchorError::InvalidPayload(format!(
+            "{} bytes exceeds OP_RETURN limit of {}",
+            payload.len(),
+            MAX_OP_RETURN_PAYLOAD
+        )));
+    }
+    let push = PushBytesBuf::try_from(payload.to_vec())
+        .map_err(|e| AnchorError::InvalidPayload(e.to_string()))?;
+    Ok(ScriptBuf::new_op_return(push))
+}
+
+/// Extract the payload of an `OP_RETURN <payload>` script
+///
+/// Returns `None` for non-OP_RETURN scripts or anything other than a single push.
+pub fn extract_op_return(script: &Script) -> Option<Vec<u8>> {
+    if !script.is_op_return() {
+        return None;
+    }
+
+    let mut instructions = script.instructions().skip(1);
+    let payload = match instructions.next() {
+        Some(Ok(Instruction::PushBytes(bytes))) => bytes.as_bytes().to_vec(),
+        None => Vec::new(),
+        _ => return None,
+    };
+    if instructions.next().is_some() {
+        return None;
+    }
+    Some(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_return_round_trip() {
+        let payload = [0xabu8; 32];
+        let script = op_return_script(&payload).unwrap();
+        assert!(script.is_op_return());
+        assert_eq!(extract_op_return(&script).unwrap(), payload.to_vec());
+    }
+
+    #[test]
+    fn test_rejects_oversized_and_foreign_scripts() {
+        assert!(matches!(
+            op_return_script(&[0u8; MAX_OP_RETURN_PAYLOAD + 1]),
+            Err(AnchorError::InvalidPayload(_))
+        ));
+        assert!(extract_op_return(&ScriptBuf::new()).is_none());
+    }
+}
@@ -0,0 +1,76 @@
+//! Request a quote, fill it and poll the option until it settles
+//!
+//! ```text
+//! cargo run -p oracle-vm-client --example quote_and_open -- \
+//!     http://127.0.0.1:3000 http://127.0.0.1:3100 http://127.0.0.1:50051
+//! ```
+
+use oracle_vm_client::{CandleInterval, Endpoints, OracleVmClient, OptionStatus};
+use oracle_vm_common::price::format_cents;
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{ExerciseStyle, Payoff, QuoteRequest};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let calculation = args.next().unwrap_or_else(|| "http://127.0.0.1:3000".into());
+    let contracts = args.next().unwrap_or_else(|| "http://127.0.0.1:3100".into());
+    let aggregator = args.next();
+    let has_aggregator = aggregator.is_some();
+    let client = OracleVmClient::new(Endpoints {
+        calculation,
+        contracts,
+        aggregator,
+    })?;
+
+    if has_aggregator {
+        let price = client.latest_price().await?;
+        println!("Consensus price: ${:.2} ({} sources)", price.price, price.data_points);
+    }
+    let candles = client.price_history(CandleInterval::OneHour, None, None, Some(24)).await?;
+    println!("Last 24h candles: {}", candles.len());
+    let pool = client.pool_stats().await?;
+    println!("Pool available liquidity: {}, net delta {:.4}", pool.available_liquidity, pool.net_delta);
+
+    let quote = client
+        .request_quote(&QuoteRequest {
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2025-03-28".into(),
+            quantity: 10_000_000,
+            otc: false,
+            referral_code: None,
+            tenant_id: None,
+            allow_partial: true,
+            style: ExerciseStyle::default(),
+            barrier: None,
+            payoff: Payoff::default(),
+        })
+        .await?;
+    println!(
+        "Quote {}: premium {} sats (${} at ${}/BTC) until {}",
+        quote.quote_id,
+        quote.premium,
+        format_cents(quote.premium_usd),
+        format_cents(quote.spot_price),
+        quote.valid_until
+    );
+
+    let option_id = format!("MM-{}", quote.quote_id);
+    // A real integration keeps the secret key to sign withdrawals and exercises
+    let (_holder_secret, holder_key) = oracle_vm_common::crypto::generate_keypair();
+    let opened = client
+        .open_option(&quote, &option_id, 880_000, "example-desk", &holder_key)
+        .await?;
+    println!("Opened {} ({:?})", opened.option.option_id, opened.option.status);
+
+    loop {
+        let view = client.option(&option_id).await?;
+        if view.option.status != OptionStatus::Active {
+            println!("{:?}: {:?}", view.option.status, view.settlement);
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+}
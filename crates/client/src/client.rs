@@ -0,0 +1,385 @@
+//! Oracle VM API client
+
+use crate::error::ClientError;
+use crate::retry::RetryPolicy;
+use crate::types::{Candle, CandleInterval, ConsensusPrice, OptionView, PoolStats};
+use oracle_vm_common::crypto::PublicKey;
+use oracle_vm_common::{CorrelationId, OptionQuote, QuoteRequest, CORRELATION_ID_HEADER};
+use oracle_vm_proto::oracle::oracle_service_client::OracleServiceClient;
+use oracle_vm_proto::oracle::GetPriceRequest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::time::Duration;
+use tonic::transport::Endpoint;
+
+/// Header carrying the option creation idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Header carrying the pool (default or tenant) API key
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Service base URLs
+#[derive(Debug, Clone)]
+pub struct Endpoints {
+    /// Calculation API, e.g. `http://127.0.0.1:3000`
+    pub calculation: String,
+    /// Contracts API, e.g. `http://127.0.0.1:3100`
+    pub contracts: String,
+    /// Aggregator gRPC, e.g. `http://127.0.0.1:50051` (needed for `latest_price`)
+    pub aggregator: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Tenant {
+    id: String,
+    api_key: String,
+}
+
+/// Typed client for quotes, option creation, settlement status, pool stats
+/// and price history
+#[derive(Debug, Clone)]
+pub struct OracleVmClient {
+    http: reqwest::Client,
+    endpoints: Endpoints,
+    tenant: Option<Tenant>,
+    api_key: Option<String>,
+    retry: RetryPolicy,
+    timeout: Duration,
+    correlation_id: Option<CorrelationId>,
+}
+
+impl OracleVmClient {
+    pub fn new(endpoints: Endpoints) -> Result<Self, ClientError> {
+        let endpoints = Endpoints {
+            calculation: endpoints.calculation.trim_end_matches('/').to_string(),
+            contracts: endpoints.contracts.trim_end_matches('/').to_string(),
+            aggregator: endpoints.aggregator,
+        };
+        for url in [&endpoints.calculation, &endpoints.contracts] {
+            reqwest::Url::parse(url).map_err(|e| ClientError::Config(format!("{}: {}", url, e)))?;
+        }
+        if let Some(url) = &endpoints.aggregator {
+            Endpoint::from_shared(url.clone()).map_err(|e| ClientError::Config(format!("{}: {}", url, e)))?;
+        }
+
+        Ok(Self {
+            http: Self::http_client(DEFAULT_TIMEOUT)?,
+            endpoints,
+            tenant: None,
+            api_key: None,
+            retry: RetryPolicy::default(),
+            timeout: DEFAULT_TIMEOUT,
+            correlation_id: None,
+        })
+    }
+
+    /// Trade on a tenant pool (`/tenants/{id}` with `X-Api-Key`); quotes are
+    /// requested for the same tenant
+    pub fn with_tenant(mut self, tenant_id: impl Into<String>, api_key: impl Into<String>) -> Self {
+        self.tenant = Some(Tenant {
+            id: tenant_id.into(),
+            api_key: api_key.into(),
+        });
+        self
+    }
+
+    /// Trade on the default pool with `X-Api-Key` (ignored once a tenant is set)
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send every request with `id` in `x-correlation-id`, so a quote, the
+    /// option it opens and its settlement share one trace across services
+    ///
+    /// Without it each call uses the caller's current ID
+    /// ([`CorrelationId::current`]) or a fresh one.
+    pub fn with_correlation_id(mut self, id: CorrelationId) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
+
+    /// Per-request timeout (default 10s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, ClientError> {
+        self.http = Self::http_client(timeout)?;
+        self.timeout = timeout;
+        Ok(self)
+    }
+
+    fn http_client(timeout: Duration) -> Result<reqwest::Client, ClientError> {
+        reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| ClientError::Config(e.to_string()))
+    }
+
+    /// Firm, signed quote (`POST /api/rfq`)
+    pub async fn request_quote(&self, request: &QuoteRequest) -> Result<OptionQuote, ClientError> {
+        let mut request = request.clone();
+        if let Some(tenant) = &self.tenant {
+            request.tenant_id.get_or_insert_with(|| tenant.id.clone());
+        }
+        let url = format!("{}/api/rfq", self.endpoints.calculation);
+        self.retry
+            .run("request_quote", || self.send(self.http.post(&url).json(&request)))
+            .await
+    }
+
+    /// Open an option by filling a firm quote (`POST /options`)
+    ///
+    /// A fresh idempotency key is generated and reused for every retry, so a
+    /// request whose response was lost is never filled twice. Use
+    /// [`Self::open_option_with_key`] to keep the key across process restarts.
+    ///
+    /// `holder_key` is bound to `user_id` on its first option; withdrawals and
+    /// other holder requests must later be signed with the matching secret key.
+    pub async fn open_option(
+        &self,
+        quote: &OptionQuote,
+        option_id: &str,
+        expiry_height: u32,
+        user_id: &str,
+        holder_key: &PublicKey,
+    ) -> Result<OptionView, ClientError> {
+        let key = uuid::Uuid::new_v4().to_string();
+        self.open_option_with_key(&key, quote, option_id, expiry_height, user_id, holder_key)
+            .await
+    }
+
+    pub async fn open_option_with_key(
+        &self,
+        idempotency_key: &str,
+        quote: &OptionQuote,
+        option_id: &str,
+        expiry_height: u32,
+        user_id: &str,
+        holder_key: &PublicKey,
+    ) -> Result<OptionView, ClientError> {
+        #[derive(Serialize)]
+        struct OpenOptionRequest<'a> {
+            quote: &'a OptionQuote,
+            option_id: &'a str,
+            expiry_height: u32,
+            user_id: &'a str,
+            holder_pubkey: String,
+        }
+
+        let body = OpenOptionRequest {
+            quote,
+            option_id,
+            expiry_height,
+            user_id,
+            holder_pubkey: holder_key.to_string(),
+        };
+        let url = self.contracts_url("/options");
+        self.retry
+            .run("open_option", || {
+                let request = self
+                    .contracts_request(self.http.post(&url))
+                    .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+                    .json(&body);
+                self.send(request)
+            })
+            .await
+    }
+
+    /// Option state and settlement result (`GET /options/{id}`)
+    pub async fn option(&self, option_id: &str) -> Result<OptionView, ClientError> {
+        let url = self.contracts_url(&format!("/options/{}", option_id));
+        self.retry
+            .run("option", || self.send(self.contracts_request(self.http.get(&url))))
+            .await
+    }
+
+    /// Pool delta and liquidity (`GET /api/pool/delta`)
+    pub async fn pool_stats(&self) -> Result<PoolStats, ClientError> {
+        let url = format!("{}/api/pool/delta", self.endpoints.calculation);
+        self.retry
+            .run("pool_stats", || self.send(self.http.get(&url)))
+            .await
+    }
+
+    /// Consensus spot price candles (`GET /api/candles`), `from`/`to` in Unix seconds
+    pub async fn price_history(
+        &self,
+        interval: CandleInterval,
+        from: Option<u64>,
+        to: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Candle>, ClientError> {
+        let url = format!("{}/api/candles", self.endpoints.calculation);
+        let mut query = vec![("interval", interval.as_str().to_string())];
+        query.extend(from.map(|from| ("from", from.to_string())));
+        query.extend(to.map(|to| ("to", to.to_string())));
+        query.extend(limit.map(|limit| ("limit", limit.to_string())));
+        self.retry
+            .run("price_history", || self.send(self.http.get(&url).query(&query)))
+            .await
+    }
+
+    /// Latest consensus price from the aggregator (gRPC `GetAggregatedPrice`)
+    pub async fn latest_price(&self) -> Result<ConsensusPrice, ClientError> {
+        let url = self
+            .endpoints
+            .aggregator
+            .clone()
+            .ok_or_else(|| ClientError::Config("no aggregator endpoint configured".into()))?;
+        self.retry
+            .run("latest_price", || async {
+                let channel = Endpoint::from_shared(url.clone())
+                    .map_err(|e| ClientError::Config(e.to_string()))?
+                    .timeout(self.timeout)
+                    .connect()
+                    .await
+                    .map_err(|e| ClientError::Transport(e.to_string()))?;
+                let mut request = tonic::Request::new(GetPriceRequest { source_filter: None });
+                self.correlation_id().inject_grpc(&mut request);
+                let response = OracleServiceClient::new(channel)
+                    .get_aggregated_price(request)
+                    .await
+                    .map_err(|status| ClientError::from_grpc(&status))?
+                    .into_inner();
+                if !response.success {
+                    return Err(ClientError::Grpc {
+                        code: "Unavailable".into(),
+                        message: "aggregator has no consensus price yet".into(),
+                        retryable: true,
+                    });
+                }
+                Ok(ConsensusPrice {
+                    price_cents: response.aggregated_price_cents,
+                    price: response.aggregated_price,
+                    data_points: response.data_points,
+                    last_update: response.last_update,
+                })
+            })
+            .await
+    }
+
+    fn contracts_url(&self, path: &str) -> String {
+        match &self.tenant {
+            Some(tenant) => format!("{}/tenants/{}{}", self.endpoints.contracts, tenant.id, path),
+            None => format!("{}{}", self.endpoints.contracts, path),
+        }
+    }
+
+    fn contracts_request(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let api_key = self
+            .tenant
+            .as_ref()
+            .map(|tenant| &tenant.api_key)
+            .or(self.api_key.as_ref());
+        match api_key {
+            Some(api_key) => request.header(API_KEY_HEADER, api_key),
+            None => request,
+        }
+    }
+
+    fn correlation_id(&self) -> CorrelationId {
+        self.correlation_id
+            .clone()
+            .unwrap_or_else(CorrelationId::current_or_generate)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T, ClientError> {
+        let response = request
+            .header(CORRELATION_ID_HEADER, self.correlation_id().as_str())
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return response.json().await.map_err(|e| ClientError::Decode(e.to_string()));
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(ClientError::from_response(status.as_u16(), &body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderMap, http::StatusCode, routing::post, Json, Router};
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_open_option_retries_with_same_idempotency_key() {
+        // First attempt fails with 503, the retry must carry the same key
+        let keys = Arc::new(Mutex::new(Vec::<String>::new()));
+        let seen = keys.clone();
+        let app = Router::new().route(
+            "/tenants/acme/options",
+            post(move |headers: HeaderMap, Json(body): Json<serde_json::Value>| {
+                let seen = seen.clone();
+                async move {
+                    assert_eq!(headers[API_KEY_HEADER].to_str().unwrap(), "secret");
+                    assert_eq!(headers[CORRELATION_ID_HEADER].to_str().unwrap(), "flow-7");
+                    let mut keys = seen.lock().unwrap();
+                    keys.push(headers[IDEMPOTENCY_KEY_HEADER].to_str().unwrap().to_string());
+                    if keys.len() == 1 {
+                        return Err((StatusCode::SERVICE_UNAVAILABLE, "CONTRACT_TRADING_HALTED: paused"));
+                    }
+                    Ok(Json(serde_json::json!({
+                        "option": {
+                            "option_id": body["option_id"],
+                            "option_type": "Call",
+                            "strike_price": 7_000_000,
+                            "quantity": 10_000_000,
+                            "premium_paid": 250_000,
+                            "expiry_height": body["expiry_height"],
+                            "status": "Active",
+                            "user_id": body["user_id"],
+                        },
+                        "settlement": null,
+                    })))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = OracleVmClient::new(Endpoints {
+            calculation: format!("http://{}", addr),
+            contracts: format!("http://{}/", addr),
+            aggregator: None,
+        })
+        .unwrap()
+        .with_tenant("acme", "secret")
+        .with_correlation_id(CorrelationId::parse("flow-7").unwrap())
+        .with_retry_policy(RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        });
+        let quote: OptionQuote = serde_json::from_value(serde_json::json!({
+            "quote_id": "Q-1",
+            "option_type": "Call",
+            "strike_price": 7_000_000,
+            "expiry": "2025-03-28",
+            "quantity": 10_000_000,
+            "premium": 250_000,
+            "spot_price": 6_800_000,
+            "issued_at": 0,
+            "valid_until": 30,
+            "signature": "",
+        }))
+        .unwrap();
+
+        let (_, holder_key) = oracle_vm_common::crypto::generate_keypair();
+        let opened = client
+            .open_option(&quote, "MM-1", 880_000, "desk", &holder_key)
+            .await
+            .unwrap();
+        assert_eq!(opened.option.option_id, "MM-1");
+        assert_eq!(opened.option.status, crate::OptionStatus::Active);
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], keys[1]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Client errors
+
+use oracle_vm_common::ErrorClass;
+use thiserror::Error;
+
+/// Errors returned by [`crate::OracleVmClient`]
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ClientError {
+    /// Connection, timeout or other transport failure before a response arrived
+    #[error("Transport error: {0}")]
+    Transport(String),
+
+    /// The service answered with an error status
+    ///
+    /// `code` is the service's `ErrorClass` code when the body carried one
+    /// (contracts API), otherwise `HTTP_<status>`.
+    #[error("{code} (HTTP {status}): {message}")]
+    Api {
+        status: u16,
+        code: String,
+        message: String,
+        retryable: bool,
+    },
+
+    /// gRPC call to the aggregator failed
+    #[error("gRPC {code}: {message}")]
+    Grpc { code: String, message: String, retryable: bool },
+
+    /// Response body did not match the expected shape
+    #[error("Invalid response: {0}")]
+    Decode(String),
+
+    /// Invalid endpoint URL or client configuration
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+}
+
+impl ClientError {
+    /// Error from a non-success HTTP response body
+    pub(crate) fn from_response(status: u16, body: &str) -> Self {
+        #[derive(serde::Deserialize)]
+        struct ServiceError {
+            code: String,
+            message: String,
+            retryable: bool,
+        }
+
+        match serde_json::from_str::<ServiceError>(body) {
+            Ok(error) => Self::Api {
+                status,
+                code: error.code,
+                message: error.message,
+                retryable: error.retryable,
+            },
+            // Calculation API answers with plain-text "CODE: message"
+            Err(_) => {
+                let (code, message) = match body.split_once(": ") {
+                    Some((code, message))
+                        if !code.is_empty() && code.chars().all(|c| c.is_ascii_uppercase() || c == '_') =>
+                    {
+                        (code.to_string(), message.to_string())
+                    }
+                    _ => (format!("HTTP_{}", status), body.to_string()),
+                };
+                Self::Api {
+                    status,
+                    code,
+                    message,
+                    retryable: matches!(status, 429 | 502 | 503 | 504),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn from_grpc(status: &tonic::Status) -> Self {
+        use tonic::Code;
+        Self::Grpc {
+            code: format!("{:?}", status.code()),
+            message: status.message().to_string(),
+            retryable: matches!(
+                status.code(),
+                Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted | Code::Aborted
+            ),
+        }
+    }
+}
+
+impl ErrorClass for ClientError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Transport(_) => "CLIENT_TRANSPORT",
+            Self::Api { .. } => "CLIENT_API",
+            Self::Grpc { .. } => "CLIENT_GRPC",
+            Self::Decode(_) => "CLIENT_DECODE",
+            Self::Config(_) => "CLIENT_CONFIG",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(_) => true,
+            Self::Api { retryable, .. } | Self::Grpc { retryable, .. } => *retryable,
+            Self::Decode(_) | Self::Config(_) => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            Self::Decode(err.to_string())
+        } else {
+            Self::Transport(err.to_string())
+        }
+    }
+}
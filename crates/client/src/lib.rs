@@ -0,0 +1,51 @@
+//! Client SDK for Oracle VM integrators
+//!
+//! Wraps the calculation HTTP API (quotes, pool stats, price history), the
+//! contracts HTTP API (option creation and settlement status) and the
+//! aggregator gRPC API (consensus price) behind typed methods. Transient
+//! failures are retried with exponential backoff; option creation carries an
+//! `Idempotency-Key` so a retried request never opens a second option.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), oracle_vm_client::ClientError> {
+//! use oracle_vm_client::{Endpoints, OracleVmClient};
+//! use oracle_vm_common::{types::OptionType, ExerciseStyle, Payoff, QuoteRequest};
+//!
+//! let client = OracleVmClient::new(Endpoints {
+//!     calculation: "http://127.0.0.1:3000".into(),
+//!     contracts: "http://127.0.0.1:3100".into(),
+//!     aggregator: None,
+//! })?;
+//! let quote = client
+//!     .request_quote(&QuoteRequest {
+//!         option_type: OptionType::Call,
+//!         strike_price: 7_000_000,
+//!         expiry: "2025-03-28".into(),
+//!         quantity: 10_000_000,
+//!         otc: false,
+//!         referral_code: None,
+//!         tenant_id: None,
+//!         allow_partial: false,
+//!         style: ExerciseStyle::default(),
+//!         barrier: None,
+//!         payoff: Payoff::default(),
+//!     })
+//!     .await?;
+//! let (_holder_secret, holder_key) = oracle_vm_common::crypto::generate_keypair();
+//! let opened = client
+//!     .open_option(&quote, "MM-1", 880_000, "mm-desk", &holder_key)
+//!     .await?;
+//! println!("{:?}", opened.option.status);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod retry;
+pub mod types;
+
+pub use client::{Endpoints, OracleVmClient};
+pub use error::ClientError;
+pub use retry::RetryPolicy;
+pub use types::{Candle, CandleInterval, ConsensusPrice, OptionInfo, OptionStatus, OptionView, PoolStats};
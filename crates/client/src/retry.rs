@@ -0,0 +1,102 @@
+//! Retry with exponential backoff
+
+use crate::error::ClientError;
+use oracle_vm_common::ErrorClass;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// Exponential backoff for retryable errors (transport, 429/5xx, gRPC unavailable)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first (1 disables retries)
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// No retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before the next attempt after `attempts` failures
+    pub fn delay_after(&self, attempts: u32) -> Duration {
+        let exponent = attempts.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+
+    /// Run `call` until it succeeds, fails with a non-retryable error or runs
+    /// out of attempts
+    pub async fn run<T, F, Fut>(&self, operation: &str, mut call: F) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match call().await {
+                Err(e) if e.is_retryable() && attempts < self.max_attempts => {
+                    let delay = self.delay_after(attempts);
+                    warn!("{} failed (attempt {}), retrying in {:?}: {}", operation, attempts, delay, e);
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_retries_only_retryable_errors() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+        assert_eq!(policy.delay_after(1), Duration::from_millis(1));
+        assert_eq!(policy.delay_after(5), Duration::from_millis(2));
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = policy
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(ClientError::Transport("connection reset".into()))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), _> = policy
+            .run("test", || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(ClientError::from_response(409, "{\"code\":\"CONTRACT_QUOTE_REUSED\",\"message\":\"used\",\"retryable\":false}"))
+            })
+            .await;
+        assert!(matches!(result, Err(ClientError::Api { ref code, .. }) if code == "CONTRACT_QUOTE_REUSED"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Response types
+//!
+//! Mirrors of the JSON bodies returned by the calculation and contracts
+//! services. Quote types are shared through `oracle-vm-common`.
+
+use oracle_vm_common::types::OptionType;
+use oracle_vm_common::{Exercise, SettlementCurrency};
+use serde::{Deserialize, Serialize};
+
+/// Option lifecycle state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionStatus {
+    Active,
+    Expired,
+    Settled,
+    /// Bought back by the pool before expiry
+    Cancelled,
+}
+
+/// Option as recorded by the contracts service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionInfo {
+    pub option_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub quantity: u64,     // satoshis
+    pub premium_paid: u64, // satoshis
+    pub expiry_height: u32,
+    pub status: OptionStatus,
+    pub user_id: String,
+}
+
+/// Settlement outcome of an expired option
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub option_id: String,
+    pub spot_price: u64, // USD cents
+    pub currency: SettlementCurrency,
+    /// Amount is in the settlement currency's unit
+    pub exercise: Exercise,
+    pub settled_at: u64,
+}
+
+/// `GET /options/{id}` body: the option and, once settled, its settlement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OptionView {
+    pub option: OptionInfo,
+    pub settlement: Option<Settlement>,
+}
+
+/// Pool delta and liquidity (`GET /api/pool/delta`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolStats {
+    pub total_call_delta: f64,
+    pub total_put_delta: f64,
+    pub net_delta: f64,
+    pub available_liquidity: f64,
+    #[serde(default)]
+    pub locked_collateral: f64,
+}
+
+/// Candle width for price history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleInterval {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl CandleInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OneMinute => "1m",
+            Self::FiveMinutes => "5m",
+            Self::OneHour => "1h",
+        }
+    }
+}
+
+/// OHLC candle of the consensus spot price (USD)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Candle {
+    /// Bucket start (Unix seconds)
+    pub start: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub trades: u64,
+}
+
+/// Latest aggregator consensus price
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsensusPrice {
+    /// Settlement reference value (USD cents)
+    pub price_cents: u64,
+    pub price: f64,
+    pub data_points: u32,
+    pub last_update: u64,
+}
@@ -0,0 +1,85 @@
+//! Knock-in / knock-out barriers
+//!
+//! A barrier option pays like a vanilla option only if the consensus price
+//! has (knock-in) or has not (knock-out) touched the barrier level before
+//! expiry. Up barriers are touched at or above the level, down barriers at
+//! or below it.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Barrier direction and effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BarrierKind {
+    UpAndOut,
+    DownAndOut,
+    UpAndIn,
+    DownAndIn,
+}
+
+impl fmt::Display for BarrierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UpAndOut => write!(f, "up_and_out"),
+            Self::DownAndOut => write!(f, "down_and_out"),
+            Self::UpAndIn => write!(f, "up_and_in"),
+            Self::DownAndIn => write!(f, "down_and_in"),
+        }
+    }
+}
+
+/// Barrier attached to an option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Barrier {
+    pub kind: BarrierKind,
+    pub level: u64, // USD cents
+}
+
+impl Barrier {
+    pub fn is_up(&self) -> bool {
+        matches!(self.kind, BarrierKind::UpAndOut | BarrierKind::UpAndIn)
+    }
+
+    pub fn is_knock_in(&self) -> bool {
+        matches!(self.kind, BarrierKind::UpAndIn | BarrierKind::DownAndIn)
+    }
+
+    /// Whether a price (USD cents) touches the barrier
+    pub fn touched_by(&self, price: u64) -> bool {
+        if self.is_up() {
+            price >= self.level
+        } else {
+            price <= self.level
+        }
+    }
+
+    /// Whether the option still pays given if the barrier has been touched
+    pub fn is_live(&self, touched: bool) -> bool {
+        touched == self.is_knock_in()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_touch_and_liveness() {
+        let up_out = Barrier {
+            kind: BarrierKind::UpAndOut,
+            level: 8_000_000,
+        };
+        assert!(!up_out.touched_by(7_999_999));
+        assert!(up_out.touched_by(8_000_000));
+        assert!(up_out.is_live(false) && !up_out.is_live(true));
+
+        let down_in = Barrier {
+            kind: BarrierKind::DownAndIn,
+            level: 6_000_000,
+        };
+        assert!(down_in.touched_by(6_000_000) && !down_in.touched_by(6_000_001));
+        assert!(!down_in.is_live(false) && down_in.is_live(true));
+        assert_eq!(down_in.kind.to_string(), "down_and_in");
+    }
+}
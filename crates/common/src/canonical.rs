@@ -0,0 +1,207 @@
+//! Canonical binary encoding for hashed and signed structures
+//!
+//! serde_json output depends on field order, map iteration and float
+//! formatting, and `|`-joined strings are ambiguous once a field contains the
+//! separator. Anything that is hashed, signed or anchored is therefore
+//! encoded with [`CanonicalEncoder`], which gives every value exactly one
+//! byte string and never gives two values the same one.
+//!
+//! Layout (integers little-endian, like the consensus proof and program input):
+//!
+//! ```text
+//! domain (u8 len + ASCII) | fields in declaration order
+//!     u8/u16/u32/u64: fixed width | bool: 0 or 1 | fixed: raw bytes
+//!     str/bytes: u32 len + bytes | option: 0, or 1 then the value
+//! optional extensions: tag u8 then the value, tags strictly increasing
+//! ```
+//!
+//! Extensions let a structure grow optional fields while values that do not
+//! use them keep their existing encoding (and signatures).
+
+use crate::crypto::sha256;
+use crate::types::OptionType;
+
+/// Builder for one canonical encoding
+#[derive(Debug, Clone)]
+pub struct CanonicalEncoder {
+    out: Vec<u8>,
+    last_extension: Option<u8>,
+}
+
+impl CanonicalEncoder {
+    /// Start an encoding under `domain` (e.g. `"quote/v2"`), so equal fields
+    /// of different structures never collide
+    pub fn new(domain: &str) -> Self {
+        assert!(domain.len() <= u8::MAX as usize, "canonical domain too long");
+        let mut out = Vec::with_capacity(64);
+        out.push(domain.len() as u8);
+        out.extend_from_slice(domain.as_bytes());
+        Self {
+            out,
+            last_extension: None,
+        }
+    }
+
+    pub fn u8(&mut self, value: u8) -> &mut Self {
+        self.out.push(value);
+        self
+    }
+
+    pub fn u16(&mut self, value: u16) -> &mut Self {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn u32(&mut self, value: u32) -> &mut Self {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn u64(&mut self, value: u64) -> &mut Self {
+        self.out.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.u8(value as u8)
+    }
+
+    /// Fixed-length bytes (hashes, keys), no length prefix
+    pub fn fixed(&mut self, bytes: &[u8]) -> &mut Self {
+        self.out.extend_from_slice(bytes);
+        self
+    }
+
+    /// Length-prefixed bytes
+    pub fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        let len = u32::try_from(bytes.len()).expect("canonical field exceeds u32::MAX bytes");
+        self.u32(len).fixed(bytes)
+    }
+
+    pub fn str(&mut self, value: &str) -> &mut Self {
+        self.bytes(value.as_bytes())
+    }
+
+    /// Option type as `"call"` / `"put"`
+    pub fn option_type(&mut self, option_type: OptionType) -> &mut Self {
+        self.str(match option_type {
+            OptionType::Call => "call",
+            OptionType::Put => "put",
+        })
+    }
+
+    /// `None` as 0, `Some` as 1 followed by the value
+    pub fn option<T>(&mut self, value: Option<T>, encode: impl FnOnce(&mut Self, T)) -> &mut Self {
+        match value {
+            Some(value) => {
+                self.u8(1);
+                encode(self, value);
+            }
+            None => {
+                self.u8(0);
+            }
+        }
+        self
+    }
+
+    /// Start optional extension `tag`; tags must be written in increasing order
+    pub fn extension(&mut self, tag: u8) -> &mut Self {
+        assert!(
+            self.last_extension.is_none_or(|last| tag > last),
+            "canonical extension tags must be strictly increasing"
+        );
+        self.last_extension = Some(tag);
+        self.u8(tag)
+    }
+
+    /// Nested value, encoded with its own domain and length-prefixed
+    pub fn value(&mut self, value: &impl CanonicalEncode) -> &mut Self {
+        self.bytes(&value.canonical_bytes())
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.out
+    }
+
+    /// SHA256 of the encoding
+    pub fn hash(self) -> [u8; 32] {
+        sha256(&self.out)
+    }
+}
+
+/// Structures with a canonical encoding
+pub trait CanonicalEncode {
+    /// Domain tag, versioned when the field layout changes
+    const DOMAIN: &'static str;
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder);
+
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut encoder = CanonicalEncoder::new(Self::DOMAIN);
+        self.encode_fields(&mut encoder);
+        encoder.finish()
+    }
+
+    fn canonical_hash(&self) -> [u8; 32] {
+        sha256(&self.canonical_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_vector() {
+        let mut encoder = CanonicalEncoder::new("test/v1");
+        encoder
+            .u8(7)
+            .u16(0x0102)
+            .u32(0x0304_0506)
+            .u64(1_700_000_000)
+            .bool(true)
+            .str("a|b")
+            .option(None::<u64>, |e, v| {
+                e.u64(v);
+            })
+            .option(Some("x"), |e, v| {
+                e.str(v);
+            })
+            .fixed(&[0xaa; 4])
+            .extension(3)
+            .u64(1);
+        assert_eq!(
+            hex::encode(encoder.finish()),
+            concat!(
+                "07", "746573742f7631", // domain "test/v1"
+                "07", "0201", "06050403", "00f1536500000000", "01",
+                "03000000", "617c62", // "a|b"
+                "00", "01", "01000000", "78", // None, Some("x")
+                "aaaaaaaa", "03", "0100000000000000",
+            )
+        );
+    }
+
+    #[test]
+    fn test_encodings_are_unambiguous() {
+        let encode = |a: &str, b: &str| {
+            let mut encoder = CanonicalEncoder::new("test/v1");
+            encoder.str(a).str(b);
+            encoder.finish()
+        };
+        // Separator-joined strings would collide here
+        assert_ne!(encode("a|b", "c"), encode("a", "b|c"));
+        assert_ne!(encode("ab", ""), encode("a", "b"));
+
+        let mut other_domain = CanonicalEncoder::new("other/v1");
+        other_domain.str("a").str("b");
+        assert_ne!(other_domain.finish(), encode("a", "b"));
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly increasing")]
+    fn test_extension_order_is_enforced() {
+        let mut encoder = CanonicalEncoder::new("test/v1");
+        encoder.extension(2).extension(1);
+    }
+}
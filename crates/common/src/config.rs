@@ -2,6 +2,8 @@
 
 use crate::{NodeId, OracleVmError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 /// Network configuration
@@ -87,6 +89,246 @@ pub trait ConfigLoader<T> {
     fn save_to_file(&self, path: &str) -> Result<()>;
 }
 
+/// Consensus parameters for a single asset
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusParams {
+    /// Minimum share of sources that must agree (0.66 = 2/3)
+    pub min_consensus_ratio: f64,
+    /// Maximum deviation from the median (0.02 = 2%)
+    pub max_price_deviation: f64,
+}
+
+impl Default for ConsensusParams {
+    fn default() -> Self {
+        Self {
+            min_consensus_ratio: 0.66,
+            max_price_deviation: 0.02,
+        }
+    }
+}
+
+impl ConsensusParams {
+    pub fn validate(&self) -> Result<()> {
+        if !(self.min_consensus_ratio > 0.0 && self.min_consensus_ratio <= 1.0) {
+            return Err(OracleVmError::Config(format!(
+                "min_consensus_ratio must be in (0, 1]: {}",
+                self.min_consensus_ratio
+            )));
+        }
+        if !(self.max_price_deviation > 0.0 && self.max_price_deviation < 1.0) {
+            return Err(OracleVmError::Config(format!(
+                "max_price_deviation must be in (0, 1): {}",
+                self.max_price_deviation
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Per-asset override; unset fields fall back to the defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusOverride {
+    pub min_consensus_ratio: Option<f64>,
+    pub max_price_deviation: Option<f64>,
+}
+
+/// Consensus configuration with per-asset overrides
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusConfig {
+    #[serde(flatten)]
+    pub defaults: ConsensusParams,
+    /// Overrides keyed by asset pair (e.g., "BTC/USD")
+    #[serde(default)]
+    pub assets: HashMap<String, ConsensusOverride>,
+}
+
+impl ConsensusConfig {
+    /// Effective parameters for an asset pair
+    pub fn params_for(&self, asset: &str) -> ConsensusParams {
+        let mut params = self.defaults;
+        if let Some(over) = self.assets.get(asset) {
+            if let Some(ratio) = over.min_consensus_ratio {
+                params.min_consensus_ratio = ratio;
+            }
+            if let Some(deviation) = over.max_price_deviation {
+                params.max_price_deviation = deviation;
+            }
+        }
+        params
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.defaults.validate()?;
+        for asset in self.assets.keys() {
+            self.params_for(asset).validate().map_err(|e| {
+                OracleVmError::Config(format!("Invalid override for {}: {}", asset, e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl ConfigLoader<ConsensusConfig> for ConsensusConfig {
+    fn load_from_file(path: &str) -> Result<ConsensusConfig> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| OracleVmError::Config(format!("Failed to read {}: {}", path, e)))?;
+        let config: ConsensusConfig = toml::from_str(&content)
+            .map_err(|e| OracleVmError::Config(format!("Failed to parse {}: {}", path, e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn load_from_env() -> Result<ConsensusConfig> {
+        let mut config = ConsensusConfig::default();
+        if let Some(value) = get_env_var("CONSENSUS_MIN_RATIO") {
+            config.defaults.min_consensus_ratio = value.parse().map_err(|_| {
+                OracleVmError::Config(format!("Invalid CONSENSUS_MIN_RATIO: {}", value))
+            })?;
+        }
+        if let Some(value) = get_env_var("CONSENSUS_MAX_DEVIATION") {
+            config.defaults.max_price_deviation = value.parse().map_err(|_| {
+                OracleVmError::Config(format!("Invalid CONSENSUS_MAX_DEVIATION: {}", value))
+            })?;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn save_to_file(&self, path: &str) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).map_err(|e| OracleVmError::Serialization(e.to_string()))?;
+        std::fs::write(path, content)
+            .map_err(|e| OracleVmError::Config(format!("Failed to write {}: {}", path, e)))
+    }
+}
+
+/// Hot-reloadable consensus configuration
+///
+/// Readers take a snapshot per round; `replace` swaps the whole config at once
+/// so a round never sees a mix of old and new parameters.
+#[derive(Debug, Clone, Default)]
+pub struct ConsensusConfigHandle {
+    inner: Arc<RwLock<Arc<ConsensusConfig>>>,
+}
+
+impl ConsensusConfigHandle {
+    pub fn new(config: ConsensusConfig) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(config))),
+        }
+    }
+
+    /// Current configuration snapshot
+    pub fn snapshot(&self) -> Arc<ConsensusConfig> {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Validate and atomically swap in a new configuration
+    pub fn replace(&self, config: ConsensusConfig) -> Result<()> {
+        config.validate()?;
+        *self.inner.write().unwrap() = Arc::new(config);
+        Ok(())
+    }
+
+    /// Re-read a config file and swap it in; the old config stays on error
+    pub fn reload_from_file(&self, path: &str) -> Result<Arc<ConsensusConfig>> {
+        let config = ConsensusConfig::load_from_file(path)?;
+        self.replace(config)?;
+        Ok(self.snapshot())
+    }
+}
+
+/// API credentials for an exchange
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExchangeCredentials {
+    pub api_key: String,
+    pub api_secret: String,
+    /// Coinbase only
+    pub passphrase: Option<String>,
+}
+
+impl std::fmt::Debug for ExchangeCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExchangeCredentials")
+            .field("api_key", &self.api_key)
+            .field("api_secret", &"<redacted>")
+            .field("passphrase", &self.passphrase.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Per-exchange client settings (`[exchanges.<name>]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExchangeConfig {
+    pub enabled: bool,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub passphrase: Option<String>,
+    /// Requests per minute shared by every client of this exchange
+    pub rate_limit: u32,
+    /// Request timeout (e.g., "10s")
+    pub timeout: String,
+    /// REST endpoint override (e.g., a local `devnet` server)
+    pub base_url: Option<String>,
+}
+
+impl Default for ExchangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            api_key: None,
+            api_secret: None,
+            passphrase: None,
+            rate_limit: 60,
+            timeout: "10s".to_string(),
+            base_url: None,
+        }
+    }
+}
+
+impl ExchangeConfig {
+    /// Credentials from the file, falling back to `<EXCHANGE>_API_KEY`,
+    /// `<EXCHANGE>_API_SECRET` and `<EXCHANGE>_API_PASSPHRASE`
+    ///
+    /// Returns `None` unless both key and secret are set.
+    pub fn credentials(&self, exchange: &str) -> Option<ExchangeCredentials> {
+        let prefix = exchange.to_uppercase();
+        let pick = |value: &Option<String>, suffix: &str| {
+            value
+                .clone()
+                .filter(|v| !v.is_empty())
+                .or_else(|| get_env_var(&format!("{}_{}", prefix, suffix)))
+        };
+        Some(ExchangeCredentials {
+            api_key: pick(&self.api_key, "API_KEY")?,
+            api_secret: pick(&self.api_secret, "API_SECRET")?,
+            passphrase: pick(&self.passphrase, "API_PASSPHRASE"),
+        })
+    }
+
+    pub fn timeout(&self) -> Result<Duration> {
+        parse_duration(&self.timeout)
+    }
+}
+
+/// Exchange client configuration keyed by exchange name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExchangesConfig {
+    #[serde(flatten)]
+    pub exchanges: HashMap<String, ExchangeConfig>,
+}
+
+impl ExchangesConfig {
+    /// Settings for `exchange`, defaults when not configured
+    pub fn get(&self, exchange: &str) -> ExchangeConfig {
+        self.exchanges
+            .get(&exchange.to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
 /// Environment variable helper
 pub fn get_env_var(key: &str) -> Option<String> {
     std::env::var(key).ok()
@@ -140,9 +382,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_consensus_overrides() {
+        let config: ConsensusConfig = toml::from_str(
+            r#"
+            min_consensus_ratio = 0.66
+            max_price_deviation = 0.05
+
+            [assets."ETH/USD"]
+            max_price_deviation = 0.03
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.params_for("BTC/USD").max_price_deviation, 0.05);
+        let eth = config.params_for("ETH/USD");
+        assert_eq!(eth.max_price_deviation, 0.03);
+        assert_eq!(eth.min_consensus_ratio, 0.66);
+    }
+
+    #[test]
+    fn test_consensus_handle_rejects_invalid() {
+        let handle = ConsensusConfigHandle::new(ConsensusConfig::default());
+        let mut invalid = ConsensusConfig::default();
+        invalid.defaults.min_consensus_ratio = 1.5;
+
+        assert!(handle.replace(invalid).is_err());
+        assert_eq!(*handle.snapshot(), ConsensusConfig::default());
+    }
+
     #[test]
     fn test_base_config() {
         let config = BaseConfig::new("test-node");
         assert_eq!(config.node_id.as_str(), "test-node");
     }
+
+    #[test]
+    fn test_exchange_config() {
+        let config: ExchangesConfig = toml::from_str(
+            r#"
+            [binance]
+            api_key = "key"
+            api_secret = "secret"
+            rate_limit = 1200
+            timeout = "5s"
+
+            [kraken]
+            enabled = false
+            "#,
+        )
+        .unwrap();
+
+        let binance = config.get("Binance");
+        assert_eq!(binance.rate_limit, 1200);
+        assert_eq!(binance.timeout().unwrap(), Duration::from_secs(5));
+        let credentials = binance.credentials("binance").unwrap();
+        assert_eq!(credentials.api_key, "key");
+        assert!(!format!("{:?}", credentials).contains("secret\""));
+
+        assert!(!config.get("kraken").enabled);
+        assert_eq!(config.get("coinbase"), ExchangeConfig::default());
+        assert!(config.get("coinbase").credentials("oraclevm_test_unset").is_none());
+    }
 }
@@ -0,0 +1,556 @@
+//! Aggregator consensus proofs for settlement programs
+//!
+//! At each settlement time the aggregator packages the signed node
+//! submissions it used, a transcript of the median computation over them and
+//! its own signature into a `ConsensusProof`. The proof has one canonical byte
+//! layout, so a BitVMX settlement program can take it as input and check every
+//! step (node signatures, sort order, median) instead of trusting a bare
+//! price.
+//!
+//! Layout (integers little-endian, matching the RISC-V program input):
+//!
+//! ```text
+//! magic "OVCP" | version u8 | settlement_time u64 | pair (u8 len + bytes)
+//! submission count u16, then per submission:
+//!     node_id (u8 len + bytes) | exchange (u8 len + bytes) | node pubkey (33)
+//!     price_cents u64 | timestamp u64 | nonce u64 | flags u8 | signature (64, compact)
+//!     (flags: bit 0 degraded, bit 1 backfilled)
+//! transcript step count u16, then per step: submission index u16 | price_cents u64
+//! median_cents u64 | aggregator pubkey (33)
+//! aggregator signature (64, compact) over everything above
+//! ```
+
+use crate::crypto::{
+    backfill_submission_payload, price_submission_payload, verify_signature, KeyStore, MemoryKeyStore, PublicKey, SecretKey, Signature,
+};
+use crate::price::{div_round, Rounding};
+use crate::{OracleVmError, Result};
+
+/// Leading bytes of an encoded proof
+pub const CONSENSUS_PROOF_MAGIC: &[u8; 4] = b"OVCP";
+
+/// Current layout version
+pub const CONSENSUS_PROOF_VERSION: u8 = 1;
+
+const PUBKEY_LEN: usize = 33;
+const SIGNATURE_LEN: usize = 64;
+
+/// Per-submission flag bits
+const FLAG_DEGRADED: u8 = 0b01;
+const FLAG_BACKFILLED: u8 = 0b10;
+
+/// One node price submission together with the node's signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedSubmission {
+    pub node_id: String,
+    pub exchange: String,
+    pub node_pubkey: PublicKey,
+    pub price_cents: u64,
+    pub timestamp: u64,
+    pub nonce: u64,
+    pub degraded: bool,
+    /// Reconstructed from exchange history after downtime
+    pub backfilled: bool,
+    pub signature: Signature,
+}
+
+impl SignedSubmission {
+    /// Bytes the node signed
+    pub fn payload(&self) -> Vec<u8> {
+        let payload = if self.backfilled {
+            backfill_submission_payload
+        } else {
+            price_submission_payload
+        };
+        payload(
+            &self.node_id,
+            &self.exchange,
+            self.price_cents,
+            self.timestamp,
+            self.nonce,
+            self.degraded,
+        )
+    }
+
+    pub fn verify(&self) -> Result<bool> {
+        verify_signature(&self.payload(), &self.signature, &self.node_pubkey)
+    }
+
+    fn sort_key(&self) -> (&str, &str, u64) {
+        (&self.exchange, &self.node_id, self.timestamp)
+    }
+}
+
+/// One entry of the sorted price list: which submission it came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MedianStep {
+    pub submission: u16,
+    pub price_cents: u64,
+}
+
+/// Median computation over the proof's submissions
+///
+/// `steps` lists every submission once in ascending price order (ties by
+/// index); the median is the middle price, or the half-even mean of the two
+/// middle prices for an even count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MedianTranscript {
+    pub steps: Vec<MedianStep>,
+    pub median_cents: u64,
+}
+
+impl MedianTranscript {
+    /// Transcript for `submissions` (None if there are none)
+    pub fn compute(submissions: &[SignedSubmission]) -> Option<Self> {
+        let mut steps: Vec<MedianStep> = submissions
+            .iter()
+            .enumerate()
+            .map(|(index, submission)| MedianStep {
+                submission: index as u16,
+                price_cents: submission.price_cents,
+            })
+            .collect();
+        steps.sort_by_key(|step| (step.price_cents, step.submission));
+        let median_cents = median_of_sorted(&steps)?;
+        Some(Self { steps, median_cents })
+    }
+
+    /// Re-check every step against the submissions
+    pub fn verify(&self, submissions: &[SignedSubmission]) -> Result<()> {
+        if self.steps.len() != submissions.len() {
+            return Err(invalid(format!(
+                "transcript has {} steps for {} submissions",
+                self.steps.len(),
+                submissions.len()
+            )));
+        }
+        let mut seen = vec![false; submissions.len()];
+        for (position, step) in self.steps.iter().enumerate() {
+            let index = step.submission as usize;
+            let Some(submission) = submissions.get(index) else {
+                return Err(invalid(format!("step {} references missing submission {}", position, index)));
+            };
+            if std::mem::replace(&mut seen[index], true) {
+                return Err(invalid(format!("submission {} appears twice in transcript", index)));
+            }
+            if step.price_cents != submission.price_cents {
+                return Err(invalid(format!("step {} price does not match submission {}", position, index)));
+            }
+        }
+        if self
+            .steps
+            .windows(2)
+            .any(|pair| (pair[0].price_cents, pair[0].submission) > (pair[1].price_cents, pair[1].submission))
+        {
+            return Err(invalid("transcript is not sorted".to_string()));
+        }
+        if median_of_sorted(&self.steps) != Some(self.median_cents) {
+            return Err(invalid(format!("median {} does not match sorted prices", self.median_cents)));
+        }
+        Ok(())
+    }
+}
+
+fn median_of_sorted(steps: &[MedianStep]) -> Option<u64> {
+    let mid = steps.len() / 2;
+    match steps.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(steps[mid].price_cents),
+        _ => {
+            let sum = steps[mid - 1].price_cents as u128 + steps[mid].price_cents as u128;
+            div_round(sum, 2, Rounding::HalfEven).map(|median| median as u64)
+        }
+    }
+}
+
+/// Aggregator-signed consensus input for one settlement time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusProof {
+    pub pair: String,
+    pub settlement_time: u64,
+    /// Sorted by (exchange, node_id, timestamp)
+    pub submissions: Vec<SignedSubmission>,
+    pub transcript: MedianTranscript,
+    pub aggregator_pubkey: PublicKey,
+    pub aggregator_signature: Signature,
+}
+
+impl ConsensusProof {
+    /// Sort the submissions, compute the median and sign as the aggregator
+    pub fn build(
+        pair: &str,
+        settlement_time: u64,
+        submissions: Vec<SignedSubmission>,
+        aggregator_key: &SecretKey,
+    ) -> Result<Self> {
+        let keys = MemoryKeyStore::with_key("aggregator", *aggregator_key);
+        Self::build_signed(pair, settlement_time, submissions, &keys, "aggregator")
+    }
+
+    /// Same as [`Self::build`], signing with the named key of a key store
+    pub fn build_signed(
+        pair: &str,
+        settlement_time: u64,
+        mut submissions: Vec<SignedSubmission>,
+        keys: &dyn KeyStore,
+        key_name: &str,
+    ) -> Result<Self> {
+        if submissions.len() > u16::MAX as usize {
+            return Err(invalid(format!("too many submissions: {}", submissions.len())));
+        }
+        submissions.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        let transcript = MedianTranscript::compute(&submissions)
+            .ok_or_else(|| OracleVmError::Aggregation("no submissions for consensus proof".to_string()))?;
+        let key_error = |e: crate::KeyStoreError| OracleVmError::Crypto(e.to_string());
+        let aggregator_pubkey = keys.public_key(key_name).map_err(key_error)?;
+        let body = encode_body(pair, settlement_time, &submissions, &transcript, &aggregator_pubkey)?;
+        Ok(Self {
+            pair: pair.to_string(),
+            settlement_time,
+            submissions,
+            transcript,
+            aggregator_pubkey,
+            aggregator_signature: keys.sign(key_name, &body).map_err(key_error)?,
+        })
+    }
+
+    /// Consensus price the settlement program should use (USD cents)
+    pub fn settlement_price(&self) -> u64 {
+        self.transcript.median_cents
+    }
+
+    /// Bytes covered by the aggregator signature
+    pub fn body_bytes(&self) -> Result<Vec<u8>> {
+        encode_body(
+            &self.pair,
+            self.settlement_time,
+            &self.submissions,
+            &self.transcript,
+            &self.aggregator_pubkey,
+        )
+    }
+
+    /// Canonical encoding: body followed by the aggregator signature
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut out = self.body_bytes()?;
+        out.extend_from_slice(&self.aggregator_signature.serialize_compact());
+        Ok(out)
+    }
+
+    /// BitVMX program input: u32 byte length, the encoding, zero padding to a 32-bit word
+    pub fn to_program_input(&self) -> Result<Vec<u8>> {
+        let bytes = self.to_bytes()?;
+        let mut input = Vec::with_capacity(bytes.len() + 8);
+        input.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        input.extend_from_slice(&bytes);
+        input.resize(input.len().next_multiple_of(4), 0);
+        Ok(input)
+    }
+
+    /// Decode the canonical encoding (does not verify signatures)
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader { bytes, pos: 0 };
+        if reader.take(4)? != CONSENSUS_PROOF_MAGIC {
+            return Err(invalid("not a consensus proof".to_string()));
+        }
+        let version = reader.u8()?;
+        if version != CONSENSUS_PROOF_VERSION {
+            return Err(invalid(format!("unsupported consensus proof version {}", version)));
+        }
+        let settlement_time = reader.u64()?;
+        let pair = reader.string()?;
+
+        let count = reader.u16()?;
+        let mut submissions = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let (node_id, exchange, node_pubkey) = (reader.string()?, reader.string()?, reader.pubkey()?);
+            let (price_cents, timestamp, nonce) = (reader.u64()?, reader.u64()?, reader.u64()?);
+            let flags = reader.u8()?;
+            if flags & !(FLAG_DEGRADED | FLAG_BACKFILLED) != 0 {
+                return Err(invalid(format!("invalid submission flags {}", flags)));
+            }
+            submissions.push(SignedSubmission {
+                node_id,
+                exchange,
+                node_pubkey,
+                price_cents,
+                timestamp,
+                nonce,
+                degraded: flags & FLAG_DEGRADED != 0,
+                backfilled: flags & FLAG_BACKFILLED != 0,
+                signature: reader.signature()?,
+            });
+        }
+
+        let steps = reader.u16()?;
+        let mut transcript = MedianTranscript {
+            steps: Vec::with_capacity(steps as usize),
+            median_cents: 0,
+        };
+        for _ in 0..steps {
+            transcript.steps.push(MedianStep {
+                submission: reader.u16()?,
+                price_cents: reader.u64()?,
+            });
+        }
+        transcript.median_cents = reader.u64()?;
+        let aggregator_pubkey = reader.pubkey()?;
+        let aggregator_signature = reader.signature()?;
+        if reader.pos != bytes.len() {
+            return Err(invalid(format!("{} trailing bytes", bytes.len() - reader.pos)));
+        }
+
+        Ok(Self {
+            pair,
+            settlement_time,
+            submissions,
+            transcript,
+            aggregator_pubkey,
+            aggregator_signature,
+        })
+    }
+
+    /// Check the aggregator signature, every node signature, the submission
+    /// order and the median transcript
+    pub fn verify(&self) -> Result<()> {
+        if !verify_signature(&self.body_bytes()?, &self.aggregator_signature, &self.aggregator_pubkey)? {
+            return Err(OracleVmError::Crypto("invalid aggregator signature".to_string()));
+        }
+        for (index, submission) in self.submissions.iter().enumerate() {
+            if !submission.verify()? {
+                return Err(OracleVmError::Crypto(format!(
+                    "invalid signature on submission {} ({}/{})",
+                    index, submission.node_id, submission.exchange
+                )));
+            }
+        }
+        if self
+            .submissions
+            .windows(2)
+            .any(|pair| pair[0].sort_key() > pair[1].sort_key())
+        {
+            return Err(invalid("submissions are not sorted".to_string()));
+        }
+        self.transcript.verify(&self.submissions)
+    }
+}
+
+fn encode_body(
+    pair: &str,
+    settlement_time: u64,
+    submissions: &[SignedSubmission],
+    transcript: &MedianTranscript,
+    aggregator_pubkey: &PublicKey,
+) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    out.extend_from_slice(CONSENSUS_PROOF_MAGIC);
+    out.push(CONSENSUS_PROOF_VERSION);
+    out.extend_from_slice(&settlement_time.to_le_bytes());
+    put_str(&mut out, pair)?;
+
+    out.extend_from_slice(&(submissions.len() as u16).to_le_bytes());
+    for submission in submissions {
+        put_str(&mut out, &submission.node_id)?;
+        put_str(&mut out, &submission.exchange)?;
+        out.extend_from_slice(&submission.node_pubkey.serialize());
+        out.extend_from_slice(&submission.price_cents.to_le_bytes());
+        out.extend_from_slice(&submission.timestamp.to_le_bytes());
+        out.extend_from_slice(&submission.nonce.to_le_bytes());
+        out.push(
+            if submission.degraded { FLAG_DEGRADED } else { 0 }
+                | if submission.backfilled { FLAG_BACKFILLED } else { 0 },
+        );
+        out.extend_from_slice(&submission.signature.serialize_compact());
+    }
+
+    out.extend_from_slice(&(transcript.steps.len() as u16).to_le_bytes());
+    for step in &transcript.steps {
+        out.extend_from_slice(&step.submission.to_le_bytes());
+        out.extend_from_slice(&step.price_cents.to_le_bytes());
+    }
+    out.extend_from_slice(&transcript.median_cents.to_le_bytes());
+    out.extend_from_slice(&aggregator_pubkey.serialize());
+    Ok(out)
+}
+
+fn invalid(message: String) -> OracleVmError {
+    OracleVmError::InvalidData(message)
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) -> Result<()> {
+    let len = u8::try_from(value.len())
+        .map_err(|_| invalid(format!("field longer than 255 bytes: {}", value)))?;
+    out.push(len);
+    out.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos + len;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or_else(|| invalid(format!("consensus proof truncated at byte {}", self.pos)))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u8()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| invalid(e.to_string()))
+    }
+
+    fn pubkey(&mut self) -> Result<PublicKey> {
+        PublicKey::from_slice(self.take(PUBKEY_LEN)?).map_err(|e| OracleVmError::Crypto(e.to_string()))
+    }
+
+    fn signature(&mut self) -> Result<Signature> {
+        Signature::from_compact(self.take(SIGNATURE_LEN)?).map_err(|e| OracleVmError::Crypto(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{generate_keypair, sha256, sign_data};
+
+    fn submission(node: &str, exchange: &str, price_cents: u64) -> SignedSubmission {
+        let (secret_key, node_pubkey) = generate_keypair();
+        let payload = price_submission_payload(node, exchange, price_cents, 1_700_035_200, 7, false);
+        SignedSubmission {
+            node_id: node.to_string(),
+            exchange: exchange.to_string(),
+            node_pubkey,
+            price_cents,
+            timestamp: 1_700_035_200,
+            nonce: 7,
+            degraded: false,
+            backfilled: false,
+            signature: sign_data(&payload, &secret_key).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_proof_roundtrip_and_verify() {
+        let (aggregator_key, _) = generate_keypair();
+        let submissions = vec![
+            submission("node-2", "kraken", 6_500_300),
+            submission("node-1", "binance", 6_500_000),
+            submission("node-3", "coinbase", 6_499_000),
+        ];
+        let proof = ConsensusProof::build("BTC/USD", 1_700_035_200, submissions, &aggregator_key).unwrap();
+        assert_eq!(proof.submissions[0].exchange, "binance");
+        assert_eq!(proof.settlement_price(), 6_500_000);
+        assert_eq!(
+            proof.transcript.steps.iter().map(|s| s.submission).collect::<Vec<_>>(),
+            vec![1, 0, 2]
+        );
+        proof.verify().unwrap();
+
+        let bytes = proof.to_bytes().unwrap();
+        assert_eq!(&bytes[..4], CONSENSUS_PROOF_MAGIC);
+        let decoded = ConsensusProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        decoded.verify().unwrap();
+
+        let input = proof.to_program_input().unwrap();
+        assert_eq!(input.len() % 4, 0);
+        assert_eq!(u32::from_le_bytes(input[..4].try_into().unwrap()) as usize, bytes.len());
+        assert_eq!(&input[4..4 + bytes.len()], &bytes[..]);
+
+        assert!(ConsensusProof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_encoding_golden_vector() {
+        // Fixed keys and RFC 6979 signatures make the whole proof deterministic
+        let signed = |seed: u8, node: &str, exchange: &str, price_cents: u64| {
+            let secret_key = SecretKey::from_slice(&[seed; 32]).unwrap();
+            let payload = price_submission_payload(node, exchange, price_cents, 1_700_035_200, 7, false);
+            SignedSubmission {
+                node_id: node.to_string(),
+                exchange: exchange.to_string(),
+                node_pubkey: crate::crypto::public_key_from_secret(&secret_key),
+                price_cents,
+                timestamp: 1_700_035_200,
+                nonce: 7,
+                degraded: false,
+                backfilled: false,
+                signature: sign_data(&payload, &secret_key).unwrap(),
+            }
+        };
+        let aggregator_key = SecretKey::from_slice(&[9; 32]).unwrap();
+        let proof = ConsensusProof::build(
+            "BTC/USD",
+            1_700_035_200,
+            vec![signed(1, "node-1", "binance", 6_500_000), signed(2, "node-2", "kraken", 6_500_300)],
+            &aggregator_key,
+        )
+        .unwrap();
+
+        let bytes = proof.to_bytes().unwrap();
+        assert_eq!(
+            hex::encode(&bytes[..23]),
+            concat!("4f564350", "01", "807a546500000000", "07", "4254432f555344", "0200")
+        );
+        assert_eq!(
+            hex::encode(sha256(&bytes)),
+            "312aa616ce5d95a7b583cc02c1d69eb9373ca7646ecb6cb42c33e0e465cc2597"
+        );
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let (aggregator_key, _) = generate_keypair();
+        let proof = ConsensusProof::build(
+            "BTC/USD",
+            1_700_035_200,
+            vec![submission("node-1", "binance", 6_500_000), submission("node-2", "kraken", 6_500_101)],
+            &aggregator_key,
+        )
+        .unwrap();
+        // even count: half-even mean of the two middle prices
+        assert_eq!(proof.settlement_price(), 6_500_050);
+
+        let mut forged_median = proof.clone();
+        forged_median.transcript.median_cents = 6_600_000;
+        assert!(forged_median.verify().is_err());
+
+        // re-signed by the aggregator, but the node signature no longer matches
+        let mut forged_price = proof.clone();
+        forged_price.submissions[0].price_cents = 6_000_000;
+        forged_price.transcript = MedianTranscript::compute(&forged_price.submissions).unwrap();
+        forged_price.aggregator_signature = sign_data(&forged_price.body_bytes().unwrap(), &aggregator_key).unwrap();
+        assert!(matches!(forged_price.verify(), Err(OracleVmError::Crypto(_))));
+
+        // a live signature does not cover the backfill payload
+        let mut relabelled = proof.clone();
+        relabelled.submissions[0].backfilled = true;
+        relabelled.aggregator_signature = sign_data(&relabelled.body_bytes().unwrap(), &aggregator_key).unwrap();
+        assert_eq!(ConsensusProof::from_bytes(&relabelled.to_bytes().unwrap()).unwrap(), relabelled);
+        assert!(relabelled.verify().is_err());
+
+        let mut bytes = proof.to_bytes().unwrap();
+        let last = bytes.len() - 70;
+        bytes[last] ^= 1;
+        assert!(ConsensusProof::from_bytes(&bytes).map_or(true, |decoded| decoded.verify().is_err()));
+    }
+}
@@ -0,0 +1,101 @@
+//! Standard contract sizing rules
+//!
+//! Option quantities are whole multiples of a standard contract size and
+//! premiums move in fixed ticks. Quoting and contract creation share these
+//! rules so a request that one side accepts is never rejected by the other.
+
+use crate::PricingError;
+use serde::{Deserialize, Serialize};
+
+/// Contract size and premium tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContractSpec {
+    /// Satoshis per contract (1_000_000 = 0.01 BTC)
+    pub contract_size: u64,
+    /// Minimum contracts per order
+    pub min_contracts: u64,
+    /// Maximum contracts per order
+    pub max_contracts: u64,
+    /// Premium tick (satoshis)
+    pub premium_tick: u64,
+}
+
+impl Default for ContractSpec {
+    fn default() -> Self {
+        Self {
+            contract_size: 1_000_000,
+            min_contracts: 1,
+            max_contracts: 500,
+            premium_tick: 100,
+        }
+    }
+}
+
+impl ContractSpec {
+    /// Number of contracts in `quantity`, rejecting fractional or out-of-range sizes
+    pub fn contracts(&self, quantity: u64) -> Result<u64, PricingError> {
+        if quantity == 0 || !quantity.is_multiple_of(self.contract_size) {
+            return Err(PricingError::NonConformingSize {
+                quantity,
+                contract_size: self.contract_size,
+            });
+        }
+        let contracts = quantity / self.contract_size;
+        if contracts < self.min_contracts || contracts > self.max_contracts {
+            return Err(PricingError::InvalidInput(format!(
+                "{} contracts outside allowed range {}..={}",
+                contracts, self.min_contracts, self.max_contracts
+            )));
+        }
+        Ok(contracts)
+    }
+
+    /// Reject premiums that are not a multiple of the tick
+    pub fn check_premium(&self, premium: u64) -> Result<(), PricingError> {
+        if !premium.is_multiple_of(self.premium_tick) {
+            return Err(PricingError::OffTickPremium {
+                premium,
+                tick: self.premium_tick,
+            });
+        }
+        Ok(())
+    }
+
+    /// Round a premium up to the next tick (the pool is the seller)
+    pub fn round_premium(&self, premium: u64) -> u64 {
+        premium.div_ceil(self.premium_tick) * self.premium_tick
+    }
+
+    /// Premium for filling `fill_quantity` of an order priced at `premium` for `quantity`
+    pub fn pro_rata_premium(&self, premium: u64, quantity: u64, fill_quantity: u64) -> u64 {
+        let share = (premium as u128 * fill_quantity as u128).div_ceil(quantity as u128) as u64;
+        self.round_premium(share)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes_and_ticks() {
+        let spec = ContractSpec::default();
+        assert_eq!(spec.contracts(10_000_000), Ok(10));
+        assert_eq!(
+            spec.contracts(10_500_000),
+            Err(PricingError::NonConformingSize {
+                quantity: 10_500_000,
+                contract_size: 1_000_000
+            })
+        );
+        assert!(spec.contracts(0).is_err());
+        assert!(spec.contracts(501 * 1_000_000).is_err());
+
+        assert!(spec.check_premium(250_000).is_ok());
+        assert!(spec.check_premium(250_050).is_err());
+        assert_eq!(spec.round_premium(250_001), 250_100);
+        assert_eq!(spec.pro_rata_premium(250_000, 10_000_000, 3_000_000), 75_000);
+        assert_eq!(spec.pro_rata_premium(100_100, 3_000_000, 1_000_000), 33_400);
+    }
+}
@@ -1,9 +1,15 @@
 //! Cryptographic utilities for Oracle VM
 
 use crate::{OracleVmError, Result};
-use bitcoin::secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey};
+use bitcoin::secp256k1::{Message, Secp256k1};
 use sha2::{Digest, Sha256};
 
+pub use bitcoin::secp256k1::{ecdsa::Signature, PublicKey, SecretKey};
+pub use crate::keystore::{
+    open_key_store, open_service_key_store, EncryptedFileKeyStore, EnvKeyStore, KeyStore, MemoryKeyStore,
+    RemoteSignerKeyStore, SignerTransport, VaultTransitTransport,
+};
+
 /// Sign data with a private key
 pub fn sign_data(data: &[u8], secret_key: &SecretKey) -> Result<Signature> {
     let secp = Secp256k1::new();
@@ -37,11 +43,109 @@ pub fn generate_keypair() -> (SecretKey, PublicKey) {
     secp.generate_keypair(&mut rand::thread_rng())
 }
 
+/// Derive the public key for a secret key
+pub fn public_key_from_secret(secret_key: &SecretKey) -> PublicKey {
+    PublicKey::from_secret_key(&Secp256k1::new(), secret_key)
+}
+
+/// Canonical bytes signed by an oracle node for a price submission
+///
+/// The price is rendered from integer cents with eight decimals, byte-for-byte
+/// what the earlier `{:.8}` dollar formatting produced for whole-cent prices,
+/// so signatures and ledger leaves from older nodes still verify.
+pub fn price_submission_payload(
+    node_id: &str,
+    source: &str,
+    price_cents: u64,
+    timestamp: u64,
+    nonce: u64,
+    degraded: bool,
+) -> Vec<u8> {
+    format!(
+        "price|{}|{}|{}.{:02}000000|{}|{}|{}",
+        node_id,
+        source,
+        price_cents / 100,
+        price_cents % 100,
+        timestamp,
+        nonce,
+        degraded
+    )
+    .into_bytes()
+}
+
+/// Canonical bytes signed by an oracle node for a backfilled (historical) price
+///
+/// Backfilled points are reconstructed from exchange klines after downtime, so
+/// they are signed under a distinct prefix and can never pass as live data.
+pub fn backfill_submission_payload(
+    node_id: &str,
+    source: &str,
+    price_cents: u64,
+    timestamp: u64,
+    nonce: u64,
+    degraded: bool,
+) -> Vec<u8> {
+    let mut payload = b"backfill|".to_vec();
+    payload.extend(price_submission_payload(node_id, source, price_cents, timestamp, nonce, degraded));
+    payload
+}
+
+/// Canonical bytes signed by an oracle node to prove key ownership at registration
+pub fn node_registration_payload(node_id: &str, public_key: &PublicKey) -> Vec<u8> {
+    format!("register|{}|{}", node_id, public_key).into_bytes()
+}
+
+/// Canonical bytes signed by an oracle node to acquire or renew a submission lease
+pub fn lease_request_payload(node_id: &str, exchange: &str, ttl_secs: u64, nonce: u64) -> Vec<u8> {
+    format!("lease|{}|{}|{}|{}", node_id, exchange, ttl_secs, nonce).into_bytes()
+}
+
+/// Canonical bytes signed by an oracle node for an implied volatility surface
+///
+/// Each point is `(expiry, strike, is_call, mark_iv)`. Floats are rendered with
+/// Rust's shortest round-trip formatting, so both ends derive the same bytes
+/// from the protobuf doubles.
+pub fn vol_surface_payload(
+    node_id: &str,
+    source: &str,
+    timestamp: u64,
+    underlying_price: f64,
+    points: impl IntoIterator<Item = (u64, f64, bool, f64)>,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut payload = format!("vol|{}|{}|{}|{}|{}", node_id, source, timestamp, underlying_price, nonce);
+    for (expiry, strike, is_call, mark_iv) in points {
+        payload.push_str(&format!("|{},{},{},{}", expiry, strike, is_call, mark_iv));
+    }
+    payload.into_bytes()
+}
+
+/// Canonical bytes signed by an oracle node to submit its FROST nonce commitment
+///
+/// Binds the commitment to the round and to the participant number, so a node
+/// cannot commit on behalf of another signer. `hiding` and `binding` are the
+/// compressed hex points exactly as sent.
+pub fn threshold_commit_payload(node_id: &str, round_id: u64, signer: u16, hiding: &str, binding: &str) -> Vec<u8> {
+    format!("threshold-commit|{}|{}|{}|{}|{}", node_id, round_id, signer, hiding, binding).into_bytes()
+}
+
 /// Hash data with SHA256
 pub fn sha256(data: &[u8]) -> [u8; 32] {
     Sha256::digest(data).into()
 }
 
+/// Compare two byte strings without exiting early on the first mismatch
+///
+/// Used for token and API key hashes so response timing does not reveal how
+/// many leading bytes of a guess were right.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 /// Merkle tree implementation
 pub struct MerkleTree {
     leaves: Vec<[u8; 32]>,
@@ -119,6 +223,50 @@ impl MerkleTree {
 
         Some(proof)
     }
+
+    /// Verify a proof produced by [`MerkleTree::proof`] for the leaf at `index`
+    /// of a tree with `leaf_count` leaves
+    ///
+    /// The leaf count is needed because an unpaired node is promoted to the
+    /// next level without a sibling, so the proof skips that level.
+    pub fn verify_proof(
+        leaf: [u8; 32],
+        index: usize,
+        leaf_count: usize,
+        proof: &[[u8; 32]],
+        root: [u8; 32],
+    ) -> bool {
+        if index >= leaf_count {
+            return false;
+        }
+
+        let mut hash = leaf;
+        let mut index = index;
+        let mut level_len = leaf_count;
+        let mut siblings = proof.iter();
+
+        while level_len > 1 {
+            let sibling_index = index ^ 1;
+            if sibling_index < level_len {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                let mut hasher = Sha256::new();
+                if sibling_index > index {
+                    hasher.update(hash);
+                    hasher.update(sibling);
+                } else {
+                    hasher.update(sibling);
+                    hasher.update(hash);
+                }
+                hash = hasher.finalize().into();
+            }
+            index /= 2;
+            level_len = level_len.div_ceil(2);
+        }
+
+        siblings.next().is_none() && hash == root
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +284,33 @@ mod tests {
         assert!(is_valid);
     }
 
+    #[test]
+    fn test_submission_payload_binds_nonce() {
+        let (secret_key, public_key) = generate_keypair();
+        let payload =
+            price_submission_payload("node-1", "binance", 7_000_050, 1_700_000_000, 1, false);
+        let signature = sign_data(&payload, &secret_key).unwrap();
+
+        let replayed =
+            price_submission_payload("node-1", "binance", 7_000_050, 1_700_000_000, 2, false);
+        let relabeled =
+            price_submission_payload("node-1", "binance", 7_000_050, 1_700_000_000, 1, true);
+        assert!(verify_signature(&payload, &signature, &public_key).unwrap());
+        assert!(!verify_signature(&replayed, &signature, &public_key).unwrap());
+        assert!(!verify_signature(&relabeled, &signature, &public_key).unwrap());
+        // Cent rendering matches the legacy `{:.8}` dollar format byte for byte
+        let legacy = format!("price|node-1|binance|{:.8}|1700000000|1|false", 70000.5);
+        assert_eq!(payload, legacy.into_bytes());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
     #[test]
     fn test_merkle_tree() {
         let leaves = vec![
@@ -152,4 +327,20 @@ mod tests {
         let proof = tree.proof(0).unwrap();
         assert!(!proof.is_empty());
     }
+
+    #[test]
+    fn test_merkle_proof_verification() {
+        let leaves: Vec<[u8; 32]> = (0..5u8).map(|i| sha256(&[i])).collect();
+        let tree = MerkleTree::new(leaves.clone());
+        let root = tree.root();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert!(MerkleTree::verify_proof(*leaf, index, leaves.len(), &proof, root));
+            assert!(!MerkleTree::verify_proof(sha256(b"forged"), index, leaves.len(), &proof, root));
+        }
+        let proof = tree.proof(1).unwrap();
+        assert!(!MerkleTree::verify_proof(leaves[1], 2, leaves.len(), &proof, root));
+        assert!(!MerkleTree::verify_proof(leaves[1], 1, 4, &proof, root));
+    }
 }
@@ -36,3 +36,971 @@ pub enum OracleVmError {
 }
 
 pub type Result<T> = std::result::Result<T, OracleVmError>;
+
+/// Stable error code and retry classification shared by all component errors
+pub trait ErrorClass {
+    /// Machine-readable code (e.g. `"CONTRACT_INSUFFICIENT_LIQUIDITY"`)
+    fn code(&self) -> &'static str;
+
+    /// Whether the same request may succeed if retried later
+    fn is_retryable(&self) -> bool;
+}
+
+/// Price collection and consensus errors (oracle-node, aggregator)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OracleError {
+    #[error("{source_name} request failed: {message}")]
+    Exchange {
+        source_name: String,
+        message: String,
+    },
+
+    #[error("{0} rate limit exceeded")]
+    RateLimited(String),
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Price out of range: {0}")]
+    PriceOutOfRange(String),
+
+    #[error("No price data available")]
+    NoPriceData,
+
+    #[error("Consensus not reached: {0}")]
+    ConsensusNotReached(String),
+
+    #[error("All price sources failed: {0}")]
+    AllSourcesFailed(String),
+
+    #[error("No committed price for {0}")]
+    CommitmentNotFound(String),
+}
+
+impl ErrorClass for OracleError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Exchange { .. } => "ORACLE_EXCHANGE",
+            Self::RateLimited(_) => "ORACLE_RATE_LIMITED",
+            Self::InvalidResponse(_) => "ORACLE_INVALID_RESPONSE",
+            Self::PriceOutOfRange(_) => "ORACLE_PRICE_OUT_OF_RANGE",
+            Self::NoPriceData => "ORACLE_NO_PRICE_DATA",
+            Self::ConsensusNotReached(_) => "ORACLE_CONSENSUS_NOT_REACHED",
+            Self::AllSourcesFailed(_) => "ORACLE_ALL_SOURCES_FAILED",
+            Self::CommitmentNotFound(_) => "ORACLE_COMMITMENT_NOT_FOUND",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            Self::InvalidResponse(_) | Self::PriceOutOfRange(_) | Self::CommitmentNotFound(_)
+        )
+    }
+}
+
+/// Premium calculation and quoting errors (calculation)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum PricingError {
+    #[error("Invalid pricing input: {0}")]
+    InvalidInput(String),
+
+    #[error("No market data: {0}")]
+    NoMarketData(String),
+
+    #[error("{0}")]
+    NotConverged(String),
+
+    #[error("Order size {size:.4} BTC exceeds max {max:.4} BTC")]
+    OrderTooLarge { size: f64, max: f64 },
+
+    #[error("Insufficient pool liquidity")]
+    InsufficientLiquidity,
+
+    #[error("Quote signing failed: {0}")]
+    Signing(String),
+
+    #[error("Repository error: {0}")]
+    Repository(String),
+
+    #[error("Expiry {0} is not on the expiry calendar")]
+    NonStandardExpiry(String),
+
+    #[error("Quantity {quantity} sats is not a multiple of the {contract_size} sat contract size")]
+    NonConformingSize { quantity: u64, contract_size: u64 },
+
+    #[error("Premium {premium} sats is not on the {tick} sat tick")]
+    OffTickPremium { premium: u64, tick: u64 },
+
+    #[error("Trade {greek} {value:.4} exceeds per-trade limit {limit:.4}; max size {max_quantity} sats, suggested split {schedule:?}")]
+    GreeksLimitExceeded {
+        greek: String,
+        value: f64,
+        limit: f64,
+        /// Largest size that fits the limits (0 if none)
+        max_quantity: u64,
+        /// Suggested trade sizes covering the requested quantity
+        schedule: Vec<u64>,
+    },
+
+    #[error("Position collateral {collateral} sats exceeds the remaining {scope} open-interest capacity of {remaining} sats")]
+    OpenInterestCapExceeded {
+        /// "strike" or "expiry"
+        scope: String,
+        collateral: u64,
+        remaining: u64,
+    },
+}
+
+impl ErrorClass for PricingError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInput(_) => "PRICING_INVALID_INPUT",
+            Self::NoMarketData(_) => "PRICING_NO_MARKET_DATA",
+            Self::NotConverged(_) => "PRICING_NOT_CONVERGED",
+            Self::OrderTooLarge { .. } => "PRICING_ORDER_TOO_LARGE",
+            Self::InsufficientLiquidity => "PRICING_INSUFFICIENT_LIQUIDITY",
+            Self::Signing(_) => "PRICING_SIGNING",
+            Self::Repository(_) => "PRICING_REPOSITORY",
+            Self::NonStandardExpiry(_) => "PRICING_NON_STANDARD_EXPIRY",
+            Self::NonConformingSize { .. } => "PRICING_NON_CONFORMING_SIZE",
+            Self::OffTickPremium { .. } => "PRICING_OFF_TICK_PREMIUM",
+            Self::GreeksLimitExceeded { .. } => "PRICING_GREEKS_LIMIT_EXCEEDED",
+            Self::OpenInterestCapExceeded { .. } => "PRICING_OPEN_INTEREST_CAP_EXCEEDED",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::NoMarketData(_) | Self::InsufficientLiquidity | Self::Repository(_)
+        )
+    }
+}
+
+/// Repository traits report failures as strings
+impl From<String> for PricingError {
+    fn from(message: String) -> Self {
+        Self::Repository(message)
+    }
+}
+
+/// Option lifecycle errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ContractError {
+    #[error("Trading halted: {0}")]
+    TradingHalted(String),
+
+    #[error("Insufficient liquidity: need {required} sats, {available} available")]
+    InsufficientLiquidity { required: u64, available: u64 },
+
+    #[error("Firm quote required")]
+    QuoteRequired,
+
+    #[error("Quote signing key not configured")]
+    QuoteKeyMissing,
+
+    #[error("Invalid quote: {0}")]
+    InvalidQuote(String),
+
+    #[error("Quote {quote_id} expired at {valid_until}")]
+    QuoteExpired { quote_id: String, valid_until: u64 },
+
+    #[error("Quote {0} already used")]
+    QuoteReused(String),
+
+    #[error("Quote {quote_id} expires at height {quoted}, requested {requested}")]
+    ExpiryMismatch { quote_id: String, quoted: u32, requested: u32 },
+
+    #[error("Expiry height unknown: {0}")]
+    ExpiryHeightUnknown(String),
+
+    #[error("Option {0} already exists")]
+    DuplicateOption(String),
+
+    #[error("Idempotency key {0} was used with a different request")]
+    IdempotencyConflict(String),
+
+    #[error("Pool ledger rejected transaction: {0}")]
+    Ledger(String),
+
+    #[error(transparent)]
+    Pricing(#[from] PricingError),
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+
+    #[error("Settlement currency not supported: {0}")]
+    UnsupportedCurrency(String),
+
+    #[error("Invalid referral: {0}")]
+    InvalidReferral(String),
+
+    #[error("Risk limit exceeded: {0}")]
+    RiskLimitExceeded(String),
+
+    #[error("User {user_id} not eligible: {reason}")]
+    Ineligible { user_id: String, reason: String },
+
+    #[error("Eligibility check unavailable: {0}")]
+    EligibilityUnavailable(String),
+
+    #[error("Insufficient LP shares: requested {requested}, {available} held")]
+    InsufficientShares { requested: u64, available: u64 },
+
+    #[error("Exit claim error: {0}")]
+    ExitClaim(String),
+
+    #[error("Target theta {requested} outside allowed band {min}..={max}")]
+    ThetaOutOfBand { requested: f64, min: f64, max: f64 },
+
+    #[error("Buy-back rejected: {0}")]
+    BuyBack(String),
+
+    #[error("Cooperative close rejected: {0}")]
+    CooperativeClose(String),
+
+    #[error("Structured note rejected: {0}")]
+    StructuredNote(String),
+
+    #[error("Yield venue error: {0}")]
+    YieldVenue(String),
+}
+
+impl ErrorClass for ContractError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::TradingHalted(_) => "CONTRACT_TRADING_HALTED",
+            Self::InsufficientLiquidity { .. } => "CONTRACT_INSUFFICIENT_LIQUIDITY",
+            Self::QuoteRequired => "CONTRACT_QUOTE_REQUIRED",
+            Self::QuoteKeyMissing => "CONTRACT_QUOTE_KEY_MISSING",
+            Self::InvalidQuote(_) => "CONTRACT_INVALID_QUOTE",
+            Self::QuoteExpired { .. } => "CONTRACT_QUOTE_EXPIRED",
+            Self::QuoteReused(_) => "CONTRACT_QUOTE_REUSED",
+            Self::ExpiryMismatch { .. } => "CONTRACT_EXPIRY_MISMATCH",
+            Self::ExpiryHeightUnknown(_) => "CONTRACT_EXPIRY_HEIGHT_UNKNOWN",
+            Self::DuplicateOption(_) => "CONTRACT_DUPLICATE_OPTION",
+            Self::IdempotencyConflict(_) => "CONTRACT_IDEMPOTENCY_CONFLICT",
+            Self::Ledger(_) => "CONTRACT_LEDGER",
+            Self::Pricing(e) => e.code(),
+            Self::Storage(_) => "CONTRACT_STORAGE",
+            Self::UnsupportedCurrency(_) => "CONTRACT_UNSUPPORTED_CURRENCY",
+            Self::InvalidReferral(_) => "CONTRACT_INVALID_REFERRAL",
+            Self::RiskLimitExceeded(_) => "CONTRACT_RISK_LIMIT_EXCEEDED",
+            Self::Ineligible { .. } => "CONTRACT_INELIGIBLE",
+            Self::EligibilityUnavailable(_) => "CONTRACT_ELIGIBILITY_UNAVAILABLE",
+            Self::InsufficientShares { .. } => "CONTRACT_INSUFFICIENT_SHARES",
+            Self::ExitClaim(_) => "CONTRACT_EXIT_CLAIM",
+            Self::ThetaOutOfBand { .. } => "CONTRACT_THETA_OUT_OF_BAND",
+            Self::BuyBack(_) => "CONTRACT_BUY_BACK",
+            Self::CooperativeClose(_) => "CONTRACT_COOPERATIVE_CLOSE",
+            Self::StructuredNote(_) => "CONTRACT_STRUCTURED_NOTE",
+            Self::YieldVenue(_) => "CONTRACT_YIELD_VENUE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::TradingHalted(_)
+            | Self::InsufficientLiquidity { .. }
+            | Self::Storage(_)
+            | Self::ExpiryHeightUnknown(_)
+            | Self::EligibilityUnavailable(_)
+            | Self::YieldVenue(_) => true,
+            Self::Pricing(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Option settlement errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SettlementError {
+    #[error("Option not found: {0}")]
+    OptionNotFound(String),
+
+    #[error("Option not active: {0}")]
+    OptionNotActive(String),
+
+    #[error("Settlement postponed, trading halted: {0}")]
+    Postponed(String),
+
+    #[error("Settlement deferred, price {price} is {deviation_bps} bps from reference {reference}")]
+    PriceOutOfBand {
+        price: u64,     // USD cents
+        reference: u64, // USD cents
+        deviation_bps: u64,
+    },
+
+    #[error("Settlement payout mismatch: expected {expected}, got {actual}")]
+    PayoutMismatch { expected: u64, actual: u64 },
+
+    #[error("Early exercise rejected: {0}")]
+    EarlyExercise(String),
+
+    #[error("Pool ledger rejected settlement: {0}")]
+    Ledger(String),
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl ErrorClass for SettlementError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::OptionNotFound(_) => "SETTLEMENT_OPTION_NOT_FOUND",
+            Self::OptionNotActive(_) => "SETTLEMENT_OPTION_NOT_ACTIVE",
+            Self::Postponed(_) => "SETTLEMENT_POSTPONED",
+            Self::PriceOutOfBand { .. } => "SETTLEMENT_PRICE_OUT_OF_BAND",
+            Self::PayoutMismatch { .. } => "SETTLEMENT_PAYOUT_MISMATCH",
+            Self::EarlyExercise(_) => "SETTLEMENT_EARLY_EXERCISE",
+            Self::Ledger(_) => "SETTLEMENT_LEDGER",
+            Self::Storage(_) => "SETTLEMENT_STORAGE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Postponed(_) | Self::PriceOutOfBand { .. } | Self::Storage(_)
+        )
+    }
+}
+
+/// On-chain commitment anchoring errors (committer, bitcoin-client)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AnchorError {
+    #[error("Bitcoin RPC error: {0}")]
+    Rpc(String),
+
+    #[error("Broadcast rejected: {0}")]
+    BroadcastRejected(String),
+
+    #[error("Insufficient funds: need {required} sats, {available} available")]
+    InsufficientFunds { required: u64, available: u64 },
+
+    #[error("Invalid anchor payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Invalid anchor route: {0}")]
+    InvalidRoute(String),
+
+    #[error("Conflicting spend: {0}")]
+    ConflictingSpend(String),
+}
+
+impl ErrorClass for AnchorError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Rpc(_) => "ANCHOR_RPC",
+            Self::BroadcastRejected(_) => "ANCHOR_BROADCAST_REJECTED",
+            Self::InsufficientFunds { .. } => "ANCHOR_INSUFFICIENT_FUNDS",
+            Self::InvalidPayload(_) => "ANCHOR_INVALID_PAYLOAD",
+            Self::InvalidRoute(_) => "ANCHOR_INVALID_ROUTE",
+            Self::ConflictingSpend(_) => "ANCHOR_CONFLICTING_SPEND",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::Rpc(_) | Self::InsufficientFunds { .. })
+    }
+}
+
+/// State snapshot and restore errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SnapshotError {
+    #[error("Snapshot I/O error: {0}")]
+    Io(String),
+
+    #[error("Corrupt snapshot: {0}")]
+    Corrupt(String),
+
+    #[error("Unsupported snapshot schema version {found} (expected {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+
+    #[error("Snapshot checksum mismatch: expected {expected}, computed {computed}")]
+    ChecksumMismatch { expected: String, computed: String },
+
+    #[error("Snapshot state is inconsistent: {0}")]
+    Inconsistent(String),
+
+    #[error("Anchor {0} not found on chain")]
+    AnchorMissing(String),
+
+    #[error("Anchor {0} payload does not match the snapshot")]
+    AnchorMismatch(String),
+
+    #[error(transparent)]
+    Anchor(#[from] AnchorError),
+}
+
+impl ErrorClass for SnapshotError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Io(_) => "SNAPSHOT_IO",
+            Self::Corrupt(_) => "SNAPSHOT_CORRUPT",
+            Self::UnsupportedVersion { .. } => "SNAPSHOT_UNSUPPORTED_VERSION",
+            Self::ChecksumMismatch { .. } => "SNAPSHOT_CHECKSUM_MISMATCH",
+            Self::Inconsistent(_) => "SNAPSHOT_INCONSISTENT",
+            Self::AnchorMissing(_) => "SNAPSHOT_ANCHOR_MISSING",
+            Self::AnchorMismatch(_) => "SNAPSHOT_ANCHOR_MISMATCH",
+            Self::Anchor(e) => e.code(),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::Io(_) => true,
+            Self::Anchor(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Payout beneficiary errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum BeneficiaryError {
+    #[error("No beneficiary registered for option {0}")]
+    NotFound(String),
+
+    #[error("Beneficiary for option {0} already registered")]
+    AlreadyRegistered(String),
+
+    #[error("Invalid payout address {address}: {reason}")]
+    InvalidAddress { address: String, reason: String },
+
+    #[error("Invalid beneficiary update signature for option {0}")]
+    InvalidSignature(String),
+
+    #[error("Stale beneficiary update nonce: expected {expected}, got {got}")]
+    StaleNonce { expected: u64, got: u64 },
+
+    #[error("Beneficiary changes for option {option_id} closed at height {cutoff_height}")]
+    CutoffPassed { option_id: String, cutoff_height: u32 },
+
+    #[error("Option not active: {0}")]
+    OptionNotActive(String),
+
+    #[error("Chain tip height unknown, cannot check the change cut-off")]
+    TipUnknown,
+
+    #[error("Beneficiary registry is not enabled")]
+    Disabled,
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl ErrorClass for BeneficiaryError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "BENEFICIARY_NOT_FOUND",
+            Self::AlreadyRegistered(_) => "BENEFICIARY_ALREADY_REGISTERED",
+            Self::InvalidAddress { .. } => "BENEFICIARY_INVALID_ADDRESS",
+            Self::InvalidSignature(_) => "BENEFICIARY_INVALID_SIGNATURE",
+            Self::StaleNonce { .. } => "BENEFICIARY_STALE_NONCE",
+            Self::CutoffPassed { .. } => "BENEFICIARY_CUTOFF_PASSED",
+            Self::OptionNotActive(_) => "BENEFICIARY_OPTION_NOT_ACTIVE",
+            Self::TipUnknown => "BENEFICIARY_TIP_UNKNOWN",
+            Self::Disabled => "BENEFICIARY_DISABLED",
+            Self::Storage(_) => "BENEFICIARY_STORAGE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::TipUnknown | Self::Storage(_))
+    }
+}
+
+/// Delta hedge execution errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum HedgeError {
+    #[error("Hedge venue {0} unavailable")]
+    VenueUnavailable(String),
+
+    #[error("Hedge order rejected by {venue}: {reason}")]
+    Rejected { venue: String, reason: String },
+
+    #[error("Unknown rebalance request {0}")]
+    UnknownRequest(u64),
+}
+
+impl ErrorClass for HedgeError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::VenueUnavailable(_) => "HEDGE_VENUE_UNAVAILABLE",
+            Self::Rejected { .. } => "HEDGE_REJECTED",
+            Self::UnknownRequest(_) => "HEDGE_UNKNOWN_REQUEST",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::VenueUnavailable(_))
+    }
+}
+
+/// Dead-man switch / emergency withdrawal errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum EmergencyError {
+    #[error("No recovery transaction has been pre-signed")]
+    PackageNotFound,
+
+    #[error("Invalid emergency configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Invalid LP payout address {address}: {reason}")]
+    InvalidAddress { address: String, reason: String },
+
+    #[error("Pool output of {value} sats cannot cover the {fee} sat recovery fee")]
+    InsufficientValue { value: u64, fee: u64 },
+
+    #[error("Recovery signing failed: {0}")]
+    Signing(String),
+
+    #[error("Recovery timelock active until height {available_at} (tip {tip})")]
+    TimelockActive { available_at: u32, tip: u32 },
+
+    #[error("Recovery package store error: {0}")]
+    Storage(String),
+
+    #[error(transparent)]
+    Chain(#[from] AnchorError),
+}
+
+impl ErrorClass for EmergencyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::PackageNotFound => "EMERGENCY_PACKAGE_NOT_FOUND",
+            Self::InvalidConfig(_) => "EMERGENCY_INVALID_CONFIG",
+            Self::InvalidAddress { .. } => "EMERGENCY_INVALID_ADDRESS",
+            Self::InsufficientValue { .. } => "EMERGENCY_INSUFFICIENT_VALUE",
+            Self::Signing(_) => "EMERGENCY_SIGNING",
+            Self::TimelockActive { .. } => "EMERGENCY_TIMELOCK_ACTIVE",
+            Self::Storage(_) => "EMERGENCY_STORAGE",
+            Self::Chain(e) => e.code(),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::TimelockActive { .. } | Self::Storage(_) => true,
+            Self::Chain(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Bilateral OTC deal errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OtcError {
+    #[error("OTC deal not found: {0}")]
+    DealNotFound(String),
+
+    #[error("OTC deal already exists: {0}")]
+    DuplicateDeal(String),
+
+    #[error("Invalid OTC terms: {0}")]
+    InvalidTerms(String),
+
+    #[error("OTC deal {deal_id} signature from {counterparty} does not verify")]
+    InvalidSignature { deal_id: String, counterparty: String },
+
+    #[error("OTC deal {deal_id} is {status}, expected {expected}")]
+    InvalidState {
+        deal_id: String,
+        status: String,
+        expected: String,
+    },
+
+    #[error("OTC escrow of {actual} sats does not match the required {required} sats")]
+    EscrowMismatch { required: u64, actual: u64 },
+
+    #[error("OTC deal {deal_id} expires at height {expiry_height} (tip {tip})")]
+    NotExpired {
+        deal_id: String,
+        expiry_height: u32,
+        tip: u32,
+    },
+
+    #[error(transparent)]
+    Settlement(#[from] SettlementError),
+}
+
+impl ErrorClass for OtcError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::DealNotFound(_) => "OTC_DEAL_NOT_FOUND",
+            Self::DuplicateDeal(_) => "OTC_DUPLICATE_DEAL",
+            Self::InvalidTerms(_) => "OTC_INVALID_TERMS",
+            Self::InvalidSignature { .. } => "OTC_INVALID_SIGNATURE",
+            Self::InvalidState { .. } => "OTC_INVALID_STATE",
+            Self::EscrowMismatch { .. } => "OTC_ESCROW_MISMATCH",
+            Self::NotExpired { .. } => "OTC_NOT_EXPIRED",
+            Self::Settlement(e) => e.code(),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::NotExpired { .. } => true,
+            Self::Settlement(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Option position token issuance errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TokenError {
+    #[error("Position token already issued for option {0}")]
+    AlreadyIssued(String),
+
+    #[error("No position token for option {0}")]
+    NotIssued(String),
+
+    #[error("Token issuer unavailable: {0}")]
+    IssuerUnavailable(String),
+
+    #[error("Token issuer rejected {option_id}: {reason}")]
+    Rejected { option_id: String, reason: String },
+}
+
+impl ErrorClass for TokenError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::AlreadyIssued(_) => "TOKEN_ALREADY_ISSUED",
+            Self::NotIssued(_) => "TOKEN_NOT_ISSUED",
+            Self::IssuerUnavailable(_) => "TOKEN_ISSUER_UNAVAILABLE",
+            Self::Rejected { .. } => "TOKEN_REJECTED",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::IssuerUnavailable(_))
+    }
+}
+
+/// Claimable balance and withdrawal errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ClaimError {
+    #[error("Claimable balances are not enabled")]
+    Disabled,
+
+    #[error("Withdrawal of {amount} sats is below the {min} sat minimum")]
+    BelowMinimum { amount: u64, min: u64 },
+
+    #[error("Invalid withdrawal address {address}: {reason}")]
+    InvalidAddress { address: String, reason: String },
+
+    #[error("Withdrawal {0} not found")]
+    WithdrawalNotFound(u64),
+
+    #[error("Withdrawal {0} already broadcast")]
+    AlreadyBroadcast(u64),
+
+    #[error("Receipt signing failed: {0}")]
+    Signing(String),
+
+    #[error(transparent)]
+    Account(#[from] AccountKeyError),
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl ErrorClass for ClaimError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Disabled => "CLAIM_DISABLED",
+            Self::BelowMinimum { .. } => "CLAIM_BELOW_MINIMUM",
+            Self::InvalidAddress { .. } => "CLAIM_INVALID_ADDRESS",
+            Self::WithdrawalNotFound(_) => "CLAIM_WITHDRAWAL_NOT_FOUND",
+            Self::AlreadyBroadcast(_) => "CLAIM_ALREADY_BROADCAST",
+            Self::Signing(_) => "CLAIM_SIGNING",
+            Self::Account(e) => e.code(),
+            Self::Storage(_) => "CLAIM_STORAGE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::BelowMinimum { .. } | Self::Storage(_))
+    }
+}
+
+/// Protocol fee schedule and treasury errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TreasuryError {
+    #[error("Invalid fee schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("Treasury balance too low: requested {requested} sats, {available} available")]
+    InsufficientBalance { requested: u64, available: u64 },
+
+    #[error("Treasury withdrawal destination not allowed: {0}")]
+    DestinationNotAllowed(String),
+
+    #[error("Treasury daily limit exceeded: requested {requested} sats, {remaining} remaining")]
+    DailyLimitExceeded { requested: u64, remaining: u64 },
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl ErrorClass for TreasuryError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidSchedule(_) => "TREASURY_INVALID_SCHEDULE",
+            Self::InsufficientBalance { .. } => "TREASURY_INSUFFICIENT_BALANCE",
+            Self::DestinationNotAllowed(_) => "TREASURY_DESTINATION_NOT_ALLOWED",
+            Self::DailyLimitExceeded { .. } => "TREASURY_DAILY_LIMIT_EXCEEDED",
+            Self::Storage(_) => "TREASURY_STORAGE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::DailyLimitExceeded { .. } | Self::Storage(_))
+    }
+}
+
+/// Tenant namespace errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TenantError {
+    #[error("Tenant not found: {0}")]
+    NotFound(String),
+
+    #[error("Missing or invalid API key for tenant {0}")]
+    Unauthorized(String),
+
+    #[error("Invalid tenant config: {0}")]
+    InvalidConfig(String),
+}
+
+impl ErrorClass for TenantError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "TENANT_NOT_FOUND",
+            Self::Unauthorized(_) => "TENANT_UNAUTHORIZED",
+            Self::InvalidConfig(_) => "TENANT_INVALID_CONFIG",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
+/// Account key errors: holder/LP requests signed by the key bound to the account (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AccountKeyError {
+    #[error("No key registered for account {0}")]
+    NoKey(String),
+
+    #[error("Account {0} is already bound to a different key")]
+    KeyMismatch(String),
+
+    #[error("Invalid account key or signature: {0}")]
+    Malformed(String),
+
+    #[error("Signature does not verify for account {0}")]
+    Unauthorized(String),
+
+    #[error("Nonce {nonce} for account {account} must be above {last}")]
+    StaleNonce { account: String, nonce: u64, last: u64 },
+
+    #[error("Account {0} already has pool state, its key must be bound by the operator")]
+    Existing(String),
+
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl ErrorClass for AccountKeyError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NoKey(_) => "ACCOUNT_NO_KEY",
+            Self::KeyMismatch(_) => "ACCOUNT_KEY_MISMATCH",
+            Self::Malformed(_) => "ACCOUNT_MALFORMED",
+            Self::Unauthorized(_) => "ACCOUNT_UNAUTHORIZED",
+            Self::StaleNonce { .. } => "ACCOUNT_STALE_NONCE",
+            Self::Existing(_) => "ACCOUNT_EXISTING",
+            Self::Storage(_) => "ACCOUNT_STORAGE",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        matches!(self, Self::Storage(_))
+    }
+}
+
+/// Orchestration flow errors (a step gave up after its retry policy)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum FlowError {
+    #[error("Flow {flow} step {step} failed after {attempts} attempts: {reason}")]
+    StepFailed {
+        flow: String,
+        step: String,
+        attempts: u32,
+        reason: String,
+    },
+
+    #[error("Flow {flow} step {step} timed out after {attempts} attempts ({timeout_ms} ms each)")]
+    StepTimedOut {
+        flow: String,
+        step: String,
+        attempts: u32,
+        timeout_ms: u64,
+    },
+}
+
+impl ErrorClass for FlowError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::StepFailed { .. } => "FLOW_STEP_FAILED",
+            Self::StepTimedOut { .. } => "FLOW_STEP_TIMEOUT",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        // The whole flow runs again on its next tick
+        true
+    }
+}
+
+/// Alerting configuration errors (contracts)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum AlertingError {
+    #[error("Invalid alerting config: {0}")]
+    InvalidConfig(String),
+}
+
+impl ErrorClass for AlertingError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidConfig(_) => "ALERTING_INVALID_CONFIG",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
+
+/// Signing key storage errors (all components)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum KeyStoreError {
+    #[error("Key not found: {0}")]
+    NotFound(String),
+
+    #[error("Key already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    #[error("Key decryption failed: {0}")]
+    Decrypt(String),
+
+    #[error("Key store backend error: {0}")]
+    Backend(String),
+
+    #[error("Ephemeral key store refused: {0}")]
+    Ephemeral(String),
+}
+
+impl ErrorClass for KeyStoreError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "KEYSTORE_NOT_FOUND",
+            Self::AlreadyExists(_) => "KEYSTORE_ALREADY_EXISTS",
+            Self::InvalidKey(_) => "KEYSTORE_INVALID_KEY",
+            Self::Decrypt(_) => "KEYSTORE_DECRYPT",
+            Self::Backend(_) => "KEYSTORE_BACKEND",
+            Self::Ephemeral(_) => "KEYSTORE_EPHEMERAL",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        // Remote signers and key files can come back
+        matches!(self, Self::Backend(_))
+    }
+}
+
+/// Threshold signing (DKG and FROST rounds) errors (oracle-node, aggregator)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ThresholdError {
+    #[error("Invalid threshold parameters: {0}")]
+    InvalidParameters(String),
+
+    #[error("Invalid proof of knowledge from participant {0}")]
+    InvalidProof(u16),
+
+    #[error("Invalid share from participant {0}")]
+    InvalidShare(u16),
+
+    #[error("Missing commitment from participant {0}")]
+    MissingCommitment(u16),
+
+    #[error("Not enough signers: {have} of {need}")]
+    InsufficientSigners { have: usize, need: usize },
+
+    #[error("No unused nonce for signing round {0}")]
+    NonceUnavailable(u64),
+
+    #[error("Signing round not found: {0}")]
+    RoundNotFound(u64),
+
+    #[error("Threshold crypto error: {0}")]
+    Crypto(String),
+}
+
+impl ErrorClass for ThresholdError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidParameters(_) => "THRESHOLD_INVALID_PARAMETERS",
+            Self::InvalidProof(_) => "THRESHOLD_INVALID_PROOF",
+            Self::InvalidShare(_) => "THRESHOLD_INVALID_SHARE",
+            Self::MissingCommitment(_) => "THRESHOLD_MISSING_COMMITMENT",
+            Self::InsufficientSigners { .. } => "THRESHOLD_INSUFFICIENT_SIGNERS",
+            Self::NonceUnavailable(_) => "THRESHOLD_NONCE_UNAVAILABLE",
+            Self::RoundNotFound(_) => "THRESHOLD_ROUND_NOT_FOUND",
+            Self::Crypto(_) => "THRESHOLD_CRYPTO",
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        // Wait for more signers before the round expires
+        matches!(self, Self::InsufficientSigners { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_and_retryability() {
+        let halted = ContractError::TradingHalted("price jump".to_string());
+        assert_eq!(halted.code(), "CONTRACT_TRADING_HALTED");
+        assert!(halted.is_retryable());
+
+        let wrapped = ContractError::from(PricingError::OrderTooLarge { size: 6.0, max: 5.0 });
+        assert_eq!(wrapped.code(), "PRICING_ORDER_TOO_LARGE");
+        assert!(!wrapped.is_retryable());
+
+        assert!(!SettlementError::OptionNotFound("X".to_string()).is_retryable());
+        assert!(OracleError::RateLimited("binance".to_string()).is_retryable());
+        assert!(!AnchorError::InvalidPayload("too long".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_messages_keep_legacy_prefixes() {
+        let err = ContractError::InsufficientLiquidity {
+            required: 10,
+            available: 5,
+        };
+        assert!(err.to_string().starts_with("Insufficient liquidity"));
+        assert!(SettlementError::OptionNotActive("X".to_string())
+            .to_string()
+            .starts_with("Option not active"));
+    }
+}
@@ -0,0 +1,84 @@
+//! System-wide event bus shared by Oracle VM components
+
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Events broadcast between components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SystemEvent {
+    /// Option creation paused and settlements postponed
+    TradingHalted {
+        reason: String,
+        timestamp: u64,         // Unix timestamp (seconds)
+        resume_at: Option<u64>, // Timed resume; None = manual resume only
+    },
+    /// Trading resumed after a halt
+    TradingResumed { timestamp: u64, manual: bool },
+}
+
+/// In-process broadcast bus; every subscriber receives every event
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Sender<SystemEvent>>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new subscriber
+    pub fn subscribe(&self) -> Receiver<SystemEvent> {
+        let (tx, rx) = channel();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    /// Broadcast an event, dropping subscribers whose receiver is gone
+    pub fn publish(&self, event: SystemEvent) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.send(event.clone()).is_ok());
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_to_all_subscribers() {
+        let bus = EventBus::new();
+        let rx1 = bus.subscribe();
+        let rx2 = bus.subscribe();
+
+        let event = SystemEvent::TradingResumed {
+            timestamp: 100,
+            manual: true,
+        };
+        bus.publish(event.clone());
+
+        assert_eq!(rx1.try_recv().unwrap(), event);
+        assert_eq!(rx2.try_recv().unwrap(), event);
+    }
+
+    #[test]
+    fn test_dropped_subscriber_removed() {
+        let bus = EventBus::new();
+        let rx = bus.subscribe();
+        drop(rx);
+
+        bus.publish(SystemEvent::TradingResumed {
+            timestamp: 100,
+            manual: false,
+        });
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+}
@@ -0,0 +1,131 @@
+//! Auto-exercise and dust payout policy
+//!
+//! ITM options are exercised automatically at expiry, but a payout smaller
+//! than the on-chain fee to send it helps nobody. Payouts below the dust
+//! threshold are either kept by the pool as revenue or credited to the
+//! holder's claimable balance. Quotes disclose the policy so buyers know it
+//! before they trade.
+//!
+//! American-style options may also be exercised by the holder before expiry
+//! at the current consensus price; the same payout policy applies.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Standard relay dust limit for a P2PKH output (satoshis)
+pub const STANDARD_DUST_LIMIT_SATS: u64 = 546;
+
+/// What happens to a payout below the dust threshold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DustHandling {
+    /// Kept by the pool as revenue
+    #[default]
+    PoolRevenue,
+    /// Credited to the holder's claimable balance
+    Accumulate,
+}
+
+impl fmt::Display for DustHandling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PoolRevenue => write!(f, "pool_revenue"),
+            Self::Accumulate => write!(f, "accumulate"),
+        }
+    }
+}
+
+/// When the holder may exercise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExerciseStyle {
+    /// Only at expiry
+    #[default]
+    European,
+    /// Any time before expiry
+    American,
+}
+
+impl ExerciseStyle {
+    pub fn is_american(&self) -> bool {
+        *self == Self::American
+    }
+
+    pub fn is_european(&self) -> bool {
+        *self == Self::European
+    }
+}
+
+impl fmt::Display for ExerciseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::European => write!(f, "european"),
+            Self::American => write!(f, "american"),
+        }
+    }
+}
+
+/// Auto-exercise policy; a zero threshold pays out every ITM option
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExercisePolicy {
+    /// Payouts strictly below this are dust (satoshis)
+    pub dust_threshold_sats: u64,
+    pub dust_handling: DustHandling,
+}
+
+/// Result of applying the policy to an intrinsic payout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Exercise {
+    /// Out of the money, nothing owed
+    Expired,
+    /// Paid out in full
+    Paid { amount: u64 },
+    /// Below the threshold and handled as dust
+    Dust { amount: u64, handling: DustHandling },
+}
+
+impl ExercisePolicy {
+    pub fn new(dust_threshold_sats: u64, dust_handling: DustHandling) -> Self {
+        Self {
+            dust_threshold_sats,
+            dust_handling,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.dust_threshold_sats > 0
+    }
+
+    pub fn apply(&self, payout: u64) -> Exercise {
+        match payout {
+            0 => Exercise::Expired,
+            amount if amount < self.dust_threshold_sats => Exercise::Dust {
+                amount,
+                handling: self.dust_handling,
+            },
+            amount => Exercise::Paid { amount },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dust_threshold() {
+        let policy = ExercisePolicy::new(STANDARD_DUST_LIMIT_SATS, DustHandling::Accumulate);
+        assert_eq!(policy.apply(0), Exercise::Expired);
+        assert_eq!(
+            policy.apply(545),
+            Exercise::Dust {
+                amount: 545,
+                handling: DustHandling::Accumulate
+            }
+        );
+        assert_eq!(policy.apply(546), Exercise::Paid { amount: 546 });
+        assert_eq!(ExercisePolicy::default().apply(1), Exercise::Paid { amount: 1 });
+    }
+}
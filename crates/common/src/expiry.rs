@@ -0,0 +1,181 @@
+//! Standardized option expiries
+//!
+//! All listed expiries settle at 08:00 UTC. The calendar lists a rolling
+//! window of daily expiries, weekly expiries on Fridays and monthly expiries
+//! on the last Friday of each month. Quoting and contract creation only accept
+//! listed expiries unless the request is flagged as an OTC deal.
+
+use crate::PricingError;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Settlement time of day for every expiry (UTC)
+pub const EXPIRY_HOUR_UTC: u32 = 8;
+
+/// Expiry series, ordered from shortest to longest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExpiryKind {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// One listed expiry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expiry {
+    /// Longest series this date belongs to
+    pub kind: ExpiryKind,
+    pub date: String,   // YYYY-MM-DD
+    pub timestamp: u64, // Unix timestamp (seconds) at 08:00 UTC
+}
+
+/// Rolling expiry calendar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExpiryCalendar {
+    /// Number of daily expiries listed
+    pub daily: u32,
+    /// Number of weekly (Friday) expiries listed
+    pub weekly: u32,
+    /// Number of monthly (last Friday) expiries listed
+    pub monthly: u32,
+}
+
+impl Default for ExpiryCalendar {
+    fn default() -> Self {
+        Self {
+            daily: 7,
+            weekly: 4,
+            monthly: 3,
+        }
+    }
+}
+
+fn expiry_timestamp(date: NaiveDate) -> u64 {
+    let time = NaiveTime::from_hms_opt(EXPIRY_HOUR_UTC, 0, 0).expect("valid expiry time");
+    date.and_time(time).and_utc().timestamp() as u64
+}
+
+fn last_friday(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    let mut day = NaiveDate::from_ymd_opt(next_year, next_month, 1).expect("valid month") - Duration::days(1);
+    while day.weekday() != Weekday::Fri {
+        day -= Duration::days(1);
+    }
+    day
+}
+
+/// Settlement timestamp (08:00 UTC) of an expiry date (YYYY-MM-DD), listed or not
+pub fn expiry_date_timestamp(date: &str) -> Result<u64, PricingError> {
+    let parsed = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| PricingError::InvalidInput(format!("Invalid expiry date {}: {}", date, e)))?;
+    Ok(expiry_timestamp(parsed))
+}
+
+impl ExpiryCalendar {
+    /// Upcoming listed expiries strictly after `now`, soonest first
+    pub fn expiries(&self, now: u64) -> Vec<Expiry> {
+        let now_dt = DateTime::<Utc>::from_timestamp(now as i64, 0).unwrap_or_default();
+        let mut first = now_dt.date_naive();
+        if expiry_timestamp(first) <= now {
+            first += Duration::days(1);
+        }
+
+        let mut dates: Vec<(NaiveDate, ExpiryKind)> = Vec::new();
+        dates.extend((0..self.daily).map(|i| (first + Duration::days(i as i64), ExpiryKind::Daily)));
+
+        let mut friday = first;
+        while friday.weekday() != Weekday::Fri {
+            friday += Duration::days(1);
+        }
+        dates.extend((0..self.weekly).map(|i| (friday + Duration::weeks(i as i64), ExpiryKind::Weekly)));
+
+        let (mut year, mut month) = (first.year(), first.month());
+        let mut listed = 0;
+        while listed < self.monthly {
+            let date = last_friday(year, month);
+            if date >= first {
+                dates.push((date, ExpiryKind::Monthly));
+                listed += 1;
+            }
+            (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        }
+
+        // A date listed in several series keeps the longest one
+        dates.sort();
+        let mut expiries: Vec<Expiry> = Vec::new();
+        for (date, kind) in dates {
+            match expiries.last_mut() {
+                Some(last) if last.date == date.to_string() => last.kind = kind,
+                _ => expiries.push(Expiry {
+                    kind,
+                    date: date.to_string(),
+                    timestamp: expiry_timestamp(date),
+                }),
+            }
+        }
+        expiries
+    }
+
+    /// Listed expiry for `date` (YYYY-MM-DD), if any
+    pub fn find(&self, date: &str, now: u64) -> Option<Expiry> {
+        self.expiries(now).into_iter().find(|expiry| expiry.date == date)
+    }
+
+    /// Settlement timestamp for a requested expiry
+    ///
+    /// Listed expiries are always accepted. With `otc` set, any future date is
+    /// accepted and settles at 08:00 UTC like the listed ones.
+    pub fn resolve(&self, date: &str, now: u64, otc: bool) -> Result<u64, PricingError> {
+        if let Some(expiry) = self.find(date, now) {
+            return Ok(expiry.timestamp);
+        }
+        if !otc {
+            return Err(PricingError::NonStandardExpiry(date.to_string()));
+        }
+
+        let timestamp = expiry_date_timestamp(date)?;
+        if timestamp <= now {
+            return Err(PricingError::InvalidInput(format!("Expiry {} is in the past", date)));
+        }
+        Ok(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-10 (Wednesday) 12:00 UTC
+    const NOW: u64 = 1_704_888_000;
+
+    #[test]
+    fn test_calendar_series() {
+        let expiries = ExpiryCalendar::default().expiries(NOW);
+
+        // Today's 08:00 expiry has passed, so dailies start tomorrow
+        assert_eq!(expiries[0].date, "2024-01-11");
+        assert_eq!(expiries[0].timestamp, 1_704_960_000);
+        assert!(expiries.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+
+        let kind_of = |date: &str| expiries.iter().find(|e| e.date == date).map(|e| e.kind);
+        assert_eq!(kind_of("2024-01-12"), Some(ExpiryKind::Weekly));
+        assert_eq!(kind_of("2024-01-26"), Some(ExpiryKind::Monthly));
+        assert_eq!(kind_of("2024-02-23"), Some(ExpiryKind::Monthly));
+        assert_eq!(kind_of("2024-03-29"), Some(ExpiryKind::Monthly));
+        assert_eq!(kind_of("2024-02-01"), None);
+    }
+
+    #[test]
+    fn test_resolve_requires_calendar_unless_otc() {
+        let calendar = ExpiryCalendar::default();
+        assert!(calendar.resolve("2024-01-19", NOW, false).is_ok());
+        assert_eq!(
+            calendar.resolve("2024-02-01", NOW, false),
+            Err(PricingError::NonStandardExpiry("2024-02-01".to_string()))
+        );
+        assert_eq!(calendar.resolve("2024-02-01", NOW, true), Ok(1_706_774_400));
+        assert!(calendar.resolve("2024-01-01", NOW, true).is_err());
+    }
+}
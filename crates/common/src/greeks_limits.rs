@@ -0,0 +1,109 @@
+//! Per-trade greeks limits
+//!
+//! A single option may not move the pool's net delta or vega by more than a
+//! configured amount. Quoting and contract creation apply the same check, so
+//! an oversized request is either quoted at a reduced size or rejected with
+//! a schedule of smaller trades that each fit the limits.
+
+use crate::PricingError;
+use serde::{Deserialize, Serialize};
+
+/// Maximum greeks a single trade may add to the pool (unset = unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GreeksLimits {
+    /// Max |delta| of one trade (BTC)
+    pub max_delta: Option<f64>,
+    /// Max |vega| of one trade (USD per vol point)
+    pub max_vega: Option<f64>,
+}
+
+impl GreeksLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_delta.is_none() && self.max_vega.is_none()
+    }
+
+    /// The most-breached limit as (greek, value, limit, allowed fraction of the trade)
+    fn tightest(&self, delta: f64, vega: f64) -> Option<(&'static str, f64, f64, f64)> {
+        [("delta", delta, self.max_delta), ("vega", vega, self.max_vega)]
+            .into_iter()
+            .filter_map(|(greek, value, limit)| {
+                let limit = limit?;
+                (value.abs() > limit).then(|| (greek, value, limit, limit / value.abs()))
+            })
+            .min_by(|a, b| a.3.total_cmp(&b.3))
+    }
+
+    /// Largest size (a multiple of `lot`) of a `quantity` trade with total
+    /// `delta`/`vega` that stays within the limits; greeks scale linearly
+    pub fn max_quantity(&self, quantity: u64, delta: f64, vega: f64, lot: u64) -> u64 {
+        match self.tightest(delta, vega) {
+            None => quantity,
+            Some((_, _, _, fraction)) => {
+                let lot = lot.max(1);
+                (quantity as f64 * fraction).floor() as u64 / lot * lot
+            }
+        }
+    }
+
+    /// Reject a trade whose greeks exceed the limits, reporting the largest
+    /// size that fits and a split schedule for the full quantity
+    pub fn check(&self, quantity: u64, delta: f64, vega: f64, lot: u64) -> Result<(), PricingError> {
+        let Some((greek, value, limit, _)) = self.tightest(delta, vega) else {
+            return Ok(());
+        };
+        let max_quantity = self.max_quantity(quantity, delta, vega, lot);
+        Err(PricingError::GreeksLimitExceeded {
+            greek: greek.to_string(),
+            value,
+            limit,
+            max_quantity,
+            schedule: split_schedule(quantity, max_quantity),
+        })
+    }
+}
+
+/// Split `quantity` into trades of at most `max_tranche` (empty if no trade fits)
+pub fn split_schedule(quantity: u64, max_tranche: u64) -> Vec<u64> {
+    if max_tranche == 0 {
+        return Vec::new();
+    }
+    let mut schedule = vec![max_tranche; (quantity / max_tranche) as usize];
+    if !quantity.is_multiple_of(max_tranche) {
+        schedule.push(quantity % max_tranche);
+    }
+    schedule
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_reduce_size_and_suggest_splits() {
+        let limits = GreeksLimits {
+            max_delta: Some(2.0),
+            max_vega: Some(500.0),
+        };
+        // 5 BTC call: delta 3.0 fits 2/3, vega 600 fits 5/6
+        assert_eq!(limits.max_quantity(500_000_000, 3.0, 600.0, 1_000_000), 333_000_000);
+        assert_eq!(limits.max_quantity(100_000_000, 0.6, 120.0, 1_000_000), 100_000_000);
+        assert!(limits.check(100_000_000, -0.6, 120.0, 1_000_000).is_ok());
+
+        match limits.check(500_000_000, -3.0, 600.0, 1_000_000) {
+            Err(PricingError::GreeksLimitExceeded {
+                greek,
+                max_quantity,
+                schedule,
+                ..
+            }) => {
+                assert_eq!(greek, "delta");
+                assert_eq!(max_quantity, 333_000_000);
+                assert_eq!(schedule, vec![333_000_000, 167_000_000]);
+            }
+            other => panic!("expected greeks limit error, got {:?}", other),
+        }
+        assert!(GreeksLimits::default().check(500_000_000, 3.0, 600.0, 1).is_ok());
+        assert!(split_schedule(10, 0).is_empty());
+    }
+}
@@ -0,0 +1,779 @@
+//! Key management backends
+//!
+//! Signing keys used to be generated with `thread_rng` at startup and lost on
+//! exit. A [`KeyStore`] names each key (e.g. `"oracle-node"`, `"aggregator"`)
+//! and signs with it without handing the secret to the caller, so the same
+//! code runs against an in-memory key, a key in the environment, a
+//! passphrase-encrypted key file, or a remote signer (HSM, Vault transit,
+//! gRPC signer) that never exposes the secret at all. [`VaultTransitTransport`]
+//! is the remote signer shipped here.
+//!
+//! Signatures are ECDSA over a 32-byte digest; [`KeyStore::sign`] hashes the
+//! data with SHA256 first, so it verifies with [`crate::crypto::verify_signature`]
+//! exactly like [`crate::crypto::sign_data`].
+
+use crate::crypto::{public_key_from_secret, sha256, PublicKey, SecretKey, Signature};
+use crate::error::KeyStoreError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bitcoin::secp256k1::{Message, Secp256k1};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use pbkdf2::pbkdf2_hmac_array;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Environment variable holding the key file passphrase
+pub const PASSPHRASE_ENV: &str = "ORACLE_VM_KEYSTORE_PASSPHRASE";
+
+/// Default prefix for keys read from the environment
+pub const DEFAULT_ENV_PREFIX: &str = "ORACLE_VM_KEY_";
+
+/// PBKDF2 iterations for newly written key files
+pub const DEFAULT_KDF_ITERATIONS: u32 = 100_000;
+
+/// PBKDF2 iterations accepted when writing or opening a key file
+pub const KDF_ITERATIONS_RANGE: std::ops::RangeInclusive<u32> = 10_000..=10_000_000;
+
+/// Vault server address (same variable as the Vault CLI)
+pub const VAULT_ADDR_ENV: &str = "VAULT_ADDR";
+
+/// Vault token (same variable as the Vault CLI)
+pub const VAULT_TOKEN_ENV: &str = "VAULT_TOKEN";
+
+/// Default mount path of the transit engine
+pub const DEFAULT_TRANSIT_MOUNT: &str = "transit";
+
+/// Named signing keys
+pub trait KeyStore: Send + Sync {
+    fn public_key(&self, name: &str) -> Result<PublicKey, KeyStoreError>;
+
+    /// ECDSA signature over a 32-byte digest (e.g. a sighash)
+    fn sign_digest(&self, name: &str, digest: &[u8; 32]) -> Result<Signature, KeyStoreError>;
+
+    /// ECDSA signature over SHA256(data)
+    fn sign(&self, name: &str, data: &[u8]) -> Result<Signature, KeyStoreError> {
+        self.sign_digest(name, &sha256(data))
+    }
+}
+
+fn sign_with(secret_key: &SecretKey, digest: &[u8; 32]) -> Signature {
+    Secp256k1::new().sign_ecdsa(&Message::from_digest(*digest), secret_key)
+}
+
+fn parse_secret(name: &str, hex_key: &str) -> Result<SecretKey, KeyStoreError> {
+    hex_key
+        .trim()
+        .parse::<SecretKey>()
+        .map_err(|e| KeyStoreError::InvalidKey(format!("{}: {}", name, e)))
+}
+
+/// Keys held in process memory
+///
+/// An ephemeral store generates a missing key on first use, which is what the
+/// services did before key stores existed (fine for tests and devnet).
+#[derive(Default)]
+pub struct MemoryKeyStore {
+    keys: RwLock<HashMap<String, SecretKey>>,
+    generate_missing: bool,
+}
+
+impl MemoryKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store that generates unknown keys instead of failing
+    pub fn ephemeral() -> Self {
+        Self {
+            generate_missing: true,
+            ..Self::default()
+        }
+    }
+
+    /// Store holding a single key
+    pub fn with_key(name: &str, secret_key: SecretKey) -> Self {
+        let store = Self::new();
+        store.insert(name, secret_key);
+        store
+    }
+
+    pub fn insert(&self, name: &str, secret_key: SecretKey) {
+        self.keys.write().unwrap().insert(name.to_string(), secret_key);
+    }
+
+    fn secret(&self, name: &str) -> Result<SecretKey, KeyStoreError> {
+        if let Some(secret_key) = self.keys.read().unwrap().get(name) {
+            return Ok(*secret_key);
+        }
+        if !self.generate_missing {
+            return Err(KeyStoreError::NotFound(name.to_string()));
+        }
+        let mut keys = self.keys.write().unwrap();
+        Ok(*keys
+            .entry(name.to_string())
+            .or_insert_with(|| crate::crypto::generate_keypair().0))
+    }
+}
+
+impl KeyStore for MemoryKeyStore {
+    fn public_key(&self, name: &str) -> Result<PublicKey, KeyStoreError> {
+        Ok(public_key_from_secret(&self.secret(name)?))
+    }
+
+    fn sign_digest(&self, name: &str, digest: &[u8; 32]) -> Result<Signature, KeyStoreError> {
+        Ok(sign_with(&self.secret(name)?, digest))
+    }
+}
+
+/// Hex secret keys from environment variables
+///
+/// Key `oracle-node` with the default prefix is read from
+/// `ORACLE_VM_KEY_ORACLE_NODE`.
+pub struct EnvKeyStore {
+    prefix: String,
+}
+
+impl EnvKeyStore {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Environment variable for a key name
+    pub fn variable(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name.to_uppercase().replace(['-', '.'], "_"))
+    }
+
+    fn secret(&self, name: &str) -> Result<SecretKey, KeyStoreError> {
+        let variable = self.variable(name);
+        let hex_key = std::env::var(&variable).map_err(|_| KeyStoreError::NotFound(variable))?;
+        parse_secret(name, &hex_key)
+    }
+}
+
+impl Default for EnvKeyStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENV_PREFIX)
+    }
+}
+
+impl KeyStore for EnvKeyStore {
+    fn public_key(&self, name: &str) -> Result<PublicKey, KeyStoreError> {
+        Ok(public_key_from_secret(&self.secret(name)?))
+    }
+
+    fn sign_digest(&self, name: &str, digest: &[u8; 32]) -> Result<Signature, KeyStoreError> {
+        Ok(sign_with(&self.secret(name)?, digest))
+    }
+}
+
+/// On-disk format of an encrypted key
+///
+/// The passphrase is stretched with PBKDF2-HMAC-SHA256 and the secret is
+/// sealed with ChaCha20-Poly1305. The public key and the iteration count are
+/// authenticated as associated data, so editing either one fails to open.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKeyFile {
+    pub public_key: PublicKey,
+    pub kdf_iterations: u32,
+    pub salt: String,
+    pub nonce: String,
+    /// Sealed secret followed by the 16-byte Poly1305 tag
+    pub ciphertext: String,
+}
+
+/// Reject work factors too weak to slow a guessing attack, or large enough to
+/// stall the process when a tampered file is opened
+fn check_iterations(iterations: u32) -> Result<(), KeyStoreError> {
+    if !KDF_ITERATIONS_RANGE.contains(&iterations) {
+        return Err(KeyStoreError::InvalidKey(format!(
+            "kdf_iterations {} outside {}..={}",
+            iterations,
+            KDF_ITERATIONS_RANGE.start(),
+            KDF_ITERATIONS_RANGE.end()
+        )));
+    }
+    Ok(())
+}
+
+fn file_cipher(passphrase: &str, salt: &[u8], iterations: u32) -> ChaCha20Poly1305 {
+    let key = pbkdf2_hmac_array::<Sha256, 32>(passphrase.as_bytes(), salt, iterations);
+    ChaCha20Poly1305::new(&key.into())
+}
+
+/// Associated data binding the header fields to the ciphertext
+fn header_aad(public_key: &PublicKey, iterations: u32) -> Vec<u8> {
+    let mut aad = public_key.serialize().to_vec();
+    aad.extend_from_slice(&iterations.to_be_bytes());
+    aad
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, KeyStoreError> {
+    hex::decode(value).map_err(|e| KeyStoreError::InvalidKey(format!("{}: {}", field, e)))
+}
+
+impl EncryptedKeyFile {
+    pub fn seal(secret_key: &SecretKey, passphrase: &str, iterations: u32) -> Result<Self, KeyStoreError> {
+        check_iterations(iterations)?;
+        let mut rng = rand::thread_rng();
+        let salt: [u8; 16] = rng.gen();
+        let nonce: [u8; 12] = rng.gen();
+        let public_key = public_key_from_secret(secret_key);
+        let ciphertext = file_cipher(passphrase, &salt, iterations)
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &secret_key.secret_bytes(),
+                    aad: &header_aad(&public_key, iterations),
+                },
+            )
+            .map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        Ok(Self {
+            public_key,
+            kdf_iterations: iterations,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt, rejecting a wrong passphrase or a tampered file
+    pub fn open(&self, passphrase: &str) -> Result<SecretKey, KeyStoreError> {
+        check_iterations(self.kdf_iterations)?;
+        let salt = decode_hex("salt", &self.salt)?;
+        let nonce: [u8; 12] = decode_hex("nonce", &self.nonce)?
+            .try_into()
+            .map_err(|_| KeyStoreError::InvalidKey("nonce must be 12 bytes".to_string()))?;
+        let ciphertext = decode_hex("ciphertext", &self.ciphertext)?;
+
+        let plaintext = file_cipher(passphrase, &salt, self.kdf_iterations)
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &header_aad(&self.public_key, self.kdf_iterations),
+                },
+            )
+            .map_err(|_| KeyStoreError::Decrypt("wrong passphrase or corrupted key file".to_string()))?;
+        let secret_key = SecretKey::from_slice(&plaintext).map_err(|e| KeyStoreError::InvalidKey(e.to_string()))?;
+        if public_key_from_secret(&secret_key) != self.public_key {
+            return Err(KeyStoreError::Decrypt("public key mismatch".to_string()));
+        }
+        Ok(secret_key)
+    }
+}
+
+/// Passphrase-encrypted key files, one `<name>.key.json` per key
+///
+/// Keys are decrypted on first use and kept in memory for the process
+/// lifetime.
+pub struct EncryptedFileKeyStore {
+    dir: PathBuf,
+    passphrase: String,
+    iterations: u32,
+    unlocked: RwLock<HashMap<String, SecretKey>>,
+}
+
+impl EncryptedFileKeyStore {
+    pub fn new(dir: impl Into<PathBuf>, passphrase: &str) -> Self {
+        Self {
+            dir: dir.into(),
+            passphrase: passphrase.to_string(),
+            iterations: DEFAULT_KDF_ITERATIONS,
+            unlocked: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// KDF work factor for keys written from now on (must be within [`KDF_ITERATIONS_RANGE`])
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.key.json", name))
+    }
+
+    /// Encrypt and write a key (fails if the key file already exists)
+    pub fn import(&self, name: &str, secret_key: &SecretKey) -> Result<PublicKey, KeyStoreError> {
+        let path = self.path(name);
+        if path.exists() {
+            return Err(KeyStoreError::AlreadyExists(name.to_string()));
+        }
+        let file = EncryptedKeyFile::seal(secret_key, &self.passphrase, self.iterations)?;
+        let json = serde_json::to_string_pretty(&file).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        std::fs::create_dir_all(&self.dir).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        write_private(&path, json.as_bytes())?;
+        self.unlocked.write().unwrap().insert(name.to_string(), *secret_key);
+        Ok(file.public_key)
+    }
+
+    /// Generate, encrypt and write a new key
+    pub fn generate(&self, name: &str) -> Result<PublicKey, KeyStoreError> {
+        self.import(name, &crate::crypto::generate_keypair().0)
+    }
+
+    fn read(&self, name: &str) -> Result<EncryptedKeyFile, KeyStoreError> {
+        let path = self.path(name);
+        let json = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => KeyStoreError::NotFound(name.to_string()),
+            _ => KeyStoreError::Backend(format!("{}: {}", path.display(), e)),
+        })?;
+        serde_json::from_str(&json).map_err(|e| KeyStoreError::InvalidKey(format!("{}: {}", name, e)))
+    }
+
+    fn secret(&self, name: &str) -> Result<SecretKey, KeyStoreError> {
+        if let Some(secret_key) = self.unlocked.read().unwrap().get(name) {
+            return Ok(*secret_key);
+        }
+        let secret_key = self.read(name)?.open(&self.passphrase)?;
+        self.unlocked.write().unwrap().insert(name.to_string(), secret_key);
+        Ok(secret_key)
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), KeyStoreError> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|e| KeyStoreError::Backend(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), KeyStoreError> {
+    std::fs::write(path, contents).map_err(|e| KeyStoreError::Backend(format!("{}: {}", path.display(), e)))
+}
+
+impl KeyStore for EncryptedFileKeyStore {
+    fn public_key(&self, name: &str) -> Result<PublicKey, KeyStoreError> {
+        // Readable without the passphrase being correct
+        Ok(self.read(name)?.public_key)
+    }
+
+    fn sign_digest(&self, name: &str, digest: &[u8; 32]) -> Result<Signature, KeyStoreError> {
+        Ok(sign_with(&self.secret(name)?, digest))
+    }
+}
+
+/// Sign request sent to a remote signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignRequest {
+    pub key: String,
+    /// Hex 32-byte digest
+    pub digest: String,
+}
+
+/// Sign response from a remote signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignResponse {
+    /// Hex DER ECDSA signature
+    pub signature: String,
+}
+
+/// Connection to a remote signer (HSM, Vault transit, gRPC signer)
+pub trait SignerTransport: Send + Sync {
+    fn public_key(&self, key: &str) -> Result<PublicKey, KeyStoreError>;
+    fn sign(&self, request: &RemoteSignRequest) -> Result<RemoteSignResponse, KeyStoreError>;
+}
+
+/// Keys that never leave a remote signer
+///
+/// Public keys are cached after the first lookup and every returned
+/// signature is verified against it, so a misrouted or faulty signer cannot
+/// slip a signature from the wrong key into an attestation.
+pub struct RemoteSignerKeyStore<T: SignerTransport> {
+    transport: T,
+    public_keys: RwLock<HashMap<String, PublicKey>>,
+}
+
+impl<T: SignerTransport> RemoteSignerKeyStore<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            public_keys: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: SignerTransport> KeyStore for RemoteSignerKeyStore<T> {
+    fn public_key(&self, name: &str) -> Result<PublicKey, KeyStoreError> {
+        if let Some(public_key) = self.public_keys.read().unwrap().get(name) {
+            return Ok(*public_key);
+        }
+        let public_key = self.transport.public_key(name)?;
+        self.public_keys.write().unwrap().insert(name.to_string(), public_key);
+        Ok(public_key)
+    }
+
+    fn sign_digest(&self, name: &str, digest: &[u8; 32]) -> Result<Signature, KeyStoreError> {
+        let public_key = self.public_key(name)?;
+        let response = self.transport.sign(&RemoteSignRequest {
+            key: name.to_string(),
+            digest: hex::encode(digest),
+        })?;
+        let signature = decode_hex("signature", &response.signature)
+            .and_then(|der| Signature::from_der(&der).map_err(|e| KeyStoreError::Backend(e.to_string())))?;
+        Secp256k1::verification_only()
+            .verify_ecdsa(&Message::from_digest(*digest), &signature, &public_key)
+            .map_err(|_| KeyStoreError::Backend(format!("remote signer returned an invalid signature for {}", name)))?;
+        Ok(signature)
+    }
+}
+
+/// Vault transit engine over HTTP
+///
+/// Each key name is a transit key holding a secp256k1 ECDSA key. Vault's
+/// built-in transit engine has no secp256k1 key type, so the mount must be a
+/// transit-compatible engine that has one; the paths and payloads are the
+/// same. Signing posts the digest as prehashed input and reads back the
+/// ASN.1 signature, and public keys come from the key's latest version.
+/// Requests block the calling thread like every other backend here.
+pub struct VaultTransitTransport {
+    address: String,
+    token: String,
+    mount: String,
+    agent: ureq::Agent,
+}
+
+impl VaultTransitTransport {
+    pub fn new(address: &str, token: &str, mount: &str) -> Self {
+        Self {
+            address: address.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            mount: mount.trim_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(10)).build(),
+        }
+    }
+
+    /// Transport for `VAULT_ADDR` with `VAULT_TOKEN`
+    pub fn from_env(mount: &str) -> Result<Self, KeyStoreError> {
+        let variable =
+            |name: &str| std::env::var(name).map_err(|_| KeyStoreError::Backend(format!("{} is not set", name)));
+        Ok(Self::new(&variable(VAULT_ADDR_ENV)?, &variable(VAULT_TOKEN_ENV)?, mount))
+    }
+
+    fn url(&self, action: &str, key: &str) -> String {
+        format!("{}/v1/{}/{}/{}", self.address, self.mount, action, key)
+    }
+
+    /// `data` object of a Vault response
+    fn data(key: &str, result: Result<ureq::Response, ureq::Error>) -> Result<serde_json::Value, KeyStoreError> {
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Err(KeyStoreError::NotFound(key.to_string())),
+            Err(e) => return Err(KeyStoreError::Backend(format!("vault {}: {}", key, e))),
+        };
+        let mut body: serde_json::Value = response
+            .into_json()
+            .map_err(|e| KeyStoreError::Backend(format!("vault {}: {}", key, e)))?;
+        Ok(body["data"].take())
+    }
+}
+
+/// Public key of a transit key version: hex SEC1 or a PEM SubjectPublicKeyInfo
+fn parse_vault_public_key(key: &str, encoded: &str) -> Result<PublicKey, KeyStoreError> {
+    let invalid = |e: String| KeyStoreError::InvalidKey(format!("{}: {}", key, e));
+    let encoded = encoded.trim();
+    if !encoded.starts_with("-----BEGIN") {
+        return encoded.parse::<PublicKey>().map_err(|e| invalid(e.to_string()));
+    }
+    let body: String = encoded.lines().filter(|line| !line.starts_with("-----")).collect();
+    let der = BASE64.decode(body).map_err(|e| invalid(e.to_string()))?;
+    // The SPKI ends with the point (65 bytes uncompressed, 33 compressed)
+    [65, 33]
+        .iter()
+        .filter_map(|len| der.len().checked_sub(*len).map(|start| &der[start..]))
+        .find_map(|point| PublicKey::from_slice(point).ok())
+        .ok_or_else(|| invalid("no secp256k1 point in public key".to_string()))
+}
+
+impl SignerTransport for VaultTransitTransport {
+    fn public_key(&self, key: &str) -> Result<PublicKey, KeyStoreError> {
+        let request = self.agent.get(&self.url("keys", key)).set("X-Vault-Token", &self.token);
+        let data = Self::data(key, request.call())?;
+        let version = data["latest_version"].as_u64().unwrap_or(1).to_string();
+        let public_key = data["keys"][version.as_str()]["public_key"]
+            .as_str()
+            .ok_or_else(|| KeyStoreError::InvalidKey(format!("{}: not an asymmetric transit key", key)))?;
+        parse_vault_public_key(key, public_key)
+    }
+
+    fn sign(&self, request: &RemoteSignRequest) -> Result<RemoteSignResponse, KeyStoreError> {
+        let digest = decode_hex("digest", &request.digest)?;
+        let body = json!({
+            "input": BASE64.encode(digest),
+            "prehashed": true,
+            "marshaling_algorithm": "asn1",
+        });
+        let call = self
+            .agent
+            .post(&self.url("sign", &request.key))
+            .set("X-Vault-Token", &self.token)
+            .send_json(body);
+        let data = Self::data(&request.key, call)?;
+        // "vault:v<version>:<base64 DER>"
+        let encoded = data["signature"]
+            .as_str()
+            .and_then(|signature| signature.rsplit(':').next())
+            .ok_or_else(|| KeyStoreError::Backend(format!("vault {}: response has no signature", request.key)))?;
+        let der = BASE64
+            .decode(encoded)
+            .map_err(|e| KeyStoreError::Backend(format!("vault {}: {}", request.key, e)))?;
+        let mut signature = Signature::from_der(&der).map_err(|e| KeyStoreError::Backend(e.to_string()))?;
+        // Vault does not enforce low-S, secp256k1 verification does
+        signature.normalize_s();
+        Ok(RemoteSignResponse {
+            signature: hex::encode(signature.serialize_der()),
+        })
+    }
+}
+
+/// Open a key store from a spec string
+///
+/// - `memory`: ephemeral in-process keys (generated on first use)
+/// - `env` or `env:<PREFIX>`: hex keys from environment variables
+/// - `file:<DIR>`: encrypted key files, passphrase from `ORACLE_VM_KEYSTORE_PASSPHRASE`
+/// - `vault` or `vault:<MOUNT>`: transit keys at `VAULT_ADDR`, authenticated with `VAULT_TOKEN`
+pub fn open_key_store(spec: &str) -> Result<Arc<dyn KeyStore>, KeyStoreError> {
+    let (kind, argument) = spec.split_once(':').unwrap_or((spec, ""));
+    match kind {
+        "memory" => Ok(Arc::new(MemoryKeyStore::ephemeral())),
+        "env" if argument.is_empty() => Ok(Arc::new(EnvKeyStore::default())),
+        "env" => Ok(Arc::new(EnvKeyStore::new(argument))),
+        "file" if !argument.is_empty() => {
+            let passphrase = std::env::var(PASSPHRASE_ENV)
+                .map_err(|_| KeyStoreError::Backend(format!("{} is not set", PASSPHRASE_ENV)))?;
+            Ok(Arc::new(EncryptedFileKeyStore::new(argument, &passphrase)))
+        }
+        "vault" => {
+            let mount = if argument.is_empty() { DEFAULT_TRANSIT_MOUNT } else { argument };
+            Ok(Arc::new(RemoteSignerKeyStore::new(VaultTransitTransport::from_env(mount)?)))
+        }
+        _ => Err(KeyStoreError::Backend(format!("unknown key store spec: {}", spec))),
+    }
+}
+
+/// Key store for a service's `--key-store` flag
+///
+/// Without a spec, or with `memory`, keys are generated per process and
+/// nothing they signed verifies after a restart. That is only allowed in
+/// development mode (with a loud warning); otherwise the service refuses to
+/// start.
+pub fn open_service_key_store(spec: Option<&str>, dev: bool) -> Result<Arc<dyn KeyStore>, KeyStoreError> {
+    match spec {
+        Some(spec) if spec != "memory" => open_key_store(spec),
+        _ if dev => {
+            tracing::warn!("⚠️ DEVELOPMENT MODE: signing with ephemeral in-memory keys, signatures will not verify after a restart");
+            Ok(Arc::new(MemoryKeyStore::ephemeral()))
+        }
+        _ => Err(KeyStoreError::Ephemeral(
+            "no persistent key store configured (pass --key-store env|file:DIR|vault)".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{generate_keypair, verify_signature};
+
+    #[test]
+    fn test_memory_and_env_stores_sign_like_sign_data() {
+        let (secret_key, public_key) = generate_keypair();
+        let store = MemoryKeyStore::with_key("oracle-node", secret_key);
+        let signature = store.sign("oracle-node", b"price").unwrap();
+        assert!(verify_signature(b"price", &signature, &public_key).unwrap());
+        assert_eq!(store.sign("aggregator", b"price"), Err(KeyStoreError::NotFound("aggregator".to_string())));
+
+        let ephemeral = MemoryKeyStore::ephemeral();
+        assert_eq!(ephemeral.public_key("a").unwrap(), ephemeral.public_key("a").unwrap());
+
+        let env = EnvKeyStore::new("KEYSTORE_TEST_");
+        assert_eq!(env.variable("oracle-node"), "KEYSTORE_TEST_ORACLE_NODE");
+        std::env::set_var("KEYSTORE_TEST_ORACLE_NODE", hex::encode(secret_key.secret_bytes()));
+        assert_eq!(env.public_key("oracle-node").unwrap(), public_key);
+        assert!(matches!(env.public_key("missing"), Err(KeyStoreError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_encrypted_file_round_trip_and_tamper() {
+        let dir = std::env::temp_dir().join(format!("oracle-vm-keystore-{}", rand::random::<u64>()));
+        let store = EncryptedFileKeyStore::new(&dir, "correct horse").with_iterations(*KDF_ITERATIONS_RANGE.start());
+        let public_key = store.generate("aggregator").unwrap();
+        assert!(matches!(store.generate("aggregator"), Err(KeyStoreError::AlreadyExists(_))));
+
+        // A fresh process unlocks it with the passphrase only
+        let reopened = EncryptedFileKeyStore::new(&dir, "correct horse");
+        let signature = reopened.sign("aggregator", b"proof").unwrap();
+        assert!(verify_signature(b"proof", &signature, &public_key).unwrap());
+
+        let wrong = EncryptedFileKeyStore::new(&dir, "battery staple");
+        assert_eq!(wrong.public_key("aggregator").unwrap(), public_key);
+        assert!(matches!(wrong.sign("aggregator", b"proof"), Err(KeyStoreError::Decrypt(_))));
+
+        let mut file = reopened.read("aggregator").unwrap();
+        file.ciphertext.replace_range(0..2, if file.ciphertext.starts_with("00") { "01" } else { "00" });
+        assert!(matches!(file.open("correct horse"), Err(KeyStoreError::Decrypt(_))));
+
+        // The iteration count is authenticated, and out-of-range counts are refused outright
+        let mut file = reopened.read("aggregator").unwrap();
+        file.kdf_iterations += 1;
+        assert!(matches!(file.open("correct horse"), Err(KeyStoreError::Decrypt(_))));
+        file.kdf_iterations = u32::MAX;
+        assert!(matches!(file.open("correct horse"), Err(KeyStoreError::InvalidKey(_))));
+        let weak = EncryptedFileKeyStore::new(&dir, "correct horse").with_iterations(10);
+        assert!(matches!(weak.generate("weak"), Err(KeyStoreError::InvalidKey(_))));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Signer that answers with a different key than it advertises
+    struct Misrouted {
+        advertised: PublicKey,
+        actual: SecretKey,
+    }
+
+    impl SignerTransport for Misrouted {
+        fn public_key(&self, _key: &str) -> Result<PublicKey, KeyStoreError> {
+            Ok(self.advertised)
+        }
+
+        fn sign(&self, request: &RemoteSignRequest) -> Result<RemoteSignResponse, KeyStoreError> {
+            let digest: [u8; 32] = hex::decode(&request.digest).unwrap().try_into().unwrap();
+            Ok(RemoteSignResponse {
+                signature: hex::encode(sign_with(&self.actual, &digest).serialize_der()),
+            })
+        }
+    }
+
+    #[test]
+    fn test_remote_signer_verifies_signatures() {
+        let (secret_key, public_key) = generate_keypair();
+        let honest = RemoteSignerKeyStore::new(Misrouted {
+            advertised: public_key,
+            actual: secret_key,
+        });
+        let signature = honest.sign("settlement", b"sighash").unwrap();
+        assert!(verify_signature(b"sighash", &signature, &public_key).unwrap());
+
+        let misrouted = RemoteSignerKeyStore::new(Misrouted {
+            advertised: public_key,
+            actual: generate_keypair().0,
+        });
+        assert!(matches!(misrouted.sign("settlement", b"sighash"), Err(KeyStoreError::Backend(_))));
+        assert!(open_key_store("memory").is_ok());
+        assert!(open_key_store("hsm").is_err());
+    }
+
+    #[test]
+    fn test_services_refuse_ephemeral_keys_outside_dev() {
+        assert!(matches!(open_service_key_store(None, false), Err(KeyStoreError::Ephemeral(_))));
+        assert!(matches!(open_service_key_store(Some("memory"), false), Err(KeyStoreError::Ephemeral(_))));
+        assert!(open_service_key_store(None, true).is_ok());
+        assert!(open_service_key_store(Some("env"), false).is_ok());
+    }
+
+    /// `n - s` (a valid high-S form of the same signature)
+    fn high_s(signature: &Signature) -> Signature {
+        const ORDER: [u8; 32] = [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+            0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+        ];
+        let mut compact = signature.serialize_compact();
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff = ORDER[i] as i16 - compact[32 + i] as i16 - borrow;
+            borrow = (diff < 0) as i16;
+            compact[32 + i] = diff.rem_euclid(256) as u8;
+        }
+        Signature::from_compact(&compact).unwrap()
+    }
+
+    /// Vault transit endpoint for one key (`oracle-node`, token `root`), answering `requests` requests
+    fn serve_vault(secret_key: SecretKey, requests: usize) -> String {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let point = public_key_from_secret(&secret_key).serialize_uncompressed();
+        let spki = [hex::decode("3056301006072a8648ce3d020106052b8104000a034200").unwrap(), point.to_vec()].concat();
+        let pem = format!("-----BEGIN PUBLIC KEY-----
+{}
+-----END PUBLIC KEY-----
+", BASE64.encode(spki));
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let (mut length, mut token) = (0, String::new());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else { break };
+                    match name.to_ascii_lowercase().as_str() {
+                        "content-length" => length = value.parse().unwrap(),
+                        "x-vault-token" => token = value.to_string(),
+                        _ => {}
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (status, response) = if token != "root" {
+                    (403, json!({ "errors": ["permission denied"] }))
+                } else if request_line.starts_with("GET /v1/transit/keys/oracle-node ") {
+                    (200, json!({ "data": { "latest_version": 1, "keys": { "1": { "public_key": pem } } } }))
+                } else if request_line.starts_with("POST /v1/transit/sign/oracle-node ") {
+                    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                    assert_eq!(body["prehashed"], true);
+                    let digest: [u8; 32] = BASE64.decode(body["input"].as_str().unwrap()).unwrap().try_into().unwrap();
+                    let der = high_s(&sign_with(&secret_key, &digest)).serialize_der();
+                    (200, json!({ "data": { "signature": format!("vault:v1:{}", BASE64.encode(der)) } }))
+                } else {
+                    (404, json!({ "errors": [] }))
+                };
+                let response = response.to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Vault\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn test_vault_transit_signs_with_remote_key() {
+        let (secret_key, public_key) = generate_keypair();
+        let address = serve_vault(secret_key, 4);
+        let store = RemoteSignerKeyStore::new(VaultTransitTransport::new(&address, "root", DEFAULT_TRANSIT_MOUNT));
+        assert_eq!(store.public_key("oracle-node").unwrap(), public_key);
+
+        // High-S signatures from Vault are normalized before verification
+        let signature = store.sign("oracle-node", b"price").unwrap();
+        assert!(verify_signature(b"price", &signature, &public_key).unwrap());
+
+        assert_eq!(store.public_key("aggregator"), Err(KeyStoreError::NotFound("aggregator".to_string())));
+        let unauthorized = RemoteSignerKeyStore::new(VaultTransitTransport::new(&address, "wrong", DEFAULT_TRANSIT_MOUNT));
+        assert!(matches!(unauthorized.public_key("oracle-node"), Err(KeyStoreError::Backend(_))));
+    }
+}
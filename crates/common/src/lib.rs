@@ -1,9 +1,48 @@
 //! Common types and utilities shared across Oracle VM components
 
+pub mod barrier;
+pub mod canonical;
+pub mod clock;
 pub mod config;
+pub mod consensus_proof;
+pub mod contract_spec;
 pub mod crypto;
 pub mod error;
+pub mod events;
+pub mod exercise;
+pub mod expiry;
+pub mod greeks_limits;
+pub mod keystore;
+pub mod network;
+pub mod open_interest;
+pub mod option_id;
+pub mod payoff;
+pub mod price;
+pub mod price_feed;
+pub mod quote;
+pub mod settlement_currency;
+pub mod shutdown;
+pub mod telemetry;
 pub mod types;
 
+pub use barrier::{Barrier, BarrierKind};
+pub use canonical::{CanonicalEncode, CanonicalEncoder};
+pub use clock::{Clock, ManualClock, SharedClock, SystemClock};
+pub use consensus_proof::{ConsensusProof, MedianStep, MedianTranscript, SignedSubmission};
+pub use contract_spec::ContractSpec;
 pub use error::*;
+pub use events::{EventBus, SystemEvent};
+pub use exercise::{DustHandling, Exercise, ExercisePolicy, ExerciseStyle};
+pub use expiry::{expiry_date_timestamp, Expiry, ExpiryCalendar, ExpiryKind};
+pub use greeks_limits::{split_schedule, GreeksLimits};
+pub use network::NetworkProfile;
+pub use open_interest::{OpenInterest, OpenInterestCapacity, OpenInterestCaps, OpenInterestEntry};
+pub use option_id::{OptionId, OptionTerms};
+pub use payoff::{binary_payout, Payoff};
+pub use price::Rounding;
+pub use price_feed::{FeedSnapshot, FeedStatus, PriceFeed};
+pub use quote::{BuyBackQuote, BuyBackRequest, OptionQuote, QuoteRequest};
+pub use settlement_currency::{Money, SettlementCurrency, UsdRail};
+pub use shutdown::{Shutdown, ShutdownSignal, WorkGuard};
+pub use telemetry::{grpc_request_span, init_tracing, CorrelationId, TracingGuard, CORRELATION_ID_HEADER};
 pub use types::*;
@@ -0,0 +1,158 @@
+//! Bitcoin network profiles
+//!
+//! A `NetworkProfile` bundles everything that differs between regtest,
+//! signet, testnet and mainnet: chain parameters, default bitcoind ports and
+//! command-line flag, and how many confirmations to wait for before treating
+//! an anchor or settlement transaction as final. Components take a profile
+//! instead of a bare `bitcoin::Network` so these values cannot drift apart.
+
+use crate::{OracleVmError, Result};
+use bitcoin::params::Params;
+use bitcoin::{Address, Network};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Chain parameters and operational defaults for one Bitcoin network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub network: Network,
+    /// Default bitcoind JSON-RPC port
+    pub rpc_port: u16,
+    /// Default bitcoind P2P port
+    pub p2p_port: u16,
+    /// Confirmations before an anchor or settlement is considered final
+    pub required_confirmations: u32,
+}
+
+impl NetworkProfile {
+    pub const MAINNET: Self = Self {
+        network: Network::Bitcoin,
+        rpc_port: 8332,
+        p2p_port: 8333,
+        required_confirmations: 6,
+    };
+
+    pub const TESTNET: Self = Self {
+        network: Network::Testnet,
+        rpc_port: 18332,
+        p2p_port: 18333,
+        required_confirmations: 3,
+    };
+
+    pub const SIGNET: Self = Self {
+        network: Network::Signet,
+        rpc_port: 38332,
+        p2p_port: 38333,
+        required_confirmations: 3,
+    };
+
+    pub const REGTEST: Self = Self {
+        network: Network::Regtest,
+        rpc_port: 18443,
+        p2p_port: 18444,
+        required_confirmations: 1,
+    };
+
+    /// Default profile for a network
+    pub fn for_network(network: Network) -> Self {
+        match network {
+            Network::Bitcoin => Self::MAINNET,
+            Network::Signet => Self::SIGNET,
+            Network::Regtest => Self::REGTEST,
+            _ => Self::TESTNET,
+        }
+    }
+
+    /// Override the confirmation requirement
+    pub fn with_confirmations(mut self, confirmations: u32) -> Self {
+        self.required_confirmations = confirmations;
+        self
+    }
+
+    /// Consensus parameters
+    pub fn params(&self) -> &'static Params {
+        match self.network {
+            Network::Bitcoin => &Params::MAINNET,
+            Network::Signet => &Params::SIGNET,
+            Network::Regtest => &Params::REGTEST,
+            _ => &Params::TESTNET3,
+        }
+    }
+
+    /// bitcoind command-line flag selecting this network (empty for mainnet)
+    pub fn bitcoind_flag(&self) -> &'static str {
+        match self.network {
+            Network::Bitcoin => "",
+            Network::Signet => "-signet",
+            Network::Regtest => "-regtest",
+            _ => "-testnet",
+        }
+    }
+
+    /// Default local RPC URL
+    pub fn default_rpc_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.rpc_port)
+    }
+
+    /// Parse an address and check that it belongs to this network
+    pub fn validate_address(&self, address: &str) -> Result<Address> {
+        Address::from_str(address)
+            .map_err(|e| OracleVmError::Bitcoin(format!("Invalid address {}: {}", address, e)))?
+            .require_network(self.network)
+            .map_err(|e| OracleVmError::Bitcoin(format!("Invalid address {}: {}", address, e)))
+    }
+
+    /// Whether `confirmations` meets the finality requirement
+    pub fn is_final(&self, confirmations: u32) -> bool {
+        confirmations >= self.required_confirmations
+    }
+}
+
+impl Default for NetworkProfile {
+    fn default() -> Self {
+        Self::TESTNET
+    }
+}
+
+impl fmt::Display for NetworkProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.network)
+    }
+}
+
+impl FromStr for NetworkProfile {
+    type Err = OracleVmError;
+
+    /// Accepts `mainnet`/`bitcoin`, `testnet`, `signet` and `regtest`
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mainnet" | "bitcoin" => Ok(Self::MAINNET),
+            "testnet" | "testnet3" => Ok(Self::TESTNET),
+            "signet" => Ok(Self::SIGNET),
+            "regtest" => Ok(Self::REGTEST),
+            other => Err(OracleVmError::Config(format!("Unknown network: {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_and_addresses() {
+        let regtest: NetworkProfile = "regtest".parse().unwrap();
+        assert_eq!(regtest, NetworkProfile::REGTEST);
+        assert_eq!(regtest.bitcoind_flag(), "-regtest");
+        assert_eq!(regtest.default_rpc_url(), "http://127.0.0.1:18443");
+        assert!(regtest.is_final(1));
+        assert!(!NetworkProfile::MAINNET.is_final(5));
+        assert!("litecoin".parse::<NetworkProfile>().is_err());
+
+        let testnet_address = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx";
+        assert!(NetworkProfile::TESTNET.validate_address(testnet_address).is_ok());
+        assert!(NetworkProfile::MAINNET.validate_address(testnet_address).is_err());
+        assert!(NetworkProfile::TESTNET.validate_address("not-an-address").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Open-interest caps per strike and per expiry
+//!
+//! Collateral locked at one strike (within one expiry) and across one expiry
+//! is capped at a percentage of pool liquidity. Close to expiry a large
+//! position pinned at a single strike is the hardest to hedge, so both caps
+//! taper linearly over the final `taper_secs` down to `min_fraction` of their
+//! full size. Open interest above a tapered cap is never forced closed; it
+//! only blocks new positions until it falls back under the cap.
+
+use crate::PricingError;
+use serde::{Deserialize, Serialize};
+
+/// Open-interest caps as a percentage of pool liquidity (unset = unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenInterestCaps {
+    /// Max collateral at one strike of one expiry (% of pool liquidity)
+    pub max_strike_pct: Option<f64>,
+    /// Max collateral across one expiry (% of pool liquidity)
+    pub max_expiry_pct: Option<f64>,
+    /// Caps start shrinking this many seconds before expiry (0 = never)
+    pub taper_secs: u64,
+    /// Fraction of the full cap left at expiry
+    pub min_fraction: f64,
+}
+
+/// Collateral (sats) already locked by open options
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenInterest {
+    /// At the requested strike of the requested expiry
+    pub strike: u64,
+    /// Across the requested expiry
+    pub expiry: u64,
+}
+
+/// Collateral (sats) locked at one strike of one expiry, as reported by the
+/// contracts service so quoting sees closes, exercises and expiries too
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenInterestEntry {
+    pub strike_price: u64,
+    /// Quoted expiry (calendar date or OTC expiry)
+    pub expiry: String,
+    pub collateral: u64,
+}
+
+/// Caps in effect for one strike/expiry and the collateral still available
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenInterestCapacity {
+    pub strike_cap: Option<u64>,
+    pub expiry_cap: Option<u64>,
+    /// Collateral a new position may still lock (None = unlimited)
+    pub remaining: Option<u64>,
+}
+
+impl OpenInterestCaps {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_strike_pct.is_none() && self.max_expiry_pct.is_none()
+    }
+
+    /// Fraction of the full caps in effect `secs_to_expiry` before expiry
+    pub fn taper(&self, secs_to_expiry: u64) -> f64 {
+        if self.taper_secs == 0 || secs_to_expiry >= self.taper_secs {
+            return 1.0;
+        }
+        let floor = self.min_fraction.clamp(0.0, 1.0);
+        floor + (1.0 - floor) * secs_to_expiry as f64 / self.taper_secs as f64
+    }
+
+    /// Caps and remaining collateral for a pool holding `pool_liquidity` sats
+    pub fn capacity(&self, pool_liquidity: u64, open: OpenInterest, secs_to_expiry: u64) -> OpenInterestCapacity {
+        let taper = self.taper(secs_to_expiry);
+        let cap = |pct: Option<f64>| {
+            pct.map(|pct| (pool_liquidity as f64 * pct.max(0.0) / 100.0 * taper).floor() as u64)
+        };
+        let strike_cap = cap(self.max_strike_pct);
+        let expiry_cap = cap(self.max_expiry_pct);
+        let remaining = [
+            strike_cap.map(|cap| cap.saturating_sub(open.strike)),
+            expiry_cap.map(|cap| cap.saturating_sub(open.expiry)),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        OpenInterestCapacity {
+            strike_cap,
+            expiry_cap,
+            remaining,
+        }
+    }
+
+    /// Reject a position locking `collateral` sats beyond the remaining capacity
+    pub fn check(
+        &self,
+        collateral: u64,
+        pool_liquidity: u64,
+        open: OpenInterest,
+        secs_to_expiry: u64,
+    ) -> Result<(), PricingError> {
+        let capacity = self.capacity(pool_liquidity, open, secs_to_expiry);
+        match capacity.remaining {
+            Some(remaining) if collateral > remaining => {
+                let scope = match (capacity.strike_cap, capacity.expiry_cap) {
+                    (Some(cap), _) if open.strike + collateral > cap => "strike",
+                    _ => "expiry",
+                };
+                Err(PricingError::OpenInterestCapExceeded {
+                    scope: scope.to_string(),
+                    collateral,
+                    remaining,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caps_taper_towards_expiry() {
+        let caps = OpenInterestCaps {
+            max_strike_pct: Some(10.0),
+            max_expiry_pct: Some(30.0),
+            taper_secs: 86_400,
+            min_fraction: 0.5,
+        };
+        let pool = 1_000_000_000;
+        let open = OpenInterest {
+            strike: 40_000_000,
+            expiry: 150_000_000,
+        };
+
+        let far = caps.capacity(pool, open, 7 * 86_400);
+        assert_eq!(far.strike_cap, Some(100_000_000));
+        assert_eq!(far.expiry_cap, Some(300_000_000));
+        assert_eq!(far.remaining, Some(60_000_000));
+
+        // Halfway through the taper window the caps are at 75%
+        let near = caps.capacity(pool, open, 43_200);
+        assert_eq!(near.strike_cap, Some(75_000_000));
+        assert_eq!(near.remaining, Some(35_000_000));
+        assert_eq!(caps.capacity(pool, open, 0).strike_cap, Some(50_000_000));
+
+        assert!(caps.check(35_000_000, pool, open, 43_200).is_ok());
+        assert!(matches!(
+            caps.check(35_000_001, pool, open, 43_200),
+            Err(PricingError::OpenInterestCapExceeded { ref scope, remaining: 35_000_000, .. }) if scope == "strike"
+        ));
+
+        // The expiry cap binds when the strike still has room
+        let crowded = OpenInterest {
+            strike: 0,
+            expiry: 290_000_000,
+        };
+        assert!(matches!(
+            caps.check(20_000_000, pool, crowded, 7 * 86_400),
+            Err(PricingError::OpenInterestCapExceeded { ref scope, remaining: 10_000_000, .. }) if scope == "expiry"
+        ));
+        assert_eq!(OpenInterestCaps::default().capacity(pool, crowded, 0).remaining, None);
+    }
+}
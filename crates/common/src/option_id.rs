@@ -0,0 +1,182 @@
+//! Canonical option identifiers
+//!
+//! Every module derives option IDs the same way: SHA256 over the creator's
+//! key, the option terms and a nonce. The string ID, the 6-byte short ID
+//! carried in anchors and the ID bound into BitVMX settlement proofs are all
+//! cut from that one digest, so an on-chain anchor can always be linked back
+//! to the option record.
+
+use crate::crypto::sha256;
+use crate::error::{OracleVmError, Result};
+use crate::types::OptionType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Prefix of the string form
+pub const OPTION_ID_PREFIX: &str = "OPT-";
+/// Length of the short ID used in anchors and BitVMX proofs
+pub const SHORT_ID_LEN: usize = 6;
+/// Bytes of the digest kept in the ID (128 bits)
+const ID_LEN: usize = 16;
+
+/// Option terms covered by the ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptionTerms {
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub quantity: u64,     // satoshis
+    /// Expiry block height or Unix timestamp, whichever the module uses
+    pub expiry: u64,
+}
+
+/// Canonical option ID, rendered as `OPT-<32 hex>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OptionId([u8; ID_LEN]);
+
+impl OptionId {
+    /// Derive the ID from the creator's key (or address bytes when no key is
+    /// known), the terms and a creator-chosen nonce
+    pub fn derive(creator: &[u8], terms: &OptionTerms, nonce: u64) -> Self {
+        let option_type = match terms.option_type {
+            OptionType::Call => "call",
+            OptionType::Put => "put",
+        };
+        let preimage = format!(
+            "option-id|v1|{}|{}|{}|{}|{}|{}",
+            hex::encode(creator),
+            option_type,
+            terms.strike_price,
+            terms.quantity,
+            terms.expiry,
+            nonce
+        );
+        let digest = sha256(preimage.as_bytes());
+        let mut id = [0u8; ID_LEN];
+        id.copy_from_slice(&digest[..ID_LEN]);
+        Self(id)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; ID_LEN] {
+        &self.0
+    }
+
+    /// First 6 bytes, used where payload space is tight (anchors, proofs)
+    pub fn short_id(&self) -> [u8; SHORT_ID_LEN] {
+        let mut short = [0u8; SHORT_ID_LEN];
+        short.copy_from_slice(&self.0[..SHORT_ID_LEN]);
+        short
+    }
+
+    /// Whether a short ID taken from an anchor or proof belongs to this option
+    pub fn matches_short(&self, short: &[u8]) -> bool {
+        short == self.short_id()
+    }
+}
+
+impl fmt::Display for OptionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", OPTION_ID_PREFIX, hex::encode(self.0))
+    }
+}
+
+impl FromStr for OptionId {
+    type Err = OracleVmError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || OracleVmError::InvalidData(format!("Invalid option ID: {}", s));
+        let hex_part = s.strip_prefix(OPTION_ID_PREFIX).ok_or_else(invalid)?;
+        let bytes = hex::decode(hex_part).map_err(|_| invalid())?;
+        let id: [u8; ID_LEN] = bytes.try_into().map_err(|_| invalid())?;
+        Ok(Self(id))
+    }
+}
+
+impl TryFrom<String> for OptionId {
+    type Error = OracleVmError;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<OptionId> for String {
+    fn from(id: OptionId) -> Self {
+        id.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn terms(strike_price: u64, expiry: u64) -> OptionTerms {
+        OptionTerms {
+            option_type: OptionType::Call,
+            strike_price,
+            quantity: 1_000_000,
+            expiry,
+        }
+    }
+
+    #[test]
+    fn test_derivation_is_stable_and_roundtrips() {
+        let creator = [2u8; 33];
+        let id = OptionId::derive(&creator, &terms(7_000_000, 800_000), 0);
+        assert_eq!(id, OptionId::derive(&creator, &terms(7_000_000, 800_000), 0));
+
+        let text = id.to_string();
+        assert!(text.starts_with("OPT-") && text.len() == 4 + 32);
+        assert_eq!(text.parse::<OptionId>().unwrap(), id);
+        assert_eq!(hex::decode(&text[4..16]).unwrap(), id.short_id());
+        assert!(id.matches_short(&id.short_id()));
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"{}\"", text));
+        assert_eq!(serde_json::from_str::<OptionId>(&json).unwrap(), id);
+
+        for bad in ["OPT-CALL-70000-800000", "OPT-zz", "abcd", "OPT-00"] {
+            assert!(bad.parse::<OptionId>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_every_input_changes_the_id() {
+        let base = OptionId::derive(&[2u8; 33], &terms(7_000_000, 800_000), 0);
+        let put = OptionTerms {
+            option_type: OptionType::Put,
+            ..terms(7_000_000, 800_000)
+        };
+        let variants = [
+            OptionId::derive(&[3u8; 33], &terms(7_000_000, 800_000), 0),
+            OptionId::derive(&[2u8; 33], &put, 0),
+            OptionId::derive(&[2u8; 33], &terms(7_000_001, 800_000), 0),
+            OptionId::derive(&[2u8; 33], &terms(7_000_000, 800_001), 0),
+            OptionId::derive(&[2u8; 33], &terms(7_000_000, 800_000), 1),
+        ];
+        for variant in variants {
+            assert_ne!(variant, base);
+            assert_ne!(variant.short_id(), base.short_id());
+        }
+    }
+
+    #[test]
+    fn test_no_collisions_across_many_options() {
+        // The same creator opening identical terms repeatedly differs only by nonce,
+        // which is where the legacy timestamp-based IDs collided
+        let mut ids = HashSet::new();
+        let mut short_ids = HashSet::new();
+        for creator in 0u8..4 {
+            for strike in 0..50u64 {
+                for nonce in 0..250u64 {
+                    let id = OptionId::derive(&[creator; 33], &terms(6_000_000 + strike * 10_000, 800_000), nonce);
+                    assert!(ids.insert(id));
+                    assert!(short_ids.insert(id.short_id()));
+                }
+            }
+        }
+        assert_eq!(ids.len(), 50_000);
+    }
+}
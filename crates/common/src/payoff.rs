@@ -0,0 +1,68 @@
+//! Vanilla vs. cash-or-nothing (binary) payoffs
+//!
+//! A binary option pays a fixed amount if the settlement price finishes
+//! beyond the strike (above for a call, below for a put) and nothing
+//! otherwise. For binaries `quantity` is that payout in satoshis, which is
+//! also all the collateral the pool has to lock.
+
+use crate::types::OptionType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How an in-the-money option pays out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Payoff {
+    /// Intrinsic value, capped at the collateral
+    #[default]
+    Vanilla,
+    /// Fixed payout of `quantity` satoshis
+    Binary,
+}
+
+impl Payoff {
+    pub fn is_vanilla(&self) -> bool {
+        *self == Self::Vanilla
+    }
+
+    pub fn is_binary(&self) -> bool {
+        *self == Self::Binary
+    }
+}
+
+impl fmt::Display for Payoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Vanilla => write!(f, "vanilla"),
+            Self::Binary => write!(f, "binary"),
+        }
+    }
+}
+
+/// Cash-or-nothing payout: `payout` if the spot finishes strictly beyond the strike
+pub fn binary_payout(option_type: OptionType, strike_price: u64, spot_price: u64, payout: u64) -> u64 {
+    let in_the_money = match option_type {
+        OptionType::Call => spot_price > strike_price,
+        OptionType::Put => spot_price < strike_price,
+    };
+    if in_the_money {
+        payout
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_payout_is_all_or_nothing() {
+        assert_eq!(binary_payout(OptionType::Call, 7_000_000, 7_000_001, 50_000), 50_000);
+        assert_eq!(binary_payout(OptionType::Call, 7_000_000, 7_000_000, 50_000), 0);
+        assert_eq!(binary_payout(OptionType::Put, 7_000_000, 6_000_000, 50_000), 50_000);
+        assert_eq!(binary_payout(OptionType::Put, 7_000_000, 9_000_000, 50_000), 0);
+        assert_eq!(Payoff::default(), Payoff::Vanilla);
+        assert_eq!(Payoff::Binary.to_string(), "binary");
+    }
+}
@@ -0,0 +1,185 @@
+//! Integer-cent price arithmetic
+//!
+//! Prices travel as `u64` USD cents from the exchange clients through
+//! consensus to settlement. Floating point only appears at the edges: parsing
+//! an exchange response, the legacy `double` gRPC fields and log output. Every
+//! conversion into cents names its rounding rule, and every derived price
+//! (means, ratios) is computed in `u128` so nothing drifts between the node
+//! that signed a price and the contract that settles on it.
+
+use serde::{de, Deserializer};
+use std::fmt;
+
+/// How a fractional cent is resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Banker's rounding; default for prices so ties do not bias upward
+    HalfEven,
+    /// Toward zero
+    Down,
+    /// Away from zero
+    Up,
+}
+
+/// Basis points in one whole (100%)
+pub const BPS: u64 = 10_000;
+
+/// `numerator / denominator` with an explicit rounding rule
+pub fn div_round(numerator: u128, denominator: u128, rounding: Rounding) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder > 0,
+        Rounding::HalfEven => {
+            let twice = remainder * 2;
+            twice > denominator || (twice == denominator && quotient % 2 == 1)
+        }
+    };
+    Some(quotient + round_up as u128)
+}
+
+/// `value * numerator / denominator` without intermediate overflow
+pub fn mul_div(value: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    div_round(value as u128 * numerator as u128, denominator as u128, rounding)
+        .and_then(|result| u64::try_from(result).ok())
+}
+
+/// Dollars (from an exchange response or a legacy `double` field) to cents
+///
+/// Returns `None` for negative, non-finite or out-of-range input.
+pub fn cents_from_dollars(dollars: f64, rounding: Rounding) -> Option<u64> {
+    if !dollars.is_finite() || dollars < 0.0 {
+        return None;
+    }
+    let cents = dollars * 100.0;
+    // Exchange prices carry at most a few decimals; snap representation
+    // error (70000.12 * 100 = 7000011.999...) before applying the rule
+    let snapped = (cents * 1e6).round() / 1e6;
+    let rounded = match rounding {
+        Rounding::HalfEven => {
+            let floor = snapped.floor();
+            let diff = snapped - floor;
+            if diff > 0.5 || (diff == 0.5 && floor % 2.0 != 0.0) {
+                floor + 1.0
+            } else {
+                floor
+            }
+        }
+        Rounding::Down => snapped.floor(),
+        Rounding::Up => snapped.ceil(),
+    };
+    (rounded <= u64::MAX as f64).then_some(rounded as u64)
+}
+
+/// Cents to dollars for display and legacy `double` fields only
+pub fn cents_to_dollars(cents: u64) -> f64 {
+    cents as f64 / 100.0
+}
+
+/// Weighted mean of `(cents, weight)` pairs
+pub fn weighted_mean_cents(prices: &[(u64, u64)], rounding: Rounding) -> Option<u64> {
+    let (sum, weight) = prices
+        .iter()
+        .fold((0u128, 0u128), |(sum, weight), (price, w)| {
+            (sum + *price as u128 * *w as u128, weight + *w as u128)
+        });
+    div_round(sum, weight, rounding).and_then(|mean| u64::try_from(mean).ok())
+}
+
+/// `|price - reference| / reference` in basis points, rounded up so a
+/// threshold comparison never lets a deviation slip under the limit
+pub fn deviation_bps(price: u64, reference: u64) -> Option<u64> {
+    let diff = price.abs_diff(reference);
+    mul_div(diff, BPS, reference, Rounding::Up)
+}
+
+/// Ratio (0.02 = 2%) to basis points, e.g. for consensus config values
+pub fn ratio_to_bps(ratio: f64) -> u64 {
+    (ratio * BPS as f64).round().max(0.0) as u64
+}
+
+/// Fixed-point display (`7000012` → `"70000.12"`)
+pub fn format_cents(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Accepts integer cents or, for records written before prices were kept in
+/// cents, a float dollar amount (rounded half-even)
+pub fn deserialize_cents<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    struct CentsVisitor;
+
+    impl de::Visitor<'_> for CentsVisitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "integer cents or a dollar amount")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+            u64::try_from(value).map_err(|_| E::custom(format!("negative price {}", value)))
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<u64, E> {
+            cents_from_dollars(value, Rounding::HalfEven)
+                .ok_or_else(|| E::custom(format!("invalid dollar price {}", value)))
+        }
+    }
+
+    deserializer.deserialize_any(CentsVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_rules() {
+        assert_eq!(cents_from_dollars(70000.12, Rounding::HalfEven), Some(7_000_012));
+        assert_eq!(cents_from_dollars(0.1 + 0.2, Rounding::HalfEven), Some(30));
+        assert_eq!(cents_from_dollars(1.005, Rounding::HalfEven), Some(100));
+        assert_eq!(cents_from_dollars(1.015, Rounding::HalfEven), Some(102));
+        assert_eq!(cents_from_dollars(1.019, Rounding::Down), Some(101));
+        assert_eq!(cents_from_dollars(1.011, Rounding::Up), Some(102));
+        assert_eq!(cents_from_dollars(-1.0, Rounding::HalfEven), None);
+        assert_eq!(cents_from_dollars(f64::NAN, Rounding::HalfEven), None);
+
+        assert_eq!(div_round(5, 2, Rounding::HalfEven), Some(2));
+        assert_eq!(div_round(7, 2, Rounding::HalfEven), Some(4));
+        assert_eq!(div_round(7, 0, Rounding::Down), None);
+    }
+
+    #[test]
+    fn test_mean_and_deviation_are_exact() {
+        // 2:1 weights, (7_000_000*2 + 7_000_001) / 3 = 7_000_000.33
+        let mean = weighted_mean_cents(&[(7_000_000, 2), (7_000_001, 1)], Rounding::HalfEven);
+        assert_eq!(mean, Some(7_000_000));
+        assert_eq!(weighted_mean_cents(&[], Rounding::HalfEven), None);
+
+        // 2% of 7_000_000 is exactly 140_000 cents
+        assert_eq!(deviation_bps(7_140_000, 7_000_000), Some(200));
+        assert_eq!(deviation_bps(7_140_001, 7_000_000), Some(201));
+        assert_eq!(ratio_to_bps(0.02), 200);
+        assert_eq!(format_cents(7_000_005), "70000.05");
+    }
+
+    #[test]
+    fn test_deserialize_legacy_dollars() {
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            #[serde(deserialize_with = "deserialize_cents")]
+            price: u64,
+        }
+        let cents: Entry = serde_json::from_str(r#"{"price": 7000012}"#).unwrap();
+        let dollars: Entry = serde_json::from_str(r#"{"price": 70000.12}"#).unwrap();
+        assert_eq!(cents.price, 7_000_012);
+        assert_eq!(dollars.price, 7_000_012);
+    }
+}
@@ -0,0 +1,175 @@
+//! Cached price feed with subscription fan-out and staleness signaling
+//!
+//! One poller per process owns the aggregator connection and publishes into a
+//! [`PriceFeed`]; everything else reads the cached value or subscribes. The
+//! feed never blocks readers on the network, and a value older than
+//! `max_age_secs` is reported as [`FeedStatus::Stale`] instead of silently
+//! being served as current.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// Freshness of the cached value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedStatus {
+    /// Nothing received yet
+    Pending,
+    Fresh,
+    /// Last value is older than the feed's max age
+    Stale,
+}
+
+/// What subscribers see on every change
+#[derive(Debug, Clone)]
+pub struct FeedSnapshot<T> {
+    pub value: Option<T>,
+    /// Unix seconds the cached value was observed at its source
+    pub observed_at: u64,
+    pub status: FeedStatus,
+    /// Failed polls since the last successful one
+    pub failures: u32,
+    pub last_error: Option<String>,
+}
+
+impl<T> FeedSnapshot<T> {
+    fn pending() -> Self {
+        Self {
+            value: None,
+            observed_at: 0,
+            status: FeedStatus::Pending,
+            failures: 0,
+            last_error: None,
+        }
+    }
+}
+
+/// Latest-value cache shared between one poller and many readers
+#[derive(Debug)]
+pub struct PriceFeed<T> {
+    max_age_secs: u64,
+    tx: watch::Sender<FeedSnapshot<T>>,
+}
+
+impl<T: Clone> PriceFeed<T> {
+    pub fn new(max_age_secs: u64) -> Self {
+        Self {
+            max_age_secs,
+            tx: watch::Sender::new(FeedSnapshot::pending()),
+        }
+    }
+
+    pub fn max_age_secs(&self) -> u64 {
+        self.max_age_secs
+    }
+
+    fn status_at(&self, has_value: bool, observed_at: u64, now: u64) -> FeedStatus {
+        if !has_value {
+            FeedStatus::Pending
+        } else if now.saturating_sub(observed_at) > self.max_age_secs {
+            FeedStatus::Stale
+        } else {
+            FeedStatus::Fresh
+        }
+    }
+
+    /// Cache a new value and notify subscribers
+    pub fn publish(&self, value: T, observed_at: u64, now: u64) {
+        let status = self.status_at(true, observed_at, now);
+        self.tx.send_replace(FeedSnapshot {
+            value: Some(value),
+            observed_at,
+            status,
+            failures: 0,
+            last_error: None,
+        });
+    }
+
+    /// Count a failed poll; subscribers are only woken when the status flips
+    pub fn record_failure(&self, error: impl Into<String>, now: u64) {
+        let error = error.into();
+        self.tx.send_if_modified(|snapshot| {
+            snapshot.failures += 1;
+            snapshot.last_error = Some(error);
+            let status = self.status_at(snapshot.value.is_some(), snapshot.observed_at, now);
+            let changed = status != snapshot.status;
+            snapshot.status = status;
+            changed
+        });
+    }
+
+    /// Current snapshot with the status re-evaluated at `now`
+    pub fn snapshot(&self, now: u64) -> FeedSnapshot<T> {
+        let mut snapshot = self.tx.borrow().clone();
+        snapshot.status = self.status_at(snapshot.value.is_some(), snapshot.observed_at, now);
+        snapshot
+    }
+
+    pub fn status(&self, now: u64) -> FeedStatus {
+        let snapshot = self.tx.borrow();
+        self.status_at(snapshot.value.is_some(), snapshot.observed_at, now)
+    }
+
+    /// Last value regardless of age
+    pub fn latest(&self) -> Option<T> {
+        self.tx.borrow().value.clone()
+    }
+
+    /// Last value if it is still within the max age
+    pub fn fresh(&self, now: u64) -> Option<T> {
+        let snapshot = self.tx.borrow();
+        match self.status_at(snapshot.value.is_some(), snapshot.observed_at, now) {
+            FeedStatus::Fresh => snapshot.value.clone(),
+            _ => None,
+        }
+    }
+
+    /// Receiver woken on every new value and every status change
+    pub fn subscribe(&self) -> watch::Receiver<FeedSnapshot<T>> {
+        self.tx.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_fan_out_and_staleness() {
+        let feed = PriceFeed::<u64>::new(60);
+        let mut a = feed.subscribe();
+        let mut b = feed.subscribe();
+        assert_eq!(feed.status(1_000), FeedStatus::Pending);
+        assert_eq!(feed.fresh(1_000), None);
+
+        feed.publish(7_000_000, 1_000, 1_000);
+        for rx in [&mut a, &mut b] {
+            rx.changed().await.unwrap();
+            assert_eq!(rx.borrow_and_update().value, Some(7_000_000));
+        }
+        assert_eq!(feed.fresh(1_030), Some(7_000_000));
+
+        // A failure within max age does not wake subscribers
+        feed.record_failure("connection refused", 1_030);
+        assert!(!a.has_changed().unwrap());
+        assert_eq!(feed.snapshot(1_030).failures, 1);
+
+        // Past max age the cached value is still readable but not fresh
+        feed.record_failure("connection refused", 1_061);
+        assert!(a.has_changed().unwrap());
+        let snapshot = a.borrow_and_update().clone();
+        assert_eq!(snapshot.status, FeedStatus::Stale);
+        assert_eq!(snapshot.failures, 2);
+        assert_eq!(feed.fresh(1_061), None);
+        assert_eq!(feed.latest(), Some(7_000_000));
+
+        // Recovery resets the failure count
+        feed.publish(7_100_000, 1_070, 1_070);
+        assert_eq!(feed.snapshot(1_070).status, FeedStatus::Fresh);
+        assert_eq!(feed.snapshot(1_070).failures, 0);
+    }
+}
@@ -0,0 +1,476 @@
+//! Firm option quotes (RFQ) issued by the calculation service
+//!
+//! A quote fixes the premium for one (type, strike, expiry, size) request
+//! until `valid_until`. The contracts side only opens positions against a
+//! quote whose signature verifies and which has not expired or been used.
+//! Buy-back quotes work the same way in the other direction: the pool
+//! offers a close-out value for an open option.
+
+use crate::barrier::Barrier;
+use crate::canonical::{CanonicalEncode, CanonicalEncoder};
+use crate::crypto::{sign_data, verify_signature, PublicKey, SecretKey, Signature};
+use crate::exercise::{ExercisePolicy, ExerciseStyle};
+use crate::payoff::Payoff;
+use crate::types::OptionType;
+use crate::{OracleVmError, Result};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Client request for a firm quote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuoteRequest {
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub expiry: String,    // Expiry date (YYYY-MM-DD)
+    pub quantity: u64,     // satoshis
+    /// OTC deal: expiry need not be on the calendar
+    #[serde(default)]
+    pub otc: bool,
+    /// Referral code credited with a share of the protocol fee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    /// Tenant whose pool the quote is for (None = default pool)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Accept a smaller quote when the full size breaches per-trade greeks limits
+    #[serde(default)]
+    pub allow_partial: bool,
+    /// American options are priced on a binomial tree
+    #[serde(default)]
+    pub style: ExerciseStyle,
+    /// Knock-in/knock-out barrier (priced by Monte Carlo, European only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barrier: Option<Barrier>,
+    /// Binary options pay `quantity` satoshis if they finish in the money
+    #[serde(default)]
+    pub payoff: Payoff,
+}
+
+/// Signed, time-limited premium quote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionQuote {
+    pub quote_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub expiry: String,
+    pub quantity: u64,     // satoshis
+    pub premium: u64,      // satoshis
+    /// USD cents per BTC: the quote-time consensus spot used for pricing and
+    /// the rate `premium_usd` is converted at
+    pub spot_price: u64,
+    pub issued_at: u64,    // Unix timestamp (seconds)
+    pub valid_until: u64,  // Unix timestamp (seconds)
+    /// `premium` in USD cents at `spot_price` (rounded down); 0 on quotes
+    /// issued before it was carried
+    #[serde(default)]
+    pub premium_usd: u64,
+    /// Priced for an off-calendar OTC expiry
+    #[serde(default)]
+    pub otc: bool,
+    /// Auto-exercise/dust policy applied at settlement
+    #[serde(default)]
+    pub exercise: ExercisePolicy,
+    /// Referral code carried from the request, covered by the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referral_code: Option<String>,
+    /// Tenant pool the quote can be filled on, covered by the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Model premium before pool utilization and inventory skew (satoshis);
+    /// `premium` is the quoted ask
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theoretical_premium: Option<u64>,
+    /// Exercise style of the option opened from this quote
+    #[serde(default, skip_serializing_if = "ExerciseStyle::is_european")]
+    pub style: ExerciseStyle,
+    /// Barrier the option opened from this quote carries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barrier: Option<Barrier>,
+    /// Payoff of the option opened from this quote
+    #[serde(default, skip_serializing_if = "Payoff::is_vanilla")]
+    pub payoff: Payoff,
+    pub signature: String, // DER hex, empty until signed
+}
+
+impl OptionQuote {
+    /// Canonical bytes covered by the quote signature
+    ///
+    /// Optional terms are canonical extensions written only when set, so
+    /// plain quotes keep their original payload: 1 OTC, 2 dust threshold and
+    /// handling, 3 referral code, 4 tenant, 5 theoretical premium, 6 American
+    /// style, 7 barrier kind and level, 8 binary payoff, 9 USD premium.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        self.canonical_bytes()
+    }
+
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<()> {
+        self.signature = sign_data(&self.signing_payload(), secret_key)?.to_string();
+        Ok(())
+    }
+
+    /// Check the signature against the quoting service key
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        verify_quote(&self.quote_id, &self.signing_payload(), &self.signature, public_key)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.valid_until
+    }
+}
+
+impl CanonicalEncode for OptionQuote {
+    const DOMAIN: &'static str = "quote/v2";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder
+            .str(&self.quote_id)
+            .option_type(self.option_type)
+            .u64(self.strike_price)
+            .str(&self.expiry)
+            .u64(self.quantity)
+            .u64(self.premium)
+            .u64(self.spot_price)
+            .u64(self.issued_at)
+            .u64(self.valid_until);
+        if self.otc {
+            encoder.extension(1);
+        }
+        if self.exercise.is_enabled() {
+            encoder
+                .extension(2)
+                .u64(self.exercise.dust_threshold_sats)
+                .str(&self.exercise.dust_handling.to_string());
+        }
+        if let Some(code) = &self.referral_code {
+            encoder.extension(3).str(code);
+        }
+        if let Some(tenant_id) = &self.tenant_id {
+            encoder.extension(4).str(tenant_id);
+        }
+        if let Some(theoretical) = self.theoretical_premium {
+            encoder.extension(5).u64(theoretical);
+        }
+        if self.style.is_american() {
+            encoder.extension(6).str(&self.style.to_string());
+        }
+        if let Some(barrier) = &self.barrier {
+            encoder
+                .extension(7)
+                .str(&barrier.kind.to_string())
+                .u64(barrier.level);
+        }
+        if self.payoff.is_binary() {
+            encoder.extension(8).str(&self.payoff.to_string());
+        }
+        if self.premium_usd > 0 {
+            encoder.extension(9).u64(self.premium_usd);
+        }
+    }
+}
+
+fn verify_quote(quote_id: &str, payload: &[u8], signature: &str, public_key: &PublicKey) -> Result<()> {
+    let signature = Signature::from_str(signature)
+        .map_err(|e| OracleVmError::Crypto(format!("Invalid quote signature: {}", e)))?;
+
+    if !verify_signature(payload, &signature, public_key)? {
+        return Err(OracleVmError::Crypto(format!(
+            "Quote {} signature mismatch",
+            quote_id
+        )));
+    }
+    Ok(())
+}
+
+/// Holder request for the pool's close-out value of an open option
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuyBackRequest {
+    pub option_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64, // USD cents
+    pub expiry: String,    // Expiry date (YYYY-MM-DD)
+    pub quantity: u64,     // satoshis
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    #[serde(default)]
+    pub style: ExerciseStyle,
+    /// Barrier of the option, priced as not yet touched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barrier: Option<Barrier>,
+    #[serde(default)]
+    pub payoff: Payoff,
+}
+
+/// Signed, time-limited offer from the pool to buy an option back early
+///
+/// `value` is the theoretical value minus the pool's spread. The contracts
+/// side checks the terms against the stored option before cancelling it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuyBackQuote {
+    pub quote_id: String,
+    pub option_id: String,
+    pub option_type: OptionType,
+    pub strike_price: u64,      // USD cents
+    pub expiry: String,
+    pub quantity: u64,          // satoshis
+    pub value: u64,             // satoshis paid to the holder
+    pub theoretical_value: u64, // satoshis, before the spread
+    pub spot_price: u64,        // USD cents, spot used for pricing
+    pub issued_at: u64,         // Unix timestamp (seconds)
+    pub valid_until: u64,       // Unix timestamp (seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    pub signature: String, // DER hex, empty until signed
+}
+
+impl BuyBackQuote {
+    /// Canonical bytes covered by the signature (tenant quotes add
+    /// extension 1 with the tenant ID)
+    pub fn signing_payload(&self) -> Vec<u8> {
+        self.canonical_bytes()
+    }
+
+    /// Sign the quote in place
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<()> {
+        self.signature = sign_data(&self.signing_payload(), secret_key)?.to_string();
+        Ok(())
+    }
+
+    /// Check the signature against the quoting service key
+    pub fn verify(&self, public_key: &PublicKey) -> Result<()> {
+        verify_quote(&self.quote_id, &self.signing_payload(), &self.signature, public_key)
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.valid_until
+    }
+}
+
+impl CanonicalEncode for BuyBackQuote {
+    const DOMAIN: &'static str = "buyback/v2";
+
+    fn encode_fields(&self, encoder: &mut CanonicalEncoder) {
+        encoder
+            .str(&self.quote_id)
+            .str(&self.option_id)
+            .option_type(self.option_type)
+            .u64(self.strike_price)
+            .str(&self.expiry)
+            .u64(self.quantity)
+            .u64(self.value)
+            .u64(self.theoretical_value)
+            .u64(self.spot_price)
+            .u64(self.issued_at)
+            .u64(self.valid_until);
+        if let Some(tenant_id) = &self.tenant_id {
+            encoder.extension(1).str(tenant_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::generate_keypair;
+
+    fn quote() -> OptionQuote {
+        OptionQuote {
+            quote_id: "Q-1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            premium: 250_000,
+            spot_price: 7_000_000,
+            issued_at: 1_000,
+            valid_until: 1_030,
+            premium_usd: 0,
+            otc: false,
+            exercise: ExercisePolicy::default(),
+            referral_code: None,
+            tenant_id: None,
+            theoretical_premium: None,
+            style: ExerciseStyle::European,
+            barrier: None,
+            payoff: Payoff::Vanilla,
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_signing_payload_golden_vector() {
+        assert_eq!(
+            hex::encode(quote().signing_payload()),
+            concat!(
+                "08", "71756f74652f7632", // "quote/v2"
+                "03000000", "512d31", "04000000", "63616c6c", // "Q-1", "call"
+                "c0cf6a0000000000", "0a000000", "323032342d30332d3031", // strike, expiry
+                "8096980000000000", "90d0030000000000", "c0cf6a0000000000", // quantity, premium, spot
+                "e803000000000000", "0604000000000000", // issued_at, valid_until
+            )
+        );
+
+        // Extensions follow the core fields in tag order
+        let quote = OptionQuote {
+            otc: true,
+            tenant_id: Some("acme".to_string()),
+            ..quote()
+        };
+        assert!(hex::encode(quote.signing_payload()).ends_with(concat!(
+            "0604000000000000", // valid_until
+            "01",               // OTC
+            "04", "04000000", "61636d65", // tenant "acme"
+        )));
+    }
+
+    #[test]
+    fn test_free_text_cannot_forge_other_terms() {
+        // A referral code spelling out a tenant must not sign as that tenant
+        let forged = OptionQuote {
+            referral_code: Some("X|tenant|acme".to_string()),
+            ..quote()
+        };
+        let tenant = OptionQuote {
+            referral_code: Some("X".to_string()),
+            tenant_id: Some("acme".to_string()),
+            ..quote()
+        };
+        assert_ne!(forged.signing_payload(), tenant.signing_payload());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = quote();
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        // Tampered premium no longer verifies
+        quote.premium = 1;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_otc_flag_is_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = OptionQuote { otc: true, ..quote() };
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        quote.otc = false;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_exercise_policy_is_signed() {
+        use crate::exercise::DustHandling;
+
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = OptionQuote {
+            exercise: ExercisePolicy::new(1_000, DustHandling::Accumulate),
+            ..quote()
+        };
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        quote.exercise.dust_handling = DustHandling::PoolRevenue;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_referral_code_is_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        let plain = quote().signing_payload();
+        let mut quote = OptionQuote {
+            referral_code: Some("ALICE10".to_string()),
+            ..quote()
+        };
+        assert!(quote.signing_payload().starts_with(&plain));
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        quote.referral_code = Some("MALLORY".to_string());
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_tenant_is_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = OptionQuote {
+            tenant_id: Some("acme".to_string()),
+            ..quote()
+        };
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        // A quote for one tenant cannot be replayed on another tenant's pool
+        quote.tenant_id = Some("globex".to_string());
+        assert!(quote.verify(&public_key).is_err());
+        quote.tenant_id = None;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_theoretical_premium_is_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = OptionQuote {
+            theoretical_premium: Some(240_000),
+            ..quote()
+        };
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        // The disclosed spread over the model price cannot be rewritten
+        quote.theoretical_premium = Some(250_000);
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_usd_premium_is_converted_at_spot_and_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        // 250,000 sats at $70,000.00 = $175.00
+        let mut quote = OptionQuote {
+            premium_usd: 17_500,
+            ..quote()
+        };
+        assert_eq!(crate::settlement_currency::sats_to_cents(quote.premium, quote.spot_price), 17_500);
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+
+        quote.premium_usd = 20_000;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_buy_back_quote_is_signed() {
+        let (secret_key, public_key) = generate_keypair();
+        let mut quote = BuyBackQuote {
+            quote_id: "B-1".to_string(),
+            option_id: "OPT-1".to_string(),
+            option_type: OptionType::Call,
+            strike_price: 7_000_000,
+            expiry: "2024-03-01".to_string(),
+            quantity: 10_000_000,
+            value: 190_000,
+            theoretical_value: 200_000,
+            spot_price: 7_000_000,
+            issued_at: 1_000,
+            valid_until: 1_030,
+            tenant_id: None,
+            signature: String::new(),
+        };
+        quote.sign(&secret_key).unwrap();
+        assert!(quote.verify(&public_key).is_ok());
+        assert!(quote.is_expired(1_030));
+
+        // The holder cannot raise the close-out value
+        quote.value = 200_000;
+        assert!(quote.verify(&public_key).is_err());
+    }
+
+    #[test]
+    fn test_expiry() {
+        let quote = quote();
+        assert!(!quote.is_expired(1_029));
+        assert!(quote.is_expired(1_030));
+    }
+}
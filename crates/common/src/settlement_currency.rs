@@ -0,0 +1,130 @@
+//! Settlement currency abstraction
+//!
+//! Options are BTC-collateralised, but premiums and payouts can be quoted and
+//! settled either in BTC (satoshis) or in a USD stable representation (cents).
+//! Conversions always use the oracle price in USD cents per BTC.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const SATS_PER_BTC: u64 = 100_000_000;
+
+/// Currency a premium or payout is denominated in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SettlementCurrency {
+    /// Satoshis
+    #[default]
+    Btc,
+    /// USD cents
+    Usd,
+}
+
+impl SettlementCurrency {
+    pub fn is_btc(&self) -> bool {
+        matches!(self, Self::Btc)
+    }
+
+    pub fn is_usd(&self) -> bool {
+        matches!(self, Self::Usd)
+    }
+}
+
+impl fmt::Display for SettlementCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Btc => write!(f, "BTC"),
+            Self::Usd => write!(f, "USD"),
+        }
+    }
+}
+
+/// How USD balances are held
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UsdRail {
+    /// Synthetic USD accounting inside the pool, backed by BTC collateral
+    Synthetic,
+    /// Taproot Assets stablecoin (e.g. USDt) identified by its asset id
+    TaprootAsset { asset_id: String },
+}
+
+/// An amount in satoshis (BTC) or cents (USD)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money {
+    pub currency: SettlementCurrency,
+    pub amount: u64,
+}
+
+impl Money {
+    pub fn sats(amount: u64) -> Self {
+        Self {
+            currency: SettlementCurrency::Btc,
+            amount,
+        }
+    }
+
+    pub fn cents(amount: u64) -> Self {
+        Self {
+            currency: SettlementCurrency::Usd,
+            amount,
+        }
+    }
+
+    /// Convert at `price_cents` per BTC (rounded down, `None` for a zero price)
+    pub fn convert(&self, to: SettlementCurrency, price_cents: u64) -> Option<Self> {
+        let amount = match (self.currency, to) {
+            (from, to) if from == to => self.amount,
+            (SettlementCurrency::Btc, SettlementCurrency::Usd) => sats_to_cents(self.amount, price_cents),
+            _ => cents_to_sats(self.amount, price_cents)?,
+        };
+        Some(Self {
+            currency: to,
+            amount,
+        })
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.currency {
+            SettlementCurrency::Btc => write!(f, "{} sats", self.amount),
+            SettlementCurrency::Usd => write!(f, "${}.{:02}", self.amount / 100, self.amount % 100),
+        }
+    }
+}
+
+/// USD cents worth of `sats` at `price_cents` per BTC
+pub fn sats_to_cents(sats: u64, price_cents: u64) -> u64 {
+    (sats as u128 * price_cents as u128 / SATS_PER_BTC as u128) as u64
+}
+
+/// Satoshis worth of `cents` at `price_cents` per BTC
+pub fn cents_to_sats(cents: u64, price_cents: u64) -> Option<u64> {
+    if price_cents == 0 {
+        return None;
+    }
+    Some((cents as u128 * SATS_PER_BTC as u128 / price_cents as u128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_at_oracle_price() {
+        let price = 7_000_000; // $70,000.00
+        assert_eq!(sats_to_cents(SATS_PER_BTC / 2, price), 3_500_000);
+        assert_eq!(cents_to_sats(700_000, price), Some(10_000_000));
+        assert_eq!(cents_to_sats(1, 0), None);
+
+        let premium = Money::cents(35_000);
+        assert_eq!(premium.convert(SettlementCurrency::Btc, price), Some(Money::sats(500_000)));
+        assert_eq!(premium.convert(SettlementCurrency::Usd, 0), Some(premium));
+        assert_eq!(premium.to_string(), "$350.00");
+        assert_eq!(
+            serde_json::to_string(&SettlementCurrency::Usd).unwrap(),
+            "\"USD\""
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Graceful shutdown coordination
+//!
+//! Each binary owns one [`Shutdown`]. Ctrl-C or SIGTERM flips a watch
+//! channel; loops and servers holding a [`ShutdownSignal`] stop taking new
+//! work, and anything that must not be cut in half (a settlement, an anchor
+//! broadcast) holds a [`WorkGuard`] so the binary can wait for it to finish
+//! before flushing persistence and exiting.
+
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Owner side: triggers shutdown and drains in-flight work
+#[derive(Debug)]
+pub struct Shutdown {
+    triggered: watch::Sender<bool>,
+    in_flight: watch::Sender<usize>,
+}
+
+/// Subscriber side, cheap to clone into tasks
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    triggered: watch::Receiver<bool>,
+    in_flight: watch::Sender<usize>,
+}
+
+/// Marks one unit of in-flight work; released on drop
+#[derive(Debug)]
+pub struct WorkGuard {
+    in_flight: watch::Sender<usize>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self {
+            triggered: watch::Sender::new(false),
+            in_flight: watch::Sender::new(0),
+        }
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            triggered: self.triggered.subscribe(),
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    /// Start shutting down; idempotent
+    pub fn trigger(&self) {
+        self.triggered.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.triggered.borrow()
+    }
+
+    /// Trigger shutdown on Ctrl-C or SIGTERM
+    pub fn listen_for_signals(&self) {
+        let triggered = self.triggered.clone();
+        tokio::spawn(async move {
+            wait_for_signal().await;
+            triggered.send_replace(true);
+        });
+    }
+
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.borrow()
+    }
+
+    /// Wait for in-flight work to finish; false if `timeout` elapsed first
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let mut in_flight = self.in_flight.subscribe();
+        let drained = tokio::time::timeout(timeout, in_flight.wait_for(|count| *count == 0))
+            .await
+            .is_ok();
+        drained
+    }
+}
+
+impl ShutdownSignal {
+    pub fn is_shutdown(&self) -> bool {
+        *self.triggered.borrow()
+    }
+
+    /// Resolves once shutdown has been triggered
+    pub async fn recv(&mut self) {
+        // The sender lives as long as the `Shutdown`; if it is gone, so is the binary
+        let _ = self.triggered.wait_for(|triggered| *triggered).await;
+    }
+
+    /// Register new work, or `None` once shutdown has started
+    pub fn begin_work(&self) -> Option<WorkGuard> {
+        if self.is_shutdown() {
+            return None;
+        }
+        self.in_flight.send_modify(|count| *count += 1);
+        Some(WorkGuard {
+            in_flight: self.in_flight.clone(),
+        })
+    }
+}
+
+impl Drop for WorkGuard {
+    fn drop(&mut self) {
+        self.in_flight.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stops_new_work_and_drains() {
+        let shutdown = Shutdown::new();
+        let mut signal = shutdown.signal();
+        let guard = signal.begin_work().unwrap();
+        assert_eq!(shutdown.in_flight(), 1);
+
+        shutdown.trigger();
+        signal.recv().await;
+        assert!(signal.begin_work().is_none());
+        assert!(!shutdown.drain(Duration::from_millis(10)).await);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            drop(guard);
+        });
+        assert!(shutdown.drain(Duration::from_secs(1)).await);
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+}
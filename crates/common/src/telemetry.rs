@@ -0,0 +1,240 @@
+//! Tracing setup and cross-service correlation IDs
+//!
+//! Every inbound request gets a [`CorrelationId`], taken from the
+//! `x-correlation-id` HTTP header or gRPC metadata entry when the caller sent
+//! one and generated otherwise. The ID is recorded on the request span and
+//! kept in a task-local for the duration of the request, so outbound calls
+//! made while handling it ([`CorrelationId::current`]) carry the same ID and
+//! one option's quote → create → settle → anchor path can be followed across
+//! calculation, contracts and the aggregator.
+//!
+//! [`init_tracing`] installs the fmt subscriber (filtered by `RUST_LOG`) and,
+//! with the `otlp` feature, exports spans to the collector named by
+//! `OTEL_EXPORTER_OTLP_ENDPOINT`.
+
+use std::fmt;
+use std::future::Future;
+use tonic::metadata::MetadataValue;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// HTTP header and gRPC metadata key carrying the correlation ID
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Longest correlation ID accepted from a caller
+pub const MAX_CORRELATION_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// Identifier shared by every span and log line of one logical request
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    /// Random 128-bit ID, hex encoded
+    pub fn generate() -> Self {
+        Self(hex::encode(rand::random::<[u8; 16]>()))
+    }
+
+    /// Caller-supplied ID; `None` if empty, too long or not `[A-Za-z0-9._-]`,
+    /// so header values never inject arbitrary text into logs
+    pub fn parse(value: &str) -> Option<Self> {
+        let valid = !value.is_empty()
+            && value.len() <= MAX_CORRELATION_ID_LEN
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+        valid.then(|| Self(value.to_string()))
+    }
+
+    /// The caller's ID if it is valid, a fresh one otherwise
+    pub fn from_header(value: Option<&str>) -> Self {
+        value.and_then(Self::parse).unwrap_or_else(Self::generate)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// ID of the request being handled on this task, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Current ID, or a fresh one for work not started by a request
+    /// (background jobs, CLI calls)
+    pub fn current_or_generate() -> Self {
+        Self::current().unwrap_or_else(Self::generate)
+    }
+
+    /// Run `future` with this ID as [`Self::current`]
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// ID from inbound gRPC metadata, generated if missing or invalid
+    pub fn from_grpc<T>(request: &tonic::Request<T>) -> Self {
+        Self::from_header(
+            request
+                .metadata()
+                .get(CORRELATION_ID_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        )
+    }
+
+    /// Attach this ID to an outbound gRPC request
+    pub fn inject_grpc<T>(&self, request: &mut tonic::Request<T>) {
+        if let Ok(value) = MetadataValue::try_from(self.as_str()) {
+            request.metadata_mut().insert(CORRELATION_ID_HEADER, value);
+        }
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Span for one inbound gRPC call, for `tonic::transport::Server::trace_fn`
+pub fn grpc_request_span(request: &tonic::codegen::http::Request<()>) -> tracing::Span {
+    let id = CorrelationId::from_header(
+        request
+            .headers()
+            .get(CORRELATION_ID_HEADER)
+            .and_then(|value| value.to_str().ok()),
+    );
+    tracing::info_span!("grpc.request", correlation_id = %id, path = %request.uri().path())
+}
+
+/// Keeps the span exporter alive; dropping it flushes pending spans
+#[must_use = "dropping the guard stops span export"]
+pub struct TracingGuard {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("OTLP span exporter shutdown failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global subscriber for `service`
+///
+/// Logs go to stdout filtered by `RUST_LOG` (default `info`). When built
+/// with the `otlp` feature and `OTEL_EXPORTER_OTLP_ENDPOINT` is set, spans
+/// are also exported over OTLP/gRPC with `service.name = service`.
+pub fn init_tracing(service: &str) -> TracingGuard {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otlp")]
+    {
+        match otlp::provider(service) {
+            Ok(Some(provider)) => {
+                use opentelemetry::trace::TracerProvider as _;
+                let tracer = provider.tracer(service.to_string());
+                registry
+                    .with(tracing_opentelemetry::layer().with_tracer(tracer))
+                    .init();
+                return TracingGuard {
+                    provider: Some(provider),
+                };
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("OTLP export disabled: {}", e),
+        }
+        registry.init();
+        TracingGuard { provider: None }
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        let _ = service;
+        registry.init();
+        TracingGuard {}
+    }
+}
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use opentelemetry_sdk::{runtime, Resource};
+
+    /// Endpoint variable defined by the OpenTelemetry spec
+    const ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+    pub fn provider(service: &str) -> Result<Option<TracerProvider>, String> {
+        let Ok(endpoint) = std::env::var(ENDPOINT_ENV) else {
+            return Ok(None);
+        };
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Some(
+            TracerProvider::builder()
+                .with_batch_exporter(exporter, runtime::Tokio)
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    service.to_string(),
+                )]))
+                .build(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rejects_unsafe_values() {
+        assert_eq!(CorrelationId::parse("req-42.a_b").unwrap().as_str(), "req-42.a_b");
+        assert!(CorrelationId::parse("").is_none());
+        assert!(CorrelationId::parse("a b").is_none());
+        assert!(CorrelationId::parse("line\nbreak").is_none());
+        assert!(CorrelationId::parse(&"a".repeat(MAX_CORRELATION_ID_LEN + 1)).is_none());
+
+        let generated = CorrelationId::from_header(Some("bad value"));
+        assert_eq!(generated.as_str().len(), 32);
+        assert_ne!(generated, CorrelationId::generate());
+    }
+
+    #[test]
+    fn test_grpc_metadata_round_trip() {
+        let id = CorrelationId::parse("abc-123").unwrap();
+        let mut request = tonic::Request::new(());
+        id.inject_grpc(&mut request);
+        assert_eq!(CorrelationId::from_grpc(&request), id);
+
+        let fresh = CorrelationId::from_grpc(&tonic::Request::new(()));
+        assert_ne!(fresh, id);
+    }
+
+    #[tokio::test]
+    async fn test_scope_sets_current() {
+        assert!(CorrelationId::current().is_none());
+        let id = CorrelationId::parse("scoped").unwrap();
+        let seen = id
+            .clone()
+            .scope(async { tokio::task::yield_now().await; CorrelationId::current() })
+            .await;
+        assert_eq!(seen, Some(id));
+        assert!(CorrelationId::current().is_none());
+    }
+}
@@ -33,6 +33,8 @@ pub struct PriceData {
     pub timestamp: DateTime<Utc>,
     pub volume: Option<u64>, // 24h volume
     pub source: String,      // Exchange name
+    #[serde(default)]
+    pub degraded: bool,      // Sourced from a fallback exchange
 }
 
 /// Signed price data with oracle signature
@@ -54,6 +56,26 @@ pub struct AggregatedPrice {
     pub confidence: f64, // 0.0 to 1.0
 }
 
+/// Implied volatility quote for a single option instrument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolQuote {
+    pub instrument: String, // Venue instrument name (e.g., "BTC-27DEC24-70000-C")
+    pub expiry: DateTime<Utc>,
+    pub strike: u64, // Strike in cents
+    pub option_type: OptionType,
+    pub mark_iv: f64, // Annualized, decimal (0.55 = 55%)
+}
+
+/// Implied volatility surface snapshot from an options venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolSurface {
+    pub pair: AssetPair,
+    pub underlying_price: u64, // Price in cents
+    pub timestamp: DateTime<Utc>,
+    pub quotes: Vec<VolQuote>,
+    pub source: String, // Venue name
+}
+
 /// Oracle node identifier
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NodeId(pub String);
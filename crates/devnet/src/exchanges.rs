@@ -0,0 +1,461 @@
+//! 가짜 Binance/Coinbase/Kraken REST + WebSocket 엔드포인트
+//!
+//! 실제 거래소와 같은 경로/응답 형식을 `/binance`, `/coinbase`, `/kraken` 아래에
+//! 제공합니다. Oracle Node 설정에서 `[exchanges.<name>] base_url`만 바꾸면
+//! 코드 수정 없이 devnet 가격을 수집합니다.
+//!
+//! | 거래소 | REST | WebSocket |
+//! |---|---|---|
+//! | binance | `GET /binance/api/v3/klines` | `/binance/ws/btcusdt@kline_1m` |
+//! | coinbase | `GET /coinbase/products/BTC-USD/candles` | `/coinbase/ws` (ticker 구독) |
+//! | kraken | `GET /kraken/0/public/OHLC` | `/kraken/ws` (ohlc 구독) |
+
+use crate::price_path::{Candle, PriceSimulator};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// WebSocket 틱 간격
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 거래소별 티커와 베이시스 (거래소 간 가격 차이 재현)
+pub struct Venue {
+    pub basis_bps: f64,
+    pub tickers: &'static [&'static str],
+}
+
+pub const BINANCE: Venue = Venue {
+    basis_bps: 3.0,
+    tickers: &["BTCUSDT"],
+};
+
+pub const COINBASE: Venue = Venue {
+    basis_bps: 0.0,
+    tickers: &["BTC-USD"],
+};
+
+pub const KRAKEN: Venue = Venue {
+    basis_bps: -2.0,
+    tickers: &["XBTUSD", "XXBTZUSD", "XBT/USD"],
+};
+
+/// Kraken 응답의 내부 페어 이름
+const KRAKEN_RESULT_KEY: &str = "XXBTZUSD";
+
+impl Venue {
+    fn lists(&self, ticker: &str) -> bool {
+        self.tickers.iter().any(|t| t.eq_ignore_ascii_case(ticker))
+    }
+}
+
+/// 모든 가짜 거래소가 공유하는 가격 경로
+pub type SharedSimulator = Arc<Mutex<PriceSimulator>>;
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// 시뮬레이터를 현재 시각까지 진행시킨 뒤 `f` 실행
+fn with_market<T>(simulator: &SharedSimulator, f: impl FnOnce(&PriceSimulator, u64) -> T) -> T {
+    let now = now();
+    let mut simulator = simulator.lock().unwrap();
+    simulator.advance_to(now);
+    f(&simulator, now)
+}
+
+fn price(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+pub fn router(simulator: SharedSimulator) -> Router {
+    Router::new()
+        .route("/binance/api/v3/klines", get(binance_klines))
+        .route("/binance/ws/:stream", get(binance_ws))
+        .route("/coinbase/products/:product_id/candles", get(coinbase_candles))
+        .route("/coinbase/ws", get(coinbase_ws))
+        .route("/kraken/0/public/OHLC", get(kraken_ohlc))
+        .route("/kraken/ws", get(kraken_ws))
+        .with_state(simulator)
+}
+
+// ---------------------------------------------------------------------------
+// Binance
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KlineQuery {
+    symbol: String,
+    start_time: Option<u64>,
+    end_time: Option<u64>,
+    limit: Option<usize>,
+}
+
+/// [open_time, open, high, low, close, volume, close_time, quote_volume, count, taker_buy_volume, taker_buy_quote_volume, ignore]
+fn binance_kline(candle: &Candle) -> Value {
+    json!([
+        candle.open_time * 1000,
+        price(candle.open),
+        price(candle.high),
+        price(candle.low),
+        price(candle.close),
+        format!("{:.5}", candle.volume),
+        (candle.open_time + 60) * 1000 - 1,
+        price(candle.volume * candle.close),
+        100,
+        format!("{:.5}", candle.volume / 2.0),
+        price(candle.volume * candle.close / 2.0),
+        "0"
+    ])
+}
+
+async fn binance_klines(
+    State(simulator): State<SharedSimulator>,
+    Query(query): Query<KlineQuery>,
+) -> Response {
+    if !BINANCE.lists(&query.symbol) {
+        let body = json!({ "code": -1121, "msg": "Invalid symbol." });
+        return (StatusCode::BAD_REQUEST, Json(body)).into_response();
+    }
+    let limit = query.limit.unwrap_or(500);
+    let candles = with_market(&simulator, |market, _| match query.start_time {
+        Some(start) => market
+            .candles_since(start / 1000)
+            .into_iter()
+            .filter(|c| query.end_time.is_none_or(|end| c.open_time * 1000 < end))
+            .take(limit)
+            .collect::<Vec<_>>(),
+        None => {
+            let mut recent = market.recent(limit);
+            recent.reverse();
+            recent
+        }
+    });
+    let klines: Vec<Value> = candles
+        .iter()
+        .map(|c| binance_kline(&c.with_basis(BINANCE.basis_bps)))
+        .collect();
+    Json(klines).into_response()
+}
+
+async fn binance_ws(
+    State(simulator): State<SharedSimulator>,
+    Path(stream): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let symbol = stream.split('@').next().unwrap_or_default().to_uppercase();
+    if !BINANCE.lists(&symbol) {
+        return (StatusCode::BAD_REQUEST, "Invalid symbol").into_response();
+    }
+    ws.on_upgrade(move |socket| {
+        stream_ticks(socket, simulator, move |market, now| {
+            let candle = market.forming().with_basis(BINANCE.basis_bps);
+            let close = market.spot(now) * (1.0 + BINANCE.basis_bps / 10_000.0);
+            json!({
+                "e": "kline",
+                "E": now * 1000,
+                "s": symbol,
+                "k": {
+                    "t": candle.open_time * 1000,
+                    "T": (candle.open_time + 60) * 1000 - 1,
+                    "s": symbol,
+                    "i": "1m",
+                    "o": price(candle.open),
+                    "c": price(close),
+                    "h": price(candle.high.max(close)),
+                    "l": price(candle.low.min(close)),
+                    "v": format!("{:.5}", candle.volume),
+                    "x": false
+                }
+            })
+        })
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Coinbase
+
+#[derive(Debug, Deserialize)]
+struct CandleQuery {
+    limit: Option<usize>,
+}
+
+async fn coinbase_candles(
+    State(simulator): State<SharedSimulator>,
+    Path(product_id): Path<String>,
+    Query(query): Query<CandleQuery>,
+) -> Response {
+    if !COINBASE.lists(&product_id) {
+        let body = json!({ "message": "NotFound" });
+        return (StatusCode::NOT_FOUND, Json(body)).into_response();
+    }
+    let candles = with_market(&simulator, |market, _| market.recent(query.limit.unwrap_or(300)));
+    // [time, low, high, open, close, volume], 최신 순
+    let body: Vec<Value> = candles
+        .iter()
+        .map(|c| {
+            let c = c.with_basis(COINBASE.basis_bps);
+            json!([c.open_time, c.low, c.high, c.open, c.close, c.volume])
+        })
+        .collect();
+    Json(body).into_response()
+}
+
+async fn coinbase_ws(State(simulator): State<SharedSimulator>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |mut socket| async move {
+        let Some(request) = next_json(&mut socket).await else {
+            return;
+        };
+        let products: Vec<String> = request["product_ids"]
+            .as_array()
+            .map(|ids| ids.iter().filter_map(|id| id.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let Some(product) = products.into_iter().find(|p| COINBASE.lists(p)) else {
+            let _ = send_json(&mut socket, &json!({ "type": "error", "message": "Failed to subscribe" })).await;
+            return;
+        };
+        let ack = json!({
+            "type": "subscriptions",
+            "channels": [{ "name": "ticker", "product_ids": [product.clone()] }]
+        });
+        if send_json(&mut socket, &ack).await.is_err() {
+            return;
+        }
+        let mut sequence = 0u64;
+        stream_ticks(socket, simulator, move |market, now| {
+            sequence += 1;
+            let spot = market.spot(now) * (1.0 + COINBASE.basis_bps / 10_000.0);
+            json!({
+                "type": "ticker",
+                "sequence": sequence,
+                "product_id": product,
+                "price": price(spot),
+                "time": chrono::DateTime::from_timestamp(now as i64, 0)
+                    .unwrap_or_default()
+                    .to_rfc3339(),
+            })
+        })
+        .await
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Kraken
+
+#[derive(Debug, Deserialize)]
+struct OhlcQuery {
+    pair: String,
+    since: Option<u64>,
+}
+
+/// [time, open, high, low, close, vwap, volume, count]
+fn kraken_ohlc_row(candle: &Candle) -> Value {
+    json!([
+        candle.open_time,
+        price(candle.open),
+        price(candle.high),
+        price(candle.low),
+        price(candle.close),
+        price((candle.open + candle.high + candle.low + candle.close) / 4.0),
+        format!("{:.8}", candle.volume),
+        100
+    ])
+}
+
+async fn kraken_ohlc(
+    State(simulator): State<SharedSimulator>,
+    Query(query): Query<OhlcQuery>,
+) -> Json<Value> {
+    if !KRAKEN.lists(&query.pair) {
+        return Json(json!({ "error": ["EQuery:Unknown asset pair"] }));
+    }
+    let (candles, last) = with_market(&simulator, |market, _| {
+        let candles = match query.since {
+            Some(since) => market.candles_since(since),
+            None => {
+                let mut recent = market.recent(720);
+                recent.reverse();
+                recent
+            }
+        };
+        let last = market.recent(1).first().map_or(0, |c| c.open_time);
+        (candles, last)
+    });
+    let rows: Vec<Value> = candles
+        .iter()
+        .map(|c| kraken_ohlc_row(&c.with_basis(KRAKEN.basis_bps)))
+        .collect();
+    Json(json!({
+        "error": [],
+        "result": { KRAKEN_RESULT_KEY: rows, "last": last }
+    }))
+}
+
+async fn kraken_ws(State(simulator): State<SharedSimulator>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |mut socket| async move {
+        let Some(request) = next_json(&mut socket).await else {
+            return;
+        };
+        let pair = request["pair"][0].as_str().unwrap_or_default().to_string();
+        if !KRAKEN.lists(&pair) {
+            let error = json!({
+                "event": "subscriptionStatus",
+                "status": "error",
+                "errorMessage": "Currency pair not supported",
+                "pair": pair,
+            });
+            let _ = send_json(&mut socket, &error).await;
+            return;
+        }
+        let ack = json!({
+            "event": "subscriptionStatus",
+            "channelID": 42,
+            "channelName": "ohlc-1",
+            "pair": pair,
+            "status": "subscribed",
+            "subscription": { "name": "ohlc", "interval": 1 }
+        });
+        if send_json(&mut socket, &ack).await.is_err() {
+            return;
+        }
+        stream_ticks(socket, simulator, move |market, now| {
+            let factor = 1.0 + KRAKEN.basis_bps / 10_000.0;
+            let candle = market.forming().with_basis(KRAKEN.basis_bps);
+            let close = market.spot(now) * factor;
+            json!([
+                42,
+                [
+                    format!("{}.000000", now),
+                    format!("{}.000000", candle.open_time + 60),
+                    price(candle.open),
+                    price(candle.high.max(close)),
+                    price(candle.low.min(close)),
+                    price(close),
+                    price((candle.open + close) / 2.0),
+                    format!("{:.8}", candle.volume),
+                    100
+                ],
+                "ohlc-1",
+                pair
+            ])
+        })
+        .await
+    })
+}
+
+// ---------------------------------------------------------------------------
+// WebSocket 공통
+
+async fn send_json(socket: &mut WebSocket, value: &Value) -> Result<(), axum::Error> {
+    socket.send(Message::Text(value.to_string())).await
+}
+
+/// 첫 텍스트 메시지(구독 요청)를 JSON으로 파싱
+async fn next_json(socket: &mut WebSocket) -> Option<Value> {
+    while let Some(Ok(message)) = socket.recv().await {
+        match message {
+            Message::Text(text) => return serde_json::from_str(&text).ok(),
+            Message::Close(_) => return None,
+            _ => continue,
+        }
+    }
+    None
+}
+
+/// 연결이 끊길 때까지 매초 `tick` 결과 전송
+async fn stream_ticks(
+    mut socket: WebSocket,
+    simulator: SharedSimulator,
+    mut tick: impl FnMut(&PriceSimulator, u64) -> Value + Send,
+) {
+    info!("🔌 WebSocket subscriber connected");
+    let mut interval = tokio::time::interval(TICK_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let message = with_market(&simulator, &mut tick);
+                if send_json(&mut socket, &message).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(message)) => debug!("Ignoring client message: {:?}", message),
+            },
+        }
+    }
+    info!("🔌 WebSocket subscriber disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::price_path::{PathConfig, PathKind};
+
+    #[test]
+    fn test_response_shapes_match_exchange_clients() {
+        let candle = Candle {
+            open_time: 1_700_000_040,
+            open: 65_000.0,
+            high: 65_100.0,
+            low: 64_900.0,
+            close: 65_050.126,
+            volume: 12.5,
+        };
+
+        // 바이낸스 클라이언트: [0]/[6]은 정수 ms, [4]는 문자열 종가
+        let kline = binance_kline(&candle);
+        assert_eq!(kline.as_array().unwrap().len(), 12);
+        assert_eq!(kline[0].as_u64(), Some(1_700_000_040_000));
+        assert_eq!(kline[6].as_u64(), Some(1_700_000_099_999));
+        assert_eq!(kline[4].as_str(), Some("65050.13"));
+
+        // Kraken 클라이언트: (u64, String × 6, u32)
+        let row = kraken_ohlc_row(&candle.with_basis(-2.0));
+        assert_eq!(row[0].as_u64(), Some(1_700_000_040));
+        assert_eq!(row[4].as_str(), Some("65037.12"));
+        assert!(row[7].is_u64());
+
+        assert!(KRAKEN.lists("xbtusd"));
+        assert!(!BINANCE.lists("ETHUSDT"));
+    }
+
+    #[tokio::test]
+    async fn test_binance_klines_serves_requested_minute() {
+        let start = now() - 600;
+        let simulator = Arc::new(Mutex::new(PriceSimulator::new(
+            PathConfig {
+                kind: PathKind::Trend { drift_bps: 1.0 },
+                start_price: 65_000.0,
+                volatility_bps: 5.0,
+                seed: 1,
+            },
+            start,
+        )));
+        let target = with_market(&simulator, |market, _| market.recent(2)[1]);
+        let query = KlineQuery {
+            symbol: "BTCUSDT".to_string(),
+            start_time: Some(target.open_time * 1000),
+            end_time: Some((target.open_time + 60) * 1000),
+            limit: Some(1),
+        };
+        let response = binance_klines(State(simulator.clone()), Query(query)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let invalid = KlineQuery {
+            symbol: "DOGEUSDT".to_string(),
+            start_time: None,
+            end_time: None,
+            limit: None,
+        };
+        let response = binance_klines(State(simulator), Query(invalid)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
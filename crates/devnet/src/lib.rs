@@ -0,0 +1,5 @@
+//! devnet 라이브러리 (바이너리와 통합 테스트가 공유하는 가짜 거래소/가격 경로)
+
+pub mod exchanges;
+pub mod mock_aggregator;
+pub mod price_path;
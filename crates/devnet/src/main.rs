@@ -0,0 +1,162 @@
+//! 로컬 개발용 devnet
+//!
+//! 실제 거래소와 Aggregator 없이 전체 스택을 띄우기 위한 바이너리입니다.
+//! 시드 기반 가격 경로로 가짜 Binance/Coinbase/Kraken REST + WebSocket을
+//! 제공하고, mock Aggregator gRPC 서버를 함께 실행합니다.
+//!
+//! ```bash
+//! cargo run -p devnet -- --path jump --seed 7
+//! cargo run -p oracle-node -- --dev --config config/devnet-node.toml --exchange binance
+//! ```
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use oracle_vm_common::Shutdown;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tonic::transport::Server;
+use tracing::info;
+
+use devnet::exchanges;
+use devnet::mock_aggregator::oracle::oracle_service_server::OracleServiceServer;
+use devnet::mock_aggregator::MockAggregator;
+use devnet::price_path::{PathConfig, PathKind, PriceSimulator};
+
+/// 가격 경로 종류 (CLI)
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PathArg {
+    Trend,
+    Jump,
+    MeanRevert,
+}
+
+/// devnet CLI 인수
+#[derive(Parser)]
+#[command(name = "devnet")]
+#[command(about = "Simulated exchanges and mock aggregator for local development")]
+struct Args {
+    /// 가짜 거래소 HTTP/WebSocket 주소
+    #[arg(long, default_value = "127.0.0.1:8900")]
+    listen: SocketAddr,
+
+    /// mock Aggregator gRPC 주소
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    aggregator_listen: SocketAddr,
+
+    /// mock Aggregator를 띄우지 않음 (실제 Aggregator를 붙일 때)
+    #[arg(long)]
+    no_aggregator: bool,
+
+    /// 가격 경로
+    #[arg(long, value_enum, default_value = "trend")]
+    path: PathArg,
+
+    /// 시작 가격 (USD)
+    #[arg(long, default_value = "65000")]
+    start_price: f64,
+
+    /// 분당 무작위 변동 표준편차 (bps)
+    #[arg(long, default_value = "8")]
+    volatility_bps: f64,
+
+    /// trend: 분당 추세 (bps)
+    #[arg(long, default_value = "1")]
+    drift_bps: f64,
+
+    /// jump: 급변 주기 (분)
+    #[arg(long, default_value = "15")]
+    jump_every: u64,
+
+    /// jump: 급변 크기 (bps)
+    #[arg(long, default_value = "300")]
+    jump_bps: f64,
+
+    /// mean-revert: 회귀 가격 (기본: 시작 가격)
+    #[arg(long)]
+    mean_price: Option<f64>,
+
+    /// mean-revert: 분당 회귀 비율
+    #[arg(long, default_value = "0.1")]
+    reversion: f64,
+
+    /// 난수 시드 (같은 시드면 같은 가격 경로)
+    #[arg(long, default_value = "42")]
+    seed: u64,
+
+    /// 시뮬레이션 시작 전 미리 생성할 과거 분봉 수
+    #[arg(long, default_value = "60")]
+    history_mins: u64,
+}
+
+impl Args {
+    fn path_config(&self) -> PathConfig {
+        let kind = match self.path {
+            PathArg::Trend => PathKind::Trend {
+                drift_bps: self.drift_bps,
+            },
+            PathArg::Jump => PathKind::Jump {
+                every_mins: self.jump_every,
+                jump_bps: self.jump_bps,
+            },
+            PathArg::MeanRevert => PathKind::MeanRevert {
+                mean: self.mean_price.unwrap_or(self.start_price),
+                speed: self.reversion,
+            },
+        };
+        PathConfig {
+            kind,
+            start_price: self.start_price,
+            volatility_bps: self.volatility_bps,
+            seed: self.seed,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+
+    // 과거 분봉을 미리 만들어 두어 첫 요청부터 직전 분봉을 돌려줌
+    let now = chrono::Utc::now().timestamp() as u64;
+    let config = args.path_config();
+    info!("📈 Price path: {:?} (seed {})", config.kind, config.seed);
+    let mut simulator = PriceSimulator::new(config, now - args.history_mins * 60);
+    simulator.advance_to(now);
+    let simulator = Arc::new(Mutex::new(simulator));
+
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals();
+
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    info!("🏦 Simulated exchanges on http://{}", args.listen);
+    for exchange in ["binance", "coinbase", "kraken"] {
+        info!("   [exchanges.{}] base_url = \"http://{}/{}\"", exchange, args.listen, exchange);
+    }
+    let mut signal = shutdown.signal();
+    let exchanges = tokio::spawn(async move {
+        axum::serve(listener, exchanges::router(simulator))
+            .with_graceful_shutdown(async move { signal.recv().await })
+            .await
+    });
+
+    if args.no_aggregator {
+        exchanges.await??;
+        return Ok(());
+    }
+
+    info!("🧪 Mock aggregator on {}", args.aggregator_listen);
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(oracle_vm_proto::FILE_DESCRIPTOR_SET)
+        .build_v1()?;
+    let mut signal = shutdown.signal();
+    Server::builder()
+        .add_service(reflection)
+        .add_service(OracleServiceServer::new(MockAggregator::new()))
+        .serve_with_shutdown(args.aggregator_listen, async move { signal.recv().await })
+        .await?;
+    exchanges.await??;
+
+    info!("👋 Devnet stopped");
+    Ok(())
+}
@@ -0,0 +1,443 @@
+//! 로컬 개발용 mock Aggregator
+//!
+//! 실제 Aggregator와 같은 gRPC 서비스를 제공하지만 서명/nonce 검증, 평판,
+//! 원장 없이 거래소별 최신 제출의 중앙값만 돌려줍니다. 리스는 항상 요청
+//! 노드에 부여하고, 거래는 중단되지 않습니다. 합의 규칙 자체를 시험하려면
+//! devnet 거래소에 실제 Aggregator를 붙이면 됩니다.
+
+use chrono::Utc;
+use futures::Stream;
+use oracle_vm_common::price::{cents_from_dollars, cents_to_dollars, format_cents, Rounding};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Mutex;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+pub use oracle_vm_proto::oracle;
+
+use oracle::oracle_service_server::OracleService;
+use oracle::{
+    AggregatedPriceUpdate, ClearQuarantineRequest, ConfigRequest, ConfigResponse,
+    ConsensusProofRequest, ConsensusProofResponse, DailyCommitmentRequest, DailyCommitmentResponse, ExchangeReputationRequest,
+    ExchangeReputationResponse, GetPriceRequest, GetPriceResponse, GetVolSurfaceRequest,
+    GetVolSurfaceResponse, HealthRequest, HealthResponse, LeaseRequest, LeaseResponse,
+    PriceDataPoint, PriceRequest, PriceResponse, RegisterNodeRequest, RegisterNodeResponse,
+    ReloadConsensusConfigRequest, ReloadConsensusConfigResponse, ReplicationStateRequest,
+    ReplicationStateResponse, ResumeTradingRequest,
+    SubmissionQueryRequest, SubmissionQueryResponse, ThresholdAttestationRequest,
+    ThresholdAttestationResponse, ThresholdCommitRequest, ThresholdCommitResponse,
+    ThresholdSignRequest, ThresholdSignResponse, TradingStatusRequest, TradingStatusResponse,
+    VolSurfaceRequest, VolSurfaceResponse,
+};
+
+/// 집계에 쓰는 제출 유효 시간 (실제 Aggregator와 동일)
+const PRICE_WINDOW_SECS: u64 = 120;
+
+/// 거래소별 최신 제출
+#[derive(Debug, Clone)]
+struct Submitted {
+    price_cents: u64,
+    timestamp: u64,
+    node_id: String,
+    received_at: u64,
+}
+
+#[derive(Default)]
+pub struct MockAggregator {
+    prices: Mutex<HashMap<String, Submitted>>,
+    nonces: Mutex<HashMap<String, u64>>,
+    surface: Mutex<Option<VolSurfaceRequest>>,
+}
+
+fn now() -> u64 {
+    Utc::now().timestamp() as u64
+}
+
+impl MockAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 유효 시간 안의 거래소별 최신 가격 중앙값 (USD 센트)
+    fn median_cents(&self, now: u64) -> Option<u64> {
+        let prices = self.prices.lock().unwrap();
+        let mut recent: Vec<u64> = prices
+            .values()
+            .filter(|p| now.saturating_sub(p.received_at) <= PRICE_WINDOW_SECS)
+            .map(|p| p.price_cents)
+            .collect();
+        recent.sort_unstable();
+        match recent.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(recent[n / 2]),
+            n => Some((recent[n / 2 - 1] + recent[n / 2]) / 2),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl OracleService for MockAggregator {
+    type StreamPricesStream =
+        Pin<Box<dyn Stream<Item = Result<AggregatedPriceUpdate, Status>> + Send>>;
+
+    async fn register_node(
+        &self,
+        request: Request<RegisterNodeRequest>,
+    ) -> Result<Response<RegisterNodeResponse>, Status> {
+        let request = request.into_inner();
+        let last_nonce = *self
+            .nonces
+            .lock()
+            .unwrap()
+            .entry(request.node_id.clone())
+            .or_default();
+        info!("🔑 [mock] Registered node {} (signatures not verified)", request.node_id);
+        Ok(Response::new(RegisterNodeResponse {
+            success: true,
+            message: "Registered with devnet mock aggregator".to_string(),
+            last_nonce,
+        }))
+    }
+
+    async fn submit_price(
+        &self,
+        request: Request<PriceRequest>,
+    ) -> Result<Response<PriceResponse>, Status> {
+        let request = request.into_inner();
+        let price_cents = if request.price_cents > 0 {
+            request.price_cents
+        } else {
+            cents_from_dollars(request.price, Rounding::HalfEven).unwrap_or(0)
+        };
+        if price_cents == 0 {
+            return Ok(Response::new(PriceResponse {
+                success: false,
+                message: "Price must be positive".to_string(),
+                aggregated_price: None,
+                timestamp: now(),
+                aggregated_price_cents: None,
+            }));
+        }
+
+        let now = now();
+        self.nonces
+            .lock()
+            .unwrap()
+            .insert(request.node_id.clone(), request.nonce);
+        // 백필 제출은 보관할 원장이 없으므로 받기만 함
+        if request.backfilled {
+            return Ok(Response::new(PriceResponse {
+                success: true,
+                message: "Backfill ignored by devnet mock aggregator".to_string(),
+                aggregated_price: None,
+                timestamp: now,
+                aggregated_price_cents: None,
+            }));
+        }
+        self.prices.lock().unwrap().insert(
+            request.source.clone(),
+            Submitted {
+                price_cents,
+                timestamp: request.timestamp,
+                node_id: request.node_id.clone(),
+                received_at: now,
+            },
+        );
+        let aggregated = self.median_cents(now);
+        info!(
+            "📨 [mock] {} from {}: ${} (median ${})",
+            request.node_id,
+            request.source,
+            format_cents(price_cents),
+            aggregated.map(format_cents).unwrap_or_default()
+        );
+
+        Ok(Response::new(PriceResponse {
+            success: true,
+            message: "Price data received".to_string(),
+            aggregated_price: aggregated.map(cents_to_dollars),
+            timestamp: now,
+            aggregated_price_cents: aggregated,
+        }))
+    }
+
+    async fn stream_prices(
+        &self,
+        _request: Request<tonic::Streaming<PriceRequest>>,
+    ) -> Result<Response<Self::StreamPricesStream>, Status> {
+        Err(Status::unimplemented("Stream prices not implemented yet"))
+    }
+
+    async fn health_check(
+        &self,
+        _request: Request<HealthRequest>,
+    ) -> Result<Response<HealthResponse>, Status> {
+        let now = now();
+        let active_nodes = self
+            .prices
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|p| now.saturating_sub(p.received_at) <= PRICE_WINDOW_SECS)
+            .map(|p| p.node_id.as_str())
+            .collect::<std::collections::HashSet<_>>()
+            .len() as u32;
+        Ok(Response::new(HealthResponse {
+            healthy: true,
+            timestamp: now,
+            active_nodes,
+            version: "devnet".to_string(),
+        }))
+    }
+
+    async fn update_config(
+        &self,
+        _request: Request<ConfigRequest>,
+    ) -> Result<Response<ConfigResponse>, Status> {
+        Ok(Response::new(ConfigResponse {
+            success: true,
+            message: "Ignored by devnet mock aggregator".to_string(),
+        }))
+    }
+
+    async fn get_aggregated_price(
+        &self,
+        _request: Request<GetPriceRequest>,
+    ) -> Result<Response<GetPriceResponse>, Status> {
+        let now = now();
+        let Some(aggregated) = self.median_cents(now) else {
+            return Ok(Response::new(GetPriceResponse {
+                success: false,
+                aggregated_price: 0.0,
+                data_points: 0,
+                last_update: 0,
+                recent_prices: vec![],
+                aggregated_price_cents: 0,
+            }));
+        };
+        let prices = self.prices.lock().unwrap();
+        let recent_prices: Vec<PriceDataPoint> = prices
+            .iter()
+            .map(|(source, p)| PriceDataPoint {
+                price: cents_to_dollars(p.price_cents),
+                timestamp: p.timestamp,
+                source: source.clone(),
+                node_id: p.node_id.clone(),
+                price_cents: p.price_cents,
+            })
+            .collect();
+        Ok(Response::new(GetPriceResponse {
+            success: true,
+            aggregated_price: cents_to_dollars(aggregated),
+            data_points: recent_prices.len() as u32,
+            last_update: prices.values().map(|p| p.received_at).max().unwrap_or(0),
+            recent_prices,
+            aggregated_price_cents: aggregated,
+        }))
+    }
+
+    async fn submit_vol_surface(
+        &self,
+        request: Request<VolSurfaceRequest>,
+    ) -> Result<Response<VolSurfaceResponse>, Status> {
+        let request = request.into_inner();
+        info!(
+            "📨 [mock] IV surface: {} points from {}",
+            request.points.len(),
+            request.source
+        );
+        *self.surface.lock().unwrap() = Some(request);
+        Ok(Response::new(VolSurfaceResponse {
+            success: true,
+            message: "IV surface received".to_string(),
+            timestamp: now(),
+        }))
+    }
+
+    async fn get_vol_surface(
+        &self,
+        _request: Request<GetVolSurfaceRequest>,
+    ) -> Result<Response<GetVolSurfaceResponse>, Status> {
+        Ok(Response::new(match self.surface.lock().unwrap().clone() {
+            Some(surface) => GetVolSurfaceResponse {
+                success: true,
+                source: surface.source,
+                timestamp: surface.timestamp,
+                underlying_price: surface.underlying_price,
+                points: surface.points,
+            },
+            None => GetVolSurfaceResponse {
+                success: false,
+                source: String::new(),
+                timestamp: 0,
+                underlying_price: 0.0,
+                points: vec![],
+            },
+        }))
+    }
+
+    async fn get_trading_status(
+        &self,
+        _request: Request<TradingStatusRequest>,
+    ) -> Result<Response<TradingStatusResponse>, Status> {
+        Ok(Response::new(TradingStatusResponse {
+            halted: false,
+            reason: String::new(),
+            halted_at: 0,
+            resume_at: None,
+        }))
+    }
+
+    async fn resume_trading(
+        &self,
+        request: Request<ResumeTradingRequest>,
+    ) -> Result<Response<TradingStatusResponse>, Status> {
+        info!("▶️ [mock] Resume requested by {}", request.into_inner().operator);
+        self.get_trading_status(Request::new(TradingStatusRequest {}))
+            .await
+    }
+
+    async fn reload_consensus_config(
+        &self,
+        _request: Request<ReloadConsensusConfigRequest>,
+    ) -> Result<Response<ReloadConsensusConfigResponse>, Status> {
+        Ok(Response::new(ReloadConsensusConfigResponse {
+            success: true,
+            message: "Devnet mock aggregator uses a plain median".to_string(),
+            min_consensus_ratio: 0.0,
+            max_price_deviation: 0.0,
+        }))
+    }
+
+    async fn list_exchange_reputation(
+        &self,
+        _request: Request<ExchangeReputationRequest>,
+    ) -> Result<Response<ExchangeReputationResponse>, Status> {
+        Ok(Response::new(ExchangeReputationResponse { exchanges: vec![] }))
+    }
+
+    async fn clear_quarantine(
+        &self,
+        _request: Request<ClearQuarantineRequest>,
+    ) -> Result<Response<ExchangeReputationResponse>, Status> {
+        Ok(Response::new(ExchangeReputationResponse { exchanges: vec![] }))
+    }
+
+    async fn acquire_lease(
+        &self,
+        request: Request<LeaseRequest>,
+    ) -> Result<Response<LeaseResponse>, Status> {
+        let request = request.into_inner();
+        Ok(Response::new(LeaseResponse {
+            granted: true,
+            holder: request.node_id,
+            expires_at: now().saturating_add(request.ttl_secs),
+        }))
+    }
+
+    async fn query_submissions(
+        &self,
+        _request: Request<SubmissionQueryRequest>,
+    ) -> Result<Response<SubmissionQueryResponse>, Status> {
+        Err(Status::unimplemented(
+            "Submission ledger is not kept by the devnet mock aggregator",
+        ))
+    }
+
+    async fn get_daily_commitment(
+        &self,
+        _request: Request<DailyCommitmentRequest>,
+    ) -> Result<Response<DailyCommitmentResponse>, Status> {
+        Err(Status::unimplemented(
+            "Daily commitments are not kept by the devnet mock aggregator",
+        ))
+    }
+
+    async fn get_consensus_proof(
+        &self,
+        _request: Request<ConsensusProofRequest>,
+    ) -> Result<Response<ConsensusProofResponse>, Status> {
+        Err(Status::unimplemented(
+            "Consensus proofs are not signed by the devnet mock aggregator",
+        ))
+    }
+
+    async fn threshold_commit(
+        &self,
+        _request: Request<ThresholdCommitRequest>,
+    ) -> Result<Response<ThresholdCommitResponse>, Status> {
+        Err(Status::unimplemented(
+            "Threshold signing is not coordinated by the devnet mock aggregator",
+        ))
+    }
+
+    async fn threshold_sign(
+        &self,
+        _request: Request<ThresholdSignRequest>,
+    ) -> Result<Response<ThresholdSignResponse>, Status> {
+        Err(Status::unimplemented(
+            "Threshold signing is not coordinated by the devnet mock aggregator",
+        ))
+    }
+
+    async fn get_threshold_attestation(
+        &self,
+        _request: Request<ThresholdAttestationRequest>,
+    ) -> Result<Response<ThresholdAttestationResponse>, Status> {
+        Err(Status::unimplemented(
+            "Threshold signing is not coordinated by the devnet mock aggregator",
+        ))
+    }
+
+    async fn get_replication_state(
+        &self,
+        _request: Request<ReplicationStateRequest>,
+    ) -> Result<Response<ReplicationStateResponse>, Status> {
+        Err(Status::unimplemented(
+            "The devnet mock aggregator has no standby to replicate to",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(source: &str, price_cents: u64) -> Request<PriceRequest> {
+        Request::new(PriceRequest {
+            price: cents_to_dollars(price_cents),
+            timestamp: now(),
+            source: source.to_string(),
+            node_id: format!("node-{}", source),
+            signature: None,
+            nonce: 1,
+            degraded: false,
+            price_cents,
+            backfilled: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_median_of_latest_per_exchange() {
+        let mock = MockAggregator::new();
+        mock.submit_price(price("binance", 6_500_300)).await.unwrap();
+        mock.submit_price(price("kraken", 6_499_900)).await.unwrap();
+        let response = mock
+            .submit_price(price("coinbase", 6_500_000))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.aggregated_price_cents, Some(6_500_000));
+
+        // 같은 거래소의 새 제출은 이전 값을 대체
+        mock.submit_price(price("binance", 6_600_000)).await.unwrap();
+        let response = mock
+            .get_aggregated_price(Request::new(GetPriceRequest { source_filter: None }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.aggregated_price_cents, 6_500_000);
+        assert_eq!(response.recent_prices.len(), 3);
+    }
+}
@@ -0,0 +1,240 @@
+//! 시드 기반 가격 경로 시뮬레이터
+//!
+//! 분 단위로 1분봉을 생성합니다. 같은 시드와 시작 분이면 항상 같은 캔들 열이
+//! 나오므로, 합의 실패나 급변 처리 같은 시나리오를 그대로 재현할 수 있습니다.
+//! 진행 중인 분의 현재가는 직전 종가와 다음 종가 사이를 선형 보간합니다.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+
+/// 보관하는 완성 분봉 수 (6시간)
+const MAX_CANDLES: usize = 360;
+
+/// 가격 경로 종류
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathKind {
+    /// 분당 일정한 추세 (bps)
+    Trend { drift_bps: f64 },
+    /// `every_mins`분마다 `jump_bps`만큼 급변 (방향은 무작위)
+    Jump { every_mins: u64, jump_bps: f64 },
+    /// `mean`으로 분당 `speed` 비율만큼 회귀
+    MeanRevert { mean: f64, speed: f64 },
+}
+
+/// 시뮬레이터 설정
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathConfig {
+    pub kind: PathKind,
+    /// 시작 가격 (USD)
+    pub start_price: f64,
+    /// 분당 무작위 변동 표준편차 (bps)
+    pub volatility_bps: f64,
+    pub seed: u64,
+}
+
+/// 1분봉 (가격은 USD)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    /// 분 시작 시각 (Unix 초, 60의 배수)
+    pub open_time: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl Candle {
+    /// 거래소별 베이시스 적용 (bps)
+    pub fn with_basis(&self, basis_bps: f64) -> Self {
+        let factor = 1.0 + basis_bps / 10_000.0;
+        Self {
+            open: self.open * factor,
+            high: self.high * factor,
+            low: self.low * factor,
+            close: self.close * factor,
+            ..*self
+        }
+    }
+}
+
+/// 분봉 생성기
+pub struct PriceSimulator {
+    config: PathConfig,
+    rng: StdRng,
+    candles: VecDeque<Candle>,
+    /// 다음에 생성할 분봉 (진행 중인 분)
+    forming: Candle,
+    /// 시작 이후 생성한 분봉 수 (급변 주기 계산용)
+    generated: u64,
+}
+
+impl PriceSimulator {
+    /// `start_time`이 속한 분부터 시작
+    pub fn new(config: PathConfig, start_time: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let open_time = start_time - start_time % 60;
+        let forming = next_candle(&config, &mut rng, open_time, config.start_price, 0);
+        Self {
+            config,
+            rng,
+            candles: VecDeque::new(),
+            forming,
+            generated: 1,
+        }
+    }
+
+    /// `now`까지 완성된 분봉 생성
+    pub fn advance_to(&mut self, now: u64) {
+        while self.forming.open_time + 60 <= now {
+            let done = self.forming;
+            self.candles.push_back(done);
+            if self.candles.len() > MAX_CANDLES {
+                self.candles.pop_front();
+            }
+            self.forming = next_candle(
+                &self.config,
+                &mut self.rng,
+                done.open_time + 60,
+                done.close,
+                self.generated,
+            );
+            self.generated += 1;
+        }
+    }
+
+    /// `since` 이후 시작한 완성 분봉 (오래된 순)
+    pub fn candles_since(&self, since: u64) -> Vec<Candle> {
+        self.candles.iter().filter(|c| c.open_time >= since).copied().collect()
+    }
+
+    /// 최근 완성 분봉 `limit`개 (최신 순)
+    pub fn recent(&self, limit: usize) -> Vec<Candle> {
+        self.candles.iter().rev().take(limit).copied().collect()
+    }
+
+    /// 진행 중인 분의 현재가 (직전 종가 → 이번 분 종가 선형 보간)
+    pub fn spot(&self, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(self.forming.open_time).min(60) as f64 / 60.0;
+        self.forming.open + (self.forming.close - self.forming.open) * elapsed
+    }
+
+    /// 진행 중인 분봉
+    pub fn forming(&self) -> Candle {
+        self.forming
+    }
+}
+
+/// 표준정규 난수 (Box-Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn next_candle(
+    config: &PathConfig,
+    rng: &mut StdRng,
+    open_time: u64,
+    open: f64,
+    index: u64,
+) -> Candle {
+    let noise = standard_normal(rng) * config.volatility_bps / 10_000.0;
+    let drift = match config.kind {
+        PathKind::Trend { drift_bps } => drift_bps / 10_000.0,
+        PathKind::Jump {
+            every_mins,
+            jump_bps,
+        } if every_mins > 0 && index > 0 && index.is_multiple_of(every_mins) => {
+            let sign = if rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+            sign * jump_bps / 10_000.0
+        }
+        PathKind::Jump { .. } => 0.0,
+        PathKind::MeanRevert { mean, speed } => speed * (mean - open) / open,
+    };
+    let close = (open * (1.0 + drift + noise)).max(1.0);
+
+    // 꼬리는 변동성의 절반 범위에서 무작위
+    let wick = config.volatility_bps / 20_000.0;
+    let high = open.max(close) * (1.0 + rng.gen_range(0.0..=wick));
+    let low = open.min(close) * (1.0 - rng.gen_range(0.0..=wick));
+    let volume = rng.gen_range(5.0..50.0);
+
+    Candle {
+        open_time,
+        open,
+        high,
+        low,
+        close,
+        volume,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(kind: PathKind) -> PathConfig {
+        PathConfig {
+            kind,
+            start_price: 65_000.0,
+            volatility_bps: 5.0,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_path() {
+        let start = 1_700_000_000;
+        let mut a = PriceSimulator::new(config(PathKind::Trend { drift_bps: 2.0 }), start);
+        let mut b = PriceSimulator::new(config(PathKind::Trend { drift_bps: 2.0 }), start);
+        a.advance_to(start + 600);
+        b.advance_to(start + 300);
+        b.advance_to(start + 600);
+
+        assert_eq!(a.recent(10), b.recent(10));
+        let first = a.candles_since(0)[0];
+        assert_eq!(first.open_time, start - start % 60);
+        assert_eq!(first.open, 65_000.0);
+        assert!(first.low <= first.open.min(first.close));
+        assert!(first.high >= first.open.max(first.close));
+        assert_eq!(a.candles_since(first.open_time + 60)[0].open, first.close);
+    }
+
+    #[test]
+    fn test_path_shapes() {
+        let start = 1_700_000_040;
+        let mut trend = PriceSimulator::new(config(PathKind::Trend { drift_bps: 20.0 }), start);
+        trend.advance_to(start + 60 * 60);
+        assert!(trend.recent(1)[0].close > 65_000.0 * 1.05);
+
+        let mut revert = PriceSimulator::new(
+            config(PathKind::MeanRevert {
+                mean: 60_000.0,
+                speed: 0.2,
+            }),
+            start,
+        );
+        revert.advance_to(start + 60 * 60);
+        assert!((revert.recent(1)[0].close - 60_000.0).abs() < 600.0);
+
+        let mut jump = PriceSimulator::new(
+            config(PathKind::Jump {
+                every_mins: 5,
+                jump_bps: 500.0,
+            }),
+            start,
+        );
+        jump.advance_to(start + 60 * 10);
+        let candles = jump.candles_since(0);
+        let moves: Vec<f64> = candles.iter().map(|c| (c.close / c.open - 1.0).abs()).collect();
+        assert!(moves[5] > 0.04);
+        assert!(moves[4] < 0.01);
+
+        // 진행 중인 분의 현재가는 시작가에서 종가로 이동
+        let forming = jump.forming();
+        assert_eq!(jump.spot(forming.open_time), forming.open);
+        assert_eq!(jump.spot(forming.open_time + 60), forming.close);
+    }
+}
@@ -0,0 +1,6 @@
+//! 워크스페이스 교차 모듈 통합 테스트 (`tests/`, `e2e` 기능으로 실행)
+//!
+//! 가짜 거래소, Oracle Node 수집 클라이언트, Aggregator 합의, Calculation 호가,
+//! Contracts 정산을 한 프로세스에서 연결해 가격 틱부터 정산 지급까지의 금액을
+//! 정확히 검증합니다. 크레이트 경계에서 단위(달러/센트/satoshi)가 어긋나면
+//! 지급액이 바뀌어 실패합니다.
@@ -0,0 +1,261 @@
+//! 가격 틱 → 정산 지급 교차 모듈 테스트
+//!
+//! 1. devnet 가짜 거래소(Binance/Coinbase/Kraken)를 로컬 포트에 띄우고 평탄한
+//!    가격 경로를 단계별로 바꿔 가며 가격을 냅니다 (거래소별 베이시스 적용).
+//! 2. Oracle Node 거래소 클라이언트가 `base_url`로 직전 분봉 종가를 수집해 센트로 바꿉니다.
+//! 3. Aggregator 합의 계산이 거래소별 센트 가격의 가중 평균을 냅니다.
+//! 4. Calculation 호가 서비스가 합의 가격(달러)으로 서명된 확정 호가(satoshis)를 냅니다.
+//! 5. Contracts 관리자가 호가를 체결하고, 만기 높이에서 합의 가격(센트)으로 정산합니다.
+//!
+//! ```bash
+//! cargo test -p oracle-vm-integration --features e2e
+//! ```
+
+use aggregator::consensus::{consensus_price, ExchangePrice};
+use btcfi_calculation::{
+    BlackScholesPricing, InMemoryMarketRepo, MarketDataRepository, MarketState, OptionParameters,
+    PricingEngine, QuoteService,
+};
+use btcfi_contracts::block_time::BlockClock;
+use btcfi_contracts::simple_contract::{OptionStatus, SimpleContractManager};
+use devnet::exchanges;
+use devnet::price_path::{PathConfig, PathKind, PriceSimulator};
+use oracle_node::binance::BinanceClient;
+use oracle_node::coinbase::CoinbaseClient;
+use oracle_node::exchange_auth::{ExchangeAccess, RateLimiters};
+use oracle_node::kraken::KrakenClient;
+use oracle_vm_common::config::{ConsensusParams, ExchangeConfig, ExchangesConfig};
+use oracle_vm_common::crypto::generate_keypair;
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::{
+    ExerciseStyle, ExpiryCalendar, ManualClock, OptionType, Payoff, QuoteRequest,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 2024-01-10 (수) 12:00 UTC: 호가/체결/정산 시각
+const NOW: u64 = 1_704_888_000;
+/// 2024-01-12 08:00 UTC 만기까지 44시간
+const EXPIRY: &str = "2024-01-12";
+const SECS_TO_EXPIRY: u64 = 44 * 3_600;
+const SECONDS_PER_YEAR: f64 = 365.0 * 86_400.0;
+const VOLATILITY: f64 = 0.6;
+const START_HEIGHT: u32 = 830_000;
+
+/// 변동 없이 `price`에 머무는 가격 경로 (1시간 분봉 이력 포함)
+fn flat_path(price: f64) -> PriceSimulator {
+    let now = wall_clock();
+    let config = PathConfig {
+        kind: PathKind::Trend { drift_bps: 0.0 },
+        start_price: price,
+        volatility_bps: 0.0,
+        seed: 7,
+    };
+    let mut simulator = PriceSimulator::new(config, now - 3_600);
+    simulator.advance_to(now);
+    simulator
+}
+
+fn wall_clock() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// 가짜 거래소와 그 주소를 쓰는 Oracle Node 클라이언트
+struct Exchanges {
+    simulator: Arc<Mutex<PriceSimulator>>,
+    binance: BinanceClient,
+    coinbase: CoinbaseClient,
+    kraken: KrakenClient,
+}
+
+impl Exchanges {
+    async fn start(price: f64) -> Self {
+        let simulator = Arc::new(Mutex::new(flat_path(price)));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = exchanges::router(simulator.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = ExchangesConfig {
+            exchanges: ["binance", "coinbase", "kraken"]
+                .into_iter()
+                .map(|name| {
+                    let settings = ExchangeConfig {
+                        base_url: Some(format!("http://{}/{}", addr, name)),
+                        ..ExchangeConfig::default()
+                    };
+                    (name.to_string(), settings)
+                })
+                .collect(),
+        };
+        let limiters = RateLimiters::new();
+        let access = |name: &str| ExchangeAccess::from_config(name, &config, &limiters);
+        Self {
+            simulator,
+            binance: BinanceClient::new().with_access(access("binance")),
+            coinbase: CoinbaseClient::new().with_access(access("coinbase")),
+            kraken: KrakenClient::new().with_access(access("kraken")),
+        }
+    }
+
+    /// 가격 경로의 다음 단계
+    fn move_to(&self, price: f64) {
+        *self.simulator.lock().unwrap() = flat_path(price);
+    }
+
+    /// 노드가 거래소별로 수집한 가격
+    async fn collect(&self) -> Vec<PriceData> {
+        vec![
+            self.binance.fetch_btc_price().await.unwrap(),
+            self.coinbase.fetch_btc_price().await.unwrap(),
+            self.kraken.fetch_btc_price().await.unwrap(),
+        ]
+    }
+}
+
+/// 노드 제출 가격의 합의 가격 (USD 센트)
+///
+/// Coinbase는 직전 분봉 시작 시각을, 나머지는 수집 시각을 보고하므로 분 단위로 맞춰
+/// 수집 시점에 따라 시각 차이 검사 결과가 달라지지 않게 합니다.
+fn consensus(prices: &[PriceData]) -> u64 {
+    let latest: HashMap<String, ExchangePrice> = prices
+        .iter()
+        .map(|data| {
+            let price = ExchangePrice {
+                price_cents: data.price,
+                timestamp: data.timestamp.timestamp() as u64 / 60 * 60,
+                degraded: data.degraded,
+            };
+            (data.source.clone(), price)
+        })
+        .collect();
+    consensus_price(&latest, &ConsensusParams::default()).unwrap()
+}
+
+fn quote_request(option_type: OptionType, strike_price: u64, quantity: u64) -> QuoteRequest {
+    QuoteRequest {
+        option_type,
+        strike_price,
+        expiry: EXPIRY.to_string(),
+        quantity,
+        otc: false,
+        referral_code: None,
+        tenant_id: None,
+        allow_partial: false,
+        style: ExerciseStyle::European,
+        barrier: None,
+        payoff: Payoff::Vanilla,
+    }
+}
+
+/// 호가와 같은 입력으로 직접 계산한 프리미엄 (satoshis)
+fn expected_premium(spot_cents: u64, option_type: OptionType, strike_price: u64, quantity: u64) -> u64 {
+    let spot = spot_cents as f64 / 100.0;
+    let usd = BlackScholesPricing::new().calculate_option_price(&OptionParameters {
+        spot,
+        strike: strike_price as f64 / 100.0,
+        time_to_expiry: SECS_TO_EXPIRY as f64 / SECONDS_PER_YEAR,
+        volatility: VOLATILITY,
+        risk_free_rate: 0.05,
+        is_call: option_type == OptionType::Call,
+    });
+    (usd / spot * quantity as f64).round() as u64
+}
+
+#[tokio::test]
+async fn test_price_tick_to_settlement_payout() {
+    let exchanges = Exchanges::start(65_000.0).await;
+
+    // 거래소별 베이시스: Binance +3bps, Coinbase 0, Kraken -2bps
+    let prices = exchanges.collect().await;
+    let cents: Vec<(String, u64)> = prices.iter().map(|p| (p.source.clone(), p.price)).collect();
+    assert_eq!(
+        cents,
+        vec![
+            ("binance".to_string(), 6_501_950),
+            ("coinbase".to_string(), 6_500_000),
+            ("kraken".to_string(), 6_498_700),
+        ]
+    );
+    // (6,501,950 + 6,500,000 + 6,498,700) / 3 = 6,500,216.67 → half-even
+    let spot_cents = consensus(&prices);
+    assert_eq!(spot_cents, 6_500_217);
+
+    // Calculation: 합의 가격(달러)으로 확정 호가 발행
+    let (signing_key, quote_key) = generate_keypair();
+    let market = Arc::new(InMemoryMarketRepo::new());
+    market
+        .update_state(MarketState::new(spot_cents as f64 / 100.0, VOLATILITY))
+        .await
+        .unwrap();
+    let quotes = QuoteService::new(BlackScholesPricing::new(), market, signing_key)
+        .with_calendar(ExpiryCalendar::default());
+    let call = quotes
+        .request_quote(&quote_request(OptionType::Call, 6_600_000, 10_000_000), NOW)
+        .await
+        .unwrap();
+    let put = quotes
+        .request_quote(&quote_request(OptionType::Put, 6_400_000, 20_000_000), NOW)
+        .await
+        .unwrap();
+    assert_eq!(call.spot_price, spot_cents);
+    assert_eq!(call.premium, expected_premium(spot_cents, OptionType::Call, 6_600_000, 10_000_000));
+    assert_eq!(put.premium, expected_premium(spot_cents, OptionType::Put, 6_400_000, 20_000_000));
+
+    // Contracts: 호가 체결 (만기 높이는 팁 헤더 시각에서 환산)
+    let clock = ManualClock::new(NOW);
+    let mut manager = SimpleContractManager::with_clock(clock.shared());
+    manager.require_quotes(quote_key);
+    manager.require_calendar(ExpiryCalendar::default());
+    manager.add_liquidity(100_000_000).unwrap();
+    let mut headers = BlockClock::new();
+    headers.observe(START_HEIGHT, NOW);
+    let expiry_height = headers.height_at(NOW + SECS_TO_EXPIRY).unwrap();
+    assert_eq!(expiry_height, START_HEIGHT + 264);
+    manager.set_block_clock(headers);
+    manager
+        .fill_quote(&call, call.quantity, "E2E-CALL".to_string(), expiry_height, "alice".to_string())
+        .unwrap();
+    manager
+        .fill_quote(&put, put.quantity, "E2E-PUT".to_string(), expiry_height, "bob".to_string())
+        .unwrap();
+
+    // 담보: 콜은 수량, 풋은 행사가(센트) × 수량(BTC)
+    let premiums = call.premium + put.premium;
+    assert_eq!(manager.pool_state.locked_collateral, 10_000_000 + 1_280_000);
+    assert_eq!(manager.pool_state.total_liquidity, 100_000_000 + premiums);
+
+    // 가격 경로: 만기 전 급등 후 만기 시점 71,000
+    exchanges.move_to(68_500.0);
+    assert_eq!(consensus(&exchanges.collect().await), 6_850_228);
+    exchanges.move_to(71_000.0);
+    let settlement_cents = consensus(&exchanges.collect().await);
+    // (7,102,130 + 7,100,000 + 7,098,580) / 3 = 7,100,236.67 → half-even
+    assert_eq!(settlement_cents, 7_100_237);
+
+    // 만기 높이에서 합의 가격으로 정산
+    clock.advance(SECS_TO_EXPIRY);
+    manager.observe_height(expiry_height);
+    let expired: Vec<String> = manager
+        .get_expired_options(expiry_height)
+        .into_iter()
+        .map(|option| option.option_id.clone())
+        .collect();
+    assert_eq!(expired.len(), 2);
+    let mut payouts = HashMap::new();
+    for option_id in expired {
+        let payout = manager.settle_option(&option_id, settlement_cents).unwrap();
+        payouts.insert(option_id, payout);
+    }
+
+    // 콜 내재가치 500,237 센트 × 0.1 BTC, 풋은 OTM
+    assert_eq!(payouts["E2E-CALL"], 50_023);
+    assert_eq!(payouts["E2E-PUT"], 0);
+    assert_eq!(manager.options["E2E-CALL"].status, OptionStatus::Settled);
+    assert_eq!(manager.pool_state.locked_collateral, 0);
+    assert_eq!(manager.pool_state.total_payout, 50_023);
+    assert_eq!(manager.pool_state.total_liquidity, 100_000_000 + premiums - 50_023);
+}
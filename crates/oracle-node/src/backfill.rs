@@ -0,0 +1,131 @@
+//! 중단 구간 가격 백필
+//!
+//! Oracle 스택이 멈춰 있던 동안에는 Aggregator 원장에 분 단위 구멍이 생깁니다.
+//! 재시작 시 지정한 구간에서 제출이 하나도 없는 분을 찾아, 거래소 REST API의
+//! 과거 1분봉 종가로 채워 `backfilled` 표시와 함께 제출합니다. 백필 제출은
+//! 실시간 제출과 다른 payload로 서명되고, 정산 증명에 쓸지는 Aggregator의
+//! 정산 정책(`--backfill-policy`)이 정합니다.
+//!
+//! 실시간 수집과 같은 기준으로, 분 `m`의 제출은 직전 분봉(`m - 60`에 시작)의
+//! 종가를 `m` 시각 가격으로 씁니다.
+
+use crate::grpc_client::GrpcAggregatorClient;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::DateTime;
+use oracle_vm_common::price::{cents_from_dollars, Rounding};
+use oracle_vm_common::types::{AssetPair, PriceData};
+use std::collections::{BTreeMap, HashSet};
+use tracing::{info, warn};
+
+/// 한 번에 요청하는 분봉 수 (Kraken OHLC 최대 720, Binance 최대 1000)
+pub const PAGE_MINUTES: u64 = 720;
+
+/// 거래소 과거 1분봉
+#[async_trait]
+pub trait KlineHistory: Send + Sync {
+    /// `[from, to)` 구간에 시작하는 1분봉 (시작 시각 → USD 종가)
+    async fn minute_closes(&self, from: u64, to: u64) -> Result<BTreeMap<u64, f64>>;
+
+    fn name(&self) -> &str;
+}
+
+/// `[from, to)` 구간에서 제출이 하나도 없는 분의 시작 시각
+pub fn missing_minutes(present: &[u64], from: u64, to: u64) -> Vec<u64> {
+    let covered: HashSet<u64> = present.iter().map(|timestamp| timestamp / 60).collect();
+    (from / 60..to.div_ceil(60))
+        .filter(|minute| !covered.contains(minute))
+        .map(|minute| minute * 60)
+        .filter(|&start| start >= from && start < to)
+        .collect()
+}
+
+/// 백필 결과
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BackfillReport {
+    /// 제출이 없던 분
+    pub missing: usize,
+    /// 채워서 제출한 분
+    pub submitted: usize,
+    /// 거래소에도 분봉이 없어 채우지 못한 분
+    pub unavailable: usize,
+}
+
+/// `[from, to)` 구간의 빈 분을 거래소 과거 분봉으로 채워 제출
+pub async fn backfill(
+    client: &mut GrpcAggregatorClient,
+    history: &dyn KlineHistory,
+    pair: &AssetPair,
+    from: u64,
+    to: u64,
+) -> Result<BackfillReport> {
+    let present = client.submitted_timestamps(history.name(), from, to).await?;
+    let missing = missing_minutes(&present, from, to);
+    let mut report = BackfillReport {
+        missing: missing.len(),
+        ..Default::default()
+    };
+    if missing.is_empty() {
+        return Ok(report);
+    }
+    info!(
+        "🧩 {} minutes without {} submissions between {} and {}",
+        missing.len(),
+        history.name(),
+        from,
+        to
+    );
+
+    for page in missing.chunk_by(|a, b| a / (PAGE_MINUTES * 60) == b / (PAGE_MINUTES * 60)) {
+        let (first, last) = (page[0], page[page.len() - 1]);
+        let closes = history.minute_closes(first - 60, last).await?;
+        for &minute in page {
+            let Some(&close) = closes.get(&(minute - 60)) else {
+                report.unavailable += 1;
+                continue;
+            };
+            let Some(price) = cents_from_dollars(close, Rounding::HalfEven) else {
+                report.unavailable += 1;
+                continue;
+            };
+            let price_data = PriceData {
+                pair: pair.clone(),
+                price,
+                timestamp: DateTime::from_timestamp(minute as i64, 0).unwrap_or_default(),
+                volume: None,
+                source: history.name().to_string(),
+                degraded: false,
+            };
+            match client.submit_backfill(&price_data).await {
+                Ok(()) => report.submitted += 1,
+                Err(e) => warn!("❌ Backfill of {} at {} rejected: {}", history.name(), minute, e),
+            }
+        }
+    }
+
+    info!(
+        "🧩 Backfill {}: {} submitted, {} unavailable of {} missing minutes",
+        history.name(),
+        report.submitted,
+        report.unavailable,
+        report.missing
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_minutes() {
+        let from = 1_700_000_050; // 분 경계가 아닌 시작
+        let to = 1_700_000_400;
+        let present = [1_700_000_065, 1_700_000_185, 1_700_000_190];
+        assert_eq!(
+            missing_minutes(&present, from, to),
+            vec![1_700_000_100, 1_700_000_220, 1_700_000_280, 1_700_000_340]
+        );
+        assert!(missing_minutes(&[], to, to).is_empty());
+    }
+}
@@ -1,15 +1,21 @@
+use crate::backfill::KlineHistory;
 use crate::price_provider::PriceProvider;
-use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::price::{cents_from_dollars, format_cents, Rounding};
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::OracleError;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Timelike};
+use crate::exchange_auth::ExchangeAccess;
+use crate::symbols::{Market, TickerSource};
 use reqwest::Client;
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
-/// 바이낸스 K-line API 주소 (1분 캔들스틱)
-const BINANCE_API_URL: &str = "https://api.binance.com/api/v3/klines";
+/// 바이낸스 REST API 기본 주소 (K-line: /api/v3/klines)
+const BINANCE_API_URL: &str = "https://api.binance.com";
 /// 최대 재시도 횟수
 const MAX_RETRIES: u32 = 3;
 /// HTTP 요청 타임아웃 (초)
@@ -22,6 +28,9 @@ type BinanceKlineResponse = Vec<[serde_json::Value; 12]>;
 /// 바이낸스와 통신하는 클라이언트
 pub struct BinanceClient {
     client: Client, // HTTP 요청을 보내는 도구
+    access: ExchangeAccess,
+    market: Market,
+    base_url: String,
 }
 
 impl BinanceClient {
@@ -33,7 +42,27 @@ impl BinanceClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            access: ExchangeAccess::public("binance"),
+            market: Market::btc_usd("binance"),
+            base_url: BINANCE_API_URL.to_string(),
+        }
+    }
+
+    /// API 키 인증/공유 속도 제한/REST 주소 설정 적용
+    pub fn with_access(mut self, access: ExchangeAccess) -> Self {
+        if let Some(base_url) = access.base_url() {
+            self.base_url = base_url.to_string();
+        }
+        self.access = access;
+        self
+    }
+
+    /// 수집할 페어/티커 (기본 BTC/USD = BTCUSDT)
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
     }
 
     /// 비트코인 가격을 가져옵니다 (재시도 포함)
@@ -51,7 +80,7 @@ impl BinanceClient {
 
             match self.fetch_btc_price_once().await {
                 Ok(price_data) => {
-                    info!("Successfully fetched BTC price: ${:.2}", price_data.price);
+                    info!("Successfully fetched BTC price: ${}", format_cents(price_data.price));
                     return Ok(price_data);
                 }
                 Err(e) if attempt < max_retries => {
@@ -77,6 +106,30 @@ impl BinanceClient {
 
     /// 한 번만 가격을 가져오기 (재시도 없음)
     async fn fetch_btc_price_once(&self) -> Result<PriceData> {
+        let close = self.fetch_kline_close(self.market.ticker()).await?;
+        let price = self.market.to_usd(close).await?;
+
+        // 6. 가격이 말이 되는지 검증
+        self.validate_price(price)?;
+
+        // 7. 현재 시간 기록
+        let timestamp = chrono::Utc::now().timestamp() as u64;
+
+        // 8. 최종 결과 반환
+        Ok(PriceData {
+            pair: self.market.pair.clone(),
+            price: cents_from_dollars(price, Rounding::HalfEven)
+                .with_context(|| format!("Invalid price: {}", price))?,
+            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
+                .unwrap_or_else(chrono::Utc::now),
+            volume: None,
+            source: "binance".to_string(),
+            degraded: false,
+        })
+    }
+
+    /// `symbol`의 직전 1분 K-line 종가 (호가 통화 단위)
+    async fn fetch_kline_close(&self, symbol: &str) -> Result<f64> {
         // 현재 시간에서 이전 완성된 분봉 시점 계산
         let now = chrono::Utc::now();
         // 현재 분의 00초로 맞추기 (예: 14:37:XX -> 14:37:00)
@@ -94,14 +147,13 @@ impl BinanceClient {
 
         // 1. 특정 시점의 1분 K-line 데이터 요청
         let url = format!(
-            "{}?symbol=BTCUSDT&interval=1m&startTime={}&endTime={}&limit=1",
-            BINANCE_API_URL, start_time, end_time
+            "{}/api/v3/klines?symbol={}&interval=1m&startTime={}&endTime={}&limit=1",
+            self.base_url, symbol, start_time, end_time
         );
 
         // 2. 바이낸스에 HTTP 요청 보내기
-        let response = self
-            .client
-            .get(&url)
+        let request = self.access.prepare(self.client.get(&url), "GET", &url).await?;
+        let response = request
             .send()
             .await
             .context("Failed to send request to Binance")?;
@@ -147,36 +199,56 @@ impl BinanceClient {
             chrono::DateTime::from_timestamp(close_time as i64 / 1000, 0).unwrap_or_default();
 
         info!(
-            "📊 Binance K-line: {:.2} USD (period: {} ~ {})",
+            "📊 Binance K-line {}: {:.2} (period: {} ~ {})",
+            symbol,
             price,
             open_time_dt.format("%H:%M:%S"),
             close_time_dt.format("%H:%M:%S")
         );
 
-        // 6. 가격이 말이 되는지 검증
-        self.validate_price(price)?;
+        Ok(price)
+    }
 
-        // 7. 현재 시간 기록
-        let timestamp = chrono::Utc::now().timestamp() as u64;
+    /// `[from, to)` 구간에 시작하는 1분봉 종가 (시작 시각 → USD, 백필용)
+    async fn fetch_minute_closes(&self, from: u64, to: u64) -> Result<BTreeMap<u64, f64>> {
+        let url = format!(
+            "{}/api/v3/klines?symbol={}&interval=1m&startTime={}&endTime={}&limit=1000",
+            self.base_url,
+            self.market.ticker(),
+            from * 1000,
+            (to * 1000).saturating_sub(1)
+        );
+        let request = self.access.prepare(self.client.get(&url), "GET", &url).await?;
+        let response = request
+            .send()
+            .await
+            .context("Failed to send request to Binance")?;
+        if !response.status().is_success() {
+            return self.handle_http_error(response.status().as_u16());
+        }
+        let klines: BinanceKlineResponse = response
+            .json()
+            .await
+            .context("Failed to parse Binance JSON response")?;
 
-        // 8. 최종 결과 반환
-        Ok(PriceData {
-            pair: AssetPair::btc_usd(),
-            price: (price * 100.0) as u64, // Convert to cents
-            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
-                .unwrap_or_else(chrono::Utc::now),
-            volume: None,
-            source: "binance".to_string(),
-        })
+        let mut closes = BTreeMap::new();
+        for kline in &klines {
+            let (Some(open_time), Some(close)) = (kline[0].as_u64(), kline[4].as_str()) else {
+                anyhow::bail!("Malformed Binance K-line");
+            };
+            let close = close.parse::<f64>().context("Failed to parse close price as number")?;
+            closes.insert(open_time / 1000, self.market.to_usd(close).await?);
+        }
+        Ok(closes)
     }
 
     /// HTTP 에러를 처리합니다
-    fn handle_http_error(&self, status_code: u16) -> Result<PriceData> {
+    fn handle_http_error<T>(&self, status_code: u16) -> Result<T> {
         match status_code {
             400 => anyhow::bail!("Bad request - Check API parameters"),
             401 => anyhow::bail!("Unauthorized - API key issue"),
             403 => anyhow::bail!("Forbidden - Access denied"),
-            404 => anyhow::bail!("Not found - Check symbol/interval (BTCUSDT/1m)"),
+            404 => anyhow::bail!("Not found - Check symbol/interval ({}/1m)", self.market.ticker()),
             429 => anyhow::bail!("Rate limit exceeded - Too many requests"),
             500..=599 => anyhow::bail!("Binance server error - Try again later"),
             _ => anyhow::bail!("HTTP error: {}", status_code),
@@ -207,7 +279,7 @@ mod tests {
 
     #[test]
     fn test_client_creation() {
-        let client = BinanceClient::new();
+        let _client = BinanceClient::new();
         // 클라이언트가 성공적으로 생성되는지 확인 (단순히 패닉 없이 생성되면 OK)
         // HTTP 클라이언트가 정상적으로 생성되었는지만 확인
     }
@@ -229,9 +301,9 @@ mod tests {
         let client = BinanceClient::new();
 
         // 다양한 HTTP 에러 코드 테스트
-        assert!(client.handle_http_error(404).is_err());
-        assert!(client.handle_http_error(429).is_err());
-        assert!(client.handle_http_error(500).is_err());
+        assert!(client.handle_http_error::<PriceData>(404).is_err());
+        assert!(client.handle_http_error::<PriceData>(429).is_err());
+        assert!(client.handle_http_error::<PriceData>(500).is_err());
     }
 
     // 실제 API 호출 테스트 (인터넷 연결 필요)
@@ -243,9 +315,9 @@ mod tests {
 
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(price_data.price > 0);
                 assert_eq!(price_data.source, "binance");
-                println!("Real BTC price: ${:.2}", price_data.price);
+                println!("Real BTC price: ${:.2}", price_data.price as f64 / 100.0);
             }
             Err(e) => {
                 println!("API call failed (this might be expected): {}", e);
@@ -256,11 +328,34 @@ mod tests {
 
 #[async_trait]
 impl PriceProvider for BinanceClient {
-    async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    async fn fetch_btc_price(&self) -> Result<PriceData, OracleError> {
+        self.fetch_btc_price_with_retry(MAX_RETRIES)
+            .await
+            .map_err(|e| OracleError::Exchange {
+                source_name: "binance".to_string(),
+                message: e.to_string(),
+            })
     }
     
     fn name(&self) -> &str {
         "binance"
     }
 }
+
+#[async_trait]
+impl KlineHistory for BinanceClient {
+    async fn minute_closes(&self, from: u64, to: u64) -> Result<BTreeMap<u64, f64>> {
+        self.fetch_minute_closes(from, to).await
+    }
+
+    fn name(&self) -> &str {
+        "binance"
+    }
+}
+
+#[async_trait]
+impl TickerSource for BinanceClient {
+    async fn fetch_close(&self, ticker: &str) -> Result<f64> {
+        self.fetch_kline_close(ticker).await
+    }
+}
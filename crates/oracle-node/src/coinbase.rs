@@ -1,15 +1,19 @@
 use crate::price_provider::PriceProvider;
-use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::price::{cents_from_dollars, Rounding};
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::OracleError;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::DateTime;
+use crate::exchange_auth::ExchangeAccess;
+use crate::symbols::{Market, TickerSource};
 use reqwest::Client;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
-/// Coinbase Pro API URL
-const COINBASE_API_URL: &str = "https://api.exchange.coinbase.com/products/BTC-USD/candles";
+/// Coinbase Exchange REST API 기본 주소 (캔들: /products/{product_id}/candles)
+const COINBASE_API_URL: &str = "https://api.exchange.coinbase.com";
 /// 최대 재시도 횟수
 const MAX_RETRIES: u32 = 3;
 /// HTTP 요청 타임아웃 (초)
@@ -22,6 +26,9 @@ type CoinbaseCandleResponse = Vec<[f64; 6]>;
 /// Coinbase Pro와 통신하는 클라이언트
 pub struct CoinbaseClient {
     client: Client,
+    access: ExchangeAccess,
+    market: Market,
+    base_url: String,
 }
 
 impl CoinbaseClient {
@@ -33,7 +40,27 @@ impl CoinbaseClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            access: ExchangeAccess::public("coinbase"),
+            market: Market::btc_usd("coinbase"),
+            base_url: COINBASE_API_URL.to_string(),
+        }
+    }
+
+    /// API 키 인증/공유 속도 제한/REST 주소 설정 적용
+    pub fn with_access(mut self, access: ExchangeAccess) -> Self {
+        if let Some(base_url) = access.base_url() {
+            self.base_url = base_url.to_string();
+        }
+        self.access = access;
+        self
+    }
+
+    /// 수집할 페어/티커 (기본 BTC/USD = BTC-USD)
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
     }
 
     /// 비트코인 가격을 가져옵니다 (재시도 포함)
@@ -77,18 +104,37 @@ impl CoinbaseClient {
 
     /// 실제 API 호출을 수행하는 함수
     async fn fetch_btc_price_once(&self) -> Result<PriceData> {
+        let (close, timestamp) = self.fetch_candle_close(self.market.ticker()).await?;
+        let close_price = self.market.to_usd(close).await?;
+
+        Ok(PriceData {
+            pair: self.market.pair.clone(),
+            price: cents_from_dollars(close_price, Rounding::HalfEven)
+                .with_context(|| format!("Invalid price: {}", close_price))?,
+            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
+                .unwrap_or_else(chrono::Utc::now),
+            volume: None,
+            source: "coinbase".to_string(),
+            degraded: false,
+        })
+    }
+
+    /// `product_id`의 최근 1분 캔들 종가와 시각
+    async fn fetch_candle_close(&self, product_id: &str) -> Result<(f64, u64)> {
         // 1분 캔들스틱 요청 (가장 최근 2개)
         let params = [
             ("granularity", "60"),    // 1분
             ("limit", "2"),           // 최근 2개
         ];
 
-        info!("🌐 Calling Coinbase API: {}", COINBASE_API_URL);
+        let url = format!(
+            "{}/products/{}/candles?granularity={}&limit={}",
+            self.base_url, product_id, params[0].1, params[1].1
+        );
+        info!("🌐 Calling Coinbase API: {}", url);
 
-        let response = self
-            .client
-            .get(COINBASE_API_URL)
-            .query(&params)
+        let request = self.access.prepare(self.client.get(&url), "GET", &url).await?;
+        let response = request
             .send()
             .await
             .context("Failed to send request to Coinbase")?;
@@ -118,7 +164,8 @@ impl CoinbaseClient {
         // 타임스탬프 로깅
         let dt = chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default();
         info!(
-            "📊 Coinbase candle: {:.2} USD (time: {})",
+            "📊 Coinbase candle {}: {:.2} (time: {})",
+            product_id,
             close_price,
             dt.format("%Y-%m-%d %H:%M:%S UTC")
         );
@@ -137,14 +184,7 @@ impl CoinbaseClient {
             );
         }
 
-        Ok(PriceData {
-            pair: AssetPair::btc_usd(),
-            price: (close_price * 100.0) as u64, // Convert to cents
-            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
-                .unwrap_or_else(chrono::Utc::now),
-            volume: None,
-            source: "coinbase".to_string(),
-        })
+        Ok((close_price, timestamp))
     }
 }
 
@@ -164,8 +204,13 @@ fn format_price_with_precision(price: f64) -> String {
 
 #[async_trait]
 impl PriceProvider for CoinbaseClient {
-    async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    async fn fetch_btc_price(&self) -> Result<PriceData, OracleError> {
+        self.fetch_btc_price_with_retry(MAX_RETRIES)
+            .await
+            .map_err(|e| OracleError::Exchange {
+                source_name: "coinbase".to_string(),
+                message: e.to_string(),
+            })
     }
     
     fn name(&self) -> &str {
@@ -214,13 +259,20 @@ mod tests {
         
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(price_data.price > 0);
                 assert_eq!(price_data.source, "coinbase");
-                println!("Real BTC price from Coinbase: ${:.2}", price_data.price);
+                println!("Real BTC price from Coinbase: ${:.2}", price_data.price as f64 / 100.0);
             }
             Err(e) => {
                 println!("Coinbase API call failed (this might be expected): {}", e);
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[async_trait]
+impl TickerSource for CoinbaseClient {
+    async fn fetch_close(&self, ticker: &str) -> Result<f64> {
+        Ok(self.fetch_candle_close(ticker).await?.0)
+    }
+}
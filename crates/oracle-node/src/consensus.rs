@@ -1,28 +1,39 @@
+use oracle_vm_common::config::{ConsensusConfig, ConsensusConfigHandle};
 use oracle_vm_common::types::PriceData;
-use anyhow::Result;
+use oracle_vm_common::OracleError;
 use tracing::{info, warn};
 
 /// 2/3 합의를 위한 ConsensusManager
+///
+/// 최소 합의 비율(기본 0.66)과 가격 편차 허용 범위(기본 2%)는
+/// ConsensusConfig에서 자산별로 읽으며, 핸들을 통해 재시작 없이 교체할 수 있습니다.
 pub struct ConsensusManager {
-    /// 최소 합의 비율 (예: 0.67 = 2/3)
-    min_consensus_ratio: f64,
-    /// 가격 편차 허용 범위 (예: 0.02 = 2%)
-    max_price_deviation: f64,
+    config: ConsensusConfigHandle,
 }
 
 impl ConsensusManager {
     pub fn new() -> Self {
-        Self {
-            min_consensus_ratio: 0.66, // 2/3 (실제로는 0.666...)
-            max_price_deviation: 0.02,  // 2%
-        }
+        Self::with_config(ConsensusConfigHandle::new(ConsensusConfig::default()))
+    }
+
+    /// 공유 설정 핸들을 사용하는 ConsensusManager 생성
+    pub fn with_config(config: ConsensusConfigHandle) -> Self {
+        Self { config }
+    }
+
+    /// 핫 리로드용 설정 핸들
+    pub fn config_handle(&self) -> &ConsensusConfigHandle {
+        &self.config
     }
     
     /// 여러 거래소의 가격 데이터를 받아서 합의된 가격을 반환
-    pub fn get_consensus_price(&self, prices: Vec<PriceData>) -> Result<f64> {
+    pub fn get_consensus_price(&self, prices: Vec<PriceData>) -> Result<f64, OracleError> {
         if prices.is_empty() {
-            anyhow::bail!("No price data available");
+            return Err(OracleError::NoPriceData);
         }
+
+        // 라운드 중 설정이 바뀌어도 한 스냅샷만 사용
+        let params = self.config.snapshot().params_for(prices[0].pair.as_str());
         
         // 가격만 추출 (cents를 다시 달러로 변환)
         let mut price_values: Vec<f64> = prices.iter().map(|p| p.price as f64 / 100.0).collect();
@@ -41,7 +52,7 @@ impl ConsensusManager {
             .into_iter()
             .filter(|&price| {
                 let deviation = ((price - median) / median).abs();
-                deviation <= self.max_price_deviation
+                deviation <= params.max_price_deviation
             })
             .collect();
         
@@ -50,15 +61,18 @@ impl ConsensusManager {
         let total_count = prices.len();
         let consensus_ratio = consensus_count as f64 / total_count as f64;
         
-        if consensus_ratio < self.min_consensus_ratio {
+        if consensus_ratio < params.min_consensus_ratio {
             warn!(
                 "Consensus not reached: {}/{} ({:.1}% < {:.1}% required)",
                 consensus_count,
                 total_count,
                 consensus_ratio * 100.0,
-                self.min_consensus_ratio * 100.0
+                params.min_consensus_ratio * 100.0
             );
-            anyhow::bail!("Consensus not reached");
+            return Err(OracleError::ConsensusNotReached(format!(
+                "{}/{} sources agree",
+                consensus_count, total_count
+            )));
         }
         
         // 유효한 가격들의 평균 반환
@@ -69,7 +83,7 @@ impl ConsensusManager {
             consensus_count,
             total_count,
             consensus_price,
-            self.max_price_deviation * 100.0
+            params.max_price_deviation * 100.0
         );
         
         Ok(consensus_price)
@@ -80,6 +94,8 @@ impl ConsensusManager {
         if prices.len() < 3 {
             return vec![];
         }
+
+        let params = self.config.snapshot().params_for(prices[0].pair.as_str());
         
         let mut price_values: Vec<f64> = prices.iter().map(|p| p.price as f64 / 100.0).collect();
         price_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
@@ -96,7 +112,7 @@ impl ConsensusManager {
             .filter(|p| {
                 let price_usd = p.price as f64 / 100.0;
                 let deviation = ((price_usd - median) / median).abs();
-                deviation > self.max_price_deviation
+                deviation > params.max_price_deviation
             })
             .map(|p| p.source.clone())
             .collect()
@@ -126,6 +142,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -133,6 +150,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -140,6 +158,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
+                degraded: false,
             },
         ];
         
@@ -161,6 +180,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -168,6 +188,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -175,6 +196,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
+                degraded: false,
             },
         ];
         
@@ -206,6 +228,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -213,6 +236,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -220,6 +244,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
+                degraded: false,
             },
         ];
         
@@ -238,6 +263,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "binance".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -245,6 +271,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "coinbase".to_string(),
+                degraded: false,
             },
             PriceData {
                 pair: AssetPair::btc_usd(),
@@ -252,6 +279,7 @@ mod tests {
                 timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
                 volume: None,
                 source: "kraken".to_string(),
+                degraded: false,
             },
         ];
         
@@ -259,4 +287,41 @@ mod tests {
         assert_eq!(outliers.len(), 1);
         assert_eq!(outliers[0], "kraken");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hot_reload_per_asset_override() {
+        let manager = ConsensusManager::new();
+        let prices = vec![
+            PriceData {
+                pair: AssetPair::btc_usd(),
+                price: 7000000, // $70,000 in cents
+                timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+                volume: None,
+                source: "binance".to_string(),
+                degraded: false,
+            },
+            PriceData {
+                pair: AssetPair::btc_usd(),
+                price: 7300000, // $73,000 in cents - 기본 2% 초과
+                timestamp: DateTime::from_timestamp(1700000000, 0).unwrap(),
+                volume: None,
+                source: "coinbase".to_string(),
+                degraded: false,
+            },
+        ];
+        assert!(manager.get_consensus_price(prices.clone()).is_err());
+
+        // BTC/USD만 5% 편차 허용으로 교체
+        let mut config = ConsensusConfig::default();
+        config.assets.insert(
+            "BTC/USD".to_string(),
+            oracle_vm_common::config::ConsensusOverride {
+                min_consensus_ratio: None,
+                max_price_deviation: Some(0.05),
+            },
+        );
+        manager.config_handle().replace(config).unwrap();
+
+        assert!(manager.get_consensus_price(prices).is_ok());
+    }
+}
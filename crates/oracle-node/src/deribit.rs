@@ -0,0 +1,277 @@
+use oracle_vm_common::types::{AssetPair, OptionType, VolQuote, VolSurface};
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// Deribit 옵션 요약 API 주소 (공개 API, 인증 불필요)
+const DERIBIT_API_URL: &str = "https://www.deribit.com/api/v2/public/get_book_summary_by_currency";
+/// 최대 재시도 횟수
+const MAX_RETRIES: u32 = 3;
+/// HTTP 요청 타임아웃 (초)
+const REQUEST_TIMEOUT: u64 = 10;
+/// Deribit 옵션 만기 시각 (UTC 08:00)
+const EXPIRY_HOUR_UTC: u32 = 8;
+
+/// Deribit JSON-RPC 응답
+#[derive(Debug, Deserialize)]
+struct DeribitResponse {
+    result: Vec<DeribitBookSummary>,
+}
+
+/// 옵션 종목별 요약 (필요한 필드만)
+#[derive(Debug, Deserialize)]
+struct DeribitBookSummary {
+    instrument_name: String,
+    mark_iv: Option<f64>,          // 퍼센트 단위 (52.3 = 52.3%)
+    underlying_price: Option<f64>, // USD
+}
+
+/// Deribit에서 BTC 옵션 mark IV 곡면을 가져오는 클라이언트
+pub struct DeribitClient {
+    client: Client,
+}
+
+impl DeribitClient {
+    /// 새로운 Deribit 클라이언트 생성
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT))
+            .user_agent("OracleVM/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client }
+    }
+
+    /// BTC 옵션 IV 곡면 조회 (재시도 포함)
+    pub async fn fetch_vol_surface(&self) -> Result<VolSurface> {
+        for attempt in 1..=MAX_RETRIES {
+            match self.fetch_vol_surface_once().await {
+                Ok(surface) => return Ok(surface),
+                Err(e) if attempt < MAX_RETRIES => {
+                    let wait_time = 2_u64.pow(attempt - 1);
+                    warn!(
+                        "Failed to fetch Deribit IV surface (attempt {}): {}. Retrying in {}s...",
+                        attempt, e, wait_time
+                    );
+                    sleep(Duration::from_secs(wait_time)).await;
+                }
+                Err(e) => {
+                    error!("All Deribit attempts failed: {}", e);
+                    return Err(e);
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
+    /// 실제 API 호출
+    async fn fetch_vol_surface_once(&self) -> Result<VolSurface> {
+        let params = [("currency", "BTC"), ("kind", "option")];
+
+        info!("🌐 Calling Deribit API: {}", DERIBIT_API_URL);
+
+        let response = self
+            .client
+            .get(DERIBIT_API_URL)
+            .query(&params)
+            .send()
+            .await
+            .context("Failed to send request to Deribit")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Deribit API returned error status: {} - {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            );
+        }
+
+        let body: DeribitResponse = response
+            .json()
+            .await
+            .context("Failed to parse Deribit response")?;
+
+        let surface = build_surface(body.result, Utc::now())?;
+        info!(
+            "📈 Deribit IV surface: {} quotes, underlying ${:.2}",
+            surface.quotes.len(),
+            surface.underlying_price as f64 / 100.0
+        );
+
+        Ok(surface)
+    }
+}
+
+impl Default for DeribitClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 종목 요약 목록을 IV 곡면으로 변환
+///
+/// mark IV가 없거나 이미 만기가 지난 종목은 제외합니다.
+fn build_surface(summaries: Vec<DeribitBookSummary>, now: DateTime<Utc>) -> Result<VolSurface> {
+    let mut quotes = Vec::new();
+    // 가장 가까운 만기의 기초자산 가격을 곡면 기준 가격으로 사용
+    let mut nearest: Option<(DateTime<Utc>, f64)> = None;
+
+    for summary in summaries {
+        let Some(mark_iv) = summary.mark_iv.filter(|iv| *iv > 0.0) else {
+            continue;
+        };
+        let Some((expiry, strike, option_type)) = parse_instrument_name(&summary.instrument_name)
+        else {
+            warn!("Skipping unrecognized Deribit instrument: {}", summary.instrument_name);
+            continue;
+        };
+        if expiry <= now {
+            continue;
+        }
+
+        if let Some(underlying) = summary.underlying_price.filter(|price| *price > 0.0) {
+            if nearest.is_none_or(|(nearest_expiry, _)| expiry < nearest_expiry) {
+                nearest = Some((expiry, underlying));
+            }
+        }
+
+        quotes.push(VolQuote {
+            instrument: summary.instrument_name,
+            expiry,
+            strike: (strike * 100.0).round() as u64,
+            option_type,
+            mark_iv: mark_iv / 100.0,
+        });
+    }
+
+    let (_, underlying_price) =
+        nearest.ok_or_else(|| anyhow::anyhow!("No usable option quotes from Deribit"))?;
+
+    Ok(VolSurface {
+        pair: AssetPair::btc_usd(),
+        underlying_price: (underlying_price * 100.0).round() as u64,
+        timestamp: now,
+        quotes,
+        source: "deribit".to_string(),
+    })
+}
+
+/// Deribit 종목명 파싱 ("BTC-27DEC24-70000-C" → 만기, 행사가, 타입)
+fn parse_instrument_name(name: &str) -> Option<(DateTime<Utc>, f64, OptionType)> {
+    let parts: Vec<&str> = name.split('-').collect();
+    if parts.len() != 4 || parts[0] != "BTC" {
+        return None;
+    }
+
+    let expiry = parse_expiry(parts[1])?;
+    let strike: f64 = parts[2].parse().ok()?;
+    let option_type = match parts[3] {
+        "C" => OptionType::Call,
+        "P" => OptionType::Put,
+        _ => return None,
+    };
+
+    Some((expiry, strike, option_type))
+}
+
+/// 만기 문자열 파싱 ("27DEC24", "5JAN25")
+fn parse_expiry(code: &str) -> Option<DateTime<Utc>> {
+    let day_len = code.chars().take_while(|c| c.is_ascii_digit()).count();
+    if day_len == 0 || code.len() != day_len + 5 {
+        return None;
+    }
+
+    let day: u32 = code[..day_len].parse().ok()?;
+    let month = match &code[day_len..day_len + 3] {
+        "JAN" => 1,
+        "FEB" => 2,
+        "MAR" => 3,
+        "APR" => 4,
+        "MAY" => 5,
+        "JUN" => 6,
+        "JUL" => 7,
+        "AUG" => 8,
+        "SEP" => 9,
+        "OCT" => 10,
+        "NOV" => 11,
+        "DEC" => 12,
+        _ => return None,
+    };
+    let year: i32 = code[day_len + 3..].parse().ok()?;
+
+    let date = NaiveDate::from_ymd_opt(2000 + year, month, day)?;
+    Some(Utc.from_utc_datetime(&date.and_hms_opt(EXPIRY_HOUR_UTC, 0, 0)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(name: &str, mark_iv: Option<f64>, underlying: f64) -> DeribitBookSummary {
+        DeribitBookSummary {
+            instrument_name: name.to_string(),
+            mark_iv,
+            underlying_price: Some(underlying),
+        }
+    }
+
+    #[test]
+    fn test_parse_instrument_name() {
+        let (expiry, strike, option_type) = parse_instrument_name("BTC-27DEC24-70000-C").unwrap();
+        assert_eq!(expiry.to_rfc3339(), "2024-12-27T08:00:00+00:00");
+        assert_eq!(strike, 70000.0);
+        assert_eq!(option_type, OptionType::Call);
+
+        let (expiry, _, option_type) = parse_instrument_name("BTC-5JAN25-65000-P").unwrap();
+        assert_eq!(expiry.to_rfc3339(), "2025-01-05T08:00:00+00:00");
+        assert_eq!(option_type, OptionType::Put);
+
+        assert!(parse_instrument_name("BTC-PERPETUAL").is_none());
+        assert!(parse_instrument_name("ETH-27DEC24-3000-C").is_none());
+        assert!(parse_instrument_name("BTC-27XYZ24-70000-C").is_none());
+    }
+
+    #[test]
+    fn test_build_surface() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 1, 0, 0, 0).unwrap();
+        let summaries = vec![
+            summary("BTC-27DEC24-70000-C", Some(55.0), 71000.0),
+            summary("BTC-6DEC24-70000-P", Some(60.0), 70500.0),
+            summary("BTC-6DEC24-80000-C", None, 70500.0), // IV 없음
+            summary("BTC-29NOV24-70000-C", Some(50.0), 70000.0), // 만기 지남
+        ];
+
+        let surface = build_surface(summaries, now).unwrap();
+        assert_eq!(surface.quotes.len(), 2);
+        assert_eq!(surface.underlying_price, 7_050_000);
+        assert_eq!(surface.quotes[0].strike, 7_000_000);
+        assert!((surface.quotes[0].mark_iv - 0.55).abs() < 1e-9);
+        assert_eq!(surface.source, "deribit");
+    }
+
+    #[test]
+    fn test_build_surface_requires_quotes() {
+        let now = Utc::now();
+        assert!(build_surface(vec![], now).is_err());
+    }
+
+    // 실제 API 호출 테스트 (수동 실행용)
+    #[tokio::test]
+    #[ignore] // 실제 API를 호출하므로 평소에는 실행하지 않음
+    async fn test_real_deribit_api() {
+        let client = DeribitClient::new();
+        match client.fetch_vol_surface().await {
+            Ok(surface) => {
+                assert!(!surface.quotes.is_empty());
+                println!("Deribit IV quotes: {}", surface.quotes.len());
+            }
+            Err(e) => println!("Deribit API call failed (this might be expected): {}", e),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! 거래소 API 키 인증과 요청 속도 제한
+//!
+//! 공개 REST 엔드포인트는 IP 기준 제한이 엄격하므로, 설정에 API 키가 있으면
+//! 거래소별 방식으로 요청에 서명/헤더를 붙입니다. 같은 거래소를 쓰는 모든
+//! 클라이언트는 하나의 토큰 버킷을 공유해 여러 심볼을 수집해도 분당 한도를
+//! 넘지 않습니다.
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use oracle_vm_common::config::{ExchangeCredentials, ExchangesConfig};
+use reqwest::RequestBuilder;
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 토큰 버킷 (분당 요청 수 기준)
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    state: Mutex<(f64, Instant)>, // (남은 토큰, 마지막 갱신 시각)
+}
+
+impl TokenBucket {
+    /// `per_minute` 요청/분, 최대 버스트는 1분 분량
+    pub fn per_minute(per_minute: u32) -> Self {
+        let capacity = per_minute.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// 토큰 하나를 즉시 꺼내거나, 다음 토큰까지 기다릴 시간 반환
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.1).as_secs_f64();
+        state.0 = (state.0 + elapsed * self.refill_per_sec).min(self.capacity);
+        state.1 = now;
+
+        if state.0 >= 1.0 {
+            state.0 -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - state.0) / self.refill_per_sec))
+        }
+    }
+
+    /// 토큰을 얻을 때까지 대기
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// 거래소별 공유 토큰 버킷
+#[derive(Default)]
+pub struct RateLimiters {
+    buckets: Mutex<HashMap<String, Arc<TokenBucket>>>,
+}
+
+impl RateLimiters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 거래소 버킷 (처음 요청 시 `per_minute`로 생성, 이후 같은 버킷 공유)
+    pub fn for_exchange(&self, exchange: &str, per_minute: u32) -> Arc<TokenBucket> {
+        self.buckets
+            .lock()
+            .unwrap()
+            .entry(exchange.to_lowercase())
+            .or_insert_with(|| Arc::new(TokenBucket::per_minute(per_minute)))
+            .clone()
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Coinbase Exchange: base64(HMAC-SHA256(base64decode(secret), timestamp + method + path + body))
+pub fn sign_coinbase(secret: &str, timestamp: u64, method: &str, path: &str, body: &str) -> Result<String> {
+    let key = BASE64.decode(secret).context("Coinbase API secret must be base64")?;
+    let message = format!("{}{}{}{}", timestamp, method.to_uppercase(), path, body);
+    Ok(BASE64.encode(hmac_sha256(&key, message.as_bytes())))
+}
+
+/// Kraken: base64(HMAC-SHA512(base64decode(secret), path + SHA256(nonce + postdata)))
+pub fn sign_kraken(secret: &str, path: &str, nonce: u64, postdata: &str) -> Result<String> {
+    let key = BASE64.decode(secret).context("Kraken API secret must be base64")?;
+    let digest = Sha256::digest(format!("{}{}", nonce, postdata).as_bytes());
+
+    let mut mac = Hmac::<Sha512>::new_from_slice(&key).expect("HMAC accepts any key length");
+    mac.update(path.as_bytes());
+    mac.update(&digest);
+    Ok(BASE64.encode(mac.finalize().into_bytes()))
+}
+
+/// 거래소 클라이언트가 요청마다 적용하는 인증/속도 제한
+#[derive(Clone)]
+pub struct ExchangeAccess {
+    exchange: String,
+    credentials: Option<ExchangeCredentials>,
+    limiter: Option<Arc<TokenBucket>>,
+    /// 설정의 `base_url` (없으면 거래소 기본 주소)
+    base_url: Option<String>,
+}
+
+impl ExchangeAccess {
+    /// 인증/제한 없음 (기존 공개 API 동작)
+    pub fn public(exchange: &str) -> Self {
+        Self {
+            exchange: exchange.to_lowercase(),
+            credentials: None,
+            limiter: None,
+            base_url: None,
+        }
+    }
+
+    /// 설정의 `[exchanges.<name>]`와 공유 버킷으로 구성
+    pub fn from_config(exchange: &str, config: &ExchangesConfig, limiters: &RateLimiters) -> Self {
+        let settings = config.get(exchange);
+        Self {
+            exchange: exchange.to_lowercase(),
+            credentials: settings.credentials(exchange),
+            limiter: Some(limiters.for_exchange(exchange, settings.rate_limit)),
+            base_url: settings
+                .base_url
+                .map(|url| url.trim_end_matches('/').to_string()),
+        }
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    /// REST 주소 교체 (로컬 devnet 등)
+    pub fn base_url(&self) -> Option<&str> {
+        self.base_url.as_deref()
+    }
+
+    /// 속도 제한 대기 후 인증 헤더 추가 (`url`은 쿼리를 포함한 전체 요청 URL)
+    pub async fn prepare(&self, builder: RequestBuilder, method: &str, url: &str) -> Result<RequestBuilder> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+        let Some(credentials) = &self.credentials else {
+            return Ok(builder);
+        };
+        let parsed = reqwest::Url::parse(url).context("Invalid request URL")?;
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+
+        let now = chrono::Utc::now();
+        Ok(match self.exchange.as_str() {
+            // 시세 조회는 서명이 필요 없고, 키 헤더로 계정 한도가 적용됨
+            "binance" => builder.header("X-MBX-APIKEY", &credentials.api_key),
+            "coinbase" => {
+                let timestamp = now.timestamp() as u64;
+                let signature = sign_coinbase(&credentials.api_secret, timestamp, method, &path, "")?;
+                builder
+                    .header("CB-ACCESS-KEY", &credentials.api_key)
+                    .header("CB-ACCESS-SIGN", signature)
+                    .header("CB-ACCESS-TIMESTAMP", timestamp.to_string())
+                    .header(
+                        "CB-ACCESS-PASSPHRASE",
+                        credentials.passphrase.clone().unwrap_or_default(),
+                    )
+            }
+            "kraken" => {
+                let nonce = now.timestamp_millis() as u64;
+                let signature = sign_kraken(&credentials.api_secret, parsed.path(), nonce, "")?;
+                builder
+                    .header("API-Key", &credentials.api_key)
+                    .header("API-Sign", signature)
+                    .header("API-Nonce", nonce.to_string())
+            }
+            _ => builder,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_limits_burst() {
+        let bucket = TokenBucket::per_minute(2);
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_ok());
+        let wait = bucket.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+
+        let limiters = RateLimiters::new();
+        let a = limiters.for_exchange("Binance", 1200);
+        let b = limiters.for_exchange("binance", 10);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_signatures() {
+        // Kraken REST API 문서 예제
+        assert_eq!(
+            sign_kraken(
+                "kQH5HW/8p1uGOVjbgWA7FunAmGO8lsSUXNsu3eow76sz84Q18fWxnyRzBHCd3pd5nE9qa99HAZtuZuj6F1huXg==",
+                "/0/private/AddOrder",
+                1616492376594,
+                "nonce=1616492376594&ordertype=limit&pair=XBTUSD&price=37500&type=buy&volume=1.25"
+            )
+            .unwrap(),
+            "4/dpxb3iT4tp/ZCVEwSnEsLxx0bqyhLpdfOpc6fn7OR8+UClSV5n9E6aSS8MPtnRfp32bAb0nmbRn6H8ndwLUQ=="
+        );
+
+        let secret = BASE64.encode(b"kraken-secret");
+        let a = sign_kraken(&secret, "/0/private/Balance", 1, "nonce=1").unwrap();
+        let b = sign_kraken(&secret, "/0/private/Balance", 2, "nonce=2").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(BASE64.decode(&a).unwrap().len(), 64);
+
+        assert!(sign_coinbase("not base64!", 1, "GET", "/products", "").is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! 거래소 장애 대응 (failover)
+//!
+//! 주 거래소가 실패하면 같은 수집 라운드 안에서 다음 거래소로 넘어갑니다.
+//! 실패한 거래소는 지수 백오프 동안 건너뛰고, 대체 거래소에서 받은 가격은
+//! `degraded`로 표시해 Aggregator가 가중치를 낮출 수 있게 합니다.
+
+use crate::price_provider::PriceProvider;
+use async_trait::async_trait;
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::OracleError;
+use std::sync::Mutex;
+use tracing::warn;
+
+/// 백오프 설정
+#[derive(Debug, Clone)]
+pub struct BackoffConfig {
+    /// 첫 실패 후 대기 시간 (초)
+    pub base_secs: u64,
+    /// 최대 대기 시간 (초)
+    pub max_secs: u64,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            base_secs: 30,
+            max_secs: 600,
+        }
+    }
+}
+
+/// 거래소별 백오프 상태
+#[derive(Debug, Clone, Default)]
+struct BackoffState {
+    consecutive_failures: u32,
+    retry_at: u64,
+}
+
+/// 주 거래소 + 대체 거래소 체인
+pub struct FailoverProvider {
+    providers: Vec<Box<dyn PriceProvider>>,
+    backoff: Mutex<Vec<BackoffState>>,
+    config: BackoffConfig,
+}
+
+impl FailoverProvider {
+    /// 첫 번째가 주 거래소, 나머지는 순서대로 대체 거래소
+    pub fn new(providers: Vec<Box<dyn PriceProvider>>, config: BackoffConfig) -> Self {
+        let backoff = vec![BackoffState::default(); providers.len()];
+        Self {
+            providers,
+            backoff: Mutex::new(backoff),
+            config,
+        }
+    }
+
+    /// 백오프 중이 아닌 첫 거래소에서 가격 조회
+    pub async fn fetch_at(&self, now: u64) -> Result<PriceData, OracleError> {
+        let mut errors = Vec::new();
+
+        for (index, provider) in self.providers.iter().enumerate() {
+            let retry_at = self.backoff.lock().unwrap()[index].retry_at;
+            if now < retry_at {
+                errors.push(format!("{}: backing off until {}", provider.name(), retry_at));
+                continue;
+            }
+
+            match provider.fetch_btc_price().await {
+                Ok(mut price_data) => {
+                    self.backoff.lock().unwrap()[index] = BackoffState::default();
+                    price_data.degraded = index > 0;
+                    return Ok(price_data);
+                }
+                Err(e) => {
+                    let delay = self.record_failure(index, now);
+                    warn!(
+                        "{} failed ({}), backing off {}s",
+                        provider.name(),
+                        e,
+                        delay
+                    );
+                    errors.push(format!("{}: {}", provider.name(), e));
+                }
+            }
+        }
+
+        Err(OracleError::AllSourcesFailed(errors.join("; ")))
+    }
+
+    fn record_failure(&self, index: usize, now: u64) -> u64 {
+        let mut backoff = self.backoff.lock().unwrap();
+        let state = &mut backoff[index];
+        state.consecutive_failures += 1;
+
+        let exponent = (state.consecutive_failures - 1).min(16);
+        let delay = self
+            .config
+            .base_secs
+            .saturating_mul(1 << exponent)
+            .min(self.config.max_secs);
+        state.retry_at = now + delay;
+        delay
+    }
+}
+
+#[async_trait]
+impl PriceProvider for FailoverProvider {
+    async fn fetch_btc_price(&self) -> Result<PriceData, OracleError> {
+        self.fetch_at(chrono::Utc::now().timestamp() as u64).await
+    }
+
+    fn name(&self) -> &str {
+        self.providers.first().map(|p| p.name()).unwrap_or("none")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use oracle_vm_common::types::AssetPair;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// 지정한 횟수만큼 실패한 뒤 성공하는 거래소
+    struct FlakyProvider {
+        name: String,
+        failures_left: AtomicU32,
+        calls: Arc<AtomicU32>,
+    }
+
+    impl FlakyProvider {
+        fn new(name: &str, failures: u32) -> (Self, Arc<AtomicU32>) {
+            let calls = Arc::new(AtomicU32::new(0));
+            let provider = Self {
+                name: name.to_string(),
+                failures_left: AtomicU32::new(failures),
+                calls: calls.clone(),
+            };
+            (provider, calls)
+        }
+    }
+
+    #[async_trait]
+    impl PriceProvider for FlakyProvider {
+        async fn fetch_btc_price(&self) -> Result<PriceData, OracleError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failures_left.load(Ordering::SeqCst) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::SeqCst);
+                return Err(OracleError::Exchange {
+                    source_name: self.name.clone(),
+                    message: "HTTP 503".to_string(),
+                });
+            }
+            Ok(PriceData {
+                pair: AssetPair::btc_usd(),
+                price: 7_000_000,
+                timestamp: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                volume: None,
+                source: self.name.clone(),
+                degraded: false,
+            })
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_and_marks_degraded() {
+        let (primary, _) = FlakyProvider::new("binance", 1);
+        let (fallback, _) = FlakyProvider::new("kraken", 0);
+        let provider = FailoverProvider::new(
+            vec![Box::new(primary), Box::new(fallback)],
+            BackoffConfig::default(),
+        );
+
+        let price = provider.fetch_at(1_000).await.unwrap();
+        assert_eq!(price.source, "kraken");
+        assert!(price.degraded);
+
+        // 백오프가 끝나면 주 거래소 복귀
+        let price = provider.fetch_at(1_030).await.unwrap();
+        assert_eq!(price.source, "binance");
+        assert!(!price.degraded);
+    }
+
+    #[tokio::test]
+    async fn test_exponential_backoff_skips_source() {
+        let (primary, primary_calls) = FlakyProvider::new("binance", 2);
+        let (fallback, _) = FlakyProvider::new("coinbase", 0);
+        let provider = FailoverProvider::new(
+            vec![Box::new(primary), Box::new(fallback)],
+            BackoffConfig {
+                base_secs: 10,
+                max_secs: 100,
+            },
+        );
+
+        provider.fetch_at(1_000).await.unwrap(); // 실패 1 → 10초
+        provider.fetch_at(1_005).await.unwrap(); // 백오프 중, 호출 안 함
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+
+        provider.fetch_at(1_010).await.unwrap(); // 실패 2 → 20초
+        provider.fetch_at(1_025).await.unwrap();
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+
+        let price = provider.fetch_at(1_030).await.unwrap();
+        assert_eq!(price.source, "binance");
+    }
+
+    #[tokio::test]
+    async fn test_all_sources_failing() {
+        let (primary, _) = FlakyProvider::new("binance", 5);
+        let provider = FailoverProvider::new(vec![Box::new(primary)], BackoffConfig::default());
+        assert!(matches!(
+            provider.fetch_at(1_000).await,
+            Err(OracleError::AllSourcesFailed(_))
+        ));
+    }
+}
@@ -1,74 +1,192 @@
-use oracle_vm_common::types::PriceData;
+use oracle_vm_common::crypto::{
+    backfill_submission_payload, lease_request_payload, node_registration_payload, price_submission_payload,
+    threshold_commit_payload, vol_surface_payload, KeyStore,
+};
+use frost_secp256k1_tr::round1::SigningCommitments;
+use frost_secp256k1_tr::round2::SignatureShare;
+use oracle_vm_common::price::{cents_to_dollars, format_cents};
+use oracle_vm_common::types::{OptionType, PriceData, VolSurface};
+use oracle_vm_common::CorrelationId;
 use anyhow::{Context, Result};
+use std::sync::Arc;
 use tonic::transport::Channel;
-use tonic::Request;
+use tonic::{Code, Request};
 use tracing::{error, info, warn};
 
-// gRPC 클라이언트 코드 (tonic-build로 자동 생성됨)
-pub mod oracle {
-    tonic::include_proto!("oracle");
-}
+// gRPC 클라이언트 코드 (oracle-vm-proto)
+pub use oracle_vm_proto::oracle;
+
+/// 제출 서명 키 이름
+pub const NODE_KEY: &str = "oracle-node";
 
-use oracle::{oracle_service_client::OracleServiceClient, HealthRequest, PriceRequest};
+use oracle::{
+    oracle_service_client::OracleServiceClient, HealthRequest, LeaseRequest, LeaseResponse,
+    PriceRequest, RegisterNodeRequest, SubmissionQueryRequest, ThresholdCommitRequest,
+    ThresholdCommitResponse, ThresholdNonceCommitment, ThresholdSignRequest, ThresholdSignResponse,
+    VolPoint, VolSurfaceRequest,
+};
+
+/// 상관관계 ID를 메타데이터에 붙인 gRPC 요청
+fn correlated<T>(correlation_id: &CorrelationId, message: T) -> Request<T> {
+    let mut request = Request::new(message);
+    correlation_id.inject_grpc(&mut request);
+    request
+}
 
 /// gRPC를 사용한 Aggregator 클라이언트
+///
+/// Aggregator 주소를 여러 개(primary, standby 순) 받으면 연결/등록이 되는 첫
+/// 주소를 쓰고, 제출이 UNAVAILABLE로 실패하면 다음 주소로 넘어가 다시 등록합니다.
 pub struct GrpcAggregatorClient {
     client: OracleServiceClient<Channel>,
+    /// Aggregator 주소 목록과 현재 사용 중인 주소 위치
+    endpoints: Vec<String>,
+    active: usize,
     node_id: String,
+    /// 제출 서명용 노드 키 저장소 (`oracle-node` 키 사용)
+    keys: Arc<dyn KeyStore>,
+    /// 마지막으로 사용한 nonce
+    nonce: u64,
 }
 
 impl GrpcAggregatorClient {
-    /// 새로운 gRPC Aggregator 클라이언트 생성
-    pub async fn new(aggregator_url: &str) -> Result<Self> {
+    /// 새로운 gRPC Aggregator 클라이언트 생성 (`aggregator_url`은 쉼표로 여러 주소 지정 가능)
+    pub async fn new(aggregator_url: &str, keys: Arc<dyn KeyStore>) -> Result<Self> {
+        Self::with_node_id(aggregator_url, None, keys).await
+    }
+
+    /// 허용 목록에 등록된 `node_id`로 클라이언트 생성 (없으면 임의 ID)
+    pub async fn with_node_id(aggregator_url: &str, node_id: Option<&str>, keys: Arc<dyn KeyStore>) -> Result<Self> {
         // Oracle Node 고유 ID 생성
-        let node_id = format!(
-            "oracle-node-{}",
-            uuid::Uuid::new_v4().to_string()[..8].to_string()
-        );
+        let node_id = match node_id {
+            Some(node_id) => node_id.to_string(),
+            None => format!("oracle-node-{}", &uuid::Uuid::new_v4().to_string()[..8]),
+        };
+        let endpoints: Vec<String> = aggregator_url
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        if endpoints.is_empty() {
+            anyhow::bail!("No aggregator URL given");
+        }
+
+        let mut last_error = None;
+        for (active, endpoint) in endpoints.iter().enumerate() {
+            match Self::connect(endpoint, &node_id, keys.as_ref()).await {
+                Ok((client, last_nonce)) => {
+                    // 재시작해도 단조 증가하도록 현재 시각(ms)에서 시작
+                    let nonce = (chrono::Utc::now().timestamp_millis() as u64).max(last_nonce);
+                    info!(
+                        "🔗 Created gRPC Aggregator client with node_id: {} ({})",
+                        node_id, endpoint
+                    );
+                    return Ok(Self {
+                        client,
+                        endpoints,
+                        active,
+                        node_id,
+                        keys,
+                        nonce,
+                    });
+                }
+                Err(e) => {
+                    warn!("❌ Aggregator {} unavailable: {:#}", endpoint, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No aggregator reachable")))
+    }
 
+    /// Aggregator 하나에 연결해 노드 키 공개키 등록, 마지막 nonce 반환
+    async fn connect(
+        endpoint: &str,
+        node_id: &str,
+        keys: &dyn KeyStore,
+    ) -> Result<(OracleServiceClient<Channel>, u64)> {
         // gRPC 채널 생성
-        let channel = Channel::from_shared(aggregator_url.to_string())
+        let channel = Channel::from_shared(endpoint.to_string())
             .context("Invalid aggregator URL")?
             .connect()
             .await
             .context("Failed to connect to Aggregator via gRPC")?;
 
-        let client = OracleServiceClient::new(channel);
+        let mut client = OracleServiceClient::new(channel);
 
-        info!(
-            "🔗 Created gRPC Aggregator client with node_id: {}",
-            node_id
-        );
+        let public_key = keys.public_key(NODE_KEY).context("Failed to load node key")?;
+        let registration = keys
+            .sign(NODE_KEY, &node_registration_payload(node_id, &public_key))
+            .context("Failed to sign node registration")?;
+        let response = client
+            .register_node(Request::new(RegisterNodeRequest {
+                node_id: node_id.to_string(),
+                public_key: public_key.to_string(),
+                signature: registration.to_string(),
+            }))
+            .await
+            .context("Failed to register node with Aggregator")?
+            .into_inner();
+        if !response.success {
+            anyhow::bail!("Aggregator rejected node registration: {}", response.message);
+        }
 
-        Ok(Self { client, node_id })
+        info!("🔑 Registered with {} (pubkey: {})", endpoint, public_key);
+        Ok((client, response.last_nonce))
+    }
+
+    /// 현재 Aggregator를 쓸 수 없을 때 다음 주소로 전환 (한 바퀴 돌아도 없으면 오류)
+    async fn failover(&mut self) -> Result<()> {
+        for step in 1..=self.endpoints.len() {
+            let next = (self.active + step) % self.endpoints.len();
+            let endpoint = &self.endpoints[next];
+            match Self::connect(endpoint, &self.node_id, self.keys.as_ref()).await {
+                Ok((client, last_nonce)) => {
+                    warn!("🔁 Failing over to Aggregator {}", endpoint);
+                    self.client = client;
+                    self.active = next;
+                    self.nonce = self.nonce.max(last_nonce);
+                    return Ok(());
+                }
+                Err(e) => warn!("❌ Aggregator {} unavailable: {:#}", endpoint, e),
+            }
+        }
+        anyhow::bail!("No aggregator reachable")
     }
 
     /// 가격 데이터를 gRPC로 Aggregator에 전송
+    ///
+    /// Aggregator가 UNAVAILABLE(종료/standby)이면 다른 주소로 넘어가 한 번 더 보냅니다.
     pub async fn submit_price(&mut self, price_data: &PriceData) -> Result<()> {
-        // Convert cents to dollars for gRPC
-        let price_usd = price_data.price as f64 / 100.0;
-        
-        let request = Request::new(PriceRequest {
-            price: price_usd,
-            timestamp: price_data.timestamp.timestamp() as u64,
-            source: price_data.source.clone(),
-            node_id: self.node_id.clone(),
-            signature: None, // 나중에 보안용으로 추가
-        });
+        let price_cents = price_data.price;
+        let request = self.price_request(price_data, false)?;
+        // 재시도/전환해도 같은 ID로 Aggregator 로그와 연결
+        let correlation_id = CorrelationId::current_or_generate();
 
         info!(
-            "📤 Sending price ${:.2} to Aggregator via gRPC...",
-            price_usd
+            "📤 Sending price ${} to Aggregator via gRPC...",
+            format_cents(price_cents)
         );
 
-        match self.client.submit_price(request).await {
+        let response = match self.client.submit_price(correlated(&correlation_id, request)).await {
+            Err(status) if status.code() == Code::Unavailable && self.endpoints.len() > 1 => {
+                warn!("❌ gRPC: Aggregator unavailable: {}", status);
+                self.failover().await?;
+                // 새 Aggregator에 맞는 nonce로 다시 서명
+                let request = self.price_request(price_data, false)?;
+                self.client.submit_price(correlated(&correlation_id, request)).await
+            }
+            response => response,
+        };
+
+        match response {
             Ok(response) => {
                 let response = response.into_inner();
                 if response.success {
-                    if let Some(aggregated_price) = response.aggregated_price {
+                    if let Some(aggregated_price) = response.aggregated_price_cents {
                         info!(
-                            "✅ gRPC: Price sent successfully! Aggregated price: ${:.2}",
-                            aggregated_price
+                            "✅ gRPC: Price sent successfully! Aggregated price: ${}",
+                            format_cents(aggregated_price)
                         );
                     } else {
                         info!("✅ gRPC: Price sent successfully! {}", response.message);
@@ -87,6 +205,231 @@ impl GrpcAggregatorClient {
         Ok(())
     }
 
+    /// 중단 구간을 채운 과거 가격 제출 (백필 payload로 서명, 실패 시 재시도/전환 없음)
+    pub async fn submit_backfill(&mut self, price_data: &PriceData) -> Result<()> {
+        let request = self.price_request(price_data, true)?;
+        let response = self
+            .client
+            .submit_price(correlated(&CorrelationId::current_or_generate(), request))
+            .await
+            .map_err(|e| anyhow::anyhow!("gRPC communication error: {}", e))?
+            .into_inner();
+        if !response.success {
+            anyhow::bail!("Aggregator rejected backfill: {}", response.message);
+        }
+        Ok(())
+    }
+
+    /// 원장에 기록된 `exchange` 제출 timestamp (모든 노드, `[from, to)`)
+    pub async fn submitted_timestamps(&mut self, exchange: &str, from: u64, to: u64) -> Result<Vec<u64>> {
+        let response = self
+            .client
+            .query_submissions(Request::new(SubmissionQueryRequest {
+                node_id: None,
+                exchange: Some(exchange.to_string()),
+                from,
+                to: to.saturating_sub(1),
+                limit: 0,
+            }))
+            .await
+            .context("Failed to query submission ledger")?;
+        Ok(response
+            .into_inner()
+            .submissions
+            .into_iter()
+            .map(|record| record.timestamp)
+            .collect())
+    }
+
+    /// 서명된 가격 제출 요청 (nonce 1 증가)
+    fn price_request(&mut self, price_data: &PriceData, backfilled: bool) -> Result<PriceRequest> {
+        // 서명/합의 기준은 정수 센트, double은 구버전 Aggregator 호환용
+        let price_cents = price_data.price;
+
+        let timestamp = price_data.timestamp.timestamp() as u64;
+        self.nonce += 1;
+        let payload = if backfilled {
+            backfill_submission_payload
+        } else {
+            price_submission_payload
+        };
+        let payload = payload(
+            &self.node_id,
+            &price_data.source,
+            price_cents,
+            timestamp,
+            self.nonce,
+            price_data.degraded,
+        );
+        let signature = self.keys.sign(NODE_KEY, &payload).context("Failed to sign price")?;
+
+        Ok(PriceRequest {
+            price: cents_to_dollars(price_cents),
+            timestamp,
+            source: price_data.source.clone(),
+            node_id: self.node_id.clone(),
+            signature: Some(signature.to_string()),
+            nonce: self.nonce,
+            degraded: price_data.degraded,
+            price_cents,
+            backfilled,
+        })
+    }
+
+    /// 서명된 리스 요청 (가격 제출과 같은 nonce 카운터 사용)
+    fn lease_request(&mut self, exchange: &str, ttl_secs: u64) -> Result<LeaseRequest> {
+        self.nonce += 1;
+        let payload = lease_request_payload(&self.node_id, exchange, ttl_secs, self.nonce);
+        let signature = self.keys.sign(NODE_KEY, &payload).context("Failed to sign lease request")?;
+        Ok(LeaseRequest {
+            node_id: self.node_id.clone(),
+            exchange: exchange.to_string(),
+            ttl_secs,
+            nonce: self.nonce,
+            signature: signature.to_string(),
+        })
+    }
+
+    /// 거래소 제출 리스 획득/갱신 (복제 노드 중 하나만 제출)
+    pub async fn acquire_lease(&mut self, exchange: &str, ttl_secs: u64) -> Result<LeaseResponse> {
+        let request = self.lease_request(exchange, ttl_secs)?;
+        let response = match self.client.acquire_lease(Request::new(request)).await {
+            Err(status) if status.code() == Code::Unavailable && self.endpoints.len() > 1 => {
+                warn!("❌ gRPC: Aggregator unavailable: {}", status);
+                self.failover().await?;
+                // 새 Aggregator에 맞는 nonce로 다시 서명
+                let request = self.lease_request(exchange, ttl_secs)?;
+                self.client.acquire_lease(Request::new(request)).await
+            }
+            response => response,
+        }
+        .context("Failed to acquire submission lease")?;
+
+        Ok(response.into_inner())
+    }
+
+    /// 임계 서명 라운드 조회 (`round_id` 0이면 가장 최근 열린 라운드)
+    pub async fn threshold_round(&mut self, round_id: u64) -> Result<ThresholdCommitResponse> {
+        let response = self
+            .client
+            .threshold_commit(Request::new(ThresholdCommitRequest {
+                node_id: self.node_id.clone(),
+                round_id,
+                commitment: None,
+                signature: String::new(),
+            }))
+            .await
+            .context("Failed to query threshold round")?;
+        Ok(response.into_inner())
+    }
+
+    /// 노드 키로 서명한 nonce 커밋먼트 제출
+    pub async fn threshold_commit(
+        &mut self,
+        round_id: u64,
+        signer: u16,
+        commitment: &SigningCommitments,
+    ) -> Result<ThresholdCommitResponse> {
+        let hiding = hex::encode(commitment.hiding().serialize()?);
+        let binding = hex::encode(commitment.binding().serialize()?);
+        let payload = threshold_commit_payload(&self.node_id, round_id, signer, &hiding, &binding);
+        let signature = self.keys.sign(NODE_KEY, &payload).context("Failed to sign nonce commitment")?;
+        let response = self
+            .client
+            .threshold_commit(Request::new(ThresholdCommitRequest {
+                node_id: self.node_id.clone(),
+                round_id,
+                commitment: Some(ThresholdNonceCommitment {
+                    signer: signer as u32,
+                    hiding,
+                    binding,
+                }),
+                signature: signature.to_string(),
+            }))
+            .await
+            .context("Failed to submit nonce commitment")?;
+        Ok(response.into_inner())
+    }
+
+    /// 서명 조각 제출
+    pub async fn threshold_sign(
+        &mut self,
+        round_id: u64,
+        signer: u16,
+        share: &SignatureShare,
+    ) -> Result<ThresholdSignResponse> {
+        let response = self
+            .client
+            .threshold_sign(Request::new(ThresholdSignRequest {
+                node_id: self.node_id.clone(),
+                round_id,
+                signer: signer as u32,
+                share: hex::encode(share.serialize()),
+            }))
+            .await
+            .context("Failed to submit signature share")?;
+        Ok(response.into_inner())
+    }
+
+    /// IV 곡면을 노드 키로 서명해 gRPC로 Aggregator에 전송 (nonce 1 증가)
+    pub async fn submit_vol_surface(&mut self, surface: &VolSurface) -> Result<()> {
+        let points: Vec<VolPoint> = surface
+            .quotes
+            .iter()
+            .map(|quote| VolPoint {
+                expiry: quote.expiry.timestamp() as u64,
+                strike: quote.strike as f64 / 100.0,
+                is_call: quote.option_type == OptionType::Call,
+                mark_iv: quote.mark_iv,
+            })
+            .collect();
+
+        let timestamp = surface.timestamp.timestamp() as u64;
+        let underlying_price = surface.underlying_price as f64 / 100.0;
+        self.nonce += 1;
+        let payload = vol_surface_payload(
+            &self.node_id,
+            &surface.source,
+            timestamp,
+            underlying_price,
+            points
+                .iter()
+                .map(|point| (point.expiry, point.strike, point.is_call, point.mark_iv)),
+            self.nonce,
+        );
+        let signature = self.keys.sign(NODE_KEY, &payload).context("Failed to sign IV surface")?;
+
+        let request = Request::new(VolSurfaceRequest {
+            source: surface.source.clone(),
+            node_id: self.node_id.clone(),
+            timestamp,
+            underlying_price,
+            points,
+            nonce: self.nonce,
+            signature: signature.to_string(),
+        });
+
+        info!(
+            "📤 Sending {} IV points to Aggregator via gRPC...",
+            surface.quotes.len()
+        );
+
+        let response = self
+            .client
+            .submit_vol_surface(request)
+            .await
+            .map_err(|e| anyhow::anyhow!("gRPC communication error: {}", e))?
+            .into_inner();
+
+        if !response.success {
+            warn!("❌ gRPC: Failed to submit IV surface: {}", response.message);
+            anyhow::bail!("Aggregator rejected IV surface: {}", response.message);
+        }
+
+        info!("✅ gRPC: IV surface sent successfully! {}", response.message);
+        Ok(())
+    }
+
     /// gRPC를 통한 Aggregator 헬스체크
     pub async fn check_health(&mut self) -> Result<bool> {
         let request = Request::new(HealthRequest {
@@ -127,7 +470,8 @@ mod tests {
     #[tokio::test]
     #[ignore] // 실제 gRPC 서버 필요
     async fn test_grpc_connection() {
-        let result = GrpcAggregatorClient::new("http://localhost:50051").await;
+        let keys = Arc::new(oracle_vm_common::crypto::MemoryKeyStore::ephemeral());
+        let result = GrpcAggregatorClient::new("http://localhost:50051", keys).await;
         // 연결 테스트는 서버가 실행 중일 때만 가능
         match result {
             Ok(_) => println!("gRPC connection successful"),
@@ -1,16 +1,22 @@
+use crate::backfill::KlineHistory;
 use crate::price_provider::PriceProvider;
-use oracle_vm_common::types::{PriceData, AssetPair};
+use oracle_vm_common::price::{cents_from_dollars, Rounding};
+use oracle_vm_common::types::PriceData;
+use oracle_vm_common::OracleError;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Timelike};
+use crate::exchange_auth::ExchangeAccess;
+use crate::symbols::{Market, TickerSource};
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{error, info, warn};
 
-/// Kraken API URL
-const KRAKEN_API_URL: &str = "https://api.kraken.com/0/public/OHLC";
+/// Kraken REST API 기본 주소 (OHLC: /0/public/OHLC)
+const KRAKEN_API_URL: &str = "https://api.kraken.com";
 /// 최대 재시도 횟수
 const MAX_RETRIES: u32 = 3;
 /// HTTP 요청 타임아웃 (초)
@@ -23,11 +29,13 @@ struct KrakenOHLCResponse {
     result: Option<KrakenResult>,
 }
 
+/// 결과 키는 요청 티커가 아닌 Kraken 내부 이름 (XBTUSD → XXBTZUSD)
 #[derive(Debug, Deserialize)]
 struct KrakenResult {
-    #[serde(rename = "XXBTZUSD")]
-    btc_usd: Vec<KrakenOHLC>,
+    #[allow(dead_code)]
     last: u64,
+    #[serde(flatten)]
+    pairs: HashMap<String, Vec<KrakenOHLC>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,6 +44,9 @@ struct KrakenOHLC(u64, String, String, String, String, String, String, u32); //
 /// Kraken과 통신하는 클라이언트
 pub struct KrakenClient {
     client: Client,
+    access: ExchangeAccess,
+    market: Market,
+    base_url: String,
 }
 
 impl KrakenClient {
@@ -47,7 +58,27 @@ impl KrakenClient {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client }
+        Self {
+            client,
+            access: ExchangeAccess::public("kraken"),
+            market: Market::btc_usd("kraken"),
+            base_url: KRAKEN_API_URL.to_string(),
+        }
+    }
+
+    /// API 키 인증/공유 속도 제한/REST 주소 설정 적용
+    pub fn with_access(mut self, access: ExchangeAccess) -> Self {
+        if let Some(base_url) = access.base_url() {
+            self.base_url = base_url.to_string();
+        }
+        self.access = access;
+        self
+    }
+
+    /// 수집할 페어/티커 (기본 BTC/USD = XBTUSD)
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
     }
 
     /// 비트코인 가격을 가져옵니다 (재시도 포함)
@@ -94,6 +125,28 @@ impl KrakenClient {
 
     /// 한 번만 가격을 가져오기 (재시도 없음)
     async fn fetch_btc_price_once(&self) -> Result<PriceData> {
+        let close = self.fetch_ohlc_close(self.market.ticker()).await?;
+        let close_price = self.market.to_usd(close).await?;
+
+        // 가격 검증
+        self.validate_price(close_price)?;
+
+        let timestamp = chrono::Utc::now().timestamp() as u64;
+
+        Ok(PriceData {
+            pair: self.market.pair.clone(),
+            price: cents_from_dollars(close_price, Rounding::HalfEven)
+                .with_context(|| format!("Invalid price: {}", close_price))?,
+            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
+                .unwrap_or_else(chrono::Utc::now),
+            volume: None,
+            source: "kraken".to_string(),
+            degraded: false,
+        })
+    }
+
+    /// `pair`의 최근 1분 OHLC 종가 (호가 통화 단위)
+    async fn fetch_ohlc_close(&self, pair: &str) -> Result<f64> {
         // 현재 시간에서 이전 완성된 분봉 시점 계산
         let now = chrono::Utc::now();
         // 현재 분의 00초로 맞추기 (예: 14:37:XX -> 14:37:00)
@@ -110,13 +163,12 @@ impl KrakenClient {
 
         // 1분 OHLC 데이터 요청 (특정 시점부터)
         let url = format!(
-            "{}?pair=XBTUSD&interval=1&since={}",
-            KRAKEN_API_URL, since_timestamp
+            "{}/0/public/OHLC?pair={}&interval=1&since={}",
+            self.base_url, pair, since_timestamp
         );
 
-        let response = self
-            .client
-            .get(&url)
+        let request = self.access.prepare(self.client.get(&url), "GET", &url).await?;
+        let response = request
             .send()
             .await
             .context("Failed to send request to Kraken")?;
@@ -139,12 +191,12 @@ impl KrakenClient {
             .result
             .ok_or_else(|| anyhow::anyhow!("No result data from Kraken"))?;
 
-        if result.btc_usd.is_empty() {
+        // 요청한 페어 하나만 돌려받음
+        let Some(latest_ohlc) = result.pairs.values().next().and_then(|ohlc| ohlc.last()) else {
             anyhow::bail!("No OHLC data received from Kraken");
-        }
+        };
 
         // 가장 최근 OHLC의 종가 사용
-        let latest_ohlc = &result.btc_usd[result.btc_usd.len() - 1];
         let timestamp = latest_ohlc.0; // timestamp
         let close_price = latest_ohlc
             .4
@@ -155,33 +207,62 @@ impl KrakenClient {
         let ohlc_time = chrono::DateTime::from_timestamp(timestamp as i64, 0).unwrap_or_default();
 
         info!(
-            "📊 Kraken OHLC: {:.2} USD (time: {})",
+            "📊 Kraken OHLC {}: {:.2} (time: {})",
+            pair,
             close_price,
             ohlc_time.format("%H:%M:%S")
         );
 
-        // 가격 검증
-        self.validate_price(close_price)?;
+        Ok(close_price)
+    }
 
-        let timestamp = chrono::Utc::now().timestamp() as u64;
+    /// `[from, to)` 구간에 시작하는 1분봉 종가 (시작 시각 → USD, 백필용)
+    ///
+    /// Kraken OHLC는 최근 720개 분봉만 돌려주므로 12시간보다 오래된 구간은 비어 있습니다.
+    async fn fetch_minute_closes(&self, from: u64, to: u64) -> Result<BTreeMap<u64, f64>> {
+        let url = format!(
+            "{}/0/public/OHLC?pair={}&interval=1&since={}",
+            self.base_url,
+            self.market.ticker(),
+            from.saturating_sub(1)
+        );
+        let request = self.access.prepare(self.client.get(&url), "GET", &url).await?;
+        let response = request
+            .send()
+            .await
+            .context("Failed to send request to Kraken")?;
+        if !response.status().is_success() {
+            return self.handle_http_error(response.status().as_u16());
+        }
+        let kraken_response: KrakenOHLCResponse = response
+            .json()
+            .await
+            .context("Failed to parse Kraken JSON response")?;
+        if !kraken_response.error.is_empty() {
+            anyhow::bail!("Kraken API error: {:?}", kraken_response.error);
+        }
+        let result = kraken_response
+            .result
+            .ok_or_else(|| anyhow::anyhow!("No result data from Kraken"))?;
 
-        Ok(PriceData {
-            pair: AssetPair::btc_usd(),
-            price: (close_price * 100.0) as u64, // Convert to cents
-            timestamp: DateTime::from_timestamp(timestamp as i64, 0)
-                .unwrap_or_else(chrono::Utc::now),
-            volume: None,
-            source: "kraken".to_string(),
-        })
+        let mut closes = BTreeMap::new();
+        for ohlc in result.pairs.values().flatten() {
+            if ohlc.0 < from || ohlc.0 >= to {
+                continue;
+            }
+            let close = ohlc.4.parse::<f64>().context("Failed to parse close price from Kraken")?;
+            closes.insert(ohlc.0, self.market.to_usd(close).await?);
+        }
+        Ok(closes)
     }
 
     /// HTTP 에러를 처리합니다
-    fn handle_http_error(&self, status_code: u16) -> Result<PriceData> {
+    fn handle_http_error<T>(&self, status_code: u16) -> Result<T> {
         match status_code {
             400 => anyhow::bail!("Bad request - Check API parameters"),
             401 => anyhow::bail!("Unauthorized - API key issue"),
             403 => anyhow::bail!("Forbidden - Access denied"),
-            404 => anyhow::bail!("Not found - Check pair (XBTUSD)"),
+            404 => anyhow::bail!("Not found - Check pair ({})", self.market.ticker()),
             429 => anyhow::bail!("Rate limit exceeded - Too many requests"),
             500..=599 => anyhow::bail!("Kraken server error - Try again later"),
             _ => anyhow::bail!("HTTP error: {}", status_code),
@@ -212,7 +293,7 @@ mod tests {
 
     #[test]
     fn test_client_creation() {
-        let client = KrakenClient::new();
+        let _client = KrakenClient::new();
         // 클라이언트가 성공적으로 생성되는지 확인
     }
 
@@ -236,9 +317,9 @@ mod tests {
 
         match result {
             Ok(price_data) => {
-                assert!(price_data.price > 0.0);
+                assert!(price_data.price > 0);
                 assert_eq!(price_data.source, "kraken");
-                println!("Real BTC price from Kraken: ${:.2}", price_data.price);
+                println!("Real BTC price from Kraken: ${:.2}", price_data.price as f64 / 100.0);
             }
             Err(e) => {
                 println!("Kraken API call failed (this might be expected): {}", e);
@@ -249,11 +330,34 @@ mod tests {
 
 #[async_trait]
 impl PriceProvider for KrakenClient {
-    async fn fetch_btc_price(&self) -> Result<PriceData> {
-        self.fetch_btc_price_with_retry(MAX_RETRIES).await
+    async fn fetch_btc_price(&self) -> Result<PriceData, OracleError> {
+        self.fetch_btc_price_with_retry(MAX_RETRIES)
+            .await
+            .map_err(|e| OracleError::Exchange {
+                source_name: "kraken".to_string(),
+                message: e.to_string(),
+            })
     }
     
     fn name(&self) -> &str {
         "kraken"
     }
 }
+
+#[async_trait]
+impl KlineHistory for KrakenClient {
+    async fn minute_closes(&self, from: u64, to: u64) -> Result<BTreeMap<u64, f64>> {
+        self.fetch_minute_closes(from, to).await
+    }
+
+    fn name(&self) -> &str {
+        "kraken"
+    }
+}
+
+#[async_trait]
+impl TickerSource for KrakenClient {
+    async fn fetch_close(&self, ticker: &str) -> Result<f64> {
+        self.fetch_ohlc_close(ticker).await
+    }
+}
@@ -1,9 +1,15 @@
+pub mod backfill;
 pub mod binance;
 pub mod coinbase;
+pub mod deribit;
+pub mod exchange_auth;
+pub mod failover;
 pub mod grpc_client;
 pub mod kraken;
 pub mod safe_price;
 pub mod price_provider;
+pub mod symbols;
+pub mod threshold;
 pub mod consensus;
 
 use anyhow::Result;
@@ -3,30 +3,84 @@ use chrono::{Timelike, Utc};
 use clap::Parser;
 use std::time::Duration;
 use tokio::time::interval;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
+mod backfill;
 mod binance;
 mod coinbase;
+mod deribit;
+mod exchange_auth;
+mod failover;
 mod grpc_client;
 mod kraken;
 mod safe_price;
 mod price_provider;
+mod symbols;
+mod threshold;
 
+use backfill::KlineHistory;
 use binance::BinanceClient;
 use coinbase::CoinbaseClient;
+use deribit::DeribitClient;
+use exchange_auth::{ExchangeAccess, RateLimiters};
+use failover::{BackoffConfig, FailoverProvider};
 use grpc_client::GrpcAggregatorClient;
 use kraken::KrakenClient;
 use price_provider::PriceProvider;
+use symbols::{Market, QuoteConverter, SymbolRegistry, SymbolsConfig, TickerSource};
+use threshold::{RoundStep, ThresholdParticipant};
+use std::sync::Arc;
 
 // PriceData는 oracle_vm_common::types에서 가져옴
 use oracle_vm_common::types::PriceData;
+use oracle_vm_common::config::ExchangesConfig;
+use oracle_vm_common::types::AssetPair;
+use oracle_vm_common::crypto::{open_service_key_store, KeyStore};
+use oracle_vm_common::{Shutdown, ShutdownSignal};
+
+/// 종료 시 진행 중인 IV 곡면 제출을 기다리는 최대 시간
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 설정 파일 중 노드가 읽는 섹션
+#[derive(Debug, Default, serde::Deserialize)]
+struct NodeFileConfig {
+    #[serde(default)]
+    exchanges: ExchangesConfig,
+    #[serde(default)]
+    symbols: SymbolsConfig,
+}
+
+/// 설정 파일 로드 (없으면 기본값: 공개 API, 기본 속도 제한)
+fn load_node_config(path: &str) -> Result<NodeFileConfig> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(toml::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            warn!("Config file {} not found, using public exchange APIs", path);
+            Ok(NodeFileConfig::default())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
 
 /// 거래소 클라이언트 생성 헬퍼
-fn create_exchange_provider(exchange: &str) -> Result<Box<dyn PriceProvider>> {
+fn create_exchange_provider(
+    exchange: &str,
+    config: &ExchangesConfig,
+    limiters: &RateLimiters,
+    market: Market,
+) -> Result<Box<dyn PriceProvider>> {
+    let access = ExchangeAccess::from_config(exchange, config, limiters);
+    if access.is_authenticated() {
+        info!("🔑 Using API credentials for {}", exchange);
+    }
+    if let Some(base_url) = access.base_url() {
+        info!("🧪 {} REST endpoint overridden: {}", exchange, base_url);
+    }
+    info!("{} {} → {}", exchange, market.pair.as_str(), market.ticker());
     match exchange.to_lowercase().as_str() {
-        "binance" => Ok(Box::new(BinanceClient::new())),
-        "coinbase" => Ok(Box::new(CoinbaseClient::new())),
-        "kraken" => Ok(Box::new(KrakenClient::new())),
+        "binance" => Ok(Box::new(BinanceClient::new().with_access(access).with_market(market))),
+        "coinbase" => Ok(Box::new(CoinbaseClient::new().with_access(access).with_market(market))),
+        "kraken" => Ok(Box::new(KrakenClient::new().with_access(access).with_market(market))),
         _ => anyhow::bail!(
             "Unsupported exchange: {}. Supported: binance, coinbase, kraken",
             exchange
@@ -34,6 +88,36 @@ fn create_exchange_provider(exchange: &str) -> Result<Box<dyn PriceProvider>> {
     }
 }
 
+/// 백필용 과거 분봉 소스 (Coinbase는 아직 지원하지 않음)
+fn create_kline_history(
+    exchange: &str,
+    config: &ExchangesConfig,
+    limiters: &RateLimiters,
+    market: Market,
+) -> Result<Box<dyn KlineHistory>> {
+    let access = ExchangeAccess::from_config(exchange, config, limiters);
+    match exchange.to_lowercase().as_str() {
+        "binance" => Ok(Box::new(BinanceClient::new().with_access(access).with_market(market))),
+        "kraken" => Ok(Box::new(KrakenClient::new().with_access(access).with_market(market))),
+        _ => anyhow::bail!("Backfill is not supported for {}. Supported: binance, kraken", exchange),
+    }
+}
+
+/// 호가 통화 환율 소스용 거래소 클라이언트 (같은 속도 제한 버킷 공유)
+fn create_ticker_source(
+    exchange: &str,
+    config: &ExchangesConfig,
+    limiters: &RateLimiters,
+) -> Result<Arc<dyn TickerSource>> {
+    let access = ExchangeAccess::from_config(exchange, config, limiters);
+    match exchange {
+        "binance" => Ok(Arc::new(BinanceClient::new().with_access(access))),
+        "coinbase" => Ok(Arc::new(CoinbaseClient::new().with_access(access))),
+        "kraken" => Ok(Arc::new(KrakenClient::new().with_access(access))),
+        _ => anyhow::bail!("Unsupported conversion source exchange: {}", exchange),
+    }
+}
+
 /// Oracle Node CLI 인수
 #[derive(Parser)]
 #[command(name = "oracle-node")]
@@ -47,7 +131,7 @@ struct Args {
     #[arg(long)]
     node_id: Option<String>,
 
-    /// Aggregator URL (설정 파일보다 우선)
+    /// Aggregator URL (설정 파일보다 우선, 쉼표로 primary,standby 순서 지정 가능)
     #[arg(long, default_value = "http://localhost:50051")]
     aggregator_url: String,
 
@@ -58,6 +142,76 @@ struct Args {
     /// 거래소 선택 (binance, coinbase, kraken)
     #[arg(long, default_value = "binance")]
     exchange: String,
+
+    /// 수집할 정식 페어 (거래소 티커는 설정의 [symbols]에서 찾음)
+    #[arg(long, default_value = "BTC/USD")]
+    pair: String,
+
+    /// 주 거래소 실패 시 순서대로 시도할 대체 거래소 (쉼표 구분)
+    #[arg(long, value_delimiter = ',')]
+    fallbacks: Vec<String>,
+
+    /// Deribit 옵션 IV 곡면 수집 활성화
+    #[arg(long)]
+    iv_feed: bool,
+
+    /// IV 곡면 수집 간격 (초)
+    #[arg(long, default_value = "300")]
+    iv_interval: u64,
+
+    /// 같은 거래소를 수집하는 복제 노드와 리스로 조정 (리스 보유 노드만 제출)
+    #[arg(long)]
+    coordinate: bool,
+
+    /// 제출 리스 유효시간 (초), 리더 장애 시 이 시간 후 대기 노드가 인계
+    #[arg(long, default_value = "180")]
+    lease_ttl: u64,
+
+    /// 노드 서명 키 저장소 (env[:PREFIX] | file:DIR | vault[:MOUNT], 없으면 --dev에서만 실행마다 새 키)
+    #[arg(long)]
+    key_store: Option<String>,
+
+    /// 로컬 개발 모드: 키 저장소 없이 실행마다 새 키로 서명 (재시작하면 노드 키가 바뀜)
+    #[arg(long)]
+    dev: bool,
+
+    /// 시작 시 최근 N분 중 제출이 없는 분을 거래소 과거 분봉으로 백필 (0이면 끔)
+    #[arg(long, default_value = "0")]
+    backfill_minutes: u64,
+
+    /// DKG 키 조각 파일 (JSON, 있으면 수집 주기마다 합의 증명 임계 서명 라운드에 참여, --node-id 필요)
+    #[arg(long)]
+    threshold_share: Option<String>,
+}
+
+/// Deribit IV 곡면을 주기적으로 수집하여 Aggregator에 전송 (종료 신호까지)
+async fn run_iv_feed(
+    aggregator_url: String,
+    keys: Arc<dyn KeyStore>,
+    interval_secs: u64,
+    mut shutdown: ShutdownSignal,
+) -> Result<()> {
+    let deribit = DeribitClient::new();
+    let mut grpc_client = GrpcAggregatorClient::new(&aggregator_url, keys).await?;
+    let mut interval = interval(Duration::from_secs(interval_secs));
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.recv() => return Ok(()),
+        }
+        let Some(_work) = shutdown.begin_work() else {
+            return Ok(());
+        };
+
+        match deribit.fetch_vol_surface().await {
+            Ok(surface) => match grpc_client.submit_vol_surface(&surface).await {
+                Ok(_) => info!("✅ Successfully sent IV surface to gRPC aggregator"),
+                Err(e) => error!("❌ Failed to send IV surface to gRPC aggregator: {}", e),
+            },
+            Err(e) => warn!("Failed to fetch Deribit IV surface: {}", e),
+        }
+    }
 }
 
 #[tokio::main]
@@ -66,18 +220,75 @@ async fn main() -> Result<()> {
     let args = Args::parse();
 
     // Initialize logging
-    tracing_subscriber::fmt::init();
+    let _tracing = oracle_vm_common::init_tracing("oracle-node");
+
+    // Ctrl-C / SIGTERM: 현재 수집 주기를 마치고 종료
+    let shutdown = Shutdown::new();
+    shutdown.listen_for_signals();
+    let mut signal = shutdown.signal();
 
     info!("Starting Oracle Node with config: {}", args.config);
     info!("Aggregator URL: {}", args.aggregator_url);
     info!("Exchange: {}", args.exchange);
     info!("Fetch interval: {}s", args.interval);
+    if args.coordinate {
+        info!("Coordination: lease-based (ttl {}s)", args.lease_ttl);
+    }
+
+    // Create exchange provider chain (primary, then fallbacks)
+    // 같은 거래소 클라이언트는 하나의 속도 제한 버킷을 공유
+    let node_config = load_node_config(&args.config)?;
+    let limiters = RateLimiters::new();
+    let registry = SymbolRegistry::with_config(&node_config.symbols)?;
+    let mut converter = QuoteConverter::new(&registry);
+    for exchange in converter.required_sources() {
+        let source = create_ticker_source(&exchange, &node_config.exchanges, &limiters)?;
+        converter = converter.with_source(&exchange, source);
+    }
+    let converter = Arc::new(converter);
+    let pair = AssetPair(args.pair.to_uppercase());
+    let market = |exchange: &str| -> Result<Market> {
+        let symbol = registry.symbol(exchange, &pair)?.clone();
+        if let Some(source) = registry.conversion(&symbol.quote) {
+            info!("{} quotes {} in {}, converted via {:?}", exchange, pair.as_str(), symbol.quote, source);
+        }
+        Ok(Market::new(pair.clone(), symbol, converter.clone()))
+    };
 
-    // Create exchange provider based on CLI argument
-    let exchange_provider = create_exchange_provider(&args.exchange)?;
+    let mut providers = vec![create_exchange_provider(
+        &args.exchange,
+        &node_config.exchanges,
+        &limiters,
+        market(&args.exchange)?,
+    )?];
+    for fallback in &args.fallbacks {
+        providers.push(create_exchange_provider(
+            fallback,
+            &node_config.exchanges,
+            &limiters,
+            market(fallback)?,
+        )?);
+    }
+    if !args.fallbacks.is_empty() {
+        info!("Fallback exchanges: {}", args.fallbacks.join(", "));
+    }
+    let exchange_provider = FailoverProvider::new(providers, BackoffConfig::default());
 
     // Create gRPC Aggregator client
-    let mut grpc_client = GrpcAggregatorClient::new(&args.aggregator_url).await?;
+    let keys = open_service_key_store(args.key_store.as_deref(), args.dev)?;
+    let mut grpc_client =
+        GrpcAggregatorClient::with_node_id(&args.aggregator_url, args.node_id.as_deref(), keys.clone()).await?;
+
+    // 임계 서명 참여 (Aggregator 허용 목록이 node_id에 서명자 번호를 배정)
+    let mut threshold = match &args.threshold_share {
+        Some(_) if args.node_id.is_none() => anyhow::bail!("--threshold-share requires --node-id"),
+        Some(path) => {
+            let participant = ThresholdParticipant::load(path)?;
+            info!("🔏 Threshold signing as signer {}", participant.signer());
+            Some(participant)
+        }
+        None => None,
+    };
 
     // Check if gRPC Aggregator is healthy
     match grpc_client.check_health().await {
@@ -90,6 +301,35 @@ async fn main() -> Result<()> {
         }
     }
 
+    // 중단 구간 백필 (현재 분은 실시간 수집이 채움)
+    if args.backfill_minutes > 0 {
+        let to = Utc::now().timestamp() as u64 / 60 * 60;
+        let from = to.saturating_sub(args.backfill_minutes * 60);
+        let history = create_kline_history(&args.exchange, &node_config.exchanges, &limiters, market(&args.exchange)?);
+        match history {
+            Ok(history) => {
+                if let Err(e) = backfill::backfill(&mut grpc_client, history.as_ref(), &pair, from, to).await {
+                    error!("❌ Backfill failed: {}", e);
+                }
+            }
+            Err(e) => warn!("⚠️ {}", e),
+        }
+    }
+
+    // Start Deribit IV surface feed in background
+    if args.iv_feed {
+        info!("IV feed: Deribit every {}s", args.iv_interval);
+        let aggregator_url = args.aggregator_url.clone();
+        let iv_interval = args.iv_interval;
+        let iv_shutdown = shutdown.signal();
+        let iv_keys = keys.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_iv_feed(aggregator_url, iv_keys, iv_interval, iv_shutdown).await {
+                error!("❌ IV feed stopped: {}", e);
+            }
+        });
+    }
+
     // Calculate next minute boundary (00 seconds)
     let now = Utc::now();
     let seconds_to_wait = 60 - now.second();
@@ -104,15 +344,23 @@ async fn main() -> Result<()> {
     );
 
     // Wait until the next minute boundary (XX:XX:00)
-    tokio::time::sleep(Duration::from_secs(seconds_to_wait as u64)).await;
+    tokio::select! {
+        _ = tokio::time::sleep(Duration::from_secs(seconds_to_wait as u64)) => {}
+        _ = signal.recv() => {
+            info!("🛑 Shutdown requested before first collection");
+            return Ok(());
+        }
+    }
 
-    // Create interval for subsequent collections
+    // Create interval for collections (the first tick fires immediately)
     let mut interval = interval(Duration::from_secs(args.interval));
 
-    // Skip the first tick (which would fire immediately)
-    interval.tick().await;
-
     loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = signal.recv() => break,
+        }
+
         // Collect price at synchronized time
         let collection_time = Utc::now();
         info!(
@@ -122,11 +370,46 @@ async fn main() -> Result<()> {
             collection_time.second()
         );
 
+        // 열린 임계 서명 라운드가 있으면 커밋/서명 (리스 대기 노드도 참여)
+        if let Some(participant) = threshold.as_mut() {
+            match participant.step(&mut grpc_client).await {
+                Ok(RoundStep::Committed(round_id)) => info!("🔏 Committed nonces for threshold round {}", round_id),
+                Ok(RoundStep::Signed { round_id, complete }) => info!(
+                    "🔏 Signed threshold round {}{}",
+                    round_id,
+                    if complete { " (group signature complete)" } else { "" }
+                ),
+                Ok(RoundStep::Idle) | Ok(RoundStep::Skipped(_)) => {}
+                Err(e) => error!("❌ Threshold signing failed: {:#}", e),
+            }
+        }
+
+        // 조정 모드: 리스를 보유한 노드만 제출, 나머지는 대기
+        if args.coordinate {
+            match grpc_client.acquire_lease(&args.exchange.to_lowercase(), args.lease_ttl).await {
+                Ok(lease) if lease.granted => {}
+                Ok(lease) => {
+                    info!(
+                        "⏸️ Standby: {} holds {} lease until {}",
+                        lease.holder, args.exchange, lease.expires_at
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    error!("❌ Failed to acquire lease: {}", e);
+                    continue;
+                }
+            }
+        }
+
         match exchange_provider.fetch_btc_price().await {
             Ok(price_data) => {
                 info!(
-                    "Fetched BTC price: ${:.2} at timestamp: {}",
-                    price_data.price, price_data.timestamp
+                    "Fetched BTC price: ${:.2} at timestamp: {} from {}{}",
+                    price_data.price,
+                    price_data.timestamp,
+                    price_data.source,
+                    if price_data.degraded { " (degraded)" } else { "" }
                 );
 
                 // Send to gRPC aggregator
@@ -139,8 +422,12 @@ async fn main() -> Result<()> {
                 error!("Failed to fetch price: {}", e);
             }
         }
+    }
 
-        // Wait for next interval
-        interval.tick().await;
+    info!("🛑 Shutting down, waiting for in-flight submissions...");
+    if !shutdown.drain(DRAIN_TIMEOUT).await {
+        warn!("⚠️ {} submissions still in flight after {:?}", shutdown.in_flight(), DRAIN_TIMEOUT);
     }
+    info!("👋 Oracle Node stopped");
+    Ok(())
 }
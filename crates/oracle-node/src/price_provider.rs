@@ -1,12 +1,12 @@
-use anyhow::Result;
 use async_trait::async_trait;
 use oracle_vm_common::types::PriceData;
+use oracle_vm_common::OracleError;
 
 /// Price provider trait for different exchanges
 #[async_trait]
 pub trait PriceProvider: Send + Sync {
     /// Fetch the current BTC price
-    async fn fetch_btc_price(&self) -> Result<PriceData>;
+    async fn fetch_btc_price(&self) -> Result<PriceData, OracleError>;
     
     /// Get the name of the exchange
     fn name(&self) -> &str;
@@ -23,7 +23,7 @@ impl MultiExchangePriceProvider {
     }
     
     /// Fetch prices from all providers
-    pub async fn fetch_all_prices(&self) -> Vec<(String, Result<PriceData>)> {
+    pub async fn fetch_all_prices(&self) -> Vec<(String, Result<PriceData, OracleError>)> {
         let mut results = Vec::new();
         
         for provider in &self.providers {
@@ -49,14 +49,27 @@ impl MultiExchangePriceProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::DateTime;
     use mockall::{mock, predicate::*};
+    use oracle_vm_common::types::AssetPair;
+
+    fn price_data(source: &str, price: u64, timestamp: i64) -> PriceData {
+        PriceData {
+            pair: AssetPair::btc_usd(),
+            price,
+            timestamp: DateTime::from_timestamp(timestamp, 0).unwrap(),
+            volume: None,
+            source: source.to_string(),
+            degraded: false,
+        }
+    }
     
     mock! {
         Provider {}
         
         #[async_trait]
         impl PriceProvider for Provider {
-            async fn fetch_btc_price(&self) -> Result<PriceData>;
+            async fn fetch_btc_price(&self) -> Result<PriceData, OracleError>;
             fn name(&self) -> &str;
         }
     }
@@ -70,20 +83,12 @@ mod tests {
         mock1.expect_name().return_const("Exchange1".to_string());
         mock1.expect_fetch_btc_price()
             .times(1)
-            .returning(|| Ok(PriceData {
-                price: 70000.0,
-                timestamp: 1700000000,
-                source: "Exchange1".to_string(),
-            }));
+            .returning(|| Ok(price_data("Exchange1", 7_000_000, 1700000000)));
             
         mock2.expect_name().return_const("Exchange2".to_string());
         mock2.expect_fetch_btc_price()
             .times(1)
-            .returning(|| Ok(PriceData {
-                price: 70100.0,
-                timestamp: 1700000001,
-                source: "Exchange2".to_string(),
-            }));
+            .returning(|| Ok(price_data("Exchange2", 7_010_000, 1700000001)));
         
         let provider = MultiExchangePriceProvider::new(vec![
             Box::new(mock1),
@@ -95,8 +100,8 @@ mod tests {
         
         // Then
         assert_eq!(prices.len(), 2);
-        assert_eq!(prices[0].price, 70000.0);
-        assert_eq!(prices[1].price, 70100.0);
+        assert_eq!(prices[0].price, 7_000_000);
+        assert_eq!(prices[1].price, 7_010_000);
     }
     
     #[tokio::test]
@@ -108,16 +113,15 @@ mod tests {
         mock1.expect_name().return_const("Exchange1".to_string());
         mock1.expect_fetch_btc_price()
             .times(1)
-            .returning(|| Err(anyhow::anyhow!("Network error")));
+            .returning(|| Err(OracleError::Exchange {
+                source_name: "Exchange1".to_string(),
+                message: "Network error".to_string(),
+            }));
             
         mock2.expect_name().return_const("Exchange2".to_string());
         mock2.expect_fetch_btc_price()
             .times(1)
-            .returning(|| Ok(PriceData {
-                price: 70100.0,
-                timestamp: 1700000001,
-                source: "Exchange2".to_string(),
-            }));
+            .returning(|| Ok(price_data("Exchange2", 7_010_000, 1700000001)));
         
         let provider = MultiExchangePriceProvider::new(vec![
             Box::new(mock1),
@@ -129,6 +133,6 @@ mod tests {
         
         // Then - Only successful price is returned
         assert_eq!(prices.len(), 1);
-        assert_eq!(prices[0].price, 70100.0);
+        assert_eq!(prices[0].price, 7_010_000);
     }
 }
\ No newline at end of file
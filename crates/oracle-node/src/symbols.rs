@@ -0,0 +1,317 @@
+//! 거래소별 심볼 정규화
+//!
+//! 정식 페어(`BTC/USD`)를 거래소 티커(`BTCUSDT`, `BTC-USD`, `XBTUSD`)로 바꾸고,
+//! 티커의 호가 통화가 USD가 아니면(USDT 등) 설정된 환율 소스로 USD로 환산합니다.
+//! 기본 BTC/USD 매핑은 내장되어 있고, 새 페어는 설정 파일만으로 추가합니다.
+//!
+//! ```toml
+//! [symbols.pairs."ETH/USD"]
+//! binance = { ticker = "ETHUSDT", quote = "USDT" }
+//! coinbase = { ticker = "ETH-USD" }
+//! kraken = { ticker = "ETHUSD" }
+//!
+//! # USDT→USD 환율: 고정값(rate = 1.0) 또는 거래소 시세
+//! [symbols.conversions.USDT]
+//! exchange = "kraken"
+//! ticker = "USDTZUSD"
+//! ```
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use oracle_vm_common::types::AssetPair;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 가격 기준 통화
+pub const USD: &str = "USD";
+
+fn usd() -> String {
+    USD.to_string()
+}
+
+/// 거래소 티커와 그 호가 통화
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ExchangeSymbol {
+    pub ticker: String,
+    #[serde(default = "usd")]
+    pub quote: String,
+}
+
+impl ExchangeSymbol {
+    pub fn new(ticker: &str, quote: &str) -> Self {
+        Self {
+            ticker: ticker.to_string(),
+            quote: quote.to_uppercase(),
+        }
+    }
+}
+
+/// 호가 통화 → USD 환율 소스
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ConversionSource {
+    /// 고정 환율
+    Fixed { rate: f64 },
+    /// 거래소 티커 종가 (예: kraken USDTZUSD)
+    Exchange { exchange: String, ticker: String },
+}
+
+/// 설정 파일 `[symbols]` 섹션 (내장 매핑 위에 덮어씀)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SymbolsConfig {
+    /// 정식 페어 → 거래소 → 티커
+    pub pairs: HashMap<String, HashMap<String, ExchangeSymbol>>,
+    /// 호가 통화 → 환율 소스
+    pub conversions: HashMap<String, ConversionSource>,
+}
+
+/// 정식 페어 ↔ 거래소 티커 매핑
+#[derive(Debug, Clone)]
+pub struct SymbolRegistry {
+    pairs: HashMap<AssetPair, HashMap<String, ExchangeSymbol>>,
+    conversions: HashMap<String, ConversionSource>,
+}
+
+impl SymbolRegistry {
+    /// 내장 매핑: BTC/USD, USDT는 1 USD로 간주
+    pub fn builtin() -> Self {
+        let btc_usd = HashMap::from([
+            ("binance".to_string(), ExchangeSymbol::new("BTCUSDT", "USDT")),
+            ("coinbase".to_string(), ExchangeSymbol::new("BTC-USD", USD)),
+            ("kraken".to_string(), ExchangeSymbol::new("XBTUSD", USD)),
+        ]);
+        Self {
+            pairs: HashMap::from([(AssetPair::btc_usd(), btc_usd)]),
+            conversions: HashMap::from([("USDT".to_string(), ConversionSource::Fixed { rate: 1.0 })]),
+        }
+    }
+
+    /// 내장 매핑에 설정을 덮어쓰고 검증
+    pub fn with_config(config: &SymbolsConfig) -> Result<Self> {
+        let mut registry = Self::builtin();
+        for (pair, symbols) in &config.pairs {
+            let entry = registry.pairs.entry(AssetPair(pair.to_uppercase())).or_default();
+            for (exchange, symbol) in symbols {
+                let symbol = ExchangeSymbol::new(&symbol.ticker, &symbol.quote);
+                entry.insert(exchange.to_lowercase(), symbol);
+            }
+        }
+        for (quote, source) in &config.conversions {
+            registry.conversions.insert(quote.to_uppercase(), source.clone());
+        }
+        registry.validate()?;
+        Ok(registry)
+    }
+
+    /// USD 외 호가 통화는 모두 환율 소스가 있어야 함
+    pub fn validate(&self) -> Result<()> {
+        for (pair, symbols) in &self.pairs {
+            for (exchange, symbol) in symbols {
+                if symbol.quote != USD && !self.conversions.contains_key(&symbol.quote) {
+                    anyhow::bail!(
+                        "{} {} ({}) is quoted in {} but no conversion to USD is configured",
+                        exchange,
+                        pair.as_str(),
+                        symbol.ticker,
+                        symbol.quote
+                    );
+                }
+            }
+        }
+        for (quote, source) in &self.conversions {
+            if let ConversionSource::Fixed { rate } = source {
+                if !(*rate > 0.0 && rate.is_finite()) {
+                    anyhow::bail!("Invalid {}→USD rate: {}", quote, rate);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `exchange`에서 `pair`를 조회할 티커
+    pub fn symbol(&self, exchange: &str, pair: &AssetPair) -> Result<&ExchangeSymbol> {
+        self.pairs
+            .get(pair)
+            .and_then(|symbols| symbols.get(&exchange.to_lowercase()))
+            .with_context(|| format!("No {} ticker configured for {}", exchange, pair.as_str()))
+    }
+
+    pub fn conversion(&self, quote: &str) -> Option<&ConversionSource> {
+        self.conversions.get(&quote.to_uppercase())
+    }
+}
+
+/// 티커 종가 조회 (환율 소스용)
+#[async_trait]
+pub trait TickerSource: Send + Sync {
+    /// 직전 1분봉 종가 (호가 통화 단위)
+    async fn fetch_close(&self, ticker: &str) -> Result<f64>;
+}
+
+/// 호가 통화 → USD 환산기
+pub struct QuoteConverter {
+    conversions: HashMap<String, ConversionSource>,
+    sources: HashMap<String, Arc<dyn TickerSource>>,
+}
+
+impl QuoteConverter {
+    pub fn new(registry: &SymbolRegistry) -> Self {
+        Self {
+            conversions: registry.conversions.clone(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// 거래소 환율 소스에 쓸 클라이언트 등록
+    pub fn with_source(mut self, exchange: &str, source: Arc<dyn TickerSource>) -> Self {
+        self.sources.insert(exchange.to_lowercase(), source);
+        self
+    }
+
+    /// 거래소 환율 소스가 참조하는 거래소 목록
+    pub fn required_sources(&self) -> Vec<String> {
+        let mut exchanges: Vec<String> = self
+            .conversions
+            .values()
+            .filter_map(|source| match source {
+                ConversionSource::Exchange { exchange, .. } => Some(exchange.to_lowercase()),
+                ConversionSource::Fixed { .. } => None,
+            })
+            .collect();
+        exchanges.sort();
+        exchanges.dedup();
+        exchanges
+    }
+
+    /// 1 `quote` = ? USD
+    pub async fn usd_rate(&self, quote: &str) -> Result<f64> {
+        let quote = quote.to_uppercase();
+        if quote == USD {
+            return Ok(1.0);
+        }
+        match self.conversions.get(&quote) {
+            Some(ConversionSource::Fixed { rate }) => Ok(*rate),
+            Some(ConversionSource::Exchange { exchange, ticker }) => {
+                let source = self
+                    .sources
+                    .get(&exchange.to_lowercase())
+                    .with_context(|| format!("{}→USD source {} is not available", quote, exchange))?;
+                let rate = source.fetch_close(ticker).await?;
+                if !(rate > 0.0 && rate.is_finite()) {
+                    anyhow::bail!("Invalid {}→USD rate from {}: {}", quote, exchange, rate);
+                }
+                Ok(rate)
+            }
+            None => anyhow::bail!("No conversion from {} to USD", quote),
+        }
+    }
+}
+
+/// 한 거래소 클라이언트가 수집하는 페어
+#[derive(Clone)]
+pub struct Market {
+    pub pair: AssetPair,
+    pub symbol: ExchangeSymbol,
+    converter: Arc<QuoteConverter>,
+}
+
+impl Market {
+    pub fn new(pair: AssetPair, symbol: ExchangeSymbol, converter: Arc<QuoteConverter>) -> Self {
+        Self {
+            pair,
+            symbol,
+            converter,
+        }
+    }
+
+    /// 내장 매핑의 BTC/USD (클라이언트 기본값)
+    pub fn btc_usd(exchange: &str) -> Self {
+        let registry = SymbolRegistry::builtin();
+        let pair = AssetPair::btc_usd();
+        let symbol = registry
+            .symbol(exchange, &pair)
+            .cloned()
+            .unwrap_or_else(|_| ExchangeSymbol::new("BTCUSD", USD));
+        Self::new(pair, symbol, Arc::new(QuoteConverter::new(&registry)))
+    }
+
+    pub fn ticker(&self) -> &str {
+        &self.symbol.ticker
+    }
+
+    /// 티커 호가 통화 가격을 USD로 환산
+    pub async fn to_usd(&self, price: f64) -> Result<f64> {
+        Ok(price * self.converter.usd_rate(&self.symbol.quote).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedTicker(f64);
+
+    #[async_trait]
+    impl TickerSource for FixedTicker {
+        async fn fetch_close(&self, _ticker: &str) -> Result<f64> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn test_config_adds_pairs_without_code() {
+        let config: SymbolsConfig = toml::from_str(
+            r#"
+            [pairs."eth/usd"]
+            binance = { ticker = "ETHUSDT", quote = "usdt" }
+            Kraken = { ticker = "ETHUSD" }
+            "#,
+        )
+        .unwrap();
+        let registry = SymbolRegistry::with_config(&config).unwrap();
+        let eth = AssetPair("ETH/USD".to_string());
+
+        assert_eq!(registry.symbol("binance", &eth).unwrap(), &ExchangeSymbol::new("ETHUSDT", "USDT"));
+        assert_eq!(registry.symbol("kraken", &eth).unwrap().quote, USD);
+        assert!(registry.symbol("coinbase", &eth).is_err());
+        assert_eq!(registry.symbol("KRAKEN", &AssetPair::btc_usd()).unwrap().ticker, "XBTUSD");
+
+        let unconvertible: SymbolsConfig = toml::from_str(
+            r#"
+            [pairs."BTC/EUR"]
+            kraken = { ticker = "XBTEUR", quote = "EUR" }
+            "#,
+        )
+        .unwrap();
+        assert!(SymbolRegistry::with_config(&unconvertible).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_usdt_converted_via_exchange_source() {
+        let config: SymbolsConfig = toml::from_str(
+            r#"
+            [conversions.USDT]
+            exchange = "kraken"
+            ticker = "USDTZUSD"
+            "#,
+        )
+        .unwrap();
+        let registry = SymbolRegistry::with_config(&config).unwrap();
+        let converter = QuoteConverter::new(&registry);
+        assert_eq!(converter.required_sources(), vec!["kraken".to_string()]);
+
+        let converter = Arc::new(converter.with_source("kraken", Arc::new(FixedTicker(0.998))));
+        let pair = AssetPair::btc_usd();
+        let market = Market::new(
+            pair.clone(),
+            registry.symbol("binance", &pair).unwrap().clone(),
+            converter,
+        );
+        assert!((market.to_usd(70_000.0).await.unwrap() - 69_860.0).abs() < 1e-6);
+
+        // 내장 기본값은 USDT를 1 USD로 간주
+        assert_eq!(Market::btc_usd("binance").to_usd(70_000.0).await.unwrap(), 70_000.0);
+    }
+}
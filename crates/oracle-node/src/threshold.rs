@@ -0,0 +1,415 @@
+//! 임계 서명(FROST) DKG와 라운드 참여
+//!
+//! 서명 알고리즘은 감사받은 `frost-secp256k1-tr` (BIP340/Taproot ciphersuite)를
+//! 그대로 쓰고, 여기서는 노드 쪽 절차만 엮습니다.
+//!
+//! - DKG: [`DkgParticipant`]가 1라운드 브로드캐스트, 참가자별 2라운드 조각,
+//!   최종 키 조각([`KeyShare`]) 생성을 차례로 진행합니다. 그룹 비밀키는 누구도
+//!   갖지 않습니다.
+//! - 서명: Aggregator는 정산 시각마다 합의 증명 본문에 대한 서명 라운드를 엽니다.
+//!   노드는 수집 주기마다 가장 최근에 열린 라운드를 조회해 nonce 커밋먼트를
+//!   보내고, 서명자 t명이 확정되면 그 서명 패키지에 서명 조각을 만들어 제출합니다.
+//!   nonce는 [`NonceStore`]에서 라운드마다 한 번만 꺼내 쓰고, 서명자로 뽑히지 않은
+//!   라운드의 nonce는 버립니다.
+
+use crate::grpc_client::oracle::{ThresholdCommitResponse, ThresholdNonceCommitment};
+use crate::grpc_client::GrpcAggregatorClient;
+use anyhow::{anyhow, Context, Result};
+use frost_secp256k1_tr::keys::dkg;
+use frost_secp256k1_tr::keys::{KeyPackage, PublicKeyPackage};
+use frost_secp256k1_tr::round1::{self, NonceCommitment, SigningCommitments, SigningNonces};
+use frost_secp256k1_tr::round2::{self, SignatureShare};
+use frost_secp256k1_tr::{Identifier, SigningPackage};
+use oracle_vm_common::ThresholdError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// DKG 참가자 번호 (1부터)
+pub type ParticipantId = u16;
+
+/// 참여를 마친 라운드를 기억하는 수
+const MAX_FINISHED_ROUNDS: usize = 90;
+
+/// 참가자 번호 → FROST 식별자
+pub fn identifier(id: ParticipantId) -> Result<Identifier, ThresholdError> {
+    Identifier::try_from(id).map_err(|_| ThresholdError::InvalidParameters(format!("invalid participant {}", id)))
+}
+
+fn crypto_error(e: frost_secp256k1_tr::Error) -> ThresholdError {
+    ThresholdError::Crypto(e.to_string())
+}
+
+/// DKG 결과 (노드별 키 조각 파일로 저장)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub id: ParticipantId,
+    pub key_package: KeyPackage,
+    /// 그룹 키와 참가자별 검증 공유키 (모든 노드에서 같음, Aggregator 설정에 사용)
+    pub public_key_package: PublicKeyPackage,
+}
+
+impl KeyShare {
+    /// 서명에 필요한 최소 서명자 수 t
+    pub fn threshold(&self) -> u16 {
+        *self.key_package.min_signers()
+    }
+}
+
+/// 한 노드의 DKG 진행 상태
+pub struct DkgParticipant {
+    id: ParticipantId,
+    participants: u16,
+    round1_secret: Option<dkg::round1::SecretPackage>,
+    round2_secret: Option<dkg::round2::SecretPackage>,
+    round1_packages: BTreeMap<Identifier, dkg::round1::Package>,
+}
+
+impl DkgParticipant {
+    /// 1라운드: 다항식 커밋먼트와 소유 증명을 만들어 모든 참가자에게 브로드캐스트
+    pub fn new(
+        id: ParticipantId,
+        threshold: u16,
+        participants: u16,
+    ) -> Result<(Self, dkg::round1::Package), ThresholdError> {
+        if id == 0 || id > participants {
+            return Err(ThresholdError::InvalidParameters(format!(
+                "participant {} in a {}-of-{} group",
+                id, threshold, participants
+            )));
+        }
+        let (secret, package) =
+            dkg::part1(identifier(id)?, participants, threshold, rand::thread_rng()).map_err(crypto_error)?;
+        let participant = Self {
+            id,
+            participants,
+            round1_secret: Some(secret),
+            round2_secret: None,
+            round1_packages: BTreeMap::new(),
+        };
+        Ok((participant, package))
+    }
+
+    pub fn id(&self) -> ParticipantId {
+        self.id
+    }
+
+    /// 2라운드: 다른 참가자 전원의 1라운드 브로드캐스트를 검증하고, 각자에게 보낼
+    /// 비밀 조각 반환 (비공개 채널로 전송)
+    pub fn round2(
+        &mut self,
+        round1_packages: BTreeMap<ParticipantId, dkg::round1::Package>,
+    ) -> Result<BTreeMap<ParticipantId, dkg::round2::Package>, ThresholdError> {
+        let secret = self
+            .round1_secret
+            .take()
+            .ok_or_else(|| ThresholdError::InvalidParameters("DKG round 2 already run".to_string()))?;
+        self.round1_packages = self.by_identifier(round1_packages)?;
+        let (secret, packages) = dkg::part2(secret, &self.round1_packages).map_err(|e| self.dkg_error(e))?;
+        self.round2_secret = Some(secret);
+        self.by_participant(packages)
+    }
+
+    /// 다른 참가자 전원이 보낸 2라운드 조각으로 키 조각 완성
+    pub fn finish(
+        self,
+        round2_packages: BTreeMap<ParticipantId, dkg::round2::Package>,
+    ) -> Result<KeyShare, ThresholdError> {
+        let secret = self
+            .round2_secret
+            .as_ref()
+            .ok_or_else(|| ThresholdError::InvalidParameters("DKG round 2 not run".to_string()))?;
+        let round2_packages = self.by_identifier(round2_packages)?;
+        let (key_package, public_key_package) =
+            dkg::part3(secret, &self.round1_packages, &round2_packages).map_err(|e| self.dkg_error(e))?;
+        Ok(KeyShare {
+            id: self.id,
+            key_package,
+            public_key_package,
+        })
+    }
+
+    fn by_identifier<T>(&self, packages: BTreeMap<ParticipantId, T>) -> Result<BTreeMap<Identifier, T>, ThresholdError> {
+        if let Some(id) = (1..=self.participants).find(|id| *id != self.id && !packages.contains_key(id)) {
+            return Err(ThresholdError::MissingCommitment(id));
+        }
+        packages
+            .into_iter()
+            .map(|(id, package)| Ok((identifier(id)?, package)))
+            .collect()
+    }
+
+    fn by_participant<T>(&self, packages: BTreeMap<Identifier, T>) -> Result<BTreeMap<ParticipantId, T>, ThresholdError> {
+        packages
+            .into_iter()
+            .map(|(id, package)| Ok((self.participant(id)?, package)))
+            .collect()
+    }
+
+    fn participant(&self, id: Identifier) -> Result<ParticipantId, ThresholdError> {
+        (1..=self.participants)
+            .find(|&participant| identifier(participant) == Ok(id))
+            .ok_or_else(|| ThresholdError::InvalidParameters(format!("unknown participant {:?}", id)))
+    }
+
+    /// 부정 참가자를 번호로 지목
+    fn dkg_error(&self, e: frost_secp256k1_tr::Error) -> ThresholdError {
+        let culprit = e.culprit().and_then(|id| self.participant(id).ok());
+        match (e, culprit) {
+            (frost_secp256k1_tr::Error::InvalidProofOfKnowledge { .. }, Some(id)) => ThresholdError::InvalidProof(id),
+            (frost_secp256k1_tr::Error::InvalidSecretShare { .. }, Some(id)) => ThresholdError::InvalidShare(id),
+            (e, _) => crypto_error(e),
+        }
+    }
+}
+
+/// 서명 라운드별 nonce (라운드마다 한 번만 사용)
+#[derive(Default)]
+pub struct NonceStore {
+    nonces: HashMap<u64, SigningNonces>,
+}
+
+impl NonceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 라운드의 nonce 커밋먼트 (서명 전 재요청이면 같은 커밋먼트)
+    pub fn commit(&mut self, round: u64, key: &KeyPackage) -> SigningCommitments {
+        *self
+            .nonces
+            .entry(round)
+            .or_insert_with(|| round1::commit(key.signing_share(), &mut rand::thread_rng()).0)
+            .commitments()
+    }
+
+    /// 라운드 서명 패키지에 서명하고 nonce 폐기
+    pub fn sign(
+        &mut self,
+        round: u64,
+        key: &KeyPackage,
+        package: &SigningPackage,
+    ) -> Result<SignatureShare, ThresholdError> {
+        let nonces = self.nonces.remove(&round).ok_or(ThresholdError::NonceUnavailable(round))?;
+        round2::sign(package, &nonces, key).map_err(crypto_error)
+    }
+
+    /// 서명자로 뽑히지 않은 라운드의 nonce 폐기
+    pub fn discard(&mut self, round: u64) {
+        self.nonces.remove(&round);
+    }
+}
+
+/// 한 주기의 라운드 진행 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundStep {
+    /// 열린 라운드 없음
+    Idle,
+    /// 커밋먼트를 보내고 서명자 확정 대기
+    Committed(u64),
+    /// 서명 조각 제출 (`complete`: 그룹 서명 완성)
+    Signed { round_id: u64, complete: bool },
+    /// 이미 참여를 마쳤거나 서명자로 뽑히지 않은 라운드
+    Skipped(u64),
+}
+
+/// 이 노드의 DKG 키 조각으로 서명 라운드에 참여
+pub struct ThresholdParticipant {
+    key: KeyShare,
+    nonces: NonceStore,
+    finished: BTreeSet<u64>,
+}
+
+impl ThresholdParticipant {
+    pub fn new(key: KeyShare) -> Self {
+        Self {
+            key,
+            nonces: NonceStore::new(),
+            finished: BTreeSet::new(),
+        }
+    }
+
+    /// DKG 결과 키 조각 파일 (JSON) 로드
+    pub fn load(path: &str) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read threshold share {}", path))?;
+        let key: KeyShare =
+            serde_json::from_str(&content).with_context(|| format!("Failed to parse threshold share {}", path))?;
+        Ok(Self::new(key))
+    }
+
+    /// DKG 참가자 번호
+    pub fn signer(&self) -> ParticipantId {
+        self.key.id
+    }
+
+    /// 가장 최근 열린 라운드를 한 단계 진행 (커밋 또는 서명)
+    pub async fn step(&mut self, client: &mut GrpcAggregatorClient) -> Result<RoundStep> {
+        let round = client.threshold_round(0).await?;
+        let round_id = round.round_id;
+        if round_id == 0 {
+            return Ok(RoundStep::Idle);
+        }
+        if self.finished.contains(&round_id) {
+            return Ok(RoundStep::Skipped(round_id));
+        }
+        // 서명자가 이미 확정됐으면 커밋하지 않음 (뽑혔다면 전에 커밋한 것)
+        let round = if round.ready {
+            round
+        } else {
+            let commitment = self.nonces.commit(round_id, &self.key.key_package);
+            client.threshold_commit(round_id, self.key.id, &commitment).await?
+        };
+        if !round.ready {
+            return Ok(RoundStep::Committed(round_id));
+        }
+
+        let package = signing_package(&round)?;
+        if !package.signing_commitments().contains_key(self.key.key_package.identifier()) {
+            self.nonces.discard(round_id);
+            self.finish(round_id);
+            return Ok(RoundStep::Skipped(round_id));
+        }
+        // 실패해도 같은 라운드 nonce는 다시 쓰지 않음
+        self.finish(round_id);
+        let share = self.nonces.sign(round_id, &self.key.key_package, &package)?;
+        let response = client.threshold_sign(round_id, self.key.id, &share).await?;
+        Ok(RoundStep::Signed {
+            round_id,
+            complete: response.complete,
+        })
+    }
+
+    fn finish(&mut self, round_id: u64) {
+        self.finished.insert(round_id);
+        while self.finished.len() > MAX_FINISHED_ROUNDS {
+            self.finished.pop_first();
+        }
+    }
+}
+
+/// 확정된 라운드 응답의 서명 패키지
+fn signing_package(round: &ThresholdCommitResponse) -> Result<SigningPackage> {
+    let message: [u8; 32] = hex::decode(&round.message)
+        .context("Invalid threshold round message")?
+        .try_into()
+        .map_err(|_| anyhow!("Threshold round {} message is not 32 bytes", round.round_id))?;
+    let commitments = round
+        .commitments
+        .iter()
+        .map(parse_commitment)
+        .collect::<Result<BTreeMap<_, _>>>()?;
+    Ok(SigningPackage::new(commitments, &message))
+}
+
+fn parse_commitment(commitment: &ThresholdNonceCommitment) -> Result<(Identifier, SigningCommitments)> {
+    let signer = u16::try_from(commitment.signer).context("Invalid threshold signer")?;
+    let point = |hex_point: &str| -> Result<NonceCommitment> {
+        NonceCommitment::deserialize(&hex::decode(hex_point)?).map_err(|e| anyhow!("{}", e))
+    };
+    Ok((
+        identifier(signer)?,
+        SigningCommitments::new(
+            point(&commitment.hiding).context("Invalid hiding commitment")?,
+            point(&commitment.binding).context("Invalid binding commitment")?,
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `n`명 DKG 전체 진행
+    fn dkg(threshold: u16, n: u16) -> Vec<KeyShare> {
+        let (mut nodes, broadcasts): (Vec<_>, BTreeMap<_, _>) = (1..=n)
+            .map(|id| {
+                let (node, broadcast) = DkgParticipant::new(id, threshold, n).unwrap();
+                (node, (id, broadcast))
+            })
+            .unzip();
+        let dealt: Vec<_> = nodes
+            .iter_mut()
+            .map(|node| {
+                let mut others = broadcasts.clone();
+                others.remove(&node.id());
+                node.round2(others).unwrap()
+            })
+            .collect();
+        nodes
+            .into_iter()
+            .map(|node| {
+                let received = (1..=n)
+                    .filter(|&dealer| dealer != node.id())
+                    .map(|dealer| (dealer, dealt[dealer as usize - 1][&node.id()].clone()))
+                    .collect();
+                node.finish(received).unwrap()
+            })
+            .collect()
+    }
+
+    fn sign_with(keys: &[&KeyShare], message: [u8; 32]) -> (SigningPackage, BTreeMap<Identifier, SignatureShare>) {
+        let mut stores: Vec<NonceStore> = keys.iter().map(|_| NonceStore::new()).collect();
+        let commitments = keys
+            .iter()
+            .zip(stores.iter_mut())
+            .map(|(key, store)| (*key.key_package.identifier(), store.commit(1, &key.key_package)))
+            .collect();
+        let package = SigningPackage::new(commitments, &message);
+        let shares = keys
+            .iter()
+            .zip(stores.iter_mut())
+            .map(|(key, store)| (*key.key_package.identifier(), store.sign(1, &key.key_package, &package).unwrap()))
+            .collect();
+        (package, shares)
+    }
+
+    #[test]
+    fn test_any_threshold_subset_signs_bip340() {
+        let keys = dkg(2, 3);
+        let group = &keys[0].public_key_package;
+        assert!(keys.iter().all(|key| key.public_key_package == *group && key.threshold() == 2));
+        let message = oracle_vm_common::crypto::sha256(b"price|BTC/USD|6500000|1700035200");
+
+        for subset in [[0, 1], [0, 2], [1, 2]] {
+            let signers: Vec<&KeyShare> = subset.iter().map(|&i| &keys[i]).collect();
+            let (package, shares) = sign_with(&signers, message);
+            let signature = frost_secp256k1_tr::aggregate(&package, &shares, group).unwrap();
+            assert!(group.verifying_key().verify(&message, &signature).is_ok());
+            assert!(group.verifying_key().verify(b"other", &signature).is_err());
+        }
+    }
+
+    #[test]
+    fn test_nonce_reuse_and_bad_dkg_shares_rejected() {
+        let keys = dkg(2, 3);
+        let message = oracle_vm_common::crypto::sha256(b"attestation");
+
+        // nonce는 라운드당 한 번만
+        let mut store = NonceStore::new();
+        let commitment = store.commit(7, &keys[0].key_package);
+        assert_eq!(store.commit(7, &keys[0].key_package), commitment);
+        let other = NonceStore::new().commit(7, &keys[1].key_package);
+        let package = SigningPackage::new(
+            BTreeMap::from([
+                (*keys[0].key_package.identifier(), commitment),
+                (*keys[1].key_package.identifier(), other),
+            ]),
+            &message,
+        );
+        store.sign(7, &keys[0].key_package, &package).unwrap();
+        assert_eq!(
+            store.sign(7, &keys[0].key_package, &package),
+            Err(ThresholdError::NonceUnavailable(7))
+        );
+
+        // 딜러 커밋먼트와 맞지 않는 2라운드 조각은 딜러를 지목해 거부
+        let (mut node, broadcast_1) = DkgParticipant::new(1, 2, 2).unwrap();
+        let (mut dealer, broadcast_2) = DkgParticipant::new(2, 2, 2).unwrap();
+        let (mut stranger, _) = DkgParticipant::new(2, 2, 2).unwrap();
+        node.round2(BTreeMap::from([(2, broadcast_2)])).unwrap();
+        dealer.round2(BTreeMap::from([(1, broadcast_1.clone())])).unwrap();
+        let mut forged = stranger.round2(BTreeMap::from([(1, broadcast_1)])).unwrap();
+        let forged = BTreeMap::from([(2, forged.remove(&1).unwrap())]);
+        assert_eq!(node.finish(forged), Err(ThresholdError::InvalidShare(2)));
+    }
+}
@@ -0,0 +1,86 @@
+//! Cox-Ross-Rubinstein binomial tree
+//!
+//! Prices American options, which may be exercised at any node before
+//! expiry. With early exercise disabled the tree converges to the
+//! Black-Scholes price, so both styles share one set of inputs.
+
+use crate::black_scholes::BlackScholesInputs;
+
+/// Default number of tree steps for quoting
+pub const DEFAULT_BINOMIAL_STEPS: usize = 200;
+
+/// Option price on an `steps`-step tree (intrinsic value once expired)
+pub fn binomial_price(inputs: &BlackScholesInputs, steps: usize, early_exercise: bool) -> f64 {
+    if inputs.time_to_expiry <= 0.0 || inputs.volatility <= 0.0 {
+        return inputs.price();
+    }
+
+    let steps = steps.max(1);
+    let dt = inputs.time_to_expiry / steps as f64;
+    let up = (inputs.volatility * dt.sqrt()).exp();
+    let down = 1.0 / up;
+    let growth = (inputs.risk_free_rate * dt).exp();
+    let p_up = (growth - down) / (up - down);
+    let discount = 1.0 / growth;
+    let intrinsic = |spot: f64| {
+        if inputs.is_call {
+            (spot - inputs.strike).max(0.0)
+        } else {
+            (inputs.strike - spot).max(0.0)
+        }
+    };
+
+    // Node i at step n has i down moves
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|i| intrinsic(inputs.spot * up.powi((steps - i) as i32) * down.powi(i as i32)))
+        .collect();
+    for step in (0..steps).rev() {
+        for i in 0..=step {
+            let held = discount * (p_up * values[i] + (1.0 - p_up) * values[i + 1]);
+            values[i] = if early_exercise {
+                let spot = inputs.spot * up.powi((step - i) as i32) * down.powi(i as i32);
+                held.max(intrinsic(spot))
+            } else {
+                held
+            };
+        }
+    }
+    values[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_matches_black_scholes_and_prices_early_exercise() {
+        let put = BlackScholesInputs {
+            spot: 100.0,
+            strike: 100.0,
+            time_to_expiry: 1.0,
+            volatility: 0.2,
+            risk_free_rate: 0.05,
+            is_call: false,
+        };
+        let call = BlackScholesInputs {
+            is_call: true,
+            ..put
+        };
+
+        // European tree converges to Black-Scholes
+        let european_put = binomial_price(&put, DEFAULT_BINOMIAL_STEPS, false);
+        assert!((european_put - put.price()).abs() < 0.02);
+
+        // Early exercise is worth something for a put, nothing for a call without dividends
+        let american_put = binomial_price(&put, DEFAULT_BINOMIAL_STEPS, true);
+        assert!((american_put - 6.09).abs() < 0.02);
+        assert!(american_put > european_put);
+        let american_call = binomial_price(&call, DEFAULT_BINOMIAL_STEPS, true);
+        assert!((american_call - binomial_price(&call, DEFAULT_BINOMIAL_STEPS, false)).abs() < 1e-9);
+
+        // Deep ITM American put is worth at least its intrinsic value
+        let deep = BlackScholesInputs { spot: 50.0, ..put };
+        assert!(binomial_price(&deep, DEFAULT_BINOMIAL_STEPS, true) >= 50.0);
+        assert_eq!(binomial_price(&BlackScholesInputs { time_to_expiry: 0.0, ..deep }, 0, true), 50.0);
+    }
+}
@@ -0,0 +1,217 @@
+//! Black-Scholes price and Greeks
+//!
+//! Vega is per 1 volatility point, theta per calendar day and rho per
+//! 1 percentage point of the risk-free rate.
+
+use serde::{Deserialize, Serialize};
+
+/// Inputs for one European option
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlackScholesInputs {
+    pub spot: f64,
+    pub strike: f64,
+    /// Years
+    pub time_to_expiry: f64,
+    pub volatility: f64,
+    pub risk_free_rate: f64,
+    pub is_call: bool,
+}
+
+/// Per-unit Greeks
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Greeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    #[serde(default)]
+    pub rho: f64,
+}
+
+impl Greeks {
+    /// Greeks of `quantity` units
+    pub fn scaled(&self, quantity: f64) -> Self {
+        Self {
+            delta: self.delta * quantity,
+            gamma: self.gamma * quantity,
+            vega: self.vega * quantity,
+            theta: self.theta * quantity,
+            rho: self.rho * quantity,
+        }
+    }
+}
+
+fn normal_cdf(x: f64) -> f64 {
+    (1.0 + libm::erf(x / std::f64::consts::SQRT_2)) / 2.0
+}
+
+fn normal_pdf(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+impl BlackScholesInputs {
+    fn d1_d2(&self) -> (f64, f64) {
+        let vol_sqrt_t = self.volatility * self.time_to_expiry.sqrt();
+        let d1 = ((self.spot / self.strike).ln()
+            + (self.risk_free_rate + self.volatility.powi(2) / 2.0) * self.time_to_expiry)
+            / vol_sqrt_t;
+        (d1, d1 - vol_sqrt_t)
+    }
+
+    fn expired(&self) -> bool {
+        self.time_to_expiry <= 0.0 || self.volatility <= 0.0
+    }
+
+    /// Option price (intrinsic value once expired)
+    pub fn price(&self) -> f64 {
+        if self.expired() {
+            return if self.is_call {
+                (self.spot - self.strike).max(0.0)
+            } else {
+                (self.strike - self.spot).max(0.0)
+            };
+        }
+
+        let (d1, d2) = self.d1_d2();
+        let discount = (-self.risk_free_rate * self.time_to_expiry).exp();
+        if self.is_call {
+            self.spot * normal_cdf(d1) - self.strike * discount * normal_cdf(d2)
+        } else {
+            self.strike * discount * normal_cdf(-d2) - self.spot * normal_cdf(-d1)
+        }
+    }
+
+    /// Cash-or-nothing price per unit of payout: e^(-rT) N(±d2)
+    /// (1 or 0 once expired, depending on whether the option finishes in the money)
+    pub fn digital_price(&self) -> f64 {
+        if self.expired() {
+            let in_the_money = if self.is_call {
+                self.spot > self.strike
+            } else {
+                self.spot < self.strike
+            };
+            return if in_the_money { 1.0 } else { 0.0 };
+        }
+
+        let (_, d2) = self.d1_d2();
+        let discount = (-self.risk_free_rate * self.time_to_expiry).exp();
+        discount * normal_cdf(if self.is_call { d2 } else { -d2 })
+    }
+
+    /// All Greeks (only delta is non-zero once expired)
+    pub fn greeks(&self) -> Greeks {
+        if self.expired() {
+            let delta = match (self.is_call, self.spot > self.strike, self.spot < self.strike) {
+                (true, true, _) => 1.0,
+                (false, _, true) => -1.0,
+                _ => 0.0,
+            };
+            return Greeks {
+                delta,
+                ..Greeks::default()
+            };
+        }
+
+        let (d1, d2) = self.d1_d2();
+        let sqrt_t = self.time_to_expiry.sqrt();
+        let pdf_d1 = normal_pdf(d1);
+        let discount = (-self.risk_free_rate * self.time_to_expiry).exp();
+        let decay = -(self.spot * pdf_d1 * self.volatility) / (2.0 * sqrt_t);
+
+        let carry = self.strike * discount;
+
+        let (delta, theta, rho) = if self.is_call {
+            (
+                normal_cdf(d1),
+                decay - self.risk_free_rate * carry * normal_cdf(d2),
+                carry * self.time_to_expiry * normal_cdf(d2),
+            )
+        } else {
+            (
+                normal_cdf(d1) - 1.0,
+                decay + self.risk_free_rate * carry * normal_cdf(-d2),
+                -carry * self.time_to_expiry * normal_cdf(-d2),
+            )
+        };
+
+        Greeks {
+            delta,
+            gamma: pdf_d1 / (self.spot * self.volatility * sqrt_t),
+            vega: self.spot * pdf_d1 * sqrt_t / 100.0,
+            theta: theta / 365.0,
+            rho: rho / 100.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_and_greeks() {
+        let call = BlackScholesInputs {
+            spot: 100.0,
+            strike: 100.0,
+            time_to_expiry: 1.0,
+            volatility: 0.2,
+            risk_free_rate: 0.05,
+            is_call: true,
+        };
+        let put = BlackScholesInputs {
+            is_call: false,
+            ..call
+        };
+
+        // Textbook values: C = 10.4506, P = 5.5735
+        assert!((call.price() - 10.4506).abs() < 1e-3);
+        assert!((put.price() - 5.5735).abs() < 1e-3);
+
+        let (c, p) = (call.greeks(), put.greeks());
+        assert!((c.delta - 0.6368).abs() < 1e-3);
+        assert!((c.delta - p.delta - 1.0).abs() < 1e-9);
+        assert!((c.gamma - p.gamma).abs() < 1e-12);
+        assert!((c.vega - 0.3752).abs() < 1e-3);
+        assert!(c.theta < 0.0);
+        assert!((c.rho - 0.5323).abs() < 1e-3);
+        assert!(p.rho < 0.0);
+
+        let expired = BlackScholesInputs {
+            time_to_expiry: 0.0,
+            spot: 90.0,
+            ..put
+        };
+        assert_eq!(expired.price(), 10.0);
+        assert_eq!(expired.greeks().delta, -1.0);
+        assert_eq!(expired.greeks().scaled(2.0).gamma, 0.0);
+    }
+
+    #[test]
+    fn test_digital_price() {
+        let call = BlackScholesInputs {
+            spot: 100.0,
+            strike: 100.0,
+            time_to_expiry: 1.0,
+            volatility: 0.2,
+            risk_free_rate: 0.05,
+            is_call: true,
+        };
+        let put = BlackScholesInputs {
+            is_call: false,
+            ..call
+        };
+
+        // e^-0.05 * N(0.15) = 0.5323; call + put pays 1 for sure
+        assert!((call.digital_price() - 0.5323).abs() < 1e-3);
+        assert!((call.digital_price() + put.digital_price() - (-0.05f64).exp()).abs() < 1e-12);
+
+        let expired = BlackScholesInputs {
+            time_to_expiry: 0.0,
+            spot: 90.0,
+            ..put
+        };
+        assert_eq!(expired.digital_price(), 1.0);
+        assert_eq!(BlackScholesInputs { is_call: true, ..expired }.digital_price(), 0.0);
+    }
+}
@@ -0,0 +1,158 @@
+//! Implied volatility solvers
+//!
+//! `implied_volatility` inverts the Black-Scholes price with Newton steps and
+//! falls back to bisection when vega is too small. `volatility_for_theta`
+//! finds the volatility whose daily theta matches a target decay, which is
+//! how the pool sets premiums.
+
+use crate::black_scholes::BlackScholesInputs;
+use thiserror::Error;
+
+/// Lowest volatility the solvers will return (1%)
+pub const MIN_VOLATILITY: f64 = 0.01;
+/// Highest volatility the solvers will return (500%)
+pub const MAX_VOLATILITY: f64 = 5.0;
+
+const MAX_ITERATIONS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SolverError {
+    #[error("target {0} is out of the reachable volatility range")]
+    OutOfRange(f64),
+    #[error("solver did not converge to target {0}")]
+    NotConverged(f64),
+}
+
+/// Volatility at which the option price equals `target_price`
+///
+/// `inputs.volatility` is used as the starting guess when it is positive.
+pub fn implied_volatility(
+    inputs: &BlackScholesInputs,
+    target_price: f64,
+) -> Result<f64, SolverError> {
+    let price_at = |volatility: f64| {
+        BlackScholesInputs {
+            volatility,
+            ..*inputs
+        }
+        .price()
+    };
+    let tolerance = 1e-8 * inputs.spot.max(1.0);
+
+    let mut volatility = if inputs.volatility > 0.0 {
+        inputs.volatility.clamp(MIN_VOLATILITY, MAX_VOLATILITY)
+    } else {
+        0.5
+    };
+    for _ in 0..MAX_ITERATIONS {
+        let candidate = BlackScholesInputs {
+            volatility,
+            ..*inputs
+        };
+        let diff = candidate.price() - target_price;
+        if diff.abs() < tolerance {
+            return Ok(volatility);
+        }
+        // Vega is quoted per volatility point
+        let vega = candidate.greeks().vega * 100.0;
+        if vega < 1e-8 {
+            break;
+        }
+        volatility -= diff / vega;
+        if !(MIN_VOLATILITY..=MAX_VOLATILITY).contains(&volatility) {
+            break;
+        }
+    }
+
+    bisect(price_at, target_price, tolerance)
+}
+
+/// Volatility at which the daily theta equals `target_theta` (negative)
+pub fn volatility_for_theta(
+    inputs: &BlackScholesInputs,
+    target_theta: f64,
+) -> Result<f64, SolverError> {
+    let theta_at = |volatility: f64| {
+        BlackScholesInputs {
+            volatility,
+            ..*inputs
+        }
+        .greeks()
+        .theta
+    };
+    bisect(theta_at, target_theta, 1e-4)
+}
+
+/// Bisection over [`MIN_VOLATILITY`, `MAX_VOLATILITY`] for a monotonic `f`
+fn bisect(f: impl Fn(f64) -> f64, target: f64, tolerance: f64) -> Result<f64, SolverError> {
+    let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+    let mut diff_low = f(low) - target;
+    let diff_high = f(high) - target;
+
+    if diff_low.abs() < tolerance {
+        return Ok(low);
+    }
+    if diff_high.abs() < tolerance {
+        return Ok(high);
+    }
+    if diff_low.signum() == diff_high.signum() {
+        return Err(SolverError::OutOfRange(target));
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        let diff_mid = f(mid) - target;
+
+        if diff_mid.abs() < tolerance || (high - low) < 1e-8 {
+            return Ok(mid);
+        }
+
+        if diff_mid.signum() == diff_low.signum() {
+            low = mid;
+            diff_low = diff_mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Err(SolverError::NotConverged(target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(is_call: bool) -> BlackScholesInputs {
+        BlackScholesInputs {
+            spot: 70_000.0,
+            strike: 75_000.0,
+            time_to_expiry: 30.0 / 365.0,
+            volatility: 0.0,
+            risk_free_rate: 0.05,
+            is_call,
+        }
+    }
+
+    #[test]
+    fn test_solvers_recover_volatility() {
+        for is_call in [true, false] {
+            let quoted = BlackScholesInputs {
+                volatility: 0.65,
+                ..inputs(is_call)
+            };
+
+            let from_price = implied_volatility(&inputs(is_call), quoted.price()).unwrap();
+            assert!((from_price - 0.65).abs() < 1e-6);
+
+            let from_theta = volatility_for_theta(&inputs(is_call), quoted.greeks().theta).unwrap();
+            assert!((from_theta - 0.65).abs() < 1e-3);
+        }
+
+        // Below intrinsic value or positive theta is unreachable
+        assert_eq!(
+            implied_volatility(&inputs(false), 1_000.0),
+            Err(SolverError::OutOfRange(1_000.0))
+        );
+        assert!(volatility_for_theta(&inputs(true), 10.0).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Canonical option pricing shared by the calculation service and contracts
+//!
+//! Every premium, Greek and implied volatility in the workspace should come
+//! from this crate so that the premium service, pool risk and theta targeting
+//! agree on the same model and units.
+
+pub mod binomial;
+pub mod black_scholes;
+pub mod implied_vol;
+pub mod monte_carlo;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use binomial::{binomial_price, DEFAULT_BINOMIAL_STEPS};
+pub use black_scholes::{BlackScholesInputs, Greeks};
+pub use implied_vol::{implied_volatility, volatility_for_theta, SolverError};
+pub use monte_carlo::{barrier_price, BarrierSpec, MonteCarloConfig};
@@ -0,0 +1,128 @@
+//! JavaScript bindings for in-browser indicative quotes
+//!
+//! Built with `wasm-pack build --target web -- --features wasm`. The frontend
+//! calls the same formulas as the premium service to refresh quotes between
+//! server updates; server quotes stay authoritative.
+//!
+//! ```text
+//! import init, { premium, greeks } from "pricing_core";
+//! await init();
+//! premium(100000, 105000, 30 / 365, 0.6, 0.05, true);
+//! greeks(100000, 105000, 30 / 365, 0.6, 0.05, true).delta;
+//! ```
+//!
+//! Time to expiry is in years; premiums are per unit of underlying in the
+//! spot's currency.
+
+use crate::black_scholes::{BlackScholesInputs, Greeks};
+use crate::{binomial_price, implied_vol, DEFAULT_BINOMIAL_STEPS};
+use wasm_bindgen::prelude::*;
+
+fn inputs(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+) -> BlackScholesInputs {
+    BlackScholesInputs {
+        spot,
+        strike,
+        time_to_expiry,
+        volatility,
+        risk_free_rate,
+        is_call,
+    }
+}
+
+/// European option premium
+#[wasm_bindgen]
+pub fn premium(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+) -> f64 {
+    inputs(spot, strike, time_to_expiry, volatility, risk_free_rate, is_call).price()
+}
+
+/// American option premium on the default binomial tree
+#[wasm_bindgen(js_name = americanPremium)]
+pub fn american_premium(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+) -> f64 {
+    binomial_price(
+        &inputs(spot, strike, time_to_expiry, volatility, risk_free_rate, is_call),
+        DEFAULT_BINOMIAL_STEPS,
+        true,
+    )
+}
+
+/// Cash-or-nothing digital premium per 1 unit of payout
+#[wasm_bindgen(js_name = digitalPremium)]
+pub fn digital_premium(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+) -> f64 {
+    inputs(spot, strike, time_to_expiry, volatility, risk_free_rate, is_call).digital_price()
+}
+
+/// Per-unit Greeks (vega/rho per 1%p, theta per day)
+#[wasm_bindgen]
+pub fn greeks(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    volatility: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+) -> Greeks {
+    inputs(spot, strike, time_to_expiry, volatility, risk_free_rate, is_call).greeks()
+}
+
+/// Volatility that reproduces `price`; throws outside the no-arbitrage range
+#[wasm_bindgen(js_name = impliedVolatility)]
+pub fn implied_volatility(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+    price: f64,
+) -> Result<f64, JsError> {
+    implied_vol::implied_volatility(
+        &inputs(spot, strike, time_to_expiry, 0.0, risk_free_rate, is_call),
+        price,
+    )
+    .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_match_native_pricing() {
+        let native = inputs(100_000.0, 105_000.0, 30.0 / 365.0, 0.6, 0.05, true);
+        assert_eq!(premium(100_000.0, 105_000.0, 30.0 / 365.0, 0.6, 0.05, true), native.price());
+        assert_eq!(greeks(100_000.0, 105_000.0, 30.0 / 365.0, 0.6, 0.05, true), native.greeks());
+
+        let price = native.price();
+        let iv = implied_volatility(100_000.0, 105_000.0, 30.0 / 365.0, 0.05, true, price).unwrap();
+        assert!((iv - 0.6).abs() < 1e-6);
+        assert!(american_premium(100_000.0, 105_000.0, 30.0 / 365.0, 0.6, 0.05, false)
+            >= premium(100_000.0, 105_000.0, 30.0 / 365.0, 0.6, 0.05, false));
+    }
+}
@@ -0,0 +1,375 @@
+//! Python bindings for the production pricing engine
+//!
+//! Exposes `BlackScholesPricing`, the implied volatility solvers and
+//! `ThetaTargetingEngine` exactly as the calculation service runs them, so
+//! research notebooks reproduce live premiums and Greeks bit for bit.
+//!
+//! Build a wheel with `maturin build --release` from this directory, or
+//! `maturin develop` to install it into the active virtualenv:
+//!
+//! ```text
+//! >>> import oracle_vm_pricing as ovp
+//! >>> ovp.BlackScholesPricing().price(100_000, 105_000, 0.25, 0.6, 0.05, True)
+//! >>> ovp.ThetaTargetingEngine().calculate_premium_with_target_theta(
+//! ...     100_000, 100_000, 100_000, 105_000, 30, 0.05, True, -50.0, 1.0)
+//! ```
+//!
+//! Units follow `pricing-core`: time to expiry in years (days for the theta
+//! engine's premium call), vega and rho per 1 percentage point, theta per
+//! calendar day.
+
+use btcfi_calculation::{
+    BlackScholesPricing, OptionParameters, PoolCapacity, PremiumResult as EnginePremium,
+    PricingEngine, ThetaTargetingEngine,
+};
+use oracle_vm_common::GreeksLimits;
+use pricing_core::BlackScholesInputs;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+fn value_error(e: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// Per-unit Greeks (vega/rho per 1%p, theta per day)
+#[pyclass(name = "Greeks", get_all, frozen)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PyGreeks {
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+impl From<pricing_core::Greeks> for PyGreeks {
+    fn from(greeks: pricing_core::Greeks) -> Self {
+        Self {
+            delta: greeks.delta,
+            gamma: greeks.gamma,
+            vega: greeks.vega,
+            theta: greeks.theta,
+            rho: greeks.rho,
+        }
+    }
+}
+
+#[pymethods]
+impl PyGreeks {
+    /// Greeks of `quantity` units
+    fn scaled(&self, quantity: f64) -> Self {
+        Self {
+            delta: self.delta * quantity,
+            gamma: self.gamma * quantity,
+            vega: self.vega * quantity,
+            theta: self.theta * quantity,
+            rho: self.rho * quantity,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Greeks(delta={}, gamma={}, vega={}, theta={}, rho={})",
+            self.delta, self.gamma, self.vega, self.theta, self.rho
+        )
+    }
+}
+
+/// Black-Scholes price and Greeks, as used by the premium service
+#[pyclass(name = "BlackScholesPricing", frozen)]
+pub struct PyBlackScholesPricing(BlackScholesPricing);
+
+#[pymethods]
+impl PyBlackScholesPricing {
+    #[new]
+    fn new() -> Self {
+        Self(BlackScholesPricing::new())
+    }
+
+    /// Option price per unit of underlying (time to expiry in years)
+    fn price(
+        &self,
+        spot: f64,
+        strike: f64,
+        time_to_expiry: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        is_call: bool,
+    ) -> f64 {
+        self.0.calculate_option_price(&OptionParameters {
+            spot,
+            strike,
+            time_to_expiry,
+            volatility,
+            risk_free_rate,
+            is_call,
+        })
+    }
+
+    fn greeks(
+        &self,
+        spot: f64,
+        strike: f64,
+        time_to_expiry: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        is_call: bool,
+    ) -> PyGreeks {
+        self.0
+            .greeks(&OptionParameters {
+                spot,
+                strike,
+                time_to_expiry,
+                volatility,
+                risk_free_rate,
+                is_call,
+            })
+            .into()
+    }
+}
+
+/// Volatility that reproduces `price`; raises `ValueError` outside the no-arbitrage range
+#[pyfunction]
+fn implied_volatility(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+    price: f64,
+) -> PyResult<f64> {
+    let inputs = BlackScholesInputs {
+        spot,
+        strike,
+        time_to_expiry,
+        volatility: 0.0,
+        risk_free_rate,
+        is_call,
+    };
+    pricing_core::implied_volatility(&inputs, price).map_err(value_error)
+}
+
+/// Volatility whose daily theta equals `target_theta` (negative)
+#[pyfunction]
+fn volatility_for_theta(
+    spot: f64,
+    strike: f64,
+    time_to_expiry: f64,
+    risk_free_rate: f64,
+    is_call: bool,
+    target_theta: f64,
+) -> PyResult<f64> {
+    let inputs = BlackScholesInputs {
+        spot,
+        strike,
+        time_to_expiry,
+        volatility: 0.0,
+        risk_free_rate,
+        is_call,
+    };
+    pricing_core::volatility_for_theta(&inputs, target_theta).map_err(value_error)
+}
+
+/// Premium quoted by the theta targeting engine
+#[pyclass(name = "PremiumResult", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyPremiumResult {
+    pub spot_price: f64,
+    pub strike_price: f64,
+    pub implied_volatility: f64,
+    pub premium_usd: f64,
+    pub premium_btc: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    /// Annual theta
+    pub theta: f64,
+    pub daily_theta: f64,
+    pub rho: f64,
+    pub slippage_multiplier: f64,
+}
+
+impl From<EnginePremium> for PyPremiumResult {
+    fn from(result: EnginePremium) -> Self {
+        Self {
+            spot_price: result.spot_price,
+            strike_price: result.strike_price,
+            implied_volatility: result.implied_volatility,
+            premium_usd: result.premium_usd,
+            premium_btc: result.premium_btc,
+            delta: result.delta,
+            gamma: result.gamma,
+            vega: result.vega,
+            theta: result.theta,
+            daily_theta: result.daily_theta,
+            rho: result.rho,
+            slippage_multiplier: result.slippage_multiplier,
+        }
+    }
+}
+
+impl From<&PyPremiumResult> for EnginePremium {
+    fn from(result: &PyPremiumResult) -> Self {
+        Self {
+            spot_price: result.spot_price,
+            strike_price: result.strike_price,
+            implied_volatility: result.implied_volatility,
+            premium_usd: result.premium_usd,
+            premium_btc: result.premium_btc,
+            delta: result.delta,
+            gamma: result.gamma,
+            vega: result.vega,
+            theta: result.theta,
+            daily_theta: result.daily_theta,
+            rho: result.rho,
+            slippage_multiplier: result.slippage_multiplier,
+        }
+    }
+}
+
+#[pymethods]
+impl PyPremiumResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "PremiumResult(spot_price={}, strike_price={}, implied_volatility={}, premium_usd={}, premium_btc={}, daily_theta={}, slippage_multiplier={})",
+            self.spot_price,
+            self.strike_price,
+            self.implied_volatility,
+            self.premium_usd,
+            self.premium_btc,
+            self.daily_theta,
+            self.slippage_multiplier
+        )
+    }
+}
+
+/// Target theta premium engine with the service's default curve and calendar
+#[pyclass(name = "ThetaTargetingEngine", frozen)]
+pub struct PyThetaTargetingEngine(ThetaTargetingEngine);
+
+#[pymethods]
+impl PyThetaTargetingEngine {
+    /// `max_delta` (BTC) and `max_vega` (USD per vol point) cap a single trade
+    #[new]
+    #[pyo3(signature = (max_delta=None, max_vega=None))]
+    fn new(max_delta: Option<f64>, max_vega: Option<f64>) -> Self {
+        Self(ThetaTargetingEngine::new().with_greeks_limits(GreeksLimits {
+            max_delta,
+            max_vega,
+        }))
+    }
+
+    /// Days until a listed expiry (`YYYY-MM-DD`); `otc` accepts any future date
+    #[pyo3(signature = (expiry, now, otc=false))]
+    fn days_to_expiry(&self, expiry: &str, now: u64, otc: bool) -> PyResult<f64> {
+        self.0.days_to_expiry(expiry, now, otc).map_err(value_error)
+    }
+
+    fn find_iv_for_target_theta(
+        &self,
+        spot: f64,
+        strike: f64,
+        time_to_expiry: f64,
+        risk_free_rate: f64,
+        is_call: bool,
+        target_theta: f64,
+    ) -> PyResult<f64> {
+        self.0
+            .find_iv_for_target_theta(spot, strike, time_to_expiry, risk_free_rate, is_call, target_theta)
+            .map_err(value_error)
+    }
+
+    /// Premium from the three exchange prices (time to expiry in days)
+    #[allow(clippy::too_many_arguments)]
+    fn calculate_premium_with_target_theta(
+        &self,
+        binance_price: f64,
+        coinbase_price: f64,
+        kraken_price: f64,
+        strike: f64,
+        time_to_expiry_days: f64,
+        risk_free_rate: f64,
+        is_call: bool,
+        target_theta: f64,
+        notional_btc: f64,
+    ) -> PyResult<PyPremiumResult> {
+        self.0
+            .calculate_premium_with_target_theta(
+                binance_price,
+                coinbase_price,
+                kraken_price,
+                strike,
+                time_to_expiry_days,
+                risk_free_rate,
+                is_call,
+                target_theta,
+                notional_btc,
+            )
+            .map(Into::into)
+            .map_err(value_error)
+    }
+
+    /// Premium after the pool utilization/delta markup
+    fn apply_pool_slippage(
+        &self,
+        result: &PyPremiumResult,
+        total_liquidity: f64,
+        locked_collateral: f64,
+        net_delta: f64,
+        notional_btc: f64,
+        is_call: bool,
+    ) -> PyResult<PyPremiumResult> {
+        let pool = PoolCapacity {
+            total_liquidity,
+            locked_collateral,
+            net_delta,
+        };
+        self.0
+            .apply_pool_slippage(&result.into(), &pool, notional_btc, is_call)
+            .map(Into::into)
+            .map_err(value_error)
+    }
+}
+
+#[pymodule]
+fn oracle_vm_pricing(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyGreeks>()?;
+    m.add_class::<PyBlackScholesPricing>()?;
+    m.add_class::<PyPremiumResult>()?;
+    m.add_class::<PyThetaTargetingEngine>()?;
+    m.add_function(wrap_pyfunction!(implied_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(volatility_for_theta, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_match_engine() {
+        let pricing = PyBlackScholesPricing::new();
+        let price = pricing.price(100_000.0, 105_000.0, 0.25, 0.6, 0.05, true);
+        let iv = implied_volatility(100_000.0, 105_000.0, 0.25, 0.05, true, price).unwrap();
+        assert!((iv - 0.6).abs() < 1e-6);
+
+        let engine = PyThetaTargetingEngine::new(None, None);
+        let result = engine
+            .calculate_premium_with_target_theta(
+                100_000.0, 100_100.0, 99_900.0, 105_000.0, 30.0, 0.05, true, -50.0, 1.0,
+            )
+            .unwrap();
+        let expected = ThetaTargetingEngine::new()
+            .calculate_premium_with_target_theta(
+                100_000.0, 100_100.0, 99_900.0, 105_000.0, 30.0, 0.05, true, -50.0, 1.0,
+            )
+            .unwrap();
+        assert_eq!(result, PyPremiumResult::from(expected));
+
+        let marked_up = engine
+            .apply_pool_slippage(&result, 100.0, 80.0, 0.0, 1.0, true)
+            .unwrap();
+        assert!(marked_up.slippage_multiplier > 1.0);
+        assert!(marked_up.premium_usd > result.premium_usd);
+    }
+}
@@ -0,0 +1,31 @@
+//! Oracle VM gRPC API
+//!
+//! The `.proto` definitions under `proto/` and the tonic clients/servers
+//! generated from them. Internal services depend on this crate instead of
+//! compiling the protos themselves, and servers register
+//! [`FILE_DESCRIPTOR_SET`] with gRPC server reflection so tools such as
+//! `grpcurl` can discover the API without a copy of the files.
+
+/// `oracle` package: `OracleService` client/server and messages
+pub mod oracle {
+    tonic::include_proto!("oracle");
+}
+
+/// Encoded `FileDescriptorSet` of every proto in this crate (for reflection)
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("oracle_descriptor");
+
+/// Proto sources, for integrators generating clients in other languages
+pub const PROTO_FILES: &[(&str, &str)] = &[("oracle.proto", include_str!("../proto/oracle.proto"))];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_set_describes_oracle_service() {
+        let contains = |needle: &[u8]| FILE_DESCRIPTOR_SET.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"oracle.proto"));
+        assert!(contains(b"OracleService"));
+        assert!(PROTO_FILES[0].1.contains("service OracleService"));
+    }
+}
@@ -0,0 +1,101 @@
+//! 옵션 정산 프로그램
+//!
+//! RISC-V(BitVMX-CPU)에서는 링커 스크립트의 입력 영역에서 16바이트를 읽고
+//! write/exit ecall로 결과를 냅니다. 호스트에서는 같은 입력을 hex 인자로 받는
+//! 실행기로 빌드되어 에뮬레이터 없이 출력을 대조할 수 있습니다.
+
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+#[cfg(target_arch = "riscv32")]
+mod riscv {
+    use core::fmt::{self, Write};
+    use core::panic::PanicInfo;
+    use settlement_programs::{write_report, SettlementInput, SETTLEMENT_INPUT_LEN};
+
+    extern "C" {
+        /// 입력 영역 시작 (programs/link.ld)
+        static __bitvmx_input: u8;
+    }
+
+    core::arch::global_asm!(
+        ".section .text._start",
+        ".global _start",
+        "_start:",
+        "la sp, __stack_top",
+        "call settle",
+        "j .",
+    );
+
+    struct Stdout;
+
+    impl Write for Stdout {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            // write(1, s, len)
+            unsafe {
+                core::arch::asm!(
+                    "ecall",
+                    in("a7") 64,
+                    inlateout("a0") 1 => _,
+                    in("a1") s.as_ptr(),
+                    in("a2") s.len(),
+                );
+            }
+            Ok(())
+        }
+    }
+
+    fn exit(code: i32) -> ! {
+        unsafe {
+            core::arch::asm!("ecall", in("a7") 93, in("a0") code, options(noreturn));
+        }
+    }
+
+    #[no_mangle]
+    extern "C" fn settle() -> ! {
+        let input = unsafe {
+            core::slice::from_raw_parts(core::ptr::addr_of!(__bitvmx_input), SETTLEMENT_INPUT_LEN)
+        };
+        match SettlementInput::decode(input) {
+            Ok(input) => {
+                let _ = write_report(&mut Stdout, input.settlement_cents());
+                exit(0)
+            }
+            Err(e) => exit(e.exit_code()),
+        }
+    }
+
+    #[panic_handler]
+    fn panic(_: &PanicInfo) -> ! {
+        exit(101)
+    }
+}
+
+#[cfg(not(target_arch = "riscv32"))]
+fn main() {
+    use settlement_programs::{write_report, SettlementInput};
+
+    let Some(hex) = std::env::args().nth(1) else {
+        eprintln!("usage: option_settlement <input hex>");
+        std::process::exit(1);
+    };
+    let bytes: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect();
+    let Some(bytes) = bytes else {
+        eprintln!("invalid input hex: {}", hex);
+        std::process::exit(1);
+    };
+
+    match SettlementInput::decode(&bytes) {
+        Ok(input) => {
+            let mut out = String::new();
+            write_report(&mut out, input.settlement_cents()).expect("write to string");
+            print!("{}", out);
+        }
+        Err(e) => {
+            eprintln!("invalid settlement input: {:?}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! BitVMX 정산 프로그램 공통 로직
+//!
+//! RISC-V 정산 프로그램(`src/bin`)과 호스트 테스트가 같은 코드를 씁니다.
+//! 입력은 `BitVmxBridge::prepare_settlement_input`이 만드는 16바이트
+//! (option_type, strike, spot, quantity; 각각 u32 LE)이고, 출력 형식은
+//! contracts의 `parse_settlement_output`이 읽는 "Settlement amount: N cents"입니다.
+//! ELF 빌드와 해시 고정은 `cargo xtask build-programs`가 맡습니다.
+
+#![no_std]
+
+use core::fmt::{self, Write};
+
+/// 정산 프로그램 버전 (ELF 해시와 함께 `programs.lock.json`에 고정)
+pub const PROGRAM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 빌드하는 정산 프로그램 (bin 이름 = ELF 파일 이름)
+pub const PROGRAMS: &[&str] = &["option_settlement"];
+
+/// RISC-V 빌드 타깃
+pub const RISCV_TARGET: &str = "riscv32im-unknown-none-elf";
+
+/// 정산 입력 길이 (바이트)
+pub const SETTLEMENT_INPUT_LEN: usize = 16;
+
+/// 정산 입력 오류 (프로그램 종료 코드로도 사용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementError {
+    InvalidLength(usize),
+    InvalidOptionType(u32),
+}
+
+impl SettlementError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SettlementError::InvalidLength(_) => 1,
+            SettlementError::InvalidOptionType(_) => 2,
+        }
+    }
+}
+
+/// 옵션 정산 입력 (가격은 USD 센트)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SettlementInput {
+    /// 0 = Call, 1 = Put
+    pub option_type: u32,
+    pub strike_cents: u32,
+    pub spot_cents: u32,
+    /// 소수점 2자리 고정소수 (100 = 1.00)
+    pub quantity: u32,
+}
+
+impl SettlementInput {
+    pub fn decode(input: &[u8]) -> Result<Self, SettlementError> {
+        if input.len() != SETTLEMENT_INPUT_LEN {
+            return Err(SettlementError::InvalidLength(input.len()));
+        }
+        let word = |index: usize| {
+            u32::from_le_bytes([
+                input[index * 4],
+                input[index * 4 + 1],
+                input[index * 4 + 2],
+                input[index * 4 + 3],
+            ])
+        };
+        let decoded = Self {
+            option_type: word(0),
+            strike_cents: word(1),
+            spot_cents: word(2),
+            quantity: word(3),
+        };
+        if decoded.option_type > 1 {
+            return Err(SettlementError::InvalidOptionType(decoded.option_type));
+        }
+        Ok(decoded)
+    }
+
+    /// 정산 금액 (센트)
+    pub fn settlement_cents(&self) -> u64 {
+        let (strike, spot) = (self.strike_cents as u64, self.spot_cents as u64);
+        let intrinsic = match self.option_type {
+            0 => spot.saturating_sub(strike),
+            _ => strike.saturating_sub(spot),
+        };
+        intrinsic * self.quantity as u64 / 100
+    }
+}
+
+/// 정산 결과 출력 (OTM이면 아무것도 쓰지 않음)
+pub fn write_report(out: &mut impl Write, settlement_cents: u64) -> fmt::Result {
+    if settlement_cents > 0 {
+        writeln!(out, "Settlement amount: {} cents", settlement_cents)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::String;
+
+    fn input(option_type: u32, strike: u32, spot: u32, quantity: u32) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        for (index, word) in [option_type, strike, spot, quantity].iter().enumerate() {
+            bytes[index * 4..index * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_settlement_matches_guide_examples() {
+        // docs/BITVMX_PROOF_GENERATION_GUIDE.md 테스트 케이스
+        let call = SettlementInput::decode(&input(0, 5_000_000, 5_200_000, 100)).unwrap();
+        assert_eq!(call.settlement_cents(), 200_000);
+        let put = SettlementInput::decode(&input(1, 5_000_000, 4_800_000, 200)).unwrap();
+        assert_eq!(put.settlement_cents(), 400_000);
+        let otm = SettlementInput::decode(&input(1, 5_000_000, 5_200_000, 100)).unwrap();
+        assert_eq!(otm.settlement_cents(), 0);
+
+        let mut out = String::new();
+        write_report(&mut out, call.settlement_cents()).unwrap();
+        assert_eq!(out, "Settlement amount: 200000 cents\n");
+
+        assert_eq!(
+            SettlementInput::decode(&input(7, 1, 1, 1)),
+            Err(SettlementError::InvalidOptionType(7))
+        );
+        assert_eq!(SettlementInput::decode(&[0; 3]).unwrap_err().exit_code(), 1);
+    }
+}
@@ -14,7 +14,7 @@ try:
 except ImportError:
     print("❌ Error: oracle_pb2 modules not found")
     print("💡 Hint: You need to generate Python gRPC stubs from oracle.proto")
-    print("   protoc --python_out=. --grpc_python_out=. proto/oracle.proto")
+    print("   protoc -Icrates/proto/proto --python_out=. --grpc_python_out=. crates/proto/proto/oracle.proto")
     sys.exit(1)
 
 def test_aggregator():
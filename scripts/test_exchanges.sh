@@ -11,11 +11,11 @@ EXCHANGES=("binance" "coinbase" "kraken")
 for exchange in "${EXCHANGES[@]}"; do
     echo ""
     echo "🔍 Testing $exchange client..."
-    echo "Command: cargo run -p oracle-node -- --exchange $exchange --interval 5"
+    echo "Command: cargo run -p oracle-node -- --dev --exchange $exchange --interval 5"
     echo "Press Enter to continue or Ctrl+C to skip..."
     read
     
-    timeout 15s cargo run -p oracle-node -- --exchange $exchange --interval 5 || {
+    timeout 15s cargo run -p oracle-node -- --dev --exchange $exchange --interval 5 || {
         echo "⚠️  Test for $exchange finished (timeout or manual stop)"
     }
 done
@@ -0,0 +1,123 @@
+//! 워크스페이스 작업 (`cargo xtask <명령>`)
+//!
+//! - `build-programs`: `programs/`의 정산 프로그램을 RISC-V ELF로 빌드해
+//!   `bitvmx_protocol/execution_files/`에 복사하고, 버전과 SHA-256을
+//!   `programs/programs.lock.json`에 고정합니다.
+//! - `verify-programs`: 복사된 ELF가 lock 파일의 해시와 같은지 확인합니다.
+//!
+//! contracts의 `EmulatorProcessBackend`는 실행 전에 같은 lock 파일로 ELF를 검사합니다.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use settlement_programs::{PROGRAMS, PROGRAM_VERSION, RISCV_TARGET};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// lock 파일 항목 (contracts `bitvmx_backend::ProgramPin`과 같은 형식)
+#[derive(Debug, Serialize, Deserialize)]
+struct ProgramPin {
+    name: String,
+    version: String,
+    sha256: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProgramLock {
+    programs: Vec<ProgramPin>,
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives in the workspace root")
+        .to_path_buf()
+}
+
+fn elf_path(root: &Path, name: &str) -> PathBuf {
+    root.join("bitvmx_protocol/execution_files").join(format!("{}.elf", name))
+}
+
+fn lock_path(root: &Path) -> PathBuf {
+    root.join("programs/programs.lock.json")
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+fn build_programs(root: &Path) -> Result<()> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let link_script = root.join("programs/link.ld");
+    let target_dir = root.join("target/programs");
+    let status = Command::new(cargo)
+        .current_dir(root)
+        .args(["build", "--release", "--package", "settlement-programs", "--bins"])
+        .args(["--target", RISCV_TARGET])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env(
+            "CARGO_TARGET_RISCV32IM_UNKNOWN_NONE_ELF_RUSTFLAGS",
+            format!("-C link-arg=-T{}", link_script.display()),
+        )
+        .status()
+        .context("Failed to run cargo")?;
+    if !status.success() {
+        bail!("RISC-V build failed (rustup target add {})", RISCV_TARGET);
+    }
+
+    let mut lock = ProgramLock::default();
+    for name in PROGRAMS {
+        let built = target_dir.join(RISCV_TARGET).join("release").join(name);
+        let dest = elf_path(root, name);
+        fs::create_dir_all(dest.parent().unwrap())?;
+        fs::copy(&built, &dest)
+            .with_context(|| format!("Failed to copy {} to {}", built.display(), dest.display()))?;
+        let sha256 = sha256_file(&dest)?;
+        println!("{} v{} -> {} ({})", name, PROGRAM_VERSION, dest.display(), sha256);
+        lock.programs.push(ProgramPin {
+            name: name.to_string(),
+            version: PROGRAM_VERSION.to_string(),
+            sha256,
+        });
+    }
+
+    fs::write(lock_path(root), serde_json::to_string_pretty(&lock)? + "\n")?;
+    println!("Pinned {} programs in {}", lock.programs.len(), lock_path(root).display());
+    Ok(())
+}
+
+fn verify_programs(root: &Path) -> Result<()> {
+    let path = lock_path(root);
+    let lock: ProgramLock = serde_json::from_str(
+        &fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?,
+    )?;
+    let mut mismatches = 0;
+    for pin in &lock.programs {
+        let actual = sha256_file(&elf_path(root, &pin.name))?;
+        if actual == pin.sha256 {
+            println!("{} v{}: ok", pin.name, pin.version);
+        } else {
+            println!("{} v{}: expected {}, found {}", pin.name, pin.version, pin.sha256, actual);
+            mismatches += 1;
+        }
+    }
+    if mismatches > 0 {
+        bail!("{} settlement programs do not match {}", mismatches, path.display());
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let root = workspace_root();
+    match std::env::args().nth(1).as_deref() {
+        Some("build-programs") => build_programs(&root),
+        Some("verify-programs") => verify_programs(&root),
+        _ => {
+            eprintln!("usage: cargo xtask <build-programs|verify-programs>");
+            std::process::exit(2);
+        }
+    }
+}